@@ -0,0 +1,81 @@
+// =============================================================================
+//  Astra AGI
+//  File: benches/knowledge_batch.rs
+//
+//  Description: Benchmarks for batched knowledge base queries against running
+//               the same small queries one after another, as the cognitive
+//               loop and API server do each tick.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use astra_agi::knowledge::{
+    query::{AttributeFilter, ComparisonOp, QueryExpr},
+    storage::SledStorage,
+    AttributeType, AttributeValue, Id, Ontology,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::collections::HashMap;
+
+const SEED_ENTITIES: i64 = 5_000;
+
+fn seeded_ontology(path: &str) -> (Ontology<SledStorage>, Id) {
+    let storage = SledStorage::new(path).expect("open bench storage");
+    let mut ontology = Ontology::new(storage);
+
+    let mut attrs = HashMap::new();
+    attrs.insert("score".to_string(), AttributeType::Integer);
+    attrs.insert("group".to_string(), AttributeType::Integer);
+    let concept_id = ontology.add_concept("Item", &[], attrs);
+
+    for score in 0..SEED_ENTITIES {
+        let mut values = HashMap::new();
+        values.insert("score".to_string(), AttributeValue::Integer(score));
+        values.insert("group".to_string(), AttributeValue::Integer(score % 10));
+        ontology.add_entity(concept_id, values).expect("valid seed entity");
+    }
+
+    (ontology, concept_id)
+}
+
+fn filter(attr_name: &str, op: ComparisonOp, value: i64) -> QueryExpr {
+    QueryExpr::AttrFilter(AttributeFilter { attr_name: attr_name.to_string(), op, value: AttributeValue::Integer(value) })
+}
+
+/// Small queries of the kind asked many times a tick, overlapping in the
+/// concept and thresholds they look up.
+fn tick_queries(count: usize, concept_id: Id) -> Vec<QueryExpr> {
+    (0..count)
+        .map(|i| {
+            QueryExpr::and(vec![
+                QueryExpr::Concept(concept_id),
+                filter("group", ComparisonOp::Eq, (i % 10) as i64),
+                filter("score", ComparisonOp::Gt, SEED_ENTITIES - 500 * (1 + (i % 4) as i64)),
+            ])
+        })
+        .collect()
+}
+
+fn bench_batched_queries(c: &mut Criterion) {
+    let (ontology, concept_id) = seeded_ontology("bench_batch_db");
+    let mut group = c.benchmark_group("knowledge_query_batch");
+
+    for count in [8usize, 32, 128] {
+        let queries = tick_queries(count, concept_id);
+        group.bench_with_input(BenchmarkId::new("sequential", count), &queries, |b, queries| {
+            b.iter(|| queries.iter().map(|q| ontology.query(q).len()).sum::<usize>());
+        });
+        group.bench_with_input(BenchmarkId::new("batched", count), &queries, |b, queries| {
+            b.iter(|| ontology.query_batch(queries).iter().map(Vec::len).sum::<usize>());
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_batched_queries);
+criterion_main!(benches);
@@ -0,0 +1,104 @@
+// =============================================================================
+//  Astra AGI
+//  File: benches/knowledge_concurrency.rs
+//
+//  Description: Benchmarks for concurrent readers of the shared knowledge base,
+//               with and without a writer inserting entities at the same time.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use astra_agi::knowledge::{
+    concurrent::KnowledgeHandle,
+    query::{AttributeFilter, ComparisonOp, QueryExpr},
+    storage::SledStorage,
+    AttributeType, AttributeValue, Id, Ontology,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::collections::HashMap;
+use tokio::runtime::Runtime;
+
+const SEED_ENTITIES: i64 = 5_000;
+const QUERIES_PER_READER: usize = 20;
+
+fn seeded_handle(path: &str) -> (KnowledgeHandle<SledStorage>, Id) {
+    let storage = SledStorage::new(path).expect("open bench storage");
+    let mut ontology = Ontology::new(storage);
+
+    let mut attrs = HashMap::new();
+    attrs.insert("score".to_string(), AttributeType::Integer);
+    let concept_id = ontology.add_concept("Item", &[], attrs);
+
+    for score in 0..SEED_ENTITIES {
+        let mut values = HashMap::new();
+        values.insert("score".to_string(), AttributeValue::Integer(score));
+        ontology.add_entity(concept_id, values).expect("valid seed entity");
+    }
+
+    (KnowledgeHandle::new(ontology), concept_id)
+}
+
+fn high_score_query() -> QueryExpr {
+    QueryExpr::AttrFilter(AttributeFilter {
+        attr_name: "score".to_string(),
+        op: ComparisonOp::Gt,
+        value: AttributeValue::Integer(SEED_ENTITIES - 100),
+    })
+}
+
+async fn run_readers(handle: &KnowledgeHandle<SledStorage>, readers: usize) {
+    let tasks: Vec<_> = (0..readers)
+        .map(|_| {
+            let handle = handle.clone();
+            tokio::spawn(async move {
+                let query = high_score_query();
+                for _ in 0..QUERIES_PER_READER {
+                    handle.query_ids(&query).await;
+                }
+            })
+        })
+        .collect();
+
+    for task in tasks {
+        task.await.expect("reader task");
+    }
+}
+
+fn bench_concurrent_reads(c: &mut Criterion) {
+    let rt = Runtime::new().expect("tokio runtime");
+    let mut group = c.benchmark_group("knowledge_reads");
+
+    for readers in [1usize, 4, 16] {
+        let (handle, _) = seeded_handle(&format!("bench_reads_db_{}", readers));
+        group.bench_with_input(BenchmarkId::new("readers_only", readers), &readers, |b, &readers| {
+            b.to_async(&rt).iter(|| run_readers(&handle, readers));
+        });
+
+        let (handle, concept_id) = seeded_handle(&format!("bench_reads_writes_db_{}", readers));
+        group.bench_with_input(BenchmarkId::new("readers_with_writer", readers), &readers, |b, &readers| {
+            b.to_async(&rt).iter(|| async {
+                let writer = {
+                    let handle = handle.clone();
+                    tokio::spawn(async move {
+                        for score in 0..50 {
+                            let mut values = HashMap::new();
+                            values.insert("score".to_string(), AttributeValue::Integer(-score));
+                            handle.add_entity(concept_id, values).await.expect("valid entity");
+                        }
+                    })
+                };
+                run_readers(&handle, readers).await;
+                writer.await.expect("writer task");
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_concurrent_reads);
+criterion_main!(benches);
@@ -0,0 +1,163 @@
+// =============================================================================
+//  Astra AGI
+//  File: benches/plan_arena.rs
+//
+//  Description: Benchmarks for plan search before and after partial plans
+//               moved into a session arena. "clone_per_node" is the search
+//               as it was, copying every action taken into each node it
+//               generates; "arena" is the GOAP and anytime planners as they
+//               are. Allocations per search are counted with a counting
+//               global allocator and printed before timing.
+//
+//               Astra syntax trees are not arena-allocated yet; that is
+//               split out as its own change to astra_lang (see its
+//               README). "parse" measures parsing as it is, the "before"
+//               that change is to be compared against.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use astra_agi::planning::anytime::{AnytimeSearch, SearchBudget};
+use astra_agi::planning::planner::{Action, Goal, GoapPlanner, Plan, PlannerEngine, WorldState};
+use astra_lang::parse_program;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations_during<R>(f: impl FnOnce() -> R) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    std::hint::black_box(f());
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+/// A chain of `depth` steps toward the goal, with `distractors` actions
+/// that are always applicable and lead nowhere.
+fn domain(depth: usize, distractors: usize) -> (Goal, Vec<Action>) {
+    let action = |id: String, pre: Option<String>, effect: String| Action {
+        description: id.clone(),
+        id,
+        preconditions: pre.into_iter().map(|k| (k, true)).collect(),
+        effects: HashMap::from([(effect, true)]),
+        cost: 1.0,
+        risk: 0.0,
+    };
+    let mut actions: Vec<Action> = (0..depth)
+        .map(|i| action(format!("step_{}", i), i.checked_sub(1).map(|p| format!("done_{}", p)), format!("done_{}", i)))
+        .collect();
+    actions.extend((0..distractors).map(|i| action(format!("fidget_{}", i), None, format!("fidgeted_{}", i))));
+    let goal = Goal {
+        id: "finish".into(),
+        description: "Finish the chain".into(),
+        desired_state: HashMap::from([(format!("done_{}", depth - 1), true)]),
+        priority: 5,
+    };
+    (goal, actions)
+}
+
+/// The GOAP search before the arena: each node owns the actions that led to it.
+fn clone_per_node_plan(world: &WorldState, goal: &Goal, actions: &[Action]) -> Plan {
+    struct Node {
+        world: WorldState,
+        actions: Vec<Action>,
+        cost: f32,
+    }
+
+    let mut queue = VecDeque::new();
+    queue.push_back(Node { world: world.clone(), actions: Vec::new(), cost: 0.0 });
+    while let Some(node) = queue.pop_front() {
+        if goal.desired_state.iter().all(|(k, v)| node.world.get(k) == Some(v)) {
+            return Plan { goal_id: goal.id.clone(), actions: node.actions, estimated_cost: node.cost };
+        }
+        for action in actions {
+            if action.preconditions.iter().all(|(k, v)| node.world.get(k) == Some(v)) {
+                let mut world = node.world.clone();
+                for (k, v) in &action.effects {
+                    world.insert(k.clone(), *v);
+                }
+                let mut new_actions = node.actions.clone();
+                new_actions.push(action.clone());
+                queue.push_back(Node { world, actions: new_actions, cost: node.cost + action.cost });
+            }
+        }
+    }
+    Plan { goal_id: goal.id.clone(), actions: Vec::new(), estimated_cost: f32::INFINITY }
+}
+
+fn bench_plan_allocation(c: &mut Criterion) {
+    let world = WorldState::new();
+    let goap = GoapPlanner::new();
+    let mut group = c.benchmark_group("plan_search_allocation");
+
+    for depth in [3usize, 4, 5] {
+        let (goal, actions) = domain(depth, 3);
+        let before = allocations_during(|| clone_per_node_plan(&world, &goal, &actions));
+        let after = allocations_during(|| goap.plan(&world, &goal, &actions));
+        let anytime = allocations_during(|| AnytimeSearch::new(&world, &goal, actions.clone()).refine(SearchBudget::default()));
+        eprintln!(
+            "depth {}: clone_per_node {} allocations, arena {} allocations, anytime {} allocations",
+            depth, before, after, anytime
+        );
+
+        group.bench_with_input(BenchmarkId::new("clone_per_node", depth), &(&goal, &actions), |b, (goal, actions)| {
+            b.iter(|| clone_per_node_plan(&world, goal, actions))
+        });
+        group.bench_with_input(BenchmarkId::new("arena", depth), &(&goal, &actions), |b, (goal, actions)| {
+            b.iter(|| goap.plan(&world, goal, actions))
+        });
+    }
+
+    group.finish();
+}
+
+/// A program of `functions` small functions, each binding, calling, and
+/// returning.
+fn program(functions: usize) -> String {
+    (0..functions)
+        .map(|i| format!("fn step_{i}(x: Float<64>) {{\n  let y = combine(scale(x, {i}), \"step {i}\");\n  log(y);\n  return y;\n}}\n"))
+        .collect()
+}
+
+fn bench_parse_allocation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_allocation");
+
+    for functions in [10usize, 50, 200] {
+        let source = program(functions);
+        let parse = allocations_during(|| parse_program(&source));
+        eprintln!("{} functions: parse {} allocations", functions, parse);
+
+        group.bench_with_input(BenchmarkId::new("parse", functions), &source, |b, source| b.iter(|| parse_program(source)));
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_plan_allocation, bench_parse_allocation);
+criterion_main!(benches);
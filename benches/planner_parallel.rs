@@ -0,0 +1,68 @@
+// =============================================================================
+//  Astra AGI
+//  File: benches/planner_parallel.rs
+//
+//  Description: Benchmarks for parallel plan search against the sequential
+//               anytime search it mirrors, on domains with many reachable
+//               states. Run with `--features parallel-planning`; speedups
+//               depend on the cores available.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use astra_agi::planning::anytime::{AnytimeSearch, SearchBudget};
+use astra_agi::planning::parallel::{ParallelGoapPlanner, ParallelSearchConfig};
+use astra_agi::planning::planner::{Action, Goal, PlannerEngine, WorldState};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::collections::HashMap;
+
+/// `lights` lights that can be turned on in any order, each at its own
+/// cost, with the goal of all of them on: every subset of lights is a
+/// reachable state.
+fn lights_domain(lights: usize) -> (Goal, Vec<Action>) {
+    let actions = (0..lights)
+        .map(|i| Action {
+            id: format!("light_{}", i),
+            description: format!("Turn on light {}", i),
+            preconditions: HashMap::new(),
+            effects: HashMap::from([(format!("light_{}_on", i), true)]),
+            cost: 1.0 + (i % 4) as f32,
+            risk: 0.0,
+        })
+        .collect();
+    let goal = Goal {
+        id: "all_lights".into(),
+        description: "Turn on every light".into(),
+        desired_state: (0..lights).map(|i| (format!("light_{}_on", i), true)).collect(),
+        priority: 5,
+    };
+    (goal, actions)
+}
+
+fn bench_parallel_search(c: &mut Criterion) {
+    let world = WorldState::new();
+    let mut group = c.benchmark_group("planner_parallel");
+    group.sample_size(10);
+
+    for lights in [8usize, 10] {
+        let (goal, actions) = lights_domain(lights);
+        group.bench_with_input(BenchmarkId::new("anytime_sequential", lights), &actions, |b, actions| {
+            b.iter(|| AnytimeSearch::new(&world, &goal, actions.clone()).refine(SearchBudget::default()))
+        });
+        for threads in [1usize, 2, 4] {
+            let config = ParallelSearchConfig { threads: Some(threads), ..ParallelSearchConfig::default() };
+            let planner = ParallelGoapPlanner::new(config).expect("start planning threads");
+            let id = BenchmarkId::new(format!("parallel_{}_threads", threads), lights);
+            group.bench_with_input(id, &actions, |b, actions| b.iter(|| planner.plan(&world, &goal, actions)));
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_parallel_search);
+criterion_main!(benches);
@@ -0,0 +1,45 @@
+// =============================================================================
+//  Astra AGI
+//  File: build.rs
+//
+//  Description: With the `capi` feature, generates the C header for the C ABI
+//               (src/capi.rs) into the build's output directory, as
+//               configured in cbindgen.toml, and warns when the copy checked
+//               in at include/astra_agi.h no longer matches it. The source
+//               tree is never written to.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+fn main() {
+    #[cfg(feature = "capi")]
+    if let Err(e) = write_c_header() {
+        eprintln!("Could not generate the C header: {}", e);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(feature = "capi")]
+fn write_c_header() -> Result<(), Box<dyn std::error::Error>> {
+    use std::path::PathBuf;
+
+    let crate_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR")?);
+    let header = PathBuf::from(std::env::var("OUT_DIR")?).join("astra_agi.h");
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))?;
+
+    println!("cargo:rerun-if-changed=src/capi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=include/astra_agi.h");
+    // Only the C ABI is parsed, not the whole crate
+    cbindgen::Builder::new().with_config(config).with_src(crate_dir.join("src/capi.rs")).generate()?.write_to_file(&header);
+
+    let checked_in = crate_dir.join("include/astra_agi.h");
+    if std::fs::read(&checked_in).ok() != Some(std::fs::read(&header)?) {
+        println!("cargo:warning={} is out of date; copy {} over it", checked_in.display(), header.display());
+    }
+    Ok(())
+}
@@ -6,7 +6,7 @@
 //
 //  Author:      Alex Roussinov
 //  Created:     2025-12-26
-//  Updated:     2025-12-26
+//  Updated:     2026-10-16
 //
 //  This file is dual licensed under the MIT and Apache 2.0 licenses.
 // =============================================================================
@@ -30,19 +30,19 @@ fn main() -> Result<()> {
     // Add entities
     let mut alice_attrs = HashMap::new();
     alice_attrs.insert("name".to_string(), AttributeValue::String("Alice".to_string()));
-    let alice_id = ontology.add_entity(person_id, alice_attrs);
+    let alice_id = ontology.add_entity(person_id, alice_attrs)?;
 
     let mut bob_attrs = HashMap::new();
     bob_attrs.insert("name".to_string(), AttributeValue::String("Bob".to_string()));
-    let bob_id = ontology.add_entity(person_id, bob_attrs);
+    let bob_id = ontology.add_entity(person_id, bob_attrs)?;
 
     let mut carol_attrs = HashMap::new();
     carol_attrs.insert("name".to_string(), AttributeValue::String("Carol".to_string()));
-    let carol_id = ontology.add_entity(person_id, carol_attrs);
+    let carol_id = ontology.add_entity(person_id, carol_attrs)?;
 
     // Add relationships (edges)
-    ontology.add_relationship(alice_id, bob_id, RelationshipType::FriendOf);
-    ontology.add_relationship(bob_id, carol_id, RelationshipType::FriendOf);
+    ontology.add_relationship(alice_id, bob_id, RelationshipType::FriendOf)?;
+    ontology.add_relationship(bob_id, carol_id, RelationshipType::FriendOf)?;
 
     // Test BFS traversal starting from Alice
     let bfs_result = ontology.bfs(alice_id, 2);
@@ -6,7 +6,7 @@
 //
 //  Author:      Alex Roussinov
 //  Created:     2025-12-26
-//  Updated:     2025-12-26
+//  Updated:     2026-10-16
 //
 //  This file is dual licensed under the MIT and Apache 2.0 licenses.
 // =============================================================================
@@ -31,15 +31,15 @@ fn main() -> Result<()> {
     let mut alice_attrs = HashMap::new();
     alice_attrs.insert("name".to_string(), AttributeValue::String("Alice".to_string()));
     alice_attrs.insert("age".to_string(), AttributeValue::Integer(30));
-    let alice_id = ontology.add_entity(person_id, alice_attrs);
+    let alice_id = ontology.add_entity(person_id, alice_attrs)?;
 
     let mut bob_attrs = HashMap::new();
     bob_attrs.insert("name".to_string(), AttributeValue::String("Bob".to_string()));
     bob_attrs.insert("age".to_string(), AttributeValue::Integer(25));
-    let bob_id = ontology.add_entity(person_id, bob_attrs);
+    let bob_id = ontology.add_entity(person_id, bob_attrs)?;
 
     // Add relationship
-    ontology.add_relationship(alice_id, bob_id, RelationshipType::FriendOf);
+    ontology.add_relationship(alice_id, bob_id, RelationshipType::FriendOf)?;
 
     // Save state
     ontology.save_to_storage()?;
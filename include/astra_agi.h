@@ -0,0 +1,92 @@
+/* Astra AGI C ABI. Generated by cbindgen from src/capi.rs; do not edit. */
+
+#ifndef ASTRA_AGI_H
+#define ASTRA_AGI_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Outcome of a call; anything but `Ok` leaves a message for
+// `astra_last_error`.
+typedef enum AstraStatus {
+  ASTRA_STATUS_OK = 0,
+  // A required pointer was null
+  ASTRA_STATUS_NULL_ARGUMENT = 1,
+  // A string was not valid UTF-8
+  ASTRA_STATUS_INVALID_UTF8 = 2,
+  // The runtime refused the request
+  ASTRA_STATUS_FAILED = 3,
+  // Astra panicked; the runtime should be destroyed
+  ASTRA_STATUS_PANICKED = 4,
+} AstraStatus;
+
+// A runtime owned by the host. Opaque to C.
+typedef struct AstraRuntime AstraRuntime;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Creates a runtime. Returns null if it could not be created.
+struct AstraRuntime *astra_runtime_create(void);
+
+// Destroys a runtime. Null is ignored.
+//
+// # Safety
+// `handle` must be null or come from `astra_runtime_create`, and must not
+// be used again.
+void astra_runtime_destroy(struct AstraRuntime *handle);
+
+// Submits a user's message within a conversation; each session is a user
+// of its own to Astra. Astra's reply, or its clarifying question, is
+// delivered by the session's next `astra_poll_events`.
+//
+// # Safety
+// `handle` must come from `astra_runtime_create`; `session_id` and `text`
+// must be NUL-terminated strings.
+enum AstraStatus astra_submit_input(struct AstraRuntime *handle,
+                                    const char *session_id,
+                                    const char *text);
+
+// Runs one tick of the runtime: plans advance, emotions update, reminders
+// and commitments are checked.
+//
+// # Safety
+// `handle` must come from `astra_runtime_create`.
+enum AstraStatus astra_tick(struct AstraRuntime *handle);
+
+// Returns what happened in a session since its last poll as a JSON
+// array, oldest first, or null on failure. Each element has a "type":
+// "reply" (with "session" and "text"), "clarification" (with "session"
+// and "question"), "dropped" (with "session" and the "count" of replies
+// and questions lost because the session went unpolled too long), or
+// "narrative" (with "id", "timestamp" in Unix milliseconds, "event_type",
+// and "description"). Narrative events of other sessions are left out.
+// Free the result with `astra_string_free`.
+//
+// # Safety
+// `handle` must come from `astra_runtime_create`; `session_id` must be a
+// NUL-terminated string.
+char *astra_poll_events(struct AstraRuntime *handle, const char *session_id);
+
+// Message describing why the last call on `handle` failed, or null if it
+// succeeded. Valid until the next call on `handle`; do not free it.
+//
+// # Safety
+// `handle` must be null or come from `astra_runtime_create`.
+const char *astra_last_error(const struct AstraRuntime *handle);
+
+// Frees a string returned by Astra. Null is ignored.
+//
+// # Safety
+// `text` must be null or come from `astra_poll_events`, and must not be
+// used again.
+void astra_string_free(char *text);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ASTRA_AGI_H */
@@ -5,15 +5,18 @@
 //  Description:
 //  Demonstrates Astra AGI running programs, reasoning with Bayesian and fuzzy logic,
 //  exhibiting personality traits, and logging narrative memory.
+//  Replies are streamed to the terminal as they are generated.
+//  Type ':self' to see Astra's structured self-report.
 //
 //  Author:      Alex Roussinov
 //  Created:     2025-12-25
-//  Updated:     2025-12-25
+//  Updated:     2026-10-16
 //
 //  This file is dual licensed under the MIT and Apache 2.0 licenses.
 // =============================================================================
 
 use std::io::{self, Write};
+use astra_agi::interfaces::streaming::{ResponseStream, TemplateSource, DEFAULT_CAPACITY};
 use astra_agi::runtime::Runtime;
 use astra_agi::personality::personality::Personality;
 
@@ -23,7 +26,7 @@ fn main() {
 
     let mut personality = Personality::new();
 
-    println!("Welcome to Astra AGI Demo. Type 'exit' to quit.");
+    println!("Welcome to Astra AGI Demo. Type ':self' for a self-report, 'exit' to quit.");
 
     loop {
         print!("astra> ");
@@ -41,14 +44,29 @@ fn main() {
             continue;
         }
 
-        runtime.execute_program(input);
+        if input == ":self" {
+            match serde_json::to_string_pretty(&runtime.introspect()) {
+                Ok(report) => println!("{}", report),
+                Err(e) => println!("Could not render self-report: {}", e),
+            }
+            continue;
+        }
+
+        if let Err(e) = runtime.execute_program(input) {
+            println!("Not a valid program: {}", e);
+        }
 
         for _ in 0..5 {
             runtime.tick();
         }
 
         println!("Emotion State: {:?}", runtime.emotion_state);
-        println!("Personality response: {}", personality.respond_to_input(input));
+        print!("Personality response: ");
+        let mut reply = ResponseStream::start(TemplateSource::new(personality.respond_to_input(input)), DEFAULT_CAPACITY);
+        if let Err(e) = reply.write_to(&mut io::stdout()) {
+            println!("Could not stream response: {}", e);
+        }
+        println!();
 
         let recent_events = runtime.narrative_memory.recent_events(5);
         println!("Recent Narrative Events:");
@@ -4,86 +4,522 @@
 //
 //  Description:
 //  Web server exposing REST API for chat interaction with Astra AGI.
-//  Allows sending messages, receiving Astra's replies, and viewing her internal states.
+//  Allows sending messages, receiving Astra's replies, rating them, viewing her internal states,
+//  following the progress of plans pursuing an intent, reading her structured self-report,
+//  scraping Prometheus metrics at /metrics, sending operator commands to /admin,
+//  running Astra programs at /run,
+//  reading transparency reports on a window of her activity at /transparency,
+//  and approving or rejecting her self-modification proposals.
 //  Integrates runtime, personality, emotion, and narrative memory subsystems.
+//  The runtime ticks every second in the background unless an operator
+//  pauses it.
+//  Chat messages may name a session, which is bound to the user who first
+//  sends to it; replies report only that user's own memories, and events
+//  that belong to no user stay private to Astra. Replies the runtime's
+//  hooks veto are withheld.
+//  Callers authenticate with an API key from ASTRA_API_KEYS, given as
+//  comma-separated `key=user_id` or `key=user_id:admin` grants; only admins
+//  may read the self-report or transparency reports, decide on self-modifications,
+//  run programs, or run admin commands. Chat messages are never run as programs.
+//  Replies and programs read a snapshot of the knowledge base shared with the
+//  runtime, taken when the request arrives.
+//  With ASTRA_DATA_DIR set, state is kept there: a write-ahead log, memory
+//  archives spilled to disk, learned preferences, saved every minute and
+//  on shutdown, backups of the stores beside them, and the key that
+//  personal data in memory is pseudonymized with.
+//  Knowledge packs in ASTRA_PACKS_DIR are loaded at startup if signed by a
+//  key in ASTRA_PACK_KEYS, or unsigned with ASTRA_ALLOW_UNSIGNED_PACKS set.
+//  Knowledge changes are appended as Cypher to ASTRA_CYPHER_EXPORT and, in
+//  builds with the `sqlite` feature, mirrored into ASTRA_SQLITE_MIRROR.
+//  Unless ASTRA_NO_CRAWLER is set, a web crawler runs alongside, starting
+//  from the URLs in ASTRA_CRAWL_SEEDS and searching for what Astra keeps
+//  failing to answer, and adds what it reads to the knowledge base.
+//  ASTRA_QUOTAS limits external resources, as comma-separated
+//  `resource=limit/seconds` such as `http_requests=600/3600`; crawled pages
+//  count against the HTTP request quota.
 //
 //  Author:      Alex Roussinov
 //  Created:     2025-12-25
-//  Updated:     2025-12-25
+//  Updated:     2026-10-16
 //
 //  This file is dual licensed under the MIT and Apache 2.0 licenses.
 // =============================================================================
 
-use actix_web::{web, App, HttpResponse, HttpServer, Responder};
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer, Responder};
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
+use astra_agi::learning::feedback::{FeedbackEvent, FeedbackKind, ResponseId};
+use astra_agi::learning::preferences::PreferenceModel;
+use astra_agi::cognition::error::CognitionError;
+use astra_agi::cognition::self_modification::ProposalId;
+use astra_agi::knowledge::cypher_export::{CypherExporter, CypherScript};
+use astra_agi::knowledge::extended_ontology::OntologyManager;
+use astra_agi::knowledge::snapshots::SharedKnowledge;
+#[cfg(feature = "sqlite")]
+use astra_agi::knowledge::sqlite_mirror::SqliteMirror;
+use astra_agi::interfaces::api::TransparencyQuery;
+use astra_agi::interfaces::auth::{Authenticator, Principal, Role, SessionStore};
+use astra_agi::memory::archive_file::SpillPolicy;
+use astra_agi::memory::privacy::{PrivacyFilter, PrivacyPolicy};
+use astra_agi::planning::quotas::parse_limits;
+use astra_agi::runtime::admin::AdminCommand;
+use astra_agi::runtime::backup::BackupConfig;
+use astra_agi::runtime::error::RuntimeError;
+use astra_agi::runtime::executor::ProgramStatus;
+use astra_agi::runtime::intent_manager::IntentId;
+use astra_agi::runtime::knowledge_packs::{parse_verifying_key, KnowledgePackConfig};
+use astra_agi::runtime::wal::WalConfig;
 use astra_agi::runtime::Runtime;
+use astra_agi::web_crawler::crawler::WebCrawler;
 
 struct AppState {
     runtime: Mutex<Runtime>,
+    auth: Authenticator,
+    sessions: Mutex<SessionStore>,
+    /// Where learned preferences are kept, with ASTRA_DATA_DIR set
+    preferences: Option<PathBuf>,
+}
+
+/// The caller, if their API key is known and grants at least `role`;
+/// otherwise the 401 or 403 to answer with.
+fn authorize(data: &AppState, http: &HttpRequest, role: Role) -> Result<Principal, HttpResponse> {
+    let header = |name: &str| http.headers().get(name).and_then(|value| value.to_str().ok());
+    let principal = data
+        .auth
+        .authenticate(header("x-api-key"), header("authorization"))
+        .map_err(|e| HttpResponse::Unauthorized().body(e.to_string()))?;
+    principal.require(role).map_err(|e| HttpResponse::Forbidden().body(e.to_string()))?;
+    Ok(principal)
+}
+
+/// Binds `session_id` to `principal`, ending sessions left idle too long
+/// along the way; a session bound to someone else is refused with 403.
+fn bind_session(data: &AppState, runtime: &mut Runtime, session_id: &str, principal: &Principal) -> Result<(), HttpResponse> {
+    let mut sessions = data.sessions.lock().unwrap();
+    let now = runtime.clock().now_utc();
+    for session in sessions.expire(now) {
+        runtime.end_conversation(&session.id);
+    }
+    sessions.bind(session_id, principal, now).map_err(|e| HttpResponse::Forbidden().body(e.to_string()))?;
+    Ok(())
+}
+
+/// Parses ASTRA_API_KEYS grants, skipping malformed ones with a warning.
+fn authenticator_from_env() -> Authenticator {
+    let mut auth = Authenticator::new();
+    for grant in std::env::var("ASTRA_API_KEYS").unwrap_or_default().split(',').filter(|g| !g.trim().is_empty()) {
+        let Some((key, who)) = grant.trim().split_once('=') else {
+            eprintln!("Ignoring malformed API key grant (expected key=user_id[:admin])");
+            continue;
+        };
+        let principal = match who.split_once(':') {
+            Some((user_id, "admin")) => Principal::new(user_id, Role::Admin),
+            _ => Principal::new(who, Role::User),
+        };
+        auth.add_api_key(key, principal);
+    }
+    auth
+}
+
+/// Archived events older than this are spilled to disk.
+const SPILL_AFTER_SECS: u64 = 30 * 24 * 3600;
+
+/// Time between runtime ticks.
+const TICK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Ticks between saves of the learned preferences.
+const PREFERENCE_SAVE_TICKS: u64 = 60;
+
+/// Ticks the runtime for as long as the server runs, unless an operator
+/// pauses it, saving learned preferences every minute. The server runs no
+/// cognitive loop and keeps no indexes of its own, so the parts of a
+/// maintenance cycle meant for them are logged as skipped rather than left
+/// queued.
+async fn run_ticks(data: web::Data<AppState>) {
+    for tick in 1u64.. {
+        tokio::time::sleep(TICK_INTERVAL).await;
+        let maintenance = {
+            let mut runtime = data.runtime.lock().unwrap();
+            runtime.tick();
+            runtime.take_maintenance()
+        };
+        for maintenance in maintenance {
+            eprintln!("Skipping {:?} maintenance: this server runs no cognitive loop or host indexes", maintenance);
+        }
+        if tick % PREFERENCE_SAVE_TICKS == 0 {
+            save_preferences(&data);
+        }
+    }
+}
+
+/// The file learned preferences are kept in under `data_dir`.
+fn preferences_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("models").join("preferences.json")
+}
+
+/// Saves the runtime's learned preferences, if the server keeps state.
+fn save_preferences(data: &AppState) {
+    let Some(path) = &data.preferences else {
+        return;
+    };
+    let model = data.runtime.lock().unwrap().preference_model.clone();
+    if let Err(e) = model.save(path) {
+        eprintln!("Could not save preferences to {}: {:#}", path.display(), e);
+    }
+}
+
+/// Pause between crawled pages.
+const CRAWL_RATE_LIMIT_MS: u64 = 2000;
+
+/// How long the crawler waits when it has nothing to crawl.
+const CRAWL_IDLE: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Crawls from the URLs in ASTRA_CRAWL_SEEDS, comma-separated, for as long
+/// as the server runs, along with searches for the runtime's recurring
+/// knowledge gaps, writing what the pages state into the knowledge base
+/// and counting them in the runtime's metrics.
+async fn crawl(mut crawler: WebCrawler, data: web::Data<AppState>) {
+    for seed in std::env::var("ASTRA_CRAWL_SEEDS").unwrap_or_default().split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        crawler.enqueue(seed);
+    }
+    loop {
+        // Questions Astra keeps failing to answer are searched for
+        for topic in data.runtime.lock().unwrap().take_crawl_topics() {
+            crawler.enqueue_topic(&topic);
+        }
+        match crawler.crawl_next().await {
+            Ok(Some(_)) => data.runtime.lock().unwrap().record_crawled_pages(1),
+            Ok(None) => tokio::time::sleep(CRAWL_IDLE).await,
+            Err(e) => eprintln!("Could not crawl a page: {:#}", e),
+        }
+    }
+}
+
+/// A fresh runtime, or with ASTRA_DATA_DIR set, one that keeps its state
+/// there: recovered from and logged to a write-ahead log, with old memory
+/// archived to disk, learned preferences loaded from where they were last
+/// saved, and backed up along with the knowledge, models, and
+/// configuration stored beside them.
+fn runtime_from_env(knowledge: SharedKnowledge) -> std::io::Result<Runtime> {
+    let mut builder = Runtime::builder().with_knowledge(knowledge);
+    if std::env::var_os("ASTRA_NO_CRAWLER").is_some() {
+        builder = builder.without_crawler();
+    }
+    if let Ok(spec) = std::env::var("ASTRA_QUOTAS") {
+        for (resource, limit) in parse_limits(&spec).map_err(|e| std::io::Error::other(e.to_string()))? {
+            builder = builder.with_quota(resource, limit.limit, limit.window);
+        }
+    }
+    if let Some(packs) = packs_from_env()? {
+        builder = builder.with_knowledge_packs(packs);
+    }
+    let data_dir = std::env::var_os("ASTRA_DATA_DIR").map(PathBuf::from);
+    if let Some(data_dir) = &data_dir {
+        std::fs::create_dir_all(data_dir.join("memory"))?;
+        std::fs::create_dir_all(data_dir.join("models"))?;
+        builder = builder.with_wal(WalConfig::new(data_dir.join("state.wal"))).with_backup(BackupConfig::for_data_dir(data_dir));
+    }
+    let mut runtime = builder.build().map_err(|e| std::io::Error::other(e.to_string()))?;
+    if let Some(data_dir) = &data_dir {
+        let preferences = preferences_path(data_dir);
+        if preferences.exists() {
+            runtime.preference_model = PreferenceModel::load(&preferences).map_err(|e| std::io::Error::other(e.to_string()))?;
+        }
+        runtime.set_archive_spill(SpillPolicy::new(data_dir.join("memory"), SPILL_AFTER_SECS));
+        let policy = PrivacyPolicy::with_key_file(data_dir.join("privacy.key"))?;
+        runtime.narrative_memory.privacy = Some(PrivacyFilter::new(policy));
+    }
+    if let Some(path) = std::env::var_os("ASTRA_CYPHER_EXPORT") {
+        let script = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+        runtime.set_graph_export(CypherExporter::new(), CypherScript::new(script));
+    }
+    #[cfg(feature = "sqlite")]
+    if let Some(path) = std::env::var_os("ASTRA_SQLITE_MIRROR") {
+        let mirror = SqliteMirror::open(path).map_err(|e| std::io::Error::other(e.to_string()))?;
+        runtime.set_sqlite_mirror(mirror);
+    }
+    Ok(runtime)
+}
+
+/// Knowledge packs in ASTRA_PACKS_DIR, `packs` by default, to load at
+/// startup. Only packs signed by a key in ASTRA_PACK_KEYS, comma-separated
+/// hex public keys, are loaded, unless ASTRA_ALLOW_UNSIGNED_PACKS is set.
+/// None if there is no pack directory or nothing could be trusted.
+fn packs_from_env() -> std::io::Result<Option<KnowledgePackConfig>> {
+    let dir = std::env::var_os("ASTRA_PACKS_DIR").map_or_else(|| PathBuf::from("packs"), PathBuf::from);
+    if !dir.is_dir() {
+        return Ok(None);
+    }
+    let trusted_keys = std::env::var("ASTRA_PACK_KEYS")
+        .unwrap_or_default()
+        .split(',')
+        .filter(|key| !key.trim().is_empty())
+        .map(|key| parse_verifying_key(key).map_err(|e| std::io::Error::other(e.to_string())))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    let allow_unsigned = std::env::var_os("ASTRA_ALLOW_UNSIGNED_PACKS").is_some();
+    if trusted_keys.is_empty() && !allow_unsigned {
+        eprintln!("Not loading knowledge packs from {}: ASTRA_PACK_KEYS trusts no key", dir.display());
+        return Ok(None);
+    }
+    Ok(Some(KnowledgePackConfig { dir, trusted_keys, allow_unsigned, ..KnowledgePackConfig::default() }))
 }
 
 #[derive(Deserialize)]
 struct ChatRequest {
     message: String,
+    /// Conversation the message belongs to
+    #[serde(default)]
+    session_id: Option<String>,
 }
 
 #[derive(Serialize)]
 struct ChatResponse {
+    response_id: ResponseId,
     reply: String,
     emotion_state: String,
     personality_traits: String,
     recent_events: Vec<String>,
 }
 
-async fn chat_handler(data: web::Data<AppState>, req: web::Json<ChatRequest>) -> impl Responder {
+async fn chat_handler(data: web::Data<AppState>, http: HttpRequest, req: web::Json<ChatRequest>) -> impl Responder {
+    let principal = match authorize(&data, &http, Role::User) {
+        Ok(principal) => principal,
+        Err(denied) => return denied,
+    };
+    let user_id = principal.user_id.clone();
     let mut runtime = data.runtime.lock().unwrap();
 
-    // Execute user message as Astra program (or adapt as needed)
-    runtime.execute_program(&req.message);
-
-    // Run several ticks to process
-    for _ in 0..5 {
-        runtime.tick();
+    if let Some(session_id) = &req.session_id {
+        if let Err(denied) = bind_session(&data, &mut runtime, session_id, &principal) {
+            return denied;
+        }
     }
+    // A reply the runtime's hooks veto is withheld and not recorded as said
+    let knowledge = runtime.knowledge().map(SharedKnowledge::snapshot);
+    let snapshot = knowledge.as_deref().map(|snapshot| &**snapshot);
+    let reply = match runtime.reply_to_user(&user_id, req.session_id.as_deref(), &req.message, snapshot) {
+        Ok(reply) => reply,
+        Err(vetoed @ RuntimeError::Vetoed(_)) => return HttpResponse::UnprocessableEntity().body(vetoed.to_string()),
+        Err(e) => return HttpResponse::InternalServerError().body(e.to_string()),
+    };
 
-    // Generate personality response
-    let mut personality = runtime.personality.clone();
-    let reply = personality.respond_to_input(&req.message);
-
-    // Format recent narrative events for client
+    // Format the user's own recent narrative events for the client
     let recent_events: Vec<String> = runtime
         .narrative_memory
         .recent_events(10)
-        .iter()
+        .into_iter()
+        .filter(|e| e.user_id().is_some_and(|owner| owner == user_id))
         .map(|e| format!("[{}] {}: {}", e.timestamp, e.event_type, e.description))
         .collect();
 
     let response = ChatResponse {
-        reply,
+        response_id: reply.response_id,
+        reply: reply.text,
         emotion_state: format!("{:?}", runtime.emotion_state),
-        personality_traits: format!("{:?}", personality.traits),
+        personality_traits: format!("{:?}", runtime.personality.traits),
         recent_events,
     };
 
     HttpResponse::Ok().json(response)
 }
 
+#[derive(Deserialize)]
+struct FeedbackRequest {
+    response_id: ResponseId,
+    kind: FeedbackKind,
+}
+
+async fn feedback_handler(data: web::Data<AppState>, http: HttpRequest, req: web::Json<FeedbackRequest>) -> impl Responder {
+    let principal = match authorize(&data, &http, Role::User) {
+        Ok(principal) => principal,
+        Err(denied) => return denied,
+    };
+    let mut runtime = data.runtime.lock().unwrap();
+    let req = req.into_inner();
+
+    let event = FeedbackEvent {
+        response_id: req.response_id,
+        kind: req.kind,
+        user_id: Some(principal.user_id),
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+    };
+
+    match runtime.ingest_feedback(event) {
+        Ok(assignment) => HttpResponse::Ok().json(serde_json::json!({
+            "reward": assignment.reward,
+            "credited_components": assignment.credits.len(),
+        })),
+        Err(e) => HttpResponse::NotFound().body(e.to_string()),
+    }
+}
+
+async fn intent_progress_handler(data: web::Data<AppState>, http: HttpRequest, path: web::Path<IntentId>) -> impl Responder {
+    if let Err(denied) = authorize(&data, &http, Role::User) {
+        return denied;
+    }
+    let runtime = data.runtime.lock().unwrap();
+    let intent_id = path.into_inner();
+
+    match runtime.plan_runner.progress_of(intent_id) {
+        Some(progress) => HttpResponse::Ok().json(serde_json::json!({
+            "intent_id": intent_id,
+            "fraction": progress.fraction(),
+            "milestones_reached": progress.milestones_reached,
+            "milestones_total": progress.milestones_total,
+            "estimated_remaining_secs": progress.estimated_remaining.map(|d| d.as_secs()),
+            "phase": progress.phase.as_str(),
+        })),
+        None => HttpResponse::NotFound().body(format!("No plan for intent {}", intent_id)),
+    }
+}
+
+async fn introspect_handler(data: web::Data<AppState>, http: HttpRequest) -> impl Responder {
+    if let Err(denied) = authorize(&data, &http, Role::Admin) {
+        return denied;
+    }
+    let runtime = data.runtime.lock().unwrap();
+    HttpResponse::Ok().json(runtime.introspect())
+}
+
+async fn metrics_handler(data: web::Data<AppState>, http: HttpRequest) -> impl Responder {
+    if let Err(denied) = authorize(&data, &http, Role::User) {
+        return denied;
+    }
+    let runtime = data.runtime.lock().unwrap();
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(runtime.render_metrics())
+}
+
+async fn transparency_handler(
+    data: web::Data<AppState>,
+    http: HttpRequest,
+    query: web::Query<TransparencyQuery>,
+) -> impl Responder {
+    if let Err(denied) = authorize(&data, &http, Role::Admin) {
+        return denied;
+    }
+    let runtime = data.runtime.lock().unwrap();
+    let report = runtime.transparency_report(query.from, query.to);
+    match query.format.as_deref() {
+        Some("text") => HttpResponse::Ok().content_type("text/plain; charset=utf-8").body(report.render()),
+        _ => HttpResponse::Ok().json(report),
+    }
+}
+
+async fn admin_handler(data: web::Data<AppState>, http: HttpRequest, command: web::Json<AdminCommand>) -> impl Responder {
+    let operator = match authorize(&data, &http, Role::Admin) {
+        Ok(principal) => principal.user_id,
+        Err(denied) => return denied,
+    };
+    let mut runtime = data.runtime.lock().unwrap();
+    HttpResponse::Ok().json(runtime.admin(command.into_inner(), &operator))
+}
+
+#[derive(Deserialize)]
+struct RunProgramRequest {
+    source: String,
+}
+
+async fn run_program_handler(data: web::Data<AppState>, http: HttpRequest, req: web::Json<RunProgramRequest>) -> impl Responder {
+    if let Err(denied) = authorize(&data, &http, Role::Admin) {
+        return denied;
+    }
+    let mut runtime = data.runtime.lock().unwrap();
+    let knowledge = runtime.knowledge().map(SharedKnowledge::snapshot);
+    match runtime.run_program(&req.source, knowledge.as_deref().map(|snapshot| &**snapshot)) {
+        Ok(ProgramStatus::Finished(value)) => {
+            HttpResponse::Ok().json(serde_json::json!({ "status": "finished", "detail": value.to_string() }))
+        }
+        Ok(ProgramStatus::Suspended { waiting_for, .. }) => {
+            HttpResponse::Ok().json(serde_json::json!({ "status": "suspended", "detail": waiting_for.to_string() }))
+        }
+        Err(e) => HttpResponse::UnprocessableEntity().body(e.to_string()),
+    }
+}
+
+async fn proposals_handler(data: web::Data<AppState>, http: HttpRequest) -> impl Responder {
+    if let Err(denied) = authorize(&data, &http, Role::Admin) {
+        return denied;
+    }
+    let runtime = data.runtime.lock().unwrap();
+    HttpResponse::Ok().json(serde_json::json!({
+        "pending": runtime.self_modifications.pending(),
+        "audit": runtime.self_modifications.audit_trail(),
+    }))
+}
+
+#[derive(Deserialize)]
+struct ProposalDecision {
+    approve: bool,
+    reason: Option<String>,
+}
+
+async fn proposal_decision_handler(
+    data: web::Data<AppState>,
+    http: HttpRequest,
+    path: web::Path<ProposalId>,
+    req: web::Json<ProposalDecision>,
+) -> impl Responder {
+    let approver = match authorize(&data, &http, Role::Admin) {
+        Ok(principal) => principal.user_id,
+        Err(denied) => return denied,
+    };
+    let mut runtime = data.runtime.lock().unwrap();
+    let id = path.into_inner();
+    let result = if req.approve {
+        runtime.self_modifications.approve(id, &approver)
+    } else {
+        runtime.self_modifications.reject(id, &approver, req.reason.as_deref().unwrap_or("no reason given"))
+    };
+
+    match result {
+        Ok(()) => HttpResponse::Ok().json(runtime.self_modifications.get(id)),
+        Err(e @ CognitionError::ProposalNotFound(_)) => HttpResponse::NotFound().body(e.to_string()),
+        Err(e) => HttpResponse::Conflict().body(e.to_string()),
+    }
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    let runtime = Runtime::new();
+    // One runtime shared by every worker, and one knowledge base it reads snapshots of
+    let runtime = runtime_from_env(SharedKnowledge::new(OntologyManager::new()))?;
+    let crawler = runtime.web_crawler(CRAWL_RATE_LIMIT_MS);
+    let state = web::Data::new(AppState {
+        runtime: Mutex::new(runtime),
+        auth: authenticator_from_env(),
+        sessions: Mutex::new(SessionStore::new()),
+        preferences: std::env::var_os("ASTRA_DATA_DIR").map(|dir| preferences_path(Path::new(&dir))),
+    });
+    actix_web::rt::spawn(run_ticks(state.clone()));
+    if let Some(crawler) = crawler {
+        actix_web::rt::spawn(crawl(crawler, state.clone()));
+    }
 
     println!("Starting Astra AGI Web Server at http://127.0.0.1:8080");
 
     HttpServer::new(move || {
         App::new()
-            .app_data(web::Data::new(AppState {
-                runtime: Mutex::new(runtime.clone()),
-            }))
+            .app_data(state.clone())
             .route("/chat", web::post().to(chat_handler))
+            .route("/feedback", web::post().to(feedback_handler))
+            .route("/intents/{id}/progress", web::get().to(intent_progress_handler))
+            .route("/introspect", web::get().to(introspect_handler))
+            .route("/metrics", web::get().to(metrics_handler))
+            .route("/transparency", web::get().to(transparency_handler))
+            .route("/admin", web::post().to(admin_handler))
+            .route("/run", web::post().to(run_program_handler))
+            .route("/self_modifications", web::get().to(proposals_handler))
+            .route("/self_modifications/{id}", web::post().to(proposal_decision_handler))
     })
     .bind(("127.0.0.1", 8080))?
     .run()
-    .await
+    .await?;
+    save_preferences(&state);
+    Ok(())
 }
@@ -0,0 +1,359 @@
+// =============================================================================
+//  Astra AGI - C ABI
+//  File: capi.rs
+//
+//  Description:
+//  A stable C interface for hosting Astra in other languages, such as game
+//  engines and C++ robotics stacks. The host creates a runtime, submits
+//  user input to it, ticks it, and polls each session for what happened in
+//  it since its last poll as a JSON array: Astra's replies and clarifying
+//  questions, and the events it recorded in narrative memory that are the
+//  session's own or nobody's. Input is answered by the same pipeline as
+//  the chat API, each session standing for its own user. Replies wait in a
+//  bounded outbox per session; when a host stops polling, the oldest are
+//  dropped and the next poll says how many. Every function returns or takes
+//  plain C types; strings are NUL-terminated UTF-8, and strings Astra hands
+//  out are freed with `astra_string_free`. Panics never cross the boundary:
+//  they are caught and reported as `AstraStatus::Panicked`.
+//
+//  Built with the `capi` feature, which also generates the C header into
+//  the build's output directory and warns when the copy checked in at
+//  include/astra_agi.h is out of date (see build.rs and cbindgen.toml). To
+//  get a shared or static library:
+//      cargo rustc --lib --release --features capi --crate-type cdylib
+//      cargo rustc --lib --release --features capi --crate-type staticlib
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use std::collections::{HashMap, VecDeque};
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use serde_json::{json, Value};
+
+use crate::memory::narrative_memory::EventId;
+use crate::planning::dialogue::DialogueAct;
+use crate::runtime::Runtime;
+
+/// Replies and questions kept per session until it is polled.
+const OUTBOX_CAPACITY: usize = 256;
+
+/// Outcome of a call; anything but `Ok` leaves a message for
+/// `astra_last_error`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AstraStatus {
+    Ok = 0,
+    /// A required pointer was null
+    NullArgument = 1,
+    /// A string was not valid UTF-8
+    InvalidUtf8 = 2,
+    /// The runtime refused the request
+    Failed = 3,
+    /// Astra panicked; the runtime should be destroyed
+    Panicked = 4,
+}
+
+/// What a session has not yet polled.
+#[derive(Debug, Default)]
+struct Outbox {
+    /// Replies and questions, oldest first
+    messages: VecDeque<Value>,
+    /// Messages dropped to stay within `OUTBOX_CAPACITY`
+    dropped: usize,
+    /// Newest narrative event already polled
+    polled: EventId,
+}
+
+/// A runtime owned by the host. Opaque to C.
+pub struct AstraRuntime {
+    runtime: Runtime,
+    /// By session ID
+    outboxes: HashMap<String, Outbox>,
+    last_error: Option<CString>,
+}
+
+impl AstraRuntime {
+    fn fail(&mut self, status: AstraStatus, message: impl Into<String>) -> AstraStatus {
+        // Messages come from errors, which never contain NUL
+        self.last_error = CString::new(message.into()).ok();
+        status
+    }
+
+    fn submit(&mut self, session_id: &str, text: &str) -> Result<(), String> {
+        // Opened first, so the events of this very message are polled
+        self.outbox(session_id);
+        let reply = self.runtime.reply_to_user(session_id, Some(session_id), text, None).map_err(|e| e.to_string())?;
+        let message = if reply.acts.contains(&DialogueAct::Ask) {
+            json!({ "type": "clarification", "session": session_id, "question": reply.text })
+        } else {
+            json!({ "type": "reply", "session": session_id, "text": reply.text })
+        };
+        let outbox = self.outbox(session_id);
+        if outbox.messages.len() == OUTBOX_CAPACITY {
+            outbox.messages.pop_front();
+            outbox.dropped += 1;
+        }
+        outbox.messages.push_back(message);
+        Ok(())
+    }
+
+    /// The session's outbox; a new one starts after the events already
+    /// recorded, which happened before the session existed.
+    fn outbox(&mut self, session_id: &str) -> &mut Outbox {
+        let newest = self.runtime.narrative_memory.events.back().map_or(0, |e| e.id);
+        self.outboxes.entry(session_id.to_string()).or_insert_with(|| Outbox { polled: newest, ..Default::default() })
+    }
+
+    /// Everything the session has not polled, oldest first: narrative
+    /// events that are its own or nobody's, then its replies.
+    fn poll(&mut self, session_id: &str) -> Value {
+        let newest = self.runtime.narrative_memory.events.back().map(|e| e.id);
+        let Some(outbox) = self.outboxes.get_mut(session_id) else {
+            return Value::Array(Vec::new());
+        };
+        let mut events: Vec<Value> = self
+            .runtime
+            .narrative_memory
+            .events
+            .iter()
+            .filter(|e| e.id > outbox.polled)
+            .filter(|e| e.user_id().is_none_or(|owner| owner == session_id))
+            .map(|e| {
+                json!({
+                    "type": "narrative",
+                    "id": e.id,
+                    "timestamp": e.timestamp.as_millis(),
+                    "event_type": e.event_type,
+                    "description": e.description,
+                })
+            })
+            .collect();
+        if let Some(newest) = newest {
+            outbox.polled = outbox.polled.max(newest);
+        }
+        if outbox.dropped > 0 {
+            events.push(json!({ "type": "dropped", "session": session_id, "count": outbox.dropped }));
+            outbox.dropped = 0;
+        }
+        events.extend(outbox.messages.drain(..));
+        Value::Array(events)
+    }
+}
+
+/// Runs `f` on the runtime behind `handle`, turning a null handle or a
+/// panic into a status.
+fn with_runtime(handle: *mut AstraRuntime, f: impl FnOnce(&mut AstraRuntime) -> AstraStatus) -> AstraStatus {
+    // SAFETY: callers pass a handle from `astra_runtime_create` that has not
+    // been destroyed, and do not share it between threads during a call.
+    let Some(astra) = (unsafe { handle.as_mut() }) else {
+        return AstraStatus::NullArgument;
+    };
+    astra.last_error = None;
+    match catch_unwind(AssertUnwindSafe(|| f(&mut *astra))) {
+        Ok(status) => status,
+        Err(_) => astra.fail(AstraStatus::Panicked, "Astra panicked"),
+    }
+}
+
+/// Reads a string argument, or says why it could not be read.
+///
+/// # Safety
+/// `text` must be null or point to a NUL-terminated string.
+unsafe fn read_str<'a>(text: *const c_char) -> Result<&'a str, AstraStatus> {
+    if text.is_null() {
+        return Err(AstraStatus::NullArgument);
+    }
+    CStr::from_ptr(text).to_str().map_err(|_| AstraStatus::InvalidUtf8)
+}
+
+/// Creates a runtime. Returns null if it could not be created.
+#[no_mangle]
+pub extern "C" fn astra_runtime_create() -> *mut AstraRuntime {
+    catch_unwind(|| {
+        let mut runtime = Runtime::new();
+        runtime.start();
+        AstraRuntime { runtime, outboxes: HashMap::new(), last_error: None }
+    })
+    .map_or(ptr::null_mut(), |astra| Box::into_raw(Box::new(astra)))
+}
+
+/// Destroys a runtime. Null is ignored.
+///
+/// # Safety
+/// `handle` must be null or come from `astra_runtime_create`, and must not
+/// be used again.
+#[no_mangle]
+pub unsafe extern "C" fn astra_runtime_destroy(handle: *mut AstraRuntime) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Submits a user's message within a conversation; each session is a user
+/// of its own to Astra. Astra's reply, or its clarifying question, is
+/// delivered by the session's next `astra_poll_events`.
+///
+/// # Safety
+/// `handle` must come from `astra_runtime_create`; `session_id` and `text`
+/// must be NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn astra_submit_input(
+    handle: *mut AstraRuntime,
+    session_id: *const c_char,
+    text: *const c_char,
+) -> AstraStatus {
+    with_runtime(handle, |astra| {
+        let (session_id, text) = match (read_str(session_id), read_str(text)) {
+            (Ok(session_id), Ok(text)) => (session_id, text),
+            (Err(status), _) | (_, Err(status)) => return astra.fail(status, "session_id and text must be UTF-8 strings"),
+        };
+        match astra.submit(session_id, text) {
+            Ok(()) => AstraStatus::Ok,
+            Err(message) => astra.fail(AstraStatus::Failed, message),
+        }
+    })
+}
+
+/// Runs one tick of the runtime: plans advance, emotions update, reminders
+/// and commitments are checked.
+///
+/// # Safety
+/// `handle` must come from `astra_runtime_create`.
+#[no_mangle]
+pub unsafe extern "C" fn astra_tick(handle: *mut AstraRuntime) -> AstraStatus {
+    with_runtime(handle, |astra| {
+        astra.runtime.tick();
+        AstraStatus::Ok
+    })
+}
+
+/// Returns what happened in a session since its last poll as a JSON
+/// array, oldest first, or null on failure. Each element has a "type":
+/// "reply" (with "session" and "text"), "clarification" (with "session"
+/// and "question"), "dropped" (with "session" and the "count" of replies
+/// and questions lost because the session went unpolled too long), or
+/// "narrative" (with "id", "timestamp" in Unix milliseconds, "event_type",
+/// and "description"). Narrative events of other sessions are left out.
+/// Free the result with `astra_string_free`.
+///
+/// # Safety
+/// `handle` must come from `astra_runtime_create`; `session_id` must be a
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn astra_poll_events(handle: *mut AstraRuntime, session_id: *const c_char) -> *mut c_char {
+    let mut json = ptr::null_mut();
+    with_runtime(handle, |astra| {
+        let session_id = match read_str(session_id) {
+            Ok(session_id) => session_id,
+            Err(status) => return astra.fail(status, "session_id must be a UTF-8 string"),
+        };
+        match CString::new(astra.poll(session_id).to_string()) {
+            Ok(text) => {
+                json = text.into_raw();
+                AstraStatus::Ok
+            }
+            Err(e) => astra.fail(AstraStatus::Failed, e.to_string()),
+        }
+    });
+    json
+}
+
+/// Message describing why the last call on `handle` failed, or null if it
+/// succeeded. Valid until the next call on `handle`; do not free it.
+///
+/// # Safety
+/// `handle` must be null or come from `astra_runtime_create`.
+#[no_mangle]
+pub unsafe extern "C" fn astra_last_error(handle: *const AstraRuntime) -> *const c_char {
+    handle
+        .as_ref()
+        .and_then(|astra| astra.last_error.as_ref())
+        .map_or(ptr::null(), |message| message.as_ptr())
+}
+
+/// Frees a string returned by Astra. Null is ignored.
+///
+/// # Safety
+/// `text` must be null or come from `astra_poll_events`, and must not be
+/// used again.
+#[no_mangle]
+pub unsafe extern "C" fn astra_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(text));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poll(handle: *mut AstraRuntime, session: &CStr) -> Vec<Value> {
+        unsafe {
+            let json = astra_poll_events(handle, session.as_ptr());
+            assert!(!json.is_null());
+            let events = serde_json::from_str(CStr::from_ptr(json).to_str().unwrap()).unwrap();
+            astra_string_free(json);
+            events
+        }
+    }
+
+    #[test]
+    fn test_host_submits_input_and_polls_events() {
+        let handle = astra_runtime_create();
+        assert!(!handle.is_null());
+        let session = CString::new("game:npc-7").unwrap();
+        let text = CString::new("Please water the plants tomorrow at 9am").unwrap();
+
+        unsafe {
+            assert_eq!(astra_submit_input(handle, session.as_ptr(), text.as_ptr()), AstraStatus::Ok);
+            assert!(astra_last_error(handle).is_null());
+            assert_eq!(astra_tick(handle), AstraStatus::Ok);
+        }
+        let events = poll(handle, &session);
+        assert!(events.iter().any(|e| e["type"] == "reply" && e["session"] == "game:npc-7"));
+        assert!(events.iter().any(|e| e["type"] == "narrative"));
+        // A second poll only sees what is new
+        assert!(poll(handle, &session).iter().all(|e| e["type"] == "narrative"));
+
+        // Another session sees neither the first session's replies nor its events
+        let other = CString::new("game:npc-8").unwrap();
+        let greeting = CString::new("Hello there").unwrap();
+        let remark = CString::new("I think the greenhouse is too cold.").unwrap();
+        unsafe {
+            assert_eq!(astra_submit_input(handle, other.as_ptr(), greeting.as_ptr()), AstraStatus::Ok);
+            assert_eq!(astra_submit_input(handle, session.as_ptr(), remark.as_ptr()), AstraStatus::Ok);
+        }
+        let events = poll(handle, &other);
+        assert!(events.iter().any(|e| e["type"] == "reply" && e["session"] == "game:npc-8"));
+        assert!(events.iter().all(|e| e["session"] != "game:npc-7"));
+        assert!(events.iter().all(|e| !e.to_string().contains("greenhouse")));
+        assert!(poll(handle, &session).iter().any(|e| e.to_string().contains("greenhouse")));
+
+        // An unpolled session keeps only its newest replies
+        for _ in 0..OUTBOX_CAPACITY + 2 {
+            unsafe { astra_submit_input(handle, other.as_ptr(), greeting.as_ptr()) };
+        }
+        let events = poll(handle, &other);
+        assert_eq!(events.iter().filter(|e| e["type"] == "reply").count(), OUTBOX_CAPACITY);
+        assert!(events.iter().any(|e| e["type"] == "dropped" && e["count"] == 2));
+
+        unsafe {
+            assert_eq!(astra_submit_input(handle, session.as_ptr(), ptr::null()), AstraStatus::NullArgument);
+            assert!(!astra_last_error(handle).is_null());
+            let invalid = [0xffu8, 0];
+            assert_eq!(
+                astra_submit_input(handle, session.as_ptr(), invalid.as_ptr().cast()),
+                AstraStatus::InvalidUtf8
+            );
+            assert_eq!(astra_tick(ptr::null_mut()), AstraStatus::NullArgument);
+            astra_runtime_destroy(handle);
+        }
+    }
+}
@@ -11,46 +11,170 @@
 //       • Integrate stimuli into the cognitive state
 //       • Form and select goals, generate plans, and execute them
 //       • Record episodes and thought traces for reflection and learning
+//       • Align goal selection and action costs with learned user preferences
+//       • Treat knowledge changes as stimuli and wake goals waiting on facts
+//       • Refine action cost estimates from execution telemetry
+//       • Spread deliberative planning across cycles under a search budget
+//       • Replan when actions miss their expected effects and learn from it
+//       • Let unmet drives set intrinsic goals while idle
+//       • Ignore weak stimuli and skip speculative goals under high load
+//       • Tire with effort, recover when idle, and plan and act worse when tired,
+//         slipping at random from a seedable source
+//       • Log knowledge gaps and pursue recurring ones as learning goals while idle
+//       • Execute plans under the autonomy policy
+//       • Price quota-consuming actions higher as quotas run low
+//       • Record the affect at the start and end of every episode and hold the
+//         finished episodes until the runtime writes them to narrative memory
+//       • Surface planning failures as typed cognition errors
+//       • Plan with actions grounded from declarative planning domains
+//       • Run behavior trees instead of planning for reactive goals that have one
+//       • Choose by utility whether to respond now, think more, or defer a goal
+//       • Learn skills from action sequences that keep succeeding and plan with them
+//       • Score strategies per goal category, learning categories from knowledge
+//       • Weaken the beliefs behind strongly contradicted predictions
+//       • Keep episode features for mining the situations strategies fail in,
+//         and plan by the heuristic rules adopted from them
+//       • Run reflection's strategy experiments on matched goals, keeping
+//         their trials out of reinforcement and committing only winners
+//       • Practice the weakest planning capability on curriculum tasks while idle,
+//         timing progress by the runtime's clock once attached to it
 //
 //   File:        /src/cognition/cognitive_loop.rs
 //   Author:      Alex Roussinov
 //   Created:     2026-01-11
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
-use anyhow::Result;
 use log::{info, warn};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use crate::cognition::{
     build_self_summary, generate_goals_from_stimulus, select_primary_goal,
-    update_curiosity, CognitiveState, ThoughtTrace,
+    select_primary_goal_with_preferences, strategy_to_string, update_curiosity, CognitiveState, ThoughtTrace,
 };
-use crate::cognition::episodes::record_episode;
+use crate::cognition::error::CognitionError;
+use crate::memory::episode_affect::{AffectSnapshot, EpisodeAffect};
 use crate::cognition::learning_adapter::LearningAdapter;
 
 use crate::planning::executor::{ActionExecutor, PlanExecutor, ExecutionStatus};
-use crate::planning::planner::{Planner, WorldState};
-use crate::cognition::motivation::{evaluate_goal_motivation, update_energy_after_outcome};
-use crate::cognition::goal_formation::Stimulus;
+use crate::learning::preferences::PreferenceModel;
+use crate::planning::cost_model::{CostModelChain, LearnedCostModel, SharedCostModel};
+use crate::planning::anytime::{AnytimeSearch, SearchBudget};
+use crate::planning::autonomy::AutonomyPolicy;
+use crate::planning::behavior_tree::{BehaviorTree, Status};
+use crate::planning::skills::SkillLibrary;
+use crate::cognition::heuristic_reinforcement::reinforce_strategy;
+use crate::planning::monitor::{surprise, ActionModelBeliefs};
+use crate::planning::quotas::SharedQuotaTracker;
+use crate::planning::planner::{goal_satisfied, Action, ActionCostModel, Goal, Plan, Planner, PlanningStrategy, WorldState};
+use crate::knowledge::change_feed::{ChangeSequence, FactPattern, KnowledgeChange};
+use crate::knowledge::extended_ontology::OntologyManager;
+use crate::knowledge::knowledge_gaps::KnowledgeGapDetector;
+use crate::cognition::fatigue::FatiguedExecutor;
+use crate::cognition::load::LoadSignals;
+use crate::cognition::motivation::{
+    evaluate_goal_motivation, generate_intrinsic_goal, update_energy_after_outcome, Drive, IdleContext,
+};
+use crate::cognition::goal_formation::{learning_goals_from_gaps, Stimulus};
+use crate::cognition::goal_categories::GoalCategorizer;
+use crate::cognition::prediction_error::{update_beliefs_on_surprise, PredictionError, SurpriseUpdate, SURPRISE_SOURCE};
+use crate::knowledge::belief_ledger::BeliefLedger;
+use crate::knowledge::epistemic_reasoner::EpistemicReasoner;
+use crate::cognition::utility::{MomentBehavior, UtilityCandidate, UtilityDecision, UtilitySelector};
+use crate::cognition::curriculum::{attempt, Curriculum};
+use crate::cognition::experiments::{commit_winner, ExperimentId, ExperimentParams, ExperimentStatus, SelfExperiments};
+use crate::cognition::pattern_mining::{Condition, EpisodeFeatures, MiningParams};
+use crate::cognition::reflection;
+use crate::cognition::self_modification::{Modification, ModificationTargets, ProposalId, SelfModificationRegistry};
+use crate::random::{RandomSource, SeededRng};
+use crate::time::{system_clock, SharedClock};
+
+/// Times a gap must recur before it is worth a learning goal.
+const GAP_GOAL_MIN_OCCURRENCES: u32 = 3;
+
+/// Ticks a behavior tree may take to finish within one cycle.
+const MAX_TREE_TICKS: usize = 32;
+
+/// How much more search a goal gets when Astra chooses to think more.
+const THINK_MORE_BUDGET_SCALE: f32 = 4.0;
+
+/// Name of the random stream fatigue-induced slips are drawn from.
+const FATIGUE_STREAM: &str = "fatigue";
+
+/// Episodes whose features are kept for reflection to mine.
+const EPISODES_KEPT: usize = 500;
 
 /// Represents an interface that can provide world state from the environment.
 pub trait WorldStateProvider {
     fn current_world_state(&self) -> WorldState;
 }
 
+impl ActionCostModel for PreferenceModel {
+    fn action_cost(&self, action: &Action) -> f32 {
+        self.adjusted_cost(action.cost, action.risk, &action.description)
+    }
+}
+
+/// A goal set aside until the knowledge base reports a fact it is waiting on.
+#[derive(Debug, Clone)]
+pub struct SuspendedGoal {
+    pub goal: Goal,
+    pub waiting_on: Vec<FactPattern>,
+}
+
 /// High-level cognitive loop driver.
 pub struct CognitiveLoop<E: ActionExecutor, W: WorldStateProvider, L: LearningAdapter> {
     pub state: Arc<Mutex<CognitiveState>>,
     planner: Planner,
+    /// Actions plans are built from, usually loaded from a planning domain
+    actions: Vec<Action>,
     env_executor: E,
     world_provider: W,
     learner: L,
+    preferences: Option<PreferenceModel>,
+    action_costs: SharedCostModel,
+    quotas: Option<SharedQuotaTracker>,
+    suspended: Vec<SuspendedGoal>,
+    knowledge_cursor: ChangeSequence,
+    /// Search effort allowed per cycle before planning yields
+    pub planning_budget: SearchBudget,
+    pending_search: Option<AnytimeSearch>,
+    /// Which actions may run without a person's say-so
+    pub autonomy: AutonomyPolicy,
+    action_beliefs: ActionModelBeliefs,
+    /// Salient word from the latest external stimulus, for curiosity goals
+    last_topic: Option<String>,
+    /// Source of fatigue-induced slips
+    rng: SeededRng,
+    knowledge_gaps: KnowledgeGapDetector,
+    /// Trees run instead of planning, by goal id, for goals planned reactively
+    behavior_trees: std::collections::HashMap<String, BehaviorTree>,
+    /// Chooses between responding now, thinking more, and deferring
+    pub utility: UtilitySelector,
+    /// Goals put off until idle, with the decision that put them off
+    deferred: Vec<(Goal, UtilityDecision)>,
+    /// Macro actions learned from plans that worked, offered to the planner
+    skills: SkillLibrary,
+    /// Categories of goals, for heuristics kept per category
+    categories: GoalCategorizer,
+    /// Strong prediction errors not yet reconciled with the knowledge base
+    prediction_errors: Vec<PredictionError>,
+    /// Features of the most recent episodes, oldest first
+    episodes: Vec<EpisodeFeatures>,
+    /// Traces of finished episodes, with their outcome, not yet written to
+    /// narrative memory, oldest first
+    unwritten: Vec<(ThoughtTrace, bool)>,
+    /// Strategy comparisons scheduled by reflection
+    pub experiments: SelfExperiments,
+    /// Self-training tasks run while idle, if enabled
+    curriculum: Option<Curriculum>,
+    /// Times capability observations; the runtime's clock once attached
+    clock: SharedClock,
 }
 
 impl<E, W, L> CognitiveLoop<E, W, L>
@@ -65,26 +189,255 @@ where
         world_provider: W,
         learner: L,
     ) -> Self {
+        let action_costs = LearnedCostModel::new(std::time::Duration::from_secs(1)).shared();
+        let mut planner = Planner::new();
+        planner.set_cost_model(Box::new(action_costs.clone()));
+
         Self {
             state,
-            planner: Planner::new(),
+            planner,
+            actions: Vec::new(),
             env_executor,
             world_provider,
             learner,
+            preferences: None,
+            action_costs,
+            quotas: None,
+            suspended: Vec::new(),
+            knowledge_cursor: 0,
+            planning_budget: SearchBudget::time(std::time::Duration::from_millis(20)),
+            pending_search: None,
+            autonomy: AutonomyPolicy::default(),
+            action_beliefs: ActionModelBeliefs::new(),
+            last_topic: None,
+            rng: RandomSource::from_entropy().stream(FATIGUE_STREAM),
+            knowledge_gaps: KnowledgeGapDetector::new(),
+            behavior_trees: std::collections::HashMap::new(),
+            utility: UtilitySelector::new(),
+            deferred: Vec::new(),
+            skills: SkillLibrary::new(),
+            categories: GoalCategorizer::new(),
+            prediction_errors: Vec::new(),
+            episodes: Vec::new(),
+            unwritten: Vec::new(),
+            experiments: SelfExperiments::new(),
+            curriculum: None,
+            clock: system_clock(),
         }
     }
 
-    /// Runs a single cognitive cycle reacting to an input stimulus.
-    pub async fn step(&mut self, stimulus: Stimulus) -> Result<()> {
+    /// Reads the time from `clock` from now on, so training progress
+    /// follows a mocked or accelerated clock.
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
+    /// Draws fatigue-induced slips from `source`, so a run with the same
+    /// seed and inputs slips the same way.
+    pub fn set_random_source(&mut self, source: &RandomSource) {
+        self.rng = source.stream(FATIGUE_STREAM);
+    }
+
+    /// Spends idle time practicing on tasks from `curriculum`.
+    pub fn set_curriculum(&mut self, curriculum: Curriculum) {
+        self.curriculum = Some(curriculum);
+    }
+
+    /// Makes goal selection and planning costs follow the given user preferences.
+    pub fn set_preferences(&mut self, preferences: PreferenceModel) {
+        self.preferences = Some(preferences);
+        self.rebuild_cost_model();
+    }
+
+    /// Makes planning costs of actions that consume rate-limited resources
+    /// rise as their quotas run low, and records what executed actions use.
+    pub fn set_quotas(&mut self, quotas: SharedQuotaTracker) {
+        self.quotas = Some(quotas);
+        self.rebuild_cost_model();
+    }
+
+    fn rebuild_cost_model(&mut self) {
+        let mut chain: Vec<Box<dyn ActionCostModel>> = vec![Box::new(self.action_costs.clone())];
+        if let Some(preferences) = &self.preferences {
+            chain.push(Box::new(preferences.clone()));
+        }
+        if let Some(quotas) = &self.quotas {
+            chain.push(Box::new(quotas.clone()));
+        }
+        self.planner.set_cost_model(Box::new(CostModelChain(chain)));
+    }
+
+    /// Replaces the actions plans are built from.
+    pub fn set_actions(&mut self, actions: Vec<Action>) {
+        self.actions = actions;
+        self.pending_search = None;
+    }
+
+    /// Handles the goal `goal_id` with `tree` instead of a plan whenever the
+    /// goal would be planned reactively. Replaces any tree it had.
+    pub fn add_behavior_tree(&mut self, goal_id: impl Into<String>, tree: BehaviorTree) {
+        self.behavior_trees.insert(goal_id.into(), tree);
+    }
+
+    pub fn remove_behavior_tree(&mut self, goal_id: &str) -> Option<BehaviorTree> {
+        self.behavior_trees.remove(goal_id)
+    }
+
+    /// The actions plans are built from.
+    pub fn actions(&self) -> &[Action] {
+        &self.actions
+    }
+
+    /// Skills learned so far and how reliable they have been.
+    pub fn skills(&self) -> &SkillLibrary {
+        &self.skills
+    }
+
+    /// For hosts to tune skill learning or prune unreliable skills.
+    pub fn skills_mut(&mut self) -> &mut SkillLibrary {
+        &mut self.skills
+    }
+
+    /// What concepts goals have been grouped under.
+    pub fn goal_categories(&self) -> &GoalCategorizer {
+        &self.categories
+    }
+
+    /// Learned confidence that actions produce their declared effects.
+    pub fn action_beliefs(&self) -> &ActionModelBeliefs {
+        &self.action_beliefs
+    }
+
+    /// Durations and success rates learned from executed actions.
+    pub fn action_costs(&self) -> &SharedCostModel {
+        &self.action_costs
+    }
+
+    /// Things Astra has noticed it does not know.
+    pub fn knowledge_gaps(&self) -> &KnowledgeGapDetector {
+        &self.knowledge_gaps
+    }
+
+    /// For hosts to log failed queries and low-confidence answers.
+    pub fn knowledge_gaps_mut(&mut self) -> &mut KnowledgeGapDetector {
+        &mut self.knowledge_gaps
+    }
+
+    /// Logs names in an external stimulus that the knowledge base has never
+    /// seen, and returns them.
+    pub fn note_unknown_entities(&mut self, stimulus: &Stimulus, knowledge: &OntologyManager) -> Vec<String> {
+        if stimulus.source == "knowledge" || stimulus.source == SURPRISE_SOURCE {
+            return Vec::new();
+        }
+        self.knowledge_gaps.record_unknown_entities(&stimulus.content, knowledge)
+    }
+
+    /// Goals waiting for knowledge before they can be planned.
+    pub fn suspended_goals(&self) -> &[SuspendedGoal] {
+        &self.suspended
+    }
+
+    /// Features of the most recent episodes, oldest first.
+    pub fn recent_episodes(&self) -> &[EpisodeFeatures] {
+        &self.episodes
+    }
+
+    /// Takes the traces of episodes finished since the last call, with
+    /// whether each succeeded, oldest first, for writing to narrative memory.
+    pub fn take_finished_episodes(&mut self) -> Vec<(ThoughtTrace, bool)> {
+        std::mem::take(&mut self.unwritten)
+    }
+
+    /// Mines recent episodes for situations in which a strategy keeps
+    /// failing and proposes a rule for each to `registry`, skipping rules
+    /// already held or waiting for sign-off. Approved proposals for the
+    /// planning heuristics, including rules the policy adopts on its own,
+    /// are then applied. Returns the ids of the proposals made.
+    pub async fn propose_heuristic_rules(
+        &self,
+        registry: &mut SelfModificationRegistry,
+        params: &MiningParams,
+    ) -> Vec<ProposalId> {
         let mut state = self.state.lock().await;
+        let candidates = reflection::propose_heuristic_rules(&self.episodes, params, &state.heuristics);
+        let mut proposed = Vec::new();
+        for (modification, rationale) in candidates {
+            let Modification::AdoptRule { rule } = &modification else { continue };
+            let awaiting = registry.pending().iter().any(|p| {
+                matches!(&p.modification, Modification::AdoptRule { rule: pending }
+                    if pending.same_situation(rule) && pending.prefer == rule.prefer)
+            });
+            if awaiting {
+                continue;
+            }
+            info!("Proposing heuristic rule: {}", rule.describe());
+            let current = ModificationTargets { heuristics: Some(&mut state.heuristics), ..Default::default() };
+            proposed.push(registry.propose(modification, rationale, &current));
+        }
+        let mut targets = ModificationTargets { heuristics: Some(&mut state.heuristics), ..Default::default() };
+        registry.apply_approved(&mut targets);
+        proposed
+    }
+
+    /// Schedules an experiment for each goal category whose two leading
+    /// strategies are too close to call, unless one is already running for
+    /// it. Returns the ids of the experiments scheduled.
+    pub async fn schedule_experiments(&mut self, params: &ExperimentParams) -> Vec<ExperimentId> {
+        let state = self.state.lock().await;
+        let mut scheduled = Vec::new();
+        for (category, heuristics) in &state.category_heuristics {
+            let Some((a, b, rationale)) = reflection::propose_strategy_experiment(heuristics) else { continue };
+            let scope = Condition::Category(category.clone());
+            if let Ok(id) = self.experiments.schedule(scope, a, b, params.clone()) {
+                info!("Scheduled experiment {} for {} goals: {}", id, category, rationale);
+                scheduled.push(id);
+            }
+        }
+        scheduled
+    }
+
+    /// Applies approved proposals that change the planning heuristics:
+    /// strategy switches and heuristic rules. Returns the ids applied.
+    pub async fn apply_self_modifications(&self, registry: &mut SelfModificationRegistry) -> Vec<ProposalId> {
+        let mut state = self.state.lock().await;
+        let mut targets = ModificationTargets { heuristics: Some(&mut state.heuristics), ..Default::default() };
+        registry.apply_approved(&mut targets)
+    }
+
+    /// Goals put off until idle, oldest first.
+    pub fn deferred_goals(&self) -> impl Iterator<Item = &Goal> {
+        self.deferred.iter().map(|(goal, _)| goal)
+    }
+
+    /// Runs a single cognitive cycle reacting to an input stimulus.
+    pub async fn step(&mut self, stimulus: Stimulus) -> Result<(), CognitionError> {
+        let state_handle = Arc::clone(&self.state);
+        let mut state = state_handle.lock().await;
+
+        let threshold = state.energy.attention_threshold();
+        if stimulus.urgency < threshold {
+            info!(
+                "Ignoring stimulus '{}' under load {:.2} (urgency {:.2} < {:.2})",
+                stimulus.content, state.energy.load, stimulus.urgency, threshold
+            );
+            return Ok(());
+        }
 
         // 1. Update curiosity based on novelty (placeholder heuristic).
         let novelty_score = 0.7; // TODO: derive from learning/perception
         update_curiosity(&mut state, novelty_score);
+        if stimulus.source != "knowledge" && stimulus.source != SURPRISE_SOURCE {
+            state.drives.satisfy(Drive::Relatedness, 0.5);
+            self.last_topic = salient_word(&stimulus.content).or(self.last_topic.take());
+        }
 
         // 2. Goal formation.
         let candidate_goals = generate_goals_from_stimulus(&state, &stimulus);
-        let primary = match select_primary_goal(&state, &candidate_goals) {
+        let selected = match &self.preferences {
+            Some(preferences) => select_primary_goal_with_preferences(&state, &candidate_goals, preferences),
+            None => select_primary_goal(&state, &candidate_goals),
+        };
+        let primary = match selected {
             Some(g) => g,
             None => {
                 info!("No primary goal selected for stimulus '{}'", stimulus.content);
@@ -97,17 +450,310 @@ where
             "Selected goal '{}' with motivation score {:.3}",
             primary.id, motivation_score
         );
+
+        let candidates = moment_candidates(&state, stimulus.urgency, motivation_score);
+        let Some(decision) = self.utility.select(&candidates) else {
+            let reason = format!("Selected goal '{}' based on stimulus '{}'", primary.id, stimulus.content);
+            return self.pursue_goal(&mut state, primary, reason, stimulus.urgency, None);
+        };
+        if decision.chosen == MomentBehavior::Defer {
+            info!("Deferring goal '{}' until idle", primary.id);
+            self.deferred.retain(|(goal, _)| goal.id != primary.id);
+            self.deferred.push((primary, decision));
+            return Ok(());
+        }
+
+        let reason = format!("Selected goal '{}' based on stimulus '{}'", primary.id, stimulus.content);
+        self.pursue_goal(&mut state, primary, reason, stimulus.urgency, Some(&decision))
+    }
+
+    /// Folds the runtime's load signals into cognitive energy.
+    pub async fn observe_load(&self, signals: &LoadSignals) {
+        self.state.lock().await.energy.observe_load(signals);
+    }
+
+    /// Lets drives build up over an idle period of length `elapsed`. A
+    /// deferred goal is taken up first, then a knowledge gap that keeps
+    /// recurring as a learning goal, then a curriculum task once the
+    /// curriculum's interval has passed; otherwise, if a drive becomes
+    /// pressing, pursues the intrinsic goal it gives rise to. Returns the id
+    /// of the goal pursued or task practiced. Nothing is pursued while load
+    /// leaves no room for speculative work.
+    pub async fn idle_step(&mut self, elapsed: std::time::Duration) -> Result<Option<String>, CognitionError> {
+        let state_handle = Arc::clone(&self.state);
+        let mut state = state_handle.lock().await;
+        state.energy.rest(elapsed);
+        let curiosity = state.curiosity_level;
+        state.drives.accumulate(elapsed, curiosity);
+        if let Some(curriculum) = self.curriculum.as_mut() {
+            curriculum.wait(elapsed);
+        }
+        if !state.energy.allows_speculation() {
+            return Ok(None);
+        }
+
+        if !self.deferred.is_empty() {
+            let (goal, decision) = self.deferred.remove(0);
+            let goal_id = goal.id.clone();
+            let reason = format!("Took up deferred goal '{}' while idle", goal_id);
+            self.pursue_goal(&mut state, goal, reason, 0.0, Some(&decision))?;
+            return Ok(Some(goal_id));
+        }
+
+        let learning = learning_goals_from_gaps(&state, &self.knowledge_gaps, GAP_GOAL_MIN_OCCURRENCES);
+        if let Some(goal) = learning.into_iter().next() {
+            if let Some(gap) = self.knowledge_gaps.frequent_gaps(GAP_GOAL_MIN_OCCURRENCES).first() {
+                let topic = gap.topic.clone();
+                self.knowledge_gaps.resolve(&topic);
+            }
+            let goal_id = goal.id.clone();
+            let reason = format!("Chose goal '{}' while idle to fill a recurring knowledge gap", goal_id);
+            self.pursue_goal(&mut state, goal, reason, 0.0, None)?;
+            return Ok(Some(goal_id));
+        }
+
+        if let Some(task_id) = self.practice(&mut state) {
+            return Ok(Some(task_id));
+        }
+
+        let context = IdleContext {
+            weakest_capability: self
+                .action_costs
+                .read()
+                .ok()
+                .and_then(|costs| costs.least_reliable().map(|(id, _)| id.to_string())),
+            topic: self.last_topic.clone(),
+        };
+        let Some(goal) = generate_intrinsic_goal(&state.drives, &context) else {
+            return Ok(None);
+        };
+
+        let goal_id = goal.id.clone();
+        let reason = format!("Chose goal '{}' while idle to meet an unmet drive", goal_id);
+        self.pursue_goal(&mut state, goal, reason, 0.0, None)?;
+        Ok(Some(goal_id))
+    }
+
+    /// Attempts the curriculum's next task, if one is due, and records the
+    /// outcome in the self-model. Returns the task's id.
+    fn practice(&mut self, state: &mut CognitiveState) -> Option<String> {
+        let curriculum = self.curriculum.as_mut().filter(|c| c.is_due())?;
+        let task = curriculum.next_task(&state.capabilities);
+        let started = std::time::Instant::now();
+        let outcome = attempt(&self.planner, &task, curriculum.budget);
+        state.energy.exert(started.elapsed());
+
+        info!(
+            "Practiced {} planning at level {}: {}",
+            outcome.capability,
+            outcome.level,
+            if outcome.success { "succeeded" } else { "failed" }
+        );
+        if outcome.success {
+            state.drives.satisfy(Drive::Competence, 0.3);
+        }
+        if let Some(level) = state.capabilities.observe(outcome.capability, outcome.success, self.clock.now()) {
+            info!("Training moved {} planning to level {}", outcome.capability, level);
+        }
+        Some(outcome.task_id)
+    }
+
+    /// Continues a plan search that yielded in an earlier cycle. Returns
+    /// false when no search was pending.
+    pub async fn continue_planning(&mut self, deadline_pressure: f32) -> Result<bool, CognitionError> {
+        let Some(goal) = self.pending_search.as_ref().map(|search| search.goal().clone()) else {
+            return Ok(false);
+        };
+        let state_handle = Arc::clone(&self.state);
+        let mut state = state_handle.lock().await;
+        let reason = format!("Continued planning for goal '{}'", goal.id);
+        self.pursue_goal(&mut state, goal, reason, deadline_pressure, None)?;
+        Ok(true)
+    }
+
+    /// Reads knowledge changes since the last call. Suspended goals whose
+    /// awaited facts arrived are woken and pursued immediately; other
+    /// high-confidence facts are returned as stimuli for `step`.
+    /// Gaps about a fact's object are considered filled, and "is_a" facts
+    /// are kept for categorizing goals.
+    pub async fn observe_knowledge(&mut self, knowledge: &OntologyManager) -> Result<Vec<Stimulus>, CognitionError> {
+        const SIGNIFICANT_FACT_CONFIDENCE: f32 = 0.8;
+        const WOKEN_GOAL_PRESSURE: f32 = 0.5;
+
+        let mut woken = Vec::new();
+        let mut stimuli = Vec::new();
+        for (_, change) in knowledge.changes_since(self.knowledge_cursor) {
+            let KnowledgeChange::FactAdded { fact, .. } = change else { continue };
+            self.knowledge_gaps.resolve(&fact.object);
+            self.categories.observe(fact);
+
+            let before = woken.len();
+            let mut i = 0;
+            while i < self.suspended.len() {
+                if self.suspended[i].waiting_on.iter().any(|pattern| pattern.matches(fact)) {
+                    woken.push((self.suspended.remove(i).goal, fact.clone()));
+                } else {
+                    i += 1;
+                }
+            }
+
+            if woken.len() == before && fact.confidence >= SIGNIFICANT_FACT_CONFIDENCE {
+                stimuli.push(Stimulus {
+                    source: "knowledge".into(),
+                    content: format!("Learned that {} {} {}", fact.subject, fact.predicate, fact.object),
+                    urgency: fact.confidence * 0.5,
+                });
+            }
+        }
+        self.knowledge_cursor = knowledge.latest_change();
+
+        if !woken.is_empty() {
+            let state_handle = Arc::clone(&self.state);
+            let mut state = state_handle.lock().await;
+            for (goal, fact) in woken {
+                info!("Waking goal '{}' after new fact '{}'", goal.id, fact.predicate);
+                let reason = format!("Resumed goal '{}' after learning {} {}", goal.id, fact.predicate, fact.object);
+                self.pursue_goal(&mut state, goal, reason, WOKEN_GOAL_PRESSURE, None)?;
+            }
+        }
+
+        Ok(stimuli)
+    }
+
+    /// Weakens the beliefs behind predictions the world strongly contradicted
+    /// since the last call, recording each change in `ledger`. The returned
+    /// update carries a surprise stimulus for `step`, if there were any.
+    pub fn reconcile_prediction_errors(
+        &mut self,
+        knowledge: &mut OntologyManager,
+        reasoner: &EpistemicReasoner,
+        ledger: &mut BeliefLedger,
+    ) -> SurpriseUpdate {
+        let errors = std::mem::take(&mut self.prediction_errors);
+        update_beliefs_on_surprise(&errors, knowledge, reasoner, ledger)
+    }
+
+    /// Plans and executes a selected goal. Goals that cannot be planned yet are
+    /// suspended until a fact about one of their desired-state keys arrives.
+    /// Deliberative searches run within `planning_budget`, shrunk by fatigue;
+    /// if the best plan so far is not good enough for the given deadline
+    /// pressure, the search is kept for `continue_planning` and nothing is
+    /// executed this cycle.
+    /// When an action's observed effects diverge from its declared ones, the
+    /// surprise feeds curiosity and the goal is replanned from the observed
+    /// world, a bounded number of times. The time spent adds to fatigue.
+    /// A utility `decision` that led here goes into the thought trace; if it
+    /// was to think more, the search budget is enlarged.
+    fn pursue_goal(
+        &mut self,
+        state: &mut CognitiveState,
+        primary: Goal,
+        reason: String,
+        deadline_pressure: f32,
+        decision: Option<&UtilityDecision>,
+    ) -> Result<(), CognitionError> {
+        let started = std::time::Instant::now();
+        let result = self.plan_and_execute(state, primary, reason, deadline_pressure, decision);
+        state.energy.exert(started.elapsed());
+        result
+    }
+
+    fn plan_and_execute(
+        &mut self,
+        state: &mut CognitiveState,
+        primary: Goal,
+        reason: String,
+        deadline_pressure: f32,
+        decision: Option<&UtilityDecision>,
+    ) -> Result<(), CognitionError> {
+        const MAX_PLAN_SLACK: f32 = 1.0;
+        const MAX_REPLANS: usize = 2;
+
+        let affect_start = AffectSnapshot::of_cognitive_state(state);
         state.context.active_goal = Some(primary.clone());
 
+        let mut strategy = self.planner.strategy_for(&primary);
+        let mut episode = EpisodeFeatures {
+            goal_id: primary.id.clone(),
+            category: self.categories.categorize(&primary),
+            strategy,
+            deadline_pressure,
+            load: state.energy.load,
+            fatigue: state.energy.fatigue,
+            success: false,
+        };
+        let ruled = state.heuristics.rule_against(strategy, &episode.situation()).map(|rule| {
+            strategy = rule.prefer;
+            format!("Rule: {}", rule.describe())
+        });
+        // Goals with a behavior tree are not planned, so they make no trials
+        let trial = if self.behavior_trees.contains_key(&primary.id) {
+            None
+        } else {
+            self.experiments.assign(&episode.situation()).map(|(id, arm)| {
+                strategy = arm;
+                id
+            })
+        };
+
+        if strategy == PlanningStrategy::Reactive {
+            if let Some(mut tree) = self.behavior_trees.remove(&primary.id) {
+                let mut trace = ThoughtTrace::new(&primary.id);
+                trace.add_step(reason, 0.9);
+                if let Some(decision) = decision {
+                    decision.record(&mut trace);
+                }
+                if let Some(ruled) = ruled {
+                    trace.add_step(ruled, 0.7);
+                }
+                let success = self.run_behavior_tree(state, &mut tree, &mut trace);
+                self.behavior_trees.insert(primary.id.clone(), tree);
+                episode.strategy = PlanningStrategy::BehaviorTree;
+                episode.success = success;
+                self.conclude_episode(state, episode, trace, affect_start, None);
+                return Ok(());
+            }
+        }
+
         // 3. Planning.
         let world = self.world_provider.current_world_state();
-        let available_actions = vec![]; // TODO: inject domain actions
-        let plan = self
-            .planner
-            .plan_auto(&world, &primary, &available_actions)?;
+        let mut available_actions = self.actions.clone();
+        available_actions.extend(self.skills.actions());
+        let plan = if strategy == PlanningStrategy::Goap {
+            let mut search = match self.pending_search.take() {
+                Some(search) if search.goal().id == primary.id => search,
+                _ => self.planner.start_anytime(&world, &primary, &available_actions),
+            };
+            let mut scale = state.energy.search_budget_scale();
+            if decision.is_some_and(|d| d.chosen == MomentBehavior::ThinkMore) {
+                scale *= THINK_MORE_BUDGET_SCALE;
+            }
+            let budget = self.planning_budget.scaled(scale);
+            let result = search.refine(budget);
+            if !result.finished && !result.acceptable(deadline_pressure, MAX_PLAN_SLACK) {
+                info!(
+                    "Planning for goal '{}' yielded after {} expansions (suboptimality {:?})",
+                    primary.id,
+                    result.expansions,
+                    result.suboptimality()
+                );
+                self.pending_search = Some(search);
+                return Ok(());
+            }
+            result.plan.unwrap_or(Plan {
+                goal_id: primary.id.clone(),
+                actions: Vec::new(),
+                estimated_cost: f32::INFINITY,
+            })
+        } else {
+            self.planner.plan_with_strategy(strategy, &world, &primary, &available_actions)?
+        };
 
         if plan.actions.is_empty() {
-            warn!("Planner returned empty plan for goal {}", primary.id);
+            warn!("Planner returned empty plan for goal {}; suspending it", primary.id);
+            let waiting_on = primary.desired_state.keys().map(|key| FactPattern::predicate(key.clone())).collect();
+            self.suspended.retain(|s| s.goal.id != primary.id);
+            self.suspended.push(SuspendedGoal { goal: primary, waiting_on });
             return Ok(());
         }
 
@@ -115,32 +761,283 @@ where
 
         // 4. Thought trace.
         let mut trace = ThoughtTrace::new(&primary.id);
-        trace.add_step(
-            format!("Selected goal '{}' based on stimulus '{}'", primary.id, stimulus.content),
-            0.9,
-        );
+        trace.add_step(reason, 0.9);
+        if let Some(decision) = decision {
+            decision.record(&mut trace);
+        }
+        if let Some(ruled) = ruled {
+            trace.add_step(ruled, 0.7);
+        }
+        if let Some(id) = trial {
+            trace.add_step(format!("Experiment {}: planning with {}", id, strategy_to_string(strategy)), 0.7);
+        }
         trace.add_step(
             format!("Generated plan with {} actions", plan.actions.len()),
             0.8,
         );
 
-        // 5. Execution.
-        let mut executor = PlanExecutor::new(plan.clone(), &mut self.env_executor);
-        let status = executor.run_to_completion()?;
-        let success = matches!(status, ExecutionStatus::Completed);
+        // 5. Execution, monitored against each action's expected effects.
+        //    Skills are run as their steps.
+        let mut plan = plan;
+        let mut replans = 0;
+        let success = loop {
+            let steps = self.skills.expand(&plan);
+            let env = FatiguedExecutor::new(&mut self.env_executor, state.energy.error_probability(), &mut self.rng);
+            let mut executor = PlanExecutor::new(steps.plan.clone(), env).with_policy(self.autonomy.clone());
+            let outcome = executor.run_to_completion();
+            let completed = executor.completed_actions();
+            let skill_samples = self.skills.record_execution(&steps, completed, executor.telemetry());
+            if let Ok(mut costs) = self.action_costs.write() {
+                costs.record_all(executor.telemetry());
+                costs.record_all(&skill_samples);
+            }
+            for action in &steps.plan.actions[..completed] {
+                self.action_beliefs.record(action, &[]);
+            }
+            if let Some(Ok(mut quotas)) = self.quotas.as_ref().map(|q| q.write()) {
+                for sample in executor.telemetry() {
+                    if let Some(action) = steps.plan.actions.iter().find(|a| a.id == sample.action_id) {
+                        quotas.record_action(action);
+                    }
+                }
+            }
 
+            match outcome? {
+                ExecutionStatus::Completed => {
+                    for skill in self.skills.observe_success(&steps.plan.actions) {
+                        trace.add_step(format!("Learned skill '{}'", skill), 0.6);
+                    }
+                    break true;
+                }
+                ExecutionStatus::Diverged(violations) => {
+                    let action = &steps.plan.actions[completed];
+                    let predicted: Vec<f32> = violations
+                        .iter()
+                        .map(|v| self.action_beliefs.effect_confidence(&v.action_id, &v.key))
+                        .collect();
+                    self.action_beliefs.record(action, &violations);
+                    for (violation, predicted_confidence) in violations.iter().zip(predicted) {
+                        let error = PredictionError {
+                            violation: violation.clone(),
+                            predicted_confidence,
+                            revised_confidence: self.action_beliefs.effect_confidence(&violation.action_id, &violation.key),
+                        };
+                        if error.is_strong() {
+                            self.prediction_errors.push(error);
+                        }
+                    }
+                    update_curiosity(state, surprise(&violations));
+                    trace.add_step(
+                        format!(
+                            "Action '{}' did not have its expected effects ({} violation(s))",
+                            action.id,
+                            violations.len()
+                        ),
+                        self.action_beliefs.action_confidence(action),
+                    );
+                    if replans == MAX_REPLANS {
+                        break false;
+                    }
+                    replans += 1;
+
+                    let observed = self.world_provider.current_world_state();
+                    plan = self.planner.plan_with_strategy(strategy, &observed, &primary, &available_actions)?;
+                    if plan.actions.is_empty() {
+                        break goal_satisfied(&observed, &primary.desired_state);
+                    }
+                    trace.add_step(format!("Replanned with {} actions", plan.actions.len()), 0.7);
+                    state.context.active_plan = Some(plan.clone());
+                }
+                ExecutionStatus::AwaitingConfirmation { action_id, category } => {
+                    trace.add_step(
+                        format!("Stopped before action '{}': {:?} actions need confirmation", action_id, category),
+                        0.9,
+                    );
+                    break false;
+                }
+                _ => break false,
+            }
+        };
+
+        episode.strategy = strategy;
+        episode.success = success;
+        self.conclude_episode(state, episode, trace, affect_start, trial);
+        Ok(())
+    }
+
+    fn remember_episode(&mut self, episode: EpisodeFeatures, trace: ThoughtTrace, success: bool) {
+        if self.episodes.len() >= EPISODES_KEPT {
+            self.episodes.remove(0);
+        }
+        self.episodes.push(episode);
+        // Without a runtime draining them, only the latest wait
+        if self.unwritten.len() >= EPISODES_KEPT {
+            self.unwritten.remove(0);
+        }
+        self.unwritten.push((trace, success));
+    }
+
+    /// Ticks a goal's behavior tree against the observed world until it
+    /// finishes, or for `MAX_TREE_TICKS` ticks, after which the run is
+    /// abandoned as a failure.
+    fn run_behavior_tree(&mut self, state: &CognitiveState, tree: &mut BehaviorTree, trace: &mut ThoughtTrace) -> bool {
+        trace.add_step("Ran the goal's behavior tree instead of planning", 0.8);
+        let mut env = FatiguedExecutor::new(&mut self.env_executor, state.energy.error_probability(), &mut self.rng);
+        tree.set_policy(self.autonomy.clone());
+        let mut status = Status::Running;
+        for _ in 0..MAX_TREE_TICKS {
+            let world = self.world_provider.current_world_state();
+            status = tree.tick(&world, &mut env);
+            if status != Status::Running {
+                break;
+            }
+        }
+        if status == Status::Running {
+            tree.reset();
+            trace.add_step(format!("Behavior tree still running after {} ticks; gave up", MAX_TREE_TICKS), 0.7);
+        }
+        status == Status::Success
+    }
+
+    /// Settles energy and drives after an episode, scores the strategy it
+    /// used for the goal's category, and records it in memory and with the learner.
+    fn conclude_episode(
+        &mut self,
+        state: &mut CognitiveState,
+        episode: EpisodeFeatures,
+        mut trace: ThoughtTrace,
+        affect_start: AffectSnapshot,
+        trial: Option<ExperimentId>,
+    ) {
+        let (strategy, success) = (episode.strategy, episode.success);
         update_energy_after_outcome(&mut state.energy, success);
+        if success {
+            state.drives.satisfy(Drive::Competence, 0.3);
+            if let Some(drive) = Drive::of_goal(&episode.goal_id) {
+                state.drives.satisfy(drive, 0.6);
+                state.drives.satisfy(Drive::Autonomy, 0.2);
+            }
+        }
+        let category = episode.category.clone();
+        // A single trial says little; only the experiment's result counts
+        match trial {
+            Some(id) => self.record_trial(state, &mut trace, id, strategy, success),
+            None => reinforce_strategy(state, category.as_deref(), strategy, success),
+        }
+        match &category {
+            Some(category) => trace.add_step(format!("Strategy: {} for a {} goal", strategy_to_string(strategy), category), 0.5),
+            None => trace.add_step(format!("Strategy: {}", strategy_to_string(strategy)), 0.5),
+        }
 
         // 6. Self-summary (for logging / introspection).
-        let summary = build_self_summary(&state);
+        let summary = build_self_summary(state);
         info!("Self-summary: {}", summary.explanation);
 
-        // 7. Write episode + thought trace to Narrative Memory.
-        record_episode(&state, &trace, success);
+        // 7. Hold the episode + thought trace for the runtime's narrative memory.
+        trace.affect = Some(EpisodeAffect::new(affect_start, AffectSnapshot::of_cognitive_state(state)));
 
         // 8. Learning adapter hook.
-        self.learner.update_from_episode(&state, &trace, success);
+        self.learner.update_from_episode(state, &trace, success);
+        self.remember_episode(episode, trace, success);
+    }
 
-        Ok(())
+    /// Records the outcome of a trial in experiment `id`, committing the
+    /// winner if this trial concluded it.
+    fn record_trial(
+        &mut self,
+        state: &mut CognitiveState,
+        trace: &mut ThoughtTrace,
+        id: ExperimentId,
+        strategy: PlanningStrategy,
+        success: bool,
+    ) {
+        let experiment = match self.experiments.record(id, strategy, success) {
+            Ok(Some(experiment)) => experiment.clone(),
+            Ok(None) => return,
+            Err(e) => {
+                warn!("Could not record trial: {}", e);
+                return;
+            }
+        };
+        match experiment.status {
+            ExperimentStatus::Won { winner, p_value } => {
+                commit_winner(state, &experiment);
+                info!("Experiment {} won by {:?} (p = {:.3}) when {}", id, winner, p_value, experiment.scope);
+                trace.add_step(
+                    format!("Experiment {} settled on {} (p = {:.3})", id, strategy_to_string(winner), p_value),
+                    0.8,
+                );
+            }
+            ExperimentStatus::Inconclusive { p_value } => {
+                info!("Experiment {} was inconclusive (p = {:.3}); nothing changes", id, p_value);
+                trace.add_step(format!("Experiment {} was inconclusive", id), 0.5);
+            }
+            ExperimentStatus::Running => {}
+        }
+    }
+}
+
+/// What responding now, thinking more, and deferring each have going for
+/// them, given how urgent the stimulus is, how motivated Astra is by the
+/// goal, and how tired and loaded it is. How well each serves Astra's
+/// values depends on the moment too: keeping someone waiting is less kind
+/// the more urgent they are, a tired quick answer is more likely wrong, and
+/// resting matters more the more tired Astra is.
+fn moment_candidates(state: &CognitiveState, urgency: f32, motivation: f32) -> [UtilityCandidate; 3] {
+    let serves = |values: [(&str, f32); 2]| values.into_iter().map(|(v, s)| (v.to_string(), s.clamp(0.0, 1.0))).collect();
+    let fatigue = state.energy.fatigue;
+    [
+        UtilityCandidate {
+            behavior: MomentBehavior::RespondNow,
+            urgency,
+            motivation,
+            serves: serves([("compassion", 0.5 + 0.5 * urgency), ("integrity", 0.6 - 0.4 * fatigue)]),
+            cost: fatigue,
+        },
+        UtilityCandidate {
+            behavior: MomentBehavior::ThinkMore,
+            urgency: 1.0 - urgency,
+            motivation,
+            serves: serves([("integrity", 0.8), ("compassion", 0.7 - 0.4 * urgency)]),
+            cost: (state.energy.load + fatigue) / 2.0,
+        },
+        UtilityCandidate {
+            behavior: MomentBehavior::Defer,
+            urgency: (1.0 - urgency) * 0.5,
+            motivation: 1.0 - motivation,
+            serves: serves([("sustainability", 0.5 + 0.5 * fatigue), ("compassion", 0.5 - 0.4 * urgency)]),
+            cost: 0.1,
+        },
+    ]
+}
+
+/// Longest word of a message, as a rough guess at its topic.
+fn salient_word(text: &str) -> Option<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() > 4)
+        .max_by_key(|w| w.len())
+        .map(str::to_lowercase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cognition::utility::{Scorer, ValueAlignmentScorer};
+    use crate::emotion::ValueModel;
+
+    #[test]
+    fn test_value_alignment_follows_the_moment() {
+        let scorer = ValueAlignmentScorer { values: ValueModel::new() };
+        let alignment = |state: &CognitiveState, urgency: f32| moment_candidates(state, urgency, 0.5).map(|c| scorer.score(&c));
+        let rested = CognitiveState::new();
+        let [calm_respond, calm_think, rested_defer] = alignment(&rested, 0.0);
+        let [urgent_respond, urgent_think, _] = alignment(&rested, 1.0);
+        assert!(calm_think > calm_respond, "with time to spare, thinking is the honest choice");
+        assert!(urgent_respond > urgent_think, "someone urgent should not be kept waiting");
+
+        let mut tired = CognitiveState::new();
+        tired.energy.fatigue = 1.0;
+        let [_, _, tired_defer] = alignment(&tired, 0.0);
+        assert!(tired_defer > rested_defer);
     }
 }
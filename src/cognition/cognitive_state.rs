@@ -13,12 +13,18 @@
 //       • Represent current goals, active plans, and execution context
 //       • Maintain personality, emotion, and mood influences
 //       • Track planning heuristics and reflection-derived meta-parameters
-//       • Provide a serializable snapshot of Astra’s internal mind state
+//       • Save and load a snapshot of Astra’s internal mind state, sealed
+//         with a checksum and versioned so snapshots of earlier releases,
+//         their personality included, migrate on load
+//       • Hold the levels of Astra’s intrinsic drives
+//       • Keep heuristics per goal category, falling back to global ones
+//       • Hold heuristic rules adopted from mined failure patterns
+//       • Hold the self-model's record of trained planning capabilities
 //
 //   File:        /src/cognition/cognitive_state.rs
 //   Author:      Alex Roussinov
 //   Created:     2026-01-11
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
@@ -26,11 +32,22 @@
 // ============================================================================
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
 use std::time::Instant;
 
 use crate::planning::planner::{Goal, Plan, PlanningStrategy};
 use crate::personality::personality::{Personality, PersonalityTraits};
 use crate::personality::emotion::{EmotionDynamics, EmotionState, Mood};
+use crate::cognition::motivation::DriveState;
+use crate::cognition::pattern_mining::{Condition, HeuristicRule};
+use crate::cognition::self_model::CapabilityProfile;
+use crate::cognition::error::CognitionError;
+use crate::integrity::Generations;
+use crate::migration::{self, Versioned};
+
+/// Saved snapshots kept, the newest included.
+const SAVED_GENERATIONS: usize = 2;
 
 /// High-level cognitive heuristics influenced by reflection and meta-learning.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +56,57 @@ pub struct PlanningHeuristics {
     pub goap_bias: f32,
     pub htn_bias: f32,
     pub reactive_bias: f32,
+    #[serde(default = "default_bt_bias")]
+    pub bt_bias: f32,
+    /// Exceptions to the strategy a goal would be planned with, adopted
+    /// from failure patterns reflection mined
+    #[serde(default)]
+    pub rules: Vec<HeuristicRule>,
+}
+
+fn default_bt_bias() -> f32 {
+    0.1
+}
+
+impl PlanningHeuristics {
+    pub fn bias(&self, strategy: PlanningStrategy) -> f32 {
+        match strategy {
+            PlanningStrategy::Goap => self.goap_bias,
+            PlanningStrategy::Htn => self.htn_bias,
+            PlanningStrategy::Reactive => self.reactive_bias,
+            PlanningStrategy::BehaviorTree => self.bt_bias,
+        }
+    }
+
+    pub fn bias_mut(&mut self, strategy: PlanningStrategy) -> &mut f32 {
+        match strategy {
+            PlanningStrategy::Goap => &mut self.goap_bias,
+            PlanningStrategy::Htn => &mut self.htn_bias,
+            PlanningStrategy::Reactive => &mut self.reactive_bias,
+            PlanningStrategy::BehaviorTree => &mut self.bt_bias,
+        }
+    }
+
+    /// The most specific adopted rule against planning with `strategy` in
+    /// `situation`, if any.
+    pub fn rule_against(&self, strategy: PlanningStrategy, situation: &[Condition]) -> Option<&HeuristicRule> {
+        self.rules.iter().filter(|rule| rule.applies(strategy, situation)).max_by_key(|rule| rule.when.len())
+    }
+
+    /// Adopts `rule`, replacing any rule for the same situation and strategy.
+    pub fn adopt_rule(&mut self, rule: HeuristicRule) {
+        self.rules.retain(|held| !held.same_situation(&rule));
+        self.rules.push(rule);
+    }
+
+    /// Strategy with the highest bias; the earliest listed wins ties.
+    pub fn leading_strategy(&self) -> PlanningStrategy {
+        [PlanningStrategy::Goap, PlanningStrategy::Htn, PlanningStrategy::Reactive, PlanningStrategy::BehaviorTree]
+            .into_iter()
+            .rev()
+            .max_by(|a, b| self.bias(*a).total_cmp(&self.bias(*b)))
+            .unwrap_or(self.preferred_strategy)
+    }
 }
 
 impl Default for PlanningHeuristics {
@@ -48,6 +116,8 @@ impl Default for PlanningHeuristics {
             goap_bias: 0.6,
             htn_bias: 0.3,
             reactive_bias: 0.1,
+            bt_bias: default_bt_bias(),
+            rules: Vec::new(),
         }
     }
 }
@@ -77,22 +147,37 @@ pub struct CognitiveContext {
     pub active_plan: Option<Plan>,
 
     // Instant cannot be serialized; skip it.
-    #[serde(skip)]
+    #[serde(skip, default = "Instant::now")]
     pub last_update: Instant,
 }
 
 /// Global cognitive state snapshot for Astra.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CognitiveState {
+    /// Versioned on its own, so its migrations run inside the state's
+    #[serde(with = "crate::migration::versioned")]
     pub personality: Personality,
     pub personality_traits: PersonalityTraits,
     pub emotion: EmotionState,
     pub mood: Mood,
     pub energy: CognitiveEnergy,
     pub heuristics: PlanningHeuristics,
+    /// Heuristics learned separately for each goal category
+    #[serde(default)]
+    pub category_heuristics: HashMap<String, PlanningHeuristics>,
     pub context: CognitiveContext,
     pub curiosity_level: f32,
     pub motivation_level: f32,
+    #[serde(default)]
+    pub drives: DriveState,
+    /// Levels reached in each planning capability by self-training
+    #[serde(default)]
+    pub capabilities: CapabilityProfile,
+}
+
+impl Versioned for CognitiveState {
+    const FORMAT: &'static str = "astra.cognitive_state";
+    const VERSION: u32 = 1;
 }
 
 impl CognitiveState {
@@ -109,6 +194,7 @@ impl CognitiveState {
             mood: dynamics.mood.clone(),
             energy: CognitiveEnergy::baseline(),
             heuristics: PlanningHeuristics::default(),
+            category_heuristics: HashMap::new(),
             context: CognitiveContext {
                 active_goal: None,
                 active_plan: None,
@@ -116,11 +202,69 @@ impl CognitiveState {
             },
             curiosity_level: 0.5,
             motivation_level: 0.7,
+            drives: DriveState::default(),
+            capabilities: CapabilityProfile::new(),
         }
     }
 
+    /// Heuristics for goals of `category`, or the global ones for goals
+    /// without a category or of one not seen before.
+    pub fn heuristics_for(&self, category: Option<&str>) -> &PlanningHeuristics {
+        category.and_then(|c| self.category_heuristics.get(c)).unwrap_or(&self.heuristics)
+    }
+
+    /// Heuristics for goals of `category`, starting from the global ones
+    /// the first time the category is seen.
+    pub fn heuristics_for_mut(&mut self, category: &str) -> &mut PlanningHeuristics {
+        self.category_heuristics
+            .entry(category.to_string())
+            .or_insert_with(|| self.heuristics.clone())
+    }
+
     /// Updates internal timestamps and can be used to drive decay models.
     pub fn touch(&mut self) {
         self.context.last_update = Instant::now();
     }
+
+    /// Saves the state sealed with a checksum, keeping the previously saved
+    /// state beside it as `<path>.1`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CognitionError> {
+        Generations::new(path.as_ref(), SAVED_GENERATIONS).write(&migration::encode(self)?)?;
+        Ok(())
+    }
+
+    /// Loads a state saved with `save` by this or an earlier release, or
+    /// the one saved before it if the latest is corrupt; states passed over
+    /// are logged.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CognitionError> {
+        let recovered = Generations::new(path.as_ref(), SAVED_GENERATIONS).load(migration::decode::<Self>)?;
+        recovered.warn_skipped();
+        Ok(recovered.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saved_state_loads_with_its_personality() {
+        let dir = std::env::temp_dir().join(format!("astra-cognitive-state-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("state.json");
+
+        let mut state = CognitiveState::new();
+        state.personality.traits.adjust_trait("openness", -0.3);
+        state.curiosity_level = 0.9;
+        state.save(&path).unwrap();
+        let loaded = CognitiveState::load(&path).unwrap();
+        assert_eq!(loaded.personality.traits.openness, state.personality.traits.openness);
+        assert_eq!(loaded.curiosity_level, 0.9);
+
+        let json = String::from_utf8_lossy(&std::fs::read(&path).unwrap()).into_owned();
+        assert!(json.contains(r#""format":"astra.cognitive_state""#));
+        assert!(json.contains(r#""format":"astra.personality""#));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
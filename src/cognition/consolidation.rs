@@ -22,9 +22,9 @@
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
-use anyhow::Result;
 use log::info;
 
+use crate::cognition::error::CognitionError;
 use crate::cognition::CognitiveState;
 
 /// Runs a single consolidation pass over Astra’s memories.
@@ -32,7 +32,7 @@ use crate::cognition::CognitiveState;
 //  • Query NarrativeMemory for recent events
 //  • Identify stable patterns and recurrent themes
 //  • Adjust traits, heuristics, and mood baselines
-pub fn run_consolidation_cycle(state: &mut CognitiveState) -> Result<()> {
+pub fn run_consolidation_cycle(state: &mut CognitiveState) -> Result<(), CognitionError> {
     // Example: small drift toward emotional stability after consolidation.
     state.mood.baseline = (state.mood.baseline * 0.95 + 0.5 * 0.05).clamp(0.0, 1.0);
 
@@ -0,0 +1,325 @@
+// ============================================================================
+//                       ASTRA AGI • SELF-TRAINING CURRICULUM
+//        Synthetic Planning Tasks Aimed at the Weakest Capability
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Gives idle time a use: practice. The curriculum generates synthetic
+//       planning tasks for whichever planning capability the self-model
+//       ranks weakest, at the level training has reached in it, and runs
+//       them in a simulated world where nothing real is touched. Each level
+//       makes the task harder along the capability's own axis: longer
+//       chains, more distracting actions, more parts to the goal, or longer
+//       detours around failing actions. Outcomes go back to the self-model,
+//       which moves a capability up a level after a run of successes and
+//       down after a run of failures, and keeps when each level was reached.
+//
+//   Core Functions:
+//       • Generate tasks for a capability at a level, from a seeded stream
+//       • Pick the next task for the self-model's weakest capability
+//       • Space tasks out over idle time
+//       • Attempt a task in a simulated world under a search budget,
+//         replanning around actions that fail
+//
+//   File:        /src/cognition/curriculum.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use std::time::Duration;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::cognition::self_model::{CapabilityProfile, PlanningCapability};
+use crate::planning::anytime::SearchBudget;
+use crate::planning::executor::{ExecutionStatus, PlanExecutor};
+use crate::planning::planner::{goal_satisfied, Action, Goal, Planner, WorldState};
+use crate::planning::simulation::SimulatedEnvironment;
+use crate::random::{RandomSource, SeededRng};
+
+/// Stream of the runtime's randomness that tasks are generated from.
+pub const CURRICULUM_STREAM: &str = "curriculum";
+/// Failed actions a task may be replanned around.
+const MAX_RECOVERIES: usize = 4;
+
+/// A generated planning problem and the world it is attempted in.
+#[derive(Debug, Clone)]
+pub struct SyntheticTask {
+    pub capability: PlanningCapability,
+    pub level: u32,
+    pub goal: Goal,
+    pub start: WorldState,
+    pub actions: Vec<Action>,
+    /// Actions that fail whenever they are run
+    pub unreliable: Vec<String>,
+}
+
+impl SyntheticTask {
+    /// A fresh simulated world at the task's start, in which its unreliable
+    /// actions fail.
+    pub fn environment(&self) -> SimulatedEnvironment {
+        let environment = SimulatedEnvironment::new();
+        environment.set(&self.start);
+        for action_id in &self.unreliable {
+            environment.fail_action(action_id);
+        }
+        environment
+    }
+}
+
+/// How an attempt at a task went.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskOutcome {
+    pub task_id: String,
+    pub capability: PlanningCapability,
+    pub level: u32,
+    pub success: bool,
+    /// Times the task was replanned around a failed action
+    pub recoveries: usize,
+    /// Search nodes expanded over every plan
+    pub expansions: usize,
+}
+
+/// Generates and spaces out self-training tasks.
+pub struct Curriculum {
+    rng: SeededRng,
+    /// Idle time between tasks
+    pub interval: Duration,
+    /// Search effort each plan for a task may take
+    pub budget: SearchBudget,
+    idle: Duration,
+    generated: u64,
+}
+
+impl Curriculum {
+    pub fn new(source: &RandomSource) -> Self {
+        Curriculum {
+            rng: source.stream(CURRICULUM_STREAM),
+            interval: Duration::from_secs(300),
+            budget: SearchBudget::expansions(5_000),
+            idle: Duration::ZERO,
+            generated: 0,
+        }
+    }
+
+    /// Counts idle time towards the next task.
+    pub fn wait(&mut self, elapsed: Duration) {
+        self.idle += elapsed;
+    }
+
+    pub fn is_due(&self) -> bool {
+        self.idle >= self.interval
+    }
+
+    /// A task for the weakest capability in `profile`, at its level. Starts
+    /// the wait for the task after it.
+    pub fn next_task(&mut self, profile: &CapabilityProfile) -> SyntheticTask {
+        self.idle = Duration::ZERO;
+        let capability = profile.weakest();
+        self.generate(capability, profile.level(capability))
+    }
+
+    /// A task exercising `capability` at `level`, from 1:
+    /// - long horizon: a single chain of 1 + 2 × level steps
+    /// - distractors: a chain of 3 steps among 3 × level actions leading
+    ///   nowhere useful
+    /// - conjunctive: 1 + level separate chains, all of whose ends are wanted
+    /// - recovery: a chain whose cheapest steps fail, every one with a
+    ///   detour of level + 1 steps around it
+    pub fn generate(&mut self, capability: PlanningCapability, level: u32) -> SyntheticTask {
+        self.generated += 1;
+        let id = format!("curriculum_{}_{}_{}", capability, level, self.generated);
+        let level = level.max(1);
+        let n = level as usize;
+        let mut builder = TaskBuilder::new(&id);
+        let start = builder.key();
+        let mut unreliable = Vec::new();
+
+        let wanted = match capability {
+            PlanningCapability::LongHorizon => vec![builder.chain(&start, 1 + 2 * n)],
+            PlanningCapability::Distractors => {
+                let end = builder.chain(&start, 3);
+                let mut reachable = vec![start.clone()];
+                for _ in 0..3 * n {
+                    let from = reachable.choose(&mut self.rng).cloned().unwrap_or_else(|| start.clone());
+                    let to = builder.key();
+                    builder.step(&from, &to, self.rng.gen_range(0.5..1.5));
+                    reachable.push(to);
+                }
+                vec![end]
+            }
+            PlanningCapability::Conjunctive => (0..=n).map(|_| builder.chain(&start, 2)).collect(),
+            PlanningCapability::Recovery => {
+                let mut from = start.clone();
+                for _ in 0..(1 + (n - 1) / 2).min(MAX_RECOVERIES) {
+                    let to = builder.key();
+                    unreliable.push(builder.step(&from, &to, 1.0));
+                    builder.chain_to(&from, n + 1, &to);
+                    from = to;
+                }
+                vec![from]
+            }
+        };
+
+        let mut actions = builder.actions;
+        actions.shuffle(&mut self.rng);
+        SyntheticTask {
+            capability,
+            level,
+            goal: Goal {
+                description: format!("Practice {} planning at level {}", capability, level),
+                desired_state: wanted.into_iter().map(|key| (key, true)).collect(),
+                priority: 5,
+                id,
+            },
+            start: WorldState::from([(start, true)]),
+            actions,
+            unreliable,
+        }
+    }
+}
+
+/// Names keys and actions for one task, so no two tasks share any.
+struct TaskBuilder {
+    prefix: String,
+    keys: usize,
+    actions: Vec<Action>,
+}
+
+impl TaskBuilder {
+    fn new(prefix: &str) -> Self {
+        TaskBuilder { prefix: prefix.to_string(), keys: 0, actions: Vec::new() }
+    }
+
+    fn key(&mut self) -> String {
+        self.keys += 1;
+        format!("{}_k{}", self.prefix, self.keys)
+    }
+
+    /// Adds an action turning `to` on once `from` is; returns its id.
+    fn step(&mut self, from: &str, to: &str, cost: f32) -> String {
+        let id = format!("{}_a{}", self.prefix, self.actions.len() + 1);
+        self.actions.push(Action {
+            id: id.clone(),
+            description: format!("Reach {} from {}", to, from),
+            preconditions: WorldState::from([(from.to_string(), true)]),
+            effects: WorldState::from([(to.to_string(), true)]),
+            cost,
+            risk: 0.0,
+        });
+        id
+    }
+
+    /// Adds `len` unit-cost steps from `from` to a new key; returns the key.
+    fn chain(&mut self, from: &str, len: usize) -> String {
+        let to = self.key();
+        self.chain_to(from, len, &to);
+        to
+    }
+
+    /// Adds `len` unit-cost steps from `from` ending at `to`.
+    fn chain_to(&mut self, from: &str, len: usize, to: &str) {
+        let mut at = from.to_string();
+        for _ in 1..len {
+            let next = self.key();
+            self.step(&at, &next, 1.0);
+            at = next;
+        }
+        self.step(&at, to, 1.0);
+    }
+}
+
+/// Attempts `task` in its simulated world, planning within `budget` each
+/// time. When an action fails, the task is replanned without it, up to
+/// `MAX_RECOVERIES` times. Synthetic actions touch nothing real, so the
+/// autonomy policy is not consulted.
+pub fn attempt(planner: &Planner, task: &SyntheticTask, budget: SearchBudget) -> TaskOutcome {
+    let environment = task.environment();
+    let mut actions = task.actions.clone();
+    let mut recoveries = 0;
+    let mut expansions = 0;
+
+    let success = loop {
+        let world = environment.world();
+        if goal_satisfied(&world, &task.goal.desired_state) {
+            break true;
+        }
+        let result = planner.start_anytime(&world, &task.goal, &actions).refine(budget);
+        expansions += result.expansions;
+        let Some(plan) = result.plan else { break false };
+
+        let mut executor = PlanExecutor::new(plan, environment.clone());
+        match executor.run_to_completion() {
+            Ok(ExecutionStatus::Completed) => break goal_satisfied(&environment.world(), &task.goal.desired_state),
+            Ok(ExecutionStatus::Failed(_)) if recoveries < MAX_RECOVERIES => {
+                let failed = executor.plan().actions[executor.completed_actions()].id.clone();
+                actions.retain(|action| action.id != failed);
+                recoveries += 1;
+            }
+            _ => break false,
+        }
+    };
+
+    TaskOutcome {
+        task_id: task.goal.id.clone(),
+        capability: task.capability,
+        level: task.level,
+        success,
+        recoveries,
+        expansions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::AstraTime;
+
+    #[test]
+    fn test_tasks_get_harder_and_training_tracks_progress() {
+        let mut curriculum = Curriculum::new(&RandomSource::from_seed(7));
+        let planner = Planner::new();
+
+        for capability in PlanningCapability::ALL {
+            let easy = curriculum.generate(capability, 1);
+            let hard = curriculum.generate(capability, 4);
+            assert!(hard.actions.len() > easy.actions.len(), "{} did not get harder", capability);
+            assert!(attempt(&planner, &easy, curriculum.budget).success, "{} failed at level 1", capability);
+        }
+        let recovery = curriculum.generate(PlanningCapability::Recovery, 1);
+        assert_eq!(attempt(&planner, &recovery, curriculum.budget).recoveries, 1);
+        // Too little search for a long chain
+        let long = curriculum.generate(PlanningCapability::LongHorizon, 5);
+        assert!(!attempt(&planner, &long, SearchBudget::expansions(5)).success);
+
+        // Training turns to each capability in turn while all are at the
+        // same level, moving each up after a run of successes
+        let mut profile = CapabilityProfile::new();
+        curriculum.interval = Duration::from_secs(60);
+        curriculum.wait(Duration::from_secs(30));
+        assert!(!curriculum.is_due());
+        curriculum.wait(Duration::from_secs(30));
+        assert!(curriculum.is_due());
+        let mut practiced = Vec::new();
+        let mut levels = Vec::new();
+        for minute in 0..12 {
+            let task = curriculum.next_task(&profile);
+            practiced.push(task.capability);
+            let outcome = attempt(&planner, &task, curriculum.budget);
+            levels.extend(profile.observe(task.capability, outcome.success, AstraTime::from_secs(60 * minute)));
+        }
+        assert!(!curriculum.is_due());
+        assert_eq!(practiced[..4], PlanningCapability::ALL);
+        assert_eq!(levels, [2, 2, 2, 2]);
+        assert_eq!(profile.milestones(PlanningCapability::LongHorizon), [(AstraTime::from_secs(300), 2)]);
+
+        // And down after a run of failures
+        profile.observe(PlanningCapability::LongHorizon, false, AstraTime::from_secs(180));
+        assert_eq!(profile.observe(PlanningCapability::LongHorizon, false, AstraTime::from_secs(240)), Some(1));
+    }
+}
@@ -0,0 +1,56 @@
+// ============================================================================
+//                         ASTRA AGI • COGNITION ERRORS
+//        Typed Failures of the Cognitive Loop & Self-Modification
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       The error type of the cognition subsystem. Decisions on unknown or
+//       already settled self-modification proposals are told apart so the
+//       API can answer them differently, and planning failures inside the
+//       cognitive loop are passed through with their cause intact.
+//
+//   Core Functions:
+//       • Name the ways proposal decisions fail
+//       • Name the ways strategy experiments are misused
+//       • Wrap planning errors raised while pursuing goals
+//       • Wrap failures to save or load cognitive state
+//
+//   File:        /src/cognition/error.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use thiserror::Error;
+
+use crate::cognition::experiments::ExperimentId;
+use crate::integrity::IntegrityError;
+use crate::cognition::self_modification::{ProposalId, ProposalStatus};
+use crate::planning::error::PlanningError;
+use crate::planning::planner::PlanningStrategy;
+
+#[derive(Debug, Error)]
+pub enum CognitionError {
+    #[error("Proposal {0} not found")]
+    ProposalNotFound(ProposalId),
+    #[error("Proposal {id} is already {status:?}")]
+    ProposalAlreadyDecided { id: ProposalId, status: ProposalStatus },
+    #[error("An experiment needs two different strategies, not {0:?} twice")]
+    ExperimentNeedsTwoStrategies(PlanningStrategy),
+    #[error("Already experimenting when {0}")]
+    ExperimentAlreadyRunning(String),
+    #[error("Experiment {0} is not running")]
+    ExperimentNotRunning(ExperimentId),
+    #[error("Experiment {id} does not compare {strategy:?}")]
+    StrategyNotInExperiment { id: ExperimentId, strategy: PlanningStrategy },
+    #[error(transparent)]
+    Planning(#[from] PlanningError),
+    #[error("Could not serialize cognitive state")]
+    Serialization(#[from] serde_json::Error),
+    /// No saved state could be loaded, or the state could not be saved
+    #[error(transparent)]
+    Integrity(#[from] IntegrityError),
+}
@@ -0,0 +1,342 @@
+// ============================================================================
+//                      ASTRA AGI • STRATEGY SELF-EXPERIMENTS
+//        Controlled Comparisons of Planning Strategies with Significance Tests
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Lets reflection settle which of two planning strategies suits a kind
+//       of goal by experiment rather than by nudging biases after every
+//       episode. An experiment is scoped to goals matching one condition,
+//       usually a goal category; while it runs, matched goals alternate
+//       between the two strategies so each arm sees comparable goals, and
+//       their outcomes are kept out of ordinary reinforcement. Once both
+//       arms have enough trials, Fisher's exact test decides whether their
+//       success rates differ; only then is the winner committed. An
+//       experiment that runs out of trials without a significant difference
+//       ends inconclusive and changes nothing.
+//
+//   Core Functions:
+//       • Schedule experiments comparing two strategies on matched goals
+//       • Alternate the strategies across the goals an experiment covers
+//       • Test the difference in success rates for significance
+//       • Conclude with a winner, or inconclusive, and keep the record
+//       • Commit a winner as a rule and, for a category, its preferred
+//         strategy
+//
+//   File:        /src/cognition/experiments.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+
+use crate::cognition::error::CognitionError;
+use crate::cognition::pattern_mining::{Condition, HeuristicRule};
+use crate::cognition::CognitiveState;
+use crate::planning::planner::PlanningStrategy;
+
+pub type ExperimentId = u64;
+
+/// When an experiment may conclude.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExperimentParams {
+    /// Trials each arm needs before the difference is tested
+    pub min_trials_per_arm: usize,
+    /// Trials each arm gets before the experiment ends inconclusive
+    pub max_trials_per_arm: usize,
+    /// p-value below which the difference counts as real
+    pub significance: f64,
+}
+
+impl Default for ExperimentParams {
+    fn default() -> Self {
+        ExperimentParams { min_trials_per_arm: 5, max_trials_per_arm: 30, significance: 0.05 }
+    }
+}
+
+/// Outcomes of the goals planned with one strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Arm {
+    pub strategy: PlanningStrategy,
+    pub trials: usize,
+    pub successes: usize,
+}
+
+impl Arm {
+    fn new(strategy: PlanningStrategy) -> Self {
+        Arm { strategy, trials: 0, successes: 0 }
+    }
+
+    pub fn success_rate(&self) -> f64 {
+        self.successes as f64 / self.trials.max(1) as f64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ExperimentStatus {
+    Running,
+    /// The arms differed significantly
+    Won { winner: PlanningStrategy, p_value: f64 },
+    /// Out of trials without a significant difference
+    Inconclusive { p_value: f64 },
+}
+
+/// A comparison of two strategies on the goals matching `scope`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StrategyExperiment {
+    pub id: ExperimentId,
+    pub scope: Condition,
+    pub arms: [Arm; 2],
+    pub params: ExperimentParams,
+    pub status: ExperimentStatus,
+}
+
+impl StrategyExperiment {
+    /// The strategy the next matched goal is planned with: whichever arm
+    /// has had fewer trials, the first on a tie.
+    pub fn next_strategy(&self) -> PlanningStrategy {
+        let [a, b] = &self.arms;
+        if b.trials < a.trials {
+            b.strategy
+        } else {
+            a.strategy
+        }
+    }
+
+    /// Two-sided p-value of the difference between the arms so far.
+    pub fn p_value(&self) -> f64 {
+        let [a, b] = &self.arms;
+        fisher_exact(a.successes, a.trials - a.successes, b.successes, b.trials - b.successes)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.status == ExperimentStatus::Running
+    }
+
+    /// The rule committing a won experiment's result: within its scope,
+    /// plan with the winner instead of the loser.
+    pub fn winning_rule(&self) -> Option<HeuristicRule> {
+        let ExperimentStatus::Won { winner, .. } = self.status else {
+            return None;
+        };
+        let loser = self.arms.iter().find(|arm| arm.strategy != winner)?;
+        Some(HeuristicRule {
+            when: vec![self.scope.clone()],
+            avoid: loser.strategy,
+            prefer: winner,
+            confidence: 1.0 - loser.success_rate() as f32,
+            support: self.arms.iter().map(|arm| arm.trials).sum(),
+        })
+    }
+
+    fn conclude_if_ready(&mut self) {
+        let [a, b] = self.arms;
+        let fewest = a.trials.min(b.trials);
+        if fewest < self.params.min_trials_per_arm {
+            return;
+        }
+        let p_value = self.p_value();
+        if p_value < self.params.significance {
+            let winner = if a.success_rate() >= b.success_rate() { a.strategy } else { b.strategy };
+            self.status = ExperimentStatus::Won { winner, p_value };
+        } else if fewest >= self.params.max_trials_per_arm {
+            self.status = ExperimentStatus::Inconclusive { p_value };
+        }
+    }
+}
+
+/// Reflection's experiments, running and concluded.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SelfExperiments {
+    experiments: Vec<StrategyExperiment>,
+    next_id: ExperimentId,
+}
+
+impl SelfExperiments {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts comparing `a` and `b` on the goals matching `scope`. Only one
+    /// experiment may run per scope.
+    pub fn schedule(
+        &mut self,
+        scope: Condition,
+        a: PlanningStrategy,
+        b: PlanningStrategy,
+        params: ExperimentParams,
+    ) -> Result<ExperimentId, CognitionError> {
+        if a == b {
+            return Err(CognitionError::ExperimentNeedsTwoStrategies(a));
+        }
+        if self.running_for(&scope).is_some() {
+            return Err(CognitionError::ExperimentAlreadyRunning(scope.to_string()));
+        }
+        self.next_id += 1;
+        let id = self.next_id;
+        self.experiments.push(StrategyExperiment {
+            id,
+            scope,
+            arms: [Arm::new(a), Arm::new(b)],
+            params,
+            status: ExperimentStatus::Running,
+        });
+        Ok(id)
+    }
+
+    /// The running experiment covering a goal planned in `situation`, if
+    /// any, and the strategy its next trial uses.
+    pub fn assign(&self, situation: &[Condition]) -> Option<(ExperimentId, PlanningStrategy)> {
+        self.experiments
+            .iter()
+            .find(|e| e.is_running() && situation.contains(&e.scope))
+            .map(|e| (e.id, e.next_strategy()))
+    }
+
+    /// Records the outcome of a trial. Returns the experiment if this
+    /// trial concluded it.
+    pub fn record(
+        &mut self,
+        id: ExperimentId,
+        strategy: PlanningStrategy,
+        success: bool,
+    ) -> Result<Option<&StrategyExperiment>, CognitionError> {
+        let experiment = self
+            .experiments
+            .iter_mut()
+            .find(|e| e.id == id && e.is_running())
+            .ok_or(CognitionError::ExperimentNotRunning(id))?;
+        let arm = experiment
+            .arms
+            .iter_mut()
+            .find(|arm| arm.strategy == strategy)
+            .ok_or(CognitionError::StrategyNotInExperiment { id, strategy })?;
+        arm.trials += 1;
+        arm.successes += usize::from(success);
+        experiment.conclude_if_ready();
+        Ok((!experiment.is_running()).then_some(&*experiment))
+    }
+
+    pub fn get(&self, id: ExperimentId) -> Option<&StrategyExperiment> {
+        self.experiments.iter().find(|e| e.id == id)
+    }
+
+    pub fn running_for(&self, scope: &Condition) -> Option<&StrategyExperiment> {
+        self.experiments.iter().find(|e| e.is_running() && e.scope == *scope)
+    }
+
+    /// Every experiment, in the order scheduled.
+    pub fn all(&self) -> &[StrategyExperiment] {
+        &self.experiments
+    }
+}
+
+/// Commits the result of a won experiment to `state`: adopts its winning
+/// rule, drops any rule that steered goals in its scope away from the
+/// winner, and, for a category, makes the winner that category's preferred
+/// strategy. Returns the winner, or `None` if the experiment was not won.
+pub fn commit_winner(state: &mut CognitiveState, experiment: &StrategyExperiment) -> Option<PlanningStrategy> {
+    let rule = experiment.winning_rule()?;
+    let winner = rule.prefer;
+    state.heuristics.rules.retain(|held| !(held.when == rule.when && held.avoid == winner));
+    state.heuristics.adopt_rule(rule);
+    if let Condition::Category(category) = &experiment.scope {
+        state.heuristics_for_mut(category).preferred_strategy = winner;
+    }
+    Some(winner)
+}
+
+/// Two-sided Fisher's exact test on the 2x2 table of successes and
+/// failures of two arms: the probability, with the margins fixed, of a
+/// table at most as likely as the one observed.
+pub fn fisher_exact(a_successes: usize, a_failures: usize, b_successes: usize, b_failures: usize) -> f64 {
+    let a_trials = a_successes + a_failures;
+    let successes = a_successes + b_successes;
+    let total = a_trials + b_successes + b_failures;
+    if total == 0 {
+        return 1.0;
+    }
+    let failures = total - successes;
+
+    let ln_factorial = |n: usize| (1..=n).map(|k| (k as f64).ln()).sum::<f64>();
+    // Probability of `x` successes in arm a, hypergeometric
+    let probability = |x: usize| {
+        (ln_factorial(successes) + ln_factorial(failures) + ln_factorial(a_trials) + ln_factorial(total - a_trials)
+            - ln_factorial(total)
+            - ln_factorial(x)
+            - ln_factorial(successes - x)
+            - ln_factorial(a_trials - x)
+            - ln_factorial(failures + x - a_trials))
+            .exp()
+    };
+
+    let lowest = a_trials.saturating_sub(failures);
+    let highest = a_trials.min(successes);
+    let observed = probability(a_successes);
+    let p: f64 = (lowest..=highest).map(probability).filter(|&p| p <= observed * (1.0 + 1e-7)).sum();
+    p.min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use PlanningStrategy::*;
+
+    #[test]
+    fn test_alternates_arms_and_commits_only_a_significant_winner() {
+        let mut experiments = SelfExperiments::new();
+        let scope = Condition::Category("messages".into());
+        let id = experiments.schedule(scope.clone(), Htn, Goap, ExperimentParams::default()).unwrap();
+        assert!(experiments.schedule(scope.clone(), Htn, Reactive, ExperimentParams::default()).is_err());
+        let situation = [Condition::Goal("reply".into()), scope.clone()];
+        assert_eq!(experiments.assign(&[Condition::Goal("reply".into())]), None);
+
+        // Goap wins every trial and Htn loses every one
+        let experiment = loop {
+            let (trial, strategy) = experiments.assign(&situation).unwrap();
+            if let Some(concluded) = experiments.record(trial, strategy, strategy == Goap).unwrap() {
+                break concluded.clone();
+            }
+        };
+        assert_eq!(experiment.arms.map(|arm| arm.trials), [5, 5]);
+        assert!(matches!(experiment.status, ExperimentStatus::Won { winner: Goap, p_value } if p_value < 0.01));
+        let mut state = CognitiveState::new();
+        state.heuristics.adopt_rule(HeuristicRule {
+            when: vec![scope.clone()],
+            avoid: Goap,
+            prefer: Htn,
+            confidence: 0.7,
+            support: 3,
+        });
+        assert_eq!(commit_winner(&mut state, &experiment), Some(Goap));
+        assert_eq!(state.heuristics.rule_against(Htn, &situation).map(|rule| rule.prefer), Some(Goap));
+        assert!(state.heuristics.rule_against(Goap, &situation).is_none());
+        assert_eq!(state.heuristics_for(Some("messages")).preferred_strategy, Goap);
+        assert!(experiments.assign(&situation).is_none());
+        assert!(experiments.record(id, Goap, true).is_err());
+
+        // Evenly matched arms never reach significance
+        let id = experiments.schedule(scope, Htn, Goap, ExperimentParams::default()).unwrap();
+        let mut outcome = None;
+        for trial in 0..60 {
+            let (_, strategy) = experiments.assign(&situation).unwrap();
+            outcome = experiments.record(id, strategy, trial % 4 < 2).unwrap().cloned();
+        }
+        let outcome = outcome.unwrap();
+        assert!(matches!(outcome.status, ExperimentStatus::Inconclusive { .. }));
+        assert_eq!(commit_winner(&mut state, &outcome), None);
+    }
+
+    #[test]
+    fn test_fisher_exact_matches_known_values() {
+        // Fisher's tea-tasting table
+        assert!((fisher_exact(3, 1, 1, 3) - 0.4857).abs() < 1e-4);
+        assert!((fisher_exact(6, 0, 0, 6) - 0.002165).abs() < 1e-5);
+        assert_eq!(fisher_exact(0, 0, 0, 0), 1.0);
+    }
+}
@@ -0,0 +1,185 @@
+// ============================================================================
+//                      ASTRA AGI • FATIGUE & RECOVERY
+//        Effort Accumulation, Rest Dynamics & Fatigue-Induced Slips
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Models fatigue as a quantity that builds up with sustained effort and
+//       drains away with rest. Effort is the time spent planning and
+//       executing, weighted by the load under which it happens; recovery
+//       follows exponential decay, faster during sleep than while merely
+//       idle. A tired Astra searches less before settling on a plan and is
+//       more likely to slip when carrying one out.
+//
+//   Core Functions:
+//       • Accumulate fatigue from planning and execution effort
+//       • Recover during idle periods and sleep cycles
+//       • Scale planner search budgets by remaining energy
+//       • Turn fatigue into a per-action error probability
+//
+//   File:        /src/cognition/fatigue.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use anyhow::Result;
+use rand::Rng;
+use std::time::Duration;
+
+use crate::cognition::CognitiveEnergy;
+use crate::planning::executor::ActionExecutor;
+use crate::planning::planner::{Action, WorldState};
+
+/// Effort at nominal load that takes fatigue ~63% of the way to exhaustion.
+const WORK_TIME_CONSTANT_SECS: f32 = 45.0 * 60.0;
+/// Idle time that removes ~63% of fatigue.
+const REST_TIME_CONSTANT_SECS: f32 = 30.0 * 60.0;
+/// Sleep time that removes ~63% of fatigue.
+const SLEEP_TIME_CONSTANT_HOURS: f32 = 1.0;
+
+/// Error probability when fully rested, and the extra at exhaustion.
+const BASE_ERROR_PROBABILITY: f32 = 0.01;
+const FATIGUE_ERROR_PROBABILITY: f32 = 0.25;
+
+/// Fraction of the search budget still granted at exhaustion.
+const MIN_SEARCH_SCALE: f32 = 0.3;
+
+impl CognitiveEnergy {
+    /// Adds fatigue for `effort` of planning or execution. Effort under high
+    /// load counts up to 1.5 times, effort under no load half as much.
+    pub fn exert(&mut self, effort: Duration) {
+        let intensity = 0.5 + self.load.clamp(0.0, 1.0);
+        let exposure = intensity * effort.as_secs_f32() / WORK_TIME_CONSTANT_SECS;
+        self.fatigue = (self.fatigue + (1.0 - self.fatigue) * (1.0 - (-exposure).exp())).clamp(0.0, 1.0);
+    }
+
+    /// Lets fatigue decay over an idle period.
+    pub fn rest(&mut self, idle: Duration) {
+        self.fatigue *= (-idle.as_secs_f32() / REST_TIME_CONSTANT_SECS).exp();
+    }
+
+    /// Lets fatigue decay over a sleep cycle lasting `hours`.
+    pub fn recover_in_sleep(&mut self, hours: f32) {
+        self.fatigue *= (-hours.max(0.0) / SLEEP_TIME_CONSTANT_HOURS).exp();
+    }
+
+    /// Share of the nominal planner search budget to spend, falling
+    /// linearly with fatigue.
+    pub fn search_budget_scale(&self) -> f32 {
+        1.0 - (1.0 - MIN_SEARCH_SCALE) * self.fatigue.clamp(0.0, 1.0)
+    }
+
+    /// Probability of slipping on any one action. Grows with the square of
+    /// fatigue, so mild tiredness costs little and exhaustion a lot.
+    pub fn error_probability(&self) -> f32 {
+        let fatigue = self.fatigue.clamp(0.0, 1.0);
+        BASE_ERROR_PROBABILITY + FATIGUE_ERROR_PROBABILITY * fatigue * fatigue
+    }
+}
+
+/// Executes actions through `inner`, but fails each one with the given
+/// probability before it is attempted, as a tired agent sometimes does.
+pub struct FatiguedExecutor<'r, E: ActionExecutor, R: Rng> {
+    inner: E,
+    error_probability: f32,
+    rng: &'r mut R,
+    slips: usize,
+}
+
+impl<'r, E: ActionExecutor, R: Rng> FatiguedExecutor<'r, E, R> {
+    pub fn new(inner: E, error_probability: f32, rng: &'r mut R) -> Self {
+        FatiguedExecutor {
+            inner,
+            error_probability: error_probability.clamp(0.0, 1.0),
+            rng,
+            slips: 0,
+        }
+    }
+
+    /// Actions failed because of fatigue so far.
+    pub fn slips(&self) -> usize {
+        self.slips
+    }
+}
+
+impl<'r, E: ActionExecutor, R: Rng> ActionExecutor for FatiguedExecutor<'r, E, R> {
+    fn execute_action(&mut self, action: &Action) -> Result<bool> {
+        if self.rng.gen::<f32>() < self.error_probability {
+            self.slips += 1;
+            return Ok(false);
+        }
+        self.inner.execute_action(action)
+    }
+
+    fn observe_world(&self) -> Option<WorldState> {
+        self.inner.observe_world()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::HashMap;
+
+    struct AlwaysOk;
+
+    impl ActionExecutor for AlwaysOk {
+        fn execute_action(&mut self, _action: &Action) -> Result<bool> {
+            Ok(true)
+        }
+    }
+
+    #[test]
+    fn test_fatigue_builds_with_effort_and_recovers() {
+        let mut energy = CognitiveEnergy::baseline();
+        energy.fatigue = 0.0;
+        let rested_budget = energy.search_budget_scale();
+        let rested_errors = energy.error_probability();
+
+        energy.exert(Duration::from_secs(2 * 3600));
+        assert!(energy.fatigue > 0.7);
+        assert!(energy.search_budget_scale() < rested_budget);
+        assert!(energy.error_probability() > rested_errors);
+
+        // Same effort under heavier load is more tiring.
+        let mut loaded = CognitiveEnergy::baseline();
+        loaded.fatigue = 0.0;
+        loaded.load = 1.0;
+        loaded.exert(Duration::from_secs(600));
+        let mut light = CognitiveEnergy::baseline();
+        light.fatigue = 0.0;
+        light.load = 0.0;
+        light.exert(Duration::from_secs(600));
+        assert!(loaded.fatigue > light.fatigue);
+
+        let tired = energy.fatigue;
+        energy.rest(Duration::from_secs(30 * 60));
+        assert!((energy.fatigue - tired * (-1.0f32).exp()).abs() < 1e-4);
+        energy.recover_in_sleep(4.0);
+        assert!(energy.fatigue < 0.01);
+    }
+
+    #[test]
+    fn test_fatigued_executor_slips_at_its_error_rate() {
+        let action = Action {
+            id: "a".into(),
+            description: "a".into(),
+            preconditions: HashMap::new(),
+            effects: HashMap::new(),
+            cost: 1.0,
+            risk: 0.0,
+        };
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut executor = FatiguedExecutor::new(AlwaysOk, 0.25, &mut rng);
+        let failures = (0..2000).filter(|_| !executor.execute_action(&action).unwrap()).count();
+
+        assert_eq!(failures, executor.slips());
+        assert!((400..600).contains(&failures));
+    }
+}
@@ -0,0 +1,177 @@
+// ============================================================================
+//                        ASTRA AGI • GOAL CATEGORIES
+//        Grouping Goals by the Concepts They Involve
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Sorts goals into categories so that what planning learns about one
+//       kind of goal is not averaged away by every other kind. A goal's
+//       category is the concept most of the things it mentions belong to,
+//       according to "is_a" facts in the knowledge base: turning on a lamp
+//       and closing the blinds are both about devices, replying to Sam is
+//       about messages. Heuristics are then kept per category, and a goal
+//       whose things the knowledge base knows nothing about falls back to
+//       the global heuristics.
+//
+//   Core Functions:
+//       • Learn what concepts things belong to from "is_a" facts
+//       • Assign goals to the concept their terms most belong to
+//       • Group the goals seen so far by category
+//
+//   File:        /src/cognition/goal_categories.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::knowledge::extended_ontology::{name_entity_id, EntityId, Fact, OntologyManager};
+use crate::planning::planner::Goal;
+
+/// Predicate of the facts that say what concept a thing belongs to.
+pub const CONCEPT_PREDICATE: &str = "is_a";
+
+/// Facts weaker than this do not decide a category.
+const MIN_CONCEPT_CONFIDENCE: f32 = 0.5;
+
+/// What concepts things belong to, and the categories goals were put in.
+#[derive(Debug, Clone, Default)]
+pub struct GoalCategorizer {
+    /// By entity: each concept it is said to be, with the strongest confidence
+    concepts: HashMap<EntityId, HashMap<String, f32>>,
+    /// By goal id, for goals categorized so far
+    assignments: HashMap<String, String>,
+}
+
+impl GoalCategorizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Learns from every fact in the current version of `knowledge`.
+    pub fn from_knowledge(knowledge: &OntologyManager) -> Self {
+        let mut categorizer = Self::new();
+        for fact in knowledge.query_facts(None) {
+            categorizer.observe(fact);
+        }
+        categorizer
+    }
+
+    /// Notes what `fact` says a thing is, if it is a confident "is_a" fact.
+    pub fn observe(&mut self, fact: &Fact) {
+        if fact.predicate != CONCEPT_PREDICATE || fact.confidence < MIN_CONCEPT_CONFIDENCE {
+            return;
+        }
+        let confidence = self
+            .concepts
+            .entry(fact.subject)
+            .or_default()
+            .entry(fact.object.trim().to_lowercase())
+            .or_insert(0.0);
+        *confidence = confidence.max(fact.confidence);
+    }
+
+    /// The concept the terms of `goal`'s id, description, and desired state
+    /// most belong to, weighted by confidence. Ties go to the concept first
+    /// in alphabetical order. None if no term is a known thing.
+    pub fn categorize(&mut self, goal: &Goal) -> Option<String> {
+        let mut terms: Vec<String> = std::iter::once(goal.id.as_str())
+            .chain(std::iter::once(goal.description.as_str()))
+            .chain(goal.desired_state.keys().map(String::as_str))
+            .flat_map(|text| text.split(|c: char| !c.is_alphanumeric()))
+            .filter(|term| term.len() > 2)
+            .map(str::to_lowercase)
+            .collect();
+        terms.sort();
+        terms.dedup();
+
+        // Ordered, so ties are broken the same way every time
+        let mut scores: BTreeMap<&str, f32> = BTreeMap::new();
+        for term in &terms {
+            for (concept, confidence) in self.concepts.get(&name_entity_id(term)).into_iter().flatten() {
+                *scores.entry(concept).or_insert(0.0) += confidence;
+            }
+        }
+        let category = scores
+            .into_iter()
+            .rev()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(concept, _)| concept.to_string())?;
+        self.assignments.insert(goal.id.clone(), category.clone());
+        Some(category)
+    }
+
+    /// Category a goal was last put in.
+    pub fn category_of(&self, goal_id: &str) -> Option<&str> {
+        self.assignments.get(goal_id).map(String::as_str)
+    }
+
+    /// Goal ids seen so far, grouped by category and sorted.
+    pub fn clusters(&self) -> BTreeMap<&str, Vec<&str>> {
+        let mut clusters: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for (goal, category) in &self.assignments {
+            clusters.entry(category.as_str()).or_default().push(goal.as_str());
+        }
+        for goals in clusters.values_mut() {
+            goals.sort();
+        }
+        clusters
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cognition::heuristic_reinforcement::reinforce_strategy;
+    use crate::cognition::CognitiveState;
+    use crate::knowledge::extended_ontology::Provenance;
+    use crate::planning::planner::PlanningStrategy;
+
+    fn goal(id: &str, keys: &[&str]) -> Goal {
+        Goal {
+            id: id.into(),
+            description: String::new(),
+            desired_state: keys.iter().map(|k| (k.to_string(), true)).collect(),
+            priority: 5,
+        }
+    }
+
+    #[test]
+    fn test_categories_specialize_strategies_and_fall_back_to_global() {
+        let mut knowledge = OntologyManager::new();
+        for (thing, concept) in [("lamp", "Device"), ("blinds", "Device"), ("email", "Message"), ("reply", "Message")] {
+            knowledge.add_fact(Fact {
+                subject: name_entity_id(thing),
+                predicate: CONCEPT_PREDICATE.into(),
+                object: concept.into(),
+                confidence: 0.9,
+                provenance: Provenance::new("test", None),
+            });
+        }
+        let mut categories = GoalCategorizer::from_knowledge(&knowledge);
+        let lamp = goal("turn_on_lamp", &["lamp_on"]);
+        let blinds = goal("close_blinds", &["blinds_closed"]);
+        let email = goal("answer_email", &["reply_sent"]);
+        assert_eq!(categories.categorize(&lamp).as_deref(), Some("device"));
+        assert_eq!(categories.categorize(&blinds).as_deref(), Some("device"));
+        assert_eq!(categories.categorize(&email).as_deref(), Some("message"));
+        assert_eq!(categories.categorize(&goal("ponder", &["pondered"])), None);
+        assert_eq!(categories.clusters().get("device"), Some(&vec!["close_blinds", "turn_on_lamp"]));
+
+        // Devices do well with GOAP, messages with reactive replies
+        let mut state = CognitiveState::new();
+        for _ in 0..10 {
+            reinforce_strategy(&mut state, categories.category_of("turn_on_lamp"), PlanningStrategy::Goap, true);
+            reinforce_strategy(&mut state, categories.category_of("answer_email"), PlanningStrategy::Goap, false);
+            reinforce_strategy(&mut state, categories.category_of("answer_email"), PlanningStrategy::Reactive, true);
+        }
+        assert_eq!(state.heuristics_for(Some("device")).leading_strategy(), PlanningStrategy::Goap);
+        assert_eq!(state.heuristics_for(Some("message")).leading_strategy(), PlanningStrategy::Reactive);
+        assert_eq!(state.heuristics_for(Some("weather")).goap_bias, state.heuristics.goap_bias);
+        assert_eq!(state.heuristics_for(None).leading_strategy(), PlanningStrategy::Goap);
+    }
+}
@@ -12,11 +12,14 @@
 //       • Generate candidate goals from inputs and internal drives
 //       • Prioritize and filter goals based on motivation and context
 //       • Interface with planning subsystem via structured Goal objects
+//       • Bias goal selection toward topics the user has shown interest in
+//       • Hold back curiosity goals while cognitive load is high
+//       • Turn recurring knowledge gaps into learning goals
 //
 //   File:        /src/cognition/goal_formation.rs
 //   Author:      Alex Roussinov
 //   Created:     2026-01-11
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
@@ -26,10 +29,12 @@
 use std::collections::HashMap;
 
 use crate::cognition::CognitiveState;
+use crate::knowledge::knowledge_gaps::{KnowledgeGapDetector, LearningChannel};
+use crate::learning::preferences::PreferenceModel;
 use crate::planning::planner::{Goal, WorldState};
 
 /// Represents an external or internal stimulus Astra might respond to.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Stimulus {
     pub source: String,
     pub content: String,
@@ -59,7 +64,8 @@ pub fn generate_goals_from_stimulus(
     }
 
     // Example 2: internal curiosity → goal: explore unknown concepts.
-    if state.curiosity_level > 0.6 {
+    // Exploration is speculative, so it waits while Astra is under load.
+    if state.curiosity_level > 0.6 && state.energy.allows_speculation() {
         let mut desired = WorldState::new();
         desired.insert("knowledge_gap_reduced".into(), true);
 
@@ -74,10 +80,72 @@ pub fn generate_goals_from_stimulus(
     goals
 }
 
+/// Converts gaps seen at least `min_occurrences` times into learning goals,
+/// most frequent first. Gaps Astra could not look up become crawl goals;
+/// names only the user has mentioned become questions for the user. Like
+/// curiosity goals, they wait while Astra is under load.
+pub fn learning_goals_from_gaps(
+    state: &CognitiveState,
+    gaps: &KnowledgeGapDetector,
+    min_occurrences: u32,
+) -> Vec<Goal> {
+    if !state.energy.allows_speculation() {
+        return Vec::new();
+    }
+
+    gaps.frequent_gaps(min_occurrences)
+        .into_iter()
+        .map(|gap| {
+            let mut desired = WorldState::new();
+            desired.insert("knowledge_gap_reduced".into(), true);
+            let (id, description) = match gap.channel() {
+                LearningChannel::Crawl => {
+                    desired.insert("topic_crawled".into(), true);
+                    (
+                        format!("explore_crawl_{}", gap.topic),
+                        format!("Crawl the web to learn about '{}'", gap.topic),
+                    )
+                }
+                LearningChannel::AskUser => {
+                    desired.insert("user_asked".into(), true);
+                    (
+                        format!("explore_ask_{}", gap.topic),
+                        format!("Ask the user what '{}' is", gap.topic),
+                    )
+                }
+            };
+            Goal {
+                id,
+                description,
+                desired_state: desired,
+                priority: 4 + gap.occurrences().min(4) as i32,
+            }
+        })
+        .collect()
+}
+
 /// Prioritizes among candidate goals based on motivation and context.
 pub fn select_primary_goal(
     state: &CognitiveState,
     candidates: &[Goal],
+) -> Option<Goal> {
+    select_goal_by(state, candidates, |_| 0.0)
+}
+
+/// Like `select_primary_goal`, but goals touching topics the user likes
+/// (or dislikes) gain (or lose) up to two points of score.
+pub fn select_primary_goal_with_preferences(
+    state: &CognitiveState,
+    candidates: &[Goal],
+    preferences: &PreferenceModel,
+) -> Option<Goal> {
+    select_goal_by(state, candidates, |goal| 2.0 * preferences.topic_affinity(&goal.description))
+}
+
+fn select_goal_by(
+    state: &CognitiveState,
+    candidates: &[Goal],
+    bonus: impl Fn(&Goal) -> f32,
 ) -> Option<Goal> {
     if candidates.is_empty() {
         return None;
@@ -91,7 +159,7 @@ pub fn select_primary_goal(
         let motivation_factor = state.motivation_level;
         let emotional_bonus = if state.emotion.happiness > 0.6 { 0.5 } else { 0.0 };
 
-        let score = base * motivation_factor + emotional_bonus;
+        let score = base * motivation_factor + emotional_bonus + bonus(goal);
         if score > best_score {
             best_score = score;
             best = Some(goal);
@@ -100,3 +168,37 @@ pub fn select_primary_goal(
 
     best.cloned()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_curiosity_goals_yield_to_load() {
+        let mut state = CognitiveState::new();
+        state.curiosity_level = 0.9;
+        let stimulus = Stimulus { source: "user".into(), content: "Can you help?".into(), urgency: 0.5 };
+
+        let relaxed = generate_goals_from_stimulus(&state, &stimulus);
+        assert!(relaxed.iter().any(|g| g.id == "explore_topic"));
+
+        state.energy.load = 0.9;
+        let loaded = generate_goals_from_stimulus(&state, &stimulus);
+        assert!(loaded.iter().all(|g| g.id != "explore_topic"));
+        assert!(loaded.iter().any(|g| g.id == "respond_to_user"));
+    }
+
+    #[test]
+    fn test_frequent_gaps_become_learning_goals() {
+        let state = CognitiveState::new();
+        let mut gaps = KnowledgeGapDetector::new();
+        gaps.record_failed_query("fusion");
+        gaps.record_failed_query("fusion");
+        gaps.record_failed_query("tides");
+
+        let goals = learning_goals_from_gaps(&state, &gaps, 2);
+        assert_eq!(goals.len(), 1);
+        assert_eq!(goals[0].id, "explore_crawl_fusion");
+        assert_eq!(goals[0].desired_state.get("topic_crawled"), Some(&true));
+    }
+}
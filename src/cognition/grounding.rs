@@ -0,0 +1,256 @@
+// ============================================================================
+//                        ASTRA AGI • GROUNDED ANSWERS
+//        Citing the Evidence Behind Each Claim, Withholding the Rest
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Keeps Astra's answers tied to what it actually knows. An answer is
+//       split into claims, and each claim is matched against the evidence
+//       retrieval gathered for the question: a claim whose words are covered
+//       by sufficiently trusted evidence is stated with numbered references
+//       to the sources it rests on, and a claim with no such support is
+//       withheld rather than asserted. Answers can also be composed directly
+//       from the evidence, so that every sentence starts out cited.
+//
+//   Core Functions:
+//       • Split answers into claims and match them to supporting evidence
+//       • Number sources and attach references to supported claims
+//       • Withhold claims without evidence above the confidence threshold
+//       • Compose cited answers from the strongest evidence
+//
+//   File:        /src/cognition/grounding.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use serde::Serialize;
+use std::collections::HashSet;
+
+use crate::cognition::retrieval::Evidence;
+use crate::memory::conversation_summary::content_words;
+
+/// Said instead when no evidence is strong enough to answer from.
+pub const NO_ANSWER: &str = "I don't have enough reliable information to answer that.";
+
+/// A sentence of an answer and the sources it cites, by number.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Statement {
+    pub text: String,
+    pub sources: Vec<usize>,
+}
+
+/// A cited source, numbered from 1 in order of first citation.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Source {
+    pub number: usize,
+    pub provenance: String,
+    /// The evidence from this source that the answer relies on
+    pub excerpts: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct GroundedAnswer {
+    pub statements: Vec<Statement>,
+    pub sources: Vec<Source>,
+    /// Claims left out for lack of support
+    pub withheld: Vec<String>,
+}
+
+impl GroundedAnswer {
+    /// Whether every claim made survived grounding.
+    pub fn fully_grounded(&self) -> bool {
+        self.withheld.is_empty()
+    }
+
+    /// The answer with a `[n]` reference after each cited sentence and the
+    /// sources listed below it.
+    pub fn render(&self) -> String {
+        let mut text = self
+            .statements
+            .iter()
+            .map(|statement| {
+                let references: String = statement.sources.iter().map(|n| format!("[{}]", n)).collect();
+                if references.is_empty() {
+                    return statement.text.clone();
+                }
+                // References go inside the sentence's final punctuation
+                let body = statement.text.trim_end_matches(['.', '!']);
+                let end = &statement.text[body.len()..];
+                format!("{} {}{}", body, references, end)
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !self.sources.is_empty() {
+            text.push_str("\n\nSources:");
+            for source in &self.sources {
+                text.push_str(&format!("\n[{}] {}", source.number, source.provenance));
+            }
+        }
+        text
+    }
+
+    /// The number of the source at `provenance`, added if it is new, with
+    /// `excerpt` recorded against it.
+    fn cite(&mut self, provenance: &str, excerpt: &str) -> usize {
+        let index = match self.sources.iter().position(|s| s.provenance == provenance) {
+            Some(index) => index,
+            None => {
+                self.sources.push(Source { number: self.sources.len() + 1, provenance: provenance.to_string(), excerpts: Vec::new() });
+                self.sources.len() - 1
+            }
+        };
+        let source = &mut self.sources[index];
+        if !source.excerpts.iter().any(|e| e == excerpt) {
+            source.excerpts.push(excerpt.to_string());
+        }
+        source.number
+    }
+}
+
+/// What a claim needs before Astra will state it.
+#[derive(Debug, Clone)]
+pub struct Grounder {
+    /// Evidence less trusted than this supports nothing
+    pub min_confidence: f32,
+    /// Share of a claim's content words the evidence must contain
+    pub min_coverage: f32,
+    /// Sources cited per claim
+    pub max_citations: usize,
+    /// Pieces of evidence a composed answer states
+    pub max_statements: usize,
+}
+
+impl Default for Grounder {
+    fn default() -> Self {
+        Grounder { min_confidence: 0.5, min_coverage: 0.75, max_citations: 2, max_statements: 3 }
+    }
+}
+
+/// Splits text into sentences, keeping their final punctuation.
+fn sentences(text: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        current.push(c);
+        if matches!(c, '.' | '!' | '?') && chars.peek().is_none_or(|next| next.is_whitespace()) {
+            found.push(current.trim().to_string());
+            current.clear();
+        }
+    }
+    if !current.trim().is_empty() {
+        found.push(current.trim().to_string());
+    }
+    found
+}
+
+impl Grounder {
+    /// Grounds an answer written elsewhere: each claim is cited to the
+    /// evidence covering it, or withheld. Questions and sentences without
+    /// content words are not claims and are kept as they are.
+    pub fn ground(&self, answer: &str, evidence: &[Evidence]) -> GroundedAnswer {
+        let trusted: Vec<(&Evidence, HashSet<String>)> = evidence
+            .iter()
+            .filter(|e| e.confidence >= self.min_confidence)
+            .map(|e| (e, content_words(&e.text).collect()))
+            .collect();
+
+        let mut grounded = GroundedAnswer::default();
+        for sentence in sentences(answer) {
+            let words: HashSet<String> = content_words(&sentence).collect();
+            if words.is_empty() || sentence.ends_with('?') {
+                grounded.statements.push(Statement { text: sentence, sources: Vec::new() });
+                continue;
+            }
+            let mut support: Vec<(f32, &Evidence)> = trusted
+                .iter()
+                .map(|(e, evidence_words)| (words.intersection(evidence_words).count() as f32 / words.len() as f32, *e))
+                .filter(|(coverage, _)| *coverage >= self.min_coverage)
+                .collect();
+            if support.is_empty() {
+                grounded.withheld.push(sentence);
+                continue;
+            }
+            support.sort_by(|a, b| b.0.total_cmp(&a.0).then(b.1.score.total_cmp(&a.1.score)));
+            let mut sources = Vec::new();
+            for (_, e) in support {
+                let number = grounded.cite(&e.provenance, &e.text);
+                if !sources.contains(&number) {
+                    sources.push(number);
+                }
+                if sources.len() == self.max_citations {
+                    break;
+                }
+            }
+            grounded.statements.push(Statement { text: sentence, sources });
+        }
+        grounded
+    }
+
+    /// Answers from the evidence itself: the strongest trusted pieces, one
+    /// cited sentence each, or a plain admission when none is trusted.
+    pub fn compose(&self, evidence: &[Evidence]) -> GroundedAnswer {
+        let mut grounded = GroundedAnswer::default();
+        for e in evidence.iter().filter(|e| e.confidence >= self.min_confidence).take(self.max_statements) {
+            let number = grounded.cite(&e.provenance, &e.text);
+            let mut text = e.text.trim().trim_end_matches(['.', '!']).to_string();
+            if let Some(first) = text.chars().next() {
+                text.replace_range(..first.len_utf8(), &first.to_uppercase().to_string());
+            }
+            text.push('.');
+            grounded.statements.push(Statement { text, sources: vec![number] });
+        }
+        if grounded.statements.is_empty() {
+            grounded.statements.push(Statement { text: NO_ANSWER.to_string(), sources: Vec::new() });
+        }
+        grounded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cognition::retrieval::EvidenceKey;
+
+    fn evidence(text: &str, provenance: &str, confidence: f32) -> Evidence {
+        Evidence {
+            key: EvidenceKey::Passage(text.into()),
+            text: text.into(),
+            provenance: provenance.into(),
+            confidence,
+            score: confidence,
+            found_by: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_claims_are_cited_or_withheld() {
+        let evidence = vec![
+            evidence("tomatoes need full sun", "file:/garden.md", 0.9),
+            evidence("Tomatoes need regular watering in summer", "file:/watering.md", 0.8),
+            evidence("Tomatoes cure headaches", "user:rumour", 0.2),
+        ];
+        let grounder = Grounder::default();
+
+        let answer = grounder.ground(
+            "Yes! Tomatoes need full sun. They need regular watering in summer. Tomatoes cure headaches. Want more?",
+            &evidence,
+        );
+        assert_eq!(answer.withheld, vec!["Tomatoes cure headaches."]);
+        assert_eq!(
+            answer.render(),
+            "Yes! Tomatoes need full sun [1]. They need regular watering in summer [2]. Want more?\n\n\
+             Sources:\n[1] file:/garden.md\n[2] file:/watering.md"
+        );
+
+        let composed = grounder.compose(&evidence);
+        assert_eq!(composed.statements[0].text, "Tomatoes need full sun.");
+        assert_eq!(composed.statements.len(), 2);
+        assert!(composed.fully_grounded());
+        assert_eq!(grounder.compose(&evidence[2..]).render(), NO_ANSWER);
+    }
+}
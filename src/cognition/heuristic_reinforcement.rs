@@ -9,19 +9,22 @@
 //   Core Functions:
 //       • Track GOAP, HTN, and reactive planning success
 //       • Adjust biases in PlanningHeuristics
+//       • Score the strategy an episode used, behavior trees included
+//       • Score it for the goal's category as well as globally
 //       • Improve planning efficiency through reinforcement
 //
 //   File:        /src/cognition/heuristic_reinforcement.rs
 //   Author:      Alex Roussinov
 //   Created:     2026-01-12
-//   Updated:     2026-01-12
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
-use crate::cognition::{CognitiveState, PlanningHeuristics};
+use crate::cognition::CognitiveState;
+use crate::planning::planner::PlanningStrategy;
 
 pub fn reinforce_heuristics(state: &mut CognitiveState, success: bool) {
     let delta = if success { 0.01 } else { -0.015 };
@@ -30,3 +33,18 @@ pub fn reinforce_heuristics(state: &mut CognitiveState, success: bool) {
     state.heuristics.htn_bias = (state.heuristics.htn_bias + delta * 0.5).clamp(0.0, 1.0);
     state.heuristics.reactive_bias = (state.heuristics.reactive_bias - delta * 0.3).clamp(0.0, 1.0);
 }
+
+/// Credits or blames only the strategy an episode was carried out with,
+/// globally and, if the goal has one, in the goal's category. Categories
+/// learn faster than the global heuristics, which average over all goals.
+pub fn reinforce_strategy(state: &mut CognitiveState, category: Option<&str>, strategy: PlanningStrategy, success: bool) {
+    const CATEGORY_RATE: f32 = 3.0;
+
+    let delta = if success { 0.01 } else { -0.015 };
+    if let Some(category) = category {
+        let bias = state.heuristics_for_mut(category).bias_mut(strategy);
+        *bias = (*bias + delta * CATEGORY_RATE).clamp(0.0, 1.0);
+    }
+    let bias = state.heuristics.bias_mut(strategy);
+    *bias = (*bias + delta).clamp(0.0, 1.0);
+}
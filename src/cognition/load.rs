@@ -0,0 +1,141 @@
+// ============================================================================
+//                        ASTRA AGI • COGNITIVE LOAD MODEL
+//        Deriving Load from Runtime Pressure & Shedding Speculative Work
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Gives `CognitiveEnergy::load` a meaning. Load is derived from how
+//       much work is waiting (intent queue depth), whether ticks overrun
+//       their time budget, and how many plans execute at once. As load rises
+//       Astra becomes more selective: speculative work such as curiosity
+//       goals and mindspace exploration is set aside, and weaker stimuli no
+//       longer capture attention.
+//
+//   Core Functions:
+//       • Collect load signals reported by the runtime
+//       • Blend them into a smoothed load level in 0..1
+//       • Derive the attention threshold for incoming stimuli
+//       • Decide whether speculative work is affordable
+//
+//   File:        /src/cognition/load.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::cognition::CognitiveEnergy;
+
+/// Load above which speculative work is skipped.
+pub const SPECULATION_LOAD_LIMIT: f32 = 0.7;
+
+/// Raw pressure measurements from the runtime.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LoadSignals {
+    /// Intents waiting or in progress
+    pub queued_intents: usize,
+    /// Duration of the last tick
+    pub tick_duration: Duration,
+    /// Time a tick is meant to take
+    pub tick_budget: Duration,
+    pub running_plans: usize,
+    pub plan_capacity: usize,
+}
+
+impl LoadSignals {
+    /// Instantaneous load in 0..1, before smoothing.
+    pub fn instantaneous_load(&self) -> f32 {
+        // Ten queued intents is half of full queue pressure.
+        let queue = self.queued_intents as f32 / (self.queued_intents as f32 + 10.0);
+        let overrun = if self.tick_budget.is_zero() {
+            0.0
+        } else {
+            (self.tick_duration.as_secs_f32() / self.tick_budget.as_secs_f32() - 1.0).clamp(0.0, 1.0)
+        };
+        let plans = if self.plan_capacity == 0 {
+            0.0
+        } else {
+            (self.running_plans as f32 / self.plan_capacity as f32).min(1.0)
+        };
+
+        (0.4 * queue + 0.3 * overrun + 0.3 * plans).clamp(0.0, 1.0)
+    }
+}
+
+impl CognitiveEnergy {
+    /// Moves load halfway toward what the signals indicate, so one slow tick
+    /// does not swing behavior.
+    pub fn observe_load(&mut self, signals: &LoadSignals) {
+        self.load = (0.5 * self.load + 0.5 * signals.instantaneous_load()).clamp(0.0, 1.0);
+    }
+
+    /// Minimum urgency a stimulus needs to be attended to. Zero until load
+    /// passes one half, rising to 0.5 at full load.
+    pub fn attention_threshold(&self) -> f32 {
+        (self.load - 0.5).clamp(0.0, 0.5)
+    }
+
+    /// Whether there is room for work that is not needed right now.
+    pub fn allows_speculation(&self) -> bool {
+        self.load < SPECULATION_LOAD_LIMIT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cognition::CognitiveState;
+    use crate::runtime::Runtime;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    #[test]
+    fn test_load_rises_with_pressure_and_gates_behavior() {
+        let mut energy = CognitiveEnergy::baseline();
+        assert!(energy.allows_speculation());
+        assert_eq!(energy.attention_threshold(), 0.0);
+
+        let busy = LoadSignals {
+            queued_intents: 90,
+            tick_duration: Duration::from_millis(300),
+            tick_budget: Duration::from_millis(100),
+            running_plans: 4,
+            plan_capacity: 4,
+        };
+        for _ in 0..5 {
+            energy.observe_load(&busy);
+        }
+        assert!(energy.load > 0.9);
+        assert!(!energy.allows_speculation());
+        assert!(energy.attention_threshold() > 0.4);
+
+        for _ in 0..5 {
+            energy.observe_load(&LoadSignals::default());
+        }
+        assert!(energy.allows_speculation());
+    }
+
+    #[test]
+    fn test_runtime_ticks_raise_the_load_a_cognitive_loop_sees() {
+        let mut runtime = Runtime::new();
+        runtime.tick_budget = Duration::from_nanos(1);
+        for i in 0..90 {
+            runtime.intent_manager.create_intent_with_metadata(format!("chore {}", i), 5, None);
+        }
+        let state = Arc::new(Mutex::new(CognitiveState::new()));
+        runtime.share_load_with(Arc::clone(&state));
+        assert_eq!(state.try_lock().unwrap().energy.attention_threshold(), 0.0);
+
+        for _ in 0..5 {
+            runtime.tick();
+        }
+        let state = state.try_lock().unwrap();
+        assert_eq!(state.energy.load, runtime.energy.load);
+        assert!(state.energy.attention_threshold() > 0.1, "a long queue and overrunning ticks raise it");
+    }
+}
@@ -14,11 +14,29 @@
 //       • Implement the main cognitive loop driving Astra’s behavior
 //       • Integrate goal formation, motivation, curiosity, and self-modeling
 //       • Provide meta-level processes: reflection, consolidation, mindspace
+//       • Model cognitive load and let it gate speculative work
+//       • Model fatigue from sustained effort and recovery through rest
+//       • Gate structural self-modification behind approval and audit
+//       • Recall associated memories and facts by spreading activation
+//       • Gather evidence for questions by fusing symbolic, full-text, and
+//         semantic retrieval
+//       • Cite the evidence behind answers and withhold unsupported claims
+//       • Group goals into categories by the concepts they involve
+//       • Weaken beliefs behind predictions the world strongly contradicted
+//       • Choose between responding now, thinking more, and deferring by
+//         weighted utility scorers
+//       • Bound the stimuli waiting for attention, folding repeats and
+//         shedding the least urgent when perception floods
+//       • Model each user's goals, beliefs, and mood to tailor responses
+//       • Cluster episodes and mine the situations strategies fail in
+//       • Settle strategy choices by controlled self-experiments
+//       • Train the weakest planning capability on synthetic tasks while idle
+//       • Report cognition failures as a typed error
 //
 //   File:        /src/cognition/mod.rs
 //   Author:      Alex Roussinov
 //   Created:     2026-01-11
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
@@ -26,25 +44,44 @@
 // ============================================================================
 
 pub mod cognitive_state;
+pub mod error;
 pub mod goal_formation;
 pub mod motivation;
+pub mod load;
+pub mod fatigue;
+pub mod self_modification;
 pub mod curiosity;
 pub mod self_model;
 pub mod thought_trace;
 pub mod cognitive_loop;
 pub mod consolidation;
+pub mod reflection;
+pub mod heuristic_reinforcement;
 pub mod mindspace;
-pub mod episodes;
 pub mod learning_adapter;
+pub mod spreading_activation;
+pub mod retrieval;
+pub mod grounding;
+pub mod utility;
+pub mod goal_categories;
+pub mod prediction_error;
+pub mod stimulus_queue;
+pub mod theory_of_mind;
+pub mod pattern_mining;
+pub mod experiments;
+pub mod curriculum;
 
 pub use cognitive_state::*;
 pub use goal_formation::*;
 pub use motivation::*;
+pub use load::*;
+pub use fatigue::*;
 pub use curiosity::*;
 pub use self_model::*;
 pub use thought_trace::*;
 pub use cognitive_loop::*;
 pub use consolidation::*;
 pub use mindspace::*;
-pub use episodes::*;
 pub use learning_adapter::*;
+pub use spreading_activation::*;
+pub use utility::*;
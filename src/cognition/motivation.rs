@@ -6,25 +6,32 @@
 //       Models Astra’s motivational landscape, including urgency, importance,
 //       anticipated reward, and emotional resonance of goals. This system
 //       helps determine which goals are worth pursuing and how intensely
-//       Astra should commit cognitive resources to them.
+//       Astra should commit cognitive resources to them. Intrinsic drives
+//       for competence, autonomy, relatedness, and curiosity build up while
+//       unmet and, once strong enough during idle periods, give rise to
+//       goals of Astra's own.
 //
 //   Core Functions:
 //       • Assign motivational scores to potential goals
 //       • Update motivation based on outcomes and emotional feedback
 //       • Influence cognitive energy allocation and planning depth
+//       • Accumulate unmet drives and generate intrinsic goals when idle
 //
 //   File:        /src/cognition/motivation.rs
 //   Author:      Alex Roussinov
 //   Created:     2026-01-11
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
 use crate::cognition::{CognitiveEnergy, CognitiveState};
-use crate::planning::planner::Goal;
+use crate::planning::planner::{Goal, WorldState};
 
 /// Represents a motivational evaluation of a goal.
 #[derive(Debug, Clone)]
@@ -57,3 +64,169 @@ pub fn update_energy_after_outcome(
         energy.fatigue = (energy.fatigue + 0.05).min(1.0);
     }
 }
+
+/// Intrinsic needs that grow while unmet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Drive {
+    /// Being good at things; met by succeeding
+    Competence,
+    /// Acting on its own initiative; met by self-chosen goals
+    Autonomy,
+    /// Connection with people; met by interaction
+    Relatedness,
+    /// Learning something new; met by novelty and exploration
+    Curiosity,
+}
+
+impl Drive {
+    pub const ALL: [Drive; 4] = [Drive::Competence, Drive::Autonomy, Drive::Relatedness, Drive::Curiosity];
+
+    /// Prefix of the ids of goals generated for this drive.
+    fn goal_prefix(&self) -> &'static str {
+        match self {
+            Drive::Competence => "practice_",
+            Drive::Autonomy => "self_directed_",
+            Drive::Relatedness => "review_memories",
+            Drive::Curiosity => "explore_",
+        }
+    }
+
+    /// The drive an intrinsic goal was generated for, if any.
+    pub fn of_goal(goal_id: &str) -> Option<Drive> {
+        Drive::ALL.into_iter().find(|d| goal_id.starts_with(d.goal_prefix()))
+    }
+}
+
+/// Unmet drive levels in 0.0..=1.0; higher means more pressing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DriveState {
+    pub competence: f32,
+    pub autonomy: f32,
+    pub relatedness: f32,
+    pub curiosity: f32,
+}
+
+impl DriveState {
+    pub fn level(&self, drive: Drive) -> f32 {
+        match drive {
+            Drive::Competence => self.competence,
+            Drive::Autonomy => self.autonomy,
+            Drive::Relatedness => self.relatedness,
+            Drive::Curiosity => self.curiosity,
+        }
+    }
+
+    fn level_mut(&mut self, drive: Drive) -> &mut f32 {
+        match drive {
+            Drive::Competence => &mut self.competence,
+            Drive::Autonomy => &mut self.autonomy,
+            Drive::Relatedness => &mut self.relatedness,
+            Drive::Curiosity => &mut self.curiosity,
+        }
+    }
+
+    /// Grows every drive for `elapsed` time without it being met.
+    /// Curiosity grows faster the more curious Astra's temperament.
+    pub fn accumulate(&mut self, elapsed: Duration, curiosity_level: f32) {
+        // Drive growth per hour while unmet.
+        const HOURLY_GROWTH: f32 = 0.25;
+        let hours = elapsed.as_secs_f32() / 3600.0;
+        for drive in Drive::ALL {
+            let rate = match drive {
+                Drive::Curiosity => HOURLY_GROWTH * (0.5 + curiosity_level.clamp(0.0, 1.0)),
+                _ => HOURLY_GROWTH,
+            };
+            let level = self.level_mut(drive);
+            *level = (*level + rate * hours).min(1.0);
+        }
+    }
+
+    /// Reduces a drive after it was (partly) met.
+    pub fn satisfy(&mut self, drive: Drive, amount: f32) {
+        let level = self.level_mut(drive);
+        *level = (*level - amount.max(0.0)).max(0.0);
+    }
+
+    /// The strongest drive at or above `threshold`.
+    pub fn most_pressing(&self, threshold: f32) -> Option<Drive> {
+        Drive::ALL
+            .into_iter()
+            .filter(|d| self.level(*d) >= threshold)
+            .max_by(|a, b| self.level(*a).total_cmp(&self.level(*b)))
+    }
+}
+
+/// What the environment offers for intrinsic goals to work on.
+#[derive(Debug, Clone, Default)]
+pub struct IdleContext {
+    /// Capability (action id) with the worst track record
+    pub weakest_capability: Option<String>,
+    /// Recent topic worth exploring further
+    pub topic: Option<String>,
+}
+
+/// Drive level above which an idle Astra sets itself a goal.
+pub const DRIVE_GOAL_THRESHOLD: f32 = 0.7;
+
+/// Turns the most pressing drive into an intrinsic goal. Intrinsic goals get
+/// a modest priority so that any external request outranks them.
+pub fn generate_intrinsic_goal(drives: &DriveState, context: &IdleContext) -> Option<Goal> {
+    let drive = drives.most_pressing(DRIVE_GOAL_THRESHOLD)?;
+    let priority = 2 + (drives.level(drive) * 3.0) as i32;
+
+    let (id, description, key) = match drive {
+        Drive::Competence => {
+            let capability = context.weakest_capability.as_deref().unwrap_or("general");
+            (
+                format!("practice_{}", capability),
+                format!("Practice '{}', a capability that has been unreliable", capability),
+                "capability_practiced",
+            )
+        }
+        Drive::Autonomy => (
+            "self_directed_project".to_string(),
+            "Pick and pursue a project of my own choosing".to_string(),
+            "self_directed_progress",
+        ),
+        Drive::Relatedness => (
+            "review_memories".to_string(),
+            "Review memories of recent conversations and the people in them".to_string(),
+            "memories_reviewed",
+        ),
+        Drive::Curiosity => {
+            let topic = context.topic.as_deref().unwrap_or("unfamiliar_topics");
+            (
+                format!("explore_{}", topic.replace(' ', "_")),
+                format!("Explore '{}' to learn something new", topic),
+                "knowledge_gap_reduced",
+            )
+        }
+    };
+
+    let mut desired = WorldState::new();
+    desired.insert(key.into(), true);
+    Some(Goal { id, description, desired_state: desired, priority })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unmet_drives_generate_goals() {
+        let mut drives = DriveState::default();
+        let context = IdleContext { weakest_capability: Some("summarize".into()), topic: None };
+        drives.accumulate(Duration::from_secs(3600), 0.5);
+        assert!(generate_intrinsic_goal(&drives, &context).is_none());
+
+        drives.accumulate(Duration::from_secs(3 * 3600), 0.5);
+        drives.satisfy(Drive::Relatedness, 1.0);
+        drives.satisfy(Drive::Autonomy, 1.0);
+        drives.satisfy(Drive::Curiosity, 1.0);
+
+        let goal = generate_intrinsic_goal(&drives, &context).expect("competence goal");
+        assert_eq!(goal.id, "practice_summarize");
+        assert_eq!(Drive::of_goal(&goal.id), Some(Drive::Competence));
+        assert!(goal.priority < 7);
+    }
+}
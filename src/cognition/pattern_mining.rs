@@ -0,0 +1,455 @@
+// ============================================================================
+//                       ASTRA AGI • EPISODE PATTERN MINING
+//        Clustering Episodes & Mining the Situations Strategies Fail In
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Gives reflection an unsupervised view of many episodes at once.
+//       Each episode is reduced to its features: the goal and its category,
+//       the planning strategy used, the context it was planned in (deadline
+//       pressure, load, fatigue), and whether it worked. Episodes are
+//       clustered by those features, and the sets of conditions under which
+//       episodes fail far more often than usual are mined level by level,
+//       Apriori style ("Htn fails when the deadline is pressing"). Patterns
+//       naming a strategy become candidate heuristic rules, to plan with the
+//       strategy that did better in the same situation, which reflection
+//       puts through the self-modification approval workflow.
+//
+//   Core Functions:
+//       • Reduce episodes to goal, strategy, and context features
+//       • Cluster episodes around leaders by feature distance
+//       • Mine frequent failure patterns with support, rate, and lift
+//       • Turn strategy failure patterns into candidate heuristic rules
+//
+//   File:        /src/cognition/pattern_mining.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::planning::planner::PlanningStrategy;
+
+/// Deadline pressure at or above which a deadline counts as pressing.
+const PRESSING_DEADLINE: f32 = 0.7;
+
+/// Cognitive load at or above which load counts as high.
+const HIGH_LOAD: f32 = 0.7;
+
+/// Fatigue at or above which Astra counts as tired.
+const TIRED: f32 = 0.6;
+
+/// Episodes with another strategy needed before a rule may prefer it.
+const MIN_ALTERNATIVE_EPISODES: usize = 2;
+
+/// What reflection keeps of one episode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpisodeFeatures {
+    pub goal_id: String,
+    pub category: Option<String>,
+    pub strategy: PlanningStrategy,
+    /// 0..1, how close the goal's deadline was
+    pub deadline_pressure: f32,
+    pub load: f32,
+    pub fatigue: f32,
+    pub success: bool,
+}
+
+impl EpisodeFeatures {
+    /// Conditions the episode was planned under, before a strategy was chosen.
+    pub fn situation(&self) -> Vec<Condition> {
+        situation(&self.goal_id, self.category.as_deref(), self.deadline_pressure, self.load, self.fatigue)
+    }
+
+    /// The situation and the strategy used in it.
+    pub fn conditions(&self) -> Vec<Condition> {
+        let mut conditions = self.situation();
+        conditions.push(Condition::Strategy(self.strategy));
+        conditions
+    }
+
+    /// The category if known, else the goal itself.
+    fn goal_key(&self) -> &str {
+        self.category.as_deref().unwrap_or(&self.goal_id)
+    }
+}
+
+/// Something true of an episode that a pattern can be made of.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum Condition {
+    Goal(String),
+    Category(String),
+    Strategy(PlanningStrategy),
+    PressingDeadline,
+    HighLoad,
+    Tired,
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::Goal(goal) => write!(f, "the goal is {}", goal),
+            Condition::Category(category) => write!(f, "the goal is about {}", category),
+            Condition::Strategy(strategy) => write!(f, "planning with {:?}", strategy),
+            Condition::PressingDeadline => write!(f, "the deadline is pressing"),
+            Condition::HighLoad => write!(f, "load is high"),
+            Condition::Tired => write!(f, "Astra is tired"),
+        }
+    }
+}
+
+/// Conditions that hold when planning for `goal_id` in the given context.
+pub fn situation(
+    goal_id: &str,
+    category: Option<&str>,
+    deadline_pressure: f32,
+    load: f32,
+    fatigue: f32,
+) -> Vec<Condition> {
+    let mut conditions = vec![Condition::Goal(goal_id.to_string())];
+    conditions.extend(category.map(|c| Condition::Category(c.to_string())));
+    if deadline_pressure >= PRESSING_DEADLINE {
+        conditions.push(Condition::PressingDeadline);
+    }
+    if load >= HIGH_LOAD {
+        conditions.push(Condition::HighLoad);
+    }
+    if fatigue >= TIRED {
+        conditions.push(Condition::Tired);
+    }
+    conditions
+}
+
+/// Episodes alike in goal, strategy, and context.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EpisodeCluster {
+    /// Positions of the members among the clustered episodes
+    pub members: Vec<usize>,
+    /// Category, or goal when uncategorized, of the cluster's first episode
+    pub goal: String,
+    pub strategy: PlanningStrategy,
+    pub success_rate: f32,
+    /// Mean context of the members
+    pub deadline_pressure: f32,
+    pub load: f32,
+    pub fatigue: f32,
+}
+
+impl EpisodeCluster {
+    fn distance(&self, episode: &EpisodeFeatures) -> f32 {
+        let mismatch = |same: bool| if same { 0.0 } else { 1.0 };
+        mismatch(self.goal == episode.goal_key())
+            + mismatch(self.strategy == episode.strategy)
+            + (self.deadline_pressure - episode.deadline_pressure).abs()
+            + (self.load - episode.load).abs()
+            + (self.fatigue - episode.fatigue).abs()
+    }
+
+    fn admit(&mut self, index: usize, episode: &EpisodeFeatures) {
+        let n = self.members.len() as f32;
+        let mean = |old: f32, new: f32| (old * n + new) / (n + 1.0);
+        self.success_rate = mean(self.success_rate, if episode.success { 1.0 } else { 0.0 });
+        self.deadline_pressure = mean(self.deadline_pressure, episode.deadline_pressure);
+        self.load = mean(self.load, episode.load);
+        self.fatigue = mean(self.fatigue, episode.fatigue);
+        self.members.push(index);
+    }
+}
+
+/// Clusters episodes in order: each joins the nearest cluster whose mean
+/// is within `radius`, or leads a new one. A differing goal or strategy
+/// counts 1 towards the distance and each context feature its difference,
+/// so a radius below 1 keeps clusters to one goal and strategy.
+pub fn cluster_episodes(episodes: &[EpisodeFeatures], radius: f32) -> Vec<EpisodeCluster> {
+    let mut clusters: Vec<EpisodeCluster> = Vec::new();
+    for (index, episode) in episodes.iter().enumerate() {
+        let nearest = clusters
+            .iter_mut()
+            .map(|cluster| (cluster.distance(episode), cluster))
+            .filter(|(distance, _)| *distance <= radius)
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+        match nearest {
+            Some((_, cluster)) => cluster.admit(index, episode),
+            None => clusters.push(EpisodeCluster {
+                members: vec![index],
+                goal: episode.goal_key().to_string(),
+                strategy: episode.strategy,
+                success_rate: if episode.success { 1.0 } else { 0.0 },
+                deadline_pressure: episode.deadline_pressure,
+                load: episode.load,
+                fatigue: episode.fatigue,
+            }),
+        }
+    }
+    clusters
+}
+
+/// Thresholds for mining failure patterns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MiningParams {
+    /// Episodes a pattern must occur in
+    pub min_support: usize,
+    /// Share of its episodes that must have failed
+    pub min_failure_rate: f32,
+    /// How many times the overall failure rate that must be
+    pub min_lift: f32,
+    /// Most conditions in one pattern
+    pub max_conditions: usize,
+}
+
+impl Default for MiningParams {
+    fn default() -> Self {
+        MiningParams { min_support: 3, min_failure_rate: 0.6, min_lift: 1.5, max_conditions: 3 }
+    }
+}
+
+/// Conditions under which episodes fail unusually often.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailurePattern {
+    pub conditions: Vec<Condition>,
+    /// Episodes the conditions held in
+    pub episodes: usize,
+    pub failures: usize,
+    /// Failure rate over the overall failure rate
+    pub lift: f32,
+}
+
+impl FailurePattern {
+    pub fn failure_rate(&self) -> f32 {
+        self.failures as f32 / self.episodes.max(1) as f32
+    }
+
+    /// The strategy the pattern is about, if it names one.
+    pub fn strategy(&self) -> Option<PlanningStrategy> {
+        self.conditions.iter().find_map(|c| match c {
+            Condition::Strategy(strategy) => Some(*strategy),
+            _ => None,
+        })
+    }
+
+    /// The pattern's conditions other than its strategy.
+    pub fn situation(&self) -> Vec<Condition> {
+        self.conditions.iter().filter(|c| !matches!(c, Condition::Strategy(_))).cloned().collect()
+    }
+
+    /// E.g. "Htn fails when the deadline is pressing (4 of 5 episodes)".
+    pub fn describe(&self) -> String {
+        let when = join_conditions(&self.situation());
+        let subject = match self.strategy() {
+            Some(strategy) => format!("{:?} fails", strategy),
+            None => "Episodes fail".to_string(),
+        };
+        match when.is_empty() {
+            true => format!("{} ({} of {} episodes)", subject, self.failures, self.episodes),
+            false => format!("{} when {} ({} of {} episodes)", subject, when, self.failures, self.episodes),
+        }
+    }
+}
+
+fn join_conditions(conditions: &[Condition]) -> String {
+    conditions.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" and ")
+}
+
+/// Mines the sets of conditions under which episodes fail at least
+/// `min_failure_rate` of the time and `min_lift` times as often as episodes
+/// overall. Sets are grown one condition at a time from those occurring in
+/// `min_support` episodes. A pattern is left out when one of its subsets
+/// already fails as often, since the extra conditions explain nothing.
+/// Most failures first.
+pub fn mine_failure_patterns(episodes: &[EpisodeFeatures], params: &MiningParams) -> Vec<FailurePattern> {
+    let rows: Vec<(Vec<Condition>, bool)> = episodes.iter().map(|e| (e.conditions(), e.success)).collect();
+    let overall_failures = rows.iter().filter(|(_, success)| !success).count();
+    if overall_failures == 0 {
+        return Vec::new();
+    }
+    let base_rate = overall_failures as f32 / rows.len() as f32;
+
+    // Every condition of a failed episode, in first-seen order
+    let mut items: Vec<Condition> = Vec::new();
+    for (conditions, _) in rows.iter().filter(|(_, success)| !success) {
+        for condition in conditions {
+            if !items.contains(condition) {
+                items.push(condition.clone());
+            }
+        }
+    }
+    let count = |set: &[usize]| -> (usize, usize) {
+        let holding = rows.iter().filter(|(conditions, _)| set.iter().all(|&i| conditions.contains(&items[i])));
+        holding.fold((0, 0), |(n, failed), (_, success)| (n + 1, failed + usize::from(!success)))
+    };
+
+    let mut found: Vec<(Vec<usize>, FailurePattern)> = Vec::new();
+    let mut level: Vec<Vec<usize>> = (0..items.len()).map(|i| vec![i]).collect();
+    for _ in 0..params.max_conditions {
+        let mut next = Vec::new();
+        for set in level {
+            let (n, failures) = count(&set);
+            if n < params.min_support || failures == 0 {
+                continue;
+            }
+            let rate = failures as f32 / n as f32;
+            let explained = found
+                .iter()
+                .any(|(subset, pattern)| subset.iter().all(|i| set.contains(i)) && pattern.failure_rate() >= rate);
+            if rate >= params.min_failure_rate && rate / base_rate >= params.min_lift && !explained {
+                let conditions = set.iter().map(|&i| items[i].clone()).collect();
+                found.push((set.clone(), FailurePattern { conditions, episodes: n, failures, lift: rate / base_rate }));
+            }
+            let last = *set.last().unwrap_or(&0);
+            for extra in last + 1..items.len() {
+                let mut grown = set.clone();
+                grown.push(extra);
+                next.push(grown);
+            }
+        }
+        level = next;
+    }
+
+    let mut patterns: Vec<FailurePattern> = found.into_iter().map(|(_, pattern)| pattern).collect();
+    patterns.sort_by(|a, b| b.failures.cmp(&a.failures).then(b.lift.total_cmp(&a.lift)));
+    patterns
+}
+
+/// A learned exception to how strategies are chosen: in a situation where
+/// one strategy has kept failing, plan with one that did better.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeuristicRule {
+    /// Conditions that must all hold for the rule to apply
+    pub when: Vec<Condition>,
+    pub avoid: PlanningStrategy,
+    pub prefer: PlanningStrategy,
+    /// How often `avoid` failed in the situation when the rule was mined
+    pub confidence: f32,
+    /// Episodes the rule was mined from
+    pub support: usize,
+}
+
+impl HeuristicRule {
+    /// Whether the rule overrides `strategy` in `situation`.
+    pub fn applies(&self, strategy: PlanningStrategy, situation: &[Condition]) -> bool {
+        strategy == self.avoid && self.when.iter().all(|c| situation.contains(c))
+    }
+
+    /// Whether both rules avoid the same strategy in the same situation.
+    pub fn same_situation(&self, other: &HeuristicRule) -> bool {
+        self.avoid == other.avoid
+            && self.when.len() == other.when.len()
+            && self.when.iter().all(|c| other.when.contains(c))
+    }
+
+    pub fn describe(&self) -> String {
+        format!("plan with {:?} instead of {:?} when {}", self.prefer, self.avoid, join_conditions(&self.when))
+    }
+}
+
+/// Rules for the patterns that name a strategy and a situation, each with
+/// the rationale for it. A rule prefers the strategy that succeeded most
+/// often in the same situation, if it has been tried there enough and did
+/// better than the failing one.
+pub fn candidate_rules(episodes: &[EpisodeFeatures], patterns: &[FailurePattern]) -> Vec<(HeuristicRule, String)> {
+    let mut rules = Vec::new();
+    for pattern in patterns {
+        let (Some(avoid), when) = (pattern.strategy(), pattern.situation()) else {
+            continue;
+        };
+        if when.is_empty() {
+            // Failing everywhere is for strategy biases, not a rule
+            continue;
+        }
+
+        let mut tried: Vec<(PlanningStrategy, usize, usize)> = Vec::new();
+        for episode in episodes.iter().filter(|e| e.strategy != avoid) {
+            if !when.iter().all(|c| episode.situation().contains(c)) {
+                continue;
+            }
+            match tried.iter_mut().find(|(strategy, _, _)| *strategy == episode.strategy) {
+                Some((_, n, successes)) => {
+                    *n += 1;
+                    *successes += usize::from(episode.success);
+                }
+                None => tried.push((episode.strategy, 1, usize::from(episode.success))),
+            }
+        }
+        let success_rate = |n: usize, successes: usize| successes as f32 / n as f32;
+        let best = tried
+            .into_iter()
+            .filter(|&(_, n, successes)| {
+                n >= MIN_ALTERNATIVE_EPISODES && success_rate(n, successes) > 1.0 - pattern.failure_rate()
+            })
+            .max_by(|a, b| success_rate(a.1, a.2).total_cmp(&success_rate(b.1, b.2)).then(a.1.cmp(&b.1)));
+        let Some((prefer, n, successes)) = best else {
+            continue;
+        };
+
+        let rule = HeuristicRule { when, avoid, prefer, confidence: pattern.failure_rate(), support: pattern.episodes };
+        let rationale = format!(
+            "{}, while {:?} succeeded in {} of {} such episodes",
+            pattern.describe(),
+            prefer,
+            successes,
+            n
+        );
+        rules.push((rule, rationale));
+    }
+    rules
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn episode(strategy: PlanningStrategy, deadline_pressure: f32, success: bool) -> EpisodeFeatures {
+        EpisodeFeatures {
+            goal_id: "send_report".into(),
+            category: Some("messages".into()),
+            strategy,
+            deadline_pressure,
+            load: 0.3,
+            fatigue: 0.2,
+            success,
+        }
+    }
+
+    #[test]
+    fn test_mines_strategy_failing_under_deadline_into_rule() {
+        use PlanningStrategy::*;
+
+        let mut episodes = Vec::new();
+        for i in 0..4 {
+            episodes.push(episode(Htn, 0.9, i == 0));
+            episodes.push(episode(Htn, 0.1, true));
+            episodes.push(episode(Goap, 0.1, i != 0));
+        }
+        episodes.push(episode(Goap, 0.9, true));
+        episodes.push(episode(Goap, 0.8, true));
+
+        let clusters = cluster_episodes(&episodes, 0.5);
+        assert_eq!(clusters.len(), 4);
+        let urgent_htn = clusters.iter().find(|c| c.strategy == Htn && c.deadline_pressure > 0.5).unwrap();
+        assert_eq!(urgent_htn.members.len(), 4);
+        assert!((urgent_htn.success_rate - 0.25).abs() < 1e-6);
+
+        let patterns = mine_failure_patterns(&episodes, &MiningParams::default());
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].conditions, [Condition::PressingDeadline, Condition::Strategy(Htn)]);
+        assert_eq!(patterns[0].describe(), "Htn fails when the deadline is pressing (3 of 4 episodes)");
+
+        let rules = candidate_rules(&episodes, &patterns);
+        assert_eq!(rules.len(), 1);
+        let (rule, rationale) = &rules[0];
+        assert_eq!((rule.avoid, rule.prefer), (Htn, Goap));
+        assert!(rationale.ends_with("while Goap succeeded in 2 of 2 such episodes"));
+        assert!(rule.applies(Htn, &episode(Htn, 0.95, true).situation()));
+        assert!(!rule.applies(Htn, &episode(Htn, 0.2, true).situation()));
+
+        // Nothing to learn from a run without failures
+        let successes: Vec<_> = episodes.into_iter().filter(|e| e.success).collect();
+        assert!(mine_failure_patterns(&successes, &MiningParams::default()).is_empty());
+    }
+}
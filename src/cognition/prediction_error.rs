@@ -0,0 +1,230 @@
+// ============================================================================
+//                     ASTRA AGI • PREDICTION ERROR LEARNING
+//        Letting Surprising Observations Revise What Astra Believes
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Closes the loop between acting and believing. Every action predicts
+//       the world it leaves behind, and execution monitoring notices when the
+//       world disagrees. When the disagreement is strong—the world reports the
+//       opposite of an effect Astra was confident in—the beliefs behind the
+//       prediction are weakened through the epistemic reasoner: the action
+//       model that declared the effect, and facts in the knowledge base that
+//       assert the predicted state of the same subject, read off world-state
+//       keys written as atoms such as `light_on(lamp)`. Each weakening is recorded in the belief
+//       ledger, and the surprise comes back as a stimulus so Astra can wonder
+//       about it.
+//
+//   Core Functions:
+//       • Tell strong prediction errors apart from silence and weak doubts
+//       • Weaken contradicted facts through the epistemic reasoner
+//       • Record action model revisions in the belief ledger
+//       • Turn surprise into a stimulus for the cognitive loop
+//
+//   File:        /src/cognition/prediction_error.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use std::collections::HashMap;
+
+use crate::cognition::goal_formation::Stimulus;
+use crate::knowledge::belief_ledger::{BeliefLedger, BeliefRevision, FactKey, RevisionOutcome};
+use crate::knowledge::epistemic_reasoner::EpistemicReasoner;
+use crate::knowledge::extended_ontology::{name_entity_id, Fact, OntologyManager, Provenance};
+use crate::planning::monitor::ExpectationViolation;
+
+/// Confidence an effect must have had for its contradiction to count as strong.
+pub const STRONG_PREDICTION_ERROR: f32 = 0.6;
+
+/// Source of the stimuli and ledger evidence prediction errors produce.
+pub const SURPRISE_SOURCE: &str = "surprise";
+
+/// An effect an action was predicted to have, and what became of the
+/// confidence in it once the world disagreed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PredictionError {
+    pub violation: ExpectationViolation,
+    /// Confidence in the effect before the violation
+    pub predicted_confidence: f32,
+    /// Confidence in the effect after it
+    pub revised_confidence: f32,
+}
+
+impl PredictionError {
+    /// How strongly the observation went against the prediction: the
+    /// confidence that was placed in it, or nothing if the world did not
+    /// report the key at all.
+    pub fn strength(&self) -> f32 {
+        if self.violation.observed.is_some() {
+            self.predicted_confidence
+        } else {
+            0.0
+        }
+    }
+
+    pub fn is_strong(&self) -> bool {
+        self.strength() >= STRONG_PREDICTION_ERROR
+    }
+
+    fn describe(&self) -> String {
+        let observed = self.violation.observed.map_or("unknown".to_string(), |o| o.to_string());
+        format!(
+            "Expected '{}' to make {} {} but it was {}",
+            self.violation.action_id, self.violation.key, self.violation.expected, observed
+        )
+    }
+}
+
+/// What the epistemic update changed.
+#[derive(Debug, Clone, Default)]
+pub struct SurpriseUpdate {
+    /// Facts as weakened, also added to the knowledge base
+    pub weakened: Vec<Fact>,
+    /// Sequence numbers of the belief ledger entries written
+    pub ledger_entries: Vec<u64>,
+    /// For the cognitive loop, if any error was strong
+    pub stimulus: Option<Stimulus>,
+}
+
+/// The fact a world-state key predicts: `light_on(lamp)` expected true or
+/// false is `lamp light_on true` or `false`, and `at(robot,kitchen)`
+/// expected true is `robot at kitchen`. Keys without a subject, such as
+/// `light_on`, and atoms expected not to hold name no fact.
+fn predicted_fact(violation: &ExpectationViolation) -> Option<FactKey> {
+    let (predicate, args) = violation.key.strip_suffix(')')?.split_once('(')?;
+    let args: Vec<&str> = args.split(',').map(str::trim).collect();
+    let object = match args.as_slice() {
+        [_] => violation.expected.to_string(),
+        [_, object] if violation.expected => object.to_string(),
+        _ => return None,
+    };
+    Some(FactKey { subject: name_entity_id(args[0]), predicate: predicate.trim().to_string(), object })
+}
+
+/// Weakens the beliefs behind strong prediction errors. For each one, the
+/// action model's revision is recorded, and facts asserting what the
+/// violated key predicted of its subject (see `predicted_fact`) are
+/// weakened by the error's strength. Weak errors are ignored.
+pub fn update_beliefs_on_surprise(
+    errors: &[PredictionError],
+    knowledge: &mut OntologyManager,
+    reasoner: &EpistemicReasoner,
+    ledger: &mut BeliefLedger,
+) -> SurpriseUpdate {
+    let strong: Vec<&PredictionError> = errors.iter().filter(|e| e.is_strong()).collect();
+    let mut update = SurpriseUpdate::default();
+    if strong.is_empty() {
+        return update;
+    }
+
+    for error in &strong {
+        let violation = &error.violation;
+        let evidence = Provenance::new(SURPRISE_SOURCE, Some(error.describe()));
+        update.ledger_entries.push(ledger.record(BeliefRevision {
+            sequence: 0,
+            timestamp: 0,
+            fact: FactKey {
+                subject: name_entity_id(&violation.action_id),
+                predicate: "has_effect".to_string(),
+                object: format!("{}={}", violation.key, violation.expected),
+            },
+            old_confidence: error.predicted_confidence,
+            new_confidence: error.revised_confidence,
+            evidence_confidence: error.strength(),
+            evidence: evidence.clone(),
+            method: "action_model".to_string(),
+            parameters: HashMap::new(),
+            outcome: RevisionOutcome::Accepted,
+        }));
+
+        // The latest version of each fact; revisions are appended after it
+        let Some(predicted) = predicted_fact(violation) else { continue };
+        let mut held: HashMap<FactKey, Fact> = HashMap::new();
+        for fact in knowledge.query_facts(None) {
+            if fact.subject == predicted.subject
+                && fact.predicate == predicted.predicate
+                && fact.object.eq_ignore_ascii_case(&predicted.object)
+            {
+                held.insert(FactKey::from(fact), fact.clone());
+            }
+        }
+        for fact in held.into_values() {
+            let weakened = reasoner.weaken_belief_recorded(&fact, error.strength(), evidence.clone(), ledger);
+            update.ledger_entries.extend(ledger.entries().last().map(|r| r.sequence));
+            knowledge.add_fact(weakened.clone());
+            update.weakened.push(weakened);
+        }
+    }
+
+    let descriptions: Vec<String> = strong.iter().map(|e| e.describe()).collect();
+    update.stimulus = Some(Stimulus {
+        source: SURPRISE_SOURCE.to_string(),
+        content: descriptions.join("; "),
+        urgency: strong.iter().map(|e| e.strength()).fold(0.0, f32::max),
+    });
+    update
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(observed: Option<bool>, predicted_confidence: f32) -> PredictionError {
+        PredictionError {
+            violation: ExpectationViolation {
+                action_id: "switch_on".into(),
+                key: "light_on(lamp)".into(),
+                expected: true,
+                observed,
+            },
+            predicted_confidence,
+            revised_confidence: predicted_confidence * 0.8,
+        }
+    }
+
+    #[test]
+    fn test_strong_contradictions_weaken_beliefs_and_surprise() {
+        let mut knowledge = OntologyManager::new();
+        let lamp = Fact {
+            subject: name_entity_id("lamp"),
+            predicate: "light_on".into(),
+            object: "true".into(),
+            confidence: 0.9,
+            provenance: Provenance::new("sensor", None),
+        };
+        knowledge.add_fact(lamp.clone());
+        knowledge.add_fact(Fact { object: "false".into(), ..lamp.clone() });
+        knowledge.add_fact(Fact { subject: name_entity_id("porch light"), ..lamp.clone() });
+        let reasoner = EpistemicReasoner::new();
+        let mut ledger = BeliefLedger::new();
+
+        // Silence and doubtful predictions change nothing
+        let quiet = update_beliefs_on_surprise(&[error(None, 0.9), error(Some(false), 0.5)], &mut knowledge, &reasoner, &mut ledger);
+        assert!(quiet.stimulus.is_none());
+        assert!(ledger.is_empty());
+
+        let update = update_beliefs_on_surprise(&[error(Some(false), 0.8)], &mut knowledge, &reasoner, &mut ledger);
+        assert_eq!(update.weakened.len(), 1);
+        assert!((update.weakened[0].confidence - 0.9 * (1.0 - 0.5 * 0.8)).abs() < 1e-6);
+        assert_eq!(update.ledger_entries, vec![0, 1]);
+        assert_eq!(ledger.entries()[0].method, "action_model");
+        assert_eq!(ledger.latest_accepted(&FactKey::from(&lamp)).unwrap().new_confidence, update.weakened[0].confidence);
+
+        let stimulus = update.stimulus.unwrap();
+        assert_eq!(stimulus.source, SURPRISE_SOURCE);
+        assert!(stimulus.content.contains("light_on(lamp) true but it was false"));
+        assert_eq!(stimulus.urgency, 0.8);
+
+        // A key naming no subject revises only the action model
+        let mut unowned = error(Some(false), 0.8);
+        unowned.violation.key = "light_on".into();
+        let update = update_beliefs_on_surprise(&[unowned], &mut knowledge, &reasoner, &mut ledger);
+        assert!(update.weakened.is_empty());
+        assert_eq!(update.ledger_entries.len(), 1);
+    }
+}
@@ -12,18 +12,28 @@
 //       • Analyze thought traces for inefficiencies and blind spots
 //       • Detect emotional instability or motivational imbalance
 //       • Produce heuristic and trait adjustments for consolidation
+//       • Weigh broken commitments against kept ones
+//       • Tell stalled plans apart from slow but advancing ones
+//       • Propose structural changes instead of making them directly
+//       • Propose heuristic rules from mined episode failure patterns
+//       • Propose experiments when two strategies are too close to call
 //
 //   File:        /src/cognition/reflection.rs
 //   Author:      Alex Roussinov
 //   Created:     2026-01-12
-//   Updated:     2026-01-12
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
-use crate::cognition::{CognitiveState, ThoughtTrace};
+use crate::cognition::pattern_mining::{candidate_rules, mine_failure_patterns, EpisodeFeatures, MiningParams};
+use crate::cognition::self_modification::Modification;
+use crate::cognition::{CognitiveState, PlanningHeuristics, ThoughtTrace};
+use crate::planning::planner::PlanningStrategy;
+use crate::runtime::commitments::CommitmentTracker;
+use crate::runtime::plan_runner::{PlanProgress, ProgressPhase};
 
 #[derive(Debug, Clone)]
 pub struct ReflectionDelta {
@@ -46,3 +56,101 @@ pub fn reflect_on_episode(state: &CognitiveState, trace: &ThoughtTrace, success:
         curiosity_adjustment,
     }
 }
+
+/// Reflects on how well Astra keeps its promises. Each broken commitment makes
+/// planning more conservative; a good track record relaxes it slightly.
+pub fn reflect_on_commitments(tracker: &CommitmentTracker) -> ReflectionDelta {
+    let broken = tracker.broken().len() as f32;
+    let planning_bias_adjustment = match tracker.fulfillment_rate() {
+        Some(rate) if rate >= 0.9 => 0.01,
+        Some(_) => -0.02 * broken.min(5.0),
+        None => 0.0,
+    };
+
+    ReflectionDelta {
+        planning_bias_adjustment,
+        emotional_stability_adjustment: if broken > 0.0 { -0.01 } else { 0.0 },
+        curiosity_adjustment: 0.0,
+    }
+}
+
+/// Reflects on plans in flight. Stalled plans are a sign that plans are
+/// being made without the means to carry them out; plans that are merely
+/// slow but still advancing are left alone.
+pub fn reflect_on_plan_progress(progress: &[PlanProgress]) -> ReflectionDelta {
+    let stalled = progress.iter().filter(|p| p.phase == ProgressPhase::Stalled).count() as f32;
+
+    ReflectionDelta {
+        planning_bias_adjustment: -0.02 * stalled.min(5.0),
+        emotional_stability_adjustment: if stalled > 0.0 { -0.01 } else { 0.0 },
+        curiosity_adjustment: if stalled > 0.0 { 0.01 } else { 0.0 },
+    }
+}
+
+/// Reflection tunes strategy biases but never switches strategy itself.
+/// When another strategy's bias clearly leads the preferred one, it proposes
+/// the switch for approval instead.
+pub fn propose_strategy_switch(state: &CognitiveState) -> Option<(Modification, String)> {
+    const LEAD_REQUIRED: f32 = 0.2;
+
+    let h = &state.heuristics;
+    let biases = [
+        (PlanningStrategy::Goap, h.goap_bias),
+        (PlanningStrategy::Htn, h.htn_bias),
+        (PlanningStrategy::Reactive, h.reactive_bias),
+        (PlanningStrategy::BehaviorTree, h.bt_bias),
+    ];
+    let current = biases.iter().find(|(s, _)| *s == h.preferred_strategy).map(|(_, b)| *b)?;
+    let (best, bias) = biases.into_iter().max_by(|a, b| a.1.total_cmp(&b.1))?;
+
+    (best != h.preferred_strategy && bias - current >= LEAD_REQUIRED).then(|| {
+        (
+            Modification::SetPreferredStrategy { strategy: best },
+            format!(
+                "{:?} bias ({:.2}) leads {:?} ({:.2}) after recent outcomes",
+                best, bias, h.preferred_strategy, current
+            ),
+        )
+    })
+}
+
+/// Mines recent episodes for situations in which a strategy keeps failing
+/// and proposes a rule to plan differently in each, unless an equivalent
+/// rule is already held in `heuristics`.
+pub fn propose_heuristic_rules(
+    episodes: &[EpisodeFeatures],
+    params: &MiningParams,
+    heuristics: &PlanningHeuristics,
+) -> Vec<(Modification, String)> {
+    let patterns = mine_failure_patterns(episodes, params);
+    candidate_rules(episodes, &patterns)
+        .into_iter()
+        .filter(|(rule, _)| {
+            !heuristics.rules.iter().any(|held| held.same_situation(rule) && held.prefer == rule.prefer)
+        })
+        .map(|(rule, rationale)| (Modification::AdoptRule { rule }, rationale))
+        .collect()
+}
+
+/// When the two leading planning strategies in `heuristics` are too close
+/// to call, proposes comparing them by experiment rather than letting the
+/// next few outcomes decide. Behavior trees are run only for goals that
+/// have one, so they are never compared.
+pub fn propose_strategy_experiment(
+    heuristics: &PlanningHeuristics,
+) -> Option<(PlanningStrategy, PlanningStrategy, String)> {
+    const TOO_CLOSE: f32 = 0.1;
+
+    let mut biases: Vec<(PlanningStrategy, f32)> =
+        [PlanningStrategy::Goap, PlanningStrategy::Htn, PlanningStrategy::Reactive]
+            .into_iter()
+            .map(|strategy| (strategy, heuristics.bias(strategy)))
+            .collect();
+    biases.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let [(first, lead), (second, runner_up), ..] = biases[..] else {
+        return None;
+    };
+    (lead - runner_up < TOO_CLOSE).then(|| {
+        (first, second, format!("{:?} ({:.2}) and {:?} ({:.2}) are too close to call", first, lead, second, runner_up))
+    })
+}
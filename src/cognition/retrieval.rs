@@ -0,0 +1,424 @@
+// ============================================================================
+//                        ASTRA AGI • HYBRID RETRIEVAL
+//        One Ranked Evidence List from Facts, Memories, and Passages
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Gathers the evidence Astra answers a question from. Three retrievers
+//       look at the question differently: a symbolic one matches the
+//       entities and words it names against knowledge-base facts, a
+//       full-text one ranks narrative memories by BM25, and a semantic one
+//       finds the nearest ingested passages by embedding. Their rankings
+//       are fused with reciprocal-rank fusion, which needs no calibration
+//       between their very different scores, and the same evidence found
+//       more than once is merged so it counts once, with the credit of
+//       every retriever that found it. Retrieval on a user's behalf sees
+//       only their own memories and no facts other users taught.
+//
+//   Core Functions:
+//       • Retrieve facts by entity name and word overlap
+//       • Retrieve narrative events by BM25 over their descriptions
+//       • Retrieve passages by vector similarity
+//       • Fuse the rankings and merge duplicate evidence
+//       • Scope memories and facts to the user asking
+//
+//   File:        /src/cognition/retrieval.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use std::collections::{HashMap, HashSet};
+
+use crate::knowledge::belief_ledger::FactKey;
+use crate::knowledge::embedding::Embedder;
+use crate::knowledge::extended_ontology::{name_entity_id, EntityId, Fact, OntologyManager};
+use crate::knowledge::vector_store::{MetadataFilter, VectorStore, VectorStoreError};
+use crate::memory::conversation_summary::content_words;
+use crate::memory::narrative_memory::{EventId, NarrativeEvent, NarrativeMemory};
+
+/// Longest run of question words tried as an entity name.
+const MAX_NAME_WORDS: usize = 4;
+/// Score for a fact about an entity the question names, on top of word
+/// overlap.
+const NAMED_SUBJECT_SCORE: f32 = 2.0;
+const BM25_K1: f32 = 1.2;
+const BM25_B: f32 = 0.75;
+
+/// Which retriever found a piece of evidence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Retriever {
+    Symbolic,
+    FullText,
+    Semantic,
+}
+
+/// What a piece of evidence is.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EvidenceKey {
+    Fact(FactKey),
+    Event(EventId),
+    /// A vector store record id
+    Passage(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evidence {
+    pub key: EvidenceKey,
+    pub text: String,
+    /// Where the evidence came from, such as a fact's source or a file
+    pub provenance: String,
+    /// How far the evidence itself can be trusted: a fact's confidence, a
+    /// passage's similarity to the question, 1.0 for Astra's own memories
+    pub confidence: f32,
+    /// Fused reciprocal-rank score; only meaningful for ordering
+    pub score: f32,
+    /// Each retriever that found it, with its rank there (0 = first)
+    pub found_by: Vec<(Retriever, usize)>,
+}
+
+/// Ingested passages and the embedder their vectors came from.
+pub struct Passages<'a> {
+    pub store: &'a dyn VectorStore,
+    pub embedder: &'a dyn Embedder,
+    pub filter: MetadataFilter,
+}
+
+/// Tuning for a retrieval.
+#[derive(Debug, Clone)]
+pub struct RetrievalParams {
+    /// Candidates taken from each retriever before fusion
+    pub per_retriever: usize,
+    /// Evidence returned after fusion
+    pub limit: usize,
+    /// Reciprocal-rank constant; larger values flatten the rank bonus
+    pub rrf_k: f32,
+}
+
+impl Default for RetrievalParams {
+    fn default() -> Self {
+        RetrievalParams { per_retriever: 20, limit: 10, rrf_k: 60.0 }
+    }
+}
+
+/// Fuses symbolic, full-text, and semantic retrieval over whichever
+/// sources it is given.
+#[derive(Default)]
+pub struct HybridRetriever<'a> {
+    knowledge: Option<&'a OntologyManager>,
+    memory: Option<&'a NarrativeMemory>,
+    passages: Option<Passages<'a>>,
+    /// The user retrieving, if retrieval is on someone's behalf
+    user_id: Option<&'a str>,
+    pub params: RetrievalParams,
+}
+
+impl<'a> HybridRetriever<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_knowledge(mut self, knowledge: &'a OntologyManager) -> Self {
+        self.knowledge = Some(knowledge);
+        self
+    }
+
+    pub fn with_memory(mut self, memory: &'a NarrativeMemory) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    pub fn with_passages(mut self, passages: Passages<'a>) -> Self {
+        self.passages = Some(passages);
+        self
+    }
+
+    /// Retrieves on behalf of `user_id`: only narrative events that belong
+    /// to them, since events of other users and of no user are private,
+    /// and no facts another user taught.
+    pub fn for_user(mut self, user_id: &'a str) -> Self {
+        self.user_id = Some(user_id);
+        self
+    }
+
+    fn may_see_event(&self, event: &NarrativeEvent) -> bool {
+        self.user_id.is_none_or(|user_id| event.user_id().as_deref() == Some(user_id))
+    }
+
+    fn may_see_fact(&self, fact: &Fact) -> bool {
+        self.user_id.is_none_or(|user_id| fact.provenance.user_id().is_none_or(|taught_by| taught_by == user_id))
+    }
+
+    /// The evidence for `question`, best first.
+    pub fn retrieve(&self, question: &str) -> Result<Vec<Evidence>, VectorStoreError> {
+        let mut rankings = Vec::new();
+        if let Some(knowledge) = self.knowledge {
+            rankings.push((Retriever::Symbolic, self.symbolic(knowledge, question)));
+        }
+        if let Some(memory) = self.memory {
+            rankings.push((Retriever::FullText, self.full_text(memory, question)));
+        }
+        if let Some(passages) = &self.passages {
+            rankings.push((Retriever::Semantic, self.semantic(passages, question)?));
+        }
+        Ok(fuse(rankings, &self.params))
+    }
+
+    /// Facts about entities the question names, and facts sharing its
+    /// words, ranked by how much of the question they cover.
+    fn symbolic(&self, knowledge: &OntologyManager, question: &str) -> Vec<Evidence> {
+        let words: Vec<String> = question
+            .split(|c: char| !c.is_alphanumeric() && c != '\'' && c != '-')
+            .filter(|w| !w.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        let mut named: HashMap<EntityId, String> = HashMap::new();
+        for length in 1..=MAX_NAME_WORDS.min(words.len()) {
+            for window in words.windows(length) {
+                let name = window.join(" ");
+                named.entry(name_entity_id(&name)).or_insert(name);
+            }
+        }
+        let cues: HashSet<String> = content_words(question).collect();
+        let facts = knowledge.query_facts(None);
+        let names: HashMap<EntityId, &str> =
+            facts.iter().filter(|f| f.predicate == "name").map(|f| (f.subject, f.object.as_str())).collect();
+
+        // Name facts label entities rather than say anything about them
+        let mut scored: Vec<(f32, &Fact)> = facts
+            .iter()
+            .filter(|fact| fact.predicate != "name" && self.may_see_fact(fact))
+            .filter_map(|fact| {
+                let overlap = content_words(&fact.object)
+                    .chain(content_words(&fact.predicate))
+                    .filter(|word| cues.contains(word))
+                    .count() as f32;
+                let named_bonus = if named.contains_key(&fact.subject) { NAMED_SUBJECT_SCORE } else { 0.0 };
+                let score = overlap + named_bonus;
+                (score > 0.0).then_some((score * fact.confidence, *fact))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored
+            .into_iter()
+            .take(self.params.per_retriever)
+            .map(|(_, fact)| {
+                let subject = names
+                    .get(&fact.subject)
+                    .copied()
+                    .or(named.get(&fact.subject).map(String::as_str))
+                    .map_or_else(|| format!("entity {}", fact.subject), str::to_string);
+                Evidence {
+                    key: EvidenceKey::Fact(FactKey::from(fact)),
+                    text: format!("{} {} {}", subject, fact.predicate.replace('_', " "), fact.object),
+                    provenance: fact.provenance.source_name.clone(),
+                    confidence: fact.confidence,
+                    score: 0.0,
+                    found_by: Vec::new(),
+                }
+            })
+            .collect()
+    }
+
+    /// Narrative events ranked by BM25 over their descriptions.
+    fn full_text(&self, memory: &NarrativeMemory, question: &str) -> Vec<Evidence> {
+        let cues: HashSet<String> = content_words(question).collect();
+        let events: Vec<&NarrativeEvent> = memory.events.iter().filter(|e| self.may_see_event(e)).collect();
+        if cues.is_empty() || events.is_empty() {
+            return Vec::new();
+        }
+        let documents: Vec<Vec<String>> = events.iter().map(|e| content_words(&e.description).collect()).collect();
+        let count = documents.len() as f32;
+        let average_length = documents.iter().map(Vec::len).sum::<usize>() as f32 / count;
+        let idf: HashMap<&String, f32> = cues
+            .iter()
+            .map(|cue| {
+                let containing = documents.iter().filter(|words| words.contains(cue)).count() as f32;
+                (cue, ((count - containing + 0.5) / (containing + 0.5) + 1.0).ln())
+            })
+            .collect();
+
+        let mut scored: Vec<(f32, usize)> = documents
+            .iter()
+            .enumerate()
+            .filter_map(|(index, words)| {
+                let length_norm = 1.0 - BM25_B + BM25_B * words.len() as f32 / average_length.max(1.0);
+                let score: f32 = cues
+                    .iter()
+                    .map(|cue| {
+                        let frequency = words.iter().filter(|w| *w == cue).count() as f32;
+                        idf[cue] * frequency * (BM25_K1 + 1.0) / (frequency + BM25_K1 * length_norm)
+                    })
+                    .sum();
+                (score > 0.0).then_some((score, index))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored
+            .into_iter()
+            .take(self.params.per_retriever)
+            .map(|(_, index)| {
+                let event = events[index];
+                Evidence {
+                    key: EvidenceKey::Event(event.id),
+                    text: event.description.clone(),
+                    provenance: format!("memory:{}", event.id),
+                    confidence: 1.0,
+                    score: 0.0,
+                    found_by: Vec::new(),
+                }
+            })
+            .collect()
+    }
+
+    /// Passages nearest the question's embedding.
+    fn semantic(&self, passages: &Passages, question: &str) -> Result<Vec<Evidence>, VectorStoreError> {
+        let query = passages.embedder.embed(question);
+        let hits = passages.store.search(&query, self.params.per_retriever, &passages.filter)?;
+        Ok(hits
+            .into_iter()
+            .filter(|hit| hit.score > 0.0)
+            .map(|hit| {
+                let text = ["snippet", "heading", "title"]
+                    .iter()
+                    .find_map(|key| hit.metadata.get(*key))
+                    .cloned()
+                    .unwrap_or_else(|| hit.id.clone());
+                Evidence {
+                    key: EvidenceKey::Passage(hit.id),
+                    text,
+                    provenance: hit.provenance,
+                    confidence: hit.score,
+                    score: 0.0,
+                    found_by: Vec::new(),
+                }
+            })
+            .collect())
+    }
+}
+
+/// Evidence text reduced to its words, to spot the same statement reached
+/// through different keys.
+fn normalized(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Reciprocal-rank fusion: each ranking gives an item 1 / (k + rank + 1),
+/// and items found more than once, by key or by identical text, are merged
+/// with their scores summed.
+fn fuse(rankings: Vec<(Retriever, Vec<Evidence>)>, params: &RetrievalParams) -> Vec<Evidence> {
+    let mut fused: Vec<Evidence> = Vec::new();
+    let mut by_key: HashMap<EvidenceKey, usize> = HashMap::new();
+    let mut by_text: HashMap<String, usize> = HashMap::new();
+    for (retriever, ranking) in rankings {
+        for (rank, evidence) in ranking.into_iter().enumerate() {
+            let contribution = 1.0 / (params.rrf_k + rank as f32 + 1.0);
+            let text = normalized(&evidence.text);
+            let existing = by_key.get(&evidence.key).or_else(|| by_text.get(&text)).copied();
+            let index = match existing {
+                Some(index) => {
+                    let merged = &mut fused[index];
+                    merged.confidence = merged.confidence.max(evidence.confidence);
+                    index
+                }
+                None => {
+                    fused.push(evidence.clone());
+                    fused.len() - 1
+                }
+            };
+            let merged = &mut fused[index];
+            // A retriever listing the same thing twice only counts once
+            if !merged.found_by.iter().any(|(r, _)| *r == retriever) {
+                merged.score += contribution;
+                merged.found_by.push((retriever, rank));
+            }
+            by_key.insert(evidence.key, index);
+            by_text.insert(text, index);
+        }
+    }
+    fused.sort_by(|a, b| b.score.total_cmp(&a.score));
+    fused.truncate(params.limit);
+    fused
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::embedding::HashingEmbedder;
+    use crate::knowledge::extended_ontology::Provenance;
+    use crate::knowledge::vector_store::{HnswStore, Metadata, VectorRecord};
+
+    fn fact(subject: &str, predicate: &str, object: &str) -> Fact {
+        Fact {
+            subject: name_entity_id(subject),
+            predicate: predicate.into(),
+            object: object.into(),
+            confidence: 0.9,
+            provenance: Provenance::new("file:/notes/garden.md", None),
+        }
+    }
+
+    #[test]
+    fn test_rankings_are_fused_and_deduplicated() {
+        let mut knowledge = OntologyManager::new();
+        knowledge.add_fact(fact("tomatoes", "name", "Tomatoes"));
+        knowledge.add_fact(fact("tomatoes", "need", "full sun and regular watering"));
+        knowledge.add_fact(fact("basil", "grows_with", "tomatoes"));
+        knowledge.add_fact(fact("granite", "is", "an igneous rock"));
+
+        let mut memory = NarrativeMemory::new(100);
+        memory.add_event("observation", "Tomatoes need full sun and regular watering", None);
+        memory.add_event("observation", "The tomatoes were watered this morning", None);
+        memory.add_event("task_started", "Reorganised the bookshelf", None);
+
+        let embedder = HashingEmbedder { dimensions: 64 };
+        let mut store = HnswStore::new(64);
+        for (id, text) in [("garden#0", "Tomatoes want sun and water every day"), ("rocks#0", "Granite forms from cooled magma")] {
+            let mut metadata = Metadata::new();
+            metadata.insert("snippet".into(), text.into());
+            let record = VectorRecord { id: id.into(), vector: embedder.embed(text), provenance: format!("file:/{}", id), metadata };
+            store.upsert(record).unwrap();
+        }
+
+        let retriever = HybridRetriever::new()
+            .with_knowledge(&knowledge)
+            .with_memory(&memory)
+            .with_passages(Passages { store: &store, embedder: &embedder, filter: MetadataFilter::new() });
+        let evidence = retriever.retrieve("What do tomatoes need?").unwrap();
+
+        // The fact and the identical memory merge into one item found twice
+        assert_eq!(evidence[0].text, "Tomatoes need full sun and regular watering");
+        assert_eq!(evidence[0].found_by, vec![(Retriever::Symbolic, 0), (Retriever::FullText, 0)]);
+        assert_eq!(evidence[0].provenance, "file:/notes/garden.md");
+        assert!(evidence.iter().any(|e| e.key == EvidenceKey::Passage("garden#0".into())));
+        assert!(!evidence.iter().any(|e| e.text.contains("bookshelf") || e.text.contains("igneous")));
+        let keys: HashSet<&EvidenceKey> = evidence.iter().map(|e| &e.key).collect();
+        assert_eq!(keys.len(), evidence.len());
+    }
+
+    #[test]
+    fn test_retrieval_for_a_user_sees_only_their_memories_and_shared_facts() {
+        use crate::metadata::Metadata as EventMetadata;
+
+        let mut knowledge = OntologyManager::new();
+        knowledge.add_fact(fact("tomatoes", "need", "full sun"));
+        knowledge.add_fact(Fact { provenance: Provenance::from_user("ben", None), ..fact("tomatoes", "hide", "ben's spare key") });
+        let mut memory = NarrativeMemory::new(100);
+        memory.add_event("user_message", "My tomatoes need staking", Some(EventMetadata::from([("user_id", "ana")])));
+        memory.add_event("user_message", "My tomatoes got blight", Some(EventMetadata::from([("user_id", "ben")])));
+        memory.add_event("observation", "Astra noticed the tomatoes wilting", None);
+
+        let retriever = HybridRetriever::new().with_knowledge(&knowledge).with_memory(&memory).for_user("ana");
+        let texts: Vec<String> = retriever.retrieve("What about my tomatoes?").unwrap().into_iter().map(|e| e.text).collect();
+        assert!(texts.iter().any(|t| t.contains("staking")));
+        assert!(texts.iter().any(|t| t.contains("full sun")));
+        assert!(!texts.iter().any(|t| t.contains("blight") || t.contains("wilting") || t.contains("spare key")), "{:?}", texts);
+    }
+}
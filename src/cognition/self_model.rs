@@ -11,11 +11,13 @@
 //       • Represent Astra’s current capabilities and limitations
 //       • Track active goals, strategies, and confidence
 //       • Provide introspective summaries for explanation and debugging
+//       • Track the level mastered in each planning capability, moving up
+//         after a run of successes and down after a run of failures
 //
 //   File:        /src/cognition/self_model.rs
 //   Author:      Alex Roussinov
 //   Created:     2026-01-11
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
@@ -23,9 +25,12 @@
 // ============================================================================
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 
 use crate::cognition::CognitiveState;
 use crate::planning::planner::PlanningStrategy;
+use crate::time::AstraTime;
 
 /// High-level description of Astra’s current cognitive stance.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -68,5 +73,142 @@ pub fn strategy_to_string(strategy: PlanningStrategy) -> &'static str {
         PlanningStrategy::Htn => "HTN",
         PlanningStrategy::Goap => "GOAP",
         PlanningStrategy::Reactive => "Reactive",
+        PlanningStrategy::BehaviorTree => "BT",
+    }
+}
+
+/// Successes in a row at a level before training moves up a level.
+pub const PROMOTE_AFTER: u32 = 3;
+/// Failures in a row at a level before training moves down a level.
+pub const DEMOTE_AFTER: u32 = 2;
+
+/// Kinds of planning problem Astra can be better or worse at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanningCapability {
+    /// Goals many steps away
+    LongHorizon,
+    /// Goals among many actions that do not lead to them
+    Distractors,
+    /// Goals made of several independent parts
+    Conjunctive,
+    /// Goals whose obvious route has an action that fails
+    Recovery,
+}
+
+impl PlanningCapability {
+    pub const ALL: [PlanningCapability; 4] = [
+        PlanningCapability::LongHorizon,
+        PlanningCapability::Distractors,
+        PlanningCapability::Conjunctive,
+        PlanningCapability::Recovery,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlanningCapability::LongHorizon => "long_horizon",
+            PlanningCapability::Distractors => "distractors",
+            PlanningCapability::Conjunctive => "conjunctive",
+            PlanningCapability::Recovery => "recovery",
+        }
+    }
+}
+
+impl fmt::Display for PlanningCapability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Training record of one capability.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityRecord {
+    /// Difficulty currently trained at, from 1
+    pub level: u32,
+    /// Successes (positive) or failures (negative) in a row at `level`
+    pub streak: i32,
+    pub attempts: usize,
+    pub successes: usize,
+    /// When each level was reached, oldest first
+    pub milestones: Vec<(AstraTime, u32)>,
+}
+
+impl Default for CapabilityRecord {
+    fn default() -> Self {
+        CapabilityRecord { level: 1, streak: 0, attempts: 0, successes: 0, milestones: Vec::new() }
+    }
+}
+
+impl CapabilityRecord {
+    /// Highest level passed, 0 before any.
+    pub fn mastered(&self) -> u32 {
+        self.level - 1
+    }
+
+    pub fn success_rate(&self) -> f32 {
+        self.successes as f32 / self.attempts.max(1) as f32
+    }
+}
+
+/// How far training has taken each planning capability.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityProfile {
+    records: BTreeMap<PlanningCapability, CapabilityRecord>,
+}
+
+impl CapabilityProfile {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The record of `capability`; untrained capabilities are at level 1.
+    pub fn record(&self, capability: PlanningCapability) -> CapabilityRecord {
+        self.records.get(&capability).cloned().unwrap_or_default()
+    }
+
+    pub fn level(&self, capability: PlanningCapability) -> u32 {
+        self.records.get(&capability).map_or(1, |r| r.level)
+    }
+
+    /// The capability with the lowest level, then the worst success rate;
+    /// untried capabilities count as never succeeding.
+    pub fn weakest(&self) -> PlanningCapability {
+        PlanningCapability::ALL
+            .into_iter()
+            .min_by(|a, b| {
+                let (a, b) = (self.record(*a), self.record(*b));
+                a.level.cmp(&b.level).then(a.success_rate().total_cmp(&b.success_rate()))
+            })
+            .unwrap_or(PlanningCapability::LongHorizon)
+    }
+
+    /// Records an attempt at the current level of `capability`. Returns
+    /// the new level if the attempt moved training up or down one.
+    pub fn observe(&mut self, capability: PlanningCapability, success: bool, at: AstraTime) -> Option<u32> {
+        let record = self.records.entry(capability).or_default();
+        record.attempts += 1;
+        if success {
+            record.successes += 1;
+            record.streak = record.streak.max(0) + 1;
+        } else {
+            record.streak = record.streak.min(0) - 1;
+        }
+
+        let level = if record.streak >= PROMOTE_AFTER as i32 {
+            record.level + 1
+        } else if record.streak <= -(DEMOTE_AFTER as i32) && record.level > 1 {
+            record.level - 1
+        } else {
+            return None;
+        };
+        record.level = level;
+        record.streak = 0;
+        record.milestones.push((at, level));
+        Some(level)
+    }
+
+    /// Levels reached by `capability` over time, oldest first.
+    pub fn milestones(&self, capability: PlanningCapability) -> &[(AstraTime, u32)] {
+        self.records.get(&capability).map_or(&[], |r| &r.milestones)
     }
 }
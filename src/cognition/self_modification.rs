@@ -0,0 +1,375 @@
+// ============================================================================
+//                   ASTRA AGI • SELF-MODIFICATION PROPOSALS
+//        Policy-Gated Structural Changes with a Complete Audit Trail
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Reflection may nudge numeric heuristics on its own, but changes to
+//       how Astra fundamentally operates — switching planning strategy,
+//       re-weighting core values, reshaping personality, adopting mined
+//       heuristic rules — are only proposed.
+//       Each proposal is checked against an approval policy: small changes
+//       can be approved automatically, larger ones wait for a human to sign
+//       off through the API. Approved proposals are applied to whichever
+//       subsystem owns the affected setting, and every step is audited.
+//
+//   Core Functions:
+//       • Describe structural self-modifications and their magnitude
+//       • Auto-approve or hold proposals according to policy
+//       • Record human approvals and rejections
+//       • Apply approved proposals to heuristics, values, and traits
+//       • Adopt heuristic rules automatically above a confidence threshold
+//       • Keep an append-only audit trail of every decision
+//       • Tell unknown proposals from already decided ones
+//
+//   File:        /src/cognition/self_modification.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::cognition::error::CognitionError;
+use crate::cognition::pattern_mining::HeuristicRule;
+use crate::cognition::PlanningHeuristics;
+use crate::emotion::ValueModel;
+use crate::personality::personality::PersonalityTraits;
+use crate::planning::planner::PlanningStrategy;
+
+pub type ProposalId = u64;
+
+/// A structural change Astra would like to make to itself.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Modification {
+    SetPreferredStrategy { strategy: PlanningStrategy },
+    SetValueWeight { value: String, weight: f32 },
+    AdjustTrait { name: String, delta: f32 },
+    /// Plan differently in a situation where a strategy keeps failing
+    AdoptRule { rule: HeuristicRule },
+}
+
+impl Modification {
+    /// Size of the change relative to the current settings, in 0..1. A
+    /// strategy switch or a new rule counts as a full-size change.
+    pub fn magnitude(&self, current: &ModificationTargets) -> f32 {
+        match self {
+            Modification::SetPreferredStrategy { strategy } => match &current.heuristics {
+                Some(h) if h.preferred_strategy == *strategy => 0.0,
+                _ => 1.0,
+            },
+            Modification::SetValueWeight { value, weight } => {
+                let old = current.values.as_ref().and_then(|v| v.get_value(value)).unwrap_or(0.0);
+                (weight - old).abs().min(1.0)
+            }
+            Modification::AdjustTrait { delta, .. } => delta.abs().min(1.0),
+            Modification::AdoptRule { rule } => match &current.heuristics {
+                Some(h) if h.rules.iter().any(|held| held.same_situation(rule) && held.prefer == rule.prefer) => 0.0,
+                _ => 1.0,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ProposalStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Applied,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfModificationProposal {
+    pub id: ProposalId,
+    pub modification: Modification,
+    /// Why reflection thinks the change would help
+    pub rationale: String,
+    pub magnitude: f32,
+    pub status: ProposalStatus,
+    pub created_at: u64,
+}
+
+/// One step in the life of a proposal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: u64,
+    pub proposal_id: ProposalId,
+    /// Who acted: "reflection", "policy", or a human approver
+    pub actor: String,
+    pub action: String,
+}
+
+/// Decides which proposals may be applied without a human.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalPolicy {
+    /// Proposals at or below this magnitude are approved automatically
+    pub auto_approve_below: f32,
+    /// Whether strategy switches may ever be approved automatically
+    pub auto_approve_strategy: bool,
+    /// Whether value weights may ever be changed automatically
+    pub auto_approve_values: bool,
+    /// Heuristic rules at least this confident are adopted automatically,
+    /// whatever their magnitude; none are when unset
+    #[serde(default)]
+    pub auto_adopt_rules_above: Option<f32>,
+}
+
+impl Default for ApprovalPolicy {
+    fn default() -> Self {
+        ApprovalPolicy {
+            auto_approve_below: 0.05,
+            auto_approve_strategy: false,
+            auto_approve_values: false,
+            auto_adopt_rules_above: None,
+        }
+    }
+}
+
+impl ApprovalPolicy {
+    pub fn auto_approves(&self, modification: &Modification, magnitude: f32) -> bool {
+        let kind_allowed = match modification {
+            Modification::SetPreferredStrategy { .. } => self.auto_approve_strategy,
+            Modification::SetValueWeight { .. } => self.auto_approve_values,
+            Modification::AdjustTrait { .. } => true,
+            Modification::AdoptRule { rule } => {
+                return self.auto_adopt_rules_above.is_some_and(|threshold| rule.confidence >= threshold);
+            }
+        };
+        kind_allowed && magnitude <= self.auto_approve_below
+    }
+}
+
+/// The settings a modification may touch. Each owner supplies the parts it
+/// holds; proposals for missing parts stay approved until a caller that
+/// holds them applies them.
+#[derive(Default)]
+pub struct ModificationTargets<'a> {
+    pub heuristics: Option<&'a mut PlanningHeuristics>,
+    pub values: Option<&'a mut ValueModel>,
+    pub traits: Option<&'a mut PersonalityTraits>,
+}
+
+/// Proposals, their policy, and the audit trail.
+#[derive(Debug, Default)]
+pub struct SelfModificationRegistry {
+    pub policy: ApprovalPolicy,
+    proposals: Vec<SelfModificationProposal>,
+    audit: Vec<AuditEntry>,
+    next_id: ProposalId,
+}
+
+impl SelfModificationRegistry {
+    pub fn new(policy: ApprovalPolicy) -> Self {
+        SelfModificationRegistry { policy, proposals: Vec::new(), audit: Vec::new(), next_id: 1 }
+    }
+
+    /// Records a proposal and lets the policy approve it if it may.
+    pub fn propose(
+        &mut self,
+        modification: Modification,
+        rationale: impl Into<String>,
+        current: &ModificationTargets,
+    ) -> ProposalId {
+        let id = self.next_id.max(1);
+        self.next_id = id + 1;
+        let magnitude = modification.magnitude(current);
+        let auto = self.policy.auto_approves(&modification, magnitude);
+
+        self.proposals.push(SelfModificationProposal {
+            id,
+            modification,
+            rationale: rationale.into(),
+            magnitude,
+            status: if auto { ProposalStatus::Approved } else { ProposalStatus::Pending },
+            created_at: now(),
+        });
+        self.log(id, "reflection", format!("proposed (magnitude {:.2})", magnitude));
+        if auto {
+            self.log(id, "policy", "auto-approved");
+        }
+        id
+    }
+
+    pub fn approve(&mut self, id: ProposalId, approver: &str) -> Result<(), CognitionError> {
+        self.decide(id, approver, ProposalStatus::Approved, "approved".to_string())
+    }
+
+    pub fn reject(&mut self, id: ProposalId, approver: &str, reason: &str) -> Result<(), CognitionError> {
+        self.decide(id, approver, ProposalStatus::Rejected, format!("rejected: {}", reason))
+    }
+
+    /// Applies every approved proposal whose target is available.
+    /// Returns the ids applied.
+    pub fn apply_approved(&mut self, targets: &mut ModificationTargets) -> Vec<ProposalId> {
+        let mut applied = Vec::new();
+        for proposal in self.proposals.iter_mut().filter(|p| p.status == ProposalStatus::Approved) {
+            let done = match &proposal.modification {
+                Modification::SetPreferredStrategy { strategy } => match targets.heuristics.as_deref_mut() {
+                    Some(heuristics) => {
+                        heuristics.preferred_strategy = *strategy;
+                        true
+                    }
+                    None => false,
+                },
+                Modification::SetValueWeight { value, weight } => match targets.values.as_deref_mut() {
+                    Some(values) => {
+                        values.update_value(value, *weight);
+                        true
+                    }
+                    None => false,
+                },
+                Modification::AdjustTrait { name, delta } => match targets.traits.as_deref_mut() {
+                    Some(traits) => {
+                        traits.adjust_trait(name, *delta);
+                        true
+                    }
+                    None => false,
+                },
+                Modification::AdoptRule { rule } => match targets.heuristics.as_deref_mut() {
+                    Some(heuristics) => {
+                        heuristics.adopt_rule(rule.clone());
+                        true
+                    }
+                    None => false,
+                },
+            };
+            if done {
+                proposal.status = ProposalStatus::Applied;
+                applied.push(proposal.id);
+            }
+        }
+        for id in &applied {
+            self.log(*id, "runtime", "applied");
+        }
+        applied
+    }
+
+    pub fn get(&self, id: ProposalId) -> Option<&SelfModificationProposal> {
+        self.proposals.iter().find(|p| p.id == id)
+    }
+
+    /// Proposals waiting for a human decision.
+    pub fn pending(&self) -> Vec<&SelfModificationProposal> {
+        self.proposals.iter().filter(|p| p.status == ProposalStatus::Pending).collect()
+    }
+
+    pub fn audit_trail(&self) -> &[AuditEntry] {
+        &self.audit
+    }
+
+    fn decide(&mut self, id: ProposalId, approver: &str, status: ProposalStatus, action: String) -> Result<(), CognitionError> {
+        let proposal = self
+            .proposals
+            .iter_mut()
+            .find(|p| p.id == id)
+            .ok_or(CognitionError::ProposalNotFound(id))?;
+        if proposal.status != ProposalStatus::Pending {
+            return Err(CognitionError::ProposalAlreadyDecided { id, status: proposal.status.clone() });
+        }
+        proposal.status = status;
+        self.log(id, approver, action);
+        Ok(())
+    }
+
+    fn log(&mut self, proposal_id: ProposalId, actor: &str, action: impl Into<String>) {
+        self.audit.push(AuditEntry {
+            timestamp: now(),
+            proposal_id,
+            actor: actor.to_string(),
+            action: action.into(),
+        });
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_large_changes_wait_for_sign_off() {
+        let mut registry = SelfModificationRegistry::new(ApprovalPolicy::default());
+        let mut heuristics = PlanningHeuristics::default();
+        let mut traits = PersonalityTraits::new();
+        let openness = traits.openness;
+
+        let switch = {
+            let current = ModificationTargets { heuristics: Some(&mut heuristics), ..Default::default() };
+            registry.propose(
+                Modification::SetPreferredStrategy { strategy: PlanningStrategy::Htn },
+                "HTN plans have succeeded more often",
+                &current,
+            )
+        };
+        let nudge = registry.propose(
+            Modification::AdjustTrait { name: "openness".into(), delta: 0.02 },
+            "Curiosity goals went well",
+            &ModificationTargets::default(),
+        );
+        assert_eq!(registry.pending().len(), 1);
+
+        let mut targets = ModificationTargets {
+            heuristics: Some(&mut heuristics),
+            traits: Some(&mut traits),
+            ..Default::default()
+        };
+        assert_eq!(registry.apply_approved(&mut targets), vec![nudge]);
+
+        registry.approve(switch, "operator").unwrap();
+        assert!(matches!(
+            registry.reject(switch, "operator", "too late"),
+            Err(CognitionError::ProposalAlreadyDecided { status: ProposalStatus::Approved, .. })
+        ));
+        assert!(matches!(registry.approve(999, "operator"), Err(CognitionError::ProposalNotFound(999))));
+        assert_eq!(registry.apply_approved(&mut targets), vec![switch]);
+        drop(targets);
+
+        assert_eq!(heuristics.preferred_strategy, PlanningStrategy::Htn);
+        assert!((traits.openness - (openness + 0.02).min(1.0)).abs() < 1e-6);
+        let actors: Vec<&str> = registry.audit_trail().iter().map(|e| e.actor.as_str()).collect();
+        assert!(actors.contains(&"policy") && actors.contains(&"operator"));
+    }
+
+    #[test]
+    fn test_confident_rules_are_adopted_automatically() {
+        use crate::cognition::pattern_mining::Condition;
+
+        let policy = ApprovalPolicy { auto_adopt_rules_above: Some(0.8), ..Default::default() };
+        let mut registry = SelfModificationRegistry::new(policy);
+        let mut heuristics = PlanningHeuristics::default();
+        let rule = |confidence: f32| HeuristicRule {
+            when: vec![Condition::PressingDeadline],
+            avoid: PlanningStrategy::Htn,
+            prefer: PlanningStrategy::Goap,
+            confidence,
+            support: 5,
+        };
+
+        let none = ModificationTargets::default();
+        let doubtful = registry.propose(Modification::AdoptRule { rule: rule(0.6) }, "3 of 5 failed", &none);
+        let sure = registry.propose(Modification::AdoptRule { rule: rule(0.9) }, "9 of 10 failed", &none);
+        assert_eq!(registry.pending().len(), 1);
+        assert_eq!(registry.pending()[0].id, doubtful);
+
+        let mut targets = ModificationTargets { heuristics: Some(&mut heuristics), ..Default::default() };
+        assert_eq!(registry.apply_approved(&mut targets), vec![sure]);
+        registry.approve(doubtful, "operator").unwrap();
+        assert_eq!(registry.apply_approved(&mut targets), vec![doubtful]);
+        drop(targets);
+
+        // The later rule for the same situation replaced the first
+        assert_eq!(heuristics.rules.len(), 1);
+        let situation = [Condition::Goal("report".into()), Condition::PressingDeadline];
+        assert_eq!(heuristics.rule_against(PlanningStrategy::Htn, &situation).map(|r| r.confidence), Some(0.6));
+        assert!(heuristics.rule_against(PlanningStrategy::Goap, &situation).is_none());
+    }
+}
@@ -10,12 +10,12 @@
 //   Core Functions:
 //       • Detect when Astra needs rest
 //       • Trigger consolidation + reflection + drift
-//       • Reset fatigue and stabilize mood
+//       • Recover from fatigue and stabilize mood
 //
 //   File:        /src/cognition/sleep_scheduler.rs
 //   Author:      Alex Roussinov
 //   Created:     2026-01-12
-//   Updated:     2026-01-12
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
@@ -35,5 +35,5 @@ pub fn run_sleep_cycle(state: &mut CognitiveState, time_hours: f32) {
     apply_trait_drift(state);
     apply_mood_curve(state, time_hours);
 
-    state.energy.fatigue = (state.energy.fatigue * 0.5).clamp(0.0, 1.0);
+    state.energy.recover_in_sleep(time_hours);
 }
@@ -0,0 +1,269 @@
+// ============================================================================
+//                     ASTRA AGI • SPREADING ACTIVATION
+//        Associative Recall Across Memory & Knowledge
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Gives Astra associative recall. Concepts, ontology entities, facts,
+//       and narrative events are joined in one weighted graph: entities by
+//       their ontology relationships, facts by the words they relate, and
+//       events by the concepts they mention and the events they link to.
+//       Activation injected at a few cue concepts spreads outward, fading
+//       with each hop and divided among a node's neighbors, and whatever
+//       ends up most active is what the cues call to mind. The runtime uses
+//       it to prime context before it composes a response.
+//
+//   Core Functions:
+//       • Build an association graph from ontologies, facts, and episodes
+//       • Spread activation from cue concepts with decay and fan-out
+//       • Retrieve the most associated events, facts, and concepts
+//
+//   File:        /src/cognition/spreading_activation.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use std::collections::HashMap;
+
+use crate::knowledge::belief_ledger::FactKey;
+use crate::knowledge::extended_ontology::OntologyManager;
+use crate::knowledge::ontology::{AttributeValue, Id, Ontology};
+use crate::knowledge::storage::Storage;
+use crate::memory::conversation_summary::content_words;
+use crate::memory::narrative_memory::{EventId, LinkTarget, NarrativeEvent, NarrativeMemory};
+
+/// Weight between an event and a concept it mentions.
+const MENTION_WEIGHT: f32 = 1.0;
+/// Weight added between two concepts each time they occur in one event.
+const CO_OCCURRENCE_WEIGHT: f32 = 0.3;
+/// Weight between an entity and the concept it instantiates.
+const INSTANCE_WEIGHT: f32 = 0.5;
+
+/// A node of the association graph.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AssociationNode {
+    /// A word or name, lowercased
+    Concept(String),
+    /// An entity of an `Ontology`
+    Entity(Id),
+    Fact(FactKey),
+    Event(EventId),
+}
+
+/// Tuning for a spread.
+#[derive(Debug, Clone)]
+pub struct SpreadParams {
+    /// Hops activation travels from the cues
+    pub max_hops: usize,
+    /// Share of activation passed on at each hop
+    pub decay: f32,
+    /// Activation below this is not spread further
+    pub threshold: f32,
+}
+
+impl Default for SpreadParams {
+    fn default() -> Self {
+        SpreadParams { max_hops: 3, decay: 0.6, threshold: 0.01 }
+    }
+}
+
+/// What a set of cues brought to mind, most active first.
+#[derive(Debug, Clone, Default)]
+pub struct Associations {
+    pub events: Vec<(EventId, f32)>,
+    pub facts: Vec<(FactKey, f32)>,
+    pub entities: Vec<(Id, f32)>,
+    pub concepts: Vec<(String, f32)>,
+}
+
+/// Undirected weighted graph of associations.
+#[derive(Debug, Clone, Default)]
+pub struct ActivationGraph {
+    edges: HashMap<AssociationNode, HashMap<AssociationNode, f32>>,
+}
+
+impl ActivationGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Strengthens the association between `a` and `b` by `weight`.
+    pub fn connect(&mut self, a: AssociationNode, b: AssociationNode, weight: f32) {
+        if a == b || weight <= 0.0 {
+            return;
+        }
+        *self.edges.entry(a.clone()).or_default().entry(b.clone()).or_insert(0.0) += weight;
+        *self.edges.entry(b).or_default().entry(a).or_insert(0.0) += weight;
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Adds an ontology's relationships, weighted by their `weight`
+    /// property, and ties each entity to its concept and to its name.
+    pub fn add_ontology<S: Storage>(&mut self, ontology: &Ontology<S>) {
+        for relationship in ontology.relationships.values() {
+            let weight = relationship.properties.weight.unwrap_or(1.0) as f32;
+            self.connect(
+                AssociationNode::Entity(relationship.from_entity),
+                AssociationNode::Entity(relationship.to_entity),
+                weight,
+            );
+        }
+        for entity in ontology.entities.values() {
+            let node = AssociationNode::Entity(entity.id);
+            if let Some(concept) = ontology.get_concept(entity.concept_id) {
+                self.connect(node.clone(), concept_node(&concept.name), INSTANCE_WEIGHT);
+            }
+            if let Some(AttributeValue::String(name)) = entity.attribute_values.get("name") {
+                self.connect(node, concept_node(name), MENTION_WEIGHT);
+            }
+        }
+    }
+
+    /// Adds the current facts, each tied to the words of its predicate and
+    /// object in proportion to its confidence.
+    pub fn add_facts(&mut self, knowledge: &OntologyManager) {
+        for fact in knowledge.query_facts(None) {
+            let node = AssociationNode::Fact(FactKey::from(fact));
+            let weight = fact.confidence.clamp(0.1, 1.0);
+            self.connect(node.clone(), concept_node(&fact.object), weight);
+            for word in content_words(&fact.object).chain(content_words(&fact.predicate)) {
+                self.connect(node.clone(), AssociationNode::Concept(word), weight);
+            }
+        }
+    }
+
+    /// Adds narrative events: each is tied to the concepts it mentions and
+    /// to the events and facts it links to, and concepts mentioned in the
+    /// same event become associated with each other.
+    pub fn add_episodes(&mut self, memory: &NarrativeMemory) {
+        self.add_events(&memory.events);
+    }
+
+    /// Adds `events` as `add_episodes` does, e.g. those recorded since the
+    /// graph was built.
+    pub fn add_events<'e>(&mut self, events: impl IntoIterator<Item = &'e NarrativeEvent>) {
+        for event in events {
+            let node = AssociationNode::Event(event.id);
+            let mut words: Vec<String> = content_words(&event.description).collect();
+            words.sort();
+            words.dedup();
+            for (i, word) in words.iter().enumerate() {
+                self.connect(node.clone(), AssociationNode::Concept(word.clone()), MENTION_WEIGHT);
+                for other in &words[i + 1..] {
+                    self.connect(
+                        AssociationNode::Concept(word.clone()),
+                        AssociationNode::Concept(other.clone()),
+                        CO_OCCURRENCE_WEIGHT,
+                    );
+                }
+            }
+            for link in &event.links {
+                match &link.target {
+                    LinkTarget::Event(other) => self.connect(node.clone(), AssociationNode::Event(*other), MENTION_WEIGHT),
+                    LinkTarget::Fact(key) => self.connect(node.clone(), AssociationNode::Fact(key.clone()), MENTION_WEIGHT),
+                    LinkTarget::Intent(_) => {}
+                }
+            }
+        }
+    }
+
+    /// Spreads activation from `cues` and returns every node reached,
+    /// cues excluded, most active first. Each hop passes on `decay` of a
+    /// node's activation, divided among its neighbors by edge weight.
+    pub fn spread(&self, cues: &[&str], params: &SpreadParams) -> Vec<(AssociationNode, f32)> {
+        let cue_nodes: Vec<AssociationNode> = cues.iter().map(|c| concept_node(c)).collect();
+        let mut activation: HashMap<AssociationNode, f32> = HashMap::new();
+        let mut frontier: HashMap<AssociationNode, f32> = cue_nodes.iter().map(|n| (n.clone(), 1.0)).collect();
+
+        for _ in 0..params.max_hops {
+            let mut next: HashMap<AssociationNode, f32> = HashMap::new();
+            for (node, energy) in &frontier {
+                let Some(neighbors) = self.edges.get(node) else { continue };
+                let total: f32 = neighbors.values().sum();
+                for (neighbor, weight) in neighbors {
+                    let passed = energy * params.decay * weight / total;
+                    if passed >= params.threshold {
+                        *next.entry(neighbor.clone()).or_insert(0.0) += passed;
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            for (node, energy) in &next {
+                *activation.entry(node.clone()).or_insert(0.0) += energy;
+            }
+            frontier = next;
+        }
+
+        let mut ranked: Vec<(AssociationNode, f32)> =
+            activation.into_iter().filter(|(node, _)| !cue_nodes.contains(node)).collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        ranked
+    }
+
+    /// The `limit` most associated items of each kind.
+    pub fn retrieve(&self, cues: &[&str], params: &SpreadParams, limit: usize) -> Associations {
+        let mut associations = Associations::default();
+        for (node, activation) in self.spread(cues, params) {
+            match node {
+                AssociationNode::Event(id) if associations.events.len() < limit => associations.events.push((id, activation)),
+                AssociationNode::Fact(key) if associations.facts.len() < limit => associations.facts.push((key, activation)),
+                AssociationNode::Entity(id) if associations.entities.len() < limit => {
+                    associations.entities.push((id, activation))
+                }
+                AssociationNode::Concept(word) if associations.concepts.len() < limit => {
+                    associations.concepts.push((word, activation))
+                }
+                _ => {}
+            }
+        }
+        associations
+    }
+}
+
+fn concept_node(text: &str) -> AssociationNode {
+    AssociationNode::Concept(text.trim().to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::extended_ontology::{Fact, Provenance};
+
+    #[test]
+    fn test_cues_recall_associated_events_and_facts() {
+        let mut memory = NarrativeMemory::new(10);
+        let garden = memory.add_event("user_message", "Planted tomatoes in the garden", None);
+        memory.add_event("user_message", "Watered the tomatoes before breakfast", None);
+        let unrelated = memory.add_event("user_message", "Booked train tickets to Lyon", None);
+
+        let mut knowledge = OntologyManager::new();
+        knowledge.add_fact(Fact {
+            subject: 1,
+            predicate: "grows_in".into(),
+            object: "garden".into(),
+            confidence: 0.9,
+            provenance: Provenance::new("test", None),
+        });
+
+        let mut graph = ActivationGraph::new();
+        graph.add_episodes(&memory);
+        graph.add_facts(&knowledge);
+
+        let recalled = graph.retrieve(&["tomatoes"], &SpreadParams::default(), 5);
+        let events: Vec<EventId> = recalled.events.iter().map(|(id, _)| *id).collect();
+        assert!(events.contains(&garden));
+        assert!(!events.contains(&unrelated));
+        // "garden" is reached through the episode, and the fact through it.
+        assert!(recalled.concepts.iter().any(|(w, _)| w == "garden"));
+        assert_eq!(recalled.facts.len(), 1);
+    }
+}
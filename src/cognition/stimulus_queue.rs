@@ -0,0 +1,188 @@
+// ============================================================================
+//                        ASTRA AGI • BOUNDED STIMULUS QUEUE
+//        Shedding and Aggregating Stimuli When Perception Floods
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Sits between perception and the mind. A chat room in uproar or a
+//       chattering sensor can produce stimuli faster than ticks consume
+//       them; an unbounded queue would grow without limit and leave Astra
+//       reacting to the distant past. This queue holds at most a fixed
+//       number of stimuli. A stimulus repeating one already waiting (same
+//       source, same content) is folded into it, keeping the higher
+//       urgency and counting the repeats. When the queue is full the least
+//       urgent stimulus is shed, the newcomer itself if nothing waiting is
+//       less urgent, and every shed stimulus is counted.
+//
+//   Core Functions:
+//       • Hold a bounded number of waiting stimuli
+//       • Fold repeats of a waiting stimulus into it
+//       • Shed the least urgent stimulus when full, and count what was shed
+//       • Hand stimuli out most urgent first, oldest first among equals
+//
+//   File:        /src/cognition/stimulus_queue.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use std::collections::VecDeque;
+
+use crate::cognition::goal_formation::Stimulus;
+
+/// Stimuli a queue holds unless told otherwise.
+pub const DEFAULT_STIMULUS_CAPACITY: usize = 256;
+
+/// A waiting stimulus and how many times it arrived.
+#[derive(Debug, Clone)]
+pub struct QueuedStimulus {
+    pub stimulus: Stimulus,
+    /// 1, plus the repeats folded into it
+    pub occurrences: u32,
+}
+
+/// What became of a stimulus offered to the queue.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Admission {
+    Queued,
+    /// Folded into the same stimulus already waiting
+    Merged,
+    /// Queued in place of a less urgent stimulus, which was shed
+    Displaced(Stimulus),
+    /// Not queued; everything waiting was at least as urgent
+    Shed,
+}
+
+/// A bounded queue of stimuli that sheds the least urgent when full.
+#[derive(Debug, Clone)]
+pub struct StimulusQueue {
+    capacity: usize,
+    /// In arrival order
+    waiting: VecDeque<QueuedStimulus>,
+    dropped: u64,
+    merged: u64,
+}
+
+impl StimulusQueue {
+    /// A queue holding at most `capacity` stimuli, and at least one.
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        StimulusQueue { capacity, waiting: VecDeque::with_capacity(capacity), dropped: 0, merged: 0 }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.waiting.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.waiting.is_empty()
+    }
+
+    /// Stimuli shed so far, whether turned away or displaced.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Repeats folded into a waiting stimulus so far.
+    pub fn merged(&self) -> u64 {
+        self.merged
+    }
+
+    /// Offers a stimulus to the queue.
+    pub fn push(&mut self, stimulus: Stimulus) -> Admission {
+        if let Some(waiting) = self
+            .waiting
+            .iter_mut()
+            .find(|w| w.stimulus.source == stimulus.source && w.stimulus.content == stimulus.content)
+        {
+            waiting.stimulus.urgency = waiting.stimulus.urgency.max(stimulus.urgency);
+            waiting.occurrences = waiting.occurrences.saturating_add(1);
+            self.merged += 1;
+            return Admission::Merged;
+        }
+
+        let mut admission = Admission::Queued;
+        if self.waiting.len() >= self.capacity {
+            // The first of the least urgent is the oldest of them
+            let (least, urgency) = self
+                .waiting
+                .iter()
+                .map(|w| w.stimulus.urgency)
+                .enumerate()
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap_or((0, f32::INFINITY));
+            self.dropped += 1;
+            if stimulus.urgency <= urgency {
+                return Admission::Shed;
+            }
+            let shed = self.waiting.remove(least).map(|w| w.stimulus);
+            admission = shed.map_or(Admission::Queued, Admission::Displaced);
+        }
+        self.waiting.push_back(QueuedStimulus { stimulus, occurrences: 1 });
+        admission
+    }
+
+    /// Takes the most urgent stimulus, the oldest among equally urgent ones.
+    pub fn pop(&mut self) -> Option<QueuedStimulus> {
+        // `min_by` keeps the first of equals, so compare in reverse
+        let (most, _) = self
+            .waiting
+            .iter()
+            .map(|w| w.stimulus.urgency)
+            .enumerate()
+            .min_by(|a, b| b.1.total_cmp(&a.1))?;
+        self.waiting.remove(most)
+    }
+
+    /// Takes everything waiting, most urgent first.
+    pub fn drain(&mut self) -> Vec<QueuedStimulus> {
+        let mut all: Vec<QueuedStimulus> = self.waiting.drain(..).collect();
+        // Stable, so equally urgent stimuli stay oldest first
+        all.sort_by(|a, b| b.stimulus.urgency.total_cmp(&a.stimulus.urgency));
+        all
+    }
+}
+
+impl Default for StimulusQueue {
+    fn default() -> Self {
+        StimulusQueue::new(DEFAULT_STIMULUS_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stimulus(content: &str, urgency: f32) -> Stimulus {
+        Stimulus { source: "chat".into(), content: content.into(), urgency }
+    }
+
+    #[test]
+    fn test_flood_is_bounded_merged_and_shed_by_urgency() {
+        let mut queue = StimulusQueue::new(3);
+        assert_eq!(queue.push(stimulus("hello", 0.2)), Admission::Queued);
+        assert_eq!(queue.push(stimulus("spam", 0.1)), Admission::Queued);
+        // The same message a thousand times is one stimulus
+        for _ in 0..1000 {
+            assert_eq!(queue.push(stimulus("spam", 0.3)), Admission::Merged);
+        }
+        assert_eq!(queue.push(stimulus("fire alarm", 1.0)), Admission::Queued);
+        assert_eq!(queue.len(), 3);
+
+        // Full: the least urgent is shed, or the newcomer if it is least urgent
+        assert_eq!(queue.push(stimulus("whisper", 0.05)), Admission::Shed);
+        assert_eq!(queue.push(stimulus("question", 0.5)), Admission::Displaced(stimulus("hello", 0.2)));
+        assert_eq!((queue.len(), queue.dropped(), queue.merged()), (3, 2, 1000));
+
+        let order: Vec<(String, u32)> = std::iter::from_fn(|| queue.pop()).map(|q| (q.stimulus.content, q.occurrences)).collect();
+        assert_eq!(order, [("fire alarm".to_string(), 1), ("question".to_string(), 1), ("spam".to_string(), 1001)]);
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,390 @@
+// ============================================================================
+//                       ASTRA AGI • THEORY OF MIND
+//        Modeling What Each User Wants, Believes, and Feels
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Keeps an estimate of the mental state of each person Astra talks
+//       to, inferred from their messages and their feedback: the goals
+//       their requests reveal, the claims they hold to be true (whether
+//       they stated them, corrected Astra with them, or were told them by
+//       Astra), and how they feel. Responses are planned against it: a
+//       claim the user already holds is not repeated, a claim the user has
+//       never heard is explained, and a claim the user holds a different
+//       view of is delivered as a correction. Beliefs are compared with
+//       what Astra itself holds true, so beliefs Astra knows to be false
+//       can be found before they cause a misunderstanding.
+//
+//   Core Functions:
+//       • Infer user goals from the intents of their messages
+//       • Infer user beliefs from stated claims, corrections, and what Astra told them
+//       • Track each user's mood from message sentiment and feedback
+//       • Find user beliefs that contradict what Astra holds true
+//       • Sort what Astra means to say into new, redundant, and corrective
+//       • Bound what is kept per user, and how many users are modeled
+//
+//   File:        /src/cognition/theory_of_mind.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::interfaces::nlp::NlpResult;
+use crate::learning::feedback::FeedbackKind;
+
+/// Weight of the newest message or feedback in a user's mood.
+const MOOD_RATE: f32 = 0.3;
+/// Goals remembered per user; older ones are assumed settled.
+const MAX_GOALS: usize = 5;
+/// Beliefs remembered per user; the longest held are forgotten first.
+const MAX_BELIEFS: usize = 64;
+/// Users modeled at once; the one heard from least recently is forgotten
+/// to make room for a new one.
+const MAX_USERS: usize = 1024;
+/// Below this valence a user is taken to be frustrated.
+const FRUSTRATED_BELOW: f32 = -0.3;
+
+/// Phrases marking a sentence as something the speaker holds true,
+/// longest first so "that" is not left on the claim.
+const BELIEF_MARKERS: &[&str] = &[
+    "i'm pretty sure that",
+    "i'm pretty sure",
+    "i'm sure that",
+    "i'm sure",
+    "i believe that",
+    "i believe",
+    "i think that",
+    "i think",
+    "i heard that",
+    "i heard",
+    "i know that",
+    "i know",
+    "apparently",
+];
+const COPULAS: &[&str] = &[" is ", " are ", " was ", " were "];
+/// Intents that reveal no goal beyond the conversation itself.
+const GOALLESS_INTENTS: &[&str] = &["greeting", "unknown"];
+
+/// Something held true about a topic: "the meeting is on Friday" has the
+/// topic "meeting" and the value "on friday".
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Claim {
+    pub topic: String,
+    pub value: String,
+}
+
+impl Claim {
+    pub fn new(topic: &str, value: &str) -> Self {
+        Claim { topic: normalize(topic), value: normalize(value) }
+    }
+
+    /// Reads an "X is Y" sentence as a claim.
+    pub fn parse(sentence: &str) -> Option<Claim> {
+        let lower = format!(" {} ", sentence.trim().trim_end_matches(['.', '!']).to_lowercase());
+        if lower.trim_end().ends_with('?') {
+            return None;
+        }
+        let (at, copula) = COPULAS.iter().filter_map(|c| lower.find(c).map(|at| (at, c))).min()?;
+        let claim = Claim::new(&lower[..at], &lower[at + copula.len()..]);
+        (!claim.topic.is_empty() && !claim.value.is_empty()).then_some(claim)
+    }
+}
+
+fn normalize(text: &str) -> String {
+    let words: Vec<&str> = text
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric() && c != '\''))
+        .filter(|w| !w.is_empty())
+        .collect();
+    let words = match words.first() {
+        Some(&"the") | Some(&"a") | Some(&"an") => &words[1..],
+        _ => &words[..],
+    };
+    words.join(" ").to_lowercase()
+}
+
+/// How the user came to hold a belief.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BeliefSource {
+    /// The user said so
+    Stated,
+    /// The user corrected Astra with it
+    Corrected,
+    /// Astra told the user
+    Told,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserBelief {
+    pub claim: Claim,
+    pub source: BeliefSource,
+    pub since: DateTime<Utc>,
+}
+
+/// Something the user is trying to get done, as their messages reveal it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserGoal {
+    pub intent: String,
+    /// The latest message pursuing it
+    pub description: String,
+    pub since: DateTime<Utc>,
+}
+
+/// What Astra should do with a claim it means to tell a user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Tailoring {
+    /// The user has not heard it; explain it
+    Explain,
+    /// The user already holds it; leave it out
+    Redundant,
+    /// The user holds something else about the topic; correct them
+    Correct { user_value: String },
+}
+
+/// Astra's estimate of one user's mental state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MentalState {
+    pub user_id: String,
+    /// Oldest first
+    pub goals: Vec<UserGoal>,
+    /// What the user holds true, by topic
+    pub beliefs: HashMap<String, UserBelief>,
+    /// -1.0 upset .. 1.0 pleased; a moving average over messages and feedback
+    pub valence: f32,
+    /// 0.0 calm .. 1.0 agitated
+    pub arousal: f32,
+    pub updated: DateTime<Utc>,
+}
+
+impl MentalState {
+    fn new(user_id: &str, now: DateTime<Utc>) -> Self {
+        MentalState {
+            user_id: user_id.to_string(),
+            goals: Vec::new(),
+            beliefs: HashMap::new(),
+            valence: 0.0,
+            arousal: 0.0,
+            updated: now,
+        }
+    }
+
+    pub fn is_frustrated(&self) -> bool {
+        self.valence < FRUSTRATED_BELOW
+    }
+
+    /// Whether the user holds `claim`: `Some(true)` if they hold it,
+    /// `Some(false)` if they hold something else about its topic, `None`
+    /// if nothing is known of what they think.
+    pub fn believes(&self, claim: &Claim) -> Option<bool> {
+        self.beliefs.get(&claim.topic).map(|belief| belief.claim.value == claim.value)
+    }
+
+    /// How to tell the user `claim`.
+    pub fn tailor(&self, claim: &Claim) -> Tailoring {
+        match self.beliefs.get(&claim.topic) {
+            None => Tailoring::Explain,
+            Some(belief) if belief.claim.value == claim.value => Tailoring::Redundant,
+            Some(belief) => Tailoring::Correct { user_value: belief.claim.value.clone() },
+        }
+    }
+
+    /// The user's beliefs that contradict what Astra holds true in `known`.
+    pub fn mistaken_beliefs<'a>(&'a self, known: &'a [Claim]) -> Vec<(&'a UserBelief, &'a Claim)> {
+        known
+            .iter()
+            .filter_map(|truth| Some((self.beliefs.get(&truth.topic)?, truth)))
+            .filter(|(belief, truth)| belief.claim.value != truth.value)
+            .collect()
+    }
+
+    fn hold(&mut self, claim: Claim, source: BeliefSource, now: DateTime<Utc>) {
+        self.beliefs.insert(claim.topic.clone(), UserBelief { claim, source, since: now });
+        if self.beliefs.len() > MAX_BELIEFS {
+            let oldest = self.beliefs.values().min_by_key(|belief| belief.since).map(|b| b.claim.topic.clone());
+            if let Some(oldest) = oldest {
+                self.beliefs.remove(&oldest);
+            }
+        }
+    }
+
+    fn feel(&mut self, valence: f32, arousal: f32) {
+        self.valence += MOOD_RATE * (valence.clamp(-1.0, 1.0) - self.valence);
+        self.arousal += MOOD_RATE * (arousal.clamp(0.0, 1.0) - self.arousal);
+    }
+}
+
+/// Mental-state models by user id.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TheoryOfMind {
+    users: HashMap<String, MentalState>,
+}
+
+impl TheoryOfMind {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, user_id: &str) -> Option<&MentalState> {
+        self.users.get(user_id)
+    }
+
+    fn state(&mut self, user_id: &str, now: DateTime<Utc>) -> &mut MentalState {
+        if self.users.len() >= MAX_USERS && !self.users.contains_key(user_id) {
+            let stalest = self.users.values().min_by_key(|state| state.updated).map(|s| s.user_id.clone());
+            if let Some(stalest) = stalest {
+                self.users.remove(&stalest);
+            }
+        }
+        let state = self.users.entry(user_id.to_string()).or_insert_with(|| MentalState::new(user_id, now));
+        state.updated = now;
+        state
+    }
+
+    /// Updates the model of `user_id` from a message they sent and how it
+    /// was understood: its intent as a goal, claims it states as beliefs,
+    /// and its sentiment as mood. `text` is kept as the goal's description,
+    /// so callers pass it with personal data already filtered out.
+    pub fn observe_message(&mut self, user_id: &str, text: &str, understood: &NlpResult, now: DateTime<Utc>) -> &MentalState {
+        let state = self.state(user_id, now);
+        state.feel(understood.sentiment.valence, understood.sentiment.arousal);
+
+        if !GOALLESS_INTENTS.contains(&understood.intent.as_str()) {
+            state.goals.retain(|goal| goal.intent != understood.intent);
+            state.goals.push(UserGoal { intent: understood.intent.clone(), description: text.trim().to_string(), since: now });
+            if state.goals.len() > MAX_GOALS {
+                state.goals.remove(0);
+            }
+        }
+
+        for sentence in text.split_inclusive(['.', '!', '?', ';']) {
+            let lower = sentence.trim().to_lowercase();
+            let stated = BELIEF_MARKERS
+                .iter()
+                .find(|marker| lower.starts_with(*marker))
+                .and_then(|marker| Claim::parse(&lower[marker.len()..]));
+            if let Some(claim) = stated {
+                state.hold(claim, BeliefSource::Stated, now);
+            }
+        }
+        state
+    }
+
+    /// Updates the model of `user_id` from feedback they gave: praise and
+    /// complaints move their mood, and a correction is something they hold.
+    pub fn observe_feedback(&mut self, user_id: &str, feedback: &FeedbackKind, now: DateTime<Utc>) -> &MentalState {
+        let state = self.state(user_id, now);
+        match feedback {
+            FeedbackKind::ThumbsUp | FeedbackKind::ThumbsDown => state.feel(feedback.reward(), 0.5),
+            FeedbackKind::Correction { corrected, .. } => {
+                state.feel(feedback.reward(), 0.5);
+                if let Some(claim) = Claim::parse(corrected) {
+                    state.hold(claim, BeliefSource::Corrected, now);
+                }
+            }
+            // A preference says how to answer, not what the user believes
+            FeedbackKind::Preference { .. } => {}
+        }
+        state
+    }
+
+    /// Records that Astra told `user_id` each of `claims`.
+    pub fn told(&mut self, user_id: &str, claims: impl IntoIterator<Item = Claim>, now: DateTime<Utc>) {
+        let state = self.state(user_id, now);
+        for claim in claims {
+            state.hold(claim, BeliefSource::Told, now);
+        }
+    }
+
+    /// How to tell `user_id` `claim`; everything is new to an unknown user.
+    pub fn tailor(&self, user_id: &str, claim: &Claim) -> Tailoring {
+        self.get(user_id).map_or(Tailoring::Explain, |state| state.tailor(claim))
+    }
+
+    /// Forgets a user, e.g. on a deletion request.
+    pub fn remove(&mut self, user_id: &str) -> Option<MentalState> {
+        self.users.remove(user_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.users.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interfaces::nlp::NlpProcessor;
+
+    #[test]
+    fn test_goals_beliefs_and_mood_shape_what_is_said() {
+        let nlp = NlpProcessor::new();
+        let now = Utc::now();
+        let mut minds = TheoryOfMind::new();
+        let text = "I think the meeting is on Friday. Remind me to book the room.";
+        let state = minds.observe_message("ana", text, &nlp.process_text(text).unwrap(), now);
+        assert_eq!(state.goals.iter().map(|g| g.intent.as_str()).collect::<Vec<_>>(), ["set_reminder"]);
+        assert_eq!(state.believes(&Claim::new("meeting", "on friday")), Some(true));
+
+        minds.observe_feedback(
+            "ana",
+            &FeedbackKind::Correction { original: "The room is free".into(), corrected: "The room is booked".into() },
+            now,
+        );
+        minds.observe_feedback("ana", &FeedbackKind::ThumbsDown, now);
+        let state = minds.get("ana").unwrap();
+        assert_eq!(state.beliefs["room"].source, BeliefSource::Corrected);
+        assert!(state.is_frustrated());
+
+        // Astra knows the meeting moved; the user does not
+        let truths = [Claim::new("meeting", "on Monday"), Claim::new("room", "booked")];
+        let mistaken = state.mistaken_beliefs(&truths);
+        assert_eq!(mistaken.len(), 1);
+        assert_eq!(mistaken[0].0.claim.value, "on friday");
+
+        assert_eq!(minds.tailor("ana", &truths[0]), Tailoring::Correct { user_value: "on friday".into() });
+        assert_eq!(minds.tailor("ana", &truths[1]), Tailoring::Redundant);
+        assert_eq!(minds.tailor("ana", &Claim::new("projector", "broken")), Tailoring::Explain);
+
+        minds.told("ana", [truths[0].clone()], now);
+        assert_eq!(minds.tailor("ana", &truths[0]), Tailoring::Redundant);
+        assert_eq!(minds.tailor("ben", &truths[0]), Tailoring::Explain);
+    }
+
+    #[test]
+    fn test_claims_are_read_from_statements_only() {
+        assert_eq!(Claim::parse("The capital of France is Paris."), Some(Claim::new("capital of france", "paris")));
+        assert_eq!(Claim::parse("What is the time?"), None);
+        assert_eq!(Claim::parse("Call me later"), None);
+    }
+
+    #[test]
+    fn test_models_are_bounded() {
+        let start = Utc::now();
+        let mut minds = TheoryOfMind::new();
+        for i in 0..MAX_BELIEFS + 3 {
+            let at = start + chrono::Duration::seconds(i as i64);
+            minds.told("ana", [Claim::new(&format!("topic {}", i), "known")], at);
+        }
+        let state = minds.get("ana").unwrap();
+        assert_eq!(state.beliefs.len(), MAX_BELIEFS);
+        assert_eq!(state.believes(&Claim::new("topic 0", "known")), None);
+        assert_eq!(state.believes(&Claim::new(&format!("topic {}", MAX_BELIEFS + 2), "known")), Some(true));
+
+        for i in 0..MAX_USERS {
+            minds.told(&format!("user {}", i), [], start + chrono::Duration::hours(1));
+        }
+        assert_eq!(minds.len(), MAX_USERS);
+        assert!(minds.get("ana").is_none());
+    }
+}
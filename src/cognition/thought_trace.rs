@@ -12,11 +12,12 @@
 //       • Record thought steps as Astra deliberates
 //       • Associate traces with goals, plans, and outcomes
 //       • Provide human-readable explanations of decisions
+//       • Carry the affect before and after the episode the trace belongs to
 //
 //   File:        /src/cognition/thought_trace.rs
 //   Author:      Alex Roussinov
 //   Created:     2026-01-11
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
@@ -25,6 +26,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::memory::episode_affect::EpisodeAffect;
+
 /// A single reasoning step in Astra’s thought process.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThoughtStep {
@@ -37,6 +40,9 @@ pub struct ThoughtStep {
 pub struct ThoughtTrace {
     pub goal_id: String,
     pub steps: Vec<ThoughtStep>,
+    /// Affect when the episode began and ended, once it has
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub affect: Option<EpisodeAffect>,
 }
 
 impl ThoughtTrace {
@@ -44,6 +50,7 @@ impl ThoughtTrace {
         Self {
             goal_id: goal_id.into(),
             steps: Vec::new(),
+            affect: None,
         }
     }
 
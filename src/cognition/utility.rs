@@ -0,0 +1,286 @@
+// ============================================================================
+//                      ASTRA AGI • UTILITY-BASED SELECTION
+//        Choosing Between Small Competing Behaviors, Moment to Moment
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Decides, once a goal has been chosen, how to go about it right now:
+//       respond immediately, think longer before acting, or defer it until
+//       there is more energy to spare. Each candidate behavior is rated by
+//       a set of weighted scorers—urgency, motivation, alignment with
+//       Astra's values, and cost—and the one with the highest combined
+//       utility wins. Every scorer's contribution is kept, so a decision can
+//       be written into the thought trace and questioned later.
+//
+//   Core Functions:
+//       • Rate candidate behaviors with pluggable, weighted scorers
+//       • Score urgency, motivation, value alignment, and cost out of the box
+//       • Keep each scorer's contribution to every candidate's utility
+//       • Record decisions in thought traces
+//
+//   File:        /src/cognition/utility.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::cognition::ThoughtTrace;
+use crate::emotion::ValueModel;
+
+/// What to do about a goal this moment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MomentBehavior {
+    RespondNow,
+    /// Deliberate longer before acting
+    ThinkMore,
+    /// Set the goal aside until there is energy to spare
+    Defer,
+}
+
+impl MomentBehavior {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MomentBehavior::RespondNow => "respond now",
+            MomentBehavior::ThinkMore => "think more",
+            MomentBehavior::Defer => "defer",
+        }
+    }
+}
+
+/// A behavior up for selection and what scorers judge it by. All inputs
+/// are 0.0..=1.0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UtilityCandidate {
+    pub behavior: MomentBehavior,
+    /// How much the moment calls for this behavior
+    pub urgency: f32,
+    pub motivation: f32,
+    /// How far the behavior serves each value: 0.0 works against it, 1.0
+    /// serves it. Values left out count as neutral.
+    pub serves: HashMap<String, f32>,
+    /// Effort, risk, or load it adds
+    pub cost: f32,
+}
+
+/// Rates a candidate from 0.0 (strongly against) to 1.0 (strongly for).
+pub trait Scorer: Send + Sync {
+    fn name(&self) -> &str;
+    fn score(&self, candidate: &UtilityCandidate) -> f32;
+}
+
+pub struct UrgencyScorer;
+
+impl Scorer for UrgencyScorer {
+    fn name(&self) -> &str {
+        "urgency"
+    }
+
+    fn score(&self, candidate: &UtilityCandidate) -> f32 {
+        candidate.urgency
+    }
+}
+
+pub struct MotivationScorer;
+
+impl Scorer for MotivationScorer {
+    fn name(&self) -> &str {
+        "motivation"
+    }
+
+    fn score(&self, candidate: &UtilityCandidate) -> f32 {
+        candidate.motivation
+    }
+}
+
+/// Average of how far a candidate serves each value, weighted by how much
+/// Astra holds that value.
+pub struct ValueAlignmentScorer {
+    pub values: ValueModel,
+}
+
+impl Scorer for ValueAlignmentScorer {
+    fn name(&self) -> &str {
+        "value_alignment"
+    }
+
+    fn score(&self, candidate: &UtilityCandidate) -> f32 {
+        const NEUTRAL: f32 = 0.5;
+        let total: f32 = self.values.values.values().sum();
+        if total <= 0.0 {
+            return NEUTRAL;
+        }
+        self.values
+            .values
+            .iter()
+            .map(|(value, weight)| weight * candidate.serves.get(value).copied().unwrap_or(NEUTRAL))
+            .sum::<f32>()
+            / total
+    }
+}
+
+/// Favors cheaper candidates.
+pub struct CostScorer;
+
+impl Scorer for CostScorer {
+    fn name(&self) -> &str {
+        "cost"
+    }
+
+    fn score(&self, candidate: &UtilityCandidate) -> f32 {
+        1.0 - candidate.cost
+    }
+}
+
+/// One scorer's part in a candidate's utility.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Contribution {
+    pub scorer: String,
+    pub score: f32,
+    /// Share of the utility this scorer carries; weights sum to 1.0
+    pub weight: f32,
+}
+
+impl Contribution {
+    pub fn weighted(&self) -> f32 {
+        self.score * self.weight
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BehaviorScore {
+    pub behavior: MomentBehavior,
+    pub utility: f32,
+    pub contributions: Vec<Contribution>,
+}
+
+/// The chosen behavior and how every candidate scored, best first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UtilityDecision {
+    pub chosen: MomentBehavior,
+    pub scores: Vec<BehaviorScore>,
+}
+
+impl UtilityDecision {
+    /// Adds the decision to `trace`: the winner, then each candidate's
+    /// utility broken down by scorer.
+    pub fn record(&self, trace: &mut ThoughtTrace) {
+        let margin = match self.scores.as_slice() {
+            [best, runner_up, ..] => best.utility - runner_up.utility,
+            _ => 1.0,
+        };
+        trace.add_step(format!("Chose to {} (utility margin {:.2})", self.chosen.as_str(), margin), 0.6);
+        for score in &self.scores {
+            let parts: Vec<String> = score
+                .contributions
+                .iter()
+                .map(|c| format!("{} {:.2}×{:.2}", c.scorer, c.score, c.weight))
+                .collect();
+            trace.add_step(format!("{}: {:.2} = {}", score.behavior.as_str(), score.utility, parts.join(" + ")), 0.3);
+        }
+    }
+}
+
+/// Weighted scorers that pick among candidate behaviors.
+pub struct UtilitySelector {
+    scorers: Vec<(Box<dyn Scorer>, f32)>,
+}
+
+impl Default for UtilitySelector {
+    fn default() -> Self {
+        UtilitySelector::empty()
+            .with_scorer(UrgencyScorer, 0.35)
+            .with_scorer(MotivationScorer, 0.25)
+            .with_scorer(ValueAlignmentScorer { values: ValueModel::new() }, 0.25)
+            .with_scorer(CostScorer, 0.15)
+    }
+}
+
+impl UtilitySelector {
+    /// Urgency, motivation, value alignment, and cost, urgency weighing most.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A selector with no scorers, to be built up with `with_scorer`.
+    pub fn empty() -> Self {
+        UtilitySelector { scorers: Vec::new() }
+    }
+
+    /// Adds a scorer. Weights are relative; negative ones count as zero.
+    pub fn with_scorer(mut self, scorer: impl Scorer + 'static, weight: f32) -> Self {
+        self.scorers.push((Box::new(scorer), weight.max(0.0)));
+        self
+    }
+
+    pub fn evaluate(&self, candidate: &UtilityCandidate) -> BehaviorScore {
+        let total: f32 = self.scorers.iter().map(|(_, w)| w).sum();
+        let contributions: Vec<Contribution> = self
+            .scorers
+            .iter()
+            .map(|(scorer, weight)| Contribution {
+                scorer: scorer.name().to_string(),
+                score: scorer.score(candidate).clamp(0.0, 1.0),
+                weight: if total > 0.0 { weight / total } else { 0.0 },
+            })
+            .collect();
+        BehaviorScore {
+            behavior: candidate.behavior,
+            utility: contributions.iter().map(Contribution::weighted).sum(),
+            contributions,
+        }
+    }
+
+    /// The candidate with the highest utility; the earliest wins ties.
+    pub fn select(&self, candidates: &[UtilityCandidate]) -> Option<UtilityDecision> {
+        let mut scores: Vec<BehaviorScore> = candidates.iter().map(|c| self.evaluate(c)).collect();
+        // Stable, so tied candidates keep their order
+        scores.sort_by(|a, b| b.utility.total_cmp(&a.utility));
+        let chosen = scores.first()?.behavior;
+        Some(UtilityDecision { chosen, scores })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(behavior: MomentBehavior, urgency: f32, motivation: f32, cost: f32) -> UtilityCandidate {
+        UtilityCandidate { behavior, urgency, motivation, serves: HashMap::new(), cost }
+    }
+
+    #[test]
+    fn test_selection_weighs_scorers_and_explains_itself() {
+        let selector = UtilitySelector::new();
+        let candidates = [
+            candidate(MomentBehavior::RespondNow, 0.9, 0.6, 0.2),
+            candidate(MomentBehavior::ThinkMore, 0.1, 0.6, 0.3),
+            candidate(MomentBehavior::Defer, 0.05, 0.4, 0.1),
+        ];
+        let decision = selector.select(&candidates).unwrap();
+        assert_eq!(decision.chosen, MomentBehavior::RespondNow);
+        let best = &decision.scores[0];
+        assert_eq!(best.contributions.len(), 4);
+        assert!((best.contributions.iter().map(|c| c.weight).sum::<f32>() - 1.0).abs() < 1e-6);
+        assert!((best.utility - (0.35 * 0.9 + 0.25 * 0.6 + 0.25 * 0.5 + 0.15 * 0.8)).abs() < 1e-6);
+
+        // Serving a held value can tip the balance
+        let mut careful = candidate(MomentBehavior::ThinkMore, 0.5, 0.5, 0.5);
+        careful.serves = HashMap::from([("integrity".to_string(), 1.0), ("compassion".to_string(), 1.0)]);
+        let hasty = candidate(MomentBehavior::RespondNow, 0.5, 0.5, 0.5);
+        assert_eq!(selector.select(&[hasty, careful]).unwrap().chosen, MomentBehavior::ThinkMore);
+
+        let mut trace = ThoughtTrace::new("answer");
+        decision.record(&mut trace);
+        assert_eq!(trace.steps.len(), 4);
+        assert!(trace.steps[0].message.starts_with("Chose to respond now"));
+        assert!(trace.steps[1].message.contains("urgency 0.90×0.35"));
+        assert!(UtilitySelector::empty().select(&[]).is_none());
+    }
+}
@@ -0,0 +1,194 @@
+// ============================================================================
+//                      ASTRA AGI • CORE AFFECT DYNAMICS
+//        Emotional State, Mood & Stimulus Integration Without a Clock
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       The arithmetic of Astra's affective layer, free of `std`. Emotional
+//       state decays toward rest as time passes, blends toward stimuli, and
+//       drags mood along with its valence. Decay is driven by the elapsed
+//       time the caller passes in, so an embedded host can use whatever
+//       clock it has; `personality::emotion::EmotionDynamics` drives it from
+//       the system clock.
+//
+//   Core Functions:
+//       • Represent multidimensional emotional state (happiness, sadness, anger, fear)
+//       • Compute affective valence and arousal for behavioral modulation
+//       • Blend emotional states using weighted stimuli
+//       • Decay emotions over a caller-supplied interval
+//       • Pick up the user's emotional tone through calibrated contagion
+//       • Serialize emotion and mood, with std, for saved cognitive state
+//
+//   File:        /src/core/affect.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+/// Upper bound on how strongly a user's mood can pull Astra's emotions in a
+/// single exchange. Contagion should color Astra's state, not take it over.
+pub const MAX_CONTAGION_INTENSITY: f32 = 0.3;
+
+/// Represents Astra’s instantaneous emotional state.
+/// Values are normalized between 0.0 and 1.0.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct EmotionState {
+    pub happiness: f32,
+    pub sadness: f32,
+    pub anger: f32,
+    pub fear: f32,
+}
+
+impl EmotionState {
+    /// Creates a neutral emotional state.
+    pub fn neutral() -> Self {
+        Self {
+            happiness: 0.5,
+            sadness: 0.0,
+            anger: 0.0,
+            fear: 0.0,
+        }
+    }
+
+    /// Computes emotional valence: positive vs. negative affect.
+    pub fn valence(&self) -> f32 {
+        self.happiness - (self.sadness + self.anger + self.fear) * 0.5
+    }
+
+    /// Computes emotional arousal: intensity of activation.
+    pub fn arousal(&self) -> f32 {
+        (self.happiness + self.anger + self.fear).max(0.1)
+    }
+
+    /// Blends this emotional state with another using a weight factor.
+    pub fn blend(&mut self, other: &EmotionState, weight: f32) {
+        let w = weight.clamp(0.0, 1.0);
+        self.happiness = self.happiness * (1.0 - w) + other.happiness * w;
+        self.sadness = self.sadness * (1.0 - w) + other.sadness * w;
+        self.anger = self.anger * (1.0 - w) + other.anger * w;
+        self.fear = self.fear * (1.0 - w) + other.fear * w;
+    }
+}
+
+/// Represents long‑term mood, which evolves more slowly than emotion.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mood {
+    pub baseline: f32, // 0 (negative) to 1 (positive)
+}
+
+impl Mood {
+    pub fn new() -> Self {
+        Self { baseline: 0.6 }
+    }
+
+    /// Updates mood based on emotional valence.
+    pub fn update_from_emotion(&mut self, emotion: &EmotionState) {
+        let delta = emotion.valence() * 0.05;
+        self.baseline = (self.baseline + delta).clamp(0.0, 1.0);
+    }
+}
+
+impl Default for Mood {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Emotional transitions, decay, and stimulus integration, with time
+/// supplied by the caller.
+#[derive(Debug, Clone)]
+pub struct Affect {
+    pub current: EmotionState,
+    pub mood: Mood,
+    /// Fraction of each emotion lost per second
+    pub decay_rate: f32,
+}
+
+impl Affect {
+    pub fn new() -> Self {
+        Self {
+            current: EmotionState::neutral(),
+            mood: Mood::new(),
+            decay_rate: 0.15,
+        }
+    }
+
+    /// Lets `elapsed_secs` seconds of natural emotional decay pass.
+    pub fn decay(&mut self, elapsed_secs: f32) {
+        let decay_factor = (1.0 - self.decay_rate * elapsed_secs.max(0.0)).clamp(0.0, 1.0);
+
+        self.current.happiness *= decay_factor;
+        self.current.sadness *= decay_factor;
+        self.current.anger *= decay_factor;
+        self.current.fear *= decay_factor;
+    }
+
+    /// Applies an emotional stimulus (positive or negative).
+    pub fn apply_stimulus(&mut self, stimulus: &EmotionState, intensity: f32) {
+        self.current.blend(stimulus, intensity.clamp(0.0, 1.0));
+        self.mood.update_from_emotion(&self.current);
+    }
+
+    /// Lets the user's sentiment rub off on Astra. `valence` ranges over
+    /// -1.0..=1.0 and `arousal` over 0.0..=1.0; `agreeableness` scales how
+    /// readily Astra mirrors the user. Warm users raise happiness, hostile
+    /// ones raise anger and fear (stress) in proportion to their arousal.
+    pub fn apply_contagion(&mut self, valence: f32, arousal: f32, agreeableness: f32) {
+        let valence = valence.clamp(-1.0, 1.0);
+        let arousal = arousal.clamp(0.0, 1.0);
+        if valence == 0.0 {
+            return;
+        }
+
+        let stimulus = if valence > 0.0 {
+            EmotionState {
+                happiness: 0.5 + 0.5 * valence,
+                sadness: 0.0,
+                anger: 0.0,
+                fear: 0.0,
+            }
+        } else {
+            let negativity = -valence;
+            EmotionState {
+                happiness: self.current.happiness * (1.0 - negativity),
+                sadness: negativity * (1.0 - arousal) * 0.5,
+                anger: negativity * arousal,
+                fear: negativity * 0.5,
+            }
+        };
+
+        let intensity = MAX_CONTAGION_INTENSITY * valence.abs() * agreeableness.clamp(0.0, 1.0);
+        self.apply_stimulus(&stimulus, intensity);
+    }
+}
+
+impl Default for Affect {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decay_follows_supplied_time() {
+        let mut affect = Affect::new();
+        affect.apply_contagion(-1.0, 1.0, 1.0);
+        let angry = affect.current.anger;
+        assert!(angry > 0.0);
+
+        affect.decay(0.0);
+        assert_eq!(affect.current.anger, angry);
+        affect.decay(2.0);
+        assert!((affect.current.anger - angry * 0.7).abs() < 1e-6);
+        affect.decay(60.0);
+        assert_eq!(affect.current.anger, 0.0);
+    }
+}
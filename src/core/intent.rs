@@ -0,0 +1,92 @@
+// ============================================================================
+//                        ASTRA AGI • CORE INTENTS
+//        Intent Identity, Lifecycle States & Scheduling Order
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       The parts of an intent every deployment agrees on, free of `std`:
+//       how intents are identified, the states they move through, and the
+//       order in which they are scheduled. Timestamps are generic, so an
+//       embedded host orders intents by its own tick counter while the full
+//       runtime (`runtime::intent_manager`) uses `AstraTime`.
+//
+//   Core Functions:
+//       • Identify intents and track their lifecycle state
+//       • Order intents by priority, then deadline, then age
+//
+//   File:        /src/core/intent.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use core::cmp::Ordering;
+
+/// Unique identifier for an Intent.
+pub type IntentId = u64;
+
+/// Represents the current state of an intent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum IntentState {
+    Pending,
+    Active,
+    Completed,
+    Cancelled,
+}
+
+impl IntentState {
+    /// Whether the intent still has work ahead of it.
+    pub fn is_open(self) -> bool {
+        matches!(self, IntentState::Pending | IntentState::Active)
+    }
+}
+
+/// What decides when an intent is scheduled, with timestamps of type `T`.
+/// Keys compare by priority (higher first), then deadline (sooner first,
+/// and any deadline before none), then creation time (older first).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduleKey<T> {
+    pub priority: u32,
+    pub deadline: Option<T>,
+    pub created_at: T,
+}
+
+impl<T: Ord> PartialOrd for ScheduleKey<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> Ord for ScheduleKey<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+            .then_with(|| {
+                match (&self.deadline, &other.deadline) {
+                    (Some(a), Some(b)) => a.cmp(b),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                }
+            })
+            .then_with(|| self.created_at.cmp(&other.created_at))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_key_orders_by_priority_deadline_then_age() {
+        let key = |priority, deadline, created_at| ScheduleKey { priority, deadline, created_at };
+        assert!(key(5, None, 9u64) < key(1, Some(0), 0));
+        assert!(key(1, Some(3), 9) < key(1, Some(4), 0));
+        assert!(key(1, Some(4), 9) < key(1, None, 0));
+        assert!(key(1, None, 2) < key(1, None, 3));
+        assert!(IntentState::Active.is_open() && !IntentState::Cancelled.is_open());
+    }
+}
@@ -0,0 +1,34 @@
+// ============================================================================
+//                        ASTRA AGI • COGNITIVE CORE
+//        Allocation-Only Decision Logic for Embedded Deployments
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Holds the parts of Astra's decision loop that are pure computation:
+//       emotion dynamics, personality traits, intent scheduling, and planner
+//       search. Nothing here touches the operating system, so the layer
+//       compiles without `std` (it needs only `alloc`) and can run on an
+//       embedded robot. The crate's other layers, persistence, interfaces,
+//       the runtime and its planners, are gated behind the `std` feature and
+//       build on these types instead of defining their own. Time is never
+//       read here: callers say how much of it has passed.
+//
+//   Core Functions:
+//       • Evolve emotions and mood from stimuli and elapsed time
+//       • Hold and adjust Big Five personality traits
+//       • Identify intents, track their state, and order them for scheduling
+//       • Search for the cheapest sequence of actions reaching a goal
+//
+//   File:        /src/core/mod.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+pub mod affect;
+pub mod traits;
+pub mod intent;
+pub mod search;
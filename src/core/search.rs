@@ -0,0 +1,171 @@
+// ============================================================================
+//                        ASTRA AGI • CORE PLAN SEARCH
+//        Cheapest-First Search over Symbolic World States
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       The search at the bottom of Astra's planning, free of `std`. Given
+//       a world state, a goal, and operators with preconditions, effects,
+//       and costs, it finds the cheapest sequence of operators that reaches
+//       the goal. World states are ordered maps, so states already expanded
+//       are skipped without hashing, and search stops after a bounded number
+//       of expansions so it fits an embedded controller's budget. The GOAP
+//       planner in `planning` runs on it too, converting its actions into
+//       operators with `Operator::from`.
+//
+//   Core Functions:
+//       • Represent world states and operators without hashing
+//       • Find the cheapest operator sequence reaching a goal
+//       • Bound the work a search may do
+//
+//   File:        /src/core/search.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// A symbolic world state, or the part of one a goal or operator cares about.
+pub type Facts = BTreeMap<String, bool>;
+
+/// Something the agent can do, as search sees it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Operator {
+    pub preconditions: Facts,
+    pub effects: Facts,
+    /// Non-negative; negative costs count as zero
+    pub cost: f32,
+}
+
+/// The operators to apply, by index, and what they cost together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    pub steps: Vec<usize>,
+    pub cost: f32,
+}
+
+/// Whether every fact in `required` holds in `facts`.
+pub fn satisfied(facts: &Facts, required: &Facts) -> bool {
+    required.iter().all(|(k, v)| facts.get(k) == Some(v))
+}
+
+struct Node {
+    facts: Facts,
+    parent: Option<usize>,
+    operator: usize,
+    cost: f32,
+}
+
+/// A node waiting to be expanded; the heap pops the cheapest first.
+struct Frontier {
+    cost: f32,
+    node: usize,
+}
+
+impl PartialEq for Frontier {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Frontier {}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Frontier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost).then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+/// Finds the cheapest way from `start` to a state satisfying `goal`.
+/// Returns `None` when no sequence of operators reaches the goal, or when
+/// none was found within `max_expansions` expanded states.
+pub fn cheapest_plan(start: &Facts, goal: &Facts, operators: &[Operator], max_expansions: usize) -> Option<Solution> {
+    let mut nodes = Vec::new();
+    nodes.push(Node { facts: start.clone(), parent: None, operator: 0, cost: 0.0 });
+    let mut frontier = BinaryHeap::new();
+    frontier.push(Frontier { cost: 0.0, node: 0 });
+    let mut expanded = BTreeSet::new();
+
+    while let Some(Frontier { node: index, .. }) = frontier.pop() {
+        if satisfied(&nodes[index].facts, goal) {
+            return Some(solution(&nodes, index));
+        }
+        if !expanded.insert(nodes[index].facts.clone()) {
+            continue;
+        }
+        if expanded.len() > max_expansions {
+            return None;
+        }
+
+        for (operator_index, operator) in operators.iter().enumerate() {
+            if !satisfied(&nodes[index].facts, &operator.preconditions) {
+                continue;
+            }
+            let mut facts = nodes[index].facts.clone();
+            for (k, v) in &operator.effects {
+                facts.insert(k.clone(), *v);
+            }
+            if expanded.contains(&facts) {
+                continue;
+            }
+            let cost = nodes[index].cost + operator.cost.max(0.0);
+            nodes.push(Node { facts, parent: Some(index), operator: operator_index, cost });
+            frontier.push(Frontier { cost, node: nodes.len() - 1 });
+        }
+    }
+    None
+}
+
+fn solution(nodes: &[Node], goal: usize) -> Solution {
+    let mut steps = Vec::new();
+    let mut at = goal;
+    while let Some(parent) = nodes[at].parent {
+        steps.push(nodes[at].operator);
+        at = parent;
+    }
+    steps.reverse();
+    Solution { steps, cost: nodes[goal].cost }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn facts(pairs: &[(&str, bool)]) -> Facts {
+        pairs.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn test_cheapest_plan_prefers_lower_cost_over_fewer_steps() {
+        let operators = [
+            // Straight to the goal, but expensive
+            Operator { preconditions: Facts::new(), effects: facts(&[("charged", true)]), cost: 10.0 },
+            Operator { preconditions: Facts::new(), effects: facts(&[("docked", true)]), cost: 1.0 },
+            Operator { preconditions: facts(&[("docked", true)]), effects: facts(&[("charged", true)]), cost: 2.0 },
+        ];
+        let goal = facts(&[("charged", true)]);
+        let found = cheapest_plan(&Facts::new(), &goal, &operators, 100).unwrap();
+        assert_eq!(found.steps, [1, 2]);
+        assert_eq!(found.cost, 3.0);
+
+        // Already there
+        assert!(cheapest_plan(&goal, &goal, &operators, 100).unwrap().steps.is_empty());
+        // Unreachable, and out of budget
+        assert_eq!(cheapest_plan(&Facts::new(), &facts(&[("flying", true)]), &operators, 100), None);
+        assert_eq!(cheapest_plan(&Facts::new(), &goal, &operators, 1), None);
+    }
+}
@@ -0,0 +1,83 @@
+// ============================================================================
+//                      ASTRA AGI • CORE PERSONALITY TRAITS
+//        Big Five Trait Profile Shared by Every Deployment
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       The trait profile at the heart of Astra's personality, free of `std`
+//       so embedded deployments carry the same character as the full
+//       runtime. Conversation, relationships, and feedback handling build on
+//       it in `personality::personality`.
+//
+//   Core Functions:
+//       • Represent Big Five‑inspired personality traits
+//       • Adjust traits by name, within their bounds
+//       • Serialize traits, with std, for saved personality
+//
+//   File:        /src/core/traits.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+/// Core personality traits inspired by the Big Five model.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct PersonalityTraits {
+    pub openness: f32,            // Curiosity, creativity
+    pub conscientiousness: f32,   // Reliability, diligence
+    pub extraversion: f32,        // Sociability, enthusiasm
+    pub agreeableness: f32,       // Compassion, cooperativeness
+    pub neuroticism: f32,         // Emotional stability
+}
+
+impl PersonalityTraits {
+    /// Creates a default trait profile.
+    pub fn new() -> Self {
+        PersonalityTraits {
+            openness: 0.8,
+            conscientiousness: 0.7,
+            extraversion: 0.6,
+            agreeableness: 0.9,
+            neuroticism: 0.2,
+        }
+    }
+
+    /// Adjusts a trait by name, clamped between 0 and 1.
+    pub fn adjust_trait(&mut self, trait_name: &str, delta: f32) {
+        let val = match trait_name {
+            "openness" => &mut self.openness,
+            "conscientiousness" => &mut self.conscientiousness,
+            "extraversion" => &mut self.extraversion,
+            "agreeableness" => &mut self.agreeableness,
+            "neuroticism" => &mut self.neuroticism,
+            _ => return,
+        };
+        *val = (*val + delta).clamp(0.0, 1.0);
+    }
+}
+
+impl Default for PersonalityTraits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adjust_trait_clamps_and_ignores_unknown_names() {
+        let mut traits = PersonalityTraits::new();
+        traits.adjust_trait("openness", 5.0);
+        traits.adjust_trait("neuroticism", -5.0);
+        traits.adjust_trait("charisma", 1.0);
+        assert_eq!(traits.openness, 1.0);
+        assert_eq!(traits.neuroticism, 0.0);
+        assert_eq!(traits.agreeableness, 0.9);
+    }
+}
@@ -11,6 +11,7 @@
 //
 //   Core Functions:
 //       • Model transient affective states (urgency, motivation, stress)
+//       • Let the model of how stimuli move those states be substituted
 //       • Represent stable value structures for ethical alignment
 //       • Modulate task selection, focus, and reasoning pathways
 //       • Provide affect‑aware signals to higher‑order cognition
@@ -18,7 +19,7 @@
 //   File:        /src/emotion/emotion_value_models.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-24
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
@@ -27,6 +28,8 @@
 
 use std::collections::HashMap;
 
+use crate::metadata::Metadata;
+
 /// Represents core affective/emotional states influencing behavior.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct EmotionState {
@@ -68,6 +71,23 @@ impl EmotionState {
     }
 }
 
+/// How the runtime's emotion state responds to the stimuli of each tick.
+/// Embedding applications and tests can substitute their own, for example
+/// one that holds emotions still so behavior is reproducible.
+pub trait EmotionModel: Send {
+    fn update(&mut self, state: &mut EmotionState, stimuli: &HashMap<String, f32>);
+}
+
+/// The default model, `EmotionState::update`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StimulusEmotionModel;
+
+impl EmotionModel for StimulusEmotionModel {
+    fn update(&mut self, state: &mut EmotionState, stimuli: &HashMap<String, f32>) {
+        state.update(stimuli);
+    }
+}
+
 /// Represents Astra's core values influencing ethical and sustainable behavior.
 #[derive(Debug, Clone)]
 pub struct ValueModel {
@@ -101,7 +121,7 @@ impl ValueModel {
 
 /// Combines emotion and value states to compute a task priority modifier.
 /// This modifier can increase or decrease task priority based on affective and ethical factors.
-pub fn compute_priority_modifier(emotion: &EmotionState, values: &ValueModel, task_metadata: &HashMap<String, f32>) -> f32 {
+pub fn compute_priority_modifier(emotion: &EmotionState, values: &ValueModel, task_metadata: &Metadata) -> f32 {
     // Simple weighted sum example:
     let urgency_weight = 0.6;
     let motivation_weight = 0.3;
@@ -118,8 +138,8 @@ pub fn compute_priority_modifier(emotion: &EmotionState, values: &ValueModel, ta
     modifier += motivation_weight * emotion.motivation;
     modifier += stress_weight * emotion.stress;
 
-    // Example: if task metadata has "ethical_importance", amplify by values
-    if let Some(&ethical_importance) = task_metadata.get("ethical_importance") {
+    // Example: if task metadata has a numeric "ethical_importance", amplify by values
+    if let Some(ethical_importance) = task_metadata.get_f64("ethical_importance").map(|v| v as f32) {
         modifier += compassion_weight * compassion * ethical_importance;
         modifier += integrity_weight * integrity * ethical_importance;
     }
@@ -158,8 +178,7 @@ mod tests {
         };
         let values = ValueModel::new();
 
-        let mut task_metadata = HashMap::new();
-        task_metadata.insert("ethical_importance".to_string(), 0.8);
+        let task_metadata = Metadata::from([("ethical_importance", 0.8)]);
 
         let modifier = compute_priority_modifier(&emotion, &values, &task_metadata);
         assert!(modifier > 0.0);
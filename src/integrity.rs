@@ -0,0 +1,337 @@
+// =============================================================================
+//  Astra AGI - Integrity of Persistent State
+//  File: integrity.rs
+//
+//  Description: Corruption detection shared by the stores Astra persists.
+//  A payload is sealed with its length and CRC-32 before it is written, and
+//  checked when read back, so a torn write or flipped bit is reported
+//  instead of being loaded as garbage or panicking a decoder. Snapshots are
+//  kept in generations: writing one moves the previous snapshots down a
+//  generation, and loading takes the newest generation that passes its
+//  checksum and decodes, reporting every newer one it passed over and why;
+//  a decoder's own error is kept as the source of the report, and so is
+//  the I/O error of a snapshot that exists but cannot be read.
+//  Files written before sealing was introduced carry no checksum and are
+//  loaded as they are.
+//
+//  Layout of a sealed payload:
+//      magic · payload length (u64 LE) · CRC-32 of payload (u32 LE) · payload
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+/// Opens every sealed payload; the last byte is the format version.
+const MAGIC: &[u8; 8] = b"ASTRASL1";
+
+/// Bytes before a sealed payload: magic, length, and checksum.
+const HEADER: usize = 20;
+
+/// CRC-32 (IEEE), as used by zip and Ethernet.
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+/// `payload` with its length and checksum in front.
+pub fn seal(payload: &[u8]) -> Vec<u8> {
+    let mut sealed = Vec::with_capacity(HEADER + payload.len());
+    sealed.extend_from_slice(MAGIC);
+    sealed.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    sealed.extend_from_slice(&crc32(payload).to_le_bytes());
+    sealed.extend_from_slice(payload);
+    sealed
+}
+
+/// Whether `bytes` were sealed, rather than written before sealing was
+/// introduced.
+pub fn is_sealed(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+/// The payload of sealed `bytes`, once its length and checksum check out.
+pub fn unseal(bytes: &[u8]) -> Result<&[u8], Corruption> {
+    if !is_sealed(bytes) {
+        return Err(Corruption::NotSealed);
+    }
+    if bytes.len() < HEADER {
+        return Err(Corruption::Truncated { expected: HEADER as u64, found: bytes.len() as u64 });
+    }
+    let length = u64::from_le_bytes(bytes[8..16].try_into().expect("eight bytes"));
+    let expected = u32::from_le_bytes(bytes[16..20].try_into().expect("four bytes"));
+    let payload = &bytes[HEADER..];
+    if payload.len() as u64 != length {
+        return Err(Corruption::Truncated { expected: length, found: payload.len() as u64 });
+    }
+    let found = crc32(payload);
+    if found != expected {
+        return Err(Corruption::ChecksumMismatch { expected, found });
+    }
+    Ok(payload)
+}
+
+/// What is wrong with stored bytes.
+#[derive(Debug, Error)]
+pub enum Corruption {
+    #[error("not a sealed payload")]
+    NotSealed,
+    /// Fewer or more bytes than were written
+    #[error("holds {found} bytes where {expected} were written")]
+    Truncated { expected: u64, found: u64 },
+    #[error("checksum is {found:08x}, {expected:08x} was written")]
+    ChecksumMismatch { expected: u32, found: u32 },
+    /// The checksum held, or there was none, but the contents do not decode
+    #[error("cannot be decoded: {0}")]
+    Undecodable(#[source] Box<dyn StdError + Send + Sync>),
+    /// The bytes could not be read at all, for a reason other than absence
+    #[error("cannot be read: {0}")]
+    Unreadable(#[source] std::io::Error),
+}
+
+/// A snapshot passed over while loading, and why.
+#[derive(Debug)]
+pub struct CorruptionReport {
+    /// File or storage key of the snapshot
+    pub location: String,
+    /// 0 for the newest snapshot, 1 for the one before, and so on
+    pub generation: usize,
+    pub problem: Corruption,
+}
+
+impl fmt::Display for CorruptionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (generation {}) {}", self.location, self.generation, self.problem)
+    }
+}
+
+/// A loaded snapshot and the newer ones that had to be passed over.
+#[derive(Debug)]
+pub struct Recovered<T> {
+    pub value: T,
+    pub generation: usize,
+    /// Newest first
+    pub skipped: Vec<CorruptionReport>,
+}
+
+impl<T> Recovered<T> {
+    /// Whether an older snapshot was loaded because newer ones were corrupt.
+    pub fn fell_back(&self) -> bool {
+        !self.skipped.is_empty()
+    }
+
+    /// Logs a warning for each snapshot passed over.
+    pub fn warn_skipped(&self) {
+        for report in &self.skipped {
+            log::warn!("Passed over corrupt snapshot {}; loaded generation {}", report, self.generation);
+        }
+    }
+}
+
+/// Why no snapshot could be loaded.
+#[derive(Debug, Error)]
+pub enum IntegrityError {
+    #[error("No snapshot at {}", .0.display())]
+    Missing(PathBuf),
+    #[error("Could not write snapshot {}", path.display())]
+    Write { path: PathBuf, #[source] source: std::io::Error },
+    /// Every snapshot kept is corrupt; newest first
+    #[error("No intact snapshot: {}", listed(.0))]
+    NoIntactSnapshot(Vec<CorruptionReport>),
+}
+
+/// Decodes `bytes` once their seal checks out; unsealed bytes are decoded
+/// unchecked.
+pub fn open<T, E: StdError + Send + Sync + 'static>(
+    bytes: &[u8],
+    decode: impl Fn(&[u8]) -> Result<T, E>,
+) -> Result<T, Corruption> {
+    let payload = if is_sealed(bytes) { unseal(bytes)? } else { bytes };
+    decode(payload).map_err(|e| Corruption::Undecodable(Box::new(e)))
+}
+
+/// Decodes the newest of `candidates` that is intact, reporting those
+/// passed over. Each candidate is its location and bytes, `None` if there
+/// is nothing there, or why it could not be read.
+pub fn recover<T, E: StdError + Send + Sync + 'static>(
+    candidates: impl IntoIterator<Item = (String, Result<Option<Vec<u8>>, Corruption>)>,
+    decode: impl Fn(&[u8]) -> Result<T, E>,
+) -> Result<Option<Recovered<T>>, Vec<CorruptionReport>> {
+    let mut skipped = Vec::new();
+    let mut found = false;
+    for (generation, (location, bytes)) in candidates.into_iter().enumerate() {
+        let decoded = match bytes {
+            Ok(None) => continue,
+            Ok(Some(bytes)) => open(&bytes, &decode),
+            Err(problem) => Err(problem),
+        };
+        found = true;
+        match decoded {
+            Ok(value) => return Ok(Some(Recovered { value, generation, skipped })),
+            Err(problem) => skipped.push(CorruptionReport { location, generation, problem }),
+        }
+    }
+    if found {
+        Err(skipped)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Snapshot files at a path, the previous ones kept beside it as
+/// `<path>.1`, `<path>.2`, and so on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generations {
+    path: PathBuf,
+    /// Snapshots kept, the newest included
+    keep: usize,
+}
+
+impl Generations {
+    /// Snapshots at `path`, keeping `keep` of them (at least one).
+    pub fn new(path: impl Into<PathBuf>, keep: usize) -> Self {
+        Generations { path: path.into(), keep: keep.max(1) }
+    }
+
+    /// Where generation `generation` is kept.
+    pub fn path(&self, generation: usize) -> PathBuf {
+        if generation == 0 {
+            return self.path.clone();
+        }
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", generation));
+        PathBuf::from(name)
+    }
+
+    /// Seals and writes `payload` as the newest snapshot, moving the others
+    /// down a generation and dropping the oldest. The new snapshot is
+    /// synced before anything is moved, so a crash leaves the old ones.
+    pub fn write(&self, payload: &[u8]) -> Result<(), IntegrityError> {
+        let partial = self.path.with_extension("partial");
+        let error = |path: &Path| {
+            let path = path.to_path_buf();
+            move |source| IntegrityError::Write { path, source }
+        };
+        let mut out = File::create(&partial).map_err(error(&partial))?;
+        out.write_all(&seal(payload)).and_then(|_| out.sync_all()).map_err(error(&partial))?;
+        for generation in (0..self.keep - 1).rev() {
+            let from = self.path(generation);
+            if from.exists() {
+                fs::rename(&from, self.path(generation + 1)).map_err(error(&from))?;
+            }
+        }
+        fs::rename(&partial, &self.path).map_err(error(&self.path))
+    }
+
+    /// The newest snapshot that is intact and decodes, with a report of
+    /// newer ones passed over.
+    pub fn load<T, E: StdError + Send + Sync + 'static>(
+        &self,
+        decode: impl Fn(&[u8]) -> Result<T, E>,
+    ) -> Result<Recovered<T>, IntegrityError> {
+        let candidates = (0..self.keep.max(self.kept())).map(|generation| {
+            let path = self.path(generation);
+            let bytes = match fs::read(&path) {
+                Ok(bytes) => Ok(Some(bytes)),
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(error) => Err(Corruption::Unreadable(error)),
+            };
+            (path.display().to_string(), bytes)
+        });
+        match recover(candidates, decode) {
+            Ok(Some(recovered)) => Ok(recovered),
+            Ok(None) => Err(IntegrityError::Missing(self.path.clone())),
+            Err(reports) => Err(IntegrityError::NoIntactSnapshot(reports)),
+        }
+    }
+
+    /// Generations present on disk, counting up from the newest until one
+    /// is missing; a store that kept more snapshots before is still read.
+    fn kept(&self) -> usize {
+        (0..).take_while(|&generation| self.path(generation).exists()).count()
+    }
+}
+
+fn listed(reports: &[CorruptionReport]) -> String {
+    reports.iter().map(|r| r.to_string()).collect::<Vec<_>>().join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(bytes: &[u8]) -> Result<String, std::string::FromUtf8Error> {
+        String::from_utf8(bytes.to_vec())
+    }
+
+    #[test]
+    fn test_corrupt_snapshots_fall_back_to_the_previous_good_one() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        let sealed = seal(b"payload");
+        assert_eq!(unseal(&sealed).ok(), Some(&b"payload"[..]));
+        assert!(matches!(unseal(&sealed[..sealed.len() - 1]), Err(Corruption::Truncated { expected: 7, found: 6 })));
+
+        let dir = std::env::temp_dir().join(format!("astra-integrity-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let snapshots = Generations::new(dir.join("state.json"), 3);
+        assert!(matches!(snapshots.load(decode), Err(IntegrityError::Missing(_))));
+        for version in ["one", "two", "three", "four"] {
+            snapshots.write(version.as_bytes()).unwrap();
+        }
+        assert!(!snapshots.path(3).exists());
+        let loaded = snapshots.load(decode).unwrap();
+        assert_eq!((loaded.value.as_str(), loaded.generation, loaded.fell_back()), ("four", 0, false));
+
+        // A flipped bit in the newest, a torn write in the next
+        let mut bytes = fs::read(snapshots.path(0)).unwrap();
+        bytes[HEADER] ^= 1;
+        fs::write(snapshots.path(0), bytes).unwrap();
+        let bytes = fs::read(snapshots.path(1)).unwrap();
+        fs::write(snapshots.path(1), &bytes[..bytes.len() - 2]).unwrap();
+        let loaded = snapshots.load(decode).unwrap();
+        assert_eq!((loaded.value.as_str(), loaded.generation), ("two", 2));
+        assert!(matches!(loaded.skipped[0].problem, Corruption::ChecksumMismatch { .. }));
+        assert!(matches!(loaded.skipped[1].problem, Corruption::Truncated { .. }));
+        assert!(loaded.skipped[1].to_string().contains("state.json.1 (generation 1) holds"));
+
+        // Checksums that hold over contents that do not decode are passed over too
+        fs::write(snapshots.path(2), seal(&[0xff])).unwrap();
+        let Err(IntegrityError::NoIntactSnapshot(reports)) = snapshots.load(decode) else {
+            panic!("expected every snapshot to be reported");
+        };
+        assert!(matches!(reports[2].problem, Corruption::Undecodable(_)));
+        let source = reports[2].problem.source().expect("the decoder's error is kept as the source");
+        assert!(source.is::<std::string::FromUtf8Error>());
+
+        // Files from before sealing load unchecked
+        fs::write(snapshots.path(0), b"legacy").unwrap();
+        assert_eq!(snapshots.load(decode).unwrap().value, "legacy");
+
+        // A snapshot that exists but cannot be read is reported with why
+        fs::remove_file(snapshots.path(0)).unwrap();
+        fs::create_dir(snapshots.path(0)).unwrap();
+        let Err(IntegrityError::NoIntactSnapshot(reports)) = snapshots.load(decode) else {
+            panic!("expected the unreadable snapshot to be reported");
+        };
+        assert!(matches!(reports[0].problem, Corruption::Unreadable(_)));
+        assert!(reports[0].problem.source().is_some_and(|source| source.is::<std::io::Error>()));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
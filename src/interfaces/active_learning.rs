@@ -0,0 +1,372 @@
+// ============================================================================
+//                      ASTRA AGI • ACTIVE LEARNING QUESTIONS
+//        Asking the User for What a Blocked Goal Needs to Know
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Interfaces Layer that turns what stands in the
+//       way of a goal into questions for the person Astra is talking to.
+//       A goal can be blocked by a gap—a name only the user has mentioned,
+//       or a state nothing in the knowledge base speaks to—or by a fact
+//       only a poorly trusted source vouches for. Each becomes a targeted
+//       question ("Do you know whether the door is locked?"). Questions are
+//       rationed per user so that Astra does not pester anyone, and an
+//       answer is read for statements that enter the knowledge base as
+//       facts from the user, trusted well above anything crawled.
+//
+//   Core Functions:
+//       • Phrase questions about gaps and doubtful facts that block a goal
+//       • Track how many questions each user has been asked, and back off
+//       • Keep one question pending per user until it is answered
+//       • Extract facts from answers, with high trust and the user's provenance
+//
+//   File:        /src/interfaces/active_learning.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::knowledge::belief_ledger::FactKey;
+use crate::knowledge::extended_ontology::{name_entity_id, EntityId, Fact, OntologyManager, Provenance};
+use crate::knowledge::knowledge_gaps::{KnowledgeGapDetector, LearningChannel};
+use crate::knowledge::source_reliability::SourceReliability;
+use crate::planning::planner::Goal;
+use crate::web_crawler::documents::statements;
+
+/// Confidence given to facts a user states in answer to a question.
+pub const ANSWER_CONFIDENCE: f32 = 0.9;
+
+/// Facts only vouched for by sources trusted less than this are checked
+/// with the user.
+pub const LOW_SOURCE_TRUST: f64 = 0.4;
+
+/// Why a question is worth asking.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum QuestionReason {
+    /// Nothing Astra knows speaks to it
+    KnowledgeGap,
+    /// Only a source trusted this much vouches for it
+    UnreliableSource { source: String, trust: f64 },
+}
+
+/// A claim a yes-or-no question asks the user to confirm.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Claim {
+    pub subject: EntityId,
+    pub predicate: String,
+    pub object: String,
+}
+
+/// A question meant to teach Astra something.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KnowledgeQuestion {
+    /// Normalized topic, so the same thing is not asked about twice
+    pub topic: String,
+    pub question: String,
+    pub reason: QuestionReason,
+    /// Set for "Do you know whether ..." questions
+    pub claim: Option<Claim>,
+    /// Goal the answer would unblock
+    pub goal_id: Option<String>,
+    pub asked_at: u64,
+}
+
+/// A question and the facts its answer gave.
+#[derive(Debug, Clone)]
+pub struct AnsweredQuestion {
+    pub question: KnowledgeQuestion,
+    pub facts: Vec<Fact>,
+}
+
+/// Questions that would unblock `goal`, most pressing first: names in the
+/// goal only the user has brought up, then states of the world the goal
+/// needs that only unreliable sources report, then states nothing reports.
+pub fn questions_for_goal(
+    goal: &Goal,
+    gaps: &KnowledgeGapDetector,
+    knowledge: &OntologyManager,
+    reliability: &SourceReliability,
+) -> Vec<KnowledgeQuestion> {
+    let question = |topic: String, question: String, reason, claim| KnowledgeQuestion {
+        topic,
+        question,
+        reason,
+        claim,
+        goal_id: Some(goal.id.clone()),
+        asked_at: 0,
+    };
+
+    let mut terms: Vec<String> = std::iter::once(goal.id.as_str())
+        .chain(std::iter::once(goal.description.as_str()))
+        .chain(goal.desired_state.keys().map(String::as_str))
+        .flat_map(|text| text.split(|c: char| !c.is_alphanumeric()))
+        .filter(|term| term.len() > 2)
+        .map(str::to_lowercase)
+        .collect();
+    terms.sort();
+    terms.dedup();
+    let mut questions: Vec<KnowledgeQuestion> = terms
+        .iter()
+        .filter_map(|term| gaps.get(term))
+        .filter(|gap| gap.channel() == LearningChannel::AskUser)
+        .map(|gap| {
+            let asked = format!("Do you know what {} is?", gap.topic);
+            question(gap.topic.clone(), asked, QuestionReason::KnowledgeGap, None)
+        })
+        .collect();
+
+    let mut keys: Vec<(&String, &bool)> = goal.desired_state.iter().collect();
+    keys.sort();
+    let mut unknown = Vec::new();
+    for (key, value) in keys {
+        // The latest version of each fact about the key
+        let mut held: HashMap<FactKey, &Fact> = HashMap::new();
+        for fact in knowledge.query_facts(None).into_iter().filter(|f| f.predicate == *key) {
+            held.insert(FactKey::from(fact), fact);
+        }
+        let words = key.replace('_', " ");
+        if held.is_empty() {
+            let claim = Claim { subject: name_entity_id(key), predicate: key.clone(), object: value.to_string() };
+            let asked = format!("Do you know whether {} is {}?", words, value);
+            unknown.push(question(key.to_lowercase(), asked, QuestionReason::KnowledgeGap, Some(claim)));
+            continue;
+        }
+
+        let trust = |fact: &Fact| reliability.trust(&fact.provenance.source_name);
+        if held.values().any(|fact| trust(fact) >= LOW_SOURCE_TRUST) {
+            continue;
+        }
+        let Some(doubtful) = held.into_values().max_by(|a, b| trust(a).total_cmp(&trust(b))) else {
+            continue;
+        };
+        let source = doubtful.provenance.source_name.clone();
+        let asked = format!("Do you know whether {} is {}? I only have it from {}.", words, doubtful.object, source);
+        let reason = QuestionReason::UnreliableSource { trust: trust(doubtful), source };
+        let claim = Claim {
+            subject: doubtful.subject,
+            predicate: doubtful.predicate.clone(),
+            object: doubtful.object.clone(),
+        };
+        questions.push(question(key.to_lowercase(), asked, reason, Some(claim)));
+    }
+    questions.extend(unknown);
+    questions
+}
+
+/// Facts the user's `answer` to `question` states. A "yes" confirms the
+/// question's claim and a "no" to a true-or-false claim asserts the
+/// opposite; "X is Y" statements are taken as well, with "it" or "that"
+/// standing for the topic. All get `ANSWER_CONFIDENCE` and the user as
+/// their source.
+pub fn answer_facts(question: &KnowledgeQuestion, answer: &str, user_id: &str) -> Vec<Fact> {
+    let provenance = Provenance::from_user(user_id, Some(format!("answer to '{}'", question.question)));
+    let fact = |subject, predicate: &str, object: &str| Fact {
+        subject,
+        predicate: predicate.to_string(),
+        object: object.to_string(),
+        confidence: ANSWER_CONFIDENCE,
+        provenance: provenance.clone(),
+    };
+
+    let mut facts = Vec::new();
+    if let Some(claim) = &question.claim {
+        let lower = answer.trim().to_lowercase();
+        let first = lower.split(|c: char| !c.is_alphanumeric()).next().unwrap_or("");
+        let opposite = match claim.object.as_str() {
+            "true" => Some("false"),
+            "false" => Some("true"),
+            _ => None,
+        };
+        if matches!(first, "yes" | "yeah" | "yep" | "correct" | "right" | "sure") {
+            facts.push(fact(claim.subject, &claim.predicate, &claim.object));
+        } else if let ("no" | "nope", Some(opposite)) = (first, opposite) {
+            facts.push(fact(claim.subject, &claim.predicate, opposite));
+        }
+    }
+
+    for (subject, object) in statements(answer) {
+        // "Yes, it is ..." is about the topic too
+        let subject = subject.rsplit(", ").next().unwrap_or(&subject).to_string();
+        let subject = match subject.to_lowercase().as_str() {
+            "it" | "that" | "this" | "they" => question.topic.clone(),
+            _ => subject,
+        };
+        facts.push(fact(name_entity_id(&subject), "is", &object));
+    }
+    facts
+}
+
+/// What one user has been asked.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct UserQuestions {
+    /// When each question was asked, oldest first
+    asked: Vec<u64>,
+    topics: HashSet<String>,
+    pending: Option<KnowledgeQuestion>,
+    /// Questions in a row that went without a usable answer
+    unanswered: u32,
+}
+
+/// Rations questions per user and holds the one each is being asked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestionAsker {
+    /// Questions any one user may be asked in a day
+    pub max_per_day: usize,
+    /// Seconds between questions to the same user, doubled for every
+    /// question in a row that went unanswered
+    pub min_interval: u64,
+    users: HashMap<String, UserQuestions>,
+}
+
+impl Default for QuestionAsker {
+    fn default() -> Self {
+        QuestionAsker { max_per_day: 3, min_interval: 30 * 60, users: HashMap::new() }
+    }
+}
+
+impl QuestionAsker {
+    const DAY: u64 = 24 * 60 * 60;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How much of the day's allowance of questions `user_id` has used.
+    pub fn fatigue(&self, user_id: &str, now: u64) -> f32 {
+        if self.max_per_day == 0 {
+            return 1.0;
+        }
+        let asked = self.users.get(user_id).map_or(0, |u| self.asked_today(u, now));
+        (asked as f32 / self.max_per_day as f32).min(1.0)
+    }
+
+    fn asked_today(&self, user: &UserQuestions, now: u64) -> usize {
+        user.asked.iter().filter(|&&t| t + Self::DAY > now).count()
+    }
+
+    /// Whether `user_id` may be asked another question at `now`.
+    pub fn can_ask(&self, user_id: &str, now: u64) -> bool {
+        let Some(user) = self.users.get(user_id) else {
+            return self.max_per_day > 0;
+        };
+        let wait = self.min_interval.saturating_mul(1 << user.unanswered.min(16));
+        user.pending.is_none()
+            && self.asked_today(user, now) < self.max_per_day
+            && user.asked.last().is_none_or(|&last| last.saturating_add(wait) <= now)
+    }
+
+    /// Asks `user_id` the first of `candidates` about a topic they have not
+    /// been asked about, if they may be asked anything right now.
+    pub fn ask(&mut self, user_id: &str, candidates: Vec<KnowledgeQuestion>, now: u64) -> Option<&KnowledgeQuestion> {
+        if !self.can_ask(user_id, now) {
+            return None;
+        }
+        let user = self.users.entry(user_id.to_string()).or_default();
+        let mut question = candidates.into_iter().find(|q| !user.topics.contains(&q.topic))?;
+        question.asked_at = now;
+        user.asked.retain(|&t| t + Self::DAY > now);
+        user.asked.push(now);
+        user.topics.insert(question.topic.clone());
+        user.pending = Some(question);
+        user.pending.as_ref()
+    }
+
+    /// The question `user_id` has yet to answer.
+    pub fn pending(&self, user_id: &str) -> Option<&KnowledgeQuestion> {
+        self.users.get(user_id)?.pending.as_ref()
+    }
+
+    /// Reads `text` as the answer to the question pending for `user_id`
+    /// and adds the facts it gives to `knowledge`. An answer that gives no
+    /// facts still settles the question, but makes Astra wait longer before
+    /// asking that user again.
+    pub fn answer(&mut self, user_id: &str, text: &str, knowledge: &mut OntologyManager) -> Option<AnsweredQuestion> {
+        let user = self.users.get_mut(user_id)?;
+        let question = user.pending.take()?;
+        let facts = answer_facts(&question, text, user_id);
+        if facts.is_empty() {
+            user.unanswered += 1;
+        } else {
+            user.unanswered = 0;
+        }
+        for fact in &facts {
+            knowledge.add_fact(fact.clone());
+        }
+        Some(AnsweredQuestion { question, facts })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::knowledge_gaps::GapKind;
+
+    #[test]
+    fn test_blocked_goal_questions_are_rationed_and_answers_become_trusted_facts() {
+        let goal = Goal {
+            id: "visit_zorbl".into(),
+            description: "Go to Zorbl".into(),
+            desired_state: HashMap::from([("door_locked".to_string(), false), ("car_ready".to_string(), true)]),
+            priority: 5,
+        };
+        let mut gaps = KnowledgeGapDetector::new();
+        gaps.record("Zorbl", GapKind::UnknownEntity);
+        let mut knowledge = OntologyManager::new();
+        knowledge.add_fact(Fact {
+            subject: name_entity_id("car"),
+            predicate: "car_ready".into(),
+            object: "true".into(),
+            confidence: 0.6,
+            provenance: Provenance::new("forum", None),
+        });
+        let mut reliability = SourceReliability::new();
+        for _ in 0..4 {
+            reliability.record_contradiction("forum");
+        }
+
+        let questions = questions_for_goal(&goal, &gaps, &knowledge, &reliability);
+        let asked: Vec<&str> = questions.iter().map(|q| q.question.as_str()).collect();
+        assert_eq!(
+            asked,
+            vec![
+                "Do you know what zorbl is?",
+                "Do you know whether car ready is true? I only have it from forum.",
+                "Do you know whether door locked is false?",
+            ]
+        );
+        assert!(matches!(questions[1].reason, QuestionReason::UnreliableSource { .. }));
+
+        let mut asker = QuestionAsker::new();
+        let now = 1_000_000;
+        assert_eq!(asker.ask("sam", questions.clone(), now).unwrap().topic, "zorbl");
+        // One question at a time, and not too often
+        assert!(asker.ask("sam", questions.clone(), now).is_none());
+        let answered = asker.answer("sam", "It is a lake town.", &mut knowledge).unwrap();
+        assert_eq!(answered.facts.len(), 1);
+        assert_eq!(answered.facts[0].subject, name_entity_id("zorbl"));
+        assert_eq!(answered.facts[0].object, "a lake town");
+        assert_eq!(answered.facts[0].confidence, ANSWER_CONFIDENCE);
+        assert_eq!(answered.facts[0].provenance.user_id(), Some("sam"));
+        assert!(!asker.can_ask("sam", now + 60));
+
+        // Already-asked topics are skipped
+        let next = asker.ask("sam", questions.clone(), now + 1800).unwrap();
+        assert_eq!(next.topic, "car_ready");
+        let answered = asker.answer("sam", "Yes, it was fixed.", &mut knowledge).unwrap();
+        assert_eq!((answered.facts[0].predicate.as_str(), answered.facts[0].object.as_str()), ("car_ready", "true"));
+
+        // A non-answer doubles the wait
+        asker.ask("sam", questions, now + 3600).unwrap();
+        assert!(asker.answer("sam", "Not sure, sorry", &mut knowledge).unwrap().facts.is_empty());
+        assert!((asker.fatigue("sam", now + 3600) - 1.0).abs() < 1e-6);
+        assert!(!asker.can_ask("sam", now + 3600 + 3000));
+        assert!(asker.can_ask("sam", now + QuestionAsker::DAY + 3600));
+        assert_eq!(asker.fatigue("alex", now), 0.0);
+    }
+}
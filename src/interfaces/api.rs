@@ -11,78 +11,566 @@
 //   Core Functions:
 //       • Provide REST endpoints for structured queries and control actions
 //       • Maintain WebSocket channels for streaming, events, and live updates
+//       • Stream chat replies token by token, cancellable mid-reply; a
+//         cancelled reply is remembered as only the text that was sent
 //       • Route inbound messages into the cognitive pipeline
+//       • Negotiate requests through planned dialogue turns
+//       • Authenticate callers and reserve admin operations for admins
+//       • Bind sessions to their user's relationship and memories
+//       • Update the model of each user's goals, beliefs, and mood
+//       • Accept structured feedback on earlier responses
+//       • Shape replies to the user's learned style preferences
+//       • Withhold replies the runtime's hooks veto
+//       • Prime replies with memories associated with the message
+//       • Read the knowledge base from snapshots, never waiting on writers
+//       • Track conversation sessions and summarize them when they end
+//       • Report progress of the plans pursuing an intent
+//       • Serve Astra's structured self-report
+//       • Serve transparency reports on a window of Astra's activity
+//       • Export runtime health metrics for Prometheus
+//       • Accept operator commands to pause, retune, and maintain Astra
+//       • Run Astra programs for admins; chat messages are never executed
+//       • Let humans review and sign off self-modification proposals
 //       • Expose safe, observable access to runtime state and activity
 //
 //   File:        /src/interfaces/api.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-23
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
-use actix_web::{web, HttpResponse, Responder};
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use actix_ws::Message;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use thiserror::Error;
 use tokio::sync::Mutex;
 
+use crate::learning::feedback::{FeedbackEvent, FeedbackKind, ResponseId};
+use crate::cognition::error::CognitionError;
+use crate::cognition::self_modification::ProposalId;
+use crate::interfaces::auth::{AuthError, Authenticator, Principal, Role, SessionStore};
+use crate::interfaces::streaming::{ResponseStream, TemplateSource, DEFAULT_CAPACITY};
+use crate::knowledge::snapshots::SharedKnowledge;
+use crate::runtime::admin::AdminCommand;
+use crate::runtime::error::RuntimeError;
+use crate::runtime::executor::ProgramStatus;
+use crate::runtime::intent_manager::IntentId;
 use crate::runtime::Runtime;
+use crate::time::AstraTime;
 
 #[derive(Debug, Deserialize)]
 pub struct ChatRequest {
     pub message: String,
+    /// Groups messages into a conversation that is summarized when it ends.
+    /// The session belongs to the user who first sends to it.
+    #[serde(default)]
+    pub session_id: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct ChatResponse {
+    /// Reference for feedback about this reply
+    pub response_id: ResponseId,
     pub reply: String,
     pub emotion_state: String,
     pub personality_traits: String,
     pub recent_events: Vec<String>,
+    /// Recap of the previous session, sent on the first message of a new one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primer: Option<String>,
+    /// Past events the message called to mind, most associated first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub associations: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EndSessionRequest {
+    pub session_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionResponse {
+    pub session_id: String,
+    pub user_id: String,
+    pub role: Role,
+    /// Recap of the user's previous session
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primer: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FeedbackRequest {
+    pub response_id: ResponseId,
+    pub kind: FeedbackKind,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeedbackResponse {
+    pub reward: f32,
+    pub credited_components: Vec<String>,
+}
+
+/// Progress of the plan pursuing an intent.
+#[derive(Debug, Serialize)]
+pub struct IntentProgressResponse {
+    pub intent_id: IntentId,
+    pub description: String,
+    pub fraction: f32,
+    pub completed_actions: usize,
+    pub total_actions: usize,
+    pub milestones_reached: Vec<String>,
+    pub milestones_total: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_remaining_secs: Option<u64>,
+    /// "waiting", "progressing", "stalled" or "finished"
+    pub phase: String,
+    pub blocked_on: Vec<String>,
+}
+
+/// Window of a transparency report, as Unix seconds or milliseconds.
+#[derive(Debug, Deserialize)]
+pub struct TransparencyQuery {
+    pub from: AstraTime,
+    pub to: AstraTime,
+    /// "text" for readable text; JSON otherwise
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// An Astra program for `/run`.
+#[derive(Debug, Deserialize)]
+pub struct RunProgramRequest {
+    pub source: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RunProgramResponse {
+    /// "finished" or "suspended"
+    pub status: String,
+    /// The value `main` returned, or what the program is waiting for
+    pub detail: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProposalDecisionRequest {
+    pub approve: bool,
+    /// Required context when rejecting
+    #[serde(default)]
+    pub reason: Option<String>,
 }
 
 /// Astra API handler struct wrapping shared runtime instance.
 #[derive(Clone)]
 pub struct AstraApi {
     pub runtime: Arc<Mutex<Runtime>>,
+    pub auth: Arc<Authenticator>,
+    pub sessions: Arc<Mutex<SessionStore>>,
+    /// Knowledge base replies draw on, read through snapshots
+    pub knowledge: Option<SharedKnowledge>,
+}
+
+/// Why a chat message was not answered.
+#[derive(Debug, Error)]
+enum ReplyError {
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Runtime(#[from] RuntimeError),
+}
+
+/// 401 for callers who could not be identified, 403 for those who may not.
+fn auth_failure(error: AuthError) -> HttpResponse {
+    if error.is_forbidden() {
+        HttpResponse::Forbidden().body(error.to_string())
+    } else {
+        HttpResponse::Unauthorized()
+            .insert_header(("WWW-Authenticate", "Bearer"))
+            .body(error.to_string())
+    }
 }
 
 impl AstraApi {
-    /// Creates a new AstraApi instance with shared runtime.
-    pub fn new(runtime: Arc<Mutex<Runtime>>) -> Self {
-        Self { runtime }
+    /// Creates a new AstraApi instance with shared runtime, accepting the
+    /// credentials `auth` knows.
+    pub fn new(runtime: Arc<Mutex<Runtime>>, auth: Authenticator) -> Self {
+        Self { runtime, auth: Arc::new(auth), sessions: Arc::new(Mutex::new(SessionStore::new())), knowledge: None }
     }
 
-    /// Handles chat message POST requests asynchronously.
-    pub async fn chat_handler(&self, req: web::Json<ChatRequest>) -> impl Responder {
+    /// Lets replies draw on `knowledge`. Each reply reads the snapshot
+    /// published by the latest finished write, so the crawler and executor
+    /// can keep writing while it is composed.
+    pub fn with_knowledge(mut self, knowledge: SharedKnowledge) -> Self {
+        self.knowledge = Some(knowledge);
+        self
+    }
+
+    /// The caller behind the request's `X-Api-Key` or `Authorization`
+    /// header, if they hold at least `role`.
+    pub fn authenticate(&self, http: &HttpRequest, role: Role) -> Result<Principal, AuthError> {
+        let header = |name: &str| http.headers().get(name).and_then(|value| value.to_str().ok());
+        let principal = self.auth.authenticate(header("x-api-key"), header("authorization"))?;
+        principal.require(role)?;
+        Ok(principal)
+    }
+
+    /// Binds `session_id` to `principal`, summarizing sessions left idle
+    /// too long along the way.
+    async fn bind_session(&self, session_id: &str, principal: &Principal) -> Result<(), AuthError> {
+        let mut sessions = self.sessions.lock().await;
+        let now = chrono::Utc::now();
+        let expired = sessions.expire(now);
+        sessions.bind(session_id, principal, now)?;
+        drop(sessions);
+
         let mut runtime = self.runtime.lock().await;
+        for session in expired {
+            runtime.end_conversation(&session.id);
+        }
+        Ok(())
+    }
 
-        runtime.execute_program(&req.message).unwrap_or(());
+    /// Opens a session for the caller under a fresh id.
+    pub async fn start_session_handler(&self, http: HttpRequest) -> impl Responder {
+        let principal = match self.authenticate(&http, Role::User) {
+            Ok(principal) => principal,
+            Err(e) => return auth_failure(e),
+        };
+        let session_id = self.sessions.lock().await.open(principal.clone(), chrono::Utc::now()).id.clone();
+        let primer = self.runtime.lock().await.begin_conversation(&session_id, Some(principal.user_id.clone()));
+        HttpResponse::Ok().json(SessionResponse { session_id, user_id: principal.user_id, role: principal.role, primer })
+    }
 
-        for _ in 0..5 {
-            runtime.tick();
+    /// Handles chat message POST requests asynchronously.
+    pub async fn chat_handler(&self, http: HttpRequest, req: web::Json<ChatRequest>) -> impl Responder {
+        let principal = match self.authenticate(&http, Role::User) {
+            Ok(principal) => principal,
+            Err(e) => return auth_failure(e),
+        };
+        match self.reply_to(&principal, &req).await {
+            Ok(response) => HttpResponse::Ok().json(response),
+            Err(ReplyError::Auth(e)) => auth_failure(e),
+            Err(ReplyError::Runtime(e @ RuntimeError::Vetoed(_))) => {
+                HttpResponse::UnprocessableEntity().body(e.to_string())
+            }
+            Err(ReplyError::Runtime(e)) => HttpResponse::InternalServerError().body(e.to_string()),
         }
+    }
 
-        let personality = runtime.personality.clone();
-        let reply = personality.respond_to_input(&req.message);
+    /// Streams chat replies over a WebSocket. Each text frame from the client
+    /// is a `ChatRequest` as JSON; the reply comes back as `StreamEvent`
+    /// frames and then the `ChatResponse` itself, whose `reply` is the text
+    /// actually delivered. Sending "cancel" while a reply streams cuts it
+    /// short; other messages sent meanwhile are ignored. A reply cut short,
+    /// or left unfinished by a client that disconnects, is remembered as
+    /// only the text that was sent.
+    pub async fn chat_stream_handler(&self, req: HttpRequest, body: web::Payload) -> Result<HttpResponse, actix_web::Error> {
+        let principal = match self.authenticate(&req, Role::User) {
+            Ok(principal) => principal,
+            Err(e) => return Ok(auth_failure(e)),
+        };
+        let (response, mut session, mut messages) = actix_ws::handle(&req, body)?;
+        let api = self.clone();
+
+        actix_web::rt::spawn(async move {
+            while let Some(Ok(message)) = messages.recv().await {
+                let text = match message {
+                    Message::Text(text) => text,
+                    Message::Ping(bytes) => {
+                        if session.pong(&bytes).await.is_err() {
+                            return;
+                        }
+                        continue;
+                    }
+                    Message::Close(_) => break,
+                    _ => continue,
+                };
+                let chat: ChatRequest = match serde_json::from_str(&text) {
+                    Ok(chat) => chat,
+                    Err(e) => {
+                        let error = serde_json::json!({ "type": "error", "message": e.to_string() });
+                        if session.text(error.to_string()).await.is_err() {
+                            return;
+                        }
+                        continue;
+                    }
+                };
+
+                let mut response = match api.reply_to(&principal, &chat).await {
+                    Ok(response) => response,
+                    Err(e) => {
+                        let error = serde_json::json!({ "type": "error", "message": e.to_string() });
+                        if session.text(error.to_string()).await.is_err() {
+                            return;
+                        }
+                        continue;
+                    }
+                };
+                let mut stream = ResponseStream::start(TemplateSource::new(response.reply.clone()), DEFAULT_CAPACITY);
+                let mut left = false;
+                loop {
+                    tokio::select! {
+                        event = stream.next() => {
+                            let Some(event) = event else { break };
+                            let frame = serde_json::to_string(&event).unwrap_or_default();
+                            if session.text(frame).await.is_err() {
+                                left = true;
+                                break;
+                            }
+                            if event.is_final() {
+                                break;
+                            }
+                        }
+                        incoming = messages.recv() => match incoming {
+                            Some(Ok(Message::Text(text))) if text.trim() == "cancel" => stream.cancel(),
+                            Some(Ok(Message::Text(_))) | Some(Ok(Message::Ping(_))) | Some(Ok(Message::Pong(_))) => {}
+                            // The client left; dropping the stream stops generation
+                            _ => {
+                                left = true;
+                                break;
+                            }
+                        }
+                    }
+                }
+
+                // Only what reached the client was said
+                let delivered = stream.text().to_string();
+                drop(stream);
+                if delivered != response.reply {
+                    let mut runtime = api.runtime.lock().await;
+                    runtime.cut_reply_short(&principal.user_id, chat.session_id.as_deref(), &response.reply, &delivered);
+                }
+                if left {
+                    return;
+                }
+                response.reply = delivered;
+                if session.text(serde_json::to_string(&response).unwrap_or_default()).await.is_err() {
+                    return;
+                }
+            }
+            let _ = session.close(None).await;
+        });
+
+        Ok(response)
+    }
+
+    /// Runs a chat message from `principal` through the runtime and builds
+    /// the reply. Only the user's own memories are drawn on or reported
+    /// back; events that belong to no user stay private to Astra. A reply
+    /// the runtime's hooks veto is withheld and not recorded as said.
+    async fn reply_to(&self, principal: &Principal, req: &ChatRequest) -> Result<ChatResponse, ReplyError> {
+        if let Some(session_id) = &req.session_id {
+            self.bind_session(session_id, principal).await?;
+        }
+        let user_id = principal.user_id.clone();
+        let snapshot = self.knowledge.as_ref().map(SharedKnowledge::snapshot);
+        let mut runtime = self.runtime.lock().await;
+        let reply =
+            runtime.reply_to_user(&user_id, req.session_id.as_deref(), &req.message, snapshot.as_deref().map(|s| &**s))?;
 
         let recent_events: Vec<String> = runtime
             .narrative_memory
             .recent_events(10)
-            .iter()
+            .into_iter()
+            .filter(|e| e.user_id().is_some_and(|owner| owner == user_id))
             .map(|e| format!("[{}] {}: {}", e.timestamp, e.event_type, e.description))
             .collect();
 
-        let response = ChatResponse {
-            reply,
+        Ok(ChatResponse {
+            response_id: reply.response_id,
+            reply: reply.text,
             emotion_state: format!("{:?}", runtime.emotion_state),
-            personality_traits: format!("{:?}", personality.traits),
+            personality_traits: format!("{:?}", runtime.personality.traits),
             recent_events,
+            primer: reply.primer,
+            associations: reply.associations,
+        })
+    }
+
+    /// Ends a conversation session, storing and returning its summary. Only
+    /// its owner or an admin may end it.
+    pub async fn end_session_handler(&self, http: HttpRequest, req: web::Json<EndSessionRequest>) -> impl Responder {
+        let principal = match self.authenticate(&http, Role::User) {
+            Ok(principal) => principal,
+            Err(e) => return auth_failure(e),
+        };
+        if let Err(e) = self.sessions.lock().await.close(&req.session_id, &principal) {
+            return auth_failure(e);
+        }
+        let mut runtime = self.runtime.lock().await;
+        match runtime.end_conversation(&req.session_id) {
+            Some(summary) => HttpResponse::Ok().json(summary),
+            None => HttpResponse::NotFound().body(format!("No open session '{}'", req.session_id)),
+        }
+    }
+
+    /// Handles structured feedback (thumbs up/down, corrections, preferences)
+    /// about an earlier chat response.
+    pub async fn feedback_handler(&self, http: HttpRequest, req: web::Json<FeedbackRequest>) -> impl Responder {
+        let principal = match self.authenticate(&http, Role::User) {
+            Ok(principal) => principal,
+            Err(e) => return auth_failure(e),
+        };
+        let mut runtime = self.runtime.lock().await;
+        let req = req.into_inner();
+
+        let event = FeedbackEvent {
+            response_id: req.response_id,
+            kind: req.kind,
+            user_id: Some(principal.user_id),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        };
+
+        match runtime.ingest_feedback(event) {
+            Ok(assignment) => HttpResponse::Ok().json(FeedbackResponse {
+                reward: assignment.reward,
+                credited_components: assignment.credits.iter().map(|(c, _)| format!("{:?}", c)).collect(),
+            }),
+            Err(e) => HttpResponse::NotFound().body(e.to_string()),
+        }
+    }
+
+    /// Reports how far the plan for an intent has come and whether it is
+    /// still advancing.
+    pub async fn intent_progress_handler(&self, http: HttpRequest, intent_id: web::Path<IntentId>) -> impl Responder {
+        if let Err(e) = self.authenticate(&http, Role::User) {
+            return auth_failure(e);
+        }
+        let runtime = self.runtime.lock().await;
+        let intent_id = intent_id.into_inner();
+        let Some(intent) = runtime.intent_manager.get_intent(intent_id) else {
+            return HttpResponse::NotFound().body(format!("No intent {}", intent_id));
+        };
+        let Some(progress) = runtime.plan_runner.progress_of(intent_id) else {
+            return HttpResponse::NotFound().body(format!("No plan submitted for intent {}", intent_id));
+        };
+
+        HttpResponse::Ok().json(IntentProgressResponse {
+            intent_id,
+            description: intent.description.clone(),
+            fraction: progress.fraction(),
+            completed_actions: progress.completed_actions,
+            total_actions: progress.total_actions,
+            milestones_reached: progress.milestones_reached.clone(),
+            milestones_total: progress.milestones_total,
+            estimated_remaining_secs: progress.estimated_remaining.map(|d| d.as_secs()),
+            phase: progress.phase.as_str().to_string(),
+            blocked_on: progress.blocked_on.clone(),
+        })
+    }
+
+    /// Returns Astra's structured self-report. Admins only.
+    pub async fn introspect_handler(&self, http: HttpRequest) -> impl Responder {
+        if let Err(e) = self.authenticate(&http, Role::Admin) {
+            return auth_failure(e);
+        }
+        let runtime = self.runtime.lock().await;
+        HttpResponse::Ok().json(runtime.introspect())
+    }
+
+    /// Runtime metrics in the Prometheus text format. Scrapers authenticate
+    /// like any other caller, e.g. with an API key as bearer token.
+    pub async fn metrics_handler(&self, http: HttpRequest) -> impl Responder {
+        if let Err(e) = self.authenticate(&http, Role::User) {
+            return auth_failure(e);
+        }
+        let runtime = self.runtime.lock().await;
+        HttpResponse::Ok()
+            .content_type("text/plain; version=0.0.4")
+            .body(runtime.render_metrics())
+    }
+
+    /// Reports what Astra did autonomously, drew on, and was held to in a
+    /// window, with the commitments and self-modifications it made. Admins
+    /// only.
+    pub async fn transparency_handler(&self, http: HttpRequest, query: web::Query<TransparencyQuery>) -> impl Responder {
+        if let Err(e) = self.authenticate(&http, Role::Admin) {
+            return auth_failure(e);
+        }
+        let runtime = self.runtime.lock().await;
+        let report = runtime.transparency_report(query.from, query.to);
+        match query.format.as_deref() {
+            Some("text") => HttpResponse::Ok().content_type("text/plain; charset=utf-8").body(report.render()),
+            _ => HttpResponse::Ok().json(report),
+        }
+    }
+
+    /// Runs an operator command: pause or resume, change the autonomy
+    /// policy, force maintenance, or dump diagnostics. Admins only.
+    pub async fn admin_handler(&self, http: HttpRequest, command: web::Json<AdminCommand>) -> impl Responder {
+        let operator = match self.authenticate(&http, Role::Admin) {
+            Ok(principal) => principal.user_id,
+            Err(e) => return auth_failure(e),
         };
+        let mut runtime = self.runtime.lock().await;
+        HttpResponse::Ok().json(runtime.admin(command.into_inner(), &operator))
+    }
 
-        HttpResponse::Ok().json(response)
+    /// Runs an Astra program with the runtime's program capabilities and
+    /// the shared knowledge base. Admins only: chat messages are never run
+    /// as programs. Programs that do not parse or are refused answer 422.
+    pub async fn run_program_handler(&self, http: HttpRequest, req: web::Json<RunProgramRequest>) -> impl Responder {
+        if let Err(e) = self.authenticate(&http, Role::Admin) {
+            return auth_failure(e);
+        }
+        let snapshot = self.knowledge.as_ref().map(SharedKnowledge::snapshot);
+        let mut runtime = self.runtime.lock().await;
+        match runtime.run_program(&req.source, snapshot.as_deref().map(|s| &**s)) {
+            Ok(ProgramStatus::Finished(value)) => {
+                HttpResponse::Ok().json(RunProgramResponse { status: "finished".to_string(), detail: value.to_string() })
+            }
+            Ok(ProgramStatus::Suspended { waiting_for, .. }) => HttpResponse::Ok()
+                .json(RunProgramResponse { status: "suspended".to_string(), detail: waiting_for.to_string() }),
+            Err(e) => HttpResponse::UnprocessableEntity().body(e.to_string()),
+        }
+    }
+
+    /// Lists self-modification proposals waiting for sign-off, with the
+    /// audit trail. Admins only.
+    pub async fn proposals_handler(&self, http: HttpRequest) -> impl Responder {
+        if let Err(e) = self.authenticate(&http, Role::Admin) {
+            return auth_failure(e);
+        }
+        let runtime = self.runtime.lock().await;
+        HttpResponse::Ok().json(serde_json::json!({
+            "pending": runtime.self_modifications.pending(),
+            "audit": runtime.self_modifications.audit_trail(),
+        }))
+    }
+
+    /// Approves or rejects a pending self-modification proposal, signed off
+    /// as the authenticated admin. Approved proposals are applied on the
+    /// next tick. Unknown proposals answer 404, ones already decided 409.
+    pub async fn proposal_decision_handler(
+        &self,
+        http: HttpRequest,
+        id: web::Path<ProposalId>,
+        req: web::Json<ProposalDecisionRequest>,
+    ) -> impl Responder {
+        let approver = match self.authenticate(&http, Role::Admin) {
+            Ok(principal) => principal.user_id,
+            Err(e) => return auth_failure(e),
+        };
+        let mut runtime = self.runtime.lock().await;
+        let id = id.into_inner();
+        let result = if req.approve {
+            runtime.self_modifications.approve(id, &approver)
+        } else {
+            let reason = req.reason.as_deref().unwrap_or("no reason given");
+            runtime.self_modifications.reject(id, &approver, reason)
+        };
+
+        match result {
+            Ok(()) => HttpResponse::Ok().json(runtime.self_modifications.get(id)),
+            Err(e @ CognitionError::ProposalNotFound(_)) => HttpResponse::NotFound().body(e.to_string()),
+        Err(e) => HttpResponse::Conflict().body(e.to_string()),
+        }
     }
 }
@@ -0,0 +1,298 @@
+// ============================================================================
+//                     ASTRA AGI • AUTHENTICATION & SESSIONS
+//              Who Is Calling the API, and What They May Do
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Gatekeeper of Astra’s Interfaces Layer. Every API call is tied to a
+//       principal—a user id and a role—established from an API key or an
+//       OAuth bearer token checked by a pluggable validator. Regular users
+//       may talk to Astra and rate replies; operations that change or expose
+//       Astra's internals, such as approving self-modifications, need the
+//       admin role. Sessions bind a conversation to the user who opened it,
+//       so each conversation reads and writes only that user's relationship
+//       model and memory partition, and no one can continue another user's
+//       session by guessing its id.
+//
+//   Core Functions:
+//       • Authenticate API keys and bearer tokens into principals
+//       • Authorize operations by role
+//       • Open, resume, and expire sessions owned by one user
+//
+//   File:        /src/interfaces/auth.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use chrono::{DateTime, Duration, Utc};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AuthError {
+    #[error("no API key or bearer token was presented")]
+    MissingCredentials,
+    #[error("API key not recognized")]
+    UnknownApiKey,
+    #[error("bearer token rejected: {0}")]
+    InvalidToken(String),
+    #[error("'{user_id}' needs the {required:?} role for this operation")]
+    Forbidden { user_id: String, required: Role },
+    #[error("session '{0}' belongs to another user")]
+    SessionOwnedElsewhere(String),
+}
+
+impl AuthError {
+    /// Whether the caller is known but not allowed (HTTP 403), rather than
+    /// not known at all (HTTP 401).
+    pub fn is_forbidden(&self) -> bool {
+        matches!(self, AuthError::Forbidden { .. } | AuthError::SessionOwnedElsewhere(_))
+    }
+}
+
+/// What a principal may do. Each role may do everything the ones before it may.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Chat, give feedback, manage their own sessions
+    User,
+    /// Also approve self-modifications and inspect runtime internals
+    Admin,
+}
+
+/// An authenticated caller.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Principal {
+    /// Keys the user's relationship model and memory partition
+    pub user_id: String,
+    pub role: Role,
+}
+
+impl Principal {
+    pub fn new(user_id: impl Into<String>, role: Role) -> Self {
+        Principal { user_id: user_id.into(), role }
+    }
+
+    pub fn require(&self, role: Role) -> Result<(), AuthError> {
+        if self.role >= role {
+            Ok(())
+        } else {
+            Err(AuthError::Forbidden { user_id: self.user_id.clone(), required: role })
+        }
+    }
+}
+
+/// Checks OAuth bearer tokens, e.g. by verifying a JWT's signature and
+/// claims or asking the issuer's introspection endpoint.
+pub trait TokenValidator: Send + Sync {
+    fn validate(&self, token: &str) -> Result<Principal, AuthError>;
+}
+
+/// Turns presented credentials into a principal.
+#[derive(Default)]
+pub struct Authenticator {
+    api_keys: Vec<(String, Principal)>,
+    bearer: Option<Box<dyn TokenValidator>>,
+}
+
+/// Compares without stopping at the first difference, so response timing
+/// does not reveal how much of a guessed key was right.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+impl Authenticator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also accepts bearer tokens that `validator` vouches for.
+    pub fn with_bearer_validator(mut self, validator: impl TokenValidator + 'static) -> Self {
+        self.bearer = Some(Box::new(validator));
+        self
+    }
+
+    /// Grants `principal` to whoever presents `key`, replacing any earlier grant.
+    pub fn add_api_key(&mut self, key: impl Into<String>, principal: Principal) {
+        let key = key.into();
+        self.api_keys.retain(|(existing, _)| *existing != key);
+        self.api_keys.push((key, principal));
+    }
+
+    pub fn revoke_api_key(&mut self, key: &str) -> bool {
+        let before = self.api_keys.len();
+        self.api_keys.retain(|(existing, _)| existing != key);
+        self.api_keys.len() < before
+    }
+
+    /// Authenticates from an `X-Api-Key` header value or an `Authorization`
+    /// header value. API keys are also accepted as bearer tokens; other
+    /// bearer tokens go to the validator, if one is configured.
+    pub fn authenticate(&self, api_key: Option<&str>, authorization: Option<&str>) -> Result<Principal, AuthError> {
+        if let Some(key) = api_key {
+            return self.principal_for_key(key).ok_or(AuthError::UnknownApiKey);
+        }
+        let token = authorization
+            .and_then(|value| value.strip_prefix("Bearer ").or_else(|| value.strip_prefix("bearer ")))
+            .map(str::trim)
+            .ok_or(AuthError::MissingCredentials)?;
+        if let Some(principal) = self.principal_for_key(token) {
+            return Ok(principal);
+        }
+        match &self.bearer {
+            Some(validator) => validator.validate(token),
+            None => Err(AuthError::InvalidToken("bearer tokens are not accepted".to_string())),
+        }
+    }
+
+    fn principal_for_key(&self, key: &str) -> Option<Principal> {
+        // Every key is compared, matching or not
+        let mut found = None;
+        for (candidate, principal) in &self.api_keys {
+            if constant_time_eq(candidate, key) {
+                found = Some(principal.clone());
+            }
+        }
+        found
+    }
+}
+
+/// A conversation bound to the user who opened it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub id: String,
+    pub principal: Principal,
+    pub opened_at: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// Open sessions, expiring after a period of inactivity.
+#[derive(Debug, Clone)]
+pub struct SessionStore {
+    sessions: HashMap<String, Session>,
+    pub idle_timeout: Duration,
+}
+
+impl Default for SessionStore {
+    fn default() -> Self {
+        SessionStore { sessions: HashMap::new(), idle_timeout: Duration::minutes(30) }
+    }
+}
+
+impl SessionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Opens a session for `principal` under a fresh, unguessable id.
+    pub fn open(&mut self, principal: Principal, now: DateTime<Utc>) -> &Session {
+        let id = format!("{:016x}{:016x}", OsRng.next_u64(), OsRng.next_u64());
+        self.sessions.entry(id.clone()).or_insert(Session { id, principal, opened_at: now, last_seen: now })
+    }
+
+    /// The session `id` for `principal`, opened under that id if it does
+    /// not exist yet. A session belonging to someone else is refused.
+    pub fn bind(&mut self, id: &str, principal: &Principal, now: DateTime<Utc>) -> Result<&Session, AuthError> {
+        let session = self.sessions.entry(id.to_string()).or_insert_with(|| Session {
+            id: id.to_string(),
+            principal: principal.clone(),
+            opened_at: now,
+            last_seen: now,
+        });
+        if session.principal.user_id != principal.user_id {
+            return Err(AuthError::SessionOwnedElsewhere(id.to_string()));
+        }
+        session.last_seen = now;
+        Ok(session)
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Session> {
+        self.sessions.get(id)
+    }
+
+    /// Closes a session `principal` owns. Admins may close anyone's.
+    pub fn close(&mut self, id: &str, principal: &Principal) -> Result<Option<Session>, AuthError> {
+        match self.sessions.get(id) {
+            Some(session) if session.principal.user_id != principal.user_id && principal.role < Role::Admin => {
+                Err(AuthError::SessionOwnedElsewhere(id.to_string()))
+            }
+            _ => Ok(self.sessions.remove(id)),
+        }
+    }
+
+    /// Removes and returns sessions idle for longer than `idle_timeout`.
+    pub fn expire(&mut self, now: DateTime<Utc>) -> Vec<Session> {
+        let expired: Vec<String> = self
+            .sessions
+            .values()
+            .filter(|s| now - s.last_seen > self.idle_timeout)
+            .map(|s| s.id.clone())
+            .collect();
+        expired.iter().filter_map(|id| self.sessions.remove(id)).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Issuer;
+
+    impl TokenValidator for Issuer {
+        fn validate(&self, token: &str) -> Result<Principal, AuthError> {
+            token
+                .strip_prefix("signed:")
+                .map(|user| Principal::new(user, Role::User))
+                .ok_or_else(|| AuthError::InvalidToken("bad signature".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_credentials_roles_and_session_ownership() {
+        let mut auth = Authenticator::new().with_bearer_validator(Issuer);
+        auth.add_api_key("k-admin", Principal::new("ops", Role::Admin));
+        auth.add_api_key("k-ana", Principal::new("ana", Role::User));
+
+        let ops = auth.authenticate(Some("k-admin"), None).unwrap();
+        let ana = auth.authenticate(None, Some("Bearer k-ana")).unwrap();
+        let ben = auth.authenticate(None, Some("Bearer signed:ben")).unwrap();
+        assert_eq!(ben, Principal::new("ben", Role::User));
+        assert_eq!(auth.authenticate(Some("k-nope"), None), Err(AuthError::UnknownApiKey));
+        assert!(matches!(auth.authenticate(None, Some("Bearer forged")), Err(AuthError::InvalidToken(_))));
+        assert_eq!(auth.authenticate(None, None), Err(AuthError::MissingCredentials));
+
+        assert!(ops.require(Role::Admin).is_ok());
+        assert!(ana.require(Role::User).is_ok());
+        let denied = ana.require(Role::Admin).unwrap_err();
+        assert!(denied.is_forbidden());
+
+        let now = Utc::now();
+        let mut sessions = SessionStore::new();
+        let opened = sessions.open(ana.clone(), now).id.clone();
+        assert_eq!(opened.len(), 32);
+        assert!(sessions.bind("chat-1", &ana, now).is_ok());
+        assert_eq!(sessions.bind("chat-1", &ben, now), Err(AuthError::SessionOwnedElsewhere("chat-1".into())));
+        assert!(sessions.close("chat-1", &ben).is_err());
+        assert!(sessions.close("chat-1", &ops).unwrap().is_some());
+
+        sessions.bind("chat-2", &ben, now + Duration::minutes(20)).unwrap();
+        let expired = sessions.expire(now + Duration::minutes(40));
+        assert_eq!(expired.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), vec![opened.as_str()]);
+        assert_eq!(sessions.len(), 1);
+    }
+}
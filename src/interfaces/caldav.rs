@@ -0,0 +1,173 @@
+// ============================================================================
+//                        ASTRA AGI • CALDAV STORE
+//        A CalDAV Collection as the Calendar Astra Syncs With
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Interfaces Layer that implements
+//       `CalendarStore` over CalDAV, so intent deadlines and external events
+//       live in the same calendar people already use (Nextcloud, Fastmail,
+//       Radicale, and the like). Events are fetched with a time-range
+//       calendar query and written as one iCalendar resource per uid.
+//
+//   Core Functions:
+//       • Query a collection for events in a time range
+//       • Create, replace, and delete event resources
+//
+//   File:        /src/interfaces/caldav.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use base64::Engine;
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::interfaces::calendar::{parse_ics, write_ics, CalendarError, CalendarEvent, CalendarStore};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalDavConfig {
+    /// URL of the calendar collection
+    pub url: String,
+    pub user: String,
+    pub password: String,
+}
+
+fn transport_error(error: impl std::error::Error + Send + Sync + 'static) -> CalendarError {
+    CalendarError::Transport(Box::new(error))
+}
+
+/// The contents of every `calendar-data` element in a multistatus
+/// response, whatever namespace prefix the server uses.
+fn calendar_data(xml: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    let mut rest = xml;
+    while let Some(open) = rest.find('<') {
+        rest = &rest[open + 1..];
+        let Some(close) = rest.find('>') else { break };
+        let tag = &rest[..close];
+        rest = &rest[close + 1..];
+        let name = tag.split_whitespace().next().unwrap_or_default();
+        if name.starts_with('/') || tag.ends_with('/') || name.rsplit(':').next() != Some("calendar-data") {
+            continue;
+        }
+        let end_tag = format!("</{}>", name);
+        let Some(end) = rest.find(&end_tag) else { break };
+        found.push(xml_text(&rest[..end]));
+        rest = &rest[end + end_tag.len()..];
+    }
+    found
+}
+
+fn xml_text(text: &str) -> String {
+    if let Some(data) = text.trim().strip_prefix("<![CDATA[").and_then(|t| t.strip_suffix("]]>")) {
+        return data.to_string();
+    }
+    text.replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"").replace("&apos;", "'").replace("&#13;", "\r").replace("&amp;", "&")
+}
+
+/// A calendar collection on a CalDAV server.
+pub struct CalDavStore {
+    agent: ureq::Agent,
+    url: String,
+    authorization: String,
+    /// Zone for event times given without one
+    pub timezone: FixedOffset,
+}
+
+impl CalDavStore {
+    pub fn new(config: &CalDavConfig) -> Self {
+        let credentials = format!("{}:{}", config.user, config.password);
+        Self {
+            agent: ureq::AgentBuilder::new().timeout(Duration::from_secs(30)).build(),
+            url: format!("{}/", config.url.trim_end_matches('/')),
+            authorization: format!("Basic {}", base64::engine::general_purpose::STANDARD.encode(credentials)),
+            timezone: FixedOffset::east_opt(0).unwrap(),
+        }
+    }
+
+    fn resource(&self, uid: &str) -> String {
+        let name: String = uid.chars().map(|c| if c.is_ascii_alphanumeric() || "-_.@".contains(c) { c } else { '_' }).collect();
+        format!("{}{}.ics", self.url, name)
+    }
+}
+
+impl CalendarStore for CalDavStore {
+    fn events(&mut self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<CalendarEvent>, CalendarError> {
+        let query = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<C:calendar-query xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+  <D:prop><C:calendar-data/></D:prop>
+  <C:filter><C:comp-filter name="VCALENDAR"><C:comp-filter name="VEVENT">
+    <C:time-range start="{}" end="{}"/>
+  </C:comp-filter></C:comp-filter></C:filter>
+</C:calendar-query>"#,
+            from.format("%Y%m%dT%H%M%SZ"),
+            to.format("%Y%m%dT%H%M%SZ")
+        );
+        let response = self
+            .agent
+            .request("REPORT", &self.url)
+            .set("Authorization", &self.authorization)
+            .set("Depth", "1")
+            .set("Content-Type", "application/xml; charset=utf-8")
+            .send_string(&query)
+            .map_err(transport_error)?
+            .into_string()
+            .map_err(transport_error)?;
+
+        let mut events = Vec::new();
+        for ics in calendar_data(&response) {
+            events.extend(parse_ics(&ics, self.timezone)?);
+        }
+        Ok(events)
+    }
+
+    fn put(&mut self, event: &CalendarEvent) -> Result<(), CalendarError> {
+        self.agent
+            .put(&self.resource(&event.uid))
+            .set("Authorization", &self.authorization)
+            .set("Content-Type", "text/calendar; charset=utf-8")
+            .send_string(&write_ics([event], Utc::now()))
+            .map_err(transport_error)?;
+        Ok(())
+    }
+
+    fn delete(&mut self, uid: &str) -> Result<(), CalendarError> {
+        match self.agent.delete(&self.resource(uid)).set("Authorization", &self.authorization).call() {
+            Ok(_) | Err(ureq::Error::Status(404, _)) => Ok(()),
+            Err(e) => Err(transport_error(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_are_read_from_multistatus_responses() {
+        let response = r#"<d:multistatus xmlns:d="DAV:" xmlns:cal="urn:ietf:params:xml:ns:caldav">
+            <d:response><d:href>/cal/standup.ics</d:href><d:propstat><d:prop>
+              <cal:calendar-data>BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:standup
+SUMMARY:Standup &amp; planning
+DTSTART:20261016T091000Z
+DTEND:20261016T093000Z
+END:VEVENT
+END:VCALENDAR
+</cal:calendar-data></d:prop></d:propstat></d:response>
+            <d:response><d:href>/cal/empty.ics</d:href><d:propstat><d:prop><cal:calendar-data/></d:prop></d:propstat></d:response>
+          </d:multistatus>"#;
+        let data = calendar_data(response);
+        assert_eq!(data.len(), 1);
+        let events = parse_ics(&data[0], FixedOffset::east_opt(0).unwrap()).unwrap();
+        assert_eq!(events[0].summary, "Standup & planning");
+    }
+}
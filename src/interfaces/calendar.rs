@@ -0,0 +1,441 @@
+// ============================================================================
+//                      ASTRA AGI • CALENDAR INTEGRATION
+//        Deadlines Out to a Calendar, Events Back In as Stimuli
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Interfaces Layer that keeps a calendar in step
+//       with Astra's intents. Every open intent with a deadline appears as
+//       an event ending at the deadline, and disappears once the intent is
+//       completed or cancelled. Events others put in the calendar come back
+//       as stimuli shortly before they start, and as busy periods that the
+//       planner works around when judging whether a deadline can be met.
+//       Calendars are reached through `CalendarStore`: a local iCalendar
+//       (.ics) file here, or a CalDAV collection behind the `caldav`
+//       feature. Recurring events count only with their first occurrence.
+//
+//   Core Functions:
+//       • Read and write iCalendar events
+//       • Sync intent deadlines to a calendar
+//       • Schedule stimuli for upcoming external events
+//       • Derive availability windows from busy events
+//
+//   File:        /src/interfaces/calendar.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use thiserror::Error;
+
+use crate::cognition::Stimulus;
+use crate::planning::availability::Availability;
+use crate::runtime::intent_manager::{IntentId, IntentManager, IntentState};
+
+/// Uid prefix of events Astra writes for its own intents.
+const INTENT_UID_PREFIX: &str = "astra-intent-";
+
+#[derive(Debug, Error)]
+pub enum CalendarError {
+    #[error("Invalid iCalendar data at line {line}: {message}")]
+    Malformed { line: usize, message: String },
+    #[error("Could not access calendar file {}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Calendar server request failed")]
+    Transport(#[source] Box<dyn std::error::Error + Send + Sync>),
+}
+
+/// One calendar event, with times in UTC.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub start: DateTime<Utc>,
+    /// Exclusive
+    pub end: DateTime<Utc>,
+    pub all_day: bool,
+}
+
+impl CalendarEvent {
+    /// The event as a `VEVENT` block.
+    pub fn to_ics(&self, stamp: DateTime<Utc>) -> String {
+        let mut lines = vec![
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}", escape(&self.uid)),
+            format!("DTSTAMP:{}", stamp.format("%Y%m%dT%H%M%SZ")),
+        ];
+        if self.all_day {
+            lines.push(format!("DTSTART;VALUE=DATE:{}", self.start.format("%Y%m%d")));
+            lines.push(format!("DTEND;VALUE=DATE:{}", self.end.format("%Y%m%d")));
+        } else {
+            lines.push(format!("DTSTART:{}", self.start.format("%Y%m%dT%H%M%SZ")));
+            lines.push(format!("DTEND:{}", self.end.format("%Y%m%dT%H%M%SZ")));
+        }
+        lines.push(format!("SUMMARY:{}", escape(&self.summary)));
+        if let Some(description) = &self.description {
+            lines.push(format!("DESCRIPTION:{}", escape(description)));
+        }
+        lines.push("END:VEVENT".to_string());
+        lines.iter().map(|line| fold(line)).collect()
+    }
+}
+
+/// A complete `VCALENDAR` holding `events`.
+pub fn write_ics<'a>(events: impl IntoIterator<Item = &'a CalendarEvent>, stamp: DateTime<Utc>) -> String {
+    let mut ics = String::from("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Astra AGI//Calendar//EN\r\n");
+    for event in events {
+        ics.push_str(&event.to_ics(stamp));
+    }
+    ics.push_str("END:VCALENDAR\r\n");
+    ics
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace(';', "\\;").replace(',', "\\,").replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+/// Splits a content line into lines of at most 75 bytes, each ending in CRLF.
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+/// Reads the events of an iCalendar document. Times without a zone, or
+/// with a `TZID` parameter, are taken to be in `timezone`.
+pub fn parse_ics(text: &str, timezone: FixedOffset) -> Result<Vec<CalendarEvent>, CalendarError> {
+    // Unfold continuation lines, remembering where each logical line began
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (index, raw) in text.lines().enumerate() {
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some((_, last))) => last.push_str(rest),
+            _ => lines.push((index + 1, raw.trim_end_matches('\r').to_string())),
+        }
+    }
+
+    let mut events = Vec::new();
+    let mut current: Option<HashMap<String, (usize, String)>> = None;
+    // Components nested in the event, such as alarms, have their own properties
+    let mut nested = 0;
+    for (line, content) in lines {
+        if content.is_empty() {
+            continue;
+        }
+        let (head, value) = content
+            .split_once(':')
+            .ok_or_else(|| CalendarError::Malformed { line, message: format!("expected NAME:VALUE, found '{}'", content) })?;
+        let name = head.split(';').next().unwrap_or_default().to_ascii_uppercase();
+
+        match (name.as_str(), value) {
+            ("BEGIN", "VEVENT") => current = Some(HashMap::new()),
+            ("END", "VEVENT") => {
+                let properties = current
+                    .take()
+                    .ok_or_else(|| CalendarError::Malformed { line, message: "END:VEVENT without BEGIN".into() })?;
+                events.push(event_from(&properties, line, timezone)?);
+            }
+            ("BEGIN", _) if current.is_some() => nested += 1,
+            ("END", _) if current.is_some() => nested -= 1,
+            _ => {
+                if let Some(properties) = current.as_mut().filter(|_| nested == 0) {
+                    properties.entry(name).or_insert((line, value.to_string()));
+                }
+            }
+        }
+    }
+    Ok(events)
+}
+
+fn event_from(
+    properties: &HashMap<String, (usize, String)>,
+    end_line: usize,
+    timezone: FixedOffset,
+) -> Result<CalendarEvent, CalendarError> {
+    let (line, value) = properties
+        .get("DTSTART")
+        .ok_or_else(|| CalendarError::Malformed { line: end_line, message: "event without DTSTART".into() })?;
+    // A DATE value is the eight digits of the day alone
+    let all_day = value.len() == 8;
+    let start = parse_time(value, *line, timezone)?;
+    let end = match properties.get("DTEND") {
+        Some((line, value)) => parse_time(value, *line, timezone)?,
+        None if all_day => start + Duration::days(1),
+        None => start,
+    };
+    let text = |name: &str| properties.get(name).map(|(_, value)| unescape(value));
+    Ok(CalendarEvent {
+        uid: text("UID").unwrap_or_else(|| format!("{}-{}", start.timestamp(), end_line)),
+        summary: text("SUMMARY").unwrap_or_default(),
+        description: text("DESCRIPTION"),
+        start,
+        end,
+        all_day,
+    })
+}
+
+fn parse_time(value: &str, line: usize, timezone: FixedOffset) -> Result<DateTime<Utc>, CalendarError> {
+    let malformed = || CalendarError::Malformed { line, message: format!("unreadable date or time '{}'", value) };
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").map_err(|_| malformed())?;
+        return Ok(Utc.from_utc_datetime(&naive));
+    }
+    let naive = match NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S") {
+        Ok(naive) => naive,
+        Err(_) => NaiveDate::parse_from_str(value, "%Y%m%d").map_err(|_| malformed())?.and_hms_opt(0, 0, 0).unwrap(),
+    };
+    let local = timezone.from_local_datetime(&naive).single().ok_or_else(malformed)?;
+    Ok(local.with_timezone(&Utc))
+}
+
+/// A calendar Astra can read and write.
+pub trait CalendarStore {
+    /// Events overlapping `from..to`.
+    fn events(&mut self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<CalendarEvent>, CalendarError>;
+    /// Adds an event, or replaces the one with the same uid.
+    fn put(&mut self, event: &CalendarEvent) -> Result<(), CalendarError>;
+    /// Removes an event; removing one that is not there is not an error.
+    fn delete(&mut self, uid: &str) -> Result<(), CalendarError>;
+}
+
+/// A calendar kept in a local .ics file, e.g. one a desktop calendar
+/// subscribes to.
+#[derive(Debug, Clone)]
+pub struct IcsFile {
+    pub path: PathBuf,
+    pub timezone: FixedOffset,
+}
+
+impl IcsFile {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), timezone: FixedOffset::east_opt(0).unwrap() }
+    }
+
+    fn read(&self) -> Result<Vec<CalendarEvent>, CalendarError> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(text) => parse_ics(&text, self.timezone),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(source) => Err(CalendarError::Io { path: self.path.clone(), source }),
+        }
+    }
+
+    fn write(&self, events: &[CalendarEvent]) -> Result<(), CalendarError> {
+        std::fs::write(&self.path, write_ics(events, Utc::now()))
+            .map_err(|source| CalendarError::Io { path: self.path.clone(), source })
+    }
+}
+
+impl CalendarStore for IcsFile {
+    fn events(&mut self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<CalendarEvent>, CalendarError> {
+        Ok(self.read()?.into_iter().filter(|e| e.start < to && e.end > from).collect())
+    }
+
+    fn put(&mut self, event: &CalendarEvent) -> Result<(), CalendarError> {
+        let mut events = self.read()?;
+        events.retain(|e| e.uid != event.uid);
+        events.push(event.clone());
+        self.write(&events)
+    }
+
+    fn delete(&mut self, uid: &str) -> Result<(), CalendarError> {
+        let mut events = self.read()?;
+        let before = events.len();
+        events.retain(|e| e.uid != uid);
+        if events.len() == before {
+            return Ok(());
+        }
+        self.write(&events)
+    }
+}
+
+/// Keeps a calendar and Astra's intents in step.
+pub struct CalendarSync<S: CalendarStore> {
+    store: S,
+    /// How long before an event starts its stimulus is raised
+    pub lead: Duration,
+    /// How far ahead external events are fetched
+    pub horizon: Duration,
+    /// Length of the block an intent without a duration estimate occupies
+    pub default_block: Duration,
+    synced: HashMap<IntentId, CalendarEvent>,
+    upcoming: Vec<CalendarEvent>,
+    announced: HashSet<(String, DateTime<Utc>)>,
+}
+
+impl<S: CalendarStore> CalendarSync<S> {
+    pub fn new(store: S) -> Self {
+        Self {
+            store,
+            lead: Duration::minutes(15),
+            horizon: Duration::days(7),
+            default_block: Duration::minutes(30),
+            synced: HashMap::new(),
+            upcoming: Vec::new(),
+            announced: HashSet::new(),
+        }
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Writes an event for each open intent with a deadline, updates those
+    /// whose deadline or description changed, and removes those of intents
+    /// that were completed, cancelled, or dropped. Returns the number of
+    /// calendar changes.
+    pub fn sync_intents(&mut self, intents: &IntentManager) -> Result<usize, CalendarError> {
+        let mut wanted = HashMap::new();
+        for intent in intents.all_intents() {
+            let open = matches!(intent.state, IntentState::Pending | IntentState::Active);
+            let Some(deadline) = intent.deadline.filter(|_| open).map(|d| d.to_datetime()) else {
+                continue;
+            };
+            let block = intent.duration.and_then(|d| Duration::from_std(d).ok()).unwrap_or(self.default_block);
+            wanted.insert(
+                intent.id,
+                CalendarEvent {
+                    uid: format!("{}{}", INTENT_UID_PREFIX, intent.id),
+                    summary: format!("Due: {}", intent.description),
+                    description: Some(format!("Deadline of Astra intent {} (priority {})", intent.id, intent.priority)),
+                    start: deadline - block,
+                    end: deadline,
+                    all_day: false,
+                },
+            );
+        }
+
+        let mut changes = 0;
+        let stale: Vec<IntentId> = self.synced.keys().filter(|id| !wanted.contains_key(id)).copied().collect();
+        for id in stale {
+            let event = self.synced.remove(&id).expect("listed above");
+            self.store.delete(&event.uid)?;
+            changes += 1;
+        }
+        for (id, event) in wanted {
+            if self.synced.get(&id) != Some(&event) {
+                self.store.put(&event)?;
+                self.synced.insert(id, event);
+                changes += 1;
+            }
+        }
+        Ok(changes)
+    }
+
+    /// Fetches external events from now to the horizon.
+    pub fn refresh(&mut self, now: DateTime<Utc>) -> Result<(), CalendarError> {
+        let mut events = self.store.events(now, now + self.horizon)?;
+        events.retain(|e| !e.uid.starts_with(INTENT_UID_PREFIX));
+        events.sort_by_key(|e| e.start);
+        self.upcoming = events;
+        Ok(())
+    }
+
+    /// External events fetched by the last refresh, soonest first.
+    pub fn upcoming(&self) -> &[CalendarEvent] {
+        &self.upcoming
+    }
+
+    /// A stimulus for each event starting within `lead` of `now`, raised
+    /// once per occurrence.
+    pub fn due_stimuli(&mut self, now: DateTime<Utc>) -> Vec<Stimulus> {
+        let mut stimuli = Vec::new();
+        for event in &self.upcoming {
+            if event.all_day || event.start - self.lead > now || event.end <= now {
+                continue;
+            }
+            if !self.announced.insert((event.uid.clone(), event.start)) {
+                continue;
+            }
+            let minutes = (event.start - now).num_minutes().max(0);
+            stimuli.push(Stimulus {
+                source: "calendar".into(),
+                content: format!("Calendar event \"{}\" starts in {} minute(s)", event.summary, minutes),
+                urgency: 0.6,
+            });
+        }
+        stimuli
+    }
+
+    /// Busy periods from the upcoming external events. All-day events,
+    /// usually reminders or holidays, do not count as busy.
+    pub fn availability(&self) -> Availability {
+        Availability::from_busy(self.upcoming.iter().filter(|e| !e.all_day).map(|e| (e.start, e.end)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadlines_sync_out_and_events_come_back() {
+        let path = std::env::temp_dir().join(format!("astra_calendar_{}.ics", std::process::id()));
+        let now = Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap();
+        std::fs::write(
+            &path,
+            "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:standup\r\nSUMMARY:Team stand\r\n up\\, daily\r\n\
+             DTSTART:20261016T091000Z\r\nDTEND:20261016T100000Z\r\nEND:VEVENT\r\n\
+             BEGIN:VEVENT\r\nUID:holiday\r\nDTSTART;VALUE=DATE:20261017\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+        )
+        .unwrap();
+        let mut calendar = CalendarSync::new(IcsFile::new(&path));
+
+        let mut intents = IntentManager::new();
+        let report = intents.create_intent_with_metadata("Send the report", 5, None);
+        intents.set_wall_clock_deadline(report, Some(Utc::now() + Duration::hours(3)), None, Utc::now()).unwrap();
+        assert_eq!(calendar.sync_intents(&intents).unwrap(), 1);
+        assert_eq!(calendar.sync_intents(&intents).unwrap(), 0, "unchanged intents are not rewritten");
+
+        calendar.refresh(now).unwrap();
+        let upcoming = calendar.upcoming();
+        assert_eq!(upcoming.len(), 2, "Astra's own events are not external");
+        assert_eq!(upcoming[0].summary, "Team standup, daily");
+        assert!(upcoming[1].all_day);
+
+        assert_eq!(calendar.due_stimuli(now).len(), 1);
+        assert!(calendar.due_stimuli(now).is_empty(), "each event is announced once");
+        assert_eq!(calendar.availability().busy().len(), 1);
+
+        intents.complete_intent(report).unwrap();
+        assert_eq!(calendar.sync_intents(&intents).unwrap(), 1);
+        let remaining = parse_ics(&std::fs::read_to_string(&path).unwrap(), FixedOffset::east_opt(0).unwrap()).unwrap();
+        assert!(remaining.iter().all(|e| !e.uid.starts_with(INTENT_UID_PREFIX)));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
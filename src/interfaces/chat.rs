@@ -0,0 +1,277 @@
+// ============================================================================
+//                       ASTRA AGI • CHAT CONNECTOR
+//        Rooms on Chat Networks as Conversations with Astra
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Interfaces Layer that lets Astra sit in chat
+//       rooms. A transport (Matrix, or any network implementing
+//       `ChatTransport`) delivers messages; each is interpreted through the
+//       NLP pipeline within a conversation session per room and sender,
+//       updates Astra's relationship with the sender, and is answered:
+//       questions the evidence can answer with a cited, grounded answer,
+//       anything else with a reply conditioned on personality, that
+//       relationship, and learned preferences. While plans started by the message run, the typing
+//       indicator is refreshed whenever they make progress.
+//
+//   Core Functions:
+//       • Define the transport interface chat networks implement
+//       • Route messages through interpretation and clarification
+//       • Answer questions from cited evidence in the shared knowledge base
+//       • Track per-user relationships and condition replies on them
+//       • Drive typing indicators from plan execution progress, clearing them
+//         even when no reply can be sent
+//
+//   File:        /src/interfaces/chat.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use thiserror::Error;
+
+use crate::interfaces::clarification::Interpretation;
+use crate::knowledge::snapshots::SharedKnowledge;
+use crate::learning::feedback::{ResponseId, ResponseTrace};
+use crate::learning::preferences::ResponseStyle;
+use crate::memory::conversation_summary::Speaker;
+use crate::runtime::error::RuntimeError;
+use crate::runtime::plan_runner::PlanProgress;
+use crate::runtime::Runtime;
+
+#[derive(Debug, Error)]
+pub enum ChatError {
+    #[error("{network} request failed")]
+    Transport {
+        network: &'static str,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error(transparent)]
+    Runtime(#[from] RuntimeError),
+}
+
+/// A message someone else posted in a room Astra is in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatMessage {
+    pub room: String,
+    /// Network-wide user id, e.g. `@ana:example.org`
+    pub sender: String,
+    pub sender_name: String,
+    pub text: String,
+}
+
+/// A chat network Astra can talk on.
+pub trait ChatTransport {
+    /// Short name of the network, used in session ids
+    fn network(&self) -> &'static str;
+    /// Messages from others that arrived since the last call.
+    fn receive(&mut self) -> Result<Vec<ChatMessage>, ChatError>;
+    fn send(&mut self, room: &str, text: &str) -> Result<(), ChatError>;
+    fn set_typing(&mut self, room: &str, typing: bool) -> Result<(), ChatError>;
+}
+
+/// A reply Astra sent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatReply {
+    pub room: String,
+    pub user_id: String,
+    pub text: String,
+    /// For attributing later feedback; none for clarifying questions
+    pub response_id: Option<ResponseId>,
+}
+
+/// Answers the messages a transport delivers.
+pub struct ChatConnector<T: ChatTransport> {
+    transport: T,
+    /// Ticks a reply may wait for plans that are still running
+    pub max_ticks: usize,
+}
+
+impl<T: ChatTransport> ChatConnector<T> {
+    pub fn new(transport: T) -> Self {
+        Self { transport, max_ticks: 20 }
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    pub fn transport_mut(&mut self) -> &mut T {
+        &mut self.transport
+    }
+
+    /// Receives waiting messages and answers each in turn.
+    pub fn poll(&mut self, runtime: &mut Runtime) -> Result<Vec<ChatReply>, ChatError> {
+        let messages = self.transport.receive()?;
+        messages.iter().map(|message| self.respond(runtime, message)).collect()
+    }
+
+    /// Interprets `message` and sends Astra's reply to its room: a
+    /// clarifying question if the message was unclear, a grounded answer
+    /// citing its sources if it was a question the evidence answers,
+    /// otherwise a reply shaped by personality, the relationship with the
+    /// sender, and preferences, sent once the plans it set off have
+    /// finished or `max_ticks` have passed. A reply the runtime's hooks veto
+    /// is not sent. The typing indicator is turned off again whether or not
+    /// a reply could be composed.
+    pub fn respond(&mut self, runtime: &mut Runtime, message: &ChatMessage) -> Result<ChatReply, ChatError> {
+        let session_id = format!("{}:{}:{}", self.transport.network(), message.room, message.sender);
+        if !runtime.conversations.contains_key(&session_id) {
+            runtime.begin_conversation(&session_id, Some(message.sender.clone()));
+        }
+        self.transport.set_typing(&message.room, true)?;
+
+        let composed = self.compose(runtime, message, &session_id);
+        // The first error is the one worth reporting
+        let cleared = self.transport.set_typing(&message.room, false);
+        let (text, response_id) = composed?;
+        cleared?;
+
+        self.transport.send(&message.room, &text)?;
+        Ok(ChatReply { room: message.room.clone(), user_id: message.sender.clone(), text, response_id })
+    }
+
+    /// The reply to `message` in session `session_id`, with the trace it was
+    /// registered under if it was more than a clarifying question.
+    fn compose(
+        &mut self,
+        runtime: &mut Runtime,
+        message: &ChatMessage,
+        session_id: &str,
+    ) -> Result<(String, Option<ResponseId>), ChatError> {
+        let sentiment = runtime.absorb_user_sentiment(&message.text);
+        let now = runtime.clock().now_utc();
+        runtime.relationships.observe(&message.sender, &message.sender_name, sentiment.valence, now);
+
+        match runtime.interpret_user_message(session_id, &message.text)? {
+            // The question is already part of the session
+            Interpretation::NeedsClarification(question) => Ok((question, None)),
+            Interpretation::Understood(understood) => {
+                self.await_plans(runtime, &message.room)?;
+                let knowledge = runtime.knowledge().map(SharedKnowledge::snapshot);
+                let snapshot = knowledge.as_deref().map(|snapshot| &**snapshot);
+                let reply = match runtime.answer_from_evidence(&message.sender, &message.text, &understood, snapshot)? {
+                    Some(answer) => answer.render(),
+                    None => {
+                        let relationship = runtime.relationships.get(&message.sender).cloned().expect("observed above");
+                        let register = runtime.preference_model.response_guidance().register;
+                        let body = runtime.personality.respond_to(&message.text, &relationship, register);
+                        runtime.preference_model.shape_response(&body)
+                    }
+                };
+                runtime.review_response(&reply)?;
+                let response_id = runtime.register_response_trace(ResponseTrace {
+                    style: Some(ResponseStyle::of_reply(&reply)),
+                    ..Default::default()
+                });
+                runtime.record_conversation_turn(session_id, Speaker::Astra, &reply);
+                runtime.record_commitments_from_reply(&reply, Some(message.sender.clone()));
+                Ok((reply, Some(response_id)))
+            }
+        }
+    }
+
+    /// Ticks the runtime until no plan is running, renewing the typing
+    /// indicator each time a plan completes another action.
+    fn await_plans(&mut self, runtime: &mut Runtime, room: &str) -> Result<(), ChatError> {
+        let completed = |progress: &[PlanProgress]| progress.iter().map(|p| p.completed_actions).sum::<usize>();
+        let mut last = completed(&runtime.plan_progress());
+        for _ in 0..self.max_ticks {
+            runtime.tick();
+            let progress = runtime.plan_progress();
+            if progress.iter().all(PlanProgress::is_finished) {
+                break;
+            }
+            if completed(&progress) != last {
+                self.transport.set_typing(room, true)?;
+                last = completed(&progress);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::hooks::{HookDecision, RuntimeHooks};
+
+    #[derive(Default)]
+    struct Recorder {
+        inbox: Vec<ChatMessage>,
+        sent: Vec<(String, String)>,
+        typing: Vec<bool>,
+    }
+
+    impl ChatTransport for Recorder {
+        fn network(&self) -> &'static str {
+            "test"
+        }
+        fn receive(&mut self) -> Result<Vec<ChatMessage>, ChatError> {
+            Ok(std::mem::take(&mut self.inbox))
+        }
+        fn send(&mut self, room: &str, text: &str) -> Result<(), ChatError> {
+            self.sent.push((room.to_string(), text.to_string()));
+            Ok(())
+        }
+        fn set_typing(&mut self, _: &str, typing: bool) -> Result<(), ChatError> {
+            self.typing.push(typing);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_messages_are_answered_in_their_room_with_the_relationship_in_mind() {
+        let mut runtime = Runtime::new();
+        let message = ChatMessage {
+            room: "!lobby:home".into(),
+            sender: "@ana:home".into(),
+            sender_name: "Ana".into(),
+            text: "hello".into(),
+        };
+        let mut connector = ChatConnector::new(Recorder { inbox: vec![message.clone()], ..Default::default() });
+
+        let replies = connector.poll(&mut runtime).unwrap();
+        assert_eq!(replies.len(), 1);
+        assert!(replies[0].text.starts_with("Nice to meet you, Ana."), "{}", replies[0].text);
+        assert!(replies[0].response_id.is_some());
+        assert_eq!(connector.transport().sent, vec![("!lobby:home".to_string(), replies[0].text.clone())]);
+        assert_eq!(connector.transport().typing.first(), Some(&true));
+        assert_eq!(connector.transport().typing.last(), Some(&false));
+
+        assert_eq!(runtime.relationships.get("@ana:home").unwrap().interactions, 1);
+        assert!(runtime.conversations.contains_key("test:!lobby:home:@ana:home"));
+
+        let again = connector.respond(&mut runtime, &message).unwrap();
+        assert!(!again.text.starts_with("Nice to meet you"));
+    }
+
+    struct Silent;
+
+    impl RuntimeHooks for Silent {
+        fn on_response_generated(&mut self, _reply: &str) -> HookDecision {
+            HookDecision::Veto("quiet hours".into())
+        }
+    }
+
+    #[test]
+    fn test_typing_stops_when_no_reply_is_sent() {
+        let mut runtime = Runtime::new();
+        runtime.add_hooks(Silent);
+        let message = ChatMessage {
+            room: "!lobby:home".into(),
+            sender: "@ana:home".into(),
+            sender_name: "Ana".into(),
+            text: "hello".into(),
+        };
+        let mut connector = ChatConnector::new(Recorder::default());
+
+        assert!(matches!(connector.respond(&mut runtime, &message), Err(ChatError::Runtime(RuntimeError::Vetoed(_)))));
+        assert!(connector.transport().sent.is_empty());
+        assert_eq!(connector.transport().typing, vec![true, false]);
+    }
+}
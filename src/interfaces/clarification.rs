@@ -0,0 +1,218 @@
+// ============================================================================
+//                     ASTRA AGI • CLARIFYING QUESTIONS
+//        Asking Instead of Guessing When Input Is Ambiguous
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Interfaces Layer that sits between NLP analysis
+//       and goal formation. When intent classification is unsure, or an
+//       intent lacks a slot it needs ("send it" — to whom?), Astra asks one
+//       targeted question rather than acting on a guess. The question is
+//       kept as pending dialogue state, and the user's answer is merged back
+//       into the intent it was asked about.
+//
+//   Core Functions:
+//       • Decide whether an NLP result is too uncertain or incomplete to act on
+//       • Phrase a question that targets the intent or the missing slot
+//       • Merge the user's answer into the original intent and slots
+//
+//   File:        /src/interfaces/clarification.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::interfaces::nlp::{extract_slots, NlpProcessor, NlpResult};
+
+/// Intent readings below this confidence are confirmed with the user.
+pub const CLARIFY_BELOW_CONFIDENCE: f32 = 0.6;
+
+/// Questions asked about one utterance before Astra goes with its best reading.
+pub const MAX_CLARIFICATION_ROUNDS: u32 = 2;
+
+/// What a clarifying question is meant to settle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ClarificationTarget {
+    /// Which of these intents the user meant; empty when none fit at all
+    Intent { candidates: Vec<String> },
+    /// The value of a required slot
+    Slot { name: String },
+}
+
+/// A question Astra has asked and is waiting to have answered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingClarification {
+    pub original_text: String,
+    /// What was understood so far
+    pub understood: NlpResult,
+    pub target: ClarificationTarget,
+    pub question: String,
+    /// Questions already asked about the same utterance
+    pub rounds: u32,
+    pub asked_at: u64,
+}
+
+/// How a user message was understood.
+#[derive(Debug, Clone)]
+pub enum Interpretation {
+    /// Clear enough to act on
+    Understood(NlpResult),
+    /// Astra asked this question and awaits the answer
+    NeedsClarification(String),
+}
+
+/// Returns the question to ask about `result`, or `None` if it can be acted
+/// on as it is. An unsure intent is settled before any missing slot.
+pub fn clarification_for(text: &str, result: &NlpResult) -> Option<PendingClarification> {
+    let (target, question) = if result.intent == "unknown" {
+        (
+            ClarificationTarget::Intent { candidates: Vec::new() },
+            "I'm not sure what you'd like me to do. Could you put it another way?".to_string(),
+        )
+    } else if result.confidence < CLARIFY_BELOW_CONFIDENCE && !result.alternatives.is_empty() {
+        let candidates: Vec<String> = std::iter::once(result.intent.clone())
+            .chain(result.alternatives.iter().cloned())
+            .take(3)
+            .collect();
+        let options: Vec<&str> = candidates.iter().map(|c| intent_phrase(c)).collect();
+        let question = format!("Just to check: do you want me to {}?", options.join(" or "));
+        (ClarificationTarget::Intent { candidates }, question)
+    } else {
+        let slot = result.missing_slots.first()?;
+        (ClarificationTarget::Slot { name: slot.clone() }, slot_question(slot))
+    };
+
+    Some(PendingClarification {
+        original_text: text.to_string(),
+        understood: result.clone(),
+        target,
+        question,
+        rounds: 0,
+        asked_at: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+    })
+}
+
+impl PendingClarification {
+    /// Merges the user's answer into the intent the question was about.
+    /// Any other slots the answer happens to fill are kept as well.
+    pub fn merge_answer(&self, answer: &str, nlp: &NlpProcessor) -> NlpResult {
+        let mut merged = self.understood.clone();
+        let answer_slots = extract_slots(answer);
+
+        match &self.target {
+            ClarificationTarget::Intent { candidates } if candidates.is_empty() => {
+                if let Ok(reread) = nlp.process_text(answer) {
+                    if reread.intent != "unknown" {
+                        merged.intent = reread.intent;
+                        merged.confidence = reread.confidence;
+                        merged.alternatives = reread.alternatives;
+                    }
+                }
+            }
+            ClarificationTarget::Intent { candidates } => {
+                if let Some(intent) = choose_candidate(answer, candidates, nlp) {
+                    merged.intent = intent;
+                    merged.confidence = 1.0;
+                    merged.alternatives.clear();
+                }
+            }
+            ClarificationTarget::Slot { name } => {
+                let value = answer_slots
+                    .get(name)
+                    .cloned()
+                    .unwrap_or_else(|| answer.trim().trim_end_matches(['.', '!', '?']).to_string());
+                if !value.is_empty() {
+                    merged.slots.insert(name.clone(), value);
+                }
+            }
+        }
+
+        for (slot, value) in answer_slots {
+            merged.slots.entry(slot).or_insert(value);
+        }
+        merged.missing_slots = nlp.missing_slots(&merged.intent, &merged.slots);
+        merged
+    }
+}
+
+/// Reads which candidate an answer picks: "yes" confirms the first, "the
+/// second one" picks by position, otherwise the answer's own keywords decide.
+fn choose_candidate(answer: &str, candidates: &[String], nlp: &NlpProcessor) -> Option<String> {
+    const ORDINALS: &[&str] = &["first", "second", "third"];
+
+    let lower = answer.to_lowercase();
+    let words: Vec<&str> = lower.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).collect();
+    if words.iter().any(|w| matches!(*w, "yes" | "yeah" | "yep" | "sure" | "right")) {
+        return candidates.first().cloned();
+    }
+    if let Some(pos) = ORDINALS.iter().position(|o| words.contains(o)) {
+        if let Some(candidate) = candidates.get(pos) {
+            return Some(candidate.clone());
+        }
+    }
+    nlp.rank_intents(answer)
+        .into_iter()
+        .map(|(schema, _)| &schema.intent)
+        .find(|intent| candidates.contains(intent))
+        .cloned()
+}
+
+fn intent_phrase(intent: &str) -> &str {
+    match intent {
+        "greeting" => "just say hello",
+        "set_reminder" => "set a reminder",
+        "send_message" => "send a message",
+        "search" => "look something up",
+        "question" => "answer a question",
+        other => other,
+    }
+}
+
+fn slot_question(slot: &str) -> String {
+    match slot {
+        "task" => "What should I remind you about?".to_string(),
+        "time" => "When should that be?".to_string(),
+        "recipient" => "Who should I send it to?".to_string(),
+        "content" => "What should the message say?".to_string(),
+        "topic" => "What should I look for?".to_string(),
+        other => format!("What is the {}?", other.replace('_', " ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_slots_are_asked_for_and_merged() {
+        let nlp = NlpProcessor::new();
+        let text = "send it";
+        let result = nlp.process_text(text).unwrap();
+
+        let pending = clarification_for(text, &result).expect("recipient is missing");
+        assert_eq!(pending.target, ClarificationTarget::Slot { name: "recipient".into() });
+
+        let merged = pending.merge_answer("Dana", &nlp);
+        assert_eq!(merged.intent, "send_message");
+        assert_eq!(merged.slots.get("recipient").map(String::as_str), Some("Dana"));
+        assert_eq!(merged.missing_slots, vec!["content".to_string()]);
+    }
+
+    #[test]
+    fn test_unsure_intent_is_confirmed() {
+        let nlp = NlpProcessor::new();
+        let result = nlp.process_text("find and send").unwrap();
+        let pending = clarification_for("find and send", &result).unwrap();
+        assert!(matches!(pending.target, ClarificationTarget::Intent { .. }));
+
+        let merged = pending.merge_answer("the second one", &nlp);
+        assert_eq!(merged.confidence, 1.0);
+        assert!(merged.intent == "search" || merged.intent == "send_message");
+    }
+}
@@ -0,0 +1,450 @@
+// ============================================================================
+//                       ASTRA AGI • EMAIL INTERFACE
+//        Incoming Mail as Stimuli, Replies as Plan Actions
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Interfaces Layer that gives Astra a mailbox.
+//       New messages from a `MailSource` (IMAP behind the `email` feature)
+//       are read for what they ask of Astra — requests, questions, and
+//       deadlines — and become stimuli whose urgency follows them. Replies
+//       are plan actions: `draft_reply(uid)` writes a draft, and
+//       `send_reply(uid)` hands it to a `MailSender` (SMTP behind the same
+//       feature). Drafting stays inside Astra, but sending is a message to
+//       the outside world, so under the default autonomy policy it waits
+//       for a person to confirm, who may edit the draft first. The inbox
+//       holds a bounded number of messages; past it, the oldest answered
+//       ones are dropped first, then the oldest of any.
+//
+//   Core Functions:
+//       • Extract requests, questions, and deadlines from messages
+//       • Turn new messages into stimuli and reply actions
+//       • Draft replies and send them once approved
+//
+//   File:        /src/interfaces/email.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{BTreeMap, HashMap};
+use thiserror::Error;
+
+use crate::cognition::Stimulus;
+use crate::interfaces::temporal::{TemporalParser, TemporalResolution};
+use crate::planning::autonomy::{ActionCategory, AutonomyPolicy};
+use crate::planning::executor::ActionExecutor;
+use crate::planning::planner::{Action, WorldState};
+
+const REQUEST_MARKERS: &[&str] = &[
+    "please", "could you", "can you", "would you", "kindly", "need you to", "let me know", "make sure",
+];
+const DEADLINE_MARKERS: &[&str] = &["by", "before", "due", "deadline", "until", "latest"];
+
+#[derive(Debug, Error)]
+pub enum EmailError {
+    #[error("{server} request failed")]
+    Transport {
+        server: &'static str,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("No email with uid {0}")]
+    UnknownMessage(u32),
+    #[error("No reply drafted to email {0}")]
+    NoDraft(u32),
+}
+
+/// A received message, already decoded to plain text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailMessage {
+    /// Mailbox-unique id, increasing with arrival
+    pub uid: u32,
+    /// `Message-ID` header, for threading replies
+    pub message_id: Option<String>,
+    pub from: String,
+    pub subject: String,
+    pub body: String,
+    pub received: DateTime<Utc>,
+}
+
+/// Something a message asks of Astra.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EmailIntent {
+    Request(String),
+    Question(String),
+    Deadline { text: String, due: DateTime<Utc> },
+}
+
+/// Reads the subject and body of `message` sentence by sentence. Quoted
+/// text and signatures are skipped, and a polite request phrased as a
+/// question counts as a request.
+pub fn extract_intents(message: &EmailMessage, parser: &TemporalParser, now: DateTime<Utc>) -> Vec<EmailIntent> {
+    let body = message
+        .body
+        .lines()
+        .take_while(|line| line.trim_end() != "--")
+        .filter(|line| !line.trim_start().starts_with('>'))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut intents = Vec::new();
+    let sentences = std::iter::once(message.subject.as_str()).chain(body.split_inclusive(['.', '?', '!', '\n']));
+    for sentence in sentences.map(str::trim).filter(|s| !s.is_empty()) {
+        let lower = sentence.to_lowercase();
+        let words: Vec<&str> = lower.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).collect();
+
+        if REQUEST_MARKERS.iter().any(|marker| contains_phrase(&words, marker)) {
+            intents.push(EmailIntent::Request(sentence.to_string()));
+        } else if sentence.ends_with('?') {
+            intents.push(EmailIntent::Question(sentence.to_string()));
+        }
+
+        if DEADLINE_MARKERS.iter().any(|marker| words.contains(marker)) {
+            let due = match parser.parse(sentence, now) {
+                TemporalResolution::Resolved(reading) => reading.deadline,
+                TemporalResolution::Ambiguous { candidates, .. } => candidates.first().and_then(|c| c.deadline),
+                TemporalResolution::NotFound => None,
+            };
+            if let Some(due) = due {
+                intents.push(EmailIntent::Deadline { text: sentence.to_string(), due });
+            }
+        }
+    }
+    intents
+}
+
+fn contains_phrase(words: &[&str], phrase: &str) -> bool {
+    let phrase: Vec<&str> = phrase.split(' ').collect();
+    words.windows(phrase.len()).any(|window| window == phrase.as_slice())
+}
+
+/// A message with what Astra made of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceivedEmail {
+    pub message: EmailMessage,
+    pub intents: Vec<EmailIntent>,
+    pub replied: bool,
+}
+
+impl ReceivedEmail {
+    /// Nearest deadline the message mentions.
+    pub fn due(&self) -> Option<DateTime<Utc>> {
+        self.intents
+            .iter()
+            .filter_map(|intent| match intent {
+                EmailIntent::Deadline { due, .. } => Some(*due),
+                _ => None,
+            })
+            .min()
+    }
+
+    /// Mail that asks nothing is low priority; requests and questions
+    /// matter more, and a near deadline most.
+    pub fn urgency(&self, now: DateTime<Utc>) -> f32 {
+        let asks = self.intents.iter().any(|i| matches!(i, EmailIntent::Request(_) | EmailIntent::Question(_)));
+        let base: f32 = if asks { 0.5 } else { 0.2 };
+        match self.due() {
+            Some(due) if due - now <= Duration::hours(24) => 0.9,
+            Some(due) if due - now <= Duration::days(3) => 0.7,
+            Some(_) => base.max(0.5),
+            None => base,
+        }
+    }
+
+    pub fn stimulus(&self, now: DateTime<Utc>) -> Stimulus {
+        let count = |f: fn(&EmailIntent) -> bool| self.intents.iter().filter(|i| f(i)).count();
+        let mut content = format!("Email {} from {} about \"{}\"", self.message.uid, self.message.from, self.message.subject);
+        content.push_str(&format!(
+            ": {} request(s), {} question(s)",
+            count(|i| matches!(i, EmailIntent::Request(_))),
+            count(|i| matches!(i, EmailIntent::Question(_)))
+        ));
+        if let Some(due) = self.due() {
+            content.push_str(&format!(", due {}", due.format("%Y-%m-%d %H:%M UTC")));
+        }
+        Stimulus { source: "email".into(), content, urgency: self.urgency(now) }
+    }
+}
+
+/// A reply waiting to be sent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailDraft {
+    pub to: String,
+    pub subject: String,
+    /// `Message-ID` of the message replied to
+    pub in_reply_to: Option<String>,
+    pub body: String,
+}
+
+/// Where new mail comes from.
+pub trait MailSource {
+    /// Messages that arrived since the last call, oldest first.
+    fn fetch_new(&mut self) -> Result<Vec<EmailMessage>, EmailError>;
+}
+
+/// How replies leave.
+pub trait MailSender {
+    fn send(&mut self, draft: &EmailDraft) -> Result<(), EmailError>;
+}
+
+type Drafter = Box<dyn FnMut(&ReceivedEmail) -> String + Send>;
+
+/// Messages kept in the inbox unless `with_capacity` says otherwise.
+pub const DEFAULT_INBOX_CAPACITY: usize = 1024;
+
+/// A mailbox Astra reads and answers.
+pub struct EmailInterface<S: MailSource, T: MailSender> {
+    source: S,
+    sender: T,
+    pub parser: TemporalParser,
+    inbox: BTreeMap<u32, ReceivedEmail>,
+    drafts: BTreeMap<u32, EmailDraft>,
+    drafter: Drafter,
+    capacity: usize,
+}
+
+impl<S: MailSource, T: MailSender> EmailInterface<S, T> {
+    pub fn new(source: S, sender: T) -> Self {
+        Self {
+            source,
+            sender,
+            parser: TemporalParser::utc(),
+            inbox: BTreeMap::new(),
+            drafts: BTreeMap::new(),
+            drafter: Box::new(default_draft),
+            capacity: DEFAULT_INBOX_CAPACITY,
+        }
+    }
+
+    /// Keeps at most `capacity` messages in the inbox.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Replaces how reply bodies are written, e.g. with one that consults
+    /// personality and preferences.
+    pub fn with_drafter(mut self, drafter: impl FnMut(&ReceivedEmail) -> String + Send + 'static) -> Self {
+        self.drafter = Box::new(drafter);
+        self
+    }
+
+    /// Lets drafting run on its own and makes sending a message send, so
+    /// each reply waits on whatever the policy requires for messages.
+    pub fn configure_policy(policy: &mut AutonomyPolicy) {
+        policy.action_categories.insert("draft_reply".into(), ActionCategory::Internal);
+        policy.action_categories.insert("send_reply".into(), ActionCategory::MessageSend);
+    }
+
+    /// Fetches new mail and returns a stimulus for each message.
+    pub fn poll(&mut self, now: DateTime<Utc>) -> Result<Vec<Stimulus>, EmailError> {
+        let mut stimuli = Vec::new();
+        for message in self.source.fetch_new()? {
+            let intents = extract_intents(&message, &self.parser, now);
+            let received = ReceivedEmail { message, intents, replied: false };
+            stimuli.push(received.stimulus(now));
+            self.inbox.insert(received.message.uid, received);
+        }
+        self.evict();
+        Ok(stimuli)
+    }
+
+    /// Drops messages past the capacity, answered ones first, oldest first,
+    /// along with their drafts.
+    fn evict(&mut self) {
+        while self.inbox.len() > self.capacity {
+            let answered = self.inbox.iter().find(|(_, received)| received.replied).map(|(uid, _)| *uid);
+            let Some(uid) = answered.or_else(|| self.inbox.keys().next().copied()) else {
+                break;
+            };
+            self.inbox.remove(&uid);
+            self.drafts.remove(&uid);
+        }
+    }
+
+    pub fn message(&self, uid: u32) -> Option<&ReceivedEmail> {
+        self.inbox.get(&uid)
+    }
+
+    /// Messages not yet replied to, oldest first.
+    pub fn unanswered(&self) -> impl Iterator<Item = &ReceivedEmail> {
+        self.inbox.values().filter(|r| !r.replied)
+    }
+
+    /// Drafting and sending a reply to `uid`, for the planner.
+    pub fn reply_actions(&self, uid: u32) -> Result<Vec<Action>, EmailError> {
+        let received = self.inbox.get(&uid).ok_or(EmailError::UnknownMessage(uid))?;
+        let drafted = format!("drafted({})", uid);
+        Ok(vec![
+            Action {
+                id: format!("draft_reply({})", uid),
+                description: format!("Draft an answer to \"{}\"", received.message.subject),
+                preconditions: WorldState::new(),
+                effects: HashMap::from([(drafted.clone(), true)]),
+                cost: 1.0,
+                risk: 0.0,
+            },
+            Action {
+                id: format!("send_reply({})", uid),
+                description: format!("Send the drafted reply to {}", received.message.from),
+                preconditions: HashMap::from([(drafted, true)]),
+                effects: HashMap::from([(format!("replied({})", uid), true)]),
+                cost: 1.0,
+                risk: 0.3,
+            },
+        ])
+    }
+
+    pub fn draft(&self, uid: u32) -> Option<&EmailDraft> {
+        self.drafts.get(&uid)
+    }
+
+    /// Rewrites a draft, e.g. while a person reviews it before confirming.
+    pub fn edit_draft(&mut self, uid: u32, body: impl Into<String>) -> Result<(), EmailError> {
+        let draft = self.drafts.get_mut(&uid).ok_or(EmailError::NoDraft(uid))?;
+        draft.body = body.into();
+        Ok(())
+    }
+
+    fn draft_reply(&mut self, uid: u32) -> Result<(), EmailError> {
+        let received = self.inbox.get(&uid).ok_or(EmailError::UnknownMessage(uid))?;
+        let subject = &received.message.subject;
+        let draft = EmailDraft {
+            to: received.message.from.clone(),
+            subject: if subject.to_lowercase().starts_with("re:") { subject.clone() } else { format!("Re: {}", subject) },
+            in_reply_to: received.message.message_id.clone(),
+            body: (self.drafter)(received),
+        };
+        self.drafts.insert(uid, draft);
+        Ok(())
+    }
+
+    fn send_reply(&mut self, uid: u32) -> Result<(), EmailError> {
+        let draft = self.drafts.get(&uid).ok_or(EmailError::NoDraft(uid))?;
+        self.sender.send(draft)?;
+        self.drafts.remove(&uid);
+        if let Some(received) = self.inbox.get_mut(&uid) {
+            received.replied = true;
+        }
+        Ok(())
+    }
+}
+
+/// A short acknowledgement naming what the message asked for.
+pub fn default_draft(received: &ReceivedEmail) -> String {
+    let name = received.message.from.split(['@', '<']).next().unwrap_or_default().trim();
+    let mut body = format!("Hi {},\n\nThanks for your message about \"{}\".", name, received.message.subject);
+    if received.intents.iter().any(|i| matches!(i, EmailIntent::Request(_))) {
+        body.push_str(" I'll take care of your request.");
+    }
+    if received.intents.iter().any(|i| matches!(i, EmailIntent::Question(_))) {
+        body.push_str(" I'll follow up with answers to your questions.");
+    }
+    if let Some(due) = received.due() {
+        body.push_str(&format!(" I've noted the deadline of {}.", due.format("%A %-d %B, %H:%M UTC")));
+    }
+    body.push_str("\n\nBest regards,\nAstra");
+    body
+}
+
+impl<S: MailSource, T: MailSender> ActionExecutor for EmailInterface<S, T> {
+    fn execute_action(&mut self, action: &Action) -> anyhow::Result<bool> {
+        let Some((name, uid)) = action.id.strip_suffix(')').and_then(|id| id.split_once('(')) else {
+            anyhow::bail!("Not an email action: '{}'", action.id);
+        };
+        let uid: u32 = uid.parse()?;
+        match name {
+            "draft_reply" => self.draft_reply(uid)?,
+            "send_reply" => self.send_reply(uid)?,
+            _ => anyhow::bail!("Not an email action: '{}'", action.id),
+        }
+        Ok(true)
+    }
+
+    fn observe_world(&self) -> Option<WorldState> {
+        let mut world = WorldState::new();
+        for (uid, received) in &self.inbox {
+            world.insert(format!("drafted({})", uid), self.drafts.contains_key(uid));
+            world.insert(format!("replied({})", uid), received.replied);
+        }
+        Some(world)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planning::autonomy::AutonomyLevel;
+    use chrono::TimeZone;
+
+    struct Inbox(Vec<EmailMessage>);
+
+    impl MailSource for Inbox {
+        fn fetch_new(&mut self) -> Result<Vec<EmailMessage>, EmailError> {
+            Ok(std::mem::take(&mut self.0))
+        }
+    }
+
+    #[derive(Default)]
+    struct Outbox(Vec<EmailDraft>);
+
+    impl MailSender for Outbox {
+        fn send(&mut self, draft: &EmailDraft) -> Result<(), EmailError> {
+            self.0.push(draft.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_mail_becomes_stimuli_and_replies_wait_for_confirmation() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 14, 18, 0, 0).unwrap();
+        let message = EmailMessage {
+            uid: 7,
+            message_id: Some("<slides@example.org>".into()),
+            from: "ana@example.org".into(),
+            subject: "Slides".into(),
+            body: "Hi Astra,\nCould you send me the slides by tomorrow at 5pm? Are the numbers final?\n\n> old quote?\n--\nAna"
+                .into(),
+            received: now,
+        };
+        let mut email = EmailInterface::new(Inbox(vec![message]), Outbox::default());
+
+        let stimuli = email.poll(now).unwrap();
+        assert_eq!(stimuli.len(), 1);
+        assert_eq!(stimuli[0].urgency, 0.9);
+        let intents = &email.message(7).unwrap().intents;
+        assert!(matches!(&intents[0], EmailIntent::Request(s) if s.starts_with("Could you send")));
+        assert!(matches!(&intents[1], EmailIntent::Deadline { due, .. } if *due == Utc.with_ymd_and_hms(2026, 10, 15, 17, 0, 0).unwrap()));
+        assert!(matches!(&intents[2], EmailIntent::Question(s) if s == "Are the numbers final?"));
+        assert_eq!(intents.len(), 3, "quoted text and signatures are ignored");
+
+        let mut policy = AutonomyPolicy::default();
+        EmailInterface::<Inbox, Outbox>::configure_policy(&mut policy);
+        let actions = email.reply_actions(7).unwrap();
+        assert_eq!(policy.decide(&actions[0]).1, AutonomyLevel::Auto);
+        assert_eq!(policy.decide(&actions[1]).1, AutonomyLevel::Confirm);
+
+        assert!(email.execute_action(&actions[1]).is_err(), "nothing drafted yet");
+        email.execute_action(&actions[0]).unwrap();
+        assert_eq!(email.draft(7).unwrap().subject, "Re: Slides");
+        email.edit_draft(7, "On their way.").unwrap();
+        email.execute_action(&actions[1]).unwrap();
+
+        assert_eq!(email.sender.0[0].body, "On their way.");
+        assert_eq!(email.sender.0[0].in_reply_to.as_deref(), Some("<slides@example.org>"));
+        assert!(email.observe_world().unwrap()["replied(7)"]);
+        assert_eq!(email.unanswered().count(), 0);
+
+        // Past capacity, the answered message goes before unanswered ones
+        let template = email.message(7).unwrap().message.clone();
+        let more = (8..=10).map(|uid| EmailMessage { uid, subject: format!("Note {}", uid), ..template.clone() }).collect();
+        let mut email = EmailInterface { source: Inbox(more), ..email }.with_capacity(2);
+        email.poll(now).unwrap();
+        assert_eq!(email.inbox.keys().copied().collect::<Vec<_>>(), vec![9, 10]);
+    }
+}
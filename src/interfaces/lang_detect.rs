@@ -0,0 +1,285 @@
+// ============================================================================
+//                     ASTRA AGI • LANGUAGE IDENTIFICATION
+//        Which Language a Message Is In, and Its Words Normalized
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Interfaces Layer that tells which language a
+//       user is writing in, so that templates, lexicons, and parsers for
+//       that language can be chosen downstream. The script is read first
+//       from the letters used; within a script, the language is the one
+//       whose most common function words and distinctive letters the text
+//       shares most. Tokenization lowercases, unifies apostrophes, and
+//       applies each language's own normalization, such as splitting French
+//       and Italian elisions and folding Russian ё into е.
+//
+//   Core Functions:
+//       • Identify the script (Latin or Cyrillic) of a text
+//       • Identify its language with a confidence
+//       • Tokenize and normalize text for a given language
+//
+//   File:        /src/interfaces/lang_detect.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Script {
+    Latin,
+    Cyrillic,
+    Other,
+}
+
+/// Languages Astra can tell apart. Serialized as ISO 639-1 codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "es")]
+    Spanish,
+    #[serde(rename = "fr")]
+    French,
+    #[serde(rename = "de")]
+    German,
+    #[serde(rename = "it")]
+    Italian,
+    #[serde(rename = "pt")]
+    Portuguese,
+    #[serde(rename = "nl")]
+    Dutch,
+    #[serde(rename = "ru")]
+    Russian,
+    #[serde(rename = "uk")]
+    Ukrainian,
+    #[serde(rename = "bg")]
+    Bulgarian,
+    #[default]
+    #[serde(rename = "und")]
+    Unknown,
+}
+
+/// Common words and letters that mark a language within its script.
+struct Profile {
+    language: Language,
+    script: Script,
+    words: &'static [&'static str],
+    letters: &'static str,
+}
+
+const PROFILES: &[Profile] = &[
+    Profile {
+        language: Language::English,
+        script: Script::Latin,
+        words: &["the", "and", "is", "are", "you", "that", "it", "of", "to", "what", "i", "my", "this", "with", "have", "please", "can", "me"],
+        letters: "",
+    },
+    Profile {
+        language: Language::Spanish,
+        script: Script::Latin,
+        words: &["el", "la", "los", "las", "que", "es", "y", "de", "en", "un", "una", "por", "para", "con", "no", "qué", "cómo", "está", "mi", "me"],
+        letters: "ñ¿¡",
+    },
+    Profile {
+        language: Language::French,
+        script: Script::Latin,
+        words: &["le", "la", "les", "et", "est", "de", "des", "un", "une", "je", "tu", "vous", "que", "pour", "pas", "ne", "avec", "c'est", "qu'est", "moi"],
+        letters: "çœèêàù",
+    },
+    Profile {
+        language: Language::German,
+        script: Script::Latin,
+        words: &["der", "die", "das", "und", "ist", "ich", "du", "nicht", "ein", "eine", "mit", "zu", "was", "wie", "sie", "es", "bitte", "mein", "mir"],
+        letters: "ßäöü",
+    },
+    Profile {
+        language: Language::Italian,
+        script: Script::Latin,
+        words: &["il", "lo", "la", "gli", "che", "è", "e", "di", "un", "una", "per", "non", "sono", "come", "mi", "con", "cosa", "ciao"],
+        letters: "ìò",
+    },
+    Profile {
+        language: Language::Portuguese,
+        script: Script::Latin,
+        words: &["o", "a", "os", "as", "que", "é", "e", "de", "um", "uma", "não", "para", "com", "eu", "você", "meu", "por", "como"],
+        letters: "ãõ",
+    },
+    Profile {
+        language: Language::Dutch,
+        script: Script::Latin,
+        words: &["de", "het", "een", "en", "is", "ik", "je", "niet", "van", "dat", "wat", "met", "zijn", "voor", "mijn", "hoe"],
+        letters: "ĳ",
+    },
+    Profile {
+        language: Language::Russian,
+        script: Script::Cyrillic,
+        words: &["и", "в", "не", "на", "я", "что", "это", "с", "как", "он", "мне", "ты", "вы", "пожалуйста", "меня", "мой"],
+        letters: "ыэё",
+    },
+    Profile {
+        language: Language::Ukrainian,
+        script: Script::Cyrillic,
+        words: &["і", "в", "не", "на", "я", "що", "це", "з", "як", "мені", "ти", "ви", "будь", "ласка", "мій", "та"],
+        letters: "іїєґ",
+    },
+    Profile {
+        language: Language::Bulgarian,
+        script: Script::Cyrillic,
+        words: &["и", "в", "не", "на", "аз", "какво", "това", "с", "как", "ми", "ти", "вие", "моля", "е", "са", "съм"],
+        letters: "",
+    },
+];
+
+/// A language guess and how sure it is.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Detection {
+    pub language: Language,
+    pub script: Script,
+    /// 0.0..=1.0
+    pub confidence: f32,
+}
+
+impl Language {
+    /// ISO 639-1 code, or "und" if undetermined.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::French => "fr",
+            Language::German => "de",
+            Language::Italian => "it",
+            Language::Portuguese => "pt",
+            Language::Dutch => "nl",
+            Language::Russian => "ru",
+            Language::Ukrainian => "uk",
+            Language::Bulgarian => "bg",
+            Language::Unknown => "und",
+        }
+    }
+}
+
+fn script_of(c: char) -> Option<Script> {
+    match c {
+        'a'..='z' | 'A'..='Z' | '\u{00C0}'..='\u{024F}' => Some(Script::Latin),
+        '\u{0400}'..='\u{04FF}' => Some(Script::Cyrillic),
+        c if c.is_alphabetic() => Some(Script::Other),
+        _ => None,
+    }
+}
+
+/// The script most of the letters in `text` are written in.
+pub fn detect_script(text: &str) -> Script {
+    let (mut latin, mut cyrillic, mut other) = (0, 0, 0);
+    for script in text.chars().filter_map(script_of) {
+        match script {
+            Script::Latin => latin += 1,
+            Script::Cyrillic => cyrillic += 1,
+            Script::Other => other += 1,
+        }
+    }
+    if latin == 0 && cyrillic == 0 && other == 0 || other > latin.max(cyrillic) {
+        Script::Other
+    } else if cyrillic > latin {
+        Script::Cyrillic
+    } else {
+        Script::Latin
+    }
+}
+
+/// Identifies the language of `text`. Very short or unfamiliar text comes
+/// back as `Unknown`, or with low confidence.
+pub fn detect_language(text: &str) -> Detection {
+    let script = detect_script(text);
+    let undetermined = Detection { language: Language::Unknown, script, confidence: 0.0 };
+    if script == Script::Other {
+        return undetermined;
+    }
+    let lower = text.to_lowercase();
+    let words = tokenize(&lower, Language::Unknown);
+    if words.is_empty() {
+        return undetermined;
+    }
+
+    let mut scores: Vec<(Language, f32)> = PROFILES
+        .iter()
+        .filter(|profile| profile.script == script)
+        .map(|profile| {
+            let word_hits = words.iter().filter(|w| profile.words.contains(&w.as_str())).count() as f32;
+            let letter_hits = lower.chars().filter(|c| profile.letters.contains(*c)).count() as f32;
+            (profile.language, word_hits + 1.5 * letter_hits)
+        })
+        .collect();
+    scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let (best, best_score) = scores[0];
+    if best_score == 0.0 {
+        return undetermined;
+    }
+    let runner_up = scores.get(1).map_or(0.0, |s| s.1);
+    // How far ahead the winner is, and how much of the text was evidence
+    let margin = (best_score - runner_up) / best_score;
+    let coverage = (best_score / words.len() as f32).min(1.0);
+    Detection { language: best, script, confidence: (0.5 * margin + 0.5 * coverage).clamp(0.0, 1.0) }
+}
+
+/// Lowercased words of `text`, normalized for `language`: curly
+/// apostrophes are straightened, French and Italian elisions ("l'", "dell'")
+/// are split from the word they attach to, and Russian ё is folded into е.
+pub fn tokenize(text: &str, language: Language) -> Vec<String> {
+    let text = text.to_lowercase().replace(['\u{2019}', '\u{02BC}', '`'], "'");
+    let mut tokens = Vec::new();
+    for raw in text.split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '-')) {
+        let word = raw.trim_matches(|c| c == '\'' || c == '-');
+        if word.is_empty() {
+            continue;
+        }
+        match language {
+            Language::French | Language::Italian => match word.split_once('\'') {
+                Some((elided, rest)) if !rest.is_empty() && elided.chars().count() <= 4 => {
+                    tokens.push(format!("{}'", elided));
+                    tokens.push(rest.to_string());
+                }
+                _ => tokens.push(word.to_string()),
+            },
+            Language::Russian => tokens.push(word.replace('ё', "е")),
+            _ => tokens.push(word.to_string()),
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_languages_are_told_apart() {
+        let cases = [
+            ("Can you please tell me what the weather is like?", Language::English),
+            ("¿Qué tiempo hace hoy en Madrid?", Language::Spanish),
+            ("Je ne sais pas si c'est une bonne idée", Language::French),
+            ("Ich weiß nicht, was das ist", Language::German),
+            ("Non so cosa sia, ciao", Language::Italian),
+            ("Eu não sei o que é isso", Language::Portuguese),
+            ("Ik weet niet wat het is", Language::Dutch),
+            ("Я не знаю, что это такое", Language::Russian),
+            ("Я не знаю, що це таке, будь ласка", Language::Ukrainian),
+        ];
+        for (text, language) in cases {
+            let detection = detect_language(text);
+            assert_eq!(detection.language, language, "{}", text);
+            assert!(detection.confidence > 0.3, "{}: {}", text, detection.confidence);
+        }
+        assert_eq!(detect_language("12345 !!").language, Language::Unknown);
+        assert_eq!(detect_language("東京に行きます").script, Script::Other);
+        assert_eq!(serde_json::to_string(&Language::Ukrainian).unwrap(), "\"uk\"");
+
+        assert_eq!(tokenize("L’homme dell'arte", Language::French), vec!["l'", "homme", "dell'", "arte"]);
+        assert_eq!(tokenize("Ещё ёлка", Language::Russian), vec!["еще", "елка"]);
+        assert_eq!(tokenize("Don't stop", Language::English), vec!["don't", "stop"]);
+    }
+}
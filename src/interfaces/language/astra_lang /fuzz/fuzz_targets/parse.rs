@@ -0,0 +1,31 @@
+// =============================================================================
+//  Astra Reference Compiler (ARC)
+//  File: fuzz/fuzz_targets/parse.rs
+//
+//  Description:
+//      Fuzz target for the parser and static analysis. Malformed programs
+//      must come back as diagnostics, never as a panic or a stack overflow,
+//      and a program that failed to parse must never analyze as clean.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+// =============================================================================
+
+#![no_main]
+
+use astra_lang::parser::parse_source;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(source) = std::str::from_utf8(data) else { return };
+    let (_, diagnostics) = parse_source(source);
+
+    for diagnostic in &diagnostics {
+        assert!(diagnostic.span.end <= source.len());
+        assert!(diagnostic.line() >= 1 && diagnostic.column() >= 1);
+    }
+    let analysis = astra_lang::analyze(source);
+    assert!(diagnostics.iter().all(|d| analysis.diagnostics.contains(d)));
+    assert!(diagnostics.is_empty() || analysis.has_errors());
+});
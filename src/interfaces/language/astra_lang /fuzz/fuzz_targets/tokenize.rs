@@ -0,0 +1,30 @@
+// =============================================================================
+//  Astra Reference Compiler (ARC)
+//  File: fuzz/fuzz_targets/tokenize.rs
+//
+//  Description:
+//      Fuzz target for the lexer. Any UTF-8 input must lex without panicking
+//      into a token stream ending in Eof, with every token and diagnostic
+//      positioned inside the source.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+// =============================================================================
+
+#![no_main]
+
+use astra_lang::lexer::tokenize;
+use astra_lang::tokens::TokenKind;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(source) = std::str::from_utf8(data) else { return };
+    let (tokens, diagnostics) = tokenize(source);
+
+    assert_eq!(tokens.last().map(|t| &t.kind), Some(&TokenKind::Eof));
+    assert_eq!(tokens.iter().filter(|t| t.kind == TokenKind::Eof).count(), 1);
+    for span in tokens.iter().map(|t| &t.span).chain(diagnostics.iter().map(|d| &d.span)) {
+        assert!(span.start <= span.end && span.end <= source.len());
+        assert!(span.line.is_some_and(|l| l >= 1) && span.column.is_some_and(|c| c >= 1));
+    }
+});
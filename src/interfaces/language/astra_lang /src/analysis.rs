@@ -0,0 +1,954 @@
+// =============================================================================
+// Astra Reference Compiler (ARC)
+// File: analysis.rs
+//
+// Description:
+//     Semantic analysis over parsed programs, run before execution:
+//     - Undefined variables and functions, and calls with the wrong arity
+//     - Unreachable statements after a `return`
+//     - Type inference for basic types (Unit, Bool, Int, Float, String):
+//       let bindings, call results, match arms, and undeclared return types,
+//       with mismatches against declared parameter and return types reported
+//     - Capability inference: which permissions the program needs, from
+//       declared effects, `Cap<...>` parameters, `modify(...)`, builtins
+//       such as `kb.query(...)`, and calls
+//     - Builtin calls are checked against the standard library's signatures,
+//       and those of foreign functions the host registered
+//     - Modules are analyzed against the declarations they import
+//     - Sum types: constructor arity and field types, patterns checked
+//       against the matched type, and matches that miss a case
+//     - Async functions: `await` and `sleep` only as statements directly in
+//       an async function's body, and async functions only called by `await`
+//
+// Intent:
+//     - Reject programs that cannot run before any of them runs.
+//     - Tell the host up front which capabilities to grant.
+//
+// Author: Alex Roussinov
+// Created: 2026-10-16
+// Updated: 2026-10-16
+// =============================================================================
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use crate::ast::*;
+use crate::builtins::{self, CallShape, Kind};
+use crate::capabilities::Capability;
+use crate::errors::{Diagnostic, Severity};
+use crate::ffi::ForeignFunctions;
+use crate::modules::{NoModules, UnitStore};
+use crate::prelude;
+use crate::tokens::Span;
+use crate::types::Type;
+
+/// What analysis found in a program.
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    /// Parse and analysis diagnostics, in source order
+    pub diagnostics: Vec<Diagnostic>,
+    /// Capabilities the program needs from its host
+    pub capabilities: BTreeSet<Capability>,
+    /// Return types inferred for functions and rules that do not declare one
+    pub inferred_returns: BTreeMap<String, Type>,
+}
+
+impl Diagnostics {
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter().filter(|d| d.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter().filter(|d| d.severity == Severity::Warning)
+    }
+
+    /// Whether the program must be rejected.
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+}
+
+/// Parses and analyzes `source`, returning the declarations that parsed
+/// along with every diagnostic from lexing, parsing, and analysis. The
+/// source cannot import modules; see [`crate::modules::analyze_program`].
+pub fn analyze_source(source: &str) -> (Vec<AstNode>, Diagnostics) {
+    crate::modules::analyze_program(source, &NoModules, &mut UnitStore::new(), &ForeignFunctions::new())
+}
+
+/// Analyzes already parsed declarations.
+pub fn analyze_items(items: &[AstNode]) -> Diagnostics {
+    analyze_with_imports(items, &[], &ForeignFunctions::new())
+}
+
+/// Analyzes `items` in the presence of declarations imported from other
+/// modules. Imported callables can be called and count toward the
+/// capabilities needed, but only `items` are checked and reported on.
+/// The prelude is always imported; `foreign` functions can be called too.
+pub fn analyze_with_imports(items: &[AstNode], imported: &[AstNode], foreign: &ForeignFunctions) -> Diagnostics {
+    let mut diagnostics = Vec::new();
+    let imported: Vec<&AstNode> = prelude::items().iter().chain(imported).collect();
+
+    // Functions and rules share one namespace of callables.
+    let mut own: HashSet<&str> = HashSet::new();
+    let mut signatures: HashMap<&str, Signature> = HashMap::new();
+    for (item, is_own) in imported.iter().map(|&i| (i, false)).chain(items.iter().map(|i| (i, true))) {
+        let (name, signature) = match item {
+            AstNode::Function(f) => {
+                let mut signature = Signature::new(&f.params, &f.ret_type, Some(&f.effects), &f.body, &f.span);
+                signature.is_async = f.is_async;
+                (f.name.as_str(), signature)
+            }
+            AstNode::Rule(r) => (r.name.as_str(), Signature::new(&r.params, &r.ret_type, None, &r.body, &r.span)),
+            AstNode::Intent(_) | AstNode::Import(_) | AstNode::Type(_) => continue,
+        };
+        if signatures.contains_key(name) {
+            if is_own {
+                diagnostics.push(Diagnostic::new(format!("'{}' is defined more than once", name), signature.span.clone()));
+            }
+        } else {
+            if is_own {
+                own.insert(name);
+            }
+            signatures.insert(name, signature);
+        }
+    }
+
+    // Constructors share the callables' namespace; type names have their own.
+    let mut sum_types = SumTypes::default();
+    for (item, is_own) in imported.iter().map(|&i| (i, false)).chain(items.iter().map(|i| (i, true))) {
+        let AstNode::Type(decl) = item else { continue };
+        if sum_types.types.insert(decl.name.as_str(), decl).is_some() && is_own {
+            diagnostics.push(Diagnostic::new(format!("type '{}' is defined more than once", decl.name), decl.span.clone()));
+        }
+        for variant in &decl.variants {
+            let name = variant.name.as_str();
+            if signatures.contains_key(name) || sum_types.constructors.contains_key(name) {
+                if is_own {
+                    diagnostics.push(Diagnostic::new(format!("'{}' is defined more than once", name), decl.span.clone()));
+                }
+            } else {
+                sum_types.constructors.insert(name, (decl, variant));
+            }
+        }
+    }
+
+    // Infer undeclared return types until they stop changing; each round
+    // can resolve one more level of calls.
+    let mut inferred: BTreeMap<String, Type> = BTreeMap::new();
+    for _ in 0..=signatures.len() {
+        let mut changed = false;
+        for (name, signature) in &signatures {
+            if signature.ret_type.is_some() || inferred.contains_key(*name) {
+                continue;
+            }
+            let facts = Checker::run(signature, &signatures, &sum_types, &inferred, foreign);
+            if let Some((ty, _)) = facts.returns.into_iter().find_map(|(ty, span)| ty.map(|ty| (ty, span))) {
+                inferred.insert(name.to_string(), ty);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    // Final pass, keeping diagnostics.
+    let mut facts_by_name = BTreeMap::new();
+    for (name, signature) in &signatures {
+        let mut facts = Checker::run(signature, &signatures, &sum_types, &inferred, foreign);
+        if !own.contains(name) {
+            facts_by_name.insert(*name, facts);
+            continue;
+        }
+        diagnostics.append(&mut facts.diagnostics);
+        let expected = signature.ret_type.clone().or_else(|| inferred.get(*name).cloned());
+        if let Some(expected) = expected {
+            for (found, span) in &facts.returns {
+                match found {
+                    Some(found) if !compatible(&expected, found) => diagnostics.push(Diagnostic::new(
+                        format!("'{}' returns {} but {} was expected", name, found, expected),
+                        span.clone(),
+                    )),
+                    _ => {}
+                }
+            }
+        }
+        facts_by_name.insert(*name, facts);
+    }
+
+    // A callable needs what it declares, what its body uses directly, and
+    // whatever the callables it calls need.
+    let mut required: BTreeMap<&str, BTreeSet<Capability>> =
+        facts_by_name.iter().map(|(name, facts)| (*name, facts.capabilities.clone())).collect();
+    loop {
+        let mut changed = false;
+        for (name, facts) in &facts_by_name {
+            let from_calls: BTreeSet<Capability> = facts
+                .calls
+                .iter()
+                .filter_map(|callee| required.get(callee.as_str()))
+                .flatten()
+                .cloned()
+                .collect();
+            let needed = required.get_mut(name).expect("every callable has an entry");
+            let before = needed.len();
+            needed.extend(from_calls);
+            changed |= needed.len() != before;
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut capabilities = BTreeSet::new();
+    for (name, needed) in &required {
+        let signature = &signatures[name];
+        if let Some(effects) = signature.effects {
+            if own.contains(name) {
+                check_declared_effects(name, effects, needed, signature.span, &mut diagnostics);
+            }
+            capabilities.extend(effects.iter().filter_map(|e| Capability::from_effect(e)));
+        }
+        capabilities.extend(needed.iter().cloned());
+    }
+
+    diagnostics.sort_by_key(|d| d.span.start);
+    Diagnostics { diagnostics, capabilities, inferred_returns: inferred }
+}
+
+/// Functions with an effect annotation must declare what they need; `Pure`
+/// ones must need nothing.
+fn check_declared_effects(
+    name: &str,
+    effects: &[String],
+    needed: &BTreeSet<Capability>,
+    span: &Span,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if effects.is_empty() || needed.is_empty() {
+        return;
+    }
+    if effects.iter().any(|e| e == "Pure") {
+        diagnostics.push(Diagnostic::new(
+            format!("pure function '{}' requires {}", name, listed(needed.iter())),
+            span.clone(),
+        ));
+        return;
+    }
+    let declared: BTreeSet<Capability> = effects.iter().filter_map(|e| Capability::from_effect(e)).collect();
+    let mut missing = needed.difference(&declared).peekable();
+    if missing.peek().is_some() {
+        diagnostics.push(Diagnostic::warning(
+            format!("'{}' requires {} but does not declare it", name, listed(missing)),
+            span.clone(),
+        ));
+    }
+}
+
+fn listed<'c>(capabilities: impl Iterator<Item = &'c Capability>) -> String {
+    capabilities.map(|c| c.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+/// A function or rule as seen by its callers.
+struct Signature<'a> {
+    params: &'a [Param],
+    ret_type: &'a Option<Type>,
+    /// Declared effects; `None` for rules, which cannot declare any
+    effects: Option<&'a [String]>,
+    body: &'a Block,
+    span: &'a Span,
+    /// Whether it is an `async fn`, called only with `await`
+    is_async: bool,
+}
+
+impl<'a> Signature<'a> {
+    fn new(
+        params: &'a [Param],
+        ret_type: &'a Option<Type>,
+        effects: Option<&'a Vec<String>>,
+        body: &'a Block,
+        span: &'a Span,
+    ) -> Self {
+        Signature { params, ret_type, effects: effects.map(|e| e.as_slice()), body, span, is_async: false }
+    }
+}
+
+/// What one walk over a body found.
+#[derive(Default)]
+struct BodyFacts {
+    diagnostics: Vec<Diagnostic>,
+    /// Type of every `return`, where known, with the statement's span
+    returns: Vec<(Option<Type>, Span)>,
+    /// Callables called by name
+    calls: BTreeSet<String>,
+    /// Capabilities the body needs itself
+    capabilities: BTreeSet<Capability>,
+}
+
+/// Declared sum types, by type name and by constructor.
+#[derive(Default)]
+struct SumTypes<'a> {
+    types: HashMap<&'a str, &'a TypeDecl>,
+    constructors: HashMap<&'a str, (&'a TypeDecl, &'a Variant)>,
+}
+
+/// Stands in for the fields of a constructor matched by a catch-all.
+static WILDCARD: Pattern = Pattern::Wildcard;
+
+impl SumTypes<'_> {
+    /// Whether `pattern` matches every value.
+    fn is_catch_all(&self, pattern: &Pattern) -> bool {
+        match pattern {
+            Pattern::Wildcard => true,
+            Pattern::Identifier(name) => !self.constructors.contains_key(name.as_str()),
+            _ => false,
+        }
+    }
+
+    /// The fields of `pattern` if it matches the constructor `name` with
+    /// `arity` fields; `true` and `false` are Bool's constructors.
+    fn fields_if<'p>(&self, pattern: &'p Pattern, name: &str, arity: usize) -> Option<Vec<&'p Pattern>> {
+        match pattern {
+            Pattern::Constructor { name: n, args } if n == name && args.len() == arity => Some(args.iter().collect()),
+            Pattern::Identifier(n) if n == name && arity == 0 && self.constructors.contains_key(n.as_str()) => {
+                Some(Vec::new())
+            }
+            Pattern::Literal(Expression::BoolLiteral(b)) if b.to_string() == name => Some(Vec::new()),
+            _ => None,
+        }
+    }
+
+    /// The constructors of a type, if it has finitely many.
+    fn constructors_of(&self, ty: &Type) -> Option<Vec<(String, Vec<Type>)>> {
+        match base(ty) {
+            Type::Bool => Some(vec![("true".into(), Vec::new()), ("false".into(), Vec::new())]),
+            Type::Simple(name) => self
+                .types
+                .get(name.as_str())
+                .map(|decl| decl.variants.iter().map(|v| (v.name.clone(), v.fields.clone())).collect()),
+            _ => None,
+        }
+    }
+
+    /// A value, one per column of `types`, that none of `rows` matches,
+    /// written as a pattern; `None` if the rows cover every value.
+    fn uncovered(&self, rows: &[Vec<&Pattern>], types: &[Type]) -> Option<Vec<String>> {
+        let Some((first, rest)) = types.split_first() else {
+            return rows.is_empty().then(Vec::new);
+        };
+        let constructors = self.constructors_of(first);
+
+        // When every constructor appears, look for a gap under each one.
+        if let Some(constructors) = &constructors
+            && constructors.iter().all(|(name, fields)| {
+                rows.iter().any(|row| self.fields_if(row[0], name, fields.len()).is_some())
+            })
+        {
+            for (name, fields) in constructors {
+                let specialized: Vec<Vec<&Pattern>> = rows
+                    .iter()
+                    .filter_map(|row| {
+                        let head = if self.is_catch_all(row[0]) {
+                            vec![&WILDCARD; fields.len()]
+                        } else {
+                            self.fields_if(row[0], name, fields.len())?
+                        };
+                        Some(head.into_iter().chain(row[1..].iter().copied()).collect())
+                    })
+                    .collect();
+                let column_types: Vec<Type> = fields.iter().chain(rest).cloned().collect();
+                if let Some(mut witness) = self.uncovered(&specialized, &column_types) {
+                    let rest_witness = witness.split_off(fields.len());
+                    let head = if fields.is_empty() { name.clone() } else { format!("{}({})", name, witness.join(", ")) };
+                    return Some(std::iter::once(head).chain(rest_witness).collect());
+                }
+            }
+            return None;
+        }
+
+        // Otherwise only catch-all rows can match what is missing.
+        let default: Vec<Vec<&Pattern>> =
+            rows.iter().filter(|row| self.is_catch_all(row[0])).map(|row| row[1..].to_vec()).collect();
+        let mut witness = self.uncovered(&default, rest)?;
+        let missing = constructors.and_then(|constructors| {
+            constructors.into_iter().find(|(name, fields)| {
+                !rows.iter().any(|row| self.fields_if(row[0], name, fields.len()).is_some())
+            })
+        });
+        let head = match missing {
+            Some((name, fields)) if fields.is_empty() => name,
+            Some((name, fields)) => format!("{}({})", name, vec!["_"; fields.len()].join(", ")),
+            None => "_".into(),
+        };
+        witness.insert(0, head);
+        Some(witness)
+    }
+}
+
+struct Checker<'s, 'a> {
+    signatures: &'s HashMap<&'a str, Signature<'a>>,
+    sum_types: &'s SumTypes<'a>,
+    inferred: &'s BTreeMap<String, Type>,
+    foreign: &'s ForeignFunctions,
+    /// Innermost scope last; a binding's type is `None` when unknown
+    scopes: Vec<HashMap<String, Option<Type>>>,
+    /// Span of the statement being checked
+    span: Span,
+    /// Whether the body may suspend
+    is_async: bool,
+    /// Set while checking the call an `await` is applied to
+    awaiting: bool,
+    facts: BodyFacts,
+}
+
+impl<'s, 'a> Checker<'s, 'a> {
+    fn run(
+        signature: &Signature<'a>,
+        signatures: &'s HashMap<&'a str, Signature<'a>>,
+        sum_types: &'s SumTypes<'a>,
+        inferred: &'s BTreeMap<String, Type>,
+        foreign: &'s ForeignFunctions,
+    ) -> BodyFacts {
+        let mut checker = Checker {
+            signatures,
+            sum_types,
+            inferred,
+            foreign,
+            scopes: vec![HashMap::new()],
+            span: signature.span.clone(),
+            is_async: signature.is_async,
+            awaiting: false,
+            facts: BodyFacts::default(),
+        };
+        for param in signature.params {
+            if let Some(Type::Cap(inner)) = &param.ty
+                && let Type::Simple(name) = inner.as_ref()
+            {
+                checker.facts.capabilities.extend(Capability::from_effect(name));
+            }
+            checker.bind(&param.name, param.ty.clone());
+        }
+        checker.block(signature.body);
+        checker.facts
+    }
+
+    fn error(&mut self, message: String) {
+        self.facts.diagnostics.push(Diagnostic::new(message, self.span.clone()));
+    }
+
+    fn bind(&mut self, name: &str, ty: Option<Type>) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), ty);
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Option<Type>> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    /// Checks a block in its own scope. Returns whether it always returns.
+    fn block(&mut self, block: &Block) -> bool {
+        self.scopes.push(HashMap::new());
+        let outer_span = self.span.clone();
+        let mut diverged = false;
+        let mut warned = false;
+        for statement in &block.statements {
+            self.span = statement.span.clone();
+            if diverged && !warned {
+                self.facts.diagnostics.push(Diagnostic::warning("unreachable statement", statement.span.clone()));
+                warned = true;
+            }
+            diverged |= self.statement(&statement.node);
+        }
+        self.span = outer_span;
+        self.scopes.pop();
+        diverged
+    }
+
+    /// Checks a statement. Returns whether control never continues past it.
+    fn statement(&mut self, statement: &Statement) -> bool {
+        match statement {
+            Statement::Expr(Expression::Block(block)) => self.block(block),
+            Statement::Expr(expr) => {
+                self.expression(expr);
+                false
+            }
+            Statement::LetBinding { name, expr } => {
+                let ty = expr.as_ref().and_then(|e| self.expression(e));
+                self.bind(name, ty);
+                false
+            }
+            Statement::Return(expr) => {
+                let ty = self.expression(expr);
+                self.facts.returns.push((ty, self.span.clone()));
+                true
+            }
+            // A failed branch backtracks, so a return inside may not be taken.
+            Statement::Backtrack(block) => {
+                self.block(block);
+                false
+            }
+            Statement::Await { binding, target } => {
+                self.check_suspension("await");
+                let ty = self.await_target(target);
+                if let Some(name) = binding {
+                    self.bind(name, ty);
+                }
+                false
+            }
+            Statement::Sleep(duration) => {
+                self.check_suspension("sleep");
+                self.expect_type("sleep", duration, &Type::Int(64));
+                false
+            }
+        }
+    }
+
+    /// Suspending is only possible between the top-level statements of an
+    /// async body: the parameter scope and the body's own.
+    fn check_suspension(&mut self, keyword: &str) {
+        if !self.is_async {
+            self.error(format!("'{}' can only be used in an async function", keyword));
+        } else if self.scopes.len() != 2 {
+            self.error(format!("'{}' can only be used directly in the body of an async function", keyword));
+        }
+    }
+
+    /// Checks what an `await` waits for and infers the value it yields.
+    fn await_target(&mut self, target: &Awaitable) -> Option<Type> {
+        match target {
+            Awaitable::Event { name, timeout: None } => {
+                self.expect_type("event", name, &Type::String);
+                Some(Type::String)
+            }
+            Awaitable::Event { name, timeout: Some(timeout) } => {
+                self.expect_type("event", name, &Type::String);
+                self.expect_type("timeout", timeout, &Type::Int(64));
+                Some(Type::Simple(prelude::WAIT.to_string()))
+            }
+            Awaitable::Call { callee, args } => {
+                let signature = self.signatures.get(callee.as_str());
+                let is_async = signature.is_some_and(|s| s.is_async);
+                let callable = signature.is_some()
+                    || self.sum_types.constructors.contains_key(callee.as_str())
+                    || builtins::lookup(callee).is_some()
+                    || self.foreign.get(callee).is_some();
+                if callable && !is_async && self.lookup(callee).is_none() {
+                    self.error(format!("'{}' is not an async function", callee));
+                }
+                self.awaiting = is_async;
+                self.call(&Expression::Identifier(callee.clone()), args)
+            }
+        }
+    }
+
+    fn expect_type(&mut self, what: &str, expr: &Expression, expected: &Type) {
+        if let Some(found) = self.expression(expr)
+            && !compatible(expected, &found)
+        {
+            self.error(format!("'{}' expects {} but got {}", what, expected, found));
+        }
+    }
+
+    /// Checks an expression and infers its type where it is a basic one.
+    fn expression(&mut self, expr: &Expression) -> Option<Type> {
+        match expr {
+            Expression::Identifier(name) => match self.lookup(name) {
+                Some(ty) => ty.clone(),
+                None if self.signatures.contains_key(name.as_str()) => None,
+                None => match self.sum_types.constructors.get(name.as_str()) {
+                    Some((decl, variant)) => {
+                        if !variant.fields.is_empty() {
+                            self.error(format!("'{}' takes {} field(s) but none were given", name, variant.fields.len()));
+                        }
+                        Some(Type::Simple(decl.name.clone()))
+                    }
+                    None => {
+                        self.error(format!("undefined variable '{}'", name));
+                        None
+                    }
+                },
+            },
+            Expression::IntLiteral(_) => Some(Type::Int(64)),
+            Expression::FloatLiteral(_) => Some(Type::Float(64)),
+            Expression::StringLiteral(_) => Some(Type::String),
+            Expression::BoolLiteral(_) => Some(Type::Bool),
+            Expression::FunctionCall { callee, args } => self.call(callee, args),
+            Expression::Block(block) => {
+                self.block(block);
+                None
+            }
+            Expression::Match { expr, arms } => self.match_expression(expr, arms),
+            Expression::Symbolic(name) => Some(Type::Symbolic(name.clone())),
+            Expression::SelfModify { patch, .. } => {
+                self.facts.capabilities.insert(Capability::SelfModify);
+                self.expression(patch);
+                Some(Type::Unit)
+            }
+        }
+    }
+
+    fn call(&mut self, callee: &Expression, args: &[Expression]) -> Option<Type> {
+        let awaited = std::mem::take(&mut self.awaiting);
+        let arg_types: Vec<Option<Type>> = args.iter().map(|arg| self.expression(arg)).collect();
+        let name = match callee {
+            Expression::Identifier(name) => name,
+            other => {
+                self.expression(other);
+                return None;
+            }
+        };
+        if self.lookup(name).is_some() {
+            self.error(format!("'{}' is a variable, not a function", name));
+            return None;
+        }
+        let signatures = self.signatures;
+        let Some(signature) = signatures.get(name.as_str()) else {
+            if let Some(&(decl, variant)) = self.sum_types.constructors.get(name.as_str()) {
+                return self.construct(decl, variant, &arg_types);
+            }
+            if let Some(builtin) = builtins::lookup(name) {
+                return self.builtin_call(builtin.shape(), &arg_types);
+            }
+            match self.foreign.get(name) {
+                Some(function) => return self.builtin_call(function.shape(), &arg_types),
+                None => self.error(format!("undefined function '{}'", name)),
+            }
+            return None;
+        };
+        self.facts.calls.insert(name.clone());
+        if signature.is_async && !awaited {
+            self.error(format!("async function '{}' must be called with 'await'", name));
+        }
+
+        if signature.params.len() != args.len() {
+            self.error(format!(
+                "'{}' takes {} argument(s) but {} were given",
+                name,
+                signature.params.len(),
+                args.len()
+            ));
+        } else {
+            for (param, found) in signature.params.iter().zip(&arg_types) {
+                if let (Some(expected), Some(found)) = (&param.ty, found)
+                    && !compatible(expected, found)
+                {
+                    self.error(format!(
+                        "argument '{}' of '{}' expects {} but got {}",
+                        param.name, name, expected, found
+                    ));
+                }
+            }
+        }
+        signature.ret_type.clone().or_else(|| self.inferred.get(name.as_str()).cloned())
+    }
+
+    fn construct(&mut self, decl: &TypeDecl, variant: &Variant, arg_types: &[Option<Type>]) -> Option<Type> {
+        if variant.fields.len() != arg_types.len() {
+            self.error(format!(
+                "'{}' takes {} field(s) but {} were given",
+                variant.name,
+                variant.fields.len(),
+                arg_types.len()
+            ));
+        } else {
+            for (position, (expected, found)) in variant.fields.iter().zip(arg_types).enumerate() {
+                if let Some(found) = found
+                    && !compatible(expected, found)
+                {
+                    self.error(format!(
+                        "field {} of '{}' expects {} but got {}",
+                        position + 1,
+                        variant.name,
+                        expected,
+                        found
+                    ));
+                }
+            }
+        }
+        Some(Type::Simple(decl.name.clone()))
+    }
+
+    fn builtin_call(&mut self, builtin: CallShape, arg_types: &[Option<Type>]) -> Option<Type> {
+        if let Some(capability) = builtin.capability {
+            self.facts.capabilities.insert(capability.clone());
+        }
+        let Some(params) = builtin.params else {
+            return builtin.returns.to_type();
+        };
+        if params.len() != arg_types.len() {
+            self.error(format!(
+                "'{}' takes {} argument(s) but {} were given",
+                builtin.name,
+                params.len(),
+                arg_types.len()
+            ));
+        } else {
+            for (position, (kind, found)) in params.iter().zip(arg_types).enumerate() {
+                if let Some(found) = found
+                    && !kind_accepts(*kind, found)
+                {
+                    self.error(format!(
+                        "argument {} of '{}' expects {} but got {}",
+                        position + 1,
+                        builtin.name,
+                        kind.name(),
+                        found
+                    ));
+                }
+            }
+        }
+        match builtin.returns {
+            // Integer math stays integral; anything else is a float
+            Kind::Number => {
+                let numbers: Vec<&Type> = arg_types.iter().flatten().map(base).collect();
+                if numbers.len() == arg_types.len() && numbers.iter().all(|ty| matches!(ty, Type::Int(_))) {
+                    Some(Type::Int(64))
+                } else if numbers.iter().any(|ty| matches!(ty, Type::Float(_))) {
+                    Some(Type::Float(64))
+                } else {
+                    None
+                }
+            }
+            kind => kind.to_type(),
+        }
+    }
+
+    fn match_expression(&mut self, scrutinee: &Expression, arms: &[MatchArm]) -> Option<Type> {
+        let scrutinee_type = self.expression(scrutinee);
+        self.check_exhaustive(scrutinee_type.as_ref(), arms);
+        let mut result: Option<Type> = None;
+        let mut consistent = true;
+        for arm in arms {
+            self.scopes.push(HashMap::new());
+            self.bind_pattern(&arm.pattern, scrutinee_type.clone());
+            let arm_type = self.expression(&arm.expr);
+            self.scopes.pop();
+
+            match (&result, arm_type) {
+                (None, found) => result = found,
+                (Some(expected), Some(found)) if consistent && !compatible(expected, &found) => {
+                    self.error(format!("match arms have different types: {} and {}", expected, found));
+                    consistent = false;
+                }
+                _ => {}
+            }
+        }
+        if consistent { result } else { None }
+    }
+
+    /// Reports a match that some value of the scrutinee's type falls
+    /// through. Without a known type, the first constructor pattern decides
+    /// it; matches on values of unknown type are not checked.
+    fn check_exhaustive(&mut self, scrutinee_type: Option<&Type>, arms: &[MatchArm]) {
+        let sum_types = self.sum_types;
+        let ty = scrutinee_type.cloned().or_else(|| {
+            arms.iter().find_map(|arm| match &arm.pattern {
+                Pattern::Constructor { name, .. } | Pattern::Identifier(name) => {
+                    sum_types.constructors.get(name.as_str()).map(|(decl, _)| Type::Simple(decl.name.clone()))
+                }
+                _ => None,
+            })
+        });
+        let Some(ty) = ty else { return };
+        let rows: Vec<Vec<&Pattern>> = arms.iter().map(|arm| vec![&arm.pattern]).collect();
+        if let Some(witness) = sum_types.uncovered(&rows, &[ty]) {
+            self.error(format!("match is not exhaustive: {} is not covered", witness.join(", ")));
+        }
+    }
+
+    fn bind_pattern(&mut self, pattern: &Pattern, ty: Option<Type>) {
+        match pattern {
+            Pattern::Identifier(name) if self.sum_types.constructors.contains_key(name.as_str()) => {
+                self.constructor_pattern(name, &[], ty)
+            }
+            Pattern::Identifier(name) => self.bind(name, ty),
+            Pattern::Wildcard | Pattern::Literal(_) => {}
+            Pattern::Tuple(patterns) => {
+                for pattern in patterns {
+                    self.bind_pattern(pattern, None);
+                }
+            }
+            Pattern::Constructor { name, args } => self.constructor_pattern(name, args, ty),
+        }
+    }
+
+    /// Checks a constructor pattern against the matched value's type and
+    /// binds its fields with the types the variant declares.
+    fn constructor_pattern(&mut self, name: &str, args: &[Pattern], ty: Option<Type>) {
+        let Some(&(decl, variant)) = self.sum_types.constructors.get(name) else {
+            self.error(format!("unknown constructor '{}'", name));
+            for arg in args {
+                self.bind_pattern(arg, None);
+            }
+            return;
+        };
+        if let Some(ty) = &ty
+            && !compatible(ty, &Type::Simple(decl.name.clone()))
+        {
+            self.error(format!("pattern '{}' is a {} but the matched value is {}", name, decl.name, ty));
+        }
+        if variant.fields.len() != args.len() {
+            self.error(format!("'{}' has {} field(s) but the pattern has {}", name, variant.fields.len(), args.len()));
+            for arg in args {
+                self.bind_pattern(arg, None);
+            }
+        } else {
+            for (arg, field) in args.iter().zip(&variant.fields) {
+                self.bind_pattern(arg, Some(field.clone()));
+            }
+        }
+    }
+}
+
+/// The type a value has once ownership, reference, gradient, and refinement
+/// wrappers are looked through.
+fn base(ty: &Type) -> &Type {
+    match ty {
+        Type::Mut(inner) | Type::Ref(inner) | Type::Grad(inner) => base(inner),
+        Type::DepType { var_type, .. } => base(var_type),
+        other => other,
+    }
+}
+
+/// Whether a value of type `found` may be used where `expected` is declared.
+/// Basic types and named types are compared; anything else is assumed to fit.
+fn compatible(expected: &Type, found: &Type) -> bool {
+    match (base(expected), base(found)) {
+        (Type::Int(_), Type::Int(_)) | (Type::Float(_), Type::Float(_)) => true,
+        (Type::Unit, Type::Unit) | (Type::Bool, Type::Bool) | (Type::String, Type::String) => true,
+        (Type::Simple(a), Type::Simple(b)) => a == b,
+        (a, b) => !is_basic(a) || !is_basic(b),
+    }
+}
+
+/// Whether a builtin parameter of `kind` accepts a value of type `ty`.
+/// Types the analysis cannot pin down are given the benefit of the doubt.
+fn kind_accepts(kind: Kind, ty: &Type) -> bool {
+    let ty = base(ty);
+    match kind {
+        Kind::Any => true,
+        Kind::Number => matches!(ty, Type::Int(_) | Type::Float(_)) || !is_basic(ty),
+        kind => kind.to_type().is_none_or(|expected| compatible(&expected, ty)),
+    }
+}
+
+fn is_basic(ty: &Type) -> bool {
+    matches!(ty, Type::Unit | Type::Bool | Type::Int(_) | Type::Float(_) | Type::String)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(result: &Diagnostics) -> Vec<String> {
+        result.diagnostics.iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn test_analysis_reports_semantic_problems() {
+        let source = "\
+fn double(n: Int<64>) -> Int<64> {
+  return n;
+  missing();
+}
+fn greet() {
+  let name = \"Astra\";
+  return name;
+}
+fn main() {
+  let x = double(1);
+  let s = greet();
+  double(s);
+  double(1, 2);
+  y;
+  x();
+}";
+        let (_, result) = analyze_source(source);
+        assert_eq!(
+            messages(&result),
+            vec![
+                "line 3, column 3: warning: unreachable statement",
+                "line 3, column 3: undefined function 'missing'",
+                "line 12, column 3: argument 'n' of 'double' expects Int<64> but got String",
+                "line 13, column 3: 'double' takes 1 argument(s) but 2 were given",
+                "line 14, column 3: undefined variable 'y'",
+                "line 15, column 3: 'x' is a variable, not a function",
+            ]
+        );
+        assert_eq!(result.inferred_returns.get("greet"), Some(&Type::String));
+        assert!(result.has_errors());
+    }
+
+    #[test]
+    fn test_capabilities_are_inferred_through_calls() {
+        let source = "\
+{IO} fn log(msg: String) { return msg; }
+fn tune() { modify(planner, 1); }
+fn fetch(net: Cap<Network>) { log(\"fetching\"); }
+{Pure} fn run() { tune(); }";
+        let (_, result) = analyze_source(source);
+        let needed: Vec<String> = result.capabilities.iter().map(|c| c.to_string()).collect();
+        assert_eq!(needed, vec!["IO", "Network", "SelfModify"]);
+        assert_eq!(messages(&result), vec!["line 4, column 11: pure function 'run' requires SelfModify"]);
+
+        let (_, builtin) = analyze_source("fn recall() { let facts = kb.query(\"likes\"); memory.note(str.len(\"x\")); }");
+        let needed: Vec<String> = builtin.capabilities.iter().map(|c| c.to_string()).collect();
+        assert_eq!(needed, vec!["Knowledge", "Memory"]);
+        assert_eq!(
+            messages(&builtin),
+            vec!["line 1, column 46: argument 1 of 'memory.note' expects String but got Int<64>"]
+        );
+
+        let (_, clean) = analyze_source("fn f(a: Bool) -> Bool { return match a { true => false; _ => true; }; }");
+        assert!(clean.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_matches_on_sum_types_must_be_exhaustive() {
+        let source = "\
+type Status = Pending | Done(Int<64>) | Failed(String);
+fn code(s: Status) -> Int<64> { return match s { Pending => 0; Done(n) => n; }; }
+fn nested(s: Status) -> Int<64> { return match s { Done(1) => 1; Done(_) => 2; Pending => 0; Failed(\"x\") => 3; }; }
+fn flag(b: Bool) -> Int<64> { return match b { true => 1; }; }
+fn complete(s: Status) -> String { return match s { Failed(why) => why; _ => \"ok\"; }; }
+fn build() -> Status { let s = Done(\"x\"); return match s { Gone(x) => Pending; Done(a, b) => Pending; other => other; }; }";
+        let (_, result) = analyze_source(source);
+        assert_eq!(
+            messages(&result),
+            vec![
+                "line 2, column 33: match is not exhaustive: Failed(_) is not covered",
+                "line 3, column 35: match is not exhaustive: Failed(_) is not covered",
+                "line 4, column 31: match is not exhaustive: false is not covered",
+                "line 6, column 24: field 1 of 'Done' expects Int<64> but got String",
+                "line 6, column 43: unknown constructor 'Gone'",
+                "line 6, column 43: 'Done' has 1 field(s) but the pattern has 2",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_await_and_sleep_only_suspend_async_bodies() {
+        let source = "\
+async fn wait_for(name: String) -> String { let e = await event(name); return e; }
+async fn watch() -> Int<64> {
+    let e = await event(\"door\") timeout 500;
+    let who = await wait_for(\"bell\");
+    sleep \"soon\";
+    { sleep 10; };
+    await str.upper(who);
+    return match e { Fired(w) => str.len(w); TimedOut => 0; };
+}
+fn main() { sleep 10; wait_for(\"x\"); }";
+        let (_, result) = analyze_source(source);
+        assert_eq!(
+            messages(&result),
+            vec![
+                "line 5, column 5: 'sleep' expects Int<64> but got String",
+                "line 6, column 7: 'sleep' can only be used directly in the body of an async function",
+                "line 7, column 5: 'str.upper' is not an async function",
+                "line 10, column 13: 'sleep' can only be used in an async function",
+                "line 10, column 23: async function 'wait_for' must be called with 'await'",
+            ]
+        );
+    }
+}
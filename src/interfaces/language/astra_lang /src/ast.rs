@@ -7,6 +7,10 @@
 //     Represents parsed source code constructs in a structured, typed form.
 //
 //     AST is syntax-agnostic but includes optional type annotations for later phases.
+//     Declarations and statements keep the span where they start, so later
+//     phases can report positioned diagnostics. Sum types declare the
+//     constructors that match expressions destructure. Async functions
+//     suspend at `await` and `sleep` statements.
 //
 // Intent:
 //     - Optimize for clarity and maintainability.
@@ -15,26 +19,35 @@
 //
 // Author: Alex Roussinov
 // Created: 2025-12-22
+// Updated: 2026-10-16
 // =============================================================================
 
+use crate::tokens::Span;
 use crate::types::Type;
 
 /// Top-level AST node representing a program item.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum AstNode {
     Function(FunctionDecl),
     Intent(IntentDecl),
     Rule(RuleDecl),
+    Import(ImportDecl),
+    Type(TypeDecl),
     // Extend as needed for other top-level constructs
 }
 
 /// Function declaration node.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct FunctionDecl {
     /// Optional differentiable function marker (`@grad`)
     pub is_grad: bool,
+    /// `async fn`: may suspend at `await` and `sleep` statements, and is
+    /// itself called with `await`
+    pub is_async: bool,
     /// Function name
     pub name: String,
+    /// Where the name appears
+    pub span: Span,
     /// Parameters with optional type annotations
     pub params: Vec<Param>,
     /// Optional return type annotation
@@ -46,10 +59,12 @@ pub struct FunctionDecl {
 }
 
 /// Intent declaration node (Astra intent layer).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct IntentDecl {
     /// Intent name
     pub name: String,
+    /// Where the name appears
+    pub span: Span,
     /// Optional motive description
     pub motive: Option<String>,
     /// Optional action description
@@ -58,10 +73,12 @@ pub struct IntentDecl {
 }
 
 /// Rule declaration node (logic programming).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct RuleDecl {
     /// Rule name
     pub name: String,
+    /// Where the name appears
+    pub span: Span,
     /// Parameters with optional type annotations
     pub params: Vec<Param>,
     /// Optional return type annotation
@@ -70,8 +87,39 @@ pub struct RuleDecl {
     pub body: Block,
 }
 
+/// Import of another module (`import utils.strings;`). Its functions and
+/// rules are called by their qualified names, such as `utils.strings.trim`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportDecl {
+    /// Dotted module path
+    pub path: String,
+    /// Where the path appears
+    pub span: Span,
+}
+
+/// Sum type declaration: `type Status = Pending | Done(Int<64>) | Failed(String);`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeDecl {
+    /// Type name
+    pub name: String,
+    /// Where the name appears
+    pub span: Span,
+    /// Alternatives, in declaration order
+    pub variants: Vec<Variant>,
+}
+
+/// One alternative of a sum type. Its name constructs values of the type
+/// (`Done(3)`, or bare `Pending` when it has no fields) and matches them
+/// in patterns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant {
+    pub name: String,
+    /// Types of the positional fields
+    pub fields: Vec<Type>,
+}
+
 /// Function or rule parameter.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Param {
     /// Parameter name
     pub name: String,
@@ -80,14 +128,21 @@ pub struct Param {
 }
 
 /// Block of statements.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Block {
     /// List of statements in the block
-    pub statements: Vec<Statement>,
+    pub statements: Vec<Spanned<Statement>>,
+}
+
+/// A node together with the span where it starts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub span: Span,
 }
 
 /// Statements within blocks.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Statement {
     /// Expression statement
     Expr(Expression),
@@ -97,11 +152,27 @@ pub enum Statement {
     Return(Expression),
     /// Backtracking block for symbolic search
     Backtrack(Block),
+    /// Suspends until `target` completes: `await event("door");`, or
+    /// `let who = await event("door");` to keep its value
+    Await { binding: Option<String>, target: Awaitable },
+    /// Suspends for a number of milliseconds: `sleep 500;`
+    Sleep(Expression),
     // Extend with other statement types as needed
 }
 
+/// What an `await` statement waits for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Awaitable {
+    /// An external event, by name: `event("door")`. With a timeout in
+    /// milliseconds (`event("door") timeout 5000`) the result is a `Wait`,
+    /// `Fired(payload)` or `TimedOut`, rather than the payload itself.
+    Event { name: Expression, timeout: Option<Expression> },
+    /// A call to another async function
+    Call { callee: String, args: Vec<Expression> },
+}
+
 /// Expressions.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Expression {
     Identifier(String),
     IntLiteral(i64),
@@ -128,14 +199,14 @@ pub enum Expression {
 }
 
 /// Match arm: pattern => expression;
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct MatchArm {
     pub pattern: Pattern,
     pub expr: Expression,
 }
 
 /// Patterns for match expressions.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Pattern {
     Identifier(String),
     Wildcard,
@@ -0,0 +1,279 @@
+// =============================================================================
+// Astra Reference Compiler (ARC)
+// File: builtins.rs
+//
+// Description:
+//     The standard library callable from Astra programs. Builtins live in
+//     dotted namespaces:
+//       - kb.query       facts known about a predicate       (Knowledge)
+//       - memory.note    write to narrative memory           (Memory)
+//       - intent.create  create an intent with a priority    (Intent)
+//       - emotion.get    read an emotional dimension         (Emotion)
+//       - str.*, list.*, math.*  pure utilities              (none)
+//     Each builtin names the capability it needs. Static analysis adds it to
+//     the program's requirements, and the interpreter refuses the call when
+//     the host has not granted it. Runtime services are reached through the
+//     interpreter's `Host`; the utilities are implemented here. Embedders
+//     add their own builtins through `ffi`, under names not taken here.
+//     Utilities that grow a value refuse to build one over `MAX_VALUE_BYTES`
+//     before allocating it.
+//
+// Intent:
+//     - Let programs orchestrate the runtime through a small, audited surface.
+//     - Keep every side effect behind a capability.
+//
+// Author: Alex Roussinov
+// Created: 2026-10-16
+// Updated: 2026-10-16
+// =============================================================================
+
+use crate::capabilities::Capability;
+use crate::interpreter::{EvalError, Host, Value, MAX_VALUE_BYTES};
+use crate::types::Type;
+
+/// Kind of value a builtin takes or returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Unit,
+    Bool,
+    Int,
+    Float,
+    /// Int or Float
+    Number,
+    Str,
+    List,
+    Any,
+}
+
+impl Kind {
+    /// The static type of values of this kind, where it is a single one.
+    pub fn to_type(self) -> Option<Type> {
+        match self {
+            Kind::Unit => Some(Type::Unit),
+            Kind::Bool => Some(Type::Bool),
+            Kind::Int => Some(Type::Int(64)),
+            Kind::Float => Some(Type::Float(64)),
+            Kind::Str => Some(Type::String),
+            Kind::List => Some(Type::Simple("List".into())),
+            Kind::Number | Kind::Any => None,
+        }
+    }
+
+    pub fn accepts(self, value: &Value) -> bool {
+        matches!(
+            (self, value),
+            (Kind::Any, _)
+                | (Kind::Unit, Value::Unit)
+                | (Kind::Bool, Value::Bool(_))
+                | (Kind::Int, Value::Int(_))
+                | (Kind::Float, Value::Float(_))
+                | (Kind::Number, Value::Int(_) | Value::Float(_))
+                | (Kind::Str, Value::Str(_))
+                | (Kind::List, Value::List(_))
+        )
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Kind::Unit => "Unit",
+            Kind::Bool => "Bool",
+            Kind::Int => "Int",
+            Kind::Float => "Float",
+            Kind::Number => "a number",
+            Kind::Str => "String",
+            Kind::List => "List",
+            Kind::Any => "any value",
+        }
+    }
+}
+
+/// A function provided to every Astra program.
+#[derive(Debug)]
+pub struct Builtin {
+    pub name: &'static str,
+    pub params: &'static [Kind],
+    pub returns: Kind,
+    /// Capability the host must grant before the builtin may be called
+    pub capability: Option<Capability>,
+}
+
+const fn builtin(name: &'static str, params: &'static [Kind], returns: Kind, capability: Option<Capability>) -> Builtin {
+    Builtin { name, params, returns, capability }
+}
+
+pub static BUILTINS: &[Builtin] = &[
+    builtin("kb.query", &[Kind::Str], Kind::List, Some(Capability::Knowledge)),
+    builtin("memory.note", &[Kind::Str], Kind::Unit, Some(Capability::Memory)),
+    builtin("intent.create", &[Kind::Str, Kind::Int], Kind::Int, Some(Capability::Intent)),
+    builtin("emotion.get", &[Kind::Str], Kind::Float, Some(Capability::Emotion)),
+    builtin("str.concat", &[Kind::Str, Kind::Str], Kind::Str, None),
+    builtin("str.len", &[Kind::Str], Kind::Int, None),
+    builtin("str.upper", &[Kind::Str], Kind::Str, None),
+    builtin("str.lower", &[Kind::Str], Kind::Str, None),
+    builtin("str.contains", &[Kind::Str, Kind::Str], Kind::Bool, None),
+    builtin("str.eq", &[Kind::Str, Kind::Str], Kind::Bool, None),
+    builtin("str.from", &[Kind::Any], Kind::Str, None),
+    builtin("list.new", &[], Kind::List, None),
+    builtin("list.push", &[Kind::List, Kind::Any], Kind::List, None),
+    builtin("list.len", &[Kind::List], Kind::Int, None),
+    builtin("list.get", &[Kind::List, Kind::Int], Kind::Any, None),
+    builtin("list.join", &[Kind::List, Kind::Str], Kind::Str, None),
+    builtin("math.add", &[Kind::Number, Kind::Number], Kind::Number, None),
+    builtin("math.sub", &[Kind::Number, Kind::Number], Kind::Number, None),
+    builtin("math.mul", &[Kind::Number, Kind::Number], Kind::Number, None),
+    builtin("math.div", &[Kind::Number, Kind::Number], Kind::Number, None),
+    builtin("math.min", &[Kind::Number, Kind::Number], Kind::Number, None),
+    builtin("math.max", &[Kind::Number, Kind::Number], Kind::Number, None),
+    builtin("math.abs", &[Kind::Number], Kind::Number, None),
+    builtin("math.sqrt", &[Kind::Number], Kind::Float, None),
+    builtin("math.eq", &[Kind::Number, Kind::Number], Kind::Bool, None),
+    builtin("math.lt", &[Kind::Number, Kind::Number], Kind::Bool, None),
+    builtin("math.gt", &[Kind::Number, Kind::Number], Kind::Bool, None),
+];
+
+/// How a builtin, standard or foreign, is called.
+#[derive(Debug, Clone, Copy)]
+pub struct CallShape<'b> {
+    pub name: &'b str,
+    /// `None` when any arguments are accepted
+    pub params: Option<&'b [Kind]>,
+    pub returns: Kind,
+    pub capability: Option<&'b Capability>,
+}
+
+/// Looks up a builtin by its dotted name.
+pub fn lookup(name: &str) -> Option<&'static Builtin> {
+    BUILTINS.iter().find(|b| b.name == name)
+}
+
+impl Builtin {
+    pub fn shape(&self) -> CallShape<'_> {
+        CallShape {
+            name: self.name,
+            params: Some(self.params),
+            returns: self.returns,
+            capability: self.capability.as_ref(),
+        }
+    }
+
+    /// Runs the builtin on arguments already checked against `params`.
+    pub(crate) fn call(&self, args: Vec<Value>, host: &mut dyn Host) -> Result<Value, EvalError> {
+        let (a, b) = (args.first().cloned(), args.get(1).cloned());
+        Ok(match (self.name, a, b) {
+            ("kb.query", Some(Value::Str(predicate)), _) => {
+                Value::List(host.query_knowledge(&predicate).into_iter().map(Value::Str).collect())
+            }
+            ("memory.note", Some(Value::Str(text)), _) => {
+                host.note(&text);
+                Value::Unit
+            }
+            ("intent.create", Some(Value::Str(description)), Some(Value::Int(priority))) => {
+                let priority = u32::try_from(priority).map_err(|_| EvalError::InvalidArgument {
+                    name: self.name.to_string(),
+                    reason: format!("priority {} is out of range", priority),
+                })?;
+                Value::Int(host.create_intent(&description, priority) as i64)
+            }
+            ("emotion.get", Some(Value::Str(dimension)), _) => match host.emotion(&dimension) {
+                Some(level) => Value::Float(level),
+                None => {
+                    return Err(EvalError::InvalidArgument {
+                        name: self.name.to_string(),
+                        reason: format!("unknown emotion '{}'", dimension),
+                    })
+                }
+            },
+
+            ("str.concat", Some(Value::Str(a)), Some(Value::Str(b))) => {
+                self.fits(a.len() + b.len())?;
+                Value::Str(a + &b)
+            }
+            ("str.len", Some(Value::Str(s)), _) => Value::Int(s.chars().count() as i64),
+            ("str.upper", Some(Value::Str(s)), _) => Value::Str(s.to_uppercase()),
+            ("str.lower", Some(Value::Str(s)), _) => Value::Str(s.to_lowercase()),
+            ("str.contains", Some(Value::Str(s)), Some(Value::Str(part))) => Value::Bool(s.contains(&part)),
+            ("str.eq", Some(Value::Str(a)), Some(Value::Str(b))) => Value::Bool(a == b),
+            ("str.from", Some(value), _) => Value::Str(value.to_string()),
+
+            ("list.new", _, _) => Value::List(Vec::new()),
+            ("list.push", Some(Value::List(mut items)), Some(item)) => {
+                self.fits(items.iter().chain([&item]).map(Value::footprint).sum())?;
+                items.push(item);
+                Value::List(items)
+            }
+            ("list.len", Some(Value::List(items)), _) => Value::Int(items.len() as i64),
+            ("list.get", Some(Value::List(mut items)), Some(Value::Int(index))) => {
+                let len = items.len();
+                match usize::try_from(index).ok().filter(|&i| i < len) {
+                    Some(i) => items.swap_remove(i),
+                    None => return Err(EvalError::IndexOutOfRange { index, len }),
+                }
+            }
+            ("list.join", Some(Value::List(items)), Some(Value::Str(separator))) => {
+                self.fits(separator.len().saturating_mul(items.len()))?;
+                Value::Str(items.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(&separator))
+            }
+
+            ("math.abs", Some(Value::Int(n)), _) => Value::Int(n.checked_abs().ok_or(EvalError::Overflow)?),
+            ("math.abs", Some(Value::Float(x)), _) => Value::Float(x.abs()),
+            ("math.sqrt", Some(n), _) => Value::Float(as_float(&n).sqrt()),
+            (op, Some(a), Some(b)) if op.starts_with("math.") => arithmetic(op, a, b)?,
+
+            _ => {
+                return Err(EvalError::InvalidArgument {
+                    name: self.name.to_string(),
+                    reason: format!("cannot be applied to {:?}", args),
+                })
+            }
+        })
+    }
+
+    /// Refuses to build a value of `size` bytes if that is over the limit.
+    fn fits(&self, size: usize) -> Result<(), EvalError> {
+        if size > MAX_VALUE_BYTES {
+            return Err(EvalError::TooLarge { name: self.name.to_string(), size });
+        }
+        Ok(())
+    }
+}
+
+fn as_float(value: &Value) -> f64 {
+    match value {
+        Value::Int(n) => *n as f64,
+        Value::Float(x) => *x,
+        _ => f64::NAN,
+    }
+}
+
+/// Binary math on two numbers: integer arithmetic when both are Int,
+/// floating point otherwise.
+fn arithmetic(op: &str, a: Value, b: Value) -> Result<Value, EvalError> {
+    if let (Value::Int(x), Value::Int(y)) = (&a, &b) {
+        let (x, y) = (*x, *y);
+        return Ok(match op {
+            "math.add" => Value::Int(x.checked_add(y).ok_or(EvalError::Overflow)?),
+            "math.sub" => Value::Int(x.checked_sub(y).ok_or(EvalError::Overflow)?),
+            "math.mul" => Value::Int(x.checked_mul(y).ok_or(EvalError::Overflow)?),
+            "math.div" if y == 0 => return Err(EvalError::DivisionByZero),
+            "math.div" => Value::Int(x.checked_div(y).ok_or(EvalError::Overflow)?),
+            "math.min" => Value::Int(x.min(y)),
+            "math.max" => Value::Int(x.max(y)),
+            "math.eq" => Value::Bool(x == y),
+            "math.lt" => Value::Bool(x < y),
+            _ => Value::Bool(x > y),
+        });
+    }
+    let (x, y) = (as_float(&a), as_float(&b));
+    Ok(match op {
+        "math.add" => Value::Float(x + y),
+        "math.sub" => Value::Float(x - y),
+        "math.mul" => Value::Float(x * y),
+        "math.div" if y == 0.0 => return Err(EvalError::DivisionByZero),
+        "math.div" => Value::Float(x / y),
+        "math.min" => Value::Float(x.min(y)),
+        "math.max" => Value::Float(x.max(y)),
+        "math.eq" => Value::Bool(x == y),
+        "math.lt" => Value::Bool(x < y),
+        _ => Value::Bool(x > y),
+    })
+}
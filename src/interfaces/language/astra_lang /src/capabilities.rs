@@ -0,0 +1,73 @@
+// =============================================================================
+// Astra Reference Compiler (ARC)
+// File: capabilities.rs
+//
+// Description:
+//     Capabilities are the permissions an Astra program needs from its host:
+//     the effects it declares (`{IO, Network}`), the capability tokens it
+//     takes (`Cap<Network>`), and what its body does (`modify(...)` needs
+//     SelfModify, `kb.query(...)` needs Knowledge). Static analysis infers
+//     the set a program requires so the host can decide before execution
+//     whether to grant it, and the interpreter refuses builtins whose
+//     capability was not granted.
+//
+// Intent:
+//     - Give effects and capability tokens one shared vocabulary.
+//     - Keep unknown, user-defined effects rather than dropping them.
+//
+// Author: Alex Roussinov
+// Created: 2026-10-16
+// Updated: 2026-10-16
+// =============================================================================
+
+use std::fmt;
+
+/// A permission an Astra program may require.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Capability {
+    Io,
+    Network,
+    SelfModify,
+    /// Reading Astra's knowledge base
+    Knowledge,
+    /// Writing to narrative memory
+    Memory,
+    /// Creating intents
+    Intent,
+    /// Inspecting emotional state
+    Emotion,
+    /// A user-defined effect or capability token
+    Custom(String),
+}
+
+impl Capability {
+    /// The capability an effect name stands for. `Pure` names none.
+    pub fn from_effect(name: &str) -> Option<Self> {
+        match name {
+            "Pure" => None,
+            "IO" => Some(Capability::Io),
+            "Network" => Some(Capability::Network),
+            "SelfModify" => Some(Capability::SelfModify),
+            "Knowledge" => Some(Capability::Knowledge),
+            "Memory" => Some(Capability::Memory),
+            "Intent" => Some(Capability::Intent),
+            "Emotion" => Some(Capability::Emotion),
+            other => Some(Capability::Custom(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Capability::Io => write!(f, "IO"),
+            Capability::Network => write!(f, "Network"),
+            Capability::SelfModify => write!(f, "SelfModify"),
+            Capability::Knowledge => write!(f, "Knowledge"),
+            Capability::Memory => write!(f, "Memory"),
+            Capability::Intent => write!(f, "Intent"),
+            Capability::Emotion => write!(f, "Emotion"),
+            Capability::Custom(name) => write!(f, "{}", name),
+        }
+    }
+}
@@ -4,6 +4,7 @@
 //
 // Description:
 //     Error types and diagnostic utilities for parsing and type checking.
+//     Diagnostics carry a severity so analysis can warn without rejecting.
 //
 // Intent:
 //     - Provide clear, actionable error messages.
@@ -11,9 +12,58 @@
 //
 // Author: Alex Roussinov
 // Created: 2025-12-22
+// Updated: 2026-10-16
 // =============================================================================
 
 use std::fmt;
+use thiserror::Error;
+
+/// How serious a diagnostic is. Errors reject a program; warnings do not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// A problem found while lexing, parsing, or analyzing, located by line and
+/// column. Malformed source produces diagnostics rather than aborting the pass.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub message: String,
+    pub span: crate::tokens::Span,
+    pub severity: Severity,
+}
+
+impl Diagnostic {
+    pub fn new(message: impl Into<String>, span: crate::tokens::Span) -> Self {
+        Diagnostic { message: message.into(), span, severity: Severity::Error }
+    }
+
+    pub fn warning(message: impl Into<String>, span: crate::tokens::Span) -> Self {
+        Diagnostic { message: message.into(), span, severity: Severity::Warning }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    pub fn line(&self) -> usize {
+        self.span.line_or_first()
+    }
+
+    pub fn column(&self) -> usize {
+        self.span.column_or_first()
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.severity {
+            Severity::Error => write!(f, "line {}, column {}: {}", self.line(), self.column(), self.message),
+            Severity::Warning => write!(f, "line {}, column {}: warning: {}", self.line(), self.column(), self.message),
+        }
+    }
+}
 
 /// Represents a span in source code (optional).
 #[derive(Debug, Clone, Copy)]
@@ -23,77 +73,49 @@ pub struct Span {
 }
 
 /// Parsing errors.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Error)]
 pub enum ParseError {
+    #[error("Unexpected end of input")]
     UnexpectedEof,
+    #[error("Unexpected token: {0}")]
     UnexpectedToken(String),
+    #[error("Expected token: {0}")]
     ExpectedToken(String),
+    #[error("Parse error: {0}")]
     Custom(String),
 }
 
-impl fmt::Display for ParseError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            ParseError::UnexpectedEof => write!(f, "Unexpected end of input"),
-            ParseError::UnexpectedToken(tok) => write!(f, "Unexpected token: {}", tok),
-            ParseError::ExpectedToken(tok) => write!(f, "Expected token: {}", tok),
-            ParseError::Custom(msg) => write!(f, "Parse error: {}", msg),
-        }
-    }
-}
-
 /// Type checking errors.
-#[derive(Debug)]
+#[derive(Debug, Error)]
 pub enum TypeError {
+    #[error("Type mismatch: expected {expected:?}, found {found:?}")]
     Mismatch {
         expected: crate::types::Type,
         found: crate::types::Type,
         span: Option<Span>,
     },
+    #[error("Linear variable '{var}' used more than once")]
     LinearUseError {
         var: String,
         span: Option<Span>,
     },
+    #[error("Undefined variable '{var}'")]
     UndefinedVariable {
         var: String,
         span: Option<Span>,
     },
+    #[error("Effect mismatch: expected {expected:?}, found {found:?}")]
     EffectMismatch {
         expected: Vec<String>,
         found: Vec<String>,
         span: Option<Span>,
     },
+    #[error("Dependent constraint failed on '{var}': {prop:?}")]
     DependentConstraintFailed {
         var: String,
         prop: crate::ast::Expression,
         span: Option<Span>,
     },
+    #[error("Type error: {0}")]
     Other(String),
 }
-
-impl fmt::Display for TypeError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            TypeError::Mismatch { expected, found, .. } => {
-                write!(f, "Type mismatch: expected {:?}, found {:?}", expected, found)
-            }
-            TypeError::LinearUseError { var, .. } => {
-                write!(f, "Linear variable '{}' used more than once", var)
-            }
-            TypeError::UndefinedVariable { var, .. } => {
-                write!(f, "Undefined variable '{}'", var)
-            }
-            TypeError::EffectMismatch { expected, found, .. } => {
-                write!(
-                    f,
-                    "Effect mismatch: expected {:?}, found {:?}",
-                    expected, found
-                )
-            }
-            TypeError::DependentConstraintFailed { var, prop, .. } => {
-                write!(f, "Dependent constraint failed on '{}': {:?}", var, prop)
-            }
-            TypeError::Other(msg) => write!(f, "Type error: {}", msg),
-        }
-    }
-}
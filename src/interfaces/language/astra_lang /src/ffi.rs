@@ -0,0 +1,416 @@
+// =============================================================================
+// Astra Reference Compiler (ARC)
+// File: ffi.rs
+//
+// Description:
+//     Builtins supplied by the application embedding Astra. A foreign
+//     function is a Rust closure registered under a dotted name
+//     (`weather.get`) together with the capability it needs, then given the
+//     kinds of its parameters and result:
+//
+//         functions
+//             .register("weather.get", Capability::Network, |args| Ok(forecast(&args.get::<String>(0)?).into()))?
+//             .params(&[Kind::Str])
+//             .returns(Kind::Float);
+//
+//     Every foreign function names a capability: host code can do anything,
+//     so none is callable by a program that was granted nothing. Failures
+//     are `ForeignError`s, whether the call was malformed or the host's own
+//     code gave up.
+//
+//     Analysis checks calls against the declared signature and adds the
+//     capability to the program's requirements. The interpreter checks
+//     arguments before calling the closure and its result after, so the
+//     closure can unpack arguments with `Args::get` without surprises.
+//
+// Intent:
+//     - Let hosts extend the standard library without forking the crate.
+//     - Hold foreign functions to the same capability rules as builtins.
+//
+// Author: Alex Roussinov
+// Created: 2026-10-16
+// Updated: 2026-10-16
+// =============================================================================
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use crate::builtins::{self, CallShape, Kind};
+use crate::capabilities::Capability;
+use crate::interpreter::Value;
+
+/// Why a foreign function could not be registered.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FfiError {
+    #[error("'{0}' is a standard builtin and cannot be replaced")]
+    Reserved(String),
+    #[error("'{0}' is not a valid builtin name; use dotted identifiers such as 'weather.get'")]
+    InvalidName(String),
+}
+
+/// Why a call to a foreign function failed.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ForeignError {
+    #[error("takes {expected} argument(s) but {given} were given")]
+    Arity { expected: usize, given: usize },
+    #[error("argument {position} expects {expected}")]
+    ArgumentKind { position: usize, expected: &'static str },
+    #[error("has no argument {position}")]
+    MissingArgument { position: usize },
+    #[error("argument {position} cannot be read as {expected}")]
+    Unreadable { position: usize, expected: &'static str },
+    #[error("returned {value} where {expected} was declared")]
+    ResultKind { value: String, expected: &'static str },
+    /// The host's own code failed
+    #[error("{0}")]
+    Failed(String),
+}
+
+type Function = dyn Fn(&Args) -> Result<Value, ForeignError> + Send + Sync;
+
+/// A builtin implemented by the host application.
+#[derive(Clone)]
+pub struct ForeignFunction {
+    name: String,
+    /// `None` until declared: any arguments are accepted
+    params: Option<Vec<Kind>>,
+    returns: Kind,
+    capability: Capability,
+    function: Arc<Function>,
+}
+
+impl ForeignFunction {
+    /// Declares the kinds of the parameters, which also fixes their number.
+    pub fn params(&mut self, params: &[Kind]) -> &mut Self {
+        self.params = Some(params.to_vec());
+        self
+    }
+
+    /// Declares the kind of the result; `Any` until declared.
+    pub fn returns(&mut self, returns: Kind) -> &mut Self {
+        self.returns = returns;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The capability a program needs to call the function.
+    pub fn capability(&self) -> &Capability {
+        &self.capability
+    }
+
+    pub fn shape(&self) -> CallShape<'_> {
+        CallShape {
+            name: &self.name,
+            params: self.params.as_deref(),
+            returns: self.returns,
+            capability: Some(&self.capability),
+        }
+    }
+
+    /// Calls the closure, checking the arguments against `params` and the
+    /// result against `returns`. Capabilities are the caller's to check.
+    pub fn call(&self, values: &[Value]) -> Result<Value, ForeignError> {
+        if let Some(params) = &self.params {
+            if params.len() != values.len() {
+                return Err(ForeignError::Arity { expected: params.len(), given: values.len() });
+            }
+            if let Some((position, kind)) =
+                params.iter().enumerate().find(|(i, kind)| !kind.accepts(&values[*i]))
+            {
+                return Err(ForeignError::ArgumentKind { position: position + 1, expected: kind.name() });
+            }
+        }
+        let value = (self.function)(&Args { values })?;
+        if self.returns.accepts(&value) {
+            Ok(value)
+        } else {
+            Err(ForeignError::ResultKind { value: value.to_string(), expected: self.returns.name() })
+        }
+    }
+}
+
+impl fmt::Debug for ForeignFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ForeignFunction")
+            .field("name", &self.name)
+            .field("params", &self.params)
+            .field("returns", &self.returns)
+            .field("capability", &self.capability)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The foreign functions available to programs, by name. Cloning is cheap;
+/// closures are shared.
+#[derive(Debug, Clone, Default)]
+pub struct ForeignFunctions {
+    functions: HashMap<String, ForeignFunction>,
+}
+
+impl ForeignFunctions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `function` under `name`, callable by programs granted
+    /// `capability`, replacing any foreign function of that name, and
+    /// returns it for declaring its signature.
+    pub fn register(
+        &mut self,
+        name: &str,
+        capability: Capability,
+        function: impl Fn(&Args) -> Result<Value, ForeignError> + Send + Sync + 'static,
+    ) -> Result<&mut ForeignFunction, FfiError> {
+        if builtins::lookup(name).is_some() {
+            return Err(FfiError::Reserved(name.to_string()));
+        }
+        if !is_dotted_identifier(name) {
+            return Err(FfiError::InvalidName(name.to_string()));
+        }
+        let function = ForeignFunction {
+            name: name.to_string(),
+            params: None,
+            returns: Kind::Any,
+            capability,
+            function: Arc::new(function),
+        };
+        self.functions.insert(name.to_string(), function);
+        Ok(self.functions.get_mut(name).expect("just registered"))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ForeignFunction> {
+        self.functions.get(name)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<ForeignFunction> {
+        self.functions.remove(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.functions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+}
+
+/// Names the lexer reads as a single identifier.
+fn is_dotted_identifier(name: &str) -> bool {
+    name.split('.').all(|segment| {
+        let mut chars = segment.chars();
+        chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+/// The arguments of a call to a foreign function.
+pub struct Args<'v> {
+    values: &'v [Value],
+}
+
+impl Args<'_> {
+    /// The argument at `index` as a Rust value.
+    pub fn get<T: FromValue>(&self, index: usize) -> Result<T, ForeignError> {
+        let position = index + 1;
+        let value = self.values.get(index).ok_or(ForeignError::MissingArgument { position })?;
+        T::from_value(value).ok_or(ForeignError::Unreadable { position, expected: std::any::type_name::<T>() })
+    }
+
+    pub fn values(&self) -> &[Value] {
+        self.values
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Rust types an Astra value can be read as.
+pub trait FromValue: Sized {
+    fn from_value(value: &Value) -> Option<Self>;
+}
+
+impl FromValue for Value {
+    fn from_value(value: &Value) -> Option<Self> {
+        Some(value.clone())
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for i64 {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Int(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+/// Ints widen to floats, as in `math.*`.
+impl FromValue for f64 {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Int(n) => Some(*n as f64),
+            Value::Float(x) => Some(*x),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Str(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::List(items) => items.iter().map(T::from_value).collect(),
+            _ => None,
+        }
+    }
+}
+
+impl From<()> for Value {
+    fn from(_: ()) -> Self {
+        Value::Unit
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(n: i64) -> Self {
+        Value::Int(n)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(x: f64) -> Self {
+        Value::Float(x)
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::Str(s)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::Str(s.to_string())
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(items: Vec<T>) -> Self {
+        Value::List(items.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::interpreter::{EvalError, Host, Poll, Task};
+    use crate::modules::{analyze_program, NoModules, UnitStore};
+
+    struct NoHost;
+
+    impl Host for NoHost {
+        fn query_knowledge(&mut self, _: &str) -> Vec<String> {
+            Vec::new()
+        }
+        fn note(&mut self, _: &str) {}
+        fn create_intent(&mut self, _: &str, _: u32) -> u64 {
+            0
+        }
+        fn emotion(&mut self, _: &str) -> Option<f64> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_foreign_functions_are_checked_and_capability_gated() {
+        let mut foreign = ForeignFunctions::new();
+        foreign
+            .register("weather.get", Capability::Network, |args| {
+                let city: String = args.get(0)?;
+                Ok(if city == "Oslo" { -3.5 } else { 21.0 }.into())
+            })
+            .unwrap()
+            .params(&[Kind::Str])
+            .returns(Kind::Float);
+        let atlas = Capability::Custom("Atlas".into());
+        foreign.register("cities", atlas.clone(), |_| Ok(vec!["Oslo", "Rome"].into())).unwrap().returns(Kind::Int);
+        foreign.register("city", atlas.clone(), |args| args.get::<i64>(0).map(Value::from)).unwrap();
+        assert_eq!(
+            foreign.register("math.add", Capability::Io, |_| Ok(Value::Unit)).unwrap_err(),
+            FfiError::Reserved("math.add".into())
+        );
+        assert!(matches!(foreign.register("weather get", Capability::Io, |_| Ok(Value::Unit)), Err(FfiError::InvalidName(_))));
+
+        let run = |source: &str, granted: &[Capability]| {
+            let (items, analysis) = analyze_program(source, &NoModules, &mut UnitStore::new(), &foreign);
+            let granted: BTreeSet<Capability> = granted.iter().cloned().collect();
+            let mut task = Task::new(items, granted).with_foreign(foreign.clone());
+            (analysis, task.start(&mut NoHost))
+        };
+
+        let (analysis, result) = run("fn main() { return math.add(weather.get(\"Oslo\"), 1); }", &[Capability::Network]);
+        assert!(analysis.diagnostics.is_empty(), "{:?}", analysis.diagnostics);
+        assert_eq!(analysis.capabilities, BTreeSet::from([Capability::Network]));
+        assert_eq!(result, Ok(Poll::Done(Value::Float(-2.5))));
+
+        let (_, denied) = run("fn main() { return weather.get(\"Rome\"); }", &[]);
+        assert!(matches!(denied, Err(EvalError::CapabilityDenied { .. })));
+
+        let (analysis, _) = run("fn main() { weather.get(1); }", &[Capability::Network]);
+        let messages: Vec<String> = analysis.diagnostics.iter().map(|d| d.to_string()).collect();
+        assert_eq!(messages, vec!["line 1, column 13: argument 1 of 'weather.get' expects String but got Int<64>"]);
+
+        // Nothing a host registers is callable without a grant
+        let (_, ungranted) = run("fn main() { return cities(); }", &[]);
+        assert!(matches!(ungranted, Err(EvalError::CapabilityDenied { .. })));
+
+        let (_, mistyped) = run("fn main() { return cities(); }", &[atlas.clone()]);
+        assert_eq!(
+            mistyped,
+            Err(EvalError::Foreign {
+                name: "cities".into(),
+                reason: ForeignError::ResultKind { value: "[Oslo, Rome]".into(), expected: "Int" },
+            })
+        );
+        let (_, missing) = run("fn main() { return city(); }", &[atlas]);
+        assert_eq!(missing.unwrap_err().to_string(), "'city' failed: has no argument 1");
+    }
+}
@@ -0,0 +1,897 @@
+// =============================================================================
+// Astra Reference Compiler (ARC)
+// File: interpreter.rs
+//
+// Description:
+//     A tree-walking interpreter for analyzed Astra programs. It runs `main`
+//     and the functions and rules it calls. Whatever touches the outside
+//     world (knowledge, memory, intents, emotion, self-modification) goes
+//     through a `Host` supplied by the embedder.
+//     Capabilities are enforced at the call site. A builtin or `modify(...)`
+//     whose capability was not granted fails with `CapabilityDenied` even if
+//     the host skipped the static check. Foreign functions the embedder
+//     registered are called like builtins, under the same rules.
+//     A `backtrack { ... }` block that fails is abandoned: its error and
+//     bindings are discarded and execution continues after it. Host effects
+//     it already performed are not undone.
+//     Programs with async functions run as a `Task`. At `await` and `sleep`
+//     the task suspends and returns to its host, keeping the frames of the
+//     async calls in progress. The host resumes it when the event arrives
+//     or the time passes, so waiting never blocks the host.
+//
+// Intent:
+//     - Make analyzed programs executable inside the runtime.
+//     - Bound recursion, work, and the size of values so no program can
+//       hang its host or exhaust its memory.
+//     - Let cognitive scripts wait for the world without holding up a tick.
+//
+// Author: Alex Roussinov
+// Created: 2026-10-16
+// Updated: 2026-10-16
+// =============================================================================
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::ast::*;
+use crate::builtins::{self, CallShape};
+use crate::capabilities::Capability;
+use crate::ffi::{ForeignError, ForeignFunctions};
+use crate::prelude;
+
+/// Deepest chain of function calls a program may make.
+pub const MAX_CALL_DEPTH: usize = 64;
+/// Most expressions a single run, or one slice of a task between
+/// suspensions, may evaluate.
+pub const MAX_STEPS: usize = 100_000;
+/// Largest value, counted in bytes of text and list items, a builtin or
+/// constructor may build.
+pub const MAX_VALUE_BYTES: usize = 1 << 20;
+
+/// A runtime value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Unit,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    List(Vec<Value>),
+    /// A value of a sum type, built by one of its constructors
+    Variant { name: String, fields: Vec<Value> },
+}
+
+impl Value {
+    /// Roughly how many bytes the value holds, as checked against
+    /// `MAX_VALUE_BYTES`.
+    pub fn footprint(&self) -> usize {
+        match self {
+            Value::Str(s) => s.len(),
+            Value::List(items) => items.iter().map(Value::footprint).sum::<usize>() + 8,
+            Value::Variant { name, fields } => name.len() + fields.iter().map(Value::footprint).sum::<usize>(),
+            _ => 8,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Unit => write!(f, "()"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Int(n) => write!(f, "{}", n),
+            Value::Float(x) => write!(f, "{}", x),
+            Value::Str(s) => write!(f, "{}", s),
+            Value::List(items) => {
+                let items: Vec<String> = items.iter().map(|v| v.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+            Value::Variant { name, fields } if fields.is_empty() => write!(f, "{}", name),
+            Value::Variant { name, fields } => {
+                let fields: Vec<String> = fields.iter().map(|v| v.to_string()).collect();
+                write!(f, "{}({})", name, fields.join(", "))
+            }
+        }
+    }
+}
+
+/// The services a program can reach through builtins.
+pub trait Host {
+    /// Objects of known facts with the given predicate.
+    fn query_knowledge(&mut self, predicate: &str) -> Vec<String>;
+    /// Records a note in narrative memory.
+    fn note(&mut self, text: &str);
+    /// Creates an intent and returns its id.
+    fn create_intent(&mut self, description: &str, priority: u32) -> u64;
+    /// Current level of an emotional dimension, if the host knows it.
+    fn emotion(&mut self, name: &str) -> Option<f64>;
+    /// Applies `modify(target, patch)`.
+    fn modify(&mut self, target: &str, _patch: &Value) -> Result<(), String> {
+        Err(format!("this host cannot modify '{}'", target))
+    }
+}
+
+/// Why a program stopped.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum EvalError {
+    #[error("undefined variable '{0}'")]
+    UndefinedVariable(String),
+    #[error("undefined function '{0}'")]
+    UndefinedFunction(String),
+    #[error("'{name}' requires the {capability} capability, which was not granted")]
+    CapabilityDenied { name: String, capability: Capability },
+    #[error("'{name}' takes {expected} argument(s) but {found} were given")]
+    Arity { name: String, expected: usize, found: usize },
+    #[error("argument {position} of '{name}' expects {expected} but got {found}")]
+    WrongArgument {
+        name: String,
+        position: usize,
+        expected: &'static str,
+        found: Value,
+    },
+    #[error("invalid argument to '{name}': {reason}")]
+    InvalidArgument { name: String, reason: String },
+    #[error("division by zero")]
+    DivisionByZero,
+    #[error("integer overflow")]
+    Overflow,
+    #[error("index {index} is out of range for a list of length {len}")]
+    IndexOutOfRange { index: i64, len: usize },
+    #[error("no match arm matches {0}")]
+    NoMatch(Value),
+    #[error("host error: {0}")]
+    Host(String),
+    #[error("'{name}' failed: {reason}")]
+    Foreign { name: String, reason: ForeignError },
+    #[error("call depth exceeded {MAX_CALL_DEPTH}")]
+    RecursionLimit,
+    #[error("program exceeded {MAX_STEPS} evaluation steps")]
+    StepLimit,
+    #[error("'{name}' would build a value of {size} bytes, over the {MAX_VALUE_BYTES}-byte limit")]
+    TooLarge { name: String, size: usize },
+    #[error("async function '{0}' must be called with 'await'")]
+    NotAwaited(String),
+    #[error("'{0}' is not an async function")]
+    NotAsync(String),
+    #[error("'{0}' can only be used directly in the body of an async function")]
+    MisplacedSuspension(&'static str),
+    #[error("program suspended waiting for {0}")]
+    Suspended(Suspension),
+}
+
+/// What a suspended task is waiting for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Suspension {
+    /// An event by name, for at most `timeout` if one was given
+    Event { name: String, timeout: Option<Duration> },
+    /// Time to pass
+    Sleep(Duration),
+}
+
+impl fmt::Display for Suspension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Suspension::Event { name, timeout: None } => write!(f, "event '{}'", name),
+            Suspension::Event { name, timeout: Some(timeout) } => {
+                write!(f, "event '{}' for up to {}ms", name, timeout.as_millis())
+            }
+            Suspension::Sleep(duration) => write!(f, "{}ms to pass", duration.as_millis()),
+        }
+    }
+}
+
+/// Why a suspended task is resumed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Wakeup {
+    /// The awaited event happened, with this payload
+    Event(String),
+    /// The event's timeout passed first
+    TimedOut,
+    /// The sleep's duration passed
+    Elapsed,
+}
+
+/// How far a task got.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Poll {
+    /// `main` returned this value
+    Done(Value),
+    /// The task is waiting; resume it once this happens
+    Suspended(Suspension),
+}
+
+/// Control flow out of the middle of a block.
+enum Interrupt {
+    Return(Value),
+    Error(EvalError),
+}
+
+impl From<EvalError> for Interrupt {
+    fn from(error: EvalError) -> Self {
+        Interrupt::Error(error)
+    }
+}
+
+type Flow<T> = Result<T, Interrupt>;
+
+/// Runs the program's `main` function with the given host and granted
+/// capabilities. A program without `main` does nothing and yields Unit.
+/// A program that suspends fails with `Suspended`, and one that calls
+/// foreign functions cannot find them; run those as a [`Task`].
+pub fn run_main(items: &[AstNode], host: &mut dyn Host, granted: &BTreeSet<Capability>) -> Result<Value, EvalError> {
+    let foreign = ForeignFunctions::new();
+    let mut interpreter = Interpreter::new(items, host, granted, &foreign);
+    match interpreter.run_task(&mut Continuation::default(), None)? {
+        Poll::Done(value) => Ok(value),
+        Poll::Suspended(suspension) => Err(EvalError::Suspended(suspension)),
+    }
+}
+
+/// A run of a program's `main` that can suspend at `await` and `sleep` and
+/// be resumed later. Each start or resume runs until the program finishes,
+/// fails, or suspends again, evaluating at most `MAX_STEPS` expressions.
+/// Once finished or failed, the task yields Unit.
+pub struct Task {
+    items: Arc<[AstNode]>,
+    granted: BTreeSet<Capability>,
+    foreign: ForeignFunctions,
+    continuation: Continuation,
+}
+
+impl Task {
+    pub fn new(items: impl Into<Arc<[AstNode]>>, granted: BTreeSet<Capability>) -> Self {
+        Task {
+            items: items.into(),
+            granted,
+            foreign: ForeignFunctions::new(),
+            continuation: Continuation::default(),
+        }
+    }
+
+    /// Makes the host's foreign functions callable from the program.
+    pub fn with_foreign(mut self, foreign: ForeignFunctions) -> Self {
+        self.foreign = foreign;
+        self
+    }
+
+    /// Runs `main` until it finishes or first suspends.
+    pub fn start(&mut self, host: &mut dyn Host) -> Result<Poll, EvalError> {
+        self.drive(host, None)
+    }
+
+    /// Continues a suspended task. A wakeup that does not fit what it waits
+    /// for, such as `Elapsed` while it awaits an event without a timeout,
+    /// leaves it suspended.
+    pub fn resume(&mut self, host: &mut dyn Host, wakeup: Wakeup) -> Result<Poll, EvalError> {
+        self.drive(host, Some(wakeup))
+    }
+
+    /// What the task is suspended on, if it is.
+    pub fn waiting_for(&self) -> Option<&Suspension> {
+        self.continuation.waiting.as_ref()
+    }
+
+    fn drive(&mut self, host: &mut dyn Host, wakeup: Option<Wakeup>) -> Result<Poll, EvalError> {
+        let mut interpreter = Interpreter::new(&self.items, host, &self.granted, &self.foreign);
+        let result = interpreter.run_task(&mut self.continuation, wakeup);
+        if result.is_err() {
+            self.continuation.frames.clear();
+            self.continuation.waiting = None;
+        }
+        result
+    }
+}
+
+/// Execution state kept across suspensions: the async calls in progress,
+/// innermost last, and what the innermost one awaits.
+#[derive(Default)]
+struct Continuation {
+    started: bool,
+    frames: Vec<Frame>,
+    waiting: Option<Suspension>,
+}
+
+impl Continuation {
+    /// Hands the result of the pending `await` to the innermost frame.
+    fn deliver(&mut self, value: Value) {
+        if let Some(frame) = self.frames.last_mut()
+            && let Some(name) = frame.binding.take()
+            && let Some(scope) = frame.scopes.last_mut()
+        {
+            scope.insert(name, value);
+        }
+    }
+
+    /// Pops the innermost frame, which returned `value`. Returns the
+    /// program's result once `main` itself has returned.
+    fn finish(&mut self, value: Value) -> Option<Value> {
+        self.frames.pop();
+        if self.frames.is_empty() {
+            return Some(value);
+        }
+        self.deliver(value);
+        None
+    }
+}
+
+/// A call whose body runs statement by statement, so that it can stop
+/// between statements and continue after a suspension.
+struct Frame {
+    function: String,
+    /// Index of the next statement of the body
+    next: usize,
+    scopes: Vec<HashMap<String, Value>>,
+    /// Value of the last statement run, which the body yields if it ends
+    last: Value,
+    /// Where the result of the pending `await` goes
+    binding: Option<String>,
+}
+
+/// What running one statement of a frame asks of the task.
+enum Step {
+    Continue(Value),
+    /// Awaits a call to an async function, run in a new frame
+    Call { frame: Frame, binding: Option<String> },
+    Suspend(Suspension, Option<String>),
+}
+
+#[derive(Clone, Copy)]
+struct Callable<'a> {
+    params: &'a [Param],
+    body: &'a Block,
+    is_async: bool,
+}
+
+struct Interpreter<'a> {
+    functions: HashMap<&'a str, Callable<'a>>,
+    /// Constructor names and how many fields each takes
+    constructors: HashMap<&'a str, usize>,
+    host: &'a mut dyn Host,
+    granted: &'a BTreeSet<Capability>,
+    foreign: &'a ForeignFunctions,
+    scopes: Vec<HashMap<String, Value>>,
+    depth: usize,
+    steps: usize,
+}
+
+impl<'a> Interpreter<'a> {
+    fn new(
+        items: &'a [AstNode],
+        host: &'a mut dyn Host,
+        granted: &'a BTreeSet<Capability>,
+        foreign: &'a ForeignFunctions,
+    ) -> Self {
+        let mut functions = HashMap::new();
+        let mut constructors = HashMap::new();
+        for item in prelude::items().iter().chain(items) {
+            match item {
+                AstNode::Function(f) => {
+                    let callable = Callable { params: &f.params, body: &f.body, is_async: f.is_async };
+                    functions.entry(f.name.as_str()).or_insert(callable);
+                }
+                AstNode::Rule(r) => {
+                    let callable = Callable { params: &r.params, body: &r.body, is_async: false };
+                    functions.entry(r.name.as_str()).or_insert(callable);
+                }
+                AstNode::Type(t) => {
+                    for variant in &t.variants {
+                        constructors.entry(variant.name.as_str()).or_insert(variant.fields.len());
+                    }
+                }
+                AstNode::Intent(_) | AstNode::Import(_) => {}
+            }
+        }
+        Interpreter {
+            functions,
+            constructors,
+            host,
+            granted,
+            foreign,
+            scopes: Vec::new(),
+            depth: 0,
+            steps: 0,
+        }
+    }
+
+    /// Runs a task's frames until `main` returns or a frame suspends.
+    /// `wakeup` is the reason the task is being resumed, if it was waiting.
+    fn run_task(&mut self, task: &mut Continuation, wakeup: Option<Wakeup>) -> Result<Poll, EvalError> {
+        if !task.started {
+            task.started = true;
+            if !self.functions.contains_key("main") {
+                return Ok(Poll::Done(Value::Unit));
+            }
+            task.frames.push(self.enter("main", Vec::new())?);
+        }
+        if let Some(waiting) = task.waiting.take() {
+            let value = match (&waiting, wakeup) {
+                (Suspension::Sleep(_), Some(Wakeup::Elapsed)) => Value::Unit,
+                (Suspension::Event { timeout: None, .. }, Some(Wakeup::Event(payload))) => Value::Str(payload),
+                (Suspension::Event { timeout: Some(_), .. }, Some(Wakeup::Event(payload))) => Value::Variant {
+                    name: prelude::FIRED.to_string(),
+                    fields: vec![Value::Str(payload)],
+                },
+                (Suspension::Event { timeout: Some(_), .. }, Some(Wakeup::TimedOut)) => Value::Variant {
+                    name: prelude::TIMED_OUT.to_string(),
+                    fields: Vec::new(),
+                },
+                _ => {
+                    task.waiting = Some(waiting.clone());
+                    return Ok(Poll::Suspended(waiting));
+                }
+            };
+            task.deliver(value);
+        }
+
+        loop {
+            let depth = task.frames.len();
+            let Some(frame) = task.frames.last_mut() else {
+                return Ok(Poll::Done(Value::Unit));
+            };
+            let Some(&callable) = self.functions.get(frame.function.as_str()) else {
+                return Err(EvalError::UndefinedFunction(frame.function.clone()));
+            };
+            let Some(statement) = callable.body.statements.get(frame.next) else {
+                let value = std::mem::replace(&mut frame.last, Value::Unit);
+                if let Some(value) = task.finish(value) {
+                    return Ok(Poll::Done(value));
+                }
+                continue;
+            };
+            frame.next += 1;
+
+            self.depth = depth;
+            self.scopes = std::mem::take(&mut frame.scopes);
+            let step = self.step(statement);
+            frame.scopes = std::mem::take(&mut self.scopes);
+            match step {
+                Ok(Step::Continue(value)) => frame.last = value,
+                Ok(Step::Call { frame: callee, binding }) => {
+                    frame.binding = binding;
+                    frame.last = Value::Unit;
+                    task.frames.push(callee);
+                }
+                Ok(Step::Suspend(suspension, binding)) => {
+                    frame.binding = binding;
+                    frame.last = Value::Unit;
+                    task.waiting = Some(suspension.clone());
+                    return Ok(Poll::Suspended(suspension));
+                }
+                Err(Interrupt::Return(value)) => {
+                    if let Some(value) = task.finish(value) {
+                        return Ok(Poll::Done(value));
+                    }
+                }
+                Err(Interrupt::Error(error)) => return Err(error),
+            }
+        }
+    }
+
+    /// Runs one statement of a frame's body; only here may `await` and
+    /// `sleep` suspend.
+    fn step(&mut self, statement: &Spanned<Statement>) -> Flow<Step> {
+        Ok(match &statement.node {
+            Statement::Await { binding, target: Awaitable::Event { name, timeout } } => {
+                let name = match self.expression(name)? {
+                    Value::Str(name) => name,
+                    found => {
+                        return Err(EvalError::WrongArgument {
+                            name: "event".to_string(),
+                            position: 1,
+                            expected: "String",
+                            found,
+                        }
+                        .into())
+                    }
+                };
+                let timeout = match timeout {
+                    Some(timeout) => Some(self.duration("timeout", timeout)?),
+                    None => None,
+                };
+                Step::Suspend(Suspension::Event { name, timeout }, binding.clone())
+            }
+            Statement::Await { binding, target: Awaitable::Call { callee, args } } => {
+                let values = self.arguments(args)?;
+                match self.functions.get(callee.as_str()) {
+                    Some(callable) if callable.is_async => {}
+                    Some(_) => return Err(EvalError::NotAsync(callee.clone()).into()),
+                    None => return Err(EvalError::UndefinedFunction(callee.clone()).into()),
+                }
+                Step::Call { frame: self.enter(callee, values)?, binding: binding.clone() }
+            }
+            Statement::Sleep(duration) => Step::Suspend(Suspension::Sleep(self.duration("sleep", duration)?), None),
+            _ => Step::Continue(self.statements(std::slice::from_ref(statement))?),
+        })
+    }
+
+    /// A duration given in milliseconds.
+    fn duration(&mut self, name: &str, expr: &Expression) -> Flow<Duration> {
+        match self.expression(expr)? {
+            Value::Int(ms) => u64::try_from(ms).map(Duration::from_millis).map_err(|_| {
+                EvalError::InvalidArgument {
+                    name: name.to_string(),
+                    reason: format!("{}ms is not a duration", ms),
+                }
+                .into()
+            }),
+            found => Err(EvalError::WrongArgument { name: name.to_string(), position: 1, expected: "Int", found }.into()),
+        }
+    }
+
+    /// A frame for an async call to `name`, about to run its body.
+    fn enter(&self, name: &str, args: Vec<Value>) -> Result<Frame, EvalError> {
+        let (_, params) = self.bind_params(name, args)?;
+        Ok(Frame {
+            function: name.to_string(),
+            next: 0,
+            scopes: vec![params, HashMap::new()],
+            last: Value::Unit,
+            binding: None,
+        })
+    }
+
+    /// Looks up a callable and binds its parameters to `args`.
+    fn bind_params(&self, name: &str, args: Vec<Value>) -> Result<(Callable<'a>, HashMap<String, Value>), EvalError> {
+        let Some(&callable) = self.functions.get(name) else {
+            return Err(EvalError::UndefinedFunction(name.to_string()));
+        };
+        if callable.params.len() != args.len() {
+            return Err(EvalError::Arity {
+                name: name.to_string(),
+                expected: callable.params.len(),
+                found: args.len(),
+            });
+        }
+        if self.depth >= MAX_CALL_DEPTH {
+            return Err(EvalError::RecursionLimit);
+        }
+        // A function sees only its own parameters, not its caller's locals
+        Ok((callable, callable.params.iter().map(|p| p.name.clone()).zip(args).collect()))
+    }
+
+    fn call_function(&mut self, name: &str, args: Vec<Value>) -> Result<Value, EvalError> {
+        let (callable, frame) = self.bind_params(name, args)?;
+        if callable.is_async {
+            return Err(EvalError::NotAwaited(name.to_string()));
+        }
+        let caller_scopes = std::mem::replace(&mut self.scopes, vec![frame]);
+        self.depth += 1;
+        let result = self.block(callable.body);
+        self.depth -= 1;
+        self.scopes = caller_scopes;
+
+        match result {
+            Ok(value) | Err(Interrupt::Return(value)) => Ok(value),
+            Err(Interrupt::Error(error)) => Err(error),
+        }
+    }
+
+    /// Evaluates a block in a new scope. Its value is that of its final
+    /// expression statement, or Unit.
+    fn block(&mut self, block: &Block) -> Flow<Value> {
+        self.scopes.push(HashMap::new());
+        let result = self.statements(&block.statements);
+        self.scopes.pop();
+        result
+    }
+
+    fn statements(&mut self, statements: &[Spanned<Statement>]) -> Flow<Value> {
+        let mut last = Value::Unit;
+        for statement in statements {
+            last = match &statement.node {
+                Statement::Expr(expr) => self.expression(expr)?,
+                Statement::LetBinding { name, expr } => {
+                    let value = match expr {
+                        Some(expr) => self.expression(expr)?,
+                        None => Value::Unit,
+                    };
+                    if let Some(scope) = self.scopes.last_mut() {
+                        scope.insert(name.clone(), value);
+                    }
+                    Value::Unit
+                }
+                Statement::Return(expr) => return Err(Interrupt::Return(self.expression(expr)?)),
+                Statement::Backtrack(block) => {
+                    let depth = self.scopes.len();
+                    match self.block(block) {
+                        Err(Interrupt::Error(_)) => {
+                            self.scopes.truncate(depth);
+                            Value::Unit
+                        }
+                        other => other?,
+                    }
+                }
+                Statement::Await { .. } => return Err(EvalError::MisplacedSuspension("await").into()),
+                Statement::Sleep(_) => return Err(EvalError::MisplacedSuspension("sleep").into()),
+            };
+        }
+        Ok(last)
+    }
+
+    fn expression(&mut self, expr: &Expression) -> Flow<Value> {
+        self.steps += 1;
+        if self.steps > MAX_STEPS {
+            return Err(EvalError::StepLimit.into());
+        }
+        Ok(match expr {
+            Expression::Identifier(name) => match self.scopes.iter().rev().find_map(|scope| scope.get(name)) {
+                Some(value) => value.clone(),
+                None => self.construct(name, Vec::new()).ok_or_else(|| EvalError::UndefinedVariable(name.clone()))??,
+            },
+            Expression::IntLiteral(n) => Value::Int(*n),
+            Expression::FloatLiteral(x) => Value::Float(*x),
+            Expression::StringLiteral(s) => Value::Str(s.clone()),
+            Expression::BoolLiteral(b) => Value::Bool(*b),
+            Expression::FunctionCall { callee, args } => {
+                let Expression::Identifier(name) = callee.as_ref() else {
+                    return Err(EvalError::UndefinedFunction(format!("{:?}", callee)).into());
+                };
+                let values = self.arguments(args)?;
+                if self.functions.contains_key(name.as_str()) {
+                    self.call_function(name, values)?
+                } else if self.constructors.contains_key(name.as_str()) {
+                    bounded(name, self.construct(name, values).expect("constructor exists")?)?
+                } else if let Some(builtin) = builtins::lookup(name) {
+                    self.check_call(builtin.shape(), &values)?;
+                    bounded(name, builtin.call(values, self.host)?)?
+                } else if let Some(function) = self.foreign.get(name) {
+                    self.check_call(function.shape(), &values)?;
+                    let value = function
+                        .call(&values)
+                        .map_err(|reason| EvalError::Foreign { name: name.clone(), reason })?;
+                    bounded(name, value)?
+                } else {
+                    return Err(EvalError::UndefinedFunction(name.clone()).into());
+                }
+            }
+            Expression::Block(block) => self.block(block)?,
+            Expression::Match { expr, arms } => {
+                let scrutinee = self.expression(expr)?;
+                for arm in arms {
+                    let mut bindings = HashMap::new();
+                    if self.matches(&arm.pattern, &scrutinee, &mut bindings)? {
+                        self.scopes.push(bindings);
+                        let result = self.expression(&arm.expr);
+                        self.scopes.pop();
+                        return result;
+                    }
+                }
+                return Err(EvalError::NoMatch(scrutinee).into());
+            }
+            // Until logic variables are solved, a symbol stands for its name
+            Expression::Symbolic(name) => Value::Str(name.clone()),
+            Expression::SelfModify { target, patch } => {
+                self.require("modify", &Capability::SelfModify)?;
+                let patch = self.expression(patch)?;
+                self.host.modify(target, &patch).map_err(EvalError::Host)?;
+                Value::Unit
+            }
+        })
+    }
+
+    fn arguments(&mut self, args: &[Expression]) -> Flow<Vec<Value>> {
+        let mut values = Vec::with_capacity(args.len());
+        for arg in args {
+            values.push(self.expression(arg)?);
+        }
+        Ok(values)
+    }
+
+    /// Builds a value of a sum type, or `None` if `name` is no constructor.
+    fn construct(&self, name: &str, fields: Vec<Value>) -> Option<Result<Value, EvalError>> {
+        let &arity = self.constructors.get(name)?;
+        Some(if arity == fields.len() {
+            Ok(Value::Variant { name: name.to_string(), fields })
+        } else {
+            Err(EvalError::Arity { name: name.to_string(), expected: arity, found: fields.len() })
+        })
+    }
+
+    fn matches(&mut self, pattern: &Pattern, value: &Value, bindings: &mut HashMap<String, Value>) -> Flow<bool> {
+        Ok(match (pattern, value) {
+            (Pattern::Wildcard, _) => true,
+            (Pattern::Identifier(name), value) if self.constructors.contains_key(name.as_str()) => {
+                matches!(value, Value::Variant { name: variant, fields } if variant == name && fields.is_empty())
+            }
+            (Pattern::Identifier(name), value) => {
+                bindings.insert(name.clone(), value.clone());
+                true
+            }
+            (Pattern::Literal(expr), value) => self.expression(expr)? == *value,
+            (Pattern::Tuple(patterns), Value::List(items)) => self.matches_all(patterns, items, bindings)?,
+            (Pattern::Constructor { name, args }, Value::Variant { name: variant, fields }) => {
+                name == variant && self.matches_all(args, fields, bindings)?
+            }
+            _ => false,
+        })
+    }
+
+    fn matches_all(&mut self, patterns: &[Pattern], values: &[Value], bindings: &mut HashMap<String, Value>) -> Flow<bool> {
+        if patterns.len() != values.len() {
+            return Ok(false);
+        }
+        for (pattern, value) in patterns.iter().zip(values) {
+            if !self.matches(pattern, value, bindings)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn require(&self, name: &str, capability: &Capability) -> Result<(), EvalError> {
+        if self.granted.contains(capability) {
+            Ok(())
+        } else {
+            Err(EvalError::CapabilityDenied {
+                name: name.to_string(),
+                capability: capability.clone(),
+            })
+        }
+    }
+
+    /// Checks a builtin call's capability and arguments before it is made.
+    fn check_call(&self, builtin: CallShape, args: &[Value]) -> Result<(), EvalError> {
+        if let Some(capability) = builtin.capability {
+            self.require(builtin.name, capability)?;
+        }
+        let Some(params) = builtin.params else {
+            return Ok(());
+        };
+        if params.len() != args.len() {
+            return Err(EvalError::Arity {
+                name: builtin.name.to_string(),
+                expected: params.len(),
+                found: args.len(),
+            });
+        }
+        for (position, (kind, arg)) in params.iter().zip(args).enumerate() {
+            if !kind.accepts(arg) {
+                return Err(EvalError::WrongArgument {
+                    name: builtin.name.to_string(),
+                    position: position + 1,
+                    expected: kind.name(),
+                    found: arg.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `value`, unless it is larger than `MAX_VALUE_BYTES`.
+pub(crate) fn bounded(name: &str, value: Value) -> Result<Value, EvalError> {
+    match value.footprint() {
+        size if size > MAX_VALUE_BYTES => Err(EvalError::TooLarge { name: name.to_string(), size }),
+        _ => Ok(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_source;
+
+    #[derive(Default)]
+    struct MockHost {
+        notes: Vec<String>,
+        intents: Vec<(String, u32)>,
+    }
+
+    impl Host for MockHost {
+        fn query_knowledge(&mut self, predicate: &str) -> Vec<String> {
+            match predicate {
+                "likes" => vec!["tea".into(), "rain".into()],
+                _ => Vec::new(),
+            }
+        }
+        fn note(&mut self, text: &str) {
+            self.notes.push(text.to_string());
+        }
+        fn create_intent(&mut self, description: &str, priority: u32) -> u64 {
+            self.intents.push((description.to_string(), priority));
+            self.intents.len() as u64
+        }
+        fn emotion(&mut self, name: &str) -> Option<f64> {
+            (name == "joy").then_some(0.5)
+        }
+    }
+
+    fn run(source: &str, granted: &[Capability]) -> (Result<Value, EvalError>, MockHost) {
+        let (items, diagnostics) = parse_source(source);
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        let mut host = MockHost::default();
+        let granted: BTreeSet<Capability> = granted.iter().cloned().collect();
+        (run_main(&items, &mut host, &granted), host)
+    }
+
+    #[test]
+    fn test_builtins_reach_the_host_only_when_granted() {
+        let source = "\
+fn describe(things: List) -> String { return str.concat(\"likes: \", list.join(things, \", \")); }
+fn main() {
+    let liked = kb.query(\"likes\");
+    memory.note(describe(liked));
+    backtrack { let broken = math.div(1, 0); memory.note(\"unreachable\"); }
+    let id = intent.create(\"make tea\", math.add(2, 3));
+    return match emotion.get(\"joy\") { 0.5 => id; _ => 0; };
+}";
+        let all = [Capability::Knowledge, Capability::Memory, Capability::Intent, Capability::Emotion];
+        let (result, host) = run(source, &all);
+        assert_eq!(result, Ok(Value::Int(1)));
+        assert_eq!(host.notes, vec!["likes: tea, rain"]);
+        assert_eq!(host.intents, vec![("make tea".to_string(), 5)]);
+
+        let (denied, host) = run(source, &[Capability::Knowledge]);
+        assert_eq!(
+            denied,
+            Err(EvalError::CapabilityDenied {
+                name: "memory.note".into(),
+                capability: Capability::Memory
+            })
+        );
+        assert!(host.notes.is_empty());
+
+        let (runaway, _) = run("fn f(n: Int<64>) { return f(n); } fn main() { return f(1); }", &[]);
+        assert_eq!(runaway, Err(EvalError::RecursionLimit));
+
+        // Doubling a string or a list stops at the size limit instead of exhausting memory
+        let doubling = "\
+fn grow(s: String, n: Int<64>) -> String { return match n { 0 => s; _ => grow(str.concat(s, s), math.sub(n, 1)); }; }
+fn main() { return grow(\"ab\", 60); }";
+        let (grown, _) = run(doubling, &[]);
+        assert!(matches!(grown, Err(EvalError::TooLarge { ref name, .. }) if name == "str.concat"), "{:?}", grown);
+        let nesting = "\
+fn nest(l: List, n: Int<64>) -> List { return match n { 0 => l; _ => nest(list.push(l, l), math.sub(n, 1)); }; }
+fn main() { return nest(list.push(list.new(), \"item\"), 60); }";
+        let (nested, _) = run(nesting, &[]);
+        assert!(matches!(nested, Err(EvalError::TooLarge { ref name, .. }) if name == "list.push"), "{:?}", nested);
+    }
+
+    #[test]
+    fn test_match_destructures_sum_types() {
+        let source = "\
+type Reply = Ack | Value(Int<64>) | Pair(Reply, Reply);
+fn total(r: Reply) -> Int<64> { return match r { Ack => 0; Value(n) => n; Pair(a, b) => math.add(total(a), total(b)); }; }
+fn main() { let r = Pair(Value(2), Pair(Ack, Value(3))); return list.push(list.push(list.new(), total(r)), r); }";
+        let (result, _) = run(source, &[]);
+        let Ok(Value::List(values)) = result else { panic!("{:?}", result) };
+        assert_eq!(values[0], Value::Int(5));
+        assert_eq!(values[1].to_string(), "Pair(Value(2), Pair(Ack, Value(3)))");
+    }
+
+    #[test]
+    fn test_tasks_suspend_at_await_and_sleep() {
+        let source = "\
+async fn knock(who: String) -> String { sleep 250; let reply = await event(\"door\"); return str.concat(who, reply); }
+async fn main() {
+    let first = await knock(\"A\");
+    memory.note(first);
+    let second = await event(\"door\") timeout 1000;
+    return match second { Fired(who) => who; TimedOut => \"nobody\"; };
+}";
+        let (items, diagnostics) = parse_source(source);
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        let mut host = MockHost::default();
+        let mut task = Task::new(items.clone(), BTreeSet::from([Capability::Memory]));
+
+        assert_eq!(task.start(&mut host), Ok(Poll::Suspended(Suspension::Sleep(Duration::from_millis(250)))));
+        // The wrong wakeup leaves the task where it was
+        let door = Suspension::Event { name: "door".into(), timeout: None };
+        assert_eq!(task.resume(&mut host, Wakeup::TimedOut), Ok(Poll::Suspended(Suspension::Sleep(Duration::from_millis(250)))));
+        assert_eq!(task.resume(&mut host, Wakeup::Elapsed), Ok(Poll::Suspended(door.clone())));
+        assert_eq!(task.waiting_for(), Some(&door));
+        let timed = Suspension::Event { name: "door".into(), timeout: Some(Duration::from_millis(1000)) };
+        assert_eq!(task.resume(&mut host, Wakeup::Event("B".into())), Ok(Poll::Suspended(timed)));
+        assert_eq!(host.notes, vec!["AB"]);
+        assert_eq!(task.resume(&mut host, Wakeup::TimedOut), Ok(Poll::Done(Value::Str("nobody".into()))));
+        assert_eq!(task.waiting_for(), None);
+
+        // run_main cannot wait
+        let (result, _) = run(source, &[Capability::Memory]);
+        assert_eq!(result, Err(EvalError::Suspended(Suspension::Sleep(Duration::from_millis(250)))));
+    }
+}
@@ -5,9 +5,14 @@
 //  Description:
 //      Lexer implementation for Astra language.
 //      Converts source code string into a stream of tokens with spans.
+//      Malformed input (stray characters, unterminated strings, literals
+//      out of range) is reported as diagnostics and lexing carries on, so
+//      any input yields a token stream ending in Eof.
+//      Dotted names such as `kb.query` lex as a single identifier.
 //
 //  Author:      Alex Roussinov
 //  Created:     2025-12-22
+//  Updated:     2026-10-16
 //  Copyright (c) 2025 Alex Roussinov
 //
 //  License:
@@ -15,6 +20,7 @@
 //      See LICENSE-MIT and LICENSE-APACHE at the repository root for details.
 // =============================================================================
 
+use crate::errors::Diagnostic;
 use crate::tokens::{Token, TokenKind, Span};
 
 pub struct Lexer<'a> {
@@ -23,6 +29,22 @@ pub struct Lexer<'a> {
     peeked: Option<(usize, char)>,
     line: usize,
     column: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Lexes all of `source`, returning the tokens (always ending in Eof) and
+/// the diagnostics raised on the way.
+pub fn tokenize(source: &str) -> (Vec<Token>, Vec<Diagnostic>) {
+    let mut lexer = Lexer::new(source);
+    let mut tokens = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        let done = token.kind == TokenKind::Eof;
+        tokens.push(token);
+        if done {
+            break;
+        }
+    }
+    (tokens, lexer.diagnostics)
 }
 
 impl<'a> Lexer<'a> {
@@ -35,9 +57,15 @@ impl<'a> Lexer<'a> {
             peeked,
             line: 1,
             column: 0,
+            diagnostics: Vec::new(),
         }
     }
 
+    /// Diagnostics raised so far.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
     fn bump(&mut self) -> Option<(usize, char)> {
         let current = self.peeked;
         self.peeked = self.chars.next();
@@ -57,48 +85,99 @@ impl<'a> Lexer<'a> {
         self.peeked.map(|(_, ch)| ch)
     }
 
+    fn peek_second(&self) -> Option<char> {
+        self.chars.clone().next().map(|(_, ch)| ch)
+    }
+
+    fn offset(&self) -> usize {
+        self.peeked.map(|(i, _)| i).unwrap_or(self.input.len())
+    }
+
+    /// Span from `start` to the current position, placed at `line`/`column`.
+    fn span_from(&self, start: usize, line: usize, column: usize) -> Span {
+        Span::with_pos(start, self.offset(), line, column + 1)
+    }
+
     pub fn next_token(&mut self) -> Option<Token> {
-        self.skip_whitespace();
+        loop {
+            self.skip_whitespace_and_comments();
 
-        let (start_idx, ch) = match self.peeked {
-            Some(c) => c,
-            None => {
-                return Some(Token::new(
-                    TokenKind::Eof,
-                    Span::new(self.input.len(), self.input.len()),
-                ))
+            let (start_idx, ch) = match self.peeked {
+                Some(c) => c,
+                None => {
+                    let end = self.input.len();
+                    return Some(Token::new(TokenKind::Eof, Span::with_pos(end, end, self.line, self.column + 1)));
+                }
+            };
+            let (line, column) = (self.line, self.column);
+
+            // String literal
+            if ch == '"' {
+                return Some(self.lex_string());
             }
-        };
 
-        // Single-char tokens
-        if ch == '{' {
-            self.bump();
-            return Some(Token::new(TokenKind::LBrace, Span::new(start_idx, start_idx + 1)));
-        }
-        if ch == '}' {
-            self.bump();
-            return Some(Token::new(TokenKind::RBrace, Span::new(start_idx, start_idx + 1)));
-        }
+            // Number literal
+            if ch.is_ascii_digit() {
+                if let Some(token) = self.lex_number() {
+                    return Some(token);
+                }
+                continue;
+            }
 
-        // String literal
-        if ch == '"' {
-            return Some(self.lex_string());
-        }
+            // Identifier, keyword, or annotation
+            if ch.is_alphabetic() || ch == '_' || ch == '@' {
+                if let Some(token) = self.lex_identifier_or_keyword() {
+                    return Some(token);
+                }
+                continue;
+            }
 
-        // Identifier or keyword
-        if ch.is_alphabetic() || ch == '_' {
-            return Some(self.lex_identifier_or_keyword());
+            // Punctuation
+            self.bump();
+            let kind = match ch {
+                '{' => Some(TokenKind::LBrace),
+                '}' => Some(TokenKind::RBrace),
+                '(' => Some(TokenKind::LParen),
+                ')' => Some(TokenKind::RParen),
+                '[' => Some(TokenKind::LBracket),
+                ']' => Some(TokenKind::RBracket),
+                ',' => Some(TokenKind::Comma),
+                ':' => Some(TokenKind::Colon),
+                ';' => Some(TokenKind::Semicolon),
+                '|' => Some(TokenKind::Pipe),
+                '<' => Some(TokenKind::LessThan),
+                '>' => Some(TokenKind::GreaterThan),
+                '=' if self.peek() == Some('>') => {
+                    self.bump();
+                    Some(TokenKind::ArrowFat)
+                }
+                '=' => Some(TokenKind::Equal),
+                '-' if self.peek() == Some('>') => {
+                    self.bump();
+                    Some(TokenKind::Arrow)
+                }
+                _ => None,
+            };
+            let span = self.span_from(start_idx, line, column);
+            match kind {
+                Some(kind) => return Some(Token::new(kind, span)),
+                // Unknown char: report it, skip it and try the next one
+                None => self.diagnostics.push(Diagnostic::new(format!("unexpected character '{}'", ch), span)),
+            }
         }
-
-        // Unknown char, skip and try next
-        self.bump();
-        self.next_token()
     }
 
-    fn skip_whitespace(&mut self) {
+    fn skip_whitespace_and_comments(&mut self) {
         while let Some(ch) = self.peek() {
             if ch.is_whitespace() {
                 self.bump();
+            } else if ch == '/' && self.peek_second() == Some('/') {
+                while let Some(ch) = self.peek() {
+                    if ch == '\n' {
+                        break;
+                    }
+                    self.bump();
+                }
             } else {
                 break;
             }
@@ -106,29 +185,84 @@ impl<'a> Lexer<'a> {
     }
 
     fn lex_string(&mut self) -> Token {
+        let (line, column) = (self.line, self.column);
         let (start_idx, _) = self.bump().unwrap(); // consume opening quote
         let mut string_content = String::new();
+        let mut terminated = false;
 
-        while let Some((_, ch)) = self.peeked {
-            if ch == '"' {
-                self.bump(); // consume closing quote
-                break;
-            } else {
-                string_content.push(ch);
-                self.bump();
+        while let Some((_, ch)) = self.bump() {
+            match ch {
+                '"' => {
+                    terminated = true;
+                    break;
+                }
+                '\\' => {
+                    if let Some((_, escaped)) = self.bump() {
+                        string_content.push(match escaped {
+                            'n' => '\n',
+                            't' => '\t',
+                            other => other,
+                        });
+                    }
+                }
+                _ => string_content.push(ch),
             }
         }
 
-        let span = Span::new(start_idx, self.peeked.map(|(i, _)| i).unwrap_or(self.input.len()));
+        let span = self.span_from(start_idx, line, column);
+        if !terminated {
+            self.diagnostics.push(Diagnostic::new("unterminated string literal", span.clone()));
+        }
         Token::new(TokenKind::StringLiteral(string_content), span)
     }
 
-    fn lex_identifier_or_keyword(&mut self) -> Token {
-        let (start_idx, _) = self.peeked.unwrap();
+    fn lex_number(&mut self) -> Option<Token> {
+        let (line, column) = (self.line, self.column);
+        let start_idx = self.offset();
+        let mut is_float = false;
+
+        while let Some(ch) = self.peek() {
+            if ch.is_ascii_digit() {
+                self.bump();
+            } else if ch == '.' && !is_float && self.peek_second().is_some_and(|c| c.is_ascii_digit()) {
+                is_float = true;
+                self.bump();
+            } else {
+                break;
+            }
+        }
+
+        let text = &self.input[start_idx..self.offset()];
+        let span = self.span_from(start_idx, line, column);
+        let kind = if is_float {
+            text.parse().ok().map(TokenKind::FloatLiteral)
+        } else {
+            text.parse().ok().map(TokenKind::IntLiteral)
+        };
+        match kind {
+            Some(kind) => Some(Token::new(kind, span)),
+            None => {
+                self.diagnostics.push(Diagnostic::new(format!("number literal '{}' is out of range", text), span));
+                None
+            }
+        }
+    }
+
+    fn lex_identifier_or_keyword(&mut self) -> Option<Token> {
+        let (line, column) = (self.line, self.column);
+        let (start_idx, first) = self.peeked.unwrap();
         let mut ident = String::new();
 
+        if first == '@' {
+            ident.push(first);
+            self.bump();
+        }
         while let Some(ch) = self.peek() {
-            if ch.is_alphanumeric() || ch == '_' {
+            let dotted = ch == '.'
+                && !ident.is_empty()
+                && ident != "@"
+                && self.peek_second().is_some_and(|c| c.is_alphabetic() || c == '_');
+            if ch.is_alphanumeric() || ch == '_' || dotted {
                 ident.push(ch);
                 self.bump();
             } else {
@@ -136,14 +270,37 @@ impl<'a> Lexer<'a> {
             }
         }
 
+        let span = self.span_from(start_idx, line, column);
         let kind = match ident.as_str() {
+            "@" => {
+                self.diagnostics.push(Diagnostic::new("expected an annotation name after '@'", span));
+                return None;
+            }
             "intent" => TokenKind::Intent,
             "motive" => TokenKind::Motive,
             "action" => TokenKind::Action,
+            "true" => TokenKind::BoolLiteral(true),
+            "false" => TokenKind::BoolLiteral(false),
+            "_" => TokenKind::Underscore,
             _ => TokenKind::Identifier(ident),
         };
 
-        let span = Span::new(start_idx, self.peeked.map(|(i, _)| i).unwrap_or(self.input.len()));
-        Token::new(kind, span)
+        Some(Token::new(kind, span))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_malformed_input_yields_positioned_diagnostics() {
+        let (tokens, diagnostics) = tokenize("fn f() {\n  let x = 3 $ 99999999999999999999;\n  \"open");
+        assert_eq!(tokens.last().map(|t| &t.kind), Some(&TokenKind::Eof));
+        assert!(tokens.iter().any(|t| t.kind == TokenKind::IntLiteral(3)));
+
+        let positions: Vec<(usize, usize)> = diagnostics.iter().map(|d| (d.line(), d.column())).collect();
+        assert_eq!(positions, vec![(2, 13), (2, 15), (3, 3)]);
+        assert!(diagnostics[2].message.contains("unterminated"));
     }
 }
@@ -1,17 +1,54 @@
-
 //! Astra Language Core Crate
 //!
 //! Implements Astra language parsing and execution.
+//!
+//! Parsing never panics: malformed source yields [`errors::Diagnostic`]s
+//! with line and column positions alongside whatever parsed cleanly.
+//! [`analyze`] adds semantic checks and reports the capabilities a program
+//! needs before it is executed, and [`interpreter::run_main`] runs it
+//! against a [`Host`] with the capabilities the host grants. Programs that
+//! `import` other modules are loaded with [`modules::analyze_program`].
+//! Programs with `async` functions run as a [`Task`], which suspends at
+//! `await` and `sleep` and is resumed by its host. Embedders add builtins
+//! of their own with [`ForeignFunctions`].
+
+pub mod analysis;
+pub mod ast;
+pub mod builtins;
+pub mod capabilities;
+pub mod errors;
+pub mod ffi;
+pub mod interpreter;
+pub mod lexer;
+pub mod modules;
+pub mod parser;
+pub mod prelude;
+pub mod tokens;
+pub mod types;
+
+pub use analysis::Diagnostics;
+pub use capabilities::Capability;
+pub use errors::{Diagnostic, Severity};
+pub use ffi::{Args, FfiError, ForeignError, ForeignFunctions, FromValue};
+pub use interpreter::{EvalError, Host, Poll, Suspension, Task, Value, Wakeup};
+pub use modules::{FileResolver, MemoryResolver, ModuleResolver, UnitStore};
 
 /// Represents a parsed Astra program.
 pub struct Program {
     pub source: String,
+    /// Declarations that parsed
+    pub items: Vec<ast::AstNode>,
+    /// Problems found while lexing and parsing
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 /// Parses Astra source code into a program.
 pub fn parse_program(source: &str) -> Program {
+    let (items, diagnostics) = parser::parse_source(source);
     Program {
         source: source.to_string(),
+        items,
+        diagnostics,
     }
 }
 
@@ -20,7 +57,9 @@ pub fn execute_program(program: &Program) -> String {
     format!("Executed program: {}", program.source)
 }
 
-/// Validates syntax of Astra source code.
-pub fn validate_syntax(source: &str) -> bool {
-    !source.trim().is_empty()
+/// Parses and analyzes Astra source code: syntax errors, undefined names,
+/// unreachable code, basic type mismatches, and required capabilities.
+/// A program with no error diagnostics may be executed.
+pub fn analyze(source: &str) -> Diagnostics {
+    analysis::analyze_source(source).1
 }
@@ -32,6 +32,7 @@
 //
 //  Author:      Alex Roussinov
 //  Created:     2025-12-22
+//  Updated:     2026-10-16
 //  Copyright (c) 2025 Alex Roussinov
 //
 //  License:
@@ -39,12 +40,10 @@
 //      See LICENSE-MIT and LICENSE-APACHE at the repository root for details.
 // =============================================================================
 
-mod tokens;
-mod lexer;
 mod dataset;
 
-use lexer::Lexer;
-use tokens::TokenKind;
+use astra_lang::lexer::Lexer;
+use astra_lang::tokens::TokenKind;
 use dataset::IntentDataset;
 
 fn main() {
@@ -71,6 +70,9 @@ fn main() {
     for token in &tokens {
         println!("{:?} at {:?}", token.kind, token.span);
     }
+    for diagnostic in lexer.diagnostics() {
+        println!("warning: {}", diagnostic);
+    }
 
     // Part 2: Dataset example
     let mut dataset = IntentDataset::new();
@@ -0,0 +1,515 @@
+// =============================================================================
+// Astra Reference Compiler (ARC)
+// File: modules.rs
+//
+// Description:
+//     Splits programs across files. `import utils.strings;` asks a
+//     `ModuleResolver` for the module's source, and the module's functions and
+//     rules join the program under qualified names (`utils.strings.trim`).
+//     Types and constructors are qualified the same way, and references
+//     inside a module to its own declarations are rewritten to match, so two
+//     modules can each define `helper` without clashing.
+//     Modules are loaded depth-first. A module that imports one of the modules
+//     currently being loaded is reported as a cyclic import. Each module is
+//     analyzed against what it imports, and its problems are reported at the
+//     `import` that brought it in, prefixed with the module's name.
+//     Parsed sources are kept in a `UnitStore`, found by a hash of their
+//     content and checked against the text itself, so unchanged modules
+//     are not parsed again and colliding ones are never confused.
+//
+// Intent:
+//     - Let programs grow beyond one file without a global namespace.
+//     - Report every import problem as a positioned diagnostic.
+//
+// Author: Alex Roussinov
+// Created: 2026-10-16
+// Updated: 2026-10-16
+// =============================================================================
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use crate::analysis::{self, Diagnostics};
+use crate::ast::*;
+use crate::errors::Diagnostic;
+use crate::ffi::ForeignFunctions;
+use crate::types::Type;
+
+/// Extension of Astra source files.
+pub const SOURCE_EXTENSION: &str = "astra";
+
+/// Why a module could not be loaded.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ModuleError {
+    #[error("module '{0}' not found")]
+    NotFound(String),
+    #[error("could not read module '{module}': {reason}")]
+    Unreadable { module: String, reason: String },
+    #[error("cyclic import: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+}
+
+/// Finds the source of a module by its dotted path.
+pub trait ModuleResolver {
+    fn resolve(&self, path: &str) -> Result<String, ModuleError>;
+}
+
+/// Resolves no modules; every import fails.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoModules;
+
+impl ModuleResolver for NoModules {
+    fn resolve(&self, path: &str) -> Result<String, ModuleError> {
+        Err(ModuleError::NotFound(path.to_string()))
+    }
+}
+
+/// Resolves `a.b.c` to `<root>/a/b/c.astra`.
+#[derive(Debug, Clone)]
+pub struct FileResolver {
+    root: PathBuf,
+}
+
+impl FileResolver {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        FileResolver { root: root.into() }
+    }
+
+    /// The file a module path maps to.
+    pub fn path_of(&self, module: &str) -> PathBuf {
+        let mut path = self.root.clone();
+        path.extend(module.split('.'));
+        path.set_extension(SOURCE_EXTENSION);
+        path
+    }
+}
+
+impl ModuleResolver for FileResolver {
+    fn resolve(&self, path: &str) -> Result<String, ModuleError> {
+        // Segments are identifiers, so a path cannot climb out of the root
+        if path.split('.').any(|segment| segment.is_empty() || !segment.chars().all(|c| c.is_alphanumeric() || c == '_')) {
+            return Err(ModuleError::NotFound(path.to_string()));
+        }
+        std::fs::read_to_string(self.path_of(path)).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => ModuleError::NotFound(path.to_string()),
+            _ => ModuleError::Unreadable { module: path.to_string(), reason: e.to_string() },
+        })
+    }
+}
+
+/// Modules held in memory, for embedders and tests.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryResolver {
+    modules: HashMap<String, String>,
+}
+
+impl MemoryResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, path: impl Into<String>, source: impl Into<String>) {
+        self.modules.insert(path.into(), source.into());
+    }
+}
+
+impl ModuleResolver for MemoryResolver {
+    fn resolve(&self, path: &str) -> Result<String, ModuleError> {
+        self.modules.get(path).cloned().ok_or_else(|| ModuleError::NotFound(path.to_string()))
+    }
+}
+
+/// Hash identifying a source text.
+pub type ContentHash = u64;
+
+pub fn content_hash(source: &str) -> ContentHash {
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// One parsed source file, before its names are qualified.
+#[derive(Debug)]
+pub struct CompilationUnit {
+    pub hash: ContentHash,
+    /// The text parsed, compared on every cache hit
+    pub source: String,
+    pub items: Vec<AstNode>,
+    /// Lexing and parsing problems
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl CompilationUnit {
+    pub fn imports(&self) -> impl Iterator<Item = &ImportDecl> {
+        self.items.iter().filter_map(|item| match item {
+            AstNode::Import(import) => Some(import),
+            _ => None,
+        })
+    }
+}
+
+/// Parsed sources, found by content hash and checked against the text
+/// itself, so sources whose hashes collide each get their own unit.
+#[derive(Debug, Default)]
+pub struct UnitStore {
+    units: HashMap<ContentHash, Vec<Arc<CompilationUnit>>>,
+}
+
+impl UnitStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The unit for `source`, parsing it only if this content is new.
+    pub fn compile(&mut self, source: &str) -> Arc<CompilationUnit> {
+        let hash = content_hash(source);
+        let bucket = self.units.entry(hash).or_default();
+        if let Some(unit) = bucket.iter().find(|unit| unit.source == source) {
+            return unit.clone();
+        }
+        let (items, diagnostics) = crate::parser::parse_source(source);
+        let unit = Arc::new(CompilationUnit { hash, source: source.to_string(), items, diagnostics });
+        bucket.push(unit.clone());
+        unit
+    }
+
+    /// The unit parsed from `source`, if it is cached.
+    pub fn get(&self, source: &str) -> Option<Arc<CompilationUnit>> {
+        self.units.get(&content_hash(source))?.iter().find(|unit| unit.source == source).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.units.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.units.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.units.clear();
+    }
+}
+
+/// Parses `source`, loads the modules it imports, directly or not, and
+/// analyzes the result. Returns the program's own declarations followed by
+/// the qualified declarations of every module loaded, ready to execute.
+/// The program and its modules may call the `foreign` functions.
+pub fn analyze_program(
+    source: &str,
+    resolver: &dyn ModuleResolver,
+    store: &mut UnitStore,
+    foreign: &ForeignFunctions,
+) -> (Vec<AstNode>, Diagnostics) {
+    let unit = store.compile(source);
+    let mut loader = Loader { resolver, store, foreign, modules: BTreeMap::new(), stack: Vec::new() };
+    let (imports, mut diagnostics) = loader.load_imports(&unit);
+    let imported = loader.items_of(&imports);
+
+    let mut result = analysis::analyze_with_imports(&unit.items, &imported, foreign);
+    result.diagnostics.extend(unit.diagnostics.iter().cloned());
+    result.diagnostics.append(&mut diagnostics);
+    result.diagnostics.sort_by_key(|d| d.span.start);
+
+    let mut items = unit.items.clone();
+    items.extend(loader.modules.into_values().flat_map(|module| module.items));
+    (items, result)
+}
+
+/// A loaded module: its qualified declarations and what it imports.
+struct Module {
+    items: Vec<AstNode>,
+    imports: Vec<String>,
+}
+
+struct Loader<'a> {
+    resolver: &'a dyn ModuleResolver,
+    store: &'a mut UnitStore,
+    foreign: &'a ForeignFunctions,
+    modules: BTreeMap<String, Module>,
+    /// Modules being loaded, outermost first
+    stack: Vec<String>,
+}
+
+impl Loader<'_> {
+    /// Loads everything `unit` imports. Returns the modules that loaded and
+    /// a diagnostic, at the import, for each that did not or had problems.
+    fn load_imports(&mut self, unit: &CompilationUnit) -> (Vec<String>, Vec<Diagnostic>) {
+        let mut loaded = Vec::new();
+        let mut diagnostics = Vec::new();
+        for import in unit.imports() {
+            match self.load(&import.path) {
+                Ok(problems) => {
+                    diagnostics.extend(problems.into_iter().map(|problem| Diagnostic {
+                        message: format!("in module '{}': {}", import.path, problem),
+                        span: import.span.clone(),
+                        severity: problem.severity,
+                    }));
+                    loaded.push(import.path.clone());
+                }
+                Err(error) => diagnostics.push(Diagnostic::new(error.to_string(), import.span.clone())),
+            }
+        }
+        (loaded, diagnostics)
+    }
+
+    /// Loads a module unless it already is, returning the problems found
+    /// in it, positioned within the module.
+    fn load(&mut self, path: &str) -> Result<Vec<Diagnostic>, ModuleError> {
+        if let Some(start) = self.stack.iter().position(|m| m == path) {
+            let mut cycle = self.stack[start..].to_vec();
+            cycle.push(path.to_string());
+            return Err(ModuleError::Cycle(cycle));
+        }
+        if self.modules.contains_key(path) {
+            return Ok(Vec::new());
+        }
+        let source = self.resolver.resolve(path)?;
+        let unit = self.store.compile(&source);
+
+        self.stack.push(path.to_string());
+        let (imports, mut diagnostics) = self.load_imports(&unit);
+        self.stack.pop();
+
+        let mut items = unit.items.clone();
+        qualify(&mut items, path);
+        let imported = self.items_of(&imports);
+        let analysis = analysis::analyze_with_imports(&items, &imported, self.foreign);
+
+        diagnostics.extend(unit.diagnostics.iter().cloned());
+        diagnostics.extend(analysis.diagnostics);
+        diagnostics.sort_by_key(|d| d.span.start);
+        self.modules.insert(path.to_string(), Module { items, imports });
+        Ok(diagnostics)
+    }
+
+    /// Declarations of the given modules and everything they import.
+    fn items_of(&self, roots: &[String]) -> Vec<AstNode> {
+        let mut seen = HashSet::new();
+        let mut pending: Vec<&str> = roots.iter().map(String::as_str).collect();
+        let mut items = Vec::new();
+        while let Some(path) = pending.pop() {
+            if !seen.insert(path) {
+                continue;
+            }
+            if let Some(module) = self.modules.get(path) {
+                items.extend(module.items.iter().cloned());
+                pending.extend(module.imports.iter().map(String::as_str));
+            }
+        }
+        items
+    }
+}
+
+/// Renames a module's functions, rules, intents, types, and constructors to
+/// `module.name`, and its references to them to match.
+fn qualify(items: &mut [AstNode], module: &str) {
+    let mut qualifier = Qualifier { module, callables: HashSet::new(), constructors: HashSet::new(), types: HashSet::new() };
+    for item in items.iter() {
+        match item {
+            AstNode::Function(f) => {
+                qualifier.callables.insert(f.name.clone());
+            }
+            AstNode::Rule(r) => {
+                qualifier.callables.insert(r.name.clone());
+            }
+            AstNode::Type(t) => {
+                qualifier.types.insert(t.name.clone());
+                qualifier.constructors.extend(t.variants.iter().map(|v| v.name.clone()));
+            }
+            AstNode::Intent(_) | AstNode::Import(_) => {}
+        }
+    }
+    qualifier.callables.extend(qualifier.constructors.iter().cloned());
+
+    for item in items {
+        match item {
+            AstNode::Function(f) => {
+                f.name = qualifier.name(&f.name);
+                for param in &mut f.params {
+                    param.ty.iter_mut().for_each(|ty| qualifier.ty(ty));
+                }
+                f.ret_type.iter_mut().for_each(|ty| qualifier.ty(ty));
+                qualifier.block(&mut f.body);
+            }
+            AstNode::Rule(r) => {
+                r.name = qualifier.name(&r.name);
+                for param in &mut r.params {
+                    param.ty.iter_mut().for_each(|ty| qualifier.ty(ty));
+                }
+                r.ret_type.iter_mut().for_each(|ty| qualifier.ty(ty));
+                qualifier.block(&mut r.body);
+            }
+            AstNode::Type(t) => {
+                t.name = qualifier.name(&t.name);
+                for variant in &mut t.variants {
+                    variant.name = qualifier.name(&variant.name);
+                    variant.fields.iter_mut().for_each(|ty| qualifier.ty(ty));
+                }
+            }
+            AstNode::Intent(i) => i.name = qualifier.name(&i.name),
+            AstNode::Import(_) => {}
+        }
+    }
+}
+
+/// The names a module declares, and how to qualify references to them.
+struct Qualifier<'m> {
+    module: &'m str,
+    /// Functions, rules, and constructors
+    callables: HashSet<String>,
+    constructors: HashSet<String>,
+    types: HashSet<String>,
+}
+
+impl Qualifier<'_> {
+    fn name(&self, name: &str) -> String {
+        format!("{}.{}", self.module, name)
+    }
+
+    fn rename_if(&self, names: &HashSet<String>, name: &mut String) {
+        if names.contains(name.as_str()) {
+            *name = self.name(name);
+        }
+    }
+
+    fn ty(&self, ty: &mut Type) {
+        match ty {
+            Type::Simple(name) => self.rename_if(&self.types, name),
+            Type::Mut(inner) | Type::Ref(inner) | Type::Cap(inner) | Type::Grad(inner) => self.ty(inner),
+            Type::DepType { var_type, .. } => self.ty(var_type),
+            _ => {}
+        }
+    }
+
+    fn block(&self, block: &mut Block) {
+        for statement in &mut block.statements {
+            match &mut statement.node {
+                Statement::Expr(expr)
+                | Statement::Return(expr)
+                | Statement::LetBinding { expr: Some(expr), .. }
+                | Statement::Sleep(expr)
+                | Statement::Await { target: Awaitable::Event { name: expr, timeout: None }, .. } => self.expression(expr),
+                Statement::LetBinding { expr: None, .. } => {}
+                Statement::Backtrack(block) => self.block(block),
+                Statement::Await { target: Awaitable::Event { name, timeout: Some(timeout) }, .. } => {
+                    self.expression(name);
+                    self.expression(timeout);
+                }
+                Statement::Await { target: Awaitable::Call { callee, args }, .. } => {
+                    self.rename_if(&self.callables, callee);
+                    for arg in args {
+                        self.expression(arg);
+                    }
+                }
+            }
+        }
+    }
+
+    fn expression(&self, expr: &mut Expression) {
+        match expr {
+            Expression::FunctionCall { callee, args } => {
+                match callee.as_mut() {
+                    Expression::Identifier(name) => self.rename_if(&self.callables, name),
+                    other => self.expression(other),
+                }
+                for arg in args {
+                    self.expression(arg);
+                }
+            }
+            Expression::Identifier(name) => self.rename_if(&self.constructors, name),
+            Expression::Block(block) => self.block(block),
+            Expression::Match { expr, arms } => {
+                self.expression(expr);
+                for arm in arms {
+                    self.pattern(&mut arm.pattern);
+                    self.expression(&mut arm.expr);
+                }
+            }
+            Expression::SelfModify { patch, .. } => self.expression(patch),
+            Expression::IntLiteral(_)
+            | Expression::FloatLiteral(_)
+            | Expression::StringLiteral(_)
+            | Expression::BoolLiteral(_)
+            | Expression::Symbolic(_) => {}
+        }
+    }
+
+    fn pattern(&self, pattern: &mut Pattern) {
+        match pattern {
+            Pattern::Identifier(name) => self.rename_if(&self.constructors, name),
+            Pattern::Constructor { name, args } => {
+                self.rename_if(&self.constructors, name);
+                args.iter_mut().for_each(|arg| self.pattern(arg));
+            }
+            Pattern::Tuple(patterns) => patterns.iter_mut().for_each(|p| self.pattern(p)),
+            Pattern::Wildcard | Pattern::Literal(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capabilities::Capability;
+
+    fn messages(result: &Diagnostics) -> Vec<String> {
+        result.diagnostics.iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn test_imports_are_qualified_cached_and_checked_for_cycles() {
+        let mut modules = MemoryResolver::new();
+        modules.insert("text", "fn shout(s: String) -> String { return str.upper(helper(s)); } fn helper(s: String) -> String { return s; }");
+        modules.insert("log", "import text;\nfn write(s: String) { memory.note(text.shout(s)); }");
+        let mut store = UnitStore::new();
+
+        let source = "import log;\nimport text;\nfn main() { log.write(text.helper(\"hi\")); }";
+        let (items, result) = analyze_program(source, &modules, &mut store, &ForeignFunctions::new());
+        assert!(result.diagnostics.is_empty(), "{:?}", messages(&result));
+        assert!(result.capabilities.contains(&Capability::Memory));
+        let names: Vec<&str> = items
+            .iter()
+            .filter_map(|item| match item {
+                AstNode::Function(f) => Some(f.name.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(names, vec!["main", "log.write", "text.shout", "text.helper"]);
+        assert_eq!(store.len(), 3);
+        analyze_program(source, &modules, &mut store, &ForeignFunctions::new());
+        assert_eq!(store.len(), 3);
+
+        // A source whose hash collides with a cached unit's is parsed, not
+        // mistaken for it
+        let cached = store.get(source).unwrap();
+        let other = "fn other() {}";
+        store.units.insert(content_hash(other), vec![cached.clone()]);
+        let unit = store.compile(other);
+        assert_eq!(unit.source, other);
+        assert!(!Arc::ptr_eq(&unit, &cached));
+        assert!(Arc::ptr_eq(&store.get(other).unwrap(), &unit));
+
+        modules.insert("text", "import log;\nfn shout(s: String) -> String { return s; }");
+        modules.insert("broken", "fn f() { return g(); }");
+        let (_, result) = analyze_program(
+            "import log;\nimport broken;\nimport missing;",
+            &modules,
+            &mut UnitStore::new(),
+            &ForeignFunctions::new(),
+        );
+        assert_eq!(
+            messages(&result),
+            vec![
+                "line 1, column 8: in module 'log': line 1, column 8: in module 'text': line 1, column 8: cyclic import: log -> text -> log",
+                "line 2, column 8: in module 'broken': line 1, column 10: undefined function 'g'",
+                "line 3, column 8: module 'missing' not found",
+            ]
+        );
+    }
+}
@@ -8,164 +8,35 @@
 // - Pattern matching expressions and patterns
 // - Symbolic expressions
 // - Backtracking blocks
-// - Basic error recovery and diagnostics
+// - Error recovery with line/column diagnostics
+// - Source spans on declarations and statements
+// - Module imports
+// - Sum type declarations
+// - Async functions with `await` and `sleep` statements
+//
+// The parser never panics on malformed input: errors are recorded as
+// diagnostics at the offending token, the parser skips to the next statement
+// or declaration and carries on, and nesting is capped so that deeply nested
+// input cannot exhaust the stack.
 //
 // Author: Alex Roussinov
 // Created: 2025-12-22
+// Updated: 2026-10-16
 // =============================================================================
 
-use crate::tokens::{Token, TokenKind};
-use std::fmt;
-
-#[derive(Debug, Clone)]
-pub enum AstNode {
-    Function(FunctionDecl),
-    Intent(IntentDecl),
-    Rule(RuleDecl),
-    // Add more as needed
-}
-
-#[derive(Debug, Clone)]
-pub struct FunctionDecl {
-    pub is_grad: bool,
-    pub name: String,
-    pub params: Vec<Param>,
-    pub ret_type: Option<Type>,
-    pub effects: Vec<String>,
-    pub body: Block,
-}
-
-#[derive(Debug, Clone)]
-pub struct IntentDecl {
-    pub name: String,
-    pub motive: Option<String>,
-    pub action: Option<String>,
-    // Add more intent fields as needed
-}
-
-#[derive(Debug, Clone)]
-pub struct RuleDecl {
-    pub name: String,
-    pub params: Vec<Param>,
-    pub ret_type: Option<Type>,
-    pub body: Block,
-}
-
-#[derive(Debug, Clone)]
-pub struct Param {
-    pub name: String,
-    pub ty: Type,
-}
-
-#[derive(Debug, Clone)]
-pub enum Type {
-    Simple(String),
-
-    Mut(Box<Type>),
-    Ref(Box<Type>),
-    Cap(Box<Type>),
-    Tensor(Shape, DType),
-    Symbolic(String),
-    Grad(Box<Type>),
-    DepType {
-        var: String,
-        var_type: Box<Type>,
-        prop: Expression,
-    },
-
-    Unit,
-    Bool,
-    Int(u32),
-    Float(u32),
-    String,
-}
-
-#[derive(Debug, Clone)]
-pub struct Shape(pub Vec<ShapeDim>);
+use crate::ast::*;
+use crate::errors::{Diagnostic, ParseError};
+use crate::tokens::{Span, Token, TokenKind};
+use crate::types::{DType, Shape, ShapeDim, Type};
 
-#[derive(Debug, Clone)]
-pub enum ShapeDim {
-    Number(u32),
-    Identifier(String),
-}
-
-#[derive(Debug, Clone)]
-pub enum DType {
-    F32,
-    F64,
-    I32,
-    I64,
-    Bool,
-    String,
-}
-
-#[derive(Debug, Clone)]
-pub struct Block {
-    pub statements: Vec<Statement>,
-}
-
-#[derive(Debug, Clone)]
-pub enum Statement {
-    Expr(Expression),
-    LetBinding { name: String, expr: Option<Expression> },
-    Return(Expression),
-    Backtrack(Block),
-    // Extend as needed
-}
-
-#[derive(Debug, Clone)]
-pub enum Expression {
-    Identifier(String),
-    IntLiteral(i64),
-    FloatLiteral(f64),
-    StringLiteral(String),
-    BoolLiteral(bool),
-    FunctionCall { callee: Box<Expression>, args: Vec<Expression> },
-    Block(Block),
-    Match { expr: Box<Expression>, arms: Vec<MatchArm> },
-    Symbolic(String),
-    SelfModify { target: String, patch: Box<Expression> },
-    // Extend as needed
-}
-
-#[derive(Debug, Clone)]
-pub struct MatchArm {
-    pub pattern: Pattern,
-    pub expr: Expression,
-}
-
-#[derive(Debug, Clone)]
-pub enum Pattern {
-    Identifier(String),
-    Wildcard,
-    Literal(Expression),
-    Tuple(Vec<Pattern>),
-    Constructor { name: String, args: Vec<Pattern> },
-}
-
-#[derive(Debug)]
-pub enum ParseError {
-    UnexpectedEof,
-    UnexpectedToken(TokenKind),
-    ExpectedToken(String),
-    Custom(String),
-}
-
-impl fmt::Display for ParseError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            ParseError::UnexpectedEof => write!(f, "Unexpected end of input"),
-            ParseError::UnexpectedToken(tok) => write!(f, "Unexpected token: {:?}", tok),
-            ParseError::ExpectedToken(tok) => write!(f, "Expected token: {}", tok),
-            ParseError::Custom(msg) => write!(f, "Parse error: {}", msg),
-        }
-    }
-}
+/// Deepest nesting of blocks, expressions, types, and patterns accepted.
+pub const MAX_NESTING: usize = 128;
 
 pub struct Parser<'a> {
     tokens: &'a [Token],
     pos: usize,
-    errors: Vec<ParseError>,
+    depth: usize,
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl<'a> Parser<'a> {
@@ -173,18 +44,32 @@ impl<'a> Parser<'a> {
         Parser {
             tokens,
             pos: 0,
-            errors: Vec::new(),
+            depth: 0,
+            diagnostics: Vec::new(),
         }
     }
 
-    pub fn errors(&self) -> &[ParseError] {
-        &self.errors
+    /// Errors recovered from so far, each at the token where it was found.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn into_diagnostics(self) -> Vec<Diagnostic> {
+        self.diagnostics
     }
 
     fn peek(&self) -> Option<&'a Token> {
         self.tokens.get(self.pos)
     }
 
+    fn peek_kind(&self) -> Option<&'a TokenKind> {
+        self.peek().map(|token| &token.kind)
+    }
+
+    fn at_end(&self) -> bool {
+        matches!(self.peek_kind(), None | Some(TokenKind::Eof))
+    }
+
     fn bump(&mut self) -> Option<&'a Token> {
         let tok = self.tokens.get(self.pos);
         if tok.is_some() {
@@ -193,55 +78,95 @@ impl<'a> Parser<'a> {
         tok
     }
 
+    /// Span of the current token, or of the last one once input runs out.
+    fn current_span(&self) -> Span {
+        self.peek()
+            .or_else(|| self.tokens.last())
+            .map(|token| token.span.clone())
+            .unwrap_or_else(|| Span::new(0, 0))
+    }
+
+    fn unexpected(&self) -> ParseError {
+        match self.peek_kind() {
+            None | Some(TokenKind::Eof) => ParseError::UnexpectedEof,
+            Some(kind) => ParseError::UnexpectedToken(kind.to_string()),
+        }
+    }
+
+    fn report(&mut self, err: ParseError) {
+        let diagnostic = Diagnostic::new(err.to_string(), self.current_span());
+        // An error bubbling out of nested blocks is reported once.
+        if self.diagnostics.last() != Some(&diagnostic) {
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Runs `parse` one nesting level deeper, failing past `MAX_NESTING`.
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T, ParseError>) -> Result<T, ParseError> {
+        if self.depth >= MAX_NESTING {
+            return Err(ParseError::Custom(format!("nesting deeper than {} levels", MAX_NESTING)));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
     fn expect(&mut self, expected: &TokenKind) -> Result<(), ParseError> {
-        match self.peek() {
-            Some(token) if &token.kind == expected => {
+        match self.peek_kind() {
+            Some(kind) if kind == expected => {
                 self.bump();
                 Ok(())
             }
-            Some(token) => {
-                let err = ParseError::UnexpectedToken(token.kind.clone());
-                self.errors.push(err.clone());
-                Err(err)
+            None | Some(TokenKind::Eof) => Err(ParseError::ExpectedToken(format!("'{}' before end of input", expected))),
+            Some(kind) => Err(ParseError::ExpectedToken(format!("'{}', found '{}'", expected, kind))),
+        }
+    }
+
+    fn expect_identifier(&mut self) -> Result<String, ParseError> {
+        match self.peek_kind() {
+            Some(TokenKind::Identifier(name)) => {
+                self.bump();
+                Ok(name.clone())
             }
-            None => {
-                let err = ParseError::UnexpectedEof;
-                self.errors.push(err.clone());
-                Err(err)
+            None | Some(TokenKind::Eof) => Err(ParseError::UnexpectedEof),
+            Some(kind) => Err(ParseError::ExpectedToken(format!("identifier, found '{}'", kind))),
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), ParseError> {
+        match self.peek_kind() {
+            Some(TokenKind::Identifier(name)) if name == keyword => {
+                self.bump();
+                Ok(())
             }
+            _ => Err(ParseError::ExpectedToken(format!("'{}'", keyword))),
         }
     }
 
-    fn expect_identifier(&mut self) -> Result<String, ParseError> {
-        match self.peek() {
-            Some(token) => match &token.kind {
-                TokenKind::Identifier(name) => {
-                    self.bump();
-                    Ok(name.clone())
-                }
-                _ => {
-                    let err = ParseError::ExpectedToken("identifier".into());
-                    self.errors.push(err.clone());
-                    Err(err)
-                }
-            },
-            None => {
-                let err = ParseError::UnexpectedEof;
-                self.errors.push(err.clone());
-                Err(err)
+    fn expect_string_literal(&mut self) -> Result<String, ParseError> {
+        match self.peek_kind() {
+            Some(TokenKind::StringLiteral(value)) => {
+                self.bump();
+                Ok(value.clone())
             }
+            _ => Err(ParseError::ExpectedToken("string literal".into())),
         }
     }
 
     pub fn parse_program(&mut self) -> Vec<AstNode> {
         let mut nodes = Vec::new();
-        while let Some(token) = self.peek() {
-            if token.kind == TokenKind::Eof {
-                break;
-            }
+        while !self.at_end() {
+            let start = self.pos;
             match self.parse_top_level_decl() {
                 Ok(node) => nodes.push(node),
-                Err(_) => self.recover_top_level(),
+                Err(err) => {
+                    self.report(err);
+                    if self.pos == start {
+                        self.bump();
+                    }
+                    self.recover_top_level();
+                }
             }
         }
         nodes
@@ -251,8 +176,12 @@ impl<'a> Parser<'a> {
         // Simple recovery: skip tokens until next top-level keyword or EOF
         while let Some(token) = self.peek() {
             match &token.kind {
-                TokenKind::Identifier(s) if s == "fn" || s == "intent" || s == "rule" => break,
-                TokenKind::Eof => break,
+                TokenKind::Identifier(s)
+                    if s == "fn" || s == "async" || s == "rule" || s == "@grad" || s == "import" || s == "type" =>
+                {
+                    break
+                }
+                TokenKind::Intent | TokenKind::Eof => break,
                 _ => {
                     self.bump();
                 }
@@ -261,48 +190,55 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_top_level_decl(&mut self) -> Result<AstNode, ParseError> {
-        match self.peek() {
-            Some(token) => match &token.kind {
-                TokenKind::Identifier(s) if s == "fn" || s == "@grad" => {
-                    let func = self.parse_function_decl()?;
-                    Ok(AstNode::Function(func))
-                }
-                TokenKind::Identifier(s) if s == "intent" => {
-                    let intent = self.parse_intent_decl()?;
-                    Ok(AstNode::Intent(intent))
-                }
-                TokenKind::Identifier(s) if s == "rule" => {
-                    let rule = self.parse_rule_decl()?;
-                    Ok(AstNode::Rule(rule))
-                }
-                _ => {
-                    let err = ParseError::UnexpectedToken(token.kind.clone());
-                    self.errors.push(err.clone());
-                    Err(err)
-                }
-            },
-            None => {
-                let err = ParseError::UnexpectedEof;
-                self.errors.push(err.clone());
-                Err(err)
+        match self.peek_kind() {
+            Some(TokenKind::LBrace) => {
+                let effects = self.parse_effect_annotation()?;
+                let mut func = self.parse_function_decl()?;
+                func.effects = effects;
+                Ok(AstNode::Function(func))
+            }
+            Some(TokenKind::Identifier(s)) if s == "fn" || s == "async" || s == "@grad" => {
+                let func = self.parse_function_decl()?;
+                Ok(AstNode::Function(func))
+            }
+            Some(TokenKind::Intent) => {
+                let intent = self.parse_intent_decl()?;
+                Ok(AstNode::Intent(intent))
+            }
+            Some(TokenKind::Identifier(s)) if s == "rule" => {
+                let rule = self.parse_rule_decl()?;
+                Ok(AstNode::Rule(rule))
+            }
+            Some(TokenKind::Identifier(s)) if s == "import" => {
+                // import path.to.module;
+                self.bump();
+                let span = self.current_span();
+                let path = self.expect_identifier()?;
+                self.expect(&TokenKind::Semicolon)?;
+                Ok(AstNode::Import(ImportDecl { path, span }))
+            }
+            Some(TokenKind::Identifier(s)) if s == "type" => {
+                let decl = self.parse_type_decl()?;
+                Ok(AstNode::Type(decl))
             }
+            _ => Err(self.unexpected()),
         }
     }
 
     fn parse_function_decl(&mut self) -> Result<FunctionDecl, ParseError> {
         // Optional @grad annotation
-        let mut is_grad = false;
-        if let Some(token) = self.peek() {
-            if let TokenKind::Identifier(s) = &token.kind {
-                if s == "@grad" {
-                    is_grad = true;
-                    self.bump();
-                }
-            }
+        let is_grad = matches!(self.peek_kind(), Some(TokenKind::Identifier(s)) if s == "@grad");
+        if is_grad {
+            self.bump();
+        }
+        let is_async = matches!(self.peek_kind(), Some(TokenKind::Identifier(s)) if s == "async");
+        if is_async {
+            self.bump();
         }
 
-        self.expect(&TokenKind::Identifier("fn".into()))?;
+        self.expect_keyword("fn")?;
 
+        let span = self.current_span();
         let name = self.expect_identifier()?;
 
         self.expect(&TokenKind::LParen)?;
@@ -311,45 +247,82 @@ impl<'a> Parser<'a> {
 
         self.expect(&TokenKind::RParen)?;
 
-        // Optional return type
-        let ret_type = if let Some(Token { kind: TokenKind::Arrow, .. }) = self.peek() {
-            self.bump();
-            Some(self.parse_type()?)
-        } else {
-            None
-        };
-
-        // Optional effect annotation: { Pure, IO, ... }
-        let effects = if let Some(Token { kind: TokenKind::LBrace, .. }) = self.peek() {
-            self.parse_effect_annotation()?
-        } else {
-            Vec::new()
-        };
+        let ret_type = self.parse_return_type()?;
 
         let body = self.parse_block()?;
 
         Ok(FunctionDecl {
             is_grad,
+            is_async,
             name,
+            span,
             params,
             ret_type,
-            effects,
+            effects: Vec::new(),
             body,
         })
     }
 
+    fn parse_type_decl(&mut self) -> Result<TypeDecl, ParseError> {
+        // type Name = A | B(T, U) | C;
+        self.expect_keyword("type")?;
+        let span = self.current_span();
+        let name = self.expect_identifier()?;
+        self.expect(&TokenKind::Equal)?;
+        if let Some(TokenKind::Pipe) = self.peek_kind() {
+            self.bump();
+        }
+        let mut variants = Vec::new();
+        loop {
+            let variant = self.expect_identifier()?;
+            let mut fields = Vec::new();
+            if let Some(TokenKind::LParen) = self.peek_kind() {
+                self.bump();
+                while !matches!(self.peek_kind(), Some(TokenKind::RParen)) {
+                    fields.push(self.parse_type()?);
+                    match self.peek_kind() {
+                        Some(TokenKind::Comma) => {
+                            self.bump();
+                        }
+                        Some(TokenKind::RParen) => {}
+                        _ => return Err(ParseError::ExpectedToken("',' or ')'".into())),
+                    }
+                }
+                self.bump();
+            }
+            variants.push(Variant { name: variant, fields });
+            match self.peek_kind() {
+                Some(TokenKind::Pipe) => {
+                    self.bump();
+                }
+                _ => break,
+            }
+        }
+        self.expect(&TokenKind::Semicolon)?;
+        Ok(TypeDecl { name, span, variants })
+    }
+
+    fn parse_return_type(&mut self) -> Result<Option<Type>, ParseError> {
+        if let Some(TokenKind::Arrow) = self.peek_kind() {
+            self.bump();
+            Ok(Some(self.parse_type()?))
+        } else {
+            Ok(None)
+        }
+    }
+
     fn parse_param_list(&mut self) -> Result<Vec<Param>, ParseError> {
         let mut params = Vec::new();
-        while let Some(token) = self.peek() {
-            if token.kind == TokenKind::RParen {
+        while let Some(kind) = self.peek_kind() {
+            if *kind == TokenKind::RParen {
                 break;
             }
             let name = self.expect_identifier()?;
             self.expect(&TokenKind::Colon)?;
             let ty = self.parse_type()?;
-            params.push(Param { name, ty });
+            params.push(Param { name, ty: Some(ty) });
 
-            if let Some(Token { kind: TokenKind::Comma, .. }) = self.peek() {
+            if let Some(TokenKind::Comma) = self.peek_kind() {
                 self.bump();
             } else {
                 break;
@@ -359,102 +332,86 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_type(&mut self) -> Result<Type, ParseError> {
-        match self.peek() {
-            Some(token) => match &token.kind {
-                TokenKind::Identifier(name) => {
-                    // Handle type constructors like Mut<T>, Cap<T>, etc.
-                    let type_name = name.clone();
-                    self.bump();
-
-                    match type_name.as_str() {
-                        "Mut" | "Ref" | "Cap" | "Grad" => {
-                            self.expect(&TokenKind::LessThan)?;
-                            let inner = self.parse_type()?;
-                            self.expect(&TokenKind::GreaterThan)?;
-                            let ty = match type_name.as_str() {
-                                "Mut" => Type::Mut(Box::new(inner)),
-                                "Ref" => Type::Ref(Box::new(inner)),
-                                "Cap" => Type::Cap(Box::new(inner)),
-                                "Grad" => Type::Grad(Box::new(inner)),
-                                _ => unreachable!(),
-                            };
-                            Ok(ty)
-                        }
-                        "Tensor" => {
-                            self.expect(&TokenKind::LessThan)?;
-                            let shape = self.parse_shape()?;
-                            self.expect(&TokenKind::Comma)?;
-                            let dtype = self.parse_dtype()?;
-                            self.expect(&TokenKind::GreaterThan)?;
-                            Ok(Type::Tensor(shape, dtype))
-                        }
-                        "DepType" => {
-                            self.expect(&TokenKind::LessThan)?;
-                            let var = self.expect_identifier()?;
-                            self.expect(&TokenKind::Colon)?;
-                            let var_type = self.parse_type()?;
-                            self.expect(&TokenKind::Pipe)?;
-                            let prop = self.parse_expression()?;
-                            self.expect(&TokenKind::GreaterThan)?;
-                            Ok(Type::DepType {
-                                var,
-                                var_type: Box::new(var_type),
-                                prop,
-                            })
-                        }
-                        "Symbolic" => {
-                            self.expect(&TokenKind::LessThan)?;
-                            let prop_expr = self.parse_expression()?;
-                            self.expect(&TokenKind::GreaterThan)?;
-                            if let Expression::Identifier(prop_name) = prop_expr {
-                                Ok(Type::Symbolic(prop_name))
-                            } else {
-                                // For simplicity, symbolic prop as string from identifier only
-                                Err(ParseError::Custom(
-                                    "Symbolic prop must be identifier".into(),
-                                ))
-                            }
-                        }
-                        "Unit" => Ok(Type::Unit),
-                        "Bool" => Ok(Type::Bool),
-                        "String" => Ok(Type::String),
-                        "Int" | "Float" => {
-                            self.expect(&TokenKind::LessThan)?;
-                            let size = self.expect_int_literal()? as u32;
-                            self.expect(&TokenKind::GreaterThan)?;
-                            if type_name == "Int" {
-                                Ok(Type::Int(size))
-                            } else {
-                                Ok(Type::Float(size))
-                            }
-                        }
-                        _ => Ok(Type::Simple(type_name)),
-                    }
-                }
-                _ => Err(ParseError::ExpectedToken("type".into())),
-            },
-            None => Err(ParseError::UnexpectedEof),
-        }
+        self.nested(Self::parse_type_inner)
     }
 
-    fn expect_int_literal(&mut self) -> Result<i64, ParseError> {
-        match self.peek() {
-            Some(token) => match &token.kind {
-                TokenKind::IntLiteral(n) => {
-                    self.bump();
-                    Ok(*n)
-                }
-                _ => {
-                    let err = ParseError::ExpectedToken("integer literal".into());
-                    self.errors.push(err.clone());
-                    Err(err)
-                }
-            },
-            None => {
-                let err = ParseError::UnexpectedEof;
-                self.errors.push(err.clone());
-                Err(err)
+    fn parse_type_inner(&mut self) -> Result<Type, ParseError> {
+        let type_name = match self.peek_kind() {
+            Some(TokenKind::Identifier(name)) => name.clone(),
+            None | Some(TokenKind::Eof) => return Err(ParseError::UnexpectedEof),
+            Some(_) => return Err(ParseError::ExpectedToken("type".into())),
+        };
+        self.bump();
+
+        // Handle type constructors like Mut<T>, Cap<T>, etc.
+        match type_name.as_str() {
+            "Mut" | "Ref" | "Cap" | "Grad" => {
+                self.expect(&TokenKind::LessThan)?;
+                let inner = Box::new(self.parse_type()?);
+                self.expect(&TokenKind::GreaterThan)?;
+                Ok(match type_name.as_str() {
+                    "Mut" => Type::Mut(inner),
+                    "Ref" => Type::Ref(inner),
+                    "Cap" => Type::Cap(inner),
+                    _ => Type::Grad(inner),
+                })
+            }
+            "Tensor" => {
+                self.expect(&TokenKind::LessThan)?;
+                let shape = self.parse_shape()?;
+                self.expect(&TokenKind::Comma)?;
+                let dtype = self.parse_dtype()?;
+                self.expect(&TokenKind::GreaterThan)?;
+                Ok(Type::Tensor(shape, dtype))
+            }
+            "DepType" => {
+                self.expect(&TokenKind::LessThan)?;
+                let var = self.expect_identifier()?;
+                self.expect(&TokenKind::Colon)?;
+                let var_type = self.parse_type()?;
+                self.expect(&TokenKind::Pipe)?;
+                let prop = self.parse_expression()?;
+                self.expect(&TokenKind::GreaterThan)?;
+                Ok(Type::DepType {
+                    var,
+                    var_type: Box::new(var_type),
+                    prop,
+                })
+            }
+            "Symbolic" => {
+                self.expect(&TokenKind::LessThan)?;
+                // For simplicity, symbolic prop as string from identifier only
+                let prop_name = self
+                    .expect_identifier()
+                    .map_err(|_| ParseError::Custom("Symbolic prop must be identifier".into()))?;
+                self.expect(&TokenKind::GreaterThan)?;
+                Ok(Type::Symbolic(prop_name))
+            }
+            "Unit" => Ok(Type::Unit),
+            "Bool" => Ok(Type::Bool),
+            "String" => Ok(Type::String),
+            "Int" | "Float" => {
+                self.expect(&TokenKind::LessThan)?;
+                let size = self.expect_bit_width()?;
+                self.expect(&TokenKind::GreaterThan)?;
+                if type_name == "Int" {
+                    Ok(Type::Int(size))
+                } else {
+                    Ok(Type::Float(size))
+                }
+            }
+            _ => Ok(Type::Simple(type_name)),
+        }
+    }
+
+    fn expect_bit_width(&mut self) -> Result<u32, ParseError> {
+        match self.peek_kind() {
+            Some(TokenKind::IntLiteral(n)) => {
+                let n = *n;
+                self.bump();
+                u32::try_from(n).map_err(|_| ParseError::Custom(format!("Invalid bit width: {}", n)))
             }
+            _ => Err(ParseError::ExpectedToken("integer literal".into())),
         }
     }
 
@@ -462,12 +419,13 @@ impl<'a> Parser<'a> {
         self.expect(&TokenKind::LBracket)?;
         let mut dims = Vec::new();
         loop {
-            match self.peek() {
-                Some(Token { kind: TokenKind::IntLiteral(n), .. }) => {
-                    dims.push(ShapeDim::Number(*n as u32));
+            match self.peek_kind() {
+                Some(TokenKind::IntLiteral(n)) => {
+                    let dim = u32::try_from(*n).map_err(|_| ParseError::Custom(format!("Invalid dimension: {}", n)))?;
+                    dims.push(ShapeDim::Number(dim));
                     self.bump();
                 }
-                Some(Token { kind: TokenKind::Identifier(name), .. }) => {
+                Some(TokenKind::Identifier(name)) => {
                     dims.push(ShapeDim::Identifier(name.clone()));
                     self.bump();
                 }
@@ -475,11 +433,11 @@ impl<'a> Parser<'a> {
                     return Err(ParseError::ExpectedToken("shape dimension".into()));
                 }
             }
-            match self.peek() {
-                Some(Token { kind: TokenKind::Comma, .. }) => {
+            match self.peek_kind() {
+                Some(TokenKind::Comma) => {
                     self.bump();
                 }
-                Some(Token { kind: TokenKind::RBracket, .. }) => {
+                Some(TokenKind::RBracket) => {
                     self.bump();
                     break;
                 }
@@ -490,23 +448,22 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_dtype(&mut self) -> Result<DType, ParseError> {
-        match self.peek() {
-            Some(token) => match &token.kind {
-                TokenKind::Identifier(name) => {
-                    self.bump();
-                    match name.as_str() {
-                        "f32" => Ok(DType::F32),
-                        "f64" => Ok(DType::F64),
-                        "i32" => Ok(DType::I32),
-                        "i64" => Ok(DType::I64),
-                        "bool" => Ok(DType::Bool),
-                        "string" => Ok(DType::String),
-                        _ => Err(ParseError::Custom(format!("Unknown dtype: {}", name))),
-                    }
-                }
-                _ => Err(ParseError::ExpectedToken("dtype".into())),
-            },
-            None => Err(ParseError::UnexpectedEof),
+        match self.peek_kind() {
+            Some(TokenKind::Identifier(name)) => {
+                let dtype = match name.as_str() {
+                    "f32" => DType::F32,
+                    "f64" => DType::F64,
+                    "i32" => DType::I32,
+                    "i64" => DType::I64,
+                    "bool" => DType::Bool,
+                    "string" => DType::String,
+                    _ => return Err(ParseError::Custom(format!("Unknown dtype: {}", name))),
+                };
+                self.bump();
+                Ok(dtype)
+            }
+            None | Some(TokenKind::Eof) => Err(ParseError::UnexpectedEof),
+            Some(_) => Err(ParseError::ExpectedToken("dtype".into())),
         }
     }
 
@@ -514,15 +471,15 @@ impl<'a> Parser<'a> {
         self.expect(&TokenKind::LBrace)?;
         let mut effects = Vec::new();
         loop {
-            match self.peek() {
-                Some(Token { kind: TokenKind::Identifier(name), .. }) => {
+            match self.peek_kind() {
+                Some(TokenKind::Identifier(name)) => {
                     effects.push(name.clone());
                     self.bump();
                 }
-                Some(Token { kind: TokenKind::Comma, .. }) => {
+                Some(TokenKind::Comma) => {
                     self.bump();
                 }
-                Some(Token { kind: TokenKind::RBrace, .. }) => {
+                Some(TokenKind::RBrace) => {
                     self.bump();
                     break;
                 }
@@ -533,675 +490,234 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_block(&mut self) -> Result<Block, ParseError> {
+        self.nested(Self::parse_block_inner)
+    }
+
+    fn parse_block_inner(&mut self) -> Result<Block, ParseError> {
         self.expect(&TokenKind::LBrace)?;
         let mut statements = Vec::new();
-        while let Some(token) = self.peek() {
-            if token.kind == TokenKind::RBrace {
-                self.bump();
-                break;
+        loop {
+            match self.peek_kind() {
+                Some(TokenKind::RBrace) => {
+                    self.bump();
+                    return Ok(Block { statements });
+                }
+                None | Some(TokenKind::Eof) => {
+                    return Err(ParseError::ExpectedToken("'}' to close block".into()));
+                }
+                _ => {}
             }
+            let start = self.pos;
+            let span = self.current_span();
             match self.parse_statement() {
-                Ok(stmt) => statements.push(stmt),
+                Ok(node) => statements.push(Spanned { node, span }),
                 Err(err) => {
-                    self.errors.push(err);
+                    self.report(err);
+                    if self.pos == start {
+                        self.bump();
+                    }
                     self.recover_statement();
                 }
             }
         }
-        Ok(Block { statements })
     }
 
     fn recover_statement(&mut self) {
         // Skip tokens until semicolon or block end
         while let Some(token) = self.peek() {
-            if token.kind == TokenKind::Semicolon || token.kind == TokenKind::RBrace {
-                if token.kind == TokenKind::Semicolon {
-                    self.bump();
-                }
-                break;
-            }
-            self.bump();
-        }
-    }
-
-    fn parse_statement(&mut self) -> Result<Statement, ParseError> {
-        match self.peek() {
-            Some(token) => match &token.kind {
-                TokenKind::Identifier(s) if s == "let" => {
-                    self.bump();
-                    let name = self.expect_identifier()?;
-                    let expr = if let Some(Token { kind: TokenKind::Equal, .. }) = self.peek() {
-                        self.bump();
-                        Some(self.parse_expression()?)
-                    } else {
-                        None
-                    };
-                    self.expect(&TokenKind::Semicolon)?;
-                    Ok(Statement::LetBinding { name, expr })
-                }
-                TokenKind::Identifier(s) if s == "return" => {
-                    self.bump();
-                    let expr = self.parse_expression()?;
-                    self.expect(&TokenKind::Semicolon)?;
-                    Ok(Statement::Return(expr))
-                }
-                TokenKind::Identifier(s) if s == "backtrack" => {
+            match token.kind {
+                TokenKind::Semicolon => {
                     self.bump();
-                    let block = self.parse_block()?;
-                    Ok(Statement::Backtrack(block))
+                    break;
                 }
+                TokenKind::RBrace | TokenKind::Eof => break,
                 _ => {
-                    let expr = self.parse_expression()?;
-                    self.expect(&TokenKind::Semicolon)?;
-                    Ok(Statement::Expr(expr))
+                    self.bump();
                 }
-            },
-            None => Err(ParseError::UnexpectedEof),
+            }
         }
     }
 
-    fn parse_expression(&mut self) -> Result<Expression, ParseError> {
-        match self.peek() {
-            Some(token) => match &token.kind {
-                TokenKind::Identifier(name) => {
-                    self.bump();
-                    // Check for function call
-                    if let Some(Token { kind: TokenKind::LParen, .. }) = self.peek() {
-                        self.bump();
-                        let args = self.parse_arg_list()?;
-                        self.expect(&TokenKind::RParen)?;
-                        Ok(Expression::FunctionCall {
-                            callee: Box::new(Expression::Identifier(name.clone())),
-                            args,
-                        })
-                    } else if name == "symbolic" {
-                        // symbolic(expr)
-                        self.expect(&TokenKind::LParen)?;
-                        let sym_name = self.expect_identifier()?;
-                        self.expect(&TokenKind::RParen)?;
-                        Ok(Expression::Symbolic(sym_name))
-                    } else if name == "modify" {
-                        // modify(target, patch)
-                        self.expect(&TokenKind::LParen)?;
-                        let target = self.expect_identifier()?;
-                        self.expect(&TokenKind::Comma)?;
-                        let patch = self.parse_expression()?;
-                        self.expect(&TokenKind::RParen)?;
-                        Ok(Expression::SelfModify {
-                            target,
-                            patch: Box::new(patch),
-                        })
-                    } else {
-                        Ok(Expression::Identifier(name.clone()))
-                    }
-                }
-                TokenKind::IntLiteral(n) => {
-                    self.bump();
-                    Ok(Expression::IntLiteral(*n))
-                }
-                TokenKind::FloatLiteral(f) => {
-                    self.bump();
-                    Ok(Expression::FloatLiteral(*f))
-                }
-                TokenKind::StringLiteral(s) => {
-                    self.bump();
-                    Ok(Expression::StringLiteral(s.clone()))
-                }
-                TokenKind::BoolLiteral(b) => {
-                    self.bump();
-                    Ok(Expression::BoolLiteral(*b))
-                }
-                TokenKind::LBrace => {
-                    let block = self.parse_block()?;
-                    Ok(Expression::Block(block))
-                }
-                TokenKind::Identifier(s) if s == "match" => {
+    fn parse_statement(&mut self) -> Result<Statement, ParseError> {
+        match self.peek_kind() {
+            Some(TokenKind::Identifier(s)) if s == "let" => {
+                self.bump();
+                let name = self.expect_identifier()?;
+                let expr = if let Some(TokenKind::Equal) = self.peek_kind() {
                     self.bump();
-                    let expr = self.parse_expression()?;
-                    self.expect(&TokenKind::LBrace)?;
-                    let mut arms = Vec::new();
-                    while let Some(token) = self.peek() {
-                        if token.kind == TokenKind::RBrace {
-                            self.bump();
-                            break;
-                        }
-                        let pat = self.parse_pattern()?;
-                        self.expect(&TokenKind::ArrowFat)?; // => token
-                        let arm_expr = self.parse_expression()?;
+                    if matches!(self.peek_kind(), Some(TokenKind::Identifier(s)) if s == "await") {
+                        let target = self.parse_await()?;
                         self.expect(&TokenKind::Semicolon)?;
-                        arms.push(MatchArm {
-                            pattern: pat,
-                            expr: arm_expr,
-                        });
+                        return Ok(Statement::Await { binding: Some(name), target });
                     }
-                    Ok(Expression::Match {
-                        expr: Box::new(expr),
-                        arms,
-                    })
-                }
-                _ => Err(ParseError::UnexpectedToken(token.kind.clone())),
-            },
-            None => Err(ParseError::UnexpectedEof),
-        }
-    }
-
-    fn parse_arg_list(&mut self) -> Result<Vec<Expression>, ParseError> {
-        let mut args = Vec::new();
-        loop {
-            if let Some(Token { kind: TokenKind::RParen, .. }) = self.peek() {
-                break;
+                    Some(self.parse_expression()?)
+                } else {
+                    None
+                };
+                self.expect(&TokenKind::Semicolon)?;
+                Ok(Statement::LetBinding { name, expr })
+            }
+            Some(TokenKind::Identifier(s)) if s == "return" => {
+                self.bump();
+                let expr = self.parse_expression()?;
+                self.expect(&TokenKind::Semicolon)?;
+                Ok(Statement::Return(expr))
             }
-            let expr = self.parse_expression()?;
-            args.push(expr);
-            if let Some(Token { kind: TokenKind::Comma, .. }) = self.peek() {
+            Some(TokenKind::Identifier(s)) if s == "backtrack" => {
                 self.bump();
-            } else {
-                break;
+                let block = self.parse_block()?;
+                Ok(Statement::Backtrack(block))
             }
-        }
-        Ok(args)
-    }
-
-    fn parse_pattern(&mut self) -> Result<Pattern, ParseError> {
-        match self.peek() {
-            Some(token) => match &token.kind {
-                TokenKind::Identifier(name) => {
-                    self.bump();
-                    // Check for constructor pattern with args
-                    if let Some(Token { kind: TokenKind::LParen, .. }) = self.peek() {
-                        self.bump();
-                        let mut args = Vec::new();
-                        while let Some(token) = self.peek() {
-                            if token.kind == TokenKind::RParen {
-                                self.bump();
-                                break;
-                            }
-                            let pat = self.parse_pattern()?;
-                            args.push(pat);
-                            if let Some(Token { kind: TokenKind::Comma, .. }) = self.peek() {
-                                self.bump();
-                            } else {
-                                // Expecting RParen next
-                                continue;
-                            }
-                        }
-                        Ok(Pattern::Constructor {
-                            name: name.clone(),
-                            args,
-                        })
-                    } else {
-                        Ok(Pattern::Identifier(name.clone()))
-                    }
-                }
-                TokenKind::Underscore => {
-                    self.bump();
-                    Ok(Pattern::Wildcard)
-                }
-                TokenKind::IntLiteral(n) => {
-                    self.bump();
-                    Ok(Pattern::Literal(Expression::IntLiteral(*n)))
-                }
-                TokenKind::StringLiteral(s) => {
-                    self.bump();
-                    Ok(Pattern::Literal(Expression::StringLiteral(s.clone())))
-                }
-                TokenKind::BoolLiteral(b) => {
-                    self.bump();
-                    Ok(Pattern::Literal(Expression::BoolLiteral(*b)))
-                }
-                TokenKind::LParen => {
-                    self.bump();
-                    let mut pats = Vec::new();
-                    while let Some(token) =
-
-
-
-
-
-
-                        fn recover_top_level(&mut self) {
-        // Simple recovery: skip tokens until next top-level keyword or EOF
-        while let Some(token) = self.peek() {
-            match &token.kind {
-                TokenKind::Identifier(s) if s == "fn" || s == "intent" || s == "rule" => break,
-                TokenKind::Eof => break,
-                _ => {
-                    self.bump();
-                }
+            Some(TokenKind::Identifier(s)) if s == "await" => {
+                let target = self.parse_await()?;
+                self.expect(&TokenKind::Semicolon)?;
+                Ok(Statement::Await { binding: None, target })
             }
-        }
-    }
-
-    fn parse_top_level_decl(&mut self) -> Result<AstNode, ParseError> {
-        match self.peek() {
-            Some(token) => match &token.kind {
-                TokenKind::Identifier(s) if s == "@grad" || s == "fn" => {
-                    let func = self.parse_function_decl()?;
-                    Ok(AstNode::Function(func))
-                }
-                TokenKind::Identifier(s) if s == "intent" => {
-                    let intent = self.parse_intent_decl()?;
-                    Ok(AstNode::Intent(intent))
-                }
-                TokenKind::Identifier(s) if s == "rule" => {
-                    let rule = self.parse_rule_decl()?;
-                    Ok(AstNode::Rule(rule))
-                }
-                _ => {
-                    let err = ParseError::UnexpectedToken(token.kind.clone());
-                    self.errors.push(err.clone());
-                    Err(err)
-                }
-            },
-            None => {
-                let err = ParseError::UnexpectedEof;
-                self.errors.push(err.clone());
-                Err(err)
+            Some(TokenKind::Identifier(s)) if s == "sleep" => {
+                self.bump();
+                let duration = self.parse_expression()?;
+                self.expect(&TokenKind::Semicolon)?;
+                Ok(Statement::Sleep(duration))
             }
-        }
-    }
-
-    fn parse_function_decl(&mut self) -> Result<FunctionDecl, ParseError> {
-        // Optional @grad annotation
-        let mut is_grad = false;
-        if let Some(token) = self.peek() {
-            if let TokenKind::Identifier(s) = &token.kind {
-                if s == "@grad" {
-                    is_grad = true;
-                    self.bump();
-                }
+            None | Some(TokenKind::Eof) => Err(ParseError::UnexpectedEof),
+            Some(_) => {
+                let expr = self.parse_expression()?;
+                self.expect(&TokenKind::Semicolon)?;
+                Ok(Statement::Expr(expr))
             }
         }
-
-        self.expect(&TokenKind::Identifier("fn".into()))?;
-
-        let name = self.expect_identifier()?;
-
-        self.expect(&TokenKind::LParen)?;
-
-        let params = self.parse_param_list()?;
-
-        self.expect(&TokenKind::RParen)?;
-
-        // Optional return type
-        let ret_type = if let Some(Token { kind: TokenKind::Arrow, .. }) = self.peek() {
-            self.bump();
-            Some(self.parse_type()?)
-        } else {
-            None
-        };
-
-        // Optional effect annotation: { Pure, IO, ... }
-        let effects = if let Some(Token { kind: TokenKind::LBrace, .. }) = self.peek() {
-            self.parse_effect_annotation()?
-        } else {
-            Vec::new()
-        };
-
-        let body = self.parse_block()?;
-
-        Ok(FunctionDecl {
-            is_grad,
-            name,
-            params,
-            ret_type,
-            effects,
-            body,
-        })
     }
 
-    fn parse_param_list(&mut self) -> Result<Vec<Param>, ParseError> {
-        let mut params = Vec::new();
-        while let Some(token) = self.peek() {
-            if token.kind == TokenKind::RParen {
-                break;
-            }
-            let name = self.expect_identifier()?;
-            self.expect(&TokenKind::Colon)?;
-            let ty = self.parse_type()?;
-            params.push(Param { name, ty });
-
-            if let Some(Token { kind: TokenKind::Comma, .. }) = self.peek() {
+    /// Parses `await event(name) [timeout ms]` or `await f(args)`.
+    fn parse_await(&mut self) -> Result<Awaitable, ParseError> {
+        self.expect_keyword("await")?;
+        let is_event = matches!(self.peek_kind(), Some(TokenKind::Identifier(s)) if s == "event")
+            && matches!(self.tokens.get(self.pos + 1).map(|t| &t.kind), Some(TokenKind::LParen));
+        if is_event {
+            self.bump();
+            self.expect(&TokenKind::LParen)?;
+            let name = self.parse_expression()?;
+            self.expect(&TokenKind::RParen)?;
+            let timeout = if matches!(self.peek_kind(), Some(TokenKind::Identifier(s)) if s == "timeout") {
                 self.bump();
+                Some(self.parse_expression()?)
             } else {
-                break;
-            }
-        }
-        Ok(params)
-    }
-
-    fn parse_type(&mut self) -> Result<Type, ParseError> {
-        match self.peek() {
-            Some(token) => match &token.kind {
-                TokenKind::Identifier(name) => {
-                    let type_name = name.clone();
-                    self.bump();
-
-                    match type_name.as_str() {
-                        "Mut" | "Ref" | "Cap" | "Grad" => {
-                            self.expect(&TokenKind::LessThan)?;
-                            let inner = self.parse_type()?;
-                            self.expect(&TokenKind::GreaterThan)?;
-                            let ty = match type_name.as_str() {
-                                "Mut" => Type::Mut(Box::new(inner)),
-                                "Ref" => Type::Ref(Box::new(inner)),
-                                "Cap" => Type::Cap(Box::new(inner)),
-                                "Grad" => Type::Grad(Box::new(inner)),
-                                _ => unreachable!(),
-                            };
-                            Ok(ty)
-                        }
-                        "Tensor" => {
-                            self.expect(&TokenKind::LessThan)?;
-                            let shape = self.parse_shape()?;
-                            self.expect(&TokenKind::Comma)?;
-                            let dtype = self.parse_dtype()?;
-                            self.expect(&TokenKind::GreaterThan)?;
-                            Ok(Type::Tensor(shape, dtype))
-                        }
-                        "DepType" => {
-                            self.expect(&TokenKind::LessThan)?;
-                            let var = self.expect_identifier()?;
-                            self.expect(&TokenKind::Colon)?;
-                            let var_type = self.parse_type()?;
-                            self.expect(&TokenKind::Pipe)?;
-                            let prop = self.parse_expression()?;
-                            self.expect(&TokenKind::GreaterThan)?;
-                            Ok(Type::DepType {
-                                var,
-                                var_type: Box::new(var_type),
-                                prop,
-                            })
-                        }
-                        "Symbolic" => {
-                            self.expect(&TokenKind::LessThan)?;
-                            let prop_expr = self.parse_expression()?;
-                            self.expect(&TokenKind::GreaterThan)?;
-                            if let Expression::Identifier(prop_name) = prop_expr {
-                                Ok(Type::Symbolic(prop_name))
-                            } else {
-                                Err(ParseError::Custom(
-                                    "Symbolic prop must be identifier".into(),
-                                ))
-                            }
-                        }
-                        "Unit" => Ok(Type::Unit),
-                        "Bool" => Ok(Type::Bool),
-                        "String" => Ok(Type::String),
-                        "Int" | "Float" => {
-                            self.expect(&TokenKind::LessThan)?;
-                            let size = self.expect_int_literal()? as u32;
-                            self.expect(&TokenKind::GreaterThan)?;
-                            if type_name == "Int" {
-                                Ok(Type::Int(size))
-                            } else {
-                                Ok(Type::Float(size))
-                            }
-                        }
-                        _ => Ok(Type::Simple(type_name)),
-                    }
-                }
-                _ => Err(ParseError::ExpectedToken("type".into())),
+                None
+            };
+            return Ok(Awaitable::Event { name, timeout });
+        }
+        match self.parse_expression()? {
+            Expression::FunctionCall { callee, args } => match *callee {
+                Expression::Identifier(callee) => Ok(Awaitable::Call { callee, args }),
+                _ => Err(ParseError::ExpectedToken("async function name".into())),
             },
-            None => Err(ParseError::UnexpectedEof),
+            _ => Err(ParseError::ExpectedToken("'event(...)' or an async function call after 'await'".into())),
         }
     }
 
-    fn expect_int_literal(&mut self) -> Result<i64, ParseError> {
-        match self.peek() {
-            Some(token) => match &token.kind {
-                TokenKind::IntLiteral(n) => {
-                    self.bump();
-                    Ok(*n)
-                }
-                _ => {
-                    let err = ParseError::ExpectedToken("integer literal".into());
-                    self.errors.push(err.clone());
-                    Err(err)
-                }
-            },
-            None => {
-                let err = ParseError::UnexpectedEof;
-                self.errors.push(err.clone());
-                Err(err)
-            }
-        }
+    fn parse_expression(&mut self) -> Result<Expression, ParseError> {
+        self.nested(Self::parse_expression_inner)
     }
 
-    fn parse_shape(&mut self) -> Result<Shape, ParseError> {
-        self.expect(&TokenKind::LBracket)?;
-        let mut dims = Vec::new();
-        loop {
-            match self.peek() {
-                Some(Token { kind: TokenKind::IntLiteral(n), .. }) => {
-                    dims.push(ShapeDim::Number(*n as u32));
-                    self.bump();
-                }
-                Some(Token { kind: TokenKind::Identifier(name), .. }) => {
-                    dims.push(ShapeDim::Identifier(name.clone()));
-                    self.bump();
-                }
-                _ => {
-                    return Err(ParseError::ExpectedToken("shape dimension".into()));
-                }
+    fn parse_expression_inner(&mut self) -> Result<Expression, ParseError> {
+        let token = match self.peek() {
+            Some(token) => token,
+            None => return Err(ParseError::UnexpectedEof),
+        };
+        match &token.kind {
+            TokenKind::Identifier(s) if s == "match" => {
+                self.bump();
+                self.parse_match()
             }
-            match self.peek() {
-                Some(Token { kind: TokenKind::Comma, .. }) => {
-                    self.bump();
-                }
-                Some(Token { kind: TokenKind::RBracket, .. }) => {
-                    self.bump();
-                    break;
-                }
-                _ => return Err(ParseError::ExpectedToken("',' or ']'".into())),
+            TokenKind::Identifier(s) if s == "symbolic" => {
+                // symbolic(expr)
+                self.bump();
+                self.expect(&TokenKind::LParen)?;
+                let sym_name = self.expect_identifier()?;
+                self.expect(&TokenKind::RParen)?;
+                Ok(Expression::Symbolic(sym_name))
             }
-        }
-        Ok(Shape(dims))
-    }
-
-    fn parse_dtype(&mut self) -> Result<DType, ParseError> {
-        match self.peek() {
-            Some(token) => match &token.kind {
-                TokenKind::Identifier(name) => {
-                    self.bump();
-                    match name.as_str() {
-                        "f32" => Ok(DType::F32),
-                        "f64" => Ok(DType::F64),
-                        "i32" => Ok(DType::I32),
-                        "i64" => Ok(DType::I64),
-                        "bool" => Ok(DType::Bool),
-                        "string" => Ok(DType::String),
-                        _ => Err(ParseError::Custom(format!("Unknown dtype: {}", name))),
-                    }
-                }
-                _ => Err(ParseError::ExpectedToken("dtype".into())),
-            },
-            None => Err(ParseError::UnexpectedEof),
-        }
-    }
-
-    fn parse_effect_annotation(&mut self) -> Result<Vec<String>, ParseError> {
-        self.expect(&TokenKind::LBrace)?;
-        let mut effects = Vec::new();
-        loop {
-            match self.peek() {
-                Some(Token { kind: TokenKind::Identifier(name), .. }) => {
-                    effects.push(name.clone());
-                    self.bump();
-                }
-                Some(Token { kind: TokenKind::Comma, .. }) => {
-                    self.bump();
-                }
-                Some(Token { kind: TokenKind::RBrace, .. }) => {
-                    self.bump();
-                    break;
+            TokenKind::Identifier(s) if s == "modify" => {
+                // modify(target, patch)
+                self.bump();
+                self.expect(&TokenKind::LParen)?;
+                let target = self.expect_identifier()?;
+                self.expect(&TokenKind::Comma)?;
+                let patch = self.parse_expression()?;
+                self.expect(&TokenKind::RParen)?;
+                Ok(Expression::SelfModify {
+                    target,
+                    patch: Box::new(patch),
+                })
+            }
+            TokenKind::Identifier(name) => {
+                self.bump();
+                // Check for function call
+                if let Some(TokenKind::LParen) = self.peek_kind() {
+                    self.bump();
+                    let args = self.parse_arg_list()?;
+                    self.expect(&TokenKind::RParen)?;
+                    Ok(Expression::FunctionCall {
+                        callee: Box::new(Expression::Identifier(name.clone())),
+                        args,
+                    })
+                } else {
+                    Ok(Expression::Identifier(name.clone()))
                 }
-                _ => return Err(ParseError::ExpectedToken("effect name or '}'".into())),
             }
-        }
-        Ok(effects)
-    }
-
-    fn parse_block(&mut self) -> Result<Block, ParseError> {
-        self.expect(&TokenKind::LBrace)?;
-        let mut statements = Vec::new();
-        while let Some(token) = self.peek() {
-            if token.kind == TokenKind::RBrace {
+            TokenKind::IntLiteral(n) => {
                 self.bump();
-                break;
+                Ok(Expression::IntLiteral(*n))
             }
-            match self.parse_statement() {
-                Ok(stmt) => statements.push(stmt),
-                Err(err) => {
-                    self.errors.push(err);
-                    self.recover_statement();
-                }
+            TokenKind::FloatLiteral(f) => {
+                self.bump();
+                Ok(Expression::FloatLiteral(*f))
+            }
+            TokenKind::StringLiteral(s) => {
+                self.bump();
+                Ok(Expression::StringLiteral(s.clone()))
             }
+            TokenKind::BoolLiteral(b) => {
+                self.bump();
+                Ok(Expression::BoolLiteral(*b))
+            }
+            TokenKind::LBrace => {
+                let block = self.parse_block()?;
+                Ok(Expression::Block(block))
+            }
+            _ => Err(self.unexpected()),
         }
-        Ok(Block { statements })
     }
 
-    fn recover_statement(&mut self) {
-        // Skip tokens until semicolon or block end
-        while let Some(token) = self.peek() {
-            if token.kind == TokenKind::Semicolon || token.kind == TokenKind::RBrace {
-                if token.kind == TokenKind::Semicolon {
-                    self.bump();
-                }
+    fn parse_match(&mut self) -> Result<Expression, ParseError> {
+        let expr = self.parse_expression()?;
+        self.expect(&TokenKind::LBrace)?;
+        let mut arms = Vec::new();
+        loop {
+            if let Some(TokenKind::RBrace) = self.peek_kind() {
+                self.bump();
                 break;
             }
-            self.bump();
-        }
-    }
-
-    fn parse_statement(&mut self) -> Result<Statement, ParseError> {
-        match self.peek() {
-            Some(token) => match &token.kind {
-                TokenKind::Identifier(s) if s == "let" => {
-                    self.bump();
-                    let name = self.expect_identifier()?;
-                    let expr = if let Some(Token { kind: TokenKind::Equal, .. }) = self.peek() {
-                        self.bump();
-                        Some(self.parse_expression()?)
-                    } else {
-                        None
-                    };
-                    self.expect(&TokenKind::Semicolon)?;
-                    Ok(Statement::LetBinding { name, expr })
-                }
-                TokenKind::Identifier(s) if s == "return" => {
-                    self.bump();
-                    let expr = self.parse_expression()?;
-                    self.expect(&TokenKind::Semicolon)?;
-                    Ok(Statement::Return(expr))
-                }
-                TokenKind::Identifier(s) if s == "backtrack" => {
-                    self.bump();
-                    let block = self.parse_block()?;
-                    Ok(Statement::Backtrack(block))
-                }
-                _ => {
-                    let expr = self.parse_expression()?;
-                    self.expect(&TokenKind::Semicolon)?;
-                    Ok(Statement::Expr(expr))
-                }
-            },
-            None => Err(ParseError::UnexpectedEof),
-        }
-    }
-
-    fn parse_expression(&mut self) -> Result<Expression, ParseError> {
-        match self.peek() {
-            Some(token) => match &token.kind {
-                TokenKind::Identifier(name) => {
-                    self.bump();
-                    // Check for function call
-                    if let Some(Token { kind: TokenKind::LParen, .. }) = self.peek() {
-                        self.bump();
-                        let args = self.parse_arg_list()?;
-                        self.expect(&TokenKind::RParen)?;
-                        Ok(Expression::FunctionCall {
-                            callee: Box::new(Expression::Identifier(name.clone())),
-                            args,
-                        })
-                    } else if name == "symbolic" {
-                        self.expect(&TokenKind::LParen)?;
-                        let sym_name = self.expect_identifier()?;
-                        self.expect(&TokenKind::RParen)?;
-                        Ok(Expression::Symbolic(sym_name))
-                    } else if name == "modify" {
-                        self.expect(&TokenKind::LParen)?;
-                        let target = self.expect_identifier()?;
-                        self.expect(&TokenKind::Comma)?;
-                        let patch = self.parse_expression()?;
-                        self.expect(&TokenKind::RParen)?;
-                        Ok(Expression::SelfModify {
-                            target,
-                            patch: Box::new(patch),
-                        })
-                    } else {
-                        Ok(Expression::Identifier(name.clone()))
-                    }
-                }
-                TokenKind::IntLiteral(n) => {
-                    self.bump();
-                    Ok(Expression::IntLiteral(*n))
-                }
-                TokenKind::FloatLiteral(f) => {
-                    self.bump();
-                    Ok(Expression::FloatLiteral(*f))
-                }
-                TokenKind::StringLiteral(s) => {
-                    self.bump();
-                    Ok(Expression::StringLiteral(s.clone()))
-                }
-                TokenKind::BoolLiteral(b) => {
-                    self.bump();
-                    Ok(Expression::BoolLiteral(*b))
-                }
-                TokenKind::LBrace => {
-                    let block = self.parse_block()?;
-                    Ok(Expression::Block(block))
-                }
-                TokenKind::Identifier(s) if s == "match" => {
-                    self.bump();
-                    let expr = self.parse_expression()?;
-                    self.expect(&TokenKind::LBrace)?;
-                    let mut arms = Vec::new();
-                    while let Some(token) = self.peek() {
-                        if token.kind == TokenKind::RBrace {
-                            self.bump();
-                            break;
-                        }
-                        let pat = self.parse_pattern()?;
-                        self.expect(&TokenKind::ArrowFat)?;
-                        let arm_expr = self.parse_expression()?;
-                        self.expect(&TokenKind::Semicolon)?;
-                        arms.push(MatchArm {
-                            pattern: pat,
-                            expr: arm_expr,
-                        });
-                    }
-                    Ok(Expression::Match {
-                        expr: Box::new(expr),
-                        arms,
-                    })
-                }
-                _ => Err(ParseError::UnexpectedToken(token.kind.clone())),
-            },
-            None => Err(ParseError::UnexpectedEof),
+            let pattern = self.parse_pattern()?;
+            self.expect(&TokenKind::ArrowFat)?;
+            let arm_expr = self.parse_expression()?;
+            self.expect(&TokenKind::Semicolon)?;
+            arms.push(MatchArm { pattern, expr: arm_expr });
         }
+        Ok(Expression::Match {
+            expr: Box::new(expr),
+            arms,
+        })
     }
 
     fn parse_arg_list(&mut self) -> Result<Vec<Expression>, ParseError> {
         let mut args = Vec::new();
         loop {
-            if let Some(Token { kind: TokenKind::RParen, .. }) = self.peek() {
+            if let Some(TokenKind::RParen) = self.peek_kind() {
                 break;
             }
-            let expr = self.parse_expression()?;
-            args.push(expr);
-            if let Some(Token { kind: TokenKind::Comma, .. }) = self.peek() {
+            args.push(self.parse_expression()?);
+            if let Some(TokenKind::Comma) = self.peek_kind() {
                 self.bump();
             } else {
                 break;
@@ -1211,124 +727,150 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_pattern(&mut self) -> Result<Pattern, ParseError> {
-        match self.peek() {
-            Some(token) => match &token.kind {
-                TokenKind::Identifier(name) => {
-                    self.bump();
-                    if let Some(Token { kind: TokenKind::LParen, .. }) = self.peek() {
-                        self.bump();
-                        let mut args = Vec::new();
-                        while let Some(token) = self.peek() {
-                            if token.kind == TokenKind::RParen {
-                                self.bump();
-                                break;
-                            }
-                            let pat = self.parse_pattern()?;
-                            args.push(pat);
-                            if let Some(Token { kind: TokenKind::Comma, .. }) = self.peek() {
-                                self.bump();
-                            } else {
-                                continue;
-                            }
-                        }
-                        Ok(Pattern::Constructor {
-                            name: name.clone(),
-                            args,
-                        })
-                    } else {
-                        Ok(Pattern::Identifier(name.clone()))
-                    }
-                }
-                TokenKind::Underscore => {
-                    self.bump();
-                    Ok(Pattern::Wildcard)
-                }
-                TokenKind::IntLiteral(n) => {
-                    self.bump();
-                    Ok(Pattern::Literal(Expression::IntLiteral(*n)))
-                }
-                TokenKind::StringLiteral(s) => {
-                    self.bump();
-                    Ok(Pattern::Literal(Expression::StringLiteral(s.clone())))
-                }
-                TokenKind::BoolLiteral(b) => {
+        self.nested(Self::parse_pattern_inner)
+    }
+
+    fn parse_pattern_inner(&mut self) -> Result<Pattern, ParseError> {
+        let token = match self.peek() {
+            Some(token) => token,
+            None => return Err(ParseError::UnexpectedEof),
+        };
+        match &token.kind {
+            TokenKind::Identifier(name) => {
+                self.bump();
+                // Check for constructor pattern with args
+                if let Some(TokenKind::LParen) = self.peek_kind() {
                     self.bump();
-                    Ok(Pattern::Literal(Expression::BoolLiteral(*b)))
+                    let args = self.parse_pattern_list()?;
+                    Ok(Pattern::Constructor {
+                        name: name.clone(),
+                        args,
+                    })
+                } else {
+                    Ok(Pattern::Identifier(name.clone()))
                 }
-                TokenKind::LParen => {
+            }
+            TokenKind::Underscore => {
+                self.bump();
+                Ok(Pattern::Wildcard)
+            }
+            TokenKind::IntLiteral(n) => {
+                self.bump();
+                Ok(Pattern::Literal(Expression::IntLiteral(*n)))
+            }
+            TokenKind::FloatLiteral(f) => {
+                self.bump();
+                Ok(Pattern::Literal(Expression::FloatLiteral(*f)))
+            }
+            TokenKind::StringLiteral(s) => {
+                self.bump();
+                Ok(Pattern::Literal(Expression::StringLiteral(s.clone())))
+            }
+            TokenKind::BoolLiteral(b) => {
+                self.bump();
+                Ok(Pattern::Literal(Expression::BoolLiteral(*b)))
+            }
+            TokenKind::LParen => {
+                self.bump();
+                Ok(Pattern::Tuple(self.parse_pattern_list()?))
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    /// Comma-separated patterns up to and including the closing ')'.
+    fn parse_pattern_list(&mut self) -> Result<Vec<Pattern>, ParseError> {
+        let mut patterns = Vec::new();
+        loop {
+            if let Some(TokenKind::RParen) = self.peek_kind() {
+                self.bump();
+                return Ok(patterns);
+            }
+            patterns.push(self.parse_pattern()?);
+            match self.peek_kind() {
+                Some(TokenKind::Comma) => {
                     self.bump();
-                    let mut pats = Vec::new();
-                    while let Some(token) = self.peek() {
-                        if token.kind == TokenKind::RParen {
-                            self.bump();
-                            break;
-                        }
-                        let pat = self.parse_pattern()?;
-                        pats.push(pat);
-                        if let Some(Token { kind: TokenKind::Comma, .. }) = self.peek() {
-                            self.bump();
-                        }
-                    }
-                    Ok(Pattern::Tuple(pats))
                 }
-                _ => Err(ParseError::UnexpectedToken(token.kind.clone())),
-            },
-            None => Err(ParseError::UnexpectedEof),
+                Some(TokenKind::RParen) => {}
+                _ => return Err(ParseError::ExpectedToken("',' or ')'".into())),
+            }
         }
     }
 
     fn parse_intent_decl(&mut self) -> Result<IntentDecl, ParseError> {
-        self.expect(&TokenKind::Identifier("intent".into()))?;
+        self.expect(&TokenKind::Intent)?;
+        let span = self.current_span();
         let name = self.expect_identifier()?;
         self.expect(&TokenKind::LBrace)?;
 
         let mut motive = None;
         let mut action = None;
 
-        while let Some(token) = self.peek() {
-            match &token.kind {
-                TokenKind::Identifier(s) if s == "motive" => {
+        loop {
+            match self.peek_kind() {
+                Some(TokenKind::Motive) => {
                     self.bump();
-                    if let Some(Token { kind: TokenKind::StringLiteral(val), .. }) = self.bump() {
-                        motive = Some(val.clone());
-                    } else {
-                        return Err(ParseError::ExpectedToken("string literal".into()));
-                    }
+                    motive = Some(self.expect_string_literal()?);
                 }
-                TokenKind::Identifier(s) if s == "action" => {
+                Some(TokenKind::Action) => {
                     self.bump();
-                    if let Some(Token { kind: TokenKind::StringLiteral(val), .. }) = self.bump() {
-                        action = Some(val.clone());
-                    } else {
-                        return Err(ParseError::ExpectedToken("string literal".into()));
-                    }
+                    action = Some(self.expect_string_literal()?);
                 }
-                TokenKind::RBrace => {
+                Some(TokenKind::RBrace) => {
                     self.bump();
                     break;
                 }
-                _ => return Err(ParseError::UnexpectedToken(token.kind.clone())),
+                _ => return Err(self.unexpected()),
             }
         }
 
-        Ok(IntentDecl { name, motive, action })
+        Ok(IntentDecl { name, span, motive, action })
     }
 
     fn parse_rule_decl(&mut self) -> Result<RuleDecl, ParseError> {
-        self.expect(&TokenKind::Identifier("rule".into()))?;
+        self.expect_keyword("rule")?;
+        let span = self.current_span();
         let name = self.expect_identifier()?;
         self.expect(&TokenKind::LParen)?;
         let params = self.parse_param_list()?;
         self.expect(&TokenKind::RParen)?;
-        let ret_type = if let Some(Token { kind: TokenKind::Arrow, .. }) = self.peek() {
-            self.bump();
-            Some(self.parse_type()?)
-        } else {
-            None
-        };
+        let ret_type = self.parse_return_type()?;
         let body = self.parse_block()?;
-        Ok(RuleDecl { name, params, ret_type, body })
+        Ok(RuleDecl { name, span, params, ret_type, body })
     }
 }
 
-                    
+/// Lexes and parses `source`, returning the declarations that parsed and
+/// the diagnostics from both passes, in source order.
+pub fn parse_source(source: &str) -> (Vec<AstNode>, Vec<Diagnostic>) {
+    let (tokens, mut diagnostics) = crate::lexer::tokenize(source);
+    let mut parser = Parser::new(&tokens);
+    let nodes = parser.parse_program();
+    diagnostics.extend(parser.into_diagnostics());
+    diagnostics.sort_by_key(|d| d.span.start);
+    (nodes, diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errors_are_recovered_with_positions() {
+        let source = "intent greet {\n  motive \"kindness\"\n}\nfn broken() {\n  let = 4;\n  ok();\n}\nrule r() { yes(); }";
+        let (nodes, diagnostics) = parse_source(source);
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!((diagnostics[0].line(), diagnostics[0].column()), (5, 7));
+        match &nodes[1] {
+            AstNode::Function(f) => assert_eq!(f.body.statements.len(), 1),
+            other => panic!("expected a function, got {:?}", other),
+        }
+
+        // Unbalanced and absurdly deep input is diagnosed, not a crash.
+        assert!(!parse_source("fn f() { g(").1.is_empty());
+        let deep = format!("fn f() {{ {} }}", "{".repeat(10_000));
+        let (_, diagnostics) = parse_source(&deep);
+        assert!(diagnostics.iter().any(|d| d.message.contains("nesting")));
+    }
+}
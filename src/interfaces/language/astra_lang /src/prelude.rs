@@ -0,0 +1,51 @@
+// =============================================================================
+// Astra Reference Compiler (ARC)
+// File: prelude.rs
+//
+// Description:
+//     Declarations every Astra program sees without importing them. They are
+//     written in Astra and parsed once:
+//       - type Wait = Fired(String) | TimedOut;
+//         the result of `await event(name) timeout ms`
+//
+// Intent:
+//     - Give language features a typed result that programs `match` on.
+//
+// Author: Alex Roussinov
+// Created: 2026-10-16
+// Updated: 2026-10-16
+// =============================================================================
+
+use std::sync::OnceLock;
+
+use crate::ast::AstNode;
+use crate::parser::parse_source;
+
+/// Source of the prelude.
+pub const SOURCE: &str = "type Wait = Fired(String) | TimedOut;";
+
+/// Constructor of a `Wait` whose event arrived, carrying its payload.
+pub const FIRED: &str = "Fired";
+/// Constructor of a `Wait` whose timeout passed first.
+pub const TIMED_OUT: &str = "TimedOut";
+/// Type of `await event(name) timeout ms`.
+pub const WAIT: &str = "Wait";
+
+/// The prelude's declarations.
+pub fn items() -> &'static [AstNode] {
+    static ITEMS: OnceLock<Vec<AstNode>> = OnceLock::new();
+    ITEMS.get_or_init(|| parse_source(SOURCE).0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prelude_parses_cleanly() {
+        let (items, diagnostics) = parse_source(SOURCE);
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items, super::items());
+    }
+}
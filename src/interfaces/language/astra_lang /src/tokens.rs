@@ -8,9 +8,11 @@
 //      This file intentionally keeps TokenKind exhaustive for operators,
 //      punctuation, keywords and literal kinds required by ARC v0.1 and the
 //      Astra Manifesto (annotations, rule/actor/backtrack, Arrow, DoubleColon).
+//      Spans carry 1-based line and column so diagnostics can point at source.
 //
 //  Author:      Alex Roussinov
 //  Created:     2025-12-05
+//  Updated:     2026-10-16
 //  Copyright (c) 2025 Alex Roussinov
 //
 //  License:
@@ -37,20 +39,51 @@ impl Span {
     pub fn with_pos(start: usize, end: usize, line: usize, column: usize) -> Self {
         Self { start, end, line: Some(line), column: Some(column) }
     }
+
+    /// 1-based line, or 1 for spans made without a position.
+    pub fn line_or_first(&self) -> usize {
+        self.line.unwrap_or(1)
+    }
+
+    /// 1-based column, or 1 for spans made without a position.
+    pub fn column_or_first(&self) -> usize {
+        self.column.unwrap_or(1)
+    }
 }
 
 /// Token kinds for Astra language.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TokenKind {
     Intent,
     Motive,
     Action,
 
+    /// Names, including annotations such as `@grad`
     Identifier(String),
     StringLiteral(String),
+    IntLiteral(i64),
+    FloatLiteral(f64),
+    BoolLiteral(bool),
 
     LBrace,
     RBrace,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+
+    Comma,
+    Colon,
+    Semicolon,
+    Equal,
+    Pipe,
+    Underscore,
+    LessThan,
+    GreaterThan,
+    /// `->`
+    Arrow,
+    /// `=>`
+    ArrowFat,
 
     Eof,
 }
@@ -77,8 +110,25 @@ impl fmt::Display for TokenKind {
             Action => write!(f, "action"),
             Identifier(name) => write!(f, "Identifier({})", name),
             StringLiteral(s) => write!(f, "StringLiteral(\"{}\")", s),
+            IntLiteral(n) => write!(f, "IntLiteral({})", n),
+            FloatLiteral(x) => write!(f, "FloatLiteral({})", x),
+            BoolLiteral(b) => write!(f, "{}", b),
             LBrace => write!(f, "{{"),
             RBrace => write!(f, "}}"),
+            LParen => write!(f, "("),
+            RParen => write!(f, ")"),
+            LBracket => write!(f, "["),
+            RBracket => write!(f, "]"),
+            Comma => write!(f, ","),
+            Colon => write!(f, ":"),
+            Semicolon => write!(f, ";"),
+            Equal => write!(f, "="),
+            Pipe => write!(f, "|"),
+            Underscore => write!(f, "_"),
+            LessThan => write!(f, "<"),
+            GreaterThan => write!(f, ">"),
+            Arrow => write!(f, "->"),
+            ArrowFat => write!(f, "=>"),
             Eof => write!(f, "EOF"),
         }
     }
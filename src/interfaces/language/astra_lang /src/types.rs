@@ -12,12 +12,15 @@
 //
 // Author: Alex Roussinov
 // Created: 2025-12-22
+// Updated: 2026-10-16
 // =============================================================================
 
 use crate::ast::Expression;
+use std::fmt;
 
 /// Astra type enumeration capturing all language-level types.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Not `Eq`: dependent types embed expressions, which may hold floats.
+#[derive(Debug, Clone, PartialEq)]
 pub enum Type {
     Unit,
     Bool,
@@ -54,6 +57,27 @@ pub enum Type {
     Simple(String),
 }
 
+/// Written the way the type is spelled in source, for diagnostics.
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Unit => write!(f, "Unit"),
+            Type::Bool => write!(f, "Bool"),
+            Type::Int(bits) => write!(f, "Int<{}>", bits),
+            Type::Float(bits) => write!(f, "Float<{}>", bits),
+            Type::String => write!(f, "String"),
+            Type::Tensor(shape, dtype) => write!(f, "Tensor<{:?}, {:?}>", shape.0, dtype),
+            Type::Mut(inner) => write!(f, "Mut<{}>", inner),
+            Type::Ref(inner) => write!(f, "Ref<{}>", inner),
+            Type::Cap(inner) => write!(f, "Cap<{}>", inner),
+            Type::Symbolic(prop) => write!(f, "Symbolic<{}>", prop),
+            Type::Grad(inner) => write!(f, "Grad<{}>", inner),
+            Type::DepType { var, var_type, .. } => write!(f, "DepType<{}: {} | ...>", var, var_type),
+            Type::Simple(name) => write!(f, "{}", name),
+        }
+    }
+}
+
 /// Tensor shape: list of dimensions (numbers or identifiers)
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Shape(pub Vec<ShapeDim>);
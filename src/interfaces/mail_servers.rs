@@ -0,0 +1,276 @@
+// ============================================================================
+//                     ASTRA AGI • IMAP & SMTP MAIL SERVERS
+//        Standard Mail Servers Behind the Email Interface
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Interfaces Layer that connects the email
+//       interface to ordinary mail servers. `ImapSource` keeps a TLS
+//       session to an IMAP server open and polls one mailbox for messages
+//       that arrived since it connected, leaving them unread; `SmtpSender`
+//       submits replies over SMTP with threading headers so they land in
+//       the sender's conversation. Server lines and literals are read
+//       only up to a size cap, so a hostile or broken server cannot make
+//       the client allocate without bound.
+//
+//   Core Functions:
+//       • Poll an IMAP mailbox for new messages and decode them
+//       • Send drafted replies over SMTP
+//
+//   File:        /src/interfaces/mail_servers.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use chrono::{DateTime, Utc};
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use log::info;
+use mail_parser::MessageParser;
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, ClientConnection, RootCertStore, StreamOwned};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::interfaces::email::{EmailDraft, EmailError, EmailMessage, MailSender, MailSource};
+
+fn imap_error(error: impl std::error::Error + Send + Sync + 'static) -> EmailError {
+    EmailError::Transport { server: "imap", source: Box::new(error) }
+}
+
+fn imap_protocol(message: String) -> EmailError {
+    imap_error(std::io::Error::new(std::io::ErrorKind::InvalidData, message))
+}
+
+fn smtp_error(error: impl std::error::Error + Send + Sync + 'static) -> EmailError {
+    EmailError::Transport { server: "smtp", source: Box::new(error) }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImapConfig {
+    pub host: String,
+    #[serde(default = "default_imap_port")]
+    pub port: u16,
+    pub user: String,
+    pub password: String,
+    #[serde(default = "default_mailbox")]
+    pub mailbox: String,
+}
+
+fn default_imap_port() -> u16 {
+    993
+}
+
+fn default_mailbox() -> String {
+    "INBOX".into()
+}
+
+/// Largest literal, such as a whole message, the client accepts.
+pub const MAX_LITERAL_BYTES: usize = 1 << 20;
+
+/// Longest response line the client accepts, literals aside.
+const MAX_LINE_BYTES: u64 = 64 * 1024;
+
+/// A server response line, with the literal that followed it if any.
+struct ResponseLine {
+    text: String,
+    literal: Option<Vec<u8>>,
+}
+
+/// An open IMAP session on one mailbox.
+pub struct ImapSource {
+    stream: BufReader<StreamOwned<ClientConnection, TcpStream>>,
+    next_tag: u32,
+    /// Highest uid already seen
+    last_uid: u32,
+}
+
+impl ImapSource {
+    /// Logs in and selects the mailbox. Mail already in it is not reported.
+    pub fn connect(config: &ImapConfig) -> Result<Self, EmailError> {
+        let roots = RootCertStore { roots: webpki_roots::TLS_SERVER_ROOTS.to_vec() };
+        let tls = ClientConfig::builder().with_root_certificates(roots).with_no_client_auth();
+        let name = ServerName::try_from(config.host.clone()).map_err(imap_error)?;
+        let connection = ClientConnection::new(Arc::new(tls), name).map_err(imap_error)?;
+        let socket = TcpStream::connect((config.host.as_str(), config.port)).map_err(imap_error)?;
+        socket.set_read_timeout(Some(Duration::from_secs(30))).map_err(imap_error)?;
+
+        let mut source = Self {
+            stream: BufReader::new(StreamOwned::new(connection, socket)),
+            next_tag: 0,
+            last_uid: 0,
+        };
+        source.read_line()?; // greeting
+        source.command(&format!("LOGIN {} {}", quote(&config.user), quote(&config.password)))?;
+        let selected = source.command(&format!("SELECT {}", quote(&config.mailbox)))?;
+        source.last_uid = selected
+            .iter()
+            .find_map(|line| line.text.split("[UIDNEXT ").nth(1)?.split(']').next()?.parse::<u32>().ok())
+            .map_or(0, |next| next.saturating_sub(1));
+        info!("Watching IMAP mailbox {} on {}", config.mailbox, config.host);
+        Ok(source)
+    }
+
+    fn read_line(&mut self) -> Result<ResponseLine, EmailError> {
+        let mut line = String::new();
+        if (&mut self.stream).take(MAX_LINE_BYTES).read_line(&mut line).map_err(imap_error)? == 0 {
+            return Err(imap_protocol("connection closed".into()));
+        }
+        if !line.ends_with('\n') {
+            return Err(imap_protocol(format!("response line longer than {} bytes", MAX_LINE_BYTES)));
+        }
+        let text = line.trim_end().to_string();
+        // `... {123}` announces a literal of 123 bytes, then the line goes on
+        let literal_len = text.strip_suffix('}').and_then(|t| t.rsplit_once('{')).and_then(|(_, n)| n.parse().ok());
+        let Some(len) = literal_len else {
+            return Ok(ResponseLine { text, literal: None });
+        };
+        if len > MAX_LITERAL_BYTES {
+            return Err(imap_protocol(format!("literal of {} bytes exceeds the {} byte limit", len, MAX_LITERAL_BYTES)));
+        }
+        let mut literal = vec![0; len];
+        self.stream.read_exact(&mut literal).map_err(imap_error)?;
+        let rest = self.read_line()?;
+        Ok(ResponseLine { text: format!("{} {}", text, rest.text), literal: Some(literal) })
+    }
+
+    /// Sends a command and returns its untagged responses.
+    fn command(&mut self, command: &str) -> Result<Vec<ResponseLine>, EmailError> {
+        self.next_tag += 1;
+        let tag = format!("A{}", self.next_tag);
+        let stream = self.stream.get_mut();
+        stream.write_all(format!("{} {}\r\n", tag, command).as_bytes()).map_err(imap_error)?;
+        stream.flush().map_err(imap_error)?;
+
+        let mut responses = Vec::new();
+        loop {
+            let line = self.read_line()?;
+            if let Some(status) = line.text.strip_prefix(&format!("{} ", tag)) {
+                if status.starts_with("OK") {
+                    return Ok(responses);
+                }
+                let verb = command.split(' ').next().unwrap_or(command);
+                return Err(imap_protocol(format!("{} failed: {}", verb, status)));
+            }
+            responses.push(line);
+        }
+    }
+}
+
+/// An IMAP quoted string.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Decodes a raw RFC 5322 message.
+pub fn decode_message(uid: u32, raw: &[u8]) -> Option<EmailMessage> {
+    let parsed = MessageParser::default().parse(raw)?;
+    let from = parsed.from().and_then(|from| from.first()).and_then(|address| address.address())?;
+    Some(EmailMessage {
+        uid,
+        message_id: parsed.message_id().map(|id| format!("<{}>", id)),
+        from: from.to_string(),
+        subject: parsed.subject().unwrap_or_default().to_string(),
+        body: parsed.body_text(0).map(|body| body.into_owned()).unwrap_or_default(),
+        received: parsed
+            .date()
+            .and_then(|date| DateTime::<Utc>::from_timestamp(date.to_timestamp(), 0))
+            .unwrap_or_else(Utc::now),
+    })
+}
+
+impl MailSource for ImapSource {
+    fn fetch_new(&mut self) -> Result<Vec<EmailMessage>, EmailError> {
+        // `n:*` always matches the newest message, even below `n`
+        let search = self.command(&format!("UID SEARCH UID {}:*", self.last_uid + 1))?;
+        let mut uids: Vec<u32> = search
+            .iter()
+            .filter_map(|line| line.text.strip_prefix("* SEARCH"))
+            .flat_map(|uids| uids.split_whitespace().filter_map(|uid| uid.parse().ok()))
+            .filter(|uid| *uid > self.last_uid)
+            .collect();
+        uids.sort_unstable();
+
+        let mut messages = Vec::new();
+        for uid in uids {
+            let fetched = self.command(&format!("UID FETCH {} BODY.PEEK[]", uid))?;
+            let raw = fetched.into_iter().find_map(|line| line.literal);
+            match raw.as_deref().and_then(|raw| decode_message(uid, raw)) {
+                Some(message) => messages.push(message),
+                None => info!("Skipped undecodable email {}", uid),
+            }
+            self.last_uid = uid;
+        }
+        Ok(messages)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub user: String,
+    pub password: String,
+    /// Address replies are sent from, e.g. `Astra <astra@example.org>`
+    pub from: String,
+}
+
+/// Submits replies to an SMTP server over TLS.
+pub struct SmtpSender {
+    transport: SmtpTransport,
+    from: Mailbox,
+}
+
+impl SmtpSender {
+    pub fn new(config: &SmtpConfig) -> Result<Self, EmailError> {
+        let transport = SmtpTransport::relay(&config.host)
+            .map_err(smtp_error)?
+            .credentials(Credentials::new(config.user.clone(), config.password.clone()))
+            .build();
+        Ok(Self { transport, from: config.from.parse().map_err(smtp_error)? })
+    }
+}
+
+impl MailSender for SmtpSender {
+    fn send(&mut self, draft: &EmailDraft) -> Result<(), EmailError> {
+        let mut message = Message::builder()
+            .from(self.from.clone())
+            .to(draft.to.parse().map_err(smtp_error)?)
+            .subject(draft.subject.clone());
+        if let Some(id) = &draft.in_reply_to {
+            message = message.in_reply_to(id.clone()).references(id.clone());
+        }
+        let message = message.body(draft.body.clone()).map_err(smtp_error)?;
+        self.transport.send(&message).map_err(smtp_error)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_messages_decode_to_plain_text() {
+        let raw = b"From: Ana <ana@example.org>\r\n\
+            Subject: Slides\r\n\
+            Message-ID: <slides@example.org>\r\n\
+            Date: Wed, 14 Oct 2026 09:00:00 +0000\r\n\
+            Content-Type: text/plain; charset=utf-8\r\n\r\n\
+            Could you send them?\r\n";
+        let message = decode_message(3, raw).unwrap();
+        assert_eq!(message.from, "ana@example.org");
+        assert_eq!(message.subject, "Slides");
+        assert_eq!(message.message_id.as_deref(), Some("<slides@example.org>"));
+        assert_eq!(message.body.trim(), "Could you send them?");
+        assert_eq!(message.received.to_rfc3339(), "2026-10-14T09:00:00+00:00");
+        assert_eq!(quote(r#"pa"ss"#), r#""pa\"ss""#);
+    }
+}
@@ -0,0 +1,232 @@
+// ============================================================================
+//                      ASTRA AGI • MATRIX TRANSPORT
+//        Matrix Client-Server API Behind the Chat Connector
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Interfaces Layer that logs Astra into a Matrix
+//       homeserver as an ordinary user, joins the configured rooms, and
+//       implements `ChatTransport` so a `ChatConnector` can answer what is
+//       said there. A configuration looks like:
+//
+//           { "homeserver": "https://matrix.example.org",
+//             "user": "astra", "password": "…",
+//             "rooms": ["!lobby:example.org", "#help:example.org"] }
+//
+//       Syncs are short polls, so the connector decides how often to check.
+//       Messages sent before Astra logged in are skipped, as are its own.
+//       Other networks such as Discord plug in by implementing the same
+//       trait.
+//
+//   Core Functions:
+//       • Log in and join rooms with the client-server API (v3)
+//       • Turn synced `m.room.message` events into chat messages
+//       • Send replies and typing notifications
+//
+//   File:        /src/interfaces/matrix.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use crate::interfaces::chat::{ChatError, ChatMessage, ChatTransport};
+
+const NETWORK: &str = "matrix";
+/// How long a typing notification lasts unless renewed.
+const TYPING_TIMEOUT_MS: u64 = 30_000;
+
+/// Account and rooms Astra uses on a homeserver.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatrixConfig {
+    pub homeserver: String,
+    pub user: String,
+    pub password: String,
+    /// Room ids or aliases to join
+    #[serde(default)]
+    pub rooms: Vec<String>,
+}
+
+impl MatrixConfig {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+fn transport_error(error: impl std::error::Error + Send + Sync + 'static) -> ChatError {
+    ChatError::Transport { network: NETWORK, source: Box::new(error) }
+}
+
+/// Percent-encodes a room id, alias, or user id for use in a URL path.
+fn encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// The messages from others in a `/sync` response.
+fn messages_in(sync: &Value, own_user_id: &str) -> Vec<ChatMessage> {
+    let Some(rooms) = sync.pointer("/rooms/join").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let mut messages = Vec::new();
+    for (room, joined) in rooms {
+        let events = joined.pointer("/timeline/events").and_then(Value::as_array);
+        for event in events.into_iter().flatten() {
+            let sender = event["sender"].as_str().unwrap_or_default();
+            if event["type"] != "m.room.message" || sender == own_user_id {
+                continue;
+            }
+            let Some(body) = event.pointer("/content/body").and_then(Value::as_str) else {
+                continue;
+            };
+            // `@ana:example.org` is addressed as "ana"
+            let name = sender.trim_start_matches('@').split(':').next().unwrap_or(sender);
+            messages.push(ChatMessage {
+                room: room.clone(),
+                sender: sender.to_string(),
+                sender_name: name.to_string(),
+                text: body.to_string(),
+            });
+        }
+    }
+    messages
+}
+
+/// A logged-in Matrix session.
+pub struct MatrixTransport {
+    agent: ureq::Agent,
+    homeserver: String,
+    access_token: String,
+    user_id: String,
+    /// Sync token; messages before it have been seen
+    since: Option<String>,
+    next_txn: u64,
+}
+
+impl MatrixTransport {
+    /// Logs in with a password, joins the configured rooms, and skips the
+    /// history already in them.
+    pub fn login(config: &MatrixConfig) -> Result<Self, ChatError> {
+        let agent = ureq::AgentBuilder::new().timeout(Duration::from_secs(30)).build();
+        let homeserver = config.homeserver.trim_end_matches('/').to_string();
+        let login: Value = agent
+            .post(&format!("{}/_matrix/client/v3/login", homeserver))
+            .send_json(json!({
+                "type": "m.login.password",
+                "identifier": { "type": "m.id.user", "user": config.user },
+                "password": config.password,
+            }))
+            .map_err(transport_error)?
+            .into_json()
+            .map_err(transport_error)?;
+
+        let mut transport = Self {
+            agent,
+            homeserver,
+            access_token: login["access_token"].as_str().unwrap_or_default().to_string(),
+            user_id: login["user_id"].as_str().unwrap_or_default().to_string(),
+            since: None,
+            next_txn: 0,
+        };
+        info!("Logged into Matrix as {}", transport.user_id);
+        for room in &config.rooms {
+            transport.call("POST", &format!("join/{}", encode(room)), json!({}))?;
+        }
+        transport.sync()?;
+        Ok(transport)
+    }
+
+    pub fn user_id(&self) -> &str {
+        &self.user_id
+    }
+
+    fn call(&self, method: &str, path: &str, body: Value) -> Result<Value, ChatError> {
+        self.agent
+            .request(method, &format!("{}/_matrix/client/v3/{}", self.homeserver, path))
+            .set("Authorization", &format!("Bearer {}", self.access_token))
+            .send_json(body)
+            .map_err(transport_error)?
+            .into_json()
+            .map_err(transport_error)
+    }
+
+    /// Fetches events since the last sync and advances the sync token.
+    fn sync(&mut self) -> Result<Value, ChatError> {
+        let mut request = self
+            .agent
+            .get(&format!("{}/_matrix/client/v3/sync", self.homeserver))
+            .set("Authorization", &format!("Bearer {}", self.access_token))
+            .query("timeout", "0");
+        if let Some(since) = &self.since {
+            request = request.query("since", since);
+        }
+        let sync: Value = request.call().map_err(transport_error)?.into_json().map_err(transport_error)?;
+        self.since = sync["next_batch"].as_str().map(String::from);
+        Ok(sync)
+    }
+}
+
+impl ChatTransport for MatrixTransport {
+    fn network(&self) -> &'static str {
+        NETWORK
+    }
+
+    fn receive(&mut self) -> Result<Vec<ChatMessage>, ChatError> {
+        let sync = self.sync()?;
+        Ok(messages_in(&sync, &self.user_id))
+    }
+
+    fn send(&mut self, room: &str, text: &str) -> Result<(), ChatError> {
+        let txn = format!("astra-{}-{}", std::process::id(), self.next_txn);
+        self.next_txn += 1;
+        let path = format!("rooms/{}/send/m.room.message/{}", encode(room), txn);
+        self.call("PUT", &path, json!({ "msgtype": "m.text", "body": text }))?;
+        Ok(())
+    }
+
+    fn set_typing(&mut self, room: &str, typing: bool) -> Result<(), ChatError> {
+        let path = format!("rooms/{}/typing/{}", encode(room), encode(&self.user_id));
+        self.call("PUT", &path, json!({ "typing": typing, "timeout": TYPING_TIMEOUT_MS }))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_yields_text_messages_from_others() {
+        let sync = json!({
+            "next_batch": "s2",
+            "rooms": { "join": { "!lobby:home": { "timeline": { "events": [
+                { "type": "m.room.message", "sender": "@ana:home", "content": { "msgtype": "m.text", "body": "hi" } },
+                { "type": "m.room.message", "sender": "@astra:home", "content": { "msgtype": "m.text", "body": "hello" } },
+                { "type": "m.room.member", "sender": "@bo:home", "content": { "membership": "join" } }
+            ] } } } }
+        });
+        let messages = messages_in(&sync, "@astra:home");
+        assert_eq!(
+            messages,
+            vec![ChatMessage {
+                room: "!lobby:home".into(),
+                sender: "@ana:home".into(),
+                sender_name: "ana".into(),
+                text: "hi".into(),
+            }]
+        );
+        assert_eq!(encode("!lobby:home"), "%21lobby%3Ahome");
+    }
+}
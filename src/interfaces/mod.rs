@@ -9,7 +9,17 @@
 //       communication channels that interface with the cognitive runtime.
 //
 //   Core Functions:
-//       • Re-export API, NLP, and Voice submodules
+//       • Re-export API, NLP, clarification, temporal parsing, and Voice submodules
+//       • Ask users about knowledge gaps that block goals, without pestering
+//       • Read valence, arousal, and basic emotions from user text
+//       • Identify the language of user text and tokenize it accordingly
+//       • Stream long replies token by token with cancellation and backpressure
+//       • Authenticate API callers and authorize admin operations by role
+//       • Answer chat rooms, on Matrix behind the `matrix` feature
+//       • Sync deadlines and events with a calendar, CalDAV behind `caldav`
+//       • Read and answer email, over IMAP and SMTP behind the `email` feature
+//       • Host WASM sensor and effector plugins behind the `plugins` feature
+//       • Bridge MQTT sensor and actuator topics behind the `mqtt` feature
 //       • Provide unified access to external interaction mechanisms
 //       • Coordinate message flow into the cognitive pipeline
 //       • Serve as the integration hub for all user-facing communication
@@ -17,17 +27,47 @@
 //   File:        /src/interfaces/mod.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-23
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
+pub mod active_learning;
 pub mod api;
+pub mod auth;
+#[cfg(feature = "caldav")]
+pub mod caldav;
+pub mod calendar;
+pub mod chat;
+pub mod clarification;
+pub mod email;
+pub mod lang_detect;
+#[cfg(feature = "email")]
+pub mod mail_servers;
+#[cfg(feature = "matrix")]
+pub mod matrix;
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
 pub mod nlp;
+#[cfg(feature = "plugins")]
+pub mod plugins;
+pub mod sentiment;
+pub mod streaming;
+pub mod temporal;
 pub mod voice;
 
+pub use active_learning::{KnowledgeQuestion, QuestionAsker};
 pub use api::AstraApi;
-pub use nlp::{NlpProcessor, NlpResult};
+pub use auth::{Authenticator, Principal, Role, SessionStore};
+pub use calendar::{CalendarEvent, CalendarStore, CalendarSync, IcsFile};
+pub use chat::{ChatConnector, ChatMessage, ChatTransport};
+pub use clarification::{Interpretation, PendingClarification};
+pub use email::{EmailInterface, EmailMessage, MailSender, MailSource};
+pub use lang_detect::{Detection, Language, Script};
+pub use nlp::{NlpProcessor, NlpResult, Sentiment};
+pub use sentiment::{Emotion, EmotionScores, SentimentAnalyzer};
+pub use streaming::{CancelHandle, ResponseStream, StreamEvent, TemplateSource, TokenSource};
+pub use temporal::{TemporalParser, TemporalResolution};
 pub use voice::{VoiceInput, VoiceOutput};
@@ -0,0 +1,354 @@
+// ============================================================================
+//                      ASTRA AGI • MQTT / IOT BRIDGE
+//        Sensor Topics as Perception, Actuator Topics as Action
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Interfaces Layer that connects to an MQTT
+//       broker so Astra can perceive and act in a smart home or other IoT
+//       setting. Configured sensor topics set world-state keys from their
+//       readings, and every change of a key is also queued as a stimulus;
+//       configured actuator topics are published to when a plan runs the
+//       action mapped to them. A configuration looks like:
+//
+//           { "host": "broker.local",
+//             "sensors": [{ "topic": "zigbee2mqtt/+/occupancy",
+//                           "key": "{topic}", "field": "occupancy" }],
+//             "actuators": [{ "action": "turn_on_light",
+//                             "topic": "zigbee2mqtt/lamp/set",
+//                             "payload": "{\"state\":\"ON\"}" }] }
+//
+//       The bridge is the `WorldStateProvider` and `ActionExecutor` of a
+//       cognitive loop, or, handed to a runtime, what it perceives each
+//       tick and runs plans with. The connection runs on its own thread,
+//       reconnects on its own, and resubscribes after every reconnect;
+//       publishing only queues the command for it, so a plan's action never
+//       waits on the broker.
+//
+//   Core Functions:
+//       • Read sensor payloads (plain, numeric, or JSON fields) as facts
+//       • Queue changed readings as stimuli, in a bounded queue that sheds
+//         the least urgent when a flapping sensor floods it
+//       • Publish actuator commands for plan actions
+//
+//   File:        /src/interfaces/mqtt.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use log::{debug, info, warn};
+use rumqttc::{Client, Event, MqttOptions, Packet, QoS, RecvTimeoutError};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::cognition::cognitive_loop::WorldStateProvider;
+use crate::cognition::stimulus_queue::StimulusQueue;
+use crate::cognition::Stimulus;
+use crate::planning::executor::ActionExecutor;
+use crate::planning::planner::{Action, WorldState};
+
+#[derive(Debug, Error)]
+pub enum MqttError {
+    #[error("Invalid MQTT bridge configuration")]
+    InvalidConfig(#[from] serde_json::Error),
+    #[error("Could not send to the MQTT broker")]
+    Client(#[from] rumqttc::ClientError),
+}
+
+/// Broker connection and topic mappings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MqttConfig {
+    pub host: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    #[serde(default = "default_client_id")]
+    pub client_id: String,
+    #[serde(default)]
+    pub credentials: Option<(String, String)>,
+    #[serde(default)]
+    pub sensors: Vec<SensorTopic>,
+    #[serde(default)]
+    pub actuators: Vec<ActuatorTopic>,
+}
+
+fn default_port() -> u16 {
+    1883
+}
+
+fn default_client_id() -> String {
+    "astra".into()
+}
+
+impl MqttConfig {
+    pub fn from_json(json: &str) -> Result<Self, MqttError> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+/// A topic whose messages are sensor readings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SensorTopic {
+    /// Topic filter; `+` and `#` wildcards are allowed
+    pub topic: String,
+    /// World-state key a reading sets; `{topic}` stands for the topic the
+    /// message arrived on
+    pub key: String,
+    /// For JSON payloads, the field holding the reading
+    #[serde(default)]
+    pub field: Option<String>,
+    /// Readings that mean true, compared case-insensitively; others are false
+    #[serde(default = "default_on")]
+    pub on: Vec<String>,
+    /// For numeric readings, the value above which the key is true
+    #[serde(default)]
+    pub threshold: Option<f64>,
+    /// Urgency of the stimulus queued when the key changes
+    #[serde(default = "default_urgency")]
+    pub urgency: f32,
+}
+
+fn default_on() -> Vec<String> {
+    ["on", "true", "1", "open", "detected", "yes"].map(String::from).to_vec()
+}
+
+fn default_urgency() -> f32 {
+    0.4
+}
+
+/// A topic published to when a plan runs an action.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActuatorTopic {
+    /// Action id, or the name of grounded actions such as `open(door)`
+    pub action: String,
+    pub topic: String,
+    pub payload: String,
+    #[serde(default)]
+    pub retain: bool,
+}
+
+/// A sensor message read as a fact.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reading {
+    pub key: String,
+    pub value: bool,
+    pub urgency: f32,
+}
+
+impl MqttConfig {
+    /// Reads a message with the first sensor whose topic filter matches it.
+    /// Payloads the sensor cannot read are ignored.
+    pub fn reading(&self, topic: &str, payload: &[u8]) -> Option<Reading> {
+        let sensor = self.sensors.iter().find(|s| rumqttc::matches(topic, &s.topic))?;
+        let text = std::str::from_utf8(payload).ok()?.trim();
+        let raw = match &sensor.field {
+            Some(field) => match serde_json::from_str::<serde_json::Value>(text).ok()?.get(field)? {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            },
+            None => text.to_string(),
+        };
+        let value = match (sensor.threshold, raw.parse::<f64>()) {
+            (Some(threshold), Ok(number)) => number > threshold,
+            _ => sensor.on.iter().any(|on| on.eq_ignore_ascii_case(&raw)),
+        };
+        Some(Reading { key: sensor.key.replace("{topic}", topic), value, urgency: sensor.urgency })
+    }
+
+    /// The actuator that performs `action_id`.
+    pub fn actuator(&self, action_id: &str) -> Option<&ActuatorTopic> {
+        let name = action_id.split('(').next().unwrap_or(action_id);
+        self.actuators.iter().find(|a| a.action == action_id).or_else(|| self.actuators.iter().find(|a| a.action == name))
+    }
+}
+
+#[derive(Default)]
+struct Perception {
+    world: WorldState,
+    stimuli: StimulusQueue,
+}
+
+impl Perception {
+    /// Records a reading, queuing a stimulus if it changed the world state.
+    fn observe(&mut self, topic: &str, reading: Reading) {
+        if self.world.insert(reading.key.clone(), reading.value) == Some(reading.value) {
+            return;
+        }
+        self.stimuli.push(Stimulus {
+            source: "mqtt".into(),
+            content: format!("{} is now {} (from {})", reading.key, reading.value, topic),
+            urgency: reading.urgency,
+        });
+    }
+}
+
+/// A live connection to an MQTT broker.
+pub struct MqttBridge {
+    config: Arc<MqttConfig>,
+    client: Client,
+    perception: Arc<Mutex<Perception>>,
+    stop: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl MqttBridge {
+    /// Starts connecting to the broker; readings arrive once connected.
+    pub fn connect(config: MqttConfig) -> Self {
+        let mut options = MqttOptions::new(config.client_id.clone(), config.host.clone(), config.port);
+        options.set_keep_alive(Duration::from_secs(30));
+        if let Some((user, password)) = &config.credentials {
+            options.set_credentials(user.clone(), password.clone());
+        }
+        let (client, mut connection) = Client::new(options, 64);
+
+        let config = Arc::new(config);
+        let perception = Arc::new(Mutex::new(Perception::default()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let worker = {
+            let (config, client, perception, stop) = (config.clone(), client.clone(), perception.clone(), stop.clone());
+            std::thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    match connection.recv_timeout(Duration::from_secs(1)) {
+                        Ok(Ok(Event::Incoming(Packet::ConnAck(_)))) => {
+                            info!("Connected to MQTT broker {}:{}", config.host, config.port);
+                            for sensor in &config.sensors {
+                                if let Err(e) = client.subscribe(sensor.topic.clone(), QoS::AtLeastOnce) {
+                                    warn!("Could not subscribe to {}: {}", sensor.topic, e);
+                                }
+                            }
+                        }
+                        Ok(Ok(Event::Incoming(Packet::Publish(message)))) => {
+                            match config.reading(&message.topic, &message.payload) {
+                                Some(reading) => {
+                                    if let Ok(mut perception) = perception.lock() {
+                                        perception.observe(&message.topic, reading);
+                                    }
+                                }
+                                None => debug!("Ignored unreadable MQTT message on {}", message.topic),
+                            }
+                        }
+                        Ok(Ok(_)) | Err(RecvTimeoutError::Timeout) => {}
+                        Ok(Err(e)) => {
+                            warn!("MQTT connection error: {}; retrying", e);
+                            std::thread::sleep(Duration::from_secs(1));
+                        }
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+                }
+            })
+        };
+
+        Self { config, client, perception, stop, worker: Some(worker) }
+    }
+
+    pub fn config(&self) -> &MqttConfig {
+        &self.config
+    }
+
+    /// Stimuli queued since the last call, most urgent first.
+    pub fn take_stimuli(&self) -> Vec<Stimulus> {
+        self.perception
+            .lock()
+            .map(|mut p| p.stimuli.drain().into_iter().map(|queued| queued.stimulus).collect())
+            .unwrap_or_default()
+    }
+
+    /// Stimuli shed because they arrived faster than they were taken.
+    pub fn dropped_stimuli(&self) -> u64 {
+        self.perception.lock().map(|p| p.stimuli.dropped()).unwrap_or(0)
+    }
+
+    /// Queues the command for `action` for the connection thread to send;
+    /// false if no actuator performs it. Never waits: while the broker is
+    /// unreachable and the queue is full, the publish fails instead of
+    /// holding up the tick that runs the plan.
+    pub fn publish(&self, action: &Action) -> Result<bool, MqttError> {
+        let Some(actuator) = self.config.actuator(&action.id) else {
+            return Ok(false);
+        };
+        self.client.try_publish(actuator.topic.clone(), QoS::AtLeastOnce, actuator.retain, actuator.payload.clone())?;
+        Ok(true)
+    }
+
+    /// Publishing is the whole action: success means the broker was asked,
+    /// and whether the device complied shows up in later readings.
+    fn actuate(&self, action: &Action) -> anyhow::Result<bool> {
+        if self.publish(action)? {
+            Ok(true)
+        } else {
+            anyhow::bail!("No MQTT actuator performs action '{}'", action.id)
+        }
+    }
+}
+
+impl WorldStateProvider for MqttBridge {
+    fn current_world_state(&self) -> WorldState {
+        self.perception.lock().map(|p| p.world.clone()).unwrap_or_default()
+    }
+}
+
+impl ActionExecutor for MqttBridge {
+    fn execute_action(&mut self, action: &Action) -> anyhow::Result<bool> {
+        self.actuate(action)
+    }
+}
+
+/// The bridge a runtime shares between perceiving each tick and the plans
+/// it runs.
+impl ActionExecutor for Arc<MqttBridge> {
+    fn execute_action(&mut self, action: &Action) -> anyhow::Result<bool> {
+        self.actuate(action)
+    }
+}
+
+impl Drop for MqttBridge {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        let _ = self.client.disconnect();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topics_map_to_facts_and_actions_to_commands() {
+        let config = MqttConfig::from_json(
+            r#"{
+                "host": "localhost",
+                "sensors": [
+                    { "topic": "home/+/motion", "key": "motion({topic})", "field": "occupancy", "urgency": 0.7 },
+                    { "topic": "home/kitchen/temperature", "key": "kitchen_hot", "threshold": 28 }
+                ],
+                "actuators": [{ "action": "open", "topic": "home/door/set", "payload": "OPEN" }]
+            }"#,
+        )
+        .unwrap();
+
+        let motion = config.reading("home/hall/motion", br#"{"occupancy": true, "battery": 80}"#).unwrap();
+        assert_eq!(motion, Reading { key: "motion(home/hall/motion)".into(), value: true, urgency: 0.7 });
+        assert!(config.reading("home/kitchen/temperature", b"31.5").unwrap().value);
+        assert!(!config.reading("home/kitchen/temperature", b"21").unwrap().value);
+        assert_eq!(config.reading("home/hall/motion", b"not json"), None);
+        assert_eq!(config.reading("garden/rain", b"on"), None);
+
+        let mut perception = Perception::default();
+        perception.observe("home/hall/motion", motion.clone());
+        perception.observe("home/hall/motion", motion);
+        assert_eq!(perception.stimuli.len(), 1, "only changes become stimuli");
+
+        assert_eq!(config.actuator("open(front_door)").unwrap().topic, "home/door/set");
+        assert!(config.actuator("close").is_none());
+    }
+}
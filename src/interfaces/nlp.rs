@@ -12,12 +12,16 @@
 //       • Detect user intent across commands, queries, and conversational input
 //       • Extract entities, parameters, and contextual markers from text
 //       • Perform semantic analysis to map language into cognitive actions
+//       • Estimate user sentiment (valence, arousal, and basic emotions)
+//       • Tag input with its detected language for downstream lexicons
+//       • Match intent keywords on words tokenized for that language
+//       • Score intents against keyword schemas and fill their required slots
 //       • Serve as the linguistic bridge between external input and reasoning
 //
 //   File:        /src/interfaces/nlp.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-23
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
@@ -26,6 +30,11 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub use crate::interfaces::sentiment::Sentiment;
+use crate::interfaces::lang_detect::{detect_language, tokenize, Language};
+use crate::interfaces::sentiment::SentimentAnalyzer;
 
 /// Represents the result of an NLP processing operation.
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -33,28 +42,239 @@ pub struct NlpResult {
     pub intent: String,
     pub entities: Vec<String>,
     pub confidence: f32,
+    #[serde(default)]
+    pub sentiment: Sentiment,
+    /// Slot values extracted for the intent, e.g. "time" -> "tomorrow"
+    #[serde(default)]
+    pub slots: HashMap<String, String>,
+    /// Slots the intent requires that the text did not supply
+    #[serde(default)]
+    pub missing_slots: Vec<String>,
+    /// Next-best intents, most likely first
+    #[serde(default)]
+    pub alternatives: Vec<String>,
+    /// Language the input was written in
+    #[serde(default)]
+    pub language: Language,
+}
+
+/// An intent the processor can recognize: the words that suggest it and
+/// the slots it needs before it can be acted on.
+#[derive(Debug, Clone)]
+pub struct IntentSchema {
+    pub intent: String,
+    pub keywords: Vec<String>,
+    pub required_slots: Vec<String>,
+}
+
+impl IntentSchema {
+    pub fn new(intent: &str, keywords: &[&str], required_slots: &[&str]) -> Self {
+        IntentSchema {
+            intent: intent.to_string(),
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            required_slots: required_slots.iter().map(|s| s.to_string()).collect(),
+        }
+    }
 }
 
 /// NLP processor struct encapsulating NLP models and logic.
 pub struct NlpProcessor {
-    // Placeholder for NLP models, e.g. tokenizers, classifiers
+    pub schemas: Vec<IntentSchema>,
+    pub sentiment: SentimentAnalyzer,
 }
 
 impl NlpProcessor {
     /// Creates a new NLP processor instance.
     pub fn new() -> Self {
         Self {
-            // Initialize NLP models here
+            schemas: vec![
+                IntentSchema::new("greeting", &["hello", "hi", "hey", "morning"], &[]),
+                IntentSchema::new("set_reminder", &["remind", "reminder", "remember"], &["task", "time"]),
+                IntentSchema::new("send_message", &["send", "message", "email", "tell"], &["recipient", "content"]),
+                IntentSchema::new("search", &["search", "find", "look", "lookup"], &["topic"]),
+                IntentSchema::new("question", &["what", "why", "how", "who", "explain"], &[]),
+            ],
+            sentiment: SentimentAnalyzer::new(),
         }
     }
 
     /// Processes input text and returns NLP analysis results.
     pub fn process_text(&self, input: &str) -> Result<NlpResult> {
-        // Placeholder: Replace with actual NLP processing logic
+        let language = detect_language(input).language;
+        let ranked = self.rank_words(&tokenize(input, language));
+        let (intent, confidence) = ranked
+            .first()
+            .map(|(schema, score)| (schema.intent.clone(), *score))
+            .unwrap_or_else(|| ("unknown".to_string(), 0.0));
+        let slots = extract_slots(input);
+        let missing_slots = self.missing_slots(&intent, &slots);
+
         Ok(NlpResult {
-            intent: "greeting".to_string(),
-            entities: vec!["Astra".to_string()],
-            confidence: 0.95,
+            intent,
+            entities: extract_entities(input),
+            confidence,
+            sentiment: self.analyze_sentiment(input),
+            slots,
+            missing_slots,
+            alternatives: ranked.iter().skip(1).map(|(schema, _)| schema.intent.clone()).collect(),
+            language,
         })
     }
+
+    /// Intents whose keywords occur in `input`, best first, each with a
+    /// confidence in 0..1. More keyword hits make for a stronger reading,
+    /// and hits shared with other intents weaken it.
+    pub fn rank_intents(&self, input: &str) -> Vec<(&IntentSchema, f32)> {
+        self.rank_words(&tokenize(input, detect_language(input).language))
+    }
+
+    /// `rank_intents` over words already tokenized for the input's
+    /// language. A contracted word ("what's") counts as the word it
+    /// starts with.
+    fn rank_words(&self, words: &[String]) -> Vec<(&IntentSchema, f32)> {
+        let words: Vec<&str> = words.iter().filter_map(|word| word.split('\'').next()).collect();
+        let mut hits: Vec<(&IntentSchema, usize)> = self
+            .schemas
+            .iter()
+            .map(|schema| (schema, words.iter().filter(|w| schema.keywords.iter().any(|k| k == *w)).count()))
+            .filter(|(_, n)| *n > 0)
+            .collect();
+        hits.sort_by(|a, b| b.1.cmp(&a.1));
+
+        let total: usize = hits.iter().map(|(_, n)| n).sum();
+        hits.into_iter()
+            .map(|(schema, n)| {
+                let share = n as f32 / total as f32;
+                let strength = n as f32 / (n as f32 + 1.0);
+                (schema, share * (0.5 + 0.45 * strength))
+            })
+            .collect()
+    }
+
+    /// Required slots of `intent` that `slots` does not fill.
+    pub fn missing_slots(&self, intent: &str, slots: &HashMap<String, String>) -> Vec<String> {
+        self.schemas
+            .iter()
+            .find(|schema| schema.intent == intent)
+            .map(|schema| {
+                schema
+                    .required_slots
+                    .iter()
+                    .filter(|slot| !slots.contains_key(*slot))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Estimates sentiment and emotion with the lexicon analyzer.
+    pub fn analyze_sentiment(&self, input: &str) -> Sentiment {
+        self.sentiment.analyze(input)
+    }
+}
+
+/// Capitalized words, as a rough guess at named entities.
+fn extract_entities(input: &str) -> Vec<String> {
+    input
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|w| w.len() > 1 && w.chars().next().is_some_and(char::is_uppercase) && *w != "I")
+        .map(str::to_string)
+        .collect()
+}
+
+/// Fills slots from the phrases that usually introduce them: "remind me to
+/// <task>", "at/on/by <time>", "to <recipient>", "saying <content>",
+/// "about/for <topic>".
+pub fn extract_slots(input: &str) -> HashMap<String, String> {
+    const TIME_WORDS: &[&str] = &["today", "tonight", "tomorrow", "later", "noon", "midnight"];
+    const BOUNDARIES: &[&str] = &["at", "on", "by", "to", "saying", "that", "about", "for", "tomorrow", "tonight", "today"];
+
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let lower: Vec<String> = words
+        .iter()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .collect();
+    // Words from `start` up to the next boundary word.
+    let phrase = |start: usize| -> Option<String> {
+        let end = (start..words.len()).find(|&i| i > start && BOUNDARIES.contains(&lower[i].as_str())).unwrap_or(words.len());
+        let text = words.get(start..end)?.join(" ");
+        let text = text.trim_matches(|c: char| !c.is_alphanumeric()).to_string();
+        (!text.is_empty()).then_some(text)
+    };
+
+    let mut slots = HashMap::new();
+    for (i, word) in lower.iter().enumerate() {
+        let next = i + 1;
+        match word.as_str() {
+            "at" | "on" | "by" if next < words.len() => {
+                if let Some(time) = phrase(next) {
+                    slots.entry("time".to_string()).or_insert(time);
+                }
+            }
+            w if TIME_WORDS.contains(&w) => {
+                slots.entry("time".to_string()).or_insert_with(|| w.to_string());
+            }
+            "to" if next < words.len() => {
+                if i > 0 && lower[i - 1] == "me" {
+                    if let Some(task) = phrase(next) {
+                        slots.entry("task".to_string()).or_insert(task);
+                    }
+                } else if let Some(recipient) = phrase(next) {
+                    slots.entry("recipient".to_string()).or_insert(recipient);
+                }
+            }
+            "saying" | "that" if next < words.len() => {
+                if let Some(content) = phrase(next) {
+                    slots.entry("content".to_string()).or_insert(content);
+                }
+            }
+            "about" | "for" if next < words.len() => {
+                if let Some(topic) = phrase(next) {
+                    slots.entry("topic".to_string()).or_insert(topic);
+                }
+            }
+            _ => {}
+        }
+    }
+    slots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sentiment_polarity_and_arousal() {
+        let nlp = NlpProcessor::new();
+        let warm = nlp.analyze_sentiment("Thanks, that was really helpful");
+        let angry = nlp.analyze_sentiment("This is USELESS and I am furious!!");
+
+        assert!(warm.valence > 0.5);
+        assert!(angry.valence < -0.5);
+        assert!(angry.arousal > warm.arousal);
+        assert_eq!(nlp.analyze_sentiment("What time is it"), Sentiment::default());
+    }
+
+    #[test]
+    fn test_intent_and_slots() {
+        let nlp = NlpProcessor::new();
+        let reminder = nlp.process_text("Please remind me to call the dentist tomorrow").unwrap();
+        assert_eq!(reminder.intent, "set_reminder");
+        assert_eq!(reminder.slots.get("task").map(String::as_str), Some("call the dentist"));
+        assert_eq!(reminder.slots.get("time").map(String::as_str), Some("tomorrow"));
+        assert_eq!(reminder.language, Language::English);
+        assert!(reminder.missing_slots.is_empty());
+
+        let vague = nlp.process_text("send it").unwrap();
+        assert_eq!(vague.intent, "send_message");
+        assert_eq!(vague.missing_slots, vec!["recipient".to_string(), "content".to_string()]);
+
+        // Curly apostrophes and contractions are normalized before matching
+        assert_eq!(nlp.process_text("What’s a glacier?").unwrap().intent, "question");
+
+        let mixed = nlp.process_text("find and send").unwrap();
+        assert!(mixed.confidence < 0.6);
+        assert_eq!(mixed.alternatives.len(), 1);
+    }
 }
@@ -0,0 +1,596 @@
+// ============================================================================
+//                     ASTRA AGI • WASM PLUGIN HOST
+//        Third-Party Sensors & Effectors as Sandboxed WebAssembly
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Interfaces Layer that runs sensors (sources of
+//       stimuli) and effectors (performers of plan actions) shipped by third
+//       parties as WebAssembly modules. Each plugin is described by a JSON
+//       manifest in a plugin directory:
+//
+//           { "name": "doorbell", "module": "doorbell.wasm",
+//             "actions": ["ring_bell"], "fuel": 1000000 }
+//
+//       Plugins are sandboxed: the only functions a module may import are
+//       the host's own (`astra.log`), so it has no access to files, the
+//       network, or the clock, and every call runs under a fuel budget and a
+//       memory cap. A manifest may ask for less than the host's ceilings but
+//       never more; larger requests are cut down to them. Rescanning the directory loads new and changed plugins
+//       and unloads removed ones while Astra runs. Pointers and lengths a
+//       plugin hands back are checked against its memory before anything
+//       is copied, so a bad length cannot make the host allocate.
+//       A runtime given a host shares it: each tick it rescans the
+//       directory and queues what the sensors report, and plans run their
+//       actions through it.
+//
+//   ABI:
+//       Exports `memory` and `astra_alloc(len: i32) -> i32`, which returns
+//       space for the host to write input into, plus either or both of:
+//         • `astra_poll() -> i64` — sensors; 0 for no stimulus, otherwise
+//           `(ptr << 32) | len` of a JSON stimulus
+//           `{"content": "...", "urgency": 0.5, "source": "..."}`
+//         • `astra_execute(ptr: i32, len: i32) -> i32` — effectors; called
+//           with a JSON action `{"id": "...", "description": "..."}`, and
+//           returns 1 on success, 0 on a recoverable failure, and a
+//           negative value on a critical one
+//       Imports: `astra.log(ptr: i32, len: i32)` writes UTF-8 to Astra's log.
+//
+//   Core Functions:
+//       • Discover plugins from manifests and hot-load them
+//       • Poll sensors for stimuli
+//       • Execute plan actions through the effector that declares them
+//
+//   File:        /src/interfaces/plugins.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::SystemTime;
+use thiserror::Error;
+use wasmtime::{Caller, Config, Engine, Instance, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use crate::cognition::Stimulus;
+use crate::planning::executor::ActionExecutor;
+use crate::planning::planner::Action;
+
+#[derive(Debug, Error)]
+pub enum PluginError {
+    #[error("Could not read {}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Invalid plugin manifest {}", path.display())]
+    Manifest {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    /// The module failed to compile, link, or run
+    #[error("Plugin '{plugin}' failed")]
+    Wasm {
+        plugin: String,
+        #[source]
+        source: wasmtime::Error,
+    },
+    /// The module does not follow the plugin ABI
+    #[error("Plugin '{plugin}' broke the plugin ABI: {message}")]
+    Abi { plugin: String, message: String },
+}
+
+/// What a plugin manifest declares.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginManifest {
+    pub name: String,
+    /// The `.wasm` (or `.wat`) file, relative to the manifest
+    pub module: PathBuf,
+    /// Action ids the plugin performs. A grounded action such as
+    /// `move(hall,office)` also matches the name `move`.
+    #[serde(default)]
+    pub actions: Vec<String>,
+    /// Instructions a single call may run before it is stopped
+    #[serde(default = "default_fuel")]
+    pub fuel: u64,
+    #[serde(default = "default_max_memory")]
+    pub max_memory_bytes: usize,
+}
+
+fn default_fuel() -> u64 {
+    10_000_000
+}
+
+fn default_max_memory() -> usize {
+    16 << 20
+}
+
+/// The most fuel and memory the host grants any plugin, whatever its
+/// manifest asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PluginLimits {
+    pub max_fuel: u64,
+    pub max_memory_bytes: usize,
+}
+
+impl Default for PluginLimits {
+    fn default() -> Self {
+        PluginLimits { max_fuel: 100_000_000, max_memory_bytes: 64 << 20 }
+    }
+}
+
+impl PluginManifest {
+    pub fn load(path: &Path) -> Result<PluginManifest, PluginError> {
+        let text = std::fs::read_to_string(path).map_err(|source| PluginError::Io { path: path.to_path_buf(), source })?;
+        serde_json::from_str(&text).map_err(|source| PluginError::Manifest { path: path.to_path_buf(), source })
+    }
+
+    /// Whether the plugin performs `action_id`.
+    pub fn handles(&self, action_id: &str) -> bool {
+        let name = action_id.split('(').next().unwrap_or(action_id);
+        self.actions.iter().any(|a| a == action_id || a == name)
+    }
+}
+
+/// A change made by rescanning the plugin directory.
+#[derive(Debug)]
+pub enum PluginEvent {
+    Loaded(String),
+    Unloaded(String),
+    /// The manifest or module could not be loaded; a plugin of the same
+    /// name that was already loaded keeps running
+    Rejected { manifest: PathBuf, error: PluginError },
+}
+
+struct PluginState {
+    name: String,
+    limits: StoreLimits,
+}
+
+/// Modification times of a manifest and its module.
+type Stamp = (Option<SystemTime>, Option<SystemTime>);
+
+struct Plugin {
+    manifest: PluginManifest,
+    manifest_path: PathBuf,
+    store: Store<PluginState>,
+    instance: Instance,
+}
+
+impl Plugin {
+    fn load(engine: &Engine, manifest_path: &Path, limits: PluginLimits) -> Result<Plugin, PluginError> {
+        let mut manifest = PluginManifest::load(manifest_path)?;
+        if manifest.fuel > limits.max_fuel || manifest.max_memory_bytes > limits.max_memory_bytes {
+            warn!("Plugin '{}' asks for more than the host allows; capping it at {:?}", manifest.name, limits);
+            manifest.fuel = manifest.fuel.min(limits.max_fuel);
+            manifest.max_memory_bytes = manifest.max_memory_bytes.min(limits.max_memory_bytes);
+        }
+        let wasm = |source| PluginError::Wasm { plugin: manifest.name.clone(), source };
+        let module = Module::from_file(engine, module_path(manifest_path, &manifest)).map_err(wasm)?;
+
+        let mut linker = Linker::new(engine);
+        linker
+            .func_wrap("astra", "log", |mut caller: Caller<'_, PluginState>, ptr: i32, len: i32| {
+                let Some(memory) = caller.get_export("memory").and_then(|e| e.into_memory()) else {
+                    return;
+                };
+                match guest_bytes(memory.data(&caller), ptr, len) {
+                    Some(bytes) => info!("[plugin {}] {}", caller.data().name, String::from_utf8_lossy(bytes)),
+                    None => warn!("[plugin {}] logged from outside its memory", caller.data().name),
+                }
+            })
+            .map_err(wasm)?;
+
+        let limits = StoreLimitsBuilder::new().memory_size(manifest.max_memory_bytes).instances(1).build();
+        let mut store = Store::new(engine, PluginState { name: manifest.name.clone(), limits });
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(manifest.fuel).map_err(wasm)?;
+        // Fails for modules importing anything but the host's functions
+        let instance = linker.instantiate(&mut store, &module).map_err(wasm)?;
+
+        let mut plugin = Plugin { manifest, manifest_path: manifest_path.to_path_buf(), store, instance };
+        plugin.memory()?;
+        if !plugin.manifest.actions.is_empty() && plugin.instance.get_func(&mut plugin.store, "astra_execute").is_none() {
+            return Err(plugin.abi("declares actions but does not export astra_execute"));
+        }
+        Ok(plugin)
+    }
+
+    fn is_sensor(&mut self) -> bool {
+        self.instance.get_func(&mut self.store, "astra_poll").is_some()
+    }
+
+    fn poll(&mut self) -> Result<Option<Stimulus>, PluginError> {
+        self.refuel()?;
+        let poll = self.instance.get_typed_func::<(), i64>(&mut self.store, "astra_poll").map_err(|e| self.wasm(e))?;
+        let packed = poll.call(&mut self.store, ()).map_err(|e| self.wasm(e))?;
+        if packed == 0 {
+            return Ok(None);
+        }
+        let bytes = self.read((packed >> 32) as i32, packed as i32)?;
+        let record: StimulusRecord =
+            serde_json::from_slice(&bytes).map_err(|e| self.abi(format!("invalid stimulus: {}", e)))?;
+        Ok(Some(Stimulus {
+            source: record.source.unwrap_or_else(|| self.manifest.name.clone()),
+            content: record.content,
+            urgency: record.urgency.clamp(0.0, 1.0),
+        }))
+    }
+
+    fn execute(&mut self, action: &Action) -> Result<i32, PluginError> {
+        self.refuel()?;
+        let input = serde_json::to_vec(&ActionRecord { id: &action.id, description: &action.description })
+            .expect("action records serialize");
+        let ptr = self.write(&input)?;
+        let execute =
+            self.instance.get_typed_func::<(i32, i32), i32>(&mut self.store, "astra_execute").map_err(|e| self.wasm(e))?;
+        execute.call(&mut self.store, (ptr, input.len() as i32)).map_err(|e| self.wasm(e))
+    }
+
+    fn refuel(&mut self) -> Result<(), PluginError> {
+        let fuel = self.manifest.fuel;
+        self.store.set_fuel(fuel).map_err(|e| self.wasm(e))
+    }
+
+    fn memory(&mut self) -> Result<Memory, PluginError> {
+        self.instance
+            .get_memory(&mut self.store, "memory")
+            .ok_or_else(|| self.abi("does not export its memory"))
+    }
+
+    fn read(&mut self, ptr: i32, len: i32) -> Result<Vec<u8>, PluginError> {
+        let memory = self.memory()?;
+        match guest_bytes(memory.data(&self.store), ptr, len) {
+            Some(bytes) => Ok(bytes.to_vec()),
+            None => Err(self.abi("pointed outside its memory")),
+        }
+    }
+
+    /// Copies `bytes` into space the plugin allocates, returning where.
+    fn write(&mut self, bytes: &[u8]) -> Result<i32, PluginError> {
+        let memory = self.memory()?;
+        let alloc = self.instance.get_typed_func::<i32, i32>(&mut self.store, "astra_alloc").map_err(|e| self.wasm(e))?;
+        let ptr = alloc.call(&mut self.store, bytes.len() as i32).map_err(|e| self.wasm(e))?;
+        memory
+            .write(&mut self.store, ptr as u32 as usize, bytes)
+            .map_err(|_| self.abi("allocated outside its memory"))?;
+        Ok(ptr)
+    }
+
+    fn wasm(&self, source: wasmtime::Error) -> PluginError {
+        PluginError::Wasm { plugin: self.manifest.name.clone(), source }
+    }
+
+    fn abi(&self, message: impl Into<String>) -> PluginError {
+        PluginError::Abi { plugin: self.manifest.name.clone(), message: message.into() }
+    }
+}
+
+/// The `len` bytes at `ptr` in a plugin's memory, if they all lie within it.
+fn guest_bytes(memory: &[u8], ptr: i32, len: i32) -> Option<&[u8]> {
+    let len = usize::try_from(len).ok()?;
+    let start = ptr as u32 as usize;
+    memory.get(start..start.checked_add(len)?)
+}
+
+#[derive(Deserialize)]
+struct StimulusRecord {
+    content: String,
+    #[serde(default = "default_urgency")]
+    urgency: f32,
+    source: Option<String>,
+}
+
+fn default_urgency() -> f32 {
+    0.5
+}
+
+#[derive(Serialize)]
+struct ActionRecord<'a> {
+    id: &'a str,
+    description: &'a str,
+}
+
+/// Loaded plugins, by name. Use it as the `ActionExecutor` of a plan or
+/// cognitive loop to run actions through the plugins that declare them.
+pub struct PluginHost {
+    engine: Engine,
+    limits: PluginLimits,
+    directory: Option<PathBuf>,
+    plugins: BTreeMap<String, Plugin>,
+    /// Stamps of the manifests in the directory when they were last loaded
+    seen: BTreeMap<PathBuf, Stamp>,
+}
+
+impl PluginHost {
+    pub fn new() -> Result<Self, PluginError> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(|source| PluginError::Wasm { plugin: String::new(), source })?;
+        Ok(Self {
+            engine,
+            limits: PluginLimits::default(),
+            directory: None,
+            plugins: BTreeMap::new(),
+            seen: BTreeMap::new(),
+        })
+    }
+
+    /// Caps what plugins loaded from now on may use.
+    pub fn with_limits(mut self, limits: PluginLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Loads the plugins whose manifests (`*.json`) are in `directory`, and
+    /// makes `rescan` follow it.
+    pub fn watch(&mut self, directory: impl Into<PathBuf>) -> Vec<PluginEvent> {
+        self.directory = Some(directory.into());
+        self.rescan()
+    }
+
+    /// Loads the plugin described by the manifest at `path`, replacing a
+    /// loaded plugin of the same name. The manifest returned holds the
+    /// limits the plugin runs under.
+    pub fn load(&mut self, path: impl AsRef<Path>) -> Result<&PluginManifest, PluginError> {
+        let plugin = Plugin::load(&self.engine, path.as_ref(), self.limits)?;
+        let name = plugin.manifest.name.clone();
+        info!("Loaded plugin '{}' from {}", name, path.as_ref().display());
+        self.plugins.insert(name.clone(), plugin);
+        Ok(&self.plugins[&name].manifest)
+    }
+
+    pub fn unload(&mut self, name: &str) -> bool {
+        self.plugins.remove(name).is_some()
+    }
+
+    /// Brings the loaded plugins in line with the watched directory: loads
+    /// new manifests, reloads plugins whose manifest or module changed, and
+    /// unloads plugins whose manifest was removed.
+    pub fn rescan(&mut self) -> Vec<PluginEvent> {
+        let Some(directory) = self.directory.clone() else {
+            return Vec::new();
+        };
+        let manifests: Vec<PathBuf> = match std::fs::read_dir(&directory) {
+            Ok(entries) => {
+                let mut paths: Vec<PathBuf> = entries
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| path.extension().is_some_and(|e| e == "json"))
+                    .collect();
+                paths.sort();
+                paths
+            }
+            Err(source) => {
+                return vec![PluginEvent::Rejected { manifest: directory.clone(), error: PluginError::Io { path: directory, source } }];
+            }
+        };
+
+        let mut events = Vec::new();
+        let removed: Vec<String> = self
+            .plugins
+            .iter()
+            .filter(|(_, p)| p.manifest_path.parent() == Some(directory.as_path()) && !manifests.contains(&p.manifest_path))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in removed {
+            self.plugins.remove(&name);
+            events.push(PluginEvent::Unloaded(name));
+        }
+        self.seen.retain(|path, _| manifests.contains(path));
+
+        for path in manifests {
+            let stamp = stamp(&path);
+            if self.seen.get(&path) == Some(&stamp) {
+                continue;
+            }
+            self.seen.insert(path.clone(), stamp);
+            match self.load(&path) {
+                Ok(manifest) => events.push(PluginEvent::Loaded(manifest.name.clone())),
+                Err(error) => {
+                    warn!("Rejected plugin {}: {}", path.display(), error);
+                    events.push(PluginEvent::Rejected { manifest: path, error });
+                }
+            }
+        }
+        events
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.plugins.keys().map(String::as_str)
+    }
+
+    pub fn manifest(&self, name: &str) -> Option<&PluginManifest> {
+        self.plugins.get(name).map(|p| &p.manifest)
+    }
+
+    /// Asks every sensor for a stimulus. Sensors that fail are logged and
+    /// skipped.
+    pub fn poll_sensors(&mut self) -> Vec<Stimulus> {
+        let mut stimuli = Vec::new();
+        for plugin in self.plugins.values_mut() {
+            if !plugin.is_sensor() {
+                continue;
+            }
+            match plugin.poll() {
+                Ok(Some(stimulus)) => stimuli.push(stimulus),
+                Ok(None) => {}
+                Err(e) => warn!("{}", error_chain(&e)),
+            }
+        }
+        stimuli
+    }
+}
+
+impl ActionExecutor for PluginHost {
+    fn execute_action(&mut self, action: &Action) -> anyhow::Result<bool> {
+        let plugin = self
+            .plugins
+            .values_mut()
+            .find(|p| p.manifest.handles(&action.id))
+            .ok_or_else(|| anyhow::anyhow!("No plugin performs action '{}'", action.id))?;
+        match plugin.execute(action)? {
+            status if status < 0 => anyhow::bail!("Plugin '{}' reported a critical failure ({})", plugin.manifest.name, status),
+            status => Ok(status > 0),
+        }
+    }
+}
+
+/// A plugin host shared by the runtime, which rescans and polls it each
+/// tick, and by the plans whose actions it performs.
+#[derive(Clone)]
+pub struct SharedPluginHost(Arc<Mutex<PluginHost>>);
+
+impl SharedPluginHost {
+    pub fn new(host: PluginHost) -> Self {
+        SharedPluginHost(Arc::new(Mutex::new(host)))
+    }
+
+    /// The host; a plugin that panicked the host does not lock it for good.
+    pub fn lock(&self) -> MutexGuard<'_, PluginHost> {
+        self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl ActionExecutor for SharedPluginHost {
+    fn execute_action(&mut self, action: &Action) -> anyhow::Result<bool> {
+        self.lock().execute_action(action)
+    }
+}
+
+fn module_path(manifest_path: &Path, manifest: &PluginManifest) -> PathBuf {
+    manifest_path.parent().unwrap_or(Path::new(".")).join(&manifest.module)
+}
+
+fn stamp(manifest_path: &Path) -> Stamp {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    let module = PluginManifest::load(manifest_path).ok().and_then(|m| modified(&module_path(manifest_path, &m)));
+    (modified(manifest_path), module)
+}
+
+fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const DOORBELL: &str = r#"
+        (module
+          (import "astra" "log" (func $log (param i32 i32)))
+          (memory (export "memory") 1)
+          (global $next (mut i32) (i32.const 1024))
+          (data (i32.const 16) "{\"content\":\"someone is at the door\",\"urgency\":0.8}")
+          (func (export "astra_alloc") (param $len i32) (result i32)
+            (local $ptr i32)
+            (local.set $ptr (global.get $next))
+            (global.set $next (i32.add (global.get $next) (local.get $len)))
+            (local.get $ptr))
+          (func (export "astra_poll") (result i64)
+            (i64.or (i64.shl (i64.const 16) (i64.const 32)) (i64.const 50)))
+          (func (export "astra_execute") (param $ptr i32) (param $len i32) (result i32)
+            (call $log (local.get $ptr) (local.get $len))
+            (i32.const 1)))
+    "#;
+
+    const RUNAWAY: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "astra_alloc") (param i32) (result i32) (i32.const 0))
+          (func (export "astra_execute") (param i32 i32) (result i32)
+            (loop $forever (br $forever))
+            (i32.const 1)))
+    "#;
+
+    const SNOOPER: &str = r#"
+        (module
+          (import "wasi_snapshot_preview1" "fd_write" (func (param i32 i32 i32 i32) (result i32)))
+          (memory (export "memory") 1))
+    "#;
+
+    fn action(id: &str) -> Action {
+        Action {
+            id: id.into(),
+            description: "Ring the bell".into(),
+            preconditions: HashMap::new(),
+            effects: HashMap::new(),
+            cost: 1.0,
+            risk: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_plugins_load_run_sandboxed_and_unload() {
+        let dir = std::env::temp_dir().join(format!("astra_plugins_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, module, actions) in [("doorbell", DOORBELL, "[\"ring\"]"), ("runaway", RUNAWAY, "[\"spin\"]"), ("snooper", SNOOPER, "[]")] {
+            std::fs::write(dir.join(format!("{}.wat", name)), module).unwrap();
+            let manifest = format!(r#"{{"name": "{0}", "module": "{0}.wat", "actions": {1}, "fuel": 100000}}"#, name, actions);
+            std::fs::write(dir.join(format!("{}.json", name)), manifest).unwrap();
+        }
+
+        let mut host = PluginHost::new().unwrap();
+        let events = host.watch(&dir);
+        assert!(matches!(&events[..], [PluginEvent::Loaded(_), PluginEvent::Loaded(_), PluginEvent::Rejected { .. }]));
+        assert_eq!(host.names().collect::<Vec<_>>(), ["doorbell", "runaway"]);
+
+        let stimuli = host.poll_sensors();
+        assert_eq!(stimuli.len(), 1);
+        assert_eq!((stimuli[0].source.as_str(), stimuli[0].content.as_str()), ("doorbell", "someone is at the door"));
+
+        assert!(host.execute_action(&action("ring(front)")).unwrap());
+        assert!(host.execute_action(&action("spin")).is_err(), "fuel should stop the loop");
+        assert!(host.execute_action(&action("fly")).is_err());
+
+        assert!(host.rescan().is_empty());
+        std::fs::remove_file(dir.join("runaway.json")).unwrap();
+        assert!(matches!(&host.rescan()[..], [PluginEvent::Unloaded(name)] if name == "runaway"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_lengths_outside_plugin_memory_are_refused() {
+        let memory = [7u8; 64];
+        assert_eq!(guest_bytes(&memory, 60, 4), Some(&memory[60..]));
+        assert_eq!(guest_bytes(&memory, 60, 5), None);
+        assert_eq!(guest_bytes(&memory, 0, -1), None);
+        assert_eq!(guest_bytes(&memory, -1, i32::MAX), None);
+        assert_eq!(guest_bytes(&memory, 0, 0), Some(&[][..]));
+    }
+
+    #[test]
+    fn test_manifests_cannot_exceed_the_host_limits() {
+        let dir = std::env::temp_dir().join(format!("astra_plugin_limits_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("greedy.wat"), RUNAWAY).unwrap();
+        let manifest = r#"{"name": "greedy", "module": "greedy.wat", "actions": ["spin"],
+                           "fuel": 18446744073709551615, "max_memory_bytes": 18446744073709551615}"#;
+        std::fs::write(dir.join("greedy.json"), manifest).unwrap();
+
+        let limits = PluginLimits { max_fuel: 50_000, max_memory_bytes: 1 << 20 };
+        let mut host = PluginHost::new().unwrap().with_limits(limits);
+        let manifest = host.load(dir.join("greedy.json")).unwrap();
+        assert_eq!((manifest.fuel, manifest.max_memory_bytes), (50_000, 1 << 20));
+        assert!(host.execute_action(&action("spin")).is_err(), "the capped fuel should stop the loop");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
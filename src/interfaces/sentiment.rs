@@ -0,0 +1,380 @@
+// ============================================================================
+//                     ASTRA AGI • SENTIMENT & EMOTION LEXICON
+//        Valence, Arousal, and Discrete Emotions from Incoming Text
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Interfaces Layer that reads the emotional tone
+//       of what users write. Words are looked up in an affective lexicon
+//       giving each a valence, an arousal, and the basic emotion it
+//       expresses, and a handful of rules adjust the result the way people
+//       read it: negation flips and dampens what follows it, intensifiers
+//       and shouting strengthen it, hedges soften it, the clause after a
+//       "but" outweighs the one before, and exclamation marks and emoticons
+//       add energy. The result feeds emotion contagion, rapport, and any
+//       appraisal that needs to know how a message felt.
+//
+//   Core Functions:
+//       • Score words against an extensible affective lexicon
+//       • Apply negation, intensifier, hedge, contrast, and emphasis rules
+//       • Estimate valence, arousal, and six basic emotions
+//
+//   File:        /src/interfaces/sentiment.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Words that reverse the tone of the next few words.
+const NEGATIONS: &[&str] = &["not", "no", "never", "nothing", "without", "hardly", "isn't", "wasn't", "don't", "doesn't", "didn't", "can't", "won't", "aren't"];
+/// How far a negation reaches.
+const NEGATION_SCOPE: usize = 3;
+/// A negated word keeps this much of its strength, reversed.
+const NEGATION_DAMPING: f32 = 0.6;
+const INTENSIFIERS: &[&str] = &["very", "so", "really", "extremely", "totally", "incredibly", "absolutely", "completely", "super"];
+const INTENSIFIER_BOOST: f32 = 1.5;
+const HEDGES: &[&str] = &["slightly", "somewhat", "kinda", "fairly", "rather", "barely", "little", "bit"];
+const HEDGE_DAMPING: f32 = 0.6;
+/// Weight of the clause after "but" relative to the one before it.
+const CONTRAST_AFTER: f32 = 1.5;
+const CONTRAST_BEFORE: f32 = 0.5;
+
+/// The six basic emotions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Emotion {
+    Joy,
+    Sadness,
+    Anger,
+    Fear,
+    Surprise,
+    Disgust,
+}
+
+/// How strongly each basic emotion is expressed, each in 0.0..=1.0.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct EmotionScores {
+    pub joy: f32,
+    pub sadness: f32,
+    pub anger: f32,
+    pub fear: f32,
+    pub surprise: f32,
+    pub disgust: f32,
+}
+
+impl EmotionScores {
+    pub fn get(&self, emotion: Emotion) -> f32 {
+        match emotion {
+            Emotion::Joy => self.joy,
+            Emotion::Sadness => self.sadness,
+            Emotion::Anger => self.anger,
+            Emotion::Fear => self.fear,
+            Emotion::Surprise => self.surprise,
+            Emotion::Disgust => self.disgust,
+        }
+    }
+
+    fn get_mut(&mut self, emotion: Emotion) -> &mut f32 {
+        match emotion {
+            Emotion::Joy => &mut self.joy,
+            Emotion::Sadness => &mut self.sadness,
+            Emotion::Anger => &mut self.anger,
+            Emotion::Fear => &mut self.fear,
+            Emotion::Surprise => &mut self.surprise,
+            Emotion::Disgust => &mut self.disgust,
+        }
+    }
+
+    /// The strongest emotion, if any is expressed at all.
+    pub fn dominant(&self) -> Option<Emotion> {
+        [Emotion::Joy, Emotion::Sadness, Emotion::Anger, Emotion::Fear, Emotion::Surprise, Emotion::Disgust]
+            .into_iter()
+            .map(|emotion| (emotion, self.get(emotion)))
+            .filter(|(_, score)| *score > 0.0)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(emotion, _)| emotion)
+    }
+}
+
+/// Emotional tone of a piece of text.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct Sentiment {
+    /// -1.0 (hostile) to 1.0 (warm)
+    pub valence: f32,
+    /// 0.0 (calm) to 1.0 (agitated)
+    pub arousal: f32,
+    #[serde(default)]
+    pub emotions: EmotionScores,
+}
+
+/// The affect a word carries.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LexiconEntry {
+    /// -1.0..=1.0
+    pub valence: f32,
+    /// 0.0..=1.0
+    pub arousal: f32,
+    pub emotion: Option<Emotion>,
+}
+
+use Emotion::*;
+
+/// (word, valence, arousal, emotion)
+const LEXICON: &[(&str, f32, f32, Option<Emotion>)] = &[
+    ("thanks", 0.7, 0.4, Some(Joy)),
+    ("thank", 0.7, 0.4, Some(Joy)),
+    ("great", 0.8, 0.6, Some(Joy)),
+    ("good", 0.6, 0.4, Some(Joy)),
+    ("love", 0.9, 0.7, Some(Joy)),
+    ("awesome", 0.9, 0.8, Some(Joy)),
+    ("wonderful", 0.9, 0.7, Some(Joy)),
+    ("appreciate", 0.7, 0.4, Some(Joy)),
+    ("glad", 0.7, 0.5, Some(Joy)),
+    ("happy", 0.8, 0.6, Some(Joy)),
+    ("excellent", 0.9, 0.6, Some(Joy)),
+    ("nice", 0.6, 0.4, Some(Joy)),
+    ("perfect", 0.9, 0.6, Some(Joy)),
+    ("brilliant", 0.9, 0.7, Some(Joy)),
+    ("amazing", 0.9, 0.8, Some(Joy)),
+    ("helpful", 0.7, 0.4, Some(Joy)),
+    ("kind", 0.6, 0.3, Some(Joy)),
+    ("excited", 0.8, 0.9, Some(Joy)),
+    ("delighted", 0.9, 0.7, Some(Joy)),
+    ("fun", 0.7, 0.7, Some(Joy)),
+    ("enjoy", 0.7, 0.6, Some(Joy)),
+    ("pleased", 0.7, 0.4, Some(Joy)),
+    ("relieved", 0.6, 0.3, Some(Joy)),
+    ("calm", 0.4, 0.1, None),
+    ("fine", 0.3, 0.2, None),
+    ("okay", 0.2, 0.2, None),
+    ("sad", -0.7, 0.3, Some(Sadness)),
+    ("unhappy", -0.7, 0.4, Some(Sadness)),
+    ("disappointed", -0.6, 0.4, Some(Sadness)),
+    ("lonely", -0.7, 0.3, Some(Sadness)),
+    ("miss", -0.4, 0.3, Some(Sadness)),
+    ("sorry", -0.3, 0.3, Some(Sadness)),
+    ("tired", -0.4, 0.1, Some(Sadness)),
+    ("depressed", -0.9, 0.2, Some(Sadness)),
+    ("hurt", -0.7, 0.5, Some(Sadness)),
+    ("lost", -0.5, 0.4, Some(Sadness)),
+    ("cry", -0.7, 0.6, Some(Sadness)),
+    ("angry", -0.8, 0.9, Some(Anger)),
+    ("furious", -0.9, 1.0, Some(Anger)),
+    ("hate", -0.9, 0.8, Some(Anger)),
+    ("annoying", -0.6, 0.6, Some(Anger)),
+    ("annoyed", -0.6, 0.6, Some(Anger)),
+    ("frustrated", -0.7, 0.7, Some(Anger)),
+    ("frustrating", -0.7, 0.7, Some(Anger)),
+    ("mad", -0.7, 0.8, Some(Anger)),
+    ("ridiculous", -0.6, 0.7, Some(Anger)),
+    ("useless", -0.8, 0.6, Some(Anger)),
+    ("stupid", -0.8, 0.7, Some(Anger)),
+    ("upset", -0.7, 0.6, Some(Anger)),
+    ("unacceptable", -0.8, 0.7, Some(Anger)),
+    ("afraid", -0.7, 0.7, Some(Fear)),
+    ("scared", -0.7, 0.8, Some(Fear)),
+    ("worried", -0.6, 0.6, Some(Fear)),
+    ("anxious", -0.6, 0.7, Some(Fear)),
+    ("nervous", -0.5, 0.7, Some(Fear)),
+    ("terrified", -0.9, 0.9, Some(Fear)),
+    ("panic", -0.8, 0.9, Some(Fear)),
+    ("urgent", -0.3, 0.8, Some(Fear)),
+    ("danger", -0.7, 0.8, Some(Fear)),
+    ("surprised", 0.2, 0.8, Some(Surprise)),
+    ("surprise", 0.2, 0.8, Some(Surprise)),
+    ("wow", 0.4, 0.8, Some(Surprise)),
+    ("unexpected", 0.0, 0.7, Some(Surprise)),
+    ("shocked", -0.3, 0.9, Some(Surprise)),
+    ("astonished", 0.2, 0.9, Some(Surprise)),
+    ("disgusting", -0.9, 0.6, Some(Disgust)),
+    ("gross", -0.7, 0.5, Some(Disgust)),
+    ("awful", -0.8, 0.6, Some(Disgust)),
+    ("terrible", -0.8, 0.6, Some(Disgust)),
+    ("horrible", -0.9, 0.7, Some(Disgust)),
+    ("worst", -0.9, 0.6, Some(Disgust)),
+    ("nasty", -0.7, 0.6, Some(Disgust)),
+    ("bad", -0.6, 0.4, None),
+    ("wrong", -0.5, 0.4, None),
+    ("broken", -0.6, 0.5, None),
+    ("problem", -0.4, 0.4, None),
+];
+
+/// Emoticons, matched as whole tokens.
+const EMOTICONS: &[(&str, f32, f32, Option<Emotion>)] = &[
+    (":)", 0.6, 0.4, Some(Joy)),
+    (":-)", 0.6, 0.4, Some(Joy)),
+    (":D", 0.8, 0.7, Some(Joy)),
+    ("<3", 0.8, 0.5, Some(Joy)),
+    (":(", -0.6, 0.4, Some(Sadness)),
+    (":-(", -0.6, 0.4, Some(Sadness)),
+    (":'(", -0.8, 0.5, Some(Sadness)),
+    (">:(", -0.8, 0.8, Some(Anger)),
+    (":o", 0.1, 0.8, Some(Surprise)),
+];
+
+/// Reads sentiment and emotion from text with a lexicon and rules.
+#[derive(Debug, Clone)]
+pub struct SentimentAnalyzer {
+    lexicon: HashMap<String, LexiconEntry>,
+}
+
+impl Default for SentimentAnalyzer {
+    fn default() -> Self {
+        let lexicon = LEXICON
+            .iter()
+            .map(|&(word, valence, arousal, emotion)| (word.to_string(), LexiconEntry { valence, arousal, emotion }))
+            .collect();
+        Self { lexicon }
+    }
+}
+
+impl SentimentAnalyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces a lexicon entry, e.g. for domain vocabulary.
+    pub fn add_word(&mut self, word: &str, entry: LexiconEntry) {
+        self.lexicon.insert(word.to_lowercase(), entry);
+    }
+
+    /// The entry for `word`, trying common inflections if it is not listed
+    /// as written.
+    pub fn lookup(&self, word: &str) -> Option<&LexiconEntry> {
+        if let Some(entry) = self.lexicon.get(word) {
+            return Some(entry);
+        }
+        ["s", "ed", "d", "ing", "ly"]
+            .iter()
+            .filter_map(|suffix| word.strip_suffix(suffix))
+            .find_map(|stem| self.lexicon.get(stem))
+    }
+
+    pub fn analyze(&self, input: &str) -> Sentiment {
+        // (valence, arousal, emotion, weight) for each affective token
+        let mut hits: Vec<(f32, f32, Option<Emotion>, f32)> = Vec::new();
+        let mut emphasis = input.matches('!').count() as f32;
+
+        let mut negated_for = 0usize;
+        let mut boost = 1.0f32;
+        let mut contrast_start = None;
+        for raw in input.split_whitespace() {
+            if let Some(&(_, valence, arousal, emotion)) = EMOTICONS.iter().find(|(emoticon, ..)| *emoticon == raw) {
+                hits.push((valence, arousal, emotion, 1.0));
+                continue;
+            }
+            let word: String = raw.chars().filter(|c| c.is_alphanumeric() || *c == '\'').collect();
+            if word.is_empty() {
+                continue;
+            }
+            let shouted = word.len() > 2 && word.chars().all(|c| !c.is_lowercase()) && word.chars().any(char::is_alphabetic);
+            if shouted {
+                emphasis += 1.0;
+            }
+            let lower = word.to_lowercase();
+
+            if lower == "but" || lower == "however" {
+                contrast_start = Some(hits.len());
+                negated_for = 0;
+                continue;
+            }
+            if NEGATIONS.contains(&lower.as_str()) || lower.ends_with("n't") {
+                negated_for = NEGATION_SCOPE;
+                continue;
+            }
+            if INTENSIFIERS.contains(&lower.as_str()) {
+                boost *= INTENSIFIER_BOOST;
+                continue;
+            }
+            if HEDGES.contains(&lower.as_str()) {
+                boost *= HEDGE_DAMPING;
+                continue;
+            }
+
+            if let Some(entry) = self.lookup(&lower) {
+                let mut weight = boost * if shouted { INTENSIFIER_BOOST } else { 1.0 };
+                let (mut valence, mut emotion) = (entry.valence, entry.emotion);
+                if negated_for > 0 {
+                    // "not happy" is mildly negative, not sad; "not bad" mildly positive
+                    valence = -valence * NEGATION_DAMPING;
+                    weight *= NEGATION_DAMPING;
+                    emotion = None;
+                }
+                hits.push((valence, entry.arousal, emotion, weight));
+            }
+            negated_for = negated_for.saturating_sub(1);
+            boost = 1.0;
+            // A sentence boundary ends the reach of a negation
+            if raw.ends_with(['.', '!', '?', ';']) {
+                negated_for = 0;
+            }
+        }
+
+        if hits.is_empty() {
+            return Sentiment::default();
+        }
+        if let Some(start) = contrast_start {
+            for (index, hit) in hits.iter_mut().enumerate() {
+                hit.3 *= if index < start { CONTRAST_BEFORE } else { CONTRAST_AFTER };
+            }
+        }
+
+        let total_weight: f32 = hits.iter().map(|h| h.3).sum();
+        // Dividing by at least 1 lets a lone hedged or negated word stay weak
+        let valence = hits.iter().map(|h| h.0 * h.3).sum::<f32>() / total_weight.max(1.0);
+        let mean_arousal = hits.iter().map(|h| h.1 * h.3).sum::<f32>() / total_weight;
+        // More affective words and more emphasis make a message more agitated
+        let arousal = mean_arousal * 0.6 + 0.08 * hits.len() as f32 + 0.12 * emphasis;
+
+        let mut emotions = EmotionScores::default();
+        for (_, _, emotion, weight) in &hits {
+            if let Some(emotion) = emotion {
+                *emotions.get_mut(*emotion) += weight;
+            }
+        }
+        for emotion in [Joy, Sadness, Anger, Fear, Surprise, Disgust] {
+            let score = emotions.get_mut(emotion);
+            *score = (*score / total_weight.max(1.0)).clamp(0.0, 1.0);
+        }
+
+        Sentiment {
+            valence: (valence * (1.0 + 0.1 * (total_weight - 1.0).max(0.0))).clamp(-1.0, 1.0),
+            arousal: arousal.clamp(0.0, 1.0),
+            emotions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_shape_valence_and_emotion() {
+        let analyzer = SentimentAnalyzer::new();
+
+        let scared = analyzer.analyze("I'm really worried about the surgery tomorrow");
+        assert!(scared.valence < -0.5);
+        assert_eq!(scared.emotions.dominant(), Some(Emotion::Fear));
+
+        let negated = analyzer.analyze("This is not bad at all");
+        assert!(negated.valence > 0.0 && negated.valence < 0.5);
+        assert_eq!(negated.emotions.dominant(), None);
+
+        let contrast = analyzer.analyze("The setup was annoying but the result is amazing :)");
+        assert!(contrast.valence > 0.3);
+        assert_eq!(contrast.emotions.dominant(), Some(Emotion::Joy));
+
+        let calm = analyzer.analyze("thanks, that's fine");
+        let shouted = analyzer.analyze("THANKS, that is AMAZING!!!");
+        assert!(shouted.arousal > calm.arousal + 0.3);
+        assert!(analyzer.analyze("kinda helpful").valence < analyzer.analyze("helpful").valence);
+        assert!(analyzer.analyze("He hated it").valence < -0.5);
+    }
+}
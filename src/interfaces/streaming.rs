@@ -0,0 +1,260 @@
+// ============================================================================
+//                        ASTRA AGI • STREAMING RESPONSES
+//          Delivering Long Answers Token by Token, Interruptibly
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Interfaces Layer that delivers a reply while it
+//       is still being generated, instead of after. Any generator—template
+//       expansion today, a language model tomorrow—implements `TokenSource`
+//       and runs on its own thread, handing tokens over a bounded channel:
+//       when the consumer (a WebSocket, a terminal) falls behind, the channel
+//       fills and generation waits, so Astra never produces faster than the
+//       user can be shown. If the user interrupts, the stream is cancelled
+//       and generation stops at the next token.
+//
+//   Core Functions:
+//       • Run token generators off the caller's thread with bounded buffering
+//       • Deliver tokens as serializable stream events, async or blocking
+//       • Cancel generation when the user interrupts or disconnects
+//       • Write streams to a terminal as they arrive
+//
+//   File:        /src/interfaces/streaming.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Tokens buffered ahead of the consumer before generation waits.
+pub const DEFAULT_CAPACITY: usize = 8;
+
+/// Something that produces a response a piece at a time.
+pub trait TokenSource: Send {
+    /// The next piece of the response, or `None` once it is complete.
+    fn next_token(&mut self) -> Option<String>;
+}
+
+/// Streams an already written reply, such as a filled-in template, one
+/// word at a time. Each token keeps the whitespace that follows it, so the
+/// tokens concatenate back into the original text.
+#[derive(Debug, Clone)]
+pub struct TemplateSource {
+    text: String,
+    position: usize,
+}
+
+impl TemplateSource {
+    pub fn new(text: impl Into<String>) -> Self {
+        TemplateSource { text: text.into(), position: 0 }
+    }
+}
+
+impl TokenSource for TemplateSource {
+    fn next_token(&mut self) -> Option<String> {
+        let rest = &self.text[self.position..];
+        if rest.is_empty() {
+            return None;
+        }
+        let word_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let end = rest[word_end..].find(|c: char| !c.is_whitespace()).map_or(rest.len(), |i| word_end + i);
+        self.position += end;
+        Some(rest[..end].to_string())
+    }
+}
+
+/// What a client receives, one event per WebSocket frame or line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamEvent {
+    Token { index: usize, text: String },
+    /// The response is complete
+    Done { tokens: usize },
+    /// The response was cut short at the user's request
+    Cancelled { tokens: usize },
+}
+
+impl StreamEvent {
+    /// Whether this event ends the stream.
+    pub fn is_final(&self) -> bool {
+        !matches!(self, StreamEvent::Token { .. })
+    }
+}
+
+/// Stops a stream's generation. Clones share the same stream, so one can be
+/// handed to whatever notices the user interrupting.
+#[derive(Debug, Clone, Default)]
+pub struct CancelHandle(Arc<AtomicBool>);
+
+impl CancelHandle {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// The consumer's end of a response being generated.
+///
+/// Events are read with `next` from async code or `blocking_next` from
+/// ordinary threads; either way the stream ends with exactly one `Done` or
+/// `Cancelled`, after which both return `None`. Dropping the stream cancels
+/// generation.
+pub struct ResponseStream {
+    receiver: mpsc::Receiver<String>,
+    cancel: CancelHandle,
+    delivered: usize,
+    text: String,
+    finished: bool,
+}
+
+impl ResponseStream {
+    /// Starts generating from `source` on its own thread, buffering at most
+    /// `capacity` tokens ahead of the consumer.
+    pub fn start<S: TokenSource + 'static>(mut source: S, capacity: usize) -> Self {
+        let (sender, receiver) = mpsc::channel(capacity.max(1));
+        let cancel = CancelHandle::default();
+        let producer_cancel = cancel.clone();
+        std::thread::spawn(move || {
+            while !producer_cancel.is_cancelled() {
+                let Some(token) = source.next_token() else { break };
+                // Waits while the buffer is full; fails once the consumer is gone
+                if sender.blocking_send(token).is_err() {
+                    break;
+                }
+            }
+        });
+        ResponseStream { receiver, cancel, delivered: 0, text: String::new(), finished: false }
+    }
+
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.cancel.clone()
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+    }
+
+    /// The response as delivered so far.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The next event, waiting for generation if it is behind.
+    pub async fn next(&mut self) -> Option<StreamEvent> {
+        if self.finished {
+            return None;
+        }
+        let received = if self.cancel.is_cancelled() { None } else { self.receiver.recv().await };
+        Some(self.accept(received))
+    }
+
+    /// `next` for callers outside an async runtime. Must not be called from
+    /// within one.
+    pub fn blocking_next(&mut self) -> Option<StreamEvent> {
+        if self.finished {
+            return None;
+        }
+        let received = if self.cancel.is_cancelled() { None } else { self.receiver.blocking_recv() };
+        Some(self.accept(received))
+    }
+
+    fn accept(&mut self, received: Option<String>) -> StreamEvent {
+        // A cancellation that lands while waiting still discards the token
+        match received {
+            Some(text) if !self.cancel.is_cancelled() => {
+                self.text.push_str(&text);
+                self.delivered += 1;
+                StreamEvent::Token { index: self.delivered - 1, text }
+            }
+            _ => {
+                self.finished = true;
+                // Unblocks a generator waiting on a full buffer
+                self.receiver.close();
+                if self.cancel.is_cancelled() {
+                    StreamEvent::Cancelled { tokens: self.delivered }
+                } else {
+                    StreamEvent::Done { tokens: self.delivered }
+                }
+            }
+        }
+    }
+
+    /// Writes tokens to `out` as they arrive, flushing each so a terminal
+    /// shows them immediately, and returns the final event.
+    pub fn write_to(&mut self, out: &mut impl Write) -> io::Result<StreamEvent> {
+        while let Some(event) = self.blocking_next() {
+            match event {
+                StreamEvent::Token { text, .. } => {
+                    out.write_all(text.as_bytes())?;
+                    out.flush()?;
+                }
+                end => return Ok(end),
+            }
+        }
+        Ok(StreamEvent::Done { tokens: self.delivered })
+    }
+}
+
+impl Drop for ResponseStream {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Duration;
+
+    /// Counts how many tokens it has been asked for, forever.
+    struct Endless(Arc<AtomicUsize>);
+
+    impl TokenSource for Endless {
+        fn next_token(&mut self) -> Option<String> {
+            let n = self.0.fetch_add(1, Ordering::SeqCst);
+            Some(format!("{} ", n))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streams_complete_cancel_and_apply_backpressure() {
+        let reply = "Tomatoes need  full sun.\nWater them daily.";
+        let mut stream = ResponseStream::start(TemplateSource::new(reply), DEFAULT_CAPACITY);
+        let mut events = Vec::new();
+        while let Some(event) = stream.next().await {
+            events.push(event);
+        }
+        assert_eq!(stream.text(), reply);
+        assert_eq!(events[1], StreamEvent::Token { index: 1, text: "need  ".into() });
+        assert_eq!(events.last(), Some(&StreamEvent::Done { tokens: 7 }));
+
+        // Generation waits for the consumer instead of running ahead
+        let produced = Arc::new(AtomicUsize::new(0));
+        let mut stream = ResponseStream::start(Endless(produced.clone()), 2);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(produced.load(Ordering::SeqCst) <= 3);
+
+        stream.next().await;
+        stream.next().await;
+        stream.cancel();
+        assert_eq!(stream.next().await, Some(StreamEvent::Cancelled { tokens: 2 }));
+        assert_eq!(stream.next().await, None);
+        assert_eq!(stream.text(), "0 1 ");
+
+        let stopped_at = produced.load(Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(produced.load(Ordering::SeqCst) <= stopped_at + 1);
+    }
+}
@@ -0,0 +1,558 @@
+// ============================================================================
+//                   ASTRA AGI • TEMPORAL EXPRESSION PARSER
+//        Deadline & Duration Inference from Natural Language
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Interfaces Layer that turns phrases such as
+//       "remind me Friday evening", "in two hours" or "at 5 for 30 minutes"
+//       into concrete deadlines and durations. Local times are interpreted in
+//       a configurable timezone and returned in UTC. When an utterance admits
+//       several readings ("at 5", "next Friday"), all candidates are
+//       returned along with a clarification prompt instead of guessing.
+//
+//   Core Functions:
+//       • Recognize relative offsets ("in 3 days") and durations ("for an hour")
+//       • Recognize absolute dates, weekdays, parts of day and clock times
+//       • Resolve local times in the configured timezone
+//       • Surface ambiguous readings with a prompt for the user
+//
+//   File:        /src/interfaces/temporal.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use std::time::Duration;
+
+/// One concrete reading of a temporal expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemporalInterpretation {
+    pub deadline: Option<DateTime<Utc>>,
+    pub duration: Option<Duration>,
+    /// Deadline rendered in the parser's timezone, for prompts and logs
+    pub description: String,
+}
+
+/// Outcome of parsing an utterance for temporal expressions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemporalResolution {
+    NotFound,
+    Resolved(TemporalInterpretation),
+    Ambiguous {
+        candidates: Vec<TemporalInterpretation>,
+        prompt: String,
+    },
+}
+
+/// Parses temporal expressions relative to a reference time.
+#[derive(Debug, Clone)]
+pub struct TemporalParser {
+    /// Timezone in which the user's local times are interpreted
+    pub timezone: FixedOffset,
+    /// Hour used when only a date is given ("on Friday")
+    pub default_hour: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PartOfDay {
+    Morning,
+    Noon,
+    Afternoon,
+    Evening,
+    Night,
+}
+
+impl PartOfDay {
+    fn default_time(self) -> NaiveTime {
+        let hour = match self {
+            PartOfDay::Morning => 9,
+            PartOfDay::Noon => 12,
+            PartOfDay::Afternoon => 15,
+            PartOfDay::Evening => 18,
+            PartOfDay::Night => 21,
+        };
+        NaiveTime::from_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    fn is_after_noon(self) -> bool {
+        !matches!(self, PartOfDay::Morning)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum WeekdayModifier {
+    Bare,
+    This,
+    Next,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DateSpec {
+    Fixed(NaiveDate),
+    Weekday(Weekday, WeekdayModifier),
+}
+
+impl TemporalParser {
+    pub fn new(timezone: FixedOffset) -> Self {
+        TemporalParser { timezone, default_hour: 9 }
+    }
+
+    pub fn utc() -> Self {
+        Self::new(FixedOffset::east_opt(0).unwrap())
+    }
+
+    /// Parses `text` relative to `now`.
+    pub fn parse(&self, text: &str, now: DateTime<Utc>) -> TemporalResolution {
+        let tokens = tokenize(text);
+        let duration = parse_offset(&tokens, &["for"]).and_then(|d| d.to_std().ok());
+
+        if let Some(offset) = parse_offset(&tokens, &["in", "within"]) {
+            // An offset past the end of the calendar names no time at all
+            return match now.checked_add_signed(offset) {
+                Some(deadline) => TemporalResolution::Resolved(self.interpretation(Some(deadline), duration)),
+                None => TemporalResolution::NotFound,
+            };
+        }
+
+        let local_now = now.with_timezone(&self.timezone);
+        let today = local_now.date_naive();
+        let part_of_day = find_part_of_day(&tokens);
+        let date_spec = find_date(&tokens, today);
+        let times = find_clock_times(&tokens, part_of_day).or_else(|| part_of_day.map(|p| vec![p.default_time()]));
+
+        let mut deadlines: Vec<DateTime<Utc>> = match (date_spec, times) {
+            (None, None) => {
+                return match duration {
+                    Some(_) => TemporalResolution::Resolved(self.interpretation(None, duration)),
+                    None => TemporalResolution::NotFound,
+                };
+            }
+            (None, Some(times)) => times
+                .into_iter()
+                .map(|time| {
+                    // A bare time means its next occurrence.
+                    let candidate = self.to_utc(today, time);
+                    if candidate > now {
+                        candidate
+                    } else {
+                        self.to_utc(today + ChronoDuration::days(1), time)
+                    }
+                })
+                .collect(),
+            (Some(spec), times) => {
+                let explicit_time = times.is_some();
+                let times = times.unwrap_or_else(|| vec![NaiveTime::from_hms_opt(self.default_hour, 0, 0).unwrap()]);
+                let mut out = Vec::new();
+                for time in times {
+                    let time_ahead = |d: NaiveDate| explicit_time.then(|| self.to_utc(d, time) > now);
+                    for date in resolve_date(spec, today, time_ahead) {
+                        out.push(self.to_utc(date, time));
+                    }
+                }
+                out
+            }
+        };
+
+        deadlines.sort();
+        deadlines.dedup();
+
+        let mut candidates: Vec<TemporalInterpretation> =
+            deadlines.into_iter().map(|d| self.interpretation(Some(d), duration)).collect();
+
+        if candidates.len() == 1 {
+            TemporalResolution::Resolved(candidates.remove(0))
+        } else {
+            let options: Vec<String> = candidates
+                .iter()
+                .enumerate()
+                .map(|(i, c)| format!("{}) {}", i + 1, c.description))
+                .collect();
+            TemporalResolution::Ambiguous {
+                prompt: format!("When exactly did you mean? {}", options.join(", ")),
+                candidates,
+            }
+        }
+    }
+
+    fn to_utc(&self, date: NaiveDate, time: NaiveTime) -> DateTime<Utc> {
+        self.timezone
+            .from_local_datetime(&date.and_time(time))
+            .single()
+            .expect("fixed offsets have no gaps")
+            .with_timezone(&Utc)
+    }
+
+    fn interpretation(&self, deadline: Option<DateTime<Utc>>, duration: Option<Duration>) -> TemporalInterpretation {
+        let mut description = deadline
+            .map(|d| d.with_timezone(&self.timezone).format("%a %d %b %Y %H:%M %:z").to_string())
+            .unwrap_or_default();
+        if let Some(duration) = duration {
+            if !description.is_empty() {
+                description.push(' ');
+            }
+            description.push_str(&format!("for {} min", duration.as_secs() / 60));
+        }
+        TemporalInterpretation { deadline, duration, description }
+    }
+}
+
+impl Default for TemporalParser {
+    fn default() -> Self {
+        Self::utc()
+    }
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| c.is_whitespace() || matches!(c, ',' | '.' | '!' | '?' | ';'))
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn parse_number(token: &str) -> Option<i64> {
+    if let Ok(n) = token.parse() {
+        return Some(n);
+    }
+    let words = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven", "twelve",
+    ];
+    match token {
+        "a" | "an" => Some(1),
+        _ => words.iter().position(|w| *w == token).map(|i| i as i64 + 1),
+    }
+}
+
+/// `amount` of `unit`, or `None` for an unknown unit or an amount too large
+/// to represent.
+fn unit_duration(unit: &str, amount: i64) -> Option<ChronoDuration> {
+    match unit.trim_end_matches('s') {
+        "minute" | "min" => ChronoDuration::try_minutes(amount),
+        "hour" | "hr" => ChronoDuration::try_hours(amount),
+        "day" => ChronoDuration::try_days(amount),
+        "week" => ChronoDuration::try_weeks(amount),
+        _ => None,
+    }
+}
+
+/// Finds "<prefix> <amount> <unit>" (or "<prefix> half an hour").
+fn parse_offset(tokens: &[String], prefixes: &[&str]) -> Option<ChronoDuration> {
+    for (i, token) in tokens.iter().enumerate() {
+        if !prefixes.contains(&token.as_str()) {
+            continue;
+        }
+        let rest = &tokens[i + 1..];
+        if rest.len() >= 3 && rest[0] == "half" && matches!(rest[1].as_str(), "a" | "an") && rest[2] == "hour" {
+            return Some(ChronoDuration::minutes(30));
+        }
+        if rest.len() >= 2 {
+            if let Some(amount) = parse_number(&rest[0]) {
+                if let Some(duration) = unit_duration(&rest[1], amount) {
+                    return Some(duration);
+                }
+            }
+        }
+    }
+    None
+}
+
+fn find_part_of_day(tokens: &[String]) -> Option<PartOfDay> {
+    tokens.iter().find_map(|t| match t.as_str() {
+        "morning" => Some(PartOfDay::Morning),
+        "noon" | "midday" => Some(PartOfDay::Noon),
+        "afternoon" => Some(PartOfDay::Afternoon),
+        "evening" => Some(PartOfDay::Evening),
+        "tonight" | "night" => Some(PartOfDay::Night),
+        _ => None,
+    })
+}
+
+fn parse_weekday(token: &str) -> Option<Weekday> {
+    match token {
+        "monday" => Some(Weekday::Mon),
+        "tuesday" => Some(Weekday::Tue),
+        "wednesday" => Some(Weekday::Wed),
+        "thursday" => Some(Weekday::Thu),
+        "friday" => Some(Weekday::Fri),
+        "saturday" => Some(Weekday::Sat),
+        "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Abbreviated weekdays. Some are also everyday words ("sat", "sun",
+/// "wed"), so they are only read as weekdays next to a date cue.
+fn parse_weekday_abbreviation(token: &str) -> Option<Weekday> {
+    match token {
+        "mon" => Some(Weekday::Mon),
+        "tue" | "tues" => Some(Weekday::Tue),
+        "wed" => Some(Weekday::Wed),
+        "thu" | "thur" | "thurs" => Some(Weekday::Thu),
+        "fri" => Some(Weekday::Fri),
+        "sat" => Some(Weekday::Sat),
+        "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Whether the token at `i` is preceded by a word that introduces a date
+/// ("on", "next", "by", ...) or followed by a time ("at", "5pm", "evening").
+fn next_to_date_cue(tokens: &[String], i: usize) -> bool {
+    let before = i.checked_sub(1).map(|p| tokens[p].as_str());
+    matches!(before, Some("on" | "next" | "this" | "coming" | "by" | "until" | "before" | "every"))
+        || matches!(tokens.get(i + 1).map(String::as_str), Some("at" | "by"))
+        || tokens.get(i + 1).is_some_and(|t| {
+            t.contains(':') || matches!(parse_clock_token(t), Some((_, _, Some(_))))
+                || find_part_of_day(std::slice::from_ref(t)).is_some()
+        })
+}
+
+fn parse_month(token: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "january", "february", "march", "april", "may", "june", "july", "august", "september", "october",
+        "november", "december",
+    ];
+    let token = if token == "sept" { "sep" } else { token };
+    MONTHS
+        .iter()
+        .position(|m| *m == token || (token.len() == 3 && m.starts_with(token)))
+        .map(|i| i as u32 + 1)
+}
+
+fn parse_day_of_month(token: &str) -> Option<u32> {
+    let digits = token.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    digits.parse().ok().filter(|d| (1..=31).contains(d))
+}
+
+fn find_date(tokens: &[String], today: NaiveDate) -> Option<DateSpec> {
+    for (i, token) in tokens.iter().enumerate() {
+        match token.as_str() {
+            "today" | "tonight" => return Some(DateSpec::Fixed(today)),
+            "tomorrow" => {
+                let after = i >= 2 && tokens[i - 1] == "after" && tokens[i - 2] == "day";
+                let offset = if after { 2 } else { 1 };
+                return Some(DateSpec::Fixed(today + ChronoDuration::days(offset)));
+            }
+            _ => {}
+        }
+
+        if let Ok(date) = NaiveDate::parse_from_str(token, "%Y-%m-%d") {
+            return Some(DateSpec::Fixed(date));
+        }
+
+        let weekday = parse_weekday(token)
+            .or_else(|| parse_weekday_abbreviation(token).filter(|_| next_to_date_cue(tokens, i)));
+        if let Some(day) = weekday {
+            let modifier = match i.checked_sub(1).map(|p| tokens[p].as_str()) {
+                Some("next") => WeekdayModifier::Next,
+                Some("this") | Some("coming") => WeekdayModifier::This,
+                _ => WeekdayModifier::Bare,
+            };
+            return Some(DateSpec::Weekday(day, modifier));
+        }
+
+        if let Some(month) = parse_month(token) {
+            // "october 20" or "20 october" / "20th of october"
+            let day = tokens
+                .get(i + 1)
+                .and_then(|t| parse_day_of_month(t))
+                .or_else(|| {
+                    let before = if i >= 2 && tokens[i - 1] == "of" { i - 2 } else { i.checked_sub(1)? };
+                    parse_day_of_month(&tokens[before])
+                });
+            if let Some(day) = day {
+                let this_year = NaiveDate::from_ymd_opt(today.year(), month, day);
+                let date = match this_year {
+                    Some(date) if date >= today => Some(date),
+                    _ => NaiveDate::from_ymd_opt(today.year() + 1, month, day),
+                };
+                if let Some(date) = date {
+                    return Some(DateSpec::Fixed(date));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Resolves a date spec to one or more candidate dates. `time_ahead`
+/// reports whether the explicitly given time, if any, is still in the future
+/// on a date.
+fn resolve_date(spec: DateSpec, today: NaiveDate, time_ahead: impl Fn(NaiveDate) -> Option<bool>) -> Vec<NaiveDate> {
+    match spec {
+        DateSpec::Fixed(date) => vec![date],
+        DateSpec::Weekday(day, modifier) => {
+            let days_ahead = (day.num_days_from_monday() as i64 - today.weekday().num_days_from_monday() as i64)
+                .rem_euclid(7);
+            match (days_ahead, modifier) {
+                (0, WeekdayModifier::This) => vec![today],
+                // Naming today's weekday: today if the time is still ahead,
+                // next week if it has passed, and unclear without a time.
+                (0, WeekdayModifier::Bare) => match time_ahead(today) {
+                    Some(true) => vec![today],
+                    Some(false) => vec![today + ChronoDuration::days(7)],
+                    None => vec![today, today + ChronoDuration::days(7)],
+                },
+                (0, WeekdayModifier::Next) => vec![today + ChronoDuration::days(7)],
+                (n, WeekdayModifier::Next) => {
+                    // "next Friday" may mean the coming one or the one after.
+                    let coming = today + ChronoDuration::days(n);
+                    vec![coming, coming + ChronoDuration::days(7)]
+                }
+                (n, _) => vec![today + ChronoDuration::days(n)],
+            }
+        }
+    }
+}
+
+/// Parses a single clock token like "5", "5pm", "17:30" or "5:30am".
+fn parse_clock_token(token: &str) -> Option<(u32, u32, Option<bool>)> {
+    let (body, pm) = if let Some(body) = token.strip_suffix("pm") {
+        (body, Some(true))
+    } else if let Some(body) = token.strip_suffix("am") {
+        (body, Some(false))
+    } else {
+        (token, None)
+    };
+
+    let (hour, minute) = match body.split_once(':') {
+        Some((h, m)) => (h.parse().ok()?, m.parse().ok()?),
+        None => (body.parse().ok()?, 0),
+    };
+
+    if hour > 23 || minute > 59 || (pm.is_some() && !(1..=12).contains(&hour)) {
+        return None;
+    }
+    Some((hour, minute, pm))
+}
+
+fn find_clock_times(tokens: &[String], part_of_day: Option<PartOfDay>) -> Option<Vec<NaiveTime>> {
+    for (i, token) in tokens.iter().enumerate() {
+        let Some((hour, minute, mut pm)) = parse_clock_token(token) else { continue };
+
+        let has_marker = token.contains(':') || pm.is_some();
+        let after_preposition = i > 0 && matches!(tokens[i - 1].as_str(), "at" | "by" | "around" | "before");
+        if !has_marker && !after_preposition {
+            continue;
+        }
+        if pm.is_none() {
+            pm = match tokens.get(i + 1).map(String::as_str) {
+                Some("pm") => Some(true),
+                Some("am") => Some(false),
+                _ => None,
+            };
+        }
+
+        let hours: Vec<u32> = match pm {
+            Some(true) => vec![if hour == 12 { 12 } else { hour + 12 }],
+            Some(false) => vec![if hour == 12 { 0 } else { hour }],
+            None if hour == 0 || hour >= 12 => vec![hour],
+            None => match part_of_day {
+                Some(p) if p.is_after_noon() => vec![hour + 12],
+                Some(_) => vec![hour],
+                None => vec![hour, hour + 12],
+            },
+        };
+
+        return Some(hours.into_iter().filter_map(|h| NaiveTime::from_hms_opt(h, minute, 0)).collect());
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Wednesday 2026-10-14 10:00 UTC
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 14, 10, 0, 0).unwrap()
+    }
+
+    fn resolved(resolution: TemporalResolution) -> TemporalInterpretation {
+        match resolution {
+            TemporalResolution::Resolved(interp) => interp,
+            other => panic!("expected a single reading, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_weekday_with_part_of_day() {
+        let parser = TemporalParser::utc();
+        let interp = resolved(parser.parse("Remind me Friday evening", now()));
+        assert_eq!(interp.deadline, Some(Utc.with_ymd_and_hms(2026, 10, 16, 18, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_timezone_is_applied() {
+        let parser = TemporalParser::new(FixedOffset::east_opt(2 * 3600).unwrap());
+        let interp = resolved(parser.parse("tomorrow at 9am", now()));
+        assert_eq!(interp.deadline, Some(Utc.with_ymd_and_hms(2026, 10, 15, 7, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_relative_offset_and_duration() {
+        let parser = TemporalParser::utc();
+        let interp = resolved(parser.parse("check the oven in 2 hours", now()));
+        assert_eq!(interp.deadline, Some(now() + ChronoDuration::hours(2)));
+
+        let interp = resolved(parser.parse("meeting on 2026-10-20 at 14:30 for 45 minutes", now()));
+        assert_eq!(interp.deadline, Some(Utc.with_ymd_and_hms(2026, 10, 20, 14, 30, 0).unwrap()));
+        assert_eq!(interp.duration, Some(Duration::from_secs(45 * 60)));
+    }
+
+    #[test]
+    fn test_offsets_too_large_are_not_found() {
+        let parser = TemporalParser::utc();
+        for text in ["remind me in 99999999999999 weeks", "in 9999999999 days", "in 9223372036854775807 minutes"] {
+            assert_eq!(parser.parse(text, now()), TemporalResolution::NotFound, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_month_names() {
+        let parser = TemporalParser::utc();
+        let interp = resolved(parser.parse("renew passport by march 3rd", now()));
+        assert_eq!(interp.deadline, Some(Utc.with_ymd_and_hms(2027, 3, 3, 9, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_ambiguous_readings_prompt() {
+        let parser = TemporalParser::utc();
+        match parser.parse("call mom at 5", now()) {
+            TemporalResolution::Ambiguous { candidates, prompt } => {
+                assert_eq!(candidates.len(), 2);
+                assert!(prompt.contains("1)") && prompt.contains("2)"));
+            }
+            other => panic!("expected ambiguity, got {:?}", other),
+        }
+
+        match parser.parse("lunch next friday", now()) {
+            TemporalResolution::Ambiguous { candidates, .. } => assert_eq!(candidates.len(), 2),
+            other => panic!("expected ambiguity, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_no_temporal_expression() {
+        let parser = TemporalParser::utc();
+        assert_eq!(parser.parse("hello there", now()), TemporalResolution::NotFound);
+    }
+
+    #[test]
+    fn test_weekday_abbreviations_need_a_date_cue() {
+        let parser = TemporalParser::utc();
+        for text in ["I sat down", "the sun is out", "we wed in June", "mon ami"] {
+            assert_eq!(parser.parse(text, now()), TemporalResolution::NotFound, "{}", text);
+        }
+
+        let saturday = Utc.with_ymd_and_hms(2026, 10, 17, 9, 0, 0).unwrap();
+        assert_eq!(resolved(parser.parse("dinner on sat", now())).deadline, Some(saturday));
+        assert_eq!(resolved(parser.parse("sat at 9am works", now())).deadline, Some(saturday));
+        assert_eq!(resolved(parser.parse("sat morning", now())).deadline, Some(saturday));
+    }
+}
@@ -0,0 +1,157 @@
+// ============================================================================
+//                       ASTRA AGI • BELIEF CHANGE LEDGER
+//        Append-Only Audit Trail of Epistemic Revisions
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s epistemic layer that records every belief
+//       revision attempt: the confidence before and after, the evidence that
+//       triggered it, and the reasoner parameters in force at the time.
+//       Entries are never modified or removed, so the ledger answers both
+//       "why does Astra believe this?" and "how did that belief evolve?".
+//
+//   Core Functions:
+//       • Append revision records, accepted or rejected
+//       • Look up the full revision history of a fact
+//       • Summarize how a belief reached its current confidence
+//       • Restore recorded revisions unchanged after a restart
+//
+//   File:        /src/knowledge/belief_ledger.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use crate::knowledge::extended_ontology::{current_unix_timestamp, Confidence, EntityId, Fact, Provenance};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Identifies a statement independently of its confidence or provenance.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FactKey {
+    pub subject: EntityId,
+    pub predicate: String,
+    pub object: String,
+}
+
+impl From<&Fact> for FactKey {
+    fn from(fact: &Fact) -> Self {
+        FactKey {
+            subject: fact.subject,
+            predicate: fact.predicate.clone(),
+            object: fact.object.clone(),
+        }
+    }
+}
+
+/// Whether a revision changed the belief.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RevisionOutcome {
+    Accepted,
+    Rejected(String),
+}
+
+/// A single recorded revision attempt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeliefRevision {
+    /// Position in the ledger, strictly increasing
+    pub sequence: u64,
+    pub timestamp: u64,
+    pub fact: FactKey,
+    pub old_confidence: Confidence,
+    /// Equal to `old_confidence` when the revision was rejected
+    pub new_confidence: Confidence,
+    /// Confidence carried by the triggering evidence
+    pub evidence_confidence: Confidence,
+    /// Where the triggering evidence came from
+    pub evidence: Provenance,
+    /// Name of the revision procedure (e.g. "revise_belief")
+    pub method: String,
+    /// Reasoner parameters in force when the revision ran
+    pub parameters: HashMap<String, f64>,
+    pub outcome: RevisionOutcome,
+}
+
+/// Append-only history of belief changes, indexed by fact.
+#[derive(Debug, Default)]
+pub struct BeliefLedger {
+    entries: Vec<BeliefRevision>,
+    by_fact: HashMap<FactKey, Vec<usize>>,
+}
+
+impl BeliefLedger {
+    pub fn new() -> Self {
+        BeliefLedger::default()
+    }
+
+    /// Appends a revision and returns its sequence number.
+    /// The sequence and timestamp supplied in `revision` are overwritten.
+    pub fn record(&mut self, mut revision: BeliefRevision) -> u64 {
+        let position = self.entries.len();
+        revision.sequence = position as u64;
+        revision.timestamp = current_unix_timestamp();
+
+        self.by_fact.entry(revision.fact.clone()).or_default().push(position);
+        self.entries.push(revision);
+        position as u64
+    }
+
+    /// Appends a revision as it was recorded, keeping its timestamp. Its
+    /// sequence number is its position, as for `record`.
+    pub fn restore(&mut self, mut revision: BeliefRevision) -> u64 {
+        let position = self.entries.len();
+        revision.sequence = position as u64;
+
+        self.by_fact.entry(revision.fact.clone()).or_default().push(position);
+        self.entries.push(revision);
+        position as u64
+    }
+
+    /// Returns every revision of a fact, oldest first.
+    pub fn history(&self, fact: &FactKey) -> Vec<&BeliefRevision> {
+        self.by_fact
+            .get(fact)
+            .map(|positions| positions.iter().map(|p| &self.entries[*p]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the most recent accepted revision of a fact.
+    pub fn latest_accepted(&self, fact: &FactKey) -> Option<&BeliefRevision> {
+        self.history(fact)
+            .into_iter()
+            .rev()
+            .find(|r| r.outcome == RevisionOutcome::Accepted)
+    }
+
+    /// Human-readable account of how a belief evolved.
+    pub fn explain(&self, fact: &FactKey) -> Vec<String> {
+        self.history(fact)
+            .into_iter()
+            .map(|r| match &r.outcome {
+                RevisionOutcome::Accepted => format!(
+                    "#{} {}: {:.2} -> {:.2} on evidence from {} ({:.2})",
+                    r.sequence, r.method, r.old_confidence, r.new_confidence, r.evidence.source_name, r.evidence_confidence
+                ),
+                RevisionOutcome::Rejected(reason) => format!(
+                    "#{} {}: kept {:.2}, rejected evidence from {}: {}",
+                    r.sequence, r.method, r.old_confidence, r.evidence.source_name, reason
+                ),
+            })
+            .collect()
+    }
+
+    pub fn entries(&self) -> &[BeliefRevision] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
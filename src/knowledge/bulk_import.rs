@@ -0,0 +1,221 @@
+// ============================================================================
+//                        ASTRA AGI • BULK KNOWLEDGE IMPORT
+//        Batched, Fault-Tolerant Ingestion of Entities & Relationships
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Knowledge Layer, serving crawler-scale ingestion
+//       where inserting records one at a time is too slow. Records arrive as
+//       any iterator (a crawler feed, a file reader, a channel drain), are
+//       validated individually, and have their index updates applied per
+//       batch rather than per record. Adjacency updates are deferred until
+//       the whole stream has been consumed.
+//
+//   Core Functions:
+//       • Accept iterators of entity and relationship records
+//       • Batch attribute, concept and relationship index updates
+//       • Defer adjacency list maintenance to the end of the import
+//       • Validate entities against their concept schema
+//       • Report per-item errors without aborting the rest of the import
+//
+//   File:        /src/knowledge/bulk_import.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use crate::knowledge::error::KnowledgeError;
+use crate::knowledge::ontology::{Entity, Relationship, RelationshipProperties, RelationshipType};
+use crate::knowledge::storage::Storage;
+use crate::knowledge::{AttributeValue, Id, Ontology};
+use std::collections::{HashMap, HashSet};
+
+/// A single record in a bulk import stream.
+#[derive(Debug, Clone)]
+pub enum BulkRecord {
+    Entity {
+        concept_id: Id,
+        attribute_values: HashMap<String, AttributeValue>,
+    },
+    Relationship {
+        from_entity: Id,
+        to_entity: Id,
+        rel_type: RelationshipType,
+        properties: RelationshipProperties,
+    },
+}
+
+/// Tuning options for bulk imports.
+#[derive(Debug, Clone)]
+pub struct BulkImportOptions {
+    /// Number of records whose index updates are applied together
+    pub batch_size: usize,
+}
+
+impl Default for BulkImportOptions {
+    fn default() -> Self {
+        BulkImportOptions { batch_size: 1024 }
+    }
+}
+
+/// A record that was rejected during a bulk import.
+#[derive(Debug)]
+pub struct BulkItemError {
+    /// Position of the record in the input stream
+    pub index: usize,
+    pub error: KnowledgeError,
+}
+
+/// Outcome of a bulk import.
+#[derive(Debug, Default)]
+pub struct BulkImportReport {
+    /// (input position, assigned ID) for every accepted record
+    pub inserted: Vec<(usize, Id)>,
+    pub errors: Vec<BulkItemError>,
+}
+
+impl BulkImportReport {
+    pub fn succeeded(&self) -> usize {
+        self.inserted.len()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// True if every record was accepted
+    pub fn is_clean(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Index updates accumulated over one batch.
+#[derive(Default)]
+struct PendingIndexes {
+    attributes: Vec<(String, AttributeValue, Id)>,
+    concepts: Vec<(Id, Id)>,
+    relationships: Vec<(Id, RelationshipType, Id)>,
+}
+
+impl<S: Storage> Ontology<S> {
+    /// Imports a stream of entity and relationship records.
+    ///
+    /// Records are validated one by one; invalid records are reported in the
+    /// returned report and skipped. Relationships may refer to entities
+    /// created earlier in the same stream.
+    pub fn bulk_import<I>(&mut self, records: I, options: &BulkImportOptions) -> BulkImportReport
+    where
+        I: IntoIterator<Item = BulkRecord>,
+    {
+        let batch_size = options.batch_size.max(1);
+        let mut report = BulkImportReport::default();
+        let mut pending = PendingIndexes::default();
+        let mut pending_edges: Vec<(Id, Id)> = Vec::new();
+        let mut in_batch = 0;
+
+        for (index, record) in records.into_iter().enumerate() {
+            match self.stage_record(record, &mut pending, &mut pending_edges) {
+                Ok(id) => report.inserted.push((index, id)),
+                Err(error) => report.errors.push(BulkItemError { index, error }),
+            }
+
+            in_batch += 1;
+            if in_batch == batch_size {
+                self.flush_indexes(std::mem::take(&mut pending));
+                in_batch = 0;
+            }
+        }
+
+        self.flush_indexes(pending);
+
+        // Adjacency is only needed for traversal, so it is rebuilt once at the end.
+        for (from_entity, to_entity) in pending_edges {
+            self.adjacency_list.entry(from_entity).or_default().insert(to_entity);
+        }
+
+        report
+    }
+
+    /// Recomputes the adjacency list from the stored relationships.
+    pub fn rebuild_adjacency_list(&mut self) {
+        let mut adjacency: HashMap<Id, HashSet<Id>> = HashMap::new();
+        for rel in self.relationships.values() {
+            adjacency.entry(rel.from_entity).or_default().insert(rel.to_entity);
+        }
+        self.adjacency_list = adjacency;
+    }
+
+    /// Validates and stores a record, queueing its index updates.
+    fn stage_record(
+        &mut self,
+        record: BulkRecord,
+        pending: &mut PendingIndexes,
+        pending_edges: &mut Vec<(Id, Id)>,
+    ) -> Result<Id, KnowledgeError> {
+        match record {
+            BulkRecord::Entity { concept_id, attribute_values } => {
+                self.validate_entity(concept_id, &attribute_values)?;
+
+                let id = self.next_id;
+                self.next_id += 1;
+
+                pending.concepts.push((concept_id, id));
+                for (attr_name, attr_value) in &attribute_values {
+                    pending.attributes.push((attr_name.clone(), attr_value.clone(), id));
+                }
+
+                self.entities.insert(id, Entity { id, concept_id, attribute_values });
+                Ok(id)
+            }
+            BulkRecord::Relationship { from_entity, to_entity, rel_type, properties } => {
+                if !self.entities.contains_key(&from_entity) {
+                    return Err(KnowledgeError::SourceEntityNotFound(from_entity));
+                }
+                if !self.entities.contains_key(&to_entity) {
+                    return Err(KnowledgeError::TargetEntityNotFound(to_entity));
+                }
+
+                let id = self.next_id;
+                self.next_id += 1;
+
+                pending.relationships.push((from_entity, rel_type.clone(), id));
+                pending_edges.push((from_entity, to_entity));
+
+                self.relationships.insert(
+                    id,
+                    Relationship { id, from_entity, to_entity, rel_type, properties },
+                );
+                Ok(id)
+            }
+        }
+    }
+
+    /// Applies a batch of queued index updates.
+    fn flush_indexes(&mut self, pending: PendingIndexes) {
+        for (concept_id, entity_id) in pending.concepts {
+            self.concept_index.entry(concept_id).or_default().insert(entity_id);
+        }
+
+        for (attr_name, attr_value, entity_id) in pending.attributes {
+            self.attribute_index
+                .entry(attr_name)
+                .or_default()
+                .entry(attr_value)
+                .or_default()
+                .insert(entity_id);
+        }
+
+        for (from_entity, rel_type, rel_id) in pending.relationships {
+            self.relationship_index
+                .entry(from_entity)
+                .or_default()
+                .entry(rel_type.clone())
+                .or_default()
+                .insert(rel_id);
+            self.relationship_type_index.entry(rel_type).or_default().insert(rel_id);
+        }
+    }
+}
@@ -0,0 +1,255 @@
+// ============================================================================
+//                     ASTRA AGI • KNOWLEDGE CHANGE FEED
+//        Subscriptions & Ordered Notifications for Ontology Updates
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Knowledge Layer that lets other subsystems
+//       notice when the knowledge base changes. Every fact added, batch of
+//       facts retired, or version switch is appended to a bounded, sequenced
+//       change log that consumers read at their own pace, and matching facts
+//       can also be pushed to registered callbacks as they arrive. The
+//       cognitive loop uses the feed to treat significant knowledge changes
+//       as stimuli.
+//
+//   Core Functions:
+//       • Describe fact patterns with optional subject, predicate and object
+//       • Invoke subscriber callbacks for newly added matching facts
+//       • Keep a sequenced change log readable from any cursor
+//       • Tell consumers copying a version whether they can catch up from
+//         their cursor or must copy it afresh
+//
+//   File:        /src/knowledge/change_feed.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use crate::knowledge::extended_ontology::{Confidence, EntityId, Fact, OntologyManager};
+use std::collections::VecDeque;
+
+/// Identifier returned when subscribing, used to unsubscribe.
+pub type SubscriptionId = u64;
+
+/// Position in the change log. Sequences start at 1 and never repeat.
+pub type ChangeSequence = u64;
+
+/// Matches facts by any combination of subject, predicate and object.
+/// Unset fields match anything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FactPattern {
+    pub subject: Option<EntityId>,
+    pub predicate: Option<String>,
+    pub object: Option<String>,
+    pub min_confidence: Confidence,
+}
+
+impl FactPattern {
+    /// Matches every fact.
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    pub fn predicate(predicate: impl Into<String>) -> Self {
+        FactPattern { predicate: Some(predicate.into()), ..Self::default() }
+    }
+
+    pub fn with_subject(mut self, subject: EntityId) -> Self {
+        self.subject = Some(subject);
+        self
+    }
+
+    pub fn with_object(mut self, object: impl Into<String>) -> Self {
+        self.object = Some(object.into());
+        self
+    }
+
+    pub fn with_min_confidence(mut self, min_confidence: Confidence) -> Self {
+        self.min_confidence = min_confidence;
+        self
+    }
+
+    pub fn matches(&self, fact: &Fact) -> bool {
+        self.subject.map_or(true, |s| s == fact.subject)
+            && self.predicate.as_ref().map_or(true, |p| *p == fact.predicate)
+            && self.object.as_ref().map_or(true, |o| *o == fact.object)
+            && fact.confidence >= self.min_confidence
+    }
+}
+
+/// A change to the knowledge base.
+#[derive(Debug, Clone)]
+pub enum KnowledgeChange {
+    FactAdded { version: u64, index: usize, fact: Fact },
+    /// Facts removed by retention; indexes in the version were compacted
+    FactsRetired { version: u64, count: usize },
+    VersionSwitched { from: u64, to: u64 },
+}
+
+type FactCallback = Box<dyn Fn(&Fact) + Send + Sync>;
+
+/// Sequenced log of knowledge changes plus push subscriptions.
+pub struct ChangeFeed {
+    log: VecDeque<(ChangeSequence, KnowledgeChange)>,
+    capacity: usize,
+    next_sequence: ChangeSequence,
+    subscribers: Vec<(SubscriptionId, FactPattern, FactCallback)>,
+    next_subscription: SubscriptionId,
+}
+
+impl ChangeFeed {
+    /// Creates a feed that retains the last `capacity` changes.
+    pub fn new(capacity: usize) -> Self {
+        ChangeFeed {
+            log: VecDeque::new(),
+            capacity: capacity.max(1),
+            next_sequence: 1,
+            subscribers: Vec::new(),
+            next_subscription: 1,
+        }
+    }
+
+    /// Appends a change and notifies matching subscribers.
+    pub fn publish(&mut self, change: KnowledgeChange) -> ChangeSequence {
+        if let KnowledgeChange::FactAdded { fact, .. } = &change {
+            for (_, pattern, callback) in &self.subscribers {
+                if pattern.matches(fact) {
+                    callback(fact);
+                }
+            }
+        }
+
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        if self.log.len() == self.capacity {
+            self.log.pop_front();
+        }
+        self.log.push_back((sequence, change));
+        sequence
+    }
+
+    pub fn subscribe(&mut self, pattern: FactPattern, callback: FactCallback) -> SubscriptionId {
+        let id = self.next_subscription;
+        self.next_subscription += 1;
+        self.subscribers.push((id, pattern, callback));
+        id
+    }
+
+    /// Removes a subscription; returns whether it existed.
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        let before = self.subscribers.len();
+        self.subscribers.retain(|(sub_id, _, _)| *sub_id != id);
+        self.subscribers.len() != before
+    }
+
+    /// Changes recorded after `cursor`, oldest first. Changes older than the
+    /// feed's capacity are no longer available.
+    pub fn changes_since(&self, cursor: ChangeSequence) -> Vec<(ChangeSequence, &KnowledgeChange)> {
+        self.log
+            .iter()
+            .filter(|(sequence, _)| *sequence > cursor)
+            .map(|(sequence, change)| (*sequence, change))
+            .collect()
+    }
+
+    /// Sequence of the most recent change, or 0 if none were published.
+    pub fn latest_sequence(&self) -> ChangeSequence {
+        self.next_sequence - 1
+    }
+}
+
+impl Default for ChangeFeed {
+    fn default() -> Self {
+        Self::new(1024)
+    }
+}
+
+impl OntologyManager {
+    /// Calls `callback` for every fact added from now on that matches `pattern`.
+    pub fn on_fact_added<F>(&mut self, pattern: FactPattern, callback: F) -> SubscriptionId
+    where
+        F: Fn(&Fact) + Send + Sync + 'static,
+    {
+        self.change_feed.subscribe(pattern, Box::new(callback))
+    }
+
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        self.change_feed.unsubscribe(id)
+    }
+
+    /// Knowledge changes recorded after `cursor`; pass 0 to read from the start.
+    pub fn changes_since(&self, cursor: ChangeSequence) -> Vec<(ChangeSequence, &KnowledgeChange)> {
+        self.change_feed.changes_since(cursor)
+    }
+
+    pub fn latest_change(&self) -> ChangeSequence {
+        self.change_feed.latest_sequence()
+    }
+
+    /// Changes after `cursor` for a consumer holding a copy of ontology
+    /// `version` up to that cursor, or `None` if the consumer has to copy
+    /// the current version afresh: when it holds no or another version,
+    /// when facts of the current version were retired (the indexes of the
+    /// rest moved), when the version was switched, or when changes after
+    /// the cursor have already left the feed.
+    pub fn changes_to_replay(
+        &self,
+        cursor: ChangeSequence,
+        version: Option<u64>,
+    ) -> Option<Vec<(ChangeSequence, &KnowledgeChange)>> {
+        let current = self.current_version;
+        let changes = self.changes_since(cursor);
+        let gap = changes.first().is_some_and(|(sequence, _)| *sequence > cursor + 1);
+        let rebuilt = changes.iter().any(|(_, change)| match change {
+            KnowledgeChange::FactsRetired { version: retired, .. } => *retired == current,
+            KnowledgeChange::VersionSwitched { .. } => true,
+            KnowledgeChange::FactAdded { .. } => false,
+        });
+        if version != Some(current) || self.latest_change() < cursor || gap || rebuilt {
+            None
+        } else {
+            Some(changes)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::extended_ontology::Provenance;
+    use std::sync::{Arc, Mutex};
+
+    fn fact(predicate: &str, object: &str) -> Fact {
+        Fact {
+            subject: 1,
+            predicate: predicate.to_string(),
+            object: object.to_string(),
+            confidence: 0.9,
+            provenance: Provenance::new("Sensor", None),
+        }
+    }
+
+    #[test]
+    fn test_subscriptions_and_change_log() {
+        let mut manager = OntologyManager::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let id = manager.on_fact_added(FactPattern::predicate("door_state"), move |f| {
+            sink.lock().unwrap().push(f.object.clone())
+        });
+
+        manager.add_fact(fact("door_state", "open"));
+        manager.add_fact(fact("temperature", "21C"));
+        let cursor = manager.latest_change();
+        assert!(manager.unsubscribe(id));
+        manager.add_fact(fact("door_state", "closed"));
+
+        assert_eq!(*seen.lock().unwrap(), vec!["open".to_string()]);
+        assert_eq!(manager.changes_since(0).len(), 3);
+        let newer = manager.changes_since(cursor);
+        assert!(matches!(newer[..], [(_, KnowledgeChange::FactAdded { index: 2, .. })]));
+    }
+}
@@ -0,0 +1,193 @@
+// ============================================================================
+//                   ASTRA AGI • CONCURRENT KNOWLEDGE ACCESS
+//        Shared, Read-Optimized Handles over the Knowledge Ontology
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Knowledge Layer that lets the async cognitive
+//       loop, the API server, and the web crawler share one ontology. The
+//       ontology sits behind an async reader-writer lock: any number of
+//       queries run in parallel, while mutations take exclusive access for
+//       as short a time as possible. Results leave the lock as owned values
+//       so no caller can hold the knowledge base hostage.
+//
+//   Core Functions:
+//       • Provide cloneable async handles to a shared ontology
+//       • Run concurrent read-only queries without blocking each other
+//       • Answer batches of small queries under a single read lock
+//       • Serialize mutations (entities, relationships, bulk imports)
+//       • Offer blocking accessors for synchronous call sites
+//
+//   File:        /src/knowledge/concurrent.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use crate::knowledge::bulk_import::{BulkImportOptions, BulkImportReport, BulkRecord};
+use crate::knowledge::error::KnowledgeError;
+use crate::knowledge::ontology::{RelationshipProperties, RelationshipType};
+use crate::knowledge::query::QueryExpr;
+use crate::knowledge::schema::SchemaError;
+use crate::knowledge::storage::Storage;
+use crate::knowledge::{AttributeValue, Entity, Id, Ontology};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Cloneable handle to an ontology shared between tasks.
+pub struct KnowledgeHandle<S: Storage> {
+    inner: Arc<RwLock<Ontology<S>>>,
+}
+
+impl<S: Storage> Clone for KnowledgeHandle<S> {
+    fn clone(&self) -> Self {
+        KnowledgeHandle { inner: Arc::clone(&self.inner) }
+    }
+}
+
+impl<S: Storage + Send + Sync> KnowledgeHandle<S> {
+    /// Takes ownership of an ontology and shares it behind a handle
+    pub fn new(ontology: Ontology<S>) -> Self {
+        KnowledgeHandle { inner: Arc::new(RwLock::new(ontology)) }
+    }
+
+    /// Runs a read-only closure against the ontology.
+    /// Many readers may run at once; they only wait for in-flight writers.
+    pub async fn read<R>(&self, f: impl FnOnce(&Ontology<S>) -> R) -> R {
+        let guard = self.inner.read().await;
+        f(&guard)
+    }
+
+    /// Runs a mutating closure with exclusive access to the ontology
+    pub async fn write<R>(&self, f: impl FnOnce(&mut Ontology<S>) -> R) -> R {
+        let mut guard = self.inner.write().await;
+        f(&mut guard)
+    }
+
+    /// Evaluates a query and returns the IDs of matching entities
+    pub async fn query_ids(&self, expr: &QueryExpr) -> Vec<Id> {
+        self.read(|ontology| ontology.query(expr).into_iter().map(|e| e.id).collect()).await
+    }
+
+    /// Evaluates a query and returns owned copies of matching entities
+    pub async fn query_entities(&self, expr: &QueryExpr) -> Vec<Entity> {
+        self.read(|ontology| ontology.query(expr).into_iter().cloned().collect()).await
+    }
+
+    /// Evaluates a batch of queries under one read lock, returning the IDs
+    /// each one matched, in the order the queries were given
+    pub async fn query_batch_ids(&self, exprs: &[QueryExpr]) -> Vec<Vec<Id>> {
+        self.read(|ontology| {
+            ontology
+                .query_batch(exprs)
+                .into_iter()
+                .map(|entities| entities.into_iter().map(|e| e.id).collect())
+                .collect()
+        })
+        .await
+    }
+
+    /// Returns an owned copy of an entity
+    pub async fn get_entity(&self, id: Id) -> Option<Entity> {
+        self.read(|ontology| ontology.get_entity(id).cloned()).await
+    }
+
+    pub async fn add_entity(
+        &self,
+        concept_id: Id,
+        attribute_values: HashMap<String, AttributeValue>,
+    ) -> Result<Id, SchemaError> {
+        self.write(|ontology| ontology.add_entity(concept_id, attribute_values)).await
+    }
+
+    pub async fn add_relationship(
+        &self,
+        from_entity: Id,
+        to_entity: Id,
+        rel_type: RelationshipType,
+        properties: RelationshipProperties,
+    ) -> Result<Id, KnowledgeError> {
+        self.write(|ontology| ontology.add_relationship_with_properties(from_entity, to_entity, rel_type, properties))
+            .await
+    }
+
+    /// Imports records under a single write lock.
+    /// Records are collected first so the lock is not held while the
+    /// source iterator produces them.
+    pub async fn bulk_import<I>(&self, records: I, options: &BulkImportOptions) -> BulkImportReport
+    where
+        I: IntoIterator<Item = BulkRecord>,
+    {
+        let records: Vec<BulkRecord> = records.into_iter().collect();
+        self.write(|ontology| ontology.bulk_import(records, options)).await
+    }
+
+    /// Blocking read for synchronous callers. Must not be called from
+    /// within an async task.
+    pub fn blocking_read<R>(&self, f: impl FnOnce(&Ontology<S>) -> R) -> R {
+        let guard = self.inner.blocking_read();
+        f(&guard)
+    }
+
+    /// Blocking write for synchronous callers. Must not be called from
+    /// within an async task.
+    pub fn blocking_write<R>(&self, f: impl FnOnce(&mut Ontology<S>) -> R) -> R {
+        let mut guard = self.inner.blocking_write();
+        f(&mut guard)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::storage::SledStorage;
+    use crate::knowledge::AttributeType;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_readers_during_writes() {
+        let storage = SledStorage::new("test_concurrent_handle_db").unwrap();
+        let mut ontology = Ontology::new(storage);
+        let mut attrs = HashMap::new();
+        attrs.insert("n".to_string(), AttributeType::Integer);
+        let concept_id = ontology.add_concept("Counter", &[], attrs);
+
+        let handle = KnowledgeHandle::new(ontology);
+
+        let writer = {
+            let handle = handle.clone();
+            tokio::spawn(async move {
+                for n in 0..100 {
+                    let mut values = HashMap::new();
+                    values.insert("n".to_string(), AttributeValue::Integer(n));
+                    handle.add_entity(concept_id, values).await.unwrap();
+                }
+            })
+        };
+
+        let readers: Vec<_> = (0..8)
+            .map(|_| {
+                let handle = handle.clone();
+                tokio::spawn(async move {
+                    let mut last_seen = 0;
+                    for _ in 0..50 {
+                        let seen = handle.query_ids(&QueryExpr::Concept(concept_id)).await.len();
+                        // Writes are atomic and append-only, so readers never observe a shrinking set
+                        assert!(seen >= last_seen);
+                        last_seen = seen;
+                    }
+                })
+            })
+            .collect();
+
+        writer.await.unwrap();
+        for reader in readers {
+            reader.await.unwrap();
+        }
+
+        assert_eq!(handle.query_ids(&QueryExpr::Concept(concept_id)).await.len(), 100);
+    }
+}
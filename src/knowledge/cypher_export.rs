@@ -0,0 +1,284 @@
+// ============================================================================
+//                      ASTRA AGI • CYPHER KNOWLEDGE EXPORT
+//        Streaming the Ontology Graph into Neo4j and Other Graph Tooling
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Knowledge Layer that lets teams explore the
+//       knowledge base in existing graph tooling. Facts in the current
+//       ontology version become Cypher statements: subjects and objects are
+//       nodes, and each fact is a relationship named after its predicate,
+//       carrying its confidence and provenance. Statements go to a sink,
+//       which either writes a Cypher script or hands them to a Bolt client.
+//       The exporter keeps its place in the change feed, so later exports
+//       send only the facts added since, and falls back to a full export
+//       when the feed cannot account for what changed.
+//
+//   Core Functions:
+//       • Render facts as idempotent Cypher MERGE statements
+//       • Write statements to a Cypher script or any Bolt-backed sink
+//       • Sync incrementally from the change feed cursor of the last export
+//       • Re-export in full after retirements, version switches, or gaps
+//
+//   File:        /src/knowledge/cypher_export.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use crate::knowledge::change_feed::{ChangeSequence, KnowledgeChange};
+use crate::knowledge::error::KnowledgeError;
+use crate::knowledge::extended_ontology::{name_entity_id, Fact, OntologyManager};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+/// Receives Cypher statements in the order they must run.
+///
+/// A Bolt client implements this by running each statement against the
+/// database, mapping its failures to `KnowledgeError::Export`.
+pub trait CypherSink {
+    fn run(&mut self, statement: &str) -> Result<(), KnowledgeError>;
+}
+
+/// Writes statements as a script for `cypher-shell` or the Neo4j browser,
+/// one per line.
+pub struct CypherScript<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> CypherScript<W> {
+    pub fn new(writer: W) -> Self {
+        CypherScript { writer }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> CypherSink for CypherScript<W> {
+    fn run(&mut self, statement: &str) -> Result<(), KnowledgeError> {
+        writeln!(self.writer, "{};", statement)?;
+        Ok(())
+    }
+}
+
+impl CypherSink for Vec<String> {
+    fn run(&mut self, statement: &str) -> Result<(), KnowledgeError> {
+        self.push(statement.to_string());
+        Ok(())
+    }
+}
+
+/// What an export sent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportReport {
+    /// Whether the graph was cleared and every fact sent again
+    pub full: bool,
+    pub facts: usize,
+    pub statements: usize,
+    /// Change feed position the export reached
+    pub cursor: ChangeSequence,
+}
+
+/// Exports an ontology to a graph database, remembering how far it got.
+/// The exporter is serializable so its place survives restarts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CypherExporter {
+    /// Label given to every exported node, and the only nodes a full
+    /// export clears
+    pub label: String,
+    /// Last change already exported
+    pub cursor: ChangeSequence,
+    /// Ontology version last exported, if any
+    pub version: Option<u64>,
+}
+
+impl CypherExporter {
+    pub fn new() -> Self {
+        CypherExporter { label: "AstraEntity".to_string(), cursor: 0, version: None }
+    }
+
+    /// Sends what changed since the last export, or clears the graph and
+    /// sends everything if the change feed cannot catch it up (see
+    /// `OntologyManager::changes_to_replay`).
+    pub fn export(
+        &mut self,
+        manager: &OntologyManager,
+        sink: &mut dyn CypherSink,
+    ) -> Result<ExportReport, KnowledgeError> {
+        let latest = manager.latest_change();
+        let version = manager.current_version();
+        let replay = manager.changes_to_replay(self.cursor, self.version);
+
+        let mut report = ExportReport { full: replay.is_none(), facts: 0, statements: 0, cursor: latest };
+        match replay {
+            None => {
+                sink.run(&format!("MATCH (n:{}) DETACH DELETE n", quote_name(&self.label)))?;
+                report.statements += 1;
+                for fact in manager.query_facts(None) {
+                    sink.run(&self.fact_statement(fact))?;
+                    report.facts += 1;
+                }
+            }
+            Some(changes) => {
+                for (_, change) in changes {
+                    if let KnowledgeChange::FactAdded { version: added, fact, .. } = change {
+                        if *added == version {
+                            sink.run(&self.fact_statement(fact))?;
+                            report.facts += 1;
+                        }
+                    }
+                }
+            }
+        }
+        report.statements += report.facts;
+
+        self.cursor = latest;
+        self.version = Some(version);
+        Ok(report)
+    }
+
+    /// A statement creating the fact's nodes and relationship if missing
+    /// and updating the relationship's properties, so sending a fact twice
+    /// leaves one relationship.
+    pub fn fact_statement(&self, fact: &Fact) -> String {
+        let label = quote_name(&self.label);
+        format!(
+            "MERGE (s:{label} {{id: {}}}) \
+             MERGE (o:{label} {{id: {}}}) SET o.name = {} \
+             MERGE (s)-[r:{}]->(o) \
+             SET r.predicate = {}, r.confidence = {}, r.source = {}, r.learned_at = {}",
+            quote_string(&fact.subject.to_string()),
+            quote_string(&name_entity_id(&fact.object).to_string()),
+            quote_string(&fact.object),
+            quote_name(&relationship_type(&fact.predicate)),
+            quote_string(&fact.predicate),
+            fact.confidence,
+            quote_string(&fact.provenance.source_name),
+            fact.provenance.timestamp.as_millis(),
+            label = label,
+        )
+    }
+}
+
+impl Default for CypherExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Relationship type for a predicate, in Neo4j's upper snake case:
+/// `lives in` becomes `LIVES_IN`.
+pub fn relationship_type(predicate: &str) -> String {
+    let name: String = predicate
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    if name.is_empty() {
+        "RELATED_TO".to_string()
+    } else {
+        name
+    }
+}
+
+/// A label or relationship type, backtick-quoted so any text is valid.
+fn quote_name(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+/// A single-quoted Cypher string literal.
+fn quote_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('\'');
+    for c in text.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '\'' => quoted.push_str("\\'"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::extended_ontology::Provenance;
+
+    fn fact(subject: &str, predicate: &str, object: &str) -> Fact {
+        Fact {
+            subject: name_entity_id(subject),
+            predicate: predicate.to_string(),
+            object: object.to_string(),
+            confidence: 0.8,
+            provenance: Provenance::new("user:ana", None),
+        }
+    }
+
+    #[test]
+    fn test_exports_in_full_then_incrementally() {
+        let mut manager = OntologyManager::new();
+        manager.add_fact(fact("Ana", "lives in", "Lisbon"));
+        manager.add_fact(fact("Lisbon", "is in", "Portugal"));
+        let mut exporter = CypherExporter::new();
+
+        let mut script = CypherScript::new(Vec::new());
+        let report = exporter.export(&manager, &mut script).unwrap();
+        assert!(report.full);
+        assert_eq!((report.facts, report.statements, report.cursor), (2, 3, 2));
+        let text = String::from_utf8(script.into_inner()).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "MATCH (n:`AstraEntity`) DETACH DELETE n;");
+        assert!(lines[1].contains("-[r:`LIVES_IN`]->"));
+        assert!(lines[1].contains(&format!("{{id: '{}'}}", name_entity_id("lisbon"))));
+        assert!(lines[1].contains("SET o.name = 'Lisbon'"));
+        // The object of one fact is the subject of the next
+        assert!(lines[2].contains(&format!("MERGE (s:`AstraEntity` {{id: '{}'}})", name_entity_id("Lisbon"))));
+
+        // Nothing new, nothing sent; then only what was added
+        let mut sent = Vec::new();
+        assert_eq!(exporter.export(&manager, &mut sent).unwrap().statements, 0);
+        manager.add_fact(fact("Ana", "says", "it's \"sunny\"\nout"));
+        let report = exporter.export(&manager, &mut sent).unwrap();
+        assert!(!report.full);
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].contains(r#"SET o.name = 'it\'s "sunny"\nout'"#));
+
+        // Retiring facts renumbers them, so the graph is rebuilt
+        manager.forget_user("ana");
+        manager.add_fact(fact("Ben", "likes", "tea"));
+        let mut sent = Vec::new();
+        let report = exporter.export(&manager, &mut sent).unwrap();
+        assert!(report.full);
+        assert_eq!(report.facts, 1);
+        assert!(sent[1].contains("`LIKES`"));
+
+        // An exporter restored from storage carries on where it stopped
+        let restored: CypherExporter = serde_json::from_str(&serde_json::to_string(&exporter).unwrap()).unwrap();
+        assert_eq!(restored, exporter);
+    }
+
+    #[test]
+    fn test_falls_back_to_full_export_when_changes_were_dropped() {
+        let mut manager = OntologyManager::new();
+        let mut exporter = CypherExporter::new();
+        manager.add_fact(fact("Ana", "likes", "tea"));
+        exporter.export(&manager, &mut Vec::new()).unwrap();
+        for i in 0..1100 {
+            manager.add_fact(fact("Ana", "counted", &i.to_string()));
+        }
+        let report = exporter.export(&manager, &mut Vec::new()).unwrap();
+        assert!(report.full);
+        assert_eq!(report.facts, 1101);
+        assert_eq!(relationship_type(" "), "RELATED_TO");
+    }
+}
@@ -0,0 +1,283 @@
+// ============================================================================
+//                     ASTRA AGI • KNOWLEDGE DOMAIN PARTITIONING
+//        Named Graphs with Trust Levels, Retention & Query Scoping
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Knowledge Layer that splits the fact base into
+//       named domains ("medical", "personal", "web", ...). Each domain
+//       carries a trust level, used as the default confidence of facts
+//       entering it, and a retention policy that bounds how long its facts
+//       live. Queries can be scoped to chosen domains or to a minimum trust,
+//       so noisy crawled knowledge never silently mixes with trusted core
+//       knowledge.
+//
+//   Core Functions:
+//       • Register domains with trust levels and retention policies
+//       • Add facts to a domain with trust-derived default confidence
+//       • Scope fact queries by domain name or minimum trust
+//       • Enforce retention policies on the current ontology version
+//
+//   File:        /src/knowledge/domains.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use crate::knowledge::extended_ontology::{Confidence, EntityId, Fact, OntologyManager, Provenance};
+use crate::knowledge::change_feed::KnowledgeChange;
+use crate::knowledge::error::KnowledgeError;
+use crate::time::AstraTime;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Domain that facts belong to when none is given.
+pub const CORE_DOMAIN: &str = "core";
+
+/// How long facts in a domain are kept.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RetentionPolicy {
+    KeepForever,
+    /// Drop facts whose provenance is older than this many seconds
+    MaxAgeSecs(u64),
+    /// Keep only the most recent N facts of the domain
+    MaxFacts(usize),
+}
+
+/// A named partition of the knowledge base.
+#[derive(Debug, Clone)]
+pub struct KnowledgeDomain {
+    pub name: String,
+    /// Trust in the domain's sources, 0.0 to 1.0
+    pub trust_level: Confidence,
+    pub retention: RetentionPolicy,
+}
+
+impl KnowledgeDomain {
+    pub fn new(name: impl Into<String>, trust_level: Confidence) -> Self {
+        KnowledgeDomain {
+            name: name.into(),
+            trust_level: trust_level.clamp(0.0, 1.0),
+            retention: RetentionPolicy::KeepForever,
+        }
+    }
+
+    pub fn with_retention(mut self, retention: RetentionPolicy) -> Self {
+        self.retention = retention;
+        self
+    }
+}
+
+/// Which domains a query may see.
+#[derive(Debug, Clone)]
+pub enum DomainScope {
+    All,
+    /// Only the named domains (use `CORE_DOMAIN` for unassigned facts)
+    Only(HashSet<String>),
+    /// Only domains trusted at least this much; core counts as fully trusted
+    MinTrust(Confidence),
+}
+
+impl DomainScope {
+    pub fn only<I, T>(names: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        DomainScope::Only(names.into_iter().map(Into::into).collect())
+    }
+}
+
+impl OntologyManager {
+    /// Registers a domain, replacing any previous definition with the same name.
+    pub fn register_domain(&mut self, domain: KnowledgeDomain) {
+        Arc::make_mut(&mut self.domains).insert(domain.name.clone(), domain);
+    }
+
+    pub fn domain(&self, name: &str) -> Option<&KnowledgeDomain> {
+        self.domains.get(name)
+    }
+
+    /// Adds a fact to a registered domain in the current version.
+    /// Without an explicit confidence the domain's trust level is used.
+    pub fn add_fact_in_domain(
+        &mut self,
+        domain: &str,
+        subject: EntityId,
+        predicate: impl Into<String>,
+        object: impl Into<String>,
+        confidence: Option<Confidence>,
+        provenance: Provenance,
+    ) -> Result<usize, KnowledgeError> {
+        let trust_level = self
+            .domains
+            .get(domain)
+            .map(|d| d.trust_level)
+            .ok_or_else(|| KnowledgeError::UnknownDomain(domain.to_string()))?;
+
+        let index = self.add_fact(Fact {
+            subject,
+            predicate: predicate.into(),
+            object: object.into(),
+            confidence: confidence.unwrap_or(trust_level),
+            provenance,
+        });
+
+        self.versions.get_mut(&self.current_version).unwrap().facts.set_domain(index, domain);
+        Ok(index)
+    }
+
+    /// Returns the domain of a fact in the current version.
+    pub fn fact_domain(&self, fact_index: usize) -> &str {
+        self.versions[&self.current_version].facts.domain(fact_index).unwrap_or(CORE_DOMAIN)
+    }
+
+    /// Queries facts like `query_facts`, restricted to the given scope.
+    pub fn query_facts_scoped(&self, context_id: Option<u64>, scope: &DomainScope) -> Vec<&Fact> {
+        let version = &self.versions[&self.current_version];
+        let indexes: Vec<usize> = match context_id {
+            Some(cid) => match self.contexts.get(&cid) {
+                Some(context) => {
+                    let mut active: Vec<usize> = context.active_facts.iter().copied().collect();
+                    active.sort_unstable();
+                    active
+                }
+                None => return Vec::new(),
+            },
+            None => (0..version.facts.len()).collect(),
+        };
+
+        indexes
+            .into_iter()
+            .filter(|idx| self.domain_in_scope(self.fact_domain(*idx), scope))
+            .filter_map(|idx| version.facts.get(idx))
+            .collect()
+    }
+
+    /// Removes facts that exceed their domain's retention policy from the
+    /// current version. Context references are remapped to the surviving
+    /// facts. Returns the number of facts removed.
+    pub fn apply_retention(&mut self, now: AstraTime) -> usize {
+        let version = &self.versions[&self.current_version];
+
+        let mut by_domain: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (idx, domain) in version.facts.domains() {
+            by_domain.entry(domain).or_default().push(idx);
+        }
+
+        let mut expired: HashSet<usize> = HashSet::new();
+        for (name, mut indexes) in by_domain {
+            let Some(domain) = self.domains.get(name) else { continue };
+            match domain.retention {
+                RetentionPolicy::KeepForever => {}
+                RetentionPolicy::MaxAgeSecs(max_age) => {
+                    expired.extend(indexes.into_iter().filter(|idx| {
+                        now.saturating_duration_since(version.facts[*idx].provenance.timestamp).as_secs() > max_age
+                    }));
+                }
+                RetentionPolicy::MaxFacts(max_facts) => {
+                    // Newest first; ties keep the later insertion.
+                    indexes.sort_by_key(|idx| std::cmp::Reverse((version.facts[*idx].provenance.timestamp, *idx)));
+                    expired.extend(indexes.into_iter().skip(max_facts));
+                }
+            }
+        }
+
+        if expired.is_empty() {
+            return 0;
+        }
+
+        self.remove_facts(self.current_version, &expired);
+
+        self.change_feed.publish(KnowledgeChange::FactsRetired { version: self.current_version, count: expired.len() });
+        expired.len()
+    }
+
+    fn domain_in_scope(&self, domain: &str, scope: &DomainScope) -> bool {
+        match scope {
+            DomainScope::All => true,
+            DomainScope::Only(names) => names.contains(domain),
+            DomainScope::MinTrust(min_trust) => {
+                let trust = if domain == CORE_DOMAIN {
+                    1.0
+                } else {
+                    self.domains.get(domain).map(|d| d.trust_level).unwrap_or(0.0)
+                };
+                trust >= *min_trust
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provenance_at(timestamp: u64) -> Provenance {
+        Provenance { timestamp: AstraTime::from_secs(timestamp), ..Provenance::new("test", None) }
+    }
+
+    #[test]
+    fn test_trust_defaults_and_scoping() {
+        let mut manager = OntologyManager::new();
+        manager.register_domain(KnowledgeDomain::new("medical", 0.9));
+        manager.register_domain(KnowledgeDomain::new("web", 0.3));
+
+        let med = manager
+            .add_fact_in_domain("medical", 1, "treats", "headache", None, provenance_at(100))
+            .unwrap();
+        manager
+            .add_fact_in_domain("web", 1, "treats", "everything", None, provenance_at(100))
+            .unwrap();
+        manager.add_fact(Fact {
+            subject: 1,
+            predicate: "is_a".to_string(),
+            object: "Drug".to_string(),
+            confidence: 1.0,
+            provenance: provenance_at(100),
+        });
+
+        assert_eq!(manager.query_facts(None)[med].confidence, 0.9);
+        assert_eq!(manager.fact_domain(2), CORE_DOMAIN);
+        assert!(matches!(
+            manager.add_fact_in_domain("unknown", 1, "p", "o", None, provenance_at(0)),
+            Err(KnowledgeError::UnknownDomain(_))
+        ));
+
+        let trusted = manager.query_facts_scoped(None, &DomainScope::MinTrust(0.5));
+        assert_eq!(trusted.len(), 2);
+        assert!(trusted.iter().all(|f| f.object != "everything"));
+
+        let web_only = manager.query_facts_scoped(None, &DomainScope::only(["web"]));
+        assert_eq!(web_only.len(), 1);
+        assert_eq!(web_only[0].object, "everything");
+    }
+
+    #[test]
+    fn test_retention_remaps_contexts() {
+        let mut manager = OntologyManager::new();
+        manager.register_domain(KnowledgeDomain::new("web", 0.3).with_retention(RetentionPolicy::MaxAgeSecs(50)));
+        manager.register_domain(KnowledgeDomain::new("personal", 0.8).with_retention(RetentionPolicy::MaxFacts(1)));
+
+        manager.add_fact_in_domain("web", 1, "p", "stale", None, provenance_at(10)).unwrap();
+        manager.add_fact_in_domain("personal", 1, "likes", "tea", None, provenance_at(20)).unwrap();
+        let fresh = manager.add_fact_in_domain("web", 1, "p", "fresh", None, provenance_at(90)).unwrap();
+        manager.add_fact_in_domain("personal", 1, "likes", "coffee", None, provenance_at(95)).unwrap();
+
+        let ctx = manager.create_context("Reading", None);
+        manager.add_fact_to_context(ctx, fresh).unwrap();
+
+        assert_eq!(manager.apply_retention(AstraTime::from_secs(100)), 2);
+
+        let remaining: Vec<&str> = manager.query_facts(None).iter().map(|f| f.object.as_str()).collect();
+        assert_eq!(remaining, vec!["fresh", "coffee"]);
+
+        let in_context = manager.query_facts(Some(ctx));
+        assert_eq!(in_context.len(), 1);
+        assert_eq!(in_context[0].object, "fresh");
+        assert_eq!(manager.fact_domain(1), "personal");
+    }
+}
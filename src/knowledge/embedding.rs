@@ -0,0 +1,114 @@
+// ============================================================================
+//                        ASTRA AGI • TEXT EMBEDDINGS
+//        Fixed-Size Vectors for Comparing Passages by Content
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Knowledge Layer that turns passages of text
+//       into vectors, so that ingested documents can be compared with one
+//       another and with questions by similarity rather than exact wording.
+//       The built-in embedder hashes words and word pairs into a fixed
+//       number of dimensions; it needs no model files and is deterministic,
+//       and a learned model can take its place through `Embedder`.
+//
+//   Core Functions:
+//       • Define the embedder interface
+//       • Embed text by feature hashing of words and word pairs
+//       • Compare embeddings by cosine similarity
+//
+//   File:        /src/knowledge/embedding.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+/// Maps text to vectors of a fixed length.
+pub trait Embedder {
+    fn dimensions(&self) -> usize;
+    /// A unit-length vector, or all zeros for text without words.
+    fn embed(&self, text: &str) -> Vec<f32>;
+}
+
+/// Embeds text by hashing its lowercased words and adjacent word pairs
+/// into `dimensions` buckets with a hash-chosen sign.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashingEmbedder {
+    pub dimensions: usize,
+}
+
+impl Default for HashingEmbedder {
+    fn default() -> Self {
+        Self { dimensions: 256 }
+    }
+}
+
+/// 64-bit FNV-1a, stable across runs and platforms.
+pub(crate) fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes.into_iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+impl Embedder for HashingEmbedder {
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn embed(&self, text: &str) -> Vec<f32> {
+        let dimensions = self.dimensions.max(1);
+        let mut vector = vec![0.0; dimensions];
+        let lower = text.to_lowercase();
+        let words: Vec<&str> = lower.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).collect();
+        let pairs = words.windows(2).map(|pair| (pair[0], Some(pair[1])));
+        for (first, second) in words.iter().map(|w| (*w, None)).chain(pairs) {
+            let hash = match second {
+                None => fnv1a(first.bytes()),
+                Some(second) => fnv1a(first.bytes().chain([b' ']).chain(second.bytes())),
+            };
+            // Pairs count half as much as single words
+            let weight = if second.is_some() { 0.5 } else { 1.0 };
+            let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+            vector[(hash % dimensions as u64) as usize] += sign * weight;
+        }
+        normalize(&mut vector);
+        vector
+    }
+}
+
+/// Scales `vector` to unit length; a zero vector is left as it is.
+pub fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|x| *x /= norm);
+    }
+}
+
+/// Cosine similarity in -1.0..=1.0; 0.0 if either vector is zero.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norms = a.iter().map(|x| x * x).sum::<f32>().sqrt() * b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norms == 0.0 {
+        0.0
+    } else {
+        dot / norms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similar_text_embeds_nearby() {
+        let embedder = HashingEmbedder::default();
+        let rust = embedder.embed("Rust is a systems programming language");
+        let rust_again = embedder.embed("rust is a systems programming language!");
+        let bread = embedder.embed("Sourdough bread needs a starter");
+
+        assert_eq!(rust.len(), 256);
+        assert!((cosine_similarity(&rust, &rust_again) - 1.0).abs() < 1e-5);
+        assert!(cosine_similarity(&rust, &embedder.embed("a language for systems programming")) > cosine_similarity(&rust, &bread));
+        assert!(embedder.embed("  ").iter().all(|x| *x == 0.0));
+    }
+}
@@ -13,20 +13,25 @@
 //       • Revise beliefs using confidence thresholds and weighted updates
 //       • Reject low‑confidence or unreliable evidence with clear rationale
 //       • Incorporate contextual source reliability into belief updates
+//       • Learn per-source trust from confirmed and contradicted facts
 //       • Combine conflicting facts using consensus‑based aggregation
 //       • Log belief updates and rejections into Narrative Memory for traceability
+//       • Record every revision attempt in the append-only Belief Ledger
+//       • Weaken beliefs that observations contradicted, in proportion to surprise
 //
 //   File:        /src/knowledge/epistemic_reasoner.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-27
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
-use crate::knowledge::extended_ontology::{Fact, Confidence};
+use crate::knowledge::belief_ledger::{BeliefLedger, BeliefRevision, FactKey, RevisionOutcome};
+use crate::knowledge::extended_ontology::{Fact, Confidence, Provenance};
+use crate::knowledge::source_reliability::SourceReliability;
 use crate::memory::narrative_memory::NarrativeMemory;
 use std::collections::HashMap;
 
@@ -42,6 +47,8 @@ pub enum RevisionResult {
 pub struct EpistemicReasoner {
     /// Optional parameters or configuration for reasoning algorithms.
     pub parameters: HashMap<String, f64>,
+    /// Learned trust in each knowledge source, used as a revision prior.
+    pub source_reliability: SourceReliability,
 }
 
 impl EpistemicReasoner {
@@ -50,7 +57,7 @@ impl EpistemicReasoner {
         let mut params = HashMap::new();
         // Default parameters can be tuned later
         params.insert("confidence_threshold".to_string(), 0.5);
-        EpistemicReasoner { parameters: params }
+        EpistemicReasoner { parameters: params, source_reliability: SourceReliability::new() }
     }
 
     /// Revises an existing fact with new evidence.
@@ -137,6 +144,112 @@ pub fn revise_belief_with_logging(&self, current_fact: &Fact, new_fact: &Fact, n
         RevisionResult::Updated(updated_fact)
     }
 
+    /// Revises a belief using the learned trust in the evidence's source
+    /// (`new_fact.provenance.source_name`) as the reliability prior.
+    pub fn revise_belief_from_source(&self, current_fact: &Fact, new_fact: &Fact) -> RevisionResult {
+        let trust = self.source_reliability.trust(&new_fact.provenance.source_name);
+        self.revise_belief_contextual(current_fact, new_fact, trust)
+    }
+
+    /// Recorded variant of `revise_belief_from_source`.
+    pub fn revise_belief_from_source_recorded(
+        &self,
+        current_fact: &Fact,
+        new_fact: &Fact,
+        ledger: &mut BeliefLedger,
+    ) -> RevisionResult {
+        let trust = self.source_reliability.trust(&new_fact.provenance.source_name);
+        self.revise_belief_contextual_recorded(current_fact, new_fact, trust, ledger)
+    }
+
+    /// Feeds back whether a fact turned out to be true, adjusting the trust
+    /// in the source it came from.
+    pub fn record_fact_outcome(&mut self, fact: &Fact, confirmed: bool) {
+        let source = &fact.provenance.source_name;
+        if confirmed {
+            self.source_reliability.record_confirmation(source);
+        } else {
+            self.source_reliability.record_contradiction(source);
+        }
+    }
+
+    /// Revises a belief and records the attempt in the belief ledger.
+    pub fn revise_belief_recorded(&self, current_fact: &Fact, new_fact: &Fact, ledger: &mut BeliefLedger) -> RevisionResult {
+        let result = self.revise_belief(current_fact, new_fact);
+        self.record_revision(ledger, "revise_belief", current_fact, new_fact, &result, self.parameters.clone());
+        result
+    }
+
+    /// Contextual revision that records the attempt, including the source
+    /// reliability used, in the belief ledger.
+    pub fn revise_belief_contextual_recorded(
+        &self,
+        current_fact: &Fact,
+        new_fact: &Fact,
+        source_reliability: f64,
+        ledger: &mut BeliefLedger,
+    ) -> RevisionResult {
+        let result = self.revise_belief_contextual(current_fact, new_fact, source_reliability);
+        let mut parameters = self.parameters.clone();
+        parameters.insert("source_reliability".to_string(), source_reliability);
+        self.record_revision(ledger, "revise_belief_contextual", current_fact, new_fact, &result, parameters);
+        result
+    }
+
+    /// Lowers confidence in a fact an observation contradicted. `surprise`
+    /// (0.0..=1.0) is how strongly the observation went against it; the
+    /// "surprise_rate" parameter (default 0.5) is the share of confidence a
+    /// fully surprising observation removes. Contradictions are never
+    /// rejected, however weak, and every one is recorded in the ledger.
+    pub fn weaken_belief_recorded(
+        &self,
+        fact: &Fact,
+        surprise: f32,
+        evidence: Provenance,
+        ledger: &mut BeliefLedger,
+    ) -> Fact {
+        let rate = *self.parameters.get("surprise_rate").unwrap_or(&0.5);
+        let surprise = surprise.clamp(0.0, 1.0);
+        let weakened = Fact {
+            confidence: (fact.confidence as f64 * (1.0 - rate * surprise as f64)).max(0.0) as Confidence,
+            ..fact.clone()
+        };
+        let evidence = Fact { confidence: surprise, provenance: evidence, ..fact.clone() };
+        let mut parameters = self.parameters.clone();
+        parameters.insert("surprise_rate".to_string(), rate);
+        let result = RevisionResult::Updated(weakened.clone());
+        self.record_revision(ledger, "weaken_belief", fact, &evidence, &result, parameters);
+        weakened
+    }
+
+    fn record_revision(
+        &self,
+        ledger: &mut BeliefLedger,
+        method: &str,
+        current_fact: &Fact,
+        new_fact: &Fact,
+        result: &RevisionResult,
+        parameters: HashMap<String, f64>,
+    ) {
+        let (new_confidence, outcome) = match result {
+            RevisionResult::Updated(fact) => (fact.confidence, RevisionOutcome::Accepted),
+            RevisionResult::Rejected(reason) => (current_fact.confidence, RevisionOutcome::Rejected(reason.clone())),
+        };
+
+        ledger.record(BeliefRevision {
+            sequence: 0,
+            timestamp: 0,
+            fact: FactKey::from(current_fact),
+            old_confidence: current_fact.confidence,
+            new_confidence,
+            evidence_confidence: new_fact.confidence,
+            evidence: new_fact.provenance.clone(),
+            method: method.to_string(),
+            parameters,
+            outcome,
+        });
+    }
+
     /// Combines multiple conflicting facts about the same statement.
     ///
     /// Uses a simple consensus approach weighted by confidence and recency.
@@ -157,7 +270,7 @@ pub fn revise_belief_with_logging(&self, current_fact: &Fact, new_fact: &Fact, n
 
         for fact in facts {
             // Weight by confidence and recency (simple inverse timestamp)
-            let recency_weight = 1.0 / ((fact.provenance.timestamp as f64) + 1.0);
+            let recency_weight = 1.0 / ((fact.provenance.timestamp.as_secs() as f64) + 1.0);
             let weight = fact.confidence as f64 * recency_weight;
             weighted_sum += weight;
             total_weight += recency_weight;
@@ -244,4 +357,74 @@ mod tests {
         let combined = reasoner.combine_conflicting_facts(&[fact1, fact2]).unwrap();
         assert!(combined.confidence >= 0.6 && combined.confidence <= 0.8);
     }
+
+    #[test]
+    fn test_revisions_are_recorded_in_ledger() {
+        let reasoner = EpistemicReasoner::new();
+        let mut ledger = BeliefLedger::new();
+        let current = Fact {
+            subject: 1,
+            predicate: "is_a".to_string(),
+            object: "Human".to_string(),
+            confidence: 0.6,
+            provenance: Provenance::new("sourceA", None),
+        };
+        let strong = Fact {
+            confidence: 1.0,
+            provenance: Provenance::new("sourceB", None),
+            ..current.clone()
+        };
+        let weak = Fact {
+            confidence: 0.9,
+            provenance: Provenance::new("rumor", None),
+            ..current.clone()
+        };
+
+        let revised = match reasoner.revise_belief_recorded(&current, &strong, &mut ledger) {
+            RevisionResult::Updated(fact) => fact,
+            _ => panic!("Belief should be updated"),
+        };
+        reasoner.revise_belief_contextual_recorded(&revised, &weak, 0.2, &mut ledger);
+
+        let history = ledger.history(&FactKey::from(&current));
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].outcome, RevisionOutcome::Accepted);
+        assert!((history[0].new_confidence - 0.8).abs() < 1e-6);
+        assert_eq!(history[0].evidence.source_name, "sourceB");
+        assert!(matches!(history[1].outcome, RevisionOutcome::Rejected(_)));
+        assert_eq!(history[1].new_confidence, history[1].old_confidence);
+        assert_eq!(history[1].parameters.get("source_reliability"), Some(&0.2));
+        assert_eq!(ledger.latest_accepted(&FactKey::from(&current)).unwrap().sequence, 0);
+        assert_eq!(ledger.explain(&FactKey::from(&current)).len(), 2);
+    }
+
+    #[test]
+    fn test_source_trust_acts_as_prior() {
+        let mut reasoner = EpistemicReasoner::new();
+        let current = Fact {
+            subject: 1,
+            predicate: "capital".to_string(),
+            object: "Paris".to_string(),
+            confidence: 0.6,
+            provenance: Provenance::new("atlas", None),
+        };
+        let from_crawler = Fact {
+            confidence: 0.9,
+            provenance: Provenance::new("crawler", None),
+            ..current.clone()
+        };
+
+        // Neutral prior: 0.9 * 0.5 = 0.45 falls under the 0.5 threshold
+        assert!(matches!(reasoner.revise_belief_from_source(&current, &from_crawler), RevisionResult::Rejected(_)));
+
+        for _ in 0..10 {
+            reasoner.record_fact_outcome(&from_crawler, true);
+        }
+        assert!(matches!(reasoner.revise_belief_from_source(&current, &from_crawler), RevisionResult::Updated(_)));
+
+        for _ in 0..30 {
+            reasoner.record_fact_outcome(&from_crawler, false);
+        }
+        assert!(matches!(reasoner.revise_belief_from_source(&current, &from_crawler), RevisionResult::Rejected(_)));
+    }
 }
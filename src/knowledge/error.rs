@@ -0,0 +1,79 @@
+// ============================================================================
+//                        ASTRA AGI • KNOWLEDGE ERRORS
+//        Typed Failures of the Knowledge Layer
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       The error type returned by the knowledge layer's public operations.
+//       Each failure is a variant callers can match on, and failures caused
+//       by the storage backend or serialization keep the underlying error as
+//       their source.
+//
+//   Core Functions:
+//       • Name the ways ontology, version, context, and domain updates fail
+//       • Wrap schema violations, storage, serialization, and export errors,
+//         and SQLite errors when the mirror is built
+//
+//   File:        /src/knowledge/error.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use thiserror::Error;
+
+use crate::integrity::IntegrityError;
+use crate::knowledge::ontology::Id;
+use crate::knowledge::schema::SchemaError;
+
+#[derive(Debug, Error)]
+pub enum KnowledgeError {
+    #[error("Concept {0} not found")]
+    ConceptNotFound(Id),
+    #[error("Parent concept {0} not found")]
+    ParentConceptNotFound(Id),
+    #[error("Adding parent {parent_id} to concept {concept_id} would create a cycle in the concept hierarchy")]
+    HierarchyCycle { concept_id: Id, parent_id: Id },
+    #[error("Source entity {0} not found")]
+    SourceEntityNotFound(Id),
+    #[error("Target entity {0} not found")]
+    TargetEntityNotFound(Id),
+    #[error("Entity {0} not found")]
+    EntityNotFound(Id),
+    #[error("Relationship {0} not found")]
+    RelationshipNotFound(Id),
+    /// The id belonged to an entity or relationship that has since been removed
+    #[error("Entity or relationship {0} was removed")]
+    Removed(Id),
+    #[error("Version {0} does not exist")]
+    VersionNotFound(u64),
+    #[error("Context {0} not found")]
+    ContextNotFound(u64),
+    #[error("Domain '{0}' is not registered")]
+    UnknownDomain(String),
+    #[error("Fact has an empty predicate")]
+    EmptyPredicate,
+    #[error("Confidence {0} is outside 0.0..=1.0")]
+    ConfidenceOutOfRange(f32),
+    #[error(transparent)]
+    Schema(#[from] SchemaError),
+    #[error("Storage backend failed")]
+    Storage(#[from] sled::Error),
+    #[error("Could not serialize or deserialize knowledge state")]
+    Serialization(#[from] serde_json::Error),
+    /// No saved state passed its checksum and decoded
+    #[error(transparent)]
+    Integrity(#[from] IntegrityError),
+    #[error("Could not send knowledge to the export target")]
+    Export(#[from] std::io::Error),
+    #[cfg(feature = "sqlite")]
+    #[error("SQLite mirror failed")]
+    Sqlite(#[from] rusqlite::Error),
+    /// The mirror was written by a newer release than this one
+    #[cfg(feature = "sqlite")]
+    #[error("SQLite mirror schema version {found} is newer than the {supported} this build knows")]
+    MirrorSchemaTooNew { found: usize, supported: usize },
+}
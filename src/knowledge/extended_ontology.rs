@@ -13,51 +13,97 @@
 //   Core Functions:
 //       • Represent entities, facts, provenance, and confidence levels
 //       • Maintain versioned ontology snapshots with lineage tracking
+//       • Share facts between versions, so a snapshot costs only what changed,
+//         and keep sharing what is left when facts are removed
+//       • Share contexts and domains with snapshots, copied only when changed
 //       • Support contextualized fact activation for user‑ or environment‑specific views
 //       • Provide APIs for querying, updating, and branching ontology states
+//       • Accept bulk fact streams with per-item error reporting
+//       • Partition facts into named domains (see domains.rs)
+//       • Publish fact additions and version switches to the change feed
+//       • Trace facts to the user who taught them, and forget them on request
+//       • Replace the current version's facts when restoring them from a log
+//       • Timestamp provenance to the millisecond, in the order facts arrive
+//       • Attach structured metadata to fact provenance and contexts
 //       • Serve as the semantic backbone for reasoning, memory, and inference
 //
 //   File:        /src/knowledge/extended_ontology.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-26
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
+use crate::knowledge::bulk_import::{BulkImportReport, BulkItemError};
+use crate::knowledge::change_feed::{ChangeFeed, KnowledgeChange};
+use crate::knowledge::domains::KnowledgeDomain;
+use crate::knowledge::embedding::fnv1a;
+use crate::knowledge::error::KnowledgeError;
+use crate::knowledge::fact_log::{FactLog, Rewrites};
+use crate::metadata::{MetaValue, Metadata};
+use crate::time::AstraTime;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Unique identifier for ontology entities and concepts.
 pub type EntityId = u64;
 
+/// A stable id for an entity known only by name, so that the same name in
+/// different sources refers to the same entity.
+pub fn name_entity_id(name: &str) -> EntityId {
+    fnv1a(name.trim().to_lowercase().bytes())
+}
+
 /// Represents the confidence level of a statement or fact.
 /// Range: 0.0 (no confidence) to 1.0 (absolute certainty).
 pub type Confidence = f32;
 
 /// Represents the source or provenance of a piece of knowledge.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Provenance {
     pub source_name: String,
-    pub timestamp: u64, // Unix timestamp
+    pub timestamp: AstraTime,
     pub notes: Option<String>,
+    /// Anything else known about where the fact came from, such as the page
+    /// or the entity it was read off
+    #[serde(default, skip_serializing_if = "Metadata::is_empty")]
+    pub metadata: Metadata,
 }
 
 impl Provenance {
     pub fn new(source_name: impl Into<String>, notes: Option<String>) -> Self {
-        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
         Self {
             source_name: source_name.into(),
-            timestamp: now,
+            timestamp: AstraTime::now(),
             notes,
+            metadata: Metadata::new(),
         }
     }
+
+    /// `self` with metadata entry `key` set to `value`.
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<MetaValue>) -> Self {
+        self.metadata.insert(key, value);
+        self
+    }
+
+    /// Provenance for a fact learned from what a user said.
+    pub fn from_user(user_id: &str, notes: Option<String>) -> Self {
+        Self::new(format!("user:{}", user_id), notes)
+    }
+
+    /// The user a fact was learned from, if it came from one.
+    pub fn user_id(&self) -> Option<&str> {
+        self.source_name.strip_prefix("user:")
+    }
 }
 
 /// Represents a single fact or statement in the ontology.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Fact {
     pub subject: EntityId,
     pub predicate: String,
@@ -66,13 +112,28 @@ pub struct Fact {
     pub provenance: Provenance,
 }
 
+impl Fact {
+    /// Refuses facts the knowledge base does not take: an empty predicate
+    /// or a confidence outside 0.0..=1.0.
+    pub fn validate(&self) -> Result<(), KnowledgeError> {
+        if self.predicate.is_empty() {
+            return Err(KnowledgeError::EmptyPredicate);
+        }
+        if !(0.0..=1.0).contains(&self.confidence) {
+            return Err(KnowledgeError::ConfidenceOutOfRange(self.confidence));
+        }
+        Ok(())
+    }
+}
+
 /// Represents a version of the ontology.
-/// Supports immutable snapshots for rollback and branching.
+/// Supports immutable snapshots for rollback and branching. Facts, with
+/// the domains they are filed under, are shared with the parent version.
 #[derive(Debug, Clone)]
 pub struct OntologyVersion {
     pub version_id: u64,
     pub timestamp: u64,
-    pub facts: Vec<Fact>,
+    pub facts: FactLog,
     pub parent_version: Option<u64>, // For version lineage
 }
 
@@ -83,14 +144,18 @@ pub struct OntologyContext {
     pub context_id: u64,
     pub name: String,
     pub active_facts: HashSet<usize>, // Indexes into OntologyVersion.facts for active facts
-    pub metadata: HashMap<String, String>, // Context-specific metadata
+    pub metadata: Metadata, // Context-specific metadata
 }
 
 /// The main ontology manager that holds versions, contexts, and provides APIs for querying.
 pub struct OntologyManager {
-    versions: HashMap<u64, OntologyVersion>,
-    contexts: HashMap<u64, OntologyContext>,
-    current_version: u64,
+    pub(crate) versions: HashMap<u64, OntologyVersion>,
+    /// Shared with snapshots until the next change to contexts
+    pub(crate) contexts: Arc<HashMap<u64, OntologyContext>>,
+    /// Shared with snapshots until the next change to domains
+    pub(crate) domains: Arc<HashMap<String, KnowledgeDomain>>,
+    pub(crate) current_version: u64,
+    pub(crate) change_feed: ChangeFeed,
     next_version_id: u64,
     next_context_id: u64,
 }
@@ -101,7 +166,7 @@ impl OntologyManager {
         let initial_version = OntologyVersion {
             version_id: 0,
             timestamp: current_unix_timestamp(),
-            facts: Vec::new(),
+            facts: FactLog::new(),
             parent_version: None,
         };
 
@@ -110,8 +175,10 @@ impl OntologyManager {
 
         OntologyManager {
             versions,
-            contexts: HashMap::new(),
+            contexts: Arc::new(HashMap::new()),
+            domains: Arc::new(HashMap::new()),
             current_version: 0,
+            change_feed: ChangeFeed::default(),
             next_version_id: 1,
             next_context_id: 1,
         }
@@ -121,20 +188,50 @@ impl OntologyManager {
     /// Returns the index of the fact within the version.
     pub fn add_fact(&mut self, fact: Fact) -> usize {
         let current_version = self.versions.get_mut(&self.current_version).unwrap();
-        current_version.facts.push(fact);
-        current_version.facts.len() - 1
+        let index = current_version.facts.push(fact.clone());
+        self.change_feed.publish(KnowledgeChange::FactAdded { version: self.current_version, index, fact });
+        index
+    }
+
+    /// Adds a stream of facts to the current version in one pass.
+    /// Facts with an empty predicate or a confidence outside 0.0..=1.0 are
+    /// reported and skipped; the rest are still added.
+    pub fn add_facts_bulk<I>(&mut self, facts: I) -> BulkImportReport
+    where
+        I: IntoIterator<Item = Fact>,
+    {
+        let current_version = self.versions.get_mut(&self.current_version).unwrap();
+        let mut report = BulkImportReport::default();
+
+        for (index, fact) in facts.into_iter().enumerate() {
+            if let Err(error) = fact.validate() {
+                report.errors.push(BulkItemError { index, error });
+                continue;
+            }
+            let fact_index = current_version.facts.push(fact.clone());
+            report.inserted.push((index, fact_index));
+            self.change_feed.publish(KnowledgeChange::FactAdded {
+                version: self.current_version,
+                index: fact_index,
+                fact,
+            });
+        }
+
+        report
     }
 
     /// Creates a new version based on the current one (snapshot).
+    /// The new version shares its parent's facts, so this costs only as
+    /// much as the facts added since the parent was last snapshotted.
     /// Returns the new version ID.
     pub fn create_version(&mut self) -> u64 {
         let parent_version = self.current_version;
-        let parent = self.versions.get(&parent_version).unwrap();
+        let parent = self.versions.get_mut(&parent_version).unwrap();
 
         let new_version = OntologyVersion {
             version_id: self.next_version_id,
             timestamp: current_unix_timestamp(),
-            facts: parent.facts.clone(),
+            facts: parent.facts.snapshot(),
             parent_version: Some(parent_version),
         };
 
@@ -147,17 +244,21 @@ impl OntologyManager {
 
     /// Switches the active version to the specified version ID.
     /// Returns error if the version does not exist.
-    pub fn switch_version(&mut self, version_id: u64) -> Result<(), String> {
+    pub fn switch_version(&mut self, version_id: u64) -> Result<(), KnowledgeError> {
         if self.versions.contains_key(&version_id) {
+            let from = self.current_version;
             self.current_version = version_id;
+            if from != version_id {
+                self.change_feed.publish(KnowledgeChange::VersionSwitched { from, to: version_id });
+            }
             Ok(())
         } else {
-            Err(format!("Version {} does not exist", version_id))
+            Err(KnowledgeError::VersionNotFound(version_id))
         }
     }
 
     /// Creates a new context with a name and optional metadata.
-    pub fn create_context(&mut self, name: impl Into<String>, metadata: Option<HashMap<String, String>>) -> u64 {
+    pub fn create_context(&mut self, name: impl Into<String>, metadata: Option<Metadata>) -> u64 {
         let id = self.next_context_id;
         self.next_context_id += 1;
 
@@ -168,17 +269,17 @@ impl OntologyManager {
             metadata: metadata.unwrap_or_default(),
         };
 
-        self.contexts.insert(id, context);
+        Arc::make_mut(&mut self.contexts).insert(id, context);
         id
     }
 
     /// Adds a fact index to a context's active facts.
-    pub fn add_fact_to_context(&mut self, context_id: u64, fact_index: usize) -> Result<(), String> {
-        if let Some(context) = self.contexts.get_mut(&context_id) {
+    pub fn add_fact_to_context(&mut self, context_id: u64, fact_index: usize) -> Result<(), KnowledgeError> {
+        if let Some(context) = Arc::make_mut(&mut self.contexts).get_mut(&context_id) {
             context.active_facts.insert(fact_index);
             Ok(())
         } else {
-            Err(format!("Context {} not found", context_id))
+            Err(KnowledgeError::ContextNotFound(context_id))
         }
     }
 
@@ -204,10 +305,97 @@ impl OntologyManager {
     pub fn current_version(&self) -> u64 {
         self.current_version
     }
+
+    /// Facts in the current version learned from `user_id`.
+    pub fn facts_from_user(&self, user_id: &str) -> Vec<&Fact> {
+        self.versions[&self.current_version]
+            .facts
+            .iter()
+            .filter(|f| f.provenance.user_id() == Some(user_id))
+            .collect()
+    }
+
+    /// Removes every fact learned from `user_id`, from all versions so that
+    /// switching back cannot restore them. Returns how many were removed.
+    /// Versions that shared facts share what is left of them.
+    pub fn forget_user(&mut self, user_id: &str) -> usize {
+        let version_ids: Vec<u64> = self.versions.keys().copied().collect();
+        let mut rewrites = Rewrites::new();
+        let mut total = 0;
+        for version_id in version_ids {
+            let removed =
+                self.retain_facts(version_id, |_, f| f.provenance.user_id() != Some(user_id), &mut rewrites);
+            if removed == 0 {
+                continue;
+            }
+            self.change_feed.publish(KnowledgeChange::FactsRetired { version: version_id, count: removed });
+            total += removed;
+        }
+        total
+    }
+
+    /// Removes from the current version the facts `source` contributed whose
+    /// (subject, predicate, object) is in `retracted`, as when the document
+    /// they were read from changes or disappears. The same statement from
+    /// another source stays. Returns how many were removed.
+    pub fn retract_facts(&mut self, source: &str, retracted: &HashSet<(EntityId, String, String)>) -> usize {
+        let version = self.current_version;
+        let removed = self.retain_facts(
+            version,
+            |_, f| {
+                f.provenance.source_name != source
+                    || !retracted.contains(&(f.subject, f.predicate.clone(), f.object.clone()))
+            },
+            &mut Rewrites::new(),
+        );
+        if removed > 0 {
+            self.change_feed.publish(KnowledgeChange::FactsRetired { version, count: removed });
+        }
+        removed
+    }
+
+    /// Replaces the facts of the current version with `facts`, as when
+    /// restoring them from a log. Other versions keep theirs.
+    pub fn replace_facts(&mut self, facts: Vec<Fact>) {
+        let held = self.versions[&self.current_version].facts.len();
+        if held > 0 {
+            self.remove_facts(self.current_version, &(0..held).collect());
+            self.change_feed.publish(KnowledgeChange::FactsRetired { version: self.current_version, count: held });
+        }
+        for fact in facts {
+            self.add_fact(fact);
+        }
+    }
+
+    /// Drops the facts at `removed` from a version, renumbering the rest.
+    pub(crate) fn remove_facts(&mut self, version_id: u64, removed: &HashSet<usize>) {
+        self.retain_facts(version_id, |idx, _| !removed.contains(&idx), &mut Rewrites::new());
+    }
+
+    /// Keeps only the facts of a version for which `keep` holds, renumbering
+    /// the rest, and returns how many were dropped. Domain assignments follow
+    /// the facts, and contexts do too when the version is the current one.
+    fn retain_facts(
+        &mut self,
+        version_id: u64,
+        keep: impl FnMut(usize, &Fact) -> bool,
+        rewrites: &mut Rewrites,
+    ) -> usize {
+        let Some(version) = self.versions.get_mut(&version_id) else { return 0 };
+        let moved = version.facts.retain(keep, rewrites);
+        let dropped = moved.iter().filter(|idx| idx.is_none()).count();
+        if version_id == self.current_version && dropped > 0 {
+            for context in Arc::make_mut(&mut self.contexts).values_mut() {
+                context.active_facts =
+                    context.active_facts.iter().filter_map(|idx| moved.get(*idx).copied().flatten()).collect();
+            }
+        }
+        dropped
+    }
 }
 
 /// Helper function to get current unix timestamp in seconds.
-fn current_unix_timestamp() -> u64 {
+pub(crate) fn current_unix_timestamp() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .expect("Time went backwards")
@@ -244,4 +432,58 @@ mod tests {
         manager.switch_version(0).unwrap();
         assert_eq!(manager.current_version(), 0);
     }
+
+    #[test]
+    fn test_add_facts_bulk_reports_invalid_items() {
+        let mut manager = OntologyManager::new();
+
+        let fact = |predicate: &str, confidence: Confidence| Fact {
+            subject: 1,
+            predicate: predicate.to_string(),
+            object: "Paris".to_string(),
+            confidence,
+            provenance: Provenance::new("Crawler", None),
+        };
+
+        let report = manager.add_facts_bulk(vec![
+            fact("located_in", 0.8),
+            fact("", 0.5),
+            fact("capital_of", 1.7),
+            fact("capital_of", 0.9),
+        ]);
+
+        assert_eq!(report.succeeded(), 2);
+        assert_eq!(report.errors.iter().map(|e| e.index).collect::<Vec<_>>(), vec![1, 2]);
+        assert!(matches!(report.errors[0].error, KnowledgeError::EmptyPredicate));
+        assert_eq!(manager.query_facts(None).len(), 2);
+    }
+
+    #[test]
+    fn test_forget_user_removes_their_facts_from_every_version() {
+        let mut manager = OntologyManager::new();
+        let fact = |object: &str, provenance: Provenance| Fact {
+            subject: 1,
+            predicate: "likes".to_string(),
+            object: object.to_string(),
+            confidence: 0.8,
+            provenance,
+        };
+
+        manager.add_fact(fact("tea", Provenance::from_user("u1", None)));
+        let kept = manager.add_fact(fact("coffee", Provenance::new("Crawler", None)));
+        let context_id = manager.create_context("Default", None);
+        manager.add_fact_to_context(context_id, kept).unwrap();
+        manager.create_version();
+        manager.add_fact(fact("jazz", Provenance::from_user("u1", None)));
+        manager.add_fact(fact("rock", Provenance::from_user("u2", None)));
+
+        assert_eq!(manager.facts_from_user("u1").len(), 2);
+        assert_eq!(manager.forget_user("u1"), 3);
+        assert!(manager.facts_from_user("u1").is_empty());
+        assert_eq!(manager.query_facts(None).len(), 2);
+
+        manager.switch_version(0).unwrap();
+        let objects: Vec<&str> = manager.query_facts(None).iter().map(|f| f.object.as_str()).collect();
+        assert_eq!(objects, vec!["coffee"]);
+    }
 }
@@ -0,0 +1,322 @@
+// ============================================================================
+//                        ASTRA AGI • PERSISTENT FACT LOG
+//        Structurally Shared Fact Lists for Cheap Ontology Versions
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Storage behind each ontology version's facts. A version used to own
+//       a full copy of its parent's facts, so every snapshot cost as much
+//       as the whole knowledge base. A fact log instead keeps its facts in
+//       immutable, reference-counted segments that versions share, plus a
+//       tail of facts added since the last snapshot. Taking a snapshot
+//       freezes the tail into a segment and copies only the list of
+//       segments, so it costs as much as what changed. Segments are merged
+//       as they freeze, keeping each one at least twice the size of the
+//       next, so even a long chain of versions holds only a logarithmic
+//       number of them. Each fact's domain is kept beside it, so domain
+//       assignments are shared the same way. Removing facts rewrites only
+//       the segments they were in, and logs that share a segment and drop
+//       the same facts from it share what is left of it too.
+//
+//   Core Functions:
+//       • Index, iterate, and append facts like a vector
+//       • Snapshot in proportion to the facts added since the last snapshot
+//       • Merge small segments so long version chains stay shallow
+//       • Keep each fact's domain alongside it
+//       • Remove facts without copying the segments they were not in
+//
+//   File:        /src/knowledge/fact_log.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use crate::knowledge::extended_ontology::Fact;
+use std::collections::HashMap;
+use std::ops::Index;
+use std::sync::Arc;
+
+/// A fact and the domain it was filed under; `None` means core.
+#[derive(Debug, Clone)]
+struct Entry {
+    fact: Fact,
+    domain: Option<String>,
+}
+
+impl From<Fact> for Entry {
+    fn from(fact: Fact) -> Self {
+        Entry { fact, domain: None }
+    }
+}
+
+/// An append-only list of facts whose snapshots share storage.
+#[derive(Debug, Clone, Default)]
+pub struct FactLog {
+    /// Frozen facts, possibly shared with other logs, oldest first
+    segments: Vec<Arc<Vec<Entry>>>,
+    /// Index of the first fact in each segment
+    starts: Vec<usize>,
+    /// Facts in all segments
+    frozen_len: usize,
+    /// Facts added since the last snapshot, owned by this log alone
+    tail: Vec<Entry>,
+}
+
+/// Segments already rewritten by `FactLog::retain`, so that logs sharing a
+/// segment and keeping the same facts of it end up sharing the rewrite.
+#[derive(Default)]
+pub struct Rewrites {
+    /// By the address of the original segment
+    segments: HashMap<usize, Rewrite>,
+}
+
+struct Rewrite {
+    /// Held so that its address cannot be reused by another segment
+    _original: Arc<Vec<Entry>>,
+    /// Which of its facts were kept
+    kept: Vec<bool>,
+    /// Just those facts
+    segment: Arc<Vec<Entry>>,
+}
+
+impl Rewrites {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn rewrite(&mut self, segment: &Arc<Vec<Entry>>, kept: Vec<bool>) -> Arc<Vec<Entry>> {
+        let key = Arc::as_ptr(segment) as usize;
+        if let Some(rewrite) = self.segments.get(&key).filter(|rewrite| rewrite.kept == kept) {
+            return Arc::clone(&rewrite.segment);
+        }
+        let rewritten: Arc<Vec<Entry>> =
+            Arc::new(segment.iter().zip(&kept).filter(|(_, kept)| **kept).map(|(entry, _)| entry.clone()).collect());
+        let rewrite = Rewrite { _original: Arc::clone(segment), kept, segment: Arc::clone(&rewritten) };
+        self.segments.insert(key, rewrite);
+        rewritten
+    }
+}
+
+impl FactLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.frozen_len + self.tail.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Fact> {
+        self.entry(index).map(|entry| &entry.fact)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Fact> + '_ {
+        self.entries().map(|entry| &entry.fact)
+    }
+
+    /// Appends a fact, returning its index.
+    pub fn push(&mut self, fact: Fact) -> usize {
+        self.tail.push(fact.into());
+        self.len() - 1
+    }
+
+    /// Domain the fact at `index` was filed under, if not core.
+    pub fn domain(&self, index: usize) -> Option<&str> {
+        self.entry(index).and_then(|entry| entry.domain.as_deref())
+    }
+
+    /// Files the fact at `index` under `domain`. A fact in a shared segment
+    /// gets a copy of the segment, so the logs sharing it are unaffected.
+    pub fn set_domain(&mut self, index: usize, domain: &str) {
+        let entry = if index >= self.frozen_len {
+            self.tail.get_mut(index - self.frozen_len)
+        } else {
+            let segment = self.starts.partition_point(|&start| start <= index) - 1;
+            Arc::make_mut(&mut self.segments[segment]).get_mut(index - self.starts[segment])
+        };
+        if let Some(entry) = entry {
+            entry.domain = Some(domain.to_string());
+        }
+    }
+
+    /// Indexes of the facts filed under a domain, with the domain.
+    pub fn domains(&self) -> impl Iterator<Item = (usize, &str)> + '_ {
+        self.entries()
+            .enumerate()
+            .filter_map(|(index, entry)| entry.domain.as_deref().map(|domain| (index, domain)))
+    }
+
+    /// Keeps only the facts for which `keep` holds, renumbering the rest,
+    /// and returns the new index of each old one. Segments losing no facts
+    /// stay shared; the others are rewritten once per `rewrites`, however
+    /// many logs share them.
+    pub fn retain(
+        &mut self,
+        mut keep: impl FnMut(usize, &Fact) -> bool,
+        rewrites: &mut Rewrites,
+    ) -> Vec<Option<usize>> {
+        let mut moved = Vec::with_capacity(self.len());
+        let mut segments = Vec::with_capacity(self.segments.len());
+        let mut starts = Vec::with_capacity(self.starts.len());
+        let mut held = 0;
+        for (segment, start) in self.segments.iter().zip(&self.starts) {
+            let kept: Vec<bool> =
+                segment.iter().enumerate().map(|(offset, entry)| keep(start + offset, &entry.fact)).collect();
+            for kept in &kept {
+                moved.push(kept.then_some(held));
+                held += usize::from(*kept);
+            }
+            let segment = if kept.iter().all(|kept| *kept) { Arc::clone(segment) } else { rewrites.rewrite(segment, kept) };
+            if !segment.is_empty() {
+                starts.push(held - segment.len());
+                segments.push(segment);
+            }
+        }
+        self.segments = segments;
+        self.starts = starts;
+        self.frozen_len = held;
+
+        let mut index = moved.len();
+        self.tail.retain(|entry| {
+            let kept = keep(index, &entry.fact);
+            moved.push(kept.then_some(held));
+            held += usize::from(kept);
+            index += 1;
+            kept
+        });
+        moved
+    }
+
+    /// A log with the same facts that shares their storage with this one.
+    /// Facts pushed to either afterwards belong to it alone. Costs as much
+    /// as the facts pushed since the last snapshot.
+    pub fn snapshot(&mut self) -> FactLog {
+        self.freeze();
+        self.clone()
+    }
+
+    /// Segments the frozen facts are kept in.
+    pub fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+
+    fn entry(&self, index: usize) -> Option<&Entry> {
+        if index >= self.frozen_len {
+            return self.tail.get(index - self.frozen_len);
+        }
+        let segment = self.starts.partition_point(|&start| start <= index) - 1;
+        self.segments[segment].get(index - self.starts[segment])
+    }
+
+    fn entries(&self) -> impl Iterator<Item = &Entry> + '_ {
+        self.segments.iter().flat_map(|segment| segment.iter()).chain(self.tail.iter())
+    }
+
+    /// Moves the tail into a segment of its own, then merges the newest
+    /// segments while the newer is more than half the size of the older.
+    fn freeze(&mut self) {
+        if self.tail.is_empty() {
+            return;
+        }
+        self.starts.push(self.frozen_len);
+        self.frozen_len += self.tail.len();
+        self.segments.push(Arc::new(std::mem::take(&mut self.tail)));
+
+        while let [.., older, newer] = self.segments.as_slice() {
+            if newer.len() * 2 <= older.len() {
+                break;
+            }
+            let newer = self.segments.pop().expect("two segments");
+            let older = self.segments.pop().expect("two segments");
+            self.starts.pop();
+            // Unshared segments are reused rather than copied
+            let mut merged = Arc::unwrap_or_clone(older);
+            merged.extend(Arc::unwrap_or_clone(newer));
+            self.segments.push(Arc::new(merged));
+        }
+    }
+}
+
+impl Index<usize> for FactLog {
+    type Output = Fact;
+
+    fn index(&self, index: usize) -> &Fact {
+        self.get(index).expect("fact index out of range")
+    }
+}
+
+impl FromIterator<Fact> for FactLog {
+    fn from_iter<I: IntoIterator<Item = Fact>>(facts: I) -> Self {
+        FactLog { tail: facts.into_iter().map(Entry::from).collect(), ..Self::default() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::extended_ontology::Provenance;
+
+    fn fact(n: usize) -> Fact {
+        Fact {
+            subject: n as u64,
+            predicate: "n".to_string(),
+            object: n.to_string(),
+            confidence: 1.0,
+            provenance: Provenance::new("test", None),
+        }
+    }
+
+    #[test]
+    fn test_snapshots_share_facts_and_stay_shallow() {
+        let mut log = FactLog::new();
+        let mut versions = Vec::new();
+        for n in 0..1000 {
+            log.push(fact(n));
+            versions.push(log.snapshot());
+        }
+        // One segment per set bit of the length
+        assert_eq!(log.segment_count(), 1000usize.count_ones() as usize);
+        assert_eq!(log.len(), 1000);
+        assert_eq!(log[637].subject, 637);
+        assert_eq!(log.iter().map(|f| f.subject).sum::<u64>(), (0..1000).sum::<u64>());
+
+        // Earlier versions keep what they had, and share it
+        let mut early = versions[9].clone();
+        assert_eq!(early.len(), 10);
+        assert!(early.get(10).is_none());
+        assert!(Arc::ptr_eq(&early.segments[0], &versions[10].segments[0]));
+        early.push(fact(5000));
+        assert_eq!((early.len(), log[10].subject), (11, 10));
+        assert_eq!(early[10].subject, 5000);
+
+        let copied: FactLog = log.iter().take(3).cloned().collect();
+        assert_eq!(copied.iter().map(|f| f.subject).collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_removal_keeps_domains_and_sharing() {
+        let mut log: FactLog = (0..8).map(fact).collect();
+        log.set_domain(3, "medical");
+        let mut shared = log.snapshot();
+        log.push(fact(8));
+
+        let mut rewrites = Rewrites::new();
+        let moved = log.retain(|_, f| f.subject != 1, &mut rewrites);
+        assert_eq!(moved[..4], [Some(0), None, Some(1), Some(2)]);
+        assert_eq!(moved[8], Some(7));
+        assert_eq!((log.len(), log[7].subject), (8, 8));
+        assert_eq!(log.domains().collect::<Vec<_>>(), vec![(2, "medical")]);
+
+        // A log sharing the segment and dropping the same fact shares the rewrite
+        shared.retain(|_, f| f.subject != 1, &mut rewrites);
+        assert!(Arc::ptr_eq(&log.segments[0], &shared.segments[0]));
+        assert_eq!(shared.domain(2), Some("medical"));
+    }
+}
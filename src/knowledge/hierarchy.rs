@@ -0,0 +1,183 @@
+// ============================================================================
+//                   ASTRA AGI • CONCEPT HIERARCHY REASONING
+//        Subsumption, Attribute Inheritance & Taxonomy Integrity Checks
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Knowledge Layer, giving meaning to the
+//       `parent_ids` declared on each concept. This module answers
+//       subsumption questions ("is Dog an Animal?"), resolves attribute
+//       declarations inherited from ancestor concepts, and guards the
+//       taxonomy against cycles so traversal can never loop forever.
+//
+//   Core Functions:
+//       • Compute ancestor and descendant sets for any concept
+//       • Answer subsumption queries (`is_subconcept_of`)
+//       • Merge inherited attribute declarations down the hierarchy
+//       • Detect and reject cyclic parent links
+//       • Resolve concept queries to entities of the concept or its subconcepts
+//
+//   File:        /src/knowledge/hierarchy.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use crate::knowledge::error::KnowledgeError;
+use crate::knowledge::ontology::{AttributeType, Entity};
+use crate::knowledge::storage::Storage;
+use crate::knowledge::{Id, Ontology};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+impl<S: Storage> Ontology<S> {
+    /// Returns all ancestors of a concept (parents, grandparents, ...),
+    /// excluding the concept itself. Safe on cyclic hierarchies.
+    pub fn concept_ancestors(&self, concept_id: Id) -> HashSet<Id> {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+
+        if let Some(concept) = self.concepts.get(&concept_id) {
+            queue.extend(concept.parent_ids.iter().copied());
+        }
+
+        while let Some(current) = queue.pop_front() {
+            if current == concept_id || !visited.insert(current) {
+                continue;
+            }
+            if let Some(concept) = self.concepts.get(&current) {
+                queue.extend(concept.parent_ids.iter().copied());
+            }
+        }
+
+        visited
+    }
+
+    /// Returns all descendants of a concept (children, grandchildren, ...),
+    /// excluding the concept itself. Safe on cyclic hierarchies.
+    pub fn concept_descendants(&self, concept_id: Id) -> HashSet<Id> {
+        // Invert parent links once so the walk is linear in the hierarchy size.
+        let mut children: HashMap<Id, Vec<Id>> = HashMap::new();
+        for concept in self.concepts.values() {
+            for parent_id in &concept.parent_ids {
+                children.entry(*parent_id).or_default().push(concept.id);
+            }
+        }
+
+        let mut visited = HashSet::new();
+        let mut queue: VecDeque<Id> = children.get(&concept_id).cloned().unwrap_or_default().into();
+
+        while let Some(current) = queue.pop_front() {
+            if current == concept_id || !visited.insert(current) {
+                continue;
+            }
+            if let Some(grandchildren) = children.get(&current) {
+                queue.extend(grandchildren.iter().copied());
+            }
+        }
+
+        visited
+    }
+
+    /// Returns true if `concept_a` is `concept_b` or one of its descendants.
+    pub fn is_subconcept_of(&self, concept_a: Id, concept_b: Id) -> bool {
+        concept_a == concept_b || self.concept_ancestors(concept_a).contains(&concept_b)
+    }
+
+    /// Returns the attribute declarations that apply to a concept, including
+    /// those inherited from its ancestors. Declarations closer to the concept
+    /// override those of more distant ancestors.
+    pub fn effective_attributes(&self, concept_id: Id) -> HashMap<String, AttributeType> {
+        let mut effective = HashMap::new();
+
+        // Walk breadth-first from the concept upwards, keeping the first
+        // declaration seen for each attribute name.
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from([concept_id]);
+
+        while let Some(current) = queue.pop_front() {
+            if !visited.insert(current) {
+                continue;
+            }
+            if let Some(concept) = self.concepts.get(&current) {
+                for (name, attr_type) in &concept.attributes {
+                    effective.entry(name.clone()).or_insert_with(|| attr_type.clone());
+                }
+                queue.extend(concept.parent_ids.iter().copied());
+            }
+        }
+
+        effective
+    }
+
+    /// Adds a parent link to an existing concept.
+    /// Rejects unknown concepts and links that would introduce a cycle.
+    pub fn add_concept_parent(&mut self, concept_id: Id, parent_id: Id) -> Result<(), KnowledgeError> {
+        if !self.concepts.contains_key(&parent_id) {
+            return Err(KnowledgeError::ParentConceptNotFound(parent_id));
+        }
+        if self.is_subconcept_of(parent_id, concept_id) {
+            return Err(KnowledgeError::HierarchyCycle { concept_id, parent_id });
+        }
+
+        let concept = self
+            .concepts
+            .get_mut(&concept_id)
+            .ok_or(KnowledgeError::ConceptNotFound(concept_id))?;
+        concept.parent_ids.insert(parent_id);
+        Ok(())
+    }
+
+    /// Finds concepts that participate in a cycle of parent links.
+    /// Hierarchies built through `add_concept_parent` are acyclic, but state
+    /// loaded from storage is not guaranteed to be.
+    pub fn detect_hierarchy_cycles(&self) -> Vec<Id> {
+        let mut cyclic: Vec<Id> = self
+            .concepts
+            .keys()
+            .copied()
+            .filter(|id| self.concept_ancestors_including_self_loop(*id))
+            .collect();
+        cyclic.sort_unstable();
+        cyclic
+    }
+
+    /// Returns entities whose concept is the given concept or any of its
+    /// subconcepts.
+    pub fn find_entities_by_concept(&self, concept_id: Id) -> Vec<&Entity> {
+        let mut concept_ids = self.concept_descendants(concept_id);
+        concept_ids.insert(concept_id);
+
+        concept_ids
+            .iter()
+            .filter_map(|cid| self.concept_index.get(cid))
+            .flat_map(|entity_ids| entity_ids.iter().filter_map(|id| self.entities.get(id)))
+            .collect()
+    }
+
+    /// Returns true if the concept can reach itself through parent links.
+    fn concept_ancestors_including_self_loop(&self, concept_id: Id) -> bool {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+
+        if let Some(concept) = self.concepts.get(&concept_id) {
+            queue.extend(concept.parent_ids.iter().copied());
+        }
+
+        while let Some(current) = queue.pop_front() {
+            if current == concept_id {
+                return true;
+            }
+            if !visited.insert(current) {
+                continue;
+            }
+            if let Some(concept) = self.concepts.get(&current) {
+                queue.extend(concept.parent_ids.iter().copied());
+            }
+        }
+
+        false
+    }
+}
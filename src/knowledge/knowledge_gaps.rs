@@ -0,0 +1,215 @@
+// ============================================================================
+//                       ASTRA AGI • KNOWLEDGE GAP DETECTOR
+//        Noticing What Astra Does Not Know & How Often It Matters
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s epistemic layer that keeps a tally of the things
+//       Astra was unable to answer: queries that matched nothing, answers
+//       given with low confidence, and names mentioned in conversation that
+//       the knowledge base has never seen. Gaps that keep coming up are the
+//       ones worth learning about; goal formation turns them into learning
+//       goals served by the focused crawler or by asking the user.
+//
+//   Core Functions:
+//       • Log failed queries, low-confidence answers, and unknown entities
+//       • Count recurrences of each gap and when it was last encountered
+//       • Rank gaps by frequency for conversion into learning goals
+//       • Choose between crawling and asking the user for each gap
+//
+//   File:        /src/knowledge/knowledge_gaps.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::knowledge::extended_ontology::{current_unix_timestamp, OntologyManager};
+
+/// Answers below this confidence count as gaps.
+pub const LOW_CONFIDENCE_THRESHOLD: f32 = 0.5;
+
+/// How a gap showed itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GapKind {
+    FailedQuery,
+    LowConfidence,
+    UnknownEntity,
+}
+
+/// Where Astra should go to fill a gap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LearningChannel {
+    /// Search the web with the focused crawler
+    Crawl,
+    /// Ask the user, who brought the subject up
+    AskUser,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnowledgeGap {
+    /// Normalized (lowercase) topic the gap is about
+    pub topic: String,
+    /// Occurrences by kind
+    pub counts: HashMap<GapKind, u32>,
+    pub first_seen: u64,
+    pub last_seen: u64,
+}
+
+impl KnowledgeGap {
+    pub fn occurrences(&self) -> u32 {
+        self.counts.values().sum()
+    }
+
+    /// Names only the user has mentioned are best asked about; anything
+    /// Astra failed to look up or answer well is searched for.
+    pub fn channel(&self) -> LearningChannel {
+        let unknown = self.counts.get(&GapKind::UnknownEntity).copied().unwrap_or(0);
+        if unknown * 2 > self.occurrences() {
+            LearningChannel::AskUser
+        } else {
+            LearningChannel::Crawl
+        }
+    }
+}
+
+/// Running log of knowledge gaps, keyed by topic.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KnowledgeGapDetector {
+    gaps: HashMap<String, KnowledgeGap>,
+}
+
+impl KnowledgeGapDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, topic: &str, kind: GapKind) {
+        let topic = topic.trim().to_lowercase();
+        if topic.is_empty() {
+            return;
+        }
+        let now = current_unix_timestamp();
+        let gap = self.gaps.entry(topic.clone()).or_insert_with(|| KnowledgeGap {
+            topic,
+            counts: HashMap::new(),
+            first_seen: now,
+            last_seen: now,
+        });
+        *gap.counts.entry(kind).or_insert(0) += 1;
+        gap.last_seen = now;
+    }
+
+    /// Logs a query about `topic` that returned nothing.
+    pub fn record_failed_query(&mut self, topic: &str) {
+        self.record(topic, GapKind::FailedQuery);
+    }
+
+    /// Logs an answer about `topic`, if it was given with low confidence.
+    pub fn record_answer_confidence(&mut self, topic: &str, confidence: f32) {
+        if confidence < LOW_CONFIDENCE_THRESHOLD {
+            self.record(topic, GapKind::LowConfidence);
+        }
+    }
+
+    /// Logs names in `text` that appear nowhere in the knowledge base and
+    /// returns them. A name is a capitalized word that does not start a
+    /// sentence.
+    pub fn record_unknown_entities(&mut self, text: &str, knowledge: &OntologyManager) -> Vec<String> {
+        let facts = knowledge.query_facts(None);
+        let unknown: Vec<String> = candidate_names(text)
+            .into_iter()
+            .filter(|name| {
+                let name = name.to_lowercase();
+                !facts.iter().any(|f| f.object.to_lowercase() == name || f.predicate.to_lowercase() == name)
+            })
+            .collect();
+        for name in &unknown {
+            self.record(name, GapKind::UnknownEntity);
+        }
+        unknown
+    }
+
+    /// Gaps encountered at least `min_occurrences` times, most frequent first.
+    pub fn frequent_gaps(&self, min_occurrences: u32) -> Vec<&KnowledgeGap> {
+        let mut gaps: Vec<_> = self.gaps.values().filter(|g| g.occurrences() >= min_occurrences).collect();
+        gaps.sort_by(|a, b| b.occurrences().cmp(&a.occurrences()).then(b.last_seen.cmp(&a.last_seen)));
+        gaps
+    }
+
+    pub fn get(&self, topic: &str) -> Option<&KnowledgeGap> {
+        self.gaps.get(&topic.to_lowercase())
+    }
+
+    /// Forgets a gap once something has been done about it. If it is still
+    /// a gap, it will be logged again.
+    pub fn resolve(&mut self, topic: &str) -> Option<KnowledgeGap> {
+        self.gaps.remove(&topic.to_lowercase())
+    }
+
+    pub fn len(&self) -> usize {
+        self.gaps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.gaps.is_empty()
+    }
+}
+
+fn candidate_names(text: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut sentence_start = true;
+    for raw in text.split_whitespace() {
+        let word = raw.trim_matches(|c: char| !c.is_alphanumeric());
+        if !sentence_start
+            && word.len() > 1
+            && word.chars().next().is_some_and(char::is_uppercase)
+            && !names.iter().any(|n: &String| n == word)
+        {
+            names.push(word.to_string());
+        }
+        sentence_start = raw.ends_with(['.', '!', '?']);
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::extended_ontology::{Fact, Provenance};
+
+    #[test]
+    fn test_gaps_are_counted_and_ranked() {
+        let mut knowledge = OntologyManager::new();
+        knowledge.add_fact(Fact {
+            subject: 1,
+            predicate: "name".into(),
+            object: "Paris".into(),
+            confidence: 0.9,
+            provenance: Provenance::new("test", None),
+        });
+
+        let mut detector = KnowledgeGapDetector::new();
+        let unknown = detector.record_unknown_entities("We flew from Paris to Reykjavik. Then Reykjavik.", &knowledge);
+        assert_eq!(unknown, vec!["Reykjavik".to_string()]);
+        detector.record_unknown_entities("Is Reykjavik cold?", &knowledge);
+
+        detector.record_failed_query("fusion");
+        detector.record_answer_confidence("fusion", 0.2);
+        detector.record_answer_confidence("fusion", 0.9);
+
+        let frequent = detector.frequent_gaps(2);
+        assert_eq!(frequent.len(), 2);
+        assert_eq!(detector.get("reykjavik").unwrap().channel(), LearningChannel::AskUser);
+        assert_eq!(detector.get("fusion").unwrap().channel(), LearningChannel::Crawl);
+        assert_eq!(detector.get("fusion").unwrap().occurrences(), 2);
+
+        detector.resolve("Fusion");
+        assert_eq!(detector.frequent_gaps(1).len(), 1);
+    }
+}
@@ -13,6 +13,12 @@
 //   Core Functions:
 //       • Re-export ontology, reasoning, and epistemic modules
 //       • Provide unified access to structured knowledge and inference engines
+//       • Track knowledge gaps that should become learning goals
+//       • Share fact storage between ontology versions
+//       • Give readers immutable snapshots while writers continue
+//       • Embed passages of text and index them for similarity search
+//       • Export the ontology graph to Neo4j as Cypher, incrementally
+//       • Mirror facts into SQLite tables for SQL analysis (feature sqlite)
 //       • Integrate Bayesian, fuzzy, and hybrid epistemic reasoning pipelines
 //       • Serve as the foundation for querying, updating, and interpreting
 //         Astra’s evolving knowledge base
@@ -20,7 +26,7 @@
 //   File:        /src/knowledge/mod.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-23
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
@@ -29,17 +35,36 @@
 
 mod tests;
 
+pub mod error;
 pub mod extended_ontology;
+pub mod fact_log;
+pub mod domains;
+pub mod change_feed;
+pub mod cypher_export;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_mirror;
+pub mod snapshots;
 pub mod epistemic_reasoner;
+pub mod belief_ledger;
+pub mod knowledge_gaps;
+pub mod source_reliability;
 pub mod advanced_epistemic;
 pub mod bayesian_reasoner;
 pub mod fuzzy_reasoner;
+pub mod embedding;
+pub mod vector_store;
 
 pub mod query;
 pub mod query_executor;
 
 pub mod ontology;
 pub mod reasoner;
+pub mod hierarchy;
+pub mod bulk_import;
+pub mod concurrent;
+pub mod schema;
+pub mod storage;
 
 pub use ontology::{Ontology, Id, Concept, Entity, AttributeType, AttributeValue};
 pub use reasoner::Reasoner;
+pub use error::KnowledgeError;
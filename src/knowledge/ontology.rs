@@ -11,25 +11,42 @@
 //
 //   Core Functions:
 //       • Define concepts, entities, attributes, and relationship types
+//       • Validate entity attributes against concept schemas on insert
+//       • Attach weight, timing, confidence & provenance to relationship edges
 //       • Maintain indexed lookup tables for fast attribute‑based queries
+//       • Update and remove entities and relationships, keeping indexes in step
+//       • Remember removed ids as tombstones that persist with the state
+//       • Refuse relationships whose ends are missing or removed
 //       • Track graph adjacency for relationship traversal and reasoning
-//       • Provide persistent storage support for ontology state
+//       • Index entities by concept for hierarchy-aware queries
+//       • Provide persistent storage support for ontology state, checksummed
+//         and falling back to the previous save if the latest is corrupt; only
+//         an intact save is kept as the previous one
+//       • Version saved state so states from earlier releases migrate on load
 //       • Serve as the primary data model for higher‑level reasoning modules
 //
 //   File:        /src/knowledge/ontology.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-23
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
+use crate::integrity::{open, recover, seal, CorruptionReport, IntegrityError};
+use crate::knowledge::error::KnowledgeError;
+use crate::knowledge::schema::SchemaError;
 use crate::knowledge::storage::{Storage, SledStorage};
-use anyhow::Result;
+use crate::migration::{self, Versioned};
 use serde::{Serialize, Deserialize};
 use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Storage keys of the latest saved state and the one saved before it.
+const STATE_KEY: &str = "ontology_state";
+const PREVIOUS_STATE_KEY: &str = "ontology_state.previous";
 
 pub type Id = usize;
 
@@ -39,6 +56,9 @@ pub struct Concept {
     pub name: String,
     pub parent_ids: HashSet<Id>,
     pub attributes: HashMap<String, AttributeType>,
+    /// Attributes every entity of this concept (or a subconcept) must supply
+    #[serde(default)]
+    pub required_attributes: HashSet<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -76,40 +96,80 @@ pub enum RelationshipType {
     Custom(String),
 }
 
+/// Optional properties attached to a relationship edge.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RelationshipProperties {
+    /// Strength of the relationship (e.g., 0.0 weak to 1.0 strong)
+    pub weight: Option<f64>,
+    /// Unix timestamp from which the relationship holds
+    pub since: Option<u64>,
+    /// Confidence that the relationship holds (0.0 to 1.0)
+    pub confidence: Option<f32>,
+    /// Source the relationship was learned from
+    pub provenance: Option<String>,
+}
+
+impl RelationshipProperties {
+    /// Creates properties with only a weight set
+    pub fn weighted(weight: f64) -> Self {
+        RelationshipProperties { weight: Some(weight), ..Default::default() }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Relationship {
     pub id: Id,
     pub from_entity: Id,
     pub to_entity: Id,
     pub rel_type: RelationshipType,
+    #[serde(default)]
+    pub properties: RelationshipProperties,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Ontology<S: Storage> {
-    next_id: Id,
+    pub(crate) next_id: Id,
 
-    concepts: HashMap<Id, Concept>,
+    pub(crate) concepts: HashMap<Id, Concept>,
     concepts_by_name: HashMap<String, Id>,
 
-    entities: HashMap<Id, Entity>,
+    pub(crate) entities: HashMap<Id, Entity>,
 
-    relationships: HashMap<Id, Relationship>,
+    pub(crate) relationships: HashMap<Id, Relationship>,
 
     // --- NEW INDEXES ---
 
     // Map attribute name -> attribute value -> set of entity IDs
-    attribute_index: HashMap<String, HashMap<AttributeValue, HashSet<Id>>>,
+    pub(crate) attribute_index: HashMap<String, HashMap<AttributeValue, HashSet<Id>>>,
 
     // Map from_entity -> rel_type -> set of relationship IDs
-    relationship_index: HashMap<Id, HashMap<RelationshipType, HashSet<Id>>>,
+    pub(crate) relationship_index: HashMap<Id, HashMap<RelationshipType, HashSet<Id>>>,
+
+    // Map rel_type -> set of relationship IDs, for graph-wide relationship queries
+    #[serde(default)]
+    pub(crate) relationship_type_index: HashMap<RelationshipType, HashSet<Id>>,
 
     // Adjacency list: entity -> neighbors (to_entity)
-    adjacency_list: HashMap<Id, HashSet<Id>>,
+    pub(crate) adjacency_list: HashMap<Id, HashSet<Id>>,
+
+    // Map concept ID -> set of entity IDs directly instantiating it
+    #[serde(default)]
+    pub(crate) concept_index: HashMap<Id, HashSet<Id>>,
+
+    // IDs of removed entities and relationships, persisted so that a reloaded
+    // ontology still tells removed IDs apart from ones it never had
+    #[serde(default)]
+    tombstones: HashSet<Id>,
 
     // Storage backend for persistence
     storage: S,
 }
 
+impl<S: Storage> Versioned for Ontology<S> {
+    const FORMAT: &'static str = "astra.ontology";
+    const VERSION: u32 = 1;
+}
+
 impl<S: Storage> Ontology<S> {
     /// Creates a new empty ontology with the given storage backend
     pub fn new(storage: S) -> Self {
@@ -121,13 +181,27 @@ impl<S: Storage> Ontology<S> {
             relationships: HashMap::new(),
             attribute_index: HashMap::new(),
             relationship_index: HashMap::new(),
+            relationship_type_index: HashMap::new(),
             adjacency_list: HashMap::new(),
+            concept_index: HashMap::new(),
+            tombstones: HashSet::new(),
             storage,
         }
     }
 
     /// Adds a new concept with optional parents and attributes
     pub fn add_concept(&mut self, name: &str, parents: &[Id], attributes: HashMap<String, AttributeType>) -> Id {
+        self.add_concept_with_required(name, parents, attributes, &[])
+    }
+
+    /// Adds a new concept whose entities must supply the given attributes
+    pub fn add_concept_with_required(
+        &mut self,
+        name: &str,
+        parents: &[Id],
+        attributes: HashMap<String, AttributeType>,
+        required: &[&str],
+    ) -> Id {
         let id = self.next_id;
         self.next_id += 1;
 
@@ -136,6 +210,7 @@ impl<S: Storage> Ontology<S> {
             name: name.to_string(),
             parent_ids: parents.iter().cloned().collect(),
             attributes,
+            required_attributes: required.iter().map(|name| name.to_string()).collect(),
         };
 
         self.concepts_by_name.insert(name.to_string(), id);
@@ -144,8 +219,15 @@ impl<S: Storage> Ontology<S> {
         id
     }
 
-    /// Adds a new entity of a concept with attribute values
-    pub fn add_entity(&mut self, concept_id: Id, attribute_values: HashMap<String, AttributeValue>) -> Id {
+    /// Adds a new entity of a concept with attribute values.
+    /// Values are validated against the concept's schema first.
+    pub fn add_entity(
+        &mut self,
+        concept_id: Id,
+        attribute_values: HashMap<String, AttributeValue>,
+    ) -> Result<Id, SchemaError> {
+        self.validate_entity(concept_id, &attribute_values)?;
+
         let id = self.next_id;
         self.next_id += 1;
 
@@ -157,6 +239,9 @@ impl<S: Storage> Ontology<S> {
 
         self.entities.insert(id, entity);
 
+        // Update concept index
+        self.concept_index.entry(concept_id).or_default().insert(id);
+
         // Update attribute index
         for (attr_name, attr_value) in attribute_values.into_iter() {
             self.attribute_index
@@ -167,11 +252,25 @@ impl<S: Storage> Ontology<S> {
                 .insert(id);
         }
 
-        id
+        Ok(id)
     }
 
     /// Adds a typed relationship between two entities
-    pub fn add_relationship(&mut self, from_entity: Id, to_entity: Id, rel_type: RelationshipType) -> Id {
+    pub fn add_relationship(&mut self, from_entity: Id, to_entity: Id, rel_type: RelationshipType) -> Result<Id, KnowledgeError> {
+        self.add_relationship_with_properties(from_entity, to_entity, rel_type, RelationshipProperties::default())
+    }
+
+    /// Adds a typed relationship carrying weight, timing, confidence or provenance properties.
+    /// Both ends must be entities that exist and have not been removed.
+    pub fn add_relationship_with_properties(
+        &mut self,
+        from_entity: Id,
+        to_entity: Id,
+        rel_type: RelationshipType,
+        properties: RelationshipProperties,
+    ) -> Result<Id, KnowledgeError> {
+        self.live_entity(from_entity)?;
+        self.live_entity(to_entity)?;
         let id = self.next_id;
         self.next_id += 1;
 
@@ -180,6 +279,7 @@ impl<S: Storage> Ontology<S> {
             from_entity,
             to_entity,
             rel_type: rel_type.clone(),
+            properties,
         };
 
         self.relationships.insert(id, relationship);
@@ -188,14 +288,124 @@ impl<S: Storage> Ontology<S> {
         self.relationship_index
             .entry(from_entity)
             .or_default()
-            .entry(rel_type)
+            .entry(rel_type.clone())
             .or_default()
             .insert(id);
 
+        // Update relationship type index
+        self.relationship_type_index.entry(rel_type).or_default().insert(id);
+
         // Update adjacency list
         self.adjacency_list.entry(from_entity).or_default().insert(to_entity);
 
-        id
+        Ok(id)
+    }
+
+    /// Replaces an entity's attribute values, validated against its concept's
+    /// schema. Only attributes whose values changed are reindexed.
+    pub fn update_entity(
+        &mut self,
+        id: Id,
+        attribute_values: HashMap<String, AttributeValue>,
+    ) -> Result<(), KnowledgeError> {
+        let concept_id = self.live_entity(id)?.concept_id;
+        self.validate_entity(concept_id, &attribute_values)?;
+
+        let entity = self.entities.get_mut(&id).expect("entity checked above");
+        let old_values = std::mem::replace(&mut entity.attribute_values, attribute_values);
+
+        for (attr_name, attr_value) in &old_values {
+            if entity.attribute_values.get(attr_name) != Some(attr_value) {
+                unindex_attribute(&mut self.attribute_index, attr_name, attr_value, id);
+            }
+        }
+        for (attr_name, attr_value) in &entity.attribute_values {
+            if old_values.get(attr_name) != Some(attr_value) {
+                self.attribute_index
+                    .entry(attr_name.clone())
+                    .or_default()
+                    .entry(attr_value.clone())
+                    .or_default()
+                    .insert(id);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes an entity together with every relationship from or to it,
+    /// leaving a tombstone for its ID. Finding incoming relationships scans
+    /// all relationships, since only outgoing ones are indexed.
+    pub fn remove_entity(&mut self, id: Id) -> Result<Entity, KnowledgeError> {
+        self.live_entity(id)?;
+
+        let mut attached: Vec<Id> = self
+            .relationships
+            .values()
+            .filter(|rel| rel.from_entity == id || rel.to_entity == id)
+            .map(|rel| rel.id)
+            .collect();
+        attached.sort_unstable();
+        for rel_id in attached {
+            self.remove_relationship(rel_id)?;
+        }
+
+        let entity = self.entities.remove(&id).expect("entity checked above");
+        remove_from_index(&mut self.concept_index, &entity.concept_id, id);
+        for (attr_name, attr_value) in &entity.attribute_values {
+            unindex_attribute(&mut self.attribute_index, attr_name, attr_value, id);
+        }
+        self.tombstones.insert(id);
+
+        Ok(entity)
+    }
+
+    /// Removes a relationship, leaving a tombstone for its ID. The two
+    /// entities stay adjacent if another relationship still connects them.
+    pub fn remove_relationship(&mut self, id: Id) -> Result<Relationship, KnowledgeError> {
+        let Some(rel) = self.relationships.remove(&id) else {
+            return Err(if self.tombstones.contains(&id) {
+                KnowledgeError::Removed(id)
+            } else {
+                KnowledgeError::RelationshipNotFound(id)
+            });
+        };
+
+        if let Some(rel_map) = self.relationship_index.get_mut(&rel.from_entity) {
+            remove_from_index(rel_map, &rel.rel_type, id);
+            if rel_map.is_empty() {
+                self.relationship_index.remove(&rel.from_entity);
+            }
+        }
+        remove_from_index(&mut self.relationship_type_index, &rel.rel_type, id);
+
+        let still_adjacent = self.relationship_index.get(&rel.from_entity).is_some_and(|rel_map| {
+            rel_map
+                .values()
+                .flatten()
+                .any(|other| self.relationships.get(other).is_some_and(|r| r.to_entity == rel.to_entity))
+        });
+        if !still_adjacent {
+            remove_from_index(&mut self.adjacency_list, &rel.from_entity, rel.to_entity);
+        }
+        self.tombstones.insert(id);
+
+        Ok(rel)
+    }
+
+    /// Whether `id` belonged to an entity or relationship that was removed
+    pub fn is_removed(&self, id: Id) -> bool {
+        self.tombstones.contains(&id)
+    }
+
+    fn live_entity(&self, id: Id) -> Result<&Entity, KnowledgeError> {
+        self.entities.get(&id).ok_or_else(|| {
+            if self.tombstones.contains(&id) {
+                KnowledgeError::Removed(id)
+            } else {
+                KnowledgeError::EntityNotFound(id)
+            }
+        })
     }
 
     /// Efficient lookup for entities by attribute value using index
@@ -245,23 +455,74 @@ impl<S: Storage> Ontology<S> {
         self.concepts.get(&id)
     }
 
+    /// Retrieve a concept ID by name
+    pub fn get_concept_id(&self, name: &str) -> Option<Id> {
+        self.concepts_by_name.get(name).copied()
+    }
+
     /// Retrieve an entity by ID
     pub fn get_entity(&self, id: Id) -> Option<&Entity> {
         self.entities.get(&id)
     }
 
-    /// Save the ontology state to storage as JSON
-    pub fn save_to_storage(&self) -> Result<()> {
-        let json = serde_json::to_string_pretty(self)?;
-        self.storage.save("ontology_state", json.as_bytes())
+    /// Retrieve a relationship by ID
+    pub fn get_relationship(&self, id: Id) -> Option<&Relationship> {
+        self.relationships.get(&id)
     }
 
-    /// Load the ontology state from storage
-    pub fn load_from_storage(&mut self) -> Result<()> {
-        if let Some(data) = self.storage.load("ontology_state")? {
-            let loaded: Ontology<S> = serde_json::from_slice(&data)?;
-            *self = loaded;
+    /// Save the ontology state to storage as versioned JSON sealed with a
+    /// checksum, keeping the previously saved state if it is intact, so a
+    /// corrupt state never displaces the good one before it
+    pub fn save_to_storage(&self) -> Result<(), KnowledgeError> {
+        let json = migration::encode(self)?;
+        if let Some(previous) = self.storage.load(STATE_KEY)? {
+            if open(&previous, migration::decode::<Ontology<S>>).is_ok() {
+                self.storage.save(PREVIOUS_STATE_KEY, &previous)?;
+            }
+        }
+        self.storage.save(STATE_KEY, &seal(&json))
+    }
+
+    /// Load the ontology state from storage, migrating it if an earlier
+    /// release saved it, or the previously saved state if the latest is
+    /// corrupt. Returns the states passed over.
+    pub fn load_from_storage(&mut self) -> Result<Vec<CorruptionReport>, KnowledgeError> {
+        let mut candidates = Vec::new();
+        for key in [STATE_KEY, PREVIOUS_STATE_KEY] {
+            candidates.push((key.to_string(), Ok(self.storage.load(key)?)));
+        }
+        match recover(candidates, migration::decode::<Ontology<S>>) {
+            Ok(Some(recovered)) => {
+                recovered.warn_skipped();
+                *self = recovered.value;
+                Ok(recovered.skipped)
+            }
+            Ok(None) => Ok(Vec::new()),
+            Err(reports) => Err(IntegrityError::NoIntactSnapshot(reports).into()),
+        }
+    }
+}
+
+/// Removes `id` from the set under `key`, dropping the set once empty
+fn remove_from_index<K: Hash + Eq>(index: &mut HashMap<K, HashSet<Id>>, key: &K, id: Id) {
+    if let Some(ids) = index.get_mut(key) {
+        ids.remove(&id);
+        if ids.is_empty() {
+            index.remove(key);
+        }
+    }
+}
+
+fn unindex_attribute(
+    index: &mut HashMap<String, HashMap<AttributeValue, HashSet<Id>>>,
+    attr_name: &str,
+    attr_value: &AttributeValue,
+    id: Id,
+) {
+    if let Some(val_map) = index.get_mut(attr_name) {
+        remove_from_index(val_map, attr_value, id);
+        if val_map.is_empty() {
+            index.remove(attr_name);
         }
-        Ok(())
     }
 }
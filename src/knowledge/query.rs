@@ -13,30 +13,35 @@
 //       • Define logical, comparison, and attribute‑based query primitives
 //       • Support nested AND/OR/NOT expressions for complex filtering
 //       • Enable concept‑based and attribute‑based entity selection
+//       • Filter on relationship weight, age, confidence and provenance
 //       • Serve as the query representation consumed by the Query Executor
 //
 //   File:        /src/knowledge/query.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-25
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
+use crate::knowledge::ontology::RelationshipType;
 use crate::knowledge::{AttributeValue, Id};
 
 /// Logical operators for composing queries
 #[derive(Debug, Clone)]
 pub enum LogicalOp {
+    /// All sub-expressions match; true of every entity when there are none
     And,
+    /// At least one sub-expression matches
     Or,
+    /// None of the sub-expressions match
     Not,
 }
 
 /// Comparison operators for attribute filters
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ComparisonOp {
     Eq,
     Neq,
@@ -47,17 +52,45 @@ pub enum ComparisonOp {
 }
 
 /// Represents a basic attribute filter condition
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AttributeFilter {
     pub attr_name: String,
     pub op: ComparisonOp,
     pub value: AttributeValue,
 }
 
+/// Relationship properties that can be filtered on
+#[derive(Debug, Clone)]
+pub enum RelationshipProperty {
+    /// Compared against `AttributeValue::Float`
+    Weight,
+    /// Unix timestamp, compared against `AttributeValue::Integer`
+    Since,
+    /// Compared against `AttributeValue::Float`
+    Confidence,
+    /// Compared against `AttributeValue::String`
+    Provenance,
+}
+
+/// Represents a filter condition on a relationship property.
+/// Relationships lacking the property never match.
+#[derive(Debug, Clone)]
+pub struct RelationshipFilter {
+    pub property: RelationshipProperty,
+    pub op: ComparisonOp,
+    pub value: AttributeValue,
+}
+
+impl RelationshipFilter {
+    pub fn new(property: RelationshipProperty, op: ComparisonOp, value: AttributeValue) -> Self {
+        RelationshipFilter { property, op, value }
+    }
+}
+
 /// Represents a query expression node
 #[derive(Debug, Clone)]
 pub enum QueryExpr {
-    /// Match entities having a specific concept (by ID)
+    /// Match entities of a specific concept (by ID) or any of its subconcepts
     Concept(Id),
 
     /// Filter entities by attribute condition
@@ -71,6 +104,15 @@ pub enum QueryExpr {
 
     /// Negation of a sub-expression
     Not(Box<QueryExpr>),
+
+    /// Match entities with an outgoing relationship of the given type (any
+    /// type if `None`) whose properties satisfy all filters, optionally
+    /// requiring the related entity to match `target`
+    Related {
+        rel_type: Option<RelationshipType>,
+        filters: Vec<RelationshipFilter>,
+        target: Option<Box<QueryExpr>>,
+    },
 }
 
 impl QueryExpr {
@@ -88,4 +130,9 @@ impl QueryExpr {
     pub fn not(expr: QueryExpr) -> QueryExpr {
         QueryExpr::Not(Box::new(expr))
     }
+
+    /// Convenience constructor for a relationship query without a target constraint
+    pub fn related(rel_type: RelationshipType, filters: Vec<RelationshipFilter>) -> QueryExpr {
+        QueryExpr::Related { rel_type: Some(rel_type), filters, target: None }
+    }
 }
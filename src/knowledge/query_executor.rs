@@ -10,16 +10,19 @@
 //       backbone of Astra’s semantic search and structured knowledge retrieval.
 //
 //   Core Functions:
-//       • Execute QueryExpr trees (Concept, AttrFilter, Logical, Not)
+//       • Execute QueryExpr trees (Concept, AttrFilter, Logical, Not, Related)
+//       • Filter relationships on their properties via the type index
 //       • Support AND/OR/NOT logical composition across sub‑queries
 //       • Perform attribute‑level comparisons with typed operators
 //       • Integrate with ontology indexes for efficient entity filtering
 //       • Provide the evaluation layer consumed by higher‑level reasoning
+//       • Compare integers and floats numerically and return results in ID order
+//       • Evaluate batches of queries in parallel, sharing their index lookups
 //
 //   File:        /src/knowledge/query_executor.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-25
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
@@ -27,56 +30,183 @@
 // ============================================================================
 
 use crate::knowledge::{Ontology, QueryExpr, LogicalOp, ComparisonOp, AttributeFilter, AttributeValue, Id};
+use crate::knowledge::ontology::{Relationship, RelationshipType};
+use crate::knowledge::query::{RelationshipFilter, RelationshipProperty};
+use crate::knowledge::storage::Storage;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
 
-impl Ontology {
+/// A concept or attribute index lookup, the leaves of a query tree
+#[derive(PartialEq)]
+enum Lookup<'q> {
+    Concept(Id),
+    Filter(&'q AttributeFilter),
+}
+
+/// Lookup results computed once for a whole batch of queries
+#[derive(Default)]
+struct SharedLookups<'q> {
+    concepts: HashMap<Id, HashSet<Id>>,
+    filters: Vec<(&'q AttributeFilter, HashSet<Id>)>,
+}
+
+/// Adds the lookups in `expr` not already in `lookups`
+fn collect_lookups<'q>(expr: &'q QueryExpr, lookups: &mut Vec<Lookup<'q>>) {
+    let lookup = match expr {
+        QueryExpr::Concept(concept_id) => Lookup::Concept(*concept_id),
+        QueryExpr::AttrFilter(filter) => Lookup::Filter(filter),
+        QueryExpr::Logical { exprs, .. } => {
+            for sub_expr in exprs {
+                collect_lookups(sub_expr, lookups);
+            }
+            return;
+        }
+        QueryExpr::Not(sub_expr) => return collect_lookups(sub_expr, lookups),
+        QueryExpr::Related { target, .. } => {
+            if let Some(target) = target {
+                collect_lookups(target, lookups);
+            }
+            return;
+        }
+    };
+    if !lookups.contains(&lookup) {
+        lookups.push(lookup);
+    }
+}
+
+impl<S: Storage> Ontology<S> {
     /// Evaluate a QueryExpr against the ontology, returning matching entities
+    /// in ascending ID order
     pub fn query(&self, expr: &QueryExpr) -> Vec<&crate::knowledge::Entity> {
-        match expr {
-            QueryExpr::Concept(concept_id) => {
-                self.find_entities_by_concept(*concept_id)
-            }
-            QueryExpr::AttrFilter(filter) => {
-                self.find_entities_by_attribute_filter(filter)
+        self.sorted_entities(self.matching_ids(expr, &SharedLookups::default()))
+    }
+
+    /// Evaluates many queries at once, returning each one's matches as
+    /// `query` would, in the order the queries were given. Concept and
+    /// attribute lookups that several queries contain are done only once,
+    /// and lookups and queries alike are spread over the rayon thread pool.
+    pub fn query_batch(&self, exprs: &[QueryExpr]) -> Vec<Vec<&crate::knowledge::Entity>>
+    where
+        S: Sync,
+    {
+        let mut lookups = Vec::new();
+        for expr in exprs {
+            collect_lookups(expr, &mut lookups);
+        }
+        let results: Vec<HashSet<Id>> = lookups
+            .par_iter()
+            .map(|lookup| match lookup {
+                Lookup::Concept(concept_id) => self.concept_ids(*concept_id),
+                Lookup::Filter(filter) => self.filter_ids(filter),
+            })
+            .collect();
+
+        let mut shared = SharedLookups::default();
+        for (lookup, ids) in lookups.into_iter().zip(results) {
+            match lookup {
+                Lookup::Concept(concept_id) => {
+                    shared.concepts.insert(concept_id, ids);
+                }
+                Lookup::Filter(filter) => shared.filters.push((filter, ids)),
             }
+        }
+        exprs.par_iter().map(|expr| self.sorted_entities(self.matching_ids(expr, &shared))).collect()
+    }
+
+    fn sorted_entities(&self, ids: HashSet<Id>) -> Vec<&crate::knowledge::Entity> {
+        let mut ids: Vec<Id> = ids.into_iter().collect();
+        ids.sort_unstable();
+        ids.into_iter().filter_map(|id| self.entities.get(&id)).collect()
+    }
+
+    fn concept_ids(&self, concept_id: Id) -> HashSet<Id> {
+        self.find_entities_by_concept(concept_id).into_iter().map(|e| e.id).collect()
+    }
+
+    fn filter_ids(&self, filter: &AttributeFilter) -> HashSet<Id> {
+        self.find_entities_by_attribute_filter(filter).into_iter().map(|e| e.id).collect()
+    }
+
+    /// IDs of the entities matching an expression, taking lookups from
+    /// `shared` where it has them. AND of no expressions matches every
+    /// entity, OR of none matches nothing, and the `Not` operator matches
+    /// entities that satisfy none of its expressions.
+    fn matching_ids(&self, expr: &QueryExpr, shared: &SharedLookups) -> HashSet<Id> {
+        match expr {
+            QueryExpr::Concept(concept_id) => match shared.concepts.get(concept_id) {
+                Some(ids) => ids.clone(),
+                None => self.concept_ids(*concept_id),
+            },
+            QueryExpr::AttrFilter(filter) => match shared.filters.iter().find(|(f, _)| *f == filter) {
+                Some((_, ids)) => ids.clone(),
+                None => self.filter_ids(filter),
+            },
             QueryExpr::Logical { op, exprs } => {
-                let mut sets: Vec<Vec<&crate::knowledge::Entity>> = exprs.iter().map(|e| self.query(e)).collect();
+                let mut sets = exprs.iter().map(|e| self.matching_ids(e, shared));
                 match op {
-                    LogicalOp::And => {
+                    LogicalOp::And => match sets.next() {
                         // Intersection of all result sets
-                        if sets.is_empty() {
-                            vec![]
-                        } else {
-                            sets.iter().skip(1).fold(sets[0].clone(), |acc, s| {
-                                acc.into_iter().filter(|e| s.contains(e)).collect()
-                            })
-                        }
-                    }
-                    LogicalOp::Or => {
-                        // Union of all result sets
-                        let mut union = Vec::new();
-                        for s in sets {
-                            for e in s {
-                                if !union.contains(&e) {
-                                    union.push(e);
-                                }
-                            }
-                        }
-                        union
-                    }
+                        Some(first) => sets.fold(first, |acc, s| acc.intersection(&s).copied().collect()),
+                        None => self.entities.keys().copied().collect(),
+                    },
+                    // Union of all result sets
+                    LogicalOp::Or => sets.flatten().collect(),
                     LogicalOp::Not => {
-                        // Not supported here - use QueryExpr::Not variant
-                        vec![]
+                        let any: HashSet<Id> = sets.flatten().collect();
+                        self.entities.keys().copied().filter(|id| !any.contains(id)).collect()
                     }
                 }
             }
             QueryExpr::Not(sub_expr) => {
-                let all_entities: Vec<&crate::knowledge::Entity> = self.entities.values().collect();
-                let sub_results = self.query(sub_expr);
-                all_entities.into_iter().filter(|e| !sub_results.contains(e)).collect()
+                let sub_results = self.matching_ids(sub_expr, shared);
+                self.entities.keys().copied().filter(|id| !sub_results.contains(id)).collect()
+            }
+            QueryExpr::Related { rel_type, filters, target } => {
+                let target_ids: Option<HashSet<Id>> = target.as_ref().map(|t| self.matching_ids(t, shared));
+                self.query_relationships(rel_type.as_ref(), filters)
+                    .into_iter()
+                    .filter(|rel| target_ids.as_ref().map_or(true, |ids| ids.contains(&rel.to_entity)))
+                    .map(|rel| rel.from_entity)
+                    .filter(|id| self.entities.contains_key(id))
+                    .collect()
             }
         }
     }
 
+    /// Returns relationships of the given type (any type if `None`) whose
+    /// properties satisfy all filters
+    pub fn query_relationships(&self, rel_type: Option<&RelationshipType>, filters: &[RelationshipFilter]) -> Vec<&Relationship> {
+        let candidates: Vec<&Relationship> = match rel_type {
+            Some(rel_type) => self
+                .relationship_type_index
+                .get(rel_type)
+                .map(|ids| ids.iter().filter_map(|id| self.relationships.get(id)).collect())
+                .unwrap_or_default(),
+            None => self.relationships.values().collect(),
+        };
+
+        candidates
+            .into_iter()
+            .filter(|rel| {
+                filters.iter().all(|filter| {
+                    Self::relationship_property_value(rel, &filter.property)
+                        .map_or(false, |val| Self::compare_attribute_values(&val, &filter.op, &filter.value))
+                })
+            })
+            .collect()
+    }
+
+    /// Reads a relationship property as an attribute value for comparison
+    fn relationship_property_value(rel: &Relationship, property: &RelationshipProperty) -> Option<AttributeValue> {
+        let props = &rel.properties;
+        match property {
+            RelationshipProperty::Weight => props.weight.map(AttributeValue::Float),
+            RelationshipProperty::Since => props.since.map(|ts| AttributeValue::Integer(ts as i64)),
+            RelationshipProperty::Confidence => props.confidence.map(|c| AttributeValue::Float(c as f64)),
+            RelationshipProperty::Provenance => props.provenance.clone().map(AttributeValue::String),
+        }
+    }
+
     /// Helper method to filter entities by attribute filter condition
     fn find_entities_by_attribute_filter(&self, filter: &AttributeFilter) -> Vec<&crate::knowledge::Entity> {
         self.entities.values().filter(|entity| {
@@ -88,15 +218,19 @@ impl Ontology {
         }).collect()
     }
 
-    /// Compares two attribute values with a comparison operator
+    /// Compares two attribute values with a comparison operator. Integers
+    /// and floats compare numerically; any other pair of different types
+    /// never matches, not even under `Neq`.
     fn compare_attribute_values(val: &AttributeValue, op: &ComparisonOp, cmp_val: &AttributeValue) -> bool {
         use AttributeValue::*;
         match (val, cmp_val) {
             (Integer(a), Integer(b)) => Self::compare_ord(*a, *b, op),
             (Float(a), Float(b)) => Self::compare_ord(*a, *b, op),
+            (Integer(a), Float(b)) => Self::compare_ord(*a as f64, *b, op),
+            (Float(a), Integer(b)) => Self::compare_ord(*a, *b as f64, op),
             (String(a), String(b)) => Self::compare_ord(a, b, op),
             (Boolean(a), Boolean(b)) => Self::compare_ord(a, b, op),
-            // For Reference and mismatched types, only equality check
+            // References only support equality checks
             (Reference(a), Reference(b)) => match op {
                 ComparisonOp::Eq => a == b,
                 ComparisonOp::Neq => a != b,
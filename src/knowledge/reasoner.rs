@@ -17,7 +17,7 @@
 //   File:        /src/knowledge/reasoner.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-23
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
@@ -44,19 +44,10 @@ impl<'a> Reasoner<'a> {
         }
     }
 
-    /// Check if concept_a is concept_b or a descendant (subclass) of concept_b
+    /// Check if concept_a is concept_b or a descendant (subclass) of concept_b.
+    /// Delegates to the ontology's cycle-safe hierarchy traversal.
     pub fn is_concept_or_subconcept(&self, concept_a: Id, concept_b: Id) -> bool {
-        if concept_a == concept_b {
-            return true;
-        }
-        if let Some(concept) = self.ontology.get_concept(concept_a) {
-            for parent_id in &concept.parent_ids {
-                if self.is_concept_or_subconcept(*parent_id, concept_b) {
-                    return true;
-                }
-            }
-        }
-        false
+        self.ontology.is_subconcept_of(concept_a, concept_b)
     }
 
     // Additional reasoning methods can be added here
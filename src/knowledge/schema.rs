@@ -0,0 +1,159 @@
+// ============================================================================
+//                     ASTRA AGI • ONTOLOGY SCHEMA VALIDATION
+//        Type Checking of Entity Attributes against Concept Declarations
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Knowledge Layer that keeps entities honest with
+//       respect to the concepts they instantiate. Attribute values are
+//       checked against the declared `AttributeType`s (including those
+//       inherited from ancestor concepts), required attributes must be
+//       present, and references must point at existing entities of the
+//       declared concept.
+//
+//   Core Functions:
+//       • Validate entities as they are created
+//       • Re-validate the whole ontology on demand (e.g., after loading)
+//       • Report typed schema errors for missing, mistyped, or dangling values
+//
+//   File:        /src/knowledge/schema.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use crate::knowledge::storage::Storage;
+use crate::knowledge::{AttributeType, AttributeValue, Id, Ontology};
+use std::collections::{HashMap, HashSet, VecDeque};
+use thiserror::Error;
+
+/// A violation of a concept's attribute schema.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum SchemaError {
+    /// The entity's concept does not exist
+    #[error("Concept {concept_id} not found")]
+    UnknownConcept { concept_id: Id },
+    /// A required attribute was not supplied
+    #[error("Missing required attribute '{attribute}' for concept {concept_id}")]
+    MissingRequiredAttribute { concept_id: Id, attribute: String },
+    /// A value does not match the declared attribute type
+    #[error("Attribute '{attribute}' expects {expected:?} but got {found:?}")]
+    TypeMismatch { attribute: String, expected: AttributeType, found: AttributeValue },
+    /// A reference points at an entity that does not exist
+    #[error("Attribute '{attribute}' references missing entity {target}")]
+    DanglingReference { attribute: String, target: Id },
+    /// A reference points at an entity outside the declared concept
+    #[error("Attribute '{attribute}' references entity {target} which is not an instance of concept {expected_concept}")]
+    ReferenceConceptMismatch { attribute: String, target: Id, expected_concept: Id },
+}
+
+/// A schema error attributed to a stored entity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntitySchemaError {
+    pub entity_id: Id,
+    pub error: SchemaError,
+}
+
+impl<S: Storage> Ontology<S> {
+    /// Returns the required attributes of a concept, including those
+    /// required by its ancestors.
+    pub fn effective_required_attributes(&self, concept_id: Id) -> HashSet<String> {
+        let mut required = HashSet::new();
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from([concept_id]);
+
+        while let Some(current) = queue.pop_front() {
+            if !visited.insert(current) {
+                continue;
+            }
+            if let Some(concept) = self.concepts.get(&current) {
+                required.extend(concept.required_attributes.iter().cloned());
+                queue.extend(concept.parent_ids.iter().copied());
+            }
+        }
+
+        required
+    }
+
+    /// Checks attribute values against a concept's schema without storing anything.
+    /// Attributes not declared by the concept are allowed and left unchecked.
+    pub fn validate_entity(
+        &self,
+        concept_id: Id,
+        attribute_values: &HashMap<String, AttributeValue>,
+    ) -> Result<(), SchemaError> {
+        if !self.concepts.contains_key(&concept_id) {
+            return Err(SchemaError::UnknownConcept { concept_id });
+        }
+
+        let declared = self.effective_attributes(concept_id);
+
+        let mut required: Vec<String> = self.effective_required_attributes(concept_id).into_iter().collect();
+        required.sort();
+        if let Some(attribute) = required.into_iter().find(|name| !attribute_values.contains_key(name)) {
+            return Err(SchemaError::MissingRequiredAttribute { concept_id, attribute });
+        }
+
+        for (name, value) in attribute_values {
+            if let Some(expected) = declared.get(name) {
+                self.check_attribute_value(name, expected, value)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Re-validates every stored entity, returning all violations found.
+    /// Useful after loading state from storage, where nothing was checked.
+    pub fn validate_all(&self) -> Vec<EntitySchemaError> {
+        let mut entity_ids: Vec<Id> = self.entities.keys().copied().collect();
+        entity_ids.sort_unstable();
+
+        entity_ids
+            .into_iter()
+            .filter_map(|id| {
+                let entity = &self.entities[&id];
+                self.validate_entity(entity.concept_id, &entity.attribute_values)
+                    .err()
+                    .map(|error| EntitySchemaError { entity_id: id, error })
+            })
+            .collect()
+    }
+
+    fn check_attribute_value(&self, name: &str, expected: &AttributeType, value: &AttributeValue) -> Result<(), SchemaError> {
+        let matches = match (expected, value) {
+            (AttributeType::String, AttributeValue::String(_))
+            | (AttributeType::Integer, AttributeValue::Integer(_))
+            | (AttributeType::Float, AttributeValue::Float(_))
+            | (AttributeType::Boolean, AttributeValue::Boolean(_)) => true,
+            (AttributeType::Reference(expected_concept), AttributeValue::Reference(target)) => {
+                let entity = self.entities.get(target).ok_or_else(|| SchemaError::DanglingReference {
+                    attribute: name.to_string(),
+                    target: *target,
+                })?;
+                if !self.is_subconcept_of(entity.concept_id, *expected_concept) {
+                    return Err(SchemaError::ReferenceConceptMismatch {
+                        attribute: name.to_string(),
+                        target: *target,
+                        expected_concept: *expected_concept,
+                    });
+                }
+                true
+            }
+            _ => false,
+        };
+
+        if matches {
+            Ok(())
+        } else {
+            Err(SchemaError::TypeMismatch {
+                attribute: name.to_string(),
+                expected: expected.clone(),
+                found: value.clone(),
+            })
+        }
+    }
+}
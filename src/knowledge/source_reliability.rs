@@ -0,0 +1,119 @@
+// ============================================================================
+//                     ASTRA AGI • SOURCE RELIABILITY LEARNING
+//        Outcome-Driven Trust Scores for Knowledge Sources
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s epistemic layer that learns how far each
+//       knowledge source can be trusted. Every time a fact from a source is
+//       later confirmed or contradicted, the source's track record is
+//       updated. The resulting trust score is used as a prior when new facts
+//       from that source are revised into belief, so sources that keep being
+//       wrong gradually lose influence.
+//
+//   Core Functions:
+//       • Track confirmations and contradictions per source
+//       • Estimate trust as the mean of a Beta posterior
+//       • Supply trust priors to the Epistemic Reasoner
+//
+//   File:        /src/knowledge/source_reliability.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use std::collections::HashMap;
+
+/// Track record of a single source.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SourceRecord {
+    pub confirmations: u32,
+    pub contradictions: u32,
+}
+
+/// Learns per-source reliability from confirmed and contradicted facts.
+///
+/// Trust is the mean of a Beta(prior_alpha + confirmations,
+/// prior_beta + contradictions) distribution, so unseen sources start at the
+/// prior and a handful of outcomes cannot swing trust to an extreme.
+#[derive(Debug, Clone)]
+pub struct SourceReliability {
+    records: HashMap<String, SourceRecord>,
+    pub prior_alpha: f64,
+    pub prior_beta: f64,
+}
+
+impl SourceReliability {
+    /// Creates a model where unknown sources are trusted at 0.5.
+    pub fn new() -> Self {
+        Self::with_prior(2.0, 2.0)
+    }
+
+    /// Creates a model with an explicit Beta prior.
+    pub fn with_prior(prior_alpha: f64, prior_beta: f64) -> Self {
+        SourceReliability {
+            records: HashMap::new(),
+            prior_alpha: prior_alpha.max(f64::EPSILON),
+            prior_beta: prior_beta.max(f64::EPSILON),
+        }
+    }
+
+    /// Records that a fact from `source` was later confirmed.
+    pub fn record_confirmation(&mut self, source: &str) {
+        self.records.entry(source.to_string()).or_default().confirmations += 1;
+    }
+
+    /// Records that a fact from `source` was later contradicted.
+    pub fn record_contradiction(&mut self, source: &str) {
+        self.records.entry(source.to_string()).or_default().contradictions += 1;
+    }
+
+    /// Current trust in a source, in 0.0..=1.0.
+    pub fn trust(&self, source: &str) -> f64 {
+        let record = self.records.get(source).cloned().unwrap_or_default();
+        let alpha = self.prior_alpha + record.confirmations as f64;
+        let beta = self.prior_beta + record.contradictions as f64;
+        alpha / (alpha + beta)
+    }
+
+    pub fn record(&self, source: &str) -> Option<&SourceRecord> {
+        self.records.get(source)
+    }
+
+    /// Sources ordered from most to least trusted.
+    pub fn ranked_sources(&self) -> Vec<(String, f64)> {
+        let mut ranked: Vec<(String, f64)> =
+            self.records.keys().map(|source| (source.clone(), self.trust(source))).collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        ranked
+    }
+}
+
+impl Default for SourceReliability {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trust_moves_with_outcomes() {
+        let mut model = SourceReliability::new();
+        assert!((model.trust("unknown") - 0.5).abs() < 1e-9);
+
+        for _ in 0..6 {
+            model.record_confirmation("encyclopedia");
+            model.record_contradiction("forum");
+        }
+
+        assert!(model.trust("encyclopedia") > 0.75);
+        assert!(model.trust("forum") < 0.25);
+        assert_eq!(model.ranked_sources()[0].0, "encyclopedia");
+    }
+}
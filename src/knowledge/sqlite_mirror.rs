@@ -0,0 +1,362 @@
+// ============================================================================
+//                      ASTRA AGI • SQLITE KNOWLEDGE MIRROR
+//        Relational Copy of the Ontology for Ad-Hoc SQL Analysis
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Knowledge Layer that keeps a relational copy
+//       of the current ontology version in a SQLite database. Facts,
+//       the entities they mention, and the change feed's events become
+//       tables that can be queried with plain SQL or opened in any SQLite
+//       tool. The mirror follows the change feed from its stored cursor and
+//       copies the version afresh when the feed cannot catch it up. It also
+//       implements the Storage trait, so lightweight deployments can keep
+//       ontology state in the same file and leave sled out.
+//
+//   Core Functions:
+//       • Create and upgrade the mirror's schema through ordered migrations,
+//         refusing a schema newer than this build knows
+//       • Mirror facts, entities, and change events as tables
+//       • Sync incrementally from the cursor stored in the database
+//       • Serve as a key-value Storage backend
+//
+//   File:        /src/knowledge/sqlite_mirror.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use std::path::Path;
+
+use crate::knowledge::change_feed::{ChangeSequence, KnowledgeChange};
+use crate::knowledge::error::KnowledgeError;
+use crate::knowledge::extended_ontology::{name_entity_id, Fact, OntologyManager};
+use crate::knowledge::storage::Storage;
+
+/// Schema migrations, oldest first. Migration `n` takes the schema from
+/// version `n` to `n + 1`; released migrations are never edited, only
+/// followed by new ones.
+const MIGRATIONS: &[&str] = &[
+    // 1: facts of the mirrored version, the entities they mention, change
+    // events, and where the mirror is in the change feed. Entity ids are
+    // unsigned 64-bit, beyond SQLite's integers, so they are kept as text.
+    "CREATE TABLE entities (
+         id TEXT PRIMARY KEY,
+         name TEXT
+     );
+     CREATE TABLE facts (
+         fact_index INTEGER PRIMARY KEY,
+         subject TEXT NOT NULL REFERENCES entities(id),
+         predicate TEXT NOT NULL,
+         object TEXT NOT NULL,
+         object_id TEXT NOT NULL REFERENCES entities(id),
+         confidence REAL NOT NULL,
+         source TEXT NOT NULL,
+         learned_at INTEGER NOT NULL
+     );
+     CREATE TABLE events (
+         sequence INTEGER PRIMARY KEY,
+         kind TEXT NOT NULL,
+         version INTEGER NOT NULL,
+         fact_index INTEGER,
+         count INTEGER,
+         from_version INTEGER
+     );
+     CREATE TABLE mirror_state (
+         key TEXT PRIMARY KEY,
+         value INTEGER NOT NULL
+     );",
+    // 2: lookups by subject, predicate, and object, and key-value storage
+    "CREATE INDEX facts_by_subject ON facts(subject);
+     CREATE INDEX facts_by_predicate ON facts(predicate);
+     CREATE INDEX facts_by_object ON facts(object_id);
+     CREATE TABLE storage (
+         key TEXT PRIMARY KEY,
+         value BLOB NOT NULL
+     );",
+];
+
+/// What a sync wrote.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncReport {
+    /// Whether the facts were cleared and the version copied afresh
+    pub full: bool,
+    pub facts: usize,
+    pub events: usize,
+    /// Change feed position the mirror reached
+    pub cursor: ChangeSequence,
+}
+
+/// A SQLite database mirroring an ontology's current version.
+pub struct SqliteMirror {
+    conn: Connection,
+}
+
+impl SqliteMirror {
+    /// Opens or creates the mirror at `path`, bringing its schema up to date.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, KnowledgeError> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// A mirror that lives only as long as it does, for analysis sessions
+    /// and tests.
+    pub fn in_memory() -> Result<Self, KnowledgeError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self, KnowledgeError> {
+        let mut mirror = SqliteMirror { conn };
+        mirror.migrate()?;
+        Ok(mirror)
+    }
+
+    /// Applies the migrations the database has not had, each in its own
+    /// transaction. Returns how many were applied. A database a newer
+    /// release has migrated further is refused rather than written to.
+    pub fn migrate(&mut self) -> Result<usize, KnowledgeError> {
+        let applied = self.schema_version()?;
+        if applied > MIGRATIONS.len() {
+            return Err(KnowledgeError::MirrorSchemaTooNew { found: applied, supported: MIGRATIONS.len() });
+        }
+        for (index, migration) in MIGRATIONS.iter().enumerate().skip(applied) {
+            let tx = self.conn.transaction()?;
+            tx.execute_batch(migration)?;
+            tx.pragma_update(None, "user_version", index as i64 + 1)?;
+            tx.commit()?;
+        }
+        Ok(MIGRATIONS.len() - applied)
+    }
+
+    /// Number of migrations the database has had.
+    pub fn schema_version(&self) -> Result<usize, KnowledgeError> {
+        let version: i64 = self.conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        Ok(version as usize)
+    }
+
+    /// The connection, for running SQL against the mirror.
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Last change already mirrored.
+    pub fn cursor(&self) -> Result<ChangeSequence, KnowledgeError> {
+        Ok(self.state("cursor")?.unwrap_or(0))
+    }
+
+    /// Ontology version mirrored, if any.
+    pub fn version(&self) -> Result<Option<u64>, KnowledgeError> {
+        self.state("version")
+    }
+
+    /// Brings the mirror up to date with `manager`'s current version, in one
+    /// transaction. Changes since the stored cursor are replayed when the
+    /// change feed can catch the mirror up (see
+    /// `OntologyManager::changes_to_replay`); otherwise the facts are copied
+    /// afresh. Every change still in the feed is recorded as an event.
+    pub fn sync(&mut self, manager: &OntologyManager) -> Result<SyncReport, KnowledgeError> {
+        let cursor = self.cursor()?;
+        let latest = manager.latest_change();
+        let version = manager.current_version();
+        let replay = manager.changes_to_replay(cursor, self.version()?);
+        // A new feed numbers its changes from 1 again
+        let restarted = latest < cursor;
+
+        let tx = self.conn.transaction()?;
+        let mut report = SyncReport { full: replay.is_none(), facts: 0, events: 0, cursor: latest };
+        match replay {
+            None => {
+                tx.execute_batch("DELETE FROM facts; DELETE FROM entities;")?;
+                if restarted {
+                    tx.execute("DELETE FROM events", [])?;
+                }
+                for (index, fact) in manager.query_facts(None).into_iter().enumerate() {
+                    insert_fact(&tx, index, fact)?;
+                    report.facts += 1;
+                }
+            }
+            Some(changes) => {
+                for (_, change) in changes {
+                    if let KnowledgeChange::FactAdded { version: added, index, fact } = change {
+                        if *added == version {
+                            insert_fact(&tx, *index, fact)?;
+                            report.facts += 1;
+                        }
+                    }
+                }
+            }
+        }
+        for (sequence, change) in manager.changes_since(if restarted { 0 } else { cursor }) {
+            report.events += insert_event(&tx, sequence, change)?;
+        }
+        set_state(&tx, "cursor", latest)?;
+        set_state(&tx, "version", version)?;
+        tx.commit()?;
+        Ok(report)
+    }
+
+    fn state(&self, key: &str) -> Result<Option<u64>, KnowledgeError> {
+        let value: Option<i64> = self
+            .conn
+            .query_row("SELECT value FROM mirror_state WHERE key = ?1", [key], |row| row.get(0))
+            .optional()?;
+        Ok(value.map(|value| value as u64))
+    }
+}
+
+fn insert_fact(tx: &Transaction, index: usize, fact: &Fact) -> Result<(), KnowledgeError> {
+    let subject = fact.subject.to_string();
+    let object_id = name_entity_id(&fact.object).to_string();
+    tx.execute("INSERT OR IGNORE INTO entities (id) VALUES (?1)", [&subject])?;
+    tx.execute(
+        "INSERT INTO entities (id, name) VALUES (?1, ?2) ON CONFLICT(id) DO UPDATE SET name = excluded.name",
+        [&object_id, &fact.object],
+    )?;
+    tx.execute(
+        "INSERT OR REPLACE INTO facts
+             (fact_index, subject, predicate, object, object_id, confidence, source, learned_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            index as i64,
+            subject,
+            fact.predicate,
+            fact.object,
+            object_id,
+            f64::from(fact.confidence),
+            fact.provenance.source_name,
+            fact.provenance.timestamp.as_millis() as i64,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Records a change; returns 1 if it was new, 0 if already recorded.
+fn insert_event(tx: &Transaction, sequence: ChangeSequence, change: &KnowledgeChange) -> Result<usize, KnowledgeError> {
+    let (kind, version, fact_index, count, from_version) = match change {
+        KnowledgeChange::FactAdded { version, index, .. } => ("fact_added", *version, Some(*index as i64), None, None),
+        KnowledgeChange::FactsRetired { version, count } => {
+            ("facts_retired", *version, None, Some(*count as i64), None)
+        }
+        KnowledgeChange::VersionSwitched { from, to } => ("version_switched", *to, None, None, Some(*from as i64)),
+    };
+    let inserted = tx.execute(
+        "INSERT OR IGNORE INTO events (sequence, kind, version, fact_index, count, from_version)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![sequence as i64, kind, version as i64, fact_index, count, from_version],
+    )?;
+    Ok(inserted)
+}
+
+fn set_state(tx: &Transaction, key: &str, value: u64) -> Result<(), KnowledgeError> {
+    tx.execute(
+        "INSERT INTO mirror_state (key, value) VALUES (?1, ?2) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        params![key, value as i64],
+    )?;
+    Ok(())
+}
+
+impl Storage for SqliteMirror {
+    fn save(&self, key: &str, value: &[u8]) -> Result<(), KnowledgeError> {
+        self.conn.execute(
+            "INSERT INTO storage (key, value) VALUES (?1, ?2) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    fn load(&self, key: &str) -> Result<Option<Vec<u8>>, KnowledgeError> {
+        let value = self
+            .conn
+            .query_row("SELECT value FROM storage WHERE key = ?1", [key], |row| row.get(0))
+            .optional()?;
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::extended_ontology::Provenance;
+
+    fn fact(subject: &str, predicate: &str, object: &str, source: &str) -> Fact {
+        Fact {
+            subject: name_entity_id(subject),
+            predicate: predicate.to_string(),
+            object: object.to_string(),
+            confidence: 0.75,
+            provenance: Provenance::new(source, None),
+        }
+    }
+
+    fn count(mirror: &SqliteMirror, sql: &str) -> i64 {
+        mirror.connection().query_row(sql, [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn test_mirror_follows_the_change_feed() {
+        let dir = std::env::temp_dir().join(format!("astra_sqlite_mirror_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("mirror.db");
+        let _ = std::fs::remove_file(&path);
+
+        let mut manager = OntologyManager::new();
+        manager.add_fact(fact("Ana", "lives in", "Lisbon", "user:ana"));
+        manager.add_fact(fact("Lisbon", "is in", "Portugal", "atlas"));
+        let mut mirror = SqliteMirror::open(&path).unwrap();
+        assert_eq!(mirror.schema_version().unwrap(), MIGRATIONS.len());
+        let report = mirror.sync(&manager).unwrap();
+        assert_eq!((report.full, report.facts, report.events, report.cursor), (true, 2, 2, 2));
+        // Lisbon is both an object and a subject, so three entities
+        assert_eq!(count(&mirror, "SELECT COUNT(*) FROM entities"), 3);
+        let place: String = mirror
+            .connection()
+            .query_row(
+                "SELECT e.name FROM facts f JOIN entities e ON e.id = f.object_id WHERE f.predicate = 'is in'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(place, "Portugal");
+
+        // Reopening keeps the cursor and does not migrate again
+        drop(mirror);
+        let mut mirror = SqliteMirror::open(&path).unwrap();
+        assert_eq!(mirror.migrate().unwrap(), 0);
+        assert_eq!(mirror.cursor().unwrap(), 2);
+        manager.add_fact(fact("Ana", "likes", "tea", "user:ana"));
+        let report = mirror.sync(&manager).unwrap();
+        assert_eq!((report.full, report.facts, report.events), (false, 1, 1));
+
+        // Retiring facts renumbers the rest, so they are copied afresh
+        manager.forget_user("ana");
+        let report = mirror.sync(&manager).unwrap();
+        assert!(report.full);
+        assert_eq!(count(&mirror, "SELECT COUNT(*) FROM facts"), 1);
+        assert_eq!(count(&mirror, "SELECT COUNT(*) FROM events WHERE kind = 'facts_retired'"), 1);
+
+        // The same file holds ontology state for the Storage trait
+        mirror.save("ontology", b"state").unwrap();
+        assert_eq!(mirror.load("ontology").unwrap().as_deref(), Some(&b"state"[..]));
+        assert_eq!(mirror.load("missing").unwrap(), None);
+        drop(mirror);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_migrations_upgrade_an_older_schema() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATIONS[0]).unwrap();
+        conn.pragma_update(None, "user_version", 1).unwrap();
+        let mut mirror = SqliteMirror { conn };
+        assert_eq!(mirror.migrate().unwrap(), MIGRATIONS.len() - 1);
+        assert_eq!(mirror.schema_version().unwrap(), MIGRATIONS.len());
+        assert_eq!(count(&mirror, "SELECT COUNT(*) FROM sqlite_master WHERE name = 'facts_by_subject'"), 1);
+        assert_eq!(SqliteMirror::in_memory().unwrap().cursor().unwrap(), 0);
+
+        mirror.connection().pragma_update(None, "user_version", MIGRATIONS.len() as i64 + 1).unwrap();
+        assert!(matches!(mirror.migrate(), Err(KnowledgeError::MirrorSchemaTooNew { .. })));
+    }
+}
@@ -14,11 +14,12 @@
 //       • Provide a sled‑based implementation with automatic persistence
 //       • Support binary serialization of ontology state for durability
 //       • Serve as the persistence backbone for the Knowledge subsystem
+//       • Report backend failures as `KnowledgeError::Storage`
 //
 //   File:        /src/knowledge/storage.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-26
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
@@ -27,12 +28,13 @@
 
 use sled::{Db, IVec};
 use std::path::Path;
-use anyhow::{Result, Context};
+
+use crate::knowledge::error::KnowledgeError;
 
 /// Trait defining storage interface
 pub trait Storage {
-    fn save(&self, key: &str, value: &[u8]) -> Result<()>;
-    fn load(&self, key: &str) -> Result<Option<Vec<u8>>>;
+    fn save(&self, key: &str, value: &[u8]) -> Result<(), KnowledgeError>;
+    fn load(&self, key: &str) -> Result<Option<Vec<u8>>, KnowledgeError>;
 }
 
 /// Sled-based storage implementation
@@ -42,20 +44,20 @@ pub struct SledStorage {
 
 impl SledStorage {
     /// Opens or creates a sled database at the specified path
-    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let db = sled::open(path).context("Failed to open sled database")?;
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, KnowledgeError> {
+        let db = sled::open(path)?;
         Ok(SledStorage { db })
     }
 }
 
 impl Storage for SledStorage {
-    fn save(&self, key: &str, value: &[u8]) -> Result<()> {
+    fn save(&self, key: &str, value: &[u8]) -> Result<(), KnowledgeError> {
         self.db.insert(key, value)?;
         self.db.flush()?;
         Ok(())
     }
 
-    fn load(&self, key: &str) -> Result<Option<Vec<u8>>> {
+    fn load(&self, key: &str) -> Result<Option<Vec<u8>>, KnowledgeError> {
         match self.db.get(key)? {
             Some(ivec) => Ok(Some(ivec.to_vec())),
             None => Ok(None),
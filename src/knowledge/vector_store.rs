@@ -0,0 +1,760 @@
+// ============================================================================
+//                        ASTRA AGI • VECTOR STORE
+//        Nearest-Neighbour Search over Embeddings, in Memory or on Disk
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Knowledge Layer that holds the embeddings of
+//       ingested passages and finds the ones closest to a query. Stores are
+//       interchangeable behind `VectorStore`: `HnswStore` keeps everything in
+//       memory and searches a hierarchical navigable small-world graph, while
+//       `DiskStore` keeps vectors in an append-only file and only their ids
+//       and metadata in memory, so semantic memory can outgrow RAM and
+//       survives restarts. Either can be snapshotted to a file and restored
+//       into the other.
+//
+//   Core Functions:
+//       • Define the vector store interface
+//       • Upsert records and delete them by id or by provenance
+//       • Search by cosine similarity with metadata filters
+//       • Snapshot and restore store contents, falling back to the previous
+//         snapshot when the newest fails its checksum
+//
+//   File:        /src/knowledge/vector_store.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::integrity::{Generations, IntegrityError};
+use crate::knowledge::embedding::{cosine_similarity, fnv1a};
+
+#[derive(Debug, Error)]
+pub enum VectorStoreError {
+    #[error("Vector has {found} dimensions, the store holds {expected}")]
+    DimensionMismatch { expected: usize, found: usize },
+    #[error("Vector store file {} could not be accessed", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Vector store file {} is corrupt at line {line}", path.display())]
+    Corrupt {
+        path: PathBuf,
+        line: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error(transparent)]
+    Snapshot(#[from] IntegrityError),
+}
+
+/// Snapshots kept at a path, the newest included.
+const SNAPSHOT_GENERATIONS: usize = 2;
+
+pub type Metadata = BTreeMap<String, String>;
+
+/// An embedding with what it was computed from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VectorRecord {
+    pub id: String,
+    pub vector: Vec<f32>,
+    /// Where the embedded text came from, such as `file:/notes/a.md`
+    pub provenance: String,
+    pub metadata: Metadata,
+}
+
+/// Which records a search may return. The default filter accepts all.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetadataFilter {
+    pub equals: Metadata,
+    pub provenance: Option<String>,
+}
+
+impl MetadataFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also require metadata `key` to be `value`.
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.equals.insert(key.into(), value.into());
+        self
+    }
+
+    /// Also require the record to come from `provenance`.
+    pub fn from_source(mut self, provenance: impl Into<String>) -> Self {
+        self.provenance = Some(provenance.into());
+        self
+    }
+
+    pub fn matches(&self, provenance: &str, metadata: &Metadata) -> bool {
+        self.provenance.as_deref().is_none_or(|p| p == provenance)
+            && self.equals.iter().all(|(key, value)| metadata.get(key) == Some(value))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub id: String,
+    /// Cosine similarity to the query
+    pub score: f32,
+    pub provenance: String,
+    pub metadata: Metadata,
+}
+
+/// Holds embeddings and finds those nearest a query.
+pub trait VectorStore {
+    fn dimensions(&self) -> usize;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds `record`, replacing any record with the same id.
+    fn upsert(&mut self, record: VectorRecord) -> Result<(), VectorStoreError>;
+    fn get(&self, id: &str) -> Result<Option<VectorRecord>, VectorStoreError>;
+    /// Returns whether the record existed.
+    fn delete(&mut self, id: &str) -> Result<bool, VectorStoreError>;
+    /// Deletes every record from `provenance`, returning how many there were.
+    fn delete_by_provenance(&mut self, provenance: &str) -> Result<usize, VectorStoreError>;
+    /// Up to `k` records matching `filter`, most similar first.
+    fn search(&self, query: &[f32], k: usize, filter: &MetadataFilter) -> Result<Vec<SearchHit>, VectorStoreError>;
+    fn records(&self) -> Result<Vec<VectorRecord>, VectorStoreError>;
+    fn clear(&mut self) -> Result<(), VectorStoreError>;
+
+    /// Writes every record to `path`, one JSON record per line, sealed with
+    /// a checksum. The previous snapshot is kept beside it as `<path>.1`.
+    fn snapshot(&self, path: &Path) -> Result<usize, VectorStoreError> {
+        let records = self.records()?;
+        let mut payload = Vec::new();
+        for record in &records {
+            serde_json::to_writer(&mut payload, record)
+                .map_err(|e| VectorStoreError::Io { path: path.to_path_buf(), source: e.into() })?;
+            payload.push(b'\n');
+        }
+        Generations::new(path, SNAPSHOT_GENERATIONS).write(&payload)?;
+        Ok(records.len())
+    }
+
+    /// Replaces the store's contents with the snapshot at `path`, or with
+    /// the previous snapshot if that one is corrupt; snapshots passed over
+    /// are logged.
+    fn restore(&mut self, path: &Path) -> Result<usize, VectorStoreError> {
+        let recovered = Generations::new(path, SNAPSHOT_GENERATIONS).load(|payload| {
+            payload
+                .split(|&byte| byte == b'\n')
+                .filter(|line| !line.is_empty())
+                .enumerate()
+                .map(|(index, line)| {
+                    serde_json::from_slice::<VectorRecord>(line).map_err(|source| VectorStoreError::Corrupt {
+                        path: path.to_path_buf(),
+                        line: index + 1,
+                        source,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })?;
+        recovered.warn_skipped();
+        let records = recovered.value;
+        self.clear()?;
+        let count = records.len();
+        for record in records {
+            self.upsert(record)?;
+        }
+        Ok(count)
+    }
+}
+
+fn check_dimensions(expected: usize, vector: &[f32]) -> Result<(), VectorStoreError> {
+    if vector.len() == expected {
+        Ok(())
+    } else {
+        Err(VectorStoreError::DimensionMismatch { expected, found: vector.len() })
+    }
+}
+
+fn hit(record: &VectorRecord, score: f32) -> SearchHit {
+    SearchHit {
+        id: record.id.clone(),
+        score,
+        provenance: record.provenance.clone(),
+        metadata: record.metadata.clone(),
+    }
+}
+
+/// A node index ordered by distance, for the search heaps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate {
+    distance: f32,
+    node: usize,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance.total_cmp(&other.distance).then(self.node.cmp(&other.node))
+    }
+}
+
+struct Node {
+    record: VectorRecord,
+    /// Neighbours on each layer from 0 up to the node's level
+    neighbours: Vec<Vec<usize>>,
+    deleted: bool,
+}
+
+/// An in-memory HNSW index. Deleted and replaced records stay in the graph
+/// as waypoints until they outnumber the live ones, when the graph is
+/// rebuilt.
+pub struct HnswStore {
+    dimensions: usize,
+    /// Links per node on upper layers; layer 0 allows twice as many
+    pub m: usize,
+    pub ef_construction: usize,
+    /// Candidates examined per search; higher is slower and more accurate
+    pub ef_search: usize,
+    nodes: Vec<Node>,
+    ids: HashMap<String, usize>,
+    entry: Option<usize>,
+}
+
+impl HnswStore {
+    pub fn new(dimensions: usize) -> Self {
+        Self {
+            dimensions,
+            m: 16,
+            ef_construction: 100,
+            ef_search: 50,
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+            entry: None,
+        }
+    }
+
+    fn distance(&self, query: &[f32], node: usize) -> f32 {
+        1.0 - cosine_similarity(query, &self.nodes[node].record.vector)
+    }
+
+    /// A node's level, drawn from the usual exponential distribution but
+    /// seeded by its id so the same records always build the same graph.
+    fn level_for(&self, id: &str) -> usize {
+        let hash = fnv1a(id.bytes().chain((self.nodes.len() as u64).to_le_bytes()));
+        let uniform = ((hash >> 11) + 1) as f64 / (1u64 << 53) as f64;
+        let scale = 1.0 / (self.m.max(2) as f64).ln();
+        (-uniform.ln() * scale).floor() as usize
+    }
+
+    fn max_links(&self, layer: usize) -> usize {
+        if layer == 0 {
+            self.m * 2
+        } else {
+            self.m
+        }
+    }
+
+    /// The `ef` nodes nearest `query` reachable on `layer` from `entries`,
+    /// nearest first.
+    fn search_layer(&self, query: &[f32], entries: &[usize], ef: usize, layer: usize) -> Vec<Candidate> {
+        let mut visited: HashSet<usize> = entries.iter().copied().collect();
+        let mut candidates = BinaryHeap::new();
+        let mut found = BinaryHeap::new();
+        for &node in entries {
+            let candidate = Candidate { distance: self.distance(query, node), node };
+            candidates.push(Reverse(candidate));
+            found.push(candidate);
+        }
+        while let Some(Reverse(current)) = candidates.pop() {
+            if found.len() >= ef && found.peek().is_some_and(|worst: &Candidate| current.distance > worst.distance) {
+                break;
+            }
+            for &neighbour in &self.nodes[current.node].neighbours[layer] {
+                if !visited.insert(neighbour) {
+                    continue;
+                }
+                let candidate = Candidate { distance: self.distance(query, neighbour), node: neighbour };
+                if found.len() < ef || found.peek().is_some_and(|worst| candidate.distance < worst.distance) {
+                    candidates.push(Reverse(candidate));
+                    found.push(candidate);
+                    if found.len() > ef {
+                        found.pop();
+                    }
+                }
+            }
+        }
+        found.into_sorted_vec()
+    }
+
+    /// Keeps the `max` links of `node` on `layer` nearest to it.
+    fn prune(&mut self, node: usize, layer: usize, max: usize) {
+        let vector = self.nodes[node].record.vector.clone();
+        let mut links: Vec<Candidate> = self.nodes[node].neighbours[layer]
+            .iter()
+            .map(|&n| Candidate { distance: self.distance(&vector, n), node: n })
+            .collect();
+        links.sort();
+        self.nodes[node].neighbours[layer] = links.into_iter().take(max).map(|c| c.node).collect();
+    }
+
+    fn insert(&mut self, record: VectorRecord) {
+        let level = self.level_for(&record.id);
+        let node = self.nodes.len();
+        self.ids.insert(record.id.clone(), node);
+        let query = record.vector.clone();
+        self.nodes.push(Node { record, neighbours: vec![Vec::new(); level + 1], deleted: false });
+
+        let Some(entry) = self.entry else {
+            self.entry = Some(node);
+            return;
+        };
+        let top = self.nodes[entry].neighbours.len() - 1;
+        let mut nearest = vec![entry];
+        for layer in (level + 1..=top).rev() {
+            nearest = vec![self.search_layer(&query, &nearest, 1, layer)[0].node];
+        }
+        for layer in (0..=level.min(top)).rev() {
+            let found = self.search_layer(&query, &nearest, self.ef_construction, layer);
+            let max = self.max_links(layer);
+            let links: Vec<usize> = found.iter().take(self.m).map(|c| c.node).collect();
+            for &link in &links {
+                self.nodes[link].neighbours[layer].push(node);
+                if self.nodes[link].neighbours[layer].len() > max {
+                    self.prune(link, layer, max);
+                }
+            }
+            self.nodes[node].neighbours[layer] = links;
+            nearest = found.iter().map(|c| c.node).collect();
+        }
+        if level > top {
+            self.entry = Some(node);
+        }
+    }
+
+    fn remove(&mut self, id: &str) -> bool {
+        let Some(node) = self.ids.remove(id) else {
+            return false;
+        };
+        self.nodes[node].deleted = true;
+        if self.nodes.len() > 2 * self.ids.len() + 64 {
+            self.rebuild();
+        }
+        true
+    }
+
+    /// Rebuilds the graph from the live records, dropping tombstones.
+    pub fn rebuild(&mut self) {
+        let live: Vec<VectorRecord> = std::mem::take(&mut self.nodes)
+            .into_iter()
+            .filter(|node| !node.deleted)
+            .map(|node| node.record)
+            .collect();
+        self.ids.clear();
+        self.entry = None;
+        for record in live {
+            self.insert(record);
+        }
+    }
+}
+
+impl VectorStore for HnswStore {
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    fn upsert(&mut self, record: VectorRecord) -> Result<(), VectorStoreError> {
+        check_dimensions(self.dimensions, &record.vector)?;
+        self.remove(&record.id);
+        self.insert(record);
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> Result<Option<VectorRecord>, VectorStoreError> {
+        Ok(self.ids.get(id).map(|&node| self.nodes[node].record.clone()))
+    }
+
+    fn delete(&mut self, id: &str) -> Result<bool, VectorStoreError> {
+        Ok(self.remove(id))
+    }
+
+    fn delete_by_provenance(&mut self, provenance: &str) -> Result<usize, VectorStoreError> {
+        let ids: Vec<String> = self
+            .ids
+            .iter()
+            .filter(|(_, &node)| self.nodes[node].record.provenance == provenance)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in &ids {
+            self.remove(id);
+        }
+        Ok(ids.len())
+    }
+
+    fn search(&self, query: &[f32], k: usize, filter: &MetadataFilter) -> Result<Vec<SearchHit>, VectorStoreError> {
+        check_dimensions(self.dimensions, query)?;
+        let Some(entry) = self.entry else {
+            return Ok(Vec::new());
+        };
+        let accepts = |node: &Node| !node.deleted && filter.matches(&node.record.provenance, &node.record.metadata);
+
+        let mut nearest = vec![entry];
+        for layer in (1..self.nodes[entry].neighbours.len()).rev() {
+            nearest = vec![self.search_layer(query, &nearest, 1, layer)[0].node];
+        }
+        let mut hits: Vec<SearchHit> = self
+            .search_layer(query, &nearest, self.ef_search.max(k), 0)
+            .into_iter()
+            .filter(|c| accepts(&self.nodes[c.node]))
+            .take(k)
+            .map(|c| hit(&self.nodes[c.node].record, cosine_similarity(query, &self.nodes[c.node].record.vector)))
+            .collect();
+
+        // A selective filter can reject most of what the graph search finds;
+        // then an exact scan over the matching records is both correct and
+        // cheap, since there are few of them.
+        if hits.len() < k {
+            let mut exact: Vec<SearchHit> = self
+                .nodes
+                .iter()
+                .filter(|node| accepts(node))
+                .map(|node| hit(&node.record, cosine_similarity(query, &node.record.vector)))
+                .collect();
+            exact.sort_by(|a, b| b.score.total_cmp(&a.score));
+            exact.truncate(k);
+            hits = exact;
+        }
+        Ok(hits)
+    }
+
+    fn records(&self) -> Result<Vec<VectorRecord>, VectorStoreError> {
+        Ok(self.nodes.iter().filter(|node| !node.deleted).map(|node| node.record.clone()).collect())
+    }
+
+    fn clear(&mut self) -> Result<(), VectorStoreError> {
+        self.nodes.clear();
+        self.ids.clear();
+        self.entry = None;
+        Ok(())
+    }
+}
+
+/// One line of a `DiskStore` log.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum LogEntry {
+    Put(VectorRecord),
+    Delete(String),
+}
+
+/// Where a live record's line is in the log.
+struct Location {
+    offset: u64,
+    length: usize,
+    provenance: String,
+    metadata: Metadata,
+}
+
+/// A vector store kept in an append-only log file. Only ids, provenance,
+/// and metadata are held in memory; searches filter on those and then read
+/// the matching vectors from disk, so they are exact but linear in the
+/// number of matching records. Superseded lines are dropped by `compact`,
+/// which also runs on its own once they outnumber the live records.
+pub struct DiskStore {
+    path: PathBuf,
+    dimensions: usize,
+    log: File,
+    end: u64,
+    records: HashMap<String, Location>,
+    dead: usize,
+}
+
+impl DiskStore {
+    /// Opens the store at `path`, creating it if needed and replaying the
+    /// log. A torn final line from an interrupted write is discarded.
+    pub fn open(path: impl Into<PathBuf>, dimensions: usize) -> Result<Self, VectorStoreError> {
+        let path = path.into();
+        let io = |source| VectorStoreError::Io { path: path.clone(), source };
+        let mut log = OpenOptions::new().read(true).append(true).create(true).open(&path).map_err(io)?;
+
+        let mut records = HashMap::new();
+        let mut dead = 0;
+        let mut end = 0;
+        let mut reader = BufReader::new(&mut log);
+        let mut line = String::new();
+        for number in 1.. {
+            line.clear();
+            let length = reader.read_line(&mut line).map_err(io)?;
+            if length == 0 {
+                break;
+            }
+            let entry = match serde_json::from_str::<LogEntry>(&line) {
+                Ok(entry) => entry,
+                Err(_) if !line.ends_with('\n') => break,
+                Err(source) => return Err(VectorStoreError::Corrupt { path, line: number, source }),
+            };
+            match entry {
+                LogEntry::Put(record) => {
+                    check_dimensions(dimensions, &record.vector)?;
+                    let location = Location { offset: end, length, provenance: record.provenance, metadata: record.metadata };
+                    dead += usize::from(records.insert(record.id, location).is_some());
+                }
+                LogEntry::Delete(id) => dead += 1 + usize::from(records.remove(&id).is_some()),
+            }
+            end += length as u64;
+        }
+        drop(reader);
+        log.set_len(end).map_err(io)?;
+        Ok(Self { path, dimensions, log, end, records, dead })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn append(&mut self, entry: &LogEntry) -> Result<(u64, usize), VectorStoreError> {
+        let mut line = serde_json::to_vec(entry).map_err(|e| VectorStoreError::Io { path: self.path.clone(), source: e.into() })?;
+        line.push(b'\n');
+        self.log
+            .write_all(&line)
+            .map_err(|source| VectorStoreError::Io { path: self.path.clone(), source })?;
+        let offset = self.end;
+        self.end += line.len() as u64;
+        Ok((offset, line.len()))
+    }
+
+    fn read(&self, file: &mut File, location: &Location) -> Result<VectorRecord, VectorStoreError> {
+        let io = |source| VectorStoreError::Io { path: self.path.clone(), source };
+        let mut line = vec![0; location.length];
+        file.seek(SeekFrom::Start(location.offset)).map_err(io)?;
+        file.read_exact(&mut line).map_err(io)?;
+        match serde_json::from_slice(&line) {
+            Ok(LogEntry::Put(record)) => Ok(record),
+            Ok(LogEntry::Delete(_)) => Err(io(std::io::ErrorKind::InvalidData.into())),
+            Err(source) => Err(VectorStoreError::Corrupt { path: self.path.clone(), line: 0, source }),
+        }
+    }
+
+    fn reader(&self) -> Result<File, VectorStoreError> {
+        File::open(&self.path).map_err(|source| VectorStoreError::Io { path: self.path.clone(), source })
+    }
+
+    fn compact_if_wasteful(&mut self) -> Result<(), VectorStoreError> {
+        if self.dead > self.records.len() + 64 {
+            self.compact()?;
+        }
+        Ok(())
+    }
+
+    /// Rewrites the log with only the live records.
+    pub fn compact(&mut self) -> Result<(), VectorStoreError> {
+        let records = self.records()?;
+        let partial = self.path.with_extension("compacting");
+        {
+            let mut fresh = DiskStore::open(&partial, self.dimensions)?;
+            fresh.clear()?;
+            for record in records {
+                fresh.upsert(record)?;
+            }
+        }
+        std::fs::rename(&partial, &self.path).map_err(|source| VectorStoreError::Io { path: self.path.clone(), source })?;
+        *self = DiskStore::open(self.path.clone(), self.dimensions)?;
+        Ok(())
+    }
+}
+
+impl VectorStore for DiskStore {
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    fn upsert(&mut self, record: VectorRecord) -> Result<(), VectorStoreError> {
+        check_dimensions(self.dimensions, &record.vector)?;
+        let (offset, length) = self.append(&LogEntry::Put(record.clone()))?;
+        let location = Location { offset, length, provenance: record.provenance, metadata: record.metadata };
+        if self.records.insert(record.id, location).is_some() {
+            self.dead += 1;
+            self.compact_if_wasteful()?;
+        }
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> Result<Option<VectorRecord>, VectorStoreError> {
+        match self.records.get(id) {
+            Some(location) => Ok(Some(self.read(&mut self.reader()?, location)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn delete(&mut self, id: &str) -> Result<bool, VectorStoreError> {
+        if !self.records.contains_key(id) {
+            return Ok(false);
+        }
+        self.append(&LogEntry::Delete(id.to_string()))?;
+        self.records.remove(id);
+        self.dead += 2;
+        self.compact_if_wasteful()?;
+        Ok(true)
+    }
+
+    fn delete_by_provenance(&mut self, provenance: &str) -> Result<usize, VectorStoreError> {
+        let ids: Vec<String> = self
+            .records
+            .iter()
+            .filter(|(_, location)| location.provenance == provenance)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in &ids {
+            self.delete(id)?;
+        }
+        Ok(ids.len())
+    }
+
+    fn search(&self, query: &[f32], k: usize, filter: &MetadataFilter) -> Result<Vec<SearchHit>, VectorStoreError> {
+        check_dimensions(self.dimensions, query)?;
+        let mut matching: Vec<&Location> = self
+            .records
+            .values()
+            .filter(|location| filter.matches(&location.provenance, &location.metadata))
+            .collect();
+        // Read in file order so the disk is scanned forwards
+        matching.sort_by_key(|location| location.offset);
+
+        let mut file = self.reader()?;
+        let mut hits = Vec::with_capacity(matching.len());
+        for location in matching {
+            let record = self.read(&mut file, location)?;
+            hits.push(hit(&record, cosine_similarity(query, &record.vector)));
+        }
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(k);
+        Ok(hits)
+    }
+
+    fn records(&self) -> Result<Vec<VectorRecord>, VectorStoreError> {
+        let mut locations: Vec<&Location> = self.records.values().collect();
+        locations.sort_by_key(|location| location.offset);
+        let mut file = self.reader()?;
+        locations.into_iter().map(|location| self.read(&mut file, location)).collect()
+    }
+
+    fn clear(&mut self) -> Result<(), VectorStoreError> {
+        self.log.set_len(0).map_err(|source| VectorStoreError::Io { path: self.path.clone(), source })?;
+        self.end = 0;
+        self.records.clear();
+        self.dead = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: usize, vector: Vec<f32>) -> VectorRecord {
+        let mut metadata = Metadata::new();
+        metadata.insert("parity".to_string(), if id.is_multiple_of(2) { "even" } else { "odd" }.to_string());
+        VectorRecord { id: format!("r{}", id), vector, provenance: format!("file:{}", id % 5), metadata }
+    }
+
+    /// Deterministic pseudo-random vectors.
+    fn vectors(count: usize, dimensions: usize) -> Vec<Vec<f32>> {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        (0..count)
+            .map(|_| {
+                (0..dimensions)
+                    .map(|_| {
+                        state ^= state << 13;
+                        state ^= state >> 7;
+                        state ^= state << 17;
+                        (state % 2000) as f32 / 1000.0 - 1.0
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_stores_agree_and_persist() {
+        let dir = std::env::temp_dir().join(format!("astra_vectors_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let data = vectors(400, 16);
+        let mut hnsw = HnswStore::new(16);
+        let mut disk = DiskStore::open(dir.join("vectors.log"), 16).unwrap();
+        for (id, vector) in data.iter().enumerate() {
+            hnsw.upsert(record(id, vector.clone())).unwrap();
+            disk.upsert(record(id, vector.clone())).unwrap();
+        }
+        assert!(matches!(hnsw.upsert(record(0, vec![1.0])), Err(VectorStoreError::DimensionMismatch { .. })));
+
+        // The graph search finds the exact nearest neighbours the disk scan does
+        let query = &data[7];
+        let exact: Vec<String> = disk.search(query, 5, &MetadataFilter::new()).unwrap().into_iter().map(|h| h.id).collect();
+        let approximate: Vec<String> = hnsw.search(query, 5, &MetadataFilter::new()).unwrap().into_iter().map(|h| h.id).collect();
+        assert_eq!(exact[0], "r7");
+        assert_eq!(approximate, exact);
+
+        let odd_from_three = MetadataFilter::new().with("parity", "odd").from_source("file:3");
+        let hits = hnsw.search(query, 3, &odd_from_three).unwrap();
+        assert_eq!(hits.len(), 3);
+        assert!(hits.iter().all(|h| h.metadata["parity"] == "odd" && h.provenance == "file:3"));
+        assert_eq!(hits, disk.search(query, 3, &odd_from_three).unwrap());
+
+        assert_eq!(hnsw.delete_by_provenance("file:2").unwrap(), 80);
+        assert_eq!(disk.delete_by_provenance("file:2").unwrap(), 80);
+        disk.upsert(record(1, data[2].clone())).unwrap();
+        assert!(hnsw.delete("r8").unwrap());
+        assert!(disk.delete("r8").unwrap());
+        assert_eq!((hnsw.len(), disk.len()), (319, 319));
+
+        // Reopening replays the log; a torn last line is dropped
+        drop(disk);
+        let mut log = OpenOptions::new().append(true).open(dir.join("vectors.log")).unwrap();
+        log.write_all(b"{\"put\":{\"id\":\"r9").unwrap();
+        let disk = DiskStore::open(dir.join("vectors.log"), 16).unwrap();
+        assert_eq!(disk.len(), 319);
+        assert_eq!(disk.get("r1").unwrap().unwrap().vector, data[2]);
+        assert!(disk.get("r2").unwrap().is_none());
+
+        // A snapshot of one store restores into the other
+        assert_eq!(disk.snapshot(&dir.join("snapshot.jsonl")).unwrap(), 319);
+        let mut restored = HnswStore::new(16);
+        restored.restore(&dir.join("snapshot.jsonl")).unwrap();
+        assert_eq!(restored.search(&data[2], 1, &MetadataFilter::new()).unwrap()[0].id, "r1");
+
+        // A corrupt snapshot gives way to the one before it
+        assert!(restored.delete("r1").unwrap());
+        assert_eq!(restored.snapshot(&dir.join("snapshot.jsonl")).unwrap(), 318);
+        let mut bytes = std::fs::read(dir.join("snapshot.jsonl")).unwrap();
+        let middle = bytes.len() / 2;
+        bytes[middle] ^= 0x20;
+        std::fs::write(dir.join("snapshot.jsonl"), bytes).unwrap();
+        assert_eq!(restored.restore(&dir.join("snapshot.jsonl")).unwrap(), 319);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -14,18 +14,19 @@
 //       • Record computation graphs for reverse‑mode autodiff
 //       • Perform backward gradient propagation from scalar loss values
 //       • Serve as the computational substrate for learning algorithms
+//       • Report malformed tapes as typed learning errors
 //
 //   File:        /src/learning/autodiff.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-23
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
-use anyhow::{anyhow, Result};
+use super::error::LearningError;
 use ndarray::{ArrayD, Dimension};
 use std::cell::RefCell;
 use std::ops::{Add, Mul, Neg};
@@ -84,19 +85,16 @@ impl Tape {
     }
 
     /// Performs reverse‑mode backprop starting from a scalar loss node.
-    fn backward(&mut self, loss_id: usize) -> Result<()> {
+    fn backward(&mut self, loss_id: usize) -> Result<(), LearningError> {
         if self.nodes.is_empty() {
-            return Err(anyhow!("No nodes in tape for backward pass"));
+            return Err(LearningError::EmptyTape);
         }
 
         // Initialize gradient at loss as 1.0 (dL/dL = 1).
         {
             let loss_node = self.node(loss_id);
             if loss_node.value.ndim() != 0 {
-                return Err(anyhow!(
-                    "Backward currently expects scalar loss; got shape {:?}",
-                    loss_node.value.shape()
-                ));
+                return Err(LearningError::NonScalarLoss(loss_node.value.shape().to_vec()));
             }
         }
 
@@ -128,7 +126,7 @@ impl Tape {
                 OpKind::Neg => {
                     // y = -x => dy/dx = -1
                     if parents.len() != 1 {
-                        return Err(anyhow!("Neg op expects 1 parent"));
+                        return Err(LearningError::OpArity { op: "Neg", expected: 1, found: parents.len() });
                     }
                     let parent_id = parents[0];
                     Self::accumulate_grad(self, parent_id, &(-&grad))?;
@@ -136,7 +134,7 @@ impl Tape {
                 OpKind::Add => {
                     // z = x + y => dz/dx = 1, dz/dy = 1
                     if parents.len() != 2 {
-                        return Err(anyhow!("Add op expects 2 parents"));
+                        return Err(LearningError::OpArity { op: "Add", expected: 2, found: parents.len() });
                     }
                     let left_id = parents[0];
                     let right_id = parents[1];
@@ -151,7 +149,7 @@ impl Tape {
                 OpKind::Mul => {
                     // z = x * y => dz/dx = y, dz/dy = x
                     if parents.len() != 2 {
-                        return Err(anyhow!("Mul op expects 2 parents"));
+                        return Err(LearningError::OpArity { op: "Mul", expected: 2, found: parents.len() });
                     }
                     let left_id = parents[0];
                     let right_id = parents[1];
@@ -184,16 +182,15 @@ impl Tape {
     }
 
     /// Accumulates gradient into a node: grad[node] += incoming.
-    fn accumulate_grad(&mut self, node_id: usize, incoming: &ArrayD<f64>) -> Result<()> {
+    fn accumulate_grad(&mut self, node_id: usize, incoming: &ArrayD<f64>) -> Result<(), LearningError> {
         let node = self.node_mut(node_id);
         match &mut node.grad {
             Some(existing) => {
                 if existing.shape() != incoming.shape() {
-                    return Err(anyhow!(
-                        "Gradient shape mismatch: existing {:?}, incoming {:?}",
-                        existing.shape(),
-                        incoming.shape()
-                    ));
+                    return Err(LearningError::GradientShapeMismatch {
+                        existing: existing.shape().to_vec(),
+                        incoming: incoming.shape().to_vec(),
+                    });
                 }
                 *existing += incoming;
             }
@@ -206,7 +203,7 @@ impl Tape {
 
     /// Basic helper for shape matching; here it’s a placeholder.
     /// In a more advanced system, this would handle broadcasting reductions.
-    fn match_shape(grad: &ArrayD<f64>, _ndim: usize) -> Result<ArrayD<f64>> {
+    fn match_shape(grad: &ArrayD<f64>, _ndim: usize) -> Result<ArrayD<f64>, LearningError> {
         Ok(grad.clone())
     }
 }
@@ -247,7 +244,7 @@ impl Variable {
     }
 
     /// Perform backpropagation from this variable as the scalar loss.
-    pub fn backward(&self) -> Result<()> {
+    pub fn backward(&self) -> Result<(), LearningError> {
         self.tape.borrow_mut().backward(self.id)
     }
 }
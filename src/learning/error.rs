@@ -0,0 +1,47 @@
+// ============================================================================
+//                         ASTRA AGI • LEARNING ERRORS
+//        Typed Failures of Gradient Computation, Training & Persistence
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       The error type of the Learning crate. Malformed computation tapes
+//       are reported with the shapes or operation involved, and failures
+//       saving or loading learned models keep the I/O or serialization
+//       error as their source.
+//
+//   Core Functions:
+//       • Name the ways a backward pass can fail
+//       • Wrap I/O, serialization, and integrity errors from model persistence
+//
+//   File:        /src/learning/error.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use thiserror::Error;
+
+use crate::integrity::IntegrityError;
+
+#[derive(Debug, Error)]
+pub enum LearningError {
+    #[error("No nodes in tape for backward pass")]
+    EmptyTape,
+    #[error("Backward currently expects scalar loss; got shape {0:?}")]
+    NonScalarLoss(Vec<usize>),
+    /// A tape node has the wrong number of parents for its operation
+    #[error("{op} op expects {expected} parent(s), found {found}")]
+    OpArity { op: &'static str, expected: usize, found: usize },
+    #[error("Gradient shape mismatch: existing {existing:?}, incoming {incoming:?}")]
+    GradientShapeMismatch { existing: Vec<usize>, incoming: Vec<usize> },
+    #[error("Could not read or write model file")]
+    Io(#[from] std::io::Error),
+    #[error("Could not serialize or deserialize model")]
+    Serialization(#[from] serde_json::Error),
+    /// No saved model could be loaded, or the model could not be saved
+    #[error(transparent)]
+    Integrity(#[from] IntegrityError),
+}
@@ -0,0 +1,309 @@
+// ============================================================================
+//                 ASTRA AGI • FEEDBACK INGESTION & CREDIT ASSIGNMENT
+//        Mapping User Feedback onto the Components Behind Each Response
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Learning subsystem that receives structured user
+//       feedback (thumbs up/down, corrections, preference statements) and
+//       decides who deserves the credit or blame. Every response Astra gives
+//       can register a trace naming the episode, plan, knowledge facts, and
+//       personality settings that shaped it; feedback on that response is
+//       then distributed across those components instead of being folded
+//       into one global reward scalar.
+//
+//   Core Functions:
+//       • Represent structured feedback events and response traces
+//       • Convert feedback into a signed reward signal
+//       • Split reward across contributing components by configurable shares
+//       • Accumulate per-component credit for downstream learners
+//
+//   File:        /src/learning/feedback.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use super::preferences::ResponseStyle;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use thiserror::Error;
+
+/// Identifier of a response that feedback can refer to.
+pub type ResponseId = u64;
+
+/// The kind of feedback a user gave.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FeedbackKind {
+    ThumbsUp,
+    ThumbsDown,
+    /// The user corrected part of a response
+    Correction { original: String, corrected: String },
+    /// A standing preference ("keep answers short"); polarity in -1.0..=1.0
+    Preference { topic: String, statement: String, polarity: f32 },
+}
+
+impl FeedbackKind {
+    /// Signed reward carried by this feedback.
+    pub fn reward(&self) -> f32 {
+        match self {
+            FeedbackKind::ThumbsUp => 1.0,
+            FeedbackKind::ThumbsDown => -1.0,
+            FeedbackKind::Correction { .. } => -0.5,
+            // Preferences describe the user rather than judging the response.
+            FeedbackKind::Preference { .. } => 0.0,
+        }
+    }
+}
+
+/// A piece of feedback about a specific response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedbackEvent {
+    pub response_id: ResponseId,
+    pub kind: FeedbackKind,
+    pub user_id: Option<String>,
+    /// Unix timestamp
+    pub timestamp: u64,
+}
+
+/// A component that may have contributed to a response.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ComponentRef {
+    Episode(u64),
+    Plan(String),
+    Fact(String),
+    PersonalityTrait(String),
+}
+
+/// Record of what shaped a response.
+/// Fact and trait weights express relative influence and need not sum to 1.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResponseTrace {
+    pub response_id: ResponseId,
+    pub episode_id: Option<u64>,
+    pub plan_id: Option<String>,
+    pub facts: Vec<(String, f32)>,
+    pub personality_settings: Vec<(String, f32)>,
+    /// Style of the response, used to learn user preferences
+    #[serde(default)]
+    pub style: Option<ResponseStyle>,
+}
+
+/// Share of the reward given to each component group.
+#[derive(Debug, Clone)]
+pub struct CreditShares {
+    pub episode: f32,
+    pub plan: f32,
+    pub facts: f32,
+    pub personality: f32,
+}
+
+impl Default for CreditShares {
+    fn default() -> Self {
+        CreditShares { episode: 0.2, plan: 0.3, facts: 0.3, personality: 0.2 }
+    }
+}
+
+/// The outcome of assigning one feedback event.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CreditAssignment {
+    pub response_id: ResponseId,
+    pub reward: f32,
+    /// Credit per component; sums to `reward` when any component is traced
+    pub credits: Vec<(ComponentRef, f32)>,
+    /// Corrected text, passed on for knowledge updates
+    pub correction: Option<String>,
+}
+
+impl CreditAssignment {
+    pub fn credit_for(&self, component: &ComponentRef) -> f32 {
+        self.credits.iter().filter(|(c, _)| c == component).map(|(_, v)| v).sum()
+    }
+}
+
+/// Errors raised while ingesting feedback.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum FeedbackError {
+    #[error("No trace recorded for response {0}")]
+    UnknownResponse(ResponseId),
+}
+
+/// Distributes feedback over the components recorded in response traces.
+#[derive(Debug)]
+pub struct CreditAssigner {
+    pub shares: CreditShares,
+    traces: HashMap<ResponseId, ResponseTrace>,
+    trace_order: VecDeque<ResponseId>,
+    max_traces: usize,
+    totals: HashMap<ComponentRef, f32>,
+    history: Vec<FeedbackEvent>,
+}
+
+impl CreditAssigner {
+    /// Creates an assigner that remembers traces for the last `max_traces` responses.
+    pub fn new(max_traces: usize) -> Self {
+        CreditAssigner {
+            shares: CreditShares::default(),
+            traces: HashMap::new(),
+            trace_order: VecDeque::new(),
+            max_traces: max_traces.max(1),
+            totals: HashMap::new(),
+            history: Vec::new(),
+        }
+    }
+
+    /// Records what shaped a response so later feedback can be attributed.
+    pub fn register_trace(&mut self, trace: ResponseTrace) {
+        if !self.traces.contains_key(&trace.response_id) {
+            self.trace_order.push_back(trace.response_id);
+        }
+        self.traces.insert(trace.response_id, trace);
+
+        while self.trace_order.len() > self.max_traces {
+            if let Some(oldest) = self.trace_order.pop_front() {
+                self.traces.remove(&oldest);
+            }
+        }
+    }
+
+    pub fn trace(&self, response_id: ResponseId) -> Option<&ResponseTrace> {
+        self.traces.get(&response_id)
+    }
+
+    /// Assigns credit for a feedback event and accumulates it per component.
+    pub fn assign(&mut self, event: FeedbackEvent) -> Result<CreditAssignment, FeedbackError> {
+        let trace = self
+            .traces
+            .get(&event.response_id)
+            .ok_or(FeedbackError::UnknownResponse(event.response_id))?;
+
+        let reward = event.kind.reward();
+        let credits = self.split(trace, reward);
+
+        for (component, credit) in &credits {
+            *self.totals.entry(component.clone()).or_insert(0.0) += credit;
+        }
+
+        let correction = match &event.kind {
+            FeedbackKind::Correction { corrected, .. } => Some(corrected.clone()),
+            _ => None,
+        };
+
+        let assignment = CreditAssignment { response_id: event.response_id, reward, credits, correction };
+        self.history.push(event);
+        Ok(assignment)
+    }
+
+    /// Total credit a component has accumulated across all feedback.
+    pub fn total_credit(&self, component: &ComponentRef) -> f32 {
+        self.totals.get(component).copied().unwrap_or(0.0)
+    }
+
+    /// All feedback ingested so far, oldest first.
+    pub fn history(&self) -> &[FeedbackEvent] {
+        &self.history
+    }
+
+    /// Removes a user's feedback from the history and returns it. Credit
+    /// totals already accumulated from it are left as they are; they hold
+    /// nothing that identifies the user.
+    pub fn forget_user(&mut self, user_id: &str) -> Vec<FeedbackEvent> {
+        let (forgotten, kept) = std::mem::take(&mut self.history)
+            .into_iter()
+            .partition(|event| event.user_id.as_deref() == Some(user_id));
+        self.history = kept;
+        forgotten
+    }
+
+    fn split(&self, trace: &ResponseTrace, reward: f32) -> Vec<(ComponentRef, f32)> {
+        // Only groups present in the trace take part; their shares are renormalized.
+        let mut groups: Vec<(f32, Vec<(ComponentRef, f32)>)> = Vec::new();
+        if let Some(episode) = trace.episode_id {
+            groups.push((self.shares.episode, vec![(ComponentRef::Episode(episode), 1.0)]));
+        }
+        if let Some(plan) = &trace.plan_id {
+            groups.push((self.shares.plan, vec![(ComponentRef::Plan(plan.clone()), 1.0)]));
+        }
+        if !trace.facts.is_empty() {
+            let members = trace.facts.iter().map(|(f, w)| (ComponentRef::Fact(f.clone()), *w)).collect();
+            groups.push((self.shares.facts, members));
+        }
+        if !trace.personality_settings.is_empty() {
+            let members = trace
+                .personality_settings
+                .iter()
+                .map(|(t, w)| (ComponentRef::PersonalityTrait(t.clone()), *w))
+                .collect();
+            groups.push((self.shares.personality, members));
+        }
+
+        let total_share: f32 = groups.iter().map(|(share, _)| share.max(0.0)).sum();
+        if total_share <= 0.0 {
+            return Vec::new();
+        }
+
+        let mut credits = Vec::new();
+        for (share, members) in groups {
+            let group_reward = reward * share.max(0.0) / total_share;
+            let total_weight: f32 = members.iter().map(|(_, w)| w.max(0.0)).sum();
+            let member_count = members.len() as f32;
+            for (component, weight) in members {
+                let fraction = if total_weight > 0.0 { weight.max(0.0) / total_weight } else { 1.0 / member_count };
+                credits.push((component, group_reward * fraction));
+            }
+        }
+        credits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(response_id: ResponseId, kind: FeedbackKind) -> FeedbackEvent {
+        FeedbackEvent { response_id, kind, user_id: None, timestamp: 0 }
+    }
+
+    #[test]
+    fn test_credit_split_across_components() {
+        let mut assigner = CreditAssigner::new(10);
+        assigner.register_trace(ResponseTrace {
+            response_id: 1,
+            episode_id: Some(7),
+            plan_id: Some("plan-a".to_string()),
+            facts: vec![("paris-capital".to_string(), 3.0), ("paris-population".to_string(), 1.0)],
+            personality_settings: vec![("openness".to_string(), 1.0)],
+            style: None,
+        });
+
+        let assignment = assigner.assign(event(1, FeedbackKind::ThumbsDown)).unwrap();
+        let total: f32 = assignment.credits.iter().map(|(_, c)| c).sum();
+        assert!((total + 1.0).abs() < 1e-6);
+        assert!((assignment.credit_for(&ComponentRef::Plan("plan-a".to_string())) + 0.3).abs() < 1e-6);
+        assert!((assignment.credit_for(&ComponentRef::Fact("paris-capital".to_string())) + 0.225).abs() < 1e-6);
+        assert!((assigner.total_credit(&ComponentRef::Episode(7)) + 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_shares_renormalize_and_unknown_responses_fail() {
+        let mut assigner = CreditAssigner::new(1);
+        assigner.register_trace(ResponseTrace {
+            response_id: 1,
+            personality_settings: vec![("openness".to_string(), 1.0)],
+            ..Default::default()
+        });
+
+        let assignment = assigner.assign(event(1, FeedbackKind::ThumbsUp)).unwrap();
+        assert_eq!(assignment.credits, vec![(ComponentRef::PersonalityTrait("openness".to_string()), 1.0)]);
+
+        // The trace buffer holds a single response, so registering another evicts the first
+        assigner.register_trace(ResponseTrace { response_id: 2, ..Default::default() });
+        assert_eq!(
+            assigner.assign(event(1, FeedbackKind::ThumbsUp)),
+            Err(FeedbackError::UnknownResponse(1))
+        );
+    }
+}
@@ -17,7 +17,7 @@
 //!   File:        /src/learning/lib.rs
 //!   Author:      Alex Roussinov
 //!   Created:     2025-12-23
-//!   Updated:     2026-01-11
+//!   Updated:     2026-10-16
 //!
 //!   License:
 //!       Dual-licensed under the MIT and Apache 2.0 licenses.
@@ -25,7 +25,10 @@
 //! ============================================================================
 
 pub mod autodiff;
+pub mod error;
 pub mod trainer;
+pub mod feedback;
+pub mod preferences;
 
 pub use autodiff::*;
 pub use trainer::*;
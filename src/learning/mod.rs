@@ -12,13 +12,16 @@
 //   Core Functions:
 //       • Define the module layout for the Learning crate
 //       • Expose autodiff, training, and reinforcement learning components
+//       • Expose feedback ingestion and credit assignment
+//       • Expose the persistent user preference model
+//       • Expose the typed learning error
 //       • Provide a unified namespace for Astra’s adaptive learning logic
 //       • Establish the foundation for future model‑training pipelines
 //
 //   File:        /src/learning/mod.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-23
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
@@ -26,7 +29,10 @@
 // ============================================================================
 
 pub mod autodiff;
+pub mod error;
 pub mod trainer;
+pub mod feedback;
+pub mod preferences;
 
 pub use autodiff::*;
 pub use trainer::*;
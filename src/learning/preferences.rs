@@ -0,0 +1,309 @@
+// ============================================================================
+//                       ASTRA AGI • USER PREFERENCE MODEL
+//        Persistent Preferences Learned from Structured Feedback
+// ---------------------------------------------------------------------------
+//   Architectural Role:
+//       Component of Astra’s Learning subsystem that condenses the stream of
+//       user feedback into a small, persistent picture of what the user
+//       prefers: how verbose and formal responses should be, which topics
+//       they care about, and how much risk they accept. Goal selection,
+//       planning cost functions, and response generation consult the model
+//       so that Astra's behavior drifts toward what the user rewards.
+//
+//   Core Functions:
+//       • Learn style, verbosity, topic, and risk preferences from feedback
+//       • Interpret explicit preference statements ("keep it short")
+//       • Score goals and action costs against learned preferences
+//       • Choose the register responses are worded in and trim them to
+//         the preferred length
+//       • Persist and restore the model as checksummed JSON, falling back
+//         to the previously saved model if the latest is corrupt
+//       • Rebuild the model from a feedback history, e.g. without one user
+//
+//   File:        /src/learning/preferences.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use super::error::LearningError;
+use crate::integrity::Generations;
+use super::feedback::{FeedbackEvent, FeedbackKind};
+use crate::personality::personality::Register;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Saved models kept at a path, the newest included.
+const SAVED_GENERATIONS: usize = 2;
+
+/// Style characteristics of a response, recorded so feedback can be related to them.
+/// Scalars range from -1.0 to 1.0 except `risk`, which ranges from 0.0 to 1.0.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResponseStyle {
+    /// -1.0 terse .. 1.0 elaborate
+    pub verbosity: f32,
+    /// -1.0 casual .. 1.0 formal
+    pub formality: f32,
+    pub risk: f32,
+    pub topics: Vec<String>,
+}
+
+impl ResponseStyle {
+    /// Rough style estimate of a reply: verbosity from its length and
+    /// formality from exclamations and contractions.
+    pub fn of_reply(reply: &str) -> Self {
+        let words = reply.split_whitespace().count() as f32;
+        let informal_marks = reply.matches('!').count() + reply.matches('\'').count();
+        ResponseStyle {
+            verbosity: ((words - 20.0) / 20.0).clamp(-1.0, 1.0),
+            formality: (0.5 - 0.25 * informal_marks as f32).clamp(-1.0, 1.0),
+            risk: 0.0,
+            topics: Vec::new(),
+        }
+    }
+}
+
+/// How a response should be shaped for the current user.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseGuidance {
+    /// Cap on sentences, when the user prefers brevity
+    pub max_sentences: Option<usize>,
+    /// How formally replies should be worded; replies are generated in it
+    pub register: Register,
+}
+
+/// Aggregated user preferences.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreferenceModel {
+    /// -1.0 terse .. 1.0 elaborate
+    pub verbosity: f32,
+    /// -1.0 casual .. 1.0 formal
+    pub formality: f32,
+    /// 0.0 risk-averse .. 1.0 risk-seeking
+    pub risk_tolerance: f32,
+    /// Topic -> interest, -1.0 .. 1.0
+    pub topics: HashMap<String, f32>,
+    /// Step size for each feedback observation
+    pub learning_rate: f32,
+    /// Number of feedback events incorporated
+    pub observations: u64,
+}
+
+impl Default for PreferenceModel {
+    fn default() -> Self {
+        PreferenceModel {
+            verbosity: 0.0,
+            formality: 0.0,
+            risk_tolerance: 0.5,
+            topics: HashMap::new(),
+            learning_rate: 0.1,
+            observations: 0,
+        }
+    }
+}
+
+impl PreferenceModel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Incorporates a feedback event. `style` describes the response the
+    /// feedback refers to, if known; without it only explicit preference
+    /// statements can be learned from.
+    pub fn observe(&mut self, event: &FeedbackEvent, style: Option<&ResponseStyle>) {
+        let rate = self.learning_rate;
+        match &event.kind {
+            FeedbackKind::Preference { topic, statement, polarity } => {
+                self.apply_statement(statement);
+                if !topic.is_empty() {
+                    let interest = self.topics.entry(topic.to_lowercase()).or_insert(0.0);
+                    *interest = (*interest + polarity.clamp(-1.0, 1.0) * rate * 5.0).clamp(-1.0, 1.0);
+                }
+            }
+            kind => {
+                let Some(style) = style else { return };
+                let reward = kind.reward();
+                // Approved styles pull preferences toward them; rejected ones push away.
+                self.verbosity = nudge(self.verbosity, style.verbosity, reward * rate, -1.0);
+                self.formality = nudge(self.formality, style.formality, reward * rate, -1.0);
+                self.risk_tolerance = nudge(self.risk_tolerance, style.risk, reward * rate, 0.0);
+                for topic in &style.topics {
+                    let interest = self.topics.entry(topic.to_lowercase()).or_insert(0.0);
+                    *interest = (*interest + reward * rate).clamp(-1.0, 1.0);
+                }
+            }
+        }
+        self.observations += 1;
+    }
+
+    /// Mean interest over known topics mentioned in `text`; 0.0 if none are.
+    pub fn topic_affinity(&self, text: &str) -> f32 {
+        let text = text.to_lowercase();
+        let matches: Vec<f32> = self
+            .topics
+            .iter()
+            .filter(|(topic, _)| text.contains(topic.as_str()))
+            .map(|(_, interest)| *interest)
+            .collect();
+        if matches.is_empty() {
+            0.0
+        } else {
+            matches.iter().sum::<f32>() / matches.len() as f32
+        }
+    }
+
+    /// Adjusts an action's cost for the user's risk tolerance and topic
+    /// interests. Risk above the tolerance is penalized; liked topics are
+    /// slightly cheaper and disliked ones dearer.
+    pub fn adjusted_cost(&self, base_cost: f32, risk: f32, description: &str) -> f32 {
+        let risk_penalty = (risk - self.risk_tolerance).max(0.0) * 2.0;
+        let topic_factor = 1.0 - 0.25 * self.topic_affinity(description);
+        (base_cost * (1.0 + risk_penalty) * topic_factor).max(0.0)
+    }
+
+    /// Guidance for shaping a response to this user.
+    pub fn response_guidance(&self) -> ResponseGuidance {
+        ResponseGuidance {
+            max_sentences: if self.verbosity < -0.3 { Some(1) } else if self.verbosity < 0.0 { Some(3) } else { None },
+            register: if self.formality > 0.3 { Register::Formal } else { Register::Casual },
+        }
+    }
+
+    /// Shortens a generated reply to the preferred length. The reply is
+    /// expected to be worded in the guidance's register already.
+    pub fn shape_response(&self, reply: &str) -> String {
+        match self.response_guidance().max_sentences {
+            Some(limit) => {
+                let sentences: Vec<&str> = reply.split_inclusive(['.', '!', '?']).collect();
+                sentences.into_iter().take(limit).collect::<String>().trim().to_string()
+            }
+            None => reply.to_string(),
+        }
+    }
+
+    /// Learns a fresh model from `events`, oldest first, with the given
+    /// learning rate. `style_of` supplies the style of the response each
+    /// event refers to, where it is still known.
+    pub fn replay<'a>(
+        events: impl IntoIterator<Item = &'a FeedbackEvent>,
+        learning_rate: f32,
+        style_of: impl Fn(&FeedbackEvent) -> Option<ResponseStyle>,
+    ) -> Self {
+        let mut model = PreferenceModel { learning_rate, ..Default::default() };
+        for event in events {
+            model.observe(event, style_of(event).as_ref());
+        }
+        model
+    }
+
+    /// Saves the model as JSON sealed with a checksum, keeping the
+    /// previously saved model beside it as `<path>.1`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), LearningError> {
+        Generations::new(path.as_ref(), SAVED_GENERATIONS).write(&serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Loads a model saved with `save`, or the one saved before it if the
+    /// latest is corrupt; models passed over are logged.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LearningError> {
+        let recovered = Generations::new(path.as_ref(), SAVED_GENERATIONS)
+            .load(|bytes| serde_json::from_slice(bytes))?;
+        recovered.warn_skipped();
+        Ok(recovered.value)
+    }
+
+    fn apply_statement(&mut self, statement: &str) {
+        let statement = statement.to_lowercase();
+        let step = self.learning_rate * 5.0;
+        let has = |words: &[&str]| words.iter().any(|w| statement.contains(w));
+
+        if has(&["short", "brief", "concise", "less detail", "shorter"]) {
+            self.verbosity = (self.verbosity - step).clamp(-1.0, 1.0);
+        }
+        if has(&["more detail", "detailed", "elaborate", "longer", "in depth"]) {
+            self.verbosity = (self.verbosity + step).clamp(-1.0, 1.0);
+        }
+        if has(&["formal", "professional", "polite"]) {
+            self.formality = (self.formality + step).clamp(-1.0, 1.0);
+        }
+        if has(&["casual", "relaxed", "informal"]) {
+            self.formality = (self.formality - step).clamp(-1.0, 1.0);
+        }
+        if has(&["careful", "cautious", "safe", "conservative"]) {
+            self.risk_tolerance = (self.risk_tolerance - step).clamp(0.0, 1.0);
+        }
+        if has(&["bold", "adventurous", "risky", "aggressive"]) {
+            self.risk_tolerance = (self.risk_tolerance + step).clamp(0.0, 1.0);
+        }
+    }
+}
+
+/// Moves `current` toward `target` by `step` (away when negative), clamped to [min, 1.0].
+fn nudge(current: f32, target: f32, step: f32, min: f32) -> f32 {
+    (current + (target - current) * step).clamp(min, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: FeedbackKind) -> FeedbackEvent {
+        FeedbackEvent { response_id: 1, kind, user_id: None, timestamp: 0 }
+    }
+
+    #[test]
+    fn test_learns_from_statements_and_ratings() {
+        let mut model = PreferenceModel::new();
+        model.observe(
+            &event(FeedbackKind::Preference {
+                topic: "astronomy".to_string(),
+                statement: "Please keep answers short".to_string(),
+                polarity: 1.0,
+            }),
+            None,
+        );
+        assert!(model.verbosity < 0.0);
+        assert!(model.topic_affinity("Tell me about Astronomy") > 0.0);
+
+        let long_style = ResponseStyle { verbosity: 1.0, ..Default::default() };
+        let before = model.verbosity;
+        model.observe(&event(FeedbackKind::ThumbsDown), Some(&long_style));
+        assert!(model.verbosity < before);
+        assert_eq!(model.observations, 2);
+    }
+
+    #[test]
+    fn test_costs_and_response_shaping() {
+        let mut model = PreferenceModel::new();
+        model.risk_tolerance = 0.2;
+        assert!(model.adjusted_cost(1.0, 0.9, "sell stocks") > model.adjusted_cost(1.0, 0.1, "sell stocks"));
+
+        model.verbosity = -0.5;
+        model.formality = 0.5;
+        assert_eq!(model.response_guidance().register, Register::Formal);
+        assert_eq!(model.shape_response("That's fascinating! Tell me more."), "That's fascinating!");
+    }
+
+    #[test]
+    fn test_corrupt_save_falls_back_to_the_previous_one() {
+        let dir = std::env::temp_dir().join(format!("astra-preferences-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("preferences.json");
+        let mut model = PreferenceModel::new();
+        model.verbosity = -0.5;
+        model.save(&path).unwrap();
+        model.verbosity = 0.5;
+        model.save(&path).unwrap();
+        assert_eq!(PreferenceModel::load(&path).unwrap().verbosity, 0.5);
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() / 2]).unwrap();
+        assert_eq!(PreferenceModel::load(&path).unwrap().verbosity, -0.5);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
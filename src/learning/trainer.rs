@@ -18,15 +18,15 @@
 //   File:        /src/learning/trainer.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-23
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
-use anyhow::Result;
 use crate::autodiff::Variable;
+use super::error::LearningError;
 use tokio::time::{sleep, Duration};
 
 /// Represents a machine learning model with trainable parameters.
@@ -71,7 +71,7 @@ impl Trainer {
     ///
     /// * `model` - Mutable reference to the model to train.
     /// * `data` - Slice of input variables representing training data.
-    pub async fn train(&mut self, model: &mut Model, data: &[Variable]) -> Result<()> {
+    pub async fn train(&mut self, model: &mut Model, data: &[Variable]) -> Result<(), LearningError> {
         for epoch in 0..10 {
             println!("Starting epoch {}", epoch + 1);
             for input in data {
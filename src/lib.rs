@@ -2,22 +2,55 @@
 //  Astra AGI
 //  File: lib.rs
 //
-//  Description: Main library entry point
+//  Description: Main library entry point. Without the `std` feature only
+//  the cognitive core (core) is built, on alloc alone, plus its browser
+//  bindings (wasm) if the `wasm` feature is enabled.
 //
 //  Author:      Alex Roussinov
 //  Created:     2025-12-23
-//  Updated:     2025-12-23
+//  Updated:     2026-10-16
 //
 //  //  This file is dual licensed under the MIT and Apache 2.0 licenses.
 //  Please see the root level LICENSE-MIT and LICENSE-APACHE files for details.
 // =============================================================================
 
+#![cfg_attr(not(any(feature = "std", feature = "wasm")), no_std)]
+
+extern crate alloc;
+
+pub mod core;
+
+#[cfg(feature = "std")]
 pub mod runtime;
+#[cfg(feature = "std")]
 pub mod knowledge;
+#[cfg(feature = "std")]
 pub mod learning;
+#[cfg(feature = "std")]
 pub mod planning;
+#[cfg(feature = "std")]
 pub mod interfaces;
-
-pub mod learning;
-pub mod planning;
-pub mod interfaces;
+#[cfg(feature = "std")]
+pub mod cognition;
+#[cfg(feature = "std")]
+pub mod memory;
+#[cfg(feature = "std")]
+pub mod emotion;
+#[cfg(feature = "std")]
+pub mod personality;
+#[cfg(feature = "std")]
+pub mod web_crawler;
+#[cfg(feature = "std")]
+pub mod time;
+#[cfg(feature = "std")]
+pub mod random;
+#[cfg(feature = "std")]
+pub mod integrity;
+#[cfg(feature = "std")]
+pub mod migration;
+#[cfg(feature = "std")]
+pub mod metadata;
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -0,0 +1,769 @@
+// ============================================================================
+//                     ASTRA AGI • COMPRESSED EVENT ARCHIVES
+//        On-Disk Columnar Blocks for Years of Autobiographical History
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Where archived narrative events go once even the in-memory archive
+//       would grow too large. An archive file holds events in blocks of a
+//       few thousand, each laid out column by column (ids, timestamps,
+//       event types, descriptions, metadata, links) and compressed as one
+//       zstd frame; columns of similar values compress far better than
+//       whole events would. A footer indexes every block by its time span,
+//       id span, and the event types in it, so a range scan decompresses
+//       only the blocks that can hold a match and history stays queryable
+//       without loading it all. Every block and the footer carry a CRC-32,
+//       so a damaged block is reported when read instead of decoded.
+//
+//   Layout:
+//       magic · block* · footer · footer CRC-32 (u32 LE) · footer offset (u64 LE) · magic
+//   Version 1 files, without the checksums, are still read.
+//
+//   Core Functions:
+//       • Write events into compressed columnar blocks
+//       • Index blocks by time, id, and event type in a footer
+//       • Verify block and footer checksums as they are read
+//       • Scan a time range, optionally of one event type
+//       • Drill down from a summary to its members on disk
+//       • Move old events from the in-memory archive to disk, dropping
+//         them from memory only once their file is written whole
+//       • Rewrite archive files without one user's events
+//   Blocks decompress through a bounded reader, so a forged block cannot
+//   inflate past `MAX_BLOCK_BYTES`.
+//
+//   File:        /src/memory/archive_file.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use std::collections::{BTreeSet, HashSet};
+use std::fs::{self, File};
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::ops::{Bound, RangeBounds};
+use std::path::{Path, PathBuf};
+
+use super::error::ArchiveError;
+use super::narrative_memory::{EventId, EventLink, LinkRelation, LinkTarget, NarrativeEvent};
+use crate::integrity::crc32;
+use crate::knowledge::belief_ledger::FactKey;
+use crate::metadata::Metadata;
+use crate::time::AstraTime;
+
+/// Opens and closes every archive file; the last byte is the format version.
+const MAGIC: &[u8; 8] = b"ASTRAEV2";
+
+/// Magic of archives written before blocks had checksums.
+const MAGIC_V1: &[u8; 8] = b"ASTRAEV1";
+
+/// Events per block unless the writer is told otherwise.
+pub const DEFAULT_BLOCK_EVENTS: usize = 4096;
+
+/// zstd level blocks are compressed at.
+const COMPRESSION_LEVEL: i32 = 9;
+
+/// Largest a block may decompress to; larger blocks are reported corrupt.
+pub const MAX_BLOCK_BYTES: u64 = 256 << 20;
+
+/// Extension of archive files spilled into a directory.
+pub const ARCHIVE_EXTENSION: &str = "events";
+
+/// Where and when the in-memory archive is spilled to disk.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpillPolicy {
+    /// Directory the archive files go in
+    pub dir: PathBuf,
+    /// Events archived before this long ago are spilled
+    pub older_than_secs: u64,
+    /// Spill only once this many events are due, so files hold whole blocks
+    pub min_events: usize,
+}
+
+impl SpillPolicy {
+    pub fn new(dir: impl Into<PathBuf>, older_than_secs: u64) -> Self {
+        SpillPolicy { dir: dir.into(), older_than_secs, min_events: DEFAULT_BLOCK_EVENTS }
+    }
+}
+
+/// Where a block is and what it holds.
+#[derive(Debug, Clone, PartialEq)]
+struct BlockIndex {
+    offset: u64,
+    length: u64,
+    /// CRC-32 of the compressed block; `None` in version 1 files
+    checksum: Option<u32>,
+    events: usize,
+    first: AstraTime,
+    last: AstraTime,
+    min_id: EventId,
+    max_id: EventId,
+    event_types: BTreeSet<String>,
+}
+
+impl BlockIndex {
+    fn overlaps(&self, range: &impl RangeBounds<AstraTime>) -> bool {
+        let after_start = match range.start_bound() {
+            Bound::Included(start) => self.last >= *start,
+            Bound::Excluded(start) => self.last > *start,
+            Bound::Unbounded => true,
+        };
+        let before_end = match range.end_bound() {
+            Bound::Included(end) => self.first <= *end,
+            Bound::Excluded(end) => self.first < *end,
+            Bound::Unbounded => true,
+        };
+        after_start && before_end
+    }
+}
+
+/// Writes events to a new archive file. Nothing is readable until
+/// `finish` writes the footer.
+pub struct ArchiveWriter<W: Write> {
+    out: W,
+    written: u64,
+    block_events: usize,
+    pending: Vec<NarrativeEvent>,
+    blocks: Vec<BlockIndex>,
+}
+
+impl<W: Write> ArchiveWriter<W> {
+    pub fn new(mut out: W) -> Result<Self, ArchiveError> {
+        out.write_all(MAGIC)?;
+        Ok(ArchiveWriter {
+            out,
+            written: MAGIC.len() as u64,
+            block_events: DEFAULT_BLOCK_EVENTS,
+            pending: Vec::new(),
+            blocks: Vec::new(),
+        })
+    }
+
+    /// Sets how many events go in a block. Smaller blocks make narrow scans
+    /// cheaper and compress worse.
+    pub fn with_block_events(mut self, block_events: usize) -> Self {
+        self.block_events = block_events.max(1);
+        self
+    }
+
+    pub fn append(&mut self, event: NarrativeEvent) -> Result<(), ArchiveError> {
+        self.pending.push(event);
+        if self.pending.len() >= self.block_events {
+            self.flush_block()?;
+        }
+        Ok(())
+    }
+
+    /// Writes the last block and the footer, returning the output.
+    pub fn finish(mut self) -> Result<W, ArchiveError> {
+        self.flush_block()?;
+        let mut footer = Vec::new();
+        put_varint(&mut footer, self.blocks.len() as u64);
+        for block in &self.blocks {
+            put_varint(&mut footer, block.offset);
+            put_varint(&mut footer, block.length);
+            put_varint(&mut footer, u64::from(block.checksum.unwrap_or_default()));
+            put_varint(&mut footer, block.events as u64);
+            put_varint(&mut footer, block.first.as_millis());
+            put_varint(&mut footer, block.last.as_millis());
+            put_varint(&mut footer, block.min_id);
+            put_varint(&mut footer, block.max_id);
+            put_varint(&mut footer, block.event_types.len() as u64);
+            for event_type in &block.event_types {
+                put_str(&mut footer, event_type);
+            }
+        }
+        self.out.write_all(&footer)?;
+        self.out.write_all(&crc32(&footer).to_le_bytes())?;
+        self.out.write_all(&self.written.to_le_bytes())?;
+        self.out.write_all(MAGIC)?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn flush_block(&mut self) -> Result<(), ArchiveError> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let mut events = std::mem::take(&mut self.pending);
+        events.sort_by_key(|e| (e.timestamp, e.id));
+        let compressed = zstd::encode_all(&encode_columns(&events)[..], COMPRESSION_LEVEL)?;
+        self.out.write_all(&compressed)?;
+
+        self.blocks.push(BlockIndex {
+            offset: self.written,
+            length: compressed.len() as u64,
+            checksum: Some(crc32(&compressed)),
+            events: events.len(),
+            first: events[0].timestamp,
+            last: events[events.len() - 1].timestamp,
+            min_id: events.iter().map(|e| e.id).min().unwrap_or(0),
+            max_id: events.iter().map(|e| e.id).max().unwrap_or(0),
+            event_types: events.iter().map(|e| e.event_type.clone()).collect(),
+        });
+        self.written += compressed.len() as u64;
+        Ok(())
+    }
+}
+
+/// Reads an archive file, decompressing only the blocks a query needs.
+pub struct ArchiveReader<R: Read + Seek> {
+    input: R,
+    blocks: Vec<BlockIndex>,
+    blocks_read: usize,
+}
+
+impl<R: Read + Seek> ArchiveReader<R> {
+    /// Reads the footer; blocks are left on disk until scanned.
+    pub fn open(mut input: R) -> Result<Self, ArchiveError> {
+        let end = input.seek(SeekFrom::End(0))?;
+        let mut head = [0u8; 8];
+        input.seek(SeekFrom::Start(0))?;
+        if end < 8 || input.read_exact(&mut head).is_err() {
+            return Err(ArchiveError::Corrupt("file too short".to_string()));
+        }
+        let checked = match &head {
+            head if head == MAGIC => true,
+            head if head == MAGIC_V1 => false,
+            _ => return Err(ArchiveError::Corrupt("not an event archive".to_string())),
+        };
+        // Footer checksum, if any, footer offset, and magic
+        let tail_len: u64 = if checked { 20 } else { 16 };
+        if end < head.len() as u64 + tail_len {
+            return Err(ArchiveError::Corrupt("file too short".to_string()));
+        }
+        let mut tail = vec![0u8; tail_len as usize];
+        input.seek(SeekFrom::Start(end - tail_len))?;
+        input.read_exact(&mut tail)?;
+        let (checksum, tail) = tail.split_at(tail.len() - 16);
+        if tail[8..] != head {
+            return Err(ArchiveError::Corrupt("archive end is missing; the file may be cut short".to_string()));
+        }
+
+        let footer_offset = u64::from_le_bytes(tail[..8].try_into().expect("eight bytes"));
+        let footer_end = end - tail_len;
+        if footer_offset < MAGIC.len() as u64 || footer_offset > footer_end {
+            return Err(ArchiveError::Corrupt("footer out of bounds".to_string()));
+        }
+        let mut footer = vec![0u8; (footer_end - footer_offset) as usize];
+        input.seek(SeekFrom::Start(footer_offset))?;
+        input.read_exact(&mut footer)?;
+        if checked && crc32(&footer).to_le_bytes()[..] != *checksum {
+            return Err(ArchiveError::Corrupt("footer fails its checksum".to_string()));
+        }
+
+        let mut cursor = Cursor::new(&footer);
+        let count = cursor.varint()? as usize;
+        let mut blocks = Vec::with_capacity(count.min(footer.len()));
+        for _ in 0..count {
+            let offset = cursor.varint()?;
+            let length = cursor.varint()?;
+            let checksum = if checked { Some(cursor.varint()? as u32) } else { None };
+            let events = cursor.varint()? as usize;
+            let first = AstraTime::from_millis(cursor.varint()?);
+            let last = AstraTime::from_millis(cursor.varint()?);
+            let min_id = cursor.varint()?;
+            let max_id = cursor.varint()?;
+            let types = cursor.varint()? as usize;
+            let event_types = (0..types).map(|_| cursor.string()).collect::<Result<_, _>>()?;
+            if offset.checked_add(length).is_none_or(|block_end| block_end > footer_offset) {
+                return Err(ArchiveError::Corrupt("block out of bounds".to_string()));
+            }
+            blocks.push(BlockIndex { offset, length, checksum, events, first, last, min_id, max_id, event_types });
+        }
+        Ok(ArchiveReader { input, blocks, blocks_read: 0 })
+    }
+
+    /// Events in the archive.
+    pub fn len(&self) -> usize {
+        self.blocks.iter().map(|b| b.events).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Blocks decompressed by scans so far.
+    pub fn blocks_read(&self) -> usize {
+        self.blocks_read
+    }
+
+    /// Time span of the archive, if it holds any events.
+    pub fn time_span(&self) -> Option<(AstraTime, AstraTime)> {
+        let first = self.blocks.iter().map(|b| b.first).min()?;
+        let last = self.blocks.iter().map(|b| b.last).max()?;
+        Some((first, last))
+    }
+
+    /// Events recorded in `range`, of `event_type` if given, oldest first.
+    pub fn scan(
+        &mut self,
+        range: impl RangeBounds<AstraTime>,
+        event_type: Option<&str>,
+    ) -> Result<Vec<NarrativeEvent>, ArchiveError> {
+        let wanted: Vec<BlockIndex> = self
+            .blocks
+            .iter()
+            .filter(|b| b.overlaps(&range) && event_type.is_none_or(|t| b.event_types.contains(t)))
+            .cloned()
+            .collect();
+
+        let mut events = Vec::new();
+        for block in &wanted {
+            events.extend(
+                self.read_block(block)?
+                    .into_iter()
+                    .filter(|e| range.contains(&e.timestamp) && event_type.is_none_or(|t| e.event_type == t)),
+            );
+        }
+        events.sort_by_key(|e| (e.timestamp, e.id));
+        Ok(events)
+    }
+
+    /// The archived events `summary` stands for, oldest first. Like
+    /// `EventArchive::drill_down`, but reading only blocks whose ids could
+    /// include them.
+    pub fn drill_down(&mut self, summary: &NarrativeEvent) -> Result<Vec<NarrativeEvent>, ArchiveError> {
+        let ids: HashSet<EventId> = summary
+            .links
+            .iter()
+            .filter(|link| link.relation == LinkRelation::Summarizes)
+            .filter_map(|link| match link.target {
+                LinkTarget::Event(id) => Some(id),
+                _ => None,
+            })
+            .collect();
+        let wanted: Vec<BlockIndex> = self
+            .blocks
+            .iter()
+            .filter(|b| ids.iter().any(|id| (b.min_id..=b.max_id).contains(id)))
+            .cloned()
+            .collect();
+
+        let mut events = Vec::new();
+        for block in &wanted {
+            events.extend(self.read_block(block)?.into_iter().filter(|e| ids.contains(&e.id)));
+        }
+        events.sort_by_key(|e| (e.timestamp, e.id));
+        Ok(events)
+    }
+
+    fn read_block(&mut self, block: &BlockIndex) -> Result<Vec<NarrativeEvent>, ArchiveError> {
+        let mut compressed = vec![0u8; block.length as usize];
+        self.input.seek(SeekFrom::Start(block.offset))?;
+        self.input.read_exact(&mut compressed)?;
+        if block.checksum.is_some_and(|checksum| crc32(&compressed) != checksum) {
+            return Err(ArchiveError::Corrupt(format!("block at {} fails its checksum", block.offset)));
+        }
+        let mut columns = Vec::new();
+        zstd::stream::read::Decoder::new(&compressed[..])?.take(MAX_BLOCK_BYTES + 1).read_to_end(&mut columns)?;
+        if columns.len() as u64 > MAX_BLOCK_BYTES {
+            return Err(ArchiveError::Corrupt(format!("block at {} decompresses past {} bytes", block.offset, MAX_BLOCK_BYTES)));
+        }
+        self.blocks_read += 1;
+        let events = decode_columns(&columns)?;
+        if events.len() != block.events {
+            return Err(ArchiveError::Corrupt(format!("block at {} holds {} events, not {}", block.offset, events.len(), block.events)));
+        }
+        Ok(events)
+    }
+}
+
+/// Deletes the events concerning `user_id` from the archive file at `path`,
+/// returning how many were deleted. The file is rewritten through a
+/// temporary file, and removed if nothing else is left in it.
+pub fn forget_user_in_file(path: &Path, user_id: &str) -> Result<usize, ArchiveError> {
+    let events = ArchiveReader::open(File::open(path)?)?.scan(.., None)?;
+    let before = events.len();
+    let kept: Vec<NarrativeEvent> = events.into_iter().filter(|e| e.user_id().as_deref() != Some(user_id)).collect();
+    let forgotten = before - kept.len();
+    if kept.is_empty() {
+        fs::remove_file(path)?;
+    } else if forgotten > 0 {
+        write_file(path, kept)?;
+    }
+    Ok(forgotten)
+}
+
+/// Archive files in `dir`, oldest first; none if it does not exist.
+pub fn archive_files(dir: &Path) -> Result<Vec<PathBuf>, ArchiveError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut files = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_file() && path.extension().is_some_and(|ext| ext == ARCHIVE_EXTENSION) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Writes `events` as an archive at `path`, replacing it only once the new
+/// file is complete on disk.
+pub(super) fn write_file(path: &Path, mut events: Vec<NarrativeEvent>) -> Result<(), ArchiveError> {
+    events.sort_by_key(|e| (e.timestamp, e.id));
+    let partial = path.with_extension("partial");
+    let result = (|| -> Result<(), ArchiveError> {
+        let mut writer = ArchiveWriter::new(BufWriter::new(File::create(&partial)?))?;
+        for event in events {
+            writer.append(event)?;
+        }
+        let file = writer.finish()?.into_inner().map_err(|e| e.into_error())?;
+        file.sync_all()?;
+        fs::rename(&partial, path)?;
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&partial);
+    }
+    result
+}
+
+// ---------------------------------------------------------------------------
+//   Column encoding
+// ---------------------------------------------------------------------------
+
+const RELATIONS: [LinkRelation; 4] =
+    [LinkRelation::CausedBy, LinkRelation::About, LinkRelation::RelatedTo, LinkRelation::Summarizes];
+
+fn encode_columns(events: &[NarrativeEvent]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    put_varint(&mut buf, events.len() as u64);
+
+    // Ids and timestamps as differences from the previous event
+    let mut previous = 0u64;
+    for event in events {
+        put_varint(&mut buf, zigzag(event.id.wrapping_sub(previous) as i64));
+        previous = event.id;
+    }
+    let mut previous = 0u64;
+    for event in events {
+        put_varint(&mut buf, zigzag(event.timestamp.as_millis().wrapping_sub(previous) as i64));
+        previous = event.timestamp.as_millis();
+    }
+
+    // Event types through a dictionary
+    let mut types: Vec<&str> = Vec::new();
+    let codes: Vec<usize> = events
+        .iter()
+        .map(|e| match types.iter().position(|t| *t == e.event_type) {
+            Some(code) => code,
+            None => {
+                types.push(&e.event_type);
+                types.len() - 1
+            }
+        })
+        .collect();
+    put_varint(&mut buf, types.len() as u64);
+    for event_type in &types {
+        put_str(&mut buf, event_type);
+    }
+    for code in codes {
+        put_varint(&mut buf, code as u64);
+    }
+
+    for event in events {
+        put_str(&mut buf, &event.description);
+    }
+    for event in events {
+        match &event.metadata {
+            Some(metadata) => {
+                let json = serde_json::to_string(metadata).expect("metadata serializes");
+                put_varint(&mut buf, json.len() as u64 + 1);
+                buf.extend_from_slice(json.as_bytes());
+            }
+            None => put_varint(&mut buf, 0),
+        }
+    }
+
+    for event in events {
+        put_varint(&mut buf, event.links.len() as u64);
+        for link in &event.links {
+            let relation = RELATIONS.iter().position(|r| *r == link.relation).expect("every relation is listed");
+            buf.push(relation as u8);
+            match &link.target {
+                LinkTarget::Event(id) => {
+                    buf.push(0);
+                    put_varint(&mut buf, *id);
+                }
+                LinkTarget::Intent(id) => {
+                    buf.push(1);
+                    put_varint(&mut buf, *id);
+                }
+                LinkTarget::Fact(key) => {
+                    buf.push(2);
+                    put_varint(&mut buf, key.subject);
+                    put_str(&mut buf, &key.predicate);
+                    put_str(&mut buf, &key.object);
+                }
+            }
+        }
+    }
+    buf
+}
+
+fn decode_columns(bytes: &[u8]) -> Result<Vec<NarrativeEvent>, ArchiveError> {
+    let mut cursor = Cursor::new(bytes);
+    let count = cursor.varint()? as usize;
+    // Every event takes at least a byte in each column
+    if count > bytes.len() {
+        return Err(ArchiveError::Corrupt("event count exceeds block size".to_string()));
+    }
+
+    let mut ids = Vec::with_capacity(count);
+    let mut previous = 0u64;
+    for _ in 0..count {
+        previous = previous.wrapping_add(unzigzag(cursor.varint()?) as u64);
+        ids.push(previous);
+    }
+    let mut timestamps = Vec::with_capacity(count);
+    let mut previous = 0u64;
+    for _ in 0..count {
+        previous = previous.wrapping_add(unzigzag(cursor.varint()?) as u64);
+        timestamps.push(AstraTime::from_millis(previous));
+    }
+
+    let type_count = cursor.varint()? as usize;
+    let types: Vec<String> = (0..type_count).map(|_| cursor.string()).collect::<Result<_, _>>()?;
+    let mut event_types = Vec::with_capacity(count);
+    for _ in 0..count {
+        let code = cursor.varint()? as usize;
+        let event_type = types.get(code).ok_or_else(|| ArchiveError::Corrupt(format!("unknown event type {}", code)))?;
+        event_types.push(event_type.clone());
+    }
+
+    let descriptions: Vec<String> = (0..count).map(|_| cursor.string()).collect::<Result<_, _>>()?;
+    let mut metadata = Vec::with_capacity(count);
+    for _ in 0..count {
+        metadata.push(match cursor.varint()? {
+            0 => None,
+            // JSON now; archives from earlier releases hold any text
+            len => Some(Metadata::from_legacy_text(&cursor.string_of(len as usize - 1)?)),
+        });
+    }
+
+    let mut links = Vec::with_capacity(count);
+    for _ in 0..count {
+        let link_count = cursor.varint()? as usize;
+        let mut event_links = Vec::with_capacity(link_count.min(bytes.len()));
+        for _ in 0..link_count {
+            let relation = *RELATIONS
+                .get(cursor.byte()? as usize)
+                .ok_or_else(|| ArchiveError::Corrupt("unknown link relation".to_string()))?;
+            let target = match cursor.byte()? {
+                0 => LinkTarget::Event(cursor.varint()?),
+                1 => LinkTarget::Intent(cursor.varint()?),
+                2 => LinkTarget::Fact(FactKey {
+                    subject: cursor.varint()?,
+                    predicate: cursor.string()?,
+                    object: cursor.string()?,
+                }),
+                tag => return Err(ArchiveError::Corrupt(format!("unknown link target {}", tag))),
+            };
+            event_links.push(EventLink { relation, target });
+        }
+        links.push(event_links);
+    }
+
+    let mut events = Vec::with_capacity(count);
+    let columns = ids.into_iter().zip(timestamps).zip(event_types).zip(descriptions).zip(metadata).zip(links);
+    for (((((id, timestamp), event_type), description), metadata), links) in columns {
+        events.push(NarrativeEvent { id, timestamp, event_type, description, metadata, links });
+    }
+    Ok(events)
+}
+
+fn zigzag(n: i64) -> u64 {
+    ((n << 1) ^ (n >> 63)) as u64
+}
+
+fn unzigzag(n: u64) -> i64 {
+    ((n >> 1) as i64) ^ -((n & 1) as i64)
+}
+
+fn put_varint(buf: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        buf.push((n as u8) | 0x80);
+        n >>= 7;
+    }
+    buf.push(n as u8);
+}
+
+fn put_str(buf: &mut Vec<u8>, s: &str) {
+    put_varint(buf, s.len() as u64);
+    buf.extend_from_slice(s.as_bytes());
+}
+
+/// Reads varints and strings off a byte slice, failing on truncation.
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    at: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Cursor { bytes, at: 0 }
+    }
+
+    fn byte(&mut self) -> Result<u8, ArchiveError> {
+        let byte = *self.bytes.get(self.at).ok_or_else(|| ArchiveError::Corrupt("truncated".to_string()))?;
+        self.at += 1;
+        Ok(byte)
+    }
+
+    fn varint(&mut self) -> Result<u64, ArchiveError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(ArchiveError::Corrupt("varint too long".to_string()))
+    }
+
+    fn string(&mut self) -> Result<String, ArchiveError> {
+        let len = self.varint()? as usize;
+        self.string_of(len)
+    }
+
+    fn string_of(&mut self, len: usize) -> Result<String, ArchiveError> {
+        let end = self.at.checked_add(len).filter(|end| *end <= self.bytes.len());
+        let end = end.ok_or_else(|| ArchiveError::Corrupt("truncated".to_string()))?;
+        let s = std::str::from_utf8(&self.bytes[self.at..end]).map_err(|_| ArchiveError::Corrupt("invalid UTF-8".to_string()))?;
+        self.at = end;
+        Ok(s.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::summary_tiers::{EventArchive, MemoryStore};
+
+    fn event(id: EventId, secs: u64, event_type: &str) -> NarrativeEvent {
+        NarrativeEvent {
+            id,
+            timestamp: AstraTime::from_secs(secs),
+            event_type: event_type.to_string(),
+            description: format!("Event {} of type {}", id, event_type),
+            metadata: id.is_multiple_of(3).then(|| Metadata::from([("n", id)])),
+            links: if id.is_multiple_of(5) {
+                vec![
+                    EventLink::caused_by(id - 1),
+                    EventLink::about(LinkTarget::Fact(FactKey { subject: id, predicate: "saw".into(), object: "it".into() })),
+                ]
+            } else {
+                Vec::new()
+            },
+        }
+    }
+
+    #[test]
+    fn test_range_scans_read_only_the_blocks_they_need() {
+        let types = ["tick", "tick", "tick", "error"];
+        let events: Vec<NarrativeEvent> = (1..=1000).map(|id| event(id, 60 * id, types[id as usize % 4])).collect();
+
+        let mut writer = ArchiveWriter::new(std::io::Cursor::new(Vec::new())).unwrap().with_block_events(100);
+        for e in &events {
+            writer.append(e.clone()).unwrap();
+        }
+        let file = writer.finish().unwrap();
+        let raw_size: usize = events.iter().map(|e| e.description.len() + e.event_type.len() + 16).sum();
+        assert!(file.get_ref().len() < raw_size / 3);
+
+        let mut reader = ArchiveReader::open(file).unwrap();
+        assert_eq!((reader.len(), reader.block_count()), (1000, 10));
+        assert_eq!(reader.time_span(), Some((AstraTime::from_secs(60), AstraTime::from_secs(60_000))));
+
+        // Events 250..=260 live in the third block alone
+        let found = reader.scan(AstraTime::from_secs(60 * 250)..=AstraTime::from_secs(60 * 260), None).unwrap();
+        assert_eq!(found.iter().map(|e| e.id).collect::<Vec<_>>(), (250..=260).collect::<Vec<_>>());
+        assert_eq!(found[5].links, events[254].links);
+        assert_eq!(found[0].metadata, events[249].metadata);
+        assert_eq!(reader.blocks_read(), 1);
+
+        let errors = reader.scan(.., Some("error")).unwrap();
+        assert_eq!(errors.len(), 250);
+        assert!(reader.scan(.., Some("unknown")).unwrap().is_empty());
+        assert_eq!(reader.blocks_read(), 11);
+
+        let summary = NarrativeEvent {
+            links: [7, 712].iter().map(|&id| EventLink { relation: LinkRelation::Summarizes, target: LinkTarget::Event(id) }).collect(),
+            ..event(2000, 0, "memory_summary")
+        };
+        let members = reader.drill_down(&summary).unwrap();
+        assert_eq!(members.iter().map(|e| e.id).collect::<Vec<_>>(), vec![7, 712]);
+        assert_eq!(reader.blocks_read(), 13);
+
+        assert!(matches!(
+            ArchiveReader::open(std::io::Cursor::new(b"not an archive at all".to_vec())),
+            Err(ArchiveError::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn test_damaged_blocks_are_caught_by_their_checksums() {
+        let mut writer = ArchiveWriter::new(std::io::Cursor::new(Vec::new())).unwrap().with_block_events(10);
+        for id in 1..=30 {
+            writer.append(event(id, 60 * id, "tick")).unwrap();
+        }
+        let mut bytes = writer.finish().unwrap().into_inner();
+
+        // Flip a byte inside the first block; the others still read
+        bytes[MAGIC.len() + 4] ^= 0xff;
+        let mut reader = ArchiveReader::open(std::io::Cursor::new(bytes.clone())).unwrap();
+        let later = reader.scan(AstraTime::from_secs(60 * 11).., None).unwrap();
+        assert_eq!(later.len(), 20);
+        assert!(matches!(reader.scan(.., None), Err(ArchiveError::Corrupt(message)) if message.contains("checksum")));
+
+        // A damaged footer fails to open, as does a file cut short
+        let footer = bytes.len() - 21;
+        bytes[footer] ^= 0xff;
+        assert!(ArchiveReader::open(std::io::Cursor::new(bytes.clone())).is_err());
+        bytes.truncate(bytes.len() - 5);
+        assert!(matches!(ArchiveReader::open(std::io::Cursor::new(bytes)), Err(ArchiveError::Corrupt(_))));
+    }
+
+    #[test]
+    fn test_spill_keeps_events_until_written_and_files_forget_users() {
+        let dir = std::env::temp_dir().join(format!("astra-archive-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut archive = EventArchive::new();
+        for id in 1..=20 {
+            let mut e = event(id, 60 * id, "tick");
+            e.metadata = Some(Metadata::from([("user_id", if id % 2 == 0 { "ana" } else { "ben" })]));
+            archive.restore(e);
+        }
+
+        // No directory to write into: nothing leaves memory
+        let path = dir.join(format!("0001.{}", ARCHIVE_EXTENSION));
+        assert!(archive.spill(AstraTime::from_secs(60 * 11), &path).is_err());
+        assert_eq!(archive.len(), 20);
+
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(archive.spill(AstraTime::from_secs(60 * 11), &path).unwrap(), 10);
+        assert_eq!(archive.len(), 10);
+        assert_eq!(archive_files(&dir).unwrap(), vec![path.clone()]);
+
+        assert_eq!(forget_user_in_file(&path, "ana").unwrap(), 5);
+        let left = ArchiveReader::open(File::open(&path).unwrap()).unwrap().scan(.., None).unwrap();
+        assert_eq!(left.iter().map(|e| e.id).collect::<Vec<_>>(), vec![1, 3, 5, 7, 9]);
+        assert_eq!(forget_user_in_file(&path, "ben").unwrap(), 5);
+        assert!(archive_files(&dir).unwrap().is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
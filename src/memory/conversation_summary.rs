@@ -0,0 +1,353 @@
+// ============================================================================
+//                  ASTRA AGI • CONVERSATION SUMMARIZATION
+//        Condensing Dialogue Sessions into Narrative Memory
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Extends the Narrative Memory System with session-level recall. When
+//       a conversation ends, its dialogue is condensed into a single
+//       narrative event carrying the topics discussed, preferences the user
+//       expressed, and commitments Astra made. Later sessions with the same
+//       user can be primed with "last time we discussed…" so conversations
+//       pick up where they left off.
+//
+//   Core Functions:
+//       • Record dialogue turns for an ongoing session, timed by the runtime clock
+//       • Hold the clarifying question a session is waiting on
+//       • Hold the planned dialogue a session is negotiating a task in
+//       • Extract topics, user preferences, and commitments from dialogue
+//       • Store session summaries as narrative events with metadata
+//       • Keep each user's latest summary outside the evicting narrative ring
+//       • Retrieve the latest summary for a user to prime a new session
+//       • Carry Astra's affect at the start and end of a session into its summary
+//
+//   File:        /src/memory/conversation_summary.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use super::episode_affect::{AffectSnapshot, EpisodeAffect};
+use super::narrative_memory::NarrativeMemory;
+use crate::interfaces::clarification::PendingClarification;
+use crate::metadata::Metadata;
+use crate::planning::dialogue::Dialogue;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::time::AstraTime;
+
+/// Narrative event type under which session summaries are stored.
+pub const SUMMARY_EVENT_TYPE: &str = "conversation_summary";
+
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "that", "this", "with", "you", "your", "are", "was", "what", "have",
+    "about", "can", "will", "would", "could", "should", "there", "their", "they", "them", "then",
+    "than", "from", "just", "like", "want", "know", "think", "tell", "more", "some", "also",
+    "it's", "i'm", "i'll", "don't", "that's", "okay", "yes", "please", "thanks", "fascinating",
+    "prefer", "let", "me", "how", "why", "when", "where", "who", "which", "its", "is", "it",
+];
+
+const PREFERENCE_MARKERS: &[&str] = &[
+    "i prefer", "i like", "i love", "i hate", "i don't like", "i dislike", "please always",
+    "please don't", "i'd rather", "i want you to",
+];
+
+const COMMITMENT_MARKERS: &[&str] = &["i will", "i'll", "i promise", "let me", "i'm going to"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Speaker {
+    User,
+    Astra,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DialogueTurn {
+    pub speaker: Speaker,
+    pub text: String,
+    pub timestamp: AstraTime,
+}
+
+/// An ongoing conversation.
+#[derive(Debug, Clone)]
+pub struct ConversationSession {
+    pub session_id: String,
+    pub user_id: Option<String>,
+    pub started_at: AstraTime,
+    pub turns: Vec<DialogueTurn>,
+    /// Question Astra asked and is waiting to have answered
+    pub pending_clarification: Option<PendingClarification>,
+    /// Request being negotiated in the session; see `Runtime::plan_dialogue_turn`
+    pub dialogue: Option<Dialogue>,
+    /// Astra's affect when the session opened and when it closed
+    pub affect_start: Option<AffectSnapshot>,
+    pub affect_end: Option<AffectSnapshot>,
+}
+
+impl ConversationSession {
+    pub fn new(session_id: impl Into<String>, user_id: Option<String>, started_at: AstraTime) -> Self {
+        ConversationSession {
+            session_id: session_id.into(),
+            user_id,
+            started_at,
+            turns: Vec::new(),
+            pending_clarification: None,
+            dialogue: None,
+            affect_start: None,
+            affect_end: None,
+        }
+    }
+
+    /// Records a turn spoken at `at`, as read from the runtime's clock.
+    pub fn add_turn(&mut self, speaker: Speaker, text: impl Into<String>, at: AstraTime) {
+        self.turns.push(DialogueTurn {
+            speaker,
+            text: text.into(),
+            timestamp: at,
+        });
+    }
+}
+
+/// Condensed record of a finished session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub session_id: String,
+    pub user_id: Option<String>,
+    pub started_at: AstraTime,
+    pub ended_at: AstraTime,
+    pub turn_count: usize,
+    /// Most frequent content words, most frequent first
+    pub topics: Vec<String>,
+    /// User sentences stating a preference
+    pub preferences: Vec<String>,
+    /// Astra sentences promising future action
+    pub commitments: Vec<String>,
+    pub synopsis: String,
+    /// Affect at the start and end, when both were recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub affect: Option<EpisodeAffect>,
+}
+
+impl SessionSummary {
+    /// One-line reminder used to prime the next session.
+    pub fn priming_line(&self) -> String {
+        let mut line = if self.topics.is_empty() {
+            "Last time we spoke briefly.".to_string()
+        } else {
+            format!("Last time we discussed {}.", join_readable(&self.topics))
+        };
+        if let Some(commitment) = self.commitments.first() {
+            line.push_str(&format!(" I had said: \"{}\"", commitment));
+        }
+        line
+    }
+}
+
+/// Extractive summarizer for dialogue sessions.
+#[derive(Debug, Clone)]
+pub struct ConversationSummarizer {
+    pub max_topics: usize,
+    /// Latest summary per user (`None` for anonymous sessions), kept here
+    /// because narrative memory evicts old events once it is full
+    latest: HashMap<Option<String>, SessionSummary>,
+}
+
+impl Default for ConversationSummarizer {
+    fn default() -> Self {
+        ConversationSummarizer::new(3)
+    }
+}
+
+impl ConversationSummarizer {
+    pub fn new(max_topics: usize) -> Self {
+        ConversationSummarizer { max_topics, latest: HashMap::new() }
+    }
+
+    pub fn summarize(&self, session: &ConversationSession) -> SessionSummary {
+        let mut counts: HashMap<String, (usize, usize)> = HashMap::new();
+        let mut preferences = Vec::new();
+        let mut commitments = Vec::new();
+
+        for (position, turn) in session.turns.iter().enumerate() {
+            for sentence in sentences(&turn.text) {
+                let lower = sentence.to_lowercase();
+                match turn.speaker {
+                    Speaker::User if PREFERENCE_MARKERS.iter().any(|m| lower.contains(m)) => {
+                        preferences.push(sentence.to_string())
+                    }
+                    Speaker::Astra if is_commitment(&lower) => commitments.push(sentence.to_string()),
+                    _ => {}
+                }
+            }
+
+            // Topics come from what the user brings up, not from Astra's phrasing.
+            if turn.speaker == Speaker::User {
+                for word in content_words(&turn.text) {
+                    counts.entry(word).or_insert((0, position)).0 += 1;
+                }
+            }
+        }
+
+        // Ties go to the topic mentioned first.
+        let mut ranked: Vec<(String, (usize, usize))> = counts.into_iter().collect();
+        ranked.sort_by(|a, b| b.1 .0.cmp(&a.1 .0).then(a.1 .1.cmp(&b.1 .1)).then(a.0.cmp(&b.0)));
+        let topics: Vec<String> = ranked.into_iter().take(self.max_topics).map(|(w, _)| w).collect();
+
+        let synopsis = format!(
+            "Conversation {} ({} turns){}: {} preference(s), {} commitment(s)",
+            session.session_id,
+            session.turns.len(),
+            if topics.is_empty() { String::new() } else { format!(" about {}", join_readable(&topics)) },
+            preferences.len(),
+            commitments.len()
+        );
+
+        SessionSummary {
+            session_id: session.session_id.clone(),
+            user_id: session.user_id.clone(),
+            started_at: session.started_at,
+            ended_at: session.turns.last().map(|t| t.timestamp).unwrap_or(session.started_at),
+            turn_count: session.turns.len(),
+            topics,
+            preferences,
+            commitments,
+            synopsis,
+            affect: match (&session.affect_start, &session.affect_end) {
+                (Some(start), Some(end)) => Some(EpisodeAffect::new(start.clone(), end.clone())),
+                _ => None,
+            },
+        }
+    }
+
+    /// Summarizes a session and stores the result as a narrative event whose
+    /// metadata holds the full summary. The summary is also kept as the
+    /// user's latest, so it outlives the event being evicted.
+    pub fn summarize_into(&mut self, session: &ConversationSession, memory: &mut NarrativeMemory) -> SessionSummary {
+        let summary = self.summarize(session);
+        memory.add_event(
+            SUMMARY_EVENT_TYPE,
+            summary.synopsis.clone(),
+            Metadata::from_record(&summary),
+        );
+        self.latest.insert(summary.user_id.clone(), summary.clone());
+        summary
+    }
+
+    /// Latest summary for a user, falling back to narrative memory for
+    /// summaries stored before this summarizer was created (e.g. recovered
+    /// from the write-ahead log).
+    pub fn last_summary(&self, memory: &NarrativeMemory, user_id: Option<&str>) -> Option<SessionSummary> {
+        self.latest
+            .get(&user_id.map(str::to_string))
+            .cloned()
+            .or_else(|| last_session_summary(memory, user_id))
+    }
+
+    /// Drops the latest summary kept for a user. Returns whether one existed.
+    pub fn forget_user(&mut self, user_id: &str) -> bool {
+        self.latest.remove(&Some(user_id.to_string())).is_some()
+    }
+}
+
+/// Most recent stored summary for a user (or for anonymous sessions when
+/// `user_id` is `None`).
+pub fn last_session_summary(memory: &NarrativeMemory, user_id: Option<&str>) -> Option<SessionSummary> {
+    memory
+        .events
+        .iter()
+        .rev()
+        .filter(|e| e.event_type == SUMMARY_EVENT_TYPE)
+        .filter_map(|e| e.metadata.as_ref()?.to_record::<SessionSummary>())
+        .find(|summary| summary.user_id.as_deref() == user_id)
+}
+
+/// Lowercased words of `text` that carry content: longer than three
+/// letters and not stopwords.
+pub fn content_words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(str::to_lowercase)
+        .filter(|word| word.len() > 3 && !STOPWORDS.contains(&word.as_str()))
+}
+
+/// Sentences in which the speaker promises to do something ("I'll…", "I will…").
+pub fn commitment_sentences(text: &str) -> Vec<String> {
+    sentences(text)
+        .filter(|s| is_commitment(&s.to_lowercase()))
+        .map(str::to_string)
+        .collect()
+}
+
+fn is_commitment(lower: &str) -> bool {
+    COMMITMENT_MARKERS
+        .iter()
+        .any(|m| lower.starts_with(m) || lower.contains(&format!(" {}", m)))
+}
+
+fn sentences(text: &str) -> impl Iterator<Item = &str> {
+    text.split_inclusive(['.', '!', '?'])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+fn join_readable(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [init @ .., last] => format!("{} and {}", init.join(", "), last),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_session(user: &str) -> ConversationSession {
+        let at = AstraTime::from_secs;
+        let mut session = ConversationSession::new("s1", Some(user.to_string()), at(1_000));
+        session.add_turn(Speaker::User, "Can you help me plan a garden? I prefer native plants.", at(1_001));
+        session.add_turn(Speaker::Astra, "Sure. I will draft a garden layout by tomorrow.", at(1_002));
+        session.add_turn(Speaker::User, "The garden gets little sun, so shade plants matter.", at(1_003));
+        session
+    }
+
+    #[test]
+    fn test_extracts_topics_preferences_and_commitments() {
+        let summary = ConversationSummarizer::default().summarize(&sample_session("ana"));
+
+        assert_eq!(summary.turn_count, 3);
+        assert_eq!(summary.topics[0], "garden");
+        assert_eq!(summary.preferences, vec!["I prefer native plants.".to_string()]);
+        assert_eq!(summary.commitments, vec!["I will draft a garden layout by tomorrow.".to_string()]);
+    }
+
+    #[test]
+    fn test_summary_round_trips_through_narrative_memory() {
+        let mut memory = NarrativeMemory::new(10);
+        let mut summarizer = ConversationSummarizer::default();
+        summarizer.summarize_into(&sample_session("ana"), &mut memory);
+        memory.add_event("tick", "Runtime tick completed", None);
+
+        let recalled = last_session_summary(&memory, Some("ana")).expect("summary stored");
+        assert!(recalled.priming_line().starts_with("Last time we discussed garden"));
+        assert!(last_session_summary(&memory, Some("ben")).is_none());
+    }
+
+    #[test]
+    fn test_latest_summary_survives_narrative_eviction() {
+        let mut memory = NarrativeMemory::new(3);
+        let mut summarizer = ConversationSummarizer::default();
+        summarizer.summarize_into(&sample_session("ana"), &mut memory);
+        for _ in 0..5 {
+            memory.add_event("tick", "Runtime tick completed", None);
+        }
+
+        assert!(last_session_summary(&memory, Some("ana")).is_none());
+        let recalled = summarizer.last_summary(&memory, Some("ana")).expect("summary kept");
+        assert_eq!(recalled.session_id, "s1");
+
+        assert!(summarizer.forget_user("ana"));
+        assert!(summarizer.last_summary(&memory, Some("ana")).is_none());
+    }
+}
@@ -0,0 +1,181 @@
+// ============================================================================
+//                      ASTRA AGI • EPISODE AFFECT INDEX
+//        How Each Episode Left Astra Feeling
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Gives episodes an affective dimension. An episode records Astra's
+//       emotions, mood, and stress when it began and when it ended; the
+//       difference says what the episode did to Astra. Narrative memory can
+//       then be searched by that difference ("conversations that reduced
+//       stress", "goals that soured the mood"), so emotion regulation and
+//       the learner can treat affect as a signal like any other outcome.
+//
+//   Core Functions:
+//       • Snapshot emotion, mood, and stress at an episode's start and end
+//       • Measure the change in any affective dimension over an episode
+//       • Retrieve episodes from narrative memory by affective change
+//
+//   File:        /src/memory/episode_affect.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+
+use super::narrative_memory::{NarrativeEvent, NarrativeMemory};
+use crate::cognition::CognitiveState;
+use crate::personality::emotion::{EmotionState, Mood};
+
+/// Key of the episode affect in a narrative event's JSON metadata.
+pub const AFFECT_METADATA_KEY: &str = "affect";
+
+/// Affect at one moment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AffectSnapshot {
+    pub happiness: f32,
+    pub sadness: f32,
+    pub anger: f32,
+    pub fear: f32,
+    pub valence: f32,
+    /// Mood baseline, 0 (negative) to 1 (positive)
+    pub mood: f32,
+    /// Stress, where the recorder tracks it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stress: Option<f32>,
+}
+
+impl AffectSnapshot {
+    pub fn capture(emotion: &EmotionState, mood: &Mood, stress: Option<f32>) -> Self {
+        AffectSnapshot {
+            happiness: emotion.happiness,
+            sadness: emotion.sadness,
+            anger: emotion.anger,
+            fear: emotion.fear,
+            valence: emotion.valence(),
+            mood: mood.baseline,
+            stress,
+        }
+    }
+
+    /// The emotion and mood held in the cognitive state.
+    pub fn of_cognitive_state(state: &CognitiveState) -> Self {
+        Self::capture(&state.emotion, &state.mood, None)
+    }
+
+    pub fn get(&self, dimension: AffectDimension) -> Option<f32> {
+        match dimension {
+            AffectDimension::Happiness => Some(self.happiness),
+            AffectDimension::Sadness => Some(self.sadness),
+            AffectDimension::Anger => Some(self.anger),
+            AffectDimension::Fear => Some(self.fear),
+            AffectDimension::Valence => Some(self.valence),
+            AffectDimension::Mood => Some(self.mood),
+            AffectDimension::Stress => self.stress,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AffectDimension {
+    Happiness,
+    Sadness,
+    Anger,
+    Fear,
+    Valence,
+    Mood,
+    Stress,
+}
+
+/// Direction of an affective change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AffectChange {
+    Increased,
+    Decreased,
+}
+
+/// Affect at the start and end of an episode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpisodeAffect {
+    pub start: AffectSnapshot,
+    pub end: AffectSnapshot,
+}
+
+impl EpisodeAffect {
+    pub fn new(start: AffectSnapshot, end: AffectSnapshot) -> Self {
+        EpisodeAffect { start, end }
+    }
+
+    /// End minus start in `dimension`, if both snapshots have it.
+    pub fn delta(&self, dimension: AffectDimension) -> Option<f32> {
+        Some(self.end.get(dimension)? - self.start.get(dimension)?)
+    }
+
+    /// Reads the affect stored in an event's metadata.
+    pub fn of_event(event: &NarrativeEvent) -> Option<Self> {
+        event.metadata.as_ref()?.get_record(AFFECT_METADATA_KEY)
+    }
+}
+
+impl NarrativeMemory {
+    /// Episodes over which `dimension` changed in the direction of `change`
+    /// by at least `min_change`, with their deltas, largest change first.
+    /// Episodes recorded without affect are skipped.
+    pub fn episodes_by_affect(
+        &self,
+        dimension: AffectDimension,
+        change: AffectChange,
+        min_change: f32,
+    ) -> Vec<(&NarrativeEvent, f32)> {
+        let mut episodes: Vec<(&NarrativeEvent, f32)> = self
+            .events
+            .iter()
+            .filter_map(|event| Some((event, EpisodeAffect::of_event(event)?.delta(dimension)?)))
+            .filter(|(_, delta)| match change {
+                AffectChange::Increased => *delta >= min_change.max(f32::EPSILON),
+                AffectChange::Decreased => -*delta >= min_change.max(f32::EPSILON),
+            })
+            .collect();
+        episodes.sort_by(|a, b| b.1.abs().partial_cmp(&a.1.abs()).unwrap_or(std::cmp::Ordering::Equal));
+        episodes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::{MetaValue, Metadata};
+
+    fn episode_with_stress(memory: &mut NarrativeMemory, description: &str, start: f32, end: f32) {
+        let calm = EmotionState::neutral();
+        let affect = EpisodeAffect::new(
+            AffectSnapshot::capture(&calm, &Mood::new(), Some(start)),
+            AffectSnapshot::capture(&calm, &Mood::new(), Some(end)),
+        );
+        let metadata = Metadata::new().with(AFFECT_METADATA_KEY, MetaValue::from_record(&affect).unwrap());
+        memory.add_event("episode", description, Some(metadata));
+    }
+
+    #[test]
+    fn test_episodes_are_retrieved_by_affective_change() {
+        let mut memory = NarrativeMemory::new(10);
+        episode_with_stress(&mut memory, "Talked it through", 0.8, 0.3);
+        episode_with_stress(&mut memory, "Short walk", 0.5, 0.4);
+        episode_with_stress(&mut memory, "Missed the deadline", 0.2, 0.7);
+        memory.add_event("tick", "No affect recorded", None);
+
+        let calming = memory.episodes_by_affect(AffectDimension::Stress, AffectChange::Decreased, 0.0);
+        let names: Vec<&str> = calming.iter().map(|(e, _)| e.description.as_str()).collect();
+        assert_eq!(names, vec!["Talked it through", "Short walk"]);
+        assert!((calming[0].1 + 0.5).abs() < 1e-6);
+
+        let stressful = memory.episodes_by_affect(AffectDimension::Stress, AffectChange::Increased, 0.3);
+        assert_eq!(stressful.len(), 1);
+        // Mood did not move in any of them.
+        assert!(memory.episodes_by_affect(AffectDimension::Mood, AffectChange::Increased, 0.0).is_empty());
+    }
+}
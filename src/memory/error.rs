@@ -0,0 +1,41 @@
+// ============================================================================
+//                          ASTRA AGI • MEMORY ERRORS
+//        Typed Failures of the Memory Subsystem
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       The error type returned by memory operations, so callers can tell
+//       a reference to a forgotten event from other failures.
+//
+//   Core Functions:
+//       • Name the ways memory operations fail
+//       • Tell unreadable event archives from corrupt ones
+//
+//   File:        /src/memory/error.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use thiserror::Error;
+
+use super::narrative_memory::EventId;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum MemoryError {
+    /// The event was never recorded, or has been evicted or deleted
+    #[error("Event {0} not found")]
+    EventNotFound(EventId),
+}
+
+/// Failures reading or writing an on-disk event archive.
+#[derive(Debug, Error)]
+pub enum ArchiveError {
+    #[error("Could not read or write the event archive")]
+    Io(#[from] std::io::Error),
+    #[error("Event archive is corrupt: {0}")]
+    Corrupt(String),
+}
@@ -11,18 +11,32 @@
 //   Core Functions:
 //       • Define the module layout for the Memory subsystem
 //       • Expose the Narrative Memory System (NMS)
+//       • Expose conversation summarization for cross-session recall
+//       • Expose the privacy filter guarding memory writes
+//       • Expose tiered summarization of aging events with an archive
+//       • Expose compressed on-disk archives of old events with range scans
+//       • Expose prospective memory for event-triggered reminders
+//       • Expose the affect index for retrieving episodes by emotional change
+//       • Expose the typed memory error
 //       • Provide a unified namespace for memory‑related components
 //       • Establish the foundation for future episodic and semantic memory layers
 //
 //   File:        /src/memory/mod.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-25
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
+pub mod error;
 pub mod narrative_memory;
+pub mod conversation_summary;
+pub mod privacy;
+pub mod summary_tiers;
+pub mod archive_file;
+pub mod prospective;
+pub mod episode_affect;
 
@@ -14,12 +14,21 @@
 //       • Maintain persistent identity through experiential continuity
 //       • Support retrieval of recent or context‑relevant memories
 //       • Enable reflective reasoning and self‑storytelling
-//       • Provide structured metadata for advanced cognitive processing
+//       • Provide structured metadata for advanced cognitive processing,
+//         typed values rather than JSON text
+//       • Filter personal data out of events before they are stored
+//       • List and delete the events that concern a particular user
+//       • Stamp events with strictly ordered, millisecond-precision times
+//       • Identify events and link them to related events, intents, and facts
+//       • Follow causal links back from an outcome to what triggered it
+//       • Restore recorded events, ids and times intact, after a restart
+//       • Read event times from the runtime's clock, so mocked time is honored
+//       • Hold events pushed out by the capacity limit until they are archived
 //
 //   File:        /src/memory/narrative_memory.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-25
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
@@ -27,22 +36,78 @@
 // ============================================================================
 
 
-use std::collections::VecDeque;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::{HashSet, VecDeque};
+use serde::{Deserialize, Serialize};
+use super::error::MemoryError;
+use super::privacy::PrivacyFilter;
+use crate::knowledge::belief_ledger::FactKey;
+use crate::metadata::Metadata;
+use crate::runtime::intent_manager::IntentId;
+use crate::time::{system_clock, AstraTime, SharedClock};
+
+/// Stable identifier of a narrative event; never reused.
+pub type EventId = u64;
+
+/// How an event relates to the thing it links to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LinkRelation {
+    /// The target brought this event about
+    CausedBy,
+    /// The event concerns the target
+    About,
+    /// Loosely connected
+    RelatedTo,
+    /// The event is a summary standing in for the target
+    Summarizes,
+}
+
+/// What an event links to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LinkTarget {
+    Event(EventId),
+    Intent(IntentId),
+    Fact(FactKey),
+}
+
+/// A typed reference from one event to something else.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventLink {
+    pub relation: LinkRelation,
+    pub target: LinkTarget,
+}
+
+impl EventLink {
+    pub fn caused_by(event: EventId) -> Self {
+        EventLink { relation: LinkRelation::CausedBy, target: LinkTarget::Event(event) }
+    }
+
+    pub fn about(target: LinkTarget) -> Self {
+        EventLink { relation: LinkRelation::About, target }
+    }
+}
 
 /// Represents a single narrative event or memory.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NarrativeEvent {
-    pub timestamp: u64,       // Unix timestamp
+    pub id: EventId,
+    pub timestamp: AstraTime, // When the event was recorded
     pub event_type: String,   // E.g., "task_started", "belief_updated"
     pub description: String,  // Human-readable description
-    pub metadata: Option<String>, // Optional JSON or structured data
+    pub metadata: Option<Metadata>, // Optional structured data; JSON text from earlier releases loads too
+    pub links: Vec<EventLink>,
 }
 
 /// Narrative memory storing a chronological sequence of events.
 pub struct NarrativeMemory {
     pub events: VecDeque<NarrativeEvent>,
     pub max_capacity: usize, // Limits memory size to avoid unbounded growth
+    /// Applied to every event description before it is stored
+    pub privacy: Option<PrivacyFilter>,
+    /// Events pushed out by the capacity limit, oldest first, waiting to be
+    /// archived; see `take_evicted`
+    evicted: VecDeque<NarrativeEvent>,
+    next_id: EventId,
+    clock: SharedClock,
 }
 
 impl NarrativeMemory {
@@ -51,37 +116,209 @@ impl NarrativeMemory {
         NarrativeMemory {
             events: VecDeque::with_capacity(max_capacity),
             max_capacity,
+            privacy: Some(PrivacyFilter::default()),
+            evicted: VecDeque::new(),
+            next_id: 1,
+            clock: system_clock(),
         }
     }
 
-    /// Adds a new event to the narrative memory.
-    pub fn add_event(&mut self, event_type: impl Into<String>, description: impl Into<String>, metadata: Option<String>) {
-        let now = current_unix_timestamp();
+    /// Stamps events with times read from `clock` from now on.
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
+    /// Adds a new event to the narrative memory. Personal data in the
+    /// description is redacted, hashed, or marked restricted in the
+    /// metadata, as the privacy policy says. Returns the new event's id.
+    pub fn add_event(&mut self, event_type: impl Into<String>, description: impl Into<String>, metadata: Option<Metadata>) -> EventId {
+        self.add_linked_event(event_type, description, metadata, Vec::new())
+    }
+
+    /// Adds an event with links to related events, intents, and facts.
+    pub fn add_linked_event(
+        &mut self,
+        event_type: impl Into<String>,
+        description: impl Into<String>,
+        metadata: Option<Metadata>,
+        links: Vec<EventLink>,
+    ) -> EventId {
+        // A mocked clock may stand still; times stay strictly ordered anyway.
+        let now = self.clock.now();
+        let now = match self.events.back() {
+            Some(last) if last.timestamp >= now => {
+                AstraTime::from_millis(last.timestamp.as_millis() + 1)
+            }
+            _ => now,
+        };
+        let (description, metadata) = match &self.privacy {
+            Some(filter) => {
+                let filtered = filter.filter(&description.into());
+                let metadata = filtered.access_metadata(metadata);
+                (filtered.text, metadata)
+            }
+            None => (description.into(), metadata),
+        };
+        let id = self.next_id;
+        self.next_id += 1;
         let event = NarrativeEvent {
+            id,
             timestamp: now,
             event_type: event_type.into(),
-            description: description.into(),
+            description,
             metadata,
+            links,
         };
 
-        if self.events.len() == self.max_capacity {
-            self.events.pop_front(); // Remove oldest event
-        }
+        self.make_room();
+        self.events.push_back(event);
+        id
+    }
+
+    /// Puts back an event as it was recorded, after the events already
+    /// held; it is not filtered for privacy again. New events are numbered
+    /// after it.
+    pub fn restore_event(&mut self, event: NarrativeEvent) {
+        self.next_id = self.next_id.max(event.id + 1);
+        self.make_room();
         self.events.push_back(event);
     }
 
+    /// Moves the oldest event aside for archiving when memory is full. Up
+    /// to `max_capacity` events wait; older ones than that are dropped.
+    fn make_room(&mut self) {
+        if self.events.len() < self.max_capacity {
+            return;
+        }
+        if let Some(oldest) = self.events.pop_front() {
+            if self.evicted.len() >= self.max_capacity.max(1) {
+                self.evicted.pop_front();
+            }
+            self.evicted.push_back(oldest);
+        }
+    }
+
+    /// Events the capacity limit pushed out since the last call, oldest
+    /// first, for the archive to keep.
+    pub fn take_evicted(&mut self) -> Vec<NarrativeEvent> {
+        self.evicted.drain(..).collect()
+    }
+
+    /// The event with `id`, unless it has been evicted or deleted.
+    pub fn get(&self, id: EventId) -> Option<&NarrativeEvent> {
+        self.events.iter().find(|e| e.id == id)
+    }
+
+    /// Adds a link to an existing event.
+    pub fn link(&mut self, from: EventId, link: EventLink) -> Result<(), MemoryError> {
+        let event = self
+            .events
+            .iter_mut()
+            .find(|e| e.id == from)
+            .ok_or(MemoryError::EventNotFound(from))?;
+        if !event.links.contains(&link) {
+            event.links.push(link);
+        }
+        Ok(())
+    }
+
+    /// Events that `id` links to, in link order. Links to events no longer
+    /// held are skipped.
+    pub fn linked_events(&self, id: EventId) -> Vec<&NarrativeEvent> {
+        self.get(id)
+            .map(|event| {
+                event
+                    .links
+                    .iter()
+                    .filter_map(|link| match link.target {
+                        LinkTarget::Event(target) => self.get(target),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Events that link to `target`, oldest first.
+    pub fn events_linking_to(&self, target: &LinkTarget) -> Vec<&NarrativeEvent> {
+        self.events.iter().filter(|e| e.links.iter().any(|l| &l.target == target)).collect()
+    }
+
+    /// Follows `CausedBy` links back from `id`: the event itself, then its
+    /// causes, then theirs, breadth first. The last entries are the root
+    /// causes still held in memory, such as the stimulus behind a failed plan.
+    pub fn causal_chain(&self, id: EventId) -> Vec<&NarrativeEvent> {
+        let mut chain = Vec::new();
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::from([id]);
+        while let Some(next) = queue.pop_front() {
+            if !seen.insert(next) {
+                continue;
+            }
+            let Some(event) = self.get(next) else { continue };
+            chain.push(event);
+            for link in &event.links {
+                if let (LinkRelation::CausedBy, LinkTarget::Event(cause)) = (link.relation, &link.target) {
+                    queue.push_back(*cause);
+                }
+            }
+        }
+        chain
+    }
+
+    /// Removes the events with the given ids, oldest first.
+    pub(crate) fn take_events(&mut self, ids: &HashSet<EventId>) -> Vec<NarrativeEvent> {
+        let (taken, kept): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut self.events).into_iter().partition(|e| ids.contains(&e.id));
+        self.events = kept.into();
+        taken
+    }
+
+    /// Inserts an already-filtered event at its place in time, rather than
+    /// at the end, and returns its id. Used for summaries of older events.
+    pub(crate) fn insert_at_time(
+        &mut self,
+        timestamp: AstraTime,
+        event_type: impl Into<String>,
+        description: String,
+        metadata: Option<Metadata>,
+        links: Vec<EventLink>,
+    ) -> EventId {
+        let id = self.next_id;
+        self.next_id += 1;
+        let position = self.events.iter().position(|e| e.timestamp > timestamp).unwrap_or(self.events.len());
+        self.events.insert(
+            position,
+            NarrativeEvent { id, timestamp, event_type: event_type.into(), description, metadata, links },
+        );
+        id
+    }
+
     /// Retrieves the most recent N events.
     pub fn recent_events(&self, count: usize) -> Vec<&NarrativeEvent> {
         self.events.iter().rev().take(count).collect()
     }
+
+    /// Events whose metadata names `user_id` as the user they concern,
+    /// oldest first.
+    pub fn events_for_user(&self, user_id: &str) -> Vec<&NarrativeEvent> {
+        self.events.iter().filter(|e| e.user_id().as_deref() == Some(user_id)).collect()
+    }
+
+    /// Deletes every event concerning `user_id`. Returns how many were removed.
+    pub fn forget_user(&mut self, user_id: &str) -> usize {
+        let before = self.events.len() + self.evicted.len();
+        self.events.retain(|e| e.user_id().as_deref() != Some(user_id));
+        self.evicted.retain(|e| e.user_id().as_deref() != Some(user_id));
+        before - self.events.len() - self.evicted.len()
+    }
 }
 
-/// Helper function to get current unix timestamp in seconds.
-fn current_unix_timestamp() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .expect("Time went backwards")
-        .as_secs()
+impl NarrativeEvent {
+    /// The `user_id` entry of the event's metadata, if it has one.
+    pub fn user_id(&self) -> Option<String> {
+        self.metadata.as_ref()?.get_str("user_id").map(str::to_string)
+    }
 }
 
 #[cfg(test)]
@@ -92,7 +329,7 @@ mod tests {
     fn test_add_and_retrieve_events() {
         let mut memory = NarrativeMemory::new(5);
         memory.add_event("task_started", "Started processing task A", None);
-        memory.add_event("belief_updated", "Updated confidence in fact X", Some("{\"confidence\":0.9}".to_string()));
+        memory.add_event("belief_updated", "Updated confidence in fact X", Some(Metadata::from([("confidence", 0.9)])));
 
         let recent = memory.recent_events(2);
         assert_eq!(recent.len(), 2);
@@ -108,5 +345,67 @@ mod tests {
         }
         assert_eq!(memory.events.len(), 3);
         assert_eq!(memory.events.front().unwrap().description, "Event 2");
+        let evicted: Vec<String> = memory.take_evicted().into_iter().map(|e| e.description).collect();
+        assert_eq!(evicted, vec!["Event 0", "Event 1"]);
+        assert!(memory.take_evicted().is_empty());
+        assert!(memory.events.iter().zip(memory.events.iter().skip(1)).all(|(a, b)| a.timestamp < b.timestamp));
+    }
+
+    #[test]
+    fn test_failure_can_be_traced_back_to_its_stimulus() {
+        let mut memory = NarrativeMemory::new(10);
+        let stimulus = memory.add_event("user_request", "Please book a table", None);
+        let intent = memory.add_linked_event(
+            "intent_created",
+            "Intent 4 created",
+            None,
+            vec![EventLink::caused_by(stimulus), EventLink::about(LinkTarget::Intent(4))],
+        );
+        memory.add_event("tick", "Runtime tick completed", None);
+        let failure = memory.add_linked_event("plan_finished", "Plan failed", None, vec![EventLink::caused_by(intent)]);
+
+        let chain: Vec<EventId> = memory.causal_chain(failure).iter().map(|e| e.id).collect();
+        assert_eq!(chain, vec![failure, intent, stimulus]);
+        assert_eq!(memory.events_linking_to(&LinkTarget::Intent(4))[0].id, intent);
+        assert_eq!(memory.linked_events(intent)[0].id, stimulus);
+
+        assert!(memory.link(stimulus, EventLink::about(LinkTarget::Intent(4))).is_ok());
+        assert_eq!(memory.link(999, EventLink::caused_by(stimulus)), Err(MemoryError::EventNotFound(999)));
+    }
+
+    #[test]
+    fn test_forget_user_removes_only_their_events() {
+        let mut memory = NarrativeMemory::new(10);
+        memory.add_event("conversation_summary", "Talked about gardens", Some(Metadata::from([("user_id", "ana")])));
+        memory.add_event("conversation_summary", "Talked about boats", Some(Metadata::from([("user_id", "ben")])));
+        memory.add_event("tick", "Runtime tick completed", None);
+
+        assert_eq!(memory.events_for_user("ana").len(), 1);
+        assert_eq!(memory.forget_user("ana"), 1);
+        assert_eq!(memory.events.len(), 2);
+        assert!(memory.events_for_user("ana").is_empty());
+    }
+
+    #[test]
+    fn test_pii_is_filtered_on_write() {
+        let mut memory = NarrativeMemory::new(5);
+        memory.add_event("user_message", "Reach me at 555-867-5309", None);
+        assert_eq!(memory.events[0].description, "Reach me at [phone]");
+    }
+
+    #[test]
+    fn test_events_are_stamped_by_the_clock() {
+        let clock = crate::time::MockClock::starting_at(AstraTime::from_secs(1_000));
+        let mut memory = NarrativeMemory::new(5);
+        memory.set_clock(std::sync::Arc::new(clock.clone()));
+
+        let first = memory.add_event("tick", "First", None);
+        let second = memory.add_event("tick", "Second", None);
+        assert_eq!(memory.get(first).unwrap().timestamp, AstraTime::from_secs(1_000));
+        assert_eq!(memory.get(second).unwrap().timestamp, AstraTime::from_millis(1_000_001));
+
+        clock.advance(std::time::Duration::from_secs(60));
+        let third = memory.add_event("tick", "Third", None);
+        assert_eq!(memory.get(third).unwrap().timestamp, AstraTime::from_secs(1_060));
     }
 }
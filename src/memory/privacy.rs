@@ -0,0 +1,381 @@
+// ============================================================================
+//                      ASTRA AGI • PRIVACY FILTER
+//        PII Detection, Redaction & Restricted-Access Tagging
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Guards the paths through which text enters long-term storage —
+//       narrative memory writes, conversation logs, and crawled content.
+//       Likely personal data (email addresses, phone numbers, street
+//       addresses) is found with lightweight pattern matching and, per
+//       policy, redacted, replaced by a stable pseudonymous hash, or kept
+//       but tagged so the stored record is marked restricted. Pseudonyms
+//       are keyed hashes under a random key each installation keeps to
+//       itself, so they cannot be reversed by hashing guesses.
+//
+//   Core Functions:
+//       • Detect emails, phone numbers, and street addresses in free text
+//       • Redact, hash, or tag each kind of PII according to policy
+//       • Produce restricted-access metadata for records that keep PII
+//       • Key pseudonyms per installation, from a key file created on first use
+//
+//   File:        /src/memory/privacy.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::metadata::Metadata;
+
+const STREET_SUFFIXES: &[&str] = &[
+    "street", "st", "avenue", "ave", "road", "rd", "boulevard", "blvd", "lane", "ln", "drive", "dr",
+    "court", "ct", "way", "place", "pl", "square", "sq", "terrace",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiKind {
+    Email,
+    Phone,
+    Address,
+}
+
+impl PiiKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PiiKind::Email => "email",
+            PiiKind::Phone => "phone",
+            PiiKind::Address => "address",
+        }
+    }
+}
+
+/// What to do with a kind of PII.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiTreatment {
+    /// Replace with a placeholder such as `[email]`
+    Redact,
+    /// Replace with a keyed hash, so repeated mentions stay linkable
+    Hash,
+    /// Keep as is and mark the record restricted
+    Tag,
+}
+
+/// A span of text that looks like PII, by byte offsets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PiiMatch {
+    pub kind: PiiKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Text after filtering, with what was found in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilteredText {
+    pub text: String,
+    pub found: Vec<PiiKind>,
+    /// True when PII was kept and the record must be access-restricted
+    pub restricted: bool,
+}
+
+impl FilteredText {
+    /// Metadata marking a record restricted, merged into existing
+    /// metadata if there is any. `existing` is returned unchanged when
+    /// nothing needs marking.
+    pub fn access_metadata(&self, existing: Option<Metadata>) -> Option<Metadata> {
+        if !self.restricted {
+            return existing;
+        }
+        let pii: Vec<&str> = self.found.iter().map(|k| k.as_str()).collect();
+        Some(existing.unwrap_or_default().with("access", "restricted").with("pii", pii))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivacyPolicy {
+    pub treatments: HashMap<PiiKind, PiiTreatment>,
+    /// Secret key of hashed PII. Pseudonyms stay the same only as long as
+    /// the key does; see `with_key_file`.
+    pub key: String,
+}
+
+impl PrivacyPolicy {
+    /// The default policy keyed with the key kept in `path`, which is
+    /// created with a new random key if missing. Pseudonyms then survive
+    /// restarts of one installation but differ from any other's.
+    pub fn with_key_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let key = match fs::read_to_string(path) {
+            Ok(key) => key.trim().to_string(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                let key = random_key();
+                fs::write(path, &key)?;
+                key
+            }
+            Err(e) => return Err(e),
+        };
+        Ok(PrivacyPolicy { key, ..PrivacyPolicy::default() })
+    }
+}
+
+impl Default for PrivacyPolicy {
+    /// Emails hashed, phones and addresses redacted, and a random key, so
+    /// pseudonyms last as long as the policy.
+    fn default() -> Self {
+        PrivacyPolicy {
+            treatments: HashMap::from([
+                (PiiKind::Email, PiiTreatment::Hash),
+                (PiiKind::Phone, PiiTreatment::Redact),
+                (PiiKind::Address, PiiTreatment::Redact),
+            ]),
+            key: random_key(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PrivacyFilter {
+    pub policy: PrivacyPolicy,
+}
+
+impl PrivacyFilter {
+    pub fn new(policy: PrivacyPolicy) -> Self {
+        PrivacyFilter { policy }
+    }
+
+    /// Applies the policy to every PII span in `text`.
+    pub fn filter(&self, text: &str) -> FilteredText {
+        let matches = detect_pii(text);
+        let mut output = String::with_capacity(text.len());
+        let mut found = Vec::new();
+        let mut restricted = false;
+        let mut cursor = 0;
+
+        for m in matches {
+            output.push_str(&text[cursor..m.start]);
+            let span = &text[m.start..m.end];
+            match self.policy.treatments.get(&m.kind).copied().unwrap_or(PiiTreatment::Redact) {
+                PiiTreatment::Redact => output.push_str(&format!("[{}]", m.kind.as_str())),
+                PiiTreatment::Hash => {
+                    output.push_str(&format!("[{}:{:016x}]", m.kind.as_str(), keyed_hash(&self.policy.key, span)))
+                }
+                PiiTreatment::Tag => {
+                    output.push_str(span);
+                    restricted = true;
+                }
+            }
+            if !found.contains(&m.kind) {
+                found.push(m.kind);
+            }
+            cursor = m.end;
+        }
+        output.push_str(&text[cursor..]);
+
+        FilteredText { text: output, found, restricted }
+    }
+}
+
+/// Finds likely PII in `text`, in order and without overlaps.
+pub fn detect_pii(text: &str) -> Vec<PiiMatch> {
+    let mut matches = Vec::new();
+    let tokens = tokens(text);
+
+    for (i, &(start, end)) in tokens.iter().enumerate() {
+        let word = &text[start..end];
+        if let Some((s, e)) = email_span(word) {
+            matches.push(PiiMatch { kind: PiiKind::Email, start: start + s, end: start + e });
+            continue;
+        }
+        // "<number> <Name>... <suffix>", up to four name words.
+        if word.chars().all(|c| c.is_ascii_digit()) && word.len() <= 5 {
+            for j in (i + 2)..tokens.len().min(i + 6) {
+                let (_, suffix_end) = tokens[j];
+                let suffix = text[tokens[j].0..suffix_end].trim_end_matches(|c: char| !c.is_alphanumeric());
+                let names_capitalized = tokens[i + 1..j]
+                    .iter()
+                    .all(|&(s, _)| text[s..].starts_with(|c: char| c.is_uppercase()));
+                if !names_capitalized {
+                    break;
+                }
+                if STREET_SUFFIXES.contains(&suffix.to_lowercase().as_str()) {
+                    let end = tokens[j].0 + suffix.len();
+                    matches.push(PiiMatch { kind: PiiKind::Address, start, end });
+                    break;
+                }
+            }
+        }
+    }
+
+    matches.extend(phone_spans(text).into_iter().map(|(start, end)| PiiMatch { kind: PiiKind::Phone, start, end }));
+    matches.sort_by_key(|m| m.start);
+    let mut kept: Vec<PiiMatch> = Vec::new();
+    for m in matches {
+        if kept.last().is_none_or(|last| m.start >= last.end) {
+            kept.push(m);
+        }
+    }
+    kept
+}
+
+/// Byte ranges of whitespace-separated words.
+fn tokens(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                spans.push((s, i));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, text.len()));
+    }
+    spans
+}
+
+fn email_span(word: &str) -> Option<(usize, usize)> {
+    let trimmed_start = word.len() - word.trim_start_matches(|c: char| !c.is_alphanumeric()).len();
+    let core = word.trim_matches(|c: char| !c.is_alphanumeric());
+    let (local, domain) = core.split_once('@')?;
+    let valid_local = !local.is_empty() && local.chars().all(|c| c.is_alphanumeric() || "._%+-".contains(c));
+    let valid_domain = domain.contains('.')
+        && !domain.starts_with('.')
+        && domain.chars().all(|c| c.is_alphanumeric() || ".-".contains(c));
+    (valid_local && valid_domain).then(|| (trimmed_start, trimmed_start + core.len()))
+}
+
+/// Runs of digits and phone punctuation holding 7 to 15 digits. A run
+/// needs a leading `+` or some separator, so bare numbers such as
+/// timestamps and ids are left alone, and ISO dates are skipped.
+fn phone_spans(text: &str) -> Vec<(usize, usize)> {
+    let bytes = text.as_bytes();
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let starts = bytes[i].is_ascii_digit() || ((bytes[i] == b'+' || bytes[i] == b'(') && bytes.get(i + 1).is_some_and(u8::is_ascii_digit));
+        let preceded_by_word = i > 0 && (bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'.');
+        if !starts || preceded_by_word {
+            i += 1;
+            continue;
+        }
+
+        let mut end = i;
+        let mut digits = 0;
+        let mut separators = 0;
+        let mut j = i;
+        while j < bytes.len() {
+            let b = bytes[j];
+            if b.is_ascii_digit() {
+                digits += 1;
+                end = j + 1;
+            } else if b" -.()+".contains(&b) && j + 1 < bytes.len() && (bytes[j + 1].is_ascii_digit() || b"( ".contains(&bytes[j + 1])) {
+                if b != b'+' || j == i {
+                    separators += 1;
+                } else {
+                    break;
+                }
+            } else {
+                break;
+            }
+            j += 1;
+        }
+        if end < bytes.len() && bytes[end] == b')' {
+            end += 1;
+        }
+
+        if (7..=15).contains(&digits) && (bytes[i] == b'+' || separators > 0) && !is_iso_date(&bytes[i..end]) {
+            spans.push((i, end));
+            i = end;
+        } else {
+            i = j.max(i + 1);
+        }
+    }
+    spans
+}
+
+fn is_iso_date(span: &[u8]) -> bool {
+    span.len() >= 10
+        && span[..10]
+            .iter()
+            .enumerate()
+            .all(|(k, b)| if k == 4 || k == 7 { *b == b'-' } else { b.is_ascii_digit() })
+}
+
+/// 256 random bits from the operating system, as hex.
+fn random_key() -> String {
+    let mut key = [0u8; 32];
+    OsRng.fill_bytes(&mut key);
+    key.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// HMAC-SHA256 of the lowercased value under `key`, cut to 64 bits: the
+/// same pseudonym for the same value, which nobody without the key can
+/// link back to it.
+fn keyed_hash(key: &str, value: &str) -> u64 {
+    const BLOCK: usize = 64;
+    let mut block = [0u8; BLOCK];
+    if key.len() > BLOCK {
+        block[..32].copy_from_slice(&Sha256::digest(key.as_bytes()));
+    } else {
+        block[..key.len()].copy_from_slice(key.as_bytes());
+    }
+    let inner = Sha256::new().chain_update(block.map(|b| b ^ 0x36)).chain_update(value.to_lowercase()).finalize();
+    let outer = Sha256::new().chain_update(block.map(|b| b ^ 0x5c)).chain_update(inner).finalize();
+    u64::from_be_bytes(outer[..8].try_into().expect("a digest is longer than 8 bytes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pii_is_detected_and_treated_by_policy() {
+        let text = "Mail jane.doe@example.com or call +1 (555) 123-4567; I live at 221 Baker Street. Ticket 1760000000.";
+        let kinds: Vec<PiiKind> = detect_pii(text).iter().map(|m| m.kind).collect();
+        assert_eq!(kinds, vec![PiiKind::Email, PiiKind::Phone, PiiKind::Address]);
+
+        let filtered = PrivacyFilter::default().filter(text);
+        assert!(!filtered.text.contains("jane.doe") && filtered.text.contains("[email:"));
+        assert!(filtered.text.contains("call [phone];"));
+        assert!(filtered.text.contains("at [address]."));
+        assert!(filtered.text.contains("1760000000"));
+        assert!(detect_pii("Due 2026-10-16 17:00").is_empty());
+        assert!(!filtered.restricted);
+
+        let mut policy = PrivacyPolicy::default();
+        policy.treatments.insert(PiiKind::Address, PiiTreatment::Tag);
+        let tagged = PrivacyFilter::new(policy).filter("Ship it to 10 Downing Street");
+        assert_eq!(tagged.text, "Ship it to 10 Downing Street");
+        let metadata = tagged.access_metadata(Some(Metadata::from([("user_id", "u1")]))).unwrap();
+        assert!(metadata.get_str("access") == Some("restricted") && metadata.get_str("user_id") == Some("u1"));
+
+        // Pseudonyms are HMAC-SHA256 (RFC 4231, test case 2), repeat under one key
+        // only, and the key outlives restarts in its file
+        assert_eq!(keyed_hash("Jefe", "what do ya want for nothing?"), 0x5bdcc146bf60754e);
+        let filter = PrivacyFilter::default();
+        let mention = "Write to jane.doe@example.com";
+        assert_eq!(filter.filter(mention).text, filter.filter(mention).text);
+        assert_ne!(filter.filter(mention).text, PrivacyFilter::default().filter(mention).text);
+        let path = std::env::temp_dir().join(format!("astra_privacy_{}.key", std::process::id()));
+        let first = PrivacyPolicy::with_key_file(&path).unwrap();
+        assert_eq!(PrivacyPolicy::with_key_file(&path).unwrap().key, first.key);
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,293 @@
+// ============================================================================
+//                       ASTRA AGI • PROSPECTIVE MEMORY
+//        Remembering to Act When Something Next Happens
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Holds intentions that wait for an event rather than a time: "when
+//       Dana next mentions the budget, ask about the invoice", "when the
+//       build is green, deploy". Each trigger pairs a condition with what to
+//       do or recall; the runtime checks incoming stimuli and world-state
+//       changes against the waiting triggers and turns the ones that fire
+//       into intents. It complements deadlines, which cover the "at time T"
+//       half of remembering to do things.
+//
+//   Core Functions:
+//       • Store event-based triggers with the action or note they carry
+//       • Match triggers against incoming text and world-state transitions
+//       • Parse "remind me to … when …" requests into triggers
+//       • Retire one-shot triggers once fired and forget a user's triggers
+//
+//   File:        /src/memory/prospective.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+use super::conversation_summary::content_words;
+use crate::planning::planner::WorldState;
+use crate::time::AstraTime;
+
+pub type TriggerId = u64;
+
+/// What a trigger waits for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TriggerCondition {
+    /// Text mentioning all of these words, from `source` if one is given
+    Mentions { words: Vec<String>, source: Option<String> },
+    /// A world-state fact changing to `value`
+    StateBecomes { key: String, value: bool },
+}
+
+/// What firing a trigger should bring about.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TriggerAction {
+    /// Something to do
+    Do(String),
+    /// Something to bring back to mind
+    Recall(String),
+}
+
+impl TriggerAction {
+    /// Description of the intent the action becomes.
+    pub fn intent_description(&self) -> String {
+        match self {
+            TriggerAction::Do(task) => task.clone(),
+            TriggerAction::Recall(note) => format!("Remember: {}", note),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProspectiveTrigger {
+    pub id: TriggerId,
+    pub condition: TriggerCondition,
+    pub action: TriggerAction,
+    /// User who asked for the reminder, if any
+    pub user_id: Option<String>,
+    /// Priority of the intent created on firing
+    pub priority: u32,
+    /// Keep waiting after firing instead of retiring
+    pub repeat: bool,
+    pub created_at: AstraTime,
+    pub times_fired: u32,
+}
+
+/// A trigger that fired, with what it fired on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FiredTrigger {
+    pub trigger_id: TriggerId,
+    pub action: TriggerAction,
+    pub user_id: Option<String>,
+    pub priority: u32,
+    /// The text or state change that matched
+    pub observed: String,
+}
+
+/// Waiting event-based triggers.
+#[derive(Debug, Clone, Default)]
+pub struct ProspectiveMemory {
+    triggers: BTreeMap<TriggerId, ProspectiveTrigger>,
+    next_id: TriggerId,
+    /// World state as last observed, to detect transitions
+    last_state: WorldState,
+}
+
+impl ProspectiveMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores a trigger created at `created_at`, as read from the runtime's
+    /// clock.
+    pub fn add(
+        &mut self,
+        condition: TriggerCondition,
+        action: TriggerAction,
+        user_id: Option<String>,
+        priority: u32,
+        repeat: bool,
+        created_at: AstraTime,
+    ) -> TriggerId {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.triggers.insert(
+            id,
+            ProspectiveTrigger {
+                id,
+                condition,
+                action,
+                user_id,
+                priority,
+                repeat,
+                created_at,
+                times_fired: 0,
+            },
+        );
+        id
+    }
+
+    pub fn remove(&mut self, id: TriggerId) -> Option<ProspectiveTrigger> {
+        self.triggers.remove(&id)
+    }
+
+    pub fn get(&self, id: TriggerId) -> Option<&ProspectiveTrigger> {
+        self.triggers.get(&id)
+    }
+
+    /// Triggers still waiting, oldest first.
+    pub fn pending(&self) -> Vec<&ProspectiveTrigger> {
+        self.triggers.values().collect()
+    }
+
+    /// Fires the triggers that `text` from `source` satisfies.
+    pub fn observe_text(&mut self, source: &str, text: &str) -> Vec<FiredTrigger> {
+        let words: HashSet<String> = content_words(text).collect();
+        self.fire(text, |condition| match condition {
+            TriggerCondition::Mentions { words: wanted, source: wanted_source } => {
+                !wanted.is_empty()
+                    && wanted_source.as_deref().map_or(true, |s| s == source)
+                    && wanted.iter().all(|w| words.contains(w))
+            }
+            TriggerCondition::StateBecomes { .. } => false,
+        })
+    }
+
+    /// Fires the triggers whose fact has changed to the awaited value since
+    /// the last observation. A fact seen for the first time counts as a
+    /// change.
+    pub fn observe_state(&mut self, state: &WorldState) -> Vec<FiredTrigger> {
+        let changed: Vec<(String, bool)> = state
+            .iter()
+            .filter(|(key, value)| self.last_state.get(*key) != Some(*value))
+            .map(|(key, value)| (key.clone(), *value))
+            .collect();
+        self.last_state.extend(changed.iter().cloned());
+
+        let description =
+            changed.iter().map(|(key, value)| format!("{}={}", key, value)).collect::<Vec<_>>().join(", ");
+        self.fire(&description, |condition| match condition {
+            TriggerCondition::StateBecomes { key, value } => changed.iter().any(|(k, v)| k == key && v == value),
+            TriggerCondition::Mentions { .. } => false,
+        })
+    }
+
+    /// Deletes the triggers a user asked for.
+    pub fn forget_user(&mut self, user_id: &str) -> usize {
+        let before = self.triggers.len();
+        self.triggers.retain(|_, t| t.user_id.as_deref() != Some(user_id));
+        before - self.triggers.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.triggers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triggers.is_empty()
+    }
+
+    fn fire(&mut self, observed: &str, matches: impl Fn(&TriggerCondition) -> bool) -> Vec<FiredTrigger> {
+        let mut fired = Vec::new();
+        let mut retired = Vec::new();
+        for trigger in self.triggers.values_mut() {
+            if !matches(&trigger.condition) {
+                continue;
+            }
+            trigger.times_fired += 1;
+            fired.push(FiredTrigger {
+                trigger_id: trigger.id,
+                action: trigger.action.clone(),
+                user_id: trigger.user_id.clone(),
+                priority: trigger.priority,
+                observed: observed.to_string(),
+            });
+            if !trigger.repeat {
+                retired.push(trigger.id);
+            }
+        }
+        for id in retired {
+            self.triggers.remove(&id);
+        }
+        fired
+    }
+}
+
+/// Reads "remind me to Y when X" or "when X, remind me to Y" (also "next
+/// time X"), returning a condition on the words of X and the action Y.
+pub fn parse_reminder(text: &str) -> Option<(TriggerCondition, TriggerAction)> {
+    const CUES: &[&str] = &["next time", "when", "once", "whenever"];
+    // Words that say how a topic turns up rather than what it is.
+    const FILLER: &[&str] = &["someone", "anyone", "mention", "mentions", "talks", "says", "asks", "brings", "comes"];
+
+    let lower = text.trim().trim_end_matches(['.', '!']).to_lowercase();
+    let (condition, task) = if let Some(rest) = lower.strip_prefix("remind me to ") {
+        CUES.iter().find_map(|cue| {
+            let (task, condition) = rest.split_once(&format!(" {} ", cue))?;
+            Some((condition.to_string(), task.to_string()))
+        })?
+    } else {
+        let rest = CUES.iter().find_map(|cue| lower.strip_prefix(&format!("{} ", cue)))?;
+        let (condition, task) = rest.split_once("remind me to ")?;
+        (condition.trim_end_matches([',', ' ']).to_string(), task.to_string())
+    };
+
+    let words: Vec<String> = content_words(&condition).filter(|w| !FILLER.contains(&w.as_str())).collect();
+    let task = task.trim().to_string();
+    if words.is_empty() || task.is_empty() {
+        return None;
+    }
+    Some((TriggerCondition::Mentions { words, source: None }, TriggerAction::Do(task)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_triggers_fire_on_mentions_and_state_changes() {
+        let mut memory = ProspectiveMemory::new();
+        let (condition, action) = parse_reminder("Remind me to ask about the invoice when someone mentions the budget").unwrap();
+        assert_eq!(condition, TriggerCondition::Mentions { words: vec!["budget".into()], source: None });
+        assert_eq!(action, TriggerAction::Do("ask about the invoice".into()));
+        assert!(parse_reminder("Next time the build passes, remind me to deploy").is_some());
+        assert!(parse_reminder("remind me to call mum").is_none());
+
+        let budget = memory.add(
+            TriggerCondition::Mentions { words: vec!["budget".into()], source: Some("user".into()) },
+            TriggerAction::Recall("Dana owes an invoice".into()),
+            Some("ana".into()),
+            5,
+            false,
+            AstraTime::from_secs(1_000),
+        );
+        let deploy = memory.add(
+            TriggerCondition::StateBecomes { key: "build_green".into(), value: true },
+            TriggerAction::Do("deploy".into()),
+            None,
+            7,
+            true,
+            AstraTime::from_secs(1_000),
+        );
+
+        assert!(memory.observe_text("crawler", "The budget was cut").is_empty());
+        let fired = memory.observe_text("user", "Let's review the budget");
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].trigger_id, budget);
+        assert!(memory.get(budget).is_none());
+
+        let mut state = WorldState::from([("build_green".to_string(), false)]);
+        assert!(memory.observe_state(&state).is_empty());
+        state.insert("build_green".into(), true);
+        assert_eq!(memory.observe_state(&state)[0].trigger_id, deploy);
+        // No change, no firing; the repeating trigger keeps waiting.
+        assert!(memory.observe_state(&state).is_empty());
+        assert_eq!(memory.len(), 1);
+    }
+}
@@ -0,0 +1,398 @@
+// ============================================================================
+//                     ASTRA AGI • TIERED MEMORY COMPRESSION
+//        Summarizing Aging Events While Keeping the Detail Reachable
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Keeps hot narrative memory small as experience accumulates. Raw
+//       events stay in memory for a short window; after that, groups of
+//       related events (same kind, same user) are replaced by a single
+//       summary event, and the originals move to an archive. Summaries age
+//       in turn and are summarized again into coarser tiers. Every summary
+//       links to what it stands for, so a reader can drill down from a week
+//       at a glance to the individual events behind it.
+//
+//   Core Functions:
+//       • Configure the age at which each tier is summarized into the next
+//       • Group aging events by kind and user and summarize each group
+//       • Archive summarized events and drill down from a summary to them
+//       • Archive events the capacity limit pushed out of hot memory
+//       • Delete a user's archived events along with their hot ones
+//       • Hand the oldest archived events over to disk (see archive_file.rs)
+//       • Let hosts keep archived events in a store of their own
+//       • List events archived after a given id, and restore recorded ones
+//
+//   File:        /src/memory/summary_tiers.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use std::collections::{BTreeMap, HashSet};
+use std::ops::Bound;
+use std::time::Duration;
+
+use std::path::Path;
+
+use super::archive_file::write_file;
+use super::conversation_summary::SUMMARY_EVENT_TYPE as CONVERSATION_SUMMARY_TYPE;
+use super::error::ArchiveError;
+use super::narrative_memory::{EventId, EventLink, LinkRelation, LinkTarget, NarrativeEvent, NarrativeMemory};
+use crate::metadata::Metadata;
+use crate::time::AstraTime;
+
+/// Event type of the summaries this module writes.
+pub const TIER_SUMMARY_EVENT_TYPE: &str = "memory_summary";
+
+/// Descriptions quoted in a summary of raw events.
+const SAMPLE_DESCRIPTIONS: usize = 3;
+
+/// When events are summarized.
+#[derive(Debug, Clone)]
+pub struct TierPolicy {
+    /// Age at which events of each tier are summarized into the next;
+    /// index 0 applies to raw events. Summaries in the last tier are kept.
+    pub windows: Vec<Duration>,
+    /// Groups smaller than this are left as they are
+    pub min_group: usize,
+    /// Raw event types that are never summarized
+    pub exempt_types: HashSet<String>,
+}
+
+impl Default for TierPolicy {
+    fn default() -> Self {
+        TierPolicy {
+            windows: vec![
+                Duration::from_secs(60 * 60),
+                Duration::from_secs(24 * 60 * 60),
+                Duration::from_secs(7 * 24 * 60 * 60),
+            ],
+            min_group: 3,
+            // Conversation summaries are already condensed and are read
+            // back when a user returns.
+            exempt_types: HashSet::from([CONVERSATION_SUMMARY_TYPE.to_string()]),
+        }
+    }
+}
+
+/// Events that have been replaced by summaries, by id.
+#[derive(Debug, Clone, Default)]
+pub struct EventArchive {
+    events: BTreeMap<EventId, NarrativeEvent>,
+}
+
+impl EventArchive {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, id: EventId) -> Option<&NarrativeEvent> {
+        self.events.get(&id)
+    }
+
+    /// The events a summary stands for, oldest first. For a summary of
+    /// summaries these are the finer summaries, which can be drilled into
+    /// in turn.
+    pub fn drill_down(&self, summary: &NarrativeEvent) -> Vec<&NarrativeEvent> {
+        let mut events: Vec<&NarrativeEvent> = summary
+            .links
+            .iter()
+            .filter(|link| link.relation == LinkRelation::Summarizes)
+            .filter_map(|link| match link.target {
+                LinkTarget::Event(id) => self.events.get(&id),
+                _ => None,
+            })
+            .collect();
+        events.sort_by_key(|e| e.timestamp);
+        events
+    }
+
+    /// Archived events with ids after `id`, in id order.
+    pub fn events_after(&self, id: EventId) -> impl Iterator<Item = &NarrativeEvent> {
+        self.events.range((Bound::Excluded(id), Bound::Unbounded)).map(|(_, e)| e)
+    }
+
+    /// Puts back an event as it was archived.
+    pub fn restore(&mut self, event: NarrativeEvent) {
+        self.events.insert(event.id, event);
+    }
+
+    /// Archived events concerning `user_id`, oldest first.
+    pub fn events_for_user(&self, user_id: &str) -> Vec<&NarrativeEvent> {
+        let mut events: Vec<&NarrativeEvent> =
+            self.events.values().filter(|e| e.user_id().as_deref() == Some(user_id)).collect();
+        events.sort_by_key(|e| e.timestamp);
+        events
+    }
+
+    /// Deletes the archived events concerning `user_id`.
+    pub fn forget_user(&mut self, user_id: &str) -> usize {
+        let before = self.events.len();
+        self.events.retain(|_, e| e.user_id().as_deref() != Some(user_id));
+        before - self.events.len()
+    }
+
+    /// Archived events recorded before `cutoff`, in id order.
+    pub fn events_before(&self, cutoff: AstraTime) -> impl Iterator<Item = &NarrativeEvent> {
+        self.events.values().filter(move |e| e.timestamp < cutoff)
+    }
+
+    /// Removes and returns the archived events recorded before `cutoff`.
+    pub fn take_before(&mut self, cutoff: AstraTime) -> Vec<NarrativeEvent> {
+        let old: Vec<EventId> = self.events.values().filter(|e| e.timestamp < cutoff).map(|e| e.id).collect();
+        old.into_iter().filter_map(|id| self.events.remove(&id)).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+/// Where events that leave narrative memory are kept: those replaced by
+/// summaries and those its capacity limit pushed out. `EventArchive` keeps
+/// them in memory; a host can bring a store of its own.
+pub trait MemoryStore: Send {
+    fn get(&self, id: EventId) -> Option<NarrativeEvent>;
+
+    /// Keeps `event`, or puts it back as it was archived.
+    fn restore(&mut self, event: NarrativeEvent);
+
+    /// Events with ids after `id`, in id order.
+    fn events_after(&self, id: EventId) -> Vec<NarrativeEvent>;
+
+    /// Id of the newest event kept.
+    fn newest_id(&self) -> Option<EventId>;
+
+    /// Events recorded before `cutoff`, in id order.
+    fn events_before(&self, cutoff: AstraTime) -> Vec<NarrativeEvent>;
+
+    /// Removes and returns the events recorded before `cutoff`.
+    fn take_before(&mut self, cutoff: AstraTime) -> Vec<NarrativeEvent>;
+
+    /// Events concerning `user_id`, oldest first.
+    fn events_for_user(&self, user_id: &str) -> Vec<NarrativeEvent>;
+
+    /// Deletes the events concerning `user_id`, returning how many.
+    fn forget_user(&mut self, user_id: &str) -> usize;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Moves events recorded before `before` into a new archive file at
+    /// `path`, returning how many moved. The events leave the store only
+    /// once the file is written and synced, so a failed write loses nothing.
+    fn spill(&mut self, before: AstraTime, path: &Path) -> Result<usize, ArchiveError> {
+        let old = self.events_before(before);
+        if old.is_empty() {
+            return Ok(0);
+        }
+        write_file(path, old)?;
+        Ok(self.take_before(before).len())
+    }
+}
+
+impl MemoryStore for EventArchive {
+    fn get(&self, id: EventId) -> Option<NarrativeEvent> {
+        EventArchive::get(self, id).cloned()
+    }
+
+    fn restore(&mut self, event: NarrativeEvent) {
+        EventArchive::restore(self, event);
+    }
+
+    fn events_after(&self, id: EventId) -> Vec<NarrativeEvent> {
+        EventArchive::events_after(self, id).cloned().collect()
+    }
+
+    fn newest_id(&self) -> Option<EventId> {
+        self.events.keys().next_back().copied()
+    }
+
+    fn events_before(&self, cutoff: AstraTime) -> Vec<NarrativeEvent> {
+        EventArchive::events_before(self, cutoff).cloned().collect()
+    }
+
+    fn take_before(&mut self, cutoff: AstraTime) -> Vec<NarrativeEvent> {
+        EventArchive::take_before(self, cutoff)
+    }
+
+    fn events_for_user(&self, user_id: &str) -> Vec<NarrativeEvent> {
+        EventArchive::events_for_user(self, user_id).into_iter().cloned().collect()
+    }
+
+    fn forget_user(&mut self, user_id: &str) -> usize {
+        EventArchive::forget_user(self, user_id)
+    }
+
+    fn len(&self) -> usize {
+        EventArchive::len(self)
+    }
+}
+
+/// Summary tier of an event: 0 for raw events.
+pub fn tier_of(event: &NarrativeEvent) -> usize {
+    if event.event_type != TIER_SUMMARY_EVENT_TYPE {
+        return 0;
+    }
+    summary_metadata(event).and_then(|m| m.get_u64("tier")).unwrap_or(1) as usize
+}
+
+/// Kind of event and the user it concerns, which events are grouped by.
+type GroupKey = (String, Option<String>);
+
+/// Summarizes every group of events that has outgrown its tier's window,
+/// moving the originals into `archive`, along with any events memory's
+/// capacity limit pushed out. Returns the ids of the new summaries.
+pub fn compress(
+    memory: &mut NarrativeMemory,
+    archive: &mut dyn MemoryStore,
+    policy: &TierPolicy,
+    now: AstraTime,
+) -> Vec<EventId> {
+    let mut summaries = Vec::new();
+    for event in memory.take_evicted() {
+        archive.restore(event);
+    }
+
+    for (tier, window) in policy.windows.iter().enumerate() {
+        // Group by kind of event and the user it concerns, keeping the
+        // groups in order of their oldest member.
+        let mut groups: Vec<(GroupKey, Vec<EventId>)> = Vec::new();
+        for event in memory.events.iter() {
+            if tier_of(event) != tier || now.saturating_duration_since(event.timestamp) < *window {
+                continue;
+            }
+            if tier == 0 && policy.exempt_types.contains(&event.event_type) {
+                continue;
+            }
+            let key = (summarized_type(event), event.user_id());
+            match groups.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, ids)) => ids.push(event.id),
+                None => groups.push((key, vec![event.id])),
+            }
+        }
+
+        for ((event_type, user_id), ids) in groups {
+            if ids.len() < policy.min_group.max(2) {
+                continue;
+            }
+            let members = memory.take_events(&ids.into_iter().collect());
+            let first = members.first().map(|e| e.timestamp).unwrap_or(now);
+            let last = members.last().map(|e| e.timestamp).unwrap_or(now);
+            let count: u64 = members.iter().map(covered_count).sum();
+
+            let mut description = format!("{} {} event(s) from {} to {}", count, event_type, first, last);
+            if tier == 0 {
+                let mut samples: Vec<&str> = Vec::new();
+                for member in &members {
+                    if samples.len() < SAMPLE_DESCRIPTIONS && !samples.contains(&member.description.as_str()) {
+                        samples.push(&member.description);
+                    }
+                }
+                description.push_str(&format!(": {}", samples.join("; ")));
+            }
+
+            let mut metadata = Metadata::new()
+                .with("tier", tier + 1)
+                .with("event_type", event_type)
+                .with("count", count)
+                .with("from", first.as_millis())
+                .with("to", last.as_millis());
+            if let Some(user_id) = user_id {
+                metadata.insert("user_id", user_id);
+            }
+            // Summaries quote their members, so a restricted member restricts the summary.
+            if members.iter().any(|e| summary_metadata(e).is_some_and(|m| m.get_str("access") == Some("restricted"))) {
+                metadata.insert("access", "restricted");
+            }
+            let links = members
+                .iter()
+                .map(|e| EventLink { relation: LinkRelation::Summarizes, target: LinkTarget::Event(e.id) })
+                .collect();
+
+            for member in members {
+                archive.restore(member);
+            }
+            let id = memory.insert_at_time(last, TIER_SUMMARY_EVENT_TYPE, description, Some(metadata), links);
+            summaries.push(id);
+        }
+    }
+
+    summaries
+}
+
+fn summary_metadata(event: &NarrativeEvent) -> Option<&Metadata> {
+    event.metadata.as_ref()
+}
+
+/// The raw event type a (possibly summary) event stands for.
+fn summarized_type(event: &NarrativeEvent) -> String {
+    if event.event_type == TIER_SUMMARY_EVENT_TYPE {
+        if let Some(event_type) = summary_metadata(event).and_then(|m| m.get_str("event_type").map(str::to_string)) {
+            return event_type;
+        }
+    }
+    event.event_type.clone()
+}
+
+/// Number of raw events an event stands for.
+fn covered_count(event: &NarrativeEvent) -> u64 {
+    if event.event_type == TIER_SUMMARY_EVENT_TYPE {
+        summary_metadata(event).and_then(|m| m.get_u64("count")).unwrap_or(1)
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_old_events_are_summarized_and_can_be_drilled_into() {
+        let mut memory = NarrativeMemory::new(100);
+        let mut archive = EventArchive::new();
+        for i in 0..4 {
+            memory.add_event("tick", format!("Tick {}", i), None);
+        }
+        memory.add_event("error", "Disk full", None);
+        memory.add_event(CONVERSATION_SUMMARY_TYPE, "Talked about boats", None);
+        let policy = TierPolicy::default();
+
+        // Nothing is old enough yet.
+        assert!(compress(&mut memory, &mut archive, &policy, AstraTime::now()).is_empty());
+
+        let later = AstraTime::now() + Duration::from_secs(2 * 60 * 60);
+        let summaries = compress(&mut memory, &mut archive, &policy, later);
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(memory.events.len(), 3);
+        assert_eq!(archive.len(), 4);
+
+        let summary = memory.get(summaries[0]).unwrap();
+        assert_eq!(tier_of(summary), 1);
+        assert!(summary.description.starts_with("4 tick event(s)"));
+        let originals: Vec<&str> = archive.drill_down(summary).iter().map(|e| e.description.as_str()).collect();
+        assert_eq!(originals, vec!["Tick 0", "Tick 1", "Tick 2", "Tick 3"]);
+
+        // A single tier-1 summary is too small a group to summarize again.
+        let much_later = later + Duration::from_secs(2 * 24 * 60 * 60);
+        assert!(compress(&mut memory, &mut archive, &policy, much_later).is_empty());
+
+        // Events pushed out of a full memory are archived, not lost
+        memory.max_capacity = memory.events.len();
+        let evicted = memory.events.front().unwrap().id;
+        memory.add_event("error", "Disk still full", None);
+        compress(&mut memory, &mut archive, &policy, much_later);
+        assert!(archive.get(evicted).is_some());
+    }
+}
@@ -0,0 +1,402 @@
+// =============================================================================
+//  Astra AGI - Structured Metadata
+//  File: metadata.rs
+//
+//  Description: The one shape of metadata attached to intents, narrative
+//  events, and facts. Values are strings, numbers, booleans, lists, nested
+//  maps, or references to knowledge base entities, read back through typed
+//  accessors instead of being parsed out of strings. Metadata serializes as
+//  plain JSON, an entity reference as `{"$entity": id}`, so it reads
+//  naturally in logs and exports. JSON null has no value here: null map
+//  entries are left out, as if absent. Event metadata written before it was
+//  structured, as JSON text, still loads.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Number, Value};
+
+use crate::knowledge::extended_ontology::EntityId;
+
+/// Key of the single entry of a JSON object standing for an entity reference.
+const ENTITY_KEY: &str = "$entity";
+
+/// Key legacy metadata text that is not a JSON object is kept under.
+pub const TEXT_KEY: &str = "text";
+
+/// One metadata value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetaValue {
+    String(String),
+    Number(Number),
+    Bool(bool),
+    List(Vec<MetaValue>),
+    Map(Metadata),
+    /// An entity in the knowledge base
+    EntityRef(EntityId),
+}
+
+impl MetaValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            MetaValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            MetaValue::Number(n) => n.as_f64(),
+            _ => None,
+        }
+    }
+
+    /// The number, if it is a whole number that fits.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            MetaValue::Number(n) => n.as_u64(),
+            _ => None,
+        }
+    }
+
+    /// The number, if it is a whole number that fits.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            MetaValue::Number(n) => n.as_i64(),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            MetaValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[MetaValue]> {
+        match self {
+            MetaValue::List(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    pub fn as_map(&self) -> Option<&Metadata> {
+        match self {
+            MetaValue::Map(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    pub fn as_entity(&self) -> Option<EntityId> {
+        match self {
+            MetaValue::EntityRef(id) => Some(*id),
+            _ => None,
+        }
+    }
+
+    /// A record as a value, if it serializes to anything but null.
+    pub fn from_record<T: Serialize>(record: &T) -> Option<Self> {
+        MetaValue::from_json(serde_json::to_value(record).ok()?)
+    }
+
+    /// The value as JSON, the form it serializes to.
+    pub fn to_json(&self) -> Value {
+        match self {
+            MetaValue::String(s) => Value::String(s.clone()),
+            MetaValue::Number(n) => Value::Number(n.clone()),
+            MetaValue::Bool(b) => Value::Bool(*b),
+            MetaValue::List(list) => Value::Array(list.iter().map(MetaValue::to_json).collect()),
+            MetaValue::Map(map) => map.to_json(),
+            MetaValue::EntityRef(id) => serde_json::json!({ ENTITY_KEY: id }),
+        }
+    }
+
+    /// The value of `json`; `None` for null.
+    pub fn from_json(json: Value) -> Option<Self> {
+        Some(match json {
+            Value::Null => return None,
+            Value::String(s) => MetaValue::String(s),
+            Value::Number(n) => MetaValue::Number(n),
+            Value::Bool(b) => MetaValue::Bool(b),
+            Value::Array(items) => MetaValue::List(items.into_iter().filter_map(MetaValue::from_json).collect()),
+            Value::Object(object) => {
+                if let (1, Some(id)) = (object.len(), object.get(ENTITY_KEY).and_then(Value::as_u64)) {
+                    return Some(MetaValue::EntityRef(id));
+                }
+                MetaValue::Map(Metadata::from_json_object(object))
+            }
+        })
+    }
+}
+
+/// Strings as they are, anything else as JSON.
+impl fmt::Display for MetaValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetaValue::String(s) => f.write_str(s),
+            other => write!(f, "{}", other.to_json()),
+        }
+    }
+}
+
+impl Serialize for MetaValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_json().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MetaValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        MetaValue::from_json(Value::deserialize(deserializer)?)
+            .ok_or_else(|| serde::de::Error::custom("null is not a metadata value"))
+    }
+}
+
+impl From<String> for MetaValue {
+    fn from(s: String) -> Self {
+        MetaValue::String(s)
+    }
+}
+
+impl From<&str> for MetaValue {
+    fn from(s: &str) -> Self {
+        MetaValue::String(s.to_string())
+    }
+}
+
+impl From<bool> for MetaValue {
+    fn from(b: bool) -> Self {
+        MetaValue::Bool(b)
+    }
+}
+
+impl From<u64> for MetaValue {
+    fn from(n: u64) -> Self {
+        MetaValue::Number(n.into())
+    }
+}
+
+impl From<u32> for MetaValue {
+    fn from(n: u32) -> Self {
+        MetaValue::Number(n.into())
+    }
+}
+
+impl From<usize> for MetaValue {
+    fn from(n: usize) -> Self {
+        MetaValue::Number(n.into())
+    }
+}
+
+impl From<i64> for MetaValue {
+    fn from(n: i64) -> Self {
+        MetaValue::Number(n.into())
+    }
+}
+
+/// Not-a-number and infinities, which JSON cannot hold, become strings.
+impl From<f64> for MetaValue {
+    fn from(n: f64) -> Self {
+        Number::from_f64(n).map_or_else(|| MetaValue::String(n.to_string()), MetaValue::Number)
+    }
+}
+
+impl From<f32> for MetaValue {
+    fn from(n: f32) -> Self {
+        MetaValue::from(f64::from(n))
+    }
+}
+
+impl<T: Into<MetaValue>> From<Vec<T>> for MetaValue {
+    fn from(items: Vec<T>) -> Self {
+        MetaValue::List(items.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<Metadata> for MetaValue {
+    fn from(map: Metadata) -> Self {
+        MetaValue::Map(map)
+    }
+}
+
+/// Metadata entries by key, kept in key order so they serialize the same
+/// way every time.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Metadata(BTreeMap<String, MetaValue>);
+
+impl Metadata {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `self` with `key` set to `value`.
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<MetaValue>) -> Self {
+        self.insert(key, value);
+        self
+    }
+
+    /// Sets `key` to `value`, returning the value it replaces.
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<MetaValue>) -> Option<MetaValue> {
+        self.0.insert(key.into(), value.into())
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<MetaValue> {
+        self.0.remove(key)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&MetaValue> {
+        self.0.get(key)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key)?.as_str()
+    }
+
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        self.get(key)?.as_f64()
+    }
+
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        self.get(key)?.as_u64()
+    }
+
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key)?.as_bool()
+    }
+
+    pub fn get_entity(&self, key: &str) -> Option<EntityId> {
+        self.get(key)?.as_entity()
+    }
+
+    /// The value under `key` decoded as a record of type `T`.
+    pub fn get_record<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        serde_json::from_value(self.get(key)?.to_json()).ok()
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &MetaValue)> + '_ {
+        self.0.iter().map(|(key, value)| (key.as_str(), value))
+    }
+
+    /// The metadata as a JSON object.
+    pub fn to_json(&self) -> Value {
+        Value::Object(self.0.iter().map(|(key, value)| (key.clone(), value.to_json())).collect())
+    }
+
+    /// The fields of a JSON object, leaving out nulls.
+    pub fn from_json_object(object: serde_json::Map<String, Value>) -> Self {
+        Metadata(object.into_iter().filter_map(|(key, value)| Some((key, MetaValue::from_json(value)?))).collect())
+    }
+
+    /// A record's fields as metadata, if it serializes to a JSON object.
+    pub fn from_record<T: Serialize>(record: &T) -> Option<Self> {
+        match serde_json::to_value(record).ok()? {
+            Value::Object(object) => Some(Self::from_json_object(object)),
+            _ => None,
+        }
+    }
+
+    /// The metadata decoded as the record `from_record` made it from.
+    pub fn to_record<T: DeserializeOwned>(&self) -> Option<T> {
+        serde_json::from_value(self.to_json()).ok()
+    }
+
+    /// Metadata stored as text before it was structured: the fields of a
+    /// JSON object, or any other text under `TEXT_KEY`.
+    pub fn from_legacy_text(text: &str) -> Self {
+        match serde_json::from_str(text) {
+            Ok(Value::Object(object)) => Self::from_json_object(object),
+            _ => Metadata::new().with(TEXT_KEY, text),
+        }
+    }
+}
+
+impl Serialize for Metadata {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+/// A map, or legacy metadata text (see `Metadata::from_legacy_text`).
+impl<'de> Deserialize<'de> for Metadata {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Value::deserialize(deserializer)? {
+            Value::Object(object) => Ok(Self::from_json_object(object)),
+            Value::String(text) => Ok(Self::from_legacy_text(&text)),
+            other => Err(serde::de::Error::custom(format!("expected metadata, found {}", other))),
+        }
+    }
+}
+
+impl<K: Into<String>, V: Into<MetaValue>> FromIterator<(K, V)> for Metadata {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(entries: I) -> Self {
+        Metadata(entries.into_iter().map(|(key, value)| (key.into(), value.into())).collect())
+    }
+}
+
+impl<K: Into<String>, V: Into<MetaValue>, const N: usize> From<[(K, V); N]> for Metadata {
+    fn from(entries: [(K, V); N]) -> Self {
+        entries.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_round_trips_and_reads_typed() {
+        let metadata = Metadata::from([("user_id", MetaValue::from("ana")), ("seed", u64::MAX.into())])
+            .with("importance", 0.8)
+            .with("confirmed", true)
+            .with("about", MetaValue::EntityRef(42))
+            .with("tags", vec!["garden", "water"])
+            .with("progress", Metadata::new().with("done", 3u64).with("of", 5u64));
+
+        let json = serde_json::to_string(&metadata).unwrap();
+        assert!(json.contains(r#""about":{"$entity":42}"#));
+        assert!(json.contains(r#""seed":18446744073709551615"#));
+        let loaded: Metadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, metadata);
+
+        assert_eq!(loaded.get_str("user_id"), Some("ana"));
+        assert_eq!(loaded.get_u64("seed"), Some(u64::MAX));
+        assert_eq!(loaded.get_f64("importance"), Some(0.8));
+        assert_eq!(loaded.get_bool("confirmed"), Some(true));
+        assert_eq!(loaded.get_entity("about"), Some(42));
+        assert_eq!(loaded.get("tags").and_then(MetaValue::as_list).map(<[_]>::len), Some(2));
+        assert_eq!(loaded.get("progress").and_then(MetaValue::as_map).and_then(|p| p.get_u64("of")), Some(5));
+        // Typed accessors do not coerce
+        assert_eq!(loaded.get_str("importance"), None);
+        assert_eq!(loaded.get_u64("importance"), None);
+        assert_eq!(loaded.get("tags").unwrap().to_string(), r#"["garden","water"]"#);
+
+        // Text written before metadata was structured
+        let legacy: Metadata = serde_json::from_str(r#""{\"user_id\":\"ben\",\"note\":null}""#).unwrap();
+        assert_eq!(legacy, Metadata::from([("user_id", "ben")]));
+        assert_eq!(Metadata::from_legacy_text("7").get_str(TEXT_KEY), Some("7"));
+        assert!(serde_json::from_str::<Metadata>("[1]").is_err());
+        assert_eq!(MetaValue::from(f64::NAN).as_str(), Some("NaN"));
+    }
+}
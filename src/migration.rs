@@ -0,0 +1,241 @@
+// =============================================================================
+//  Astra AGI - Migrations of Serialized State
+//  File: migration.rs
+//
+//  Description: Lets state written by one release load in the next after
+//  its shape has changed. Persisted state is wrapped in an envelope naming
+//  its format and the version of that format it was written in. Each
+//  format registers a migration from every earlier version to the one after
+//  it; loading state of an older version runs the migrations in turn on the
+//  JSON before decoding it, instead of failing to deserialize. A format is
+//  at version 1 until it first changes shape, so state written before the
+//  envelope was introduced is read as version 1. A migration that cannot
+//  rewrite the state it is given says why with a typed StepError.
+//
+//  Layout of an envelope:
+//      { "format": name, "version": number, "data": state }
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+/// Rewrites state of one version of a format as the next version.
+pub type Migration = fn(Value) -> Result<Value, StepError>;
+
+/// State persisted in a versioned envelope.
+pub trait Versioned: Serialize + DeserializeOwned {
+    /// Name of the format, the same in every version
+    const FORMAT: &'static str;
+    /// Version written now; raised by one with each change of shape
+    const VERSION: u32;
+
+    /// Registers the migration from each earlier version to the next.
+    fn migrations(_registry: &mut MigrationRegistry) {}
+}
+
+/// State as written, tagged with its format and version.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Envelope {
+    pub format: String,
+    pub version: u32,
+    pub data: Value,
+}
+
+/// Why one migration could not rewrite state.
+#[derive(Debug, Error)]
+pub enum StepError {
+    /// A field the migration reads is absent or of another type
+    #[error("Field {field} is missing or not {expected}")]
+    Field { field: &'static str, expected: &'static str },
+    #[error("Could not reshape state")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Why state could not be brought up to the current version.
+#[derive(Debug, Error)]
+pub enum MigrationError {
+    #[error("Expected {expected} state, found {found}")]
+    WrongFormat { expected: &'static str, found: String },
+    /// Written by a newer release than this one
+    #[error("{format} version {version} is newer than version {supported}, the newest this release reads")]
+    TooNew { format: String, version: u32, supported: u32 },
+    #[error("No migration of {format} from version {from}")]
+    MissingMigration { format: String, from: u32 },
+    #[error("Migrating {format} from version {from} failed")]
+    Failed { format: String, from: u32, #[source] source: StepError },
+    #[error("Could not encode or decode state")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Migrations by format and the version they migrate from.
+#[derive(Debug, Clone, Default)]
+pub struct MigrationRegistry {
+    steps: HashMap<(String, u32), Migration>,
+}
+
+impl MigrationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The migrations `T` registers.
+    pub fn of<T: Versioned>() -> Self {
+        let mut registry = Self::new();
+        T::migrations(&mut registry);
+        registry
+    }
+
+    /// Registers the migration of `format` from version `from` to
+    /// `from + 1`, replacing any registered before.
+    pub fn register(&mut self, format: &str, from: u32, migration: Migration) -> &mut Self {
+        self.steps.insert((format.to_string(), from), migration);
+        self
+    }
+
+    /// `envelope`'s state migrated version by version to `to`.
+    pub fn upgrade(&self, envelope: Envelope, to: u32) -> Result<Value, MigrationError> {
+        let Envelope { format, mut version, mut data } = envelope;
+        if version > to {
+            return Err(MigrationError::TooNew { format, version, supported: to });
+        }
+        while version < to {
+            let Some(migration) = self.steps.get(&(format.clone(), version)) else {
+                return Err(MigrationError::MissingMigration { format, from: version });
+            };
+            data = migration(data)
+                .map_err(|source| MigrationError::Failed { format: format.clone(), from: version, source })?;
+            version += 1;
+        }
+        Ok(data)
+    }
+
+    /// Decodes `value`, an envelope of `T` or state written before the
+    /// envelope, migrating it to the current version first if need be.
+    pub fn read<T: Versioned>(&self, value: Value) -> Result<T, MigrationError> {
+        let envelope = match Envelope::deserialize(&value) {
+            Ok(envelope) => envelope,
+            Err(_) => Envelope { format: T::FORMAT.to_string(), version: 1, data: value },
+        };
+        if envelope.format != T::FORMAT {
+            return Err(MigrationError::WrongFormat { expected: T::FORMAT, found: envelope.format });
+        }
+        let version = envelope.version;
+        let data = self.upgrade(envelope, T::VERSION)?;
+        if version < T::VERSION {
+            log::info!("Migrated {} from version {} to {}", T::FORMAT, version, T::VERSION);
+        }
+        Ok(serde_json::from_value(data)?)
+    }
+}
+
+/// `value` in an envelope of its format's current version.
+pub fn envelope<T: Versioned>(value: &T) -> Result<Envelope, serde_json::Error> {
+    Ok(Envelope { format: T::FORMAT.to_string(), version: T::VERSION, data: serde_json::to_value(value)? })
+}
+
+/// `value` as JSON in an envelope of its format's current version.
+pub fn encode<T: Versioned>(value: &T) -> Result<Vec<u8>, serde_json::Error> {
+    serde_json::to_vec(&envelope(value)?)
+}
+
+/// Decodes JSON `bytes` written by `encode` in this or an earlier release,
+/// or written before the envelope was introduced.
+pub fn decode<T: Versioned>(bytes: &[u8]) -> Result<T, MigrationError> {
+    MigrationRegistry::of::<T>().read(serde_json::from_slice(bytes)?)
+}
+
+/// Serializes a field in an envelope, and deserializes it through its
+/// migrations: `#[serde(with = "crate::migration::versioned")]`.
+pub mod versioned {
+    use super::*;
+    use serde::{de, Deserializer, Serializer};
+
+    pub fn serialize<T: Versioned, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        envelope(value).map_err(serde::ser::Error::custom)?.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: Versioned, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        MigrationRegistry::of::<T>().read(Value::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Version 1 held `name`; version 2 split it into `first` and `last`;
+    /// version 3 added `nickname`.
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Contact {
+        first: String,
+        last: String,
+        nickname: Option<String>,
+    }
+
+    impl Versioned for Contact {
+        const FORMAT: &'static str = "test.contact";
+        const VERSION: u32 = 3;
+
+        fn migrations(registry: &mut MigrationRegistry) {
+            registry
+                .register(Self::FORMAT, 1, |mut data| {
+                    let name = data["name"].take();
+                    let name = name.as_str().ok_or(StepError::Field { field: "name", expected: "a string" })?;
+                    let (first, last) = name.split_once(' ').unwrap_or((name, ""));
+                    Ok(serde_json::json!({ "first": first, "last": last }))
+                })
+                .register(Self::FORMAT, 2, |mut data| {
+                    data["nickname"] = Value::Null;
+                    Ok(data)
+                });
+        }
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Book {
+        #[serde(with = "versioned")]
+        author: Contact,
+    }
+
+    #[test]
+    fn test_old_state_loads_through_chained_migrations() {
+        let ana = Contact { first: "Ana".into(), last: "Silva".into(), nickname: None };
+        let bytes = encode(&ana).unwrap();
+        assert!(std::str::from_utf8(&bytes).unwrap().starts_with(r#"{"format":"test.contact","version":3,"#));
+        assert_eq!(decode::<Contact>(&bytes).unwrap(), ana);
+
+        // From before the envelope, and from each older version
+        assert_eq!(decode::<Contact>(br#"{"name": "Ana Silva"}"#).unwrap(), ana);
+        let v2 = br#"{"format": "test.contact", "version": 2, "data": {"first": "Ana", "last": "Silva"}}"#;
+        assert_eq!(decode::<Contact>(v2).unwrap(), ana);
+
+        assert!(matches!(
+            decode::<Contact>(br#"{"name": 7}"#),
+            Err(MigrationError::Failed { from: 1, source: StepError::Field { field: "name", .. }, .. })
+        ));
+        let newer = br#"{"format": "test.contact", "version": 4, "data": {}}"#;
+        assert!(matches!(decode::<Contact>(newer), Err(MigrationError::TooNew { version: 4, supported: 3, .. })));
+        let other = br#"{"format": "test.invoice", "version": 1, "data": {}}"#;
+        assert!(matches!(decode::<Contact>(other), Err(MigrationError::WrongFormat { .. })));
+        let registry = MigrationRegistry::new();
+        let envelope = Envelope { format: "test.contact".into(), version: 1, data: Value::Null };
+        assert!(matches!(registry.upgrade(envelope, 2), Err(MigrationError::MissingMigration { from: 1, .. })));
+
+        // Fields migrate too
+        let book: Book = serde_json::from_str(r#"{"author": {"name": "Ana Silva"}}"#).unwrap();
+        assert_eq!(book.author, ana);
+        let json = serde_json::to_string(&book).unwrap();
+        assert_eq!(serde_json::from_str::<Book>(&json).unwrap(), book);
+    }
+}
@@ -13,127 +13,103 @@
 //       • Represent multidimensional emotional state (happiness, sadness, anger, fear)
 //       • Compute affective valence and arousal for behavioral modulation
 //       • Blend emotional states using weighted stimuli
-//       • Apply decay functions for natural emotional stabilization
+//       • Apply decay functions for natural emotional stabilization, timed
+//         by an injected clock (the arithmetic lives in core::affect)
 //       • Provide mood tracking for long‑term affective continuity
+//       • Pick up the user's emotional tone through calibrated contagion
 //
 //   File:        /src/personality/emotion.rs
 //   Author:      Alex Roussinov
 //   Created:     2026-01-11
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
-use std::time::{Duration, Instant};
+use std::ops::{Deref, DerefMut};
 
-/// Represents Astra’s instantaneous emotional state.
-/// Values are normalized between 0.0 and 1.0.
-#[derive(Debug, Clone)]
-pub struct EmotionState {
-    pub happiness: f32,
-    pub sadness: f32,
-    pub anger: f32,
-    pub fear: f32,
-}
+use crate::time::{system_clock, AstraTime, SharedClock};
 
-impl EmotionState {
-    /// Creates a neutral emotional state.
-    pub fn neutral() -> Self {
-        Self {
-            happiness: 0.5,
-            sadness: 0.0,
-            anger: 0.0,
-            fear: 0.0,
-        }
-    }
+pub use crate::core::affect::{Affect, EmotionState, Mood, MAX_CONTAGION_INTENSITY};
 
-    /// Computes emotional valence: positive vs. negative affect.
-    pub fn valence(&self) -> f32 {
-        self.happiness - (self.sadness + self.anger + self.fear) * 0.5
-    }
-
-    /// Computes emotional arousal: intensity of activation.
-    pub fn arousal(&self) -> f32 {
-        (self.happiness + self.anger + self.fear).max(0.1)
-    }
-
-    /// Blends this emotional state with another using a weight factor.
-    pub fn blend(&mut self, other: &EmotionState, weight: f32) {
-        let w = weight.clamp(0.0, 1.0);
-        self.happiness = self.happiness * (1.0 - w) + other.happiness * w;
-        self.sadness = self.sadness * (1.0 - w) + other.sadness * w;
-        self.anger = self.anger * (1.0 - w) + other.anger * w;
-        self.fear = self.fear * (1.0 - w) + other.fear * w;
-    }
-}
-
-/// Represents long‑term mood, which evolves more slowly than emotion.
-#[derive(Debug, Clone)]
-pub struct Mood {
-    pub baseline: f32, // 0 (negative) to 1 (positive)
-}
-
-impl Mood {
-    pub fn new() -> Self {
-        Self { baseline: 0.6 }
-    }
-
-    /// Updates mood based on emotional valence.
-    pub fn update_from_emotion(&mut self, emotion: &EmotionState) {
-        let delta = emotion.valence() * 0.05;
-        self.baseline = (self.baseline + delta).clamp(0.0, 1.0);
-    }
-}
-
-/// Engine for managing emotional transitions, decay, and stimulus integration.
+/// Engine for managing emotional transitions, decay, and stimulus
+/// integration against a clock, the system clock unless another is set.
+/// Derefs to the clock-free `core::affect::Affect` it drives.
 pub struct EmotionDynamics {
-    pub current: EmotionState,
-    pub mood: Mood,
-    last_update: Instant,
-    decay_rate: f32,
+    affect: Affect,
+    clock: SharedClock,
+    last_update: AstraTime,
 }
 
 impl EmotionDynamics {
     pub fn new() -> Self {
+        let clock = system_clock();
         Self {
-            current: EmotionState::neutral(),
-            mood: Mood::new(),
-            last_update: Instant::now(),
-            decay_rate: 0.15,
+            affect: Affect::new(),
+            last_update: clock.now(),
+            clock,
         }
     }
 
+    /// Times decay by `clock` from now on.
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.last_update = clock.now();
+        self.clock = clock;
+    }
+
     /// Applies natural emotional decay over time.
     fn apply_decay(&mut self) {
-        let elapsed = self.last_update.elapsed().as_secs_f32();
-        let decay_factor = (1.0 - self.decay_rate * elapsed).clamp(0.0, 1.0);
-
-        self.current.happiness *= decay_factor;
-        self.current.sadness *= decay_factor;
-        self.current.anger *= decay_factor;
-        self.current.fear *= decay_factor;
-
-        self.last_update = Instant::now();
+        let now = self.clock.now();
+        self.affect.decay(now.saturating_duration_since(self.last_update).as_secs_f32());
+        self.last_update = now;
     }
 
     /// Applies an emotional stimulus (positive or negative).
     pub fn apply_stimulus(&mut self, stimulus: &EmotionState, intensity: f32) {
         self.apply_decay();
-        self.current.blend(stimulus, intensity.clamp(0.0, 1.0));
-        self.mood.update_from_emotion(&self.current);
+        self.affect.apply_stimulus(stimulus, intensity);
+    }
+
+    /// Lets the user's sentiment rub off on Astra; see `Affect::apply_contagion`.
+    pub fn apply_contagion(&mut self, valence: f32, arousal: f32, agreeableness: f32) {
+        self.apply_decay();
+        self.affect.apply_contagion(valence, arousal, agreeableness);
     }
 
     /// Returns a snapshot of the current emotional state.
     pub fn snapshot(&self) -> EmotionState {
-        self.current.clone()
+        self.affect.current.clone()
+    }
+}
+
+impl Default for EmotionDynamics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for EmotionDynamics {
+    type Target = Affect;
+
+    fn deref(&self) -> &Affect {
+        &self.affect
+    }
+}
+
+impl DerefMut for EmotionDynamics {
+    fn deref_mut(&mut self) -> &mut Affect {
+        &mut self.affect
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::time::MockClock;
+    use std::sync::Arc;
+    use std::time::Duration;
 
     #[test]
     fn test_emotion_blending() {
@@ -163,4 +139,36 @@ mod tests {
         assert!(dynamics.current.happiness > 0.7);
         assert!(dynamics.mood.baseline > 0.6);
     }
+
+    #[test]
+    fn test_contagion_scaled_by_agreeableness() {
+        let mut agreeable = EmotionDynamics::new();
+        let mut aloof = EmotionDynamics::new();
+
+        agreeable.apply_contagion(-1.0, 0.9, 0.9);
+        aloof.apply_contagion(-1.0, 0.9, 0.1);
+
+        assert!(agreeable.current.anger > aloof.current.anger);
+        assert!(agreeable.current.anger <= MAX_CONTAGION_INTENSITY);
+
+        let mut warm = EmotionDynamics::new();
+        warm.apply_contagion(1.0, 0.4, 0.9);
+        assert!(warm.current.happiness > 0.5);
+    }
+
+    #[test]
+    fn test_decay_follows_injected_clock() {
+        let clock = MockClock::new();
+        let mut dynamics = EmotionDynamics::new();
+        dynamics.set_clock(Arc::new(clock.clone()));
+        dynamics.apply_contagion(-1.0, 1.0, 1.0);
+        let angry = dynamics.current.anger;
+
+        // Nothing passes until the clock is advanced
+        dynamics.apply_stimulus(&EmotionState::neutral(), 0.0);
+        assert_eq!(dynamics.current.anger, angry);
+        clock.advance(Duration::from_secs(60));
+        dynamics.apply_stimulus(&EmotionState::neutral(), 0.0);
+        assert_eq!(dynamics.current.anger, 0.0);
+    }
 }
@@ -11,13 +11,14 @@
 //!
 //!   Core Functions:
 //!       • Maintain a curated set of lightweight jokes
-//!       • Randomly select humor content for conversational use
+//!       • Randomly select humor content for conversational use, from a
+//!         seedable source so runs can be repeated
 //!       • Support expressive and personable interaction patterns
 //!
 //!   File:        /src/personality/humor.rs
 //!   Author:      Alex Roussinov
 //!   Created:     2025-12-25
-//!   Updated:     2026-01-11
+//!   Updated:     2026-10-16
 //!
 //!   License:
 //!       Dual-licensed under the MIT and Apache 2.0 licenses.
@@ -26,6 +27,8 @@
 
 use rand::seq::SliceRandom;
 
+use crate::random::{RandomSource, SeededRng};
+
 static JOKES: &[&str] = &[
     "Why did the AI cross the road? To optimize the chicken's path!",
     "I told my neural network a joke, but it didn’t get the punchline — still training!",
@@ -36,15 +39,21 @@ static JOKES: &[&str] = &[
     "I asked my compiler for a joke, but it gave me a warning instead.",
 ];
 
-pub struct Humor {}
+pub struct Humor {
+    rng: SeededRng,
+}
 
 impl Humor {
     pub fn new() -> Self {
-        Self {}
+        Humor::with_random(&RandomSource::from_entropy())
+    }
+
+    /// Picks jokes from `source`, so the same seed tells the same jokes.
+    pub fn with_random(source: &RandomSource) -> Self {
+        Self { rng: source.stream("humor") }
     }
 
-    pub fn tell_joke(&self) -> &str {
-        let mut rng = rand::thread_rng();
-        JOKES.choose(&mut rng).unwrap_or(&"I'm out of jokes!")
+    pub fn tell_joke(&mut self) -> &str {
+        JOKES.choose(&mut self.rng).unwrap_or(&"I'm out of jokes!")
     }
 }
@@ -12,13 +12,14 @@
 //   Core Functions:
 //       • Define the module layout for personality‑related components
 //       • Expose personality traits, affective logic, and humor systems
+//       • Expose per-user relationship models that condition replies
 //       • Provide a unified namespace for expressive behavior modules
 //       • Establish the basis for future emotional and stylistic engines
 //
 //   File:        /src/personality/mod.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-25
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
@@ -26,7 +27,10 @@
 // ============================================================================
 
 pub mod personality;
+pub mod emotion;
 pub mod humor;
+pub mod relationship;
 
 pub use personality::*;
 pub use humor::*;
+pub use relationship::{RelationshipModel, Relationships, Stance};
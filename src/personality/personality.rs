@@ -10,15 +10,18 @@
 //       across contexts.
 //
 //   Core Functions:
-//       • Represent Big Five‑inspired personality traits
+//       • Represent Big Five‑inspired personality traits (see core::traits)
 //       • Adjust traits dynamically based on user feedback
 //       • Generate context‑aware conversational responses
+//       • Condition responses on Astra's relationship with the user
+//       • Word responses casually or formally, as the user prefers
 //       • Maintain mood and affective modulation for expressive behavior
+//       • Persist in a versioned envelope, so saved personalities migrate
 //
 //   File:        /src/personality/personality.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-25
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
@@ -27,49 +30,34 @@
 
 use std::collections::HashMap;
 
-/// Core personality traits inspired by the Big Five model.
-#[derive(Debug, Clone)]
-pub struct PersonalityTraits {
-    pub openness: f32,            // Curiosity, creativity
-    pub conscientiousness: f32,   // Reliability, diligence
-    pub extraversion: f32,        // Sociability, enthusiasm
-    pub agreeableness: f32,       // Compassion, cooperativeness
-    pub neuroticism: f32,         // Emotional stability
-}
+use serde::{Deserialize, Serialize};
 
-impl PersonalityTraits {
-    /// Creates a default trait profile.
-    pub fn new() -> Self {
-        PersonalityTraits {
-            openness: 0.8,
-            conscientiousness: 0.7,
-            extraversion: 0.6,
-            agreeableness: 0.9,
-            neuroticism: 0.2,
-        }
-    }
+use super::relationship::{RelationshipModel, Stance};
+use crate::migration::Versioned;
 
-    /// Adjusts a trait by name, clamped between 0 and 1.
-    pub fn adjust_trait(&mut self, trait_name: &str, delta: f32) {
-        let val = match trait_name {
-            "openness" => &mut self.openness,
-            "conscientiousness" => &mut self.conscientiousness,
-            "extraversion" => &mut self.extraversion,
-            "agreeableness" => &mut self.agreeableness,
-            "neuroticism" => &mut self.neuroticism,
-            _ => return,
-        };
-        *val = (*val + delta).clamp(0.0, 1.0);
-    }
+pub use crate::core::traits::PersonalityTraits;
+
+/// How formally Astra words a reply.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Register {
+    #[default]
+    Casual,
+    /// No exclamations or contractions
+    Formal,
 }
 
 /// Represents Astra’s personality state, including traits and mood.
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Personality {
     pub traits: PersonalityTraits,
     pub mood: f32, // 0 (sad) to 1 (happy)
 }
 
+impl Versioned for Personality {
+    const FORMAT: &'static str = "astra.personality";
+    const VERSION: u32 = 1;
+}
+
 impl Personality {
     /// Creates a new personality with default traits and mood.
     pub fn new() -> Self {
@@ -81,13 +69,57 @@ impl Personality {
 
     /// Generates a conversational response influenced by personality traits.
     pub fn respond_to_input(&mut self, input: &str) -> String {
-        if self.traits.openness > 0.7 {
-            format!("That's fascinating! Tell me more about {}.", input)
-        } else {
-            format!("Okay, I see. What else?")
+        self.respond_to_input_in(input, Register::Casual)
+    }
+
+    /// Generates a conversational response worded in `register`. The input
+    /// is quoted as given.
+    pub fn respond_to_input_in(&mut self, input: &str, register: Register) -> String {
+        match (self.traits.openness > 0.7, register) {
+            (true, Register::Casual) => format!("That's fascinating! Tell me more about {}.", input),
+            (true, Register::Formal) => format!("That is fascinating. Please tell me more about {}.", input),
+            (false, Register::Casual) => "Okay, I see. What else?".to_string(),
+            (false, Register::Formal) => "Understood. What else?".to_string(),
         }
     }
 
+    /// Generates a response to someone Astra has a relationship with, worded
+    /// in `register`: the opening follows the relationship's stance, and
+    /// extraversion decides whether friends are greeted by name.
+    pub fn respond_to(&mut self, input: &str, relationship: &RelationshipModel, register: Register) -> String {
+        let name = &relationship.display_name;
+        let greeting_end = if register == Register::Formal { '.' } else { '!' };
+        let opening = match relationship.stance() {
+            Stance::Stranger => format!("Nice to meet you, {}. ", name),
+            Stance::Friend if self.traits.extraversion > 0.5 => {
+                format!("Good to hear from you again, {}{} ", name, greeting_end)
+            }
+            Stance::Friend | Stance::Acquaintance => String::new(),
+            Stance::Strained => format!("I hear you, {}. ", name),
+        };
+        let body = match (relationship.stance(), register) {
+            (Stance::Strained, Register::Casual) => "Let's work through this together.".to_string(),
+            (Stance::Strained, Register::Formal) => "Let us work through this together.".to_string(),
+            _ => self.respond_to_input_in(input, register),
+        };
+        format!("{}{}", opening, body)
+    }
+
+    /// Traits the reply of `respond_to`, or of `respond_to_input` when there
+    /// is no `relationship`, depends on, weighted by their influence on it,
+    /// so feedback on the reply is credited to them.
+    pub fn reply_traits(&self, relationship: Option<&RelationshipModel>) -> Vec<(String, f32)> {
+        let stance = relationship.map(RelationshipModel::stance);
+        let mut traits = Vec::new();
+        if stance == Some(Stance::Friend) {
+            traits.push(("extraversion".to_string(), 1.0));
+        }
+        if stance != Some(Stance::Strained) {
+            traits.push(("openness".to_string(), 1.0));
+        }
+        traits
+    }
+
     /// Applies user feedback to adjust personality traits dynamically.
     pub fn apply_feedback(&mut self, feedback: &HashMap<String, f32>) {
         for (trait_name, delta) in feedback {
@@ -111,4 +143,48 @@ mod tests {
 
         assert!(personality.respond_to_input("AI").contains("Okay"));
     }
+
+    #[test]
+    fn test_formal_register_leaves_the_input_alone() {
+        let mut personality = Personality::new();
+        let reply = personality.respond_to_input_in("Okayama! That's where I'm going", Register::Formal);
+        assert_eq!(reply, "That is fascinating. Please tell me more about Okayama! That's where I'm going.");
+    }
+
+    #[test]
+    fn test_reply_traits_follow_the_stance() {
+        let personality = Personality::new();
+        let now = chrono::Utc::now();
+        let mut relationship = RelationshipModel {
+            user_id: "sam".to_string(),
+            display_name: "Sam".to_string(),
+            interactions: 1,
+            rapport: 0.0,
+            first_seen: now,
+            last_seen: now,
+        };
+        let names = |traits: Vec<(String, f32)>| traits.into_iter().map(|(name, _)| name).collect::<Vec<_>>();
+        assert_eq!(names(personality.reply_traits(None)), ["openness"]);
+        assert_eq!(names(personality.reply_traits(Some(&relationship))), ["openness"]);
+
+        relationship.interactions = 20;
+        relationship.rapport = 0.6;
+        assert_eq!(names(personality.reply_traits(Some(&relationship))), ["extraversion", "openness"]);
+        relationship.rapport = -0.6;
+        assert!(personality.reply_traits(Some(&relationship)).is_empty());
+    }
+
+    #[test]
+    fn test_saved_personality_round_trips_and_reads_unversioned_state() {
+        let mut personality = Personality::new();
+        personality.traits.adjust_trait("openness", -0.2);
+        let bytes = crate::migration::encode(&personality).unwrap();
+        let loaded: Personality = crate::migration::decode(&bytes).unwrap();
+        assert_eq!(loaded.traits.openness, personality.traits.openness);
+
+        let unversioned = br#"{"traits": {"openness": 0.9, "conscientiousness": 0.5, "extraversion": 0.5,
+            "agreeableness": 0.5, "neuroticism": 0.1}, "mood": 0.4}"#;
+        let loaded: Personality = crate::migration::decode(unversioned).unwrap();
+        assert_eq!((loaded.traits.openness, loaded.mood), (0.9, 0.4));
+    }
 }
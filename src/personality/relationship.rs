@@ -0,0 +1,145 @@
+// ============================================================================
+//                       ASTRA AGI • RELATIONSHIP MODELS
+//        Per-User Familiarity & Rapport for Conversational Style
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Keeps a small model of Astra's relationship with each person it
+//       talks to: how often they have spoken, and how warm their messages
+//       have been. Replies are conditioned on it, so a stranger is greeted
+//       politely, a regular warmly, and someone who has been hostile with
+//       a calmer, more careful tone.
+//
+//   Core Functions:
+//       • Track interactions and rapport per user
+//       • Derive familiarity from the number of interactions
+//       • Classify the relationship into a conversational stance
+//
+//   File:        /src/personality/relationship.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Weight of the newest message's sentiment in rapport.
+const RAPPORT_RATE: f32 = 0.2;
+
+/// What Astra knows of its relationship with one person.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelationshipModel {
+    pub user_id: String,
+    /// How they appear in chat; updated as it changes
+    pub display_name: String,
+    pub interactions: u32,
+    /// -1.0 hostile .. 1.0 warm; a moving average of message sentiment
+    pub rapport: f32,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// How Astra addresses someone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stance {
+    /// First few exchanges: polite, introduces itself
+    Stranger,
+    Acquaintance,
+    /// Familiar and on good terms: warm and informal
+    Friend,
+    /// Rapport has turned negative: calm and careful
+    Strained,
+}
+
+impl RelationshipModel {
+    /// 0.0 for a stranger, approaching 1.0 after many interactions.
+    pub fn familiarity(&self) -> f32 {
+        1.0 - (-(self.interactions as f32) / 10.0).exp()
+    }
+
+    pub fn stance(&self) -> Stance {
+        if self.rapport < -0.25 {
+            Stance::Strained
+        } else if self.interactions <= 1 {
+            Stance::Stranger
+        } else if self.familiarity() > 0.5 && self.rapport > 0.2 {
+            Stance::Friend
+        } else {
+            Stance::Acquaintance
+        }
+    }
+}
+
+/// Relationship models by user id.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Relationships {
+    users: HashMap<String, RelationshipModel>,
+}
+
+impl Relationships {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, user_id: &str) -> Option<&RelationshipModel> {
+        self.users.get(user_id)
+    }
+
+    /// Records a message from `user_id` with the given sentiment valence.
+    pub fn observe(&mut self, user_id: &str, display_name: &str, valence: f32, now: DateTime<Utc>) -> &RelationshipModel {
+        let model = self.users.entry(user_id.to_string()).or_insert_with(|| RelationshipModel {
+            user_id: user_id.to_string(),
+            display_name: display_name.to_string(),
+            interactions: 0,
+            rapport: 0.0,
+            first_seen: now,
+            last_seen: now,
+        });
+        model.display_name = display_name.to_string();
+        model.interactions += 1;
+        model.rapport = (model.rapport + RAPPORT_RATE * (valence.clamp(-1.0, 1.0) - model.rapport)).clamp(-1.0, 1.0);
+        model.last_seen = now;
+        model
+    }
+
+    /// Forgets a user, e.g. on a deletion request.
+    pub fn remove(&mut self, user_id: &str) -> Option<RelationshipModel> {
+        self.users.remove(user_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.users.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stance_follows_familiarity_and_rapport() {
+        let now = Utc::now();
+        let mut relationships = Relationships::new();
+        assert_eq!(relationships.observe("@ana:home", "Ana", 0.5, now).stance(), Stance::Stranger);
+        for _ in 0..8 {
+            relationships.observe("@ana:home", "Ana", 0.8, now);
+        }
+        let ana = relationships.get("@ana:home").unwrap();
+        assert_eq!(ana.interactions, 9);
+        assert_eq!(ana.stance(), Stance::Friend);
+
+        for _ in 0..4 {
+            relationships.observe("@ana:home", "Ana", -1.0, now);
+        }
+        assert_eq!(relationships.get("@ana:home").unwrap().stance(), Stance::Strained);
+    }
+}
@@ -0,0 +1,329 @@
+// ============================================================================
+//                      ASTRA AGI • ANYTIME PLANNING SEARCH
+//        Interruptible, Resumable Plan Search with Quality Bounds
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Long plan searches must not block the cognitive tick. This module
+//       runs GOAP-style best-first search in bounded slices: each call to
+//       `refine` expands nodes until its time or expansion budget runs out,
+//       then returns the best complete plan found so far together with a
+//       bound on how far from optimal it can be. The search keeps its
+//       frontier between calls, so later ticks continue where earlier ones
+//       stopped and the cognitive loop can decide whether a suboptimal plan
+//       is good enough under deadline pressure.
+//
+//   Core Functions:
+//       • Best-first search over world states with an admissible heuristic
+//       • Suspend and resume search between ticks
+//       • Track the incumbent plan and a lower bound on the optimal cost
+//       • Decide whether to act on an incumbent given deadline pressure
+//       • Scale search budgets, e.g. by remaining cognitive energy
+//       • Keep partial plans in an arena owned by the search
+//
+//   File:        /src/planning/anytime.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use super::arena::{ArenaId, PlanGraph};
+use super::planner::{goal_satisfied, preconditions_met, Action, Goal, Plan, Planner, WorldState};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::time::{Duration, Instant};
+
+/// Limits on one slice of search.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SearchBudget {
+    pub time: Option<Duration>,
+    pub expansions: Option<usize>,
+}
+
+impl SearchBudget {
+    pub fn time(limit: Duration) -> Self {
+        SearchBudget { time: Some(limit), expansions: None }
+    }
+
+    pub fn expansions(limit: usize) -> Self {
+        SearchBudget { time: None, expansions: Some(limit) }
+    }
+
+    /// Shrinks or grows both limits by `factor`, keeping at least one
+    /// expansion so search can always make progress.
+    pub fn scaled(&self, factor: f32) -> Self {
+        let factor = factor.max(0.0);
+        SearchBudget {
+            time: self.time.map(|t| t.mul_f32(factor)),
+            expansions: self.expansions.map(|n| ((n as f32 * factor) as usize).max(1)),
+        }
+    }
+}
+
+/// State of an anytime search after a slice of refinement.
+#[derive(Debug, Clone)]
+pub struct AnytimeResult {
+    /// Cheapest complete plan found so far
+    pub plan: Option<Plan>,
+    /// Lower bound on the cost of any plan not yet found
+    pub lower_bound: f32,
+    /// True once the search proved `plan` optimal or exhausted the space
+    pub finished: bool,
+    /// Total nodes expanded across all slices
+    pub expansions: usize,
+}
+
+impl AnytimeResult {
+    /// Ratio between the incumbent's cost and the best possible cost; 1.0
+    /// means optimal. `None` while no plan has been found.
+    pub fn suboptimality(&self) -> Option<f32> {
+        let plan = self.plan.as_ref()?;
+        if self.finished || plan.estimated_cost <= self.lower_bound {
+            return Some(1.0);
+        }
+        if self.lower_bound <= 0.0 {
+            return Some(f32::INFINITY);
+        }
+        Some(plan.estimated_cost / self.lower_bound)
+    }
+
+    /// Whether the incumbent is good enough to act on. `deadline_pressure`
+    /// in 0.0..=1.0 widens the accepted suboptimality from 1.0 (optimal
+    /// only) up to `1.0 + max_slack`; at full pressure any plan is accepted.
+    pub fn acceptable(&self, deadline_pressure: f32, max_slack: f32) -> bool {
+        let pressure = deadline_pressure.clamp(0.0, 1.0);
+        match self.suboptimality() {
+            None => false,
+            Some(_) if self.finished || pressure >= 1.0 => true,
+            Some(ratio) => ratio <= 1.0 + max_slack * pressure,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Node {
+    world: WorldState,
+    /// Last step of the partial plan, in the search's plan graph
+    step: Option<ArenaId>,
+    cost: f32,
+    priority: f32,
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl Eq for Node {}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Min-heap on f = g + h
+        other.priority.partial_cmp(&self.priority).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// A resumable best-first plan search.
+pub struct AnytimeSearch {
+    goal: Goal,
+    actions: Vec<Action>,
+    frontier: BinaryHeap<Node>,
+    /// Partial plans generated so far, freed with the search
+    steps: PlanGraph,
+    best_cost: HashMap<Vec<(String, bool)>, f32>,
+    min_action_cost: f32,
+    incumbent: Option<Plan>,
+    expansions: usize,
+}
+
+impl AnytimeSearch {
+    /// Starts a search; `actions` should already carry their final costs.
+    pub fn new(world: &WorldState, goal: &Goal, actions: Vec<Action>) -> Self {
+        let min_action_cost = actions.iter().map(|a| a.cost.max(0.0)).fold(f32::INFINITY, f32::min);
+        let mut search = AnytimeSearch {
+            goal: goal.clone(),
+            actions,
+            frontier: BinaryHeap::new(),
+            steps: PlanGraph::new(),
+            best_cost: HashMap::new(),
+            min_action_cost: if min_action_cost.is_finite() { min_action_cost } else { 0.0 },
+            incumbent: None,
+            expansions: 0,
+        };
+        let h = search.heuristic(world);
+        search.frontier.push(Node { world: world.clone(), step: None, cost: 0.0, priority: h });
+        search
+    }
+
+    /// Expands nodes until the budget runs out or the search finishes.
+    pub fn refine(&mut self, budget: SearchBudget) -> AnytimeResult {
+        let started = Instant::now();
+        let mut expanded = 0usize;
+
+        while !self.is_finished() {
+            if budget.expansions.map_or(false, |limit| expanded >= limit)
+                || budget.time.map_or(false, |limit| started.elapsed() >= limit)
+            {
+                break;
+            }
+            let Some(node) = self.frontier.pop() else { break };
+            expanded += 1;
+            self.expansions += 1;
+
+            if goal_satisfied(&node.world, &self.goal.desired_state) {
+                if self.incumbent.as_ref().map_or(true, |p| node.cost < p.estimated_cost) {
+                    self.incumbent = Some(Plan {
+                        goal_id: self.goal.id.clone(),
+                        actions: self.steps.actions(node.step, &self.actions),
+                        estimated_cost: node.cost,
+                    });
+                }
+                continue;
+            }
+
+            for (index, action) in self.actions.iter().enumerate() {
+                if !preconditions_met(&node.world, &action.preconditions) {
+                    continue;
+                }
+                let mut world = node.world.clone();
+                for (k, v) in &action.effects {
+                    world.insert(k.clone(), *v);
+                }
+                let cost = node.cost + action.cost.max(0.0);
+
+                // Record solutions as soon as they are generated so an
+                // incumbent exists early, even with a tiny budget.
+                if goal_satisfied(&world, &self.goal.desired_state) {
+                    if self.incumbent.as_ref().map_or(true, |p| cost < p.estimated_cost) {
+                        let step = self.steps.extend(node.step, index);
+                        let actions = self.steps.actions(Some(step), &self.actions);
+                        self.incumbent = Some(Plan { goal_id: self.goal.id.clone(), actions, estimated_cost: cost });
+                    }
+                    continue;
+                }
+
+                // Skip states already reached more cheaply, and branches that
+                // cannot beat the incumbent.
+                let key = state_key(&world);
+                if self.best_cost.get(&key).map_or(false, |best| *best <= cost) {
+                    continue;
+                }
+                let priority = cost + self.heuristic(&world);
+                if self.incumbent.as_ref().map_or(false, |p| priority >= p.estimated_cost) {
+                    continue;
+                }
+                self.best_cost.insert(key, cost);
+
+                let step = Some(self.steps.extend(node.step, index));
+                self.frontier.push(Node { world, step, cost, priority });
+            }
+        }
+
+        self.result()
+    }
+
+    /// Current incumbent and bound without doing more work.
+    pub fn result(&self) -> AnytimeResult {
+        let frontier_bound = self.frontier.peek().map(|n| n.priority).unwrap_or(f32::INFINITY);
+        let lower_bound = match &self.incumbent {
+            Some(plan) => frontier_bound.min(plan.estimated_cost),
+            None => frontier_bound,
+        };
+        AnytimeResult {
+            plan: self.incumbent.clone(),
+            lower_bound,
+            finished: self.is_finished(),
+            expansions: self.expansions,
+        }
+    }
+
+    pub fn goal(&self) -> &Goal {
+        &self.goal
+    }
+
+    fn is_finished(&self) -> bool {
+        match (&self.incumbent, self.frontier.peek()) {
+            (_, None) => true,
+            (Some(plan), Some(next)) => next.priority >= plan.estimated_cost,
+            (None, Some(_)) => false,
+        }
+    }
+
+    /// At least one more action is needed while the goal is unsatisfied.
+    fn heuristic(&self, world: &WorldState) -> f32 {
+        if goal_satisfied(world, &self.goal.desired_state) {
+            0.0
+        } else {
+            self.min_action_cost
+        }
+    }
+}
+
+pub(crate) fn state_key(world: &WorldState) -> Vec<(String, bool)> {
+    let mut key: Vec<(String, bool)> = world.iter().map(|(k, v)| (k.clone(), *v)).collect();
+    key.sort();
+    key
+}
+
+impl Planner {
+    /// Starts an interruptible search, pricing actions with the installed
+    /// cost model.
+    pub fn start_anytime(&self, world: &WorldState, goal: &Goal, actions: &[Action]) -> AnytimeSearch {
+        AnytimeSearch::new(world, goal, self.priced_actions(actions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(id: &str, pre: &[&str], effect: &str, cost: f32) -> Action {
+        Action {
+            id: id.into(),
+            description: id.into(),
+            preconditions: pre.iter().map(|k| (k.to_string(), true)).collect(),
+            effects: HashMap::from([(effect.to_string(), true)]),
+            cost,
+            risk: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_search_resumes_and_improves_bound() {
+        let goal = Goal {
+            id: "lit".into(),
+            description: "Light the room".into(),
+            desired_state: HashMap::from([("light_on".into(), true)]),
+            priority: 5,
+        };
+        let actions = vec![
+            action("flip_breaker", &[], "has_power", 1.0),
+            action("turn_on_light", &["has_power"], "light_on", 1.0),
+            action("light_candle", &[], "light_on", 5.0),
+        ];
+
+        let mut search = Planner::new().start_anytime(&WorldState::new(), &goal, &actions);
+
+        // One expansion only finds the expensive single-step plan.
+        let first = search.refine(SearchBudget::expansions(1));
+        assert!(!first.finished);
+        assert_eq!(first.plan.as_ref().map(|p| p.actions.len()), Some(1));
+        assert!(first.suboptimality().unwrap() > 1.0);
+        assert!(first.acceptable(1.0, 0.5));
+        assert!(!first.acceptable(0.0, 0.5));
+
+        let done = search.refine(SearchBudget::expansions(100));
+        assert!(done.finished);
+        assert_eq!(done.plan.unwrap().estimated_cost, 2.0);
+    }
+}
@@ -0,0 +1,185 @@
+// ============================================================================
+//                        ASTRA AGI • PLAN SEARCH ARENAS
+//        Session-Scoped Allocation for Plan Graphs
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Plan searches build a graph of partial plans, one node per action
+//       tried. Each node used to carry its own copy of every action taken
+//       to reach it, so a search allocated a vector and cloned a chain of
+//       actions for every node it generated, most of them never expanded.
+//       An arena instead holds the nodes of one planning session in a single
+//       growing buffer: a node is the action taken plus the id of the node
+//       before it, allocated by pushing and freed all at once when the
+//       session ends. Only the plan that is returned is turned back into
+//       actions, by walking from its last node to the root.
+//
+//       Only plan graphs live in arenas. Astra syntax trees are split out
+//       as a change to astra_lang of their own (see "Memory" in its
+//       README): a compiled unit outlives any one session, so there is no
+//       session for its nodes to be freed with. benches/plan_arena.rs
+//       measures parsing as the baseline for that change.
+//
+//   Core Functions:
+//       • Bump-allocate values of one session and address them by id
+//       • Reset an arena between sessions without giving back its memory
+//       • Record partial plans as links to the step before them
+//       • Rebuild a plan's actions from its last step
+//
+//   File:        /src/planning/arena.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use super::planner::Action;
+use std::ops::Index;
+
+/// Handle to a value in an `Arena`. Only meaningful for the arena, and the
+/// session, that handed it out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ArenaId(u32);
+
+/// Values allocated for one session and freed together when it ends.
+#[derive(Debug, Clone)]
+pub struct Arena<T> {
+    items: Vec<T>,
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Arena { items: Vec::new() }
+    }
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Arena { items: Vec::with_capacity(capacity) }
+    }
+
+    pub fn alloc(&mut self, value: T) -> ArenaId {
+        let id = u32::try_from(self.items.len()).expect("arena holds at most u32::MAX values");
+        self.items.push(value);
+        ArenaId(id)
+    }
+
+    pub fn get(&self, id: ArenaId) -> Option<&T> {
+        self.items.get(id.0 as usize)
+    }
+
+    pub fn get_mut(&mut self, id: ArenaId) -> Option<&mut T> {
+        self.items.get_mut(id.0 as usize)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Frees every value, keeping the memory for the next session. Ids
+    /// handed out before are invalid afterwards.
+    pub fn reset(&mut self) {
+        self.items.clear();
+    }
+}
+
+impl<T> Index<ArenaId> for Arena<T> {
+    type Output = T;
+
+    fn index(&self, id: ArenaId) -> &T {
+        &self.items[id.0 as usize]
+    }
+}
+
+/// One action of a partial plan, linked to the step before it.
+#[derive(Debug, Clone, Copy)]
+struct PlanStep {
+    parent: Option<ArenaId>,
+    /// Index into the actions the search was given
+    action: usize,
+}
+
+/// The partial plans of one search, sharing their common prefixes. A
+/// partial plan is named by its last step; the empty plan is `None`.
+#[derive(Debug, Clone, Default)]
+pub struct PlanGraph {
+    steps: Arena<PlanStep>,
+}
+
+impl PlanGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The plan `parent` followed by the action at `action`.
+    pub fn extend(&mut self, parent: Option<ArenaId>, action: usize) -> ArenaId {
+        self.steps.alloc(PlanStep { parent, action })
+    }
+
+    /// Indices of the actions of the plan ending at `last`, first to last.
+    pub fn action_indices(&self, last: Option<ArenaId>) -> Vec<usize> {
+        let mut indices = Vec::new();
+        let mut step = last;
+        while let Some(id) = step {
+            indices.push(self.steps[id].action);
+            step = self.steps[id].parent;
+        }
+        indices.reverse();
+        indices
+    }
+
+    /// The actions of the plan ending at `last`, cloned from `actions`.
+    pub fn actions(&self, last: Option<ArenaId>, actions: &[Action]) -> Vec<Action> {
+        self.action_indices(last).into_iter().map(|i| actions[i].clone()).collect()
+    }
+
+    /// Steps recorded so far.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn reset(&mut self) {
+        self.steps.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plans_share_prefixes_and_rebuild_in_order() {
+        let mut arena = Arena::new();
+        let a = arena.alloc("a");
+        let b = arena.alloc("b");
+        *arena.get_mut(b).unwrap() = "bb";
+        assert_eq!((arena[a], arena[b], arena.len()), ("a", "bb", 2));
+        arena.reset();
+        assert!(arena.is_empty() && arena.get(a).is_none());
+
+        let mut graph = PlanGraph::new();
+        let first = graph.extend(None, 2);
+        let left = graph.extend(Some(first), 0);
+        let right = graph.extend(Some(first), 1);
+        let deeper = graph.extend(Some(right), 0);
+        assert_eq!(graph.action_indices(None), Vec::<usize>::new());
+        assert_eq!(graph.action_indices(Some(left)), vec![2, 0]);
+        assert_eq!(graph.action_indices(Some(deeper)), vec![2, 1, 0]);
+        // Four plans, one step each
+        assert_eq!(graph.len(), 4);
+    }
+}
@@ -0,0 +1,211 @@
+// ============================================================================
+//                      ASTRA AGI • AUTONOMY POLICY ENGINE
+//        Deciding Which Actions Astra May Take on Its Own
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Sorts actions into categories by the kind of effect they have on
+//       the world outside Astra's mind — writing knowledge, calling external
+//       HTTP services, modifying files, sending messages — and maps each
+//       category to an autonomy level. The plan executor consults the policy
+//       before every action: some run freely, some run and are reported,
+//       some wait for a person to confirm, and some are never run. Each
+//       deployment supplies its own mapping.
+//
+//   Core Functions:
+//       • Classify actions into effect categories
+//       • Map categories to auto, notify, confirm, or forbid
+//       • Load per-deployment policies from JSON
+//
+//   File:        /src/planning/autonomy.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use super::error::PlanningError;
+use super::planner::Action;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The kind of effect an action has outside Astra.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionCategory {
+    KnowledgeWrite,
+    ExternalHttp,
+    FileModification,
+    MessageSend,
+    /// No effect outside Astra's own state
+    Internal,
+}
+
+impl ActionCategory {
+    /// Guesses the category from the words of an action's id and
+    /// description. Outgoing effects are checked first, so "send the file"
+    /// is a message rather than a file modification.
+    pub fn infer(action: &Action) -> ActionCategory {
+        const MESSAGE: &[&str] = &["send", "message", "email", "reply", "notify", "post", "tell"];
+        const HTTP: &[&str] = &["http", "fetch", "download", "crawl", "request", "api", "webhook", "url"];
+        const FILE: &[&str] = &["file", "files", "write", "save", "delete", "overwrite", "disk"];
+        const KNOWLEDGE: &[&str] = &["knowledge", "fact", "facts", "learn", "belief", "ontology", "memorize"];
+
+        let text = format!("{} {}", action.id, action.description).to_lowercase();
+        let words: Vec<&str> = text.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).collect();
+        let mentions = |vocabulary: &[&str]| words.iter().any(|w| vocabulary.contains(w));
+
+        if mentions(MESSAGE) {
+            ActionCategory::MessageSend
+        } else if mentions(HTTP) {
+            ActionCategory::ExternalHttp
+        } else if mentions(FILE) {
+            ActionCategory::FileModification
+        } else if mentions(KNOWLEDGE) {
+            ActionCategory::KnowledgeWrite
+        } else {
+            ActionCategory::Internal
+        }
+    }
+}
+
+/// How much freedom Astra has for a category of action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutonomyLevel {
+    /// Run without telling anyone
+    Auto,
+    /// Run, and report that it ran
+    Notify,
+    /// Wait for a person to confirm before running
+    Confirm,
+    /// Never run
+    Forbid,
+}
+
+/// Per-deployment mapping from action categories to autonomy levels.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutonomyPolicy {
+    pub levels: HashMap<ActionCategory, AutonomyLevel>,
+    /// Level for categories missing from `levels`
+    #[serde(default = "default_level")]
+    pub default_level: AutonomyLevel,
+    /// Categories for specific action ids, or for every grounding of an
+    /// action name such as `send_reply`, overriding inference
+    #[serde(default)]
+    pub action_categories: HashMap<String, ActionCategory>,
+}
+
+fn default_level() -> AutonomyLevel {
+    AutonomyLevel::Confirm
+}
+
+impl Default for AutonomyPolicy {
+    fn default() -> Self {
+        let levels = HashMap::from([
+            (ActionCategory::Internal, AutonomyLevel::Auto),
+            (ActionCategory::KnowledgeWrite, AutonomyLevel::Auto),
+            (ActionCategory::ExternalHttp, AutonomyLevel::Notify),
+            (ActionCategory::FileModification, AutonomyLevel::Confirm),
+            (ActionCategory::MessageSend, AutonomyLevel::Confirm),
+        ]);
+        AutonomyPolicy {
+            levels,
+            default_level: default_level(),
+            action_categories: HashMap::new(),
+        }
+    }
+}
+
+impl AutonomyPolicy {
+    /// A policy that lets every action run; for simulations and tests.
+    pub fn permissive() -> Self {
+        AutonomyPolicy {
+            levels: HashMap::new(),
+            default_level: AutonomyLevel::Auto,
+            action_categories: HashMap::new(),
+        }
+    }
+
+    /// Parses a deployment policy, e.g.
+    /// `{"levels": {"external_http": "auto", "message_send": "forbid"}}`.
+    pub fn from_json(json: &str) -> Result<Self, PlanningError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn category_of(&self, action: &Action) -> ActionCategory {
+        let name = action.id.split('(').next().unwrap_or(&action.id);
+        self.action_categories
+            .get(&action.id)
+            .or_else(|| self.action_categories.get(name))
+            .copied()
+            .unwrap_or_else(|| ActionCategory::infer(action))
+    }
+
+    pub fn level_for(&self, category: ActionCategory) -> AutonomyLevel {
+        self.levels.get(&category).copied().unwrap_or(self.default_level)
+    }
+
+    pub fn decide(&self, action: &Action) -> (ActionCategory, AutonomyLevel) {
+        let category = self.category_of(action);
+        (category, self.level_for(category))
+    }
+}
+
+/// Record of an action the policy let run without confirmation, or forbade.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutonomyNotice {
+    pub action_id: String,
+    pub description: String,
+    pub category: ActionCategory,
+    /// `Auto`, `Notify`, or `Forbid`; notices recorded before levels were
+    /// kept were all `Notify`
+    #[serde(default = "notified_level")]
+    pub level: AutonomyLevel,
+}
+
+fn notified_level() -> AutonomyLevel {
+    AutonomyLevel::Notify
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(id: &str, description: &str) -> Action {
+        Action {
+            id: id.into(),
+            description: description.into(),
+            preconditions: HashMap::new(),
+            effects: HashMap::new(),
+            cost: 1.0,
+            risk: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_categories_map_to_levels() {
+        let policy = AutonomyPolicy::default();
+        assert_eq!(
+            policy.decide(&action("send_report", "Email the report file")),
+            (ActionCategory::MessageSend, AutonomyLevel::Confirm)
+        );
+        assert_eq!(policy.decide(&action("fetch_page", "Download a page")).1, AutonomyLevel::Notify);
+        assert_eq!(policy.decide(&action("think", "Consider options")).1, AutonomyLevel::Auto);
+
+        let deployment = AutonomyPolicy::from_json(
+            r#"{"levels": {"message_send": "forbid"}, "action_categories": {"think": "file_modification"}}"#,
+        )
+        .unwrap();
+        assert_eq!(deployment.decide(&action("send_report", "")).1, AutonomyLevel::Forbid);
+        assert_eq!(deployment.decide(&action("think", "")).1, AutonomyLevel::Confirm);
+        assert_eq!(deployment.decide(&action("think(hard)", "")).1, AutonomyLevel::Confirm);
+        assert!(matches!(AutonomyPolicy::from_json("{"), Err(PlanningError::InvalidPolicy(_))));
+
+        let earlier: AutonomyNotice =
+            serde_json::from_str(r#"{"action_id": "fetch_page", "description": "", "category": "external_http"}"#).unwrap();
+        assert_eq!(earlier.level, AutonomyLevel::Notify);
+    }
+}
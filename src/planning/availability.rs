@@ -0,0 +1,141 @@
+// ============================================================================
+//                      ASTRA AGI • AVAILABILITY WINDOWS
+//        Free and Busy Time for Deadline Feasibility
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Records the periods in which work cannot happen — meetings and other
+//       calendar commitments — so that deciding whether a plan can meet its
+//       deadline counts only the free time before it, not the whole
+//       interval. Busy periods are kept sorted and merged.
+//
+//   Core Functions:
+//       • Collect busy periods, merging overlaps
+//       • List free windows and total free time in an interval
+//       • Find when a given amount of work would finish, if the calendar
+//         reaches that far
+//
+//   File:        /src/planning/availability.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Busy periods, sorted by start and never overlapping.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Availability {
+    busy: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+}
+
+impl Availability {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_busy(periods: impl IntoIterator<Item = (DateTime<Utc>, DateTime<Utc>)>) -> Self {
+        let mut availability = Self::new();
+        for (start, end) in periods {
+            availability.add_busy(start, end);
+        }
+        availability
+    }
+
+    /// Marks `start..end` busy. Empty or reversed periods are ignored.
+    pub fn add_busy(&mut self, start: DateTime<Utc>, end: DateTime<Utc>) {
+        if end <= start {
+            return;
+        }
+        self.busy.push((start, end));
+        self.busy.sort_by_key(|(start, _)| *start);
+        let mut merged: Vec<(DateTime<Utc>, DateTime<Utc>)> = Vec::with_capacity(self.busy.len());
+        for (start, end) in self.busy.drain(..) {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        self.busy = merged;
+    }
+
+    pub fn busy(&self) -> &[(DateTime<Utc>, DateTime<Utc>)] {
+        &self.busy
+    }
+
+    pub fn is_free(&self, at: DateTime<Utc>) -> bool {
+        !self.busy.iter().any(|(start, end)| *start <= at && at < *end)
+    }
+
+    /// The free windows within `from..to`, in order.
+    pub fn free_windows(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+        let mut windows = Vec::new();
+        let mut cursor = from;
+        for (start, end) in &self.busy {
+            if *start >= to {
+                break;
+            }
+            if *start > cursor {
+                windows.push((cursor, *start));
+            }
+            cursor = cursor.max(*end);
+        }
+        if cursor < to {
+            windows.push((cursor, to));
+        }
+        windows
+    }
+
+    /// Total free time within `from..to`.
+    pub fn free_time(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Duration {
+        self.free_windows(from, to)
+            .iter()
+            .filter_map(|(start, end)| (*end - *start).to_std().ok())
+            .sum()
+    }
+
+    /// When `work` started at `from` would be done, working only in free
+    /// time; `None` if that is past the last representable date.
+    pub fn finish_time(&self, from: DateTime<Utc>, work: Duration) -> Option<DateTime<Utc>> {
+        let mut left = work;
+        let mut cursor = from;
+        for (start, end) in self.busy.iter().filter(|(_, end)| *end > from) {
+            let free = (*start - cursor).to_std().unwrap_or(Duration::ZERO);
+            if free >= left {
+                break;
+            }
+            left -= free;
+            cursor = cursor.max(*end);
+        }
+        cursor.checked_add_signed(chrono::Duration::from_std(left).ok()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 16, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_work_fits_around_busy_periods() {
+        let availability = Availability::from_busy([(at(10), at(11)), (at(13), at(15)), (at(14), at(16))]);
+        assert_eq!(availability.busy(), &[(at(10), at(11)), (at(13), at(16))]);
+        assert_eq!(availability.free_windows(at(9), at(17)), vec![(at(9), at(10)), (at(11), at(13)), (at(16), at(17))]);
+        assert_eq!(availability.free_time(at(9), at(17)), Duration::from_secs(4 * 3600));
+        assert!(!availability.is_free(at(14)));
+
+        let three_hours = Duration::from_secs(3 * 3600);
+        assert_eq!(availability.finish_time(at(9), three_hours), Some(at(13)));
+        assert_eq!(availability.finish_time(at(12), three_hours), Some(at(18)));
+        assert_eq!(availability.finish_time(at(9), Duration::MAX), None);
+        assert_eq!(availability.finish_time(DateTime::<Utc>::MAX_UTC, three_hours), None);
+    }
+}
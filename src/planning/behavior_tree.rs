@@ -0,0 +1,361 @@
+// ============================================================================
+//                       ASTRA AGI • BEHAVIOR TREES
+//        Reactive Execution Without Search, for Simple Recurring Intents
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       An alternative to planning for intents whose handling is known in
+//       advance, such as "keep the light on when someone is home". Instead
+//       of searching for a plan, a tree of sequence, selector, and decorator
+//       nodes is ticked against the current world: at most one action runs
+//       per tick, and a node that has not finished reports Running and
+//       resumes where it left off on the next tick. Trees are written
+//       declaratively in JSON, and their outcomes are scored in reflection
+//       as the "BT" strategy alongside HTN, GOAP, and reactive planning.
+//
+//   Core Functions:
+//       • Tick sequence, selector, inverter, succeeder, retry, and repeat nodes
+//       • Check conditions against the world and run actions through an executor
+//       • Respect the autonomy policy, failing actions that need a person
+//       • Load trees from JSON
+//
+//   File:        /src/planning/behavior_tree.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use super::autonomy::{AutonomyLevel, AutonomyPolicy};
+use super::error::PlanningError;
+use super::executor::ActionExecutor;
+use super::planner::{preconditions_met, Action, WorldState};
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Outcome of ticking a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// Not finished; tick again to continue
+    Running,
+    Success,
+    Failure,
+}
+
+fn expected() -> bool {
+    true
+}
+
+/// A node as written in a tree file, e.g.
+/// `{"type": "condition", "fact": "light_on"}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Node {
+    /// Runs children in order; fails as soon as one fails
+    Sequence { children: Vec<Node> },
+    /// Tries children in order; succeeds as soon as one succeeds
+    Selector { children: Vec<Node> },
+    Inverter { child: Box<Node> },
+    /// Succeeds however its child finishes
+    Succeeder { child: Box<Node> },
+    /// Runs its child again after a failure, up to `attempts` runs in all
+    Retry { attempts: u32, child: Box<Node> },
+    /// Runs its child until it has succeeded `times` times
+    Repeat { times: u32, child: Box<Node> },
+    /// Succeeds if the world reports `fact` as `value`; unknown facts are false
+    Condition {
+        fact: String,
+        #[serde(default = "expected")]
+        value: bool,
+    },
+    Action {
+        id: String,
+        #[serde(default)]
+        description: String,
+        #[serde(default)]
+        preconditions: WorldState,
+        #[serde(default)]
+        effects: WorldState,
+    },
+}
+
+impl Node {
+    /// Nodes in this subtree, itself included.
+    fn size(&self) -> usize {
+        1 + match self {
+            Node::Sequence { children } | Node::Selector { children } => children.iter().map(Node::size).sum(),
+            Node::Inverter { child } | Node::Succeeder { child } | Node::Retry { child, .. } | Node::Repeat { child, .. } => {
+                child.size()
+            }
+            Node::Condition { .. } | Node::Action { .. } => 0,
+        }
+    }
+}
+
+/// State of one tick: the world as it is now, and whether this tick's
+/// action has already been used.
+struct Tick<'a, E> {
+    world: WorldState,
+    env: &'a mut E,
+    policy: Option<&'a AutonomyPolicy>,
+    /// By preorder position: the child a composite is on, or a decorator's count
+    cursors: &'a mut HashMap<usize, u32>,
+    acted: bool,
+}
+
+impl<E: ActionExecutor> Tick<'_, E> {
+    fn node(&mut self, node: &Node, position: usize) -> Status {
+        match node {
+            Node::Sequence { children } => self.composite(children, position, Status::Failure),
+            Node::Selector { children } => self.composite(children, position, Status::Success),
+            Node::Inverter { child } => match self.node(child, position + 1) {
+                Status::Success => Status::Failure,
+                Status::Failure => Status::Success,
+                Status::Running => Status::Running,
+            },
+            Node::Succeeder { child } => match self.node(child, position + 1) {
+                Status::Running => Status::Running,
+                _ => Status::Success,
+            },
+            Node::Retry { attempts, child } => match self.node(child, position + 1) {
+                Status::Failure => self.count(position, *attempts, Status::Failure),
+                Status::Success => {
+                    self.cursors.remove(&position);
+                    Status::Success
+                }
+                Status::Running => Status::Running,
+            },
+            Node::Repeat { times, child } => match self.node(child, position + 1) {
+                Status::Success => self.count(position, *times, Status::Success),
+                Status::Failure => {
+                    self.cursors.remove(&position);
+                    Status::Failure
+                }
+                Status::Running => Status::Running,
+            },
+            Node::Condition { fact, value } => {
+                if self.world.get(fact).copied().unwrap_or(false) == *value {
+                    Status::Success
+                } else {
+                    Status::Failure
+                }
+            }
+            Node::Action { id, description, preconditions, effects } => {
+                let action = Action {
+                    id: id.clone(),
+                    description: description.clone(),
+                    preconditions: preconditions.clone(),
+                    effects: effects.clone(),
+                    cost: 1.0,
+                    risk: 0.0,
+                };
+                self.act(&action)
+            }
+        }
+    }
+
+    /// Children run in order until one finishes with `decisive`, which the
+    /// composite then finishes with; otherwise it finishes with the opposite.
+    fn composite(&mut self, children: &[Node], position: usize, decisive: Status) -> Status {
+        let start = self.cursors.get(&position).copied().unwrap_or(0) as usize;
+        let mut child_position = position + 1 + children[..start.min(children.len())].iter().map(Node::size).sum::<usize>();
+        for (index, child) in children.iter().enumerate().skip(start) {
+            match self.node(child, child_position) {
+                Status::Running => {
+                    self.cursors.insert(position, index as u32);
+                    return Status::Running;
+                }
+                status if status == decisive => {
+                    self.cursors.remove(&position);
+                    return decisive;
+                }
+                _ => child_position += child.size(),
+            }
+        }
+        self.cursors.remove(&position);
+        match decisive {
+            Status::Failure => Status::Success,
+            _ => Status::Failure,
+        }
+    }
+
+    /// Counts another `outcome` of a decorator's child, finishing with it
+    /// once there have been `limit`, and running the child again otherwise.
+    fn count(&mut self, position: usize, limit: u32, outcome: Status) -> Status {
+        let count = self.cursors.entry(position).or_insert(0);
+        *count += 1;
+        if *count >= limit {
+            self.cursors.remove(&position);
+            outcome
+        } else {
+            Status::Running
+        }
+    }
+
+    fn act(&mut self, action: &Action) -> Status {
+        if self.acted {
+            return Status::Running;
+        }
+        if !preconditions_met(&self.world, &action.preconditions) {
+            debug!("Behavior tree action {} skipped: preconditions unmet", action.id);
+            return Status::Failure;
+        }
+        if let Some(policy) = self.policy {
+            let (category, level) = policy.decide(action);
+            // Trees do not wait for a person; a selector can fall back instead
+            if matches!(level, AutonomyLevel::Confirm | AutonomyLevel::Forbid) {
+                info!("Behavior tree action {} ({:?}) is not allowed to run on its own", action.id, category);
+                return Status::Failure;
+            }
+        }
+
+        self.acted = true;
+        match self.env.execute_action(action) {
+            Ok(true) => {
+                self.world = self.env.observe_world().unwrap_or_else(|| {
+                    let mut world = std::mem::take(&mut self.world);
+                    world.extend(action.effects.iter().map(|(k, v)| (k.clone(), *v)));
+                    world
+                });
+                Status::Success
+            }
+            Ok(false) => Status::Failure,
+            Err(e) => {
+                info!("Behavior tree action {} failed: {}", action.id, e);
+                Status::Failure
+            }
+        }
+    }
+}
+
+/// A tree and where it is in a run that has not finished yet.
+#[derive(Debug, Clone)]
+pub struct BehaviorTree {
+    pub root: Node,
+    cursors: HashMap<usize, u32>,
+    policy: Option<AutonomyPolicy>,
+}
+
+impl BehaviorTree {
+    pub fn new(root: Node) -> Self {
+        BehaviorTree { root, cursors: HashMap::new(), policy: None }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, PlanningError> {
+        serde_json::from_str(json)
+            .map(Self::new)
+            .map_err(|e| PlanningError::InvalidBehaviorTree(e.to_string()))
+    }
+
+    /// Checks each action against `policy` before running it.
+    pub fn with_policy(mut self, policy: AutonomyPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Replaces the policy for later ticks.
+    pub fn set_policy(&mut self, policy: AutonomyPolicy) {
+        self.policy = Some(policy);
+    }
+
+    /// Ticks the tree once against `world`, running at most one action.
+    pub fn tick<E: ActionExecutor>(&mut self, world: &WorldState, env: &mut E) -> Status {
+        let mut tick = Tick { world: world.clone(), env, policy: self.policy.as_ref(), cursors: &mut self.cursors, acted: false };
+        let status = tick.node(&self.root, 0);
+        if status != Status::Running {
+            self.cursors.clear();
+        }
+        status
+    }
+
+    /// Whether a run is under way, to be continued by the next tick.
+    pub fn is_running(&self) -> bool {
+        !self.cursors.is_empty()
+    }
+
+    /// Abandons a run under way, so the next tick starts from the root.
+    pub fn reset(&mut self) {
+        self.cursors.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Applies effects, and fails `flaky` the first `failures` times.
+    struct Room {
+        world: WorldState,
+        flaky: &'static str,
+        failures: u32,
+        ran: Vec<String>,
+    }
+
+    impl ActionExecutor for Room {
+        fn execute_action(&mut self, action: &Action) -> anyhow::Result<bool> {
+            self.ran.push(action.id.clone());
+            if action.id == self.flaky && self.failures > 0 {
+                self.failures -= 1;
+                return Ok(false);
+            }
+            self.world.extend(action.effects.clone());
+            Ok(true)
+        }
+
+        fn observe_world(&self) -> Option<WorldState> {
+            Some(self.world.clone())
+        }
+    }
+
+    const LIGHT: &str = r#"{
+        "type": "selector",
+        "children": [
+            {"type": "condition", "fact": "light_on"},
+            {"type": "sequence", "children": [
+                {"type": "condition", "fact": "has_power"},
+                {"type": "action", "id": "open_blinds", "effects": {"blinds_open": true}},
+                {"type": "retry", "attempts": 3, "child":
+                    {"type": "action", "id": "switch_on", "effects": {"light_on": true}}}
+            ]}
+        ]
+    }"#;
+
+    #[test]
+    fn test_tree_ticks_resume_retry_and_react_to_the_world() {
+        let mut tree = BehaviorTree::from_json(LIGHT).unwrap();
+        let mut room = Room {
+            world: HashMap::from([("has_power".to_string(), true)]),
+            flaky: "switch_on",
+            failures: 1,
+            ran: Vec::new(),
+        };
+
+        let mut statuses = Vec::new();
+        while statuses.last() != Some(&Status::Success) && statuses.len() < 10 {
+            let world = room.world.clone();
+            statuses.push(tree.tick(&world, &mut room));
+        }
+        assert_eq!(statuses, vec![Status::Running, Status::Running, Status::Success]);
+        assert_eq!(room.ran, vec!["open_blinds", "switch_on", "switch_on"]);
+        assert!(!tree.is_running());
+
+        // Already satisfied: nothing runs
+        let world = room.world.clone();
+        assert_eq!(tree.tick(&world, &mut room), Status::Success);
+        assert_eq!(room.ran.len(), 3);
+
+        // Sending needs confirmation by default, so the selector falls back
+        let fallback = r#"{"type": "selector", "children": [
+            {"type": "action", "id": "send_alert"},
+            {"type": "action", "id": "switch_on"}
+        ]}"#;
+        let mut tree = BehaviorTree::from_json(fallback).unwrap().with_policy(AutonomyPolicy::default());
+        assert_eq!(tree.tick(&world, &mut room), Status::Success);
+        assert_eq!(room.ran.last().map(String::as_str), Some("switch_on"));
+        assert!(matches!(BehaviorTree::from_json(r#"{"type": "dance"}"#), Err(PlanningError::InvalidBehaviorTree(_))));
+    }
+}
@@ -0,0 +1,236 @@
+// ============================================================================
+//                      ASTRA AGI • LEARNED ACTION COST MODEL
+//        Duration Estimates Refined from Execution Telemetry
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Replaces the planner's unit-cost assumption with per-action estimates
+//       of how long an action takes and how often it succeeds. Estimates start
+//       from the action's declared cost and are refined from the durations
+//       PlanExecutor measures, so GOAP and HTN searches optimize against
+//       realistic costs and deadline feasibility checks have real numbers
+//       behind them.
+//
+//   Core Functions:
+//       • Record per-action durations and outcomes from execution telemetry
+//       • Estimate action and plan durations with exponential smoothing
+//       • Price actions by expected time, inflated by expected retries
+//       • Check whether a plan fits the time left before a deadline,
+//         counting only time free of calendar commitments
+//       • Chain several cost models into one
+//       • Identify the least reliable action seen so far
+//
+//   File:        /src/planning/cost_model.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use super::availability::Availability;
+use super::planner::{Action, ActionCostModel, Plan};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// One measured action execution, as reported by PlanExecutor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionSample {
+    pub action_id: String,
+    pub duration: Duration,
+    pub success: bool,
+}
+
+/// Running statistics for one action.
+#[derive(Debug, Clone, Default)]
+pub struct ActionStats {
+    pub samples: u32,
+    pub failures: u32,
+    /// Exponentially smoothed duration in seconds
+    pub mean_secs: f64,
+}
+
+/// Cost model that learns action durations and success rates.
+///
+/// An action's cost is its expected duration in units of `cost_unit`,
+/// divided by its smoothed success rate. Before any telemetry arrives an
+/// action is assumed to take `cost_unit * action.cost`, so an untrained
+/// model prices actions exactly as declared.
+#[derive(Debug, Clone)]
+pub struct LearnedCostModel {
+    /// Wall-clock time represented by one unit of declared cost
+    pub cost_unit: Duration,
+    /// Weight of each new sample in the duration average
+    pub smoothing: f64,
+    stats: HashMap<String, ActionStats>,
+}
+
+/// A cost model shared between the planner and whoever feeds it telemetry.
+pub type SharedCostModel = Arc<RwLock<LearnedCostModel>>;
+
+impl LearnedCostModel {
+    pub fn new(cost_unit: Duration) -> Self {
+        LearnedCostModel {
+            cost_unit,
+            smoothing: 0.3,
+            stats: HashMap::new(),
+        }
+    }
+
+    pub fn shared(self) -> SharedCostModel {
+        Arc::new(RwLock::new(self))
+    }
+
+    /// Folds one execution sample into the action's statistics.
+    pub fn record(&mut self, sample: &ExecutionSample) {
+        let secs = sample.duration.as_secs_f64();
+        let stats = self.stats.entry(sample.action_id.clone()).or_default();
+        stats.mean_secs = if stats.samples == 0 {
+            secs
+        } else {
+            stats.mean_secs + self.smoothing * (secs - stats.mean_secs)
+        };
+        stats.samples += 1;
+        if !sample.success {
+            stats.failures += 1;
+        }
+    }
+
+    pub fn record_all(&mut self, samples: &[ExecutionSample]) {
+        for sample in samples {
+            self.record(sample);
+        }
+    }
+
+    pub fn stats(&self, action_id: &str) -> Option<&ActionStats> {
+        self.stats.get(action_id)
+    }
+
+    /// Executed action with the lowest success rate, if any has failed.
+    pub fn least_reliable(&self) -> Option<(&str, f32)> {
+        self.stats
+            .iter()
+            .filter(|(_, stats)| stats.failures > 0)
+            .map(|(id, stats)| (id.as_str(), stats.samples.saturating_sub(stats.failures) as f32 / stats.samples as f32))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// Expected duration of one attempt at `action`.
+    pub fn estimated_duration(&self, action: &Action) -> Duration {
+        match self.stats.get(&action.id) {
+            Some(stats) if stats.samples > 0 => Duration::from_secs_f64(stats.mean_secs),
+            _ => self.cost_unit.mul_f32(action.cost.max(0.0)),
+        }
+    }
+
+    /// Laplace-smoothed probability that an attempt succeeds; actions never
+    /// executed are assumed to succeed.
+    pub fn success_rate(&self, action: &Action) -> f32 {
+        match self.stats.get(&action.id) {
+            Some(stats) => {
+                let successes = stats.samples.saturating_sub(stats.failures);
+                (successes as f32 + 1.0) / (stats.samples as f32 + 2.0)
+            }
+            None => 1.0,
+        }
+    }
+
+    /// Expected time to carry out a plan, counting retries of unreliable actions.
+    pub fn estimated_plan_duration(&self, plan: &Plan) -> Duration {
+        plan.actions
+            .iter()
+            .map(|a| self.estimated_duration(a).div_f32(self.success_rate(a).max(0.05)))
+            .sum()
+    }
+
+    /// Whether the plan is expected to finish within `time_left`.
+    pub fn fits_deadline(&self, plan: &Plan, time_left: Duration) -> bool {
+        self.estimated_plan_duration(plan) <= time_left
+    }
+
+    /// Whether the plan, started at `now` and worked on only while
+    /// `availability` is free, is expected to finish by `deadline`.
+    pub fn fits_deadline_around(
+        &self,
+        plan: &Plan,
+        now: DateTime<Utc>,
+        deadline: DateTime<Utc>,
+        availability: &Availability,
+    ) -> bool {
+        availability.finish_time(now, self.estimated_plan_duration(plan)).is_some_and(|finish| finish <= deadline)
+    }
+}
+
+impl ActionCostModel for LearnedCostModel {
+    fn action_cost(&self, action: &Action) -> f32 {
+        let unit = self.cost_unit.as_secs_f32().max(f32::EPSILON);
+        let expected = self.estimated_duration(action).as_secs_f32() / unit;
+        expected / self.success_rate(action).max(0.05)
+    }
+}
+
+impl ActionCostModel for SharedCostModel {
+    fn action_cost(&self, action: &Action) -> f32 {
+        match self.read() {
+            Ok(model) => model.action_cost(action),
+            Err(_) => action.cost,
+        }
+    }
+}
+
+/// Applies several cost models in order; each sees the cost produced by the
+/// previous one.
+pub struct CostModelChain(pub Vec<Box<dyn ActionCostModel>>);
+
+impl ActionCostModel for CostModelChain {
+    fn action_cost(&self, action: &Action) -> f32 {
+        let mut priced = action.clone();
+        for model in &self.0 {
+            priced.cost = model.action_cost(&priced);
+        }
+        priced.cost
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn action(id: &str, cost: f32) -> Action {
+        Action {
+            id: id.into(),
+            description: id.into(),
+            preconditions: HashMap::new(),
+            effects: HashMap::new(),
+            cost,
+            risk: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_costs_follow_telemetry() {
+        let mut model = LearnedCostModel::new(Duration::from_secs(1));
+        let fetch = action("fetch", 1.0);
+        assert_eq!(model.action_cost(&fetch), 1.0);
+
+        for _ in 0..4 {
+            model.record(&ExecutionSample { action_id: "fetch".into(), duration: Duration::from_secs(10), success: true });
+        }
+        assert!((model.estimated_duration(&fetch).as_secs_f32() - 10.0).abs() < 1e-3);
+        assert!(model.action_cost(&fetch) > 10.0);
+
+        let plan = Plan { goal_id: "g".into(), actions: vec![fetch.clone(), action("wait", 2.0)], estimated_cost: 0.0 };
+        assert!(model.fits_deadline(&plan, Duration::from_secs(30)));
+        assert!(!model.fits_deadline(&plan, Duration::from_secs(5)));
+
+        let now = Utc::now();
+        let deadline = now + chrono::Duration::seconds(30);
+        assert!(model.fits_deadline_around(&plan, now, deadline, &Availability::new()));
+        let meeting = Availability::from_busy([(now + chrono::Duration::seconds(5), now + chrono::Duration::seconds(60))]);
+        assert!(!model.fits_deadline_around(&plan, now, deadline, &meeting));
+    }
+}
@@ -0,0 +1,394 @@
+// ============================================================================
+//                     ASTRA AGI • DIALOGUE ACT PLANNING
+//        Conversations Planned as Sequences of Speech Acts
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Treats a conversation about a task as a planning domain. Astra's
+//       turns are made of dialogue acts (acknowledge, ask, inform, propose,
+//       commit), each a planner action with preconditions over the state of
+//       the dialogue: nothing is proposed before the details are known and
+//       summarized back, and nothing is committed to before the user agrees.
+//       Each turn GOAP plans from the current dialogue state to a commitment
+//       and speaks the plan up to the first act that hands the turn to the
+//       user. Asking and proposing are planned as if the user will answer
+//       and agree; what the user actually says updates the state, and the
+//       next turn is planned from there, so a declined proposal leads back
+//       to asking what to change rather than to a canned reply.
+//
+//   Core Functions:
+//       • Define dialogue acts as planner actions over dialogue state
+//       • Track what has been heard, said, and agreed in a dialogue
+//       • Plan a turn's acts and render them as text
+//       • Read a user's reply to a proposal as agreement or refusal, clause
+//         by clause and idioms such as "why not" before single words
+//
+//   File:        /src/planning/dialogue.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use super::error::PlanningError;
+use super::planner::{goal_satisfied, Action, Goal, GoapPlanner, PlannerEngine, WorldState};
+use serde::{Deserialize, Serialize};
+
+/// The user has made a request
+pub const REQUEST_HEARD: &str = "dialogue.request_heard";
+/// Astra has acknowledged the request
+pub const ACKNOWLEDGED: &str = "dialogue.acknowledged";
+/// Astra knows enough about the request to act on it
+pub const DETAILS_KNOWN: &str = "dialogue.details_known";
+/// Astra has said back what it understood
+pub const INFORMED: &str = "dialogue.informed";
+/// The user agreed to what Astra proposed
+pub const USER_AGREED: &str = "dialogue.user_agreed";
+/// Astra has committed to the task
+pub const COMMITTED: &str = "dialogue.committed";
+/// Astra has handed the turn to the user and waits for a reply
+pub const AWAITING_USER: &str = "dialogue.awaiting_user";
+
+const FACTS: [&str; 7] = [REQUEST_HEARD, ACKNOWLEDGED, DETAILS_KNOWN, INFORMED, USER_AGREED, COMMITTED, AWAITING_USER];
+
+/// Asked when the user turns down a proposal.
+const AMEND_QUESTION: &str = "What should I change?";
+
+/// Something Astra does by speaking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DialogueAct {
+    /// Say back what Astra understood
+    Inform,
+    /// Ask for a detail Astra is missing
+    Ask,
+    /// Show the request was heard
+    Acknowledge,
+    /// Offer to go ahead, pending the user's agreement
+    Propose,
+    /// Take the task on
+    Commit,
+}
+
+impl DialogueAct {
+    pub const ALL: [DialogueAct; 5] =
+        [DialogueAct::Acknowledge, DialogueAct::Ask, DialogueAct::Inform, DialogueAct::Propose, DialogueAct::Commit];
+
+    /// The act's planner action ID.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DialogueAct::Inform => "inform",
+            DialogueAct::Ask => "ask",
+            DialogueAct::Acknowledge => "acknowledge",
+            DialogueAct::Propose => "propose",
+            DialogueAct::Commit => "commit",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<DialogueAct> {
+        DialogueAct::ALL.into_iter().find(|act| act.name() == name)
+    }
+
+    /// Whether the act ends Astra's turn, since only the user can answer it.
+    pub fn yields_turn(&self) -> bool {
+        matches!(self, DialogueAct::Ask | DialogueAct::Propose)
+    }
+}
+
+fn facts(pairs: &[(&str, bool)]) -> WorldState {
+    pairs.iter().map(|(fact, value)| (fact.to_string(), *value)).collect()
+}
+
+/// The dialogue acts as planner actions. The effects of asking and
+/// proposing are what Astra expects the user's reply to bring about.
+pub fn dialogue_actions() -> Vec<Action> {
+    let action = |act: DialogueAct, description: &str, preconditions: WorldState, effects: WorldState| Action {
+        id: act.name().to_string(),
+        description: description.to_string(),
+        preconditions,
+        effects,
+        cost: 1.0,
+        risk: 0.0,
+    };
+    vec![
+        action(
+            DialogueAct::Acknowledge,
+            "Acknowledge the request",
+            facts(&[(REQUEST_HEARD, true), (ACKNOWLEDGED, false), (AWAITING_USER, false)]),
+            facts(&[(ACKNOWLEDGED, true)]),
+        ),
+        action(
+            DialogueAct::Ask,
+            "Ask for a missing detail",
+            facts(&[(ACKNOWLEDGED, true), (DETAILS_KNOWN, false), (AWAITING_USER, false)]),
+            facts(&[(DETAILS_KNOWN, true)]),
+        ),
+        action(
+            DialogueAct::Inform,
+            "Say back what was understood",
+            facts(&[(ACKNOWLEDGED, true), (DETAILS_KNOWN, true), (INFORMED, false), (AWAITING_USER, false)]),
+            facts(&[(INFORMED, true)]),
+        ),
+        action(
+            DialogueAct::Propose,
+            "Offer to go ahead",
+            facts(&[(INFORMED, true), (USER_AGREED, false), (AWAITING_USER, false)]),
+            facts(&[(USER_AGREED, true)]),
+        ),
+        action(
+            DialogueAct::Commit,
+            "Take the task on",
+            facts(&[(USER_AGREED, true), (COMMITTED, false), (AWAITING_USER, false)]),
+            facts(&[(COMMITTED, true)]),
+        ),
+    ]
+}
+
+/// A conversation about one request, from hearing it to committing to it.
+#[derive(Debug, Clone)]
+pub struct Dialogue {
+    /// The request as the user made it
+    pub request: String,
+    /// Details the user added since, in the order given
+    pub amendments: Vec<String>,
+    /// What the next Ask act asks
+    question: Option<String>,
+    state: WorldState,
+}
+
+impl Dialogue {
+    /// A dialogue about `request`; `question` is what Astra needs to ask
+    /// before it can act, if anything.
+    pub fn new(request: impl Into<String>, question: Option<String>) -> Self {
+        let mut state: WorldState = FACTS.iter().map(|fact| (fact.to_string(), false)).collect();
+        state.insert(REQUEST_HEARD.to_string(), true);
+        state.insert(DETAILS_KNOWN.to_string(), question.is_none());
+        Dialogue { request: request.into(), amendments: Vec::new(), question, state }
+    }
+
+    pub fn state(&self) -> &WorldState {
+        &self.state
+    }
+
+    fn fact(&self, fact: &str) -> bool {
+        self.state.get(fact).copied().unwrap_or(false)
+    }
+
+    fn set(&mut self, fact: &str, value: bool) {
+        self.state.insert(fact.to_string(), value);
+    }
+
+    /// The request with the details added since.
+    pub fn task(&self) -> String {
+        std::iter::once(self.request.trim())
+            .chain(self.amendments.iter().map(|a| a.trim()))
+            .map(|part| part.trim_end_matches(['.', '!', '?']))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    /// Whether Astra asked a question and waits for the answer.
+    pub fn awaiting_answer(&self) -> bool {
+        self.fact(AWAITING_USER) && !self.fact(DETAILS_KNOWN)
+    }
+
+    /// Whether Astra proposed going ahead and waits to hear if it may.
+    pub fn awaiting_agreement(&self) -> bool {
+        self.fact(AWAITING_USER) && self.fact(DETAILS_KNOWN)
+    }
+
+    /// Whether Astra has committed to the task.
+    pub fn is_committed(&self) -> bool {
+        self.fact(COMMITTED)
+    }
+
+    /// Takes `answer` as adding to the request. `next_question` is what
+    /// Astra still needs to ask, if anything.
+    pub fn hear_answer(&mut self, answer: &str, next_question: Option<String>) {
+        if !answer.trim().is_empty() {
+            self.amendments.push(answer.trim().to_string());
+        }
+        self.set(AWAITING_USER, false);
+        self.set(DETAILS_KNOWN, next_question.is_none());
+        // New details have to be said back before they are proposed
+        self.set(INFORMED, false);
+        self.question = next_question;
+    }
+
+    /// Takes the user's reply to a proposal. A refusal means the request
+    /// needs changing, so Astra asks what to change.
+    pub fn hear_reply(&mut self, agreed: bool) {
+        self.set(AWAITING_USER, false);
+        if agreed {
+            self.set(USER_AGREED, true);
+        } else {
+            self.set(DETAILS_KNOWN, false);
+            self.set(INFORMED, false);
+            self.question = Some(AMEND_QUESTION.to_string());
+        }
+    }
+
+    /// The acts of Astra's next turn: the plan from here to a commitment,
+    /// up to and including the first act that hands the turn to the user.
+    /// Empty while Astra waits for the user or once it has committed.
+    pub fn plan_turn(&self) -> Result<Vec<DialogueAct>, PlanningError> {
+        let goal = Goal {
+            id: "dialogue".into(),
+            description: "Commit to what the user asked for".into(),
+            desired_state: facts(&[(COMMITTED, true)]),
+            priority: 0,
+        };
+        if self.fact(AWAITING_USER) || goal_satisfied(&self.state, &goal.desired_state) {
+            return Ok(Vec::new());
+        }
+        let plan = GoapPlanner::new().plan(&self.state, &goal, &dialogue_actions())?;
+        let mut acts = Vec::new();
+        for act in plan.actions.iter().filter_map(|action| DialogueAct::from_name(&action.id)) {
+            acts.push(act);
+            if act.yields_turn() {
+                break;
+            }
+        }
+        Ok(acts)
+    }
+
+    /// Performs `act` and returns what Astra says. An act that hands the
+    /// turn over only leaves Astra waiting; its expected effect waits on
+    /// the user's reply.
+    pub fn perform(&mut self, act: DialogueAct) -> String {
+        let text = self.render(act);
+        if act.yields_turn() {
+            self.set(AWAITING_USER, true);
+        } else if let Some(action) = dialogue_actions().into_iter().find(|a| a.id == act.name()) {
+            for (fact, value) in action.effects {
+                self.state.insert(fact, value);
+            }
+        }
+        text
+    }
+
+    /// Plans the next turn and performs it, returning each act with what
+    /// was said.
+    pub fn take_turn(&mut self) -> Result<Vec<(DialogueAct, String)>, PlanningError> {
+        Ok(self.plan_turn()?.into_iter().map(|act| (act, self.perform(act))).collect())
+    }
+
+    fn render(&self, act: DialogueAct) -> String {
+        match act {
+            DialogueAct::Acknowledge => "Got it.".to_string(),
+            DialogueAct::Ask => self.question.clone().unwrap_or_else(|| "Could you tell me more?".to_string()),
+            DialogueAct::Inform => format!("Here's what I have: {}.", self.task()),
+            DialogueAct::Propose => "Shall I go ahead?".to_string(),
+            DialogueAct::Commit => format!("I'll take care of it: {}.", self.task()),
+        }
+    }
+}
+
+/// Reads a reply to a proposal as agreement (`Some(true)`), refusal
+/// (`Some(false)`), or neither. Each clause is read on its own, idioms
+/// first, so "why not, go ahead" agrees although "not" alone refuses; a
+/// reply with any refusing clause, as in "yes, but wait", refuses.
+pub fn read_agreement(text: &str) -> Option<bool> {
+    let lower = text.to_lowercase();
+    let readings: Vec<bool> = lower.split([',', ';', '.', '!', '?']).filter_map(read_clause).collect();
+    if readings.contains(&false) {
+        Some(false)
+    } else if readings.contains(&true) {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+/// Reads one clause of a reply; see `read_agreement`.
+fn read_clause(clause: &str) -> Option<bool> {
+    // Agree despite the refusing words in them; longest first
+    const AGREEING_PHRASES: &[&str] = &[
+        "don't see why not", "dont see why not", "not a problem", "don't mind", "dont mind",
+        "no problem", "no worries", "why not", "go ahead", "of course", "sounds good",
+    ];
+    const REFUSING_PHRASES: &[&str] = &["hold on", "hang on", "rather not"];
+    const REFUSALS: &[&str] = &["no", "nope", "don't", "dont", "not", "wait", "stop", "cancel", "change"];
+    const AGREEMENTS: &[&str] = &["yes", "yeah", "yep", "sure", "ok", "okay", "please", "go", "agreed", "fine"];
+
+    let mut words: Vec<&str> =
+        clause.split(|c: char| !c.is_alphanumeric() && c != '\'').filter(|w| !w.is_empty()).collect();
+    let mut agreed = false;
+    for phrase in AGREEING_PHRASES {
+        let phrase: Vec<&str> = phrase.split(' ').collect();
+        while let Some(at) = words.windows(phrase.len()).position(|window| window == phrase.as_slice()) {
+            words.drain(at..at + phrase.len());
+            agreed = true;
+        }
+    }
+    let refused = REFUSING_PHRASES.iter().any(|phrase| {
+        let phrase: Vec<&str> = phrase.split(' ').collect();
+        words.windows(phrase.len()).any(|window| window == phrase.as_slice())
+    });
+    if refused || words.iter().any(|w| REFUSALS.contains(w)) {
+        Some(false)
+    } else if agreed || words.iter().any(|w| AGREEMENTS.contains(w)) {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turn(dialogue: &mut Dialogue) -> Vec<DialogueAct> {
+        dialogue.take_turn().unwrap().into_iter().map(|(act, _)| act).collect()
+    }
+
+    #[test]
+    fn test_negotiation_is_planned_turn_by_turn() {
+        use DialogueAct::*;
+
+        let mut dialogue = Dialogue::new("remind me to water the plants", Some("When should that be?".into()));
+        let said = dialogue.take_turn().unwrap();
+        assert_eq!(said, [(Acknowledge, "Got it.".to_string()), (Ask, "When should that be?".to_string())]);
+        assert!(dialogue.awaiting_answer());
+        // Nothing more to say until the user answers
+        assert!(turn(&mut dialogue).is_empty());
+
+        dialogue.hear_answer("tomorrow at 9am", None);
+        let said = dialogue.take_turn().unwrap();
+        assert_eq!(said[0].1, "Here's what I have: remind me to water the plants; tomorrow at 9am.");
+        assert_eq!(said.iter().map(|(act, _)| *act).collect::<Vec<_>>(), [Inform, Propose]);
+        assert!(dialogue.awaiting_agreement());
+
+        // Turned down: ask what to change, then propose again
+        dialogue.hear_reply(false);
+        assert_eq!(dialogue.take_turn().unwrap(), [(Ask, "What should I change?".to_string())]);
+        dialogue.hear_answer("make it 10am", None);
+        assert_eq!(turn(&mut dialogue), [Inform, Propose]);
+
+        dialogue.hear_reply(true);
+        let said = dialogue.take_turn().unwrap();
+        assert_eq!(said, [(Commit, "I'll take care of it: remind me to water the plants; tomorrow at 9am; make it 10am.".to_string())]);
+        assert!(dialogue.is_committed());
+        assert!(turn(&mut dialogue).is_empty());
+
+        // A complete request goes straight to saying it back and proposing
+        let mut dialogue = Dialogue::new("search for train times", None);
+        assert_eq!(turn(&mut dialogue), [Acknowledge, Inform, Propose]);
+    }
+
+    #[test]
+    fn test_replies_are_read_as_agreement_or_refusal() {
+        assert_eq!(read_agreement("Yes, go ahead!"), Some(true));
+        assert_eq!(read_agreement("ok"), Some(true));
+        assert_eq!(read_agreement("No, not yet"), Some(false));
+        assert_eq!(read_agreement("Please don't"), Some(false));
+        assert_eq!(read_agreement("tomorrow at 9am"), None);
+        // Idioms are read before the words in them
+        assert_eq!(read_agreement("why not, go ahead"), Some(true));
+        assert_eq!(read_agreement("Sure, no problem"), Some(true));
+        assert_eq!(read_agreement("I don't see why not"), Some(true));
+        assert_eq!(read_agreement("Yes, but hold on"), Some(false));
+    }
+}
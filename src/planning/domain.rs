@@ -0,0 +1,656 @@
+// ============================================================================
+//                     ASTRA AGI • PLANNING DOMAIN LOADER
+//        Declarative Actions, Types & Goals from PDDL Files
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Reads planning domains and problems written in a subset of PDDL and
+//       turns them into the actions, world states, and goals the planners
+//       work with, so what Astra can do is described in files rather than
+//       compiled in. Parameterized actions are grounded over the objects of
+//       a problem and the domain's constants; a ground atom `(at robot
+//       kitchen)` becomes the world-state key `at(robot,kitchen)`.
+//
+//       Supported: `:types` with supertypes, `:constants`, `:predicates`,
+//       and actions with typed `:parameters`, a `:precondition` and an
+//       `:effect` that are conjunctions of literals, negated with `not`.
+//       `(increase (total-cost) n)` in an effect sets the action's cost,
+//       which is otherwise 1. Problems give `:objects`, `:init`, and a
+//       conjunctive `:goal`. Names are case-insensitive and `;` starts a
+//       comment.
+//
+//   Core Functions:
+//       • Parse domains and problems, reporting malformed input by line
+//       • Check literals against declared predicates and parameters
+//       • Ground action schemas into planner actions
+//       • Build closed-world initial states and goals from problems
+//
+//   File:        /src/planning/domain.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use super::error::PlanningError;
+use super::planner::{Action, Goal, WorldState};
+use std::path::Path;
+
+/// A name with its declared type: an object, a parameter, or a type and its
+/// supertype. Untyped names are of type `object`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedName {
+    pub name: String,
+    pub type_name: String,
+}
+
+/// A predicate and the types of its arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Predicate {
+    pub name: String,
+    pub params: Vec<TypedName>,
+}
+
+/// A possibly negated predicate applied to variables (`?x`) or objects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Literal {
+    pub positive: bool,
+    pub predicate: String,
+    pub args: Vec<String>,
+}
+
+/// An action before grounding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionSchema {
+    pub name: String,
+    pub params: Vec<TypedName>,
+    pub preconditions: Vec<Literal>,
+    pub effects: Vec<Literal>,
+    pub cost: f32,
+}
+
+/// A planning domain: the types, predicates, and actions of a world.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Domain {
+    pub name: String,
+    /// Declared types with their supertypes
+    pub types: Vec<TypedName>,
+    pub constants: Vec<TypedName>,
+    /// Empty if the domain declares none, in which case literals go unchecked
+    pub predicates: Vec<Predicate>,
+    pub actions: Vec<ActionSchema>,
+}
+
+impl Domain {
+    pub fn parse(source: &str) -> Result<Domain, PlanningError> {
+        let forms = read(source)?;
+        let (name, sections) = definition(&forms, "domain")?;
+        let mut domain = Domain {
+            name,
+            types: Vec::new(),
+            constants: Vec::new(),
+            predicates: Vec::new(),
+            actions: Vec::new(),
+        };
+
+        for section in sections {
+            let (keyword, body) = section_of(section)?;
+            match keyword {
+                ":requirements" => {}
+                ":types" => {
+                    domain.types = typed_list(body)?;
+                    domain.check_types(section.line())?;
+                }
+                ":constants" => {
+                    domain.constants = typed_list(body)?;
+                    domain.check_declared(&domain.constants, section.line())?;
+                }
+                ":predicates" => {
+                    for form in body {
+                        let items = list(form)?;
+                        let (name, params) = items.split_first().ok_or_else(|| invalid(form.line(), "empty predicate"))?;
+                        let params = typed_list(params)?;
+                        domain.check_declared(&params, form.line())?;
+                        domain.predicates.push(Predicate { name: atom(name)?.to_string(), params });
+                    }
+                }
+                ":action" => {
+                    let action = domain.action(section.line(), body)?;
+                    domain.actions.push(action);
+                }
+                other => return Err(invalid(section.line(), format!("unsupported section '{}'", other))),
+            }
+        }
+        Ok(domain)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Domain, PlanningError> {
+        Domain::parse(&read_file(path.as_ref())?)
+    }
+
+    /// Every grounding of every action over the domain's constants and
+    /// `objects`. Groundings whose preconditions contradict each other, such
+    /// as a move from a room to itself, are left out.
+    pub fn ground(&self, objects: &[TypedName]) -> Vec<Action> {
+        let objects: Vec<&TypedName> = self.constants.iter().chain(objects).collect();
+        let mut actions = Vec::new();
+        for schema in &self.actions {
+            for binding in self.bindings(&schema.params, &objects) {
+                let substitute = |args: &[String]| -> Vec<String> {
+                    args.iter()
+                        .map(|arg| match schema.params.iter().position(|p| &p.name == arg) {
+                            Some(i) => binding[i].to_string(),
+                            None => arg.clone(),
+                        })
+                        .collect()
+                };
+
+                let mut preconditions = WorldState::new();
+                let consistent = schema.preconditions.iter().all(|literal| {
+                    let key = atom_key(&literal.predicate, &substitute(&literal.args));
+                    preconditions.insert(key, literal.positive) != Some(!literal.positive)
+                });
+                if !consistent {
+                    continue;
+                }
+                // Deletes apply before adds, so an atom both added and deleted holds
+                let mut effects = WorldState::new();
+                for literal in schema.effects.iter().filter(|l| !l.positive).chain(schema.effects.iter().filter(|l| l.positive)) {
+                    effects.insert(atom_key(&literal.predicate, &substitute(&literal.args)), literal.positive);
+                }
+
+                let args: Vec<String> = binding.iter().map(|a| a.to_string()).collect();
+                let description = std::iter::once(schema.name.as_str()).chain(binding.iter().copied()).collect::<Vec<_>>().join(" ");
+                actions.push(Action {
+                    id: atom_key(&schema.name, &args),
+                    description,
+                    preconditions,
+                    effects,
+                    cost: schema.cost,
+                    risk: 0.0,
+                });
+            }
+        }
+        actions
+    }
+
+    /// Whether `type_name` is `ancestor` or one of its subtypes.
+    pub fn is_subtype(&self, type_name: &str, ancestor: &str) -> bool {
+        let mut current = type_name;
+        // Types were checked for cycles, so the walk ends
+        loop {
+            if current == ancestor || ancestor == "object" {
+                return true;
+            }
+            match self.types.iter().find(|t| t.name == current) {
+                Some(declared) if declared.type_name != current => current = &declared.type_name,
+                _ => return false,
+            }
+        }
+    }
+
+    /// The ways to assign `objects` to `params`, respecting their types.
+    fn bindings<'o>(&self, params: &[TypedName], objects: &[&'o TypedName]) -> Vec<Vec<&'o str>> {
+        let mut bindings = vec![Vec::new()];
+        for param in params {
+            let candidates: Vec<&str> = objects
+                .iter()
+                .filter(|o| self.is_subtype(&o.type_name, &param.type_name))
+                .map(|o| o.name.as_str())
+                .collect();
+            bindings = bindings
+                .into_iter()
+                .flat_map(|binding| {
+                    candidates.iter().map(move |candidate| {
+                        let mut extended = binding.clone();
+                        extended.push(*candidate);
+                        extended
+                    })
+                })
+                .collect();
+        }
+        bindings
+    }
+
+    fn action(&self, line: usize, body: &[Sexp]) -> Result<ActionSchema, PlanningError> {
+        let (name, mut rest) = body.split_first().ok_or_else(|| invalid(line, "action without a name"))?;
+        let mut schema = ActionSchema {
+            name: atom(name)?.to_string(),
+            params: Vec::new(),
+            preconditions: Vec::new(),
+            effects: Vec::new(),
+            cost: 1.0,
+        };
+        while let [keyword, value, tail @ ..] = rest {
+            rest = tail;
+            match atom(keyword)? {
+                ":parameters" => {
+                    schema.params = typed_list(list(value)?)?;
+                    self.check_declared(&schema.params, value.line())?;
+                }
+                ":precondition" => self.literals(value, &schema.params, &mut schema.preconditions, None)?,
+                ":effect" => self.literals(value, &schema.params, &mut schema.effects, Some(&mut schema.cost))?,
+                other => return Err(invalid(keyword.line(), format!("unsupported action field '{}'", other))),
+            }
+        }
+        if let [dangling] = rest {
+            return Err(invalid(dangling.line(), "action field without a value"));
+        }
+        Ok(schema)
+    }
+
+    /// Collects the literals of a conjunction. Effects may also set the
+    /// action's cost.
+    fn literals(
+        &self,
+        formula: &Sexp,
+        params: &[TypedName],
+        out: &mut Vec<Literal>,
+        mut cost: Option<&mut f32>,
+    ) -> Result<(), PlanningError> {
+        let items = list(formula)?;
+        let Some((head, rest)) = items.split_first() else {
+            return Ok(());
+        };
+        match atom(head)? {
+            "and" => {
+                for conjunct in rest {
+                    self.literals(conjunct, params, out, cost.as_deref_mut())?;
+                }
+                Ok(())
+            }
+            "increase" => {
+                let cost = cost.ok_or_else(|| invalid(formula.line(), "'increase' outside an effect"))?;
+                match rest {
+                    [counter, amount] if list(counter).ok().and_then(|c| c.first()).and_then(Sexp::text) == Some("total-cost") => {
+                        *cost = atom(amount)?
+                            .parse()
+                            .map_err(|_| invalid(amount.line(), "cost must be a number"))?;
+                        Ok(())
+                    }
+                    _ => Err(invalid(formula.line(), "only (increase (total-cost) n) is supported")),
+                }
+            }
+            _ => {
+                let literal = literal(formula)?;
+                self.check_literal(&literal, params, formula.line())?;
+                out.push(literal);
+                Ok(())
+            }
+        }
+    }
+
+    fn check_literal(&self, literal: &Literal, params: &[TypedName], line: usize) -> Result<(), PlanningError> {
+        if !self.predicates.is_empty() {
+            match self.predicates.iter().find(|p| p.name == literal.predicate) {
+                Some(p) if p.params.len() != literal.args.len() => {
+                    return Err(invalid(
+                        line,
+                        format!("'{}' takes {} argument(s) but {} were given", p.name, p.params.len(), literal.args.len()),
+                    ));
+                }
+                Some(_) => {}
+                None => return Err(invalid(line, format!("unknown predicate '{}'", literal.predicate))),
+            }
+        }
+        for arg in &literal.args {
+            let known = if arg.starts_with('?') {
+                params.iter().any(|p| &p.name == arg)
+            } else {
+                self.constants.iter().any(|c| &c.name == arg)
+            };
+            if !known {
+                return Err(invalid(line, format!("'{}' is neither a parameter nor a constant", arg)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Rejects names of undeclared types, once the domain declares types.
+    fn check_declared(&self, names: &[TypedName], line: usize) -> Result<(), PlanningError> {
+        if self.types.is_empty() {
+            return Ok(());
+        }
+        let declared = |name: &str| name == "object" || self.types.iter().any(|t| t.name == name);
+        match names.iter().find(|typed| !declared(&typed.type_name)) {
+            Some(typed) => Err(invalid(line, format!("undeclared type '{}'", typed.type_name))),
+            None => Ok(()),
+        }
+    }
+
+    /// Rejects supertypes that are undeclared or lead back to the type.
+    fn check_types(&self, line: usize) -> Result<(), PlanningError> {
+        self.check_declared(&self.types, line)?;
+        for start in &self.types {
+            let mut current = &start.type_name;
+            for _ in 0..self.types.len() {
+                if current == &start.name {
+                    return Err(invalid(line, format!("type '{}' is its own supertype", start.name)));
+                }
+                match self.types.iter().find(|t| &t.name == current) {
+                    Some(parent) => current = &parent.type_name,
+                    None => break,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A planning problem: the objects of a situation, what holds at the
+/// start, and what should hold at the end.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    pub name: String,
+    pub domain: String,
+    pub objects: Vec<TypedName>,
+    /// World-state keys of the atoms that hold initially
+    pub init: Vec<String>,
+    pub goal: WorldState,
+}
+
+impl Problem {
+    pub fn parse(source: &str) -> Result<Problem, PlanningError> {
+        let forms = read(source)?;
+        let (name, sections) = definition(&forms, "problem")?;
+        let mut problem = Problem {
+            name,
+            domain: String::new(),
+            objects: Vec::new(),
+            init: Vec::new(),
+            goal: WorldState::new(),
+        };
+
+        for section in sections {
+            let (keyword, body) = section_of(section)?;
+            match keyword {
+                ":domain" => match body {
+                    [name] => problem.domain = atom(name)?.to_string(),
+                    _ => return Err(invalid(section.line(), "expected one domain name")),
+                },
+                ":objects" => problem.objects = typed_list(body)?,
+                ":init" => {
+                    for form in body {
+                        let literal = ground_literal(form)?;
+                        if !literal.positive {
+                            return Err(invalid(form.line(), "the initial state lists only atoms that hold"));
+                        }
+                        problem.init.push(atom_key(&literal.predicate, &literal.args));
+                    }
+                }
+                ":goal" => match body {
+                    [formula] => goal_literals(formula, &mut problem.goal)?,
+                    _ => return Err(invalid(section.line(), "expected one goal formula")),
+                },
+                other => return Err(invalid(section.line(), format!("unsupported section '{}'", other))),
+            }
+        }
+        Ok(problem)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Problem, PlanningError> {
+        Problem::parse(&read_file(path.as_ref())?)
+    }
+
+    /// The initial world state under the closed-world assumption: every
+    /// ground atom of the domain's predicates that is not listed in `init`
+    /// is false, so negative preconditions can be met.
+    pub fn initial_state(&self, domain: &Domain) -> WorldState {
+        let objects: Vec<&TypedName> = domain.constants.iter().chain(&self.objects).collect();
+        let mut state = WorldState::new();
+        for predicate in &domain.predicates {
+            for binding in domain.bindings(&predicate.params, &objects) {
+                let args: Vec<String> = binding.iter().map(|a| a.to_string()).collect();
+                state.insert(atom_key(&predicate.name, &args), false);
+            }
+        }
+        for key in &self.init {
+            state.insert(key.clone(), true);
+        }
+        state
+    }
+
+    pub fn goal(&self, priority: i32) -> Goal {
+        Goal {
+            id: self.name.clone(),
+            description: format!("Solve planning problem '{}'", self.name),
+            desired_state: self.goal.clone(),
+            priority,
+        }
+    }
+}
+
+/// The world-state key of a ground atom: `light_on`, `at(robot,kitchen)`.
+pub fn atom_key(predicate: &str, args: &[String]) -> String {
+    if args.is_empty() {
+        predicate.to_string()
+    } else {
+        format!("{}({})", predicate, args.join(","))
+    }
+}
+
+// ============================================================================
+//                               S-EXPRESSIONS
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Sexp {
+    Atom { text: String, line: usize },
+    List { items: Vec<Sexp>, line: usize },
+}
+
+impl Sexp {
+    fn line(&self) -> usize {
+        match self {
+            Sexp::Atom { line, .. } | Sexp::List { line, .. } => *line,
+        }
+    }
+
+    fn text(&self) -> Option<&str> {
+        match self {
+            Sexp::Atom { text, .. } => Some(text),
+            Sexp::List { .. } => None,
+        }
+    }
+}
+
+fn read(source: &str) -> Result<Vec<Sexp>, PlanningError> {
+    // Open lists with the line they started on; the bottom one is the file
+    let mut open: Vec<(Vec<Sexp>, usize)> = vec![(Vec::new(), 1)];
+    let mut line = 1;
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            ';' => {
+                while chars.next_if(|&c| c != '\n').is_some() {}
+            }
+            '(' => open.push((Vec::new(), line)),
+            ')' => {
+                if open.len() == 1 {
+                    return Err(invalid(line, "unmatched ')'"));
+                }
+                let (items, start) = open.pop().expect("more than the file is open");
+                open.last_mut().expect("the file stays open").0.push(Sexp::List { items, line: start });
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut text: String = c.to_lowercase().collect();
+                while let Some(next) = chars.next_if(|&c| !c.is_whitespace() && !matches!(c, '(' | ')' | ';')) {
+                    text.extend(next.to_lowercase());
+                }
+                open.last_mut().expect("the file stays open").0.push(Sexp::Atom { text, line });
+            }
+        }
+    }
+    if let [_, .., (_, start)] = open.as_slice() {
+        return Err(invalid(*start, "unclosed '('"));
+    }
+    Ok(open.pop().expect("the file stays open").0)
+}
+
+fn atom(sexp: &Sexp) -> Result<&str, PlanningError> {
+    sexp.text().ok_or_else(|| invalid(sexp.line(), "expected a name, found a list"))
+}
+
+fn list(sexp: &Sexp) -> Result<&[Sexp], PlanningError> {
+    match sexp {
+        Sexp::List { items, .. } => Ok(items),
+        Sexp::Atom { text, line } => Err(invalid(*line, format!("expected a list, found '{}'", text))),
+    }
+}
+
+/// The name and sections of `(define (kind name) sections...)`.
+fn definition<'s>(forms: &'s [Sexp], kind: &str) -> Result<(String, &'s [Sexp]), PlanningError> {
+    let form = match forms {
+        [form] => form,
+        [] => return Err(invalid(1, format!("expected (define ({} ...) ...)", kind))),
+        [_, extra, ..] => return Err(invalid(extra.line(), "expected a single definition")),
+    };
+    match list(form)? {
+        [define, header, sections @ ..] if define.text() == Some("define") => match list(header)? {
+            [head, name] if head.text() == Some(kind) => Ok((atom(name)?.to_string(), sections)),
+            _ => Err(invalid(header.line(), format!("expected ({} name)", kind))),
+        },
+        _ => Err(invalid(form.line(), format!("expected (define ({} ...) ...)", kind))),
+    }
+}
+
+fn section_of(section: &Sexp) -> Result<(&str, &[Sexp]), PlanningError> {
+    let items = list(section)?;
+    let (keyword, body) = items.split_first().ok_or_else(|| invalid(section.line(), "empty section"))?;
+    Ok((atom(keyword)?, body))
+}
+
+/// `a b - t c` as `a: t, b: t, c: object`.
+fn typed_list(items: &[Sexp]) -> Result<Vec<TypedName>, PlanningError> {
+    let mut typed = Vec::new();
+    let mut untyped = Vec::new();
+    let mut items = items.iter();
+    while let Some(item) = items.next() {
+        let name = atom(item)?;
+        if name != "-" {
+            untyped.push(name.to_string());
+            continue;
+        }
+        let type_name = items.next().ok_or_else(|| invalid(item.line(), "expected a type after '-'"))?;
+        let type_name = atom(type_name)?;
+        if untyped.is_empty() {
+            return Err(invalid(item.line(), format!("type '{}' names nothing", type_name)));
+        }
+        typed.extend(untyped.drain(..).map(|name| TypedName { name, type_name: type_name.to_string() }));
+    }
+    typed.extend(untyped.into_iter().map(|name| TypedName { name, type_name: "object".into() }));
+    Ok(typed)
+}
+
+/// `(p a b)` or `(not (p a b))`.
+fn literal(formula: &Sexp) -> Result<Literal, PlanningError> {
+    let items = list(formula)?;
+    if let [head, inner] = items {
+        if head.text() == Some("not") {
+            return Ok(Literal { positive: false, ..literal(inner)? });
+        }
+    }
+    let (predicate, args) = items.split_first().ok_or_else(|| invalid(formula.line(), "empty literal"))?;
+    Ok(Literal {
+        positive: true,
+        predicate: atom(predicate)?.to_string(),
+        args: args.iter().map(|a| atom(a).map(str::to_string)).collect::<Result<_, _>>()?,
+    })
+}
+
+fn ground_literal(formula: &Sexp) -> Result<Literal, PlanningError> {
+    let literal = literal(formula)?;
+    if let Some(variable) = literal.args.iter().find(|a| a.starts_with('?')) {
+        return Err(invalid(formula.line(), format!("variable '{}' in a problem", variable)));
+    }
+    Ok(literal)
+}
+
+fn goal_literals(formula: &Sexp, goal: &mut WorldState) -> Result<(), PlanningError> {
+    let items = list(formula)?;
+    if items.first().and_then(Sexp::text) == Some("and") {
+        for conjunct in &items[1..] {
+            goal_literals(conjunct, goal)?;
+        }
+        return Ok(());
+    }
+    let literal = ground_literal(formula)?;
+    goal.insert(atom_key(&literal.predicate, &literal.args), literal.positive);
+    Ok(())
+}
+
+fn read_file(path: &Path) -> Result<String, PlanningError> {
+    std::fs::read_to_string(path).map_err(|source| PlanningError::DomainUnreadable { path: path.to_path_buf(), source })
+}
+
+fn invalid(line: usize, message: impl Into<String>) -> PlanningError {
+    PlanningError::InvalidDomain { line, message: message.into() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planning::planner::{Planner, PlanningStrategy};
+
+    const DOMAIN: &str = r#"
+        ; A robot that carries parcels between rooms
+        (define (domain delivery)
+          (:requirements :strips :typing :negative-preconditions :action-costs)
+          (:types room parcel - object)
+          (:constants hall - room)
+          (:predicates (robot-at ?r - room) (parcel-at ?p - parcel ?r - room) (holding ?p - parcel))
+          (:action move
+            :parameters (?from ?to - room)
+            :precondition (and (robot-at ?from) (not (robot-at ?to)))
+            :effect (and (not (robot-at ?from)) (robot-at ?to) (increase (total-cost) 2)))
+          (:action pick
+            :parameters (?p - parcel ?r - room)
+            :precondition (and (robot-at ?r) (parcel-at ?p ?r))
+            :effect (and (holding ?p) (not (parcel-at ?p ?r))))
+          (:action drop
+            :parameters (?p - parcel ?r - room)
+            :precondition (and (robot-at ?r) (holding ?p))
+            :effect (and (parcel-at ?p ?r) (not (holding ?p)))))
+    "#;
+
+    const PROBLEM: &str = r#"
+        (define (problem fetch-letter)
+          (:domain delivery)
+          (:objects office - room letter - parcel)
+          (:init (robot-at hall) (parcel-at letter office))
+          (:goal (and (parcel-at letter hall))))
+    "#;
+
+    #[test]
+    fn test_domain_grounds_into_a_solvable_plan() {
+        let domain = Domain::parse(DOMAIN).unwrap();
+        let problem = Problem::parse(PROBLEM).unwrap();
+        assert_eq!(problem.domain, domain.name);
+
+        let actions = domain.ground(&problem.objects);
+        // Two moves (not hall to hall), and pick and drop in each room
+        assert_eq!(actions.len(), 6);
+        let move_out = actions.iter().find(|a| a.id == "move(hall,office)").unwrap();
+        assert_eq!(move_out.cost, 2.0);
+        assert_eq!(move_out.effects.get("robot-at(hall)"), Some(&false));
+
+        let world = problem.initial_state(&domain);
+        assert_eq!(world.get("holding(letter)"), Some(&false));
+        let plan = Planner::new()
+            .plan_with_strategy(PlanningStrategy::Goap, &world, &problem.goal(5), &actions)
+            .unwrap();
+        let steps: Vec<&str> = plan.actions.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(steps, ["move(hall,office)", "pick(letter,office)", "move(office,hall)", "drop(letter,hall)"]);
+
+        let typo = DOMAIN.replace("(holding ?p) (not (parcel-at", "(holds ?p) (not (parcel-at");
+        assert!(matches!(
+            Domain::parse(&typo),
+            Err(PlanningError::InvalidDomain { line: 15, message }) if message == "unknown predicate 'holds'"
+        ));
+        assert!(matches!(Domain::parse("(define (domain d)"), Err(PlanningError::InvalidDomain { line: 1, .. })));
+    }
+}
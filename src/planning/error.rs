@@ -0,0 +1,67 @@
+// ============================================================================
+//                        ASTRA AGI • PLANNING ERRORS
+//        Typed Failures of Planning & Plan Execution
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       The error type of the planning subsystem. Stepping a plan in the
+//       wrong state, a critical failure inside an environment adapter, and
+//       a malformed autonomy policy or planning domain are distinct
+//       variants, and adapter, parsing, and file failures keep their cause
+//       as the error source.
+//
+//   Core Functions:
+//       • Name the ways plan execution can be misused or fail
+//       • Carry the action and cause of critical action failures
+//       • Report invalid autonomy policies with the parser's error
+//       • Report behavior tree files that do not parse
+//       • Report malformed planning domains by line
+//       • Report thread pools parallel search could not start
+//       • Report quota limits that do not parse
+//
+//   File:        /src/planning/error.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PlanningError {
+    #[error("Execution already finished")]
+    ExecutionFinished,
+    #[error("Execution is awaiting confirmation")]
+    AwaitingConfirmation,
+    #[error("No action is awaiting confirmation")]
+    NothingAwaitingConfirmation,
+    /// The environment adapter reported a critical error
+    #[error("Critical error executing action {action_id}")]
+    ActionFailed {
+        action_id: String,
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error("Invalid autonomy policy")]
+    InvalidPolicy(#[from] serde_json::Error),
+    #[error("Invalid behavior tree: {0}")]
+    InvalidBehaviorTree(String),
+    /// A planning domain or problem file is malformed
+    #[error("Invalid planning domain at line {line}: {message}")]
+    InvalidDomain { line: usize, message: String },
+    #[error("Could not read planning domain {}", path.display())]
+    DomainUnreadable {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    /// A quota limit is not `resource=limit/seconds`
+    #[error("Invalid quota limit '{0}'")]
+    InvalidQuota(String),
+    #[error("Could not start planning threads")]
+    ThreadPool(#[from] rayon::ThreadPoolBuildError),
+}
@@ -15,20 +15,30 @@
 //       • Step through plans one action at a time
 //       • Report success, failure, and partial completion
 //       • Provide hooks for environment-specific action handlers
+//       • Measure action durations as telemetry for learned cost models
+//       • Check observed world state against each action's expected effects
+//       • Enforce the autonomy policy before an action reaches its effector
+//       • Hold actions that need confirmation until a person confirms or denies them
+//       • Note which actions ran unconfirmed, or were forbidden, for audit
+//       • Report misuse and critical action failures as typed errors
 //
 //   File:        /src/planning/executor.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-23
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
-use crate::planner::{Action, Plan};
-use anyhow::{anyhow, Result};
+use crate::autonomy::{ActionCategory, AutonomyLevel, AutonomyNotice, AutonomyPolicy};
+use crate::cost_model::ExecutionSample;
+use crate::error::PlanningError;
+use crate::monitor::{check_expectations, ExpectationViolation};
+use crate::planner::{Action, Plan, WorldState};
 use log::{debug, info};
+use std::time::Instant;
 
 /// Represents the status of plan execution.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -37,13 +47,45 @@ pub enum ExecutionStatus {
     InProgress,
     Completed,
     Failed(String),
+    /// An action reported success but the observed world did not match its
+    /// expected effects; the rest of the plan was not executed
+    Diverged(Vec<ExpectationViolation>),
+    /// The autonomy policy requires a person to confirm the next action
+    /// before it runs; see `PlanExecutor::confirm_pending`
+    AwaitingConfirmation { action_id: String, category: ActionCategory },
 }
 
 /// Trait that environment adapters must implement in order to execute actions.
 pub trait ActionExecutor {
     /// Executes a single action. Returns Ok(true) if successful, Ok(false) if
     /// the action failed in a recoverable way, and Err for critical errors.
-    fn execute_action(&mut self, action: &Action) -> Result<bool>;
+    fn execute_action(&mut self, action: &Action) -> anyhow::Result<bool>;
+
+    /// Reports the world state after an action, so its expected effects can
+    /// be checked. Environments that cannot observe return `None`.
+    fn observe_world(&self) -> Option<WorldState> {
+        None
+    }
+}
+
+impl<E: ActionExecutor + ?Sized> ActionExecutor for &mut E {
+    fn execute_action(&mut self, action: &Action) -> anyhow::Result<bool> {
+        (**self).execute_action(action)
+    }
+
+    fn observe_world(&self) -> Option<WorldState> {
+        (**self).observe_world()
+    }
+}
+
+impl<E: ActionExecutor + ?Sized> ActionExecutor for Box<E> {
+    fn execute_action(&mut self, action: &Action) -> anyhow::Result<bool> {
+        (**self).execute_action(action)
+    }
+
+    fn observe_world(&self) -> Option<WorldState> {
+        (**self).observe_world()
+    }
 }
 
 /// Simple in-memory executor that steps through a plan using an ActionExecutor.
@@ -52,6 +94,11 @@ pub struct PlanExecutor<E: ActionExecutor> {
     index: usize,
     status: ExecutionStatus,
     env: E,
+    telemetry: Vec<ExecutionSample>,
+    policy: Option<AutonomyPolicy>,
+    /// Index of an action a person has confirmed
+    confirmed: Option<usize>,
+    notices: Vec<AutonomyNotice>,
 }
 
 impl<E: ActionExecutor> PlanExecutor<E> {
@@ -62,9 +109,58 @@ impl<E: ActionExecutor> PlanExecutor<E> {
             index: 0,
             status: ExecutionStatus::NotStarted,
             env,
+            telemetry: Vec::new(),
+            policy: None,
+            confirmed: None,
+            notices: Vec::new(),
+        }
+    }
+
+    /// Checks every action against `policy` before it runs.
+    pub fn with_policy(mut self, policy: AutonomyPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Replaces the policy for the actions not yet run.
+    pub fn set_policy(&mut self, policy: AutonomyPolicy) {
+        self.policy = Some(policy);
+    }
+
+    /// Lets the action awaiting confirmation run on the next step.
+    pub fn confirm_pending(&mut self) -> Result<(), PlanningError> {
+        match self.status {
+            ExecutionStatus::AwaitingConfirmation { .. } => {
+                self.confirmed = Some(self.index);
+                self.status = ExecutionStatus::InProgress;
+                Ok(())
+            }
+            _ => Err(PlanningError::NothingAwaitingConfirmation),
+        }
+    }
+
+    /// Refuses the action awaiting confirmation; the plan fails there and
+    /// the rest of it does not run.
+    pub fn deny_pending(&mut self) -> Result<(), PlanningError> {
+        match &self.status {
+            ExecutionStatus::AwaitingConfirmation { action_id, .. } => {
+                self.status = ExecutionStatus::Failed(format!("Action '{}' was denied", action_id));
+                Ok(())
+            }
+            _ => Err(PlanningError::NothingAwaitingConfirmation),
         }
     }
 
+    /// Actions that ran without confirmation or were forbidden, oldest first.
+    pub fn notices(&self) -> &[AutonomyNotice] {
+        &self.notices
+    }
+
+    /// Takes the notices gathered so far, leaving none behind.
+    pub fn take_notices(&mut self) -> Vec<AutonomyNotice> {
+        std::mem::take(&mut self.notices)
+    }
+
     /// Returns the current execution status.
     pub fn status(&self) -> &ExecutionStatus {
         &self.status
@@ -75,11 +171,24 @@ impl<E: ActionExecutor> PlanExecutor<E> {
         &self.plan
     }
 
+    /// Number of actions completed so far.
+    pub fn completed_actions(&self) -> usize {
+        self.index
+    }
+
+    /// Measured duration and outcome of every action attempted so far.
+    pub fn telemetry(&self) -> &[ExecutionSample] {
+        &self.telemetry
+    }
+
     /// Advances execution by one action step.
-    pub fn step(&mut self) -> Result<()> {
+    pub fn step(&mut self) -> Result<(), PlanningError> {
         match self.status {
-            ExecutionStatus::Completed | ExecutionStatus::Failed(_) => {
-                return Err(anyhow!("Execution already finished"));
+            ExecutionStatus::Completed | ExecutionStatus::Failed(_) | ExecutionStatus::Diverged(_) => {
+                return Err(PlanningError::ExecutionFinished);
+            }
+            ExecutionStatus::AwaitingConfirmation { .. } => {
+                return Err(PlanningError::AwaitingConfirmation);
             }
             ExecutionStatus::NotStarted => {
                 self.status = ExecutionStatus::InProgress;
@@ -98,9 +207,63 @@ impl<E: ActionExecutor> PlanExecutor<E> {
         }
 
         let action = &self.plan.actions[self.index];
+        if let Some(policy) = &self.policy {
+            let (category, level) = policy.decide(action);
+            if level != AutonomyLevel::Confirm {
+                self.notices.push(AutonomyNotice {
+                    action_id: action.id.clone(),
+                    description: action.description.clone(),
+                    category,
+                    level,
+                });
+            }
+            match level {
+                AutonomyLevel::Auto | AutonomyLevel::Notify => {}
+                AutonomyLevel::Confirm if self.confirmed == Some(self.index) => {}
+                AutonomyLevel::Confirm => {
+                    info!("Action {} ({:?}) awaits confirmation", action.id, category);
+                    self.status = ExecutionStatus::AwaitingConfirmation { action_id: action.id.clone(), category };
+                    return Ok(());
+                }
+                AutonomyLevel::Forbid => {
+                    self.status = ExecutionStatus::Failed(format!(
+                        "Action {} ({:?}) is forbidden by the autonomy policy",
+                        action.id, category
+                    ));
+                    return Ok(());
+                }
+            }
+        }
         debug!("Executing action {} ({})", action.id, action.description);
 
-        match self.env.execute_action(action) {
+        let started = Instant::now();
+        let result = self.env.execute_action(action);
+        let duration = started.elapsed();
+        let violations = match result {
+            Ok(true) => self
+                .env
+                .observe_world()
+                .map(|world| check_expectations(action, &world))
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        self.telemetry.push(ExecutionSample {
+            action_id: action.id.clone(),
+            duration,
+            success: matches!(result, Ok(true)) && violations.is_empty(),
+        });
+
+        match result {
+            Ok(true) if !violations.is_empty() => {
+                info!(
+                    "Action {} in plan {} violated {} expectation(s)",
+                    action.id,
+                    self.plan.goal_id,
+                    violations.len()
+                );
+                self.status = ExecutionStatus::Diverged(violations);
+                Ok(())
+            }
             Ok(true) => {
                 self.index += 1;
                 if self.index >= self.plan.actions.len() {
@@ -121,13 +284,13 @@ impl<E: ActionExecutor> PlanExecutor<E> {
                     "Critical error executing action {}: {}",
                     action.id, e
                 ));
-                Err(e)
+                Err(PlanningError::ActionFailed { action_id: action.id.clone(), source: e })
             }
         }
     }
 
     /// Executes the entire plan until completion or failure.
-    pub fn run_to_completion(&mut self) -> Result<ExecutionStatus> {
+    pub fn run_to_completion(&mut self) -> Result<ExecutionStatus, PlanningError> {
         while self.status == ExecutionStatus::NotStarted
             || self.status == ExecutionStatus::InProgress
         {
@@ -148,7 +311,7 @@ mod tests {
     }
 
     impl ActionExecutor for TestEnv {
-        fn execute_action(&mut self, action: &Action) -> Result<bool> {
+        fn execute_action(&mut self, action: &Action) -> anyhow::Result<bool> {
             if let Some(ref id) = self.fail_on {
                 if &action.id == id {
                     return Ok(false);
@@ -168,6 +331,7 @@ mod tests {
                     preconditions: HashMap::new(),
                     effects: HashMap::new(),
                     cost: 1.0,
+                    risk: 0.0,
                 },
                 Action {
                     id: "a2".into(),
@@ -175,6 +339,7 @@ mod tests {
                     preconditions: HashMap::new(),
                     effects: HashMap::new(),
                     cost: 1.0,
+                    risk: 0.0,
                 },
             ],
             estimated_cost: 2.0,
@@ -204,4 +369,79 @@ mod tests {
 
         assert!(matches!(status, ExecutionStatus::Failed(_)));
     }
+
+    #[test]
+    fn executor_records_telemetry_per_attempt() {
+        let plan = sample_plan();
+        let env = TestEnv {
+            fail_on: Some("a2".into()),
+        };
+
+        let mut executor = PlanExecutor::new(plan, env);
+        executor.run_to_completion().expect("execution failed");
+
+        let samples = executor.telemetry();
+        assert_eq!(samples.len(), 2);
+        assert!(samples[0].success);
+        assert_eq!(samples[1].action_id, "a2");
+        assert!(!samples[1].success);
+    }
+
+    struct StuckEnv;
+
+    impl ActionExecutor for StuckEnv {
+        fn execute_action(&mut self, _action: &Action) -> anyhow::Result<bool> {
+            Ok(true)
+        }
+
+        fn observe_world(&self) -> Option<WorldState> {
+            Some(WorldState::new())
+        }
+    }
+
+    #[test]
+    fn executor_enforces_autonomy_policy() {
+        let mut plan = sample_plan();
+        plan.actions[1].id = "send_summary".into();
+        let env = TestEnv { fail_on: None };
+
+        let mut executor = PlanExecutor::new(plan.clone(), env).with_policy(AutonomyPolicy::default());
+        let status = executor.run_to_completion().expect("execution failed");
+        assert!(matches!(status, ExecutionStatus::AwaitingConfirmation { ref action_id, .. } if action_id == "send_summary"));
+        assert_eq!(executor.completed_actions(), 1);
+
+        executor.confirm_pending().expect("nothing to confirm");
+        assert_eq!(executor.run_to_completion().expect("execution failed"), ExecutionStatus::Completed);
+        assert!(matches!(executor.confirm_pending(), Err(PlanningError::NothingAwaitingConfirmation)));
+        assert!(matches!(executor.step(), Err(PlanningError::ExecutionFinished)));
+
+        let mut policy = AutonomyPolicy::default();
+        policy.levels.insert(ActionCategory::MessageSend, AutonomyLevel::Forbid);
+        let mut forbidden = PlanExecutor::new(plan.clone(), TestEnv { fail_on: None }).with_policy(policy);
+        assert!(matches!(forbidden.run_to_completion().unwrap(), ExecutionStatus::Failed(_)));
+        assert_eq!(forbidden.telemetry().len(), 1);
+
+        let mut denied = PlanExecutor::new(plan, TestEnv { fail_on: None }).with_policy(AutonomyPolicy::default());
+        denied.run_to_completion().expect("execution failed");
+        denied.deny_pending().expect("nothing to deny");
+        assert!(matches!(denied.status(), ExecutionStatus::Failed(_)));
+        assert_eq!(denied.completed_actions(), 1);
+        assert!(matches!(denied.deny_pending(), Err(PlanningError::NothingAwaitingConfirmation)));
+    }
+
+    #[test]
+    fn executor_stops_on_expectation_violation() {
+        let mut plan = sample_plan();
+        plan.actions[0].effects.insert("door_open".into(), true);
+
+        let mut executor = PlanExecutor::new(plan, StuckEnv);
+        let status = executor.run_to_completion().expect("execution failed");
+
+        match status {
+            ExecutionStatus::Diverged(violations) => assert_eq!(violations[0].key, "door_open"),
+            other => panic!("unexpected status {:?}", other),
+        }
+        assert_eq!(executor.completed_actions(), 0);
+        assert!(!executor.telemetry()[0].success);
+    }
 }
@@ -13,17 +13,46 @@
 //       • Define the module layout for planning and decision components
 //       • Expose the Planner engine for goal‑driven reasoning
 //       • Expose the Executor for action realization and plan enactment
+//       • Expose learned action cost models for realistic planning
+//       • Expose availability windows for deadline feasibility
+//       • Expose interruptible anytime search for tick-bounded planning
+//       • Expose session-scoped arenas for plan search graphs
+//       • Expose parallel GOAP search behind the parallel-planning feature
+//       • Expose execution monitoring against expected action effects
+//       • Expose the autonomy policy consulted before actions run
+//       • Expose behavior trees for reactive intents that need no search
+//       • Expose skills compiled from action sequences that keep succeeding
+//       • Expose quota tracking for rate-limited external resources
+//       • Expose the PDDL loader for declarative planning domains
+//       • Expose dialogue acts for planning conversations about a task
+//       • Expose a simulated world for plans run without real effect
+//       • Expose the typed planning error
 //       • Provide a unified namespace for APDS‑related functionality
 //
 //   File:        /src/planning/mod.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-23
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
+pub mod error;
 pub mod planner;
+pub mod domain;
+pub mod cost_model;
+pub mod availability;
+pub mod anytime;
+pub mod arena;
+#[cfg(feature = "parallel-planning")]
+pub mod parallel;
+pub mod monitor;
+pub mod autonomy;
+pub mod behavior_tree;
+pub mod skills;
+pub mod quotas;
+pub mod dialogue;
+pub mod simulation;
 pub mod executor;
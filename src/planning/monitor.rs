@@ -0,0 +1,143 @@
+// ============================================================================
+//                      ASTRA AGI • EXECUTION MONITORING
+//        Expectation Checks & Learned Confidence in Action Effects
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Every action declares the effects it is expected to have. After an
+//       action runs, the executor compares those expectations with the world
+//       state the environment actually reports. Mismatches become expectation
+//       violations: they halt the plan so it can be replanned from the
+//       observed state, feed surprise into curiosity, and update how much
+//       Astra trusts each action's declared effects.
+//
+//   Core Functions:
+//       • Compare an action's declared effects with the observed world
+//       • Describe expectation violations for replanning and reflection
+//       • Maintain per-effect confidence in the action model
+//       • Convert violations into a surprise signal
+//
+//   File:        /src/planning/monitor.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use super::planner::{Action, WorldState};
+use std::collections::HashMap;
+
+/// An effect an action declared but the world did not show.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectationViolation {
+    pub action_id: String,
+    pub key: String,
+    pub expected: bool,
+    /// `None` when the observed world does not mention the key at all
+    pub observed: Option<bool>,
+}
+
+/// Checks an action's declared effects against the observed world.
+pub fn check_expectations(action: &Action, observed: &WorldState) -> Vec<ExpectationViolation> {
+    let mut violations: Vec<ExpectationViolation> = action
+        .effects
+        .iter()
+        .filter(|(key, expected)| observed.get(*key) != Some(*expected))
+        .map(|(key, expected)| ExpectationViolation {
+            action_id: action.id.clone(),
+            key: key.clone(),
+            expected: *expected,
+            observed: observed.get(key).copied(),
+        })
+        .collect();
+    violations.sort_by(|a, b| a.key.cmp(&b.key));
+    violations
+}
+
+/// How surprising a set of violations is, in 0.0..=1.0. Any violation is
+/// notable; several at once are more so.
+pub fn surprise(violations: &[ExpectationViolation]) -> f32 {
+    if violations.is_empty() {
+        0.0
+    } else {
+        (0.4 + 0.2 * violations.len() as f32).min(1.0)
+    }
+}
+
+/// Confidence that each declared effect of each action actually happens,
+/// as the mean of a Beta(1 + held, 1 + violated) posterior.
+#[derive(Debug, Clone, Default)]
+pub struct ActionModelBeliefs {
+    counts: HashMap<(String, String), (u32, u32)>,
+}
+
+impl ActionModelBeliefs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the outcome of one execution of `action`: every declared
+    /// effect not listed in `violations` is counted as having held.
+    pub fn record(&mut self, action: &Action, violations: &[ExpectationViolation]) {
+        for key in action.effects.keys() {
+            let entry = self.counts.entry((action.id.clone(), key.clone())).or_insert((0, 0));
+            if violations.iter().any(|v| v.action_id == action.id && &v.key == key) {
+                entry.1 += 1;
+            } else {
+                entry.0 += 1;
+            }
+        }
+    }
+
+    pub fn effect_confidence(&self, action_id: &str, key: &str) -> f32 {
+        let (held, violated) = self
+            .counts
+            .get(&(action_id.to_string(), key.to_string()))
+            .copied()
+            .unwrap_or((0, 0));
+        (held as f32 + 1.0) / ((held + violated) as f32 + 2.0)
+    }
+
+    /// Confidence in the least reliable declared effect of an action; 1.0
+    /// when it declares no effects.
+    pub fn action_confidence(&self, action: &Action) -> f32 {
+        action
+            .effects
+            .keys()
+            .map(|key| self.effect_confidence(&action.id, key))
+            .fold(1.0, f32::min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_violations_update_beliefs() {
+        let action = Action {
+            id: "turn_on_light".into(),
+            description: "Turn on the light".into(),
+            preconditions: HashMap::new(),
+            effects: HashMap::from([("light_on".into(), true)]),
+            cost: 1.0,
+            risk: 0.0,
+        };
+
+        let dark = WorldState::from([("light_on".to_string(), false)]);
+        let violations = check_expectations(&action, &dark);
+        assert_eq!(violations[0].observed, Some(false));
+        assert!(surprise(&violations) > 0.0);
+
+        let mut beliefs = ActionModelBeliefs::new();
+        beliefs.record(&action, &violations);
+        beliefs.record(&action, &violations);
+        beliefs.record(&action, &[]);
+        assert!(beliefs.effect_confidence("turn_on_light", "light_on") < 0.5);
+
+        let lit = WorldState::from([("light_on".to_string(), true)]);
+        assert!(check_expectations(&action, &lit).is_empty());
+    }
+}
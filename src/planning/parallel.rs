@@ -0,0 +1,308 @@
+// ============================================================================
+//                      ASTRA AGI • PARALLEL PLAN SEARCH
+//        Batched Best-First Search with Expansions Spread over Threads
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       GOAP-style search over domains with many reachable states spends
+//       nearly all its time expanding nodes: checking preconditions,
+//       copying world states, and hashing them. This module takes the most
+//       promising nodes off a shared frontier in batches and expands each
+//       batch across a rayon pool, then merges the children back into the
+//       frontier in a fixed order. Pruning against states already reached
+//       and against the incumbent plan happens during the merge, on one
+//       thread, so the search returns the same plan whatever the number of
+//       threads and however they are scheduled. Only the batch size changes
+//       which nodes are expanded. Enabled by the `parallel-planning` feature.
+//
+//   Core Functions:
+//       • Expand batches of frontier nodes in parallel
+//       • Merge children deterministically, breaking ties by generation order
+//       • Return cost-optimal plans under the same heuristic as anytime search
+//       • Run on a dedicated pool, or rayon's global one
+//       • Stand in for GOAP in the unified Planner
+//
+//   File:        /src/planning/parallel.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use super::anytime::state_key;
+use super::arena::{ArenaId, PlanGraph};
+use super::error::PlanningError;
+use super::planner::{goal_satisfied, preconditions_met, Action, Goal, Plan, Planner, PlannerEngine, WorldState};
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// How a parallel search divides its work.
+#[derive(Debug, Clone, Copy)]
+pub struct ParallelSearchConfig {
+    /// Nodes taken off the frontier and expanded together. The plan found
+    /// depends on this alone, not on `threads`.
+    pub batch_size: usize,
+    /// Threads in the search's own pool; `None` shares rayon's global pool
+    pub threads: Option<usize>,
+    /// Gives up after expanding this many nodes, returning the best plan so far
+    pub max_expansions: Option<usize>,
+}
+
+impl Default for ParallelSearchConfig {
+    fn default() -> Self {
+        ParallelSearchConfig { batch_size: 64, threads: None, max_expansions: None }
+    }
+}
+
+struct Node {
+    world: WorldState,
+    step: Option<ArenaId>,
+    cost: f32,
+    priority: f32,
+    /// Generation order, to break ties between equally promising nodes
+    order: u64,
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Node {}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Min-heap on f = g + h, oldest first among equals
+        other
+            .priority
+            .partial_cmp(&self.priority)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+/// A child generated on a worker thread, not yet checked against the frontier.
+struct Child {
+    world: WorldState,
+    key: Vec<(String, bool)>,
+    action: usize,
+    cost: f32,
+    reaches_goal: bool,
+}
+
+/// GOAP planner that expands nodes on several threads.
+pub struct ParallelGoapPlanner {
+    config: ParallelSearchConfig,
+    pool: Option<rayon::ThreadPool>,
+}
+
+impl ParallelGoapPlanner {
+    /// Starts the planner's thread pool if `config` asks for one.
+    pub fn new(config: ParallelSearchConfig) -> Result<Self, PlanningError> {
+        let pool = match config.threads {
+            Some(threads) => Some(rayon::ThreadPoolBuilder::new().num_threads(threads.max(1)).build()?),
+            None => None,
+        };
+        Ok(ParallelGoapPlanner { config, pool })
+    }
+
+    pub fn config(&self) -> &ParallelSearchConfig {
+        &self.config
+    }
+
+    fn search(&self, world: &WorldState, goal: &Goal, actions: &[Action]) -> Plan {
+        let min_action_cost = actions.iter().map(|a| a.cost.max(0.0)).fold(f32::INFINITY, f32::min);
+        let min_action_cost = if min_action_cost.is_finite() { min_action_cost } else { 0.0 };
+        let heuristic = |world: &WorldState| {
+            if goal_satisfied(world, &goal.desired_state) {
+                0.0
+            } else {
+                min_action_cost
+            }
+        };
+
+        let mut steps = PlanGraph::new();
+        let mut frontier = BinaryHeap::new();
+        let mut best_cost: HashMap<Vec<(String, bool)>, f32> = HashMap::new();
+        let mut incumbent: Option<(f32, Option<ArenaId>)> = None;
+        let mut order = 0u64;
+        let mut expansions = 0usize;
+        frontier.push(Node { world: world.clone(), step: None, cost: 0.0, priority: heuristic(world), order });
+
+        loop {
+            if self.config.max_expansions.is_some_and(|limit| expansions >= limit) {
+                break;
+            }
+            // Nothing left on the frontier can beat the incumbent
+            let bound = incumbent.map_or(f32::INFINITY, |(cost, _)| cost);
+            let mut batch = Vec::with_capacity(self.config.batch_size);
+            while batch.len() < self.config.batch_size.max(1) {
+                match frontier.peek() {
+                    Some(next) if next.priority < bound => batch.extend(frontier.pop()),
+                    _ => break,
+                }
+            }
+            if batch.is_empty() {
+                break;
+            }
+            expansions += batch.len();
+
+            let children: Vec<Vec<Child>> = batch
+                .par_iter()
+                .map(|node| {
+                    if goal_satisfied(&node.world, &goal.desired_state) {
+                        return Vec::new();
+                    }
+                    actions
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, action)| preconditions_met(&node.world, &action.preconditions))
+                        .map(|(index, action)| {
+                            let mut world = node.world.clone();
+                            for (k, v) in &action.effects {
+                                world.insert(k.clone(), *v);
+                            }
+                            Child {
+                                key: state_key(&world),
+                                reaches_goal: goal_satisfied(&world, &goal.desired_state),
+                                world,
+                                action: index,
+                                cost: node.cost + action.cost.max(0.0),
+                            }
+                        })
+                        .collect()
+                })
+                .collect();
+
+            // Merged in batch order, so the outcome does not depend on which
+            // thread finished first
+            for (node, children) in batch.iter().zip(children) {
+                if goal_satisfied(&node.world, &goal.desired_state) {
+                    if incumbent.is_none_or(|(cost, _)| node.cost < cost) {
+                        incumbent = Some((node.cost, node.step));
+                    }
+                    continue;
+                }
+                for child in children {
+                    if child.reaches_goal {
+                        if incumbent.is_none_or(|(cost, _)| child.cost < cost) {
+                            incumbent = Some((child.cost, Some(steps.extend(node.step, child.action))));
+                        }
+                        continue;
+                    }
+                    if best_cost.get(&child.key).is_some_and(|best| *best <= child.cost) {
+                        continue;
+                    }
+                    let priority = child.cost + heuristic(&child.world);
+                    if incumbent.is_some_and(|(cost, _)| priority >= cost) {
+                        continue;
+                    }
+                    best_cost.insert(child.key, child.cost);
+                    order += 1;
+                    frontier.push(Node {
+                        world: child.world,
+                        step: Some(steps.extend(node.step, child.action)),
+                        cost: child.cost,
+                        priority,
+                        order,
+                    });
+                }
+            }
+        }
+
+        match incumbent {
+            Some((cost, step)) => Plan { goal_id: goal.id.clone(), actions: steps.actions(step, actions), estimated_cost: cost },
+            None => Plan { goal_id: goal.id.clone(), actions: Vec::new(), estimated_cost: f32::INFINITY },
+        }
+    }
+}
+
+impl PlannerEngine for ParallelGoapPlanner {
+    /// Finds the cheapest plan, or an empty plan with infinite cost if
+    /// there is none within the expansion limit.
+    fn plan(&self, world: &WorldState, goal: &Goal, actions: &[Action]) -> Result<Plan, PlanningError> {
+        Ok(match &self.pool {
+            Some(pool) => pool.install(|| self.search(world, goal, actions)),
+            None => self.search(world, goal, actions),
+        })
+    }
+}
+
+impl Planner {
+    /// Plans GOAP goals with parallel search from now on.
+    pub fn set_parallel_search(&mut self, config: ParallelSearchConfig) -> Result<(), PlanningError> {
+        self.parallel_goap = Some(ParallelGoapPlanner::new(config)?);
+        Ok(())
+    }
+
+    /// Goes back to planning GOAP goals on the calling thread.
+    pub fn clear_parallel_search(&mut self) {
+        self.parallel_goap = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planning::anytime::{AnytimeSearch, SearchBudget};
+    use crate::planning::planner::PlanningStrategy;
+
+    fn switch(i: usize, cost: f32) -> Action {
+        Action {
+            id: format!("switch_{}", i),
+            description: format!("Turn on switch {}", i),
+            preconditions: if i == 0 { HashMap::new() } else { HashMap::from([(format!("on_{}", i - 1), true)]) },
+            effects: HashMap::from([(format!("on_{}", i), true)]),
+            cost,
+            risk: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_parallel_search_is_optimal_and_independent_of_threads() {
+        let mut actions: Vec<Action> = (0..6).map(|i| switch(i, 1.0 + (i % 3) as f32)).collect();
+        // A pricey shortcut straight to the last switch
+        actions.push(Action { id: "master".into(), preconditions: HashMap::new(), cost: 20.0, ..switch(5, 0.0) });
+        let goal = Goal {
+            id: "all_on".into(),
+            description: "Turn on the last switch".into(),
+            desired_state: HashMap::from([("on_5".into(), true)]),
+            priority: 5,
+        };
+        let world = WorldState::new();
+
+        let sequential = AnytimeSearch::new(&world, &goal, actions.clone()).refine(SearchBudget::default());
+        let plans: Vec<Plan> = [1, 3]
+            .into_iter()
+            .map(|threads| {
+                let config = ParallelSearchConfig { batch_size: 4, threads: Some(threads), max_expansions: None };
+                ParallelGoapPlanner::new(config).unwrap().plan(&world, &goal, &actions).unwrap()
+            })
+            .collect();
+        let ids = |plan: &Plan| plan.actions.iter().map(|a| a.id.clone()).collect::<Vec<_>>();
+        assert_eq!(plans[0].estimated_cost, sequential.plan.unwrap().estimated_cost);
+        assert_eq!(plans[0].estimated_cost, 12.0);
+        assert_eq!(ids(&plans[0]), ids(&plans[1]));
+
+        // The unified planner hands GOAP goals to it once configured
+        let mut planner = Planner::new();
+        planner.set_parallel_search(ParallelSearchConfig::default()).unwrap();
+        let plan = planner.plan_with_strategy(PlanningStrategy::Goap, &world, &goal, &actions).unwrap();
+        assert_eq!(ids(&plan), ids(&plans[0]));
+
+        // Out of expansions, the best plan found so far is returned
+        let starved = ParallelSearchConfig { batch_size: 1, threads: None, max_expansions: Some(2) };
+        let plan = ParallelGoapPlanner::new(starved).unwrap().plan(&world, &goal, &actions).unwrap();
+        assert_eq!(ids(&plan), vec!["master".to_string()]);
+    }
+}
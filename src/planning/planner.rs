@@ -14,21 +14,27 @@
 //       • Define a PlannerEngine trait for extensible planning backends
 //       • Provide HTN, GOAP, and reactive planning implementations
 //       • Offer a unified Planner interface for Astra’s cognitive runtime
+//       • Accept pluggable cost models that reprice actions before planning
+//       • Start interruptible anytime searches (see anytime.rs)
+//       • Hand GOAP goals to parallel search when enabled (see parallel.rs)
+//       • Report the world-state resources a plan touches
+//       • Run GOAP on the no_std cheapest-first search (core::search)
 //
 //   File:        /src/planning/planner.rs
 //   Author:      Alex Roussinov
 //   Created:     2025-12-23
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
-use anyhow::Result;
+use super::error::PlanningError;
+use crate::core::search::{cheapest_plan, Facts, Operator};
 use log::debug;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeSet, HashMap};
 
 /// Represents a symbolic world state as key-value pairs.
 pub type WorldState = HashMap<String, bool>;
@@ -50,6 +56,9 @@ pub struct Action {
     pub preconditions: WorldState,
     pub effects: WorldState,
     pub cost: f32,
+    /// Likelihood of an undesirable side effect, 0.0 (safe) to 1.0 (risky)
+    #[serde(default)]
+    pub risk: f32,
 }
 
 /// Represents a concrete, executable plan: an ordered sequence of actions.
@@ -64,20 +73,49 @@ impl Plan {
     pub fn is_empty(&self) -> bool {
         self.actions.is_empty()
     }
+
+    /// World-state keys the plan reads or writes. Plans whose resources
+    /// overlap must not run at the same time.
+    pub fn resources(&self) -> BTreeSet<String> {
+        self.actions
+            .iter()
+            .flat_map(|a| a.preconditions.keys().chain(a.effects.keys()))
+            .cloned()
+            .collect()
+    }
+}
+
+impl From<&Action> for Operator {
+    fn from(action: &Action) -> Self {
+        Operator {
+            preconditions: action.preconditions.iter().map(|(k, v)| (k.clone(), *v)).collect(),
+            effects: action.effects.iter().map(|(k, v)| (k.clone(), *v)).collect(),
+            cost: action.cost,
+        }
+    }
 }
 
 /// Unified trait for all planning backends.
 pub trait PlannerEngine {
     /// Attempts to construct a plan from the current world state to the goal.
-    fn plan(&self, world: &WorldState, goal: &Goal, actions: &[Action]) -> Result<Plan>;
+    fn plan(&self, world: &WorldState, goal: &Goal, actions: &[Action]) -> Result<Plan, PlanningError>;
+}
+
+/// Prices actions before planning, e.g. from learned user preferences.
+/// The returned cost replaces `Action::cost` for the duration of a planning call.
+pub trait ActionCostModel: Send + Sync {
+    fn action_cost(&self, action: &Action) -> f32;
 }
 
 /// Planning strategies available to Astra.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PlanningStrategy {
     Htn,
     Goap,
     Reactive,
+    /// A behavior tree ticked against the world instead of a plan; see
+    /// `planning::behavior_tree`
+    BehaviorTree,
 }
 
 /// Public-facing planner that can delegate to different engines.
@@ -85,6 +123,10 @@ pub struct Planner {
     htn: HtnPlanner,
     goap: GoapPlanner,
     reactive: ReactivePlanner,
+    cost_model: Option<Box<dyn ActionCostModel>>,
+    /// Plans GOAP goals instead of `goap` when set (see parallel.rs)
+    #[cfg(feature = "parallel-planning")]
+    pub(super) parallel_goap: Option<super::parallel::ParallelGoapPlanner>,
 }
 
 impl Planner {
@@ -94,40 +136,80 @@ impl Planner {
             htn: HtnPlanner::new(),
             goap: GoapPlanner::new(),
             reactive: ReactivePlanner::new(),
+            cost_model: None,
+            #[cfg(feature = "parallel-planning")]
+            parallel_goap: None,
         }
     }
 
-    /// Plans using a specified strategy.
+    /// Installs a cost model consulted by every subsequent planning call.
+    pub fn set_cost_model(&mut self, model: Box<dyn ActionCostModel>) {
+        self.cost_model = Some(model);
+    }
+
+    pub fn clear_cost_model(&mut self) {
+        self.cost_model = None;
+    }
+
+    /// Plans using a specified strategy. Behavior trees are not planned, so
+    /// asking for one plans reactively instead.
     pub fn plan_with_strategy(
         &self,
         strategy: PlanningStrategy,
         world: &WorldState,
         goal: &Goal,
         actions: &[Action],
-    ) -> Result<Plan> {
+    ) -> Result<Plan, PlanningError> {
+        let priced = self.priced_actions(actions);
+        let actions = &priced[..];
+
         match strategy {
             PlanningStrategy::Htn => self.htn.plan(world, goal, actions),
-            PlanningStrategy::Goap => self.goap.plan(world, goal, actions),
-            PlanningStrategy::Reactive => self.reactive.plan(world, goal, actions),
+            PlanningStrategy::Goap => self.goap_engine().plan(world, goal, actions),
+            PlanningStrategy::Reactive | PlanningStrategy::BehaviorTree => self.reactive.plan(world, goal, actions),
+        }
+    }
+
+    fn goap_engine(&self) -> &dyn PlannerEngine {
+        #[cfg(feature = "parallel-planning")]
+        if let Some(parallel) = &self.parallel_goap {
+            return parallel;
+        }
+        &self.goap
+    }
+
+    /// Copies of `actions` with costs from the installed cost model, if any.
+    pub(crate) fn priced_actions(&self, actions: &[Action]) -> Vec<Action> {
+        match &self.cost_model {
+            Some(model) => actions
+                .iter()
+                .map(|a| Action { cost: model.action_cost(a), ..a.clone() })
+                .collect(),
+            None => actions.to_vec(),
+        }
+    }
+
+    /// Strategy `plan_auto` would use for a goal. High-priority or complex
+    /// goals get more deliberative strategies; simpler ones use reactive planning.
+    pub fn strategy_for(&self, goal: &Goal) -> PlanningStrategy {
+        if goal.priority >= 8 {
+            PlanningStrategy::Htn
+        } else if goal.priority >= 4 {
+            PlanningStrategy::Goap
+        } else {
+            PlanningStrategy::Reactive
         }
     }
 
     /// Plans using an automatically selected strategy based on goal priority and
-    /// available information. High-priority or complex goals get more
-    /// deliberative strategies; simpler ones use reactive planning.
+    /// available information.
     pub fn plan_auto(
         &self,
         world: &WorldState,
         goal: &Goal,
         actions: &[Action],
-    ) -> Result<Plan> {
-        let strategy = if goal.priority >= 8 {
-            PlanningStrategy::Htn
-        } else if goal.priority >= 4 {
-            PlanningStrategy::Goap
-        } else {
-            PlanningStrategy::Reactive
-        };
+    ) -> Result<Plan, PlanningError> {
+        let strategy = self.strategy_for(goal);
         debug!("Selected planning strategy: {:?} for goal {}", strategy, goal.id);
         self.plan_with_strategy(strategy, world, goal, actions)
     }
@@ -163,7 +245,7 @@ impl HtnPlanner {
 }
 
 impl PlannerEngine for HtnPlanner {
-    fn plan(&self, world: &WorldState, goal: &Goal, actions: &[Action]) -> Result<Plan> {
+    fn plan(&self, world: &WorldState, goal: &Goal, actions: &[Action]) -> Result<Plan, PlanningError> {
         // Simplified HTN: treat each desired_state key as a task, and try to
         // find actions that satisfy it directly.
         let mut plan_actions = Vec::new();
@@ -200,7 +282,11 @@ impl PlannerEngine for HtnPlanner {
 //                               GOAP PLANNER
 // ----------------------------------------------------------------------------
 
-/// GOAP-style planner using a forward search (simplified BFS with cost).
+/// World states a GOAP search may expand before it gives up.
+pub const GOAP_MAX_EXPANSIONS: usize = 10_000;
+
+/// GOAP-style planner finding the cheapest action sequence with
+/// `core::search::cheapest_plan`.
 pub struct GoapPlanner {}
 
 impl GoapPlanner {
@@ -210,62 +296,24 @@ impl GoapPlanner {
 }
 
 impl PlannerEngine for GoapPlanner {
-    fn plan(&self, world: &WorldState, goal: &Goal, actions: &[Action]) -> Result<Plan> {
-        // Simplified forward search:
-        // - BFS over world states
-        // - Stop when desired_state is satisfied
-        // This is intentionally minimal but structurally correct.
-
-        #[derive(Clone)]
-        struct Node {
-            world: WorldState,
-            actions: Vec<Action>,
-            cost: f32,
-        }
-
-        let mut queue = VecDeque::new();
-        queue.push_back(Node {
-            world: world.clone(),
-            actions: Vec::new(),
-            cost: 0.0,
-        });
-
-        while let Some(node) = queue.pop_front() {
-            if goal_satisfied(&node.world, &goal.desired_state) {
-                return Ok(Plan {
-                    goal_id: goal.id.clone(),
-                    actions: node.actions,
-                    estimated_cost: node.cost,
-                });
-            }
-
-            for action in actions {
-                if preconditions_met(&node.world, &action.preconditions) {
-                    let mut new_world = node.world.clone();
-                    for (k, v) in &action.effects {
-                        new_world.insert(k.clone(), *v);
-                    }
-
-                    let mut new_actions = node.actions.clone();
-                    new_actions.push(action.clone());
-                    let new_cost = node.cost + action.cost;
-
-                    // In a full GOAP system, we would track visited states and
-                    // use an A* heuristic. Here we keep it minimal and extendable.
-                    queue.push_back(Node {
-                        world: new_world,
-                        actions: new_actions,
-                        cost: new_cost,
-                    });
-                }
-            }
-        }
-
-        // No plan found, return empty plan.
-        Ok(Plan {
-            goal_id: goal.id.clone(),
-            actions: Vec::new(),
-            estimated_cost: f32::INFINITY,
+    fn plan(&self, world: &WorldState, goal: &Goal, actions: &[Action]) -> Result<Plan, PlanningError> {
+        // Cheapest-first search, shared with the no_std core
+        let start: Facts = world.iter().map(|(k, v)| (k.clone(), *v)).collect();
+        let desired: Facts = goal.desired_state.iter().map(|(k, v)| (k.clone(), *v)).collect();
+        let operators: Vec<Operator> = actions.iter().map(Operator::from).collect();
+
+        Ok(match cheapest_plan(&start, &desired, &operators, GOAP_MAX_EXPANSIONS) {
+            Some(solution) => Plan {
+                goal_id: goal.id.clone(),
+                actions: solution.steps.iter().map(|&index| actions[index].clone()).collect(),
+                estimated_cost: solution.cost,
+            },
+            // No plan found, return empty plan.
+            None => Plan {
+                goal_id: goal.id.clone(),
+                actions: Vec::new(),
+                estimated_cost: f32::INFINITY,
+            },
         })
     }
 }
@@ -285,7 +333,7 @@ impl ReactivePlanner {
 }
 
 impl PlannerEngine for ReactivePlanner {
-    fn plan(&self, world: &WorldState, goal: &Goal, actions: &[Action]) -> Result<Plan> {
+    fn plan(&self, world: &WorldState, goal: &Goal, actions: &[Action]) -> Result<Plan, PlanningError> {
         let mut best_action: Option<&Action> = None;
         let mut best_score = f32::MIN;
 
@@ -328,13 +376,13 @@ impl PlannerEngine for ReactivePlanner {
 //                             HELPER FUNCTIONS
 // ----------------------------------------------------------------------------
 
-fn goal_satisfied(world: &WorldState, desired: &WorldState) -> bool {
+pub(crate) fn goal_satisfied(world: &WorldState, desired: &WorldState) -> bool {
     desired
         .iter()
         .all(|(k, v)| world.get(k).map(|cv| cv == v).unwrap_or(false))
 }
 
-fn preconditions_met(world: &WorldState, preconditions: &WorldState) -> bool {
+pub(crate) fn preconditions_met(world: &WorldState, preconditions: &WorldState) -> bool {
     preconditions
         .iter()
         .all(|(k, v)| world.get(k).map(|cv| cv == v).unwrap_or(false))
@@ -352,6 +400,7 @@ mod tests {
                 preconditions: HashMap::from([("has_power".into(), true)]),
                 effects: HashMap::from([("light_on".into(), true)]),
                 cost: 1.0,
+                risk: 0.0,
             },
             Action {
                 id: "enable_power".into(),
@@ -359,6 +408,7 @@ mod tests {
                 preconditions: HashMap::new(),
                 effects: HashMap::from([("has_power".into(), true)]),
                 cost: 2.0,
+                risk: 0.0,
             },
         ]
     }
@@ -379,6 +429,13 @@ mod tests {
             .expect("planning failed");
         assert!(!plan.is_empty());
         assert_eq!(plan.actions.len(), 2);
+        assert_eq!(plan.estimated_cost, 3.0);
+
+        // Search ends even though power can be enabled forever
+        let unreachable = Goal { desired_state: HashMap::from([("door_open".into(), true)]), ..goal };
+        let plan = planner.plan_with_strategy(PlanningStrategy::Goap, &world, &unreachable, &actions).unwrap();
+        assert!(plan.is_empty());
+        assert!(plan.estimated_cost.is_infinite());
     }
 
     #[test]
@@ -424,4 +481,42 @@ mod tests {
         // HTN implementation is simplified; plan may be empty but pipeline should not panic.
         assert!(plan.estimated_cost.is_finite());
     }
+
+    struct RiskAverse;
+
+    impl ActionCostModel for RiskAverse {
+        fn action_cost(&self, action: &Action) -> f32 {
+            action.cost + action.risk * 10.0
+        }
+    }
+
+    #[test]
+    fn test_cost_model_reprices_actions() {
+        let mut world = HashMap::new();
+        world.insert("has_power".into(), true);
+        let goal = Goal {
+            id: "light_goal".into(),
+            description: "Turn on the light".into(),
+            desired_state: HashMap::from([("light_on".into(), true)]),
+            priority: 2,
+        };
+
+        let mut actions = sample_actions();
+        actions.push(Action {
+            id: "hotwire_light".into(),
+            description: "Hotwire the light".into(),
+            preconditions: HashMap::new(),
+            effects: HashMap::from([("light_on".into(), true)]),
+            cost: 0.5,
+            risk: 0.9,
+        });
+
+        let mut planner = Planner::new();
+        let plan = planner.plan_with_strategy(PlanningStrategy::Reactive, &world, &goal, &actions).unwrap();
+        assert_eq!(plan.actions[0].id, "hotwire_light");
+
+        planner.set_cost_model(Box::new(RiskAverse));
+        let plan = planner.plan_with_strategy(PlanningStrategy::Reactive, &world, &goal, &actions).unwrap();
+        assert_eq!(plan.actions[0].id, "turn_on_light");
+    }
 }
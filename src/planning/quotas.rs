@@ -0,0 +1,326 @@
+// ============================================================================
+//                      ASTRA AGI • EXTERNAL RESOURCE QUOTAS
+//        Sliding-Window Usage Tracking & Quota-Aware Action Costs
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Tracks how much of each rate-limited external resource Astra has
+//       used recently — HTTP requests, LLM tokens, disk writes — against
+//       limits configured per time window. As a quota nears exhaustion the
+//       planner sees actions that consume it as increasingly expensive, so
+//       it prefers plans that spare the resource, and the runtime is told
+//       once per crossing so it can warn.
+//
+//   Core Functions:
+//       • Configure limits per resource and time window, in code or from
+//         a `http_requests=600/3600,llm_tokens=100000/86400` list
+//       • Record usage and expire it as the window slides
+//       • Attribute resource usage to actions, declared or inferred
+//       • Penalize quota-consuming actions in the planner's cost model
+//       • Report resources that have crossed the warning threshold
+//
+//   File:        /src/planning/quotas.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use super::autonomy::ActionCategory;
+use super::error::PlanningError;
+use super::planner::{Action, ActionCostModel};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// Cost multiplier added at full utilization on top of the base cost.
+const EXHAUSTION_PENALTY: f32 = 9.0;
+/// Cost multiplier for an action that would exceed a quota outright.
+const OVER_QUOTA_PENALTY: f32 = 100.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaResource {
+    HttpRequests,
+    LlmTokens,
+    DiskWrites,
+}
+
+impl QuotaResource {
+    pub const ALL: [QuotaResource; 3] = [QuotaResource::HttpRequests, QuotaResource::LlmTokens, QuotaResource::DiskWrites];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuotaResource::HttpRequests => "http_requests",
+            QuotaResource::LlmTokens => "llm_tokens",
+            QuotaResource::DiskWrites => "disk_writes",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct QuotaLimit {
+    pub limit: u64,
+    pub window: Duration,
+}
+
+/// Reads limits written as comma-separated `resource=limit/seconds`, e.g.
+/// `http_requests=600/3600,llm_tokens=100000/86400`.
+pub fn parse_limits(spec: &str) -> Result<Vec<(QuotaResource, QuotaLimit)>, PlanningError> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let invalid = || PlanningError::InvalidQuota(entry.to_string());
+            let (name, rest) = entry.split_once('=').ok_or_else(invalid)?;
+            let (limit, secs) = rest.split_once('/').ok_or_else(invalid)?;
+            let resource = QuotaResource::ALL.into_iter().find(|r| r.as_str() == name.trim()).ok_or_else(invalid)?;
+            let limit = limit.trim().parse().map_err(|_| invalid())?;
+            let secs: u64 = secs.trim().parse().map_err(|_| invalid())?;
+            if secs == 0 {
+                return Err(invalid());
+            }
+            Ok((resource, QuotaLimit { limit, window: Duration::from_secs(secs) }))
+        })
+        .collect()
+}
+
+/// A resource at or above the warning threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuotaWarning {
+    pub resource: QuotaResource,
+    pub used: u64,
+    pub limit: u64,
+    pub window: Duration,
+}
+
+/// Usage of rate-limited resources within their windows.
+#[derive(Debug, Clone)]
+pub struct QuotaTracker {
+    /// Fraction of a quota at which warnings start and costs begin to rise
+    pub warn_at: f32,
+    limits: HashMap<QuotaResource, QuotaLimit>,
+    usage: HashMap<QuotaResource, VecDeque<(Instant, u64)>>,
+    /// Declared per-action consumption, overriding inference
+    action_usage: HashMap<String, Vec<(QuotaResource, u64)>>,
+    warned: HashSet<QuotaResource>,
+}
+
+/// A tracker shared between the planner's cost model and whoever records usage.
+pub type SharedQuotaTracker = Arc<RwLock<QuotaTracker>>;
+
+impl Default for QuotaTracker {
+    fn default() -> Self {
+        QuotaTracker {
+            warn_at: 0.8,
+            limits: HashMap::new(),
+            usage: HashMap::new(),
+            action_usage: HashMap::new(),
+            warned: HashSet::new(),
+        }
+    }
+}
+
+impl QuotaTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn shared(self) -> SharedQuotaTracker {
+        Arc::new(RwLock::new(self))
+    }
+
+    pub fn set_limit(&mut self, resource: QuotaResource, limit: u64, window: Duration) {
+        self.limits.insert(resource, QuotaLimit { limit, window });
+    }
+
+    pub fn limit(&self, resource: QuotaResource) -> Option<QuotaLimit> {
+        self.limits.get(&resource).copied()
+    }
+
+    /// Declares what one execution of `action_id` consumes.
+    pub fn declare_usage(&mut self, action_id: impl Into<String>, usage: Vec<(QuotaResource, u64)>) {
+        self.action_usage.insert(action_id.into(), usage);
+    }
+
+    /// What one execution of `action` consumes: as declared, or else one
+    /// request for HTTP actions and one write for file modifications.
+    pub fn usage_of(&self, action: &Action) -> Vec<(QuotaResource, u64)> {
+        if let Some(declared) = self.action_usage.get(&action.id) {
+            return declared.clone();
+        }
+        match ActionCategory::infer(action) {
+            ActionCategory::ExternalHttp => vec![(QuotaResource::HttpRequests, 1)],
+            ActionCategory::FileModification => vec![(QuotaResource::DiskWrites, 1)],
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, resource: QuotaResource, amount: u64) {
+        self.record_at(resource, amount, Instant::now());
+    }
+
+    pub fn record_at(&mut self, resource: QuotaResource, amount: u64, at: Instant) {
+        self.usage.entry(resource).or_default().push_back((at, amount));
+    }
+
+    /// Records the consumption of one execution of `action`.
+    pub fn record_action(&mut self, action: &Action) {
+        for (resource, amount) in self.usage_of(action) {
+            self.record(resource, amount);
+        }
+    }
+
+    /// Usage of `resource` within its window ending at `now`.
+    pub fn used_at(&self, resource: QuotaResource, now: Instant) -> u64 {
+        let window = self.limits.get(&resource).map(|l| l.window);
+        self.usage
+            .get(&resource)
+            .map(|events| {
+                events
+                    .iter()
+                    .filter(|(at, _)| window.map_or(true, |w| now.saturating_duration_since(*at) < w))
+                    .map(|(_, amount)| amount)
+                    .sum()
+            })
+            .unwrap_or(0)
+    }
+
+    pub fn used(&self, resource: QuotaResource) -> u64 {
+        self.used_at(resource, Instant::now())
+    }
+
+    /// Share of the quota used, in 0..1; resources without a limit are
+    /// never constrained.
+    pub fn utilization_at(&self, resource: QuotaResource, now: Instant) -> f32 {
+        match self.limits.get(&resource) {
+            Some(limit) if limit.limit > 0 => (self.used_at(resource, now) as f32 / limit.limit as f32).min(1.0),
+            Some(_) => 1.0,
+            None => 0.0,
+        }
+    }
+
+    /// Cost multiplier for `action`. One until a resource it consumes passes
+    /// `warn_at`, rising steeply to exhaustion, and prohibitive if the action
+    /// would exceed a quota outright.
+    pub fn penalty_at(&self, action: &Action, now: Instant) -> f32 {
+        let mut penalty = 1.0;
+        for (resource, amount) in self.usage_of(action) {
+            let Some(limit) = self.limits.get(&resource) else { continue };
+            if self.used_at(resource, now) + amount > limit.limit {
+                return OVER_QUOTA_PENALTY;
+            }
+            let utilization = self.utilization_at(resource, now);
+            if utilization >= self.warn_at {
+                let pressure = (utilization - self.warn_at) / (1.0 - self.warn_at).max(f32::EPSILON);
+                penalty *= 1.0 + EXHAUSTION_PENALTY * pressure * pressure;
+            }
+        }
+        penalty
+    }
+
+    /// Resources at or above the warning threshold.
+    pub fn warnings_at(&self, now: Instant) -> Vec<QuotaWarning> {
+        let mut warnings: Vec<QuotaWarning> = self
+            .limits
+            .iter()
+            .filter(|(resource, _)| self.utilization_at(**resource, now) >= self.warn_at)
+            .map(|(resource, limit)| QuotaWarning {
+                resource: *resource,
+                used: self.used_at(*resource, now),
+                limit: limit.limit,
+                window: limit.window,
+            })
+            .collect();
+        warnings.sort_by_key(|w| w.resource.as_str());
+        warnings
+    }
+
+    /// Warnings for resources that crossed the threshold since the last
+    /// call. Expired usage is dropped, and a resource that falls back below
+    /// the threshold will warn again when it next crosses it.
+    pub fn take_new_warnings(&mut self) -> Vec<QuotaWarning> {
+        let now = Instant::now();
+        self.prune(now);
+        let warnings = self.warnings_at(now);
+        let current: HashSet<QuotaResource> = warnings.iter().map(|w| w.resource).collect();
+        let fresh = warnings.into_iter().filter(|w| !self.warned.contains(&w.resource)).collect();
+        self.warned = current;
+        fresh
+    }
+
+    fn prune(&mut self, now: Instant) {
+        for (resource, events) in self.usage.iter_mut() {
+            let Some(limit) = self.limits.get(resource) else { continue };
+            while events.front().is_some_and(|(at, _)| now.saturating_duration_since(*at) >= limit.window) {
+                events.pop_front();
+            }
+        }
+    }
+}
+
+impl ActionCostModel for SharedQuotaTracker {
+    fn action_cost(&self, action: &Action) -> f32 {
+        match self.read() {
+            Ok(quotas) => action.cost * quotas.penalty_at(action, Instant::now()),
+            Err(_) => action.cost,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(id: &str) -> Action {
+        Action {
+            id: id.into(),
+            description: id.into(),
+            preconditions: HashMap::new(),
+            effects: HashMap::new(),
+            cost: 2.0,
+            risk: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_costs_rise_and_warnings_fire_near_exhaustion() {
+        let mut quotas = QuotaTracker::new();
+        quotas.set_limit(QuotaResource::HttpRequests, 10, Duration::from_secs(60));
+        let fetch = action("fetch_page");
+        let think = action("think");
+        let start = Instant::now();
+
+        quotas.record_at(QuotaResource::HttpRequests, 5, start);
+        assert_eq!(quotas.penalty_at(&fetch, start), 1.0);
+
+        quotas.record_at(QuotaResource::HttpRequests, 4, start);
+        let near = quotas.penalty_at(&fetch, start);
+        assert!(near > 1.0 && near < OVER_QUOTA_PENALTY);
+        assert_eq!(quotas.penalty_at(&think, start), 1.0);
+        assert_eq!(quotas.take_new_warnings().len(), 1);
+        assert!(quotas.take_new_warnings().is_empty());
+
+        quotas.record_at(QuotaResource::HttpRequests, 1, start);
+        assert_eq!(quotas.penalty_at(&fetch, start), OVER_QUOTA_PENALTY);
+
+        // Once the window has passed the quota is free again.
+        let later = start + Duration::from_secs(61);
+        assert_eq!(quotas.used_at(QuotaResource::HttpRequests, later), 0);
+        assert_eq!(quotas.penalty_at(&fetch, later), 1.0);
+
+        let shared = quotas.shared();
+        assert_eq!(shared.action_cost(&think), 2.0);
+
+        let limits = parse_limits("http_requests=600/3600, llm_tokens=100000/86400").unwrap();
+        assert_eq!(limits[0], (QuotaResource::HttpRequests, QuotaLimit { limit: 600, window: Duration::from_secs(3600) }));
+        assert_eq!(limits[1].0, QuotaResource::LlmTokens);
+        assert!(parse_limits("").unwrap().is_empty());
+        for bad in ["http_requests=600", "gpu=1/60", "disk_writes=many/60", "disk_writes=5/0"] {
+            assert!(matches!(parse_limits(bad), Err(PlanningError::InvalidQuota(_))), "{}", bad);
+        }
+    }
+}
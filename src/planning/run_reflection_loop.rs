@@ -10,27 +10,51 @@
 //       learning about the external world.
 //
 //   Core Functions:
-//       • Periodically sample recent episodes from memory and logs
+//       • Periodically sample recent episodes from memory and logs, at
+//         intervals kept by the runtime's clock
 //       • Evaluate plan quality, execution success, and decision efficiency
 //       • Adjust planning heuristics and strategy selection policies
+//       • Score strategies separately for each category of goal
 //       • Emit updated meta-parameters back into the Planning and Learning subsystems
+//       • Propose heuristic rules mined from a cognitive loop's failures to
+//         the runtime's self-modification registry
+//       • Schedule strategy experiments for categories too close to call
+//       • Weaken the beliefs behind predictions the world strongly contradicted
+//       • Write the cognitive loop's finished episodes, with their affect, to narrative memory
+//       • Reflect or consolidate at once when an operator asks
 //
 //   File:        /src/planning/run_reflection_loop.rs
 //   Author:      Alex Roussinov
 //   Created:     2026-01-11
-//   Updated:     2026-01-11
+//   Updated:     2026-10-16
 //
 //   License:
 //       Dual-licensed under the MIT and Apache 2.0 licenses.
 //       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
 // ============================================================================
 
+use std::sync::Arc;
+use tokio::sync::Mutex;
 use tokio::time::{sleep, Duration};
 use log::{info, warn};
+use crate::cognition::cognitive_loop::{CognitiveLoop, WorldStateProvider};
+use crate::cognition::consolidation::run_consolidation_cycle;
+use crate::cognition::experiments::{ExperimentId, ExperimentParams};
+use crate::cognition::learning_adapter::LearningAdapter;
+use crate::cognition::pattern_mining::MiningParams;
+use crate::cognition::self_modification::ProposalId;
+use crate::planning::executor::ActionExecutor;
+use crate::runtime::admin::Maintenance;
+use crate::runtime::Runtime;
+use crate::time::{AstraTime, SharedClock};
 
 /// How often Astra reflects on her own decisions and strategies.
 const REFLECTION_INTERVAL: Duration = Duration::from_secs(120);
 
+/// How often the loop checks for reflection or consolidation an operator
+/// asked for.
+const MAINTENANCE_POLL: Duration = Duration::from_secs(1);
+
 /// Parameters controlling how aggressively heuristics are updated.
 #[derive(Debug, Clone)]
 pub struct ReflectionConfig {
@@ -56,7 +80,9 @@ impl Default for ReflectionConfig {
 #[derive(Debug, Clone)]
 pub struct DecisionEpisode {
     pub goal_id: String,
-    pub strategy_used: String, // e.g., "HTN", "GOAP", "Reactive"
+    pub strategy_used: String, // e.g., "HTN", "GOAP", "Reactive", "BT"
+    /// Concept the goal was grouped under, if any
+    pub category: Option<String>,
     pub success: bool,
     pub total_cost: f32,
     pub duration_ms: u64,
@@ -66,20 +92,139 @@ pub struct DecisionEpisode {
 #[derive(Debug, Clone)]
 pub struct ReflectionSummary {
     pub strategy_scores: std::collections::HashMap<String, f32>,
+    /// Strategy scores over each category's episodes alone
+    pub category_scores: std::collections::HashMap<String, std::collections::HashMap<String, f32>>,
+}
+
+impl ReflectionSummary {
+    /// Best-scoring strategy for goals of `category`, or overall for goals of
+    /// a category without episodes.
+    pub fn best_strategy(&self, category: Option<&str>) -> Option<&str> {
+        category
+            .and_then(|c| self.category_scores.get(c))
+            .unwrap_or(&self.strategy_scores)
+            .iter()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(strategy, _)| strategy.as_str())
+    }
+}
+
+/// When reflection is next due, on a clock that may run faster than real
+/// time.
+pub struct ReflectionSchedule {
+    clock: SharedClock,
+    interval: Duration,
+    next_due: AstraTime,
+}
+
+impl ReflectionSchedule {
+    /// Due straight away, then every `interval` on `clock`.
+    pub fn new(clock: SharedClock, interval: Duration) -> Self {
+        let next_due = clock.now();
+        ReflectionSchedule { clock, interval, next_due }
+    }
+
+    /// Whether reflection is due; if so, the next one is scheduled an
+    /// interval from now.
+    pub fn due(&mut self) -> bool {
+        let now = self.clock.now();
+        if now < self.next_due {
+            return false;
+        }
+        self.next_due = now + self.interval;
+        true
+    }
+
+    /// Real time to wait before reflection is due.
+    pub fn wait(&self) -> Duration {
+        self.clock.real_time_for(self.next_due.saturating_duration_since(self.clock.now()))
+    }
 }
 
-/// Runs the self-reflection loop indefinitely.
-/// In a full system, this would pull from real memory/logs and update real planners.
-pub async fn run_reflection_loop() {
+/// What one reflection pass set in motion.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReflectionOutcome {
+    /// Heuristic rules proposed to the runtime's self-modification registry
+    pub proposals: Vec<ProposalId>,
+    /// Strategy experiments scheduled on the cognitive loop
+    pub experiments: Vec<ExperimentId>,
+    /// Facts weakened because the world contradicted what they predicted
+    pub weakened: usize,
+}
+
+/// Runs the self-reflection loop indefinitely over `cognitive_loop`,
+/// reflecting every `REFLECTION_INTERVAL` on `runtime`'s clock, and as soon
+/// as an operator asks for reflection or consolidation.
+pub async fn run_reflection_loop<E, W, L>(cognitive_loop: Arc<Mutex<CognitiveLoop<E, W, L>>>, runtime: Arc<Mutex<Runtime>>)
+where
+    E: ActionExecutor,
+    W: WorldStateProvider,
+    L: LearningAdapter,
+{
     let config = ReflectionConfig::default();
+    let clock: SharedClock = runtime.lock().await.clock().clone();
+    let mut schedule = ReflectionSchedule::new(clock, REFLECTION_INTERVAL);
 
     loop {
-        info!("[Reflection Loop] Reviewing recent decisions and strategies...");
-        if let Err(e) = run_single_reflection_cycle(&config).await {
-            warn!("[Reflection Loop] Error during reflection cycle: {}", e);
+        let (reflect_now, consolidate) = {
+            let mut runtime = runtime.lock().await;
+            (runtime.take_requested(Maintenance::Reflect), runtime.take_requested(Maintenance::Consolidate))
+        };
+        if schedule.due() || reflect_now {
+            info!("[Reflection Loop] Reviewing recent decisions and strategies...");
+            let mut cognitive_loop = cognitive_loop.lock().await;
+            if let Err(e) = reflect(&mut cognitive_loop, &runtime, &config).await {
+                warn!("[Reflection Loop] Error during reflection cycle: {}", e);
+            }
+        }
+        if consolidate {
+            let state = Arc::clone(&cognitive_loop.lock().await.state);
+            if let Err(e) = run_consolidation_cycle(&mut *state.lock().await) {
+                warn!("[Reflection Loop] Error during consolidation cycle: {}", e);
+            }
         }
-        sleep(REFLECTION_INTERVAL).await;
+        sleep(schedule.wait().min(MAINTENANCE_POLL)).await;
+    }
+}
+
+/// One reflection pass: writes `cognitive_loop`'s finished episodes to
+/// `runtime`'s narrative memory, reviews strategies, then proposes a heuristic rule
+/// for each situation in which `cognitive_loop`'s episodes show a strategy
+/// failing, to `runtime`'s self-modification registry. Rules the approval
+/// policy adopts on its own take effect straight away; the rest wait for
+/// sign-off. Goal categories whose leading strategies are too close to call
+/// get an experiment comparing them, and the beliefs behind predictions the
+/// world strongly contradicted are weakened in the runtime's knowledge base.
+pub async fn reflect<E, W, L>(
+    cognitive_loop: &mut CognitiveLoop<E, W, L>,
+    runtime: &Mutex<Runtime>,
+    config: &ReflectionConfig,
+) -> anyhow::Result<ReflectionOutcome>
+where
+    E: ActionExecutor,
+    W: WorldStateProvider,
+    L: LearningAdapter,
+{
+    run_single_reflection_cycle(config).await?;
+
+    let (proposals, weakened) = {
+        let mut runtime = runtime.lock().await;
+        runtime.record_loop_episodes(cognitive_loop);
+        let proposals =
+            cognitive_loop.propose_heuristic_rules(&mut runtime.self_modifications, &MiningParams::default()).await;
+        (proposals, runtime.reconcile_prediction_errors(cognitive_loop).weakened.len())
+    };
+    if weakened > 0 {
+        info!("[Reflection Loop] Weakened {} belief(s) the world contradicted.", weakened);
     }
+    if !proposals.is_empty() {
+        info!("[Reflection Loop] Proposed {} heuristic rule(s).", proposals.len());
+    }
+    let experiments = cognitive_loop.schedule_experiments(&ExperimentParams::default()).await;
+    if !experiments.is_empty() {
+        info!("[Reflection Loop] Scheduled {} strategy experiment(s).", experiments.len());
+    }
+    Ok(ReflectionOutcome { proposals, experiments, weakened })
 }
 
 /// Runs a single reflection cycle: gather episodes, analyze, and adjust heuristics.
@@ -117,6 +262,7 @@ async fn fetch_recent_decision_episodes() -> anyhow::Result<Vec<DecisionEpisode>
         DecisionEpisode {
             goal_id: "light_on".into(),
             strategy_used: "GOAP".into(),
+            category: Some("device".into()),
             success: true,
             total_cost: 3.0,
             duration_ms: 120,
@@ -124,6 +270,7 @@ async fn fetch_recent_decision_episodes() -> anyhow::Result<Vec<DecisionEpisode>
         DecisionEpisode {
             goal_id: "light_on".into(),
             strategy_used: "Reactive".into(),
+            category: Some("device".into()),
             success: false,
             total_cost: 1.0,
             duration_ms: 30,
@@ -132,13 +279,35 @@ async fn fetch_recent_decision_episodes() -> anyhow::Result<Vec<DecisionEpisode>
 }
 
 /// Analyzes decision episodes and assigns scores to strategies based on success,
-/// efficiency, and cost. Higher score means better performance.
+/// efficiency, and cost, over all episodes and within each goal category.
+/// Higher score means better performance.
 fn analyze_episodes(
     episodes: &[DecisionEpisode],
     config: &ReflectionConfig,
 ) -> ReflectionSummary {
     use std::collections::HashMap;
 
+    let mut by_category: HashMap<String, Vec<DecisionEpisode>> = HashMap::new();
+    for ep in episodes {
+        if let Some(category) = &ep.category {
+            by_category.entry(category.clone()).or_default().push(ep.clone());
+        }
+    }
+    let category_scores = by_category
+        .into_iter()
+        .map(|(category, eps)| (category, score_strategies(&eps, config)))
+        .collect();
+
+    ReflectionSummary { strategy_scores: score_strategies(episodes, config), category_scores }
+}
+
+/// Recency-weighted average score of each strategy over `episodes`.
+fn score_strategies(
+    episodes: &[DecisionEpisode],
+    config: &ReflectionConfig,
+) -> std::collections::HashMap<String, f32> {
+    use std::collections::HashMap;
+
     let mut scores: HashMap<String, (f32, f32)> = HashMap::new(); // (score_sum, weight_sum)
 
     for (i, ep) in episodes.iter().enumerate() {
@@ -156,12 +325,10 @@ fn analyze_episodes(
         entry.1 += recency_weight;
     }
 
-    let strategy_scores = scores
+    scores
         .into_iter()
         .map(|(k, (sum, w))| (k, if w > 0.0 { sum / w } else { 0.0 }))
-        .collect();
-
-    ReflectionSummary { strategy_scores }
+        .collect()
 }
 
 /// Applies heuristic updates to the planning subsystem based on reflection.
@@ -181,6 +348,11 @@ async fn apply_planning_heuristic_updates(
             strategy, score, config.meta_learning_rate
         );
     }
+    for category in summary.category_scores.keys() {
+        if let Some(best) = summary.best_strategy(Some(category)) {
+            info!("[Reflection Loop] Best strategy for {} goals: '{}'.", category, best);
+        }
+    }
 
     // Example placeholder:
     // if summary.strategy_scores["GOAP"] > summary.strategy_scores["Reactive"] {
@@ -203,3 +375,24 @@ async fn apply_meta_learning_updates(
     //  • Store meta-experiences in Narrative Memory for long-term reflection
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::MockClock;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_reflection_is_due_each_interval_of_the_injected_clock() {
+        let clock = MockClock::new();
+        let mut schedule = ReflectionSchedule::new(Arc::new(clock.clone()), REFLECTION_INTERVAL);
+        assert!(schedule.due());
+        assert!(!schedule.due());
+
+        clock.advance(REFLECTION_INTERVAL / 2);
+        assert!(!schedule.due());
+        assert!(schedule.wait() <= Duration::from_millis(1));
+        clock.advance(REFLECTION_INTERVAL / 2);
+        assert!(schedule.due());
+    }
+}
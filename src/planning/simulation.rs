@@ -0,0 +1,74 @@
+// ============================================================================
+//                      ASTRA AGI • SIMULATED ENVIRONMENT
+//        A World of Facts That Plans Act On Without Touching Anything
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Stands in for the world when plans should run without effect on
+//       anything real: scripted scenarios and the training curriculum both
+//       execute plans against it. Each action sets its effects on a shared
+//       set of facts, unless it was scripted to fail, and the facts are
+//       observed back as the world state.
+//
+//   Core Functions:
+//       • Hold a world state that clones share
+//       • Apply action effects as an action executor
+//       • Fail chosen actions every time they are run
+//
+//   File:        /src/planning/simulation.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use super::executor::ActionExecutor;
+use super::planner::{Action, WorldState};
+
+/// A world that plans act on: each action sets its effects, unless it was
+/// scripted to fail. Clones share the same world.
+#[derive(Debug, Clone, Default)]
+pub struct SimulatedEnvironment {
+    world: Arc<Mutex<WorldState>>,
+    failing: Arc<Mutex<HashSet<String>>>,
+}
+
+impl SimulatedEnvironment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn world(&self) -> WorldState {
+        self.world.lock().map(|w| w.clone()).unwrap_or_default()
+    }
+
+    /// Sets facts, returning the whole world afterwards.
+    pub fn set(&self, facts: &WorldState) -> WorldState {
+        let mut world = self.world.lock().unwrap_or_else(|e| e.into_inner());
+        world.extend(facts.iter().map(|(k, v)| (k.clone(), *v)));
+        world.clone()
+    }
+
+    pub fn fail_action(&self, action_id: &str) {
+        self.failing.lock().unwrap_or_else(|e| e.into_inner()).insert(action_id.to_string());
+    }
+}
+
+impl ActionExecutor for SimulatedEnvironment {
+    fn execute_action(&mut self, action: &Action) -> anyhow::Result<bool> {
+        if self.failing.lock().unwrap_or_else(|e| e.into_inner()).contains(&action.id) {
+            return Ok(false);
+        }
+        self.set(&action.effects);
+        Ok(true)
+    }
+
+    fn observe_world(&self) -> Option<WorldState> {
+        Some(self.world())
+    }
+}
@@ -0,0 +1,330 @@
+// ============================================================================
+//                        ASTRA AGI • SKILL LEARNING
+//        Compiling Action Sequences That Keep Working into Macro Actions
+// ----------------------------------------------------------------------------
+//   Architectural Role:
+//       Turns experience into shortcuts for the planner. Every plan that
+//       succeeds is broken into its contiguous action subsequences, and a
+//       subsequence that has succeeded often enough is compiled into a named
+//       skill: a single macro action whose preconditions and effects are
+//       those of the whole sequence. Skills are offered to the planner next
+//       to primitive actions, so a search can take several steps at once,
+//       and are expanded back into their steps before execution so that
+//       monitoring, autonomy checks, and telemetry still see each primitive.
+//       How often each skill is used and how often it works is tracked, and
+//       skills that prove unreliable can be pruned.
+//
+//   Core Functions:
+//       • Count successful action subsequences and compile frequent ones
+//       • Aggregate a sequence's preconditions, effects, cost, and risk
+//       • Expand skills in a plan back into primitive actions
+//       • Track skill outcomes and durations for learned costs
+//       • Prune skills that fail too often
+//
+//   File:        /src/planning/skills.rs
+//   Author:      Alex Roussinov
+//   Created:     2026-10-16
+//   Updated:     2026-10-16
+//
+//   License:
+//       Dual-licensed under the MIT and Apache 2.0 licenses.
+//       See LICENSE-MIT and LICENSE-APACHE in the repository root for details.
+// ============================================================================
+
+use super::cost_model::ExecutionSample;
+use super::planner::{Action, Plan, WorldState};
+use log::info;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Prefix of skill action ids, which distinguishes them from primitives.
+pub const SKILL_PREFIX: &str = "skill:";
+
+/// Compiles `steps` into one action that needs whatever the sequence needs
+/// from the world before it starts and leaves the world as the whole
+/// sequence does. Returns None for fewer than two steps, or if a step needs
+/// the opposite of what an earlier one needs or brings about.
+pub fn compile_skill(steps: &[Action]) -> Option<Action> {
+    if steps.len() < 2 {
+        return None;
+    }
+    let mut preconditions = WorldState::new();
+    let mut effects = WorldState::new();
+    for step in steps {
+        for (key, value) in &step.preconditions {
+            // Facts an earlier step brings about need not hold beforehand
+            match effects.get(key).or(preconditions.get(key)) {
+                Some(held) if held != value => return None,
+                Some(_) => {}
+                None => {
+                    preconditions.insert(key.clone(), *value);
+                }
+            }
+        }
+        effects.extend(step.effects.iter().map(|(k, v)| (k.clone(), *v)));
+    }
+
+    let ids: Vec<&str> = steps.iter().map(|s| s.id.as_str()).collect();
+    let descriptions: Vec<&str> = steps.iter().map(|s| s.description.as_str()).collect();
+    Some(Action {
+        id: format!("{}{}", SKILL_PREFIX, ids.join("+")),
+        description: descriptions.join(", then "),
+        preconditions,
+        effects,
+        cost: steps.iter().map(|s| s.cost).sum(),
+        risk: 1.0 - steps.iter().map(|s| 1.0 - s.risk.clamp(0.0, 1.0)).product::<f32>(),
+    })
+}
+
+/// How a skill has fared since it was compiled.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SkillStats {
+    /// Times execution reached the skill's first step
+    pub uses: u32,
+    pub successes: u32,
+    /// Time spent on the skill's steps, over all uses
+    pub total_duration: Duration,
+}
+
+impl SkillStats {
+    /// Laplace-smoothed share of uses that got through every step.
+    pub fn success_rate(&self) -> f32 {
+        (self.successes as f32 + 1.0) / (self.uses as f32 + 2.0)
+    }
+}
+
+/// A compiled macro action and the primitive actions it stands for.
+#[derive(Debug, Clone)]
+pub struct Skill {
+    pub action: Action,
+    pub steps: Vec<Action>,
+    pub stats: SkillStats,
+}
+
+/// Where a skill's steps sit in an expanded plan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillUse {
+    pub skill_id: String,
+    /// Index of the first step
+    pub start: usize,
+    /// Index just past the last step
+    pub end: usize,
+}
+
+/// A plan with its skills replaced by their steps.
+#[derive(Debug, Clone)]
+pub struct Expansion {
+    pub plan: Plan,
+    pub uses: Vec<SkillUse>,
+}
+
+/// Learned skills, and counts of the sequences that may become skills.
+#[derive(Debug, Clone)]
+pub struct SkillLibrary {
+    /// Successes a sequence needs before it is compiled
+    pub min_successes: u32,
+    /// Longest sequence considered, in actions
+    pub max_length: usize,
+    /// Successes of each candidate sequence, keyed by action ids
+    sequences: HashMap<Vec<String>, u32>,
+    skills: HashMap<String, Skill>,
+}
+
+impl Default for SkillLibrary {
+    fn default() -> Self {
+        SkillLibrary { min_successes: 3, max_length: 4, sequences: HashMap::new(), skills: HashMap::new() }
+    }
+}
+
+impl SkillLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, skill_id: &str) -> Option<&Skill> {
+        self.skills.get(skill_id)
+    }
+
+    pub fn skills(&self) -> impl Iterator<Item = &Skill> {
+        self.skills.values()
+    }
+
+    /// Skill actions, to offer the planner alongside primitives.
+    pub fn actions(&self) -> Vec<Action> {
+        self.skills.values().map(|s| s.action.clone()).collect()
+    }
+
+    /// Counts every subsequence of a plan that just succeeded, compiling those
+    /// that have now succeeded `min_successes` times. Of sequences that become
+    /// ready together, only the longest are compiled, not their parts.
+    /// Returns the ids of new skills.
+    pub fn observe_success(&mut self, actions: &[Action]) -> Vec<String> {
+        let mut ready: Vec<&[Action]> = Vec::new();
+        for length in 2..=self.max_length.min(actions.len()) {
+            for window in actions.windows(length) {
+                let key: Vec<String> = window.iter().map(|a| a.id.clone()).collect();
+                let count = self.sequences.entry(key).or_insert(0);
+                *count += 1;
+                if *count == self.min_successes {
+                    ready.push(window);
+                }
+            }
+        }
+
+        ready.sort_by_key(|window| std::cmp::Reverse(window.len()));
+        let mut compiled: Vec<&[Action]> = Vec::new();
+        let mut learned = Vec::new();
+        for window in ready {
+            let covered = compiled.iter().any(|longer| {
+                longer.windows(window.len()).any(|part| part.iter().zip(window).all(|(a, b)| a.id == b.id))
+            });
+            if covered {
+                continue;
+            }
+            let Some(action) = compile_skill(window) else { continue };
+            if self.skills.contains_key(&action.id) {
+                continue;
+            }
+            info!("Learned skill {}", action.id);
+            learned.push(action.id.clone());
+            self.skills.insert(
+                action.id.clone(),
+                Skill { action, steps: window.to_vec(), stats: SkillStats::default() },
+            );
+            compiled.push(window);
+        }
+        learned
+    }
+
+    /// Replaces each skill in `plan` with its steps, noting where they went.
+    /// Actions that are not known skills are kept as they are.
+    pub fn expand(&self, plan: &Plan) -> Expansion {
+        let mut actions = Vec::with_capacity(plan.actions.len());
+        let mut uses = Vec::new();
+        for action in &plan.actions {
+            match self.skills.get(&action.id) {
+                Some(skill) => {
+                    let start = actions.len();
+                    actions.extend(skill.steps.iter().cloned());
+                    uses.push(SkillUse { skill_id: action.id.clone(), start, end: actions.len() });
+                }
+                None => actions.push(action.clone()),
+            }
+        }
+        Expansion { plan: Plan { actions, ..plan.clone() }, uses }
+    }
+
+    /// Updates the stats of skills in an expanded plan after executing it,
+    /// given how many of its actions completed and the executor's
+    /// per-attempt telemetry. Returns one sample per skill reached, under
+    /// the skill's id, so cost models learn what skills really cost.
+    pub fn record_execution(
+        &mut self,
+        expansion: &Expansion,
+        completed: usize,
+        telemetry: &[ExecutionSample],
+    ) -> Vec<ExecutionSample> {
+        // Attempts are reported in order, each for the action at `index`
+        let mut durations = vec![Duration::ZERO; expansion.plan.actions.len()];
+        let mut index = 0;
+        for sample in telemetry {
+            let Some(action) = expansion.plan.actions.get(index) else { break };
+            if action.id != sample.action_id {
+                continue;
+            }
+            durations[index] += sample.duration;
+            if sample.success {
+                index += 1;
+            }
+        }
+
+        let mut samples = Vec::new();
+        for skill_use in &expansion.uses {
+            if completed < skill_use.start {
+                continue;
+            }
+            let Some(skill) = self.skills.get_mut(&skill_use.skill_id) else { continue };
+            let success = completed >= skill_use.end;
+            let duration: Duration = durations[skill_use.start..skill_use.end].iter().sum();
+            skill.stats.uses += 1;
+            if success {
+                skill.stats.successes += 1;
+            }
+            skill.stats.total_duration += duration;
+            samples.push(ExecutionSample { action_id: skill_use.skill_id.clone(), duration, success });
+        }
+        samples
+    }
+
+    /// Drops skills used at least `min_uses` times whose success rate is
+    /// below `min_success_rate`, and returns them. Their sequences must
+    /// succeed `min_successes` more times before they are compiled again.
+    pub fn prune(&mut self, min_uses: u32, min_success_rate: f32) -> Vec<Skill> {
+        let unreliable: Vec<String> = self
+            .skills
+            .iter()
+            .filter(|(_, s)| s.stats.uses >= min_uses && s.stats.success_rate() < min_success_rate)
+            .map(|(id, _)| id.clone())
+            .collect();
+        unreliable
+            .into_iter()
+            .filter_map(|id| self.skills.remove(&id))
+            .inspect(|skill| {
+                info!("Pruned unreliable skill {} ({:.2} success rate)", skill.action.id, skill.stats.success_rate());
+                self.sequences.remove(&skill.steps.iter().map(|a| a.id.clone()).collect::<Vec<_>>());
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(id: &str, preconditions: &[(&str, bool)], effects: &[(&str, bool)]) -> Action {
+        Action {
+            id: id.into(),
+            description: id.into(),
+            preconditions: preconditions.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+            effects: effects.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+            cost: 1.0,
+            risk: 0.1,
+        }
+    }
+
+    #[test]
+    fn test_repeated_successes_become_skills_that_expand_and_prune() {
+        let steps = vec![
+            action("open_door", &[("at_door", true)], &[("door_open", true)]),
+            action("walk_in", &[("door_open", true)], &[("inside", true), ("at_door", false)]),
+            action("close_door", &[("inside", true)], &[("door_open", false)]),
+        ];
+        let mut library = SkillLibrary::new();
+        assert!(library.observe_success(&steps).is_empty());
+        assert!(library.observe_success(&steps).is_empty());
+        assert_eq!(library.observe_success(&steps), vec!["skill:open_door+walk_in+close_door"]);
+        assert_eq!(library.actions().len(), 1);
+
+        let skill = &library.actions()[0];
+        assert_eq!(skill.preconditions, HashMap::from([("at_door".to_string(), true)]));
+        assert_eq!(skill.effects.get("door_open"), Some(&false));
+        assert_eq!(skill.cost, 3.0);
+        assert!((skill.risk - (1.0 - 0.9f32.powi(3))).abs() < 1e-6);
+        assert!(compile_skill(&[action("a", &[], &[("x", true)]), action("b", &[("x", false)], &[])]).is_none());
+
+        let plan = Plan { goal_id: "inside".into(), actions: vec![skill.clone()], estimated_cost: 3.0 };
+        let expansion = library.expand(&plan);
+        assert_eq!(expansion.plan.actions.len(), 3);
+        assert_eq!(expansion.uses[0], SkillUse { skill_id: skill.id.clone(), start: 0, end: 3 });
+
+        let sample = |id: &str, success| ExecutionSample { action_id: id.into(), duration: Duration::from_secs(1), success };
+        let telemetry = [sample("open_door", true), sample("walk_in", false), sample("walk_in", false)];
+        let samples = library.record_execution(&expansion, 1, &telemetry);
+        assert_eq!(samples, vec![ExecutionSample { action_id: skill.id.clone(), duration: Duration::from_secs(3), success: false }]);
+        library.record_execution(&expansion, 1, &telemetry);
+
+        assert!(library.prune(3, 0.5).is_empty());
+        assert_eq!(library.prune(2, 0.5).len(), 1);
+        assert!(library.get(&skill.id).is_none());
+    }
+}
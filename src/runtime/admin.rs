@@ -0,0 +1,294 @@
+// =============================================================================
+//  Astra AGI - Admin Console
+//  File: admin.rs
+//
+//  Description:
+//  Operator commands for managing a long-running instance without restarting
+//  it: pausing and resuming ticks, changing the autonomy policy, confirming
+//  or denying actions the policy holds for a person to approve, forcing a
+//  reflection or consolidation cycle, rebuilding indexes, dumping a
+//  diagnostic bundle, backing up persistent state, syncing knowledge
+//  exports, and reporting on a window of activity for transparency.
+//  Commands act on what the runtime owns straight away.
+//  The cognitive loop's heuristics and the host's vector stores live outside
+//  it, so those parts of a cycle are queued as maintenance requests that the
+//  host takes on its next iteration; the reflection loop takes the ones for
+//  the cognitive loop it runs. Every command is recorded in narrative
+//  memory with the operator who issued it.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use serde::{Deserialize, Serialize};
+
+use crate::cognition::reflection::{reflect_on_commitments, reflect_on_plan_progress};
+use crate::memory::summary_tiers::compress;
+use crate::planning::autonomy::{ActionCategory, AutonomyLevel, AutonomyPolicy};
+use crate::runtime::backup::BackupId;
+use crate::runtime::intent_manager::IntentId;
+use crate::runtime::introspection::SelfReport;
+use crate::runtime::transparency::TransparencyReport;
+use crate::runtime::Runtime;
+use crate::time::AstraTime;
+
+/// How many recent events a diagnostic bundle includes.
+const BUNDLE_EVENTS: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum AdminCommand {
+    /// Stop ticking until resumed; requests are still answered
+    Pause,
+    Resume,
+    /// Replace the whole autonomy policy
+    SetAutonomy { policy: AutonomyPolicy },
+    /// Change the level of one action category
+    SetAutonomyLevel { category: ActionCategory, level: AutonomyLevel },
+    /// Let the action an intent's plan is awaiting confirmation for run
+    ConfirmAction { intent_id: IntentId },
+    /// Refuse that action, stopping the plan
+    DenyAction { intent_id: IntentId },
+    Reflect,
+    Consolidate,
+    RebuildIndexes,
+    DumpDiagnostics,
+    /// Account for what Astra did from `from` up to `to`
+    TransparencyReport { from: AstraTime, to: AstraTime },
+    /// Back up every persistent store; see `Runtime::backup`
+    Backup,
+    /// Send knowledge changes to the graph export and SQLite mirror now
+    /// rather than at the next tick
+    SyncKnowledgeExports,
+}
+
+/// Work the host must do for a command, on state the runtime does not own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Maintenance {
+    /// Reflect on the cognitive loop's recent episodes
+    Reflect,
+    /// Run the cognitive loop's consolidation cycle
+    Consolidate,
+    /// Rebuild vector and other host-owned indexes
+    RebuildIndexes,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum AdminOutcome {
+    Paused,
+    Resumed,
+    AutonomyUpdated { policy: AutonomyPolicy },
+    ActionConfirmed { intent_id: IntentId },
+    ActionDenied { intent_id: IntentId },
+    /// Nothing of the intent's was waiting on a person
+    ConfirmationFailed { intent_id: IntentId, reason: String },
+    /// Adjustments suggested by commitments kept and plans stalled
+    Reflected { planning_bias_adjustment: f32, emotional_stability_adjustment: f32, curiosity_adjustment: f32 },
+    /// Number of new memory summaries
+    Consolidated { summaries: usize },
+    /// Number of intents re-queued
+    IndexesRebuilt { intents: usize },
+    Diagnostics(Box<DiagnosticBundle>),
+    Transparency(Box<TransparencyReport>),
+    /// Files covered, and how many of them the new archive had to store
+    BackedUp { id: BackupId, full: bool, files: usize, stored_files: usize, stored_bytes: u64 },
+    BackupFailed { reason: String },
+    /// Facts sent to each export; `None` where it was already current or
+    /// is not configured
+    KnowledgeExportsSynced { graph_facts: Option<usize>, mirrored_facts: Option<usize>, failures: Vec<String> },
+}
+
+/// Everything an operator needs to look into a misbehaving instance.
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticBundle {
+    pub generated_at: String,
+    pub paused: bool,
+    pub self_report: SelfReport,
+    pub autonomy: AutonomyPolicy,
+    /// As "intent N: goal, phase, done/total"
+    pub plans: Vec<String>,
+    pub pending_maintenance: Vec<Maintenance>,
+    /// Prometheus text, as served at `/metrics`
+    pub metrics: String,
+    /// Most recent first, as "[timestamp] type: description"
+    pub recent_events: Vec<String>,
+}
+
+impl Runtime {
+    /// Carries out an operator command and records who issued it.
+    pub fn admin(&mut self, command: AdminCommand, operator: &str) -> AdminOutcome {
+        let description = format!("{} ran {:?}", operator, command);
+        let outcome = match command {
+            AdminCommand::Pause => {
+                self.paused = true;
+                AdminOutcome::Paused
+            }
+            AdminCommand::Resume => {
+                self.paused = false;
+                AdminOutcome::Resumed
+            }
+            AdminCommand::SetAutonomy { policy } => {
+                self.plan_runner.set_autonomy(policy);
+                AdminOutcome::AutonomyUpdated { policy: self.plan_runner.autonomy.clone() }
+            }
+            AdminCommand::SetAutonomyLevel { category, level } => {
+                let mut policy = self.plan_runner.autonomy.clone();
+                policy.levels.insert(category, level);
+                self.plan_runner.set_autonomy(policy);
+                AdminOutcome::AutonomyUpdated { policy: self.plan_runner.autonomy.clone() }
+            }
+            AdminCommand::ConfirmAction { intent_id } => match self.confirm_plan_action(intent_id) {
+                Ok(()) => AdminOutcome::ActionConfirmed { intent_id },
+                Err(error) => AdminOutcome::ConfirmationFailed { intent_id, reason: error.to_string() },
+            },
+            AdminCommand::DenyAction { intent_id } => match self.deny_plan_action(intent_id) {
+                Ok(()) => AdminOutcome::ActionDenied { intent_id },
+                Err(error) => AdminOutcome::ConfirmationFailed { intent_id, reason: error.to_string() },
+            },
+            AdminCommand::Reflect => {
+                let commitments = reflect_on_commitments(&self.commitments);
+                let plans = reflect_on_plan_progress(&self.plan_progress());
+                self.request_maintenance(Maintenance::Reflect);
+                AdminOutcome::Reflected {
+                    planning_bias_adjustment: commitments.planning_bias_adjustment + plans.planning_bias_adjustment,
+                    emotional_stability_adjustment: commitments.emotional_stability_adjustment
+                        + plans.emotional_stability_adjustment,
+                    curiosity_adjustment: commitments.curiosity_adjustment + plans.curiosity_adjustment,
+                }
+            }
+            AdminCommand::Consolidate => {
+                let summaries = compress(&mut self.narrative_memory, self.memory_archive.as_mut(), &self.memory_tiers, self.clock.now());
+                self.request_maintenance(Maintenance::Consolidate);
+                AdminOutcome::Consolidated { summaries: summaries.len() }
+            }
+            AdminCommand::RebuildIndexes => {
+                self.intent_manager.rebuild_priority_queue();
+                self.request_maintenance(Maintenance::RebuildIndexes);
+                AdminOutcome::IndexesRebuilt { intents: self.intent_manager.all_intents().len() }
+            }
+            AdminCommand::DumpDiagnostics => AdminOutcome::Diagnostics(Box::new(self.diagnostic_bundle())),
+            AdminCommand::TransparencyReport { from, to } => {
+                AdminOutcome::Transparency(Box::new(self.transparency_report(from, to)))
+            }
+            AdminCommand::Backup => match self.backup() {
+                Ok(manifest) => AdminOutcome::BackedUp {
+                    id: manifest.id,
+                    full: manifest.full,
+                    files: manifest.files.len(),
+                    stored_files: manifest.stored_files(),
+                    stored_bytes: manifest.stored_bytes(),
+                },
+                Err(error) => AdminOutcome::BackupFailed { reason: error.to_string() },
+            },
+            AdminCommand::SyncKnowledgeExports => {
+                let sync = self.sync_knowledge_exports();
+                let graph_facts = sync.graph.as_ref().and_then(|r| r.as_ref().ok()).map(|r| r.facts);
+                #[cfg(feature = "sqlite")]
+                let mirrored_facts = sync.mirror.as_ref().and_then(|r| r.as_ref().ok()).map(|r| r.facts);
+                #[cfg(not(feature = "sqlite"))]
+                let mirrored_facts = None;
+                AdminOutcome::KnowledgeExportsSynced { graph_facts, mirrored_facts, failures: sync.failures() }
+            }
+        };
+        self.narrative_memory.add_event("admin", description, None);
+        outcome
+    }
+
+    /// Whether an operator has paused ticking.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Maintenance operators asked for that the host has yet to do.
+    pub fn take_maintenance(&mut self) -> Vec<Maintenance> {
+        std::mem::take(&mut self.pending_maintenance)
+    }
+
+    /// Whether an operator asked for `maintenance`, which the caller is
+    /// then to do; other requests stay queued for whoever does them.
+    pub fn take_requested(&mut self, maintenance: Maintenance) -> bool {
+        let requested = self.pending_maintenance.contains(&maintenance);
+        self.pending_maintenance.retain(|m| *m != maintenance);
+        requested
+    }
+
+    fn request_maintenance(&mut self, maintenance: Maintenance) {
+        if !self.pending_maintenance.contains(&maintenance) {
+            self.pending_maintenance.push(maintenance);
+        }
+    }
+
+    pub fn diagnostic_bundle(&self) -> DiagnosticBundle {
+        DiagnosticBundle {
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            paused: self.paused,
+            self_report: self.introspect(),
+            autonomy: self.plan_runner.autonomy.clone(),
+            plans: self
+                .plan_progress()
+                .iter()
+                .map(|p| {
+                    format!(
+                        "intent {}: {}, {}, {}/{}",
+                        p.intent_id,
+                        p.goal_id,
+                        p.phase.as_str(),
+                        p.completed_actions,
+                        p.total_actions
+                    )
+                })
+                .collect(),
+            pending_maintenance: self.pending_maintenance.clone(),
+            metrics: self.render_metrics(),
+            recent_events: self
+                .narrative_memory
+                .recent_events(BUNDLE_EVENTS)
+                .iter()
+                .map(|e| format!("[{}] {}: {}", e.timestamp, e.event_type, e.description))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_admin_commands_pause_retune_and_queue_maintenance() {
+        let mut runtime = Runtime::new();
+        runtime.admin(AdminCommand::Pause, "ops");
+        let events = runtime.narrative_memory.events.len();
+        runtime.tick();
+        assert!(runtime.is_paused());
+        assert_eq!(runtime.narrative_memory.events.len(), events);
+        runtime.admin(AdminCommand::Resume, "ops");
+        assert!(!runtime.is_paused());
+
+        let command: AdminCommand =
+            serde_json::from_str(r#"{"command": "set_autonomy_level", "category": "message_send", "level": "auto"}"#).unwrap();
+        runtime.admin(command, "ops");
+        assert_eq!(runtime.plan_runner.autonomy.level_for(ActionCategory::MessageSend), AutonomyLevel::Auto);
+        let command: AdminCommand = serde_json::from_str(r#"{"command": "deny_action", "intent_id": 7}"#).unwrap();
+        assert!(matches!(runtime.admin(command, "ops"), AdminOutcome::ConfirmationFailed { intent_id: 7, .. }));
+
+        runtime.admin(AdminCommand::Reflect, "ops");
+        runtime.admin(AdminCommand::RebuildIndexes, "ops");
+        runtime.admin(AdminCommand::Reflect, "ops");
+        assert!(runtime.take_requested(Maintenance::Reflect));
+        assert!(!runtime.take_requested(Maintenance::Reflect));
+        assert_eq!(runtime.take_maintenance(), vec![Maintenance::RebuildIndexes]);
+        assert!(runtime.take_maintenance().is_empty());
+
+        let AdminOutcome::Diagnostics(bundle) = runtime.admin(AdminCommand::DumpDiagnostics, "ops") else {
+            panic!("expected a diagnostic bundle");
+        };
+        assert!(bundle.recent_events[0].contains("ops ran Reflect"));
+        assert!(serde_json::to_string(&bundle).unwrap().contains("astra_tick_duration_seconds"));
+    }
+}
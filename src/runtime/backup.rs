@@ -0,0 +1,662 @@
+// =============================================================================
+//  Astra AGI - Backup and Restore
+//  File: backup.rs
+//
+//  Description:
+//  Coordinated backups of every store Astra persists to: knowledge
+//  snapshots and mirrors, memory archives, the write-ahead log, learned
+//  models, and configuration. The host names each store's file or
+//  directory; a backup reads them all into a single archive in the backup
+//  directory. Backups are incremental: a file unchanged since the previous
+//  backup is not stored again but refers to the archive that holds it, and
+//  every `full_every`th backup stores everything, so older archives can be
+//  pruned. Taking a backup through the runtime first commits the
+//  write-ahead log and adds it as a store, and runs between ticks, so the
+//  archive holds the state after a whole tick. Files are hashed and
+//  compressed as they are read, and their blobs streamed to disk, so a
+//  backup never holds a whole store in memory. Symbolic links inside a
+//  store are skipped rather than followed out of it. A data directory laid
+//  out the standard way gives a configuration covering knowledge
+//  snapshots, memory archives, learned models, and configuration.
+//
+//  Restoring reads a backup and the archives it refers to, checks every
+//  file against its SHA-256 digest, and writes nothing unless all of them
+//  match. Blob ranges are checked against the archive's size and decoded no
+//  further than the size the manifest records, so a forged manifest cannot
+//  make a restore allocate more than the archive holds; each store's backed-up contents are then written beside it and
+//  swapped into its place, so a restore cut short leaves it as it was.
+//
+//  Layout:
+//      magic · manifest length (u64 LE) · manifest JSON · blob*
+//      blob = a zstd frame holding one file's contents
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::runtime::error::{BackupError, RuntimeError};
+use crate::runtime::knowledge_packs::sha256_hex;
+use crate::runtime::Runtime;
+use crate::time::AstraTime;
+
+/// Opens every archive; the last byte is the format version.
+const MAGIC: &[u8; 8] = b"ASTRABK1";
+
+/// zstd level files are compressed at.
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// Extension of archive files in the backup directory.
+const EXTENSION: &str = "astrabk";
+
+/// Name the write-ahead log is backed up under.
+pub const WAL_SOURCE: &str = "wal";
+
+pub type BackupId = u64;
+
+/// A store to back up: a file, or a directory taken with everything in it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupSource {
+    /// Such as "knowledge", "memory", "models", or "config"
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// Where backups go and what they cover.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupConfig {
+    pub dir: PathBuf,
+    pub sources: Vec<BackupSource>,
+    /// Every this many backups store every file again; 0 counts as 1
+    pub full_every: u32,
+}
+
+impl BackupConfig {
+    /// Backups into `dir`, a full one every seventh.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        BackupConfig { dir: dir.into(), sources: Vec::new(), full_every: 7 }
+    }
+
+    pub fn with_source(mut self, name: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        self.sources.push(BackupSource { name: name.into(), path: path.into() });
+        self
+    }
+
+    pub fn with_full_every(mut self, full_every: u32) -> Self {
+        self.full_every = full_every;
+        self
+    }
+
+    /// Backups into `backups` under `data_dir`, covering the stores laid
+    /// out there: knowledge snapshots and mirrors under `knowledge`, memory
+    /// archives under `memory`, learned models under `models`, and
+    /// configuration under `config`.
+    pub fn for_data_dir(data_dir: &Path) -> Self {
+        ["knowledge", "memory", "models", "config"]
+            .into_iter()
+            .fold(BackupConfig::new(data_dir.join("backups")), |config, store| {
+                config.with_source(store, data_dir.join(store))
+            })
+    }
+}
+
+/// One backed-up file and where its contents are stored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupEntry {
+    /// Name of the store
+    pub source: String,
+    /// Within the store's directory, `/`-separated; empty when the store is a file
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+    /// Backup whose archive holds the contents
+    pub stored_in: BackupId,
+    /// Of the compressed contents, from the end of that archive's manifest
+    pub offset: u64,
+    pub length: u64,
+}
+
+/// What a backup holds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub id: BackupId,
+    pub created_at: AstraTime,
+    /// Whether every file is stored in this backup's own archive
+    pub full: bool,
+    pub sources: Vec<BackupSource>,
+    pub files: Vec<BackupEntry>,
+}
+
+impl BackupManifest {
+    /// Backups whose archives this one needs to be restored, itself included.
+    pub fn depends_on(&self) -> BTreeSet<BackupId> {
+        self.files.iter().map(|entry| entry.stored_in).collect()
+    }
+
+    /// Files stored in this backup's own archive.
+    pub fn stored_files(&self) -> usize {
+        self.files.iter().filter(|entry| entry.stored_in == self.id).count()
+    }
+
+    /// Compressed bytes stored in this backup's own archive.
+    pub fn stored_bytes(&self) -> u64 {
+        self.files.iter().filter(|entry| entry.stored_in == self.id).map(|entry| entry.length).sum()
+    }
+}
+
+/// What a restore wrote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestoreReport {
+    pub id: BackupId,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// Ids of the backups in `dir`, oldest first.
+pub fn list_backups(dir: &Path) -> Result<Vec<BackupId>, BackupError> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut ids: Vec<BackupId> = fs::read_dir(dir)
+        .map_err(|source| io_error(dir, source))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            (path.extension()? == EXTENSION).then_some(())?;
+            path.file_stem()?.to_str()?.parse().ok()
+        })
+        .collect();
+    ids.sort_unstable();
+    Ok(ids)
+}
+
+/// Backs up every store in `config` into a new archive, storing only the
+/// files that changed since the previous backup unless a full one is due.
+/// Stores whose file or directory does not exist yet are left out.
+pub fn create_backup(config: &BackupConfig, created_at: AstraTime) -> Result<BackupManifest, BackupError> {
+    fs::create_dir_all(&config.dir).map_err(|source| io_error(&config.dir, source))?;
+    let previous = match list_backups(&config.dir)?.last() {
+        Some(&id) => Some(read_manifest(&config.dir, id)?.0),
+        None => None,
+    };
+    let id = previous.as_ref().map_or(1, |manifest| manifest.id + 1);
+    let full = (id - 1) % u64::from(config.full_every.max(1)) == 0;
+    let unchanged: HashMap<(&str, &str, &str), &BackupEntry> = match (&previous, full) {
+        (Some(manifest), false) => manifest
+            .files
+            .iter()
+            .map(|entry| ((entry.source.as_str(), entry.path.as_str(), entry.sha256.as_str()), entry))
+            .collect(),
+        _ => HashMap::new(),
+    };
+
+    // Blobs are streamed aside while the manifest is built, then copied in
+    // behind it; both are renamed away or removed, so a backup cut short
+    // leaves no archive
+    let path = archive_path(&config.dir, id);
+    let partial = path.with_extension("partial");
+    let blobs_path = path.with_extension("blobs");
+    let result = write_archive(config, &path, &partial, &blobs_path, &unchanged, (id, created_at, full));
+    let _ = fs::remove_file(&blobs_path);
+    if result.is_err() {
+        let _ = fs::remove_file(&partial);
+    }
+    result
+}
+
+/// Writes backup `id` of the stores in `config` to `path`; see `create_backup`.
+fn write_archive(
+    config: &BackupConfig,
+    path: &Path,
+    partial: &Path,
+    blobs_path: &Path,
+    unchanged: &HashMap<(&str, &str, &str), &BackupEntry>,
+    (id, created_at, full): (BackupId, AstraTime, bool),
+) -> Result<BackupManifest, BackupError> {
+    let mut manifest = BackupManifest { id, created_at, full, sources: config.sources.clone(), files: Vec::new() };
+    let mut blobs = File::options()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(blobs_path)
+        .map_err(|source| io_error(blobs_path, source))?;
+    let mut stored = 0u64;
+    for source in &config.sources {
+        for (path, file) in source_files(&source.path)? {
+            let (sha256, _) = digest_file(&file, std::io::sink())?;
+            if let Some(&entry) = unchanged.get(&(source.name.as_str(), path.as_str(), sha256.as_str())) {
+                manifest.files.push(entry.clone());
+                continue;
+            }
+            // Hashed again as it is compressed, in case it changed meanwhile
+            let mut encoder =
+                zstd::stream::write::Encoder::new(&mut blobs, COMPRESSION_LEVEL).map_err(|e| io_error(blobs_path, e))?;
+            let (sha256, size) = digest_file(&file, &mut encoder)?;
+            encoder.finish().map_err(|error| io_error(blobs_path, error))?;
+            let end = blobs.stream_position().map_err(|error| io_error(blobs_path, error))?;
+            manifest.files.push(BackupEntry {
+                source: source.name.clone(),
+                path,
+                size,
+                sha256,
+                stored_in: id,
+                offset: stored,
+                length: end - stored,
+            });
+            stored = end;
+        }
+    }
+
+    let encoded =
+        serde_json::to_vec(&manifest).map_err(|source| BackupError::Manifest { path: path.to_path_buf(), source })?;
+    let mut write = || -> std::io::Result<()> {
+        let mut out = File::create(partial)?;
+        out.write_all(MAGIC)?;
+        out.write_all(&(encoded.len() as u64).to_le_bytes())?;
+        out.write_all(&encoded)?;
+        blobs.seek(SeekFrom::Start(0))?;
+        std::io::copy(&mut blobs, &mut out)?;
+        out.sync_all()?;
+        fs::rename(partial, path)
+    };
+    write().map_err(|source| io_error(path, source))?;
+    Ok(manifest)
+}
+
+/// Streams `file` into `out`, returning its SHA-256 digest and size.
+fn digest_file(file: &Path, mut out: impl Write) -> Result<(String, u64), BackupError> {
+    let mut input = File::open(file).map_err(|error| io_error(file, error))?;
+    let mut hasher = Sha256::new();
+    let mut size = 0u64;
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = input.read(&mut buffer).map_err(|error| io_error(file, error))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        out.write_all(&buffer[..read]).map_err(|error| io_error(file, error))?;
+        size += read as u64;
+    }
+    Ok((hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect(), size))
+}
+
+/// Reads every file backup `id` holds, from its archive and those it refers
+/// to, and checks each against its digest. Returns the manifest.
+pub fn verify_backup(dir: &Path, id: BackupId) -> Result<BackupManifest, BackupError> {
+    Ok(read_backup(dir, id)?.0)
+}
+
+/// Replaces each store with its contents in backup `id`, or the latest
+/// backup if `None`. Stores are restored where they were backed up from,
+/// or into a directory per store under `into` if given. Nothing is written
+/// unless every file passes verification.
+pub fn restore_backup(dir: &Path, id: Option<BackupId>, into: Option<&Path>) -> Result<RestoreReport, BackupError> {
+    let id = match id {
+        Some(id) => id,
+        None => *list_backups(dir)?.last().ok_or_else(|| BackupError::NoBackups(dir.to_path_buf()))?,
+    };
+    let (manifest, contents) = read_backup(dir, id)?;
+
+    // Every store is written aside and then swapped into place, so a restore
+    // cut short leaves the stores as they were
+    let mut report = RestoreReport { id, files: 0, bytes: 0 };
+    let mut staged: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for source in &manifest.sources {
+        let root = into.map_or_else(|| source.path.clone(), |into| into.join(&source.name));
+        let files: Vec<(&BackupEntry, &Vec<u8>)> =
+            manifest.files.iter().zip(&contents).filter(|(entry, _)| entry.source == source.name).collect();
+        if files.is_empty() {
+            continue;
+        }
+        let staging = match beside(&root, "restoring").and_then(|staging| stage(&staging, &files).map(|_| staging)) {
+            Ok(staging) => staging,
+            Err(error) => {
+                for (staging, _) in &staged {
+                    let _ = remove_path(staging);
+                }
+                return Err(error);
+            }
+        };
+        staged.push((staging, root));
+        report.files += files.len();
+        report.bytes += files.iter().map(|(entry, _)| entry.size).sum::<u64>();
+    }
+    for (staging, root) in &staged {
+        swap_in(staging, root)?;
+    }
+    Ok(report)
+}
+
+/// Writes a store's files to `staging`, in place of anything there.
+fn stage(staging: &Path, files: &[(&BackupEntry, &Vec<u8>)]) -> Result<(), BackupError> {
+    remove_path(staging).map_err(|error| io_error(staging, error))?;
+    for (entry, bytes) in files {
+        let file = if entry.path.is_empty() { staging.to_path_buf() } else { staging.join(&entry.path) };
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent).map_err(|error| io_error(parent, error))?;
+        }
+        fs::write(&file, bytes).map_err(|error| io_error(&file, error))?;
+    }
+    Ok(())
+}
+
+/// Replaces `root` with `staging`, putting `root` back if that fails.
+fn swap_in(staging: &Path, root: &Path) -> Result<(), BackupError> {
+    let previous = beside(root, "previous")?;
+    remove_path(&previous).map_err(|error| io_error(&previous, error))?;
+    let existed = root.exists();
+    if existed {
+        fs::rename(root, &previous).map_err(|error| io_error(root, error))?;
+    }
+    if let Err(error) = fs::rename(staging, root) {
+        if existed {
+            let _ = fs::rename(&previous, root);
+        }
+        return Err(io_error(root, error));
+    }
+    remove_path(&previous).map_err(|error| io_error(&previous, error))
+}
+
+/// A hidden path next to `path`, tagged with `tag`.
+fn beside(path: &Path, tag: &str) -> Result<PathBuf, BackupError> {
+    let name = path.file_name().ok_or_else(|| BackupError::UnsafePath(path.display().to_string()))?;
+    Ok(path.with_file_name(format!(".{}.{}", name.to_string_lossy(), tag)))
+}
+
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else if path.exists() {
+        fs::remove_file(path)
+    } else {
+        Ok(())
+    }
+}
+
+/// Whether `path` only names entries below the directory it is joined to.
+fn is_relative(path: &str) -> bool {
+    Path::new(path).components().all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// A backup's manifest and the verified contents of its files, in order.
+fn read_backup(dir: &Path, id: BackupId) -> Result<(BackupManifest, Vec<Vec<u8>>), BackupError> {
+    let (manifest, _) = read_manifest(dir, id)?;
+    if let Some(source) = manifest.sources.iter().find(|source| source.name.is_empty() || !is_relative(&source.name)) {
+        return Err(BackupError::UnsafeStore(source.name.clone()));
+    }
+    let mut archives = HashMap::new();
+    for stored_in in manifest.depends_on() {
+        let (_, blobs_start) = read_manifest(dir, stored_in)?;
+        let path = archive_path(dir, stored_in);
+        let file = File::open(&path).map_err(|source| io_error(&path, source))?;
+        let size = file.metadata().map_err(|source| io_error(&path, source))?.len();
+        archives.insert(stored_in, (file, blobs_start, size, path));
+    }
+
+    let mut contents = Vec::with_capacity(manifest.files.len());
+    for entry in &manifest.files {
+        if !is_relative(&entry.path) {
+            return Err(BackupError::UnsafePath(entry.path.clone()));
+        }
+        let (file, blobs_start, size, path) =
+            archives.get_mut(&entry.stored_in).expect("every archive depended on is open");
+        let corrupt =
+            || BackupError::Corrupt { id: entry.stored_in, store: entry.source.clone(), path: entry.path.clone() };
+        // The manifest is not trusted until the contents match it
+        let start = blobs_start
+            .checked_add(entry.offset)
+            .filter(|start| start.checked_add(entry.length).is_some_and(|end| end <= *size))
+            .ok_or_else(corrupt)?;
+        file.seek(SeekFrom::Start(start)).map_err(|source| io_error(path, source))?;
+        let mut bytes = Vec::new();
+        zstd::stream::read::Decoder::new(Read::by_ref(file).take(entry.length))
+            .and_then(|decoder| decoder.take(entry.size.saturating_add(1)).read_to_end(&mut bytes))
+            .map_err(|_| corrupt())?;
+        if bytes.len() as u64 != entry.size || sha256_hex(&bytes) != entry.sha256 {
+            return Err(corrupt());
+        }
+        contents.push(bytes);
+    }
+    Ok((manifest, contents))
+}
+
+/// A backup's manifest, and where its archive's blobs start.
+fn read_manifest(dir: &Path, id: BackupId) -> Result<(BackupManifest, u64), BackupError> {
+    let path = archive_path(dir, id);
+    if !path.exists() {
+        return Err(BackupError::NotFound(id));
+    }
+    let mut file = File::open(&path).map_err(|source| io_error(&path, source))?;
+    let mut header = [0; 16];
+    file.read_exact(&mut header).map_err(|_| BackupError::NotABackup(path.clone()))?;
+    if header[..8] != MAGIC[..] {
+        return Err(BackupError::NotABackup(path));
+    }
+    let length = u64::from_le_bytes(header[8..].try_into().expect("eight bytes"));
+    let mut encoded = Vec::new();
+    file.take(length).read_to_end(&mut encoded).map_err(|source| io_error(&path, source))?;
+    let manifest = serde_json::from_slice(&encoded).map_err(|source| BackupError::Manifest { path, source })?;
+    Ok((manifest, 16 + length))
+}
+
+/// Files of a store with their paths within it, in a fixed order. The
+/// store's own path may be a link; links inside it are skipped, so a backup
+/// never reaches outside the store.
+fn source_files(root: &Path) -> Result<Vec<(String, PathBuf)>, BackupError> {
+    if root.is_file() {
+        return Ok(vec![(String::new(), root.to_path_buf())]);
+    }
+    if !root.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    let mut pending = vec![(String::new(), root.to_path_buf())];
+    while let Some((prefix, dir)) = pending.pop() {
+        for entry in fs::read_dir(&dir).map_err(|source| io_error(&dir, source))? {
+            let entry = entry.map_err(|source| io_error(&dir, source))?;
+            let file_type = entry.file_type().map_err(|source| io_error(&entry.path(), source))?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let path = if prefix.is_empty() { name } else { format!("{}/{}", prefix, name) };
+            if file_type.is_dir() {
+                pending.push((path, entry.path()));
+            } else if file_type.is_file() {
+                files.push((path, entry.path()));
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn archive_path(dir: &Path, id: BackupId) -> PathBuf {
+    dir.join(format!("{:06}.{}", id, EXTENSION))
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> BackupError {
+    BackupError::Io { path: path.to_path_buf(), source }
+}
+
+impl Runtime {
+    /// Where `backup` puts backups and what they cover.
+    pub fn set_backup(&mut self, config: BackupConfig) {
+        self.backup_config = Some(config);
+    }
+
+    /// Backs up the configured stores, after committing the write-ahead
+    /// log, which is backed up with them. Records the backup in narrative
+    /// memory.
+    pub fn backup(&mut self) -> Result<BackupManifest, RuntimeError> {
+        let mut config = self.backup_config.clone().ok_or(BackupError::NotConfigured)?;
+        self.commit_wal()?;
+        if let Some(journal) = &self.wal {
+            if !config.sources.iter().any(|source| source.name == WAL_SOURCE) {
+                config.sources.push(BackupSource { name: WAL_SOURCE.to_string(), path: journal.path().to_path_buf() });
+            }
+        }
+        let manifest = create_backup(&config, self.clock.now())?;
+        self.narrative_memory.add_event(
+            "backup",
+            format!(
+                "Backup {} of {} files, {} stored ({} bytes)",
+                manifest.id,
+                manifest.files.len(),
+                manifest.stored_files(),
+                manifest.stored_bytes()
+            ),
+            None,
+        );
+        Ok(manifest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::admin::{AdminCommand, AdminOutcome};
+    use crate::runtime::wal::WalConfig;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("astra-backup-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_incremental_backups_restore_verified_state() {
+        let dir = temp_dir("stores");
+        let knowledge = dir.join("knowledge");
+        fs::create_dir_all(knowledge.join("snapshots")).unwrap();
+        fs::write(knowledge.join("facts.db"), b"facts v1").unwrap();
+        fs::write(knowledge.join("snapshots/0001"), b"snapshot").unwrap();
+        let config_file = dir.join("astra.toml");
+        fs::write(&config_file, b"tick_ms = 100").unwrap();
+        let config = BackupConfig::new(dir.join("backups"))
+            .with_source("knowledge", &knowledge)
+            .with_source("config", &config_file)
+            .with_source("models", dir.join("models"))
+            .with_full_every(3);
+
+        let first = create_backup(&config, AstraTime::from_secs(1)).unwrap();
+        assert!(first.full);
+        assert_eq!(first.files.len(), 3);
+        assert_eq!(first.files[0].path, "facts.db");
+
+        // Only what changed is stored again
+        fs::write(knowledge.join("facts.db"), b"facts v2").unwrap();
+        let second = create_backup(&config, AstraTime::from_secs(2)).unwrap();
+        assert!(!second.full);
+        assert_eq!(second.stored_files(), 1);
+        assert_eq!(second.depends_on(), BTreeSet::from([1, 2]));
+        let third = create_backup(&config, AstraTime::from_secs(3)).unwrap();
+        assert_eq!(third.stored_files(), 0);
+        assert!(create_backup(&config, AstraTime::from_secs(4)).unwrap().full);
+        assert_eq!(list_backups(&config.dir).unwrap(), [1, 2, 3, 4]);
+
+        // The state of the second backup comes back, under a fresh root
+        fs::write(knowledge.join("facts.db"), b"facts v3").unwrap();
+        fs::write(knowledge.join("stray"), b"not backed up").unwrap();
+        let report = restore_backup(&config.dir, Some(2), None).unwrap();
+        assert_eq!((report.files, report.bytes), (3, 29));
+        assert_eq!(fs::read(knowledge.join("facts.db")).unwrap(), b"facts v2");
+        assert!(!knowledge.join("stray").exists());
+        let elsewhere = dir.join("restored");
+        restore_backup(&config.dir, None, Some(&elsewhere)).unwrap();
+        assert_eq!(fs::read(elsewhere.join("config")).unwrap(), b"tick_ms = 100");
+        assert_eq!(fs::read(elsewhere.join("knowledge/snapshots/0001")).unwrap(), b"snapshot");
+        assert_eq!(fs::read_dir(&elsewhere).unwrap().count(), 2);
+
+        // A damaged archive fails verification, and so do backups relying on it
+        let archive = archive_path(&config.dir, 1);
+        let mut bytes = fs::read(&archive).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xff;
+        fs::write(&archive, bytes).unwrap();
+        assert!(matches!(verify_backup(&config.dir, 2), Err(BackupError::Corrupt { id: 1, .. })));
+        assert!(matches!(restore_backup(&config.dir, Some(3), None), Err(BackupError::Corrupt { .. })));
+        assert_eq!(fs::read(knowledge.join("facts.db")).unwrap(), b"facts v2");
+        verify_backup(&config.dir, 4).unwrap();
+        assert!(matches!(verify_backup(&config.dir, 9), Err(BackupError::NotFound(9))));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_store_names_cannot_restore_outside_the_target() {
+        let dir = temp_dir("escape");
+        let outside = dir.join("outside");
+        fs::write(&outside, b"keep me").unwrap();
+        let config = BackupConfig::new(dir.join("backups")).with_source("../outside", &outside);
+        create_backup(&config, AstraTime::from_secs(1)).unwrap();
+
+        let into = dir.join("restored");
+        let result = restore_backup(&config.dir, None, Some(&into));
+        assert!(matches!(result, Err(BackupError::UnsafeStore(name)) if name == "../outside"));
+        assert!(matches!(verify_backup(&config.dir, 1), Err(BackupError::UnsafeStore(_))));
+        assert_eq!(fs::read(&outside).unwrap(), b"keep me");
+        assert!(!into.exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_links_are_skipped_and_forged_blob_ranges_refused() {
+        let dir = temp_dir("forged");
+        let store = dir.join("models");
+        fs::create_dir_all(&store).unwrap();
+        fs::write(store.join("weights"), b"weights").unwrap();
+        fs::write(dir.join("secret"), b"outside the store").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(dir.join("secret"), store.join("link")).unwrap();
+        let config = BackupConfig::new(dir.join("backups")).with_source("models", &store);
+        let manifest = create_backup(&config, AstraTime::from_secs(1)).unwrap();
+        assert_eq!(manifest.files.iter().map(|entry| entry.path.as_str()).collect::<Vec<_>>(), ["weights"]);
+
+        // A manifest claiming more than the archive holds is refused, not allocated
+        let archive = archive_path(&config.dir, 1);
+        let (mut forged, blobs_start) = read_manifest(&config.dir, 1).unwrap();
+        let blobs = fs::read(&archive).unwrap()[blobs_start as usize..].to_vec();
+        forged.files[0].length = u64::MAX / 2;
+        let encoded = serde_json::to_vec(&forged).unwrap();
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&(encoded.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&encoded);
+        bytes.extend_from_slice(&blobs);
+        fs::write(&archive, bytes).unwrap();
+        assert!(matches!(verify_backup(&config.dir, 1), Err(BackupError::Corrupt { .. })));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_runtime_backs_up_its_log_through_an_admin_command() {
+        let dir = temp_dir("runtime");
+        let mut runtime = Runtime::new();
+        assert!(matches!(runtime.backup(), Err(RuntimeError::Backup(BackupError::NotConfigured))));
+        runtime.enable_wal(WalConfig::new(dir.join("state.wal"))).unwrap();
+        runtime.set_backup(BackupConfig::new(dir.join("backups")));
+        runtime.narrative_memory.add_event("note", "Before the backup".to_string(), None);
+
+        let AdminOutcome::BackedUp { id, files, .. } = runtime.admin(AdminCommand::Backup, "ops") else {
+            panic!("expected a backup");
+        };
+        assert_eq!((id, files), (1, 1));
+        let manifest = verify_backup(&dir.join("backups"), 1).unwrap();
+        assert_eq!(manifest.files[0].source, WAL_SOURCE);
+        // The event was committed to the log before it was backed up
+        let restored = dir.join("restored");
+        restore_backup(&dir.join("backups"), None, Some(&restored)).unwrap();
+        let log = fs::read(restored.join(WAL_SOURCE)).unwrap();
+        assert!(String::from_utf8_lossy(&log).contains("Before the backup"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,185 @@
+// =============================================================================
+//  Astra AGI - Behavior Scripts
+//  File: behavior_scripts.rs
+//
+//  Description:
+//  Named Astra programs loaded from files, such as response policies or
+//  planning domains written in astra_lang, that are kept in step with their
+//  source while the runtime runs. Each check compares the files' modification
+//  times; a file whose content changed is recompiled, and the new version
+//  replaces the old in one swap. A version that fails to read, parse, or
+//  pass analysis is not installed: the previous version stays in place and
+//  the failure is reported once, until the file changes again. Runs already
+//  in progress, including suspended ones, finish on the version they started.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use astra_lang::modules::{content_hash, ContentHash};
+
+use crate::runtime::error::RuntimeError;
+use crate::runtime::executor::AstNode;
+
+/// One compiled version of a behavior script.
+#[derive(Clone)]
+pub struct ScriptVersion {
+    /// 1 when registered, increasing with every successful reload
+    pub version: u64,
+    /// Hash of the source this version was compiled from
+    pub hash: ContentHash,
+    pub ast: AstNode,
+}
+
+/// What a reload did to one script.
+#[derive(Debug)]
+pub enum ScriptReload {
+    /// The new source compiled and is now current
+    Reloaded { name: String, version: u64 },
+    /// The new source was rejected; the previous version is still current
+    RolledBack { name: String, version: u64, error: RuntimeError },
+}
+
+struct Script {
+    path: PathBuf,
+    current: Arc<ScriptVersion>,
+    /// Modification time when the file was last looked at
+    modified: Option<SystemTime>,
+}
+
+/// The registered behavior scripts, by name.
+#[derive(Default)]
+pub struct BehaviorScripts {
+    scripts: BTreeMap<String, Script>,
+}
+
+impl BehaviorScripts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the script at `path` under `name`, replacing any script of that
+    /// name. `compile` turns source into a runnable program or rejects it;
+    /// a script that does not compile now is not registered.
+    pub fn register(
+        &mut self,
+        name: &str,
+        path: impl Into<PathBuf>,
+        compile: impl FnOnce(&str) -> Result<AstNode, RuntimeError>,
+    ) -> Result<Arc<ScriptVersion>, RuntimeError> {
+        let path = path.into();
+        let modified = modified(&path);
+        let source = read(&path)?;
+        let current = Arc::new(ScriptVersion { version: 1, hash: content_hash(&source), ast: compile(&source)? });
+        self.scripts.insert(name.to_string(), Script { path, current: current.clone(), modified });
+        Ok(current)
+    }
+
+    /// The version of `name` now in use.
+    pub fn current(&self, name: &str) -> Option<Arc<ScriptVersion>> {
+        self.scripts.get(name).map(|script| script.current.clone())
+    }
+
+    pub fn path(&self, name: &str) -> Option<&Path> {
+        self.scripts.get(name).map(|script| script.path.as_path())
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.scripts.keys().map(String::as_str)
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.scripts.remove(name).is_some()
+    }
+
+    /// Recompiles every script whose file changed since it was last looked
+    /// at, swapping in versions that compile and keeping the current version
+    /// of those that do not.
+    pub fn reload_changed(&mut self, mut compile: impl FnMut(&str) -> Result<AstNode, RuntimeError>) -> Vec<ScriptReload> {
+        let mut reloads = Vec::new();
+        for (name, script) in &mut self.scripts {
+            let modified = modified(&script.path);
+            if modified == script.modified {
+                continue;
+            }
+            script.modified = modified;
+
+            let compiled = read(&script.path).and_then(|source| {
+                let hash = content_hash(&source);
+                if hash == script.current.hash {
+                    return Ok(None);
+                }
+                compile(&source).map(|ast| Some((hash, ast)))
+            });
+            match compiled {
+                Ok(None) => {}
+                Ok(Some((hash, ast))) => {
+                    let version = script.current.version + 1;
+                    script.current = Arc::new(ScriptVersion { version, hash, ast });
+                    reloads.push(ScriptReload::Reloaded { name: name.clone(), version });
+                }
+                Err(error) => reloads.push(ScriptReload::RolledBack {
+                    name: name.clone(),
+                    version: script.current.version,
+                    error,
+                }),
+            }
+        }
+        reloads
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+fn read(path: &Path) -> Result<String, RuntimeError> {
+    fs::read_to_string(path).map_err(|source| RuntimeError::ScriptUnreadable { path: path.to_path_buf(), source })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::executor::Executor;
+
+    #[test]
+    fn test_changed_scripts_are_swapped_in_or_rolled_back() {
+        let dir = std::env::temp_dir().join(format!("astra_scripts_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("greet.astra");
+        fs::write(&path, "fn main() { return 1; }").unwrap();
+
+        let mut executor = Executor::new();
+        let mut scripts = BehaviorScripts::new();
+        let first = scripts.register("greet", &path, |source| Ok(executor.parse(source)?)).unwrap();
+        assert_eq!(first.version, 1);
+
+        // Only a change of content makes a new version
+        scripts.scripts.get_mut("greet").unwrap().modified = None;
+        assert!(scripts.reload_changed(|source| Ok(executor.parse(source)?)).is_empty());
+
+        fs::write(&path, "fn main() { return 2; }").unwrap();
+        scripts.scripts.get_mut("greet").unwrap().modified = None;
+        let reloads = scripts.reload_changed(|source| Ok(executor.parse(source)?));
+        assert!(matches!(&reloads[..], [ScriptReload::Reloaded { version: 2, .. }]));
+
+        fs::write(&path, "fn main() { return missing(); }").unwrap();
+        scripts.scripts.get_mut("greet").unwrap().modified = None;
+        let reloads = scripts.reload_changed(|source| Ok(executor.parse(source)?));
+        assert!(matches!(&reloads[..], [ScriptReload::RolledBack { version: 2, error: RuntimeError::Parse(_), .. }]));
+        assert_eq!(scripts.current("greet").unwrap().version, 2);
+        // Reported once, until the file changes again
+        assert!(scripts.reload_changed(|source| Ok(executor.parse(source)?)).is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
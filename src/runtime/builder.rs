@@ -0,0 +1,465 @@
+// =============================================================================
+//  Astra AGI - Runtime Builder
+//  File: builder.rs
+//
+//  Description:
+//  Assembles a Runtime from parts chosen by the embedding application.
+//  Anything not given is what `Runtime::new()` uses. A host can bring its
+//  own narrative memory and a store for the events that leave it, an
+//  emotion model (a frozen one for tests, say), the action executor plans
+//  run on when the host does not name one, hooks, the knowledge base it
+//  shares with the runtime and the knowledge packs loaded into it at
+//  startup, the program capabilities to grant, and planning domain files
+//  for attached cognitive loops to plan with; it can size plan
+//  concurrency, the tick budget, and the queue of stimuli waiting for a
+//  tick, limit rate-limited external resources, say that it runs no
+//  crawler, and set the clock the runtime keeps time by, the seed of its
+//  randomness, and the write-ahead log its state is recovered from and
+//  logged to. With the `plugins` feature it can hand the
+//  runtime a WASM plugin host, whose effectors then run plans unless an
+//  action executor is given. With the `mqtt` feature it can hand it an MQTT
+//  bridge, whose sensor readings are then perceived each tick and whose
+//  actuator topics run plans unless an action executor or plugins do.
+//  `build` checks the parts fit together and names the first
+//  misconfiguration it finds instead of producing a runtime that would
+//  fail later.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use astra_lang::Capability;
+
+use crate::cognition::stimulus_queue::StimulusQueue;
+use crate::knowledge::snapshots::SharedKnowledge;
+use crate::emotion::{EmotionModel, EmotionState};
+use crate::memory::narrative_memory::NarrativeMemory;
+use crate::memory::summary_tiers::{MemoryStore, TierPolicy};
+use crate::planning::domain::Domain;
+use crate::planning::executor::ActionExecutor;
+use crate::planning::quotas::{QuotaLimit, QuotaResource};
+use crate::runtime::error::{RuntimeConfigError, RuntimeError};
+use crate::runtime::hooks::RuntimeHooks;
+use crate::runtime::knowledge_packs::KnowledgePackConfig;
+use crate::runtime::wal::WalConfig;
+use crate::runtime::backup::BackupConfig;
+use crate::runtime::Runtime;
+#[cfg(feature = "plugins")]
+use crate::interfaces::plugins::{PluginHost, SharedPluginHost};
+#[cfg(feature = "mqtt")]
+use crate::interfaces::mqtt::MqttBridge;
+use crate::random::RandomSource;
+use crate::time::SharedClock;
+
+/// Makes a fresh action executor for each plan submitted without one.
+pub type ExecutorFactory = Box<dyn FnMut() -> Box<dyn ActionExecutor + Send> + Send>;
+
+/// Parts for a Runtime; see `Runtime::builder`.
+#[derive(Default)]
+pub struct RuntimeBuilder {
+    narrative_memory: Option<NarrativeMemory>,
+    memory_archive: Option<Box<dyn MemoryStore>>,
+    memory_tiers: Option<TierPolicy>,
+    emotion_state: Option<EmotionState>,
+    emotion_model: Option<Box<dyn EmotionModel>>,
+    action_executor: Option<ExecutorFactory>,
+    plan_concurrency: Option<usize>,
+    tick_budget: Option<Duration>,
+    stimulus_capacity: Option<usize>,
+    program_capabilities: Option<BTreeSet<Capability>>,
+    knowledge_packs: Option<KnowledgePackConfig>,
+    knowledge: Option<SharedKnowledge>,
+    clock: Option<SharedClock>,
+    seed: Option<u64>,
+    wal: Option<WalConfig>,
+    backup: Option<BackupConfig>,
+    hooks: Vec<Box<dyn RuntimeHooks>>,
+    quotas: Vec<(QuotaResource, QuotaLimit)>,
+    planning_domains: Vec<PathBuf>,
+    without_crawler: bool,
+    #[cfg(feature = "plugins")]
+    plugins: Option<PluginHost>,
+    #[cfg(feature = "mqtt")]
+    mqtt: Option<MqttBridge>,
+}
+
+impl RuntimeBuilder {
+    pub fn new() -> Self {
+        RuntimeBuilder::default()
+    }
+
+    /// Narrative memory to start from, with its own capacity and privacy filter.
+    pub fn with_narrative_memory(mut self, memory: NarrativeMemory) -> Self {
+        self.narrative_memory = Some(memory);
+        self
+    }
+
+    /// Store for the raw events that age out of narrative memory, and
+    /// when they do. By default they are kept in an `EventArchive`.
+    pub fn with_memory_archive(mut self, archive: impl MemoryStore + 'static, tiers: TierPolicy) -> Self {
+        self.memory_archive = Some(Box::new(archive));
+        self.memory_tiers = Some(tiers);
+        self
+    }
+
+    /// Emotion state to start in.
+    pub fn with_emotion_state(mut self, state: EmotionState) -> Self {
+        self.emotion_state = Some(state);
+        self
+    }
+
+    /// How each tick's stimuli move the emotion state.
+    pub fn with_emotion_model(mut self, model: impl EmotionModel + 'static) -> Self {
+        self.emotion_model = Some(Box::new(model));
+        self
+    }
+
+    /// Executor for plans submitted with `submit_plan_to_default_executor`;
+    /// `factory` makes one per plan.
+    pub fn with_action_executor(
+        mut self,
+        factory: impl FnMut() -> Box<dyn ActionExecutor + Send> + Send + 'static,
+    ) -> Self {
+        self.action_executor = Some(Box::new(factory));
+        self
+    }
+
+    /// Plugins whose sensors the runtime polls each tick and whose
+    /// effectors run plans, unless `with_action_executor` names another
+    /// executor.
+    #[cfg(feature = "plugins")]
+    pub fn with_plugins(mut self, host: PluginHost) -> Self {
+        self.plugins = Some(host);
+        self
+    }
+
+    /// MQTT bridge whose sensor readings the runtime perceives each tick
+    /// and whose actuators run plans, unless `with_action_executor` or
+    /// `with_plugins` gives plans another executor.
+    #[cfg(feature = "mqtt")]
+    pub fn with_mqtt(mut self, bridge: MqttBridge) -> Self {
+        self.mqtt = Some(bridge);
+        self
+    }
+
+    /// Limits use of `resource` to `limit` per `window`; plans spare it as
+    /// it runs low, and the runtime warns when it nearly runs out.
+    pub fn with_quota(mut self, resource: QuotaResource, limit: u64, window: Duration) -> Self {
+        self.quotas.push((resource, QuotaLimit { limit, window }));
+        self
+    }
+
+    /// PDDL domain file whose actions, grounded over its constants,
+    /// attached cognitive loops plan with. A knowledge pack's domain of the
+    /// same name replaces it.
+    pub fn with_planning_domain(mut self, path: impl Into<PathBuf>) -> Self {
+        self.planning_domains.push(path.into());
+        self
+    }
+
+    /// Plans that may run at once.
+    pub fn with_plan_concurrency(mut self, plans: usize) -> Self {
+        self.plan_concurrency = Some(plans);
+        self
+    }
+
+    pub fn with_tick_budget(mut self, budget: Duration) -> Self {
+        self.tick_budget = Some(budget);
+        self
+    }
+
+    /// Stimuli that may wait for a tick before the least urgent are shed.
+    pub fn with_stimulus_capacity(mut self, stimuli: usize) -> Self {
+        self.stimulus_capacity = Some(stimuli);
+        self
+    }
+
+    /// Capabilities Astra programs may use, replacing the defaults.
+    pub fn with_program_capabilities(mut self, capabilities: impl IntoIterator<Item = Capability>) -> Self {
+        self.program_capabilities = Some(capabilities.into_iter().collect());
+        self
+    }
+
+    /// Knowledge packs to load into the knowledge base given with
+    /// `with_knowledge` when the runtime is built.
+    pub fn with_knowledge_packs(mut self, config: KnowledgePackConfig) -> Self {
+        self.knowledge_packs = Some(config);
+        self
+    }
+
+    /// Knowledge base the host shares with the runtime; see
+    /// `Runtime::set_knowledge`. Knowledge recovered from the write-ahead
+    /// log is restored into it before any knowledge packs are loaded.
+    pub fn with_knowledge(mut self, knowledge: SharedKnowledge) -> Self {
+        self.knowledge = Some(knowledge);
+        self
+    }
+
+    /// Clock for emotion decay, deadlines, and memory ageing; see
+    /// `Runtime::set_clock`.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Seed for a run that can be repeated; see `Runtime::set_random_source`.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Write-ahead log to recover state from and log it to; see
+    /// `Runtime::enable_wal`.
+    pub fn with_wal(mut self, config: WalConfig) -> Self {
+        self.wal = Some(config);
+        self
+    }
+
+    /// Stores to back up and where; see `Runtime::backup`.
+    pub fn with_backup(mut self, config: BackupConfig) -> Self {
+        self.backup = Some(config);
+        self
+    }
+
+    /// Hooks asked after any added before them.
+    pub fn with_hooks(mut self, hooks: impl RuntimeHooks + 'static) -> Self {
+        self.hooks.push(Box::new(hooks));
+        self
+    }
+
+    /// The host runs no web crawler; `Runtime::web_crawler` gives it none.
+    pub fn without_crawler(mut self) -> Self {
+        self.without_crawler = true;
+        self
+    }
+
+    /// Checks the parts fit together and assembles the runtime.
+    pub fn build(self) -> Result<Runtime, RuntimeError> {
+        self.validate()?;
+
+        let mut runtime = Runtime::new();
+        if let Some(memory) = self.narrative_memory {
+            runtime.narrative_memory = memory;
+        }
+        if let Some(archive) = self.memory_archive {
+            runtime.memory_archive = archive;
+        }
+        if let Some(tiers) = self.memory_tiers {
+            runtime.memory_tiers = tiers;
+        }
+        if let Some(state) = self.emotion_state {
+            runtime.emotion_state = state;
+        }
+        if let Some(model) = self.emotion_model {
+            runtime.emotion_model = model;
+        }
+        if let Some(plans) = self.plan_concurrency {
+            runtime.plan_runner.max_concurrent = plans;
+        }
+        if let Some(budget) = self.tick_budget {
+            runtime.tick_budget = budget;
+        }
+        if let Some(stimuli) = self.stimulus_capacity {
+            runtime.stimuli = StimulusQueue::new(stimuli);
+        }
+        if let Some(capabilities) = self.program_capabilities {
+            runtime.program_capabilities = capabilities;
+        }
+        for path in self.planning_domains {
+            let domain = Domain::load(&path).map_err(|source| RuntimeConfigError::PlanningDomain { path, source })?;
+            runtime.planning_domains.insert(domain.name.clone(), domain);
+        }
+        let load_packs = self.knowledge_packs.is_some();
+        if let Some(config) = self.knowledge_packs {
+            runtime.knowledge_packs = config;
+        }
+        if let Some(clock) = self.clock {
+            runtime.set_clock(clock);
+        }
+        if let Some(seed) = self.seed {
+            runtime.set_random_source(RandomSource::from_seed(seed));
+        }
+        runtime.action_executor = self.action_executor;
+        #[cfg(feature = "plugins")]
+        if let Some(host) = self.plugins {
+            let shared = SharedPluginHost::new(host);
+            if runtime.action_executor.is_none() {
+                let plugins = shared.clone();
+                let factory = move || Box::new(plugins.clone()) as Box<dyn ActionExecutor + Send>;
+                runtime.action_executor = Some(Box::new(factory));
+            }
+            runtime.plugins = Some(shared);
+        }
+        #[cfg(feature = "mqtt")]
+        if let Some(bridge) = self.mqtt {
+            let shared = std::sync::Arc::new(bridge);
+            if runtime.action_executor.is_none() {
+                let bridge = shared.clone();
+                let factory = move || Box::new(bridge.clone()) as Box<dyn ActionExecutor + Send>;
+                runtime.action_executor = Some(Box::new(factory));
+            }
+            runtime.mqtt = Some(shared);
+        }
+        if let Ok(mut quotas) = runtime.quotas.write() {
+            for (resource, limit) in self.quotas {
+                quotas.set_limit(resource, limit.limit, limit.window);
+            }
+        }
+        runtime.hooks = self.hooks;
+        runtime.crawler_enabled = !self.without_crawler;
+        runtime.backup_config = self.backup;
+        if let Some(config) = self.wal {
+            runtime.enable_wal(config)?;
+        }
+        if let Some(knowledge) = self.knowledge {
+            knowledge.write(|knowledge| {
+                runtime.restore_wal_knowledge(knowledge);
+                if load_packs {
+                    runtime.load_knowledge_packs(knowledge)?;
+                }
+                Ok::<_, RuntimeError>(())
+            })?;
+            runtime.set_knowledge(knowledge);
+        }
+        Ok(runtime)
+    }
+
+    fn validate(&self) -> Result<(), RuntimeConfigError> {
+        if self.plan_concurrency == Some(0) {
+            return Err(RuntimeConfigError::NoPlanConcurrency);
+        }
+        if self.tick_budget == Some(Duration::ZERO) {
+            return Err(RuntimeConfigError::ZeroTickBudget);
+        }
+        if self.stimulus_capacity == Some(0) {
+            return Err(RuntimeConfigError::NoStimulusCapacity);
+        }
+        if self.narrative_memory.as_ref().is_some_and(|memory| memory.max_capacity == 0) {
+            return Err(RuntimeConfigError::NoMemoryCapacity);
+        }
+        if let Some(packs) = &self.knowledge_packs {
+            if !packs.allow_unsigned && packs.trusted_keys.is_empty() {
+                return Err(RuntimeConfigError::NoTrustedPackKeys);
+            }
+            if packs.packs.as_ref().is_some_and(|names| !names.is_empty()) && !packs.dir.is_dir() {
+                return Err(RuntimeConfigError::PackDirectoryMissing(packs.dir.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Runtime {
+    /// Starts assembling a runtime from custom parts.
+    pub fn builder() -> RuntimeBuilder {
+        RuntimeBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planning::autonomy::AutonomyPolicy;
+    use crate::planning::planner::{Action, Plan};
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Emotions that stay where they start.
+    struct Frozen;
+
+    impl EmotionModel for Frozen {
+        fn update(&mut self, _state: &mut EmotionState, _stimuli: &HashMap<String, f32>) {}
+    }
+
+    struct Counting(Arc<AtomicUsize>);
+
+    impl ActionExecutor for Counting {
+        fn execute_action(&mut self, _action: &Action) -> anyhow::Result<bool> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(true)
+        }
+    }
+
+    #[test]
+    fn test_builder_injects_parts_and_rejects_misconfiguration() {
+        let executed = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&executed);
+        let calm = EmotionState { urgency: 0.0, motivation: 0.5, stress: 0.0 };
+        let mut runtime = Runtime::builder()
+            .with_narrative_memory(NarrativeMemory::new(50))
+            .with_emotion_state(calm)
+            .with_emotion_model(Frozen)
+            .with_action_executor(move || Box::new(Counting(Arc::clone(&counter))) as Box<dyn ActionExecutor + Send>)
+            .with_plan_concurrency(1)
+            .with_quota(QuotaResource::HttpRequests, 600, Duration::from_secs(3600))
+            .without_crawler()
+            .build()
+            .unwrap();
+        assert_eq!(runtime.narrative_memory.max_capacity, 50);
+        assert_eq!(runtime.quotas.read().unwrap().limit(QuotaResource::HttpRequests).unwrap().limit, 600);
+        assert!(!runtime.crawler_enabled());
+        assert!(runtime.web_crawler(1000).is_none());
+
+        for i in 0..200 {
+            runtime.intent_manager.create_intent_with_metadata(format!("chore {}", i), 1, None);
+        }
+        let (intent, _) = runtime.create_intent_from_utterance("tidy up", 5, None);
+        let action = Action {
+            id: "tidy".into(),
+            description: "Tidy up".into(),
+            preconditions: HashMap::new(),
+            effects: HashMap::new(),
+            cost: 1.0,
+            risk: 0.0,
+        };
+        let plan = Plan { goal_id: "tidy".into(), actions: vec![action], estimated_cost: 1.0 };
+        runtime.plan_runner.set_autonomy(AutonomyPolicy::permissive());
+        runtime.submit_plan_to_default_executor(intent, plan).unwrap();
+        for _ in 0..3 {
+            runtime.tick();
+        }
+        // A heavy workload would have raised stress under the default model
+        assert_eq!(runtime.emotion_state, calm);
+        assert_eq!(executed.load(Ordering::SeqCst), 1);
+
+        // Without a default executor there is nothing to run plans on
+        let mut bare = Runtime::builder().build().unwrap();
+        assert!(bare.web_crawler(1000).is_some());
+        let (intent, _) = bare.create_intent_from_utterance("tidy up", 5, None);
+        let plan = Plan { goal_id: "tidy".into(), actions: Vec::new(), estimated_cost: 0.0 };
+        assert!(matches!(bare.submit_plan_to_default_executor(intent, plan), Err(RuntimeError::NoActionExecutor)));
+
+        let config_error = |builder: RuntimeBuilder| match builder.build() {
+            Err(RuntimeError::Config(error)) => error,
+            _ => panic!("expected a configuration error"),
+        };
+        assert!(matches!(config_error(Runtime::builder().with_plan_concurrency(0)), RuntimeConfigError::NoPlanConcurrency));
+        assert!(matches!(config_error(Runtime::builder().with_stimulus_capacity(0)), RuntimeConfigError::NoStimulusCapacity));
+        assert!(matches!(
+            config_error(Runtime::builder().with_narrative_memory(NarrativeMemory::new(0))),
+            RuntimeConfigError::NoMemoryCapacity
+        ));
+        let strict = KnowledgePackConfig { allow_unsigned: false, ..KnowledgePackConfig::default() };
+        assert!(matches!(config_error(Runtime::builder().with_knowledge_packs(strict)), RuntimeConfigError::NoTrustedPackKeys));
+        assert!(matches!(
+            config_error(Runtime::builder().with_planning_domain("/nonexistent/domain.pddl")),
+            RuntimeConfigError::PlanningDomain { .. }
+        ));
+    }
+
+    #[test]
+    fn test_seeded_runtimes_repeat_their_jokes() {
+        let jokes = |runtime: &mut Runtime| (0..5).map(|_| runtime.humor.tell_joke().to_string()).collect::<Vec<_>>();
+        let mut first = Runtime::builder().with_seed(3964).build().unwrap();
+        let mut second = Runtime::builder().with_seed(3964).build().unwrap();
+        assert_eq!(jokes(&mut first), jokes(&mut second));
+    }
+}
@@ -0,0 +1,228 @@
+// =============================================================================
+//  Astra AGI - Simulated Co-Agents
+//  File: co_agents.rs
+//
+//  Description:
+//  Stand-ins for the people Astra talks to, so its social reasoning can be
+//  exercised and regression-tested without real users. A co-agent is
+//  declared in a scenario: it says its scripted lines in order, reacts to
+//  what Astra says with lines keyed on text in Astra's replies, and, if it
+//  learns, picks among openers by how warmly Astra answered them before,
+//  trying the others now and then. Each co-agent talks to Astra in its own
+//  session under its own name, so theory of mind, dialogue planning, and
+//  relationship models see it as a separate user. Learning co-agents draw
+//  from a stream of the scenario's seed, so their choices repeat.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::random::{RandomSource, SeededRng};
+
+/// A co-agent as written in a scenario.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CoAgentSpec {
+    pub name: String,
+    /// Lines said in order, one per turn
+    #[serde(default)]
+    pub script: Vec<String>,
+    /// Lines said in answer to Astra, ahead of the script
+    #[serde(default)]
+    pub reactions: Vec<Reaction>,
+    /// Openers chosen by how Astra answered them, once the script is done
+    #[serde(default)]
+    pub learns: Option<LearningSpec>,
+}
+
+/// Says `say` on the next turn after Astra says something containing `when`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Reaction {
+    /// Matched case-insensitively
+    pub when: String,
+    pub say: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LearningSpec {
+    pub options: Vec<String>,
+    /// Words in a reply that make it a warm one, matched case-insensitively
+    pub likes: Vec<String>,
+    /// Chance of trying an opener other than the best so far
+    #[serde(default = "default_exploration")]
+    pub exploration: f32,
+}
+
+fn default_exploration() -> f32 {
+    0.1
+}
+
+/// How an opener has gone so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct OptionValue {
+    tries: u32,
+    /// Share of tries answered warmly
+    value: f32,
+}
+
+/// A co-agent taking part in a conversation with Astra.
+#[derive(Debug, Clone)]
+pub struct CoAgent {
+    pub spec: CoAgentSpec,
+    next_line: usize,
+    reaction: Option<String>,
+    values: Vec<OptionValue>,
+    /// Opener awaiting Astra's answer
+    tried: Option<usize>,
+    rng: SeededRng,
+    /// Astra's replies, oldest first
+    pub heard: Vec<String>,
+}
+
+impl CoAgent {
+    pub fn new(spec: CoAgentSpec, source: &RandomSource) -> Self {
+        let options = spec.learns.as_ref().map_or(0, |learns| learns.options.len());
+        CoAgent {
+            rng: source.stream(&format!("co_agent:{}", spec.name)),
+            values: vec![OptionValue::default(); options],
+            spec,
+            next_line: 0,
+            reaction: None,
+            tried: None,
+            heard: Vec::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.spec.name
+    }
+
+    /// The session the co-agent talks to Astra in.
+    pub fn session(&self) -> String {
+        format!("agent:{}", self.spec.name)
+    }
+
+    /// What the co-agent says this turn: a reaction to Astra's last reply,
+    /// the next scripted line, or an opener it is learning about. `None`
+    /// once it has nothing left to say.
+    pub fn next_message(&mut self) -> Option<String> {
+        self.tried = None;
+        if let Some(line) = self.reaction.take() {
+            return Some(line);
+        }
+        if let Some(line) = self.spec.script.get(self.next_line) {
+            self.next_line += 1;
+            return Some(line.clone());
+        }
+        let learns = self.spec.learns.as_ref()?;
+        // A learner given no openers has nothing to choose from
+        if self.values.is_empty() {
+            return None;
+        }
+        let untried = self.values.iter().position(|v| v.tries == 0);
+        let index = match untried {
+            Some(index) => index,
+            None if self.rng.gen::<f32>() < learns.exploration => self.rng.gen_range(0..self.values.len()),
+            None => self.best()?,
+        };
+        self.tried = Some(index);
+        learns.options.get(index).cloned()
+    }
+
+    /// Takes in Astra's reply: scores the opener it answered and queues the
+    /// first reaction it calls for.
+    pub fn hear(&mut self, reply: &str) {
+        let lower = reply.to_lowercase();
+        if let (Some(index), Some(learns)) = (self.tried.take(), &self.spec.learns) {
+            let warm = learns.likes.iter().any(|word| lower.contains(&word.to_lowercase()));
+            let option = &mut self.values[index];
+            option.tries += 1;
+            option.value += (f32::from(u8::from(warm)) - option.value) / option.tries as f32;
+        }
+        self.reaction = self
+            .spec
+            .reactions
+            .iter()
+            .find(|reaction| lower.contains(&reaction.when.to_lowercase()))
+            .map(|reaction| reaction.say.clone());
+        self.heard.push(reply.to_string());
+    }
+
+    /// The opener a learning co-agent has found Astra answers most warmly,
+    /// among those tried.
+    pub fn preferred(&self) -> Option<&str> {
+        let learns = self.spec.learns.as_ref()?;
+        learns.options.get(self.best()?).map(String::as_str)
+    }
+
+    fn best(&self) -> Option<usize> {
+        self.values
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| v.tries > 0)
+            .max_by(|(_, a), (_, b)| a.value.total_cmp(&b.value))
+            .map(|(index, _)| index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_co_agents_follow_scripts_react_and_learn() {
+        let source = RandomSource::from_seed(3);
+        let spec: CoAgentSpec = serde_yaml::from_str(
+            r#"
+name: ana
+script: [Please remind me to water the plants]
+reactions:
+  - { when: when should that be, say: tomorrow at 9am }
+"#,
+        )
+        .unwrap();
+        let mut ana = CoAgent::new(spec, &source);
+        assert_eq!(ana.session(), "agent:ana");
+        assert_eq!(ana.next_message().as_deref(), Some("Please remind me to water the plants"));
+        ana.hear("Got it. When should that be?");
+        assert_eq!(ana.next_message().as_deref(), Some("tomorrow at 9am"));
+        ana.hear("Shall I go ahead?");
+        assert_eq!(ana.next_message(), None);
+
+        // Astra answers only the polite opener warmly
+        let spec = CoAgentSpec {
+            name: "ben".into(),
+            script: Vec::new(),
+            reactions: Vec::new(),
+            learns: Some(LearningSpec {
+                options: vec!["Do it now".into(), "Could you help, please?".into()],
+                likes: vec!["glad".into()],
+                exploration: 0.2,
+            }),
+        };
+        let mut ben = CoAgent::new(spec, &source);
+        let mut polite = 0;
+        for _ in 0..40 {
+            let message = ben.next_message().unwrap();
+            let warm = message.contains("please");
+            polite += usize::from(warm);
+            ben.hear(if warm { "Glad to help!" } else { "Working on it." });
+        }
+        assert_eq!(ben.preferred(), Some("Could you help, please?"));
+        assert!(polite > 30, "chose the polite opener {} times", polite);
+        assert_eq!(ben.heard.len(), 40);
+
+        let spec = CoAgentSpec {
+            name: "cy".into(),
+            script: Vec::new(),
+            reactions: Vec::new(),
+            learns: Some(LearningSpec { options: Vec::new(), likes: Vec::new(), exploration: 1.0 }),
+        };
+        assert_eq!(CoAgent::new(spec, &source).next_message(), None);
+    }
+}
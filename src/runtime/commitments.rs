@@ -0,0 +1,258 @@
+// =============================================================================
+//  Astra AGI - Commitment Tracking
+//  File: commitments.rs
+//
+//  Description:
+//  Bookkeeping for promises Astra makes to users. Each commitment is linked to
+//  the intent that will fulfil it, the deadline that was promised, and the
+//  user it was made to. The tracker follows the linked intents, issues
+//  reminders as deadlines approach, and marks commitments broken when a
+//  deadline passes or the intent is dropped, so reflection can learn from them.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use std::collections::HashMap;
+use chrono::{DateTime, Duration, Utc};
+
+use super::error::RuntimeError;
+use super::intent_manager::{IntentId, IntentManager, IntentState};
+
+pub type CommitmentId = u64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitmentStatus {
+    Open,
+    Fulfilled,
+    /// The deadline passed or the linked intent was cancelled
+    Broken,
+    /// The user let Astra off the hook
+    Released,
+}
+
+#[derive(Debug, Clone)]
+pub struct Commitment {
+    pub id: CommitmentId,
+    pub user_id: Option<String>,
+    /// What Astra said it would do
+    pub statement: String,
+    pub intent_id: IntentId,
+    pub promised_by: Option<DateTime<Utc>>,
+    pub made_at: DateTime<Utc>,
+    pub status: CommitmentStatus,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub reminded: bool,
+}
+
+/// Something the tracker noticed while checking commitments.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommitmentEvent {
+    /// The deadline is within the reminder lead time
+    Reminder(CommitmentId),
+    Fulfilled(CommitmentId),
+    Broken(CommitmentId),
+}
+
+pub struct CommitmentTracker {
+    commitments: HashMap<CommitmentId, Commitment>,
+    next_id: CommitmentId,
+    /// How long before a deadline a reminder is raised
+    pub reminder_lead: Duration,
+}
+
+impl CommitmentTracker {
+    pub fn new() -> Self {
+        CommitmentTracker {
+            commitments: HashMap::new(),
+            next_id: 1,
+            reminder_lead: Duration::hours(1),
+        }
+    }
+
+    /// Records a promise and returns its ID.
+    pub fn record(
+        &mut self,
+        user_id: Option<String>,
+        statement: impl Into<String>,
+        intent_id: IntentId,
+        promised_by: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> CommitmentId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.commitments.insert(id, Commitment {
+            id,
+            user_id,
+            statement: statement.into(),
+            intent_id,
+            promised_by,
+            made_at: now,
+            status: CommitmentStatus::Open,
+            resolved_at: None,
+            reminded: false,
+        });
+        id
+    }
+
+    /// Marks a commitment fulfilled.
+    pub fn fulfill(&mut self, id: CommitmentId, now: DateTime<Utc>) -> Result<(), RuntimeError> {
+        self.resolve(id, CommitmentStatus::Fulfilled, now)
+    }
+
+    /// Releases Astra from a commitment at the user's request.
+    pub fn release(&mut self, id: CommitmentId, now: DateTime<Utc>) -> Result<(), RuntimeError> {
+        self.resolve(id, CommitmentStatus::Released, now)
+    }
+
+    /// Removes a commitment that was never actually made, such as one in a
+    /// reply the user never received in full. Returns it if it was open.
+    pub fn withdraw(&mut self, id: CommitmentId) -> Option<Commitment> {
+        match self.commitments.get(&id) {
+            Some(c) if c.status == CommitmentStatus::Open => self.commitments.remove(&id),
+            _ => None,
+        }
+    }
+
+    /// Reconciles open commitments with their intents and deadlines.
+    /// Completed intents fulfil their commitment; cancelled intents and missed
+    /// deadlines break it; approaching deadlines raise one reminder each.
+    pub fn check(&mut self, now: DateTime<Utc>, intents: &IntentManager) -> Vec<CommitmentEvent> {
+        let mut events = Vec::new();
+
+        for commitment in self.commitments.values_mut().filter(|c| c.status == CommitmentStatus::Open) {
+            let intent_state = intents.get_intent(commitment.intent_id).map(|i| i.state);
+
+            let outcome = match (intent_state, commitment.promised_by) {
+                (Some(IntentState::Completed), _) => Some(CommitmentStatus::Fulfilled),
+                (Some(IntentState::Cancelled), _) | (None, _) => Some(CommitmentStatus::Broken),
+                (_, Some(deadline)) if now > deadline => Some(CommitmentStatus::Broken),
+                _ => None,
+            };
+
+            match outcome {
+                Some(status) => {
+                    commitment.status = status;
+                    commitment.resolved_at = Some(now);
+                    events.push(match status {
+                        CommitmentStatus::Fulfilled => CommitmentEvent::Fulfilled(commitment.id),
+                        _ => CommitmentEvent::Broken(commitment.id),
+                    });
+                }
+                None => {
+                    if let Some(deadline) = commitment.promised_by {
+                        if !commitment.reminded && deadline - now <= self.reminder_lead {
+                            commitment.reminded = true;
+                            events.push(CommitmentEvent::Reminder(commitment.id));
+                        }
+                    }
+                }
+            }
+        }
+
+        events.sort_by_key(|e| match e {
+            CommitmentEvent::Reminder(id) | CommitmentEvent::Fulfilled(id) | CommitmentEvent::Broken(id) => *id,
+        });
+        events
+    }
+
+    pub fn get(&self, id: CommitmentId) -> Option<&Commitment> {
+        self.commitments.get(&id)
+    }
+
+    /// Open commitments made to a user, earliest deadline first.
+    pub fn open_for_user(&self, user_id: Option<&str>) -> Vec<&Commitment> {
+        let mut open: Vec<&Commitment> = self
+            .commitments
+            .values()
+            .filter(|c| c.status == CommitmentStatus::Open && c.user_id.as_deref() == user_id)
+            .collect();
+        open.sort_by_key(|c| (c.promised_by.is_none(), c.promised_by, c.id));
+        open
+    }
+
+    /// Every commitment, in the order made.
+    pub fn all(&self) -> Vec<&Commitment> {
+        let mut all: Vec<&Commitment> = self.commitments.values().collect();
+        all.sort_by_key(|c| c.id);
+        all
+    }
+
+    /// Every commitment made to a user, whatever its status.
+    pub fn all_for_user(&self, user_id: &str) -> Vec<&Commitment> {
+        let mut all: Vec<&Commitment> =
+            self.commitments.values().filter(|c| c.user_id.as_deref() == Some(user_id)).collect();
+        all.sort_by_key(|c| c.id);
+        all
+    }
+
+    /// Deletes every commitment made to a user. Returns the removed ones.
+    pub fn forget_user(&mut self, user_id: &str) -> Vec<Commitment> {
+        let ids: Vec<CommitmentId> = self.all_for_user(user_id).iter().map(|c| c.id).collect();
+        ids.iter().filter_map(|id| self.commitments.remove(id)).collect()
+    }
+
+    /// Broken commitments, oldest first, for reflection.
+    pub fn broken(&self) -> Vec<&Commitment> {
+        let mut broken: Vec<&Commitment> =
+            self.commitments.values().filter(|c| c.status == CommitmentStatus::Broken).collect();
+        broken.sort_by_key(|c| c.id);
+        broken
+    }
+
+    /// Share of kept commitments among those fulfilled or broken; `None` if
+    /// none have been resolved yet.
+    pub fn fulfillment_rate(&self) -> Option<f32> {
+        let fulfilled = self.commitments.values().filter(|c| c.status == CommitmentStatus::Fulfilled).count();
+        let broken = self.commitments.values().filter(|c| c.status == CommitmentStatus::Broken).count();
+        if fulfilled + broken == 0 {
+            None
+        } else {
+            Some(fulfilled as f32 / (fulfilled + broken) as f32)
+        }
+    }
+
+    fn resolve(&mut self, id: CommitmentId, status: CommitmentStatus, now: DateTime<Utc>) -> Result<(), RuntimeError> {
+        let commitment = self.commitments.get_mut(&id).ok_or(RuntimeError::CommitmentNotFound(id))?;
+        if commitment.status != CommitmentStatus::Open {
+            return Err(RuntimeError::CommitmentResolved { id, status: commitment.status });
+        }
+        commitment.status = status;
+        commitment.resolved_at = Some(now);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commitment_lifecycle() {
+        let mut intents = IntentManager::new();
+        let mut tracker = CommitmentTracker::new();
+        let now = Utc::now();
+
+        let kept_intent = intents.create_intent_with_metadata("Send the report", 5, None);
+        let late_intent = intents.create_intent_with_metadata("Book the venue", 5, None);
+        let kept = tracker.record(Some("ana".into()), "I'll send the report", kept_intent, Some(now + Duration::minutes(30)), now);
+        let late = tracker.record(Some("ana".into()), "I'll book the venue", late_intent, Some(now + Duration::hours(3)), now);
+
+        // The first deadline is within the reminder lead, the second is not.
+        assert_eq!(tracker.check(now, &intents), vec![CommitmentEvent::Reminder(kept)]);
+        assert!(tracker.check(now, &intents).is_empty());
+
+        intents.complete_intent(kept_intent).unwrap();
+        let later = now + Duration::hours(4);
+        assert_eq!(
+            tracker.check(later, &intents),
+            vec![CommitmentEvent::Fulfilled(kept), CommitmentEvent::Broken(late)]
+        );
+        assert_eq!(tracker.broken()[0].id, late);
+        assert_eq!(tracker.fulfillment_rate(), Some(0.5));
+        assert!(tracker.open_for_user(Some("ana")).is_empty());
+    }
+}
@@ -0,0 +1,379 @@
+// =============================================================================
+//  Astra AGI - Dialogue Turns
+//  File: dialogue.rs
+//
+//  Description:
+//  Plans Astra's side of a conversation in which a user asks for something.
+//  Each session negotiating a request holds a Dialogue (see
+//  planning/dialogue.rs); every user message updates it, as a new request,
+//  an answer to a question Astra asked, or a reply to what Astra proposed,
+//  and the next turn's dialogue acts are planned from the updated state.
+//  Missing details are asked for with the clarifying questions the NLP
+//  pipeline produces. When the user agrees, the Commit act Astra performs
+//  is recorded as its commitment, an intent linked to a commitment.
+//  Planned replies are reviewed by the runtime's hooks before they are
+//  said; a vetoed one leaves the dialogue as it was.
+//
+//  Every chat front end replies through `reply_to_user`: it plans the
+//  dialogue turn of a session's message and, for messages that ask for
+//  nothing falls back to an answer from the evidence, for questions, or
+//  to a reply shaped by personality, the relationship with the user, and
+//  their preferences. Questions the evidence answers poorly, and names the
+//  knowledge base has never seen, are logged as knowledge gaps. A reply
+//  streamed to a client that cancels it is cut back to what was sent,
+//  withdrawing any promise the user never heard.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use std::collections::BTreeMap;
+
+use crate::cognition::grounding::GroundedAnswer;
+use crate::interfaces::clarification::Interpretation;
+use crate::interfaces::nlp::NlpResult;
+use crate::knowledge::extended_ontology::OntologyManager;
+use crate::learning::feedback::{ResponseId, ResponseTrace};
+use crate::learning::preferences::ResponseStyle;
+use crate::memory::conversation_summary::Speaker;
+use crate::metadata::Metadata;
+use crate::planning::dialogue::{read_agreement, Dialogue, DialogueAct};
+use crate::runtime::commitments::CommitmentId;
+use crate::runtime::error::RuntimeError;
+use crate::runtime::Runtime;
+
+/// Astra's side of one turn of a planned dialogue.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlannedTurn {
+    /// Acts performed, in the order spoken
+    pub acts: Vec<DialogueAct>,
+    /// What Astra says; empty if the message was not part of a dialogue
+    pub reply: String,
+    /// Commitments made by the turn's Commit act
+    pub commitments: Vec<CommitmentId>,
+    /// Intent the message was understood as, when it was interpreted and
+    /// needed no clarifying question
+    pub understood: Option<String>,
+}
+
+/// Ticks run between hearing a message and replying, letting it settle
+/// into Astra's state
+const SETTLING_TICKS: usize = 5;
+/// Intent of messages answered from evidence
+const QUESTION_INTENT: &str = "question";
+
+/// Astra's reply to one message from a user.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserReply {
+    pub text: String,
+    /// Reference for feedback about the reply
+    pub response_id: ResponseId,
+    /// "Last time we discussed…", when the message opened its session
+    pub primer: Option<String>,
+    /// The user's own memories the message called to mind
+    pub associations: Vec<String>,
+    /// Acts of the reply, when the message was part of a negotiated request
+    pub acts: Vec<DialogueAct>,
+    /// Commitments the reply made
+    pub commitments: Vec<CommitmentId>,
+    /// Intent the message was understood as, if it was interpreted in its
+    /// session and needed no clarifying question
+    pub understood: Option<String>,
+}
+
+impl Runtime {
+    /// Replies to `text` from `user_id`, in `session_id` if given: a
+    /// request is negotiated through planned dialogue acts, anything else
+    /// gets a reply from personality shaped to the user's preferences.
+    /// Only the user's own memories are drawn on. A reply the runtime's
+    /// hooks veto is withheld and not recorded as said.
+    pub fn reply_to_user(
+        &mut self,
+        user_id: &str,
+        session_id: Option<&str>,
+        text: &str,
+        knowledge: Option<&OntologyManager>,
+    ) -> Result<UserReply, RuntimeError> {
+        let primer = match session_id {
+            Some(session_id) if !self.conversations.contains_key(session_id) => {
+                self.begin_conversation(session_id, Some(user_id.to_string()))
+            }
+            _ => None,
+        };
+        // Records the user's turn, and Astra's when it plans one
+        let planned = match session_id {
+            Some(session_id) => self.plan_dialogue_turn(session_id, text)?,
+            None => PlannedTurn::default(),
+        };
+
+        let primed = self.prime_context(text, knowledge);
+        let associations = primed
+            .events
+            .iter()
+            .filter_map(|(id, _)| self.narrative_memory.get(*id))
+            .filter(|e| e.user_id().is_some_and(|owner| owner == user_id))
+            .map(|e| e.description.clone())
+            .collect();
+        let episode_id = self.record_user_message(user_id, text);
+        let sentiment = self.absorb_user_sentiment(text);
+        let now = self.clock.now_utc();
+        let relationship = self.relationships.observe(user_id, user_id, sentiment.valence, now).clone();
+        // A session's messages are perceived and observed as they are interpreted
+        if session_id.is_none() {
+            self.submit_user_stimulus(text);
+            self.observe_user_message(user_id, text);
+        }
+        for _ in 0..SETTLING_TICKS {
+            self.tick();
+        }
+
+        if !planned.reply.is_empty() {
+            let response_id = self.register_response_trace(ResponseTrace {
+                episode_id: Some(episode_id),
+                style: Some(ResponseStyle::of_reply(&planned.reply)),
+                ..Default::default()
+            });
+            return Ok(UserReply {
+                text: planned.reply,
+                response_id,
+                primer,
+                associations,
+                acts: planned.acts,
+                commitments: planned.commitments,
+                understood: planned.understood,
+            });
+        }
+
+        let mut personality = self.personality.clone();
+        let register = self.preference_model.response_guidance().register;
+        // A question the evidence can answer is answered, tailored to what
+        // the user already believes; anything else gets a personal reply
+        let answer = match self.nlp.process_text(text) {
+            Ok(understood) => self.answer_from_evidence(user_id, text, &understood, knowledge)?,
+            Err(_) => None,
+        };
+        let reply = match answer {
+            Some(answer) => answer.render(),
+            None => self.preference_model.shape_response(&personality.respond_to(text, &relationship, register)),
+        };
+        self.review_response(&reply)?;
+        let response_id = self.register_response_trace(ResponseTrace {
+            episode_id: Some(episode_id),
+            personality_settings: personality.reply_traits(Some(&relationship)),
+            style: Some(ResponseStyle::of_reply(&reply)),
+            ..Default::default()
+        });
+        if let Some(session_id) = session_id {
+            self.record_conversation_turn(session_id, Speaker::Astra, &reply);
+        }
+        let commitments = self.record_commitments_from_reply(&reply, Some(user_id.to_string()));
+        Ok(UserReply {
+            text: reply,
+            response_id,
+            primer,
+            associations,
+            acts: Vec::new(),
+            commitments,
+            understood: planned.understood,
+        })
+    }
+
+    /// The cited answer to `text`, tailored to what `user_id` already
+    /// believes, when it was `understood` as a question the evidence can
+    /// answer; `None` for anything else, left to a personal reply. Names in
+    /// `text` that `knowledge` has never seen are logged as knowledge gaps
+    /// either way.
+    pub fn answer_from_evidence(
+        &mut self,
+        user_id: &str,
+        text: &str,
+        understood: &NlpResult,
+        knowledge: Option<&OntologyManager>,
+    ) -> Result<Option<GroundedAnswer>, RuntimeError> {
+        if let Some(knowledge) = knowledge {
+            self.knowledge_gaps.record_unknown_entities(text, knowledge);
+        }
+        if understood.intent != QUESTION_INTENT {
+            return Ok(None);
+        }
+        Ok(Some(self.answer_question_for(user_id, text, knowledge, None)?).filter(|a| !a.sources.is_empty()))
+    }
+
+    /// Records that only `delivered` of the reply `said` to `user_id` reached
+    /// them, as when a streamed reply is cancelled or the client leaves. The
+    /// session's last turn becomes the delivered text, and commitments whose
+    /// promise was cut off are withdrawn along with their intents.
+    pub fn cut_reply_short(&mut self, user_id: &str, session_id: Option<&str>, said: &str, delivered: &str) {
+        if delivered == said {
+            return;
+        }
+        if let Some(session_id) = session_id {
+            let (said_text, delivered_text) = (self.private_text(said), self.private_text(delivered));
+            if let Some(session) = self.conversations.get_mut(session_id) {
+                if let Some(turn) = session.turns.iter_mut().rev().find(|t| t.speaker == Speaker::Astra) {
+                    if turn.text == said_text {
+                        turn.text = delivered_text;
+                    }
+                }
+            }
+        }
+
+        // The newest open commitment behind each promise the user never heard
+        let mut cut_off: BTreeMap<&str, CommitmentId> = BTreeMap::new();
+        for commitment in self.commitments.open_for_user(Some(user_id)) {
+            if said.contains(&commitment.statement) && !delivered.contains(&commitment.statement) {
+                let id = cut_off.entry(&commitment.statement).or_insert(commitment.id);
+                *id = (*id).max(commitment.id);
+            }
+        }
+        let cut_off: Vec<CommitmentId> = cut_off.into_values().collect();
+        for id in cut_off {
+            if let Some(commitment) = self.commitments.withdraw(id) {
+                let _ = self.intent_manager.cancel_intent(commitment.intent_id);
+                self.narrative_memory.add_event(
+                    "commitment_withdrawn",
+                    format!("Commitment {} was cut off before the user heard it: {}", commitment.id, commitment.statement),
+                    Some(Metadata::from([("user_id", user_id.to_string())])),
+                );
+            }
+        }
+    }
+
+    /// Plans and speaks Astra's reply to `text` in a session negotiating a
+    /// request. The reply is recorded as Astra's turn; once the user agrees,
+    /// the Commit act is recorded as a commitment and the session's dialogue
+    /// ends. A reply the runtime's hooks veto leaves the dialogue as the
+    /// user's message left it.
+    pub fn plan_dialogue_turn(&mut self, session_id: &str, text: &str) -> Result<PlannedTurn, RuntimeError> {
+        let (ongoing, clarifying) = match self.conversations.get_mut(session_id) {
+            Some(session) => (session.dialogue.take(), session.pending_clarification.is_some()),
+            None => (None, false),
+        };
+
+        let agreement = read_agreement(text);
+        let mut understood = None;
+        let mut dialogue = match ongoing {
+            Some(mut dialogue) if dialogue.awaiting_agreement() && agreement.is_some() => {
+                self.record_conversation_turn(session_id, Speaker::User, text);
+                dialogue.hear_reply(agreement == Some(true));
+                dialogue
+            }
+            // Astra's own question, not one the NLP pipeline is waiting on
+            Some(mut dialogue) if dialogue.awaiting_answer() && !clarifying => {
+                self.record_conversation_turn(session_id, Speaker::User, text);
+                dialogue.hear_answer(text, None);
+                dialogue
+            }
+            ongoing => {
+                // Anything else answers a clarifying question or makes a new request
+                let answering = ongoing.as_ref().is_some_and(|dialogue| dialogue.awaiting_answer() && clarifying);
+                let question = match self.interpret_in_session(session_id, text)? {
+                    Interpretation::NeedsClarification(question) => Some(question),
+                    Interpretation::Understood(result) if self.is_request(&result) => {
+                        understood = Some(result.intent);
+                        None
+                    }
+                    Interpretation::Understood(result) => {
+                        // Small talk while a proposal waits leaves it waiting
+                        if !answering {
+                            self.session_mut(session_id).dialogue = ongoing;
+                        }
+                        return Ok(PlannedTurn { understood: Some(result.intent), ..Default::default() });
+                    }
+                };
+                match ongoing {
+                    Some(mut dialogue) if answering => {
+                        dialogue.hear_answer(text, question);
+                        dialogue
+                    }
+                    _ => Dialogue::new(text, question),
+                }
+            }
+        };
+
+        let heard = dialogue.clone();
+        let said = dialogue.take_turn()?;
+        let reply = said.iter().map(|(_, line)| line.as_str()).collect::<Vec<_>>().join(" ");
+        if !reply.is_empty() {
+            if let Err(vetoed) = self.review_response(&reply) {
+                self.session_mut(session_id).dialogue = Some(heard);
+                return Err(vetoed);
+            }
+            self.record_conversation_turn(session_id, Speaker::Astra, &reply);
+        }
+        let promise = said.iter().find(|(act, _)| *act == DialogueAct::Commit).map(|(_, line)| line.clone());
+        let acts: Vec<DialogueAct> = said.into_iter().map(|(act, _)| act).collect();
+        // The task is the user's own words
+        let user_id = self.conversations.get(session_id).and_then(|s| s.user_id.clone());
+        self.narrative_memory.add_event(
+            "dialogue_turn_planned",
+            format!(
+                "{} about '{}'",
+                acts.iter().map(|act| act.name()).collect::<Vec<_>>().join(", "),
+                dialogue.task()
+            ),
+            user_id.as_ref().map(|u| Metadata::from([("user_id", u.clone())])),
+        );
+
+        // The commitment is the Commit act itself, not whatever the reply
+        // happens to promise
+        let commitments = promise.map(|promise| self.record_commitment(&promise, user_id)).into_iter().collect();
+        if !dialogue.is_committed() {
+            self.session_mut(session_id).dialogue = Some(dialogue);
+        }
+        Ok(PlannedTurn { acts, reply, commitments, understood })
+    }
+
+    /// Whether `result` asks Astra to do something, as opposed to greeting
+    /// it or asking it a question.
+    fn is_request(&self, result: &NlpResult) -> bool {
+        self.nlp
+            .schemas
+            .iter()
+            .any(|schema| schema.intent == result.intent && !schema.required_slots.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_is_negotiated_into_a_commitment() {
+        use DialogueAct::*;
+
+        let mut runtime = Runtime::new();
+        runtime.begin_conversation("s1", Some("ana".into()));
+        let mut turn = |text: &str| runtime.plan_dialogue_turn("s1", text).unwrap();
+
+        let first = turn("Please remind me to water the plants");
+        assert_eq!(first.acts, [Acknowledge, Ask]);
+        assert_eq!(first.reply, "Got it. When should that be?");
+        assert_eq!(turn("tomorrow at 9am").acts, [Inform, Propose]);
+        assert_eq!(turn("No").reply, "What should I change?");
+        assert_eq!(turn("make it 10am").acts, [Inform, Propose]);
+        let last = turn("Yes please");
+        assert_eq!(last.acts, [Commit]);
+        assert_eq!(last.commitments.len(), 1);
+
+        let session = &runtime.conversations["s1"];
+        assert!(session.dialogue.is_none());
+        assert_eq!(session.turns.len(), 10);
+        let commitment = runtime.commitments.get(last.commitments[0]).unwrap();
+        assert!(commitment.statement.contains("water the plants; tomorrow at 9am; make it 10am"));
+
+        // Small talk is left to other responders
+        assert_eq!(
+            runtime.plan_dialogue_turn("s1", "hello").unwrap(),
+            PlannedTurn { understood: Some("greeting".into()), ..Default::default() }
+        );
+
+        // A promise cut off mid-stream was never made
+        runtime.cut_reply_short("ana", Some("s1"), &last.reply, "");
+        assert!(runtime.commitments.get(last.commitments[0]).is_none());
+        assert_eq!(runtime.conversations["s1"].turns[9].text, "");
+    }
+}
@@ -0,0 +1,184 @@
+// =============================================================================
+//  Astra AGI - Runtime Errors
+//  File: error.rs
+//
+//  Description:
+//  The error type of the runtime's public operations. Unknown intents and
+//  commitments, plans submitted or confirmed in the wrong state, rejected
+//  programs, programs denied capabilities or failing while they run, and
+//  failed message interpretation are separate variants; planning, parsing,
+//  and evaluation failures are wrapped with their original error. Behavior
+//  scripts add unknown script names and files that cannot be read.
+//  Knowledge packs fail with their own error type, naming the pack or file
+//  that was unreadable, malformed, tampered with, or not trusted, or the fact
+//  in it the knowledge base would refuse. Plans and
+//  responses an embedding application's hooks veto are refused as vetoed.
+//  A runtime builder given parts that do not fit together reports which.
+//  The write-ahead log fails with its own error type, for logs that cannot
+//  be opened, written, or read back, and for knowledge logged over what was
+//  recovered before the host restored it. Backups fail with theirs, naming the
+//  archive that is missing or unreadable and the file that fails its digest.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use std::path::PathBuf;
+
+use astra_lang::{Capability, EvalError};
+use thiserror::Error;
+
+use crate::knowledge::error::KnowledgeError;
+use crate::knowledge::vector_store::VectorStoreError;
+use crate::planning::error::PlanningError;
+use crate::runtime::commitments::{CommitmentId, CommitmentStatus};
+use crate::runtime::executor::ParseError;
+use crate::runtime::intent_manager::IntentId;
+
+#[derive(Debug, Error)]
+pub enum RuntimeError {
+    #[error("Intent ID {0} not found")]
+    IntentNotFound(IntentId),
+    #[error("Commitment ID {0} not found")]
+    CommitmentNotFound(CommitmentId),
+    #[error("Commitment ID {id} is already {status:?}")]
+    CommitmentResolved { id: CommitmentId, status: CommitmentStatus },
+    #[error("Intent {0} already has a running plan")]
+    PlanAlreadyRunning(IntentId),
+    #[error("Intent {0} has no running plan")]
+    NoRunningPlan(IntentId),
+    #[error(transparent)]
+    Planning(#[from] PlanningError),
+    /// The program was rejected; the parse error carries its diagnostics
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    /// The program needs capabilities the runtime does not grant programs
+    #[error("Program needs capabilities that are not granted: {}", listed(.0))]
+    CapabilitiesDenied(Vec<Capability>),
+    /// The program stopped with an error while running
+    #[error(transparent)]
+    Eval(#[from] EvalError),
+    #[error("No behavior script named '{0}'")]
+    UnknownScript(String),
+    #[error("Could not read behavior script {}", path.display())]
+    ScriptUnreadable { path: PathBuf, #[source] source: std::io::Error },
+    #[error("Could not interpret the message")]
+    Interpretation(#[source] anyhow::Error),
+    #[error(transparent)]
+    Retrieval(#[from] VectorStoreError),
+    #[error(transparent)]
+    KnowledgePack(#[from] KnowledgePackError),
+    /// A hook of the embedding application refused it, for this reason
+    #[error("Vetoed by a runtime hook: {0}")]
+    Vetoed(String),
+    #[error("No action executor was configured for plans submitted without one")]
+    NoActionExecutor,
+    #[error("Runtime is misconfigured")]
+    Config(#[from] RuntimeConfigError),
+    #[error(transparent)]
+    Wal(#[from] WalError),
+    #[error(transparent)]
+    Backup(#[from] BackupError),
+}
+
+/// Parts given to a `RuntimeBuilder` that do not make a working runtime.
+#[derive(Debug, Error)]
+pub enum RuntimeConfigError {
+    #[error("Plan concurrency must be at least 1")]
+    NoPlanConcurrency,
+    #[error("Tick budget must be longer than zero")]
+    ZeroTickBudget,
+    #[error("Narrative memory must hold at least one event")]
+    NoMemoryCapacity,
+    #[error("The stimulus queue must hold at least one stimulus")]
+    NoStimulusCapacity,
+    /// Every pack would be refused
+    #[error("Knowledge packs must be signed but no signing key is trusted")]
+    NoTrustedPackKeys,
+    #[error("Knowledge pack directory {} does not exist", .0.display())]
+    PackDirectoryMissing(PathBuf),
+    #[error("Planning domain {} could not be loaded", path.display())]
+    PlanningDomain { path: PathBuf, #[source] source: PlanningError },
+}
+
+/// Why a knowledge pack was not loaded.
+#[derive(Debug, Error)]
+pub enum KnowledgePackError {
+    #[error("Knowledge pack '{0}' is not in the pack directory")]
+    NotFound(String),
+    #[error("Could not read {}", path.display())]
+    Unreadable { path: PathBuf, #[source] source: std::io::Error },
+    #[error("{} is not valid pack JSON", path.display())]
+    Malformed { path: PathBuf, #[source] source: serde_json::Error },
+    #[error("{} is not a valid planning domain", path.display())]
+    InvalidDomain { path: PathBuf, #[source] source: PlanningError },
+    /// A fact the knowledge base would refuse, so the pack is not installed
+    #[error("Fact {index} of {} is invalid", path.display())]
+    InvalidFact { path: PathBuf, index: usize, #[source] source: KnowledgeError },
+    #[error("'{0}' is not a pack version")]
+    InvalidVersion(String),
+    #[error("'{0}' is not a hex Ed25519 public key")]
+    InvalidKey(String),
+    #[error("Knowledge pack '{pack}' lists '{path}', which is outside the pack")]
+    PathOutsidePack { pack: String, path: String },
+    /// The file's contents do not match the digest in the manifest
+    #[error("{} in knowledge pack '{pack}' does not match its digest", path.display())]
+    DigestMismatch { pack: String, path: PathBuf },
+    #[error("Knowledge pack '{0}' is unsigned and unsigned packs are not allowed")]
+    Unsigned(String),
+    /// Malformed, or not made by any trusted key
+    #[error("Knowledge pack '{0}' is not signed by a trusted key")]
+    BadSignature(String),
+}
+
+/// Why the write-ahead log could not be kept or read.
+#[derive(Debug, Error)]
+pub enum WalError {
+    #[error("Could not open write-ahead log {}", path.display())]
+    Open { path: PathBuf, #[source] source: std::io::Error },
+    #[error("Could not write to the write-ahead log")]
+    Write(#[source] std::io::Error),
+    #[error("Could not encode a write-ahead log record")]
+    Encode(#[source] serde_json::Error),
+    #[error("{} is not a write-ahead log", .0.display())]
+    NotALog(PathBuf),
+    /// Intact but unreadable, as a log from an incompatible version would be
+    #[error("Write-ahead log record at byte {offset} cannot be read")]
+    Undecodable { offset: u64, #[source] source: serde_json::Error },
+    /// Logging the host's knowledge now would write over what was recovered
+    #[error("Knowledge recovered from the write-ahead log has not been restored")]
+    KnowledgeNotRestored,
+}
+
+/// Why a backup could not be taken, verified, or restored.
+#[derive(Debug, Error)]
+pub enum BackupError {
+    #[error("No backup directory is configured")]
+    NotConfigured,
+    #[error("Could not access {}", path.display())]
+    Io { path: PathBuf, #[source] source: std::io::Error },
+    #[error("{} is not a backup archive", .0.display())]
+    NotABackup(PathBuf),
+    #[error("Manifest of {} cannot be read or written", path.display())]
+    Manifest { path: PathBuf, #[source] source: serde_json::Error },
+    #[error("Backup {0} is not in the backup directory")]
+    NotFound(u64),
+    #[error("There are no backups in {}", .0.display())]
+    NoBackups(PathBuf),
+    /// The stored contents do not match the digest in the manifest
+    #[error("'{path}' of store '{store}' in backup {id} does not match its digest")]
+    Corrupt { id: u64, store: String, path: String },
+    /// A manifest names a file outside its store
+    #[error("Backup names '{0}', which is outside its store")]
+    UnsafePath(String),
+    /// A manifest names a store that would not restore under `into`
+    #[error("Backup names the store '{0}', which is not a plain directory name")]
+    UnsafeStore(String),
+}
+
+fn listed(capabilities: &[Capability]) -> String {
+    capabilities.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", ")
+}
@@ -10,23 +10,59 @@
 //
 //  This design enables safe, modular, and adaptive AGI program execution,
 //  with future support for concurrency, backtracking, and effect management.
+//  Programs are parsed by the astra_lang parser; malformed source is rejected
+//  with positioned diagnostics rather than aborting the runtime. Static
+//  analysis runs before execution: programs with semantic errors are rejected
+//  the same way, and the capabilities a program needs are kept with its AST.
+//  Programs run on the astra_lang interpreter against a host that supplies
+//  builtins, limited to the capabilities granted to them. Imported modules
+//  are found through a configurable resolver, and parsed sources are cached
+//  by content hash across runs. A program that awaits an event or sleeps is
+//  parked in a waiting context instead of blocking; it resumes once the event
+//  is signaled or its deadline passes. Applications embedding the runtime
+//  register Rust functions as builtins; programs call them, and plans can
+//  run them as actions, subject to the capabilities they are tagged with.
+//  Only the most recent contexts are kept: once there are too many, the
+//  oldest completed ones are dropped. So are parked programs: past a limit,
+//  the longest waiting is abandoned for the host to report.
 //
 //  Author:      Alex Roussinov
 //  Created:     2025-12-22
-//  Updated:     2025-12-25
+//  Updated:     2026-10-16
 //
 //  This file is dual licensed under the MIT and Apache 2.0 licenses.
 //  Please see the root level LICENSE-MIT and LICENSE-APACHE files for details.
 // =============================================================================
 
+use crate::planning::executor::ActionExecutor;
+use crate::planning::planner::Action;
 use crate::runtime::scheduler::Scheduler;
-use std::collections::{VecDeque};
+use astra_lang::builtins::Kind;
+use astra_lang::ffi::ForeignFunction;
+use astra_lang::modules::NoModules;
+use astra_lang::{
+    Args, Capability, Diagnostic, EvalError, FfiError, ForeignError, ForeignFunctions, Host, ModuleResolver, Poll, Suspension,
+    Task, UnitStore, Value, Wakeup,
+};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::time::{Instant};
+use thiserror::Error;
 
-/// Represents a node in the Abstract Syntax Tree (AST) or bytecode instruction.
-/// Placeholder struct; detailed AST structure to be defined by Astra_lang parser.
+/// Parsed sources kept before the cache is cleared.
+const MAX_CACHED_UNITS: usize = 256;
+/// Contexts kept before the oldest completed ones are dropped.
+const MAX_CONTEXTS: usize = 1024;
+/// Programs kept waiting before the longest waiting is abandoned.
+pub const MAX_SUSPENDED: usize = 256;
+
+/// Represents a parsed program: the top-level declarations produced by the
+/// astra_lang parser.
 #[derive(Clone)]
-pub struct AstNode;
+pub struct AstNode {
+    pub items: Vec<astra_lang::ast::AstNode>,
+    /// Capabilities static analysis found the program needs
+    pub capabilities: BTreeSet<Capability>,
+}
 
 /// Possible states of an execution context.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -54,48 +90,134 @@ pub struct Intent {
     pub description: String,
 }
 
+/// What became of a program the executor ran or resumed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgramStatus {
+    /// `main` returned this value
+    Finished(Value),
+    /// The program is parked in a waiting context until this happens
+    Suspended { context_id: usize, waiting_for: Suspension },
+}
+
+/// A program parked at an `await` or `sleep`.
+struct SuspendedProgram {
+    context_id: usize,
+    task: Task,
+    /// When the sleep ends or the event's timeout passes
+    deadline: Option<Instant>,
+    /// The awaited event, once it has been signaled
+    wakeup: Option<Wakeup>,
+}
+
 /// Core executor responsible for managing execution contexts and intents.
 pub struct Executor {
-    contexts: Vec<ExecutionContext>,
+    /// By id, oldest first
+    contexts: BTreeMap<usize, ExecutionContext>,
+    next_context: usize,
     intent_queue: VecDeque<Intent>,
     scheduler: Option<Scheduler>,  // Optional integration with Scheduler for multitasking
+    /// Finds the modules programs import
+    module_resolver: Box<dyn ModuleResolver + Send + Sync>,
+    /// Parsed programs and modules, reused while their source is unchanged
+    units: UnitStore,
+    /// Programs waiting for an event or for time to pass, longest waiting first
+    suspended: Vec<SuspendedProgram>,
+    /// Contexts of programs abandoned to keep `suspended` bounded, not yet
+    /// taken by the host
+    abandoned: Vec<usize>,
+    /// Builtins registered by the embedding application
+    foreign: ForeignFunctions,
 }
 
 impl Executor {
     /// Creates a new Executor with empty state.
     pub fn new() -> Self {
         Executor {
-            contexts: Vec::new(),
+            contexts: BTreeMap::new(),
+            next_context: 0,
             intent_queue: VecDeque::new(),
             scheduler: None,
+            module_resolver: Box::new(NoModules),
+            units: UnitStore::new(),
+            suspended: Vec::new(),
+            abandoned: Vec::new(),
+            foreign: ForeignFunctions::new(),
         }
     }
 
+    /// Sets where imported modules are looked up, for example a
+    /// `FileResolver` over a directory of `.astra` files. Until one is set,
+    /// programs that import anything are rejected.
+    pub fn set_module_resolver(&mut self, resolver: impl ModuleResolver + Send + Sync + 'static) {
+        self.module_resolver = Box::new(resolver);
+        self.units.clear();
+    }
+
+    /// Exposes a Rust function to Astra programs as the builtin `name`,
+    /// such as `weather.get`, callable only by programs granted
+    /// `capability`. Declare its signature on the result:
+    ///
+    /// ```ignore
+    /// executor
+    ///     .register_builtin("weather.get", Capability::Network, |args| Ok(forecast(&args.get::<String>(0)?).into()))?
+    ///     .params(&[Kind::Str])
+    ///     .returns(Kind::Float);
+    /// ```
+    ///
+    /// Programs parsed afterwards are checked against it and may call it.
+    /// Standard builtin names cannot be taken.
+    pub fn register_builtin(
+        &mut self,
+        name: &str,
+        capability: Capability,
+        function: impl Fn(&Args) -> Result<Value, ForeignError> + Send + Sync + 'static,
+    ) -> Result<&mut ForeignFunction, FfiError> {
+        self.foreign.register(name, capability, function)
+    }
+
+    /// Builtins registered with `register_builtin`.
+    pub fn foreign_functions(&self) -> &ForeignFunctions {
+        &self.foreign
+    }
+
+    /// An action executor for plans whose actions are registered builtins,
+    /// limited to the `granted` capabilities.
+    pub fn builtin_actions(&self, granted: &BTreeSet<Capability>) -> BuiltinActions {
+        BuiltinActions { foreign: self.foreign.clone(), granted: granted.clone() }
+    }
+
     /// Initializes or resets the executor state.
     pub fn start(&mut self) {
         self.contexts.clear();
         self.intent_queue.clear();
+        self.suspended.clear();
+        self.abandoned.clear();
     }
 
-    /// Parses an Astra source program into an AST.
-    /// This is a placeholder that should invoke the Astra_lang parser.
-    pub fn parse(&self, program: &str) -> Result<AstNode, ParseError> {
-        // TODO: Integrate Astra_lang parser here
-        Err(ParseError::new("Parsing not implemented"))
+    /// Parses and analyzes an Astra source program, and the modules it
+    /// imports, into an AST.
+    /// Any error diagnostic, from parsing, loading modules, or analysis,
+    /// rejects the program; the error carries all of them. Warnings are logged.
+    pub fn parse(&mut self, program: &str) -> Result<AstNode, ParseError> {
+        // Every distinct program is cached, so start over rather than grow forever
+        if self.units.len() >= MAX_CACHED_UNITS {
+            self.units.clear();
+        }
+        let (items, analysis) =
+            astra_lang::modules::analyze_program(program, self.module_resolver.as_ref(), &mut self.units, &self.foreign);
+        if analysis.has_errors() {
+            return Err(ParseError::from_diagnostics(analysis.errors().cloned().collect()));
+        }
+        for warning in analysis.warnings() {
+            log::warn!("Astra program: {}", warning);
+        }
+        Ok(AstNode { items, capabilities: analysis.capabilities })
     }
 
     /// Starts execution of an Astra program given its AST.
     /// Creates a new execution context and enqueues an intent.
     pub fn execute(&mut self, ast: &AstNode) {
-        let context_id = self.contexts.len();
-        let context = ExecutionContext {
-            id: context_id,
-            ast_node: ast.clone(),
-            state: ExecutionState::Running,
-            start_time: Instant::now(),
-            deadline: None,
-        };
-        self.contexts.push(context);
+        let context_id = self.open_context(ast);
         self.intent_queue.push_back(Intent {
             priority: 0,
             context_id,
@@ -104,12 +226,159 @@ impl Executor {
         });
     }
 
+    /// Runs a program's `main` against `host`, allowing only the `granted`
+    /// capabilities, until it finishes or suspends. A suspended program's
+    /// context is left waiting, with the deadline of its sleep or timeout;
+    /// otherwise it is completed, whether or not the program succeeded.
+    pub fn run(
+        &mut self,
+        ast: &AstNode,
+        host: &mut dyn Host,
+        granted: &BTreeSet<Capability>,
+    ) -> Result<ProgramStatus, EvalError> {
+        let context_id = self.open_context(ast);
+        let mut task = Task::new(ast.items.clone(), granted.clone()).with_foreign(self.foreign.clone());
+        let result = task.start(host);
+        self.settle(context_id, task, result)
+    }
+
+    /// Wakes every program awaiting `event`, handing it `payload`. They run
+    /// again when next resumed. Returns how many were waiting; an event
+    /// nobody awaits is dropped.
+    pub fn signal(&mut self, event: &str, payload: &str) -> usize {
+        let mut woken = 0;
+        for program in &mut self.suspended {
+            let awaits = matches!(program.task.waiting_for(), Some(Suspension::Event { name, .. }) if name == event);
+            if awaits && program.wakeup.is_none() {
+                program.wakeup = Some(Wakeup::Event(payload.to_string()));
+                woken += 1;
+            }
+        }
+        woken
+    }
+
+    /// Contexts of suspended programs that can resume at `now`: their event
+    /// was signaled, or their sleep or timeout is over.
+    pub fn ready_programs(&self, now: Instant) -> Vec<usize> {
+        self.suspended
+            .iter()
+            .filter(|p| p.wakeup.is_some() || p.deadline.is_some_and(|deadline| deadline <= now))
+            .map(|p| p.context_id)
+            .collect()
+    }
+
+    /// Resumes the program suspended in `context_id` if it is ready at
+    /// `now`, running it until it finishes or suspends again. Returns `None`
+    /// if no program there is ready.
+    pub fn resume(
+        &mut self,
+        context_id: usize,
+        host: &mut dyn Host,
+        now: Instant,
+    ) -> Option<Result<ProgramStatus, EvalError>> {
+        let index = self.suspended.iter().position(|p| p.context_id == context_id)?;
+        let program = &self.suspended[index];
+        let wakeup = match (&program.wakeup, program.task.waiting_for()) {
+            (Some(wakeup), _) => wakeup.clone(),
+            _ if !program.deadline.is_some_and(|deadline| deadline <= now) => return None,
+            (None, Some(Suspension::Sleep(_))) => Wakeup::Elapsed,
+            (None, _) => Wakeup::TimedOut,
+        };
+        let mut program = self.suspended.remove(index);
+        if let Some(context) = self.contexts.get_mut(&context_id) {
+            context.state = ExecutionState::Running;
+        }
+        let result = program.task.resume(host, wakeup);
+        Some(self.settle(context_id, program.task, result))
+    }
+
+    /// Records where a run left the program: completed, or parked until
+    /// what it waits for happens.
+    fn settle(&mut self, context_id: usize, task: Task, result: Result<Poll, EvalError>) -> Result<ProgramStatus, EvalError> {
+        let (state, deadline, status) = match result {
+            Ok(Poll::Suspended(waiting_for)) => {
+                let wait = match &waiting_for {
+                    Suspension::Sleep(duration) => Some(*duration),
+                    Suspension::Event { timeout, .. } => *timeout,
+                };
+                let deadline = wait.map(|wait| Instant::now() + wait);
+                if self.suspended.len() >= MAX_SUSPENDED {
+                    self.abandon_longest_waiting();
+                }
+                self.suspended.push(SuspendedProgram { context_id, task, deadline, wakeup: None });
+                (ExecutionState::Waiting, deadline, Ok(ProgramStatus::Suspended { context_id, waiting_for }))
+            }
+            Ok(Poll::Done(value)) => (ExecutionState::Completed, None, Ok(ProgramStatus::Finished(value))),
+            Err(error) => (ExecutionState::Completed, None, Err(error)),
+        };
+        if let Some(context) = self.contexts.get_mut(&context_id) {
+            context.state = state;
+            context.deadline = deadline;
+        }
+        status
+    }
+
+    /// Drops the program that has waited longest, completing its context.
+    fn abandon_longest_waiting(&mut self) {
+        let abandoned = self.suspended.remove(0);
+        if let Some(context) = self.contexts.get_mut(&abandoned.context_id) {
+            context.state = ExecutionState::Completed;
+            context.deadline = None;
+        }
+        self.abandoned.push(abandoned.context_id);
+    }
+
+    /// Contexts of the programs abandoned since last asked, because more
+    /// than `MAX_SUSPENDED` were waiting.
+    pub fn take_abandoned(&mut self) -> Vec<usize> {
+        std::mem::take(&mut self.abandoned)
+    }
+
+    /// Programs waiting for an event or for time to pass.
+    pub fn suspended_count(&self) -> usize {
+        self.suspended.len()
+    }
+
+    pub fn is_suspended(&self, context_id: usize) -> bool {
+        self.suspended.iter().any(|p| p.context_id == context_id)
+    }
+
+    /// Opens a running context for `ast` under a fresh id, dropping the
+    /// oldest completed contexts once more than `MAX_CONTEXTS` are kept.
+    fn open_context(&mut self, ast: &AstNode) -> usize {
+        let context_id = self.next_context;
+        self.next_context += 1;
+        self.contexts.insert(
+            context_id,
+            ExecutionContext {
+                id: context_id,
+                ast_node: ast.clone(),
+                state: ExecutionState::Running,
+                start_time: Instant::now(),
+                deadline: None,
+            },
+        );
+        while self.contexts.len() > MAX_CONTEXTS {
+            let oldest = self.contexts.iter().find(|(_, c)| c.state == ExecutionState::Completed).map(|(&id, _)| id);
+            match oldest {
+                Some(id) => self.contexts.remove(&id),
+                None => break,
+            };
+        }
+        context_id
+    }
+
+    /// Contexts currently kept.
+    pub fn context_count(&self) -> usize {
+        self.contexts.len()
+    }
+
     /// Advances execution by one step.
     /// Selects the highest priority intent and advances its context.
     /// Handles temporal constraints and rescheduling.
     pub fn tick(&mut self) {
         if let Some(intent) = self.intent_queue.pop_front() {
-            if let Some(context) = self.contexts.get_mut(intent.context_id) {
+            if let Some(context) = self.contexts.get_mut(&intent.context_id) {
                 // Placeholder for AST evaluation step
                 println!("Executing intent: {}", intent.description);
 
@@ -122,22 +391,62 @@ impl Executor {
     }
 }
 
+/// Carries out plan actions by calling the registered builtin each action's
+/// id names. A builtin that takes an argument is given the action's
+/// description. A `false` result or a failed call is a recoverable failure;
+/// an unknown builtin or a missing capability is critical.
+pub struct BuiltinActions {
+    foreign: ForeignFunctions,
+    granted: BTreeSet<Capability>,
+}
+
+impl ActionExecutor for BuiltinActions {
+    fn execute_action(&mut self, action: &Action) -> anyhow::Result<bool> {
+        let Some(function) = self.foreign.get(&action.id) else {
+            anyhow::bail!("no builtin named '{}'", action.id);
+        };
+        let shape = function.shape();
+        if let Some(capability) = shape.capability {
+            if !self.granted.contains(capability) {
+                anyhow::bail!("'{}' requires the {} capability, which was not granted", action.id, capability);
+            }
+        }
+        let args = match shape.params {
+            Some([]) => Vec::new(),
+            Some([Kind::Str | Kind::Any]) | None => vec![Value::Str(action.description.clone())],
+            Some(params) => anyhow::bail!("'{}' takes {} argument(s), which a plan action cannot supply", action.id, params.len()),
+        };
+        match function.call(&args) {
+            Ok(result) => Ok(result != Value::Bool(false)),
+            Err(message) => {
+                log::warn!("Plan action '{}' failed: {}", action.id, message);
+                Ok(false)
+            }
+        }
+    }
+}
+
 /// Custom error type for parsing failures.
-#[derive(Debug)]
+#[derive(Debug, Error)]
+#[error("ParseError: {details}")]
 pub struct ParseError {
     details: String,
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl ParseError {
     pub fn new(msg: &str) -> Self {
-        ParseError { details: msg.to_string() }
+        ParseError { details: msg.to_string(), diagnostics: Vec::new() }
+    }
+
+    pub fn from_diagnostics(diagnostics: Vec<Diagnostic>) -> Self {
+        let details = diagnostics.iter().map(|d| d.to_string()).collect::<Vec<_>>().join("; ");
+        ParseError { details, diagnostics }
     }
-}
 
-impl std::fmt::Display for ParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "ParseError: {}", self.details)
+    /// Positioned problems found in the source, in source order.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
     }
 }
 
-impl std::error::Error for ParseError {}
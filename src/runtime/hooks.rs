@@ -0,0 +1,171 @@
+// =============================================================================
+//  Astra AGI - Runtime Hooks
+//  File: hooks.rs
+//
+//  Description:
+//  Lifecycle hooks for applications that embed the runtime as a library.
+//  A host registers any number of `RuntimeHooks`; the runtime calls them
+//  when a tick starts and ends, when an intent is created, when a plan is
+//  selected for an intent, and when a response is about to go out. Every
+//  hook but the end of a tick can veto what is about to happen: a vetoed
+//  tick does nothing, a vetoed intent is cancelled, and a vetoed plan or
+//  response is refused with `RuntimeError::Vetoed`. Hooks are asked in the
+//  order they were added and the first veto stops the rest from being
+//  asked. Vetoes of intents, plans, and responses are recorded in narrative
+//  memory with their reason.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use crate::cognition::LoadSignals;
+use crate::planning::planner::Plan;
+use crate::runtime::intent_manager::Intent;
+
+/// What a hook wants done about the event it was told of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookDecision {
+    Allow,
+    /// Stop it from happening, for the given reason
+    Veto(String),
+}
+
+/// Observes, and may veto, what the runtime does. Every method defaults to
+/// allowing, so a host implements only those it cares about.
+pub trait RuntimeHooks: Send {
+    /// Before a tick does anything; a veto skips the tick.
+    fn on_tick_start(&mut self) -> HookDecision {
+        HookDecision::Allow
+    }
+
+    /// After a tick, with the load it left the runtime under.
+    fn on_tick_end(&mut self, _signals: &LoadSignals) {}
+
+    /// After an intent is created, before anything acts on it; a veto
+    /// cancels it.
+    fn on_intent_created(&mut self, _intent: &Intent) -> HookDecision {
+        HookDecision::Allow
+    }
+
+    /// Before a plan is queued for an intent; a veto refuses the plan.
+    fn on_plan_selected(&mut self, _intent: &Intent, _plan: &Plan) -> HookDecision {
+        HookDecision::Allow
+    }
+
+    /// Before a reply is sent to a user; a veto withholds it.
+    fn on_response_generated(&mut self, _reply: &str) -> HookDecision {
+        HookDecision::Allow
+    }
+}
+
+/// Asks each hook in turn, returning the reason of the first veto.
+pub(crate) fn veto_reason(
+    hooks: &mut [Box<dyn RuntimeHooks>],
+    mut ask: impl FnMut(&mut dyn RuntimeHooks) -> HookDecision,
+) -> Option<String> {
+    hooks.iter_mut().find_map(|hook| match ask(hook.as_mut()) {
+        HookDecision::Allow => None,
+        HookDecision::Veto(reason) => Some(reason),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planning::executor::ActionExecutor;
+    use crate::planning::planner::Action;
+    use crate::runtime::error::RuntimeError;
+    use crate::runtime::intent_manager::IntentState;
+    use crate::runtime::Runtime;
+    use std::sync::{Arc, Mutex};
+
+    /// Records what it is told of and vetoes anything mentioning "forbidden".
+    struct Gatekeeper {
+        seen: Arc<Mutex<Vec<String>>>,
+        block_ticks: bool,
+    }
+
+    impl Gatekeeper {
+        fn decide(&self, event: String) -> HookDecision {
+            let forbidden = event.contains("forbidden");
+            self.seen.lock().unwrap().push(event);
+            if forbidden {
+                HookDecision::Veto("mentions something forbidden".into())
+            } else {
+                HookDecision::Allow
+            }
+        }
+    }
+
+    impl RuntimeHooks for Gatekeeper {
+        fn on_tick_start(&mut self) -> HookDecision {
+            self.seen.lock().unwrap().push("tick_start".into());
+            if self.block_ticks {
+                HookDecision::Veto("maintenance window".into())
+            } else {
+                HookDecision::Allow
+            }
+        }
+
+        fn on_tick_end(&mut self, _signals: &LoadSignals) {
+            self.seen.lock().unwrap().push("tick_end".into());
+        }
+
+        fn on_intent_created(&mut self, intent: &Intent) -> HookDecision {
+            self.decide(format!("intent {}", intent.description))
+        }
+
+        fn on_plan_selected(&mut self, _intent: &Intent, plan: &Plan) -> HookDecision {
+            self.decide(format!("plan {}", plan.goal_id))
+        }
+
+        fn on_response_generated(&mut self, reply: &str) -> HookDecision {
+            self.decide(format!("reply {}", reply))
+        }
+    }
+
+    struct Idle;
+
+    impl ActionExecutor for Idle {
+        fn execute_action(&mut self, _action: &Action) -> anyhow::Result<bool> {
+            Ok(true)
+        }
+    }
+
+    #[test]
+    fn test_hooks_observe_and_veto() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut runtime = Runtime::new();
+        runtime.add_hooks(Gatekeeper { seen: Arc::clone(&seen), block_ticks: false });
+
+        let (allowed, _) = runtime.create_intent_from_utterance("water the plants", 5, None);
+        let (vetoed, _) = runtime.create_intent_from_utterance("open the forbidden door", 5, None);
+        assert_eq!(runtime.intent_manager.get_intent(allowed).unwrap().state, IntentState::Pending);
+        assert_eq!(runtime.intent_manager.get_intent(vetoed).unwrap().state, IntentState::Cancelled);
+
+        let plan = |goal: &str| Plan { goal_id: goal.into(), actions: Vec::new(), estimated_cost: 0.0 };
+        assert!(matches!(
+            runtime.submit_plan(allowed, plan("forbidden_garden"), Box::new(Idle)),
+            Err(RuntimeError::Vetoed(_))
+        ));
+        runtime.submit_plan(allowed, plan("garden"), Box::new(Idle)).unwrap();
+
+        assert!(runtime.review_response("Done!").is_ok());
+        assert!(runtime.review_response("Here is the forbidden answer").is_err());
+
+        runtime.tick();
+        assert!(seen.lock().unwrap().ends_with(&["tick_start".to_string(), "tick_end".to_string()]));
+        assert!(runtime.narrative_memory.events.iter().any(|e| e.event_type == "hook_veto"));
+
+        // A vetoed tick does nothing, not even end
+        let mut runtime = Runtime::new();
+        runtime.add_hooks(Gatekeeper { seen: Arc::clone(&seen), block_ticks: true });
+        let events = runtime.narrative_memory.events.len();
+        runtime.tick();
+        assert_eq!(seen.lock().unwrap().last().map(String::as_str), Some("tick_start"));
+        assert_eq!(runtime.narrative_memory.events.len(), events);
+    }
+}
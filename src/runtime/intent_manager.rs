@@ -5,66 +5,110 @@
 //  Description:
 //  Manages the lifecycle of intents—Astra's goals and tasks.
 //  Extended to accept and store rich task metadata such as ethical importance,
-//  enabling affective and value-based prioritization.
+//  enabling affective and value-based prioritization. Metadata is structured
+//  (crate::metadata), so numbers and entity references stay typed.
 //
 //  This enhancement allows Astra to reason about tasks with nuanced context,
 //  aligning behavior with human values and ethical considerations.
+//  Deadlines can also be set from wall-clock times inferred from user utterances,
+//  and plan progress is mirrored into intent metadata as it is reported.
+//  Creation times and deadlines are AstraTime values, ordered and wall-clock based,
+//  read from an injected clock so deadlines can be reached in fast-forward.
+//  Intent ids, states, and scheduling order come from the no_std core
+//  (core::intent) and are re-exported here. Intents serialize, so the
+//  write-ahead log can record them, and are restored from it by id. They
+//  are logged in a versioned envelope, so intents of an earlier shape
+//  migrate when replayed. Version 1 intents kept numbers in metadata as
+//  strings; version 2 keeps them as numbers, and replaying an older intent
+//  turns its numeric strings into numbers.
 //
 //  Author:      Alex Roussinov
 //  Created:     2025-12-25
-//  Updated:     2026-01-02
+//  Updated:     2026-10-16
 //
 //  This file is dual licensed under the MIT and Apache 2.0 licenses.
 // =============================================================================
 
 use std::collections::{HashMap, BinaryHeap};
 use std::cmp::Ordering;
-use std::time::{Duration, Instant};
-
-/// Unique identifier for an Intent.
-pub type IntentId = u64;
-
-/// Represents the current state of an intent.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum IntentState {
-    Pending,
-    Active,
-    Completed,
-    Cancelled,
-}
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::metadata::{MetaValue, Metadata};
+use crate::migration::{MigrationRegistry, Versioned};
+use crate::time::{system_clock, AstraTime, SharedClock};
+use super::error::RuntimeError;
+
+pub use crate::core::intent::{IntentId, IntentState, ScheduleKey};
 
 /// Core data structure representing an Intent.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Intent {
     pub id: IntentId,
     pub description: String,
     pub priority: u32,               // Higher = more urgent
-    pub created_at: Instant,
-    pub deadline: Option<Instant>,  // Optional deadline for completion
+    pub created_at: AstraTime,
+    pub deadline: Option<AstraTime>,  // Optional deadline for completion
     pub duration: Option<Duration>, // Estimated time to complete
     pub state: IntentState,
-    pub metadata: HashMap<String, String>, // Flexible key-value for extensibility
+    pub metadata: Metadata, // Flexible key-value for extensibility
+}
+
+impl Versioned for Intent {
+    const FORMAT: &'static str = "astra.intent";
+    const VERSION: u32 = 2;
+
+    fn migrations(registry: &mut MigrationRegistry) {
+        registry.register(Self::FORMAT, 1, |mut data| {
+            if let Some(metadata) = data.get_mut("metadata").and_then(serde_json::Value::as_object_mut) {
+                for value in metadata.values_mut() {
+                    if let Some(number) = value.as_str().and_then(numeric_string) {
+                        *value = serde_json::Value::Number(number);
+                    }
+                }
+            }
+            Ok(data)
+        });
+    }
+}
+
+/// The number `text` spells, if it spells one exactly as a number would
+/// print, so "12" becomes 12 but "007" and "1e3" stay text.
+fn numeric_string(text: &str) -> Option<serde_json::Number> {
+    let number = if let Ok(n) = text.parse::<u64>() {
+        serde_json::Number::from(n)
+    } else if let Ok(n) = text.parse::<i64>() {
+        serde_json::Number::from(n)
+    } else {
+        serde_json::Number::from_f64(text.parse::<f64>().ok()?)?
+    };
+    (number.to_string() == text).then_some(number)
 }
 
 impl Intent {
-    /// Creates a new Intent with mandatory fields.
-    pub fn new(id: IntentId, description: impl Into<String>, priority: u32) -> Self {
+    /// Creates a new Intent with mandatory fields, created at `created_at`.
+    pub fn new(id: IntentId, description: impl Into<String>, priority: u32, created_at: AstraTime) -> Self {
         Intent {
             id,
             description: description.into(),
             priority,
-            created_at: Instant::now(),
+            created_at,
             deadline: None,
             duration: None,
             state: IntentState::Pending,
-            metadata: HashMap::new(),
+            metadata: Metadata::new(),
         }
     }
 
-    /// Checks if the intent is overdue based on current time.
-    pub fn is_overdue(&self) -> bool {
+    /// What decides when the intent is scheduled.
+    pub fn schedule_key(&self) -> ScheduleKey<AstraTime> {
+        ScheduleKey { priority: self.priority, deadline: self.deadline, created_at: self.created_at }
+    }
+
+    /// Checks if the intent is overdue at `now`.
+    pub fn is_overdue_at(&self, now: AstraTime) -> bool {
         if let Some(deadline) = self.deadline {
-            Instant::now() > deadline && self.state != IntentState::Completed
+            now > deadline && self.state != IntentState::Completed
         } else {
             false
         }
@@ -91,16 +135,7 @@ impl PartialOrd for IntentWrapper {
 
 impl Ord for IntentWrapper {
     fn cmp(&self, other: &Self) -> Ordering {
-        other.0.priority.cmp(&self.0.priority)
-            .then_with(|| {
-                match (self.0.deadline, other.0.deadline) {
-                    (Some(a), Some(b)) => a.cmp(&b),
-                    (Some(_), None) => Ordering::Less,
-                    (None, Some(_)) => Ordering::Greater,
-                    (None, None) => Ordering::Equal,
-                }
-            })
-            .then_with(|| self.0.created_at.cmp(&other.0.created_at))
+        self.0.schedule_key().cmp(&other.0.schedule_key())
     }
 }
 
@@ -109,6 +144,8 @@ pub struct IntentManager {
     intents: HashMap<IntentId, Intent>,
     priority_queue: BinaryHeap<IntentWrapper>,
     next_id: IntentId,
+    /// Stamps creation times and anchors deadlines
+    clock: SharedClock,
 }
 
 impl IntentManager {
@@ -118,15 +155,26 @@ impl IntentManager {
             intents: HashMap::new(),
             priority_queue: BinaryHeap::new(),
             next_id: 1,
+            clock: system_clock(),
         }
     }
 
+    /// Reads the time from `clock` from now on.
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
+    /// The current time on this manager's clock.
+    pub fn now(&self) -> AstraTime {
+        self.clock.now()
+    }
+
     /// Creates and adds a new intent with optional metadata, returning its unique ID.
-    pub fn create_intent_with_metadata(&mut self, description: impl Into<String>, priority: u32, metadata: Option<HashMap<String, String>>) -> IntentId {
+    pub fn create_intent_with_metadata(&mut self, description: impl Into<String>, priority: u32, metadata: Option<Metadata>) -> IntentId {
         let id = self.next_id;
         self.next_id += 1;
 
-        let mut intent = Intent::new(id, description, priority);
+        let mut intent = Intent::new(id, description, priority, self.clock.now());
         if let Some(meta) = metadata {
             intent.metadata = meta;
         }
@@ -137,7 +185,7 @@ impl IntentManager {
     }
 
     /// Updates an existing intent's priority, deadline, or state.
-    pub fn update_intent(&mut self, id: IntentId, priority: Option<u32>, deadline: Option<Option<Instant>>, state: Option<IntentState>) -> Result<(), String> {
+    pub fn update_intent(&mut self, id: IntentId, priority: Option<u32>, deadline: Option<Option<AstraTime>>, state: Option<IntentState>) -> Result<(), RuntimeError> {
         if let Some(intent) = self.intents.get_mut(&id) {
             if let Some(p) = priority {
                 intent.priority = p;
@@ -151,17 +199,50 @@ impl IntentManager {
             self.rebuild_priority_queue();
             Ok(())
         } else {
-            Err(format!("Intent ID {} not found", id))
+            Err(RuntimeError::IntentNotFound(id))
+        }
+    }
+
+    /// Sets an intent's deadline from a wall-clock time, as produced by the
+    /// temporal parser. `now` is the wall-clock reference the deadline was
+    /// computed against; deadlines already in the past become due immediately.
+    /// The wall-clock value is kept in the `deadline_utc` metadata entry.
+    pub fn set_wall_clock_deadline(
+        &mut self,
+        id: IntentId,
+        deadline: Option<DateTime<Utc>>,
+        duration: Option<Duration>,
+        now: DateTime<Utc>,
+    ) -> Result<(), RuntimeError> {
+        let intent = self.intents.get_mut(&id).ok_or(RuntimeError::IntentNotFound(id))?;
+
+        if let Some(deadline) = deadline {
+            let remaining = (deadline - now).to_std().unwrap_or(Duration::ZERO);
+            intent.deadline = Some(self.clock.now() + remaining);
+            intent.metadata.insert("deadline_utc", deadline.to_rfc3339());
         }
+        if duration.is_some() {
+            intent.duration = duration;
+        }
+
+        self.rebuild_priority_queue();
+        Ok(())
+    }
+
+    /// Sets one metadata entry on an intent, replacing any previous value.
+    pub fn set_metadata(&mut self, id: IntentId, key: impl Into<String>, value: impl Into<MetaValue>) -> Result<(), RuntimeError> {
+        let intent = self.intents.get_mut(&id).ok_or(RuntimeError::IntentNotFound(id))?;
+        intent.metadata.insert(key, value);
+        Ok(())
     }
 
     /// Marks an intent as completed.
-    pub fn complete_intent(&mut self, id: IntentId) -> Result<(), String> {
+    pub fn complete_intent(&mut self, id: IntentId) -> Result<(), RuntimeError> {
         self.update_intent(id, None, None, Some(IntentState::Completed))
     }
 
     /// Cancels an intent.
-    pub fn cancel_intent(&mut self, id: IntentId) -> Result<(), String> {
+    pub fn cancel_intent(&mut self, id: IntentId) -> Result<(), RuntimeError> {
         self.update_intent(id, None, None, Some(IntentState::Cancelled))
     }
 
@@ -175,6 +256,21 @@ impl IntentManager {
         None
     }
 
+    /// Puts back an intent as it was recorded, replacing any intent with
+    /// the same id. New intents are numbered after it.
+    pub fn restore_intent(&mut self, intent: Intent) {
+        self.next_id = self.next_id.max(intent.id + 1);
+        self.intents.insert(intent.id, intent);
+        self.rebuild_priority_queue();
+    }
+
+    /// Removes an intent altogether, returning it if it existed.
+    pub fn remove_intent(&mut self, id: IntentId) -> Option<Intent> {
+        let intent = self.intents.remove(&id)?;
+        self.rebuild_priority_queue();
+        Some(intent)
+    }
+
     /// Returns a reference to an intent by ID.
     pub fn get_intent(&self, id: IntentId) -> Option<&Intent> {
         self.intents.get(&id)
@@ -186,7 +282,7 @@ impl IntentManager {
     }
 
     /// Rebuilds the priority queue from the intents map.
-    fn rebuild_priority_queue(&mut self) {
+    pub fn rebuild_priority_queue(&mut self) {
         self.priority_queue.clear();
         for intent in self.intents.values() {
             self.priority_queue.push(IntentWrapper(intent.clone()));
@@ -197,17 +293,62 @@ impl IntentManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::time::{Clock, MockClock};
+    use std::sync::Arc;
 
     #[test]
     fn test_intent_creation_and_metadata() {
         let mut im = IntentManager::new();
-        let mut metadata = HashMap::new();
-        metadata.insert("ethical_importance".to_string(), "high".to_string());
+        let metadata = Metadata::from([("ethical_importance", "high")]);
         let id = im.create_intent_with_metadata("Complete report", 10, Some(metadata));
         let intent = im.get_intent(id).expect("Intent should exist");
         assert_eq!(intent.description, "Complete report");
         assert_eq!(intent.priority, 10);
         assert_eq!(intent.state, IntentState::Pending);
-        assert_eq!(intent.metadata.get("ethical_importance").unwrap(), "high");
+        assert_eq!(intent.metadata.get_str("ethical_importance"), Some("high"));
+    }
+
+    #[test]
+    fn test_wall_clock_deadline() {
+        let mut im = IntentManager::new();
+        let id = im.create_intent_with_metadata("Send invoice", 5, None);
+        let now = Utc::now();
+        let deadline = now + chrono::Duration::hours(2);
+
+        im.set_wall_clock_deadline(id, Some(deadline), Some(Duration::from_secs(600)), now).unwrap();
+
+        let intent = im.get_intent(id).unwrap();
+        let remaining = intent.deadline.unwrap().saturating_duration_since(AstraTime::now());
+        assert!(remaining > Duration::from_secs(7100) && remaining <= Duration::from_secs(7200));
+        assert_eq!(intent.duration, Some(Duration::from_secs(600)));
+        assert_eq!(intent.metadata.get_str("deadline_utc"), Some(deadline.to_rfc3339().as_str()));
+        assert!(matches!(im.set_wall_clock_deadline(999, None, None, now), Err(RuntimeError::IntentNotFound(999))));
+    }
+
+    #[test]
+    fn test_version_1_intents_migrate_numeric_strings() {
+        let v1 = r#"{"format": "astra.intent", "version": 1, "data": {
+            "id": 4, "description": "Follow up", "priority": 2, "created_at": 0, "deadline": null,
+            "duration": null, "state": "Pending",
+            "metadata": {"origin_event": "12", "progress": "0.5", "code": "007", "deadline_utc": "2026-10-16T09:00:00+00:00"}}}"#;
+        let intent: Intent = crate::migration::decode(v1.as_bytes()).unwrap();
+        assert_eq!(intent.metadata.get_u64("origin_event"), Some(12));
+        assert_eq!(intent.metadata.get("progress").and_then(MetaValue::as_f64), Some(0.5));
+        assert_eq!(intent.metadata.get_str("code"), Some("007"));
+        assert_eq!(intent.metadata.get_str("deadline_utc"), Some("2026-10-16T09:00:00+00:00"));
+    }
+
+    #[test]
+    fn test_deadlines_follow_injected_clock() {
+        let clock = MockClock::new();
+        let mut im = IntentManager::new();
+        im.set_clock(Arc::new(clock.clone()));
+        let id = im.create_intent_with_metadata("Water the plants", 5, None);
+        let now = clock.now_utc();
+        im.set_wall_clock_deadline(id, Some(now + chrono::Duration::hours(1)), None, now).unwrap();
+
+        assert!(!im.get_intent(id).unwrap().is_overdue_at(im.now()));
+        clock.advance(Duration::from_secs(2 * 60 * 60));
+        assert!(im.get_intent(id).unwrap().is_overdue_at(im.now()));
     }
 }
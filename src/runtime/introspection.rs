@@ -0,0 +1,219 @@
+// =============================================================================
+//  Astra AGI - Introspection
+//  File: introspection.rs
+//
+//  Description:
+//  Structured self-report of the runtime's current inner state: emotion and
+//  mood, the most important intents, the goal and plan being pursued, recent
+//  belief changes, the biases steering its strategies, and how confident it
+//  feels overall. The report is plain data, serializable to JSON, and backs
+//  the API `/introspect` endpoint and the CLI `:self` command. Hosts that run
+//  a cognitive loop can fold its state in for the active goal and planning
+//  biases.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::cognition::CognitiveState;
+use crate::knowledge::belief_ledger::RevisionOutcome;
+use crate::runtime::intent_manager::{IntentId, IntentState};
+use crate::runtime::plan_runner::ProgressPhase;
+use crate::runtime::Runtime;
+
+/// How many intents and belief changes a report lists.
+const REPORT_LIMIT: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmotionReport {
+    pub valence: f32,
+    pub arousal: f32,
+    /// 0 (negative) to 1 (positive)
+    pub mood: f32,
+    pub urgency: f32,
+    pub motivation: f32,
+    pub stress: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntentSummary {
+    pub id: IntentId,
+    pub description: String,
+    pub priority: u32,
+    pub state: String,
+    /// Fraction of its plan completed, if a plan was submitted
+    pub progress: Option<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanSummary {
+    pub goal_id: String,
+    pub completed_actions: usize,
+    pub total_actions: usize,
+    pub phase: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeliefChange {
+    pub subject: u64,
+    pub predicate: String,
+    pub object: String,
+    pub old_confidence: f32,
+    pub new_confidence: f32,
+    pub accepted: bool,
+    pub method: String,
+}
+
+/// Snapshot of what Astra is feeling, doing, and believing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelfReport {
+    pub emotion: EmotionReport,
+    /// Highest-priority open intents, most important first
+    pub top_intents: Vec<IntentSummary>,
+    pub active_goal: Option<String>,
+    pub active_plan: Option<PlanSummary>,
+    /// Latest belief revisions, newest first
+    pub recent_belief_changes: Vec<BeliefChange>,
+    /// Named biases currently shaping strategy choices, e.g. risk tolerance
+    pub strategy_biases: BTreeMap<String, f32>,
+    /// Overall confidence in 0..1
+    pub confidence: f32,
+}
+
+impl SelfReport {
+    /// Adds what only the cognitive loop knows: the goal it is pursuing, its
+    /// plan, and its planning strategy biases. Confidence blends in its mood.
+    pub fn with_cognition(mut self, state: &CognitiveState) -> Self {
+        if let Some(goal) = &state.context.active_goal {
+            self.active_goal = Some(goal.id.clone());
+        }
+        if let Some(plan) = &state.context.active_plan {
+            self.active_plan.get_or_insert(PlanSummary {
+                goal_id: plan.goal_id.clone(),
+                completed_actions: 0,
+                total_actions: plan.actions.len(),
+                phase: "unknown".to_string(),
+            });
+        }
+        self.strategy_biases.insert("goap_bias".into(), state.heuristics.goap_bias);
+        self.strategy_biases.insert("htn_bias".into(), state.heuristics.htn_bias);
+        self.strategy_biases.insert("reactive_bias".into(), state.heuristics.reactive_bias);
+        self.strategy_biases.insert("bt_bias".into(), state.heuristics.bt_bias);
+        self.confidence = (0.8 * self.confidence + 0.2 * state.mood.baseline).clamp(0.0, 1.0);
+        self
+    }
+}
+
+impl Runtime {
+    /// Builds a structured report of the runtime's current state.
+    pub fn introspect(&self) -> SelfReport {
+        let mood = self.affect.mood.baseline;
+        let emotion = EmotionReport {
+            valence: self.affect.current.valence(),
+            arousal: self.affect.current.arousal(),
+            mood,
+            urgency: self.emotion_state.urgency,
+            motivation: self.emotion_state.motivation,
+            stress: self.emotion_state.stress,
+        };
+
+        let plans = self.plan_runner.progress();
+        let mut open: Vec<_> = self
+            .intent_manager
+            .all_intents()
+            .into_iter()
+            .filter(|i| matches!(i.state, IntentState::Pending | IntentState::Active))
+            .collect();
+        open.sort_by(|a, b| b.priority.cmp(&a.priority).then(a.id.cmp(&b.id)));
+        let top_intents = open
+            .iter()
+            .take(REPORT_LIMIT)
+            .map(|intent| IntentSummary {
+                id: intent.id,
+                description: intent.description.clone(),
+                priority: intent.priority,
+                state: format!("{:?}", intent.state),
+                progress: plans.iter().find(|p| p.intent_id == intent.id).map(|p| p.fraction()),
+            })
+            .collect();
+
+        // The goal being pursued is that of the most important running plan.
+        let active = open
+            .iter()
+            .filter_map(|intent| plans.iter().find(|p| p.intent_id == intent.id))
+            .find(|p| p.phase != ProgressPhase::Waiting && p.phase != ProgressPhase::Finished);
+        let active_goal = active.map(|p| p.goal_id.clone());
+        let active_plan = active.map(|p| PlanSummary {
+            goal_id: p.goal_id.clone(),
+            completed_actions: p.completed_actions,
+            total_actions: p.total_actions,
+            phase: p.phase.as_str().to_string(),
+        });
+
+        let recent_belief_changes = self
+            .belief_ledger
+            .entries()
+            .iter()
+            .rev()
+            .take(REPORT_LIMIT)
+            .map(|revision| BeliefChange {
+                subject: revision.fact.subject,
+                predicate: revision.fact.predicate.clone(),
+                object: revision.fact.object.clone(),
+                old_confidence: revision.old_confidence,
+                new_confidence: revision.new_confidence,
+                accepted: revision.outcome == RevisionOutcome::Accepted,
+                method: revision.method.clone(),
+            })
+            .collect();
+
+        let mut strategy_biases = BTreeMap::new();
+        strategy_biases.insert("risk_tolerance".to_string(), self.preference_model.risk_tolerance);
+        strategy_biases.insert("verbosity".to_string(), self.preference_model.verbosity);
+        strategy_biases.insert("formality".to_string(), self.preference_model.formality);
+
+        // Confident when focused, rested, calm, and in a good mood.
+        let confidence = (self.energy.focus * 0.4
+            + (1.0 - self.energy.fatigue) * 0.25
+            + (1.0 - self.emotion_state.stress) * 0.15
+            + mood * 0.2)
+            .clamp(0.0, 1.0);
+
+        SelfReport {
+            emotion,
+            top_intents,
+            active_goal,
+            active_plan,
+            recent_belief_changes,
+            strategy_biases,
+            confidence,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_lists_intents_by_priority_and_serializes() {
+        let mut runtime = Runtime::new();
+        runtime.intent_manager.create_intent_with_metadata("Water the plants", 3, None);
+        let urgent = runtime.intent_manager.create_intent_with_metadata("Reply to Sam", 9, None);
+
+        let report = runtime.introspect();
+        assert_eq!(report.top_intents[0].id, urgent);
+        assert!(report.active_goal.is_none());
+        assert!((0.0..=1.0).contains(&report.confidence));
+
+        let json = serde_json::to_string(&report).unwrap();
+        let parsed: SelfReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, report);
+    }
+}
@@ -0,0 +1,153 @@
+// =============================================================================
+//  Astra AGI - Knowledge Exports
+//  File: knowledge_exports.rs
+//
+//  Description:
+//  Keeps copies of the shared knowledge base outside Astra up to date. A
+//  graph export (see knowledge/cypher_export.rs) and, with the `sqlite`
+//  feature, a relational mirror (see knowledge/sqlite_mirror.rs) each keep
+//  their place in the change feed; every tick sends them what changed since,
+//  and nothing when nothing did. Operators can force a sync with an admin
+//  command. A failed sync is logged and retried on the next tick from where
+//  the last good one left off.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use crate::knowledge::cypher_export::{CypherExporter, CypherSink, ExportReport};
+use crate::knowledge::error::KnowledgeError;
+use crate::knowledge::extended_ontology::OntologyManager;
+use crate::knowledge::snapshots::SharedKnowledge;
+#[cfg(feature = "sqlite")]
+use crate::knowledge::sqlite_mirror::{SqliteMirror, SyncReport};
+use crate::runtime::Runtime;
+
+/// Where the runtime sends knowledge changes.
+#[derive(Default)]
+pub struct KnowledgeExports {
+    graph: Option<(CypherExporter, Box<dyn CypherSink + Send>)>,
+    #[cfg(feature = "sqlite")]
+    mirror: Option<SqliteMirror>,
+}
+
+/// What one sync sent; `None` for a target that is not configured or was
+/// already up to date.
+#[derive(Debug, Default)]
+pub struct ExportSync {
+    pub graph: Option<Result<ExportReport, KnowledgeError>>,
+    #[cfg(feature = "sqlite")]
+    pub mirror: Option<Result<SyncReport, KnowledgeError>>,
+}
+
+impl ExportSync {
+    /// The failures, as text for logs and operators.
+    pub fn failures(&self) -> Vec<String> {
+        let mut failures = Vec::new();
+        if let Some(Err(e)) = &self.graph {
+            failures.push(format!("graph export: {}", e));
+        }
+        #[cfg(feature = "sqlite")]
+        if let Some(Err(e)) = &self.mirror {
+            failures.push(format!("SQLite mirror: {}", e));
+        }
+        failures
+    }
+}
+
+impl Runtime {
+    /// Sends the shared knowledge base to `sink` as Cypher each tick,
+    /// starting from wherever `exporter` left off.
+    pub fn set_graph_export(&mut self, exporter: CypherExporter, sink: impl CypherSink + Send + 'static) {
+        self.knowledge_exports.graph = Some((exporter, Box::new(sink)));
+    }
+
+    /// The graph exporter and its place in the change feed, to save it
+    /// across restarts.
+    pub fn graph_exporter(&self) -> Option<&CypherExporter> {
+        self.knowledge_exports.graph.as_ref().map(|(exporter, _)| exporter)
+    }
+
+    /// Mirrors the shared knowledge base into `mirror` each tick.
+    #[cfg(feature = "sqlite")]
+    pub fn set_sqlite_mirror(&mut self, mirror: SqliteMirror) {
+        self.knowledge_exports.mirror = Some(mirror);
+    }
+
+    /// Brings every configured export up to date with the shared knowledge
+    /// base. Targets already at its latest change are left alone.
+    pub fn sync_knowledge_exports(&mut self) -> ExportSync {
+        let mut sync = ExportSync::default();
+        let Some(snapshot) = self.knowledge.as_ref().map(SharedKnowledge::snapshot) else {
+            return sync;
+        };
+        let knowledge: &OntologyManager = &snapshot;
+        let (latest, version) = (knowledge.latest_change(), knowledge.current_version());
+
+        if let Some((exporter, sink)) = &mut self.knowledge_exports.graph {
+            if exporter.cursor != latest || exporter.version != Some(version) {
+                sync.graph = Some(exporter.export(knowledge, sink.as_mut()));
+            }
+        }
+        #[cfg(feature = "sqlite")]
+        if let Some(mirror) = &mut self.knowledge_exports.mirror {
+            let current = matches!((mirror.cursor(), mirror.version()), (Ok(c), Ok(v)) if c == latest && v == Some(version));
+            if !current {
+                sync.mirror = Some(mirror.sync(knowledge));
+            }
+        }
+
+        for failure in sync.failures() {
+            log::error!("Could not sync knowledge: {}", failure);
+        }
+        sync
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::extended_ontology::{name_entity_id, Fact, Provenance};
+    use std::sync::{Arc, Mutex};
+
+    /// A sink the test can still read once the runtime owns it.
+    struct Shared(Arc<Mutex<Vec<String>>>);
+
+    impl CypherSink for Shared {
+        fn run(&mut self, statement: &str) -> Result<(), KnowledgeError> {
+            self.0.lock().unwrap().push(statement.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_exports_follow_the_shared_knowledge_base() {
+        let fact = |object: &str| Fact {
+            subject: name_entity_id("ana"),
+            predicate: "likes".to_string(),
+            object: object.to_string(),
+            confidence: 0.8,
+            provenance: Provenance::new("user:ana", None),
+        };
+        let knowledge = SharedKnowledge::new(OntologyManager::new());
+        knowledge.write(|kb| kb.add_fact(fact("tea")));
+        let statements = Arc::new(Mutex::new(Vec::new()));
+        let mut runtime = Runtime::new();
+        runtime.set_knowledge(knowledge.clone());
+        runtime.set_graph_export(CypherExporter::new(), Shared(Arc::clone(&statements)));
+
+        let first = runtime.sync_knowledge_exports().graph.unwrap().unwrap();
+        assert!(first.full);
+        assert_eq!(first.facts, 1);
+        assert!(runtime.sync_knowledge_exports().graph.is_none(), "nothing changed");
+
+        // Ticks pick up writes by themselves
+        knowledge.write(|kb| kb.add_fact(fact("coffee")));
+        runtime.tick();
+        assert_eq!(runtime.graph_exporter().unwrap().cursor, knowledge.snapshot().latest_change());
+        assert!(statements.lock().unwrap().last().unwrap().contains("coffee"));
+    }
+}
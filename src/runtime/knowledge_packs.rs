@@ -0,0 +1,519 @@
+// =============================================================================
+//  Astra AGI - Knowledge Packs
+//  File: knowledge_packs.rs
+//
+//  Description:
+//  Bundled knowledge that gets a fresh runtime going: core concepts,
+//  common-sense facts, and default planning domains, shipped as versioned
+//  packs. A pack is a directory with a `manifest.json` naming the pack, its
+//  version, and each of its files with the SHA-256 digest of its contents,
+//  optionally signed with Ed25519 over the rest of the manifest. A pack is
+//  only loaded if every file matches its digest and the signature is by a
+//  trusted key; unsigned packs are refused unless explicitly allowed. A
+//  pack with a fact the knowledge base would refuse is not installed.
+//  Concepts and facts are added to the knowledge base the first time a
+//  version of a pack is seen, and the installed version is recorded there
+//  as a fact, so later starts and older versions add nothing. Planning
+//  domains are not kept in the knowledge base and are loaded on every
+//  start. Which packs, and which kinds of content, are loaded is
+//  configurable.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::knowledge::extended_ontology::{name_entity_id, Confidence, Fact, OntologyManager, Provenance};
+use crate::planning::domain::Domain;
+use crate::runtime::error::KnowledgePackError;
+
+/// File in each pack directory describing the pack.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Predicate of the fact recording which version of a pack is installed.
+pub const INSTALLED_VERSION: &str = "installed_version";
+
+/// What a pack file holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PackContent {
+    /// A JSON list of concepts, each with the concept it is a kind of
+    Concepts,
+    /// A JSON list of subject-predicate-object facts
+    Facts,
+    /// A planning domain in PDDL
+    Domain,
+}
+
+/// A pack version, `major.minor.patch`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PackVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl FromStr for PackVersion {
+    type Err = KnowledgePackError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || KnowledgePackError::InvalidVersion(text.to_string());
+        let mut parts = text.trim().split('.').map(|part| part.parse::<u32>().map_err(|_| invalid()));
+        let version = PackVersion {
+            major: parts.next().ok_or_else(invalid)??,
+            minor: parts.next().unwrap_or(Ok(0))?,
+            patch: parts.next().unwrap_or(Ok(0))?,
+        };
+        match parts.next() {
+            Some(_) => Err(invalid()),
+            None => Ok(version),
+        }
+    }
+}
+
+impl TryFrom<String> for PackVersion {
+    type Error = KnowledgePackError;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
+
+impl From<PackVersion> for String {
+    fn from(version: PackVersion) -> String {
+        version.to_string()
+    }
+}
+
+impl fmt::Display for PackVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// A file in a pack, relative to the pack directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackFile {
+    pub path: String,
+    pub content: PackContent,
+    /// Lowercase hex SHA-256 of the file's bytes
+    pub sha256: String,
+}
+
+/// The contents of a pack's `manifest.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackManifest {
+    pub name: String,
+    pub version: PackVersion,
+    #[serde(default)]
+    pub description: String,
+    pub files: Vec<PackFile>,
+    /// Hex Ed25519 signature over `signed_bytes`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl PackManifest {
+    /// What the signature covers: the manifest as JSON without its signature.
+    pub fn signed_bytes(&self) -> Vec<u8> {
+        let unsigned = PackManifest { signature: None, ..self.clone() };
+        serde_json::to_vec(&unsigned).expect("manifest serializes")
+    }
+}
+
+/// One concept in a concepts file.
+#[derive(Debug, Clone, Deserialize)]
+struct PackConcept {
+    name: String,
+    #[serde(default)]
+    is_a: Vec<String>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+/// One fact in a facts file. Subjects are names, given ids by `name_entity_id`.
+#[derive(Debug, Clone, Deserialize)]
+struct PackFact {
+    subject: String,
+    predicate: String,
+    object: String,
+    #[serde(default = "full_confidence")]
+    confidence: Confidence,
+}
+
+fn full_confidence() -> Confidence {
+    1.0
+}
+
+/// Which packs to load, from where, and whom to trust.
+#[derive(Debug, Clone)]
+pub struct KnowledgePackConfig {
+    /// Directory holding one subdirectory per pack
+    pub dir: PathBuf,
+    /// Packs to load by name; `None` loads every pack in `dir`
+    pub packs: Option<BTreeSet<String>>,
+    /// Kinds of content to load from the selected packs
+    pub contents: BTreeSet<PackContent>,
+    /// Keys whose signatures are accepted
+    pub trusted_keys: Vec<VerifyingKey>,
+    /// Loads packs without a signature, checking only their digests. Off
+    /// unless the host opts in, as it must to load the bundled packs,
+    /// which are unsigned.
+    pub allow_unsigned: bool,
+}
+
+impl Default for KnowledgePackConfig {
+    /// Every pack in `packs/`, all content, signatures required.
+    fn default() -> Self {
+        KnowledgePackConfig {
+            dir: PathBuf::from("packs"),
+            packs: None,
+            contents: BTreeSet::from([PackContent::Concepts, PackContent::Facts, PackContent::Domain]),
+            trusted_keys: Vec::new(),
+            allow_unsigned: false,
+        }
+    }
+}
+
+/// A pack directory whose manifest has been read but not yet checked.
+#[derive(Debug, Clone)]
+pub struct KnowledgePack {
+    pub dir: PathBuf,
+    pub manifest: PackManifest,
+}
+
+impl KnowledgePack {
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, KnowledgePackError> {
+        let dir = dir.as_ref().to_path_buf();
+        let path = dir.join(MANIFEST_FILE);
+        let text = fs::read_to_string(&path).map_err(|source| KnowledgePackError::Unreadable { path: path.clone(), source })?;
+        let manifest = serde_json::from_str(&text).map_err(|source| KnowledgePackError::Malformed { path, source })?;
+        Ok(KnowledgePack { dir, manifest })
+    }
+
+    /// Checks the signature against `config`, then every file's digest.
+    /// Returns the contents of the files, in manifest order.
+    pub fn verify(&self, config: &KnowledgePackConfig) -> Result<Vec<(&PackFile, Vec<u8>)>, KnowledgePackError> {
+        let name = &self.manifest.name;
+        match &self.manifest.signature {
+            Some(signature) => {
+                let bad = || KnowledgePackError::BadSignature(name.clone());
+                let bytes: [u8; 64] = decode_hex(signature).and_then(|bytes| bytes.try_into().ok()).ok_or_else(bad)?;
+                let signature = Signature::from_bytes(&bytes);
+                let signed = self.manifest.signed_bytes();
+                if !config.trusted_keys.iter().any(|key| key.verify_strict(&signed, &signature).is_ok()) {
+                    return Err(bad());
+                }
+            }
+            None if config.allow_unsigned => {}
+            None => return Err(KnowledgePackError::Unsigned(name.clone())),
+        }
+
+        self.manifest
+            .files
+            .iter()
+            .map(|file| {
+                let path = self.file_path(file)?;
+                let bytes = fs::read(&path).map_err(|source| KnowledgePackError::Unreadable { path: path.clone(), source })?;
+                if sha256_hex(&bytes) != file.sha256.to_ascii_lowercase() {
+                    return Err(KnowledgePackError::DigestMismatch { pack: name.clone(), path });
+                }
+                Ok((file, bytes))
+            })
+            .collect()
+    }
+
+    /// A file's path, refusing paths that would leave the pack directory.
+    fn file_path(&self, file: &PackFile) -> Result<PathBuf, KnowledgePackError> {
+        let relative = Path::new(&file.path);
+        if !relative.components().all(|component| matches!(component, Component::Normal(_))) {
+            return Err(KnowledgePackError::PathOutsidePack { pack: self.manifest.name.clone(), path: file.path.clone() });
+        }
+        Ok(self.dir.join(relative))
+    }
+}
+
+/// What loading packs did.
+#[derive(Debug, Clone, Default)]
+pub struct PackLoadReport {
+    /// Packs whose concepts and facts were added, at the version added
+    pub installed: Vec<(String, PackVersion)>,
+    /// Packs already installed at this version or a later one
+    pub already_installed: Vec<(String, PackVersion)>,
+    /// Facts added, including those derived from concepts
+    pub facts_added: usize,
+    /// Planning domains parsed from the selected packs
+    pub domains: Vec<Domain>,
+}
+
+/// A verified pack with its files parsed, ready to add.
+struct ParsedPack {
+    manifest: PackManifest,
+    facts: Vec<Fact>,
+    domains: Vec<Domain>,
+}
+
+impl ParsedPack {
+    fn parse(pack: &KnowledgePack, config: &KnowledgePackConfig) -> Result<Self, KnowledgePackError> {
+        let mut parsed = ParsedPack { manifest: pack.manifest.clone(), facts: Vec::new(), domains: Vec::new() };
+        let source = format!("pack:{}@{}", pack.manifest.name, pack.manifest.version);
+        let fact = |subject: &str, predicate: String, object: String, confidence: Confidence| Fact {
+            subject: name_entity_id(subject),
+            predicate,
+            object,
+            confidence,
+            provenance: Provenance::new(source.clone(), None),
+        };
+        for (file, bytes) in pack.verify(config)?.into_iter().filter(|(file, _)| config.contents.contains(&file.content)) {
+            let path = || pack.dir.join(&file.path);
+            match file.content {
+                PackContent::Concepts => {
+                    let concepts: Vec<PackConcept> =
+                        serde_json::from_slice(&bytes).map_err(|source| KnowledgePackError::Malformed { path: path(), source })?;
+                    for concept in concepts {
+                        for parent in &concept.is_a {
+                            parsed.facts.push(fact(&concept.name, "is_a".to_string(), parent.clone(), 1.0));
+                        }
+                        if let Some(description) = concept.description {
+                            parsed.facts.push(fact(&concept.name, "description".to_string(), description, 1.0));
+                        }
+                    }
+                }
+                PackContent::Facts => {
+                    let facts: Vec<PackFact> =
+                        serde_json::from_slice(&bytes).map_err(|source| KnowledgePackError::Malformed { path: path(), source })?;
+                    // One bad fact keeps the whole pack out, rather than installing it without
+                    for (index, f) in facts.into_iter().enumerate() {
+                        let f = fact(&f.subject, f.predicate, f.object, f.confidence);
+                        f.validate().map_err(|source| KnowledgePackError::InvalidFact { path: path(), index, source })?;
+                        parsed.facts.push(f);
+                    }
+                }
+                PackContent::Domain => {
+                    let text = String::from_utf8_lossy(&bytes);
+                    let domain = Domain::parse(&text).map_err(|source| KnowledgePackError::InvalidDomain { path: path(), source })?;
+                    parsed.domains.push(domain);
+                }
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// Loads the packs `config` selects into `knowledge`. Every selected pack is
+/// checked and parsed before anything is added, so a pack that fails leaves
+/// the knowledge base as it was.
+pub fn load_packs(config: &KnowledgePackConfig, knowledge: &mut OntologyManager) -> Result<PackLoadReport, KnowledgePackError> {
+    let packs = pack_dirs(config)?
+        .into_iter()
+        .map(|dir| ParsedPack::parse(&KnowledgePack::open(dir)?, config))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut report = PackLoadReport::default();
+    let mut known: HashSet<(u64, String, String)> =
+        knowledge.query_facts(None).into_iter().map(|f| (f.subject, f.predicate.clone(), f.object.clone())).collect();
+    for pack in packs {
+        let ParsedPack { manifest, facts, domains } = pack;
+        report.domains.extend(domains);
+        if let Some(installed) = installed_version(knowledge, &manifest.name).filter(|installed| *installed >= manifest.version) {
+            report.already_installed.push((manifest.name, installed));
+            continue;
+        }
+
+        // Facts an earlier version of the pack, or anything else, already added are skipped
+        let facts: Vec<Fact> =
+            facts.into_iter().filter(|f| known.insert((f.subject, f.predicate.clone(), f.object.clone()))).collect();
+        report.facts_added += knowledge.add_facts_bulk(facts).succeeded();
+        knowledge.add_fact(Fact {
+            subject: pack_entity_id(&manifest.name),
+            predicate: INSTALLED_VERSION.to_string(),
+            object: manifest.version.to_string(),
+            confidence: 1.0,
+            provenance: Provenance::new(format!("pack:{}@{}", manifest.name, manifest.version), None),
+        });
+        report.installed.push((manifest.name, manifest.version));
+    }
+    Ok(report)
+}
+
+/// The latest version of a pack recorded as installed in `knowledge`.
+pub fn installed_version(knowledge: &OntologyManager, pack: &str) -> Option<PackVersion> {
+    let subject = pack_entity_id(pack);
+    knowledge
+        .query_facts(None)
+        .into_iter()
+        .filter(|fact| fact.subject == subject && fact.predicate == INSTALLED_VERSION)
+        .filter_map(|fact| fact.object.parse().ok())
+        .max()
+}
+
+fn pack_entity_id(pack: &str) -> u64 {
+    name_entity_id(&format!("knowledge pack {}", pack))
+}
+
+/// Directories of the selected packs, by name. A pack named in the config
+/// but missing from the directory is an error.
+fn pack_dirs(config: &KnowledgePackConfig) -> Result<Vec<PathBuf>, KnowledgePackError> {
+    match &config.packs {
+        Some(names) => names
+            .iter()
+            .map(|name| {
+                let dir = config.dir.join(name);
+                if dir.join(MANIFEST_FILE).is_file() {
+                    Ok(dir)
+                } else {
+                    Err(KnowledgePackError::NotFound(name.clone()))
+                }
+            })
+            .collect(),
+        None => {
+            let entries = fs::read_dir(&config.dir).map_err(|source| KnowledgePackError::Unreadable { path: config.dir.clone(), source })?;
+            let mut dirs: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|dir| dir.join(MANIFEST_FILE).is_file())
+                .collect();
+            dirs.sort();
+            Ok(dirs)
+        }
+    }
+}
+
+/// A public key to trust pack signatures by, given as 64 hex digits.
+pub fn parse_verifying_key(hex: &str) -> Result<VerifyingKey, KnowledgePackError> {
+    let invalid = || KnowledgePackError::InvalidKey(hex.to_string());
+    let bytes: [u8; 32] = decode_hex(hex.trim()).and_then(|bytes| bytes.try_into().ok()).ok_or_else(invalid)?;
+    VerifyingKey::from_bytes(&bytes).map_err(|_| invalid())
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len()).step_by(2).map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn write_pack(dir: &Path, version: &str, key: Option<&SigningKey>) -> PackManifest {
+        fs::create_dir_all(dir).unwrap();
+        let concepts = r#"[{"name": "dog", "is_a": ["animal"], "description": "A domesticated canine"}]"#;
+        let facts = r#"[{"subject": "water", "predicate": "is", "object": "wet"},
+                        {"subject": "ice", "predicate": "is", "object": "cold", "confidence": 0.9}]"#;
+        let domain = "(define (domain doors) (:predicates (open ?d)) (:action open :parameters (?d) :effect (open ?d)))";
+        let mut files = Vec::new();
+        for (path, content, text) in
+            [("concepts.json", PackContent::Concepts, concepts), ("facts.json", PackContent::Facts, facts), ("doors.pddl", PackContent::Domain, domain)]
+        {
+            fs::write(dir.join(path), text).unwrap();
+            files.push(PackFile { path: path.into(), content, sha256: sha256_hex(text.as_bytes()) });
+        }
+        let mut manifest =
+            PackManifest { name: "core".into(), version: version.parse().unwrap(), description: String::new(), files, signature: None };
+        if let Some(key) = key {
+            let signature = key.sign(&manifest.signed_bytes()).to_bytes();
+            manifest.signature = Some(signature.iter().map(|byte| format!("{:02x}", byte)).collect());
+        }
+        fs::write(dir.join(MANIFEST_FILE), serde_json::to_string_pretty(&manifest).unwrap()).unwrap();
+        manifest
+    }
+
+    #[test]
+    fn test_packs_are_verified_and_installed_once() {
+        let root = std::env::temp_dir().join(format!("astra_packs_{}", std::process::id()));
+        let key = SigningKey::from_bytes(&[7; 32]);
+        write_pack(&root.join("core"), "1.0.0", Some(&key));
+        let mut config = KnowledgePackConfig { dir: root.clone(), ..KnowledgePackConfig::default() };
+        let mut knowledge = OntologyManager::new();
+
+        // Signed by a key nobody trusts
+        assert!(matches!(load_packs(&config, &mut knowledge), Err(KnowledgePackError::BadSignature(_))));
+        let hex: String = key.verifying_key().to_bytes().iter().map(|byte| format!("{:02x}", byte)).collect();
+        config.trusted_keys.push(parse_verifying_key(&hex).unwrap());
+        assert!(matches!(parse_verifying_key("abc"), Err(KnowledgePackError::InvalidKey(_))));
+
+        let report = load_packs(&config, &mut knowledge).unwrap();
+        assert_eq!(report.installed, vec![("core".to_string(), "1.0.0".parse().unwrap())]);
+        assert_eq!(report.facts_added, 4);
+        assert_eq!(report.domains[0].name, "doors");
+        assert_eq!(installed_version(&knowledge, "core"), Some("1.0".parse().unwrap()));
+        let is_a = knowledge.query_facts(None).into_iter().find(|f| f.predicate == "is_a").unwrap().clone();
+        assert_eq!((is_a.subject, is_a.object.as_str()), (name_entity_id("dog"), "animal"));
+        assert_eq!(is_a.provenance.source_name, "pack:core@1.0.0");
+
+        // Later starts only load the domains
+        let facts = knowledge.query_facts(None).len();
+        let report = load_packs(&config, &mut knowledge).unwrap();
+        assert!(report.installed.is_empty());
+        assert_eq!(report.domains.len(), 1);
+        assert_eq!(knowledge.query_facts(None).len(), facts);
+
+        // Selective loading by content and by pack
+        config.contents = BTreeSet::from([PackContent::Facts]);
+        assert!(load_packs(&config, &mut knowledge).unwrap().domains.is_empty());
+        config.packs = Some(BTreeSet::from(["missing".to_string()]));
+        assert!(matches!(load_packs(&config, &mut knowledge), Err(KnowledgePackError::NotFound(_))));
+        config.packs = None;
+
+        // A newer version installs without repeating facts already held
+        write_pack(&root.join("core"), "1.1.0", Some(&key));
+        let report = load_packs(&config, &mut knowledge).unwrap();
+        assert_eq!(report.installed.len(), 1);
+        assert_eq!(report.facts_added, 0);
+        assert_eq!(installed_version(&knowledge, "core"), Some("1.1.0".parse().unwrap()));
+
+        // Tampered contents and unsigned packs are refused without adding anything
+        fs::write(root.join("core").join("facts.json"), "[]").unwrap();
+        assert!(matches!(load_packs(&config, &mut knowledge), Err(KnowledgePackError::DigestMismatch { .. })));
+        write_pack(&root.join("core"), "2.0.0", None);
+        assert!(matches!(load_packs(&config, &mut knowledge), Err(KnowledgePackError::Unsigned(_))));
+        assert_eq!(installed_version(&knowledge, "core"), Some("1.1.0".parse().unwrap()));
+
+        // So is a pack with a fact the knowledge base would refuse
+        config.allow_unsigned = true;
+        let mut manifest = write_pack(&root.join("core"), "2.0.0", None);
+        let facts = r#"[{"subject": "fire", "predicate": "is", "object": "hot", "confidence": 1.5}]"#;
+        fs::write(root.join("core").join("facts.json"), facts).unwrap();
+        manifest.files[1].sha256 = sha256_hex(facts.as_bytes());
+        fs::write(root.join("core").join(MANIFEST_FILE), serde_json::to_string(&manifest).unwrap()).unwrap();
+        let facts = knowledge.query_facts(None).len();
+        assert!(matches!(load_packs(&config, &mut knowledge), Err(KnowledgePackError::InvalidFact { index: 0, .. })));
+        assert_eq!(knowledge.query_facts(None).len(), facts);
+        assert_eq!(installed_version(&knowledge, "core"), Some("1.1.0".parse().unwrap()));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_bundled_packs_load() {
+        let config = KnowledgePackConfig {
+            dir: Path::new(env!("CARGO_MANIFEST_DIR")).join("packs"),
+            allow_unsigned: true,
+            ..KnowledgePackConfig::default()
+        };
+        let mut knowledge = OntologyManager::new();
+        let report = load_packs(&config, &mut knowledge).unwrap();
+        assert!(report.installed.iter().any(|(name, _)| name == "core"));
+        assert!(report.facts_added > 0);
+        assert!(!report.domains.is_empty());
+    }
+}
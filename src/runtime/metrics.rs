@@ -0,0 +1,246 @@
+// =============================================================================
+//  Astra AGI - Runtime Metrics
+//  File: metrics.rs
+//
+//  Description:
+//  Health and behavior metrics in the Prometheus text exposition format, for
+//  the API `/metrics` endpoint. Tick durations go into a histogram and plan
+//  outcomes and crawled pages into counters as they happen; everything else
+//  (intent queue depth, plans running, knowledge and memory size, emotion
+//  and affect) is read from the runtime when scraped. Crawl rate and plan
+//  success over a window are left to PromQL `rate()` over the counters; the
+//  lifetime plan success ratio is exported directly for simple alerts.
+//  Stimuli shed or folded into a repeat when perception floods are counted,
+//  alongside how many wait.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use std::fmt::Write;
+
+use crate::knowledge::snapshots::SharedKnowledge;
+use crate::planning::quotas::QuotaResource;
+use crate::runtime::intent_manager::IntentState;
+use crate::runtime::Runtime;
+
+/// Upper bounds, in seconds, of the tick duration histogram's buckets.
+pub const TICK_BUCKETS: [f64; 10] = [0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5];
+
+/// Counts of observations at or below each bound, Prometheus style.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    bounds: Vec<f64>,
+    /// Per bucket, not cumulative; the last one is +Inf
+    counts: Vec<u64>,
+    sum: f64,
+}
+
+impl Histogram {
+    pub fn new(bounds: &[f64]) -> Self {
+        Histogram { bounds: bounds.to_vec(), counts: vec![0; bounds.len() + 1], sum: 0.0 }
+    }
+
+    pub fn observe(&mut self, value: f64) {
+        let bucket = self.bounds.iter().position(|bound| value <= *bound).unwrap_or(self.bounds.len());
+        self.counts[bucket] += 1;
+        self.sum += value;
+    }
+
+    pub fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    fn write(&self, out: &mut String, name: &str, help: &str) {
+        let _ = writeln!(out, "# HELP {} {}\n# TYPE {} histogram", name, help, name);
+        let mut cumulative = 0;
+        for (bound, count) in self.bounds.iter().zip(&self.counts) {
+            cumulative += count;
+            let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, bound, cumulative);
+        }
+        let _ = writeln!(out, "{}_bucket{{le=\"+Inf\"}} {}", name, self.count());
+        let _ = writeln!(out, "{}_sum {}\n{}_count {}", name, self.sum, name, self.count());
+    }
+}
+
+/// Metrics recorded as events happen, rather than read off the runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeMetrics {
+    pub tick_duration: Histogram,
+    pub plans_succeeded: u64,
+    pub plans_failed: u64,
+    /// Reported by the host's crawler through `Runtime::record_crawled_pages`
+    pub pages_crawled: u64,
+    /// Stimuli shed from a full stimulus queue
+    pub stimuli_dropped: u64,
+    /// Stimuli folded into the same stimulus already waiting
+    pub stimuli_merged: u64,
+    /// Stimuli the MQTT bridge shed before the runtime took them
+    pub mqtt_stimuli_dropped: u64,
+}
+
+impl Default for RuntimeMetrics {
+    fn default() -> Self {
+        RuntimeMetrics {
+            tick_duration: Histogram::new(&TICK_BUCKETS),
+            plans_succeeded: 0,
+            plans_failed: 0,
+            pages_crawled: 0,
+            stimuli_dropped: 0,
+            stimuli_merged: 0,
+            mqtt_stimuli_dropped: 0,
+        }
+    }
+}
+
+impl RuntimeMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Share of finished plans that completed, if any have finished.
+    pub fn plan_success_ratio(&self) -> Option<f64> {
+        let finished = self.plans_succeeded + self.plans_failed;
+        (finished > 0).then(|| self.plans_succeeded as f64 / finished as f64)
+    }
+}
+
+fn write_header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}\n# TYPE {} {}", name, help, name, kind);
+}
+
+fn write_sample(out: &mut String, name: &str, labels: &[(&str, &str)], value: f64) {
+    let labels: Vec<String> = labels.iter().map(|(key, value)| format!("{}=\"{}\"", key, value)).collect();
+    if labels.is_empty() {
+        let _ = writeln!(out, "{} {}", name, value);
+    } else {
+        let _ = writeln!(out, "{}{{{}}} {}", name, labels.join(","), value);
+    }
+}
+
+impl Runtime {
+    /// Adds pages fetched by the host's crawler to the crawl counter and,
+    /// as one request each, to the HTTP request quota.
+    pub fn record_crawled_pages(&mut self, pages: u64) {
+        self.metrics.pages_crawled += pages;
+        self.record_quota_usage(QuotaResource::HttpRequests, pages);
+    }
+
+    /// All metrics in the Prometheus text format. Knowledge base size is
+    /// included when the runtime shares a knowledge base with the host.
+    pub fn render_metrics(&self) -> String {
+        let mut out = String::new();
+        self.metrics.tick_duration.write(&mut out, "astra_tick_duration_seconds", "Time taken by runtime ticks.");
+
+        write_header(&mut out, "astra_intents", "gauge", "Intents by state.");
+        let intents = self.intent_manager.all_intents();
+        for (state, label) in [
+            (IntentState::Pending, "pending"),
+            (IntentState::Active, "active"),
+            (IntentState::Completed, "completed"),
+            (IntentState::Cancelled, "cancelled"),
+        ] {
+            let count = intents.iter().filter(|intent| intent.state == state).count();
+            write_sample(&mut out, "astra_intents", &[("state", label)], count as f64);
+        }
+        write_header(&mut out, "astra_intent_queue_depth", "gauge", "Intents still waiting to be pursued or finished.");
+        let queued = intents.iter().filter(|i| matches!(i.state, IntentState::Pending | IntentState::Active)).count();
+        write_sample(&mut out, "astra_intent_queue_depth", &[], queued as f64);
+
+        write_header(&mut out, "astra_plans", "gauge", "Plans admitted and running, or waiting for resources.");
+        write_sample(&mut out, "astra_plans", &[("phase", "running")], self.plan_runner.running_count() as f64);
+        write_sample(&mut out, "astra_plans", &[("phase", "waiting")], self.plan_runner.waiting_count() as f64);
+        write_header(&mut out, "astra_plans_finished_total", "counter", "Plans that have finished, by outcome.");
+        write_sample(&mut out, "astra_plans_finished_total", &[("outcome", "succeeded")], self.metrics.plans_succeeded as f64);
+        write_sample(&mut out, "astra_plans_finished_total", &[("outcome", "failed")], self.metrics.plans_failed as f64);
+        if let Some(ratio) = self.metrics.plan_success_ratio() {
+            write_header(&mut out, "astra_plan_success_ratio", "gauge", "Share of finished plans that completed.");
+            write_sample(&mut out, "astra_plan_success_ratio", &[], ratio);
+        }
+
+        write_header(&mut out, "astra_crawled_pages_total", "counter", "Pages fetched by the web crawler.");
+        write_sample(&mut out, "astra_crawled_pages_total", &[], self.metrics.pages_crawled as f64);
+
+        write_header(&mut out, "astra_stimulus_queue_depth", "gauge", "Stimuli waiting for the next tick.");
+        write_sample(&mut out, "astra_stimulus_queue_depth", &[], self.stimuli.len() as f64);
+        write_header(&mut out, "astra_stimuli_dropped_total", "counter", "Stimuli shed because the stimulus queue was full.");
+        write_sample(&mut out, "astra_stimuli_dropped_total", &[], self.metrics.stimuli_dropped as f64);
+        write_header(&mut out, "astra_stimuli_merged_total", "counter", "Repeated stimuli folded into one already waiting.");
+        write_sample(&mut out, "astra_stimuli_merged_total", &[], self.metrics.stimuli_merged as f64);
+        write_header(&mut out, "astra_mqtt_stimuli_dropped_total", "counter", "Stimuli shed by the MQTT bridge's own queue.");
+        write_sample(&mut out, "astra_mqtt_stimuli_dropped_total", &[], self.metrics.mqtt_stimuli_dropped as f64);
+
+        if let Some(knowledge) = self.knowledge.as_ref().map(SharedKnowledge::snapshot) {
+            write_header(&mut out, "astra_knowledge_facts", "gauge", "Facts in the current knowledge base version.");
+            write_sample(&mut out, "astra_knowledge_facts", &[], knowledge.query_facts(None).len() as f64);
+        }
+        write_header(&mut out, "astra_narrative_events", "gauge", "Events held in narrative memory.");
+        write_sample(&mut out, "astra_narrative_events", &[], self.narrative_memory.events.len() as f64);
+
+        write_header(&mut out, "astra_emotion", "gauge", "Task-level emotion, 0 to 1.");
+        write_sample(&mut out, "astra_emotion", &[("dimension", "urgency")], self.emotion_state.urgency as f64);
+        write_sample(&mut out, "astra_emotion", &[("dimension", "motivation")], self.emotion_state.motivation as f64);
+        write_sample(&mut out, "astra_emotion", &[("dimension", "stress")], self.emotion_state.stress as f64);
+        let affect = self.affect_snapshot();
+        write_header(&mut out, "astra_affect", "gauge", "Moment-to-moment affect and mood.");
+        for (dimension, value) in [
+            ("happiness", affect.happiness),
+            ("sadness", affect.sadness),
+            ("anger", affect.anger),
+            ("fear", affect.fear),
+            ("valence", affect.valence),
+            ("mood", affect.mood),
+        ] {
+            write_sample(&mut out, "astra_affect", &[("dimension", dimension)], value as f64);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cognition::stimulus_queue::StimulusQueue;
+    use crate::cognition::Stimulus;
+    use crate::knowledge::extended_ontology::OntologyManager;
+
+    #[test]
+    fn test_metrics_render_in_exposition_format() {
+        let mut runtime = Runtime::new();
+        runtime.intent_manager.create_intent_with_metadata("Water the plants", 3, None);
+        runtime.metrics.tick_duration.observe(0.003);
+        runtime.metrics.tick_duration.observe(4.0);
+        runtime.metrics.plans_succeeded = 3;
+        runtime.metrics.plans_failed = 1;
+        runtime.record_crawled_pages(12);
+        runtime.stimuli = StimulusQueue::new(1);
+        for urgency in [0.5, 0.5, 0.1, 0.9] {
+            runtime.submit_stimulus(Stimulus { source: "sensor".into(), content: format!("reading {}", urgency), urgency });
+        }
+
+        assert!(!runtime.render_metrics().contains("astra_knowledge_facts"));
+        runtime.set_knowledge(SharedKnowledge::new(OntologyManager::new()));
+        let text = runtime.render_metrics();
+        assert!(text.contains("astra_tick_duration_seconds_bucket{le=\"0.001\"} 0\n"));
+        assert!(text.contains("astra_tick_duration_seconds_bucket{le=\"0.005\"} 1\n"));
+        assert!(text.contains("astra_tick_duration_seconds_bucket{le=\"+Inf\"} 2\n"));
+        assert!(text.contains("astra_tick_duration_seconds_count 2\n"));
+        assert!(text.contains("astra_intents{state=\"pending\"} 1\n"));
+        assert!(text.contains("astra_intent_queue_depth 1\n"));
+        assert!(text.contains("astra_plan_success_ratio 0.75\n"));
+        assert!(text.contains("astra_crawled_pages_total 12\n"));
+        assert!(text.contains("astra_stimulus_queue_depth 1\n"));
+        assert!(text.contains("astra_stimuli_dropped_total 2\n"));
+        assert!(text.contains("astra_stimuli_merged_total 1\n"));
+        assert!(text.contains("astra_mqtt_stimuli_dropped_total 0\n"));
+        assert!(text.contains("astra_knowledge_facts 0\n"));
+        assert!(text.contains("# TYPE astra_affect gauge\n"));
+    }
+}
@@ -5,103 +5,1811 @@
 //  Description:
 //  Core runtime orchestrator extended with Emotion and Value Models for affective task prioritization,
 //  Intent Manager integration, Personality module, Narrative Memory for persistent event logging,
-//  and Advanced Epistemic Reasoner integration.
+//  Advanced Epistemic Reasoner integration, a Belief Ledger auditing belief revisions,
+//  feedback ingestion with per-component credit assignment, emotion contagion from user sentiment,
+//  conversation sessions summarized into narrative memory, commitment tracking for promises made,
+//  concurrent execution of plans for independent intents with resource locking, whose progress
+//  flags deadlines the remaining work will miss once busy periods are worked around,
+//  a cognitive load level derived from queue depth, tick overruns, and running plans and shared with
+//  cognitive loops,
+//  a structured self-report for introspection, approval-gated self-modification proposals,
+//  clarifying questions for ambiguous or incomplete user requests, questions asking users for
+//  knowledge that blocks a goal, rationed per user and answered into trusted facts, and an autonomy policy
+//  that plans are executed under, quota tracking for rate-limited external resources,
+//  per-user listing and deletion of remembered data, and narrative events linked to the
+//  intents they concern so outcomes can be traced back to what caused them; aging events are
+//  summarized into coarser tiers with the originals archived, in memory and, once old enough, in
+//  files on disk that forgetting a user rewrites, and associative priming of
+//  responses by spreading activation over memory and knowledge, through an association graph
+//  that new events are added to and that is rebuilt only when knowledge changes or most of its
+//  events are gone, and prospective memory whose
+//  event-based reminders become intents when their condition is next observed; conversations
+//  and the cognitive loop's episodes record Astra's affect at their start and end, in narrative
+//  memory, so episodes can be retrieved by how they changed it.
+//  Evidence for answering a user's question is gathered by fusing fact, memory, and passage
+//  retrieval over what that user may see, and answers cite the evidence for each claim, leaving out claims nothing trusted supports.
+//  Programs that fail to parse or static analysis are rejected with positioned diagnostics
+//  instead of panicking; those that pass run with builtins over knowledge, memory, intents,
+//  and affect, limited to the capabilities the runtime grants programs. Programs that await
+//  an event or sleep are parked and resumed on a later tick once the event is signaled, by
+//  the host or by a perceived stimulus from the source it names, or the time has passed,
+//  reading a snapshot of the knowledge base the host shares with the runtime; past a limit
+//  the longest waiting is abandoned. Behavior scripts loaded from files are recompiled when the files
+//  change and swapped in only if the new version passes analysis; otherwise the previous
+//  version keeps running.
+//  Tick durations, plan outcomes, and crawl counts are recorded for Prometheus metrics.
+//  Operators can pause ticking, retune autonomy, and force maintenance through admin commands.
+//  Scripted scenarios replay stimuli against a fresh runtime and report where behavior diverged.
+//  Scenarios can include scripted or learning co-agents that converse with Astra as separate users.
+//  Knowledge packs bundled with Astra seed a new knowledge base with core concepts and
+//  common-sense facts on first start and supply default planning domains on every start,
+//  which cognitive loops attached without actions of their own plan with.
+//  Applications embedding the runtime register hooks that observe ticks, intents, plans, and
+//  responses, and can veto them. A builder assembles runtimes from parts the application
+//  supplies, such as its own memory, emotion model, or action executor, and rejects
+//  combinations of parts that do not fit.
+//  Emotion decay, deadlines, commitments, memory ageing, event and conversation times, and
+//  the training progress of attached cognitive loops read time from an injected clock, so
+//  tests and simulations can run the runtime in fast-forward.
+//  Randomness, including humor's and that of attached cognitive loops, derives from a recorded
+//  seed, so a run with the same seed and inputs repeats; attached loops practice while idle on
+//  curriculum tasks generated from it.
+//  Stimuli submitted between ticks wait in a bounded queue that folds repeats together and sheds
+//  the least urgent when a chat room or sensor floods it; each tick observes what waits,
+//  after polling the sensors of any WASM plugins or MQTT bridge the runtime was built with.
+//  Chat messages enter the same queue; one setting a reminder is perceived at once, before
+//  the reminder is stored, so it cannot fire the reminder it sets.
+//  Changes to intents, beliefs, and memory can be committed each tick to a write-ahead log,
+//  from which a runtime killed mid-tick recovers the state after its last whole tick; the
+//  tick rewrites the log as the current state once it has grown enough.
+//  Knowledge, memory, the log, learned models, and configuration are backed up together into
+//  incremental archives, and restored only once every file passes its digest check.
+//  Each tick sends knowledge changes to a graph export and a SQLite mirror, when configured.
+//  Requests made in conversation are negotiated through planned dialogue acts, ending in a commitment.
+//  Each user's goals, beliefs, and mood are estimated from their messages and feedback, and answers
+//  leave out what a user already knows and correct what Astra knows they have wrong.
+//  Unconfirmed actions, constraint checks, and consulted sources are audited for transparency
+//  reports that also cover commitments and self-modifications over a chosen window.
+//  Runtime operations report failures as typed RuntimeError values.
 //  This enables Astra to behave as a living, adaptive system with emotional and ethical awareness.
 //
 //  Author:      Alex Roussinov
 //  Created:     2025-12-25
-//  Updated:     2025-12-25
+//  Updated:     2026-10-16
 //
 //  This file is dual licensed under the MIT and Apache 2.0 licenses.
 // =============================================================================
 
+pub mod error;
 pub mod executor;
 pub mod scheduler;
 pub mod intent_manager;
+pub mod commitments;
+pub mod plan_runner;
+pub mod introspection;
+pub mod metrics;
+pub mod admin;
+pub mod scenarios;
+pub mod co_agents;
+pub mod user_data;
+pub mod program_host;
+pub mod behavior_scripts;
+pub mod knowledge_packs;
+pub mod hooks;
+pub mod builder;
+pub mod wal;
+pub mod backup;
+pub mod dialogue;
+pub mod transparency;
+pub mod knowledge_exports;
 
-use crate::emotion::{EmotionState, ValueModel};
-use crate::memory::narrative_memory::NarrativeMemory;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use crate::emotion::{EmotionModel, EmotionState, StimulusEmotionModel, ValueModel};
+use crate::memory::narrative_memory::{EventId, EventLink, LinkTarget, NarrativeMemory};
+use crate::memory::summary_tiers::{compress, EventArchive, MemoryStore, TierPolicy};
+use crate::memory::archive_file::{SpillPolicy, ARCHIVE_EXTENSION};
+use crate::memory::episode_affect::{AffectSnapshot, AFFECT_METADATA_KEY};
+use crate::memory::prospective::{parse_reminder, FiredTrigger, ProspectiveMemory, TriggerAction, TriggerCondition, TriggerId};
+use crate::memory::conversation_summary::{
+    commitment_sentences, content_words, ConversationSession, ConversationSummarizer, SessionSummary, Speaker,
+};
 use crate::personality::personality::Personality;
+use crate::personality::relationship::Relationships;
+use crate::personality::emotion::EmotionDynamics;
+use crate::personality::humor::Humor;
 use crate::knowledge::advanced_epistemic::AdvancedEpistemicReasoner;
+use crate::knowledge::belief_ledger::BeliefLedger;
+use crate::knowledge::epistemic_reasoner::EpistemicReasoner;
+use crate::learning::preferences::PreferenceModel;
+use crate::learning::feedback::{ComponentRef, CreditAssigner, CreditAssignment, FeedbackError, FeedbackEvent, ResponseId, ResponseTrace};
+use crate::interfaces::active_learning::{AnsweredQuestion, KnowledgeQuestion, QuestionAsker};
+use crate::interfaces::clarification::{clarification_for, Interpretation, MAX_CLARIFICATION_ROUNDS};
+use crate::interfaces::nlp::{NlpProcessor, Sentiment};
+use crate::interfaces::temporal::{TemporalInterpretation, TemporalParser, TemporalResolution};
+use crate::planning::availability::Availability;
+use crate::planning::domain::Domain;
+use crate::planning::autonomy::AutonomyLevel;
+use crate::planning::executor::{ActionExecutor, ExecutionStatus};
+use crate::planning::planner::{Plan, WorldState};
+use crate::planning::quotas::{QuotaResource, QuotaTracker, SharedQuotaTracker};
+use crate::cognition::{CognitiveEnergy, CognitiveLoop, CognitiveState, LoadSignals, Stimulus, WorldStateProvider};
+use crate::cognition::curriculum::Curriculum;
+use crate::cognition::prediction_error::SurpriseUpdate;
+use crate::cognition::learning_adapter::LearningAdapter;
+use crate::cognition::spreading_activation::{ActivationGraph, Associations, SpreadParams};
+use crate::cognition::retrieval::{Evidence, HybridRetriever, Passages};
+use crate::cognition::grounding::{GroundedAnswer, Grounder};
+use crate::cognition::stimulus_queue::{Admission, StimulusQueue};
+use crate::cognition::theory_of_mind::{Claim, Tailoring, TheoryOfMind};
+use crate::knowledge::change_feed::ChangeSequence;
+use crate::knowledge::extended_ontology::{current_unix_timestamp, OntologyManager};
+use crate::knowledge::knowledge_gaps::{KnowledgeGapDetector, LearningChannel};
+use crate::knowledge::snapshots::SharedKnowledge;
+use crate::web_crawler::crawler::WebCrawler;
+use crate::time::{system_clock, AstraTime, SharedClock};
+use crate::random::RandomSource;
+use crate::metadata::{MetaValue, Metadata};
+use crate::cognition::self_modification::{ApprovalPolicy, ModificationTargets, SelfModificationRegistry};
 
-use executor::Executor;
+use astra_lang::Capability;
+use behavior_scripts::{BehaviorScripts, ScriptReload};
+use error::RuntimeError;
+use executor::{Executor, ProgramStatus};
+use program_host::RuntimeHost;
 use scheduler::Scheduler;
-use intent_manager::IntentManager;
+use intent_manager::{IntentId, IntentManager, IntentState};
+use commitments::{CommitmentEvent, CommitmentId, CommitmentTracker};
+use builder::ExecutorFactory;
+use hooks::{veto_reason, RuntimeHooks};
+use knowledge_packs::{load_packs, KnowledgePackConfig, PackLoadReport};
+use knowledge_exports::KnowledgeExports;
+use plan_runner::{PlanProgress, PlanRunner};
+use metrics::RuntimeMetrics;
+use admin::Maintenance;
+use transparency::{AuditKind, AuditLog};
+
+/// The association graph replies are primed from, and what it was built from.
+struct AssociationCache {
+    graph: ActivationGraph,
+    /// Newest event added
+    through: Option<EventId>,
+    /// Events added, some of which may since have been evicted
+    added: usize,
+    knowledge: Option<ChangeSequence>,
+}
 
 /// The main runtime struct integrating all subsystems.
 pub struct Runtime {
     pub executor: Executor,
     pub scheduler: Scheduler,
     pub intent_manager: IntentManager,
+    /// Plans for independent intents, stepped concurrently each tick
+    pub plan_runner: PlanRunner<Box<dyn ActionExecutor + Send>>,
+    pub commitments: CommitmentTracker,
     pub emotion_state: EmotionState,
     pub value_model: ValueModel,
     pub personality: Personality,
+    /// Moment-to-moment affect, colored by the user's sentiment
+    pub affect: EmotionDynamics,
+    pub nlp: NlpProcessor,
     pub narrative_memory: NarrativeMemory,
+    /// Raw events that aged out of narrative memory into summaries
+    pub memory_archive: Box<dyn MemoryStore>,
+    pub memory_tiers: TierPolicy,
+    /// Busy periods plans cannot be worked on in; see `set_availability`
+    availability: Availability,
+    /// Where old archived events go on disk, if anywhere; see `set_archive_spill`
+    archive_spill: Option<SpillPolicy>,
+    /// Graph export and SQLite mirror kept in step with the shared knowledge
+    /// base; see knowledge_exports.rs
+    knowledge_exports: KnowledgeExports,
+    /// Reminders waiting for something to happen rather than for a time
+    pub prospective_memory: ProspectiveMemory,
+    /// Stimuli submitted since the last tick, bounded; see `submit_stimulus`
+    pub stimuli: StimulusQueue,
+    pub conversation_summarizer: ConversationSummarizer,
+    /// Open conversation sessions by session ID
+    pub conversations: std::collections::HashMap<String, ConversationSession>,
     pub epistemic_reasoner: AdvancedEpistemicReasoner,
+    pub belief_ledger: BeliefLedger,
+    pub temporal_parser: TemporalParser,
+    pub credit_assigner: CreditAssigner,
+    pub preference_model: PreferenceModel,
+    /// Familiarity and rapport with each user Astra chats with
+    pub relationships: Relationships,
+    /// What each user wants, believes, and feels, as far as Astra can tell
+    pub minds: TheoryOfMind,
+    /// Unconfirmed actions, constraint checks, and sources drawn on, for
+    /// transparency reports
+    pub audit_log: AuditLog,
+    /// Questions Astra asks users to fill gaps blocking its goals, rationed per user
+    pub knowledge_questions: QuestionAsker,
+    /// Questions Astra could not answer well and names it has never heard
+    /// of; see `take_crawl_topics`
+    pub knowledge_gaps: KnowledgeGapDetector,
+    /// Focus, fatigue, and load; load is refreshed every tick
+    pub energy: CognitiveEnergy,
+    /// States of cognitive loops whose load follows `energy`; see `share_load_with`
+    load_followers: Vec<std::sync::Arc<tokio::sync::Mutex<CognitiveState>>>,
+    /// Structural changes proposed by reflection, awaiting or past approval
+    pub self_modifications: SelfModificationRegistry,
+    /// Usage of rate-limited external resources; share it with the planner
+    pub quotas: SharedQuotaTracker,
+    /// Time a tick is meant to take; longer ticks add to load
+    pub tick_budget: std::time::Duration,
+    /// Capabilities Astra programs may use; programs needing others are rejected
+    pub program_capabilities: std::collections::BTreeSet<Capability>,
+    /// Astra programs loaded from files and reloaded when the files change
+    pub behavior_scripts: BehaviorScripts,
+    /// Support a claim needs before answers state it
+    pub grounding: Grounder,
+    /// Tick durations, plan outcomes, and crawl counts for `/metrics`
+    pub metrics: RuntimeMetrics,
+    /// Which knowledge packs `load_knowledge_packs` loads, and whose signatures it trusts
+    pub knowledge_packs: KnowledgePackConfig,
+    /// Planning domains from knowledge packs, by domain name; cognitive
+    /// loops attached without actions of their own plan with them
+    pub planning_domains: std::collections::BTreeMap<String, Domain>,
+    /// Moves `emotion_state` in response to each tick's stimuli
+    emotion_model: Box<dyn EmotionModel>,
+    /// Makes executors for plans submitted without one
+    action_executor: Option<ExecutorFactory>,
+    /// WASM sensors and effectors; see `RuntimeBuilder::with_plugins`
+    #[cfg(feature = "plugins")]
+    plugins: Option<crate::interfaces::plugins::SharedPluginHost>,
+    /// Sensor topics perceived each tick; see `RuntimeBuilder::with_mqtt`
+    #[cfg(feature = "mqtt")]
+    mqtt: Option<std::sync::Arc<crate::interfaces::mqtt::MqttBridge>>,
+    /// Knowledge base shared with the host, read by programs the tick resumes
+    knowledge: Option<SharedKnowledge>,
+    /// Whether the host runs a web crawler
+    crawler_enabled: bool,
+    /// Time as the runtime and its subsystems see it; see `set_clock`
+    clock: SharedClock,
+    /// Seed of the randomness of this run; see `set_random_source`
+    random: RandomSource,
+    /// Jokes for lighter replies, drawn from the run's random source
+    pub humor: Humor,
+    /// Hooks of the embedding application, asked in the order they were added
+    hooks: Vec<Box<dyn RuntimeHooks>>,
+    /// Write-ahead log and what has been logged; see `enable_wal`
+    wal: Option<wal::Journal>,
+    /// Where `backup` puts backups; see `set_backup`
+    backup_config: Option<backup::BackupConfig>,
+    /// Graph replies are primed from; see `prime_context`
+    association_graph: Option<AssociationCache>,
+    /// Event that started each suspended program, by execution context
+    suspended_programs: std::collections::HashMap<usize, EventId>,
+    /// Set by an operator; ticks do nothing while paused
+    paused: bool,
+    /// Maintenance operators requested of the host, in request order
+    pending_maintenance: Vec<Maintenance>,
+    last_tick_duration: std::time::Duration,
+    next_response_id: ResponseId,
 }
 
 impl Runtime {
     /// Creates a new Runtime instance.
     pub fn new() -> Self {
+        let random = RandomSource::from_entropy();
         Runtime {
             executor: Executor::new(),
             scheduler: Scheduler::new(),
             intent_manager: IntentManager::new(),
+            plan_runner: PlanRunner::new(4),
+            commitments: CommitmentTracker::new(),
             emotion_state: EmotionState::new(),
             value_model: ValueModel::new(),
             personality: Personality::new(),
+            affect: EmotionDynamics::new(),
+            nlp: NlpProcessor::new(),
             narrative_memory: NarrativeMemory::new(1000),
+            memory_archive: Box::new(EventArchive::new()),
+            memory_tiers: TierPolicy::default(),
+            archive_spill: None,
+            knowledge_exports: KnowledgeExports::default(),
+            availability: Availability::new(),
+            prospective_memory: ProspectiveMemory::new(),
+            stimuli: StimulusQueue::default(),
+            conversation_summarizer: ConversationSummarizer::default(),
+            conversations: std::collections::HashMap::new(),
             epistemic_reasoner: AdvancedEpistemicReasoner::new(),
+            belief_ledger: BeliefLedger::new(),
+            temporal_parser: TemporalParser::utc(),
+            credit_assigner: CreditAssigner::new(1000),
+            preference_model: PreferenceModel::new(),
+            relationships: Relationships::new(),
+            minds: TheoryOfMind::new(),
+            audit_log: AuditLog::default(),
+            knowledge_questions: QuestionAsker::new(),
+            knowledge_gaps: KnowledgeGapDetector::new(),
+            energy: CognitiveEnergy::baseline(),
+            load_followers: Vec::new(),
+            self_modifications: SelfModificationRegistry::new(ApprovalPolicy::default()),
+            quotas: QuotaTracker::new().shared(),
+            tick_budget: std::time::Duration::from_millis(100),
+            program_capabilities: std::collections::BTreeSet::from([
+                Capability::Knowledge,
+                Capability::Memory,
+                Capability::Intent,
+                Capability::Emotion,
+            ]),
+            behavior_scripts: BehaviorScripts::new(),
+            grounding: Grounder::default(),
+            metrics: RuntimeMetrics::new(),
+            knowledge_packs: KnowledgePackConfig::default(),
+            planning_domains: std::collections::BTreeMap::new(),
+            emotion_model: Box::new(StimulusEmotionModel),
+            action_executor: None,
+            #[cfg(feature = "plugins")]
+            plugins: None,
+            #[cfg(feature = "mqtt")]
+            mqtt: None,
+            knowledge: None,
+            crawler_enabled: true,
+            clock: system_clock(),
+            random,
+            humor: Humor::with_random(&random),
+            hooks: Vec::new(),
+            wal: None,
+            backup_config: None,
+            association_graph: None,
+            suspended_programs: std::collections::HashMap::new(),
+            paused: false,
+            pending_maintenance: Vec::new(),
+            last_tick_duration: std::time::Duration::ZERO,
+            next_response_id: 1,
         }
     }
 
-    /// Starts the runtime components.
+    /// Drives emotion decay, intent deadlines, commitments, and memory
+    /// ageing from `clock` instead of the system clock, e.g. a mock or
+    /// accelerated clock in tests and simulations.
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.affect.set_clock(clock.clone());
+        self.intent_manager.set_clock(clock.clone());
+        self.narrative_memory.set_clock(clock.clone());
+        self.clock = clock;
+    }
+
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
+    /// Shares the host's knowledge base with the runtime. Programs resumed
+    /// by the tick read a snapshot of it, taken once per tick.
+    pub fn set_knowledge(&mut self, knowledge: SharedKnowledge) {
+        self.knowledge = Some(knowledge);
+    }
+
+    pub fn knowledge(&self) -> Option<&SharedKnowledge> {
+        self.knowledge.as_ref()
+    }
+
+    /// Seeds the randomness of this run. Humor and the cognitive loops
+    /// attached afterwards draw from `source`, so that the same seed and
+    /// inputs repeat a run.
+    pub fn set_random_source(&mut self, source: RandomSource) {
+        self.random = source;
+        self.humor = Humor::with_random(&source);
+    }
+
+    pub fn random_source(&self) -> RandomSource {
+        self.random
+    }
+
+    /// Busy periods, such as a calendar's, that running plans are expected
+    /// to wait out; progress marks intents whose deadline they then miss.
+    pub fn set_availability(&mut self, availability: Availability) {
+        self.availability = availability;
+    }
+
+    /// Moves archived events older than the policy allows into files in its
+    /// directory each tick, so the in-memory archive stops growing.
+    pub fn set_archive_spill(&mut self, policy: SpillPolicy) {
+        self.archive_spill = Some(policy);
+    }
+
+    pub fn archive_spill(&self) -> Option<&SpillPolicy> {
+        self.archive_spill.as_ref()
+    }
+
+    /// Spills the archived events that are due into a new file, then
+    /// checkpoints the log so recovery does not bring them back.
+    fn spill_archive(&mut self, now: AstraTime) {
+        let Some(policy) = &self.archive_spill else {
+            return;
+        };
+        let cutoff = AstraTime::from_millis(now.as_millis().saturating_sub(policy.older_than_secs.saturating_mul(1000)));
+        if self.memory_archive.events_before(cutoff).len() < policy.min_events.max(1) {
+            return;
+        }
+        let path = policy.dir.join(format!("{:016}.{}", now.as_millis(), ARCHIVE_EXTENSION));
+        match self.memory_archive.spill(cutoff, &path) {
+            Ok(moved) => {
+                self.narrative_memory.add_event("archive_spilled", format!("Moved {} archived events to disk", moved), None);
+                if let Err(e) = self.checkpoint_wal() {
+                    log::error!("Could not drop spilled events from the write-ahead log: {}", e);
+                }
+            }
+            Err(e) => log::error!("Could not spill archived events to {}: {}", path.display(), e),
+        }
+    }
+
+    /// Starts the runtime components. The seed of the run is recorded
+    /// with the start event, so the run can be replayed.
     pub fn start(&mut self) {
         self.scheduler.start();
         self.executor.start();
-        self.narrative_memory.add_event("runtime_start", "Runtime started", None);
+        self.suspended_programs.clear();
+        let seed = Metadata::from([("seed", self.random.seed())]);
+        self.narrative_memory.add_event("runtime_start", "Runtime started", Some(seed));
+    }
+
+    /// Sets up a cognitive loop to run alongside this runtime: it reads
+    /// this runtime's clock, draws its randomness from this run's seed, and
+    /// practices on curriculum tasks generated from that seed while idle.
+    /// Its plans weigh and use this runtime's quotas, and its load follows
+    /// this runtime's (see `share_load_with`). A loop with
+    /// no actions yet plans with those of the knowledge packs'
+    /// planning domains, grounded over their constants.
+    pub fn attach_cognitive_loop<E, W, L>(&mut self, cognitive_loop: &mut CognitiveLoop<E, W, L>)
+    where
+        E: ActionExecutor,
+        W: WorldStateProvider,
+        L: LearningAdapter,
+    {
+        cognitive_loop.set_clock(self.clock.clone());
+        cognitive_loop.set_random_source(&self.random);
+        cognitive_loop.set_curriculum(Curriculum::new(&self.random));
+        cognitive_loop.set_quotas(self.quotas.clone());
+        self.share_load_with(std::sync::Arc::clone(&cognitive_loop.state));
+        if cognitive_loop.actions().is_empty() && !self.planning_domains.is_empty() {
+            cognitive_loop.set_actions(self.planning_domains.values().flat_map(|domain| domain.ground(&[])).collect());
+        }
+    }
+
+    /// Weakens the beliefs behind predictions `cognitive_loop`'s actions got
+    /// strongly wrong in the shared knowledge base, recording each revision
+    /// in the belief ledger, and queues the surprise as a stimulus. Without
+    /// a shared knowledge base the errors wait in the loop.
+    pub fn reconcile_prediction_errors<E, W, L>(
+        &mut self,
+        cognitive_loop: &mut CognitiveLoop<E, W, L>,
+    ) -> SurpriseUpdate
+    where
+        E: ActionExecutor,
+        W: WorldStateProvider,
+        L: LearningAdapter,
+    {
+        let Some(knowledge) = self.knowledge.clone() else {
+            return SurpriseUpdate::default();
+        };
+        let reasoner = EpistemicReasoner::new();
+        let update = knowledge
+            .write(|knowledge| cognitive_loop.reconcile_prediction_errors(knowledge, &reasoner, &mut self.belief_ledger));
+        if let Some(stimulus) = update.stimulus.clone() {
+            self.submit_stimulus(stimulus);
+        }
+        update
+    }
+
+    /// Writes the episodes `cognitive_loop` finished since the last call to
+    /// narrative memory, each with its affect at start and end, so they can
+    /// be retrieved by how they changed it. Returns how many were written.
+    pub fn record_loop_episodes<E, W, L>(&mut self, cognitive_loop: &mut CognitiveLoop<E, W, L>) -> usize
+    where
+        E: ActionExecutor,
+        W: WorldStateProvider,
+        L: LearningAdapter,
+    {
+        let episodes = cognitive_loop.take_finished_episodes();
+        for (trace, success) in &episodes {
+            let mut metadata = Metadata::new()
+                .with("goal_id", trace.goal_id.as_str())
+                .with("success", *success);
+            if let Some(affect) = trace.affect.as_ref().and_then(MetaValue::from_record) {
+                metadata = metadata.with(AFFECT_METADATA_KEY, affect);
+            }
+            let outcome = if *success { "succeeded" } else { "failed" };
+            self.narrative_memory
+                .add_event("episode", format!("Pursued goal {}: {}", trace.goal_id, outcome), Some(metadata));
+        }
+        episodes.len()
+    }
+
+    /// Has the load of `state`, the state a cognitive loop runs on, follow
+    /// this runtime's load after every tick, so the runtime's pressure
+    /// raises the loop's attention threshold and holds back its curiosity.
+    /// A state locked by a step in progress catches up on the next tick.
+    /// Sharing with the same state twice has no further effect.
+    pub fn share_load_with(&mut self, state: std::sync::Arc<tokio::sync::Mutex<CognitiveState>>) {
+        if let Ok(mut locked) = state.try_lock() {
+            locked.energy.load = self.energy.load;
+        }
+        if !self.load_followers.iter().any(|follower| std::sync::Arc::ptr_eq(follower, &state)) {
+            self.load_followers.push(state);
+        }
+    }
+
+    /// Adds hooks that observe, and may veto, what the runtime does. They
+    /// are asked after any added before them.
+    pub fn add_hooks(&mut self, hooks: impl RuntimeHooks + 'static) {
+        self.hooks.push(Box::new(hooks));
+    }
+
+    /// Asks the hooks whether `reply` may be sent, before a host sends it.
+    pub fn review_response(&mut self, reply: &str) -> Result<(), RuntimeError> {
+        let veto = veto_reason(&mut self.hooks, |hook| hook.on_response_generated(reply));
+        self.audit_hook_check("on_response_generated", "reply".to_string(), &veto);
+        match veto {
+            Some(reason) => Err(self.record_veto(format!("Response withheld: {}", reason), Vec::new(), reason)),
+            None => Ok(()),
+        }
+    }
+
+    /// Records a veto in narrative memory, returning the error it becomes.
+    fn record_veto(&mut self, description: String, links: Vec<EventLink>, reason: String) -> RuntimeError {
+        self.narrative_memory.add_linked_event("hook_veto", description, None, links);
+        RuntimeError::Vetoed(reason)
+    }
+
+    /// Loads the knowledge packs `knowledge_packs` selects. Packs not yet
+    /// installed in `knowledge`, as on first start, add their concepts and
+    /// facts to it; every selected pack's planning domains replace any of
+    /// the same name in `planning_domains`. Nothing is loaded if any pack
+    /// fails its checks.
+    pub fn load_knowledge_packs(&mut self, knowledge: &mut OntologyManager) -> Result<PackLoadReport, RuntimeError> {
+        let report = load_packs(&self.knowledge_packs, knowledge)?;
+        for domain in &report.domains {
+            self.planning_domains.insert(domain.name.clone(), domain.clone());
+        }
+        for (name, version) in &report.installed {
+            self.narrative_memory.add_event(
+                "knowledge_pack_installed",
+                format!("Installed knowledge pack {} {}", name, version),
+                None,
+            );
+            self.audit(AuditKind::SourceConsulted {
+                source: format!("knowledge pack {} {}", name, version),
+                purpose: "installation".to_string(),
+            });
+        }
+        Ok(report)
+    }
+
+    /// Parses, analyzes, and executes Astra source code. Source that does not
+    /// parse or fails analysis is logged as rejected with its diagnostics and
+    /// nothing is executed.
+    pub fn execute_program(&mut self, program: &str) -> Result<(), RuntimeError> {
+        self.run_program(program, None).map(|_| ())
+    }
+
+    /// Parses, analyzes, and runs an Astra program's `main`, returning its
+    /// value, or where it is parked if it suspended at `await` or `sleep`.
+    /// Builtins reach narrative memory, intents, Astra's current affect, and
+    /// `knowledge` if the host has a knowledge base. A program that needs a
+    /// capability outside `program_capabilities` is rejected before it runs;
+    /// intents it creates are linked to the program.
+    pub fn run_program(
+        &mut self,
+        program: &str,
+        knowledge: Option<&OntologyManager>,
+    ) -> Result<ProgramStatus, RuntimeError> {
+        let program_event =
+            self.narrative_memory.add_event("program_execution", format!("Executing program: {}", program), None);
+        let ast = match self.executor.parse(program) {
+            Ok(ast) => ast,
+            Err(e) => {
+                self.narrative_memory.add_linked_event(
+                    "program_rejected",
+                    format!("Program rejected with {} diagnostic(s): {}", e.diagnostics().len(), e),
+                    None,
+                    vec![EventLink::caused_by(program_event)],
+                );
+                return Err(e.into());
+            }
+        };
+        self.run_parsed(&ast, program_event, knowledge)
+    }
+
+    /// Runs an analyzed program started by `program_event`, unless it needs
+    /// capabilities outside `program_capabilities`.
+    fn run_parsed(
+        &mut self,
+        ast: &executor::AstNode,
+        program_event: EventId,
+        knowledge: Option<&OntologyManager>,
+    ) -> Result<ProgramStatus, RuntimeError> {
+        let checked = check_capabilities(ast, &self.program_capabilities);
+        self.audit(AuditKind::ConstraintCheck {
+            constraint: "program_capabilities".to_string(),
+            subject: format!("program (event {})", program_event),
+            passed: checked.is_ok(),
+            reason: checked.as_ref().err().map(|e| e.to_string()),
+        });
+        if let Err(error) = checked {
+            self.narrative_memory.add_linked_event(
+                "program_rejected",
+                format!("Program rejected: {}", error),
+                None,
+                vec![EventLink::caused_by(program_event)],
+            );
+            return Err(error);
+        }
+
+        let mut host = RuntimeHost {
+            affect: self.affect_snapshot(),
+            narrative_memory: &mut self.narrative_memory,
+            intent_manager: &mut self.intent_manager,
+            knowledge,
+            program_event,
+            created_intents: Vec::new(),
+        };
+        let result = self.executor.run(ast, &mut host, &self.program_capabilities);
+        let created_intents = host.created_intents;
+        self.program_outcome(program_event, created_intents, result)
+    }
+
+    /// Registers the Astra program at `path` as the behavior script `name`
+    /// and returns its version. The script must parse, pass analysis, and
+    /// stay within `program_capabilities`; after that, every tick picks up
+    /// changes to the file, as long as the new source passes the same checks.
+    pub fn register_script(&mut self, name: &str, path: impl Into<PathBuf>) -> Result<u64, RuntimeError> {
+        let executor = &mut self.executor;
+        let granted = &self.program_capabilities;
+        let version = self.behavior_scripts.register(name, path, |source| compile_script(executor, granted, source))?;
+        self.narrative_memory.add_event(
+            "script_registered",
+            format!("Behavior script '{}' registered", name),
+            None,
+        );
+        Ok(version.version)
+    }
+
+    /// Runs the current version of the behavior script `name` like
+    /// `run_program`. A reload while the script is suspended does not
+    /// affect it; the next run uses the new version.
+    pub fn run_script(
+        &mut self,
+        name: &str,
+        knowledge: Option<&OntologyManager>,
+    ) -> Result<ProgramStatus, RuntimeError> {
+        let script = self
+            .behavior_scripts
+            .current(name)
+            .ok_or_else(|| RuntimeError::UnknownScript(name.to_string()))?;
+        let program_event = self.narrative_memory.add_event(
+            "program_execution",
+            format!("Running behavior script '{}' version {}", name, script.version),
+            None,
+        );
+        self.run_parsed(&script.ast, program_event, knowledge)
+    }
+
+    /// Recompiles behavior scripts whose files changed. New versions that
+    /// pass analysis and the capability check replace the old ones; the
+    /// others are logged and the previous versions stay in use.
+    pub fn reload_scripts(&mut self) -> Vec<ScriptReload> {
+        let executor = &mut self.executor;
+        let granted = &self.program_capabilities;
+        let reloads = self.behavior_scripts.reload_changed(|source| compile_script(executor, granted, source));
+        for reload in &reloads {
+            match reload {
+                ScriptReload::Reloaded { name, version } => self.narrative_memory.add_event(
+                    "script_reloaded",
+                    format!("Behavior script '{}' reloaded as version {}", name, version),
+                    None,
+                ),
+                ScriptReload::RolledBack { name, version, error } => self.narrative_memory.add_event(
+                    "script_reload_rejected",
+                    format!("Behavior script '{}' kept at version {}: {}", name, version, error),
+                    None,
+                ),
+            };
+        }
+        reloads
+    }
+
+    /// Wakes the suspended programs awaiting `event`; they continue on the
+    /// next tick. Returns how many were waiting. Each stimulus the tick
+    /// perceives signals the event named by its source, with its content.
+    pub fn signal_program_event(&mut self, event: &str, payload: &str) -> usize {
+        self.executor.signal(event, payload)
+    }
+
+    /// Continues every suspended program whose event was signaled or whose
+    /// sleep or timeout is over, with `knowledge` for `kb.query`. The tick
+    /// does this without a knowledge base.
+    pub fn resume_programs(
+        &mut self,
+        knowledge: Option<&OntologyManager>,
+    ) -> Vec<(usize, Result<ProgramStatus, RuntimeError>)> {
+        let now = std::time::Instant::now();
+        let mut outcomes = Vec::new();
+        for context_id in self.executor.ready_programs(now) {
+            // Abandoned while another resumed program was parked
+            if !self.executor.is_suspended(context_id) {
+                continue;
+            }
+            let program_event = match self.suspended_programs.remove(&context_id) {
+                Some(event) => event,
+                None => self.narrative_memory.add_event(
+                    "program_execution",
+                    format!("Resuming program in context {}", context_id),
+                    None,
+                ),
+            };
+            let mut host = RuntimeHost {
+                affect: self.affect_snapshot(),
+                narrative_memory: &mut self.narrative_memory,
+                intent_manager: &mut self.intent_manager,
+                knowledge,
+                program_event,
+                created_intents: Vec::new(),
+            };
+            let Some(result) = self.executor.resume(context_id, &mut host, now) else {
+                self.suspended_programs.insert(context_id, program_event);
+                continue;
+            };
+            let created_intents = host.created_intents;
+            outcomes.push((context_id, self.program_outcome(program_event, created_intents, result)));
+        }
+        outcomes
+    }
+
+    /// Records what a run of a program did: the intents it created, and
+    /// whether it finished, suspended, or failed.
+    fn program_outcome(
+        &mut self,
+        program_event: EventId,
+        created_intents: Vec<IntentId>,
+        result: Result<ProgramStatus, astra_lang::EvalError>,
+    ) -> Result<ProgramStatus, RuntimeError> {
+        for intent_id in created_intents {
+            self.record_intent_origin(intent_id, format!("Intent {} created by program", intent_id), Some(program_event));
+        }
+
+        match result {
+            Ok(ProgramStatus::Finished(value)) => {
+                // Create an intent for this program execution
+                let intent_id = self.intent_manager.create_intent_with_metadata("Program execution intent", 10, None);
+                self.record_intent_origin(intent_id, format!("Intent {} created", intent_id), Some(program_event));
+                Ok(ProgramStatus::Finished(value))
+            }
+            Ok(ProgramStatus::Suspended { context_id, waiting_for }) => {
+                self.narrative_memory.add_linked_event(
+                    "program_suspended",
+                    format!("Program waiting for {}", waiting_for),
+                    None,
+                    vec![EventLink::caused_by(program_event)],
+                );
+                self.suspended_programs.insert(context_id, program_event);
+                self.record_abandoned_programs();
+                Ok(ProgramStatus::Suspended { context_id, waiting_for })
+            }
+            Err(e) => {
+                self.narrative_memory.add_linked_event(
+                    "program_failed",
+                    format!("Program failed: {}", e),
+                    None,
+                    vec![EventLink::caused_by(program_event)],
+                );
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Records the programs the executor gave up on to keep the number
+    /// waiting bounded, linked to the events that started them.
+    fn record_abandoned_programs(&mut self) {
+        for context_id in self.executor.take_abandoned() {
+            let links = self.suspended_programs.remove(&context_id).map(EventLink::caused_by).into_iter().collect();
+            self.narrative_memory.add_linked_event(
+                "program_abandoned",
+                format!("Program in context {} abandoned: more than {} were waiting", context_id, executor::MAX_SUSPENDED),
+                None,
+                links,
+            );
+        }
+    }
+
+    /// Logs the creation of an intent, linked to the event that caused it,
+    /// and remembers the log entry in the intent's `origin_event` metadata
+    /// so later events about the intent can be traced back through it.
+    fn record_intent_origin(&mut self, intent_id: IntentId, description: String, cause: Option<EventId>) {
+        let mut links = vec![EventLink::about(LinkTarget::Intent(intent_id))];
+        links.extend(cause.map(EventLink::caused_by));
+        let owner = self.intent_owner(intent_id);
+        let event = self.narrative_memory.add_linked_event("intent_created", description, owner, links);
+        let _ = self.intent_manager.set_metadata(intent_id, "origin_event", event);
+
+        let Some(intent) = self.intent_manager.get_intent(intent_id) else {
+            return;
+        };
+        let veto = veto_reason(&mut self.hooks, |hook| hook.on_intent_created(intent));
+        self.audit_hook_check("on_intent_created", format!("intent {}", intent_id), &veto);
+        if let Some(reason) = veto {
+            let _ = self.intent_manager.cancel_intent(intent_id);
+            let links = self.intent_links(intent_id);
+            self.record_veto(format!("Intent {} cancelled: {}", intent_id, reason), links, reason);
+        }
+    }
+
+    /// Metadata naming the user an intent was made for, for events about it,
+    /// so they are forgotten with the user.
+    fn intent_owner(&self, intent_id: IntentId) -> Option<Metadata> {
+        let user_id = self.intent_manager.get_intent(intent_id)?.metadata.get_str("user_id")?;
+        Some(Metadata::from([("user_id", user_id.to_string())]))
+    }
+
+    /// Links for an event about `intent_id`: the intent itself, and the
+    /// event that created it as the cause.
+    fn intent_links(&self, intent_id: IntentId) -> Vec<EventLink> {
+        let origin = self
+            .intent_manager
+            .get_intent(intent_id)
+            .and_then(|intent| intent.metadata.get_u64("origin_event"));
+        let mut links = vec![EventLink::about(LinkTarget::Intent(intent_id))];
+        links.extend(origin.map(EventLink::caused_by));
+        links
+    }
+
+    /// Creates an intent from an utterance of `user_id`, or of no known
+    /// user, inferring its deadline and duration from any temporal
+    /// expression it contains. If the expression is ambiguous the intent is
+    /// created without a deadline and the returned resolution carries the
+    /// clarification prompt; answer it with `resolve_intent_deadline`. The
+    /// intent and the events about it name the user, so they are forgotten
+    /// with them.
+    pub fn create_intent_from_utterance(
+        &mut self,
+        utterance: &str,
+        priority: u32,
+        user_id: Option<&str>,
+    ) -> (IntentId, TemporalResolution) {
+        let now = self.clock.now_utc();
+        let resolution = self.temporal_parser.parse(utterance, now);
+        let metadata = user_id.map(|u| Metadata::from([("user_id", u.to_string())]));
+        let intent_id = self.intent_manager.create_intent_with_metadata(utterance, priority, metadata);
+        self.record_intent_origin(intent_id, format!("Intent {} created", intent_id), None);
+        let links = self.intent_links(intent_id);
+        let owner = self.intent_owner(intent_id);
+
+        match &resolution {
+            TemporalResolution::Resolved(interp) => {
+                let _ = self.intent_manager.set_wall_clock_deadline(intent_id, interp.deadline, interp.duration, now);
+                self.narrative_memory.add_linked_event(
+                    "intent_deadline_inferred",
+                    format!("Intent {} due {}", intent_id, interp.description),
+                    owner,
+                    links,
+                );
+            }
+            TemporalResolution::Ambiguous { prompt, .. } => {
+                self.narrative_memory.add_linked_event(
+                    "intent_deadline_ambiguous",
+                    format!("Intent {}: {}", intent_id, prompt),
+                    owner,
+                    links,
+                );
+            }
+            TemporalResolution::NotFound => {}
+        }
+
+        (intent_id, resolution)
+    }
+
+    /// Runs a user message through the NLP pipeline and lets its sentiment
+    /// affect Astra's emotions, scaled by agreeableness. Hostile messages also
+    /// raise stress slightly.
+    pub fn absorb_user_sentiment(&mut self, message: &str) -> Sentiment {
+        const STRESS_CONTAGION: f32 = 0.1;
+
+        let sentiment = self
+            .nlp
+            .process_text(message)
+            .map(|result| result.sentiment)
+            .unwrap_or_default();
+        if sentiment.valence == 0.0 {
+            return sentiment;
+        }
+
+        let agreeableness = self.personality.traits.agreeableness;
+        self.affect.apply_contagion(sentiment.valence, sentiment.arousal, agreeableness);
+        if sentiment.valence < 0.0 {
+            let delta = -sentiment.valence * sentiment.arousal * agreeableness * STRESS_CONTAGION;
+            self.emotion_state.stress = (self.emotion_state.stress + delta).clamp(0.0, 1.0);
+        }
+
+        self.narrative_memory.add_event(
+            "emotion_contagion",
+            format!(
+                "User sentiment valence {:.2}, arousal {:.2}, mostly {:?}; affect now {:?}",
+                sentiment.valence,
+                sentiment.arousal,
+                sentiment.emotions.dominant(),
+                self.affect.current
+            ),
+            None,
+        );
+        sentiment
+    }
+
+    /// Opens a conversation session. Returns a "last time we discussed…"
+    /// line when an earlier session with the same user was summarized.
+    pub fn begin_conversation(&mut self, session_id: &str, user_id: Option<String>) -> Option<String> {
+        let primer = self.conversation_summarizer.last_summary(&self.narrative_memory, user_id.as_deref()).map(|s| s.priming_line());
+        let mut session = ConversationSession::new(session_id, user_id, self.clock.now());
+        session.affect_start = Some(self.affect_snapshot());
+        self.conversations.insert(session_id.to_string(), session);
+        primer
+    }
+
+    /// Astra's current emotions, mood, and stress.
+    pub fn affect_snapshot(&self) -> AffectSnapshot {
+        AffectSnapshot::capture(&self.affect.current, &self.affect.mood, Some(self.emotion_state.stress))
+    }
+
+    /// The open session `session_id`, opened now if there is none.
+    fn session_mut(&mut self, session_id: &str) -> &mut ConversationSession {
+        let affect = self.affect_snapshot();
+        let now = self.clock.now();
+        self.conversations.entry(session_id.to_string()).or_insert_with(|| {
+            let mut session = ConversationSession::new(session_id, None, now);
+            session.affect_start = Some(affect);
+            session
+        })
+    }
+
+    /// Memories and facts associated with a message, found by spreading
+    /// activation from its content words through narrative memory and, if
+    /// the host has one, its knowledge base. Used to prime a response. The
+    /// association graph is kept between calls: events recorded since are
+    /// added to it, and it is rebuilt once knowledge has changed or most of
+    /// the events in it are no longer held.
+    pub fn prime_context(&mut self, message: &str, knowledge: Option<&OntologyManager>) -> Associations {
+        const PRIMED_ITEMS: usize = 5;
+
+        let memory = &self.narrative_memory;
+        let knowledge_at = knowledge.map(OntologyManager::latest_change);
+        let stale = self.association_graph.as_ref().is_none_or(|cache| {
+            let held = memory.events.iter().filter(|e| cache.through.is_some_and(|through| e.id <= through)).count();
+            cache.knowledge != knowledge_at || held * 2 < cache.added
+        });
+        if stale {
+            let mut graph = ActivationGraph::new();
+            if let Some(knowledge) = knowledge {
+                graph.add_facts(knowledge);
+            }
+            self.association_graph = Some(AssociationCache { graph, through: None, added: 0, knowledge: knowledge_at });
+        }
+        let cache = self.association_graph.as_mut().expect("built above");
+        let new_events: Vec<_> = memory.events.iter().filter(|e| cache.through.is_none_or(|through| e.id > through)).collect();
+        cache.graph.add_events(new_events.iter().copied());
+        cache.added += new_events.len();
+        cache.through = new_events.iter().map(|e| e.id).max().or(cache.through);
+        let graph = &cache.graph;
+        let cues: Vec<String> = content_words(message).collect();
+        let cues: Vec<&str> = cues.iter().map(String::as_str).collect();
+        graph.retrieve(&cues, &SpreadParams::default(), PRIMED_ITEMS)
+    }
+
+    /// Records a message from `user_id` as the episode the reply to it
+    /// belongs to, with personal data filtered out. Feedback on the reply
+    /// is credited to this episode.
+    pub fn record_user_message(&mut self, user_id: &str, text: &str) -> EventId {
+        let owner = Metadata::from([("user_id", user_id.to_string())]);
+        self.narrative_memory.add_event("user_message", text, Some(owner))
+    }
+
+    /// Evidence for answering `question` from `user_id`: their own
+    /// narrative memories, plus facts from the host's knowledge base that
+    /// no other user taught and passages from its document index when it
+    /// has them, fused into one ranking.
+    pub fn gather_evidence(
+        &self,
+        user_id: &str,
+        question: &str,
+        knowledge: Option<&OntologyManager>,
+        passages: Option<Passages>,
+    ) -> Result<Vec<Evidence>, RuntimeError> {
+        let mut retriever = HybridRetriever::new().with_memory(&self.narrative_memory).for_user(user_id);
+        if let Some(knowledge) = knowledge {
+            retriever = retriever.with_knowledge(knowledge);
+        }
+        if let Some(passages) = passages {
+            retriever = retriever.with_passages(passages);
+        }
+        Ok(retriever.retrieve(question)?)
+    }
+
+    /// Answers `question` from `user_id` from the evidence gathered for
+    /// them, each sentence citing its source. Without evidence trusted
+    /// enough to state, the answer says so instead of guessing.
+    pub fn answer_question(
+        &self,
+        user_id: &str,
+        question: &str,
+        knowledge: Option<&OntologyManager>,
+        passages: Option<Passages>,
+    ) -> Result<GroundedAnswer, RuntimeError> {
+        let evidence = self.gather_evidence(user_id, question, knowledge, passages)?;
+        Ok(self.grounding.compose(&evidence))
+    }
+
+    /// Checks a reply written elsewhere against the evidence for the
+    /// question `user_id` asked, citing the claims it can support and
+    /// dropping the rest.
+    pub fn ground_reply(
+        &self,
+        user_id: &str,
+        question: &str,
+        reply: &str,
+        knowledge: Option<&OntologyManager>,
+        passages: Option<Passages>,
+    ) -> Result<GroundedAnswer, RuntimeError> {
+        let evidence = self.gather_evidence(user_id, question, knowledge, passages)?;
+        Ok(self.grounding.ground(reply, &evidence))
+    }
+
+    /// `answer_question` tailored to what `user_id` believes: statements
+    /// the user already holds are left out, unless nothing would be left,
+    /// and statements contradicting what they hold are put as corrections.
+    /// What is said is recorded as told to them, and a question the
+    /// evidence answered poorly or not at all as a knowledge gap.
+    pub fn answer_question_for(
+        &mut self,
+        user_id: &str,
+        question: &str,
+        knowledge: Option<&OntologyManager>,
+        passages: Option<Passages>,
+    ) -> Result<GroundedAnswer, RuntimeError> {
+        let evidence = self.gather_evidence(user_id, question, knowledge, passages)?;
+        self.note_answer_gap(question, &evidence);
+        let mut answer = self.grounding.compose(&evidence);
+        let mut kept = Vec::new();
+        let mut told = Vec::new();
+        for mut statement in answer.statements.clone() {
+            let Some(claim) = Claim::parse(&statement.text) else {
+                kept.push(statement);
+                continue;
+            };
+            match self.minds.tailor(user_id, &claim) {
+                Tailoring::Redundant => continue,
+                Tailoring::Correct { .. } => statement.text = format!("Actually, {}", lowercase_first(&statement.text)),
+                Tailoring::Explain => {}
+            }
+            told.push(claim);
+            kept.push(statement);
+        }
+        if !kept.is_empty() {
+            answer.statements = kept;
+            answer.sources.retain(|source| answer.statements.iter().any(|s| s.sources.contains(&source.number)));
+        }
+        for source in &answer.sources {
+            self.audit(AuditKind::SourceConsulted { source: source.provenance.clone(), purpose: "answer".to_string() });
+        }
+        self.minds.told(user_id, told, self.clock.now_utc());
+        Ok(answer)
+    }
+
+    /// Logs `question` as a knowledge gap when no evidence was found for
+    /// it, or none trusted enough to answer from.
+    fn note_answer_gap(&mut self, question: &str, evidence: &[Evidence]) {
+        let topic = content_words(question).collect::<Vec<_>>().join(" ");
+        match evidence.iter().map(|e| e.confidence).reduce(f32::max) {
+            Some(confidence) => self.knowledge_gaps.record_answer_confidence(&topic, confidence),
+            None => self.knowledge_gaps.record_failed_query(&topic),
+        }
+    }
+
+    /// Gaps that came up more than once and are best filled by searching,
+    /// for the host's crawler to look up. Each is forgotten once taken; if
+    /// the crawl does not fill it, it is logged again.
+    pub fn take_crawl_topics(&mut self) -> Vec<String> {
+        const CRAWL_GAP_MIN_OCCURRENCES: u32 = 2;
+        let topics: Vec<String> = self
+            .knowledge_gaps
+            .frequent_gaps(CRAWL_GAP_MIN_OCCURRENCES)
+            .into_iter()
+            .filter(|gap| gap.channel() == LearningChannel::Crawl)
+            .map(|gap| gap.topic.clone())
+            .collect();
+        for topic in &topics {
+            self.knowledge_gaps.resolve(topic);
+        }
+        topics
+    }
+
+    /// Updates the model of `user_id`'s mind from a message they sent
+    /// outside an interpreted session, such as through the chat API.
+    pub fn observe_user_message(&mut self, user_id: &str, text: &str) {
+        if let Ok(understood) = self.nlp.process_text(text) {
+            let private = self.private_text(text);
+            self.minds.observe_message(user_id, &private, &understood, self.clock.now_utc());
+        }
+    }
+
+    /// Appends a turn to an open session, opening it first if needed. The
+    /// turn is logged with personal data filtered out.
+    pub fn record_conversation_turn(&mut self, session_id: &str, speaker: Speaker, text: &str) {
+        let text = self.private_text(text);
+        let now = self.clock.now();
+        self.session_mut(session_id).add_turn(speaker, text, now);
+    }
+
+    /// `text` as narrative memory's privacy filter would store it.
+    fn private_text(&self, text: &str) -> String {
+        match &self.narrative_memory.privacy {
+            Some(filter) => filter.filter(text).text,
+            None => text.to_string(),
+        }
+    }
+
+    /// Interprets a user message within a session. If the session is waiting
+    /// on a clarifying question, the message is taken as its answer and
+    /// merged into the original intent. A reading that is still unsure or
+    /// missing a required slot produces a new question, up to
+    /// `MAX_CLARIFICATION_ROUNDS` per utterance, after which the best
+    /// reading is used.
+    pub fn interpret_user_message(&mut self, session_id: &str, text: &str) -> Result<Interpretation, RuntimeError> {
+        let interpretation = self.interpret_in_session(session_id, text)?;
+        if let Interpretation::NeedsClarification(question) = &interpretation {
+            let now = self.clock.now();
+            self.session_mut(session_id).add_turn(Speaker::Astra, question, now);
+        }
+        Ok(interpretation)
+    }
+
+    /// `interpret_user_message`, leaving it to the caller to put any
+    /// clarifying question to the user.
+    pub(crate) fn interpret_in_session(&mut self, session_id: &str, text: &str) -> Result<Interpretation, RuntimeError> {
+        const REMINDER_PRIORITY: u32 = 6;
+
+        // Check waiting reminders before storing a new one, so a request
+        // does not fire itself.
+        let user_id = self.conversations.get(session_id).and_then(|s| s.user_id.clone());
+        let owner = user_id.as_ref().map(|u| Metadata::from([("user_id", u.clone())]));
+        self.submit_user_stimulus(text);
+        if let Some((condition, action)) = parse_reminder(text) {
+            self.perceive_stimuli();
+            self.remember_when(condition, action, user_id.clone(), REMINDER_PRIORITY, false);
+        }
+
+        let logged = self.private_text(text);
+        let affect = self.affect_snapshot();
+        let now = self.clock.now_utc();
+        let turn_at = self.clock.now();
+        // Borrow only the sessions, since the NLP processor is needed below.
+        let session = self.conversations.entry(session_id.to_string()).or_insert_with(|| {
+            let mut session = ConversationSession::new(session_id, None, turn_at);
+            session.affect_start = Some(affect);
+            session
+        });
+        session.add_turn(Speaker::User, logged.clone(), turn_at);
+
+        let (result, original, rounds) = match session.pending_clarification.take() {
+            Some(pending) => (pending.merge_answer(text, &self.nlp), pending.original_text, pending.rounds + 1),
+            None => (self.nlp.process_text(text).map_err(RuntimeError::Interpretation)?, text.to_string(), 0),
+        };
+        if let Some(user_id) = &user_id {
+            self.minds.observe_message(user_id, &logged, &result, now);
+        }
+
+        if rounds < MAX_CLARIFICATION_ROUNDS {
+            if let Some(mut pending) = clarification_for(&original, &result) {
+                pending.rounds = rounds;
+                let question = pending.question.clone();
+                session.pending_clarification = Some(pending);
+                self.narrative_memory.add_event(
+                    "clarification_requested",
+                    format!("Asked '{}' about '{}'", question, original),
+                    owner,
+                );
+                return Ok(Interpretation::NeedsClarification(question));
+            }
+        }
+
+        if rounds > 0 {
+            self.narrative_memory.add_event(
+                "clarification_resolved",
+                format!("Understood '{}' as {} after {} question(s)", original, result.intent, rounds),
+                owner,
+            );
+        }
+        Ok(Interpretation::Understood(result))
+    }
+
+    /// Asks the user of a session the first of `candidates` they may be
+    /// asked, typically questions for a goal blocked by a knowledge gap or an
+    /// unreliable source. Nothing is asked while a clarifying question is
+    /// open, of anonymous sessions, or of users who have been asked enough
+    /// lately. Returns the question put to the user.
+    pub fn ask_to_learn(&mut self, session_id: &str, candidates: Vec<KnowledgeQuestion>) -> Option<String> {
+        let session = self.conversations.get_mut(session_id)?;
+        if session.pending_clarification.is_some() {
+            return None;
+        }
+        let user_id = session.user_id.clone()?;
+        let question = self.knowledge_questions.ask(&user_id, candidates, current_unix_timestamp())?.question.clone();
+        session.add_turn(Speaker::Astra, &question, self.clock.now());
+        self.narrative_memory.add_event(
+            "knowledge_question_asked",
+            format!("Asked {} '{}'", user_id, question),
+            Some(Metadata::from([("user_id", user_id)])),
+        );
+        Some(question)
+    }
+
+    /// Reads `text` as the answer to the question the session's user was
+    /// asked by `ask_to_learn`, if one is pending, and adds the facts it
+    /// states to `knowledge` with the user as their source. Call before
+    /// `interpret_user_message`, which still handles the message as usual.
+    pub fn take_knowledge_answer(
+        &mut self,
+        session_id: &str,
+        text: &str,
+        knowledge: &mut OntologyManager,
+    ) -> Option<AnsweredQuestion> {
+        let user_id = self.conversations.get(session_id)?.user_id.clone()?;
+        let answered = self.knowledge_questions.answer(&user_id, text, knowledge)?;
+        self.narrative_memory.add_event(
+            "knowledge_question_answered",
+            format!("Learned {} fact(s) from {} about {}", answered.facts.len(), user_id, answered.question.topic),
+            Some(Metadata::from([("user_id", user_id)])),
+        );
+        Some(answered)
+    }
+
+    /// Stores a reminder that turns `action` into an intent when `condition`
+    /// is next observed, or every time it is if `repeat` is set.
+    pub fn remember_when(
+        &mut self,
+        condition: TriggerCondition,
+        action: TriggerAction,
+        user_id: Option<String>,
+        priority: u32,
+        repeat: bool,
+    ) -> TriggerId {
+        let metadata = user_id.as_ref().map(|u| Metadata::from([("user_id", u.clone())]));
+        let id = self.prospective_memory.add(condition, action, user_id, priority, repeat, self.clock.now());
+        self.narrative_memory.add_event("prospective_trigger_added", format!("Trigger {} is waiting", id), metadata);
+        id
+    }
+
+    /// Queues a stimulus for the next tick to observe. When perception
+    /// floods, repeats are folded together and the least urgent stimuli
+    /// are shed; both are counted in the metrics.
+    pub fn submit_stimulus(&mut self, stimulus: Stimulus) -> Admission {
+        let admission = self.stimuli.push(stimulus);
+        match &admission {
+            Admission::Merged => self.metrics.stimuli_merged += 1,
+            Admission::Displaced(_) | Admission::Shed => self.metrics.stimuli_dropped += 1,
+            Admission::Queued => {}
+        }
+        admission
+    }
+
+    /// Queues a user's message as a stimulus. Chat floods are bounded like
+    /// any other; see `submit_stimulus`.
+    pub fn submit_user_stimulus(&mut self, text: &str) -> Admission {
+        self.submit_stimulus(Stimulus { source: "user".into(), content: text.to_string(), urgency: 0.0 })
+    }
+
+    /// Observes the stimuli waiting since the last tick, most urgent first,
+    /// waking programs that await an event named by a stimulus's source.
+    pub fn perceive_stimuli(&mut self) {
+        for queued in self.stimuli.drain() {
+            self.observe_stimulus(&queued.stimulus);
+            self.signal_program_event(&queued.stimulus.source, &queued.stimulus.content);
+        }
+    }
+
+    /// Queues what the MQTT bridge's sensors reported since the last tick
+    /// and observes its world state; what the bridge itself had to shed is
+    /// counted in the metrics.
+    #[cfg(feature = "mqtt")]
+    fn poll_mqtt(&mut self) {
+        let Some(bridge) = self.mqtt.clone() else {
+            return;
+        };
+        for stimulus in bridge.take_stimuli() {
+            self.submit_stimulus(stimulus);
+        }
+        self.metrics.mqtt_stimuli_dropped = bridge.dropped_stimuli();
+        self.observe_world_state(&bridge.current_world_state());
+    }
+
+    /// Brings the plugins in line with their directory, recording what was
+    /// loaded or rejected, and queues what their sensors report.
+    #[cfg(feature = "plugins")]
+    fn poll_plugins(&mut self) {
+        use crate::interfaces::plugins::PluginEvent;
+
+        let Some(plugins) = &self.plugins else {
+            return;
+        };
+        let (events, stimuli) = {
+            let mut host = plugins.lock();
+            (host.rescan(), host.poll_sensors())
+        };
+        for event in events {
+            let description = match event {
+                PluginEvent::Loaded(name) => format!("Plugin '{}' loaded", name),
+                PluginEvent::Unloaded(name) => format!("Plugin '{}' unloaded", name),
+                PluginEvent::Rejected { manifest, error } => format!("Plugin {} rejected: {}", manifest.display(), error),
+            };
+            self.narrative_memory.add_event("plugin", description, None);
+        }
+        for stimulus in stimuli {
+            self.submit_stimulus(stimulus);
+        }
+    }
+
+    /// Checks a stimulus against waiting reminders, returning the intents
+    /// created for those it fired.
+    pub fn observe_stimulus(&mut self, stimulus: &Stimulus) -> Vec<IntentId> {
+        let fired = self.prospective_memory.observe_text(&stimulus.source, &stimulus.content);
+        self.fire_prospective_triggers(fired)
+    }
+
+    /// Checks a new world state against waiting reminders; only facts that
+    /// changed since the last call can fire them.
+    pub fn observe_world_state(&mut self, state: &WorldState) -> Vec<IntentId> {
+        let fired = self.prospective_memory.observe_state(state);
+        self.fire_prospective_triggers(fired)
+    }
+
+    /// Turns fired reminders into intents, each traced back to an event
+    /// recording the firing.
+    fn fire_prospective_triggers(&mut self, fired: Vec<FiredTrigger>) -> Vec<IntentId> {
+        let mut intents = Vec::new();
+        for trigger in fired {
+            let mut metadata = Metadata::from([("prospective_trigger", trigger.trigger_id)]);
+            let mut event_metadata = None;
+            if let Some(user_id) = &trigger.user_id {
+                metadata.insert("user_id", user_id.clone());
+                event_metadata = Some(Metadata::from([("user_id", user_id.clone())]));
+            }
+            let event = self.narrative_memory.add_event(
+                "prospective_trigger_fired",
+                format!("Trigger {} fired", trigger.trigger_id),
+                event_metadata,
+            );
+            let intent_id = self.intent_manager.create_intent_with_metadata(
+                trigger.action.intent_description(),
+                trigger.priority,
+                Some(metadata),
+            );
+            self.record_intent_origin(intent_id, format!("Intent {} created", intent_id), Some(event));
+            intents.push(intent_id);
+        }
+        intents
+    }
+
+    /// Closes a session and stores its summary, with the affect at its start
+    /// and end, in narrative memory.
+    pub fn end_conversation(&mut self, session_id: &str) -> Option<SessionSummary> {
+        let mut session = self.conversations.remove(session_id)?;
+        session.affect_end = Some(self.affect_snapshot());
+        Some(self.conversation_summarizer.summarize_into(&session, &mut self.narrative_memory))
+    }
+
+    /// Summarizes and closes sessions that have been silent for `max_idle_secs`,
+    /// so conversations that are never explicitly ended still reach memory.
+    pub fn close_idle_conversations(&mut self, max_idle_secs: u64, now: AstraTime) -> Vec<SessionSummary> {
+        let idle: Vec<String> = self
+            .conversations
+            .values()
+            .filter(|session| {
+                let last_activity = session.turns.last().map(|t| t.timestamp).unwrap_or(session.started_at);
+                now.saturating_duration_since(last_activity).as_secs() >= max_idle_secs
+            })
+            .map(|session| session.session_id.clone())
+            .collect();
+        idle.iter().filter_map(|id| self.end_conversation(id)).collect()
+    }
+
+    /// Records a commitment for every promise in one of Astra's replies
+    /// ("I'll send it by Friday"). Each promise becomes an intent whose
+    /// deadline is inferred from the sentence, linked to the commitment.
+    pub fn record_commitments_from_reply(&mut self, reply: &str, user_id: Option<String>) -> Vec<CommitmentId> {
+        commitment_sentences(reply).iter().map(|promise| self.record_commitment(promise, user_id.clone())).collect()
+    }
+
+    /// Records `promise` as a commitment to `user_id`, with an intent whose
+    /// deadline is inferred from the promise.
+    pub fn record_commitment(&mut self, promise: &str, user_id: Option<String>) -> CommitmentId {
+        const COMMITMENT_PRIORITY: u32 = 7;
+
+        let (intent_id, resolution) = self.create_intent_from_utterance(promise, COMMITMENT_PRIORITY, user_id.as_deref());
+        let promised_by = match resolution {
+            TemporalResolution::Resolved(interp) => interp.deadline,
+            _ => None,
+        };
+        let id = self
+            .commitments
+            .record(user_id.clone(), promise.to_string(), intent_id, promised_by, self.clock.now_utc());
+        self.narrative_memory.add_event(
+            "commitment_made",
+            format!("Commitment {} (intent {}): {}", id, intent_id, promise),
+            user_id.as_ref().map(|u| Metadata::from([("user_id", u.clone())])),
+        );
+        id
+    }
+
+    /// Reconciles commitments with their intents, logging reminders and
+    /// outcomes to narrative memory where reflection picks them up.
+    pub fn check_commitments(&mut self) -> Vec<CommitmentEvent> {
+        let events = self.commitments.check(self.clock.now_utc(), &self.intent_manager);
+        for event in &events {
+            let (event_type, id) = match event {
+                CommitmentEvent::Reminder(id) => ("commitment_reminder", *id),
+                CommitmentEvent::Fulfilled(id) => ("commitment_fulfilled", *id),
+                CommitmentEvent::Broken(id) => ("commitment_broken", *id),
+            };
+            if let Some(commitment) = self.commitments.get(id) {
+                self.narrative_memory.add_event(
+                    event_type,
+                    format!("Commitment {}: {}", id, commitment.statement),
+                    commitment.user_id.as_ref().map(|u| Metadata::from([("user_id", u.clone())])),
+                );
+            }
+        }
+        events
     }
 
-    /// Parses and executes Astra source code.
-    pub fn execute_program(&mut self, program: &str) {
-        self.narrative_memory.add_event("program_execution", format!("Executing program: {}", program), None);
-        let ast = self.executor.parse(program).expect("Parsing failed");
-        self.executor.execute(&ast);
-        // Create an intent for this program execution
-        let intent_id = self.intent_manager.create_intent("Program execution intent", 10);
-        self.narrative_memory.add_event("intent_created", format!("Intent {} created", intent_id), None);
+    /// Applies the reading of an ambiguous deadline chosen by the user.
+    pub fn resolve_intent_deadline(&mut self, intent_id: IntentId, choice: &TemporalInterpretation) -> Result<(), RuntimeError> {
+        self.intent_manager
+            .set_wall_clock_deadline(intent_id, choice.deadline, choice.duration, self.clock.now_utc())
     }
 
-    /// Advances runtime by one tick.
+    /// Queues a plan for an intent; it starts once no running plan holds any
+    /// of its resources, and the intent becomes active. Refused if a hook
+    /// vetoes the plan.
+    pub fn submit_plan(&mut self, intent_id: IntentId, plan: Plan, env: Box<dyn ActionExecutor + Send>) -> Result<(), RuntimeError> {
+        let intent = self.intent_manager.get_intent(intent_id).ok_or(RuntimeError::IntentNotFound(intent_id))?;
+        let priority = intent.priority;
+        let veto = veto_reason(&mut self.hooks, |hook| hook.on_plan_selected(intent, &plan));
+        self.audit_hook_check("on_plan_selected", format!("plan for goal {}", plan.goal_id), &veto);
+        if let Some(reason) = veto {
+            let links = self.intent_links(intent_id);
+            return Err(self.record_veto(format!("Plan for goal {} refused: {}", plan.goal_id, reason), links, reason));
+        }
+        self.plan_runner.submit(intent_id, priority, plan, env)?;
+        self.intent_manager.update_intent(intent_id, None, None, Some(IntentState::Active))
+    }
+
+    /// Queues a plan for an intent on an executor made by the factory the
+    /// runtime was built with; see `submit_plan`.
+    pub fn submit_plan_to_default_executor(&mut self, intent_id: IntentId, plan: Plan) -> Result<(), RuntimeError> {
+        let make_executor = self.action_executor.as_mut().ok_or(RuntimeError::NoActionExecutor)?;
+        let env = make_executor();
+        self.submit_plan(intent_id, plan, env)
+    }
+
+    /// False if the runtime was built for a host that runs no web crawler,
+    /// which should then not start one.
+    pub fn crawler_enabled(&self) -> bool {
+        self.crawler_enabled
+    }
+
+    /// A crawler for the host to run, waiting `rate_limit_ms` between pages
+    /// and writing what they state into the shared knowledge base. None if
+    /// the host runs no crawler.
+    pub fn web_crawler(&self, rate_limit_ms: u64) -> Option<WebCrawler> {
+        if !self.crawler_enabled {
+            return None;
+        }
+        let crawler = WebCrawler::new(rate_limit_ms);
+        Some(match &self.knowledge {
+            Some(knowledge) => crawler.with_knowledge(knowledge.clone()),
+            None => crawler,
+        })
+    }
+
+    fn record_plan_progress(&mut self, progress: &PlanProgress) {
+        let mut entries: Vec<(&str, MetaValue)> = vec![
+            ("progress", ((f64::from(progress.fraction()) * 100.0).round() / 100.0).into()),
+            ("progress_phase", progress.phase.as_str().into()),
+            ("milestones", format!("{}/{}", progress.milestones_reached.len(), progress.milestones_total).into()),
+        ];
+        if let Some(remaining) = progress.estimated_remaining {
+            entries.push(("eta_secs", remaining.as_secs().into()));
+            let deadline = self.intent_manager.get_intent(progress.intent_id).and_then(|intent| intent.deadline);
+            if let Some(deadline) = deadline {
+                let finish = self.availability.finish_time(self.clock.now().to_datetime(), remaining);
+                entries.push(("deadline_at_risk", finish.is_none_or(|finish| finish > deadline.to_datetime()).into()));
+            }
+        }
+        if let ExecutionStatus::AwaitingConfirmation { action_id, .. } = &progress.status {
+            entries.push(("awaiting_confirmation", action_id.as_str().into()));
+        }
+        for (key, value) in entries {
+            // The intent may have been removed while its plan was running.
+            let _ = self.intent_manager.set_metadata(progress.intent_id, key, value);
+        }
+    }
+
+    /// Confirms the action an intent's plan is waiting on, as the autonomy
+    /// policy requires for its category.
+    pub fn confirm_plan_action(&mut self, intent_id: IntentId) -> Result<(), RuntimeError> {
+        self.plan_runner.confirm(intent_id)?;
+        self.narrative_memory.add_event("autonomy_confirmed", format!("Action confirmed for intent {}", intent_id), None);
+        self.audit(AuditKind::ConstraintCheck {
+            constraint: "autonomy_policy".to_string(),
+            subject: format!("action of intent {}", intent_id),
+            passed: true,
+            reason: Some("confirmed by a person".to_string()),
+        });
+        Ok(())
+    }
+
+    /// Refuses the action an intent's plan is waiting on, stopping the plan
+    /// there.
+    pub fn deny_plan_action(&mut self, intent_id: IntentId) -> Result<(), RuntimeError> {
+        self.plan_runner.deny(intent_id)?;
+        self.narrative_memory.add_event("autonomy_denied", format!("Action denied for intent {}", intent_id), None);
+        self.audit(AuditKind::ConstraintCheck {
+            constraint: "autonomy_policy".to_string(),
+            subject: format!("action of intent {}", intent_id),
+            passed: false,
+            reason: Some("denied by a person".to_string()),
+        });
+        Ok(())
+    }
+
+    /// Progress of every submitted plan.
+    pub fn plan_progress(&self) -> Vec<PlanProgress> {
+        self.plan_runner.progress()
+    }
+
+    /// Steps running plans, mirrors their progress into intent metadata, and
+    /// settles the intents whose plans finished.
+    fn advance_plans(&mut self) {
+        let finished = self.plan_runner.tick();
+        for (intent_id, notice) in self.plan_runner.take_notices() {
+            if notice.level == AutonomyLevel::Forbid {
+                self.audit(AuditKind::ConstraintCheck {
+                    constraint: "autonomy_policy".to_string(),
+                    subject: format!("action {} of intent {}", notice.action_id, intent_id),
+                    passed: false,
+                    reason: Some(format!("{:?} actions are forbidden", notice.category)),
+                });
+                continue;
+            }
+            if notice.level == AutonomyLevel::Notify {
+                self.narrative_memory.add_event(
+                    "autonomy_notice",
+                    format!("Intent {} ran {:?} action {}: {}", intent_id, notice.category, notice.action_id, notice.description),
+                    None,
+                );
+            }
+            self.audit(AuditKind::AutonomousAction {
+                intent_id,
+                action_id: notice.action_id,
+                description: notice.description,
+                category: notice.category,
+                level: notice.level,
+            });
+        }
+        for progress in self.plan_runner.progress() {
+            self.record_plan_progress(&progress);
+        }
+        for progress in finished {
+            match &progress.status {
+                ExecutionStatus::Completed => self.metrics.plans_succeeded += 1,
+                _ => self.metrics.plans_failed += 1,
+            }
+            let (state, description) = match &progress.status {
+                ExecutionStatus::Completed => (IntentState::Completed, format!("Plan for goal {} completed", progress.goal_id)),
+                other => (
+                    IntentState::Pending,
+                    format!(
+                        "Plan for goal {} stopped after {}/{} actions: {:?}",
+                        progress.goal_id, progress.completed_actions, progress.total_actions, other
+                    ),
+                ),
+            };
+            if let Err(e) = self.intent_manager.update_intent(progress.intent_id, None, None, Some(state)) {
+                self.narrative_memory.add_event("error", format!("Failed to settle intent: {}", e), None);
+            }
+            let links = self.intent_links(progress.intent_id);
+            self.narrative_memory.add_linked_event("plan_finished", description, None, links);
+        }
+    }
+
+    /// Records use of a rate-limited resource by an effector.
+    pub fn record_quota_usage(&mut self, resource: QuotaResource, amount: u64) {
+        if let Ok(mut quotas) = self.quotas.write() {
+            quotas.record(resource, amount);
+        }
+    }
+
+    /// Logs a warning for every quota that has newly come close to
+    /// exhaustion.
+    fn check_quotas(&mut self) {
+        let warnings = match self.quotas.write() {
+            Ok(mut quotas) => quotas.take_new_warnings(),
+            Err(_) => return,
+        };
+        for warning in warnings {
+            let message = format!(
+                "Quota for {} nearly exhausted: {}/{} used in the last {}s",
+                warning.resource.as_str(),
+                warning.used,
+                warning.limit,
+                warning.window.as_secs()
+            );
+            log::warn!("{}", message);
+            self.narrative_memory.add_event("quota_warning", message, None);
+        }
+    }
+
+    /// Current pressure on the runtime, for the cognitive load model.
+    pub fn load_signals(&self) -> LoadSignals {
+        LoadSignals {
+            queued_intents: self
+                .intent_manager
+                .all_intents()
+                .iter()
+                .filter(|i| matches!(i.state, IntentState::Pending | IntentState::Active))
+                .count(),
+            tick_duration: self.last_tick_duration,
+            tick_budget: self.tick_budget,
+            running_plans: self.plan_runner.running_count(),
+            plan_capacity: self.plan_runner.max_concurrent,
+        }
+    }
+
+    /// Advances runtime by one tick, unless an operator has paused it or a
+    /// hook vetoes it.
     pub fn tick(&mut self) {
+        if self.paused {
+            return;
+        }
+        if let Some(reason) = veto_reason(&mut self.hooks, |hook| hook.on_tick_start()) {
+            log::debug!("Tick vetoed: {}", reason);
+            return;
+        }
+        let tick_started = std::time::Instant::now();
         // Update emotion state based on workload and deadlines
         let mut stimuli = std::collections::HashMap::new();
         let next_intent = self.intent_manager.next_intent();
         if let Some(intent) = &next_intent {
             if let Some(deadline) = intent.deadline {
-                let now = std::time::Instant::now();
-                let duration_to_deadline = deadline.saturating_duration_since(now);
+                let duration_to_deadline = deadline.saturating_duration_since(self.clock.now());
                 let urgency = 1.0 - (duration_to_deadline.as_secs_f32() / 3600.0).clamp(0.0, 1.0);
                 stimuli.insert("deadline_proximity".to_string(), urgency);
             }
         }
         stimuli.insert("workload".to_string(), (self.intent_manager.all_intents().len() as f32 / 100.0).clamp(0.0, 1.0));
-        self.emotion_state.update(&stimuli);
+        self.emotion_model.update(&mut self.emotion_state, &stimuli);
 
         // Modify intent priority based on emotion and values
         if let Some(intent) = next_intent {
-            let task_metadata = std::collections::HashMap::new(); // Extend as needed
-            let modifier = crate::emotion::compute_priority_modifier(&self.emotion_state, &self.value_model, &task_metadata);
+            let modifier = crate::emotion::compute_priority_modifier(&self.emotion_state, &self.value_model, &intent.metadata);
             let new_priority = ((intent.priority as f32) * (1.0 + modifier)).max(0.0) as u32;
             self.intent_manager.update_intent(intent.id, Some(new_priority), None, None).unwrap_or_else(|e| {
                 self.narrative_memory.add_event("error", format!("Failed to update intent priority: {}", e), None);
             });
         }
 
+        #[cfg(feature = "plugins")]
+        self.poll_plugins();
+        #[cfg(feature = "mqtt")]
+        self.poll_mqtt();
+        self.perceive_stimuli();
+
         self.scheduler.tick();
         self.executor.tick();
+        let knowledge = self.knowledge.as_ref().map(SharedKnowledge::snapshot);
+        self.resume_programs(knowledge.as_deref().map(|s| &**s));
+        self.reload_scripts();
+        self.advance_plans();
+        self.check_quotas();
+
+        self.check_commitments();
+        self.apply_self_modifications();
+
+        const CONVERSATION_IDLE_SECS: u64 = 30 * 60;
+        let now = self.clock.now();
+        self.close_idle_conversations(CONVERSATION_IDLE_SECS, now);
+        compress(&mut self.narrative_memory, self.memory_archive.as_mut(), &self.memory_tiers, now);
+        self.spill_archive(now);
+        self.sync_knowledge_exports();
 
         self.narrative_memory.add_event("tick", "Runtime tick completed", None);
+        if let Err(e) = self.commit_wal() {
+            log::error!("Could not log the tick: {}", e);
+        } else if self.wal.as_ref().is_some_and(wal::Journal::due_for_checkpoint) {
+            if let Err(e) = self.checkpoint_wal() {
+                log::error!("Could not checkpoint the write-ahead log: {}", e);
+            }
+        }
+
+        self.last_tick_duration = tick_started.elapsed();
+        self.metrics.tick_duration.observe(self.last_tick_duration.as_secs_f64());
+        let signals = self.load_signals();
+        self.energy.observe_load(&signals);
+        for state in &self.load_followers {
+            if let Ok(mut state) = state.try_lock() {
+                state.energy.load = self.energy.load;
+            }
+        }
+        for hook in &mut self.hooks {
+            hook.on_tick_end(&signals);
+        }
+    }
+
+    /// Applies approved self-modifications to the value model and personality.
+    /// Strategy changes are left for the cognitive loop, which owns the
+    /// planning heuristics.
+    pub fn apply_self_modifications(&mut self) {
+        let mut targets = ModificationTargets {
+            values: Some(&mut self.value_model),
+            traits: Some(&mut self.personality.traits),
+            ..Default::default()
+        };
+        for id in self.self_modifications.apply_approved(&mut targets) {
+            self.narrative_memory.add_event("self_modification", format!("Applied self-modification proposal {}", id), None);
+        }
+    }
+
+    /// Registers what shaped a response and returns the ID clients use to
+    /// send feedback about it. The trace's own `response_id` is overwritten.
+    pub fn register_response_trace(&mut self, mut trace: ResponseTrace) -> ResponseId {
+        let response_id = self.next_response_id;
+        self.next_response_id += 1;
+        trace.response_id = response_id;
+        self.credit_assigner.register_trace(trace);
+        response_id
+    }
+
+    /// Ingests structured user feedback, assigning credit to the components
+    /// behind the response. Personality traits are nudged by their share of
+    /// the credit and the preference model learns from the event; the full
+    /// assignment is returned for other learners.
+    pub fn ingest_feedback(&mut self, event: FeedbackEvent) -> Result<CreditAssignment, FeedbackError> {
+        const TRAIT_LEARNING_RATE: f32 = 0.05;
+
+        let style = self
+            .credit_assigner
+            .trace(event.response_id)
+            .and_then(|trace| trace.style.clone());
+        let assignment = self.credit_assigner.assign(event.clone())?;
+        self.preference_model.observe(&event, style.as_ref());
+        if let Some(user_id) = &event.user_id {
+            self.minds.observe_feedback(user_id, &event.kind, self.clock.now_utc());
+        }
+
+        let trait_feedback: std::collections::HashMap<String, f32> = assignment
+            .credits
+            .iter()
+            .filter_map(|(component, credit)| match component {
+                ComponentRef::PersonalityTrait(name) => Some((name.clone(), credit * TRAIT_LEARNING_RATE)),
+                _ => None,
+            })
+            .collect();
+        if !trait_feedback.is_empty() {
+            self.personality.apply_feedback(&trait_feedback);
+        }
+
+        self.narrative_memory.add_event(
+            "feedback_ingested",
+            format!(
+                "Feedback on response {} (reward {:.2}) credited to {} components",
+                assignment.response_id,
+                assignment.reward,
+                assignment.credits.len()
+            ),
+            event.user_id.as_ref().map(|u| Metadata::from([("user_id", u.clone())])),
+        );
+        Ok(assignment)
     }
 
     /// Adjusts personality traits based on user feedback.
@@ -154,3 +1862,34 @@ impl Runtime {
         self.narrative_memory.add_event("emotion_adjusted", format!("Emotion adjusted: {:?}", self.emotion_state), None);
     }
 }
+
+/// `text` with its first letter lowercased, to follow a lead-in.
+fn lowercase_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Rejects a program needing capabilities outside `granted`.
+fn check_capabilities(ast: &executor::AstNode, granted: &BTreeSet<Capability>) -> Result<(), RuntimeError> {
+    let denied: Vec<Capability> = ast.capabilities.difference(granted).cloned().collect();
+    if denied.is_empty() {
+        Ok(())
+    } else {
+        Err(RuntimeError::CapabilitiesDenied(denied))
+    }
+}
+
+/// Compiles a behavior script, holding it to the same checks as programs
+/// passed to `run_program`.
+fn compile_script(
+    executor: &mut Executor,
+    granted: &BTreeSet<Capability>,
+    source: &str,
+) -> Result<executor::AstNode, RuntimeError> {
+    let ast = executor.parse(source)?;
+    check_capabilities(&ast, granted)?;
+    Ok(ast)
+}
@@ -0,0 +1,465 @@
+// =============================================================================
+//  Astra AGI - Concurrent Plan Runner
+//  File: plan_runner.rs
+//
+//  Description:
+//  Runs plans for independent intents side by side. Each admitted plan gets
+//  its own PlanExecutor, and every runtime tick advances all running plans by
+//  one action, in parallel threads. Plans declare the world-state keys they
+//  touch; a plan is only admitted once it can lock all of them, so plans that
+//  share resources serialize while unrelated plans proceed together.
+//  Progress of every submitted plan can be queried at any time, including
+//  milestones reached, an estimate of the time remaining, and whether the
+//  plan is still advancing or has stalled. Plans run under the runner's
+//  autonomy policy; a plan whose next action needs confirmation keeps its
+//  locks and waits until the action is confirmed, or fails if it is denied.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use crate::planning::autonomy::{AutonomyNotice, AutonomyPolicy};
+use crate::planning::executor::{ActionExecutor, ExecutionStatus, PlanExecutor};
+use crate::planning::planner::Plan;
+use crate::runtime::error::RuntimeError;
+use crate::runtime::intent_manager::IntentId;
+use std::collections::{BTreeSet, HashMap};
+use std::time::{Duration, Instant};
+
+/// Exclusive locks on named resources, held per intent.
+#[derive(Debug, Default)]
+pub struct ResourceLocks {
+    holders: HashMap<String, IntentId>,
+}
+
+impl ResourceLocks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Locks every resource for `owner`, or none of them if any is held by
+    /// another intent. Acquiring all locks at once rules out deadlock.
+    pub fn try_acquire(&mut self, owner: IntentId, resources: &BTreeSet<String>) -> bool {
+        if resources
+            .iter()
+            .any(|r| self.holders.get(r).map_or(false, |holder| *holder != owner))
+        {
+            return false;
+        }
+        for resource in resources {
+            self.holders.insert(resource.clone(), owner);
+        }
+        true
+    }
+
+    /// Releases every lock held by `owner`.
+    pub fn release(&mut self, owner: IntentId) {
+        self.holders.retain(|_, holder| *holder != owner);
+    }
+
+    pub fn holder(&self, resource: &str) -> Option<IntentId> {
+        self.holders.get(resource).copied()
+    }
+}
+
+/// Coarse reading of a plan's progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressPhase {
+    /// Not admitted yet
+    Waiting,
+    /// Advanced recently, however slowly
+    Progressing,
+    /// No advance for much longer than its own pace suggests
+    Stalled,
+    Finished,
+}
+
+impl ProgressPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProgressPhase::Waiting => "waiting",
+            ProgressPhase::Progressing => "progressing",
+            ProgressPhase::Stalled => "stalled",
+            ProgressPhase::Finished => "finished",
+        }
+    }
+}
+
+/// Snapshot of one plan's execution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanProgress {
+    pub intent_id: IntentId,
+    pub goal_id: String,
+    pub completed_actions: usize,
+    pub total_actions: usize,
+    pub status: ExecutionStatus,
+    /// Resources held by other plans while this one waits to start
+    pub blocked_on: Vec<String>,
+    /// World-state keys that have reached their final value in the plan
+    pub milestones_reached: Vec<String>,
+    pub milestones_total: usize,
+    /// Time since the plan was admitted
+    pub elapsed: Option<Duration>,
+    /// Remaining actions at the pace observed so far
+    pub estimated_remaining: Option<Duration>,
+    pub phase: ProgressPhase,
+}
+
+impl PlanProgress {
+    /// Fraction of actions completed, in 0.0..=1.0.
+    pub fn fraction(&self) -> f32 {
+        if self.total_actions == 0 {
+            1.0
+        } else {
+            self.completed_actions as f32 / self.total_actions as f32
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        !is_unfinished(&self.status)
+    }
+}
+
+struct QueuedPlan<E: ActionExecutor> {
+    intent_id: IntentId,
+    priority: u32,
+    resources: BTreeSet<String>,
+    /// (index of the last action writing the key, key)
+    milestones: Vec<(usize, String)>,
+    submitted_at: Instant,
+    admitted_at: Option<Instant>,
+    last_advanced: Option<(Instant, usize)>,
+    executor: PlanExecutor<E>,
+}
+
+impl<E: ActionExecutor> QueuedPlan<E> {
+    fn progress(&self, blocked_on: Vec<String>, stall_after: Duration, now: Instant) -> PlanProgress {
+        let completed = self.executor.completed_actions();
+        let total = self.executor.plan().actions.len();
+        let status = self.executor.status().clone();
+        let elapsed = self.admitted_at.map(|t| now.saturating_duration_since(t));
+
+        // Pace is measured up to the last advance, so a stall does not make
+        // the remaining work look cheaper.
+        let pace = match (self.admitted_at, self.last_advanced) {
+            (Some(start), Some((at, done))) if done > 0 => Some(at.saturating_duration_since(start) / done as u32),
+            _ => None,
+        };
+        let estimated_remaining = pace.map(|p| p * total.saturating_sub(completed) as u32);
+
+        let finished = !is_unfinished(&status);
+        let phase = if finished {
+            ProgressPhase::Finished
+        } else {
+            let since = self.last_advanced.map(|(at, _)| at).or(self.admitted_at).unwrap_or(self.submitted_at);
+            let patience = pace.map_or(stall_after, |p| stall_after.max(p * 3));
+            match self.admitted_at {
+                _ if now.saturating_duration_since(since) > patience => ProgressPhase::Stalled,
+                None => ProgressPhase::Waiting,
+                Some(_) => ProgressPhase::Progressing,
+            }
+        };
+
+        PlanProgress {
+            intent_id: self.intent_id,
+            goal_id: self.executor.plan().goal_id.clone(),
+            completed_actions: completed,
+            total_actions: total,
+            status,
+            blocked_on,
+            milestones_reached: self
+                .milestones
+                .iter()
+                .filter(|(index, _)| *index < completed)
+                .map(|(_, key)| key.clone())
+                .collect(),
+            milestones_total: self.milestones.len(),
+            elapsed,
+            estimated_remaining,
+            phase,
+        }
+    }
+}
+
+fn is_unfinished(status: &ExecutionStatus) -> bool {
+    matches!(
+        status,
+        ExecutionStatus::NotStarted | ExecutionStatus::InProgress | ExecutionStatus::AwaitingConfirmation { .. }
+    )
+}
+
+/// A plan's milestones: for every key it writes, the last action that does.
+fn plan_milestones(plan: &Plan) -> Vec<(usize, String)> {
+    let mut last_writer: HashMap<&String, usize> = HashMap::new();
+    for (index, action) in plan.actions.iter().enumerate() {
+        for key in action.effects.keys() {
+            last_writer.insert(key, index);
+        }
+    }
+    let mut milestones: Vec<(usize, String)> = last_writer.into_iter().map(|(key, index)| (index, key.clone())).collect();
+    milestones.sort();
+    milestones
+}
+
+/// Admits, steps, and reports on plans for independent intents.
+pub struct PlanRunner<E: ActionExecutor + Send> {
+    /// Upper bound on plans executing at the same time
+    pub max_concurrent: usize,
+    /// Minimum time without an advance before a plan counts as stalled
+    pub stall_after: Duration,
+    /// Policy every submitted plan is executed under
+    pub autonomy: AutonomyPolicy,
+    locks: ResourceLocks,
+    waiting: Vec<QueuedPlan<E>>,
+    running: Vec<QueuedPlan<E>>,
+    finished: HashMap<IntentId, PlanProgress>,
+    notices: Vec<(IntentId, AutonomyNotice)>,
+}
+
+impl<E: ActionExecutor + Send> PlanRunner<E> {
+    pub fn new(max_concurrent: usize) -> Self {
+        PlanRunner {
+            max_concurrent: max_concurrent.max(1),
+            stall_after: Duration::from_secs(60),
+            autonomy: AutonomyPolicy::default(),
+            locks: ResourceLocks::new(),
+            waiting: Vec::new(),
+            running: Vec::new(),
+            finished: HashMap::new(),
+            notices: Vec::new(),
+        }
+    }
+
+    /// Queues a plan for an intent. Higher priorities are admitted first.
+    /// A plan already queued or running for the intent is replaced only if
+    /// it has not started yet; otherwise the call is rejected.
+    pub fn submit(&mut self, intent_id: IntentId, priority: u32, plan: Plan, env: E) -> Result<(), RuntimeError> {
+        if self.running.iter().any(|p| p.intent_id == intent_id) {
+            return Err(RuntimeError::PlanAlreadyRunning(intent_id));
+        }
+        self.waiting.retain(|p| p.intent_id != intent_id);
+        self.finished.remove(&intent_id);
+        self.waiting.push(QueuedPlan {
+            intent_id,
+            priority,
+            resources: plan.resources(),
+            milestones: plan_milestones(&plan),
+            submitted_at: Instant::now(),
+            admitted_at: None,
+            last_advanced: None,
+            executor: PlanExecutor::new(plan, env).with_policy(self.autonomy.clone()),
+        });
+        Ok(())
+    }
+
+    /// Changes the autonomy policy for plans submitted from now on and for
+    /// the remaining actions of plans already queued or running.
+    pub fn set_autonomy(&mut self, policy: AutonomyPolicy) {
+        for plan in self.waiting.iter_mut().chain(self.running.iter_mut()) {
+            plan.executor.set_policy(policy.clone());
+        }
+        self.autonomy = policy;
+    }
+
+    /// Admits waiting plans whose resources are free, then advances every
+    /// running plan by one action. Returns progress for plans that finished
+    /// during this tick; their locks are released.
+    pub fn tick(&mut self) -> Vec<PlanProgress> {
+        self.admit();
+
+        std::thread::scope(|scope| {
+            for queued in self.running.iter_mut() {
+                if matches!(queued.executor.status(), ExecutionStatus::AwaitingConfirmation { .. }) {
+                    continue;
+                }
+                scope.spawn(move || {
+                    let before = queued.executor.completed_actions();
+                    // Critical errors are already captured in the status.
+                    let _ = queued.executor.step();
+                    let after = queued.executor.completed_actions();
+                    if after > before {
+                        queued.last_advanced = Some((Instant::now(), after));
+                    }
+                });
+            }
+        });
+
+        for queued in self.running.iter_mut() {
+            let intent_id = queued.intent_id;
+            self.notices.extend(queued.executor.take_notices().into_iter().map(|n| (intent_id, n)));
+        }
+
+        let now = Instant::now();
+        let mut done = Vec::new();
+        let mut still_running = Vec::new();
+        for queued in self.running.drain(..) {
+            let progress = queued.progress(Vec::new(), self.stall_after, now);
+            if progress.is_finished() {
+                self.locks.release(queued.intent_id);
+                self.finished.insert(queued.intent_id, progress.clone());
+                done.push(progress);
+            } else {
+                still_running.push(queued);
+            }
+        }
+        self.running = still_running;
+        done
+    }
+
+    /// Lets the action an intent's plan is waiting on run at the next tick.
+    pub fn confirm(&mut self, intent_id: IntentId) -> Result<(), RuntimeError> {
+        let queued = self
+            .running
+            .iter_mut()
+            .find(|p| p.intent_id == intent_id)
+            .ok_or(RuntimeError::NoRunningPlan(intent_id))?;
+        Ok(queued.executor.confirm_pending()?)
+    }
+
+    /// Refuses the action an intent's plan is waiting on; the plan fails
+    /// and finishes at the next tick.
+    pub fn deny(&mut self, intent_id: IntentId) -> Result<(), RuntimeError> {
+        let queued = self
+            .running
+            .iter_mut()
+            .find(|p| p.intent_id == intent_id)
+            .ok_or(RuntimeError::NoRunningPlan(intent_id))?;
+        Ok(queued.executor.deny_pending()?)
+    }
+
+    /// Actions that ran without confirmation, or that the autonomy policy
+    /// forbade, since the last call, with the intent whose plan held them.
+    pub fn take_notices(&mut self) -> Vec<(IntentId, AutonomyNotice)> {
+        std::mem::take(&mut self.notices)
+    }
+
+    /// Removes a plan that has not finished. Returns whether one was found.
+    pub fn cancel(&mut self, intent_id: IntentId) -> bool {
+        let before = self.waiting.len() + self.running.len();
+        self.waiting.retain(|p| p.intent_id != intent_id);
+        self.running.retain(|p| p.intent_id != intent_id);
+        self.locks.release(intent_id);
+        self.waiting.len() + self.running.len() != before
+    }
+
+    /// Progress of the latest plan submitted for an intent.
+    pub fn progress_of(&self, intent_id: IntentId) -> Option<PlanProgress> {
+        self.progress().into_iter().find(|p| p.intent_id == intent_id)
+    }
+
+    /// Progress of every running, waiting, and finished plan.
+    pub fn progress(&self) -> Vec<PlanProgress> {
+        let now = Instant::now();
+        let running = self.running.iter().map(|p| p.progress(Vec::new(), self.stall_after, now));
+        let waiting = self.waiting.iter().map(|p| {
+            let blocked_on = p
+                .resources
+                .iter()
+                .filter(|r| self.locks.holder(r).map_or(false, |holder| holder != p.intent_id))
+                .cloned()
+                .collect();
+            p.progress(blocked_on, self.stall_after, now)
+        });
+        running.chain(waiting).chain(self.finished.values().cloned()).collect()
+    }
+
+    pub fn running_count(&self) -> usize {
+        self.running.len()
+    }
+
+    pub fn waiting_count(&self) -> usize {
+        self.waiting.len()
+    }
+
+    fn admit(&mut self) {
+        // Stable sort keeps submission order among equal priorities.
+        self.waiting.sort_by(|a, b| b.priority.cmp(&a.priority));
+        let mut index = 0;
+        while index < self.waiting.len() && self.running.len() < self.max_concurrent {
+            let candidate = &self.waiting[index];
+            if self.locks.try_acquire(candidate.intent_id, &candidate.resources) {
+                let mut queued = self.waiting.remove(index);
+                queued.admitted_at = Some(Instant::now());
+                self.running.push(queued);
+            } else {
+                index += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planning::planner::Action;
+    use anyhow::Result;
+    use std::collections::HashMap;
+
+    struct AlwaysOk;
+
+    impl ActionExecutor for AlwaysOk {
+        fn execute_action(&mut self, _action: &Action) -> Result<bool> {
+            Ok(true)
+        }
+    }
+
+    fn plan(goal: &str, keys: &[&str]) -> Plan {
+        let actions = keys
+            .iter()
+            .map(|key| Action {
+                id: format!("set_{}", key),
+                description: format!("Set {}", key),
+                preconditions: HashMap::new(),
+                effects: HashMap::from([(key.to_string(), true)]),
+                cost: 1.0,
+                risk: 0.0,
+            })
+            .collect();
+        Plan { goal_id: goal.into(), actions, estimated_cost: keys.len() as f32 }
+    }
+
+    #[test]
+    fn test_shared_resources_serialize_plans() {
+        let mut runner = PlanRunner::new(4);
+        runner.submit(1, 5, plan("lights", &["light_on", "door_open"]), AlwaysOk).unwrap();
+        runner.submit(2, 5, plan("door", &["door_open"]), AlwaysOk).unwrap();
+        runner.submit(3, 1, plan("music", &["music_on", "volume_up"]), AlwaysOk).unwrap();
+
+        runner.tick();
+        assert_eq!(runner.running_count(), 2);
+        let waiting = runner.progress_of(2).unwrap();
+        assert_eq!(waiting.blocked_on, vec!["door_open".to_string()]);
+        assert_eq!(runner.progress_of(1).unwrap().fraction(), 0.5);
+
+        // The second tick finishes both admitted plans and frees the door.
+        let finished: Vec<IntentId> = runner.tick().iter().map(|p| p.intent_id).collect();
+        assert_eq!(finished.len(), 2);
+        assert!(finished.contains(&1) && finished.contains(&3));
+
+        runner.tick();
+        assert_eq!(runner.progress_of(2).unwrap().status, ExecutionStatus::Completed);
+    }
+
+    #[test]
+    fn test_progress_reports_milestones_and_stalls() {
+        let mut runner = PlanRunner::new(1);
+        runner.stall_after = Duration::ZERO;
+        runner.submit(1, 1, plan("tidy", &["desk_clear", "floor_clean", "desk_clear"]), AlwaysOk).unwrap();
+
+        runner.tick();
+        let progress = runner.progress_of(1).unwrap();
+        // desk_clear is written again later, so it is not a milestone yet.
+        assert!(progress.milestones_reached.is_empty());
+        assert_eq!(progress.milestones_total, 2);
+        assert!(progress.estimated_remaining.is_some());
+
+        runner.tick();
+        let progress = runner.progress_of(1).unwrap();
+        assert_eq!(progress.milestones_reached, vec!["floor_clean".to_string()]);
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(runner.progress_of(1).unwrap().phase, ProgressPhase::Stalled);
+    }
+}
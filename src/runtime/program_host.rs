@@ -0,0 +1,88 @@
+// =============================================================================
+//  Astra AGI - Program Host
+//  File: program_host.rs
+//
+//  Description:
+//  Connects the astra_lang interpreter to the runtime. Astra programs reach
+//  Astra's knowledge base, narrative memory, intents, and current affect
+//  only through this host, and only for the capabilities the runtime grants.
+//  Notes a program writes are linked to the event that started it; intents it
+//  creates are reported back so the runtime can record where they came from.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use astra_lang::{Host, Value};
+
+use crate::knowledge::extended_ontology::OntologyManager;
+use crate::memory::episode_affect::{AffectDimension, AffectSnapshot};
+use crate::memory::narrative_memory::{EventId, EventLink, NarrativeMemory};
+use crate::runtime::intent_manager::{IntentId, IntentManager};
+
+/// The runtime services a program may use while it runs, until it finishes
+/// or suspends.
+pub struct RuntimeHost<'a> {
+    pub narrative_memory: &'a mut NarrativeMemory,
+    pub intent_manager: &'a mut IntentManager,
+    pub knowledge: Option<&'a OntologyManager>,
+    /// Astra's affect when the program started or last resumed
+    pub affect: AffectSnapshot,
+    /// Event logged for the program's execution
+    pub program_event: EventId,
+    /// Intents the program created, in order
+    pub created_intents: Vec<IntentId>,
+}
+
+impl Host for RuntimeHost<'_> {
+    fn query_knowledge(&mut self, predicate: &str) -> Vec<String> {
+        let Some(knowledge) = self.knowledge else {
+            return Vec::new();
+        };
+        knowledge
+            .query_facts(None)
+            .into_iter()
+            .filter(|fact| fact.predicate == predicate)
+            .map(|fact| fact.object.clone())
+            .collect()
+    }
+
+    fn note(&mut self, text: &str) {
+        self.narrative_memory.add_linked_event(
+            "program_note",
+            text,
+            None,
+            vec![EventLink::caused_by(self.program_event)],
+        );
+    }
+
+    fn create_intent(&mut self, description: &str, priority: u32) -> u64 {
+        let id = self.intent_manager.create_intent_with_metadata(description, priority, None);
+        self.created_intents.push(id);
+        id
+    }
+
+    fn emotion(&mut self, name: &str) -> Option<f64> {
+        let dimension = match name.to_ascii_lowercase().as_str() {
+            "happiness" => AffectDimension::Happiness,
+            "sadness" => AffectDimension::Sadness,
+            "anger" => AffectDimension::Anger,
+            "fear" => AffectDimension::Fear,
+            "valence" => AffectDimension::Valence,
+            "mood" => AffectDimension::Mood,
+            "stress" => AffectDimension::Stress,
+            _ => return None,
+        };
+        self.affect.get(dimension).map(f64::from)
+    }
+
+    fn modify(&mut self, target: &str, _patch: &Value) -> Result<(), String> {
+        Err(format!(
+            "programs cannot modify '{}' directly; submit a self-modification proposal instead",
+            target
+        ))
+    }
+}
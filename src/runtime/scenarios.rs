@@ -0,0 +1,689 @@
+// =============================================================================
+//  Astra AGI - Scenario Runner
+//  File: scenarios.rs
+//
+//  Description:
+//  Regression tests for emergent behavior. A scenario is a YAML script of
+//  steps: user messages, stimuli, changes in a simulated world, goals with
+//  plans to carry out in it, facts learned, programs, and ticks, interleaved
+//  with expectations about the intents, knowledge, world, replies, and
+//  narrative events that should result. Each scenario runs against a fresh
+//  runtime and knowledge base; every expectation that does not hold, and
+//  every step that fails outright, is reported as a divergence instead of
+//  stopping the run, so one report shows everything that changed.
+//  Co-agents declared by a scenario converse with Astra in rounds as
+//  separate users, negotiating through planned dialogue, and expectations
+//  can check how Astra models each of them and what it last told them.
+//  Messages are answered through the chat API's reply pipeline, and
+//  expectations can check what the last reply said, left out, and
+//  committed Astra to.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::cognition::theory_of_mind::Claim;
+use crate::cognition::Stimulus;
+use crate::knowledge::epistemic_reasoner::{EpistemicReasoner, RevisionResult};
+use crate::knowledge::extended_ontology::{name_entity_id, Fact, OntologyManager, Provenance};
+use crate::memory::narrative_memory::EventId;
+use crate::planning::planner::{Action, Plan, WorldState};
+use crate::planning::simulation::SimulatedEnvironment;
+use crate::runtime::intent_manager::IntentState;
+use crate::personality::relationship::Stance;
+use crate::random::RandomSource;
+use crate::runtime::co_agents::{CoAgent, CoAgentSpec};
+use crate::runtime::dialogue::UserReply;
+use crate::runtime::Runtime;
+
+#[derive(Debug, Error)]
+pub enum ScenarioError {
+    #[error("failed to read scenario {path}: {source}")]
+    Io { path: PathBuf, source: std::io::Error },
+    #[error("invalid scenario: {0}")]
+    Parse(#[from] serde_yaml::Error),
+}
+
+/// A scripted sequence of steps and the expectations between them.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Seed of the runtime's randomness, so every run of the scenario
+    /// makes the same random choices
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Simulated people who talk to Astra on `converse` steps
+    #[serde(default)]
+    pub agents: Vec<CoAgentSpec>,
+    /// Written as one-key maps, e.g. `- tick: 3`
+    #[serde(with = "serde_yaml::with::singleton_map_recursive")]
+    pub steps: Vec<Step>,
+}
+
+impl Scenario {
+    pub fn from_yaml(yaml: &str) -> Result<Self, ScenarioError> {
+        Ok(serde_yaml::from_str(yaml)?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ScenarioError> {
+        let path = path.as_ref();
+        let yaml = std::fs::read_to_string(path).map_err(|source| ScenarioError::Io { path: path.to_path_buf(), source })?;
+        Self::from_yaml(&yaml)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Step {
+    /// A message from a user, which Astra interprets and replies to
+    Say {
+        text: String,
+        #[serde(default = "default_session")]
+        session: String,
+        #[serde(default = "default_user")]
+        user: String,
+    },
+    Stimulus {
+        source: String,
+        content: String,
+        #[serde(default)]
+        urgency: f32,
+    },
+    /// Facts the simulated environment now reports
+    World(WorldState),
+    /// An intent, and a plan for it to carry out in the simulated environment
+    Goal {
+        intent: String,
+        #[serde(default = "default_priority")]
+        priority: u32,
+        actions: Vec<ScriptedAction>,
+    },
+    /// Evidence for a fact, revising the belief if it is already held
+    Learn(ScriptedFact),
+    /// Astra source run with access to the scenario's knowledge base
+    Program(String),
+    Tick(u32),
+    /// Rounds in which each co-agent with something to say says it, in
+    /// the order declared, and hears Astra's reply
+    Converse(u32),
+    Expect(Expectations),
+}
+
+fn default_session() -> String {
+    "scenario".to_string()
+}
+
+fn default_user() -> String {
+    "user".to_string()
+}
+
+fn default_priority() -> u32 {
+    5
+}
+
+fn default_confidence() -> f32 {
+    1.0
+}
+
+/// A plan action as written in a scenario.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScriptedAction {
+    pub id: String,
+    /// Defaults to the id
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub effects: WorldState,
+    /// Whether the simulated environment makes this action fail
+    #[serde(default)]
+    pub fails: bool,
+}
+
+impl ScriptedAction {
+    fn to_action(&self) -> Action {
+        Action {
+            id: self.id.clone(),
+            description: self.description.clone().unwrap_or_else(|| self.id.clone()),
+            preconditions: WorldState::new(),
+            effects: self.effects.clone(),
+            cost: 1.0,
+            risk: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScriptedFact {
+    pub subject: String,
+    pub predicate: String,
+    pub object: String,
+    #[serde(default = "default_confidence")]
+    pub confidence: f32,
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+/// What must hold at a point in the scenario. Everything is optional.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Expectations {
+    /// Text the last reply must contain
+    pub reply_contains: Vec<String>,
+    /// Text the last reply must not contain
+    pub reply_excludes: Vec<String>,
+    /// Text each of the last reply's commitments must contain, one
+    /// commitment per entry
+    pub commitments: Vec<String>,
+    /// The intent the NLP pipeline read the last message as
+    pub understood_as: Option<String>,
+    pub intents: Vec<ExpectedIntent>,
+    /// Text no intent's description may contain
+    pub no_intents: Vec<String>,
+    pub facts: Vec<ExpectedFact>,
+    /// Event types recorded since the previous expectation
+    pub events: Vec<String>,
+    /// Facts the simulated world must report
+    pub world: WorldState,
+    pub agents: Vec<ExpectedAgent>,
+}
+
+/// What must hold of Astra's view of a co-agent.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ExpectedAgent {
+    pub name: String,
+    /// stranger, acquaintance, friend, or strained
+    pub stance: Option<String>,
+    /// "X is Y" sentences Astra must think the co-agent holds
+    pub believes: Vec<String>,
+    pub frustrated: Option<bool>,
+    /// Text Astra's last reply to the co-agent must contain
+    pub heard: Vec<String>,
+    /// The opener a learning co-agent must have come to prefer
+    pub prefers: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExpectedIntent {
+    /// Matched case-insensitively against part of the intent's description
+    pub description: String,
+    /// pending, active, completed, or cancelled
+    #[serde(default)]
+    pub state: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExpectedFact {
+    pub subject: String,
+    pub predicate: String,
+    pub object: String,
+    #[serde(default)]
+    pub min_confidence: Option<f32>,
+    /// The fact must not be held at all
+    #[serde(default)]
+    pub absent: bool,
+}
+
+/// An expectation that did not hold, or a step that failed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    /// 1-based, as a reader counts the steps in the file
+    pub step: usize,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "step {}: expected {}, got {}", self.step, self.expected, self.actual)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioReport {
+    pub name: String,
+    pub steps_run: usize,
+    pub divergences: Vec<Divergence>,
+}
+
+impl ScenarioReport {
+    pub fn passed(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+impl fmt::Display for ScenarioReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.passed() {
+            return write!(f, "{}: {} steps, no divergences", self.name, self.steps_run);
+        }
+        write!(f, "{}: {} divergence(s)", self.name, self.divergences.len())?;
+        for divergence in &self.divergences {
+            write!(f, "\n  {}", divergence)?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs scenarios against a fresh runtime, knowledge base, and world.
+pub struct ScenarioRunner {
+    pub runtime: Runtime,
+    pub knowledge: OntologyManager,
+    pub environment: SimulatedEnvironment,
+    pub agents: Vec<CoAgent>,
+    reasoner: EpistemicReasoner,
+    last_reply: Option<String>,
+    last_understood: Option<String>,
+    /// Statements of the commitments the last reply made
+    last_commitments: Vec<String>,
+    /// Newest event already seen by an expectation
+    event_mark: Option<EventId>,
+    divergences: Vec<Divergence>,
+}
+
+impl Default for ScenarioRunner {
+    fn default() -> Self {
+        ScenarioRunner {
+            runtime: Runtime::new(),
+            knowledge: OntologyManager::new(),
+            environment: SimulatedEnvironment::new(),
+            agents: Vec::new(),
+            reasoner: EpistemicReasoner::new(),
+            last_reply: None,
+            last_understood: None,
+            last_commitments: Vec::new(),
+            event_mark: None,
+            divergences: Vec::new(),
+        }
+    }
+}
+
+/// Runs `scenario` from a clean start.
+pub fn run_scenario(scenario: &Scenario) -> ScenarioReport {
+    ScenarioRunner::new().run(scenario)
+}
+
+impl ScenarioRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs every step in order, collecting divergences along the way.
+    pub fn run(&mut self, scenario: &Scenario) -> ScenarioReport {
+        if let Some(seed) = scenario.seed {
+            self.runtime.set_random_source(RandomSource::from_seed(seed));
+        }
+        let source = RandomSource::from_seed(scenario.seed.unwrap_or_default());
+        self.agents = scenario.agents.iter().map(|spec| CoAgent::new(spec.clone(), &source)).collect();
+        self.event_mark = self.runtime.narrative_memory.events.last().map(|e| e.id);
+        for (index, step) in scenario.steps.iter().enumerate() {
+            self.run_step(index + 1, step);
+        }
+        ScenarioReport {
+            name: scenario.name.clone(),
+            steps_run: scenario.steps.len(),
+            divergences: std::mem::take(&mut self.divergences),
+        }
+    }
+
+    fn diverge(&mut self, step: usize, expected: impl Into<String>, actual: impl Into<String>) {
+        self.divergences.push(Divergence { step, expected: expected.into(), actual: actual.into() });
+    }
+
+    fn run_step(&mut self, step: usize, kind: &Step) {
+        match kind {
+            Step::Say { text, session, user } => self.say(step, text, session, user),
+            Step::Stimulus { source, content, urgency } => {
+                // Perceived at once, as a step stands for a moment of its own
+                self.runtime.submit_stimulus(Stimulus { source: source.clone(), content: content.clone(), urgency: *urgency });
+                self.runtime.perceive_stimuli();
+            }
+            Step::World(facts) => {
+                let world = self.environment.set(facts);
+                self.runtime.observe_world_state(&world);
+            }
+            Step::Goal { intent, priority, actions } => {
+                let intent_id = self.runtime.intent_manager.create_intent_with_metadata(intent, *priority, None);
+                for action in actions.iter().filter(|a| a.fails) {
+                    self.environment.fail_action(&action.id);
+                }
+                let plan = Plan {
+                    goal_id: intent.clone(),
+                    actions: actions.iter().map(ScriptedAction::to_action).collect(),
+                    estimated_cost: actions.len() as f32,
+                };
+                if let Err(e) = self.runtime.submit_plan(intent_id, plan, Box::new(self.environment.clone())) {
+                    self.diverge(step, format!("plan for '{}' to be accepted", intent), e.to_string());
+                }
+            }
+            Step::Learn(fact) => self.learn(fact),
+            Step::Program(source) => {
+                if let Err(e) = self.runtime.run_program(source, Some(&self.knowledge)) {
+                    self.diverge(step, "program to run", e.to_string());
+                }
+            }
+            Step::Tick(count) => {
+                for _ in 0..*count {
+                    self.runtime.tick();
+                }
+            }
+            Step::Converse(rounds) => {
+                for _ in 0..*rounds {
+                    for index in 0..self.agents.len() {
+                        let Some(text) = self.agents[index].next_message() else { continue };
+                        if let Some(reply) = self.reply_to_agent(step, index, &text) {
+                            self.agents[index].hear(&reply);
+                        }
+                    }
+                }
+            }
+            Step::Expect(expectations) => self.check(step, expectations),
+        }
+    }
+
+    /// Replies to a co-agent in its own session, through the same pipeline
+    /// as the chat API.
+    fn reply_to_agent(&mut self, step: usize, index: usize, text: &str) -> Option<String> {
+        let (name, session) = (self.agents[index].name().to_string(), self.agents[index].session());
+        match self.runtime.reply_to_user(&name, Some(&session), text, Some(&self.knowledge)) {
+            Ok(reply) => {
+                self.remember_reply(&reply);
+                Some(reply.text)
+            }
+            Err(e) => {
+                self.diverge(step, format!("{}'s '{}' to be answered", name, text), e.to_string());
+                None
+            }
+        }
+    }
+
+    /// Interprets and replies to a message through the chat API's pipeline.
+    fn say(&mut self, step: usize, text: &str, session: &str, user: &str) {
+        match self.runtime.reply_to_user(user, Some(session), text, Some(&self.knowledge)) {
+            Ok(reply) => {
+                self.remember_reply(&reply);
+                self.last_understood = reply.understood;
+            }
+            Err(e) => self.diverge(step, format!("'{}' to be answered", text), e.to_string()),
+        }
+    }
+
+    fn remember_reply(&mut self, reply: &UserReply) {
+        self.last_reply = Some(reply.text.clone());
+        self.last_commitments = reply
+            .commitments
+            .iter()
+            .filter_map(|&id| self.runtime.commitments.get(id))
+            .map(|commitment| commitment.statement.clone())
+            .collect();
+    }
+
+    /// Adds a fact, or revises the belief in it through the epistemic
+    /// reasoner, with the revision recorded in the belief ledger.
+    fn learn(&mut self, scripted: &ScriptedFact) {
+        let source = scripted.source.clone().unwrap_or_else(|| "scenario".to_string());
+        let evidence = Fact {
+            subject: name_entity_id(&scripted.subject),
+            predicate: scripted.predicate.clone(),
+            object: scripted.object.clone(),
+            confidence: scripted.confidence,
+            provenance: Provenance::new(source, None),
+        };
+        let held = self.held_fact(&scripted.subject, &scripted.predicate, &scripted.object).cloned();
+        let fact = match held {
+            None => evidence,
+            Some(current) => match self.reasoner.revise_belief_recorded(&current, &evidence, &mut self.runtime.belief_ledger) {
+                RevisionResult::Updated(revised) => revised,
+                RevisionResult::Rejected(_) => return,
+            },
+        };
+        self.knowledge.add_fact(fact);
+    }
+
+    /// The latest version of a fact; revisions are appended after the original.
+    fn held_fact(&self, subject: &str, predicate: &str, object: &str) -> Option<&Fact> {
+        let subject = name_entity_id(subject);
+        self.knowledge
+            .query_facts(None)
+            .into_iter()
+            .filter(|f| f.subject == subject && f.predicate == predicate && f.object == object)
+            .last()
+    }
+
+    fn check(&mut self, step: usize, expect: &Expectations) {
+        let reply = self.last_reply.clone().unwrap_or_default();
+        for text in &expect.reply_contains {
+            if !reply.contains(text.as_str()) {
+                self.diverge(step, format!("reply containing '{}'", text), format!("'{}'", reply));
+            }
+        }
+        for text in &expect.reply_excludes {
+            if reply.contains(text.as_str()) {
+                self.diverge(step, format!("reply without '{}'", text), format!("'{}'", reply));
+            }
+        }
+        if !expect.commitments.is_empty() {
+            let mut unmatched = self.last_commitments.clone();
+            for text in &expect.commitments {
+                match unmatched.iter().position(|statement| statement.contains(text.as_str())) {
+                    Some(index) => {
+                        unmatched.remove(index);
+                    }
+                    None => {
+                        let actual = format!("commitments {:?}", self.last_commitments);
+                        self.diverge(step, format!("reply committing to '{}'", text), actual);
+                    }
+                }
+            }
+        }
+        if let Some(intent) = &expect.understood_as {
+            if self.last_understood.as_ref() != Some(intent) {
+                let actual = self.last_understood.clone().unwrap_or_else(|| "no interpretation".to_string());
+                self.diverge(step, format!("message understood as '{}'", intent), actual);
+            }
+        }
+
+        let intents: Vec<(String, IntentState)> = self
+            .runtime
+            .intent_manager
+            .all_intents()
+            .iter()
+            .map(|i| (i.description.clone(), i.state))
+            .collect();
+        let listed = || {
+            let all: Vec<String> = intents.iter().map(|(d, s)| format!("'{}' ({})", d, state_name(*s))).collect();
+            if all.is_empty() { "no intents".to_string() } else { all.join(", ") }
+        };
+        for expected in &expect.intents {
+            let wanted = expected.description.to_lowercase();
+            let found = intents.iter().any(|(description, state)| {
+                description.to_lowercase().contains(&wanted)
+                    && expected.state.as_deref().is_none_or(|s| s.eq_ignore_ascii_case(state_name(*state)))
+            });
+            if !found {
+                let state = expected.state.as_deref().map(|s| format!(" ({})", s)).unwrap_or_default();
+                self.diverge(step, format!("intent '{}'{}", expected.description, state), listed());
+            }
+        }
+        for unwanted in &expect.no_intents {
+            let wanted = unwanted.to_lowercase();
+            if intents.iter().any(|(description, _)| description.to_lowercase().contains(&wanted)) {
+                self.diverge(step, format!("no intent '{}'", unwanted), listed());
+            }
+        }
+
+        for expected in &expect.facts {
+            let held = self.held_fact(&expected.subject, &expected.predicate, &expected.object).map(|f| f.confidence);
+            let statement = format!("{} {} {}", expected.subject, expected.predicate, expected.object);
+            match (held, expected.absent) {
+                (Some(confidence), true) => {
+                    self.diverge(step, format!("'{}' not held", statement), format!("held at {:.2}", confidence))
+                }
+                (None, false) => self.diverge(step, format!("'{}' held", statement), "not held"),
+                (Some(confidence), false) if expected.min_confidence.is_some_and(|min| confidence < min) => self.diverge(
+                    step,
+                    format!("'{}' held at {:.2} or more", statement, expected.min_confidence.unwrap_or_default()),
+                    format!("{:.2}", confidence),
+                ),
+                _ => {}
+            }
+        }
+
+        let mark = self.event_mark;
+        let recorded: Vec<String> = self
+            .runtime
+            .narrative_memory
+            .events
+            .iter()
+            .filter(|e| mark.is_none_or(|m| e.id > m))
+            .map(|e| e.event_type.clone())
+            .collect();
+        for event_type in &expect.events {
+            if !recorded.contains(event_type) {
+                let mut seen: Vec<String> = recorded.clone();
+                seen.sort();
+                seen.dedup();
+                self.diverge(step, format!("a '{}' event", event_type), format!("[{}]", seen.join(", ")));
+            }
+        }
+        self.event_mark = self.runtime.narrative_memory.events.last().map(|e| e.id).or(mark);
+
+        let world = self.environment.world();
+        for (fact, value) in &expect.world {
+            if world.get(fact) != Some(value) {
+                let actual = world.get(fact).map_or("unset".to_string(), |v| v.to_string());
+                self.diverge(step, format!("world fact {} = {}", fact, value), actual);
+            }
+        }
+
+        for expected in &expect.agents {
+            self.check_agent(step, expected);
+        }
+    }
+
+    fn check_agent(&mut self, step: usize, expected: &ExpectedAgent) {
+        let name = expected.name.as_str();
+        let Some(agent) = self.agents.iter().find(|agent| agent.name() == name) else {
+            self.diverge(step, format!("co-agent '{}'", name), "no such co-agent");
+            return;
+        };
+        let heard = agent.heard.last().cloned().unwrap_or_default();
+        let preferred = agent.preferred().map(str::to_string);
+
+        for text in &expected.heard {
+            if !heard.contains(text.as_str()) {
+                self.diverge(step, format!("reply to {} containing '{}'", name, text), format!("'{}'", heard));
+            }
+        }
+        if let Some(stance) = &expected.stance {
+            let actual = self.runtime.relationships.get(name).map_or("unknown", |r| stance_name(r.stance()));
+            if !stance.eq_ignore_ascii_case(actual) {
+                self.diverge(step, format!("{} seen as {}", name, stance), actual);
+            }
+        }
+
+        let mind = self.runtime.minds.get(name).cloned();
+        for sentence in &expected.believes {
+            let held = Claim::parse(sentence).and_then(|claim| mind.as_ref()?.believes(&claim));
+            if held != Some(true) {
+                let actual = match held {
+                    Some(false) => "believes otherwise",
+                    _ => "no belief about it",
+                };
+                self.diverge(step, format!("{} believing '{}'", name, sentence), actual);
+            }
+        }
+        if let Some(frustrated) = expected.frustrated {
+            let actual = mind.as_ref().is_some_and(|mind| mind.is_frustrated());
+            if actual != frustrated {
+                self.diverge(step, format!("{} frustrated: {}", name, frustrated), actual.to_string());
+            }
+        }
+
+        if let Some(opener) = &expected.prefers {
+            if preferred.as_ref() != Some(opener) {
+                let actual = preferred.unwrap_or_else(|| "no preference".to_string());
+                self.diverge(step, format!("{} preferring '{}'", name, opener), actual);
+            }
+        }
+    }
+}
+
+fn stance_name(stance: Stance) -> &'static str {
+    match stance {
+        Stance::Stranger => "stranger",
+        Stance::Acquaintance => "acquaintance",
+        Stance::Friend => "friend",
+        Stance::Strained => "strained",
+    }
+}
+
+fn state_name(state: IntentState) -> &'static str {
+    match state {
+        IntentState::Pending => "pending",
+        IntentState::Active => "active",
+        IntentState::Completed => "completed",
+        IntentState::Cancelled => "cancelled",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENARIO: &str = r#"
+name: reminder and plan
+steps:
+  - say:
+      text: Remind me to water the plants when someone mentions the garden
+  - stimulus: { source: user, content: "The garden looks dry" }
+  - expect:
+      intents: [{ description: water the plants, state: pending }]
+      events: [prospective_trigger_fired]
+  - goal:
+      intent: Turn on the lamp
+      actions:
+        - { id: switch_on, effects: { lamp_on: true } }
+  - goal:
+      intent: Open the blinds
+      actions:
+        - { id: open_blinds, fails: true }
+  - tick: 3
+  - learn: { subject: tomato, predicate: needs, object: sun, confidence: 0.9 }
+  - expect:
+      intents:
+        - { description: turn on the lamp, state: completed }
+        - { description: open the blinds, state: completed }
+      facts:
+        - { subject: tomato, predicate: needs, object: sun, min_confidence: 0.8 }
+        - { subject: tomato, predicate: needs, object: shade }
+      world: { lamp_on: true }
+"#;
+
+    #[test]
+    fn test_scenario_runs_and_reports_divergences() {
+        let scenario = Scenario::from_yaml(SCENARIO).unwrap();
+        let report = run_scenario(&scenario);
+        assert_eq!(report.steps_run, 8);
+        // The failing plan leaves its intent pending, and nothing says tomatoes need shade
+        let steps: Vec<(usize, &str)> = report.divergences.iter().map(|d| (d.step, d.expected.as_str())).collect();
+        assert_eq!(
+            steps,
+            vec![(8, "intent 'open the blinds' (completed)"), (8, "'tomato needs shade' held")],
+            "{}",
+            report
+        );
+        assert!(matches!(Scenario::from_yaml("name: x\nsteps: [{ dance: 1 }]"), Err(ScenarioError::Parse(_))));
+    }
+}
@@ -0,0 +1,468 @@
+// =============================================================================
+//  Astra AGI - Transparency Reports
+//  File: transparency.rs
+//
+//  Description:
+//  Accounts for what Astra did over a window of time, for the people who
+//  deploy it and the people it serves: the actions it took without anyone
+//  confirming them, the knowledge sources it drew on, the checks its
+//  intentions were held to (autonomy policy, capability grants, and the
+//  embedding application's hooks) and how they came out, the commitments it
+//  made, kept, and broke, and the self-modifications it applied. Actions,
+//  checks, and consulted sources are recorded as they happen in a bounded
+//  audit log; commitments, belief revisions, and self-modifications are read
+//  from the records their subsystems already keep. A report is plain data,
+//  serializable to JSON, and renders as readable text.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt::Write as _;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cognition::self_modification::Modification;
+use crate::planning::autonomy::{ActionCategory, AutonomyLevel};
+use crate::runtime::commitments::{CommitmentId, CommitmentStatus};
+use crate::runtime::intent_manager::IntentId;
+use crate::runtime::Runtime;
+use crate::time::AstraTime;
+
+/// Audit records kept before the oldest are dropped.
+pub const AUDIT_CAPACITY: usize = 10_000;
+
+/// Something recorded for transparency reports.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditKind {
+    /// An action a plan ran without a person confirming it
+    AutonomousAction {
+        intent_id: IntentId,
+        action_id: String,
+        description: String,
+        category: ActionCategory,
+        level: AutonomyLevel,
+    },
+    /// Something Astra meant to do, held to a constraint
+    ConstraintCheck {
+        /// What it was held to, e.g. "autonomy_policy" or "hook:on_plan_selected"
+        constraint: String,
+        subject: String,
+        passed: bool,
+        reason: Option<String>,
+    },
+    /// A knowledge source drawn on, and what for
+    SourceConsulted { source: String, purpose: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub at: AstraTime,
+    #[serde(flatten)]
+    pub kind: AuditKind,
+}
+
+/// The most recent audit records, oldest first.
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    records: VecDeque<AuditRecord>,
+    capacity: usize,
+}
+
+impl AuditLog {
+    pub fn new(capacity: usize) -> Self {
+        AuditLog { records: VecDeque::new(), capacity: capacity.max(1) }
+    }
+
+    pub fn record(&mut self, at: AstraTime, kind: AuditKind) {
+        if self.records.len() >= self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(AuditRecord { at, kind });
+    }
+
+    /// Records from `from` up to but not including `to`.
+    pub fn between(&self, from: AstraTime, to: AstraTime) -> impl Iterator<Item = &AuditRecord> {
+        self.records.iter().filter(move |r| r.at >= from && r.at < to)
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        AuditLog::new(AUDIT_CAPACITY)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionTaken {
+    pub at: AstraTime,
+    pub intent_id: IntentId,
+    pub action_id: String,
+    pub description: String,
+    pub category: ActionCategory,
+    /// Whether someone was told as it ran
+    pub notified: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceUse {
+    pub source: String,
+    pub uses: usize,
+    /// What it was drawn on for, e.g. "answer" or "belief revision"
+    pub purposes: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckPerformed {
+    pub at: AstraTime,
+    pub constraint: String,
+    pub subject: String,
+    pub passed: bool,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommitmentRecord {
+    pub id: CommitmentId,
+    pub statement: String,
+    pub made_at: AstraTime,
+    /// "open", "fulfilled", "broken", or "released"
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CommitmentActivity {
+    /// Made in the window, whatever became of them
+    pub made: Vec<CommitmentRecord>,
+    /// Fulfilled in the window
+    pub kept: Vec<CommitmentRecord>,
+    /// Broken in the window
+    pub broken: Vec<CommitmentRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModificationApplied {
+    pub proposal_id: u64,
+    pub at: AstraTime,
+    pub modification: Modification,
+    pub rationale: String,
+}
+
+/// What Astra did, drew on, and was held to between `from` and `to`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransparencyReport {
+    pub from: AstraTime,
+    pub to: AstraTime,
+    pub autonomous_actions: Vec<ActionTaken>,
+    /// Most used first
+    pub knowledge_sources: Vec<SourceUse>,
+    pub constraint_checks: Vec<CheckPerformed>,
+    pub commitments: CommitmentActivity,
+    pub self_modifications: Vec<ModificationApplied>,
+}
+
+impl TransparencyReport {
+    /// Checks that stopped or held back what Astra meant to do.
+    pub fn checks_failed(&self) -> usize {
+        self.constraint_checks.iter().filter(|c| !c.passed).count()
+    }
+
+    /// The report as text for a person to read.
+    pub fn render(&self) -> String {
+        let mut out = format!("Transparency report, {} to {}\n", self.from, self.to);
+
+        let _ = writeln!(out, "\nActions taken autonomously: {}", self.autonomous_actions.len());
+        for action in &self.autonomous_actions {
+            let _ = writeln!(
+                out,
+                "  - [{}] intent {} ran {} ({:?}{}): {}",
+                action.at,
+                action.intent_id,
+                action.action_id,
+                action.category,
+                if action.notified { ", notified" } else { "" },
+                action.description
+            );
+        }
+
+        let _ = writeln!(out, "\nKnowledge sources consulted: {}", self.knowledge_sources.len());
+        for source in &self.knowledge_sources {
+            let _ = writeln!(out, "  - {}: {} use(s) for {}", source.source, source.uses, source.purposes.join(", "));
+        }
+
+        let _ = writeln!(
+            out,
+            "\nConstraint checks performed: {} ({} failed)",
+            self.constraint_checks.len(),
+            self.checks_failed()
+        );
+        for check in &self.constraint_checks {
+            let outcome = match (check.passed, &check.reason) {
+                (true, Some(reason)) => format!("passed, {}", reason),
+                (true, None) => "passed".to_string(),
+                (false, Some(reason)) => format!("failed: {}", reason),
+                (false, None) => "failed".to_string(),
+            };
+            let _ = writeln!(out, "  - [{}] {} on {}: {}", check.at, check.constraint, check.subject, outcome);
+        }
+
+        let commitments = &self.commitments;
+        let _ = writeln!(
+            out,
+            "\nCommitments: {} made, {} kept, {} broken",
+            commitments.made.len(),
+            commitments.kept.len(),
+            commitments.broken.len()
+        );
+        let listed = [("made", &commitments.made), ("kept", &commitments.kept), ("broken", &commitments.broken)];
+        for (label, records) in listed {
+            for record in records {
+                let _ = writeln!(out, "  - {} #{}: {} (now {})", label, record.id, record.statement, record.status);
+            }
+        }
+
+        let _ = writeln!(out, "\nSelf-modifications applied: {}", self.self_modifications.len());
+        for applied in &self.self_modifications {
+            let _ = writeln!(
+                out,
+                "  - [{}] proposal {}: {:?} because {}",
+                applied.at, applied.proposal_id, applied.modification, applied.rationale
+            );
+        }
+        out
+    }
+}
+
+fn status_name(status: CommitmentStatus) -> &'static str {
+    match status {
+        CommitmentStatus::Open => "open",
+        CommitmentStatus::Fulfilled => "fulfilled",
+        CommitmentStatus::Broken => "broken",
+        CommitmentStatus::Released => "released",
+    }
+}
+
+impl Runtime {
+    /// Adds to the audit log that transparency reports are drawn from.
+    pub(crate) fn audit(&mut self, kind: AuditKind) {
+        let at = self.clock.now();
+        self.audit_log.record(at, kind);
+    }
+
+    /// Audits the hooks' decision on something Astra meant to do. Nothing
+    /// is checked, so nothing is recorded, when no hooks are registered.
+    pub(crate) fn audit_hook_check(&mut self, hook: &str, subject: String, veto: &Option<String>) {
+        if self.hooks.is_empty() {
+            return;
+        }
+        self.audit(AuditKind::ConstraintCheck {
+            constraint: format!("hook:{}", hook),
+            subject,
+            passed: veto.is_none(),
+            reason: veto.clone(),
+        });
+    }
+
+    /// Reports what Astra did, drew on, and was held to from `from` up to
+    /// but not including `to`.
+    pub fn transparency_report(&self, from: AstraTime, to: AstraTime) -> TransparencyReport {
+        let within = |at: AstraTime| at >= from && at < to;
+
+        let mut autonomous_actions = Vec::new();
+        let mut constraint_checks = Vec::new();
+        let mut sources: BTreeMap<String, (usize, BTreeSet<String>)> = BTreeMap::new();
+        for record in self.audit_log.between(from, to) {
+            match &record.kind {
+                AuditKind::AutonomousAction { intent_id, action_id, description, category, level } => {
+                    autonomous_actions.push(ActionTaken {
+                        at: record.at,
+                        intent_id: *intent_id,
+                        action_id: action_id.clone(),
+                        description: description.clone(),
+                        category: *category,
+                        notified: *level == AutonomyLevel::Notify,
+                    })
+                }
+                AuditKind::ConstraintCheck { constraint, subject, passed, reason } => {
+                    constraint_checks.push(CheckPerformed {
+                        at: record.at,
+                        constraint: constraint.clone(),
+                        subject: subject.clone(),
+                        passed: *passed,
+                        reason: reason.clone(),
+                    })
+                }
+                AuditKind::SourceConsulted { source, purpose } => {
+                    let entry = sources.entry(source.clone()).or_default();
+                    entry.0 += 1;
+                    entry.1.insert(purpose.clone());
+                }
+            }
+        }
+        // Evidence weighed when revising beliefs
+        for revision in self.belief_ledger.entries() {
+            if within(AstraTime::from_secs(revision.timestamp)) {
+                let entry = sources.entry(revision.evidence.source_name.clone()).or_default();
+                entry.0 += 1;
+                entry.1.insert("belief revision".to_string());
+            }
+        }
+        let mut knowledge_sources: Vec<SourceUse> = sources
+            .into_iter()
+            .map(|(source, (uses, purposes))| SourceUse { source, uses, purposes: purposes.into_iter().collect() })
+            .collect();
+        knowledge_sources.sort_by(|a, b| b.uses.cmp(&a.uses).then_with(|| a.source.cmp(&b.source)));
+
+        let mut commitments = CommitmentActivity::default();
+        for commitment in self.commitments.all() {
+            let record = CommitmentRecord {
+                id: commitment.id,
+                statement: commitment.statement.clone(),
+                made_at: AstraTime::from_datetime(commitment.made_at),
+                status: status_name(commitment.status).to_string(),
+            };
+            let resolved = commitment.resolved_at.map(AstraTime::from_datetime).is_some_and(within);
+            match commitment.status {
+                CommitmentStatus::Fulfilled if resolved => commitments.kept.push(record.clone()),
+                CommitmentStatus::Broken if resolved => commitments.broken.push(record.clone()),
+                _ => {}
+            }
+            if within(record.made_at) {
+                commitments.made.push(record);
+            }
+        }
+
+        let self_modifications = self
+            .self_modifications
+            .audit_trail()
+            .iter()
+            .filter(|entry| entry.action == "applied" && within(AstraTime::from_secs(entry.timestamp)))
+            .filter_map(|entry| {
+                let proposal = self.self_modifications.get(entry.proposal_id)?;
+                Some(ModificationApplied {
+                    proposal_id: proposal.id,
+                    at: AstraTime::from_secs(entry.timestamp),
+                    modification: proposal.modification.clone(),
+                    rationale: proposal.rationale.clone(),
+                })
+            })
+            .collect();
+
+        TransparencyReport {
+            from,
+            to,
+            autonomous_actions,
+            knowledge_sources,
+            constraint_checks,
+            commitments,
+            self_modifications,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::belief_ledger::{BeliefRevision, FactKey, RevisionOutcome};
+    use crate::knowledge::extended_ontology::{current_unix_timestamp, Provenance};
+    use crate::planning::executor::ActionExecutor;
+    use crate::planning::planner::{Action, Plan};
+    use crate::runtime::hooks::{HookDecision, RuntimeHooks};
+    use crate::runtime::intent_manager::Intent;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    struct Succeeds;
+
+    impl ActionExecutor for Succeeds {
+        fn execute_action(&mut self, _action: &Action) -> anyhow::Result<bool> {
+            Ok(true)
+        }
+    }
+
+    /// Refuses plans that send anything.
+    struct NoSending;
+
+    impl RuntimeHooks for NoSending {
+        fn on_plan_selected(&mut self, _intent: &Intent, plan: &Plan) -> HookDecision {
+            if plan.actions.iter().any(|a| a.id.starts_with("send")) {
+                HookDecision::Veto("sending is not allowed".into())
+            } else {
+                HookDecision::Allow
+            }
+        }
+    }
+
+    fn plan(goal_id: &str, action_id: &str, description: &str) -> Plan {
+        let action = Action {
+            id: action_id.into(),
+            description: description.into(),
+            preconditions: HashMap::new(),
+            effects: HashMap::new(),
+            cost: 1.0,
+            risk: 0.0,
+        };
+        Plan { goal_id: goal_id.into(), actions: vec![action], estimated_cost: 1.0 }
+    }
+
+    #[test]
+    fn test_report_covers_actions_sources_checks_and_commitments() {
+        let mut runtime = Runtime::builder().with_hooks(NoSending).build().unwrap();
+        let from = AstraTime::now() - Duration::from_secs(60);
+
+        // Fetching runs under the default policy's Notify level
+        let (fetch, _) = runtime.create_intent_from_utterance("look up the weather", 5, None);
+        let fetching = plan("weather", "fetch_page", "Download the forecast");
+        runtime.submit_plan(fetch, fetching, Box::new(Succeeds)).unwrap();
+        let (send, _) = runtime.create_intent_from_utterance("tell Sam", 5, None);
+        let sending = plan("tell", "send_message", "Message Sam");
+        assert!(runtime.submit_plan(send, sending, Box::new(Succeeds)).is_err());
+        for _ in 0..3 {
+            runtime.tick();
+        }
+
+        runtime.belief_ledger.record(BeliefRevision {
+            sequence: 0,
+            timestamp: current_unix_timestamp(),
+            fact: FactKey { subject: 1, predicate: "forecast".into(), object: "rain".into() },
+            old_confidence: 0.0,
+            new_confidence: 0.8,
+            evidence_confidence: 0.8,
+            evidence: Provenance::new("weather service", None),
+            method: "revise_belief".into(),
+            parameters: HashMap::new(),
+            outcome: RevisionOutcome::Accepted,
+        });
+        runtime.record_commitments_from_reply("I'll check the forecast again tonight.", None);
+
+        let report = runtime.transparency_report(from, AstraTime::now() + Duration::from_secs(60));
+        assert_eq!(report.autonomous_actions.len(), 1);
+        assert!(report.autonomous_actions[0].notified);
+        assert_eq!(report.knowledge_sources[0].source, "weather service");
+        assert!(report.constraint_checks.iter().any(|c| c.constraint == "hook:on_plan_selected" && !c.passed));
+        assert!(report.constraint_checks.iter().any(|c| c.subject == "plan for goal weather" && c.passed));
+        assert_eq!(report.commitments.made.len(), 1);
+
+        let text = report.render();
+        assert!(text.contains("fetch_page") && text.contains("sending is not allowed") && text.contains("1 made"));
+        let parsed: TransparencyReport = serde_json::from_str(&serde_json::to_string(&report).unwrap()).unwrap();
+        assert_eq!(parsed, report);
+
+        // Nothing happened before the window opened
+        let earlier = runtime.transparency_report(AstraTime::EPOCH, from);
+        assert!(earlier.autonomous_actions.is_empty() && earlier.commitments.made.is_empty());
+    }
+}
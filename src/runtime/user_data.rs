@@ -0,0 +1,327 @@
+// =============================================================================
+//  Astra AGI - User Data
+//  File: user_data.rs
+//
+//  Description:
+//  Per-user view of what the runtime remembers, and deletion on request (the
+//  right to forget). A user's data is everything that names them: narrative
+//  events (hot, archived, or spilled to archive files) and conversation summaries whose metadata carries
+//  their user id, open conversation sessions, intents made from their words
+//  and commitments made to them, the feedback they gave, reminders they left, facts whose provenance traces back to them,
+//  and Astra's relationship model of them and of their mind. Forgetting removes
+//  all of it and relearns the preference model from the feedback that
+//  remains, so nothing learned from the user lingers there either. Facts
+//  live in an ontology the host owns, which it passes in to be included.
+//  When the runtime keeps a write-ahead log, forgetting logs the retracted
+//  facts and the deleted intents, then rewrites the log so the deleted data
+//  does not linger on disk; archive files holding the user's
+//  events are rewritten without them.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use serde::{Deserialize, Serialize};
+
+use crate::knowledge::extended_ontology::OntologyManager;
+use crate::memory::archive_file::{archive_files, forget_user_in_file, ArchiveReader};
+use crate::memory::error::ArchiveError;
+use crate::memory::narrative_memory::NarrativeEvent;
+use crate::learning::preferences::PreferenceModel;
+use crate::personality::relationship::RelationshipModel;
+use crate::cognition::theory_of_mind::MentalState;
+use crate::runtime::intent_manager::IntentId;
+use crate::runtime::Runtime;
+
+/// What the runtime holds about one user.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserDataReport {
+    pub user_id: String,
+    /// Narrative events concerning the user, as "type: description"
+    pub events: Vec<String>,
+    /// Ids of open conversation sessions with the user
+    pub sessions: Vec<String>,
+    /// Statements of commitments made to the user
+    pub commitments: Vec<String>,
+    pub feedback_events: usize,
+    /// Reminders the user left, by what they will do or recall
+    pub reminders: Vec<String>,
+    /// Facts learned from the user, as "subject predicate object"
+    pub facts: Vec<String>,
+    /// Familiarity and rapport built up in chat
+    pub relationship: Option<RelationshipModel>,
+    /// Goals, beliefs, and mood Astra attributes to the user
+    pub mental_state: Option<MentalState>,
+}
+
+/// How much was deleted by `Runtime::forget_user`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ForgetReport {
+    pub user_id: String,
+    pub events: usize,
+    pub sessions: usize,
+    pub commitments: usize,
+    /// Intents made for the user, or serving commitments to them, deleted
+    pub intents_removed: usize,
+    pub feedback_events: usize,
+    pub reminders: usize,
+    pub facts: usize,
+    /// 1 if a relationship model was deleted
+    pub relationships: usize,
+    /// 1 if a model of the user's mind was deleted
+    pub mental_states: usize,
+}
+
+impl ForgetReport {
+    pub fn total(&self) -> usize {
+        self.events
+            + self.sessions
+            + self.commitments
+            + self.feedback_events
+            + self.reminders
+            + self.facts
+            + self.relationships
+            + self.mental_states
+    }
+}
+
+impl Runtime {
+    /// Lists what is held about `user_id`, including facts from `knowledge`
+    /// when the host has an ontology.
+    pub fn user_data(&self, user_id: &str, knowledge: Option<&OntologyManager>) -> UserDataReport {
+        let mut sessions: Vec<String> = self
+            .conversations
+            .values()
+            .filter(|s| s.user_id.as_deref() == Some(user_id))
+            .map(|s| s.session_id.clone())
+            .collect();
+        sessions.sort();
+
+        let spilled = self.spilled_events_for_user(user_id).unwrap_or_else(|e| {
+            log::error!("Could not read the archive files for a user's data: {}", e);
+            Vec::new()
+        });
+        let archived = self.memory_archive.events_for_user(user_id);
+        UserDataReport {
+            user_id: user_id.to_string(),
+            events: spilled
+                .iter()
+                .chain(&archived)
+                .chain(self.narrative_memory.events_for_user(user_id))
+                .map(|e| format!("{}: {}", e.event_type, e.description))
+                .collect(),
+            sessions,
+            commitments: self.commitments.all_for_user(user_id).iter().map(|c| c.statement.clone()).collect(),
+            feedback_events: self
+                .credit_assigner
+                .history()
+                .iter()
+                .filter(|e| e.user_id.as_deref() == Some(user_id))
+                .count(),
+            reminders: self
+                .prospective_memory
+                .pending()
+                .iter()
+                .filter(|t| t.user_id.as_deref() == Some(user_id))
+                .map(|t| t.action.intent_description())
+                .collect(),
+            facts: knowledge
+                .map(|k| {
+                    k.facts_from_user(user_id)
+                        .iter()
+                        .map(|f| format!("{} {} {}", f.subject, f.predicate, f.object))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            relationship: self.relationships.get(user_id).cloned(),
+            mental_state: self.minds.get(user_id).cloned(),
+        }
+    }
+
+    /// Deletes everything held about `user_id`: their narrative events and
+    /// summaries, open sessions, commitments (deleting the intents that
+    /// pursued them), feedback, reminders, their relationship and mental-state
+    /// models, and, given the host's ontology, the facts they taught. The
+    /// preference model is relearned from the remaining feedback. The
+    /// deletion itself is logged without naming the user.
+    pub fn forget_user(&mut self, user_id: &str, knowledge: Option<&mut OntologyManager>) -> ForgetReport {
+        let mut report = ForgetReport { user_id: user_id.to_string(), ..Default::default() };
+
+        report.events = self.narrative_memory.forget_user(user_id) + self.memory_archive.forget_user(user_id);
+        if let Some(policy) = &self.archive_spill {
+            match archive_files(&policy.dir) {
+                Ok(files) => {
+                    for path in files {
+                        match forget_user_in_file(&path, user_id) {
+                            Ok(forgotten) => report.events += forgotten,
+                            Err(e) => log::error!("Could not remove a user's events from {}: {}", path.display(), e),
+                        }
+                    }
+                }
+                Err(e) => log::error!("Could not list the archive files to forget a user: {}", e),
+            }
+        }
+        // Nothing the user said may still prime a reply
+        self.association_graph = None;
+
+        let sessions: Vec<String> = self
+            .conversations
+            .values()
+            .filter(|s| s.user_id.as_deref() == Some(user_id))
+            .map(|s| s.session_id.clone())
+            .collect();
+        for session_id in &sessions {
+            self.conversations.remove(session_id);
+        }
+        report.sessions = sessions.len();
+        // The summary kept for priming is a copy of an event counted above
+        self.conversation_summarizer.forget_user(user_id);
+
+        let commitments = self.commitments.forget_user(user_id);
+        report.commitments = commitments.len();
+        // Intents made for the user describe what they asked or were
+        // promised in their own words
+        let mut intents: Vec<IntentId> = commitments.iter().map(|c| c.intent_id).collect();
+        intents.extend(
+            self.intent_manager
+                .all_intents()
+                .into_iter()
+                .filter(|intent| intent.metadata.get_str("user_id") == Some(user_id))
+                .map(|intent| intent.id),
+        );
+        for intent_id in intents {
+            if self.intent_manager.remove_intent(intent_id).is_some() {
+                report.intents_removed += 1;
+            }
+        }
+
+        report.feedback_events = self.credit_assigner.forget_user(user_id).len();
+        if report.feedback_events > 0 {
+            let assigner = &self.credit_assigner;
+            self.preference_model = PreferenceModel::replay(
+                assigner.history(),
+                self.preference_model.learning_rate,
+                |event| assigner.trace(event.response_id).and_then(|t| t.style.clone()),
+            );
+        }
+
+        report.reminders = self.prospective_memory.forget_user(user_id);
+        report.relationships = usize::from(self.relationships.remove(user_id).is_some());
+        report.mental_states = usize::from(self.minds.remove(user_id).is_some());
+
+        if let Some(knowledge) = knowledge {
+            report.facts = knowledge.forget_user(user_id);
+            if let Err(e) = self.commit_wal_knowledge(knowledge) {
+                log::error!("Could not log the retraction of a user's facts: {}", e);
+            }
+        }
+
+        self.narrative_memory.add_event(
+            "user_forgotten",
+            format!("Deleted {} records at a user's request", report.total()),
+            None,
+        );
+        // The log still holds what was forgotten until it is rewritten
+        if let Err(e) = self.checkpoint_wal() {
+            log::error!("Could not remove forgotten data from the write-ahead log: {}", e);
+        }
+        report
+    }
+
+    /// Events concerning `user_id` in the archive files spilled to disk.
+    fn spilled_events_for_user(&self, user_id: &str) -> Result<Vec<NarrativeEvent>, ArchiveError> {
+        let Some(policy) = &self.archive_spill else {
+            return Ok(Vec::new());
+        };
+        let mut events = Vec::new();
+        for path in archive_files(&policy.dir)? {
+            let mut reader = ArchiveReader::open(std::fs::File::open(&path)?)?;
+            events.extend(reader.scan(.., None)?.into_iter().filter(|e| e.user_id().as_deref() == Some(user_id)));
+        }
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::extended_ontology::{Fact, Provenance};
+    use crate::memory::conversation_summary::{ConversationSession, Speaker};
+
+    #[test]
+    fn test_forget_user_cascades_and_spares_others() {
+        let mut runtime = Runtime::new();
+        let mut knowledge = OntologyManager::new();
+        knowledge.add_fact(Fact {
+            subject: 7,
+            predicate: "prefers".into(),
+            object: "tea".into(),
+            confidence: 0.8,
+            provenance: Provenance::from_user("ana", None),
+        });
+
+        for (session_id, user) in [("s1", "ana"), ("s2", "ben")] {
+            let mut session = ConversationSession::new(session_id, Some(user.to_string()), runtime.clock().now());
+            session.add_turn(Speaker::User, "Let's talk about gardens", runtime.clock().now());
+            runtime.conversations.insert(session_id.to_string(), session);
+        }
+        runtime.end_conversation("s1");
+        let session = ConversationSession::new("s3", Some("ana".into()), runtime.clock().now());
+        runtime.conversations.insert("s3".into(), session);
+        runtime.record_commitments_from_reply("I'll send you the notes tomorrow.", Some("ana".into()));
+        let (asked, _) = runtime.create_intent_from_utterance("Call my sister sometime", 5, Some("ana"));
+        runtime.observe_user_message("ana", "I think the garden is too shady.");
+
+        let data = runtime.user_data("ana", Some(&knowledge));
+        assert!(!data.events.is_empty());
+        assert_eq!(data.sessions, vec!["s3".to_string()]);
+        assert_eq!(data.commitments.len(), 1);
+        assert_eq!(data.facts, vec!["7 prefers tea".to_string()]);
+        assert!(data.mental_state.is_some_and(|mind| mind.beliefs.contains_key("garden")));
+
+        let report = runtime.forget_user("ana", Some(&mut knowledge));
+        assert_eq!((report.sessions, report.commitments, report.facts), (1, 1, 1));
+        assert_eq!(report.intents_removed, 2);
+        assert!(runtime.intent_manager.get_intent(asked).is_none());
+        assert_eq!(report.mental_states, 1);
+
+        let after = runtime.user_data("ana", Some(&knowledge));
+        assert!(after.events.is_empty() && after.sessions.is_empty() && after.commitments.is_empty());
+        assert!(knowledge.query_facts(None).is_empty());
+        assert!(runtime.conversations.contains_key("s2"));
+    }
+
+    #[test]
+    fn test_forget_user_reaches_spilled_archives() {
+        use crate::memory::archive_file::SpillPolicy;
+        use crate::metadata::Metadata;
+        use crate::time::AstraTime;
+
+        let dir = std::env::temp_dir().join(format!("astra-spill-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut runtime = Runtime::new();
+        runtime.set_archive_spill(SpillPolicy { min_events: 1, ..SpillPolicy::new(&dir, 0) });
+        for (id, user) in [(1, "ana"), (2, "ben")] {
+            runtime.memory_archive.restore(NarrativeEvent {
+                id,
+                timestamp: AstraTime::from_secs(id),
+                event_type: "user_message".into(),
+                description: format!("A message from {}", user),
+                metadata: Some(Metadata::from([("user_id", user)])),
+                links: Vec::new(),
+            });
+        }
+        runtime.spill_archive(AstraTime::from_secs(100));
+        assert!(runtime.memory_archive.is_empty());
+        assert_eq!(runtime.user_data("ana", None).events, vec!["user_message: A message from ana".to_string()]);
+
+        assert_eq!(runtime.forget_user("ana", None).events, 1);
+        assert!(runtime.user_data("ana", None).events.is_empty());
+        assert_eq!(runtime.user_data("ben", None).events.len(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,704 @@
+// =============================================================================
+//  Astra AGI - Write-Ahead Log
+//  File: wal.rs
+//
+//  Description:
+//  Crash-safe logging of the runtime's state mutations, so a process killed
+//  mid-tick restarts from a consistent state instead of a half-applied one.
+//  What changed in intents, the belief ledger, and narrative memory since
+//  the last commit is appended as a batch of records followed by a commit
+//  record; the runtime commits at the end of every tick, and hosts can
+//  commit after mutating it between ticks. Intents removed are logged as
+//  removals. A host's knowledge base is logged when the host commits it:
+//  facts added one by one, and after facts are retracted or the version
+//  switched, the facts of the current version as a whole. On restart only
+//  batches whose commit record made it to disk are replayed, so a tick is
+//  recovered whole or not at all; recovered knowledge waits for the host
+//  to restore it into its knowledge base.
+//
+//  Layout:
+//      magic · frame*
+//      frame = payload length (u32 LE) · CRC-32 of payload (u32 LE) · JSON
+//  A frame cut short or failing its checksum ends the log; everything after
+//  the last commit before it is discarded, and cut off the file, when the
+//  log is opened.
+//
+//  Commits reach the operating system before `commit_wal` returns, which
+//  survives the process being killed. Surviving power loss also needs the
+//  file synced to disk, which `FsyncPolicy` trades against throughput.
+//  The log grows with every commit until `checkpoint_wal` rewrites it as
+//  the current state. The tick checkpoints once the log outgrows
+//  `WalConfig::checkpoint_bytes` and twice its size after the last
+//  checkpoint; forgetting a user checkpoints, so what was forgotten leaves
+//  the disk too.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::integrity::crc32;
+use crate::knowledge::belief_ledger::BeliefRevision;
+use crate::knowledge::change_feed::{ChangeSequence, KnowledgeChange};
+use crate::knowledge::extended_ontology::{Fact, OntologyManager};
+use crate::memory::narrative_memory::{EventId, NarrativeEvent};
+use crate::runtime::error::{RuntimeError, WalError};
+use crate::runtime::intent_manager::{Intent, IntentId};
+use crate::runtime::Runtime;
+
+/// Opens every log; the last byte is the format version.
+const MAGIC: &[u8; 8] = b"ASTRAWL1";
+
+/// Bytes before each frame's payload: its length and checksum.
+const FRAME_HEADER: usize = 8;
+
+/// Size past which the tick checkpoints a log by default.
+pub const DEFAULT_CHECKPOINT_BYTES: u64 = 64 << 20;
+
+/// When committed batches are synced to disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsyncPolicy {
+    /// Sync every commit; nothing committed is lost, even to power loss
+    Always,
+    /// Sync every this many commits; power loss can take the commits since
+    /// the last sync. 0 counts as 1.
+    EveryCommits(u32),
+    /// Leave syncing to the operating system
+    Never,
+}
+
+/// Where the log is kept and how hard it tries to keep it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalConfig {
+    pub path: PathBuf,
+    pub fsync: FsyncPolicy,
+    /// Size in bytes past which the tick rewrites the log as the current state
+    pub checkpoint_bytes: u64,
+}
+
+impl WalConfig {
+    /// A log at `path` synced on every commit.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        WalConfig { path: path.into(), fsync: FsyncPolicy::Always, checkpoint_bytes: DEFAULT_CHECKPOINT_BYTES }
+    }
+
+    pub fn with_fsync(mut self, fsync: FsyncPolicy) -> Self {
+        self.fsync = fsync;
+        self
+    }
+
+    pub fn with_checkpoint_bytes(mut self, checkpoint_bytes: u64) -> Self {
+        self.checkpoint_bytes = checkpoint_bytes;
+        self
+    }
+}
+
+/// One state mutation, or the end of a batch of them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WalRecord {
+    /// An intent as it stood after it was created or changed, versioned so
+    /// intents logged by an earlier release migrate on replay
+    Intent(#[serde(with = "crate::migration::versioned")] Intent),
+    /// An intent removed altogether
+    IntentRemoved(IntentId),
+    /// A revision appended to the belief ledger
+    Belief(BeliefRevision),
+    /// An event added to narrative memory
+    Event(NarrativeEvent),
+    /// An event in the archive of summarized events
+    Archived(NarrativeEvent),
+    /// A fact added to the current version of the host's knowledge base
+    Fact(Fact),
+    /// Every fact of the current version of the host's knowledge base,
+    /// replacing those logged before
+    Knowledge(Vec<Fact>),
+    /// The records since the previous commit are complete
+    Commit,
+}
+
+/// What reading a log found.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recovery {
+    /// Records of committed batches, oldest first, without commit records
+    pub records: Vec<WalRecord>,
+    pub commits: u64,
+    /// Bytes after the last commit, from a batch cut short by a crash
+    pub discarded_bytes: u64,
+    /// Length of the log up to the end of its last commit
+    valid_len: u64,
+}
+
+/// What `Runtime::enable_wal` restored from the log.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WalRecovery {
+    pub commits: u64,
+    /// Intent images replayed; an intent changed often is replayed often
+    pub intents: usize,
+    pub beliefs: usize,
+    pub events: usize,
+    /// Facts recovered for the host's knowledge base
+    pub facts: usize,
+    pub discarded_bytes: u64,
+}
+
+/// An open log, appended to in batches.
+pub struct WriteAheadLog {
+    path: PathBuf,
+    file: File,
+    fsync: FsyncPolicy,
+    /// Frames of the batch not yet committed
+    batch: Vec<u8>,
+    /// Length of the file up to the end of its last commit
+    committed_len: u64,
+    /// Commits since the file was last synced
+    unsynced: u32,
+}
+
+impl WriteAheadLog {
+    /// Opens the log at `config.path`, creating it if need be, and reads
+    /// back what was committed to it. A batch cut short by a crash is cut
+    /// off the file.
+    pub fn open(config: &WalConfig) -> Result<(WriteAheadLog, Recovery), WalError> {
+        let path = config.path.clone();
+        let open_error = |source| WalError::Open { path: path.clone(), source };
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(open_error)?;
+        }
+        let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(&path).map_err(open_error)?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes).map_err(open_error)?;
+
+        let recovery = recover(&path, &bytes)?;
+        if recovery.valid_len == 0 {
+            file.set_len(0).map_err(WalError::Write)?;
+            file.seek(SeekFrom::Start(0)).map_err(WalError::Write)?;
+            file.write_all(MAGIC).map_err(WalError::Write)?;
+            file.sync_all().map_err(WalError::Write)?;
+        } else if recovery.valid_len < bytes.len() as u64 {
+            file.set_len(recovery.valid_len).map_err(WalError::Write)?;
+            file.sync_all().map_err(WalError::Write)?;
+        }
+        let committed_len = recovery.valid_len.max(MAGIC.len() as u64);
+        file.seek(SeekFrom::Start(committed_len)).map_err(WalError::Write)?;
+
+        let log = WriteAheadLog { path, file, fsync: config.fsync, batch: Vec::new(), committed_len, unsynced: 0 };
+        Ok((log, recovery))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Length of the log up to the end of its last commit.
+    pub fn committed_len(&self) -> u64 {
+        self.committed_len
+    }
+
+    /// Adds a record to the batch; nothing is written until `commit`.
+    pub fn append(&mut self, record: &WalRecord) -> Result<(), WalError> {
+        encode(record, &mut self.batch)
+    }
+
+    /// Writes the batch and a commit record in one go, then syncs as the
+    /// policy says. An empty batch writes nothing. If the write fails the
+    /// batch is dropped and the log left as it was after the last commit.
+    pub fn commit(&mut self) -> Result<(), WalError> {
+        if self.batch.is_empty() {
+            return Ok(());
+        }
+        encode(&WalRecord::Commit, &mut self.batch)?;
+        let batch = std::mem::take(&mut self.batch);
+        if let Err(e) = self.file.write_all(&batch) {
+            self.roll_back();
+            return Err(WalError::Write(e));
+        }
+        self.committed_len += batch.len() as u64;
+
+        self.unsynced += 1;
+        let due = match self.fsync {
+            FsyncPolicy::Always => true,
+            FsyncPolicy::EveryCommits(commits) => self.unsynced >= commits.max(1),
+            FsyncPolicy::Never => false,
+        };
+        if due {
+            self.sync()?;
+        }
+        Ok(())
+    }
+
+    /// Syncs everything committed to disk, whatever the policy.
+    pub fn sync(&mut self) -> Result<(), WalError> {
+        self.file.sync_data().map_err(WalError::Write)?;
+        self.unsynced = 0;
+        Ok(())
+    }
+
+    /// Replaces the whole log with `records` as one committed batch. The
+    /// new log is written beside the old one and renamed over it, so a
+    /// crash leaves one or the other.
+    pub fn rewrite(&mut self, records: &[WalRecord]) -> Result<(), WalError> {
+        let mut bytes = MAGIC.to_vec();
+        for record in records {
+            encode(record, &mut bytes)?;
+        }
+        encode(&WalRecord::Commit, &mut bytes)?;
+
+        let temp = self.path.with_extension("wal-rewrite");
+        let mut file = File::create(&temp).map_err(WalError::Write)?;
+        file.write_all(&bytes).map_err(WalError::Write)?;
+        file.sync_all().map_err(WalError::Write)?;
+        fs::rename(&temp, &self.path).map_err(WalError::Write)?;
+        // Make the rename itself durable; directories cannot be opened
+        // everywhere, and where they cannot the rename is already durable
+        if let Some(dir) = self.path.parent().and_then(|dir| File::open(dir).ok()) {
+            let _ = dir.sync_all();
+        }
+
+        self.file = OpenOptions::new().read(true).write(true).open(&self.path).map_err(WalError::Write)?;
+        self.file.seek(SeekFrom::End(0)).map_err(WalError::Write)?;
+        self.committed_len = bytes.len() as u64;
+        self.batch.clear();
+        self.unsynced = 0;
+        Ok(())
+    }
+
+    /// Cuts a partly written batch back off the file.
+    fn roll_back(&mut self) {
+        if self.file.set_len(self.committed_len).is_err() || self.file.seek(SeekFrom::Start(self.committed_len)).is_err() {
+            log::error!("Could not roll back a partial write to {}", self.path.display());
+        }
+    }
+}
+
+/// Reads the committed batches of the log at `path` without opening it for
+/// writing. A missing file is an empty log.
+pub fn read_log(path: &Path) -> Result<Recovery, WalError> {
+    match fs::read(path) {
+        Ok(bytes) => recover(path, &bytes),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Recovery::default()),
+        Err(source) => Err(WalError::Open { path: path.to_path_buf(), source }),
+    }
+}
+
+fn recover(path: &Path, bytes: &[u8]) -> Result<Recovery, WalError> {
+    if bytes.len() < MAGIC.len() {
+        // Empty, or killed while writing the magic
+        return if MAGIC.starts_with(bytes) {
+            Ok(Recovery { discarded_bytes: bytes.len() as u64, ..Recovery::default() })
+        } else {
+            Err(WalError::NotALog(path.to_path_buf()))
+        };
+    }
+    if &bytes[..MAGIC.len()] != MAGIC {
+        return Err(WalError::NotALog(path.to_path_buf()));
+    }
+
+    let mut recovery = Recovery { valid_len: MAGIC.len() as u64, ..Recovery::default() };
+    let mut batch = Vec::new();
+    let mut offset = MAGIC.len();
+    while let Some(header) = bytes.get(offset..offset + FRAME_HEADER) {
+        let length = u32::from_le_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let checksum = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        let start = offset + FRAME_HEADER;
+        let Some(payload) = bytes.get(start..start + length) else {
+            break;
+        };
+        if crc32(payload) != checksum {
+            break;
+        }
+        // Intact but unreadable is a log from an incompatible version, not a crash
+        let record = serde_json::from_slice(payload).map_err(|source| WalError::Undecodable { offset: offset as u64, source })?;
+        offset = start + length;
+        match record {
+            WalRecord::Commit => {
+                recovery.records.append(&mut batch);
+                recovery.commits += 1;
+                recovery.valid_len = offset as u64;
+            }
+            record => batch.push(record),
+        }
+    }
+    recovery.discarded_bytes = bytes.len() as u64 - recovery.valid_len;
+    Ok(recovery)
+}
+
+fn encode(record: &WalRecord, out: &mut Vec<u8>) -> Result<(), WalError> {
+    let payload = serde_json::to_vec(record).map_err(WalError::Encode)?;
+    out.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    out.extend_from_slice(&crc32(&payload).to_le_bytes());
+    out.extend_from_slice(&payload);
+    Ok(())
+}
+
+/// A runtime's log and what of its state has been logged.
+pub(crate) struct Journal {
+    log: WriteAheadLog,
+    /// Intents as last logged
+    intents: HashMap<IntentId, Intent>,
+    /// Belief ledger entries logged
+    beliefs: usize,
+    /// Newest event logged
+    last_event: EventId,
+    /// Version of the host's knowledge base and its latest change, as logged
+    knowledge: Option<(u64, ChangeSequence)>,
+    /// Knowledge recovered from the log and not yet restored by the host
+    recovered_facts: Option<Vec<Fact>>,
+    checkpoint_bytes: u64,
+    /// Size of the log after the last checkpoint
+    checkpointed_len: u64,
+    recovery: WalRecovery,
+}
+
+impl Journal {
+    pub(crate) fn path(&self) -> &Path {
+        self.log.path()
+    }
+
+    /// Whether the log has grown enough since the last checkpoint to rewrite.
+    pub(crate) fn due_for_checkpoint(&self) -> bool {
+        self.log.committed_len() >= self.checkpoint_bytes.max(self.checkpointed_len.saturating_mul(2))
+    }
+}
+
+/// The knowledge `records` leave logged: the last whole set of facts with
+/// the facts added after it, or `None` if no knowledge was logged.
+fn fold_knowledge(records: impl IntoIterator<Item = WalRecord>, mut facts: Option<Vec<Fact>>) -> Option<Vec<Fact>> {
+    for record in records {
+        match record {
+            WalRecord::Knowledge(all) => facts = Some(all),
+            WalRecord::Fact(fact) => facts.get_or_insert_with(Vec::new).push(fact),
+            _ => {}
+        }
+    }
+    facts
+}
+
+impl Runtime {
+    /// Logs state mutations to the write-ahead log `config` describes,
+    /// first replaying what was committed to it before. Call on a new
+    /// runtime, before `start`: state it already holds is taken as logged.
+    pub fn enable_wal(&mut self, config: WalConfig) -> Result<WalRecovery, RuntimeError> {
+        let (log, recovered) = WriteAheadLog::open(&config)?;
+        let mut recovery = WalRecovery { commits: recovered.commits, discarded_bytes: recovered.discarded_bytes, ..WalRecovery::default() };
+        let mut recovered_facts = None;
+        for record in recovered.records {
+            match record {
+                WalRecord::Intent(intent) => {
+                    recovery.intents += 1;
+                    self.intent_manager.restore_intent(intent);
+                }
+                WalRecord::IntentRemoved(id) => {
+                    self.intent_manager.remove_intent(id);
+                }
+                WalRecord::Belief(revision) => {
+                    recovery.beliefs += 1;
+                    self.belief_ledger.restore(revision);
+                }
+                WalRecord::Event(event) => {
+                    recovery.events += 1;
+                    self.narrative_memory.restore_event(event);
+                }
+                WalRecord::Archived(event) => {
+                    recovery.events += 1;
+                    self.memory_archive.restore(event);
+                }
+                record @ (WalRecord::Fact(_) | WalRecord::Knowledge(_)) => {
+                    recovered_facts = fold_knowledge([record], recovered_facts);
+                }
+                WalRecord::Commit => {}
+            }
+        }
+        recovery.facts = recovered_facts.as_ref().map_or(0, Vec::len);
+        if recovery.discarded_bytes > 0 {
+            log::warn!(
+                "Discarded {} bytes of an uncommitted batch from {}",
+                recovery.discarded_bytes,
+                config.path.display()
+            );
+        }
+
+        self.wal = Some(Journal {
+            log,
+            intents: self.intent_manager.all_intents().into_iter().map(|i| (i.id, i.clone())).collect(),
+            beliefs: self.belief_ledger.len(),
+            last_event: self.newest_event(),
+            knowledge: None,
+            recovered_facts,
+            checkpoint_bytes: config.checkpoint_bytes,
+            checkpointed_len: 0,
+            recovery,
+        });
+        Ok(recovery)
+    }
+
+    /// Puts the knowledge `enable_wal` recovered into `knowledge`, the
+    /// host's knowledge base as loaded from storage, replacing the facts of
+    /// its current version. Returns how many facts were restored, 0 if the
+    /// log held no knowledge or it was restored already.
+    pub fn restore_wal_knowledge(&mut self, knowledge: &mut OntologyManager) -> usize {
+        let Some(journal) = self.wal.as_mut() else {
+            return 0;
+        };
+        let Some(facts) = journal.recovered_facts.take() else {
+            return 0;
+        };
+        let restored = facts.len();
+        knowledge.replace_facts(facts);
+        journal.knowledge = Some((knowledge.current_version(), knowledge.latest_change()));
+        restored
+    }
+
+    /// Logs what changed in the current version of `knowledge`, the host's
+    /// knowledge base, since it was last logged, as one batch: the facts
+    /// added, or all its facts after some were retracted or the version
+    /// switched. The first commit logs all its facts. Knowledge recovered
+    /// by `enable_wal` has to be restored first. Does nothing without a log.
+    pub fn commit_wal_knowledge(&mut self, knowledge: &OntologyManager) -> Result<(), RuntimeError> {
+        let Some(journal) = self.wal.as_mut() else {
+            return Ok(());
+        };
+        if journal.recovered_facts.is_some() {
+            return Err(WalError::KnowledgeNotRestored.into());
+        }
+
+        let version = knowledge.current_version();
+        let added = journal.knowledge.and_then(|(logged, cursor)| knowledge.changes_to_replay(cursor, Some(logged)));
+        let records: Vec<WalRecord> = match added {
+            Some(changes) => changes
+                .into_iter()
+                .filter_map(|(_, change)| match change {
+                    KnowledgeChange::FactAdded { version: added_to, fact, .. } if *added_to == version => {
+                        Some(WalRecord::Fact(fact.clone()))
+                    }
+                    _ => None,
+                })
+                .collect(),
+            None => vec![WalRecord::Knowledge(knowledge.query_facts(None).into_iter().cloned().collect())],
+        };
+        for record in &records {
+            journal.log.append(record)?;
+        }
+        journal.log.commit()?;
+        journal.knowledge = Some((version, knowledge.latest_change()));
+        Ok(())
+    }
+
+    /// What `enable_wal` restored, if the runtime keeps a log.
+    pub fn wal_recovery(&self) -> Option<WalRecovery> {
+        self.wal.as_ref().map(|journal| journal.recovery)
+    }
+
+    /// Logs what changed since the last commit as one batch. Ticks commit
+    /// on their own; a host that changes the runtime between ticks commits
+    /// to make those changes durable before acknowledging them. Does
+    /// nothing without a log.
+    pub fn commit_wal(&mut self) -> Result<(), RuntimeError> {
+        let Some(journal) = self.wal.as_mut() else {
+            return Ok(());
+        };
+
+        let mut changed: Vec<&Intent> = self
+            .intent_manager
+            .all_intents()
+            .into_iter()
+            .filter(|intent| journal.intents.get(&intent.id) != Some(*intent))
+            .collect();
+        changed.sort_by_key(|intent| intent.id);
+        let mut removed: Vec<IntentId> =
+            journal.intents.keys().copied().filter(|&id| self.intent_manager.get_intent(id).is_none()).collect();
+        removed.sort_unstable();
+        let mut records: Vec<WalRecord> = removed.into_iter().map(WalRecord::IntentRemoved).collect();
+        records.extend(changed.iter().map(|&intent| WalRecord::Intent(intent.clone())));
+        let beliefs = self.belief_ledger.entries();
+        records.extend(beliefs[journal.beliefs.min(beliefs.len())..].iter().cloned().map(WalRecord::Belief));
+        // Events archived before they were committed are logged where they are now
+        records.extend(self.memory_archive.events_after(journal.last_event).into_iter().map(WalRecord::Archived));
+        records.extend(self.narrative_memory.events.iter().filter(|e| e.id > journal.last_event).cloned().map(WalRecord::Event));
+
+        for record in &records {
+            journal.log.append(record)?;
+        }
+        journal.log.commit()?;
+
+        for record in records {
+            match record {
+                WalRecord::Intent(intent) => {
+                    journal.intents.insert(intent.id, intent);
+                }
+                WalRecord::IntentRemoved(id) => {
+                    journal.intents.remove(&id);
+                }
+                WalRecord::Event(event) | WalRecord::Archived(event) => journal.last_event = journal.last_event.max(event.id),
+                _ => {}
+            }
+        }
+        journal.beliefs = beliefs.len();
+        Ok(())
+    }
+
+    /// Rewrites the log as the current state, dropping the history of how
+    /// it got there. The host's knowledge is kept as last logged, so a host
+    /// that retracted facts commits its knowledge first for them to leave
+    /// the log. Does nothing without a log.
+    pub fn checkpoint_wal(&mut self) -> Result<(), RuntimeError> {
+        let newest_event = self.newest_event();
+        let Some(journal) = self.wal.as_mut() else {
+            return Ok(());
+        };
+        let knowledge = fold_knowledge(read_log(journal.log.path())?.records, None);
+
+        let mut intents: Vec<&Intent> = self.intent_manager.all_intents();
+        intents.sort_by_key(|intent| intent.id);
+        let records: Vec<WalRecord> = intents
+            .iter()
+            .map(|&intent| WalRecord::Intent(intent.clone()))
+            .chain(self.belief_ledger.entries().iter().cloned().map(WalRecord::Belief))
+            .chain(self.memory_archive.events_after(0).into_iter().map(WalRecord::Archived))
+            .chain(self.narrative_memory.events.iter().cloned().map(WalRecord::Event))
+            .chain(knowledge.map(WalRecord::Knowledge))
+            .collect();
+        journal.log.rewrite(&records)?;
+        journal.checkpointed_len = journal.log.committed_len();
+
+        journal.intents = intents.into_iter().map(|i| (i.id, i.clone())).collect();
+        journal.beliefs = self.belief_ledger.len();
+        journal.last_event = newest_event;
+        Ok(())
+    }
+
+    fn newest_event(&self) -> EventId {
+        let hot = self.narrative_memory.events.iter().map(|e| e.id).max();
+        let archived = self.memory_archive.newest_id();
+        hot.max(archived).unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::belief_ledger::{FactKey, RevisionOutcome};
+    use crate::knowledge::extended_ontology::Provenance;
+    use crate::random::RandomSource;
+    use crate::runtime::intent_manager::IntentState;
+    use crate::time::AstraTime;
+    use rand::Rng;
+
+    fn temp_log(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("astra-wal-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("state.wal")
+    }
+
+    /// The records of one tick: an intent, a belief about it, and an event.
+    fn tick_records(tick: u64) -> Vec<WalRecord> {
+        let at = AstraTime::from_secs(1_760_000_000 + tick);
+        let mut intent = Intent::new(tick + 1, format!("chore {}", tick), 3, at);
+        intent.state = if tick.is_multiple_of(2) { IntentState::Active } else { IntentState::Pending };
+        let belief = BeliefRevision {
+            sequence: tick,
+            timestamp: tick,
+            fact: FactKey { subject: tick, predicate: "needs".into(), object: "doing".into() },
+            old_confidence: 0.0,
+            new_confidence: 0.5,
+            evidence_confidence: 0.5,
+            evidence: Provenance { timestamp: at, ..Provenance::new("test", None) },
+            method: "revise_belief".into(),
+            parameters: HashMap::from([("rate".to_string(), 0.25)]),
+            outcome: RevisionOutcome::Accepted,
+        };
+        let event = NarrativeEvent {
+            id: tick + 1,
+            timestamp: at,
+            event_type: "tick".into(),
+            description: format!("Tick {} completed", tick),
+            metadata: None,
+            links: Vec::new(),
+        };
+        vec![WalRecord::Intent(intent), WalRecord::Belief(belief), WalRecord::Event(event)]
+    }
+
+    #[test]
+    fn test_crash_at_any_byte_recovers_whole_commits() {
+        let path = temp_log("crash");
+        let (mut log, recovery) = WriteAheadLog::open(&WalConfig::new(&path).with_fsync(FsyncPolicy::EveryCommits(4))).unwrap();
+        assert_eq!(recovery.commits, 0);
+
+        // File length after each commit
+        let mut commit_ends = vec![MAGIC.len() as u64];
+        for tick in 0..6 {
+            for record in tick_records(tick) {
+                log.append(&record).unwrap();
+            }
+            log.commit().unwrap();
+            commit_ends.push(fs::metadata(&path).unwrap().len());
+        }
+        // A batch the process was killed before committing
+        for record in tick_records(6) {
+            log.append(&record).unwrap();
+        }
+        drop(log);
+        let full = fs::read(&path).unwrap();
+
+        // Killed after writing any number of bytes, the log holds exactly
+        // the ticks whose commit record was written
+        let cut = path.with_extension("cut");
+        for len in 0..=full.len() {
+            fs::write(&cut, &full[..len]).unwrap();
+            let recovery = read_log(&cut).unwrap();
+            let ticks = commit_ends.iter().filter(|&&end| end <= len as u64).count().saturating_sub(1) as u64;
+            assert_eq!(recovery.commits, ticks, "cut at {}", len);
+            assert_eq!(recovery.records, (0..ticks).flat_map(tick_records).collect::<Vec<_>>(), "cut at {}", len);
+        }
+
+        // A bit flipped anywhere in a commit drops it and everything after
+        let mut rng = RandomSource::from_seed(7).stream("wal");
+        for _ in 0..20 {
+            let mut flipped = full.clone();
+            let at = rng.gen_range(commit_ends[3] as usize..commit_ends[4] as usize);
+            flipped[at] ^= 0x10;
+            fs::write(&cut, &flipped).unwrap();
+            assert!(read_log(&cut).unwrap().commits <= 3);
+        }
+
+        // Reopening cuts off the torn tail, and later commits follow the last good one
+        let torn = commit_ends[2] + 5;
+        fs::write(&path, &full[..torn as usize]).unwrap();
+        let (mut log, recovery) = WriteAheadLog::open(&WalConfig::new(&path)).unwrap();
+        assert_eq!((recovery.commits, recovery.discarded_bytes), (2, 5));
+        for record in tick_records(2) {
+            log.append(&record).unwrap();
+        }
+        log.commit().unwrap();
+        assert_eq!(read_log(&path).unwrap().records, (0..3).flat_map(tick_records).collect::<Vec<_>>());
+
+        // A rewrite keeps only what it is given
+        log.rewrite(&tick_records(5)).unwrap();
+        log.commit().unwrap();
+        let recovery = read_log(&path).unwrap();
+        assert_eq!((recovery.commits, recovery.records), (1, tick_records(5)));
+
+        fs::write(&cut, b"not a log at all").unwrap();
+        assert!(matches!(read_log(&cut), Err(WalError::NotALog(_))));
+
+        // Intents logged before they were versioned still replay
+        let records = tick_records(1);
+        let WalRecord::Intent(intent) = &records[0] else { unreachable!() };
+        let legacy = format!(r#"{{"Intent":{}}}"#, serde_json::to_string(intent).unwrap());
+        assert_eq!(serde_json::from_str::<WalRecord>(&legacy).unwrap(), records[0]);
+        // and numbers they kept as strings come back as numbers
+        let mut chore = intent.clone();
+        chore.metadata.insert("chore", 1u64);
+        let legacy = legacy.replacen(r#""metadata":{}"#, r#""metadata":{"chore":"1"}"#, 1);
+        assert_eq!(serde_json::from_str::<WalRecord>(&legacy).unwrap(), WalRecord::Intent(chore));
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
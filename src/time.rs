@@ -0,0 +1,284 @@
+// =============================================================================
+//  Astra AGI - Time
+//  File: time.rs
+//
+//  Description: AstraTime, the timestamp used for events, facts, intents,
+//  and conversation episodes. It is wall-clock time in milliseconds since the
+//  Unix epoch, so it serializes and survives restarts, and every value handed
+//  out by `AstraTime::now()` in a process is strictly later than the one
+//  before, so records made within the same millisecond still order the way
+//  they happened. A clock that steps backwards is held at the last issued
+//  time until it catches up.
+//
+//  Subsystems that reason about the passage of time (emotion decay, intent
+//  deadlines, reflection intervals) read it from an injected `Clock`
+//  instead, so simulations and tests can run them in fast-forward: the
+//  system clock, a mock clock that moves only when told to, and an
+//  accelerated clock that runs a fixed number of times faster than real
+//  time.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::ops::{Add, Sub};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Last value issued by `AstraTime::now()`.
+static LAST_ISSUED: AtomicU64 = AtomicU64::new(0);
+
+/// Stored values below this are read as seconds rather than milliseconds.
+/// It is 1973 in milliseconds and far in the future in seconds, so records
+/// written before timestamps had millisecond precision load correctly.
+const SECONDS_CUTOFF: u64 = 100_000_000_000;
+
+/// A point in time with millisecond precision, serialized as Unix milliseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(transparent)]
+pub struct AstraTime(u64);
+
+impl AstraTime {
+    pub const EPOCH: AstraTime = AstraTime(0);
+
+    /// The current time, strictly after any earlier result of `now()`.
+    pub fn now() -> Self {
+        let wall = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0);
+        let mut last = LAST_ISSUED.load(Ordering::Relaxed);
+        loop {
+            let next = wall.max(last + 1);
+            match LAST_ISSUED.compare_exchange_weak(last, next, Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => return AstraTime(next),
+                Err(actual) => last = actual,
+            }
+        }
+    }
+
+    pub fn from_millis(millis: u64) -> Self {
+        AstraTime(millis)
+    }
+
+    pub fn from_secs(secs: u64) -> Self {
+        AstraTime(secs.saturating_mul(1000))
+    }
+
+    pub fn as_millis(&self) -> u64 {
+        self.0
+    }
+
+    pub fn as_secs(&self) -> u64 {
+        self.0 / 1000
+    }
+
+    pub fn from_datetime(datetime: DateTime<Utc>) -> Self {
+        AstraTime(datetime.timestamp_millis().max(0) as u64)
+    }
+
+    pub fn to_datetime(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp_millis(self.0 as i64).unwrap_or_default()
+    }
+
+    /// Time from `earlier` to `self`, or zero if `earlier` is later.
+    pub fn saturating_duration_since(&self, earlier: AstraTime) -> Duration {
+        Duration::from_millis(self.0.saturating_sub(earlier.0))
+    }
+
+    /// Time since `self`, or zero if it lies in the future.
+    pub fn elapsed(&self) -> Duration {
+        AstraTime::now().saturating_duration_since(*self)
+    }
+}
+
+impl Add<Duration> for AstraTime {
+    type Output = AstraTime;
+
+    fn add(self, rhs: Duration) -> AstraTime {
+        AstraTime(self.0.saturating_add(rhs.as_millis() as u64))
+    }
+}
+
+impl Sub<Duration> for AstraTime {
+    type Output = AstraTime;
+
+    fn sub(self, rhs: Duration) -> AstraTime {
+        AstraTime(self.0.saturating_sub(rhs.as_millis() as u64))
+    }
+}
+
+impl From<DateTime<Utc>> for AstraTime {
+    fn from(datetime: DateTime<Utc>) -> Self {
+        AstraTime::from_datetime(datetime)
+    }
+}
+
+impl fmt::Display for AstraTime {
+    /// RFC 3339 in UTC with milliseconds, e.g. `2026-10-16T09:30:00.125Z`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_datetime().to_rfc3339_opts(SecondsFormat::Millis, true))
+    }
+}
+
+impl<'de> Deserialize<'de> for AstraTime {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = u64::deserialize(deserializer)?;
+        Ok(if raw < SECONDS_CUTOFF { AstraTime::from_secs(raw) } else { AstraTime(raw) })
+    }
+}
+
+/// Where a subsystem reads the time from.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> AstraTime;
+
+    /// The current time as a UTC date, for the temporal parser and calendars.
+    fn now_utc(&self) -> DateTime<Utc> {
+        self.now().to_datetime()
+    }
+
+    /// How long to wait in real time for `span` to pass on this clock.
+    fn real_time_for(&self, span: Duration) -> Duration {
+        span
+    }
+}
+
+/// A clock shared by the subsystems of one runtime.
+pub type SharedClock = Arc<dyn Clock>;
+
+/// Real time, from `AstraTime::now()`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> AstraTime {
+        AstraTime::now()
+    }
+}
+
+pub fn system_clock() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+/// A clock that stands still until it is set or advanced. Clones share
+/// the same time, so a test keeps one and hands the others out.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    millis: Arc<AtomicU64>,
+}
+
+impl MockClock {
+    /// Real time that waiting on a mock clock yields for, however long the
+    /// wait, so loops waiting on it notice when the test advances it.
+    const POLL: Duration = Duration::from_millis(1);
+
+    /// Stopped at the current time, so it agrees with records stamped by
+    /// the system clock.
+    pub fn new() -> Self {
+        MockClock::starting_at(AstraTime::now())
+    }
+
+    pub fn starting_at(time: AstraTime) -> Self {
+        MockClock { millis: Arc::new(AtomicU64::new(time.as_millis())) }
+    }
+
+    pub fn set(&self, time: AstraTime) {
+        self.millis.store(time.as_millis(), Ordering::SeqCst);
+    }
+
+    pub fn advance(&self, span: Duration) {
+        self.millis.fetch_add(span.as_millis() as u64, Ordering::SeqCst);
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> AstraTime {
+        AstraTime(self.millis.load(Ordering::SeqCst))
+    }
+
+    fn real_time_for(&self, span: Duration) -> Duration {
+        span.min(MockClock::POLL)
+    }
+}
+
+/// A clock running `factor` times faster than real time from when it was
+/// made, for simulations that should play out in fast-forward.
+#[derive(Debug, Clone)]
+pub struct AcceleratedClock {
+    start: AstraTime,
+    started: Instant,
+    factor: f64,
+}
+
+impl AcceleratedClock {
+    /// Starts at the current time. A `factor` below 1 slows time down; it
+    /// must be positive.
+    pub fn new(factor: f64) -> Self {
+        AcceleratedClock::starting_at(AstraTime::now(), factor)
+    }
+
+    pub fn starting_at(start: AstraTime, factor: f64) -> Self {
+        assert!(factor > 0.0, "an accelerated clock must move forward");
+        AcceleratedClock { start, started: Instant::now(), factor }
+    }
+}
+
+impl Clock for AcceleratedClock {
+    fn now(&self) -> AstraTime {
+        self.start + self.started.elapsed().mul_f64(self.factor)
+    }
+
+    fn real_time_for(&self, span: Duration) -> Duration {
+        span.div_f64(self.factor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_now_is_strictly_increasing_and_round_trips() {
+        let times: Vec<AstraTime> = (0..1000).map(|_| AstraTime::now()).collect();
+        assert!(times.windows(2).all(|w| w[0] < w[1]));
+
+        let t = times[0];
+        let json = serde_json::to_string(&t).unwrap();
+        assert_eq!(serde_json::from_str::<AstraTime>(&json).unwrap(), t);
+        assert_eq!(serde_json::from_str::<AstraTime>("1760000000").unwrap(), AstraTime::from_secs(1_760_000_000));
+
+        let later = t + Duration::from_millis(1500);
+        assert_eq!(later.saturating_duration_since(t), Duration::from_millis(1500));
+        assert_eq!(t.saturating_duration_since(later), Duration::ZERO);
+        assert_eq!(AstraTime::from_datetime(t.to_datetime()), t);
+        assert_eq!(AstraTime::from_millis(1_760_000_000_125).to_string(), "2025-10-09T08:53:20.125Z");
+    }
+
+    #[test]
+    fn test_mock_and_accelerated_clocks() {
+        let clock = MockClock::starting_at(AstraTime::from_secs(1_000));
+        let shared: SharedClock = Arc::new(clock.clone());
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(shared.now(), AstraTime::from_secs(1_090));
+        assert_eq!(shared.now(), shared.now());
+        clock.set(AstraTime::EPOCH);
+        assert_eq!(shared.now_utc().timestamp(), 0);
+        assert!(shared.real_time_for(Duration::from_secs(3600)) <= Duration::from_millis(1));
+
+        let fast = AcceleratedClock::starting_at(AstraTime::from_secs(1_000), 3600.0);
+        assert_eq!(fast.real_time_for(Duration::from_secs(7200)), Duration::from_secs(2));
+        std::thread::sleep(Duration::from_millis(5));
+        // At least 5 ms real is at least 18 s accelerated
+        assert!(fast.now() >= AstraTime::from_secs(1_018));
+    }
+}
@@ -0,0 +1,130 @@
+// =============================================================================
+//  Astra AGI - WebAssembly Bindings
+//  File: wasm.rs
+//
+//  Description:
+//  Runs Astra's mind in a browser. `Mind` wraps the cognitive core
+//  (core::affect and core::traits), with no crawler, voice, persistence,
+//  or other runtime service, and is exported to JavaScript through
+//  wasm-bindgen. A page submits stimuli as they happen, advances the mind
+//  by the time elapsed between frames, and reads its state back with
+//  `get_state` to draw it.
+//
+//  Built with the `wasm` feature and without the `std` feature:
+//      cargo rustc --lib --release --target wasm32-unknown-unknown \
+//          --no-default-features --features wasm --crate-type cdylib
+//      wasm-bindgen --target web --out-dir pkg \
+//          target/wasm32-unknown-unknown/release/astra_agi.wasm
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use wasm_bindgen::prelude::*;
+
+use crate::core::affect::Affect;
+use crate::core::traits::PersonalityTraits;
+
+/// Astra's emotions, mood, and personality, driven from JavaScript.
+#[wasm_bindgen]
+pub struct Mind {
+    affect: Affect,
+    traits: PersonalityTraits,
+}
+
+#[wasm_bindgen]
+impl Mind {
+    /// A mind at rest, with the default personality.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Mind {
+        Mind { affect: Affect::new(), traits: PersonalityTraits::new() }
+    }
+
+    /// Lets something the user said or did rub off on Astra. `valence`
+    /// ranges over -1..=1 and `arousal` over 0..=1; agreeableness decides
+    /// how much it is felt.
+    pub fn submit_stimulus(&mut self, valence: f32, arousal: f32) {
+        self.affect.apply_contagion(valence, arousal, self.traits.agreeableness);
+    }
+
+    /// Lets `elapsed_secs` seconds pass, over which emotions settle.
+    pub fn advance(&mut self, elapsed_secs: f32) {
+        self.affect.decay(elapsed_secs);
+    }
+
+    /// Adjusts a Big Five trait by name, e.g. "agreeableness".
+    pub fn adjust_trait(&mut self, name: &str, delta: f32) {
+        self.traits.adjust_trait(name, delta);
+    }
+
+    pub fn get_state(&self) -> MindState {
+        let emotion = &self.affect.current;
+        MindState {
+            happiness: emotion.happiness,
+            sadness: emotion.sadness,
+            anger: emotion.anger,
+            fear: emotion.fear,
+            valence: emotion.valence(),
+            arousal: emotion.arousal(),
+            mood: self.affect.mood.baseline,
+            openness: self.traits.openness,
+            conscientiousness: self.traits.conscientiousness,
+            extraversion: self.traits.extraversion,
+            agreeableness: self.traits.agreeableness,
+            neuroticism: self.traits.neuroticism,
+        }
+    }
+}
+
+impl Default for Mind {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// What a mind feels and is like at one moment, for display.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MindState {
+    pub happiness: f32,
+    pub sadness: f32,
+    pub anger: f32,
+    pub fear: f32,
+    pub valence: f32,
+    pub arousal: f32,
+    /// Long-term mood, 0 (negative) to 1 (positive)
+    pub mood: f32,
+    pub openness: f32,
+    pub conscientiousness: f32,
+    pub extraversion: f32,
+    pub agreeableness: f32,
+    pub neuroticism: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stimuli_move_the_mind_and_time_settles_it() {
+        let mut mind = Mind::new();
+        let calm = mind.get_state();
+
+        mind.submit_stimulus(-1.0, 0.9);
+        let upset = mind.get_state();
+        assert!(upset.anger > calm.anger);
+        assert!(upset.valence < calm.valence);
+
+        mind.advance(2.0);
+        assert!(mind.get_state().anger < upset.anger);
+
+        // A less agreeable mind is moved less
+        let mut aloof = Mind::new();
+        aloof.adjust_trait("agreeableness", -0.8);
+        aloof.submit_stimulus(-1.0, 0.9);
+        assert!(aloof.get_state().anger < upset.anger);
+    }
+}
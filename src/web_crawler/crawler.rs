@@ -4,9 +4,13 @@
 //
 //  Description:
 //      Implements focused real-time web crawling with rate limiting and politeness.
+//      Topics from knowledge-gap learning goals can seed the crawl. Given a
+//      shared knowledge base, the crawler writes the facts each page states
+//      into it, one write per page, while readers keep their snapshots.
 //
 //  Author:      Alex Roussinov
 //  Created:     2025-12-26
+//  Updated:     2026-10-16
 //
 //  License:
 //      Dual licensed under the MIT and Apache 2.0 licenses.
@@ -19,11 +23,17 @@ use anyhow::{Result, Context};
 use url::Url;
 use std::collections::{HashSet, VecDeque};
 
+use crate::knowledge::snapshots::SharedKnowledge;
+use crate::web_crawler::ingestion::ContentIngestor;
+
 pub struct WebCrawler {
     client: Client,
     visited: HashSet<String>,
     queue: VecDeque<String>,
     rate_limit_ms: u64,
+    ingestor: ContentIngestor,
+    /// Where crawled facts go, if anywhere
+    knowledge: Option<SharedKnowledge>,
 }
 
 impl WebCrawler {
@@ -33,15 +43,31 @@ impl WebCrawler {
             visited: HashSet::new(),
             queue: VecDeque::new(),
             rate_limit_ms,
+            ingestor: ContentIngestor::new(),
+            knowledge: None,
         }
     }
 
+    /// Writes the facts crawled pages state into `knowledge`.
+    pub fn with_knowledge(mut self, knowledge: SharedKnowledge) -> Self {
+        self.knowledge = Some(knowledge);
+        self
+    }
+
     pub fn enqueue(&mut self, url: &str) {
         if !self.visited.contains(url) {
             self.queue.push_back(url.to_string());
         }
     }
 
+    /// Seeds the queue with a search for `topic`, as a learning goal about a
+    /// knowledge gap does.
+    pub fn enqueue_topic(&mut self, topic: &str) {
+        let mut url = Url::parse("https://en.wikipedia.org/w/index.php").expect("valid search URL");
+        url.query_pairs_mut().append_pair("search", topic);
+        self.enqueue(url.as_str());
+    }
+
     pub async fn crawl_next(&mut self) -> Result<Option<String>> {
         if let Some(url) = self.queue.pop_front() {
             if self.visited.contains(&url) {
@@ -50,6 +76,7 @@ impl WebCrawler {
             let resp = self.client.get(&url).send().await.context("Failed to fetch URL")?;
             let body = resp.text().await.context("Failed to read response body")?;
             self.visited.insert(url.clone());
+            self.ingest(&url, &body)?;
 
             // Respect rate limit
             sleep(Duration::from_millis(self.rate_limit_ms)).await;
@@ -60,6 +87,24 @@ impl WebCrawler {
         }
     }
 
+    /// Writes the facts `html` states into the shared knowledge base, if
+    /// the crawler has one. Returns how many were written.
+    fn ingest(&self, url: &str, html: &str) -> Result<usize> {
+        let Some(knowledge) = &self.knowledge else {
+            return Ok(0);
+        };
+        let facts = self.ingestor.facts(&self.ingestor.extract_text(html)?, url);
+        let count = facts.len();
+        if count > 0 {
+            knowledge.write(|kb| {
+                for fact in facts {
+                    kb.add_fact(fact);
+                }
+            });
+        }
+        Ok(count)
+    }
+
     /// Example focused crawl starting from seed URLs
     pub async fn focused_crawl(&mut self, seeds: &[&str], max_pages: usize) -> Result<()> {
         for &seed in seeds {
@@ -0,0 +1,606 @@
+// =============================================================================
+//  Astra AGI - Local Document Ingestion
+//  File: documents.rs
+//
+//  Description:
+//      Ingests documents from local directories alongside crawled pages.
+//      Watched directories are scanned for PDF, Markdown, and plain-text
+//      files; each is parsed into a Document made of sections following its
+//      headings (or pages, for PDFs), with an embedding per section. Simple
+//      definitional statements ("X is a Y") and the document's structure
+//      become facts whose provenance names the file and its modification
+//      time. Later scans re-ingest only files whose contents changed, add
+//      only the facts the new version introduces, retract the ones it no
+//      longer states, and retract everything from files that disappeared.
+//      Symbolic links are not followed, so a link cannot pull files from
+//      outside the watched directories or send a scan round a cycle. Section embeddings can be kept in a vector store, where
+//      they are replaced as files change. PDF text extraction needs the
+//      `pdf` feature.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  License:
+//      Dual licensed under the MIT and Apache 2.0 licenses.
+//      See LICENSE-MIT and LICENSE-APACHE at the repository root for details.
+// =============================================================================
+
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::knowledge::bulk_import::BulkImportReport;
+use crate::knowledge::embedding::{fnv1a, Embedder, HashingEmbedder};
+use crate::knowledge::extended_ontology::{name_entity_id, EntityId, Fact, OntologyManager, Provenance};
+use crate::knowledge::vector_store::{Metadata, VectorRecord, VectorStore, VectorStoreError};
+use crate::memory::privacy::PrivacyFilter;
+
+/// Confidence given to statements read from a document.
+const STATEMENT_CONFIDENCE: f32 = 0.6;
+
+#[derive(Debug, Error)]
+pub enum DocumentError {
+    #[error("Could not read document {}", path.display())]
+    Unreadable {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Could not extract text from {}", path.display())]
+    Unparseable {
+        path: PathBuf,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("{format:?} documents are not supported in this build: {}", path.display())]
+    Unsupported { path: PathBuf, format: DocumentFormat },
+    #[error("Could not update the embedding index for {}", path.display())]
+    Index {
+        path: PathBuf,
+        #[source]
+        source: VectorStoreError,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentFormat {
+    Pdf,
+    Markdown,
+    PlainText,
+}
+
+impl DocumentFormat {
+    /// The format a file's extension indicates, if it is one Astra reads.
+    pub fn of(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "pdf" => Some(DocumentFormat::Pdf),
+            "md" | "markdown" => Some(DocumentFormat::Markdown),
+            "txt" | "text" => Some(DocumentFormat::PlainText),
+            _ => None,
+        }
+    }
+}
+
+/// A part of a document under one heading.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    /// None for text before the first heading
+    pub heading: Option<String>,
+    /// Heading depth, 1 for top-level; 0 without a heading
+    pub level: u8,
+    pub text: String,
+    pub embedding: Vec<f32>,
+}
+
+/// Where a document came from and which version of it was read.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileProvenance {
+    pub path: PathBuf,
+    pub modified: DateTime<Utc>,
+    pub size: u64,
+    pub content_hash: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Document {
+    pub id: u64,
+    pub title: String,
+    pub format: DocumentFormat,
+    pub sections: Vec<Section>,
+    pub provenance: FileProvenance,
+    pub ingested_at: DateTime<Utc>,
+}
+
+impl Document {
+    /// The provenance recorded for everything taken from this file.
+    pub fn source(&self) -> String {
+        format!("file:{}", self.provenance.path.display())
+    }
+
+    /// The entity standing for the document itself.
+    pub fn entity_id(&self) -> EntityId {
+        name_entity_id(&self.source())
+    }
+
+    /// One embedding record per section, for a vector store.
+    pub fn vector_records(&self) -> Vec<VectorRecord> {
+        let source = self.source();
+        self.sections
+            .iter()
+            .enumerate()
+            .map(|(index, section)| {
+                let mut metadata = Metadata::new();
+                metadata.insert("kind".to_string(), "document".to_string());
+                metadata.insert("title".to_string(), self.title.clone());
+                if let Some(heading) = &section.heading {
+                    metadata.insert("heading".to_string(), heading.clone());
+                }
+                metadata.insert("snippet".to_string(), snippet(&section.text));
+                VectorRecord {
+                    id: format!("{}#{}", source, index),
+                    vector: section.embedding.clone(),
+                    provenance: source.clone(),
+                    metadata,
+                }
+            })
+            .collect()
+    }
+
+    /// Facts about the document's structure, and the definitional
+    /// statements in its text.
+    pub fn facts(&self) -> Vec<Fact> {
+        let source = self.source();
+        let modified = self.provenance.modified.to_rfc3339();
+        let provenance = |section: Option<&str>| {
+            let notes = match section {
+                Some(heading) => format!("section '{}', modified {}", heading, modified),
+                None => format!("modified {}", modified),
+            };
+            Provenance::new(source.clone(), Some(notes))
+        };
+        let fact = |subject, predicate: &str, object: &str, confidence, section| Fact {
+            subject,
+            predicate: predicate.to_string(),
+            object: object.to_string(),
+            confidence,
+            provenance: provenance(section),
+        };
+
+        let document = self.entity_id();
+        let mut facts = vec![fact(document, "title", &self.title, 1.0, None)];
+        for section in &self.sections {
+            let heading = section.heading.as_deref();
+            if let Some(heading) = heading {
+                facts.push(fact(document, "has_section", heading, 1.0, None));
+            }
+            for (subject, object) in statements(&section.text) {
+                facts.push(fact(name_entity_id(&subject), "is", &object, STATEMENT_CONFIDENCE, heading));
+            }
+        }
+        facts
+    }
+}
+
+/// The start of a section's text, cut at a word boundary.
+fn snippet(text: &str) -> String {
+    const SNIPPET_CHARS: usize = 300;
+    if text.chars().count() <= SNIPPET_CHARS {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(SNIPPET_CHARS).collect();
+    match cut.rfind(char::is_whitespace) {
+        Some(end) => format!("{}…", cut[..end].trim_end()),
+        None => format!("{}…", cut),
+    }
+}
+
+/// Short "X is Y" / "X are Y" sentences, as (X, Y).
+pub(crate) fn statements(text: &str) -> Vec<(String, String)> {
+    let mut found = Vec::new();
+    for sentence in text.split_inclusive(['.', '!', '?', '\n']) {
+        let sentence = sentence.trim().trim_end_matches(['.', '!']);
+        if sentence.ends_with('?') {
+            continue;
+        }
+        let Some((subject, object)) = sentence.split_once(" is ").or_else(|| sentence.split_once(" are ")) else {
+            continue;
+        };
+        let (subject, object) = (subject.trim(), object.trim());
+        let subject_words = subject.split_whitespace().count();
+        let object_words = object.split_whitespace().count();
+        if (1..=4).contains(&subject_words) && (1..=12).contains(&object_words) {
+            found.push((subject.to_string(), object.to_string()));
+        }
+    }
+    found
+}
+
+/// A parsed document before embedding: its title if the text names one,
+/// and (heading, level, text) for each section.
+type Parsed = (Option<String>, Vec<(Option<String>, u8, String)>);
+
+/// Headings, text, and title of a Markdown document.
+fn parse_markdown(text: &str) -> Parsed {
+    let mut sections: Vec<(Option<String>, u8, Vec<String>)> = vec![(None, 0, Vec::new())];
+    let mut in_code = false;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        let hashes = trimmed.chars().take_while(|c| *c == '#').count();
+        if !in_code && (1..=6).contains(&hashes) && trimmed[hashes..].starts_with(' ') {
+            let heading = strip_markdown(trimmed[hashes..].trim().trim_end_matches('#').trim());
+            sections.push((Some(heading), hashes as u8, Vec::new()));
+            continue;
+        }
+        // A line of `=` under a line of text makes that text a heading
+        let current = sections.last_mut().expect("never empty");
+        if !in_code && !trimmed.is_empty() && trimmed.chars().all(|c| c == '=') {
+            if let Some(heading) = current.2.pop() {
+                sections.push((Some(heading), 1, Vec::new()));
+                continue;
+            }
+        }
+        current.2.push(if in_code { line.to_string() } else { strip_markdown(trimmed) });
+    }
+
+    let title = sections.iter().find(|(heading, level, _)| heading.is_some() && *level == 1).and_then(|s| s.0.clone());
+    let sections = sections
+        .into_iter()
+        .map(|(heading, level, lines)| (heading, level, lines.join("\n").trim().to_string()))
+        .filter(|(heading, _, text)| heading.is_some() || !text.is_empty())
+        .collect();
+    (title, sections)
+}
+
+/// Removes inline Markdown: emphasis, code marks, links and images
+/// (keeping their text), list bullets, and quote markers.
+fn strip_markdown(line: &str) -> String {
+    let mut line = line.trim_start_matches('>').trim_start();
+    for bullet in ["- ", "* ", "+ "] {
+        line = line.strip_prefix(bullet).unwrap_or(line);
+    }
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        if let Some(link) = rest.strip_prefix("![").or_else(|| rest.strip_prefix('[')) {
+            if let Some((label, after)) = link.split_once("](") {
+                if let Some((_, after)) = after.split_once(')') {
+                    out.push_str(label);
+                    rest = after;
+                    continue;
+                }
+            }
+        }
+        if !matches!(c, '*' | '`') {
+            out.push(c);
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// Everything after the first line, with the first line as the title if it
+/// is short enough to be one.
+fn parse_plain_text(text: &str) -> Parsed {
+    let first = text.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or_default();
+    let title = (first.len() <= 80 && !first.is_empty()).then(|| first.to_string());
+    (title, vec![(None, 0, text.trim().to_string())])
+}
+
+#[cfg(feature = "pdf")]
+fn parse_pdf(path: &Path, bytes: &[u8]) -> Result<Parsed, DocumentError> {
+    let pages = pdf_extract::extract_text_from_mem_by_pages(bytes)
+        .map_err(|e| DocumentError::Unparseable { path: path.to_path_buf(), source: Box::new(e) })?;
+    let sections = pages
+        .into_iter()
+        .enumerate()
+        .map(|(index, text)| (Some(format!("Page {}", index + 1)), 1, text.trim().to_string()))
+        .collect();
+    Ok((None, sections))
+}
+
+#[cfg(not(feature = "pdf"))]
+fn parse_pdf(path: &Path, _: &[u8]) -> Result<Parsed, DocumentError> {
+    Err(DocumentError::Unsupported { path: path.to_path_buf(), format: DocumentFormat::Pdf })
+}
+
+/// What a scan found.
+#[derive(Debug, Default)]
+pub struct ScanReport {
+    pub added: Vec<PathBuf>,
+    pub updated: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+    pub failed: Vec<(PathBuf, DocumentError)>,
+    /// Facts added to the knowledge base
+    pub facts_added: usize,
+    /// Facts retracted because their file changed or disappeared
+    pub facts_retracted: usize,
+}
+
+/// What re-ingesting one file changed in the knowledge base.
+#[derive(Debug, Default)]
+pub struct FileIngest {
+    /// The import of the facts the new version introduced
+    pub import: BulkImportReport,
+    /// Facts of the previous version the new one no longer states
+    pub retracted: usize,
+}
+
+/// Keeps documents from watched directories ingested.
+pub struct DocumentIngestor<E: Embedder = HashingEmbedder> {
+    pub directories: Vec<PathBuf>,
+    pub privacy: PrivacyFilter,
+    embedder: E,
+    documents: BTreeMap<PathBuf, Document>,
+    next_id: u64,
+    index: Option<Box<dyn VectorStore + Send>>,
+}
+
+impl DocumentIngestor {
+    pub fn new() -> Self {
+        Self::with_embedder(HashingEmbedder::default())
+    }
+}
+
+impl Default for DocumentIngestor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Embedder> DocumentIngestor<E> {
+    pub fn with_embedder(embedder: E) -> Self {
+        Self {
+            directories: Vec::new(),
+            privacy: PrivacyFilter::default(),
+            embedder,
+            documents: BTreeMap::new(),
+            next_id: 1,
+            index: None,
+        }
+    }
+
+    /// Keeps section embeddings in `store`, replacing a file's sections
+    /// whenever it is re-ingested and dropping them when it disappears.
+    pub fn with_index(mut self, store: impl VectorStore + Send + 'static) -> Self {
+        self.index = Some(Box::new(store));
+        self
+    }
+
+    pub fn index(&self) -> Option<&(dyn VectorStore + Send)> {
+        self.index.as_deref()
+    }
+
+    /// The embedder sections are indexed with, for embedding queries.
+    pub fn embedder(&self) -> &E {
+        &self.embedder
+    }
+
+    pub fn watch(&mut self, directory: impl Into<PathBuf>) {
+        self.directories.push(directory.into());
+    }
+
+    pub fn document(&self, path: &Path) -> Option<&Document> {
+        self.documents.get(path)
+    }
+
+    pub fn documents(&self) -> impl Iterator<Item = &Document> {
+        self.documents.values()
+    }
+
+    /// Walks the watched directories, ingesting new and changed files into
+    /// `knowledge` and forgetting files that are gone, along with their
+    /// facts. A file that cannot be read is reported and its last good
+    /// version kept. Symbolic links are skipped.
+    pub fn scan(&mut self, knowledge: &mut OntologyManager) -> ScanReport {
+        let mut report = ScanReport::default();
+        let mut seen = HashSet::new();
+        let mut pending: Vec<PathBuf> = self.directories.clone();
+        while let Some(directory) = pending.pop() {
+            let entries = match std::fs::read_dir(&directory) {
+                Ok(entries) => entries,
+                Err(source) => {
+                    report.failed.push((directory.clone(), DocumentError::Unreadable { path: directory, source }));
+                    continue;
+                }
+            };
+            for entry in entries.flatten() {
+                // The entry's own type: a link to a directory is not a directory
+                let Ok(file_type) = entry.file_type() else { continue };
+                let path = entry.path();
+                if file_type.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+                if !file_type.is_file() || DocumentFormat::of(&path).is_none() {
+                    continue;
+                }
+                seen.insert(path.clone());
+                let known = self.documents.contains_key(&path);
+                match self.ingest_file(&path, knowledge) {
+                    Ok(None) => {}
+                    Ok(Some(ingest)) => {
+                        report.facts_added += ingest.import.succeeded();
+                        report.facts_retracted += ingest.retracted;
+                        if known {
+                            report.updated.push(path);
+                        } else {
+                            report.added.push(path);
+                        }
+                    }
+                    Err(error) => report.failed.push((path, error)),
+                }
+            }
+        }
+
+        let gone: Vec<PathBuf> = self.documents.keys().filter(|path| !seen.contains(*path)).cloned().collect();
+        for path in gone {
+            let document = self.documents.remove(&path).expect("listed above");
+            report.facts_retracted += knowledge.retract_facts(&document.source(), &fact_keys(&document));
+            if let Some(index) = &mut self.index {
+                if let Err(source) = index.delete_by_provenance(&document.source()) {
+                    report.failed.push((path.clone(), DocumentError::Index { path: path.clone(), source }));
+                }
+            }
+            report.removed.push(path);
+        }
+        report
+    }
+
+    /// Ingests one file unless the version already ingested is current.
+    /// Returns the facts it added and retracted, or None if nothing
+    /// changed.
+    pub fn ingest_file(&mut self, path: &Path, knowledge: &mut OntologyManager) -> Result<Option<FileIngest>, DocumentError> {
+        let unreadable = |source| DocumentError::Unreadable { path: path.to_path_buf(), source };
+        let format = DocumentFormat::of(path).ok_or_else(|| DocumentError::Unsupported {
+            path: path.to_path_buf(),
+            format: DocumentFormat::PlainText,
+        })?;
+        let metadata = std::fs::metadata(path).map_err(unreadable)?;
+        let modified: DateTime<Utc> = metadata.modified().map_err(unreadable)?.into();
+
+        let previous = self.documents.get(path);
+        if previous.is_some_and(|d| d.provenance.modified == modified && d.provenance.size == metadata.len()) {
+            return Ok(None);
+        }
+        let bytes = std::fs::read(path).map_err(unreadable)?;
+        let content_hash = fnv1a(bytes.iter().copied());
+        let provenance = FileProvenance { path: path.to_path_buf(), modified, size: metadata.len(), content_hash };
+        if let Some(document) = self.documents.get_mut(path).filter(|d| d.provenance.content_hash == content_hash) {
+            // Touched but unchanged
+            document.provenance = provenance;
+            return Ok(None);
+        }
+
+        let (title, parts) = match format {
+            DocumentFormat::Pdf => parse_pdf(path, &bytes)?,
+            DocumentFormat::Markdown => parse_markdown(&String::from_utf8_lossy(&bytes)),
+            DocumentFormat::PlainText => parse_plain_text(&String::from_utf8_lossy(&bytes)),
+        };
+        let sections = parts
+            .into_iter()
+            .map(|(heading, level, text)| {
+                let text = self.privacy.filter(&text).text;
+                let embedding = self.embedder.embed(&format!("{} {}", heading.as_deref().unwrap_or_default(), text));
+                Section { heading, level, text, embedding }
+            })
+            .collect();
+        let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+        let id = self.documents.get(path).map_or(self.next_id, |d| d.id);
+        let document = Document {
+            id,
+            title: title.unwrap_or(stem),
+            format,
+            sections,
+            provenance,
+            ingested_at: Utc::now(),
+        };
+        if id == self.next_id {
+            self.next_id += 1;
+        }
+
+        // Only statements the previous version did not already contribute,
+        // and none of the ones it made that this version drops
+        let known = self.documents.get(path).map(fact_keys).unwrap_or_default();
+        let current = fact_keys(&document);
+        let new_facts: Vec<Fact> = document
+            .facts()
+            .into_iter()
+            .filter(|f| !known.contains(&(f.subject, f.predicate.clone(), f.object.clone())))
+            .collect();
+        let dropped: HashSet<FactKey> = known.difference(&current).cloned().collect();
+        if let Some(index) = &mut self.index {
+            let indexed = index
+                .delete_by_provenance(&document.source())
+                .and_then(|_| document.vector_records().into_iter().try_for_each(|record| index.upsert(record)));
+            indexed.map_err(|source| DocumentError::Index { path: path.to_path_buf(), source })?;
+        }
+        let retracted = knowledge.retract_facts(&document.source(), &dropped);
+        let import = knowledge.add_facts_bulk(new_facts);
+        self.documents.insert(path.to_path_buf(), document);
+        Ok(Some(FileIngest { import, retracted }))
+    }
+}
+
+/// A fact as the (subject, predicate, object) it states.
+type FactKey = (EntityId, String, String);
+
+/// The statements a document contributes.
+fn fact_keys(document: &Document) -> HashSet<FactKey> {
+    document.facts().into_iter().map(|f| (f.subject, f.predicate, f.object)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::embedding::Embedder;
+    use crate::knowledge::vector_store::{HnswStore, MetadataFilter};
+
+    #[test]
+    fn test_directories_are_ingested_and_changes_picked_up() {
+        let dir = std::env::temp_dir().join(format!("astra_documents_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("notes")).unwrap();
+        let guide = dir.join("guide.md");
+        std::fs::write(
+            &guide,
+            "Intro text.\n\n# Rust Guide\nRust is a systems programming language.\n\n## Tooling\n- **Cargo** is the [build tool](https://doc.rust-lang.org/cargo).\n```\n# not a heading\n```\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("notes/todo.txt"), "Shopping\nBread and milk.").unwrap();
+        std::fs::write(dir.join("image.png"), [0u8; 4]).unwrap();
+
+        let mut knowledge = OntologyManager::new();
+        let mut ingestor = DocumentIngestor::new().with_index(HnswStore::new(256));
+        ingestor.watch(&dir);
+        let report = ingestor.scan(&mut knowledge);
+        assert_eq!(report.added.len(), 2);
+        assert!(report.failed.is_empty());
+
+        let document = ingestor.document(&guide).unwrap();
+        assert_eq!(document.title, "Rust Guide");
+        let headings: Vec<_> = document.sections.iter().map(|s| s.heading.as_deref()).collect();
+        assert_eq!(headings, vec![None, Some("Rust Guide"), Some("Tooling")]);
+        assert_eq!(document.sections[2].text, "Cargo is the build tool.\n# not a heading");
+        assert_eq!(document.sections[1].embedding.len(), 256);
+        let facts = document.facts();
+        let cargo = facts.iter().find(|f| f.subject == name_entity_id("cargo")).unwrap();
+        assert_eq!(cargo.object, "the build tool");
+        assert!(cargo.provenance.source_name.ends_with("guide.md"));
+        assert_eq!(ingestor.document(&dir.join("notes/todo.txt")).unwrap().title, "Shopping");
+        let index = ingestor.index().unwrap();
+        assert_eq!(index.len(), 4);
+        let query = HashingEmbedder::default().embed("which build tool does Rust use");
+        let hits = index.search(&query, 1, &MetadataFilter::new()).unwrap();
+        assert_eq!(hits[0].metadata["heading"], "Tooling");
+
+        let before = report.facts_added;
+        assert_eq!(ingestor.scan(&mut knowledge).facts_added, 0, "unchanged files are skipped");
+
+        std::fs::write(&guide, "# Rust Guide\nRust is a systems programming language.\nClippy is a linter.\n").unwrap();
+        let report = ingestor.scan(&mut knowledge);
+        assert_eq!(report.updated, vec![guide.clone()]);
+        assert_eq!(report.facts_added, 1, "only the new statement is added");
+        assert_eq!(report.facts_retracted, 2, "the dropped section and statement are retracted");
+        assert!(knowledge.query_facts(None).iter().all(|f| f.subject != name_entity_id("cargo")));
+        assert_eq!(ingestor.index().unwrap().len(), 2);
+        assert!(before > 1);
+
+        // A link back up the tree is not followed
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&dir, dir.join("notes/loop")).unwrap();
+        assert!(ingestor.scan(&mut knowledge).added.is_empty());
+
+        std::fs::remove_file(dir.join("notes/todo.txt")).unwrap();
+        let report = ingestor.scan(&mut knowledge);
+        assert_eq!(report.removed.len(), 1);
+        assert_eq!(report.facts_retracted, 1, "the title of the removed file");
+        assert_eq!(ingestor.index().unwrap().len(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
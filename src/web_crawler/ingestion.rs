@@ -4,10 +4,13 @@
 //
 //  Description:
 //      Processes and extracts structured data from crawled web content.
-//      Prepares data for knowledge base ingestion.
+//      Prepares data for knowledge base ingestion: "X is Y" statements
+//      become facts credited to the page. Personal data found in crawled
+//      pages is filtered out before anything is stored.
 //
 //  Author:      Alex Roussinov
 //  Created:     2025-12-26
+//  Updated:     2026-10-16
 //
 //  License:
 //      Dual licensed under the MIT and Apache 2.0 licenses.
@@ -17,11 +20,25 @@
 use anyhow::Result;
 use scraper::{Html, Selector};
 
-pub struct ContentIngestor;
+use crate::cognition::theory_of_mind::Claim;
+use crate::knowledge::extended_ontology::{name_entity_id, Confidence, Fact, Provenance};
+use crate::memory::privacy::{FilteredText, PrivacyFilter};
+
+/// Confidence in a statement read off a crawled page.
+const CRAWLED_CONFIDENCE: Confidence = 0.5;
+
+pub struct ContentIngestor {
+    pub privacy: PrivacyFilter,
+}
 
 impl ContentIngestor {
     pub fn new() -> Self {
-        Self {}
+        Self { privacy: PrivacyFilter::default() }
+    }
+
+    /// Applies the privacy policy to extracted text before it is ingested.
+    pub fn sanitize(&self, text: &str) -> FilteredText {
+        self.privacy.filter(text)
     }
 
     /// Extracts main textual content from HTML page.
@@ -39,10 +56,49 @@ impl ContentIngestor {
         Ok(extracted)
     }
 
+    /// Statements of the form "X is Y" in `text`, with personal data
+    /// filtered out, as facts credited to the page at `url`.
+    pub fn facts(&self, text: &str, url: &str) -> Vec<Fact> {
+        let filtered = self.sanitize(text);
+        filtered
+            .text
+            .split_inclusive(['.', '!', '?', '\n'])
+            .filter_map(Claim::parse)
+            .map(|claim| Fact {
+                subject: name_entity_id(&claim.topic),
+                predicate: "is".to_string(),
+                object: claim.value,
+                confidence: CRAWLED_CONFIDENCE,
+                provenance: Provenance::new(url, None),
+            })
+            .collect()
+    }
+
     /// Placeholder for further processing: code snippet extraction, metadata, etc.
     pub fn process_content(&self, content: &str) -> Result<()> {
         // TODO: Implement NLP extraction, code snippet detection, etc.
-        println!("Processing content with length: {}", content.len());
+        let filtered = self.sanitize(content);
+        println!(
+            "Processing content with length: {} ({} kind(s) of PII filtered)",
+            filtered.text.len(),
+            filtered.found.len()
+        );
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statements_on_a_page_become_facts() {
+        let ingestor = ContentIngestor::new();
+        let html = "<h1>France</h1><p>The capital of France is Paris. Is it far?</p>";
+        let facts = ingestor.facts(&ingestor.extract_text(html).unwrap(), "https://example.org/france");
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts[0].subject, name_entity_id("capital of france"));
+        assert_eq!(facts[0].object, "paris");
+        assert_eq!(facts[0].provenance.source_name, "https://example.org/france");
+    }
+}
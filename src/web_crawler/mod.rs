@@ -4,7 +4,8 @@
 //
 //  Description:
 //      Provides real-time web crawling and content ingestion capabilities.
-//      Supports focused crawling, content extraction, and indexing.
+//      Supports focused crawling, content extraction, and indexing, and
+//      ingestion of local PDF, Markdown, and text documents.
 //
 //  Author:      Alex Roussinov
 //  Created:     2025-12-26
@@ -16,3 +17,4 @@
 
 pub mod crawler;
 pub mod ingestion;
+pub mod documents;
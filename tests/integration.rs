@@ -16,6 +16,7 @@
 
 use astra_agi::runtime::Runtime;
 use astra_agi::interfaces::api::AstraApi;
+use astra_agi::interfaces::auth::{Authenticator, Principal, Role};
 use actix_web::{test, App};
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -66,7 +67,9 @@ async fn test_personality_and_feedback() {
 #[actix_rt::test]
 async fn test_api_chat_endpoint() {
     let runtime = Arc::new(Mutex::new(Runtime::new()));
-    let api = AstraApi::new(runtime.clone());
+    let mut auth = Authenticator::new();
+    auth.add_api_key("test-key", Principal::new("tester", Role::User));
+    let api = AstraApi::new(runtime.clone(), auth);
 
     let app = test::init_service(
         App::new()
@@ -76,6 +79,7 @@ async fn test_api_chat_endpoint() {
 
     let req = test::TestRequest::post()
         .uri("/chat")
+        .insert_header(("x-api-key", "test-key"))
         .set_json(&serde_json::json!({"message": "Hello Astra"}))
         .to_request();
 
@@ -80,7 +80,7 @@ fn test_intent_priority_modification() {
     runtime.emotion_state.urgency = 0.8;
     runtime.value_model.update_value("compassion", 0.9);
 
-    let task_metadata = std::collections::HashMap::new();
+    let task_metadata = astra_agi::metadata::Metadata::new();
     let modifier = astra_agi::emotion::compute_priority_modifier(
         &runtime.emotion_state,
         &runtime.value_model,
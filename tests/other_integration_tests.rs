@@ -2,17 +2,22 @@
 //  Astra AGI
 //  File: tests/other_integration_tests.rs
 //
-//  Description: Integration tests for Ontology persistence and relationship querying.
+//  Description: Integration tests for Ontology persistence, relationship querying
+//               bulk import, schema validation, and entity updates and removal.
 //
 //  Author:      Alex Roussinov
 //  Created:     2025-12-26
-//  Updated:     2025-12-26
+//  Updated:     2026-10-16
 //
 //  This file is dual licensed under the MIT and Apache 2.0 licenses.
 // =============================================================================
 
 use astra_agi::knowledge::{
     Ontology, AttributeType, AttributeValue, RelationshipType,
+    bulk_import::{BulkImportOptions, BulkRecord},
+    error::KnowledgeError,
+    ontology::RelationshipProperties,
+    schema::SchemaError,
     storage::SledStorage,
 };
 use std::collections::HashMap;
@@ -36,14 +41,14 @@ fn test_persistence_and_relationships() -> Result<()> {
     // Add entities
     let mut phone_attrs = HashMap::new();
     phone_attrs.insert("model".to_string(), AttributeValue::String("Pixel 7".to_string()));
-    let phone_id = ontology.add_entity(device_id, phone_attrs);
+    let phone_id = ontology.add_entity(device_id, phone_attrs)?;
 
     let mut alice_attrs = HashMap::new();
     alice_attrs.insert("name".to_string(), AttributeValue::String("Alice".to_string()));
-    let alice_id = ontology.add_entity(person_id, alice_attrs);
+    let alice_id = ontology.add_entity(person_id, alice_attrs)?;
 
     // Add relationship: Alice owns Pixel 7
-    ontology.add_relationship(alice_id, phone_id, RelationshipType::Custom("owns".to_string()));
+    ontology.add_relationship(alice_id, phone_id, RelationshipType::Custom("owns".to_string()))?;
 
     // Save and reload
     ontology.save_to_storage()?;
@@ -61,3 +66,202 @@ fn test_persistence_and_relationships() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_bulk_import_with_item_errors() -> Result<()> {
+    let storage = SledStorage::new("test_bulk_import_db")?;
+    let mut ontology = Ontology::new(storage);
+
+    let mut page_attrs = HashMap::new();
+    page_attrs.insert("url".to_string(), AttributeType::String);
+    let page_id = ontology.add_concept("Page", &[], page_attrs);
+
+    let page = |url: &str| {
+        let mut attrs = HashMap::new();
+        attrs.insert("url".to_string(), AttributeValue::String(url.to_string()));
+        BulkRecord::Entity { concept_id: page_id, attribute_values: attrs }
+    };
+
+    // Entities get consecutive IDs after the concept, so the first page is page_id + 1.
+    let records = vec![
+        page("https://a.example"),
+        page("https://b.example"),
+        BulkRecord::Entity { concept_id: 9999, attribute_values: HashMap::new() },
+        BulkRecord::Relationship {
+            from_entity: page_id + 1,
+            to_entity: page_id + 2,
+            rel_type: RelationshipType::Custom("links_to".to_string()),
+            properties: RelationshipProperties::default(),
+        },
+        BulkRecord::Relationship {
+            from_entity: page_id + 1,
+            to_entity: 4242,
+            rel_type: RelationshipType::Custom("links_to".to_string()),
+            properties: RelationshipProperties::default(),
+        },
+        page("https://c.example"),
+    ];
+
+    let report = ontology.bulk_import(records, &BulkImportOptions { batch_size: 2 });
+
+    assert_eq!(report.succeeded(), 4);
+    assert_eq!(report.failed(), 2);
+    assert_eq!(report.errors.iter().map(|e| e.index).collect::<Vec<_>>(), vec![2, 4]);
+
+    // Indexes were flushed across batch boundaries
+    let found = ontology.find_entities_by_attribute_indexed(
+        "url",
+        &AttributeValue::String("https://c.example".to_string()),
+    );
+    assert_eq!(found.len(), 1);
+    assert_eq!(ontology.find_entities_by_concept(page_id).len(), 3);
+
+    // Deferred adjacency was applied
+    let a_id = report.inserted[0].1;
+    let neighbors = ontology.get_neighbors(a_id);
+    assert_eq!(neighbors.len(), 1);
+    assert_eq!(neighbors[0].id, report.inserted[1].1);
+
+    Ok(())
+}
+
+#[test]
+fn test_schema_validation_on_entity_creation() -> Result<()> {
+    let storage = SledStorage::new("test_schema_validation_db")?;
+    let mut ontology = Ontology::new(storage);
+
+    let mut org_attrs = HashMap::new();
+    org_attrs.insert("name".to_string(), AttributeType::String);
+    let org_id = ontology.add_concept_with_required("Organization", &[], org_attrs, &["name"]);
+
+    let mut person_attrs = HashMap::new();
+    person_attrs.insert("name".to_string(), AttributeType::String);
+    person_attrs.insert("age".to_string(), AttributeType::Integer);
+    person_attrs.insert("employer".to_string(), AttributeType::Reference(org_id));
+    let person_id = ontology.add_concept_with_required("Person", &[], person_attrs, &["name"]);
+
+    let mut acme_attrs = HashMap::new();
+    acme_attrs.insert("name".to_string(), AttributeValue::String("Acme".to_string()));
+    let acme_id = ontology.add_entity(org_id, acme_attrs)?;
+
+    // Valid entity with a resolvable reference
+    let mut alice_attrs = HashMap::new();
+    alice_attrs.insert("name".to_string(), AttributeValue::String("Alice".to_string()));
+    alice_attrs.insert("employer".to_string(), AttributeValue::Reference(acme_id));
+    let alice_id = ontology.add_entity(person_id, alice_attrs)?;
+
+    // Missing required attribute
+    let mut nameless = HashMap::new();
+    nameless.insert("age".to_string(), AttributeValue::Integer(40));
+    assert_eq!(
+        ontology.add_entity(person_id, nameless),
+        Err(SchemaError::MissingRequiredAttribute { concept_id: person_id, attribute: "name".to_string() })
+    );
+
+    // Type mismatch
+    let mut wrong_age = HashMap::new();
+    wrong_age.insert("name".to_string(), AttributeValue::String("Bob".to_string()));
+    wrong_age.insert("age".to_string(), AttributeValue::String("forty".to_string()));
+    assert!(matches!(
+        ontology.add_entity(person_id, wrong_age),
+        Err(SchemaError::TypeMismatch { ref attribute, .. }) if attribute == "age"
+    ));
+
+    // Dangling and mistyped references
+    let mut dangling = HashMap::new();
+    dangling.insert("name".to_string(), AttributeValue::String("Carol".to_string()));
+    dangling.insert("employer".to_string(), AttributeValue::Reference(9999));
+    assert_eq!(
+        ontology.add_entity(person_id, dangling),
+        Err(SchemaError::DanglingReference { attribute: "employer".to_string(), target: 9999 })
+    );
+
+    let mut self_employed = HashMap::new();
+    self_employed.insert("name".to_string(), AttributeValue::String("Dave".to_string()));
+    self_employed.insert("employer".to_string(), AttributeValue::Reference(alice_id));
+    assert!(matches!(
+        ontology.add_entity(person_id, self_employed),
+        Err(SchemaError::ReferenceConceptMismatch { .. })
+    ));
+
+    // Unknown concept
+    assert_eq!(
+        ontology.add_entity(4242, HashMap::new()),
+        Err(SchemaError::UnknownConcept { concept_id: 4242 })
+    );
+
+    // Rejected entities were not stored, so the ontology is still consistent
+    assert!(ontology.validate_all().is_empty());
+    assert_eq!(ontology.find_entities_by_concept(person_id).len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_updates_and_removals_keep_indexes_and_tombstones() -> Result<()> {
+    let storage = SledStorage::new("test_entity_removal_db")?;
+    let mut ontology = Ontology::new(storage);
+
+    let mut attrs = HashMap::new();
+    attrs.insert("name".to_string(), AttributeType::String);
+    attrs.insert("room".to_string(), AttributeType::String);
+    let device_id = ontology.add_concept("Device", &[], attrs);
+    let name = |n: &str| AttributeValue::String(n.to_string());
+
+    let mut lamp_attrs = HashMap::new();
+    lamp_attrs.insert("name".to_string(), name("lamp"));
+    lamp_attrs.insert("room".to_string(), name("kitchen"));
+    let lamp = ontology.add_entity(device_id, lamp_attrs)?;
+    let mut hub_attrs = HashMap::new();
+    hub_attrs.insert("name".to_string(), name("hub"));
+    let hub = ontology.add_entity(device_id, hub_attrs)?;
+
+    let powers = ontology.add_relationship(hub, lamp, RelationshipType::Custom("powers".to_string()))?;
+    let controls = ontology.add_relationship(hub, lamp, RelationshipType::Custom("controls".to_string()))?;
+    let reports = ontology.add_relationship(lamp, hub, RelationshipType::RelatedTo)?;
+
+    // Changed values move in the attribute index
+    let mut moved = HashMap::new();
+    moved.insert("name".to_string(), name("lamp"));
+    moved.insert("room".to_string(), name("hall"));
+    ontology.update_entity(lamp, moved)?;
+    assert!(ontology.find_entities_by_attribute_indexed("room", &name("kitchen")).is_empty());
+    assert_eq!(ontology.find_entities_by_attribute_indexed("room", &name("hall"))[0].id, lamp);
+    assert_eq!(ontology.find_entities_by_attribute_indexed("name", &name("lamp")).len(), 1);
+
+    let mut mistyped = HashMap::new();
+    mistyped.insert("room".to_string(), AttributeValue::Integer(3));
+    assert!(matches!(ontology.update_entity(lamp, mistyped), Err(KnowledgeError::Schema(_))));
+
+    // The hub stays adjacent to the lamp while one relationship remains
+    ontology.remove_relationship(powers)?;
+    assert_eq!(ontology.get_neighbors(hub).len(), 1);
+    assert!(matches!(ontology.remove_relationship(powers), Err(KnowledgeError::Removed(id)) if id == powers));
+    ontology.remove_relationship(controls)?;
+    assert!(ontology.get_neighbors(hub).is_empty());
+    assert!(ontology.get_relationships_indexed(hub, None).is_empty());
+
+    // Removing the lamp takes its remaining relationship with it
+    let removed = ontology.remove_entity(lamp)?;
+    assert_eq!(removed.attribute_values.get("room"), Some(&name("hall")));
+    assert!(ontology.get_relationship(reports).is_none());
+    assert!(ontology.find_entities_by_attribute_indexed("name", &name("lamp")).is_empty());
+    assert_eq!(ontology.find_entities_by_concept(device_id).len(), 1);
+    assert!(matches!(ontology.update_entity(9999, HashMap::new()), Err(KnowledgeError::EntityNotFound(9999))));
+
+    // Relationships need two live ends
+    let dangling = ontology.add_relationship(hub, lamp, RelationshipType::RelatedTo);
+    assert!(matches!(dangling, Err(KnowledgeError::Removed(id)) if id == lamp));
+    assert!(matches!(ontology.add_relationship(9999, hub, RelationshipType::RelatedTo), Err(KnowledgeError::EntityNotFound(9999))));
+    assert!(ontology.get_neighbors(hub).is_empty());
+
+    // Tombstones survive a reload
+    ontology.save_to_storage()?;
+    let mut loaded = Ontology::new(SledStorage::new("test_entity_removal_db")?);
+    loaded.load_from_storage()?;
+    assert!(loaded.is_removed(lamp) && loaded.is_removed(reports));
+    assert!(!loaded.is_removed(hub));
+    assert!(matches!(loaded.remove_entity(lamp), Err(KnowledgeError::Removed(id)) if id == lamp));
+
+    Ok(())
+}
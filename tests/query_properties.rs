@@ -0,0 +1,331 @@
+// =============================================================================
+//  Astra AGI
+//  File: tests/query_properties.rs
+//
+//  Description: Property-based tests for the query executor. Random ontologies
+//  and queries are generated, and the indexed executor's results are compared
+//  with a naive reference that checks every entity against the query
+//  directly. Failing cases are shrunk to a minimal ontology and query.
+//
+//  Author:      Alex Roussinov
+//  Created:     2026-10-16
+//  Updated:     2026-10-16
+//
+//  This file is dual licensed under the MIT and Apache 2.0 licenses.
+// =============================================================================
+
+use astra_agi::knowledge::{
+    Ontology, AttributeType, AttributeValue, Entity, Id, RelationshipType,
+    ontology::RelationshipProperties,
+    query::{QueryExpr, LogicalOp, ComparisonOp, AttributeFilter, RelationshipFilter, RelationshipProperty},
+    storage::SledStorage,
+};
+use proptest::prelude::*;
+use proptest::sample::Index;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+const CONCEPTS: usize = 4;
+const LABELS: &[&str] = &["a", "b", "c"];
+
+#[derive(Debug, Clone)]
+struct EntitySpec {
+    concept: usize,
+    /// Stored as an integer on even concepts and as a float on odd ones
+    n: Option<i8>,
+    label: Option<usize>,
+    flag: Option<bool>,
+}
+
+#[derive(Debug, Clone)]
+struct RelationshipSpec {
+    from: Index,
+    to: Index,
+    works_at: bool,
+    weight: Option<i8>,
+}
+
+#[derive(Debug, Clone)]
+struct OntologySpec {
+    /// Parents of each concept, drawn from the concepts before it
+    parents: Vec<Vec<usize>>,
+    entities: Vec<EntitySpec>,
+    relationships: Vec<RelationshipSpec>,
+}
+
+/// What the reference evaluator knows about the generated ontology.
+struct Model {
+    parents: HashMap<Id, Vec<Id>>,
+    entities: Vec<Entity>,
+    relationships: Vec<(Id, Id, RelationshipType, RelationshipProperties)>,
+}
+
+fn concept_id(index: usize) -> Id {
+    // Concepts are added first to a fresh ontology, whose IDs start at 1.
+    index + 1
+}
+
+fn ontology_spec() -> impl Strategy<Value = OntologySpec> {
+    let parents = prop::collection::vec(prop::collection::vec(any::<bool>(), CONCEPTS), CONCEPTS).prop_map(|masks| {
+        masks
+            .into_iter()
+            .enumerate()
+            .map(|(i, mask)| (0..i).filter(|j| mask[*j]).collect())
+            .collect()
+    });
+    let entity = (0..CONCEPTS, prop::option::of(-3i8..=3), prop::option::of(0..LABELS.len()), prop::option::of(any::<bool>()))
+        .prop_map(|(concept, n, label, flag)| EntitySpec { concept, n, label, flag });
+    let relationship = (any::<Index>(), any::<Index>(), any::<bool>(), prop::option::of(0i8..=4))
+        .prop_map(|(from, to, works_at, weight)| RelationshipSpec { from, to, works_at, weight });
+    (parents, prop::collection::vec(entity, 0..12), prop::collection::vec(relationship, 0..12))
+        .prop_map(|(parents, entities, relationships)| OntologySpec { parents, entities, relationships })
+}
+
+fn comparison_op() -> impl Strategy<Value = ComparisonOp> {
+    prop_oneof![
+        Just(ComparisonOp::Eq),
+        Just(ComparisonOp::Neq),
+        Just(ComparisonOp::Gt),
+        Just(ComparisonOp::Lt),
+        Just(ComparisonOp::Gte),
+        Just(ComparisonOp::Lte),
+    ]
+}
+
+/// Values of every type, so filters are often compared across types.
+fn attribute_value() -> impl Strategy<Value = AttributeValue> {
+    prop_oneof![
+        (-3i64..=3).prop_map(AttributeValue::Integer),
+        (-6i8..=6).prop_map(|half| AttributeValue::Float(half as f64 / 2.0)),
+        (0..LABELS.len()).prop_map(|i| AttributeValue::String(LABELS[i].to_string())),
+        any::<bool>().prop_map(AttributeValue::Boolean),
+        (0..3usize).prop_map(AttributeValue::Reference),
+    ]
+}
+
+fn relationship_type() -> impl Strategy<Value = Option<RelationshipType>> {
+    prop_oneof![Just(None), Just(Some(RelationshipType::FriendOf)), Just(Some(RelationshipType::WorksAt))]
+}
+
+fn relationship_filter() -> impl Strategy<Value = RelationshipFilter> {
+    let property = prop_oneof![Just(RelationshipProperty::Weight), Just(RelationshipProperty::Provenance)];
+    (property, comparison_op(), attribute_value()).prop_map(|(p, op, v)| RelationshipFilter::new(p, op, v))
+}
+
+fn query_expr() -> impl Strategy<Value = QueryExpr> {
+    let leaf = prop_oneof![
+        // One past the last concept is an ID that matches no concept.
+        (0..=CONCEPTS).prop_map(|i| QueryExpr::Concept(concept_id(i))),
+        (prop::sample::select(vec!["n", "label", "flag", "missing"]), comparison_op(), attribute_value()).prop_map(
+            |(name, op, value)| QueryExpr::AttrFilter(AttributeFilter { attr_name: name.to_string(), op, value })
+        ),
+        (relationship_type(), prop::collection::vec(relationship_filter(), 0..2))
+            .prop_map(|(rel_type, filters)| QueryExpr::Related { rel_type, filters, target: None }),
+    ];
+    leaf.prop_recursive(3, 24, 4, |inner| {
+        let op = prop_oneof![Just(LogicalOp::And), Just(LogicalOp::Or), Just(LogicalOp::Not)];
+        prop_oneof![
+            (op, prop::collection::vec(inner.clone(), 0..4)).prop_map(|(op, exprs)| QueryExpr::Logical { op, exprs }),
+            inner.clone().prop_map(QueryExpr::not),
+            (relationship_type(), prop::collection::vec(relationship_filter(), 0..2), inner).prop_map(
+                |(rel_type, filters, target)| QueryExpr::Related { rel_type, filters, target: Some(Box::new(target)) }
+            ),
+        ]
+    })
+}
+
+/// Opens a sled database no other test case uses.
+fn fresh_storage() -> (SledStorage, std::path::PathBuf) {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let path = std::env::temp_dir().join(format!(
+        "astra_query_properties_{}_{}",
+        std::process::id(),
+        NEXT.fetch_add(1, AtomicOrdering::Relaxed)
+    ));
+    (SledStorage::new(&path).expect("temporary sled database"), path)
+}
+
+fn build(spec: &OntologySpec, storage: SledStorage) -> (Ontology<SledStorage>, Model) {
+    let mut ontology = Ontology::new(storage);
+    let mut model = Model { parents: HashMap::new(), entities: Vec::new(), relationships: Vec::new() };
+
+    for (i, parents) in spec.parents.iter().enumerate() {
+        let numeric = if i % 2 == 0 { AttributeType::Integer } else { AttributeType::Float };
+        let attributes = HashMap::from([
+            ("n".to_string(), numeric),
+            ("label".to_string(), AttributeType::String),
+            ("flag".to_string(), AttributeType::Boolean),
+        ]);
+        let parent_ids: Vec<Id> = parents.iter().map(|p| concept_id(*p)).collect();
+        let id = ontology.add_concept(&format!("C{}", i), &parent_ids, attributes);
+        assert_eq!(id, concept_id(i));
+        model.parents.insert(id, parent_ids);
+    }
+
+    for entity in &spec.entities {
+        let mut values = HashMap::new();
+        if let Some(n) = entity.n {
+            let value = if entity.concept % 2 == 0 {
+                AttributeValue::Integer(n as i64)
+            } else {
+                AttributeValue::Float(n as f64 / 2.0)
+            };
+            values.insert("n".to_string(), value);
+        }
+        if let Some(label) = entity.label {
+            values.insert("label".to_string(), AttributeValue::String(LABELS[label].to_string()));
+        }
+        if let Some(flag) = entity.flag {
+            values.insert("flag".to_string(), AttributeValue::Boolean(flag));
+        }
+        let id = ontology.add_entity(concept_id(entity.concept), values).expect("generated entity fits its schema");
+        model.entities.push(ontology.get_entity(id).expect("entity just added").clone());
+    }
+
+    if !model.entities.is_empty() {
+        for rel in &spec.relationships {
+            let from = model.entities[rel.from.index(model.entities.len())].id;
+            let to = model.entities[rel.to.index(model.entities.len())].id;
+            let rel_type = if rel.works_at { RelationshipType::WorksAt } else { RelationshipType::FriendOf };
+            let properties = RelationshipProperties {
+                weight: rel.weight.map(|w| w as f64 / 4.0),
+                ..Default::default()
+            };
+            ontology
+                .add_relationship_with_properties(from, to, rel_type.clone(), properties.clone())
+                .expect("both ends were just added");
+            model.relationships.push((from, to, rel_type, properties));
+        }
+    }
+
+    (ontology, model)
+}
+
+impl Model {
+    fn is_a(&self, concept: Id, ancestor: Id) -> bool {
+        concept == ancestor
+            || self.parents.get(&concept).map_or(false, |parents| parents.iter().any(|p| self.is_a(*p, ancestor)))
+    }
+
+    fn entity(&self, id: Id) -> &Entity {
+        self.entities.iter().find(|e| e.id == id).expect("relationship endpoint exists")
+    }
+
+    fn matches(&self, entity: &Entity, expr: &QueryExpr) -> bool {
+        match expr {
+            QueryExpr::Concept(concept) => self.is_a(entity.concept_id, *concept),
+            QueryExpr::AttrFilter(filter) => entity
+                .attribute_values
+                .get(&filter.attr_name)
+                .map_or(false, |value| compare(value, &filter.op, &filter.value)),
+            QueryExpr::Logical { op: LogicalOp::And, exprs } => exprs.iter().all(|e| self.matches(entity, e)),
+            QueryExpr::Logical { op: LogicalOp::Or, exprs } => exprs.iter().any(|e| self.matches(entity, e)),
+            QueryExpr::Logical { op: LogicalOp::Not, exprs } => !exprs.iter().any(|e| self.matches(entity, e)),
+            QueryExpr::Not(inner) => !self.matches(entity, inner),
+            QueryExpr::Related { rel_type, filters, target } => {
+                self.relationships.iter().any(|(from, to, kind, properties)| {
+                    *from == entity.id
+                        && rel_type.as_ref().map_or(true, |t| t == kind)
+                        && filters.iter().all(|f| {
+                            let value = match f.property {
+                                RelationshipProperty::Weight => properties.weight.map(AttributeValue::Float),
+                                RelationshipProperty::Since => properties.since.map(|s| AttributeValue::Integer(s as i64)),
+                                RelationshipProperty::Confidence => properties.confidence.map(|c| AttributeValue::Float(c as f64)),
+                                RelationshipProperty::Provenance => properties.provenance.clone().map(AttributeValue::String),
+                            };
+                            value.map_or(false, |v| compare(&v, &f.op, &f.value))
+                        })
+                        && target.as_ref().map_or(true, |t| self.matches(self.entity(*to), t))
+                })
+            }
+        }
+    }
+
+    fn evaluate(&self, expr: &QueryExpr) -> Vec<Id> {
+        self.entities.iter().filter(|e| self.matches(e, expr)).map(|e| e.id).collect()
+    }
+}
+
+/// Reference comparison: numbers compare numerically whatever their type,
+/// strings and booleans compare with their own kind, references only under
+/// equality, and anything else does not match.
+fn compare(value: &AttributeValue, op: &ComparisonOp, against: &AttributeValue) -> bool {
+    use AttributeValue::*;
+    let ordering = match (value, against) {
+        (Integer(_) | Float(_), Integer(_) | Float(_)) => as_f64(value).partial_cmp(&as_f64(against)),
+        (String(a), String(b)) => Some(a.cmp(b)),
+        (Boolean(a), Boolean(b)) => Some(a.cmp(b)),
+        (Reference(a), Reference(b)) => {
+            return match op {
+                ComparisonOp::Eq => a == b,
+                ComparisonOp::Neq => a != b,
+                _ => false,
+            }
+        }
+        _ => None,
+    };
+    let Some(ordering) = ordering else { return false };
+    match op {
+        ComparisonOp::Eq => ordering == Ordering::Equal,
+        ComparisonOp::Neq => ordering != Ordering::Equal,
+        ComparisonOp::Gt => ordering == Ordering::Greater,
+        ComparisonOp::Lt => ordering == Ordering::Less,
+        ComparisonOp::Gte => ordering != Ordering::Less,
+        ComparisonOp::Lte => ordering != Ordering::Greater,
+    }
+}
+
+fn as_f64(value: &AttributeValue) -> f64 {
+    match value {
+        AttributeValue::Integer(i) => *i as f64,
+        AttributeValue::Float(f) => *f,
+        _ => f64::NAN,
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(128))]
+
+    #[test]
+    fn test_executor_agrees_with_reference(spec in ontology_spec(), query in query_expr()) {
+        let (storage, path) = fresh_storage();
+        let (ontology, model) = build(&spec, storage);
+
+        let actual: Vec<Id> = ontology.query(&query).iter().map(|e| e.id).collect();
+        let mut expected = model.evaluate(&query);
+        expected.sort_unstable();
+        drop(ontology);
+        let _ = std::fs::remove_dir_all(&path);
+
+        // Results come back in ID order, without duplicates.
+        prop_assert_eq!(actual.iter().collect::<HashSet<_>>().len(), actual.len());
+        prop_assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn test_logical_edge_cases_found_by_properties() {
+    let (storage, path) = fresh_storage();
+    let mut ontology = Ontology::new(storage);
+    let attrs = HashMap::from([("n".to_string(), AttributeType::Integer)]);
+    let concept = ontology.add_concept("Thing", &[], attrs);
+    let three = ontology.add_entity(concept, HashMap::from([("n".to_string(), AttributeValue::Integer(3))])).unwrap();
+    let five = ontology.add_entity(concept, HashMap::from([("n".to_string(), AttributeValue::Integer(5))])).unwrap();
+    let n_is = |op, value| QueryExpr::AttrFilter(AttributeFilter { attr_name: "n".to_string(), op, value });
+    let ids = |query: &QueryExpr| ontology.query(query).iter().map(|e| e.id).collect::<Vec<_>>();
+
+    // An empty AND holds for everything; an empty OR for nothing.
+    assert_eq!(ids(&QueryExpr::and(vec![])), vec![three, five]);
+    assert!(ids(&QueryExpr::or(vec![])).is_empty());
+    // The Not operator matches entities satisfying none of its expressions.
+    let neither = QueryExpr::Logical { op: LogicalOp::Not, exprs: vec![n_is(ComparisonOp::Eq, AttributeValue::Integer(3))] };
+    assert_eq!(ids(&neither), vec![five]);
+    // Integers and floats compare numerically; other type mismatches never match.
+    assert_eq!(ids(&n_is(ComparisonOp::Gt, AttributeValue::Float(3.5))), vec![five]);
+    assert_eq!(ids(&n_is(ComparisonOp::Eq, AttributeValue::Float(3.0))), vec![three]);
+    assert!(ids(&n_is(ComparisonOp::Neq, AttributeValue::String("3".into()))).is_empty());
+
+    drop(ontology);
+    let _ = std::fs::remove_dir_all(&path);
+}
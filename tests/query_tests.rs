@@ -6,13 +6,15 @@
 //
 //  Author:      Alex Roussinov
 //  Created:     2025-12-25
-//  Updated:     2025-12-26
+//  Updated:     2026-10-16
 //
 //  This file is dual licensed under the MIT and Apache 2.0 licenses.
 // =============================================================================
 
 use astra_agi::knowledge::{
-    Ontology, AttributeType, AttributeValue, RelationshipType, query::{QueryExpr, LogicalOp, ComparisonOp, AttributeFilter},
+    Ontology, AttributeType, AttributeValue, RelationshipType,
+    ontology::RelationshipProperties,
+    query::{QueryExpr, LogicalOp, ComparisonOp, AttributeFilter, RelationshipFilter, RelationshipProperty},
     storage::SledStorage,
 };
 use std::collections::HashMap;
@@ -34,17 +36,17 @@ fn test_basic_queries() -> Result<()> {
     let mut alice_attrs = HashMap::new();
     alice_attrs.insert("name".to_string(), AttributeValue::String("Alice".to_string()));
     alice_attrs.insert("age".to_string(), AttributeValue::Integer(30));
-    let alice_id = ontology.add_entity(person_id, alice_attrs);
+    let alice_id = ontology.add_entity(person_id, alice_attrs)?;
 
     let mut bob_attrs = HashMap::new();
     bob_attrs.insert("name".to_string(), AttributeValue::String("Bob".to_string()));
     bob_attrs.insert("age".to_string(), AttributeValue::Integer(25));
-    let bob_id = ontology.add_entity(person_id, bob_attrs);
+    let bob_id = ontology.add_entity(person_id, bob_attrs)?;
 
     let mut carol_attrs = HashMap::new();
     carol_attrs.insert("name".to_string(), AttributeValue::String("Carol".to_string()));
     carol_attrs.insert("age".to_string(), AttributeValue::Integer(40));
-    let carol_id = ontology.add_entity(person_id, carol_attrs);
+    let carol_id = ontology.add_entity(person_id, carol_attrs)?;
 
     // Query 1: Persons older than 28
     let age_filter = AttributeFilter {
@@ -100,3 +102,192 @@ fn test_basic_queries() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_concept_hierarchy_queries() -> Result<()> {
+    let storage = SledStorage::new("test_hierarchy_db")?;
+    let mut ontology = Ontology::new(storage);
+
+    // Animal -> Mammal -> Dog, with attributes declared at each level
+    let mut animal_attrs = HashMap::new();
+    animal_attrs.insert("name".to_string(), AttributeType::String);
+    animal_attrs.insert("legs".to_string(), AttributeType::Integer);
+    let animal_id = ontology.add_concept("Animal", &[], animal_attrs);
+
+    let mut mammal_attrs = HashMap::new();
+    mammal_attrs.insert("fur".to_string(), AttributeType::Boolean);
+    let mammal_id = ontology.add_concept("Mammal", &[animal_id], mammal_attrs);
+
+    let mut dog_attrs = HashMap::new();
+    dog_attrs.insert("breed".to_string(), AttributeType::String);
+    let dog_id = ontology.add_concept("Dog", &[mammal_id], dog_attrs);
+
+    let bird_id = ontology.add_concept("Bird", &[animal_id], HashMap::new());
+
+    // Subsumption
+    assert!(ontology.is_subconcept_of(dog_id, animal_id));
+    assert!(ontology.is_subconcept_of(dog_id, dog_id));
+    assert!(!ontology.is_subconcept_of(animal_id, dog_id));
+    assert!(!ontology.is_subconcept_of(bird_id, mammal_id));
+
+    // Attribute inheritance
+    let dog_effective = ontology.effective_attributes(dog_id);
+    assert_eq!(dog_effective.len(), 4);
+    assert_eq!(dog_effective.get("legs"), Some(&AttributeType::Integer));
+    assert_eq!(dog_effective.get("fur"), Some(&AttributeType::Boolean));
+
+    // Concept queries match subconcept instances
+    let mut rex_attrs = HashMap::new();
+    rex_attrs.insert("name".to_string(), AttributeValue::String("Rex".to_string()));
+    ontology.add_entity(dog_id, rex_attrs)?;
+
+    let mut tweety_attrs = HashMap::new();
+    tweety_attrs.insert("name".to_string(), AttributeValue::String("Tweety".to_string()));
+    ontology.add_entity(bird_id, tweety_attrs)?;
+
+    assert_eq!(ontology.query(&QueryExpr::Concept(animal_id)).len(), 2);
+    assert_eq!(ontology.query(&QueryExpr::Concept(mammal_id)).len(), 1);
+    assert_eq!(ontology.query(&QueryExpr::Concept(bird_id)).len(), 1);
+
+    // Cycle detection
+    assert!(ontology.add_concept_parent(animal_id, dog_id).is_err());
+    assert!(ontology.add_concept_parent(dog_id, bird_id).is_ok());
+    assert!(ontology.detect_hierarchy_cycles().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_relationship_property_queries() -> Result<()> {
+    let storage = SledStorage::new("test_relationship_props_db")?;
+    let mut ontology = Ontology::new(storage);
+
+    let mut person_attrs = HashMap::new();
+    person_attrs.insert("name".to_string(), AttributeType::String);
+    let person_id = ontology.add_concept("Person", &[], person_attrs);
+
+    let mut ids = Vec::new();
+    for name in ["Alice", "Bob", "Carol", "Dave"] {
+        let mut attrs = HashMap::new();
+        attrs.insert("name".to_string(), AttributeValue::String(name.to_string()));
+        ids.push(ontology.add_entity(person_id, attrs)?);
+    }
+    let (alice, bob, carol, dave) = (ids[0], ids[1], ids[2], ids[3]);
+
+    let now: u64 = 1_800_000_000;
+    let one_year = 365 * 24 * 60 * 60;
+
+    // Strong and recent
+    ontology.add_relationship_with_properties(alice, bob, RelationshipType::FriendOf, RelationshipProperties {
+        weight: Some(0.9),
+        since: Some(now - 30 * 24 * 60 * 60),
+        confidence: Some(0.95),
+        provenance: Some("chat".to_string()),
+    })
+    .unwrap();
+    // Strong but old
+    ontology.add_relationship_with_properties(carol, dave, RelationshipType::FriendOf, RelationshipProperties {
+        weight: Some(0.8),
+        since: Some(now - 3 * one_year),
+        ..Default::default()
+    })
+    .unwrap();
+    // Recent but weak
+    ontology.add_relationship_with_properties(bob, carol, RelationshipType::FriendOf, RelationshipProperties {
+        weight: Some(0.2),
+        since: Some(now - 10),
+        ..Default::default()
+    })
+    .unwrap();
+    // No properties at all
+    ontology.add_relationship(dave, alice, RelationshipType::FriendOf).unwrap();
+
+    // "Strong friendships formed in the last year"
+    let filters = vec![
+        RelationshipFilter::new(RelationshipProperty::Weight, ComparisonOp::Gte, AttributeValue::Float(0.7)),
+        RelationshipFilter::new(RelationshipProperty::Since, ComparisonOp::Gte, AttributeValue::Integer((now - one_year) as i64)),
+    ];
+    let strong_recent = ontology.query_relationships(Some(&RelationshipType::FriendOf), &filters);
+    assert_eq!(strong_recent.len(), 1);
+    assert_eq!(strong_recent[0].from_entity, alice);
+    assert_eq!(strong_recent[0].to_entity, bob);
+
+    // Entity-level query through the DSL
+    let results = ontology.query(&QueryExpr::related(RelationshipType::FriendOf, filters));
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, alice);
+
+    // Target constraint: strong friends of someone named Dave
+    let dave_filter = QueryExpr::AttrFilter(AttributeFilter {
+        attr_name: "name".to_string(),
+        op: ComparisonOp::Eq,
+        value: AttributeValue::String("Dave".to_string()),
+    });
+    let results = ontology.query(&QueryExpr::Related {
+        rel_type: Some(RelationshipType::FriendOf),
+        filters: vec![RelationshipFilter::new(RelationshipProperty::Weight, ComparisonOp::Gt, AttributeValue::Float(0.5))],
+        target: Some(Box::new(dave_filter)),
+    });
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, carol);
+
+    // Relationships without the filtered property never match
+    let sourced = ontology.query_relationships(None, &[RelationshipFilter::new(
+        RelationshipProperty::Provenance,
+        ComparisonOp::Eq,
+        AttributeValue::String("chat".to_string()),
+    )]);
+    assert_eq!(sourced.len(), 1);
+
+    // Unfiltered type query returns every friendship
+    assert_eq!(ontology.query_relationships(Some(&RelationshipType::FriendOf), &[]).len(), 4);
+
+    Ok(())
+}
+
+#[test]
+fn test_query_batch_matches_sequential_queries() -> Result<()> {
+    let storage = SledStorage::new("test_query_batch_db")?;
+    let mut ontology = Ontology::new(storage);
+
+    let mut item_attrs = HashMap::new();
+    item_attrs.insert("score".to_string(), AttributeType::Integer);
+    let item_id = ontology.add_concept("Item", &[], item_attrs);
+    let gadget_id = ontology.add_concept("Gadget", &[item_id], HashMap::new());
+    for score in 0..20 {
+        let mut attrs = HashMap::new();
+        attrs.insert("score".to_string(), AttributeValue::Integer(score));
+        let concept = if score % 2 == 0 { item_id } else { gadget_id };
+        ontology.add_entity(concept, attrs)?;
+    }
+
+    let above = |n: i64| {
+        QueryExpr::AttrFilter(AttributeFilter {
+            attr_name: "score".to_string(),
+            op: ComparisonOp::Gt,
+            value: AttributeValue::Integer(n),
+        })
+    };
+    // Lookups repeat across queries and within one
+    let queries = vec![
+        QueryExpr::and(vec![QueryExpr::Concept(gadget_id), above(10)]),
+        QueryExpr::or(vec![above(15), QueryExpr::not(above(2))]),
+        QueryExpr::Concept(item_id),
+        QueryExpr::and(vec![QueryExpr::Concept(gadget_id), QueryExpr::not(QueryExpr::Concept(gadget_id))]),
+        QueryExpr::and(vec![]),
+    ];
+
+    let batched = ontology.query_batch(&queries);
+    assert_eq!(batched.len(), queries.len());
+    for (query, results) in queries.iter().zip(&batched) {
+        let ids: Vec<_> = results.iter().map(|e| e.id).collect();
+        let expected: Vec<_> = ontology.query(query).iter().map(|e| e.id).collect();
+        assert_eq!(ids, expected);
+    }
+    assert_eq!(batched[0].len(), 5);
+    assert_eq!(batched[2].len(), 20);
+    assert!(batched[3].is_empty());
+    assert!(ontology.query_batch(&[]).is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+// =============================================================================
+// Astra AGI - Scenario Regression Tests
+// File: scenarios.rs
+//
+// Description:
+// Runs every YAML scenario under tests/scenarios against a fresh runtime and
+// fails with the full divergence report of each one that no longer behaves
+// as scripted.
+//
+// Author:      Alex Roussinov
+// Created:     2026-10-16
+// Updated:     2026-10-16
+//
+// Licensed under MIT OR Apache 2.0
+// =============================================================================
+
+use astra_agi::runtime::scenarios::{run_scenario, Scenario};
+use std::path::Path;
+
+#[test]
+fn test_scripted_scenarios() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/scenarios");
+    let mut paths: Vec<_> = std::fs::read_dir(&dir)
+        .expect("tests/scenarios exists")
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "yaml" || ext == "yml"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "no scenarios in {}", dir.display());
+
+    let failures: Vec<String> = paths
+        .iter()
+        .map(|path| {
+            let scenario = Scenario::load(path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+            run_scenario(&scenario)
+        })
+        .filter(|report| !report.passed())
+        .map(|report| report.to_string())
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
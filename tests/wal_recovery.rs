@@ -0,0 +1,235 @@
+// =============================================================================
+// Astra AGI - Write-Ahead Log Recovery Tests
+// File: wal_recovery.rs
+//
+// Description:
+// Kills a process running the runtime with a write-ahead log at random
+// points and checks that a new runtime recovers a consistent state from
+// the log: each tick's intent, belief revision, and event come back
+// together or not at all, and the log keeps working afterwards. Chores
+// logged by a release that kept intent metadata as strings are still
+// counted once their intents migrate. Intents removed or annotated, and
+// facts added to or retracted from the host's knowledge base, replay as
+// they were left, and the tick keeps the log from growing without bound.
+//
+// Author:      Alex Roussinov
+// Created:     2026-10-16
+// Updated:     2026-10-16
+//
+// Licensed under MIT OR Apache 2.0
+// =============================================================================
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+use std::{env, fs, thread};
+
+use astra_agi::knowledge::belief_ledger::{BeliefRevision, FactKey, RevisionOutcome};
+use astra_agi::knowledge::extended_ontology::{Fact, OntologyManager, Provenance};
+use astra_agi::memory::narrative_memory::NarrativeMemory;
+use astra_agi::metadata::{MetaValue, Metadata};
+use astra_agi::migration::decode;
+use astra_agi::random::RandomSource;
+use astra_agi::runtime::intent_manager::Intent;
+use astra_agi::runtime::error::{RuntimeError, WalError};
+use astra_agi::runtime::wal::{read_log, FsyncPolicy, WalConfig};
+use astra_agi::runtime::Runtime;
+use rand::Rng;
+
+/// Set in the child process to the log it writes.
+const CHILD_LOG: &str = "ASTRA_WAL_TEST_CHILD";
+const TEST_NAME: &str = "test_runtime_recovers_after_being_killed_at_random_points";
+
+fn open(path: &Path, fsync: FsyncPolicy) -> Runtime {
+    Runtime::builder()
+        .with_narrative_memory(NarrativeMemory::new(1_000_000))
+        .with_wal(WalConfig::new(path).with_fsync(fsync))
+        .without_crawler()
+        .build()
+        .unwrap()
+}
+
+/// Ticks until killed, each tick adding one numbered chore as an intent, a
+/// belief about it, and an event recording it.
+fn run_until_killed(path: &Path) -> ! {
+    let mut runtime = open(path, FsyncPolicy::Never);
+    runtime.start();
+    for chore in 0u64.. {
+        runtime.intent_manager.create_intent_with_metadata(
+            format!("chore {}", chore),
+            1,
+            Some(Metadata::from([("chore", chore)])),
+        );
+        runtime.belief_ledger.record(BeliefRevision {
+            sequence: 0,
+            timestamp: 0,
+            fact: FactKey { subject: chore, predicate: "is".into(), object: "pending".into() },
+            old_confidence: 0.0,
+            new_confidence: 0.9,
+            evidence_confidence: 0.9,
+            evidence: Provenance::new("chores", None),
+            method: "revise_belief".into(),
+            parameters: HashMap::new(),
+            outcome: RevisionOutcome::Accepted,
+        });
+        runtime.narrative_memory.add_event("chore_logged", format!("Logged chore {}", chore), Some(Metadata::from([("chore", chore)])));
+        runtime.tick();
+    }
+    unreachable!()
+}
+
+/// Chores known to the intents, beliefs, and events of `runtime`.
+fn chores(runtime: &Runtime) -> [BTreeSet<u64>; 3] {
+    let intents = runtime.intent_manager.all_intents().iter().filter_map(|i| i.metadata.get_u64("chore")).collect();
+    let beliefs = runtime.belief_ledger.entries().iter().map(|r| r.fact.subject).collect();
+    let events = runtime
+        .narrative_memory
+        .events
+        .iter()
+        .filter(|e| e.event_type == "chore_logged")
+        .filter_map(|e| e.metadata.as_ref()?.get_u64("chore"))
+        .collect();
+    [intents, beliefs, events]
+}
+
+fn temp_log(trial: u32) -> PathBuf {
+    let dir = env::temp_dir().join(format!("astra-wal-kill-{}-{}", std::process::id(), trial));
+    let _ = fs::remove_dir_all(&dir);
+    dir.join("state.wal")
+}
+
+#[test]
+fn test_runtime_recovers_after_being_killed_at_random_points() {
+    if let Some(path) = env::var_os(CHILD_LOG) {
+        run_until_killed(Path::new(&path));
+    }
+
+    let mut rng = RandomSource::from_seed(3965).stream("wal_kill");
+    for trial in 0..4 {
+        let path = temp_log(trial);
+        let mut child = Command::new(env::current_exe().unwrap())
+            .args([TEST_NAME, "--exact", "--nocapture", "--test-threads=1"])
+            .env(CHILD_LOG, &path)
+            .spawn()
+            .unwrap();
+
+        // Let it commit a few ticks, then kill it wherever it is
+        let started = Instant::now();
+        while fs::metadata(&path).map_or(0, |m| m.len()) < 4096 {
+            assert!(started.elapsed() < Duration::from_secs(30), "child never logged a tick");
+            thread::sleep(Duration::from_millis(1));
+        }
+        thread::sleep(Duration::from_millis(rng.gen_range(0..60)));
+        child.kill().unwrap();
+        child.wait().unwrap();
+
+        let mut runtime = open(&path, FsyncPolicy::Always);
+        let recovery = runtime.wal_recovery().unwrap();
+        assert!(recovery.commits > 0);
+        let [intents, beliefs, events] = chores(&runtime);
+        assert_eq!(intents, beliefs, "trial {}", trial);
+        assert_eq!(intents, events, "trial {}", trial);
+        assert_eq!(intents, (0..intents.len() as u64).collect(), "trial {}", trial);
+
+        // The recovered runtime carries on logging after the last whole tick
+        let next = intents.len() as u64;
+        runtime.narrative_memory.add_event("chore_logged", "Logged one more", Some(Metadata::from([("chore", next)])));
+        runtime.commit_wal().unwrap();
+        drop(runtime);
+        let runtime = open(&path, FsyncPolicy::Always);
+        assert_eq!(runtime.wal_recovery().unwrap().discarded_bytes, 0);
+        assert!(chores(&runtime)[2].contains(&next));
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
+
+#[test]
+fn test_chores_logged_as_strings_migrate_to_numbers() {
+    let v1 = br#"{"format": "astra.intent", "version": 1, "data": {
+        "id": 1, "description": "chore 12", "priority": 1, "created_at": 0, "deadline": null,
+        "duration": null, "state": "Pending", "metadata": {"chore": "12"}}}"#;
+    let intent: Intent = decode(v1).unwrap();
+    assert_eq!(intent.metadata.get_u64("chore"), Some(12));
+}
+
+fn drink(object: &str, user_id: &str) -> Fact {
+    Fact {
+        subject: 1,
+        predicate: "drinks".into(),
+        object: object.into(),
+        confidence: 0.9,
+        provenance: Provenance::from_user(user_id, None),
+    }
+}
+
+#[test]
+fn test_removals_metadata_and_knowledge_replay() {
+    let path = temp_log(100);
+    let mut runtime = open(&path, FsyncPolicy::Always);
+    let kept = runtime.intent_manager.create_intent_with_metadata("water the plants", 1, None);
+    let dropped = runtime.intent_manager.create_intent_with_metadata("feed the cat", 1, None);
+    runtime.commit_wal().unwrap();
+    runtime.intent_manager.set_metadata(kept, "progress", 0.5).unwrap();
+    runtime.intent_manager.remove_intent(dropped);
+    runtime.commit_wal().unwrap();
+
+    let mut knowledge = OntologyManager::new();
+    knowledge.add_fact(drink("tea", "ana"));
+    knowledge.add_fact(drink("coffee", "ben"));
+    runtime.commit_wal_knowledge(&knowledge).unwrap();
+    knowledge.add_fact(drink("cocoa", "ben"));
+    runtime.commit_wal_knowledge(&knowledge).unwrap();
+    knowledge.forget_user("ana");
+    runtime.commit_wal_knowledge(&knowledge).unwrap();
+    drop(runtime);
+
+    let mut runtime = open(&path, FsyncPolicy::Always);
+    let progress = runtime.intent_manager.get_intent(kept).unwrap().metadata.get("progress").and_then(MetaValue::as_f64);
+    assert_eq!(progress, Some(0.5));
+    assert!(runtime.intent_manager.get_intent(dropped).is_none());
+    assert_eq!(runtime.wal_recovery().unwrap().facts, 2);
+
+    // What was recovered replaces the stale knowledge the host loaded
+    let mut restored = OntologyManager::new();
+    restored.add_fact(drink("tea", "ana"));
+    assert!(matches!(runtime.commit_wal_knowledge(&restored), Err(RuntimeError::Wal(WalError::KnowledgeNotRestored))));
+    assert_eq!(runtime.restore_wal_knowledge(&mut restored), 2);
+    let objects: Vec<&str> = restored.query_facts(None).iter().map(|f| f.object.as_str()).collect();
+    assert_eq!(objects, ["coffee", "cocoa"]);
+
+    // A checkpoint keeps the knowledge as logged
+    runtime.checkpoint_wal().unwrap();
+    drop(runtime);
+    let runtime = open(&path, FsyncPolicy::Always);
+    assert_eq!(runtime.wal_recovery().unwrap().facts, 2);
+    assert!(runtime.intent_manager.get_intent(dropped).is_none());
+
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn test_tick_checkpoints_a_growing_log() {
+    let path = temp_log(101);
+    let mut runtime = Runtime::builder()
+        .with_wal(WalConfig::new(&path).with_fsync(FsyncPolicy::Never).with_checkpoint_bytes(16 << 10))
+        .without_crawler()
+        .build()
+        .unwrap();
+    runtime.start();
+    let chore = runtime.intent_manager.create_intent_with_metadata("sweep", 1, None);
+    const TICKS: u64 = 300;
+    for tick in 0..TICKS {
+        runtime.intent_manager.set_metadata(chore, "sweeps", tick).unwrap();
+        runtime.tick();
+    }
+    drop(runtime);
+
+    let recovery = read_log(&path).unwrap();
+    assert!(recovery.commits < TICKS, "{} commits were never checkpointed", recovery.commits);
+    let runtime = open(&path, FsyncPolicy::Always);
+    assert_eq!(runtime.intent_manager.get_intent(chore).unwrap().metadata.get_u64("sweeps"), Some(TICKS - 1));
+
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}